@@ -43,18 +43,22 @@ mod resolver {
     use once_cell::sync::Lazy;
 
     use distribution_types::{CachedDist, IndexLocations, Requirement, Resolution, SourceDist};
-    use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder};
+    use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder, PackageName};
     use platform_tags::{Arch, Os, Platform, Tags};
     use uv_cache::Cache;
     use uv_client::RegistryClient;
-    use uv_configuration::{BuildKind, Concurrency, NoBinary, NoBuild, SetupPyStrategy};
+    use uv_configuration::{
+        BuildKind, Concurrency, NoBinary, NoBuild, PreferBinary, PreferSource, SetupPyStrategy,
+    };
     use uv_distribution::DistributionDatabase;
+    use uv_extract::ExtractPool;
     use uv_interpreter::{Interpreter, PythonEnvironment};
     use uv_resolver::{
         FlatIndex, InMemoryIndex, Manifest, Options, PythonRequirement, ResolutionGraph, Resolver,
     };
     use uv_types::{
-        BuildContext, BuildIsolation, EmptyInstalledPackages, HashStrategy, SourceBuildTrait,
+        BuildContext, BuildCounter, BuildIsolation, EmptyInstalledPackages, HashStrategy,
+        SourceBuildTrait,
     };
 
     static MARKERS: Lazy<MarkerEnvironment> = Lazy::new(|| {
@@ -119,6 +123,8 @@ mod resolver {
         cache: Cache,
         interpreter: Interpreter,
         index_locations: IndexLocations,
+        extract_pool: ExtractPool,
+        build_counter: BuildCounter,
     }
 
     impl Context {
@@ -127,6 +133,8 @@ mod resolver {
                 cache,
                 interpreter,
                 index_locations: IndexLocations::default(),
+                extract_pool: ExtractPool::new(1).unwrap(),
+                build_counter: BuildCounter::default(),
             }
         }
     }
@@ -154,10 +162,30 @@ mod resolver {
             &NoBinary::None
         }
 
+        fn prefer_source(&self) -> &PreferSource {
+            &PreferSource::None
+        }
+
+        fn prefer_binary(&self) -> &PreferBinary {
+            &PreferBinary::None
+        }
+
         fn setup_py_strategy(&self) -> SetupPyStrategy {
             SetupPyStrategy::default()
         }
 
+        fn build_command(&self, _: &PackageName) -> Option<&str> {
+            None
+        }
+
+        fn extract_pool(&self) -> &ExtractPool {
+            &self.extract_pool
+        }
+
+        fn build_counter(&self) -> &BuildCounter {
+            &self.build_counter
+        }
+
         fn index_locations(&self) -> &IndexLocations {
             &self.index_locations
         }
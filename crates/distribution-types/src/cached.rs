@@ -154,6 +154,21 @@ impl Hashed for CachedRegistryDist {
     }
 }
 
+impl Hashed for CachedDirectUrlDist {
+    fn hashes(&self) -> &[HashDigest] {
+        &self.hashes
+    }
+}
+
+impl Hashed for CachedDist {
+    fn hashes(&self) -> &[HashDigest] {
+        match self {
+            Self::Registry(dist) => dist.hashes(),
+            Self::Url(dist) => dist.hashes(),
+        }
+    }
+}
+
 impl CachedDirectUrlDist {
     /// Initialize a [`CachedDirectUrlDist`] from a [`WheelFilename`], [`url::Url`], and [`Path`].
     pub fn from_url(
@@ -49,6 +49,18 @@ impl<'a> HashPolicy<'a> {
             Self::Validate(hashes) => hashes,
         }
     }
+
+    /// Return the policy to use when computing digests for a downloaded archive.
+    ///
+    /// If no hash policy is specified, the archive is hashed with the algorithms reported by the
+    /// index (if any), such that a cached copy can later be checked against the index.
+    #[must_use]
+    pub fn or_index(self, index: &'a [HashDigest]) -> Self {
+        match self {
+            Self::None if !index.is_empty() => Self::Validate(index),
+            policy => policy,
+        }
+    }
 }
 
 pub trait Hashed {
@@ -81,4 +93,11 @@ pub trait Hashed {
                 .any(|algorithm| self.hashes().iter().any(|hash| hash.algorithm == algorithm)),
         }
     }
+
+    /// Returns `true` if the archive's digests contradict those reported by the index, i.e., if
+    /// the archive includes a digest for one of the index's algorithms, but none of them match.
+    fn conflicts_with(&self, index: &[HashDigest]) -> bool {
+        let index = HashPolicy::Validate(index);
+        self.has_digests(index) && !self.satisfies(index)
+    }
 }
@@ -7,8 +7,8 @@ use tracing::warn;
 use url::Url;
 
 use pep440_rs::Version;
-use pypi_types::DirectUrl;
-use uv_fs::Simplified;
+use pypi_types::{ArchiveInfo, DirectUrl, HashDigest, ARCHIVE_INFO_FILENAME};
+use uv_fs::{normalize_path, Simplified};
 use uv_normalize::PackageName;
 
 use crate::{DistributionMetadata, InstalledMetadata, InstalledVersion, Name, VersionOrUrlRef};
@@ -55,8 +55,10 @@ pub struct InstalledLegacyEditable {
     pub name: PackageName,
     pub version: Version,
     pub egg_link: PathBuf,
+    /// The directory that contains the `.egg-info` directory (e.g., `src` in a `src` layout).
     pub target: PathBuf,
-    pub target_url: Url,
+    /// The URL of the project root, i.e., the directory that contains the `setup.py`.
+    pub url: Url,
     pub egg_info: PathBuf,
 }
 
@@ -141,15 +143,15 @@ impl InstalledDist {
 
             // https://setuptools.pypa.io/en/latest/deprecated/python_eggs.html#egg-links
             // https://github.com/pypa/pip/blob/946f95d17431f645da8e2e0bf4054a72db5be766/src/pip/_internal/metadata/importlib/_envs.py#L86-L108
+            //
+            // The first line is the directory that contains the `.egg-info` directory, and the
+            // (optional) second line is the relative path from there to the project root.
             let contents = fs::read_to_string(path)?;
-            let Some(target) = contents.lines().find_map(|line| {
-                let line = line.trim();
-                if line.is_empty() {
-                    None
-                } else {
-                    Some(PathBuf::from(line))
-                }
-            }) else {
+            let mut lines = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty());
+            let Some(target) = lines.next().map(PathBuf::from) else {
                 warn!("Invalid `.egg-link` file: {path:?}");
                 return Ok(None);
             };
@@ -162,8 +164,15 @@ impl InstalledDist {
 
             // Normalisation comes from `pkg_resources.to_filename`.
             let egg_info = target.join(file_stem.replace('-', "_") + ".egg-info");
-            let url = Url::from_file_path(&target)
-                .map_err(|()| anyhow!("Invalid `.egg-link` target: {}", target.user_display()))?;
+
+            // Identify the editable by its project root, which differs from the target if the
+            // project's packages live in a subdirectory (e.g., `package_dir={"": "src"}`).
+            let project = lines
+                .next()
+                .and_then(|setup_path| normalize_path(&target.join(setup_path)).ok())
+                .unwrap_or_else(|| target.clone());
+            let url = Url::from_file_path(&project)
+                .map_err(|()| anyhow!("Invalid `.egg-link` target: {}", project.user_display()))?;
 
             // Mildly unfortunate that we must read metadata to get the version.
             let content = match fs::read(egg_info.join("PKG-INFO")) {
@@ -186,7 +195,7 @@ impl InstalledDist {
                 version: Version::from_str(&metadata.version)?,
                 egg_link: path.to_path_buf(),
                 target,
-                target_url: url,
+                url,
                 egg_info,
             })));
         }
@@ -224,6 +233,16 @@ impl InstalledDist {
         Ok(Some(direct_url))
     }
 
+    /// Read the `uv_archive_info.json` file from a `.dist-info` directory.
+    pub fn archive_info(path: &Path) -> Result<Option<ArchiveInfo>> {
+        let path = path.join(ARCHIVE_INFO_FILENAME);
+        let Ok(file) = fs_err::File::open(path) else {
+            return Ok(None);
+        };
+        let archive_info = serde_json::from_reader::<fs_err::File, ArchiveInfo>(file)?;
+        Ok(Some(archive_info))
+    }
+
     /// Read the `METADATA` file from a `.dist-info` directory.
     pub fn metadata(&self) -> Result<pypi_types::Metadata23> {
         match self {
@@ -275,13 +294,37 @@ impl InstalledDist {
         }
     }
 
+    /// Return the hashes of the archive from which the distribution was installed, as recorded in
+    /// its `direct_url.json` or, for registry distributions, its `uv_archive_info.json`.
+    ///
+    /// Hashes are only recorded for distributions installed from an archive by uv with hash
+    /// checking enabled.
+    pub fn hashes(&self) -> Vec<HashDigest> {
+        match self {
+            Self::Registry(dist) => match Self::archive_info(&dist.path) {
+                Ok(archive_info) => archive_info
+                    .map(|archive_info| archive_info.digests())
+                    .unwrap_or_default(),
+                Err(err) => {
+                    warn!("Failed to read archive info for {}: {err}", dist.name);
+                    vec![]
+                }
+            },
+            Self::Url(dist) => match dist.direct_url.as_ref() {
+                DirectUrl::ArchiveUrl { archive_info, .. } => archive_info.digests(),
+                DirectUrl::LocalDirectory { .. } | DirectUrl::VcsUrl { .. } => vec![],
+            },
+            Self::EggInfo(_) | Self::LegacyEditable(_) => vec![],
+        }
+    }
+
     /// Return the [`Url`] of the distribution, if it is editable.
     pub fn as_editable(&self) -> Option<&Url> {
         match self {
             Self::Registry(_) => None,
             Self::Url(dist) => dist.editable.then_some(&dist.url),
             Self::EggInfo(_) => None,
-            Self::LegacyEditable(dist) => Some(&dist.target_url),
+            Self::LegacyEditable(dist) => Some(&dist.url),
         }
     }
 }
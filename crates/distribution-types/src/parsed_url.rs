@@ -1,9 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Error, Result};
 use thiserror::Error;
 use url::Url;
 
+use distribution_filename::SourceDistExtension;
 use pep508_rs::VerbatimUrl;
 use uv_git::{GitSha, GitUrl};
 
@@ -58,6 +59,22 @@ pub struct ParsedPathUrl {
     pub editable: bool,
 }
 
+impl ParsedPathUrl {
+    /// Returns `true` if the path refers to a distribution archive (i.e., a wheel or source
+    /// distribution), rather than a directory.
+    pub fn is_archive(&self) -> bool {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                Path::new(name)
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
+                    || SourceDistExtension::from_filename(name).is_some()
+            })
+    }
+}
+
 /// A Git repository URL.
 ///
 /// Examples:
@@ -188,6 +205,18 @@ impl TryFrom<&ParsedPathUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &ParsedPathUrl) -> Result<Self, Self::Error> {
+        // Per PEP 610, a local archive (e.g., a `.whl` or `.tar.gz` file) is recorded with
+        // `archive_info`; only a local directory is recorded with `dir_info`.
+        if !value.editable && value.is_archive() {
+            return Ok(Self::ArchiveUrl {
+                url: value.url.to_string(),
+                archive_info: pypi_types::ArchiveInfo {
+                    hash: None,
+                    hashes: None,
+                },
+                subdirectory: None,
+            });
+        }
         Ok(Self::LocalDirectory {
             url: value.url.to_string(),
             dir_info: pypi_types::DirInfo {
@@ -308,4 +337,31 @@ mod tests {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn pep610_local_path() -> Result<()> {
+        // A local directory is recorded with `dir_info`.
+        let parsed = ParsedUrl::try_from(Url::parse("file:///path/to/directory")?)?;
+        let direct_url = pypi_types::DirectUrl::try_from(&parsed)?;
+        assert!(matches!(
+            direct_url,
+            pypi_types::DirectUrl::LocalDirectory { .. }
+        ));
+
+        // Local archives are recorded with `archive_info`.
+        for url in [
+            "file:///path/to/foo-1.0.0-py3-none-any.whl",
+            "file:///path/to/foo-1.0.0.tar.gz",
+        ] {
+            let parsed = ParsedUrl::try_from(Url::parse(url)?)?;
+            let direct_url = pypi_types::DirectUrl::try_from(&parsed)?;
+            assert!(
+                matches!(direct_url, pypi_types::DirectUrl::ArchiveUrl { .. }),
+                "{url}"
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -24,6 +24,8 @@ struct PrioritizedDistInner {
     wheels: Vec<(RegistryBuiltWheel, WheelCompatibility)>,
     /// The hashes for each distribution.
     hashes: Vec<HashDigest>,
+    /// Whether to prefer the source distribution over wheels, when both are compatible.
+    prefer_source: bool,
 }
 
 /// A distribution that can be used for both resolution and installation.
@@ -102,9 +104,19 @@ impl Display for IncompatibleDist {
                     Some(_) => f.write_str("was published after the exclude newer time"),
                     None => f.write_str("has no publish time"),
                 },
+                IncompatibleWheel::MinReleaseAge(ts) => match ts {
+                    Some(_) => {
+                        f.write_str("was published more recently than the minimum release age")
+                    }
+                    None => f.write_str("has no publish time"),
+                },
                 IncompatibleWheel::RequiresPython(python) => {
                     write!(f, "requires at python {python}")
                 }
+                IncompatibleWheel::MismatchedHash(constraint) => write!(
+                    f,
+                    "has no distributions with hashes matching the constraint `{constraint}`"
+                ),
             },
             Self::Source(incompatibility) => match incompatibility {
                 IncompatibleSource::NoBuild => {
@@ -122,9 +134,19 @@ impl Display for IncompatibleDist {
                     Some(_) => f.write_str("was published after the exclude newer time"),
                     None => f.write_str("has no publish time"),
                 },
+                IncompatibleSource::MinReleaseAge(ts) => match ts {
+                    Some(_) => {
+                        f.write_str("was published more recently than the minimum release age")
+                    }
+                    None => f.write_str("has no publish time"),
+                },
                 IncompatibleSource::RequiresPython(python) => {
                     write!(f, "requires python {python}")
                 }
+                IncompatibleSource::MismatchedHash(constraint) => write!(
+                    f,
+                    "has no distributions with hashes matching the constraint `{constraint}`"
+                ),
             },
             Self::Unavailable => f.write_str("has no available distributions"),
         }
@@ -140,10 +162,14 @@ pub enum WheelCompatibility {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum IncompatibleWheel {
     ExcludeNewer(Option<i64>),
+    /// The wheel was uploaded more recently than the minimum release age.
+    MinReleaseAge(Option<i64>),
     Tag(IncompatibleTag),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
     NoBinary,
+    /// The wheel doesn't match the hashes of the given constraint.
+    MismatchedHash(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -155,9 +181,13 @@ pub enum SourceDistCompatibility {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum IncompatibleSource {
     ExcludeNewer(Option<i64>),
+    /// The source distribution was uploaded more recently than the minimum release age.
+    MinReleaseAge(Option<i64>),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
     NoBuild,
+    /// The source distribution doesn't match the hashes of the given constraint.
+    MismatchedHash(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -182,6 +212,7 @@ impl PrioritizedDist {
             wheels: vec![(dist, compatibility)],
             source: None,
             hashes,
+            prefer_source: false,
         }))
     }
 
@@ -196,6 +227,7 @@ impl PrioritizedDist {
             wheels: vec![],
             source: Some((dist, compatibility)),
             hashes,
+            prefer_source: false,
         }))
     }
 
@@ -237,18 +269,25 @@ impl PrioritizedDist {
         self.0.hashes.extend(hashes);
     }
 
+    /// Prefer the source distribution over wheels, when both are compatible (e.g., to build
+    /// against a local toolchain).
+    pub fn prefer_source(&mut self) {
+        self.0.prefer_source = true;
+    }
+
     /// Return the highest-priority distribution for the package version, if any.
     pub fn get(&self) -> Option<CompatibleDist> {
         let best_wheel = self.0.best_wheel_index.map(|i| &self.0.wheels[i]);
         match (&best_wheel, &self.0.source) {
             // If both are compatible, break ties based on the hash outcome. For example, prefer a
             // source distribution with a matching hash over a wheel with a mismatched hash. When
-            // the outcomes are equivalent (e.g., both have a matching hash), prefer the wheel.
+            // the outcomes are equivalent (e.g., both have a matching hash), prefer the wheel,
+            // unless source distributions are preferred.
             (
                 Some((wheel, WheelCompatibility::Compatible(wheel_hash, tag_priority))),
                 Some((sdist, SourceDistCompatibility::Compatible(sdist_hash))),
             ) => {
-                if sdist_hash > wheel_hash {
+                if sdist_hash > wheel_hash || (self.0.prefer_source && sdist_hash == wheel_hash) {
                     Some(CompatibleDist::SourceDist {
                         sdist,
                         prioritized: self,
@@ -460,19 +499,35 @@ impl SourceDistCompatibility {
 impl IncompatibleSource {
     fn is_more_compatible(&self, other: &Self) -> bool {
         match self {
+            // Distributions with mismatched hashes satisfy every other requirement, so they're the
+            // closest to being usable
+            Self::MismatchedHash(_) => !matches!(other, Self::MismatchedHash(_)),
             Self::ExcludeNewer(timestamp_self) => match other {
+                Self::MismatchedHash(_) => false,
                 // Smaller timestamps are closer to the cut-off time
                 Self::ExcludeNewer(timestamp_other) => timestamp_other < timestamp_self,
+                Self::MinReleaseAge(_)
+                | Self::NoBuild
+                | Self::RequiresPython(_)
+                | Self::Yanked(_) => true,
+            },
+            Self::MinReleaseAge(timestamp_self) => match other {
+                Self::MismatchedHash(_) | Self::ExcludeNewer(_) => false,
+                // Smaller timestamps are closer to the cut-off time
+                Self::MinReleaseAge(timestamp_other) => timestamp_other < timestamp_self,
                 Self::NoBuild | Self::RequiresPython(_) | Self::Yanked(_) => true,
             },
             Self::RequiresPython(_) => match other {
-                Self::ExcludeNewer(_) => false,
+                Self::MismatchedHash(_) | Self::ExcludeNewer(_) | Self::MinReleaseAge(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
                 Self::NoBuild | Self::Yanked(_) => true,
             },
             Self::Yanked(_) => match other {
-                Self::ExcludeNewer(_) | Self::RequiresPython(_) => false,
+                Self::MismatchedHash(_)
+                | Self::ExcludeNewer(_)
+                | Self::MinReleaseAge(_)
+                | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
                 Self::NoBuild => true,
@@ -485,7 +540,11 @@ impl IncompatibleSource {
 impl IncompatibleWheel {
     fn is_more_compatible(&self, other: &Self) -> bool {
         match self {
+            // Distributions with mismatched hashes satisfy every other requirement, so they're the
+            // closest to being usable
+            Self::MismatchedHash(_) => !matches!(other, Self::MismatchedHash(_)),
             Self::ExcludeNewer(timestamp_self) => match other {
+                Self::MismatchedHash(_) => false,
                 // Smaller timestamps are closer to the cut-off time
                 Self::ExcludeNewer(timestamp_other) => match (timestamp_self, timestamp_other) {
                     (None, _) => true,
@@ -494,21 +553,44 @@ impl IncompatibleWheel {
                         timestamp_other < timestamp_self
                     }
                 },
+                Self::MinReleaseAge(_)
+                | Self::NoBinary
+                | Self::RequiresPython(_)
+                | Self::Tag(_)
+                | Self::Yanked(_) => true,
+            },
+            Self::MinReleaseAge(timestamp_self) => match other {
+                Self::MismatchedHash(_) | Self::ExcludeNewer(_) => false,
+                // Smaller timestamps are closer to the cut-off time
+                Self::MinReleaseAge(timestamp_other) => match (timestamp_self, timestamp_other) {
+                    (None, _) => true,
+                    (_, None) => false,
+                    (Some(timestamp_self), Some(timestamp_other)) => {
+                        timestamp_other < timestamp_self
+                    }
+                },
                 Self::NoBinary | Self::RequiresPython(_) | Self::Tag(_) | Self::Yanked(_) => true,
             },
             Self::Tag(tag_self) => match other {
-                Self::ExcludeNewer(_) => false,
+                Self::MismatchedHash(_) | Self::ExcludeNewer(_) | Self::MinReleaseAge(_) => false,
                 Self::Tag(tag_other) => tag_other > tag_self,
                 Self::NoBinary | Self::RequiresPython(_) | Self::Yanked(_) => true,
             },
             Self::RequiresPython(_) => match other {
-                Self::ExcludeNewer(_) | Self::Tag(_) => false,
+                Self::MismatchedHash(_)
+                | Self::ExcludeNewer(_)
+                | Self::MinReleaseAge(_)
+                | Self::Tag(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
                 Self::NoBinary | Self::Yanked(_) => true,
             },
             Self::Yanked(_) => match other {
-                Self::ExcludeNewer(_) | Self::Tag(_) | Self::RequiresPython(_) => false,
+                Self::MismatchedHash(_)
+                | Self::ExcludeNewer(_)
+                | Self::MinReleaseAge(_)
+                | Self::Tag(_)
+                | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
                 Self::NoBinary => true,
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
-use pep508_rs::{MarkerEnvironment, UnnamedRequirement};
+use pep508_rs::{MarkerEnvironment, MarkerTree, UnnamedRequirement};
 use uv_normalize::ExtraName;
 
 use crate::{ParsedUrl, ParsedUrlError, Requirement, RequirementSource};
@@ -55,6 +55,14 @@ impl UnresolvedRequirement {
         }
     }
 
+    /// Returns the markers for the requirement, if any.
+    pub fn marker(&self) -> Option<&MarkerTree> {
+        match self {
+            Self::Named(requirement) => requirement.marker.as_ref(),
+            Self::Unnamed(requirement) => requirement.marker.as_ref(),
+        }
+    }
+
     /// Returns the extras for the requirement.
     pub fn extras(&self) -> &[ExtraName] {
         match self {
@@ -0,0 +1,608 @@
+//! Audit the shared libraries in a wheel for compatibility with a `manylinux` platform tag, in the
+//! spirit of `auditwheel show`.
+//!
+//! Only the dynamic section and the GNU version requirements of each ELF file are inspected: the
+//! libraries it links against (`DT_NEEDED`), and the `GLIBC_*`, `GLIBCXX_*`, `CXXABI_*` and
+//! `GCC_*` symbol versions it requires.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Seek};
+
+use zip::ZipArchive;
+
+use distribution_filename::WheelFilename;
+use platform_tags::Arch;
+
+use crate::Error;
+
+/// The libraries that a `manylinux` wheel may link against without bundling them, per the
+/// `manylinux2014` policy (which is the most permissive of the legacy policies, and the baseline
+/// for PEP 600 tags).
+const MANYLINUX_LIBRARIES: &[&str] = &[
+    "libgcc_s.so.1",
+    "libstdc++.so.6",
+    "libm.so.6",
+    "libdl.so.2",
+    "librt.so.1",
+    "libc.so.6",
+    "libnsl.so.1",
+    "libutil.so.1",
+    "libpthread.so.0",
+    "libresolv.so.2",
+    "libX11.so.6",
+    "libXext.so.6",
+    "libXrender.so.1",
+    "libICE.so.6",
+    "libSM.so.6",
+    "libGL.so.1",
+    "libgobject-2.0.so.0",
+    "libgthread-2.0.so.0",
+    "libglib-2.0.so.0",
+    "ld-linux.so.2",
+    "ld-linux-x86-64.so.2",
+    "ld-linux-aarch64.so.1",
+    "ld64.so.1",
+    "ld64.so.2",
+    "ld-linux-armhf.so.3",
+];
+
+/// The symbol version prefixes that are constrained by a `manylinux` policy, beyond `GLIBC`.
+const SYMBOL_VERSION_PREFIXES: &[&str] = &["GLIBC", "GLIBCXX", "CXXABI", "GCC"];
+
+/// The newest `GLIBCXX`, `CXXABI` and `GCC` symbol versions available on the baseline
+/// distribution of each `manylinux` policy, keyed by the policy's `GLIBC` version, per
+/// `auditwheel`'s policies.
+///
+/// A policy for a `GLIBC` version that isn't listed inherits the limits of the newest listed
+/// version below it.
+const MANYLINUX_SYMBOL_VERSIONS: &[((u16, u16), &[(&str, &[u16])])] = &[
+    (
+        (2, 5),
+        &[
+            ("GLIBCXX", &[3, 4, 8]),
+            ("CXXABI", &[1, 3, 1]),
+            ("GCC", &[4, 2, 0]),
+        ],
+    ),
+    (
+        (2, 12),
+        &[
+            ("GLIBCXX", &[3, 4, 13]),
+            ("CXXABI", &[1, 3, 3]),
+            ("GCC", &[4, 3, 0]),
+        ],
+    ),
+    (
+        (2, 17),
+        &[
+            ("GLIBCXX", &[3, 4, 19]),
+            ("CXXABI", &[1, 3, 7]),
+            ("GCC", &[4, 8, 0]),
+        ],
+    ),
+    (
+        (2, 24),
+        &[
+            ("GLIBCXX", &[3, 4, 22]),
+            ("CXXABI", &[1, 3, 10]),
+            ("GCC", &[4, 8, 0]),
+        ],
+    ),
+    (
+        (2, 28),
+        &[
+            ("GLIBCXX", &[3, 4, 25]),
+            ("CXXABI", &[1, 3, 11]),
+            ("GCC", &[7, 0, 0]),
+        ],
+    ),
+    (
+        (2, 31),
+        &[
+            ("GLIBCXX", &[3, 4, 28]),
+            ("CXXABI", &[1, 3, 12]),
+            ("GCC", &[7, 0, 0]),
+        ],
+    ),
+    (
+        (2, 34),
+        &[
+            ("GLIBCXX", &[3, 4, 29]),
+            ("CXXABI", &[1, 3, 13]),
+            ("GCC", &[7, 0, 0]),
+        ],
+    ),
+    (
+        (2, 35),
+        &[
+            ("GLIBCXX", &[3, 4, 30]),
+            ("CXXABI", &[1, 3, 13]),
+            ("GCC", &[12, 0, 0]),
+        ],
+    ),
+];
+
+/// The shared libraries in a wheel, and their requirements on the host system.
+#[derive(Debug, Clone, Default)]
+pub struct WheelAudit {
+    /// The architectures that the shared libraries were compiled for.
+    pub archs: Vec<Arch>,
+    /// The paths of the shared libraries in the wheel.
+    pub libraries: Vec<String>,
+    /// The external libraries that the shared libraries link against, excluding any that are
+    /// bundled in the wheel.
+    pub external: BTreeSet<String>,
+    /// The newest symbol version required by any of the shared libraries, for each of the
+    /// `GLIBC`, `GLIBCXX`, `CXXABI` and `GCC` prefixes (e.g., `GLIBCXX` to `[3, 4, 19]`).
+    pub symbol_versions: BTreeMap<String, Vec<u16>>,
+}
+
+impl WheelAudit {
+    /// Audit the shared libraries in a wheel from a reader over its archive (e.g., a file on disk).
+    pub fn from_reader(
+        filename: &WheelFilename,
+        reader: impl Read + Seek + Sized,
+    ) -> Result<Self, Error> {
+        let mut archive =
+            ZipArchive::new(reader).map_err(|err| Error::Zip(filename.to_string(), err))?;
+        Self::from_archive(filename, &mut archive)
+    }
+
+    /// Audit the shared libraries in a wheel archive.
+    pub fn from_archive(
+        filename: &WheelFilename,
+        archive: &mut ZipArchive<impl Read + Seek + Sized>,
+    ) -> Result<Self, Error> {
+        let mut audit = Self::default();
+        let mut bundled = BTreeSet::new();
+        let mut needed = BTreeSet::new();
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .map_err(|err| Error::Zip(filename.to_string(), err))?;
+            if file.is_dir() {
+                continue;
+            }
+            let name = file.name().to_string();
+
+            // Read just enough to identify ELF files.
+            let mut magic = [0; 4];
+            if file.size() < 4 || file.read_exact(&mut magic).is_err() || magic != *b"\x7fELF" {
+                continue;
+            }
+            let mut contents = magic.to_vec();
+            file.read_to_end(&mut contents)?;
+
+            let elf = Elf::parse(&contents)
+                .map_err(|err| Error::InvalidWheel(format!("Invalid ELF file `{name}`: {err}")))?;
+            if let Some(arch) = elf.arch {
+                if !audit.archs.contains(&arch) {
+                    audit.archs.push(arch);
+                }
+            }
+            needed.extend(elf.needed);
+            for version in elf.versions {
+                if let Some((prefix, version)) = parse_symbol_version(&version) {
+                    let newest = audit.symbol_versions.entry(prefix.to_string()).or_default();
+                    if version > *newest {
+                        *newest = version;
+                    }
+                }
+            }
+            if let Some(basename) = name.rsplit('/').next() {
+                bundled.insert(basename.to_string());
+            }
+            audit.libraries.push(name);
+        }
+        audit.external = needed
+            .into_iter()
+            .filter(|library| !bundled.contains(library))
+            .collect();
+        Ok(audit)
+    }
+
+    /// Returns the reasons that the wheel is incompatible with the given `manylinux` version
+    /// (e.g., `(2, 17)` for `manylinux_2_17`) and architecture, if any.
+    pub fn manylinux_violations(&self, glibc: (u16, u16), arch: Arch) -> Vec<String> {
+        let mut violations = Vec::new();
+        for other in &self.archs {
+            if *other != arch {
+                violations.push(format!(
+                    "contains shared libraries built for `{other}`, rather than `{arch}`"
+                ));
+            }
+        }
+        for library in &self.external {
+            if !MANYLINUX_LIBRARIES.contains(&library.as_str()) {
+                violations.push(format!(
+                    "links against `{library}`, which isn't bundled in the wheel or allowed by the policy"
+                ));
+            }
+        }
+        for (prefix, required) in &self.symbol_versions {
+            let allowed = if prefix == "GLIBC" {
+                vec![glibc.0, glibc.1]
+            } else {
+                let Some(allowed) = policy_symbol_version(glibc, prefix) else {
+                    continue;
+                };
+                allowed.to_vec()
+            };
+            if *required > allowed {
+                violations.push(format!(
+                    "requires `{prefix}_{}`, which is newer than `manylinux_{}_{}` allows (`{prefix}_{}`)",
+                    format_version(required),
+                    glibc.0,
+                    glibc.1,
+                    format_version(&allowed)
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// Parse a `manylinux` platform tag (e.g., `manylinux_2_17_x86_64` or `manylinux2014_x86_64`)
+/// into the `GLIBC` version and architecture that it targets.
+pub fn parse_manylinux_tag(tag: &str) -> Option<((u16, u16), Arch)> {
+    let (glibc, arch) = if let Some(rest) = tag.strip_prefix("manylinux_") {
+        let mut parts = rest.splitn(3, '_');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        ((major, minor), parts.next()?)
+    } else if let Some(arch) = tag.strip_prefix("manylinux2014_") {
+        ((2, 17), arch)
+    } else if let Some(arch) = tag.strip_prefix("manylinux2010_") {
+        ((2, 12), arch)
+    } else if let Some(arch) = tag.strip_prefix("manylinux1_") {
+        ((2, 5), arch)
+    } else {
+        return None;
+    };
+    let arch = [
+        Arch::Aarch64,
+        Arch::Armv6L,
+        Arch::Armv7L,
+        Arch::Powerpc64Le,
+        Arch::Powerpc64,
+        Arch::X86,
+        Arch::X86_64,
+        Arch::S390X,
+    ]
+    .into_iter()
+    .find(|candidate| candidate.to_string() == arch)?;
+    Some((glibc, arch))
+}
+
+/// Returns the newest symbol version with the given prefix that the `manylinux` policy for the
+/// given `GLIBC` version allows, if the policy constrains that prefix.
+fn policy_symbol_version(glibc: (u16, u16), prefix: &str) -> Option<&'static [u16]> {
+    MANYLINUX_SYMBOL_VERSIONS
+        .iter()
+        .rev()
+        .find(|(policy, _)| *policy <= glibc)
+        .and_then(|(_, versions)| {
+            versions
+                .iter()
+                .find(|(name, _)| *name == prefix)
+                .map(|(_, version)| *version)
+        })
+}
+
+/// Parse a `GLIBCXX_3.4.19`-style symbol version into its prefix and version components, if the
+/// prefix is one that a `manylinux` policy constrains.
+fn parse_symbol_version(version: &str) -> Option<(&str, Vec<u16>)> {
+    let (prefix, version) = version.split_once('_')?;
+    if !SYMBOL_VERSION_PREFIXES.contains(&prefix) {
+        return None;
+    }
+    let version = version
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u16>>>()?;
+    Some((prefix, version))
+}
+
+/// Format version components as a dotted version (e.g., `3.4.19`).
+fn format_version(version: &[u16]) -> String {
+    version
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The dynamic linking information of an ELF file.
+struct Elf {
+    arch: Option<Arch>,
+    needed: Vec<String>,
+    versions: Vec<String>,
+}
+
+const SHT_DYNAMIC: u32 = 6;
+const SHT_GNU_VERNEED: u32 = 0x6fff_fffe;
+const DT_NULL: u64 = 0;
+const DT_NEEDED: u64 = 1;
+
+impl Elf {
+    fn parse(data: &[u8]) -> Result<Self, &'static str> {
+        // The `EI_CLASS` and `EI_DATA` identification bytes.
+        let is_64 = match data.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err("unsupported class"),
+        };
+        let is_le = match data.get(5) {
+            Some(1) => true,
+            Some(2) => false,
+            _ => return Err("unsupported byte order"),
+        };
+        let reader = Reader { data, is_64, is_le };
+
+        let arch = match reader.u16(0x12)? {
+            3 => Some(Arch::X86),
+            62 => Some(Arch::X86_64),
+            183 => Some(Arch::Aarch64),
+            40 => Some(Arch::Armv7L),
+            21 if reader.is_le => Some(Arch::Powerpc64Le),
+            21 => Some(Arch::Powerpc64),
+            22 => Some(Arch::S390X),
+            _ => None,
+        };
+
+        let (shoff, shentsize, shnum) = if reader.is_64 {
+            (reader.u64(0x28)?, reader.u16(0x3a)?, reader.u16(0x3c)?)
+        } else {
+            (
+                u64::from(reader.u32(0x20)?),
+                reader.u16(0x2e)?,
+                reader.u16(0x30)?,
+            )
+        };
+
+        // Read the section headers, as `(type, offset, size, link)`.
+        let mut sections = Vec::with_capacity(usize::from(shnum));
+        for index in 0..u64::from(shnum) {
+            let header = offset(shoff, index * u64::from(shentsize))?;
+            let section = if reader.is_64 {
+                (
+                    reader.u32(offset(header, 4)?)?,
+                    reader.u64(offset(header, 0x18)?)?,
+                    reader.u64(offset(header, 0x20)?)?,
+                    reader.u32(offset(header, 0x28)?)?,
+                )
+            } else {
+                (
+                    reader.u32(offset(header, 4)?)?,
+                    u64::from(reader.u32(offset(header, 0x10)?)?),
+                    u64::from(reader.u32(offset(header, 0x14)?)?),
+                    reader.u32(offset(header, 0x18)?)?,
+                )
+            };
+            sections.push(section);
+        }
+        let strtab = |link: u32| -> Result<u64, &'static str> {
+            sections
+                .get(link as usize)
+                .map(|(_, offset, _, _)| *offset)
+                .ok_or("invalid string table")
+        };
+
+        let mut needed = Vec::new();
+        let mut versions = Vec::new();
+        for (kind, start, size, link) in &sections {
+            match *kind {
+                SHT_DYNAMIC => {
+                    let strings = strtab(*link)?;
+                    let entry_size = if reader.is_64 { 16 } else { 8 };
+                    let end = offset(*start, *size)?;
+                    let mut entry = *start;
+                    while offset(entry, entry_size)? <= end {
+                        let (tag, value) = if reader.is_64 {
+                            (reader.u64(entry)?, reader.u64(offset(entry, 8)?)?)
+                        } else {
+                            (
+                                u64::from(reader.u32(entry)?),
+                                u64::from(reader.u32(offset(entry, 4)?)?),
+                            )
+                        };
+                        match tag {
+                            DT_NULL => break,
+                            DT_NEEDED => needed.push(reader.str(offset(strings, value)?)?),
+                            _ => {}
+                        }
+                        entry = offset(entry, entry_size)?;
+                    }
+                }
+                SHT_GNU_VERNEED => {
+                    let strings = strtab(*link)?;
+                    let mut verneed = *start;
+                    loop {
+                        let count = reader.u16(offset(verneed, 2)?)?;
+                        let mut vernaux =
+                            offset(verneed, u64::from(reader.u32(offset(verneed, 8)?)?))?;
+                        for _ in 0..count {
+                            let name = reader.u32(offset(vernaux, 8)?)?;
+                            versions.push(reader.str(offset(strings, u64::from(name))?)?);
+                            let next = reader.u32(offset(vernaux, 12)?)?;
+                            if next == 0 {
+                                break;
+                            }
+                            vernaux = offset(vernaux, u64::from(next))?;
+                        }
+                        let next = reader.u32(offset(verneed, 12)?)?;
+                        if next == 0 {
+                            break;
+                        }
+                        verneed = offset(verneed, u64::from(next))?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            arch,
+            needed,
+            versions,
+        })
+    }
+}
+
+fn offset(base: u64, offset: u64) -> Result<u64, &'static str> {
+    base.checked_add(offset).ok_or("offset out of bounds")
+}
+
+/// A reader for the fixed-width fields of an ELF file.
+struct Reader<'a> {
+    data: &'a [u8],
+    is_64: bool,
+    is_le: bool,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, offset: u64) -> Result<[u8; N], &'static str> {
+        let start = usize::try_from(offset).map_err(|_| "offset out of bounds")?;
+        let end = start.checked_add(N).ok_or("offset out of bounds")?;
+        self.data
+            .get(start..end)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or("offset out of bounds")
+    }
+
+    fn u16(&self, offset: u64) -> Result<u16, &'static str> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32, &'static str> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn u64(&self, offset: u64) -> Result<u64, &'static str> {
+        let bytes = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Read a NUL-terminated string.
+    fn str(&self, offset: u64) -> Result<String, &'static str> {
+        let start = usize::try_from(offset).map_err(|_| "offset out of bounds")?;
+        let bytes = self.data.get(start..).ok_or("offset out of bounds")?;
+        let end = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or("unterminated string")?;
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use platform_tags::Arch;
+
+    use super::{parse_manylinux_tag, parse_symbol_version, Elf, WheelAudit};
+
+    #[test]
+    fn symbol_version() {
+        assert_eq!(
+            parse_symbol_version("GLIBC_2.17"),
+            Some(("GLIBC", vec![2, 17]))
+        );
+        assert_eq!(
+            parse_symbol_version("GLIBC_2.2.5"),
+            Some(("GLIBC", vec![2, 2, 5]))
+        );
+        assert_eq!(
+            parse_symbol_version("GLIBCXX_3.4.19"),
+            Some(("GLIBCXX", vec![3, 4, 19]))
+        );
+        assert_eq!(
+            parse_symbol_version("CXXABI_1.3"),
+            Some(("CXXABI", vec![1, 3]))
+        );
+        assert_eq!(
+            parse_symbol_version("GCC_4.8.0"),
+            Some(("GCC", vec![4, 8, 0]))
+        );
+        assert_eq!(parse_symbol_version("GLIBC_PRIVATE"), None);
+        assert_eq!(parse_symbol_version("OPENSSL_3.0.0"), None);
+    }
+
+    #[test]
+    fn manylinux_tag() {
+        assert_eq!(
+            parse_manylinux_tag("manylinux_2_28_aarch64"),
+            Some(((2, 28), Arch::Aarch64))
+        );
+        assert_eq!(
+            parse_manylinux_tag("manylinux2014_x86_64"),
+            Some(((2, 17), Arch::X86_64))
+        );
+        assert_eq!(parse_manylinux_tag("linux_x86_64"), None);
+        assert_eq!(parse_manylinux_tag("manylinux_2_17_sparc"), None);
+    }
+
+    #[test]
+    fn manylinux_violations() {
+        let audit = WheelAudit {
+            archs: vec![Arch::X86_64],
+            libraries: vec!["foo/_speedups.so".to_string()],
+            external: BTreeSet::from(["libc.so.6".to_string(), "libssl.so.3".to_string()]),
+            symbol_versions: BTreeMap::from([
+                ("GLIBC".to_string(), vec![2, 28]),
+                ("GLIBCXX".to_string(), vec![3, 4, 25]),
+                ("CXXABI".to_string(), vec![1, 3, 9]),
+            ]),
+        };
+        assert_eq!(
+            audit.manylinux_violations((2, 28), Arch::X86_64),
+            vec![
+                "links against `libssl.so.3`, which isn't bundled in the wheel or allowed by the policy"
+            ]
+        );
+        assert_eq!(
+            audit.manylinux_violations((2, 17), Arch::Aarch64),
+            vec![
+                "contains shared libraries built for `x86_64`, rather than `aarch64`",
+                "links against `libssl.so.3`, which isn't bundled in the wheel or allowed by the policy",
+                "requires `CXXABI_1.3.9`, which is newer than `manylinux_2_17` allows (`CXXABI_1.3.7`)",
+                "requires `GLIBC_2.28`, which is newer than `manylinux_2_17` allows (`GLIBC_2.17`)",
+                "requires `GLIBCXX_3.4.25`, which is newer than `manylinux_2_17` allows (`GLIBCXX_3.4.19`)",
+            ]
+        );
+    }
+
+    /// Section sizes and offsets near `u64::MAX` must be rejected rather than overflow.
+    #[test]
+    fn elf_overflow() {
+        let mut data = vec![0; 0x40 + 0x40];
+        data[..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2;
+        data[5] = 1;
+        data[0x12..0x14].copy_from_slice(&62u16.to_le_bytes());
+        // A single section header, immediately after the ELF header.
+        data[0x28..0x30].copy_from_slice(&0x40u64.to_le_bytes());
+        data[0x3a..0x3c].copy_from_slice(&0x40u16.to_le_bytes());
+        data[0x3c..0x3e].copy_from_slice(&1u16.to_le_bytes());
+        // An `SHT_DYNAMIC` section whose end overflows.
+        data[0x44..0x48].copy_from_slice(&6u32.to_le_bytes());
+        data[0x58..0x60].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+        data[0x60..0x68].copy_from_slice(&16u64.to_le_bytes());
+        assert!(Elf::parse(&data).is_err());
+    }
+}
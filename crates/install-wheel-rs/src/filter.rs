@@ -0,0 +1,159 @@
+use std::io;
+use std::path::{Component, Path};
+
+use fs_err as fs;
+use glob::Pattern;
+
+use crate::record::RecordEntry;
+use crate::Error;
+
+/// Classes of files to omit when installing a wheel, e.g., to slim down a container image.
+///
+/// Omitted files are removed from the wheel's `RECORD`, such that the installed distribution can
+/// still be uninstalled and verified.
+#[derive(Debug, Default, Clone)]
+pub struct InstallFilter {
+    /// Whether to omit test suites, i.e., any `tests` directory within the wheel.
+    exclude_tests: bool,
+    /// Glob patterns, relative to `site-packages`, for files and directories to omit.
+    exclude: Vec<Pattern>,
+}
+
+impl InstallFilter {
+    /// Create an [`InstallFilter`] that omits test suites (if `exclude_tests` is `true`) along with
+    /// any files or directories matching the given glob patterns.
+    pub fn new(exclude_tests: bool, exclude: &[String]) -> Result<Self, Error> {
+        let exclude = exclude
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|err| Error::InvalidPattern(pattern.clone(), err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            exclude_tests,
+            exclude,
+        })
+    }
+
+    /// Returns `true` if the filter doesn't omit any files.
+    pub fn is_empty(&self) -> bool {
+        !self.exclude_tests && self.exclude.is_empty()
+    }
+
+    /// Returns `true` if the file at the given path, relative to `site-packages`, should be omitted.
+    pub fn excludes(&self, path: &Path) -> bool {
+        let mut components = path.components();
+        let Some(Component::Normal(first)) = components.next() else {
+            return false;
+        };
+        let first = first.to_string_lossy();
+
+        // Never omit the `.dist-info` directory, which the installer relies upon.
+        if first.ends_with(".dist-info") {
+            return false;
+        }
+
+        // The `purelib` and `platlib` directories within `.data` are moved into `site-packages`,
+        // so their files are filtered by the path at which they'll be installed. The remaining
+        // `.data` files (e.g., scripts and headers) are installed elsewhere, and are retained.
+        let path = if first.ends_with(".data") {
+            match components.next() {
+                Some(Component::Normal(scheme)) if scheme == "purelib" || scheme == "platlib" => {
+                    components.as_path()
+                }
+                _ => return false,
+            }
+        } else {
+            path
+        };
+
+        if self.exclude_tests
+            && path
+                .parent()
+                .is_some_and(|parent| parent.components().any(|c| c.as_os_str() == "tests"))
+        {
+            return true;
+        }
+
+        // A pattern may match the file itself, or any of the directories that contain it.
+        path.ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .any(|ancestor| {
+                self.exclude
+                    .iter()
+                    .any(|pattern| pattern.matches_path(ancestor))
+            })
+    }
+
+    /// Remove any excluded files from an unpacked wheel in `site-packages`, along with their
+    /// `RECORD` entries, returning the number of files removed.
+    pub(crate) fn apply(
+        &self,
+        site_packages: &Path,
+        record: &mut Vec<RecordEntry>,
+    ) -> Result<usize, Error> {
+        let mut removed = 0;
+        let mut retained = Vec::with_capacity(record.len());
+        for entry in record.drain(..) {
+            if !self.excludes(Path::new(&entry.path)) {
+                retained.push(entry);
+                continue;
+            }
+
+            let path = site_packages.join(&entry.path);
+            match fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            // Remove any directories that were left empty (e.g., a `tests` directory).
+            for ancestor in path.ancestors().skip(1) {
+                if ancestor == site_packages || fs::remove_dir(ancestor).is_err() {
+                    break;
+                }
+            }
+        }
+        *record = retained;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::InstallFilter;
+
+    #[test]
+    fn excludes() {
+        let filter =
+            InstallFilter::new(true, &["*.pyi".to_string(), "foo/docs".to_string()]).unwrap();
+
+        // Test suites are omitted, at any depth.
+        assert!(filter.excludes(Path::new("foo/tests/test_foo.py")));
+        assert!(filter.excludes(Path::new("foo/bar/tests/__init__.py")));
+        assert!(!filter.excludes(Path::new("foo/tests.py")));
+        assert!(!filter.excludes(Path::new("foo/testing/__init__.py")));
+
+        // Patterns match files, or the directories that contain them.
+        assert!(filter.excludes(Path::new("foo/__init__.pyi")));
+        assert!(filter.excludes(Path::new("foo/docs/index.rst")));
+        assert!(!filter.excludes(Path::new("foo/__init__.py")));
+
+        // The `.dist-info` directory is always retained.
+        assert!(!filter.excludes(Path::new("foo-1.0.dist-info/tests/RECORD")));
+
+        // Files in `.data` are filtered by the path at which they're installed, if they're
+        // installed into `site-packages`.
+        assert!(filter.excludes(Path::new("foo-1.0.data/purelib/foo/tests/test_foo.py")));
+        assert!(filter.excludes(Path::new("foo-1.0.data/platlib/foo/__init__.pyi")));
+        assert!(filter.excludes(Path::new("foo-1.0.data/platlib/foo/docs/index.rst")));
+        assert!(!filter.excludes(Path::new("foo-1.0.data/purelib/foo/__init__.py")));
+        assert!(!filter.excludes(Path::new("foo-1.0.data/scripts/foo.pyi")));
+        assert!(!filter.excludes(Path::new("foo-1.0.data/data/share/tests/foo.txt")));
+
+        assert!(InstallFilter::default().is_empty());
+        assert!(InstallFilter::new(false, &["[".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Inspect the contents of a wheel without installing it.
+
+use std::io::{Read, Seek};
+
+use configparser::ini::Ini;
+use zip::ZipArchive;
+
+use distribution_filename::WheelFilename;
+
+use crate::metadata::find_archive_dist_info;
+use crate::wheel::read_record_file;
+use crate::Error;
+
+/// The files in the `.dist-info` directory that are read when inspecting a wheel.
+pub const INSPECTED_FILES: &[&str] = &["WHEEL", "METADATA", "RECORD", "entry_points.txt"];
+
+/// A summary of the contents of a wheel's `.dist-info` directory.
+#[derive(Debug, Clone)]
+pub struct WheelInspection {
+    /// The `.dist-info` prefix, e.g., `foo-1.0.0`.
+    pub dist_info_prefix: String,
+    /// The fields of the `WHEEL` file, in order.
+    pub wheel: Vec<(String, String)>,
+    /// The header fields of the `METADATA` file, in order (excluding the description body).
+    pub metadata: Vec<(String, String)>,
+    /// A summary of the `RECORD` file.
+    pub record: RecordSummary,
+    /// The entry points, grouped by section (e.g., `console_scripts`) and sorted by name.
+    pub entry_points: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// A summary of a wheel's `RECORD` file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecordSummary {
+    /// The number of files listed in the `RECORD`.
+    pub files: usize,
+    /// The total uncompressed size of the files listed in the `RECORD`, in bytes.
+    pub size: u64,
+    /// The number of files listed without a hash (typically, just the `RECORD` itself).
+    pub unhashed: usize,
+}
+
+impl WheelInspection {
+    /// Inspect a wheel from its `.dist-info` files, as `(name, contents)` pairs. Any files other
+    /// than those in [`INSPECTED_FILES`] are ignored.
+    pub fn from_dist_info_files(
+        dist_info_prefix: String,
+        files: &[(String, String)],
+    ) -> Result<Self, Error> {
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|(file, _)| file == name)
+                .map(|(_, contents)| contents.as_str())
+        };
+
+        let Some(wheel) = file("WHEEL") else {
+            return Err(Error::InvalidWheel(format!(
+                "Missing {dist_info_prefix}.dist-info/WHEEL"
+            )));
+        };
+        let Some(metadata) = file("METADATA") else {
+            return Err(Error::InvalidWheel(format!(
+                "Missing {dist_info_prefix}.dist-info/METADATA"
+            )));
+        };
+
+        let record = if let Some(record) = file("RECORD") {
+            let entries = read_record_file(&mut record.as_bytes())?;
+            RecordSummary {
+                files: entries.len(),
+                size: entries.iter().filter_map(|entry| entry.size).sum(),
+                unhashed: entries.iter().filter(|entry| entry.hash.is_none()).count(),
+            }
+        } else {
+            RecordSummary::default()
+        };
+
+        let entry_points = if let Some(entry_points) = file("entry_points.txt") {
+            let mapping = Ini::new_cs()
+                .read(entry_points.to_string())
+                .map_err(|err| {
+                    Error::InvalidWheel(format!("entry_points.txt is invalid: {err}"))
+                })?;
+            let mut sections = mapping
+                .into_iter()
+                .map(|(section, entries)| {
+                    let mut entries = entries
+                        .into_iter()
+                        .map(|(name, value)| (name, value.unwrap_or_default()))
+                        .collect::<Vec<_>>();
+                    entries.sort();
+                    (section, entries)
+                })
+                .filter(|(_, entries)| !entries.is_empty())
+                .collect::<Vec<_>>();
+            sections.sort();
+            sections
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            dist_info_prefix,
+            wheel: parse_headers(wheel),
+            metadata: parse_headers(metadata),
+            record,
+            entry_points,
+        })
+    }
+
+    /// Inspect a wheel from a reader over its archive (e.g., a file on disk).
+    pub fn from_reader(
+        filename: &WheelFilename,
+        reader: impl Read + Seek + Sized,
+    ) -> Result<Self, Error> {
+        let mut archive =
+            ZipArchive::new(reader).map_err(|err| Error::Zip(filename.to_string(), err))?;
+        Self::from_archive(filename, &mut archive)
+    }
+
+    /// Inspect a wheel from a local archive.
+    pub fn from_archive(
+        filename: &WheelFilename,
+        archive: &mut ZipArchive<impl Read + Seek + Sized>,
+    ) -> Result<Self, Error> {
+        let dist_info_prefix =
+            find_archive_dist_info(filename, archive.file_names().map(|name| (name, name)))?
+                .1
+                .to_string();
+
+        let mut files = Vec::with_capacity(INSPECTED_FILES.len());
+        for name in INSPECTED_FILES {
+            let path = format!("{dist_info_prefix}.dist-info/{name}");
+            let mut file = match archive.by_name(&path) {
+                Ok(file) => file,
+                Err(zip::result::ZipError::FileNotFound) => continue,
+                Err(err) => return Err(Error::Zip(filename.to_string(), err)),
+            };
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+            files.push(((*name).to_string(), contents));
+        }
+
+        Self::from_dist_info_files(dist_info_prefix, &files)
+    }
+}
+
+/// Parse the `Key: value` headers of a `WHEEL` or `METADATA` file, preserving their order and
+/// stopping at the first blank line (i.e., the start of the description body).
+///
+/// Continuation lines (which start with whitespace) are appended to the preceding value.
+fn parse_headers(contents: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::WheelInspection;
+
+    #[test]
+    fn from_dist_info_files() {
+        let files = [
+            (
+                "WHEEL".to_string(),
+                indoc! {"
+                    Wheel-Version: 1.0
+                    Generator: bdist_wheel (0.37.1)
+                    Root-Is-Purelib: true
+                    Tag: py3-none-any
+                "}
+                .to_string(),
+            ),
+            (
+                "METADATA".to_string(),
+                indoc! {"
+                    Metadata-Version: 2.1
+                    Name: foo
+                    Version: 1.0.0
+                    Requires-Dist: bar
+                    Requires-Dist: baz ; extra == 'test'
+
+                    The description.
+                "}
+                .to_string(),
+            ),
+            (
+                "RECORD".to_string(),
+                indoc! {"
+                    foo/__init__.py,sha256=A-aT2fL2h-D0Djao33_LTRwil0ASt8KlXAAOsw8wWCQ,15
+                    foo-1.0.0.dist-info/METADATA,sha256=A-aT2fL2h-D0Djao33_LTRwil0ASt8KlXAAOsw8wWCQ,120
+                    foo-1.0.0.dist-info/RECORD,,
+                "}
+                .to_string(),
+            ),
+            (
+                "entry_points.txt".to_string(),
+                indoc! {"
+                    [console_scripts]
+                    foo = foo:main
+                    foo-cli = foo.cli:main
+                "}
+                .to_string(),
+            ),
+        ];
+
+        let inspection =
+            WheelInspection::from_dist_info_files("foo-1.0.0".to_string(), &files).unwrap();
+
+        assert_eq!(inspection.wheel.len(), 4);
+        assert_eq!(
+            inspection.metadata,
+            vec![
+                ("Metadata-Version".to_string(), "2.1".to_string()),
+                ("Name".to_string(), "foo".to_string()),
+                ("Version".to_string(), "1.0.0".to_string()),
+                ("Requires-Dist".to_string(), "bar".to_string()),
+                (
+                    "Requires-Dist".to_string(),
+                    "baz ; extra == 'test'".to_string()
+                ),
+            ]
+        );
+        assert_eq!(inspection.record.files, 3);
+        assert_eq!(inspection.record.size, 135);
+        assert_eq!(inspection.record.unhashed, 1);
+        assert_eq!(
+            inspection.entry_points,
+            vec![(
+                "console_scripts".to_string(),
+                vec![
+                    ("foo".to_string(), "foo:main".to_string()),
+                    ("foo-cli".to_string(), "foo.cli:main".to_string()),
+                ]
+            )]
+        );
+    }
+}
@@ -8,16 +8,26 @@ use platform_info::PlatformInfoError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+pub use filter::InstallFilter;
 use pep440_rs::Version;
 use platform_tags::{Arch, Os};
 use pypi_types::Scheme;
-pub use uninstall::{uninstall_egg, uninstall_legacy_editable, uninstall_wheel, Uninstall};
+pub use script::{ScriptKind, ScriptOptions};
+pub use uninstall::{
+    editable_roots, installed_files, recorded_hashes, script_names, uninstall_egg,
+    uninstall_legacy_editable, uninstall_wheel, Uninstall, UninstallOptions,
+};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
+pub use wheel::rewrite_shebangs;
 
+pub mod audit;
+mod filter;
+pub mod inspect;
 pub mod linker;
 pub mod metadata;
 mod record;
+pub mod retag;
 mod script;
 mod uninstall;
 mod wheel;
@@ -66,6 +76,9 @@ pub enum Error {
     WalkDir(#[from] walkdir::Error),
     #[error("RECORD file doesn't match wheel contents: {0}")]
     RecordFile(String),
+    /// A file's hash or size differs from the one listed in the `RECORD`.
+    #[error("RECORD file doesn't match wheel contents: {0}")]
+    RecordMismatch(String),
     #[error("RECORD file is invalid")]
     RecordCsv(#[from] csv::Error),
     #[error("Broken virtualenv: {0}")]
@@ -110,4 +123,8 @@ pub enum Error {
     MismatchedVersion(Version, Version),
     #[error("Invalid egg-link")]
     InvalidEggLink(PathBuf),
+    #[error("Wheel contains files that differ only in case, which would collide on a case-insensitive filesystem: `{0}` and `{1}`")]
+    CaseCollision(String, String),
+    #[error("Invalid installation exclusion pattern: `{0}`")]
+    InvalidPattern(String, #[source] glob::PatternError),
 }
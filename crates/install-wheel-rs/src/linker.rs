@@ -14,15 +14,16 @@ use tracing::{debug, instrument};
 
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
-use pypi_types::DirectUrl;
+use pypi_types::{ArchiveInfo, DirectUrl};
+use uv_fs::{find_case_collision, is_case_insensitive, verbatim_path};
 use uv_normalize::PackageName;
 
-use crate::script::{scripts_from_ini, Script};
+use crate::script::{scripts_from_ini, Script, ScriptOptions};
 use crate::wheel::{
-    extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file,
-    write_script_entrypoints, LibKind,
+    copy_and_hash, extra_dist_info, install_data, parse_metadata, parse_wheel_file,
+    read_record_file, write_script_entrypoints, LibKind,
 };
-use crate::{Error, Layout};
+use crate::{Error, InstallFilter, Layout};
 
 /// Install the given wheel to the given venv
 ///
@@ -37,8 +38,11 @@ pub fn install_wheel(
     wheel: impl AsRef<Path>,
     filename: &WheelFilename,
     direct_url: Option<&DirectUrl>,
+    archive_info: Option<&ArchiveInfo>,
     installer: Option<&str>,
     link_mode: LinkMode,
+    script_options: ScriptOptions,
+    filter: &InstallFilter,
 ) -> Result<(), Error> {
     let dist_info_prefix = find_dist_info(&wheel)?;
     let metadata = dist_info_metadata(&dist_info_prefix, &wheel)?;
@@ -74,8 +78,6 @@ pub fn install_wheel(
         LibKind::Pure => &layout.scheme.purelib,
         LibKind::Plat => &layout.scheme.platlib,
     };
-    let num_unpacked = link_mode.link_wheel_files(site_packages, &wheel)?;
-    debug!(name, "Extracted {num_unpacked} files");
 
     // Read the RECORD file.
     let mut record_file = File::open(
@@ -85,6 +87,26 @@ pub fn install_wheel(
     )?;
     let mut record = read_record_file(&mut record_file)?;
 
+    // Files that differ only in case would clobber each other on a case-insensitive filesystem.
+    if let Some((first, second)) =
+        find_case_collision(record.iter().map(|entry| Path::new(&entry.path)))
+    {
+        if is_case_insensitive(site_packages)? {
+            return Err(Error::CaseCollision(
+                first.to_string_lossy().to_string(),
+                second.to_string_lossy().to_string(),
+            ));
+        }
+    }
+
+    let num_unpacked = link_mode.link_wheel_files(site_packages, &wheel)?;
+    debug!(name, "Extracted {num_unpacked} files");
+
+    if !filter.is_empty() {
+        let num_removed = filter.apply(site_packages, &mut record)?;
+        debug!(name, "Removed {num_removed} excluded files");
+    }
+
     let (console_scripts, gui_scripts) =
         parse_scripts(&wheel, &dist_info_prefix, None, layout.python_version.1)?;
 
@@ -94,8 +116,24 @@ pub fn install_wheel(
         debug!(name, "Writing entrypoints");
 
         fs_err::create_dir_all(&layout.scheme.scripts)?;
-        write_script_entrypoints(layout, site_packages, &console_scripts, &mut record, false)?;
-        write_script_entrypoints(layout, site_packages, &gui_scripts, &mut record, true)?;
+        write_script_entrypoints(
+            layout,
+            site_packages,
+            &dist_info_prefix,
+            &console_scripts,
+            &mut record,
+            false,
+            script_options,
+        )?;
+        write_script_entrypoints(
+            layout,
+            site_packages,
+            &dist_info_prefix,
+            &gui_scripts,
+            &mut record,
+            true,
+            script_options,
+        )?;
     }
 
     // 2.a Unpacked archive includes distribution-1.0.dist-info/ and (if there is data) distribution-1.0.data/.
@@ -111,6 +149,7 @@ pub fn install_wheel(
             &console_scripts,
             &gui_scripts,
             &mut record,
+            script_options,
         )?;
         // 2.c If applicable, update scripts starting with #!python to point to the correct interpreter.
         // Script are unsupported through data
@@ -126,6 +165,7 @@ pub fn install_wheel(
         &dist_info_prefix,
         true,
         direct_url,
+        archive_info,
         installer,
         &mut record,
     )?;
@@ -143,6 +183,64 @@ pub fn install_wheel(
     Ok(())
 }
 
+/// Verify the files in an unzipped wheel against the hashes and sizes in its `RECORD` file.
+///
+/// Intended to be run after unzipping a wheel into a staging directory, but before committing it
+/// (e.g., to the cache), such that a corrupted or truncated archive is never installed.
+///
+/// Entries without a hash (like the `RECORD` itself) and entries using hash algorithms other than
+/// SHA-256 are skipped.
+#[instrument(skip_all)]
+pub fn verify_record(wheel: impl AsRef<Path>) -> Result<(), Error> {
+    let wheel = wheel.as_ref();
+    let dist_info_prefix = find_dist_info(wheel)?;
+
+    let mut record_file = File::open(wheel.join(format!("{dist_info_prefix}.dist-info/RECORD")))?;
+    let record = read_record_file(&mut record_file)?;
+
+    for entry in record {
+        let Some(expected) = entry.hash.as_deref() else {
+            continue;
+        };
+        if !expected.starts_with("sha256=") {
+            debug!(
+                "Skipping verification of `{}` due to unsupported hash: {expected}",
+                entry.path
+            );
+            continue;
+        }
+
+        let mut file = match File::open(wheel.join(&entry.path)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::RecordFile(format!(
+                    "`{}` is listed in the RECORD, but is missing from the wheel",
+                    entry.path
+                )));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let (size, actual) = copy_and_hash(&mut file, &mut std::io::sink())?;
+
+        if actual != expected {
+            return Err(Error::RecordMismatch(format!(
+                "`{}` has hash `{actual}`, but the RECORD lists `{expected}`",
+                entry.path
+            )));
+        }
+        if let Some(expected) = entry.size {
+            if size != expected {
+                return Err(Error::RecordMismatch(format!(
+                    "`{}` has size {size}, but the RECORD lists {expected}",
+                    entry.path
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Find the `dist-info` directory in an unzipped wheel.
 ///
 /// See: <https://github.com/PyO3/python-pkginfo-rs>
@@ -240,6 +338,9 @@ impl LinkMode {
         site_packages: impl AsRef<Path>,
         wheel: impl AsRef<Path>,
     ) -> Result<usize, Error> {
+        // Use extended-length paths, since deeply nested packages can easily exceed `MAX_PATH`.
+        let site_packages = verbatim_path(site_packages.as_ref());
+        let wheel = verbatim_path(wheel.as_ref());
         match self {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
@@ -532,3 +633,40 @@ fn hardlink_wheel_files(
 
     Ok(count)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use super::verify_record;
+
+    #[test]
+    fn test_verify_record() -> Result<(), Error> {
+        let wheel = tempfile::tempdir()?;
+        fs_err::create_dir_all(wheel.path().join("foo-1.0.dist-info"))?;
+        fs_err::write(
+            wheel.path().join("foo-1.0.dist-info/RECORD"),
+            "foo.py,sha256=A-aT2fL2h-D0Djao33_LTRwil0ASt8KlXAAOsw8wWCQ,15\nfoo-1.0.dist-info/RECORD,,\n",
+        )?;
+
+        // The file matches the `RECORD`.
+        fs_err::write(wheel.path().join("foo.py"), "print('hello')\n")?;
+        verify_record(wheel.path())?;
+
+        // The file was modified.
+        fs_err::write(wheel.path().join("foo.py"), "print('bye')\n")?;
+        assert!(matches!(
+            verify_record(wheel.path()),
+            Err(Error::RecordMismatch(_))
+        ));
+
+        // The file is missing.
+        fs_err::remove_file(wheel.path().join("foo.py"))?;
+        assert!(matches!(
+            verify_record(wheel.path()),
+            Err(Error::RecordFile(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+//! Rewrite the compatibility tags of a built wheel, in the spirit of `wheel tags`.
+
+use std::io::{Read, Seek, Write};
+
+use data_encoding::BASE64URL_NOPAD;
+use sha2::{Digest, Sha256};
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use distribution_filename::WheelFilename;
+
+use crate::metadata::find_archive_dist_info;
+use crate::record::RecordEntry;
+use crate::wheel::read_record_file;
+use crate::Error;
+
+/// Copy a wheel from a reader over its archive (e.g., a file on disk) to `writer`, replacing its
+/// compatibility tags with those of `retagged`.
+pub fn retag_wheel(
+    filename: &WheelFilename,
+    retagged: &WheelFilename,
+    reader: impl Read + Seek + Sized,
+    writer: impl Write + Seek,
+) -> Result<(), Error> {
+    let mut archive =
+        ZipArchive::new(reader).map_err(|err| Error::Zip(filename.to_string(), err))?;
+    retag_archive(filename, retagged, &mut archive, writer)
+}
+
+/// Copy a wheel archive to `writer`, replacing its compatibility tags with those of `retagged`.
+///
+/// The `Tag` entries in the `WHEEL` file are replaced with the expanded set of tags (one per
+/// combination of Python, ABI, and platform tag), and the `RECORD` entry for the `WHEEL` file is
+/// updated to match. All other members are copied verbatim, without recompression.
+pub fn retag_archive(
+    filename: &WheelFilename,
+    retagged: &WheelFilename,
+    archive: &mut ZipArchive<impl Read + Seek + Sized>,
+    writer: impl Write + Seek,
+) -> Result<(), Error> {
+    let dist_info_prefix =
+        find_archive_dist_info(filename, archive.file_names().map(|name| (name, name)))?
+            .1
+            .to_string();
+    let wheel_path = format!("{dist_info_prefix}.dist-info/WHEEL");
+    let record_path = format!("{dist_info_prefix}.dist-info/RECORD");
+
+    let mut writer = ZipWriter::new(writer);
+    let mut wheel = None;
+    let mut record = None;
+    for index in 0..archive.len() {
+        let name = archive
+            .by_index_raw(index)
+            .map_err(|err| Error::Zip(filename.to_string(), err))?
+            .name()
+            .to_string();
+        if name == wheel_path || name == record_path {
+            let mut contents = String::new();
+            archive
+                .by_index(index)
+                .map_err(|err| Error::Zip(filename.to_string(), err))?
+                .read_to_string(&mut contents)?;
+            if name == wheel_path {
+                wheel = Some(contents);
+            } else {
+                record = Some(contents);
+            }
+            continue;
+        }
+        let file = archive
+            .by_index_raw(index)
+            .map_err(|err| Error::Zip(filename.to_string(), err))?;
+        writer
+            .raw_copy_file(file)
+            .map_err(|err| Error::Zip(retagged.to_string(), err))?;
+    }
+
+    let Some(wheel) = wheel else {
+        return Err(Error::InvalidWheel(format!("Missing {wheel_path}")));
+    };
+    let wheel = retag_wheel_file(&wheel, retagged);
+    let options = FileOptions::default();
+    writer
+        .start_file(&wheel_path, options)
+        .map_err(|err| Error::Zip(retagged.to_string(), err))?;
+    writer.write_all(wheel.as_bytes())?;
+
+    // Update the hash of the `WHEEL` file in the `RECORD`, which is always written last, per the
+    // specification.
+    if let Some(record) = record {
+        let mut entries = read_record_file(&mut record.as_bytes())?;
+        for entry in &mut entries {
+            if entry.path == wheel_path {
+                entry.hash = Some(format!(
+                    "sha256={}",
+                    BASE64URL_NOPAD.encode(&Sha256::digest(wheel.as_bytes()))
+                ));
+                entry.size = Some(wheel.len() as u64);
+            }
+        }
+        writer
+            .start_file(&record_path, options)
+            .map_err(|err| Error::Zip(retagged.to_string(), err))?;
+        write_record(&mut writer, &entries)?;
+    }
+
+    writer
+        .finish()
+        .map_err(|err| Error::Zip(retagged.to_string(), err))?;
+    Ok(())
+}
+
+/// Replace the `Tag` entries in the contents of a `WHEEL` file with the tags of the given wheel
+/// filename, retaining the position and order of all other entries.
+fn retag_wheel_file(contents: &str, retagged: &WheelFilename) -> String {
+    let mut tags = Vec::new();
+    for python_tag in &retagged.python_tag {
+        for abi_tag in &retagged.abi_tag {
+            for platform_tag in &retagged.platform_tag {
+                tags.push(format!("Tag: {python_tag}-{abi_tag}-{platform_tag}"));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut tags = Some(tags);
+    for line in contents.lines() {
+        if line
+            .split_once(':')
+            .is_some_and(|(key, _)| key.trim() == "Tag")
+        {
+            // Emit the new tags in place of the first existing tag.
+            lines.extend(tags.take().into_iter().flatten());
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+
+    // Insert the tags before any trailing blank lines, if the file didn't have any to begin with.
+    if let Some(tags) = tags {
+        let end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |index| index + 1);
+        lines.splice(end..end, tags);
+    }
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
+
+fn write_record(writer: impl Write, entries: &[RecordEntry]) -> Result<(), Error> {
+    let mut record_writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .escape(b'"')
+        .from_writer(writer);
+    for entry in entries {
+        record_writer.serialize(entry)?;
+    }
+    record_writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read, Write};
+    use std::str::FromStr;
+
+    use indoc::indoc;
+    use zip::write::FileOptions;
+    use zip::{ZipArchive, ZipWriter};
+
+    use distribution_filename::WheelFilename;
+
+    use super::retag_archive;
+
+    #[test]
+    fn retag() {
+        let wheel = indoc! {"
+            Wheel-Version: 1.0
+            Generator: bdist_wheel (0.37.1)
+            Root-Is-Purelib: false
+            Tag: cp312-cp312-linux_x86_64
+        "};
+
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut writer = ZipWriter::new(&mut buffer);
+            for (name, contents) in [
+                ("foo/__init__.py", "print('foo')\n"),
+                ("foo-1.0.0.dist-info/WHEEL", wheel),
+                ("foo-1.0.0.dist-info/METADATA", "Name: foo\nVersion: 1.0.0\n"),
+                (
+                    "foo-1.0.0.dist-info/RECORD",
+                    "foo/__init__.py,,\nfoo-1.0.0.dist-info/WHEEL,,\nfoo-1.0.0.dist-info/RECORD,,\n",
+                ),
+            ] {
+                writer.start_file(name, FileOptions::default()).unwrap();
+                writer.write_all(contents.as_bytes()).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let filename = WheelFilename::from_str("foo-1.0.0-cp312-cp312-linux_x86_64.whl").unwrap();
+        let retagged = WheelFilename::from_str(
+            "foo-1.0.0-cp312-cp312-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+        )
+        .unwrap();
+        let mut output = Cursor::new(Vec::new());
+        retag_archive(
+            &filename,
+            &retagged,
+            &mut ZipArchive::new(buffer).unwrap(),
+            &mut output,
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(output).unwrap();
+        let mut read = |name: &str| {
+            let mut contents = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut contents)
+                .unwrap();
+            contents
+        };
+        assert_eq!(
+            read("foo-1.0.0.dist-info/WHEEL"),
+            indoc! {"
+                Wheel-Version: 1.0
+                Generator: bdist_wheel (0.37.1)
+                Root-Is-Purelib: false
+                Tag: cp312-cp312-manylinux_2_17_x86_64
+                Tag: cp312-cp312-manylinux2014_x86_64
+            "}
+        );
+        assert_eq!(read("foo/__init__.py"), "print('foo')\n");
+        let record = read("foo-1.0.0.dist-info/RECORD");
+        assert!(record.contains("foo-1.0.0.dist-info/WHEEL,sha256="));
+        assert!(record.contains("foo-1.0.0.dist-info/RECORD,,"));
+    }
+}
@@ -2,10 +2,52 @@ use configparser::ini::Ini;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_hash::FxHashSet;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{wheel, Error};
 
+/// How to generate the entrypoint scripts (e.g., `console_scripts`) for a wheel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ScriptKind {
+    /// Write a wrapper script (or, on Windows, a launcher executable) into the scripts directory.
+    #[default]
+    Wrapper,
+    /// Write the wrapper script into the `.dist-info` directory, and symlink it into the scripts
+    /// directory. Not supported on Windows, where launcher executables are always used.
+    Symlink,
+}
+
+/// Options to control the generation of entrypoint scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptOptions {
+    /// How to generate the entrypoint scripts.
+    pub kind: ScriptKind,
+    /// Whether to rewrite `#!python` shebangs (and generate entrypoint shebangs) to point to the
+    /// environment's interpreter. If `false`, the `#!python` placeholder is retained, e.g., for
+    /// installs that will be relocated and have their shebangs rewritten later.
+    pub rewrite_shebangs: bool,
+    /// Whether to use GUI launchers for `gui_scripts` on Windows. If `false`, `gui_scripts` use
+    /// console launchers, like `console_scripts`.
+    pub gui_trampolines: bool,
+    /// Whether to allow a script to be overwritten by a distribution other than the one that
+    /// provides it. If `false`, such conflicts are reported as errors before installing.
+    pub allow_conflicts: bool,
+}
+
+impl Default for ScriptOptions {
+    fn default() -> Self {
+        Self {
+            kind: ScriptKind::default(),
+            rewrite_shebangs: true,
+            gui_trampolines: true,
+            allow_conflicts: false,
+        }
+    }
+}
+
 /// A script defining the name of the runnable entrypoint and the module and function that should be
 /// run.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -1,63 +1,74 @@
 use std::collections::BTreeSet;
+use std::io;
 use std::path::{Component, Path, PathBuf};
 
+use configparser::ini::Ini;
 use fs_err as fs;
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashSet;
 use std::sync::Mutex;
 use tracing::debug;
-use uv_fs::write_atomic_sync;
+use uv_fs::{verbatim_path, write_atomic_sync};
 
-use crate::wheel::read_record_file;
+use crate::record::RecordEntry;
+use crate::script::Script;
+use crate::wheel::{read_record_file, read_scripts_from_section};
 use crate::Error;
 
+/// Options for uninstalling a distribution.
+#[derive(Debug, Default, Clone)]
+pub struct UninstallOptions {
+    /// Determine which files and directories would be removed, without removing anything.
+    pub dry_run: bool,
+    /// The environment's scripts directory. If provided, any console and GUI scripts for the
+    /// distribution's entry points that aren't listed in its `RECORD` (e.g., as written by older
+    /// installers, or for `.egg-info` distributions) are removed too.
+    pub scripts: Option<PathBuf>,
+}
+
 /// Uninstall the wheel represented by the given `.dist-info` directory.
-pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
+pub fn uninstall_wheel(dist_info: &Path, options: &UninstallOptions) -> Result<Uninstall, Error> {
     let Some(site_packages) = dist_info.parent() else {
         return Err(Error::BrokenVenv(
             "dist-info directory is not in a site-packages directory".to_string(),
         ));
     };
 
-    // Read the RECORD file.
-    let record = {
-        let record_path = dist_info.join("RECORD");
-        let mut record_file = match fs::File::open(&record_path) {
-            Ok(record_file) => record_file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                return Err(Error::MissingRecord(record_path));
-            }
-            Err(err) => return Err(err.into()),
-        };
-        read_record_file(&mut record_file)?
-    };
+    // Read the RECORD file, and the entry points, before anything is removed.
+    let record = read_installed_record(dist_info)?;
+    let entry_points = read_entry_points(dist_info)?;
 
-    let mut file_count = 0usize;
-    let mut dir_count = 0usize;
+    let mut remover = Remover::new(options);
 
     // Uninstall the files, keeping track of any directories that are left empty.
     let mut visited = BTreeSet::new();
+    let mut modules = Vec::new();
     for entry in &record {
-        let path = site_packages.join(&entry.path);
-        match fs::remove_file(&path) {
-            Ok(()) => {
-                debug!("Removed file: {}", path.display());
-                file_count += 1;
-                if let Some(parent) = path.parent() {
-                    visited.insert(normalize_path(parent));
+        let path = normalize_path(&site_packages.join(&entry.path));
+        match remover.remove_file(&path) {
+            Ok(true) => {
+                if path.extension().is_some_and(|ext| ext == "py") {
+                    modules.push(path.clone());
                 }
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-            Err(err) => match fs::remove_dir_all(&path) {
-                Ok(()) => {
-                    debug!("Removed directory: {}", path.display());
-                    dir_count += 1;
-                }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Ok(false) => {}
+            Err(err) => match remover.remove_dir_all(&path) {
+                Ok(_) => {}
                 Err(_) => return Err(err.into()),
             },
         }
+        // Track the parent directory even if the file was already missing, to clean up
+        // directories left behind by a previous, partial uninstall.
+        if let Some(parent) = path.parent() {
+            visited.insert(parent.to_path_buf());
+        }
     }
 
+    // Remove the bytecode of any removed modules, which is rarely listed in the RECORD. This also
+    // covers modules at the root of `site-packages`, whose `__pycache__` is shared with other
+    // distributions.
+    remover.remove_orphaned_bytecode(&modules)?;
+
     // If any directories were left empty, remove them. Iterate in reverse order such that we visit
     // the deepest directories first.
     for path in visited.iter().rev() {
@@ -79,33 +90,15 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
             // If the directory contains a `__pycache__` directory, always remove it. `__pycache__`
             // may or may not be listed in the RECORD, but installers are expected to be smart
             // enough to remove it either way.
-            let pycache = path.join("__pycache__");
-            match fs::remove_dir_all(&pycache) {
-                Ok(()) => {
-                    debug!("Removed directory: {}", pycache.display());
-                    dir_count += 1;
-                }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-                Err(err) => return Err(err.into()),
-            }
+            remover.remove_dir_all(&path.join("__pycache__"))?;
 
-            // Try to read from the directory. If it doesn't exist, assume we deleted it in a
-            // previous iteration.
-            let mut read_dir = match fs::read_dir(path) {
-                Ok(read_dir) => read_dir,
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
-                Err(err) => return Err(err.into()),
-            };
-
-            // If the directory is not empty, we're done.
-            if read_dir.next().is_some() {
+            // If the directory is not empty (or doesn't exist, since we deleted it in a previous
+            // iteration), we're done.
+            if !remover.is_empty_dir(path)? {
                 break;
             }
 
-            fs::remove_dir(path)?;
-
-            debug!("Removed directory: {}", path.display());
-            dir_count += 1;
+            remover.remove_dir(path)?;
 
             if let Some(parent) = path.parent() {
                 path = parent;
@@ -115,18 +108,112 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
         }
     }
 
-    Ok(Uninstall {
-        file_count,
-        dir_count,
-    })
+    // Remove any scripts that weren't listed in the RECORD.
+    if let Some(scripts) = options.scripts.as_deref() {
+        remover.remove_scripts(scripts, &entry_points)?;
+    }
+
+    Ok(remover.finish())
+}
+
+/// Return the absolute paths of the files that belong to the wheel represented by the given
+/// `.dist-info` directory, as listed in its `RECORD` file.
+pub fn installed_files(dist_info: &Path) -> Result<Vec<PathBuf>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    Ok(read_installed_record(dist_info)?
+        .into_iter()
+        .map(|entry| normalize_path(&site_packages.join(entry.path)))
+        .collect())
+}
+
+/// Return the directories that an editable install adds to `sys.path`, as listed in the `.pth`
+/// files in the `RECORD` of the given `.dist-info` directory.
+///
+/// A PEP 660 editable may span several roots, e.g., if the build backend maps packages from more
+/// than one source directory, or emits a namespace package whose portions live in separate
+/// directories. Lines that execute code (i.e., `import` statements) and comments are skipped, and
+/// relative paths are resolved against `site-packages`, as in the `site` module.
+pub fn editable_roots(dist_info: &Path) -> Result<Vec<PathBuf>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    let mut roots = Vec::new();
+    for entry in read_installed_record(dist_info)? {
+        // `.pth` files are only processed at the root of `site-packages`.
+        let path = Path::new(&entry.path);
+        if path.extension().map_or(true, |ext| ext != "pth") || path.components().count() != 1 {
+            continue;
+        }
+        let contents = match fs::read_to_string(site_packages.join(path)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("import ")
+                || line.starts_with("import\t")
+            {
+                continue;
+            }
+            let root = normalize_path(&site_packages.join(line));
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+    }
+    Ok(roots)
+}
+
+/// Return the paths (relative to `site-packages`) and hashes of the files that belong to the
+/// wheel represented by the given `.dist-info` directory, as listed in its `RECORD` file.
+///
+/// The `.dist-info` directory may belong to an installed wheel, or to an unzipped wheel (e.g., in
+/// the cache). Entries without a hash (like the `RECORD` itself) are omitted.
+pub fn recorded_hashes(dist_info: &Path) -> Result<Vec<(String, String)>, Error> {
+    Ok(read_installed_record(dist_info)?
+        .into_iter()
+        .filter_map(|entry| Some((entry.path, entry.hash?)))
+        .collect())
+}
+
+/// Return the names of the console and GUI scripts declared by the distribution represented by
+/// the given `.dist-info` or `.egg-info` directory.
+pub fn script_names(info: &Path) -> Result<Vec<String>, Error> {
+    Ok(read_entry_points(info)?
+        .into_iter()
+        .map(|script| script.name)
+        .collect())
+}
+
+/// Read the `RECORD` file from an installed `.dist-info` directory.
+fn read_installed_record(dist_info: &Path) -> Result<Vec<RecordEntry>, Error> {
+    let record_path = dist_info.join("RECORD");
+    let mut record_file = match fs::File::open(&record_path) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    read_record_file(&mut record_file)
 }
 
 /// Uninstall the egg represented by the `.egg-info` directory.
 ///
 /// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L483>
-pub fn uninstall_egg(egg_info: &Path) -> Result<Uninstall, Error> {
-    let mut file_count = 0usize;
-    let mut dir_count = 0usize;
+pub fn uninstall_egg(egg_info: &Path, options: &UninstallOptions) -> Result<Uninstall, Error> {
+    let mut remover = Remover::new(options);
 
     let dist_location = egg_info
         .parent()
@@ -163,52 +250,43 @@ pub fn uninstall_egg(egg_info: &Path) -> Result<Uninstall, Error> {
         }
     };
 
+    // Read the entry points, before the `.egg-info` directory is removed.
+    let entry_points = read_entry_points(egg_info)?;
+
     // Remove everything in `top_level.txt`.
+    let mut modules = Vec::new();
     for entry in top_level {
         let path = dist_location.join(&entry);
 
         // Remove as a directory.
-        match fs_err::remove_dir_all(&path) {
-            Ok(()) => {
-                debug!("Removed directory: {}", path.display());
-                dir_count += 1;
-                continue;
-            }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-            Err(err) => return Err(err.into()),
+        if remover.remove_dir_all(&path)? {
+            continue;
         }
 
         // Remove as a `.py`, `.pyc`, or `.pyo` file.
         for exten in &["py", "pyc", "pyo"] {
             let path = path.with_extension(exten);
-            match fs_err::remove_file(&path) {
-                Ok(()) => {
-                    debug!("Removed file: {}", path.display());
-                    file_count += 1;
-                    break;
+            if remover.remove_file(&path)? {
+                if *exten == "py" {
+                    modules.push(path);
                 }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-                Err(err) => return Err(err.into()),
+                break;
             }
         }
     }
 
+    // Remove the bytecode of any removed top-level modules.
+    remover.remove_orphaned_bytecode(&modules)?;
+
     // Remove the `.egg-info` directory.
-    match fs_err::remove_dir_all(egg_info) {
-        Ok(()) => {
-            debug!("Removed directory: {}", egg_info.display());
-            dir_count += 1;
-        }
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-        Err(err) => {
-            return Err(err.into());
-        }
+    remover.remove_dir_all(egg_info)?;
+
+    // Remove any scripts for the entry points.
+    if let Some(scripts) = options.scripts.as_deref() {
+        remover.remove_scripts(scripts, &entry_points)?;
     }
 
-    Ok(Uninstall {
-        file_count,
-        dir_count,
-    })
+    Ok(remover.finish())
 }
 
 fn normcase(s: &str) -> String {
@@ -224,8 +302,11 @@ static EASY_INSTALL_PTH: Lazy<Mutex<i32>> = Lazy::new(Mutex::default);
 /// Uninstall the legacy editable represented by the `.egg-link` file.
 ///
 /// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L534-L552>
-pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
-    let mut file_count = 0usize;
+pub fn uninstall_legacy_editable(
+    egg_link: &Path,
+    options: &UninstallOptions,
+) -> Result<Uninstall, Error> {
+    let mut remover = Remover::new(options);
 
     // Find the target line in the `.egg-link` file.
     let contents = fs::read_to_string(egg_link)?;
@@ -244,13 +325,13 @@ pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
     // This comes from `pkg_resources.normalize_path`
     let target_line = normcase(target_line);
 
-    match fs::remove_file(egg_link) {
-        Ok(()) => {
-            debug!("Removed file: {}", egg_link.display());
-            file_count += 1;
-        }
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
-        Err(err) => return Err(err.into()),
+    remover.remove_file(egg_link)?;
+
+    // Remove the `-nspkg.pth` file that `setup.py develop` writes alongside the `.egg-link` for
+    // projects with namespace packages, which isn't tracked anywhere else.
+    if let Some(stem) = egg_link.file_stem() {
+        let nspkg = egg_link.with_file_name(format!("{}-nspkg.pth", stem.to_string_lossy()));
+        remover.remove_file(&nspkg)?;
     }
 
     let site_package = egg_link.parent().ok_or(Error::BrokenVenv(
@@ -258,6 +339,11 @@ pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
     ))?;
     let easy_install = site_package.join("easy-install.pth");
 
+    // In a dry run, leave `easy-install.pth` untouched.
+    if options.dry_run {
+        return Ok(remover.finish());
+    }
+
     // Since uv has an environment lock, it's enough to add a mutex here to ensure we never
     // lose writes to `easy-install.pth` (this is the only place in uv where `easy-install.pth`
     // is modified).
@@ -281,10 +367,7 @@ pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
         debug!("Removed line from `easy-install.pth`: {target_line}");
     }
 
-    Ok(Uninstall {
-        file_count,
-        dir_count: 0usize,
-    })
+    Ok(remover.finish())
 }
 
 #[derive(Debug, Default)]
@@ -293,6 +376,214 @@ pub struct Uninstall {
     pub file_count: usize,
     /// The number of directories that were removed during the uninstallation.
     pub dir_count: usize,
+    /// The files and directories that were removed (or, in a dry run, would be removed), in the
+    /// order of removal. Directories are listed without their contents.
+    pub paths: Vec<PathBuf>,
+}
+
+/// Removes files and directories, or, in a dry run, records what would be removed.
+struct Remover<'a> {
+    options: &'a UninstallOptions,
+    /// The paths that were (or would be) removed, to determine which directories would be left
+    /// empty in a dry run.
+    removed: FxHashSet<PathBuf>,
+    summary: Uninstall,
+}
+
+impl<'a> Remover<'a> {
+    fn new(options: &'a UninstallOptions) -> Self {
+        Self {
+            options,
+            removed: FxHashSet::default(),
+            summary: Uninstall::default(),
+        }
+    }
+
+    fn record(&mut self, path: &Path, is_dir: bool) {
+        if is_dir {
+            debug!("Removed directory: {}", path.display());
+            self.summary.dir_count += 1;
+        } else {
+            debug!("Removed file: {}", path.display());
+            self.summary.file_count += 1;
+        }
+        self.removed.insert(path.to_path_buf());
+        self.summary.paths.push(path.to_path_buf());
+    }
+
+    /// Returns `true` if the path has already been (or would have been) removed.
+    fn is_removed(&self, path: &Path) -> bool {
+        self.removed.contains(path)
+    }
+
+    /// Remove a file (or symlink), returning `false` if it doesn't exist.
+    fn remove_file(&mut self, path: &Path) -> io::Result<bool> {
+        if self.is_removed(path) {
+            return Ok(false);
+        }
+        let result = if self.options.dry_run {
+            fs::symlink_metadata(path).and_then(|metadata| {
+                if metadata.is_dir() {
+                    Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("Is a directory: {}", path.display()),
+                    ))
+                } else {
+                    Ok(())
+                }
+            })
+        } else {
+            fs::remove_file(verbatim_path(path))
+        };
+        match result {
+            Ok(()) => {
+                self.record(path, false);
+                Ok(true)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Remove a directory and its contents, returning `false` if it doesn't exist.
+    fn remove_dir_all(&mut self, path: &Path) -> io::Result<bool> {
+        if self.is_removed(path) {
+            return Ok(false);
+        }
+        let result = if self.options.dry_run {
+            fs::read_dir(path).map(|_| ())
+        } else {
+            fs::remove_dir_all(verbatim_path(path))
+        };
+        match result {
+            Ok(()) => {
+                self.record(path, true);
+                Ok(true)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Remove an empty directory.
+    fn remove_dir(&mut self, path: &Path) -> io::Result<()> {
+        if !self.options.dry_run {
+            fs::remove_dir(path)?;
+        }
+        self.record(path, true);
+        Ok(())
+    }
+
+    /// Returns `true` if the directory exists, and is (or, in a dry run, would be) empty.
+    fn is_empty_dir(&self, path: &Path) -> io::Result<bool> {
+        if self.is_removed(path) {
+            return Ok(false);
+        }
+        let read_dir = match fs::read_dir(path) {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        for entry in read_dir {
+            if !self.is_removed(&entry?.path()) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Remove the cached bytecode (e.g., `__pycache__/foo.cpython-312.pyc`) for the given `.py`
+    /// modules, along with any `__pycache__` directories left empty.
+    fn remove_orphaned_bytecode(&mut self, modules: &[PathBuf]) -> io::Result<()> {
+        let mut pycaches = BTreeSet::new();
+        for module in modules {
+            let (Some(parent), Some(stem)) = (module.parent(), module.file_stem()) else {
+                continue;
+            };
+            let pycache = parent.join("__pycache__");
+            let read_dir = match fs::read_dir(&pycache) {
+                Ok(read_dir) => read_dir,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            let prefix = format!("{}.", stem.to_string_lossy());
+            for entry in read_dir {
+                let entry = entry?;
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                if file_name.starts_with(&prefix) && file_name.ends_with(".pyc") {
+                    self.remove_file(&entry.path())?;
+                }
+            }
+            pycaches.insert(pycache);
+        }
+        for pycache in pycaches {
+            if self.is_empty_dir(&pycache)? {
+                self.remove_dir(&pycache)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the scripts for the given entry points from the scripts directory, if they're
+    /// (still) present and invoke the entry point.
+    fn remove_scripts(&mut self, scripts: &Path, entry_points: &[Script]) -> io::Result<()> {
+        for entry_point in entry_points {
+            for name in [
+                entry_point.name.clone(),
+                format!("{}.exe", entry_point.name),
+                format!("{}-script.py", entry_point.name),
+                format!("{}-script.pyw", entry_point.name),
+            ] {
+                let path = scripts.join(name);
+                if self.is_removed(&path) {
+                    continue;
+                }
+                // Only remove scripts that import the entry point's module, to avoid removing a
+                // script of the same name that belongs to another distribution.
+                let contents = match fs::read(&path) {
+                    Ok(contents) => contents,
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                    Err(err) => return Err(err),
+                };
+                let import = format!("from {} import", entry_point.module);
+                if !String::from_utf8_lossy(&contents).contains(&import) {
+                    debug!(
+                        "Skipping script that doesn't invoke `{}`: {}",
+                        entry_point.module,
+                        path.display()
+                    );
+                    continue;
+                }
+                self.remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Uninstall {
+        self.summary
+    }
+}
+
+/// Read the console and GUI script entry points from a `.dist-info` or `.egg-info` directory.
+fn read_entry_points(info: &Path) -> Result<Vec<Script>, Error> {
+    let contents = match fs::read_to_string(info.join("entry_points.txt")) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let Ok(mapping) = Ini::new_cs().read(contents) else {
+        debug!("Ignoring invalid `entry_points.txt` in: {}", info.display());
+        return Ok(Vec::new());
+    };
+    let mut scripts = Vec::new();
+    for section in ["console_scripts", "gui_scripts"] {
+        if let Some(entries) = mapping.get(section) {
+            scripts.extend(read_scripts_from_section(entries, section, None)?);
+        }
+    }
+    Ok(scripts)
 }
 
 /// Normalize a path, removing things like `.` and `..`.
@@ -324,3 +615,129 @@ fn normalize_path(path: &Path) -> PathBuf {
     }
     ret
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::Error;
+
+    use super::{editable_roots, uninstall_wheel, UninstallOptions};
+
+    /// Create a `foo` distribution with a package, a root module with orphaned bytecode, and a
+    /// console script that isn't listed in the `RECORD`.
+    fn install_foo(site_packages: &Path, scripts: &Path) -> Result<(), Error> {
+        fs_err::create_dir_all(site_packages.join("foo-1.0.dist-info"))?;
+        fs_err::create_dir_all(site_packages.join("foo/sub/__pycache__"))?;
+        fs_err::create_dir_all(site_packages.join("__pycache__"))?;
+        fs_err::create_dir_all(scripts)?;
+        fs_err::write(site_packages.join("foo/sub/__init__.py"), "")?;
+        fs_err::write(
+            site_packages.join("foo/sub/__pycache__/__init__.cpython-312.pyc"),
+            "",
+        )?;
+        fs_err::write(site_packages.join("bar.py"), "")?;
+        fs_err::write(site_packages.join("__pycache__/bar.cpython-312.pyc"), "")?;
+        fs_err::write(site_packages.join("__pycache__/other.cpython-312.pyc"), "")?;
+        fs_err::write(
+            site_packages.join("foo-1.0.dist-info/RECORD"),
+            "foo/sub/__init__.py,,\nbar.py,,\nfoo-1.0.dist-info/entry_points.txt,,\nfoo-1.0.dist-info/RECORD,,\n",
+        )?;
+        fs_err::write(
+            site_packages.join("foo-1.0.dist-info/entry_points.txt"),
+            "[console_scripts]\nfoo = foo.sub:main\nbaz = baz:main\n",
+        )?;
+        fs_err::write(scripts.join("foo"), "from foo.sub import main\n")?;
+        fs_err::write(scripts.join("baz"), "from other import main\n")?;
+        Ok(())
+    }
+
+    #[test]
+    fn uninstall_residual_artifacts() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        let scripts = temp_dir.path().join("bin");
+        install_foo(&site_packages, &scripts)?;
+
+        let options = UninstallOptions {
+            dry_run: true,
+            scripts: Some(scripts.clone()),
+        };
+
+        // A dry run lists everything, but removes nothing.
+        let dry_run = uninstall_wheel(&site_packages.join("foo-1.0.dist-info"), &options)?;
+        let mut paths = dry_run
+            .paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(temp_dir.path())
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "bin/foo",
+                "site-packages/__pycache__/bar.cpython-312.pyc",
+                "site-packages/bar.py",
+                "site-packages/foo",
+                "site-packages/foo-1.0.dist-info",
+                "site-packages/foo-1.0.dist-info/RECORD",
+                "site-packages/foo-1.0.dist-info/entry_points.txt",
+                "site-packages/foo/sub",
+                "site-packages/foo/sub/__init__.py",
+                "site-packages/foo/sub/__pycache__",
+                "site-packages/foo/sub/__pycache__/__init__.cpython-312.pyc",
+            ]
+        );
+        assert!(site_packages.join("foo/sub/__init__.py").exists());
+        assert!(scripts.join("foo").exists());
+
+        // The real uninstall removes the same paths.
+        let options = UninstallOptions {
+            dry_run: false,
+            ..options
+        };
+        let uninstall = uninstall_wheel(&site_packages.join("foo-1.0.dist-info"), &options)?;
+        assert_eq!(uninstall.paths.len(), dry_run.paths.len());
+        for path in &uninstall.paths {
+            assert!(!path.exists(), "{} still exists", path.display());
+        }
+        assert!(site_packages
+            .join("__pycache__/other.cpython-312.pyc")
+            .exists());
+        assert!(scripts.join("baz").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn editable_roots_multiple() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.dist-info");
+        fs_err::create_dir_all(&dist_info)?;
+        fs_err::write(
+            dist_info.join("RECORD"),
+            "_foo.pth,,\n__editable___foo_finder.py,,\nfoo-1.0.dist-info/RECORD,,\n",
+        )?;
+        fs_err::write(
+            site_packages.join("_foo.pth"),
+            format!(
+                "# comment\n{}\nimport __editable___foo_finder; __editable___foo_finder.install()\n../lib\n{}\n",
+                temp_dir.path().join("src").display(),
+                temp_dir.path().join("src").display(),
+            ),
+        )?;
+
+        assert_eq!(
+            editable_roots(&dist_info)?,
+            vec![temp_dir.path().join("src"), temp_dir.path().join("lib")]
+        );
+
+        Ok(())
+    }
+}
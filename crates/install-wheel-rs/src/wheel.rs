@@ -14,11 +14,11 @@ use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::ZipWriter;
 
-use pypi_types::DirectUrl;
+use pypi_types::{ArchiveInfo, DirectUrl, ARCHIVE_INFO_FILENAME};
 use uv_fs::Simplified;
 
 use crate::record::RecordEntry;
-use crate::script::Script;
+use crate::script::{Script, ScriptKind, ScriptOptions};
 use crate::{Error, Layout};
 
 const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
@@ -90,7 +90,10 @@ pub(crate) fn read_scripts_from_section(
 /// <https://github.com/richo/hashing-copy/blob/d8dd2fdb63c6faf198de0c9e5713d6249cbb5323/src/lib.rs#L10-L52>
 /// which in turn got it from std
 /// <https://doc.rust-lang.org/1.58.0/src/std/io/copy.rs.html#128-156>
-fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<(u64, String)> {
+pub(crate) fn copy_and_hash(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> io::Result<(u64, String)> {
     // TODO: Do we need to support anything besides sha256?
     let mut hasher = Sha256::new();
     // Same buf size as std. Note that this number is important for performance
@@ -143,6 +146,81 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
     format!("#!{executable}")
 }
 
+/// Parse the Python executable from a script's shebang, as written by [`format_shebang`].
+///
+/// Returns the executable, along with the byte offset at which the shebang ends.
+fn parse_shebang(contents: &str) -> Option<(PathBuf, usize)> {
+    const EXEC_PREFIX: &str = "#!/bin/sh\n'''exec' ";
+    const EXEC_SUFFIX: &str = " \"$0\" \"$@\"\n' '''";
+
+    if let Some(rest) = contents.strip_prefix(EXEC_PREFIX) {
+        let (quoted, _) = rest.split_once(EXEC_SUFFIX)?;
+        let executable = quoted
+            .strip_prefix('\'')?
+            .strip_suffix('\'')?
+            .replace(r#"'"'"'"#, "'");
+        let end = EXEC_PREFIX.len() + quoted.len() + EXEC_SUFFIX.len();
+        return Some((PathBuf::from(executable), end));
+    }
+
+    let line = contents.strip_prefix("#!")?.lines().next()?;
+    Some((PathBuf::from(line.trim()), 2 + line.len()))
+}
+
+/// Rewrite the shebangs of the scripts in the given directory that refer to a Python executable
+/// for which `is_stale` returns `true`, such that they refer to `executable` instead (e.g., after
+/// the environment was moved).
+///
+/// Only scripts with a shebang are rewritten; Windows launchers are left as-is.
+///
+/// Returns the paths to the rewritten scripts.
+pub fn rewrite_shebangs(
+    scripts: &Path,
+    executable: &Path,
+    os_name: &str,
+    is_stale: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut rewritten = Vec::new();
+    for entry in fs::read_dir(scripts)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let path = entry.path();
+
+        // Skip any binary files (e.g., Windows launchers) and files without a shebang.
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some((previous, end)) = parse_shebang(&contents) else {
+            continue;
+        };
+        if previous == executable || !is_stale(&previous) {
+            continue;
+        }
+
+        let contents = format!(
+            "{}{}",
+            format_shebang(executable, os_name),
+            &contents[end..]
+        );
+        fs::write(&path, contents)?;
+        rewritten.push(path);
+    }
+    Ok(rewritten)
+}
+
+/// Return the shebang to use for scripts in the given environment.
+///
+/// If shebang rewriting is disabled, the `#!python` placeholder is used instead.
+fn script_shebang(layout: &Layout, options: ScriptOptions) -> String {
+    if options.rewrite_shebangs {
+        format_shebang(&layout.sys_executable, &layout.os_name)
+    } else {
+        "#!python".to_string()
+    }
+}
+
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
 /// stored zip file. The launcher will look for `python[w].exe` adjacent to it in the same directory
 /// to start the embedded script.
@@ -218,12 +296,17 @@ pub(crate) fn windows_script_launcher(
 }
 
 /// Create the wrapper scripts in the bin folder of the venv for launching console scripts.
+///
+/// With [`ScriptKind::Symlink`], the wrapper scripts are written to the `.dist-info` directory
+/// instead, and symlinked into the bin folder (on Unix).
 pub(crate) fn write_script_entrypoints(
     layout: &Layout,
     site_packages: &Path,
+    dist_info_prefix: &str,
     entrypoints: &[Script],
     record: &mut Vec<RecordEntry>,
     is_gui: bool,
+    options: ScriptOptions,
 ) -> Result<(), Error> {
     for entrypoint in entrypoints {
         let entrypoint_absolute = if cfg!(windows) {
@@ -253,19 +336,62 @@ pub(crate) fn write_script_entrypoints(
             })?;
 
         // Generate the launcher script.
-        let launcher_python_script = get_script_launcher(
-            entrypoint,
-            &format_shebang(&layout.sys_executable, &layout.os_name),
-        );
+        let launcher_python_script =
+            get_script_launcher(entrypoint, &script_shebang(layout, options));
 
         // If necessary, wrap the launcher script in a Windows launcher binary.
         if cfg!(windows) {
             write_file_recorded(
                 site_packages,
                 &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable)?,
+                &windows_script_launcher(
+                    &launcher_python_script,
+                    is_gui && options.gui_trampolines,
+                    &layout.sys_executable,
+                )?,
+                record,
+            )?;
+        } else if options.kind == ScriptKind::Symlink {
+            // Write the launcher script into the `.dist-info` directory.
+            let target_relative = PathBuf::from(format!("{dist_info_prefix}.dist-info"))
+                .join("scripts")
+                .join(&entrypoint.name);
+            fs::create_dir_all(site_packages.join(&target_relative).parent().unwrap())?;
+            write_file_recorded(
+                site_packages,
+                &target_relative,
+                &launcher_python_script,
                 record,
             )?;
+
+            // Make the launcher executable.
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(
+                    site_packages.join(&target_relative),
+                    std::fs::Permissions::from_mode(0o755),
+                )?;
+            }
+
+            // Symlink the launcher into the bin folder, replacing any existing symlink.
+            let target =
+                pathdiff::diff_paths(site_packages.join(&target_relative), &layout.scheme.scripts)
+                    .ok_or_else(|| {
+                        Error::Io(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!(
+                                "Could not find relative path for: {}",
+                                target_relative.simplified_display()
+                            ),
+                        ))
+                    })?;
+            uv_fs::replace_symlink(&target, &entrypoint_absolute)?;
+            record.push(RecordEntry {
+                path: entrypoint_relative.display().to_string(),
+                hash: None,
+                size: None,
+            });
         } else {
             write_file_recorded(
                 site_packages,
@@ -432,6 +558,7 @@ fn install_script(
     site_packages: &Path,
     record: &mut [RecordEntry],
     file: &DirEntry,
+    options: ScriptOptions,
 ) -> Result<(), Error> {
     if !file.file_type()?.is_file() {
         return Err(Error::InvalidWheel(format!(
@@ -467,10 +594,8 @@ fn install_script(
     // scripts might be binaries, so we read an exact number of bytes instead of the first line as string
     let mut start = vec![0; placeholder_python.len()];
     script.read_exact(&mut start)?;
-    let size_and_encoded_hash = if start == placeholder_python {
-        let start = format_shebang(&layout.sys_executable, &layout.os_name)
-            .as_bytes()
-            .to_vec();
+    let size_and_encoded_hash = if start == placeholder_python && options.rewrite_shebangs {
+        let start = script_shebang(layout, options).as_bytes().to_vec();
         let mut target = File::create(&script_absolute)?;
         let size_and_encoded_hash = copy_and_hash(&mut start.chain(script), &mut target)?;
         fs::remove_file(&path)?;
@@ -525,6 +650,7 @@ pub(crate) fn install_data(
     console_scripts: &[Script],
     gui_scripts: &[Script],
     record: &mut [RecordEntry],
+    options: ScriptOptions,
 ) -> Result<(), Error> {
     for entry in fs::read_dir(data_dir)? {
         let entry = entry?;
@@ -562,7 +688,7 @@ pub(crate) fn install_data(
                         initialized = true;
                     }
 
-                    install_script(layout, site_packages, record, &file)?;
+                    install_script(layout, site_packages, record, &file, options)?;
                 }
             }
             Some("headers") => {
@@ -613,12 +739,14 @@ pub(crate) fn write_file_recorded(
     Ok(())
 }
 
-/// Adds `INSTALLER`, `REQUESTED` and `direct_url.json` to the .dist-info dir
+/// Adds `INSTALLER`, `REQUESTED`, `direct_url.json`, and `uv_archive_info.json` to the .dist-info
+/// dir
 pub(crate) fn extra_dist_info(
     site_packages: &Path,
     dist_info_prefix: &str,
     requested: bool,
     direct_url: Option<&DirectUrl>,
+    archive_info: Option<&ArchiveInfo>,
     installer: Option<&str>,
     record: &mut Vec<RecordEntry>,
 ) -> Result<(), Error> {
@@ -634,6 +762,14 @@ pub(crate) fn extra_dist_info(
             record,
         )?;
     }
+    if let Some(archive_info) = archive_info {
+        write_file_recorded(
+            site_packages,
+            &dist_info_dir.join(ARCHIVE_INFO_FILENAME),
+            serde_json::to_string(archive_info)?.as_bytes(),
+            record,
+        )?;
+    }
     if let Some(installer) = installer {
         write_file_recorded(
             site_packages,
@@ -904,6 +1040,23 @@ mod test {
         assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
     }
 
+    #[test]
+    fn test_parse_shebang() {
+        for executable in [
+            "/usr/bin/python3",
+            "/usr/bin/path to python3",
+            "/usr/bin/path to 'python3'",
+        ] {
+            let shebang = format_shebang(executable, "posix");
+            let contents = format!("{shebang}\nimport sys\n");
+            let (parsed, end) = parse_shebang(&contents).unwrap();
+            assert_eq!(parsed, Path::new(executable));
+            assert_eq!(&contents[end..], "\nimport sys\n");
+        }
+
+        assert_eq!(parse_shebang("import sys\n"), None);
+    }
+
     #[test]
     fn test_empty_value() -> Result<(), Error> {
         let wheel = indoc! {r"
@@ -80,8 +80,9 @@ impl<'a> Cursor<'a> {
         Some((pos, char))
     }
 
+    /// Returns the number of bytes remaining in the input.
     pub(crate) fn remaining(&self) -> usize {
-        self.chars.clone().count()
+        self.input.len() - self.pos
     }
 
     /// Peeks over the cursor as long as the condition is met, without consuming it.
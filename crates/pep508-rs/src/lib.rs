@@ -93,7 +93,8 @@ pub enum Pep508ErrorSource<T: Pep508Url = VerbatimUrl> {
 impl<T: Pep508Url> Display for Pep508Error<T> {
     /// Pretty formatting with underline.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // We can use char indices here since it's a Vec<char>
+        // The start and length are byte offsets into the input, while the underline is measured
+        // in (display) columns.
         let start_offset = self.input[..self.start]
             .chars()
             .flat_map(|c| c.width())
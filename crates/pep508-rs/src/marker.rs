@@ -1692,6 +1692,52 @@ impl MarkerTree {
         }
     }
 
+    /// Returns the expressions that decide that this marker tree evaluates to `false` in the given
+    /// environment, or an empty vector if it evaluates to `true`.
+    ///
+    /// For an `and`, these are the expressions that decide each unsatisfied operand; for an `or`,
+    /// every operand is unsatisfied, so these are the expressions that decide all operands.
+    pub fn unsatisfied_expressions(
+        &self,
+        env: &MarkerEnvironment,
+        extras: &[ExtraName],
+    ) -> Vec<&MarkerExpression> {
+        let mut unsatisfied = Vec::new();
+        self.collect_unsatisfied_expressions(env, extras, &mut unsatisfied);
+        unsatisfied
+    }
+
+    fn collect_unsatisfied_expressions<'a>(
+        &'a self,
+        env: &MarkerEnvironment,
+        extras: &[ExtraName],
+        unsatisfied: &mut Vec<&'a MarkerExpression>,
+    ) {
+        match self {
+            Self::Expression(expression) => {
+                if !expression.evaluate(Some(env), extras, &mut TracingReporter) {
+                    unsatisfied.push(expression);
+                }
+            }
+            Self::And(expressions) => {
+                for expression in expressions {
+                    expression.collect_unsatisfied_expressions(env, extras, unsatisfied);
+                }
+            }
+            Self::Or(expressions) => {
+                if expressions
+                    .iter()
+                    .any(|x| x.evaluate_reporter_impl(Some(env), extras, &mut TracingReporter))
+                {
+                    return;
+                }
+                for expression in expressions {
+                    expression.collect_unsatisfied_expressions(env, extras, unsatisfied);
+                }
+            }
+        }
+    }
+
     /// Checks if the requirement should be activated with the given set of active extras and a set
     /// of possible python versions (from `requires-python`) without evaluating the remaining
     /// environment markers, i.e. if there is potentially an environment that could activate this
@@ -2171,6 +2217,43 @@ mod test {
         assert!(!marker3.evaluate(&env37, &[]));
     }
 
+    #[test]
+    fn test_unsatisfied_expressions() {
+        let env37 = env37();
+        let unsatisfied = |marker: &str| {
+            MarkerTree::from_str(marker)
+                .unwrap()
+                .unsatisfied_expressions(&env37, &[])
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // A satisfied marker has no unsatisfied expressions.
+        assert!(unsatisfied("python_version >= '3.7' and sys_platform == 'linux'").is_empty());
+        assert!(unsatisfied("python_version < '3' or sys_platform == 'linux'").is_empty());
+
+        // Only the unsatisfied operands of an `and` decide it.
+        assert_eq!(
+            unsatisfied("python_version >= '3.7' and sys_platform == 'win32'"),
+            vec!["sys_platform == 'win32'"]
+        );
+
+        // All operands of an `or` decide it.
+        assert_eq!(
+            unsatisfied("python_version < '3' or sys_platform == 'win32'"),
+            vec!["python_version < '3'", "sys_platform == 'win32'"]
+        );
+
+        // Satisfied `or`s within an unsatisfied `and` are skipped.
+        assert_eq!(
+            unsatisfied(
+                "(os_name == 'nt' or sys_platform == 'linux') and (python_version < '3' or sys_platform == 'darwin')"
+            ),
+            vec!["python_version < '3'", "sys_platform == 'darwin'"]
+        );
+    }
+
     #[test]
     #[cfg(feature = "tracing")]
     fn warnings() {
@@ -2309,6 +2392,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn wrong_trailing_non_ascii() {
+        assert_snapshot!(
+            parse_err(r#"python_version == "3.8"éé"#),
+            @r#"
+            Unexpected character 'é', expected 'and', 'or' or end of input
+            python_version == "3.8"éé
+                                   ^"#
+        );
+    }
+
     #[test]
     fn test_marker_expression() {
         assert_eq!(
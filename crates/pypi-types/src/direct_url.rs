@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::{HashAlgorithm, HashDigest};
+
 /// Metadata for a distribution that was installed via a direct URL.
 ///
 /// See: <https://packaging.python.org/en/latest/specifications/direct-url-data-structure/>
@@ -41,6 +44,24 @@ pub enum DirectUrl {
     },
 }
 
+impl DirectUrl {
+    /// Record the [`HashDigest`]s of the archive from which the distribution was installed, if the
+    /// URL refers to an archive.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &[HashDigest]) -> Self {
+        match self {
+            Self::ArchiveUrl {
+                url, subdirectory, ..
+            } if !hashes.is_empty() => Self::ArchiveUrl {
+                url,
+                archive_info: ArchiveInfo::from_hashes(hashes),
+                subdirectory,
+            },
+            direct_url => direct_url,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct DirInfo {
@@ -48,6 +69,11 @@ pub struct DirInfo {
     pub editable: Option<bool>,
 }
 
+/// The name of the file in a `.dist-info` directory in which uv records the [`ArchiveInfo`] of a
+/// distribution installed from a registry, which, unlike a direct URL installation, has no
+/// `direct_url.json` in which to record it.
+pub const ARCHIVE_INFO_FILENAME: &str = "uv_archive_info.json";
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ArchiveInfo {
@@ -57,6 +83,60 @@ pub struct ArchiveInfo {
     pub hashes: Option<HashMap<String, String>>,
 }
 
+impl ArchiveInfo {
+    /// Create an [`ArchiveInfo`] from the [`HashDigest`]s of an archive.
+    pub fn from_hashes(hashes: &[HashDigest]) -> Self {
+        if hashes.is_empty() {
+            return Self {
+                hash: None,
+                hashes: None,
+            };
+        }
+        Self {
+            // The `hash` key is deprecated, but tools may still rely on it.
+            hash: hashes
+                .iter()
+                .find(|digest| digest.algorithm == HashAlgorithm::Sha256)
+                .map(|digest| format!("{}={}", digest.algorithm, digest.digest)),
+            hashes: Some(
+                hashes
+                    .iter()
+                    .map(|digest| (digest.algorithm.to_string(), digest.digest.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Return the [`HashDigest`]s recorded for the archive, ignoring any unsupported algorithms.
+    pub fn digests(&self) -> Vec<HashDigest> {
+        let mut digests = self
+            .hashes
+            .iter()
+            .flatten()
+            .filter_map(|(algorithm, digest)| {
+                Some(HashDigest {
+                    algorithm: HashAlgorithm::from_str(algorithm).ok()?,
+                    digest: digest.clone().into_boxed_str(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if let Some((algorithm, digest)) =
+            self.hash.as_deref().and_then(|hash| hash.split_once('='))
+        {
+            if let Ok(algorithm) = HashAlgorithm::from_str(algorithm) {
+                let digest = HashDigest {
+                    algorithm,
+                    digest: digest.to_string().into_boxed_str(),
+                };
+                if !digests.contains(&digest) {
+                    digests.push(digest);
+                }
+            }
+        }
+        digests
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VcsInfo {
@@ -123,3 +203,40 @@ impl TryFrom<&DirectUrl> for Url {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{ArchiveInfo, DirectUrl, HashDigest};
+
+    #[test]
+    fn archive_hashes() {
+        let digest = HashDigest::from_str(
+            "sha256:75909db2664838d015e3d9139004ee16711748a52c8f336b52882266540215d8",
+        )
+        .unwrap();
+        let direct_url = DirectUrl::ArchiveUrl {
+            url: "https://files.pythonhosted.org/packages/b8/8b/31273bf66016be6ad22bb7345c37ff350276cfd46e389a0c2ac5da9d9073/wheel-0.41.2-py3-none-any.whl".to_string(),
+            archive_info: ArchiveInfo::from_hashes(&[]),
+            subdirectory: None,
+        }
+        .with_hashes(std::slice::from_ref(&digest));
+
+        let DirectUrl::ArchiveUrl { archive_info, .. } = direct_url else {
+            panic!("expected an archive URL");
+        };
+        assert_eq!(
+            archive_info.hash.as_deref(),
+            Some("sha256=75909db2664838d015e3d9139004ee16711748a52c8f336b52882266540215d8")
+        );
+        assert_eq!(archive_info.digests(), vec![digest]);
+
+        // The deprecated `hash` key alone should suffice.
+        let archive_info = ArchiveInfo {
+            hashes: None,
+            ..archive_info
+        };
+        assert_eq!(archive_info.digests().len(), 1);
+    }
+}
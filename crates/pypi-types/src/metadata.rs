@@ -1,5 +1,6 @@
 //! Derived from `pypi_types_crate`.
 
+use std::collections::BTreeSet;
 use std::str::FromStr;
 
 use indexmap::IndexMap;
@@ -242,6 +243,89 @@ impl Metadata23 {
             provides_extras,
         })
     }
+
+    /// Expand the given extras to include those enabled transitively through requirements on the
+    /// package itself (e.g., `black[colorama,d] ; extra == 'all'`).
+    ///
+    /// Markers that reference the environment are treated as true.
+    pub fn expand_extras(&self, extras: &[ExtraName]) -> Vec<ExtraName> {
+        let mut extras = extras.to_vec();
+        loop {
+            let mut changed = false;
+            for requirement in &self.requires_dist {
+                if requirement.name != self.name {
+                    continue;
+                }
+                let enabled = requirement.marker.as_ref().map_or(true, |marker| {
+                    marker.evaluate_optional_environment(None, &extras)
+                });
+                if !enabled {
+                    continue;
+                }
+                for extra in &requirement.extras {
+                    if !extras.contains(extra) {
+                        extras.push(extra.clone());
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return extras;
+            }
+        }
+    }
+}
+
+/// The differences between the dependency metadata of two distributions of the same package
+/// version, e.g., a wheel and the source distribution from which it was (ostensibly) built.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataDiff {
+    /// The `Requires-Python` of each distribution, if they differ.
+    pub requires_python: Option<(Option<VersionSpecifiers>, Option<VersionSpecifiers>)>,
+    /// The `Requires-Dist` entries that are only present in the first distribution.
+    pub requires_dist_removed: Vec<String>,
+    /// The `Requires-Dist` entries that are only present in the second distribution.
+    pub requires_dist_added: Vec<String>,
+}
+
+impl MetadataDiff {
+    /// Compare the dependency metadata of two distributions.
+    ///
+    /// `Requires-Dist` entries are compared in their normalized form, ignoring order.
+    pub fn new(first: &Metadata23, second: &Metadata23) -> Self {
+        let requires_python = if first.requires_python == second.requires_python {
+            None
+        } else {
+            Some((
+                first.requires_python.clone(),
+                second.requires_python.clone(),
+            ))
+        };
+
+        let first = first
+            .requires_dist
+            .iter()
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>();
+        let second = second
+            .requires_dist
+            .iter()
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>();
+
+        Self {
+            requires_python,
+            requires_dist_removed: first.difference(&second).cloned().collect(),
+            requires_dist_added: second.difference(&first).cloned().collect(),
+        }
+    }
+
+    /// Returns `true` if the dependency metadata of the two distributions is equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.requires_python.is_none()
+            && self.requires_dist_removed.is_empty()
+            && self.requires_dist_added.is_empty()
+    }
 }
 
 /// A `pyproject.toml` as specified in PEP 517.
@@ -358,11 +442,11 @@ mod tests {
     use std::str::FromStr;
 
     use pep440_rs::Version;
-    use uv_normalize::PackageName;
+    use uv_normalize::{ExtraName, PackageName};
 
     use crate::MetadataError;
 
-    use super::Metadata23;
+    use super::{Metadata23, MetadataDiff};
 
     #[test]
     fn test_parse_metadata() {
@@ -506,4 +590,53 @@ mod tests {
         );
         assert_eq!(meta.provides_extras, vec!["dotenv".parse().unwrap()]);
     }
+
+    #[test]
+    fn test_expand_extras() {
+        let meta = Metadata23::parse_metadata(
+            b"Metadata-Version: 2.1\nName: foo\nVersion: 1.0\nRequires-Dist: anyio\nRequires-Dist: sphinx ; extra == 'docs'\nRequires-Dist: pytest ; extra == 'test'\nRequires-Dist: foo[docs,test] ; extra == 'all'\nRequires-Dist: foo[all] ; extra == 'dev'",
+        )
+        .unwrap();
+        let extra = |name: &str| ExtraName::from_str(name).unwrap();
+
+        assert_eq!(
+            meta.expand_extras(&[extra("dev")]),
+            vec![extra("dev"), extra("all"), extra("docs"), extra("test")]
+        );
+        assert_eq!(meta.expand_extras(&[extra("docs")]), vec![extra("docs")]);
+        assert!(meta.expand_extras(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_metadata_diff() {
+        let wheel = Metadata23::parse_metadata(
+            b"Metadata-Version: 2.1\nName: foo\nVersion: 1.0\nRequires-Python: >=3.8\nRequires-Dist: bar>=1\nRequires-Dist: baz ; extra == 'test'",
+        )
+        .unwrap();
+
+        // The same requirements, in a different order and format, are equivalent.
+        let sdist = Metadata23::parse_metadata(
+            b"Metadata-Version: 2.1\nName: foo\nVersion: 1.0\nRequires-Python: >=3.8\nRequires-Dist: baz; extra == \"test\"\nRequires-Dist: bar >= 1",
+        )
+        .unwrap();
+        assert!(MetadataDiff::new(&wheel, &sdist).is_empty());
+
+        let sdist = Metadata23::parse_metadata(
+            b"Metadata-Version: 2.1\nName: foo\nVersion: 1.0\nRequires-Python: >=3.7\nRequires-Dist: bar>=1\nRequires-Dist: qux",
+        )
+        .unwrap();
+        let diff = MetadataDiff::new(&wheel, &sdist);
+        assert_eq!(
+            diff.requires_python,
+            Some((
+                Some(">=3.8".parse().unwrap()),
+                Some(">=3.7".parse().unwrap())
+            ))
+        );
+        assert_eq!(
+            diff.requires_dist_removed,
+            vec!["baz ; extra == 'test'".to_string()]
+        );
+        assert_eq!(diff.requires_dist_added, vec!["qux".to_string()]);
+    }
 }
@@ -10,11 +10,42 @@ use crate::lenient_requirement::LenientVersionSpecifiers;
 /// A collection of "files" from `PyPI`'s JSON API for a single package.
 #[derive(Debug, Clone, Deserialize)]
 pub struct SimpleJson {
+    /// The response metadata, including the API version.
+    #[serde(default)]
+    pub meta: Option<SimpleJsonMeta>,
     /// The list of [`File`]s available for download sorted by filename.
     #[serde(deserialize_with = "sorted_simple_json_files")]
     pub files: Vec<File>,
 }
 
+/// The projects available on an index, from the root of `PyPI`'s JSON API.
+///
+/// See: <https://peps.python.org/pep-0691/#project-list>
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimpleProjectsJson {
+    /// The response metadata, including the API version.
+    #[serde(default)]
+    pub meta: Option<SimpleJsonMeta>,
+    pub projects: Vec<SimpleProject>,
+}
+
+/// The `meta` key of a response from `PyPI`'s JSON API.
+///
+/// See: <https://peps.python.org/pep-0691/#json-serialization>
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimpleJsonMeta {
+    /// The version of the API (e.g., `1.1`).
+    #[serde(rename = "api-version")]
+    pub api_version: Option<String>,
+}
+
+/// A project listed at the root of `PyPI`'s JSON API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimpleProject {
+    /// The name of the project, which may not be normalized.
+    pub name: String,
+}
+
 /// Deserializes a sequence of "simple" files from `PyPI` and ensures that they
 /// are sorted in a stable order.
 fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>, D::Error> {
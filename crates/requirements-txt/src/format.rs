@@ -0,0 +1,416 @@
+//! Canonical formatting for `requirements.txt` files.
+//!
+//! Formatting never changes the meaning of a file: every requirement, option, and hash is
+//! preserved. Within those bounds, the formatter:
+//!
+//!  * normalizes package names per PEP 503 (e.g., `Flask_SQLAlchemy` to `flask-sqlalchemy`),
+//!  * sorts requirements by name, after any options (e.g., `-r`, `-e`, `--index-url`),
+//!  * merges duplicate requirements, combining their hashes, and
+//!  * writes hashes one per continuation line, with a four-space indent.
+//!
+//! The file's line endings are preserved: if its first line ends in `\r\n`, so does every line
+//! of the formatted output.
+//!
+//! Comments are carried along with the requirement they annotate: unindented comments attach to
+//! the requirement that follows them, while indented comments (such as the `# via` annotations
+//! written by `uv pip compile`) attach to the requirement that precedes them. Comments that
+//! precede the first statement are kept as a header.
+
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::{RequirementsTxtRequirement, RequirementsTxtRequirementError};
+
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error("Couldn't parse requirement at line {line}")]
+    Requirement {
+        line: usize,
+        #[source]
+        err: RequirementsTxtRequirementError,
+    },
+    #[error(
+        "Unsupported per-requirement option `{option}` at line {line} (only `--hash` is supported)"
+    )]
+    UnsupportedOption { option: String, line: usize },
+    #[error("Missing value for `--hash` at line {line}")]
+    MissingHash { line: usize },
+}
+
+/// A requirement, along with its hashes and any attached comments.
+#[derive(Debug)]
+struct Entry {
+    /// The requirement, with a normalized package name.
+    requirement: String,
+    /// The sort key: named requirements by name, followed by unnamed requirements.
+    key: (bool, String),
+    hashes: Vec<String>,
+    /// The comment at the end of the requirement, if any (e.g., `# pinned for CVE-2024-1234`).
+    comment: Option<String>,
+    /// Unindented comment lines that precede the requirement.
+    leading: Vec<String>,
+    /// Indented comment lines that follow the requirement.
+    trailing: Vec<String>,
+}
+
+/// Format the contents of a `requirements.txt` file.
+///
+/// The `working_dir` is used to resolve relative paths when validating requirements.
+pub fn format_requirements_txt(
+    content: &str,
+    working_dir: impl AsRef<Path>,
+) -> Result<String, FormatError> {
+    // Preserve the line endings of the file, as determined by its first line.
+    let newline = match content.find('\n') {
+        Some(index) if content[..index].ends_with('\r') => "\r\n",
+        _ => "\n",
+    };
+
+    let mut header: Vec<String> = Vec::new();
+    let mut options: Vec<String> = Vec::new();
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut seen_statement = false;
+    let mut last_was_requirement = false;
+
+    for (line, text) in logical_lines(content) {
+        let (statement, comment) = split_comment(&text);
+        let statement = statement.trim();
+
+        // An empty or comment-only line.
+        if statement.is_empty() {
+            match comment {
+                None if !seen_statement => header.push(String::new()),
+                None => {}
+                Some(comment) if !seen_statement => header.push(comment.to_string()),
+                Some(comment)
+                    if last_was_requirement
+                        && pending.is_empty()
+                        && text.starts_with(char::is_whitespace) =>
+                {
+                    entries
+                        .last_mut()
+                        .expect("a requirement precedes the comment")
+                        .trailing
+                        .push(comment.to_string());
+                }
+                Some(comment) => {
+                    pending.push(comment.to_string());
+                    last_was_requirement = false;
+                }
+            }
+            continue;
+        }
+
+        seen_statement = true;
+
+        // An option, such as `-r`, `-e`, or `--index-url`, which is preserved verbatim.
+        if statement.starts_with('-') {
+            options.append(&mut pending);
+            options.push(with_comment(
+                &statement.split_whitespace().collect::<Vec<_>>().join(" "),
+                comment,
+            ));
+            last_was_requirement = false;
+            continue;
+        }
+
+        let mut entry = parse_requirement(statement, line, working_dir.as_ref())?;
+        entry.comment = comment.map(ToString::to_string);
+
+        // Merge any duplicate requirements. The merged entry is moved to the end, such that any
+        // indented comments that follow attach to it.
+        if let Some(index) = entries
+            .iter()
+            .position(|existing| existing.requirement == entry.requirement)
+        {
+            let mut existing = entries.remove(index);
+            existing.hashes.extend(entry.hashes);
+            existing.comment = existing.comment.or(entry.comment);
+            for comment in pending.drain(..) {
+                if !existing.leading.contains(&comment) {
+                    existing.leading.push(comment);
+                }
+            }
+            entries.push(existing);
+        } else {
+            entry.leading = std::mem::take(&mut pending);
+            entries.push(entry);
+        }
+        last_was_requirement = true;
+    }
+
+    entries.sort_by(|a, b| {
+        a.key
+            .cmp(&b.key)
+            .then_with(|| a.requirement.cmp(&b.requirement))
+    });
+
+    let mut output = String::new();
+    for line in &header {
+        output.push_str(line);
+        output.push_str(newline);
+    }
+    for option in &options {
+        output.push_str(option);
+        output.push_str(newline);
+    }
+    if !options.is_empty() && !entries.is_empty() {
+        output.push_str(newline);
+    }
+    for mut entry in entries {
+        for comment in &entry.leading {
+            output.push_str(comment);
+            output.push_str(newline);
+        }
+
+        entry.hashes.sort();
+        entry.hashes.dedup();
+        if entry.hashes.is_empty() {
+            output.push_str(&with_comment(&entry.requirement, entry.comment.as_deref()));
+        } else {
+            output.push_str(&entry.requirement);
+            for hash in &entry.hashes {
+                output.push_str(" \\");
+                output.push_str(newline);
+                output.push_str("    --hash=");
+                output.push_str(hash);
+            }
+            if let Some(comment) = &entry.comment {
+                output.push_str("  ");
+                output.push_str(comment);
+            }
+        }
+        output.push_str(newline);
+
+        for comment in &entry.trailing {
+            output.push_str("    ");
+            output.push_str(comment);
+            output.push_str(newline);
+        }
+    }
+    for comment in &pending {
+        output.push_str(comment);
+        output.push_str(newline);
+    }
+
+    Ok(output)
+}
+
+/// Split the content into logical lines, joining lines that end in an escaped newline.
+///
+/// Returns the (one-based) line number at which each logical line starts.
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (index, line) in content.lines().enumerate() {
+        let (start, mut text) = current.take().unwrap_or((index + 1, String::new()));
+        // Comments can't be continued onto the next line.
+        let is_comment = text.trim().is_empty() && line.trim_start().starts_with('#');
+        if let Some(continued) = line.strip_suffix('\\').filter(|_| !is_comment) {
+            text.push_str(continued);
+            text.push(' ');
+            current = Some((start, text));
+        } else {
+            text.push_str(line);
+            lines.push((start, text));
+        }
+    }
+    if let Some(line) = current {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Split a line into its statement and its comment, if any.
+///
+/// As in `pip`, a `#` only starts a comment at the start of a line, or after whitespace.
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut previous = None;
+    for (index, char) in line.char_indices() {
+        if char == '#' && previous.map_or(true, char::is_whitespace) {
+            return (&line[..index], Some(line[index..].trim_end()));
+        }
+        previous = Some(char);
+    }
+    (line, None)
+}
+
+/// Append a comment to a statement, separated by two spaces.
+fn with_comment(statement: &str, comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => format!("{statement}  {comment}"),
+        None => statement.to_string(),
+    }
+}
+
+/// Parse a requirement and its trailing `--hash` options into an [`Entry`], with a normalized
+/// package name.
+fn parse_requirement(
+    statement: &str,
+    line: usize,
+    working_dir: &Path,
+) -> Result<Entry, FormatError> {
+    // The requirement ends at the first option (i.e., `--hash`).
+    let end = statement
+        .char_indices()
+        .find(|(index, char)| {
+            char.is_whitespace() && statement[*index..].trim_start().starts_with("--")
+        })
+        .map_or(statement.len(), |(index, _)| index);
+    let (requirement, rest) = statement.split_at(end);
+
+    let mut hashes = Vec::new();
+    let mut tokens = rest.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if let Some(hash) = token.strip_prefix("--hash=") {
+            hashes.push(hash.to_string());
+        } else if token == "--hash" {
+            let hash = tokens.next().ok_or(FormatError::MissingHash { line })?;
+            hashes.push(hash.to_string());
+        } else {
+            return Err(FormatError::UnsupportedOption {
+                option: token
+                    .split_once('=')
+                    .map_or(token, |(option, _)| option)
+                    .to_string(),
+                line,
+            });
+        }
+    }
+
+    let requirement = requirement.trim();
+    let (requirement, key) = match RequirementsTxtRequirement::parse(requirement, working_dir)
+        .map_err(|err| FormatError::Requirement { line, err })?
+    {
+        RequirementsTxtRequirement::Named(parsed) => {
+            // Replace the package name as written with its normalized form, preserving the
+            // remainder of the requirement verbatim (e.g., to retain environment variables in
+            // URLs).
+            let end = requirement
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(requirement.len());
+            let name = parsed.name.to_string();
+            (format!("{name}{}", &requirement[end..]), (false, name))
+        }
+        RequirementsTxtRequirement::Unnamed(_) => {
+            (requirement.to_string(), (true, requirement.to_string()))
+        }
+    };
+
+    Ok(Entry {
+        requirement,
+        key,
+        hashes,
+        comment: None,
+        leading: Vec::new(),
+        trailing: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::format_requirements_txt;
+
+    fn format(content: &str) -> String {
+        format_requirements_txt(content, std::env::current_dir().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn normalize_and_sort() {
+        let content = indoc! {r"
+            Werkzeug>=3
+            Flask_SQLAlchemy==3.1.1 ; python_version >= '3.8'
+            -r base.txt
+            --index-url https://pypi.org/simple
+            # Testing
+            PyTest
+        "};
+        insta::assert_snapshot!(format(content), @r###"
+        -r base.txt
+        --index-url https://pypi.org/simple
+
+        flask-sqlalchemy==3.1.1 ; python_version >= '3.8'
+        # Testing
+        pytest
+        werkzeug>=3
+        "###);
+    }
+
+    #[test]
+    fn merge_duplicates() {
+        let content = indoc! {r"
+            anyio==4.3.0 --hash=sha256:bbb
+            idna==3.6
+            ANYIO==4.3.0 \
+                --hash=sha256:aaa \
+                --hash=sha256:bbb
+            idna==3.6  # pinned
+        "};
+        insta::assert_snapshot!(format(content), @r###"
+        anyio==4.3.0 \
+            --hash=sha256:aaa \
+            --hash=sha256:bbb
+        idna==3.6  # pinned
+        "###);
+    }
+
+    #[test]
+    fn preserve_annotations() {
+        let content = indoc! {r"
+            # This file was autogenerated by uv via the following command:
+            #    uv pip compile requirements.in
+            sniffio==1.3.1
+                # via anyio
+            anyio==4.3.0 --hash sha256:aaa # web
+                # via -r requirements.in
+        "};
+        insta::assert_snapshot!(format(content), @r###"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in
+        anyio==4.3.0 \
+            --hash=sha256:aaa  # web
+            # via -r requirements.in
+        sniffio==1.3.1
+            # via anyio
+        "###);
+    }
+
+    #[test]
+    fn idempotent() {
+        let content = indoc! {r"
+            # Header
+
+            -e ./editable
+            b==1 \
+                --hash=sha256:aaa
+            a[extra]>=1  # comment
+                # via c
+            # Footer
+        "};
+        let formatted = format(content);
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn preserve_crlf() {
+        let content = "b==1\r\na==1 --hash=sha256:aaa  # pinned\r\n    # via c\r\n";
+        assert_eq!(
+            format(content),
+            "a==1 \\\r\n    --hash=sha256:aaa  # pinned\r\n    # via c\r\nb==1\r\n"
+        );
+    }
+
+    #[test]
+    fn unsupported_option() {
+        let err = format_requirements_txt(
+            "flask==3.0.0 --global-option=foo\n",
+            std::env::current_dir().unwrap(),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Unsupported per-requirement option `--global-option` at line 1 (only `--hash` is supported)");
+    }
+}
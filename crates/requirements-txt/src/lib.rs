@@ -59,8 +59,10 @@ use uv_fs::{normalize_url_path, Simplified};
 use uv_normalize::ExtraName;
 use uv_warnings::warn_user;
 
+pub use crate::format::{format_requirements_txt, FormatError};
 pub use crate::requirement::{RequirementsTxtRequirement, RequirementsTxtRequirementError};
 
+mod format;
 mod requirement;
 
 /// We emit one of those for each requirements.txt entry
@@ -461,6 +463,7 @@ impl RequirementsTxt {
                 {
                     return Err(RequirementsTxtFileError {
                         file: requirements_txt.to_path_buf(),
+                        location: None,
                         error: RequirementsTxtParserError::IO(io::Error::new(
                             io::ErrorKind::InvalidInput,
                             "Remote file not supported without `http` feature",
@@ -474,6 +477,7 @@ impl RequirementsTxt {
                     if client_builder.is_offline() {
                         return Err(RequirementsTxtFileError {
                             file: requirements_txt.to_path_buf(),
+                            location: None,
                             error: RequirementsTxtParserError::IO(io::Error::new(
                                 io::ErrorKind::InvalidInput,
                                 format!("Network connectivity is disabled, but a remote requirements file was requested: {}", requirements_txt.display()),
@@ -491,6 +495,7 @@ impl RequirementsTxt {
             }
             .map_err(|err| RequirementsTxtFileError {
                 file: requirements_txt.to_path_buf(),
+                location: None,
                 error: err,
             })?;
 
@@ -505,6 +510,9 @@ impl RequirementsTxt {
         .await
         .map_err(|err| RequirementsTxtFileError {
             file: requirements_txt.to_path_buf(),
+            location: err
+                .start()
+                .map(|start| calculate_row_column(&content, start)),
             error: err,
         })?;
         if data == Self::default() {
@@ -540,13 +548,7 @@ impl RequirementsTxt {
                     start,
                     end,
                 } => {
-                    let filename = expand_env_vars(&filename);
-                    let sub_file =
-                        if filename.starts_with("http://") || filename.starts_with("https://") {
-                            PathBuf::from(filename.as_ref())
-                        } else {
-                            requirements_dir.join(filename.as_ref())
-                        };
+                    let sub_file = sub_file(&filename, requirements_dir);
                     let sub_requirements =
                         Box::pin(Self::parse(&sub_file, working_dir, client_builder))
                             .await
@@ -579,13 +581,7 @@ impl RequirementsTxt {
                     start,
                     end,
                 } => {
-                    let filename = expand_env_vars(&filename);
-                    let sub_file =
-                        if filename.starts_with("http://") || filename.starts_with("https://") {
-                            PathBuf::from(filename.as_ref())
-                        } else {
-                            requirements_dir.join(filename.as_ref())
-                        };
+                    let sub_file = sub_file(&filename, requirements_dir);
                     let sub_constraints =
                         Box::pin(Self::parse(&sub_file, working_dir, client_builder))
                             .await
@@ -652,6 +648,30 @@ impl RequirementsTxt {
         Ok(data)
     }
 
+    /// Return the `-r` and `-c` files referenced directly by the given `requirements.txt`
+    /// contents, without reading them.
+    ///
+    /// Relative paths are resolved against the directory of `requirements_txt`, as in
+    /// [`RequirementsTxt::parse`]; remote files are returned as-is.
+    pub fn includes(
+        content: &str,
+        working_dir: &Path,
+        requirements_txt: &Path,
+    ) -> Result<Vec<PathBuf>, RequirementsTxtParserError> {
+        let requirements_dir = requirements_txt.parent().unwrap_or(working_dir);
+        let mut s = Scanner::new(content);
+
+        let mut includes = Vec::new();
+        while let Some(statement) = parse_entry(&mut s, content, working_dir, requirements_txt)? {
+            if let RequirementsTxtStatement::Requirements { filename, .. }
+            | RequirementsTxtStatement::Constraint { filename, .. } = statement
+            {
+                includes.push(sub_file(&filename, requirements_dir));
+            }
+        }
+        Ok(includes)
+    }
+
     /// Merge the data from a nested `requirements` file (`other`) into this one.
     pub fn update_from(&mut self, other: Self) {
         let RequirementsTxt {
@@ -679,6 +699,16 @@ impl RequirementsTxt {
     }
 }
 
+/// Resolve the path or URL of a `-r` or `-c` file against the directory of the including file.
+fn sub_file(filename: &str, requirements_dir: &Path) -> PathBuf {
+    let filename = expand_env_vars(filename);
+    if filename.starts_with("http://") || filename.starts_with("https://") {
+        PathBuf::from(filename.as_ref())
+    } else {
+        requirements_dir.join(filename.as_ref())
+    }
+}
+
 /// Parse a single entry, that is a requirement, an inclusion or a comment line
 ///
 /// Consumes all preceding trivia (whitespace and comments). If it returns None, we've reached
@@ -759,26 +789,26 @@ fn parse_entry(
         RequirementsTxtStatement::FindLinks(path_or_url)
     } else if s.eat_if("--no-binary") {
         let given = parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
-        let specifier = PackageNameSpecifier::from_str(given).map_err(|err| {
+        let specifiers = parse_package_name_specifiers(given).map_err(|(specifier, err)| {
             RequirementsTxtParserError::NoBinary {
                 source: err,
-                specifier: given.to_string(),
+                specifier,
                 start,
                 end: s.cursor(),
             }
         })?;
-        RequirementsTxtStatement::NoBinary(NoBinary::from_arg(specifier))
+        RequirementsTxtStatement::NoBinary(NoBinary::from_args(specifiers))
     } else if s.eat_if("--only-binary") {
         let given = parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
-        let specifier = PackageNameSpecifier::from_str(given).map_err(|err| {
-            RequirementsTxtParserError::NoBinary {
+        let specifiers = parse_package_name_specifiers(given).map_err(|(specifier, err)| {
+            RequirementsTxtParserError::OnlyBinary {
                 source: err,
-                specifier: given.to_string(),
+                specifier,
                 start,
                 end: s.cursor(),
             }
         })?;
-        RequirementsTxtStatement::OnlyBinary(NoBuild::from_arg(specifier))
+        RequirementsTxtStatement::OnlyBinary(NoBuild::from_args(specifiers, false))
     } else if s.at(char::is_ascii_alphanumeric) || s.at(|char| matches!(char, '.' | '/' | '$')) {
         let source = if requirements_txt == Path::new("-") {
             None
@@ -948,33 +978,51 @@ fn parse_requirement_and_hashes(
     Ok((requirement, hashes))
 }
 
-/// Parse `--hash=... --hash ...` after a requirement
+/// Parse `--hash=... --hash ...` after a requirement, which may span multiple lines via escaped
+/// newlines.
+///
+/// `--hash` is the only supported per-requirement option; any other option (e.g.,
+/// `--global-option`) is rejected with the location at which it appears.
 fn parse_hashes(content: &str, s: &mut Scanner) -> Result<Vec<String>, RequirementsTxtParserError> {
     let mut hashes = Vec::new();
-    if s.eat_while("--hash").is_empty() {
-        let (line, column) = calculate_row_column(content, s.cursor());
-        return Err(RequirementsTxtParserError::Parser {
-            message: format!(
-                "Expected '--hash', found '{:?}'",
-                s.eat_while(|c: char| !c.is_whitespace())
-            ),
-            line,
-            column,
-        });
-    }
-    let hash = parse_value(content, s, |c: char| !c.is_whitespace())?;
-    hashes.push(hash.to_string());
     loop {
-        eat_wrappable_whitespace(s);
         if !s.eat_if("--hash") {
+            if s.at("--") {
+                let (line, column) = calculate_row_column(content, s.cursor());
+                let option = s.eat_while(|c: char| !c.is_whitespace() && c != '=');
+                return Err(RequirementsTxtParserError::Parser {
+                    message: format!(
+                        "Unsupported per-requirement option `{option}` (only `--hash` is supported)"
+                    ),
+                    line,
+                    column,
+                });
+            }
             break;
         }
         let hash = parse_value(content, s, |c: char| !c.is_whitespace())?;
         hashes.push(hash.to_string());
+        eat_wrappable_whitespace(s);
     }
     Ok(hashes)
 }
 
+/// Parse the comma-separated package names (or `:all:` and `:none:`) accepted by `--no-binary`
+/// and `--only-binary`, e.g., `--no-binary foo,bar`.
+///
+/// On failure, returns the offending specifier alongside the error.
+fn parse_package_name_specifiers(
+    given: &str,
+) -> Result<Vec<PackageNameSpecifier>, (String, uv_normalize::InvalidNameError)> {
+    given
+        .split(',')
+        .map(str::trim)
+        .map(|specifier| {
+            PackageNameSpecifier::from_str(specifier).map_err(|err| (specifier.to_string(), err))
+        })
+        .collect()
+}
+
 /// In `-<key>=<value>` or `-<key> value`, this parses the part after the key
 fn parse_value<'a, T>(
     content: &str,
@@ -1026,6 +1074,8 @@ async fn read_url_to_string(
 #[derive(Debug)]
 pub struct RequirementsTxtFileError {
     file: PathBuf,
+    /// The line and column at which the error starts, if known.
+    location: Option<(usize, usize)>,
     error: RequirementsTxtParserError,
 }
 
@@ -1095,6 +1145,21 @@ pub enum RequirementsTxtParserError {
 }
 
 impl RequirementsTxtParserError {
+    /// The byte offset at which the error starts, if the error refers to a location in the file.
+    fn start(&self) -> Option<usize> {
+        match self {
+            Self::Url { start, .. }
+            | Self::NoBinary { start, .. }
+            | Self::OnlyBinary { start, .. }
+            | Self::UnnamedConstraint { start, .. }
+            | Self::UnsupportedRequirement { start, .. }
+            | Self::Pep508 { start, .. }
+            | Self::ParsedUrl { start, .. }
+            | Self::Subfile { start, .. } => Some(*start),
+            _ => None,
+        }
+    }
+
     /// Add a fixed offset to the location of the error.
     #[must_use]
     fn with_offset(self, offset: usize) -> Self {
@@ -1263,6 +1328,18 @@ impl std::error::Error for RequirementsTxtParserError {
     }
 }
 
+impl RequirementsTxtFileError {
+    /// Format the location of an error starting at the given byte offset, preferring the line and
+    /// column if known.
+    fn position(&self, start: usize) -> String {
+        if let Some((line, column)) = self.location {
+            format!("line {line}, column {column}")
+        } else {
+            format!("position {start}")
+        }
+    }
+}
+
 impl Display for RequirementsTxtFileError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.error {
@@ -1270,8 +1347,9 @@ impl Display for RequirementsTxtFileError {
             RequirementsTxtParserError::Url { url, start, .. } => {
                 write!(
                     f,
-                    "Invalid URL in `{}` at position {start}: `{url}`",
+                    "Invalid URL in `{}` at {}: `{url}`",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::VerbatimUrl { url, .. } => {
@@ -1298,25 +1376,32 @@ impl Display for RequirementsTxtFileError {
                     self.file.user_display(),
                 )
             }
-            RequirementsTxtParserError::NoBinary { specifier, .. } => {
+            RequirementsTxtParserError::NoBinary {
+                specifier, start, ..
+            } => {
                 write!(
                     f,
-                    "Invalid specifier for `--no-binary` in `{}`: {specifier}",
+                    "Invalid specifier for `--no-binary` in `{}` at {}: {specifier}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
-            RequirementsTxtParserError::OnlyBinary { specifier, .. } => {
+            RequirementsTxtParserError::OnlyBinary {
+                specifier, start, ..
+            } => {
                 write!(
                     f,
-                    "Invalid specifier for `--only-binary` in `{}`: {specifier}",
+                    "Invalid specifier for `--only-binary` in `{}` at {}: {specifier}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
-            RequirementsTxtParserError::UnnamedConstraint { .. } => {
+            RequirementsTxtParserError::UnnamedConstraint { start, .. } => {
                 write!(
                     f,
-                    "Unnamed requirements are not allowed as constraints in `{}`",
+                    "Unnamed requirements are not allowed as constraints in `{}` at {}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::Parser {
@@ -1333,29 +1418,33 @@ impl Display for RequirementsTxtFileError {
             RequirementsTxtParserError::UnsupportedRequirement { start, .. } => {
                 write!(
                     f,
-                    "Unsupported requirement in {} at position {start}",
+                    "Unsupported requirement in {} at {}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::Pep508 { start, .. } => {
                 write!(
                     f,
-                    "Couldn't parse requirement in `{}` at position {start}",
+                    "Couldn't parse requirement in `{}` at {}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::ParsedUrl { start, .. } => {
                 write!(
                     f,
-                    "Couldn't parse URL in `{}` at position {start}",
+                    "Couldn't parse URL in `{}` at {}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::Subfile { start, .. } => {
                 write!(
                     f,
-                    "Error parsing included file in `{}` at position {start}",
+                    "Error parsing included file in `{}` at {}",
                     self.file.user_display(),
+                    self.position(*start),
                 )
             }
             RequirementsTxtParserError::NonUnicodeUrl { url } => {
@@ -1636,7 +1725,7 @@ mod test {
             filters => filters,
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Error parsing included file in `<REQUIREMENTS_TXT>` at position 0
+            Error parsing included file in `<REQUIREMENTS_TXT>` at line 1, column 1
             failed to read from file `<MISSING_TXT>`
             "###);
         });
@@ -1667,7 +1756,7 @@ mod test {
             filters => filters
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at position 0
+            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at line 1, column 1
             Expected an alphanumeric character starting the extra name, found 'ö'
             numpy[ö]==1.29
                   ^
@@ -1700,7 +1789,7 @@ mod test {
             filters => filters
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at position 0
+            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at line 1, column 1
             empty host
             numpy @ https:///
                     ^^^^^^^^^
@@ -1761,7 +1850,7 @@ mod test {
             filters => filters
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at position 6
+            Couldn't parse requirement in `<REQUIREMENTS_TXT>` at line 1, column 7
             Expected either alphanumerical character (starting the extra name) or ']' (ending the extras section), found ','
             black[,abcdef]
                   ^
@@ -1794,7 +1883,7 @@ mod test {
             filters => filters
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Invalid URL in `<REQUIREMENTS_TXT>` at position 0: `123`
+            Invalid URL in `<REQUIREMENTS_TXT>` at line 1, column 1: `123`
             relative URL without a base
             "###);
         });
@@ -1825,7 +1914,7 @@ mod test {
             filters => filters
         }, {
             insta::assert_snapshot!(errors, @r###"
-            Invalid URL in `<REQUIREMENTS_TXT>` at position 0: `https:////`
+            Invalid URL in `<REQUIREMENTS_TXT>` at line 1, column 1: `https:////`
             empty host
             "###);
         });
@@ -2007,6 +2096,176 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn no_binary_list() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            --no-binary flask, jinja2
+            --only-binary=:all:
+        "})?;
+
+        let requirements = RequirementsTxt::parse(
+            requirements_txt.path(),
+            temp_dir.path(),
+            &BaseClientBuilder::new(),
+        )
+        .await
+        .unwrap();
+
+        insta::assert_debug_snapshot!((requirements.no_binary, requirements.only_binary), @r###"
+        (
+            Packages(
+                [
+                    PackageName(
+                        "flask",
+                    ),
+                    PackageName(
+                        "jinja2",
+                    ),
+                ],
+            ),
+            All,
+        )
+        "###);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_only_binary() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            flask
+            --only-binary flask,-jinja2
+        "})?;
+
+        let error = RequirementsTxt::parse(
+            requirements_txt.path(),
+            temp_dir.path(),
+            &BaseClientBuilder::new(),
+        )
+        .await
+        .unwrap_err();
+        let errors = anyhow::Error::new(error).chain().join("\n");
+
+        let requirement_txt = regex::escape(&requirements_txt.path().user_display().to_string());
+        let filters = vec![(requirement_txt.as_str(), "<REQUIREMENTS_TXT>")];
+        insta::with_settings!({
+            filters => filters
+        }, {
+            insta::assert_snapshot!(errors, @r###"
+            Invalid specifier for `--only-binary` in `<REQUIREMENTS_TXT>` at line 2, column 1: -jinja2
+            Not a valid package or extra name: "-jinja2". Names must start and end with a letter or digit and may only contain -, _, ., and alphanumeric characters.
+            "###);
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unsupported_per_requirement_option() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {r"
+            flask==3.0.0 \
+                --hash=sha256:3da4a7fa6c2d8b4e0fd72a2ad13b41b9abbf7f7d7b5a5d3f2cc7b3f1f3d2c1b0 \
+                --global-option=--no-user-cfg
+        "})?;
+
+        let error = RequirementsTxt::parse(
+            requirements_txt.path(),
+            temp_dir.path(),
+            &BaseClientBuilder::new(),
+        )
+        .await
+        .unwrap_err();
+        let errors = anyhow::Error::new(error).chain().join("\n");
+
+        let requirement_txt = regex::escape(&requirements_txt.path().user_display().to_string());
+        let filters = vec![(requirement_txt.as_str(), "<REQUIREMENTS_TXT>")];
+        insta::with_settings!({
+            filters => filters
+        }, {
+            insta::assert_snapshot!(errors, @r###"
+            Unsupported per-requirement option `--global-option` (only `--hash` is supported) at <REQUIREMENTS_TXT>:3:5
+            "###);
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn constraint_markers_and_hashes() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            -c constraints.txt
+        "})?;
+
+        let constraints_txt = temp_dir.child("constraints.txt");
+        constraints_txt.write_str(indoc! {r#"
+            pytz==2023.3 ; python_version < "3.9" \
+                --hash=sha256:1d8ce29db189191fb55338ee6d0387d82ab59f3d00eac103412d64e0ebd0c588
+        "#})?;
+
+        let requirements = RequirementsTxt::parse(
+            requirements_txt.path(),
+            temp_dir.path(),
+            &BaseClientBuilder::new(),
+        )
+        .await
+        .unwrap();
+
+        insta::with_settings!({
+            filters => path_filters(&path_filter(temp_dir.path())),
+        }, {
+            insta::assert_debug_snapshot!(requirements.constraints, @r###"
+            [
+                Requirement {
+                    name: PackageName(
+                        "pytz",
+                    ),
+                    extras: [],
+                    version_or_url: Some(
+                        VersionSpecifier(
+                            VersionSpecifiers(
+                                [
+                                    VersionSpecifier {
+                                        operator: Equal,
+                                        version: "2023.3",
+                                    },
+                                ],
+                            ),
+                        ),
+                    ),
+                    marker: Some(
+                        Expression(
+                            Version {
+                                key: PythonVersion,
+                                specifier: VersionSpecifier {
+                                    operator: LessThan,
+                                    version: "3.9",
+                                },
+                            },
+                        ),
+                    ),
+                    origin: Some(
+                        File(
+                            "<REQUIREMENTS_DIR>/constraints.txt",
+                        ),
+                    ),
+                },
+            ]
+            "###);
+        });
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[cfg(not(windows))]
     async fn nested_editable() -> Result<()> {
@@ -2084,6 +2343,36 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn includes() -> Result<()> {
+        let content = indoc! {"
+            -r child.txt
+            --requirement=../sibling/requirements.txt
+            # -r commented.txt
+            anyio==4.0.0 \\
+                --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+            -c https://example.com/constraints.txt
+            --constraint constraints.txt
+        "};
+
+        let includes = RequirementsTxt::includes(
+            content,
+            Path::new("/work"),
+            Path::new("/project/requirements.txt"),
+        )?;
+        assert_eq!(
+            includes,
+            [
+                PathBuf::from("/project/child.txt"),
+                PathBuf::from("/project/../sibling/requirements.txt"),
+                PathBuf::from("https://example.com/constraints.txt"),
+                PathBuf::from("/project/constraints.txt"),
+            ]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn nested_conflicting_index_url() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -4,7 +4,8 @@ use thiserror::Error;
 
 use distribution_types::ParsedUrlError;
 use pep508_rs::{
-    Pep508Error, Pep508ErrorSource, RequirementOrigin, TracingReporter, UnnamedRequirement,
+    Pep508Error, Pep508ErrorSource, Reporter, RequirementOrigin, TracingReporter,
+    UnnamedRequirement,
 };
 
 /// A requirement specifier in a `requirements.txt` file.
@@ -44,9 +45,19 @@ impl RequirementsTxtRequirement {
     pub fn parse(
         input: &str,
         working_dir: impl AsRef<Path>,
+    ) -> Result<Self, RequirementsTxtRequirementError> {
+        Self::parse_reporter(input, working_dir, &mut TracingReporter)
+    }
+
+    /// Parse a requirement as seen in a `requirements.txt` file, with the given reporter for
+    /// marker warnings.
+    pub fn parse_reporter(
+        input: &str,
+        working_dir: impl AsRef<Path>,
+        reporter: &mut impl Reporter,
     ) -> Result<Self, RequirementsTxtRequirementError> {
         // Attempt to parse as a PEP 508-compliant requirement.
-        match pep508_rs::Requirement::parse(input, &working_dir) {
+        match pep508_rs::Requirement::parse_reporter(input, &working_dir, reporter) {
             Ok(requirement) => Ok(Self::Named(requirement)),
             Err(err) => match err.message {
                 Pep508ErrorSource::UnsupportedRequirement(_) => {
@@ -54,7 +65,7 @@ impl RequirementsTxtRequirement {
                     Ok(Self::Unnamed(UnnamedRequirement::parse(
                         input,
                         &working_dir,
-                        &mut TracingReporter,
+                        reporter,
                     )?))
                 }
                 _ => Err(RequirementsTxtRequirementError::Pep508(err)),
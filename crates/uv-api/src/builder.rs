@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio_util::sync::CancellationToken;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use uv_cache::Cache;
+use uv_client::{Connectivity, NetworkTimeouts, RegistryClientBuilder};
+use uv_configuration::{
+    Concurrency, ConfigSettings, HostOverride, HttpVersion, IndexRateLimit, IndexStrategy,
+    KeyringProviderType, NoBinary, NoBuild, TrustedHost,
+};
+use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_resolver::{InMemoryIndex, Options};
+use uv_types::InFlight;
+
+use crate::{Error, Reporter, Uv};
+
+/// A builder for a [`Uv`] instance.
+#[derive(Default)]
+pub struct UvBuilder {
+    cache_dir: Option<PathBuf>,
+    no_cache: bool,
+    python: Option<String>,
+    index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    native_tls: bool,
+    timeouts: NetworkTimeouts,
+    host_overrides: Vec<HostOverride>,
+    trusted_hosts: Vec<TrustedHost>,
+    rate_limits: Vec<IndexRateLimit>,
+    http_version: Option<HttpVersion>,
+    options: Options,
+    config_settings: ConfigSettings,
+    no_build: NoBuild,
+    no_binary: NoBinary,
+    link_mode: LinkMode,
+    concurrency: Concurrency,
+    reporter: Option<Arc<dyn Reporter>>,
+    cancellation: CancellationToken,
+}
+
+impl UvBuilder {
+    /// Create a new [`UvBuilder`] with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the cache at the given directory, rather than the default user cache directory.
+    #[must_use]
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Use a temporary cache directory, which is removed when the [`Uv`] instance is dropped.
+    #[must_use]
+    pub fn no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    /// The Python interpreter to use when creating virtual environments (e.g., `3.12` or a path
+    /// to an executable). Defaults to the first Python interpreter found on the system.
+    #[must_use]
+    pub fn python(mut self, python: impl Into<String>) -> Self {
+        self.python = Some(python.into());
+        self
+    }
+
+    /// The package indexes and `--find-links` locations to use.
+    #[must_use]
+    pub fn index_locations(mut self, index_locations: IndexLocations) -> Self {
+        self.index_locations = index_locations;
+        self
+    }
+
+    /// The strategy to use when resolving against multiple indexes.
+    #[must_use]
+    pub fn index_strategy(mut self, index_strategy: IndexStrategy) -> Self {
+        self.index_strategy = index_strategy;
+        self
+    }
+
+    /// The keyring provider to use for index authentication.
+    #[must_use]
+    pub fn keyring_provider(mut self, keyring_provider: KeyringProviderType) -> Self {
+        self.keyring_provider = keyring_provider;
+        self
+    }
+
+    /// Whether to allow network access.
+    #[must_use]
+    pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Whether to load TLS certificates from the platform's native certificate store.
+    #[must_use]
+    pub fn native_tls(mut self, native_tls: bool) -> Self {
+        self.native_tls = native_tls;
+        self
+    }
+
+    /// The connect, read, and overall timeouts for network requests.
+    #[must_use]
+    pub fn timeouts(mut self, timeouts: NetworkTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Connect to the given hosts at fixed addresses, rather than resolving them via DNS.
+    #[must_use]
+    pub fn host_overrides(mut self, host_overrides: Vec<HostOverride>) -> Self {
+        self.host_overrides = host_overrides;
+        self
+    }
+
+    /// Skip TLS certificate verification for the given hosts.
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
+    /// Cap the rate of requests sent to the given hosts.
+    #[must_use]
+    pub fn rate_limits(mut self, rate_limits: Vec<IndexRateLimit>) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Only use the given HTTP version, rather than negotiating it with each server.
+    #[must_use]
+    pub fn http_version(mut self, http_version: HttpVersion) -> Self {
+        self.http_version = Some(http_version);
+        self
+    }
+
+    /// The resolver [`Options`] (e.g., the resolution and pre-release strategies).
+    #[must_use]
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Settings to pass to PEP 517 build backends.
+    #[must_use]
+    pub fn config_settings(mut self, config_settings: ConfigSettings) -> Self {
+        self.config_settings = config_settings;
+        self
+    }
+
+    /// The packages for which source distributions should not be built.
+    #[must_use]
+    pub fn no_build(mut self, no_build: NoBuild) -> Self {
+        self.no_build = no_build;
+        self
+    }
+
+    /// The packages for which pre-built wheels should not be used.
+    #[must_use]
+    pub fn no_binary(mut self, no_binary: NoBinary) -> Self {
+        self.no_binary = no_binary;
+        self
+    }
+
+    /// The method to use when installing packages from the cache.
+    #[must_use]
+    pub fn link_mode(mut self, link_mode: LinkMode) -> Self {
+        self.link_mode = link_mode;
+        self
+    }
+
+    /// The maximum number of concurrent downloads, builds, and installs.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// A [`Reporter`] to notify of events over the course of each operation.
+    #[must_use]
+    pub fn reporter(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.reporter = Some(Arc::new(reporter));
+        self
+    }
+
+    /// A [`CancellationToken`] with which to abort in-flight operations.
+    ///
+    /// On cancellation, resolutions and downloads return [`Error::Cancelled`], and installations
+    /// roll back any partial modifications to the environment before doing the same.
+    #[must_use]
+    pub fn cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Initialize the cache, locate the base Python interpreter, and build the [`Uv`] instance.
+    pub fn build(self) -> Result<Uv, Error> {
+        let cache = Cache::from_settings(self.no_cache, self.cache_dir)?.init()?;
+
+        let base = if let Some(python) = self.python.as_deref() {
+            PythonEnvironment::from_requested_python(python, SystemPython::Allowed, None, &cache)?
+        } else {
+            PythonEnvironment::from_default_python(&cache)?
+        };
+        let interpreter = base.into_interpreter();
+
+        let client = RegistryClientBuilder::new(cache.clone())
+            .native_tls(self.native_tls)
+            .timeouts(self.timeouts)
+            .host_overrides(self.host_overrides)
+            .trusted_hosts(self.trusted_hosts)
+            .rate_limits(self.rate_limits)
+            .http_version(self.http_version)
+            .index_urls(self.index_locations.index_urls())
+            .index_strategy(self.index_strategy)
+            .keyring(self.keyring_provider)
+            .connectivity(self.connectivity)
+            .markers(interpreter.markers())
+            .platform(interpreter.platform())
+            .build();
+
+        Ok(Uv {
+            cache,
+            interpreter,
+            client,
+            index_locations: self.index_locations,
+            index: InMemoryIndex::default(),
+            in_flight: InFlight::default(),
+            options: self.options,
+            config_settings: self.config_settings,
+            no_build: self.no_build,
+            no_binary: self.no_binary,
+            link_mode: self.link_mode,
+            concurrency: self.concurrency,
+            reporter: self.reporter,
+            cancellation: self.cancellation,
+        })
+    }
+}
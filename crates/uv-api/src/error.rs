@@ -0,0 +1,47 @@
+use thiserror::Error;
+
+/// An error that can occur when running a [`crate::Uv`] operation.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Pep508(#[from] pep508_rs::Pep508Error),
+
+    #[error(transparent)]
+    ParsedUrl(#[from] Box<distribution_types::ParsedUrlError>),
+
+    #[error("Failed to find a Python interpreter")]
+    Interpreter(#[from] uv_interpreter::Error),
+
+    #[error("Failed to determine compatibility tags")]
+    Tags(#[from] platform_tags::TagsError),
+
+    #[error("Failed to create virtualenv")]
+    Virtualenv(#[from] uv_virtualenv::Error),
+
+    #[error("Failed to read `--find-links` entries")]
+    FlatIndex(#[from] uv_client::FlatIndexError),
+
+    #[error(transparent)]
+    Resolve(#[from] uv_resolver::ResolveError),
+
+    #[error("Failed to download distributions")]
+    Download(#[from] uv_installer::PrepareError),
+
+    #[error("Failed to install distributions")]
+    Install(#[from] uv_installer::InstallError),
+
+    #[error("Failed to uninstall package")]
+    Uninstall(#[from] uv_installer::UninstallError),
+
+    #[error("Failed to roll back the environment")]
+    Transaction(#[from] uv_installer::TransactionError),
+
+    #[error("The operation was cancelled")]
+    Cancelled,
+
+    #[error(transparent)]
+    Anyhow(#[from] anyhow::Error),
+}
@@ -0,0 +1,30 @@
+use distribution_types::{CachedDist, InstalledDist, Resolution};
+
+/// An event emitted over the course of a [`crate::Uv`] operation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Event<'a> {
+    /// A set of requirements was resolved.
+    Resolved(&'a Resolution),
+    /// A distribution was downloaded (and built, if necessary) into the cache.
+    Downloaded(&'a CachedDist),
+    /// A distribution was removed from the environment.
+    Uninstalled(&'a InstalledDist),
+    /// A distribution was installed into the environment.
+    Installed(&'a CachedDist),
+}
+
+/// A callback for observing the progress of [`crate::Uv`] operations.
+pub trait Reporter: Send + Sync {
+    /// Callback to invoke when an [`Event`] occurs.
+    fn on_event(&self, event: Event<'_>);
+}
+
+impl<F> Reporter for F
+where
+    F: Fn(Event<'_>) + Send + Sync,
+{
+    fn on_event(&self, event: Event<'_>) {
+        self(event);
+    }
+}
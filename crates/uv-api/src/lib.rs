@@ -0,0 +1,54 @@
+//! A stable, programmatic interface to uv.
+//!
+//! [`Uv`] exposes resolve, install, and virtual environment operations to Rust applications,
+//! such that they can be run in-process with typed options, rather than by shelling out to the
+//! `uv` command-line interface:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), uv_api::Error> {
+//! use std::path::Path;
+//!
+//! use uv_api::Uv;
+//!
+//! let uv = Uv::builder().python("3.12").build()?;
+//! let venv = uv.create_venv(Path::new(".venv"))?;
+//! let requirements = vec![Uv::parse_requirement("flask>=3")?];
+//! let resolution = uv.resolve(requirements, &venv).await?;
+//! uv.install(&resolution, &venv).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The types used by the underlying crates (e.g., [`Resolution`], [`Plan`], and
+//! [`SitePackages`]) are re-exported from this crate, and are versioned alongside it.
+
+pub use tokio_util::sync::CancellationToken;
+
+pub use distribution_types::{
+    CachedDist, Dist, FlatIndexLocation, IndexLocations, IndexUrl, InstalledDist, Name,
+    Requirement, Resolution, ResolvedDist,
+};
+pub use install_wheel_rs::linker::LinkMode;
+pub use pep508_rs::MarkerTree;
+pub use uv_cache::Cache;
+pub use uv_client::Connectivity;
+pub use uv_configuration::{
+    Concurrency, ConfigSettings, IndexStrategy, KeyringProviderType, NoBinary, NoBuild,
+};
+pub use uv_installer::{Plan, SitePackages};
+pub use uv_interpreter::{Interpreter, PythonEnvironment};
+pub use uv_resolver::{Options, OptionsBuilder, PreReleaseMode, ResolutionGraph, ResolutionMode};
+
+pub use crate::builder::UvBuilder;
+pub use crate::error::Error;
+pub use crate::event::{Event, Reporter};
+#[cfg(feature = "pyo3")]
+pub use crate::python::python_module;
+pub use crate::uv::Uv;
+
+mod builder;
+mod error;
+mod event;
+#[cfg(feature = "pyo3")]
+mod python;
+mod uv;
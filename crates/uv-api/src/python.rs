@@ -0,0 +1,349 @@
+//! Python bindings for resolution and environment inspection.
+//!
+//! ```python
+//! import uv_api
+//!
+//! resolution = uv_api.resolve(["flask>=3"], python="3.12")
+//! for package in resolution.packages:
+//!     print(package.requirement, package.hashes)
+//!
+//! environment = uv_api.inspect_environment(".venv")
+//! print(environment.python_version, [package.name for package in environment.packages])
+//! ```
+
+use std::fmt::Write;
+use std::future::Future;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::{pyclass, pyfunction, pymethods, pymodule, wrap_pyfunction};
+use pyo3::{types::PyModule, Bound, PyErr, PyResult, Python};
+use serde::de::value::StrDeserializer;
+use serde::de::DeserializeOwned;
+
+use distribution_types::{
+    DistributionMetadata, FlatIndexLocation, IndexLocations, IndexUrl, Name, VersionOrUrlRef,
+};
+use uv_client::Connectivity;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_resolver::{OptionsBuilder, PreReleaseMode, ResolutionMode};
+
+use crate::{Error, Uv, UvBuilder};
+
+/// A package pinned by a resolution.
+#[pyclass(module = "uv_api", name = "ResolvedPackage", get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PyResolvedPackage {
+    /// The normalized package name.
+    name: String,
+    /// The pinned version, if the package was resolved from a registry.
+    version: Option<String>,
+    /// The URL of the package, if the package was resolved from a direct URL.
+    url: Option<String>,
+    /// The pinned requirement, e.g., `flask==3.0.3`.
+    requirement: String,
+    /// The hashes of the pinned distribution, e.g., `sha256:...`.
+    hashes: Vec<String>,
+}
+
+#[pymethods]
+impl PyResolvedPackage {
+    fn __repr__(&self) -> String {
+        format!("<ResolvedPackage {}>", self.requirement)
+    }
+}
+
+/// A set of pinned packages, along with the markers under which the resolution is valid.
+#[pyclass(module = "uv_api", name = "Resolution", get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PyResolution {
+    /// The pinned packages, sorted by name.
+    packages: Vec<PyResolvedPackage>,
+    /// A PEP 508 marker expression under which the resolution is guaranteed to be valid.
+    markers: String,
+}
+
+#[pymethods]
+impl PyResolution {
+    fn __len__(&self) -> usize {
+        self.packages.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Resolution of {} packages>", self.packages.len())
+    }
+}
+
+/// A package installed in a Python environment.
+#[pyclass(module = "uv_api", name = "InstalledPackage", get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PyInstalledPackage {
+    /// The normalized package name.
+    name: String,
+    /// The installed version.
+    version: String,
+    /// The path to the package's `.dist-info` or `.egg-info` directory.
+    path: PathBuf,
+}
+
+#[pymethods]
+impl PyInstalledPackage {
+    fn __repr__(&self) -> String {
+        format!("<InstalledPackage {}=={}>", self.name, self.version)
+    }
+}
+
+/// A Python environment, along with the packages installed into it.
+#[pyclass(module = "uv_api", name = "Environment", get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PyEnvironment {
+    /// The root of the environment.
+    root: PathBuf,
+    /// The path to the environment's Python executable.
+    python: PathBuf,
+    /// The Python version, e.g., `3.12.1`.
+    python_version: String,
+    /// The installed packages, sorted by name.
+    packages: Vec<PyInstalledPackage>,
+}
+
+#[pymethods]
+impl PyEnvironment {
+    fn __repr__(&self) -> String {
+        format!(
+            "<Environment at {} with {} packages>",
+            self.root.display(),
+            self.packages.len()
+        )
+    }
+}
+
+/// Resolve a list of PEP 508 requirements into a set of pinned packages.
+///
+/// Resolution targets the given Python interpreter (e.g., `3.12` or a path to an executable), or
+/// the first Python interpreter found on the system.
+#[pyfunction]
+#[pyo3(signature = (
+    requirements,
+    *,
+    python = None,
+    index_url = None,
+    extra_index_url = Vec::new(),
+    find_links = Vec::new(),
+    no_index = false,
+    resolution = None,
+    prerelease = None,
+    cache_dir = None,
+    offline = false,
+))]
+#[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
+fn resolve(
+    py: Python<'_>,
+    requirements: Vec<String>,
+    python: Option<String>,
+    index_url: Option<String>,
+    extra_index_url: Vec<String>,
+    find_links: Vec<String>,
+    no_index: bool,
+    resolution: Option<String>,
+    prerelease: Option<String>,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+) -> PyResult<PyResolution> {
+    let requirements = requirements
+        .iter()
+        .map(|requirement| Uv::parse_requirement(requirement))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| PyValueError::new_err(format_error(&err)))?;
+
+    let index_locations = IndexLocations::new(
+        index_url
+            .as_deref()
+            .map(IndexUrl::from_str)
+            .transpose()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?,
+        extra_index_url
+            .iter()
+            .map(|url| IndexUrl::from_str(url))
+            .collect::<Result<_, _>>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?,
+        find_links
+            .iter()
+            .map(|location| FlatIndexLocation::from_str(location))
+            .collect::<Result<_, _>>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?,
+        no_index,
+    );
+
+    let mut options = OptionsBuilder::new();
+    if let Some(resolution) = resolution {
+        options = options.resolution_mode(parse_enum::<ResolutionMode>(&resolution)?);
+    }
+    if let Some(prerelease) = prerelease {
+        options = options.prerelease_mode(parse_enum::<PreReleaseMode>(&prerelease)?);
+    }
+
+    let mut builder = UvBuilder::new()
+        .index_locations(index_locations)
+        .options(options.build())
+        .connectivity(if offline {
+            Connectivity::Offline
+        } else {
+            Connectivity::Online
+        });
+    if let Some(python) = python {
+        builder = builder.python(python);
+    }
+    if let Some(cache_dir) = cache_dir {
+        builder = builder.cache_dir(cache_dir);
+    }
+
+    py.allow_threads(|| {
+        let uv = builder.build()?;
+        let environment = PythonEnvironment::from_interpreter(uv.interpreter().clone());
+        let (graph, markers) = block_on(uv.resolve_graph(requirements, &environment))?;
+
+        let mut packages = graph
+            .distributions()
+            .map(|(dist, hashes)| {
+                let (requirement, version, url) = match dist.version_or_url() {
+                    VersionOrUrlRef::Version(version) => (
+                        format!("{}=={version}", dist.name()),
+                        Some(version.to_string()),
+                        None,
+                    ),
+                    VersionOrUrlRef::Url(url) => (
+                        format!("{} @ {url}", dist.name()),
+                        None,
+                        Some(url.to_string()),
+                    ),
+                };
+                PyResolvedPackage {
+                    name: dist.name().to_string(),
+                    requirement,
+                    version,
+                    url,
+                    hashes: hashes.iter().map(ToString::to_string).collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(PyResolution {
+            packages,
+            markers: markers.to_string(),
+        })
+    })
+    .map_err(|err: Error| PyRuntimeError::new_err(format_error(&err)))
+}
+
+/// Inspect the Python environment at the given path (or the active virtual environment),
+/// returning its interpreter and installed packages.
+#[pyfunction]
+#[pyo3(signature = (path = None, *, cache_dir = None))]
+fn inspect_environment(
+    py: Python<'_>,
+    path: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+) -> PyResult<PyEnvironment> {
+    py.allow_threads(|| {
+        let cache = uv_cache::Cache::from_settings(false, cache_dir)?.init()?;
+        let environment = if let Some(path) = path {
+            PythonEnvironment::from_root(&path, &cache)?
+        } else {
+            PythonEnvironment::from_virtualenv(None, &cache)?
+        };
+        let site_packages = SitePackages::from_executable(&environment)?;
+
+        let mut packages = site_packages
+            .iter()
+            .map(|dist| PyInstalledPackage {
+                name: dist.name().to_string(),
+                version: dist.version().to_string(),
+                path: dist.path().to_path_buf(),
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(PyEnvironment {
+            root: environment.root().to_path_buf(),
+            python: environment.python_executable().to_path_buf(),
+            python_version: environment.interpreter().python_version().to_string(),
+            packages,
+        })
+    })
+    .map_err(|err: Error| PyRuntimeError::new_err(format_error(&err)))
+}
+
+/// Run a future to completion on a dedicated runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime")
+        .block_on(future)
+}
+
+/// Parse a kebab-case option (e.g., `lowest-direct`) into the corresponding enum variant.
+fn parse_enum<T: DeserializeOwned>(value: &str) -> Result<T, PyErr> {
+    T::deserialize(StrDeserializer::<serde::de::value::Error>::new(value))
+        .map_err(|err| PyValueError::new_err(format!("Invalid value `{value}`: {err}")))
+}
+
+/// Format an error along with its chain of causes.
+fn format_error(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        let _ = write!(message, "\n  Caused by: {cause}");
+        source = cause.source();
+    }
+    message
+}
+
+/// Resolve requirements and inspect Python environments with uv.
+#[pymodule]
+#[pyo3(name = "uv_api")]
+pub fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyResolution>()?;
+    m.add_class::<PyResolvedPackage>()?;
+    m.add_class::<PyEnvironment>()?;
+    m.add_class::<PyInstalledPackage>()?;
+    m.add_function(wrap_pyfunction!(resolve, m)?)?;
+    m.add_function(wrap_pyfunction!(inspect_environment, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use uv_resolver::{PreReleaseMode, ResolutionMode};
+
+    use super::{format_error, parse_enum};
+
+    #[test]
+    fn parse_kebab_case() {
+        assert_eq!(
+            parse_enum::<ResolutionMode>("lowest-direct").unwrap(),
+            ResolutionMode::LowestDirect
+        );
+        assert_eq!(
+            parse_enum::<PreReleaseMode>("if-necessary-or-explicit").unwrap(),
+            PreReleaseMode::IfNecessaryOrExplicit
+        );
+        assert!(parse_enum::<ResolutionMode>("LowestDirect").is_err());
+    }
+
+    #[test]
+    fn format_causes() {
+        let err = anyhow!("Failed to read file").context("Failed to resolve");
+        let err: &(dyn std::error::Error + 'static) = err.as_ref();
+        assert_eq!(
+            format_error(err),
+            "Failed to resolve\n  Caused by: Failed to read file"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use distribution_types::{
+    CachedDist, IndexLocations, InstalledDist, Name, Requirement, Resolution,
+};
+use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::UninstallOptions;
+use pep508_rs::MarkerTree;
+use uv_cache::Cache;
+use uv_client::{FlatIndexClient, RegistryClient};
+use uv_configuration::{
+    Concurrency, ConfigSettings, NoBinary, NoBuild, PythonPreference, Reinstall, SetupPyStrategy,
+};
+use uv_dispatch::BuildDispatch;
+use uv_distribution::DistributionDatabase;
+use uv_installer::{
+    InstallError, Installer, Plan, Planner, PrepareError, Preparer, SitePackages, Transaction,
+};
+use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_resolver::{
+    FlatIndex, InMemoryIndex, Manifest, Options, PythonRequirement, ResolutionGraph, ResolveError,
+    Resolver,
+};
+use uv_types::{BuildIsolation, HashStrategy, InFlight};
+
+use crate::{Error, Event, Reporter, UvBuilder};
+
+/// An in-process handle to uv's resolver, installer, and virtual environment management.
+///
+/// Instances share a cache, an HTTP client, and an in-memory index across operations. Construct
+/// one with [`Uv::builder`].
+pub struct Uv {
+    pub(crate) cache: Cache,
+    pub(crate) interpreter: Interpreter,
+    pub(crate) client: RegistryClient,
+    pub(crate) index_locations: IndexLocations,
+    pub(crate) index: InMemoryIndex,
+    pub(crate) in_flight: InFlight,
+    pub(crate) options: Options,
+    pub(crate) config_settings: ConfigSettings,
+    pub(crate) no_build: NoBuild,
+    pub(crate) no_binary: NoBinary,
+    pub(crate) link_mode: LinkMode,
+    pub(crate) concurrency: Concurrency,
+    pub(crate) reporter: Option<Arc<dyn Reporter>>,
+    pub(crate) cancellation: CancellationToken,
+}
+
+impl Uv {
+    /// Create a [`UvBuilder`] with the default settings.
+    pub fn builder() -> UvBuilder {
+        UvBuilder::new()
+    }
+
+    /// Parse a PEP 508 requirement (e.g., `flask>=3`).
+    pub fn parse_requirement(requirement: &str) -> Result<Requirement, Error> {
+        Ok(Requirement::from_pep508(pep508_rs::Requirement::from_str(
+            requirement,
+        )?)?)
+    }
+
+    /// The cache used by this instance.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// The base Python interpreter, used when creating virtual environments.
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    /// Create a virtual environment at the given path, using the base Python interpreter.
+    pub fn create_venv(&self, path: &Path) -> Result<PythonEnvironment, Error> {
+        Ok(uv_virtualenv::create_venv(
+            path,
+            self.interpreter.clone(),
+            uv_virtualenv::Prompt::None,
+            false,
+            PythonPreference::default(),
+            false,
+        )?)
+    }
+
+    /// Open the existing virtual environment at the given path.
+    pub fn open_venv(&self, path: &Path) -> Result<PythonEnvironment, Error> {
+        Ok(PythonEnvironment::from_root(path, &self.cache)?)
+    }
+
+    /// Resolve a set of requirements for the given environment.
+    ///
+    /// Packages that are already installed in the environment are preferred, if compatible.
+    pub async fn resolve(
+        &self,
+        requirements: Vec<Requirement>,
+        venv: &PythonEnvironment,
+    ) -> Result<Resolution, Error> {
+        let graph = self.resolve_manifest(requirements, venv).await?;
+        let resolution = Resolution::from(graph);
+
+        self.report(Event::Resolved(&resolution));
+        Ok(resolution)
+    }
+
+    /// Resolve a set of requirements for the given environment, returning the full
+    /// [`ResolutionGraph`] (including the hashes of each pinned distribution), along with the
+    /// markers under which the resolution is guaranteed to be valid.
+    pub async fn resolve_graph(
+        &self,
+        requirements: Vec<Requirement>,
+        venv: &PythonEnvironment,
+    ) -> Result<(ResolutionGraph, MarkerTree), Error> {
+        let manifest = Manifest::simple(requirements.clone());
+        let graph = self.resolve_manifest(requirements, venv).await?;
+        let markers = graph
+            .marker_tree(&manifest, &self.index, venv.interpreter().markers())
+            .map_err(Error::ParsedUrl)?;
+        Ok((graph, markers))
+    }
+
+    async fn resolve_manifest(
+        &self,
+        requirements: Vec<Requirement>,
+        venv: &PythonEnvironment,
+    ) -> Result<ResolutionGraph, Error> {
+        let interpreter = venv.interpreter();
+        let markers = interpreter.markers();
+        let tags = interpreter.tags()?;
+        let python_requirement = PythonRequirement::from_marker_environment(interpreter, markers);
+        let flat_index = self.flat_index(interpreter).await?;
+        let build_dispatch = self.build_dispatch(interpreter, &flat_index);
+        let site_packages = SitePackages::from_executable(venv)?;
+
+        let resolver = Resolver::new(
+            Manifest::simple(requirements),
+            self.options,
+            &python_requirement,
+            Some(markers),
+            tags,
+            &flat_index,
+            &self.index,
+            &HashStrategy::None,
+            &build_dispatch,
+            site_packages,
+            DistributionDatabase::new(&self.client, &build_dispatch, self.concurrency.downloads),
+        )?
+        .with_cancellation(self.cancellation.clone());
+        match resolver.resolve().await {
+            Ok(graph) => Ok(graph),
+            Err(ResolveError::Cancelled) => Err(Error::Cancelled),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Determine the changes required to bring the environment in line with the resolution,
+    /// without modifying it.
+    pub fn plan(&self, resolution: &Resolution, venv: &PythonEnvironment) -> Result<Plan, Error> {
+        let tags = venv.interpreter().tags()?;
+        let site_packages = SitePackages::from_executable(venv)?;
+        let requirements = resolution.requirements().collect::<Vec<_>>();
+        let plan = Planner::with_requirements(&requirements)
+            .with_resolution(resolution)
+            .build(
+                site_packages,
+                &Reinstall::None,
+                &self.no_binary,
+                &self.no_build,
+                &HashStrategy::None,
+                &self.index_locations,
+                &self.cache,
+                venv,
+                tags,
+            )
+            .context("Failed to determine installation plan")?;
+        Ok(plan)
+    }
+
+    /// Install a resolution into the given environment, returning the installed distributions.
+    ///
+    /// Packages that are installed but absent from the resolution are left as-is.
+    pub async fn install(
+        &self,
+        resolution: &Resolution,
+        venv: &PythonEnvironment,
+    ) -> Result<Vec<CachedDist>, Error> {
+        let interpreter = venv.interpreter();
+        let tags = interpreter.tags()?;
+        let Plan {
+            cached,
+            remote,
+            reinstalls,
+            hash_mismatches,
+            reinstall_reasons: _,
+            extraneous: _,
+        } = self.plan(resolution, venv)?;
+
+        // Download, build, and unzip any missing distributions.
+        let wheels = if remote.is_empty() {
+            vec![]
+        } else {
+            let remote = remote
+                .iter()
+                .map(|dist| {
+                    resolution
+                        .get_remote(&dist.name)
+                        .cloned()
+                        .expect("Resolution should contain all packages")
+                })
+                .collect::<Vec<_>>();
+
+            let flat_index = self.flat_index(interpreter).await?;
+            let build_dispatch = self.build_dispatch(interpreter, &flat_index);
+            let preparer = Preparer::new(
+                &self.cache,
+                tags,
+                &HashStrategy::None,
+                DistributionDatabase::new(
+                    &self.client,
+                    &build_dispatch,
+                    self.concurrency.downloads,
+                ),
+            )
+            .with_cancellation(self.cancellation.clone());
+            let wheels = match preparer.prepare(remote, &self.in_flight).await {
+                Ok(wheels) => wheels,
+                Err(PrepareError::Cancelled) => return Err(Error::Cancelled),
+                Err(err) => return Err(err.into()),
+            };
+            for wheel in &wheels {
+                self.report(Event::Downloaded(wheel));
+            }
+            wheels
+        };
+
+        // Journal the modifications to the environment, such that a failed or cancelled
+        // installation can be rolled back.
+        let mut transaction = Transaction::new(venv)?;
+
+        // Remove any outdated installations.
+        for dist_info in reinstalls.iter().chain(hash_mismatches.iter()) {
+            if let Err(err) = transaction.stage(dist_info) {
+                transaction.rollback(&[])?;
+                return Err(err.into());
+            }
+            if let Err(err) = self.uninstall(dist_info).await {
+                transaction.rollback(&[])?;
+                return Err(err);
+            }
+        }
+
+        // Install the resolved distributions.
+        let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
+        if !wheels.is_empty() {
+            if let Err(err) = Installer::new(venv)
+                .with_link_mode(self.link_mode)
+                .with_cancellation(self.cancellation.clone())
+                .install(&wheels)
+                .await
+            {
+                transaction.rollback(&wheels)?;
+                return Err(match err {
+                    InstallError::Cancelled => Error::Cancelled,
+                    err => err.into(),
+                });
+            }
+            for wheel in &wheels {
+                self.report(Event::Installed(wheel));
+            }
+        }
+
+        transaction.commit()?;
+
+        Ok(wheels)
+    }
+
+    /// Resolve a set of requirements and install them into the given environment.
+    pub async fn resolve_and_install(
+        &self,
+        requirements: Vec<Requirement>,
+        venv: &PythonEnvironment,
+    ) -> Result<Vec<CachedDist>, Error> {
+        let resolution = self.resolve(requirements, venv).await?;
+        self.install(&resolution, venv).await
+    }
+
+    /// Remove an installed distribution from its environment.
+    pub async fn uninstall(&self, dist_info: &InstalledDist) -> Result<(), Error> {
+        let summary = uv_installer::uninstall(dist_info, &UninstallOptions::default()).await?;
+        debug!(
+            "Uninstalled {} ({} file{}, {} director{})",
+            dist_info.name(),
+            summary.file_count,
+            if summary.file_count == 1 { "" } else { "s" },
+            summary.dir_count,
+            if summary.dir_count == 1 { "y" } else { "ies" },
+        );
+        self.report(Event::Uninstalled(dist_info));
+        Ok(())
+    }
+
+    /// Resolve the flat indexes from the configured `--find-links` locations.
+    async fn flat_index(&self, interpreter: &Interpreter) -> Result<FlatIndex, Error> {
+        let tags = interpreter.tags()?;
+        let client = FlatIndexClient::new(&self.client, &self.cache);
+        let entries = client.fetch(self.index_locations.flat_index()).await?;
+        Ok(FlatIndex::from_entries(
+            entries,
+            tags,
+            &HashStrategy::None,
+            &self.no_build,
+            &self.no_binary,
+        ))
+    }
+
+    /// Create a [`BuildDispatch`] for building source distributions against the interpreter.
+    fn build_dispatch<'a>(
+        &'a self,
+        interpreter: &'a Interpreter,
+        flat_index: &'a FlatIndex,
+    ) -> BuildDispatch<'a> {
+        BuildDispatch::new(
+            &self.client,
+            &self.cache,
+            interpreter,
+            &self.index_locations,
+            flat_index,
+            &self.index,
+            &self.in_flight,
+            SetupPyStrategy::default(),
+            &self.config_settings,
+            BuildIsolation::Isolated,
+            self.link_mode,
+            &self.no_build,
+            &self.no_binary,
+            self.concurrency,
+        )
+        .with_options(self.options)
+    }
+
+    fn report(&self, event: Event<'_>) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.on_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distribution_types::RequirementSource;
+
+    use super::*;
+
+    #[test]
+    fn parse_requirement() {
+        let requirement = Uv::parse_requirement("flask>=3").unwrap();
+        assert_eq!(requirement.name.as_ref(), "flask");
+        assert!(matches!(
+            requirement.source,
+            RequirementSource::Registry { .. }
+        ));
+
+        assert!(Uv::parse_requirement("flask>=").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Integration tests for the programmatic interface. These tests require a Python interpreter on
+//! the `PATH`, and resolve against the wheels in `scripts/links`, without network access.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use uv_api::{
+    CancellationToken, Connectivity, Error, Event, FlatIndexLocation, IndexLocations, Name, Uv,
+    UvBuilder,
+};
+
+/// Create a [`UvBuilder`] that resolves against `scripts/links` alone, with a temporary cache.
+fn builder() -> UvBuilder {
+    let links = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../scripts/links")
+        .canonicalize()
+        .expect("`scripts/links` should exist");
+    Uv::builder()
+        .no_cache(true)
+        .connectivity(Connectivity::Offline)
+        .index_locations(IndexLocations::new(
+            None,
+            vec![],
+            vec![FlatIndexLocation::Path(links)],
+            true,
+        ))
+}
+
+#[tokio::test]
+async fn resolve_and_install() -> Result<()> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let uv = builder()
+        .reporter({
+            let events = events.clone();
+            move |event: Event<'_>| {
+                let event = match event {
+                    Event::Resolved(_) => "resolved".to_string(),
+                    Event::Downloaded(dist) => format!("downloaded {}", dist.name()),
+                    Event::Installed(dist) => format!("installed {}", dist.name()),
+                    Event::Uninstalled(dist) => format!("uninstalled {}", dist.name()),
+                    _ => unreachable!(),
+                };
+                events.lock().unwrap().push(event);
+            }
+        })
+        .build()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let venv = uv.create_venv(&temp_dir.path().join(".venv"))?;
+
+    let requirements = vec![Uv::parse_requirement("validation==2.0.0")?];
+    let resolution = uv.resolve(requirements, &venv).await?;
+    assert_eq!(resolution.len(), 1);
+
+    // Before installing, the distribution is planned for download.
+    let plan = uv.plan(&resolution, &venv)?;
+    assert_eq!(plan.remote.len(), 1);
+
+    let installed = uv.install(&resolution, &venv).await?;
+    assert_eq!(installed.len(), 1);
+    assert_eq!(installed[0].name().as_ref(), "validation");
+
+    // Once installed, there's nothing left to do.
+    let plan = uv.plan(&resolution, &venv)?;
+    assert!(plan.remote.is_empty());
+    assert!(plan.cached.is_empty());
+    assert!(plan.reinstalls.is_empty());
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        ["resolved", "downloaded validation", "installed validation"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_unsatisfiable() -> Result<()> {
+    let uv = builder().build()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let venv = uv.create_venv(&temp_dir.path().join(".venv"))?;
+
+    let requirements = vec![Uv::parse_requirement("validation==2.5.0")?];
+    let err = uv.resolve(requirements, &venv).await.unwrap_err();
+    assert!(matches!(err, Error::Resolve(_)), "{err:?}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_cancelled() -> Result<()> {
+    let cancellation = CancellationToken::new();
+    let uv = builder().cancellation(cancellation.clone()).build()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let venv = uv.create_venv(&temp_dir.path().join(".venv"))?;
+
+    cancellation.cancel();
+    let requirements = vec![Uv::parse_requirement("validation==2.0.0")?];
+    let err = uv.resolve(requirements, &venv).await.unwrap_err();
+    assert!(matches!(err, Error::Cancelled), "{err:?}");
+
+    Ok(())
+}
@@ -0,0 +1,1086 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use http::{Extensions, StatusCode};
+use once_cell::sync::Lazy;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use tokio::process::Command;
+use tokio::sync::OnceCell;
+use tracing::{debug, trace, warn};
+use url::Url;
+
+use crate::credentials::Credentials;
+
+/// Global token cache for a uv invocation, shared across uv clients.
+static CLOUD_TOKENS: Lazy<CloudTokenCache> = Lazy::new(CloudTokenCache::default);
+
+/// Tokens are refreshed once they're within this margin of their expiry, such that they don't
+/// expire mid-request.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// The assumed lifetime of tokens that don't report an expiry. GCP access tokens are valid for an
+/// hour, but `gcloud` may return a cached token, so we refresh well before then.
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// The Azure AD resource ID for Azure DevOps, used to request tokens for Azure Artifacts.
+const AZURE_DEVOPS_RESOURCE: &str = "499b84ac-1321-427f-aa17-267ca6975798";
+
+/// The Azure AD endpoints used to sign in with a device code.
+const AZURE_AUTHORITY: &str = "https://login.microsoftonline.com/organizations/oauth2/v2.0/";
+
+/// The public client ID of the Azure CLI, with which device-code sign-ins are requested.
+const AZURE_CLI_CLIENT_ID: &str = "04b07795-8ddb-461a-bbee-02f9e1bf7b46";
+
+/// The time after which a failed attempt to obtain a token is retried.
+const FAILURE_LIFETIME: Duration = Duration::from_secs(60);
+
+/// The lifetime of personal access tokens provided via the environment. These can't be refreshed,
+/// so they're only re-read if they're rejected.
+const PAT_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A package index hosted by a cloud provider, which authenticates with short-lived tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CloudRegistry {
+    /// An AWS CodeArtifact repository, e.g.,
+    /// `my-domain-111122223333.d.codeartifact.us-west-2.amazonaws.com`.
+    CodeArtifact {
+        domain: String,
+        owner: String,
+        region: String,
+    },
+    /// A GCP Artifact Registry repository, e.g., `us-central1-python.pkg.dev`.
+    ArtifactRegistry { host: String },
+    /// An Azure Artifacts feed, e.g., `pkgs.dev.azure.com/my-organization` or
+    /// `my-organization.pkgs.visualstudio.com`.
+    AzureArtifacts { organization: String },
+}
+
+impl CloudRegistry {
+    /// Identify the cloud registry that hosts the given URL, if any.
+    pub(crate) fn from_url(url: &Url) -> Option<Self> {
+        if url.scheme() != "https" {
+            return None;
+        }
+        let host = url.host_str()?;
+
+        // E.g., `my-domain-111122223333.d.codeartifact.us-west-2.amazonaws.com`.
+        if let Some(rest) = host.strip_suffix(".amazonaws.com") {
+            let (prefix, region) = rest.split_once(".d.codeartifact.")?;
+            let (domain, owner) = prefix.rsplit_once('-')?;
+            if domain.is_empty()
+                || owner.len() != 12
+                || !owner.bytes().all(|byte| byte.is_ascii_digit())
+                || region.is_empty()
+                || region.contains('.')
+            {
+                return None;
+            }
+            return Some(Self::CodeArtifact {
+                domain: domain.to_string(),
+                owner: owner.to_string(),
+                region: region.to_string(),
+            });
+        }
+
+        // E.g., `us-central1-python.pkg.dev`.
+        if host
+            .strip_suffix("-python.pkg.dev")
+            .is_some_and(|location| !location.is_empty() && !location.contains('.'))
+        {
+            return Some(Self::ArtifactRegistry {
+                host: host.to_string(),
+            });
+        }
+
+        // E.g., `pkgs.dev.azure.com/my-organization/_packaging/my-feed/pypi/simple/` or
+        // `my-organization.pkgs.visualstudio.com/_packaging/my-feed/pypi/simple/`.
+        if host == "pkgs.dev.azure.com" || host.ends_with(".pkgs.visualstudio.com") {
+            return azure_organization(url)
+                .map(|organization| Self::AzureArtifacts { organization });
+        }
+
+        None
+    }
+
+    /// The username to use alongside the token.
+    fn username(&self) -> &'static str {
+        match self {
+            Self::CodeArtifact { .. } => "aws",
+            Self::ArtifactRegistry { .. } => "oauth2accesstoken",
+            Self::AzureArtifacts { .. } => "VssSessionToken",
+        }
+    }
+
+    /// Obtain a token using the ambient cloud credentials, via the provider's command-line
+    /// interface.
+    ///
+    /// For Azure Artifacts, a personal access token provided via the environment takes precedence,
+    /// and interactive sessions fall back to signing in with a device code.
+    async fn fetch_token(&self) -> Option<Token> {
+        match self {
+            Self::CodeArtifact {
+                domain,
+                owner,
+                region,
+            } => {
+                let output = run(Command::new("aws").args([
+                    "codeartifact",
+                    "get-authorization-token",
+                    "--domain",
+                    domain.as_str(),
+                    "--domain-owner",
+                    owner.as_str(),
+                    "--region",
+                    region.as_str(),
+                    "--output",
+                    "json",
+                ]))
+                .await?;
+                Token::from_codeartifact(&output)
+                    .inspect_err(|err| {
+                        warn!("Failed to parse response from `aws codeartifact`: {err}");
+                    })
+                    .ok()
+            }
+            Self::ArtifactRegistry { .. } => {
+                let output =
+                    run(Command::new("gcloud").args(["auth", "print-access-token"])).await?;
+                let value = output.trim();
+                if value.is_empty() {
+                    return None;
+                }
+                Some(Token {
+                    value: value.to_string(),
+                    expires: Instant::now() + DEFAULT_LIFETIME,
+                })
+            }
+            Self::AzureArtifacts { .. } => {
+                if let Some(token) = self.azure_pat(
+                    |name| std::env::var(name).ok().filter(|value| !value.is_empty()),
+                    configured_azure_organization,
+                ) {
+                    return Some(token);
+                }
+                if let Some(output) = run(Command::new("az").args([
+                    "account",
+                    "get-access-token",
+                    "--resource",
+                    AZURE_DEVOPS_RESOURCE,
+                    "--output",
+                    "json",
+                ]))
+                .await
+                {
+                    return Token::from_azure(&output)
+                        .inspect_err(|err| {
+                            warn!("Failed to parse response from `az account`: {err}");
+                        })
+                        .ok();
+                }
+
+                // Without a PAT or an `az` session, sign in with a device code, provided there's a
+                // user to complete the sign-in.
+                if !std::io::stderr().is_terminal() {
+                    return None;
+                }
+                let authority = Url::parse(AZURE_AUTHORITY).expect("valid authority URL");
+                device_code_login(&reqwest::Client::new(), &authority)
+                    .await
+                    .inspect_err(|err| warn!("Failed to sign in to Azure: {err}"))
+                    .ok()
+            }
+        }
+    }
+
+    /// Read a personal access token for an Azure Artifacts feed from the environment, via the
+    /// given variable lookup.
+    ///
+    /// Supports the `VSS_NUGET_EXTERNAL_FEED_ENDPOINTS` variable used by `artifacts-keyring` and the
+    /// Azure Artifacts Credential Provider, which scopes each token to a feed, followed by the
+    /// `AZURE_DEVOPS_EXT_PAT` variable used by the Azure DevOps CLI. The latter isn't scoped, so
+    /// it's only sent to the `configured` organization (see [`configured_azure_organization`]).
+    fn azure_pat(
+        &self,
+        var: impl Fn(&str) -> Option<String>,
+        configured: impl FnOnce() -> Option<String>,
+    ) -> Option<Token> {
+        let Self::AzureArtifacts { organization } = self else {
+            return None;
+        };
+        let pat = var("VSS_NUGET_EXTERNAL_FEED_ENDPOINTS")
+            .and_then(|endpoints| self.azure_pat_from_feed_endpoints(&endpoints))
+            .or_else(|| {
+                let pat = var("AZURE_DEVOPS_EXT_PAT")?;
+                if configured().as_ref() == Some(organization) {
+                    Some(pat)
+                } else {
+                    debug!(
+                        "Ignoring `AZURE_DEVOPS_EXT_PAT` for {self}, which isn't the configured organization"
+                    );
+                    None
+                }
+            })?;
+        trace!("Using personal access token from the environment for {self}");
+        Some(Token {
+            value: pat,
+            expires: Instant::now() + PAT_LIFETIME,
+        })
+    }
+
+    /// Find the personal access token for this registry in a `VSS_NUGET_EXTERNAL_FEED_ENDPOINTS`
+    /// value, e.g., `{"endpointCredentials": [{"endpoint": "...", "password": "..."}]}`.
+    fn azure_pat_from_feed_endpoints(&self, endpoints: &str) -> Option<String> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FeedEndpoints {
+            endpoint_credentials: Vec<EndpointCredentials>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EndpointCredentials {
+            endpoint: String,
+            password: String,
+        }
+
+        let endpoints: FeedEndpoints = serde_json::from_str(endpoints)
+            .inspect_err(|err| warn!("Failed to parse `VSS_NUGET_EXTERNAL_FEED_ENDPOINTS`: {err}"))
+            .ok()?;
+        endpoints
+            .endpoint_credentials
+            .into_iter()
+            .find(|credentials| {
+                Url::parse(&credentials.endpoint)
+                    .ok()
+                    .and_then(|endpoint| Self::from_url(&endpoint))
+                    .is_some_and(|registry| registry == *self)
+            })
+            .map(|credentials| credentials.password)
+    }
+}
+
+/// Return the (lowercase) Azure DevOps organization of a URL, e.g., `my-organization` for
+/// `https://dev.azure.com/my-organization/` or `https://my-organization.visualstudio.com/`, and
+/// their Azure Artifacts (`pkgs`) counterparts.
+fn azure_organization(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let organization = if host == "dev.azure.com" || host == "pkgs.dev.azure.com" {
+        url.path_segments()?.next()?
+    } else {
+        host.strip_suffix(".pkgs.visualstudio.com")
+            .or_else(|| host.strip_suffix(".visualstudio.com"))?
+    };
+    if organization.is_empty() || organization.contains('.') {
+        return None;
+    }
+    Some(organization.to_lowercase())
+}
+
+/// Return the Azure DevOps organization that's configured for the environment: that of the
+/// current Azure Pipelines run (`SYSTEM_COLLECTIONURI`), or else the default organization of the
+/// Azure DevOps CLI (per `az devops configure --defaults organization=...`).
+fn configured_azure_organization() -> Option<String> {
+    if let Some(organization) = std::env::var("SYSTEM_COLLECTIONURI")
+        .ok()
+        .and_then(|uri| Url::parse(&uri).ok())
+        .and_then(|url| azure_organization(&url))
+    {
+        return Some(organization);
+    }
+    let config_dir = std::env::var_os("AZURE_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+                .map(|home| PathBuf::from(home).join(".azure"))
+        })?;
+    let config = std::fs::read_to_string(config_dir.join("azuredevops").join("config")).ok()?;
+    azure_organization_from_config(&config)
+}
+
+/// Parse the default organization from the Azure DevOps CLI configuration, an INI file with an
+/// `organization` URL in its `[defaults]` section.
+fn azure_organization_from_config(config: &str) -> Option<String> {
+    let mut defaults = false;
+    for line in config.lines().map(str::trim) {
+        if let Some(section) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            defaults = section.trim() == "defaults";
+        } else if defaults {
+            if let Some(("organization", value)) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+            {
+                return Url::parse(value)
+                    .ok()
+                    .and_then(|url| azure_organization(&url));
+            }
+        }
+    }
+    None
+}
+
+/// Sign in to Azure AD with a device code, and return an access token for Azure DevOps.
+///
+/// The user is prompted to enter the code in a browser, on any device, while the token endpoint
+/// of the `authority` is polled until the sign-in completes (or the code expires).
+async fn device_code_login(client: &reqwest::Client, authority: &Url) -> anyhow::Result<Token> {
+    #[derive(serde::Deserialize)]
+    struct DeviceCode {
+        device_code: String,
+        /// The instructions to display to the user, including the code and the URL at which to
+        /// enter it.
+        message: String,
+        expires_in: u64,
+        interval: u64,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TokenResponse {
+        access_token: Option<String>,
+        expires_in: Option<u64>,
+        error: Option<String>,
+        error_description: Option<String>,
+    }
+
+    let scope = format!("{AZURE_DEVOPS_RESOURCE}/.default");
+    let device_code: DeviceCode = client
+        .post(authority.join("devicecode")?)
+        .form(&[
+            ("client_id", AZURE_CLI_CLIENT_ID),
+            ("scope", scope.as_str()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    #[allow(clippy::print_stderr)]
+    {
+        eprintln!("{}", device_code.message);
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(device_code.expires_in);
+    let mut interval = Duration::from_secs(device_code.interval);
+    loop {
+        tokio::time::sleep(interval).await;
+        if Instant::now() >= deadline {
+            anyhow::bail!("The device code expired before the sign-in was completed");
+        }
+
+        // Pending sign-ins are reported as errors (with a `400` status), so the status is ignored.
+        let response: TokenResponse = client
+            .post(authority.join("token")?)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", AZURE_CLI_CLIENT_ID),
+                ("device_code", device_code.device_code.as_str()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+        if let Some(access_token) = response.access_token {
+            return Ok(Token {
+                value: access_token,
+                expires: Instant::now()
+                    + response
+                        .expires_in
+                        .map_or(DEFAULT_LIFETIME, Duration::from_secs),
+            });
+        }
+        match response.error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += Duration::from_secs(5),
+            _ => anyhow::bail!(
+                "{}",
+                response
+                    .error_description
+                    .or(response.error)
+                    .unwrap_or_else(|| "The sign-in failed".to_string())
+            ),
+        }
+    }
+}
+
+impl Display for CloudRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CodeArtifact {
+                domain,
+                owner,
+                region,
+            } => write!(f, "CodeArtifact domain `{domain}` ({owner}, {region})"),
+            Self::ArtifactRegistry { host } => write!(f, "Artifact Registry at `{host}`"),
+            Self::AzureArtifacts { organization } => {
+                write!(f, "Azure Artifacts organization `{organization}`")
+            }
+        }
+    }
+}
+
+/// Run a command, returning its standard output if it succeeds.
+async fn run(command: &mut Command) -> Option<String> {
+    let program = command.as_std().get_program().to_string_lossy().to_string();
+    let output = command
+        .output()
+        .await
+        .inspect_err(|err| warn!("Failure running `{program}` command: {err}"))
+        .ok()?;
+    if !output.status.success() {
+        warn!(
+            "`{program}` command failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .inspect_err(|err| warn!("Failed to parse response from `{program}` command: {err}"))
+        .ok()
+}
+
+/// A short-lived token, along with the time at which it expires.
+#[derive(Debug, Clone)]
+struct Token {
+    value: String,
+    expires: Instant,
+}
+
+impl Token {
+    /// Parse the output of `aws codeartifact get-authorization-token --output json`.
+    fn from_codeartifact(output: &str) -> Result<Self, serde_json::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AuthorizationToken {
+            authorization_token: String,
+            expiration: Option<serde_json::Value>,
+        }
+
+        let response: AuthorizationToken = serde_json::from_str(output)?;
+
+        // The expiration is reported as a timestamp (by v2 of the AWS CLI), or as seconds since
+        // the epoch (by v1).
+        let expiration = match response.expiration {
+            Some(serde_json::Value::String(expiration)) => {
+                chrono::DateTime::parse_from_rfc3339(&expiration)
+                    .ok()
+                    .map(|expiration| expiration.with_timezone(&chrono::Utc))
+            }
+            Some(serde_json::Value::Number(expiration)) => expiration
+                .as_f64()
+                .and_then(|expiration| chrono::DateTime::from_timestamp(expiration as i64, 0)),
+            _ => None,
+        };
+
+        Ok(Self::with_expiration(
+            response.authorization_token,
+            expiration,
+        ))
+    }
+
+    /// Parse the output of `az account get-access-token --output json`.
+    fn from_azure(output: &str) -> Result<Self, serde_json::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AccessToken {
+            access_token: String,
+            /// The expiration, in seconds since the epoch (omitted by older versions of the CLI).
+            #[serde(rename = "expires_on")]
+            expires_on: Option<i64>,
+        }
+
+        let response: AccessToken = serde_json::from_str(output)?;
+        let expiration = response
+            .expires_on
+            .and_then(|expiration| chrono::DateTime::from_timestamp(expiration, 0));
+        Ok(Self::with_expiration(response.access_token, expiration))
+    }
+
+    /// Create a token that expires at the given time, if known.
+    fn with_expiration(value: String, expiration: Option<chrono::DateTime<chrono::Utc>>) -> Self {
+        let lifetime = expiration.map_or(DEFAULT_LIFETIME, |expiration| {
+            // Tokens that have already expired have no remaining lifetime.
+            (expiration - chrono::Utc::now())
+                .to_std()
+                .unwrap_or_default()
+        });
+        Self {
+            value,
+            expires: Instant::now() + lifetime,
+        }
+    }
+
+    /// Returns `true` if the token should be refreshed before it's used.
+    fn is_stale(&self, now: Instant) -> bool {
+        now + EXPIRY_MARGIN >= self.expires
+    }
+}
+
+/// The outcome of an attempt to obtain a token for a cloud registry.
+#[derive(Debug, Clone)]
+enum Fetch {
+    /// A token was obtained.
+    Token(Token),
+    /// No token could be obtained (e.g., as the provider's command-line interface isn't
+    /// installed), at the given time.
+    Failed(Instant),
+}
+
+impl Fetch {
+    /// Returns `true` if the outcome should be discarded, and a new token fetched.
+    fn is_stale(&self, now: Instant) -> bool {
+        match self {
+            Self::Token(token) => token.is_stale(now),
+            Self::Failed(at) => now >= *at + FAILURE_LIFETIME,
+        }
+    }
+}
+
+/// A cache of the tokens obtained for each cloud registry.
+#[derive(Default)]
+pub(crate) struct CloudTokenCache {
+    /// The most recent fetch for each registry. Concurrent requests share a single fetch, and
+    /// failed fetches are cached, such that a missing command-line interface isn't invoked for
+    /// every request.
+    tokens: Mutex<HashMap<CloudRegistry, Arc<OnceCell<Fetch>>>>,
+    /// The registries for which a token from the ambient credentials succeeded where the
+    /// credentials provided by the user failed (e.g., as they expired). Requests to these
+    /// registries always use the ambient credentials.
+    preferred: Mutex<HashSet<CloudRegistry>>,
+}
+
+impl CloudTokenCache {
+    /// Return credentials for the registry, fetching a new token if the cached token is stale (or
+    /// if `refresh` is set).
+    async fn credentials(
+        &self,
+        registry: &CloudRegistry,
+        backend: &CloudTokenBackend,
+        refresh: bool,
+    ) -> Option<Credentials> {
+        // Find the current fetch, or start a new one. The lock is released before the fetch is
+        // awaited, such that requests to other registries aren't blocked.
+        let cell = {
+            let mut tokens = self.tokens.lock().unwrap();
+            let now = Instant::now();
+            match tokens.get(registry) {
+                // Join an in-flight fetch.
+                Some(cell) if cell.get().is_none() => cell.clone(),
+                // Reuse a fresh token, or a recent failure (even if a refresh was requested).
+                Some(cell)
+                    if cell.get().is_some_and(|fetch| {
+                        !fetch.is_stale(now) && (!refresh || matches!(fetch, Fetch::Failed(_)))
+                    }) =>
+                {
+                    trace!("Using cached token for {registry}");
+                    cell.clone()
+                }
+                _ => {
+                    let cell = Arc::new(OnceCell::new());
+                    tokens.insert(registry.clone(), cell.clone());
+                    cell
+                }
+            }
+        };
+
+        let fetch = cell
+            .get_or_init(|| async {
+                debug!("Fetching token for {registry}");
+                match backend.fetch_token(registry).await {
+                    Some(token) => Fetch::Token(token),
+                    None => Fetch::Failed(Instant::now()),
+                }
+            })
+            .await;
+        let Fetch::Token(token) = fetch else {
+            return None;
+        };
+        Some(Credentials::new(
+            Some(registry.username().to_string()),
+            Some(token.value.clone()),
+        ))
+    }
+}
+
+/// The source of tokens for cloud registries.
+enum CloudTokenBackend {
+    /// Use the cloud provider's command-line interface (i.e., `aws` or `gcloud`).
+    Subprocess,
+    /// Return the given tokens in order, treating every URL as a registry.
+    #[cfg(test)]
+    Dummy(Mutex<std::collections::VecDeque<&'static str>>),
+}
+
+impl CloudTokenBackend {
+    fn registry(&self, url: &Url) -> Option<CloudRegistry> {
+        match self {
+            Self::Subprocess => CloudRegistry::from_url(url),
+            #[cfg(test)]
+            Self::Dummy(_) => Some(CloudRegistry::ArtifactRegistry {
+                host: url.host_str()?.to_string(),
+            }),
+        }
+    }
+
+    async fn fetch_token(&self, registry: &CloudRegistry) -> Option<Token> {
+        match self {
+            Self::Subprocess => registry.fetch_token().await,
+            #[cfg(test)]
+            Self::Dummy(tokens) => tokens.lock().unwrap().pop_front().map(|value| Token {
+                value: value.to_string(),
+                expires: Instant::now() + DEFAULT_LIFETIME,
+            }),
+        }
+    }
+}
+
+/// A middleware that authenticates requests to cloud-hosted indexes (AWS CodeArtifact, GCP
+/// Artifact Registry, and Azure Artifacts) with short-lived tokens, obtained from the ambient
+/// cloud credentials and refreshed before they expire.
+///
+/// Requests that already include a password (e.g., a token embedded in the index URL) are sent
+/// as-is, unless they're rejected, in which case they're retried with a fresh token.
+pub struct CloudAuthMiddleware {
+    backend: CloudTokenBackend,
+    cache: Option<CloudTokenCache>,
+}
+
+impl CloudAuthMiddleware {
+    pub fn new() -> Self {
+        Self {
+            backend: CloudTokenBackend::Subprocess,
+            cache: None,
+        }
+    }
+
+    /// Get the configured token cache.
+    ///
+    /// If not set, the global cache is used.
+    fn cache(&self) -> &CloudTokenCache {
+        self.cache.as_ref().unwrap_or(&CLOUD_TOKENS)
+    }
+
+    /// Create a middleware that returns the given tokens in order, for any URL.
+    #[cfg(test)]
+    fn dummy(tokens: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            backend: CloudTokenBackend::Dummy(Mutex::new(tokens.into_iter().collect())),
+            cache: Some(CloudTokenCache::default()),
+        }
+    }
+}
+
+impl Default for CloudAuthMiddleware {
+    fn default() -> Self {
+        CloudAuthMiddleware::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for CloudAuthMiddleware {
+    async fn handle(
+        &self,
+        mut request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(registry) = self.backend.registry(request.url()) else {
+            return next.run(request, extensions).await;
+        };
+
+        // Requests with streaming bodies can't be retried.
+        let Some(retry_request) = request.try_clone() else {
+            return next.run(request, extensions).await;
+        };
+
+        // Unless the user provided a password, attach a token.
+        let has_password = Credentials::from_request(&request)
+            .is_some_and(|credentials| credentials.password().is_some());
+        if !has_password || self.cache().preferred.lock().unwrap().contains(&registry) {
+            if let Some(credentials) = self
+                .cache()
+                .credentials(&registry, &self.backend, false)
+                .await
+            {
+                request = authenticate(&credentials, request);
+            }
+        }
+
+        let response = next.clone().run(request, extensions).await?;
+        if !matches!(
+            response.status(),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+        ) {
+            return Ok(response);
+        }
+
+        // The token may have expired or been revoked; retry with a fresh one.
+        debug!(
+            "Request for {} failed with {}; retrying with a fresh token for {registry}",
+            retry_request.url(),
+            response.status()
+        );
+        let Some(credentials) = self
+            .cache()
+            .credentials(&registry, &self.backend, true)
+            .await
+        else {
+            return Ok(response);
+        };
+        let response = next
+            .run(authenticate(&credentials, retry_request), extensions)
+            .await?;
+        if has_password && response.status().is_success() {
+            self.cache().preferred.lock().unwrap().insert(registry);
+        }
+        Ok(response)
+    }
+}
+
+/// Attach the credentials to the request, replacing any credentials embedded in the URL.
+fn authenticate(credentials: &Credentials, mut request: Request) -> Request {
+    let url = request.url_mut();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    credentials.authenticate(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+    use wiremock::matchers::{basic_auth, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[test]
+    fn from_url() {
+        let url = Url::parse(
+            "https://my-domain-111122223333.d.codeartifact.us-west-2.amazonaws.com/pypi/my-repo/simple/",
+        )
+        .unwrap();
+        assert_eq!(
+            CloudRegistry::from_url(&url),
+            Some(CloudRegistry::CodeArtifact {
+                domain: "my-domain".to_string(),
+                owner: "111122223333".to_string(),
+                region: "us-west-2".to_string(),
+            })
+        );
+
+        let url =
+            Url::parse("https://us-central1-python.pkg.dev/my-project/my-repo/simple/").unwrap();
+        assert_eq!(
+            CloudRegistry::from_url(&url),
+            Some(CloudRegistry::ArtifactRegistry {
+                host: "us-central1-python.pkg.dev".to_string(),
+            })
+        );
+
+        for url in [
+            "https://pkgs.dev.azure.com/My-Organization/_packaging/my-feed/pypi/simple/",
+            "https://pkgs.dev.azure.com/my-organization/my-project/_packaging/my-feed/pypi/simple/",
+            "https://my-organization.pkgs.visualstudio.com/_packaging/my-feed/pypi/simple/",
+        ] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(
+                CloudRegistry::from_url(&url),
+                Some(CloudRegistry::AzureArtifacts {
+                    organization: "my-organization".to_string(),
+                }),
+                "{url}"
+            );
+        }
+
+        for url in [
+            "https://pypi.org/simple/",
+            "http://us-central1-python.pkg.dev/my-project/my-repo/simple/",
+            "https://my-domain.d.codeartifact.us-west-2.amazonaws.com/pypi/my-repo/simple/",
+            "https://s3.us-west-2.amazonaws.com/bucket/",
+            "https://us-central1-docker.pkg.dev/my-project/my-repo/",
+            "https://pkgs.dev.azure.com/",
+            "https://dev.azure.com/my-organization/",
+        ] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(CloudRegistry::from_url(&url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn codeartifact_token() {
+        let token = Token::from_codeartifact(
+            r#"{"authorizationToken": "token", "expiration": "2000-01-01T00:00:00+00:00"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.value, "token");
+        assert!(token.is_stale(Instant::now()));
+
+        let token = Token::from_codeartifact(r#"{"authorizationToken": "token"}"#).unwrap();
+        assert!(!token.is_stale(Instant::now()));
+    }
+
+    #[test]
+    fn azure_token() {
+        let token = Token::from_azure(
+            r#"{"accessToken": "token", "expiresOn": "2000-01-01 00:00:00.000000", "expires_on": 946684800, "tokenType": "Bearer"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.value, "token");
+        assert!(token.is_stale(Instant::now()));
+
+        let token = Token::from_azure(r#"{"accessToken": "token"}"#).unwrap();
+        assert!(!token.is_stale(Instant::now()));
+    }
+
+    #[test]
+    fn azure_feed_endpoints() {
+        let registry = CloudRegistry::AzureArtifacts {
+            organization: "my-organization".to_string(),
+        };
+        let endpoints = r#"{"endpointCredentials": [
+            {"endpoint": "https://pkgs.dev.azure.com/other-organization/_packaging/feed/pypi/simple/", "username": "user", "password": "other"},
+            {"endpoint": "https://pkgs.dev.azure.com/my-organization/_packaging/feed/pypi/simple/", "username": "user", "password": "pat"}
+        ]}"#;
+        assert_eq!(
+            registry.azure_pat_from_feed_endpoints(endpoints),
+            Some("pat".to_string())
+        );
+        assert_eq!(
+            registry.azure_pat_from_feed_endpoints(r#"{"endpointCredentials": []}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn azure_organization_config() {
+        let config = r"
+[core]
+organization = https://dev.azure.com/other-organization/
+
+[defaults]
+project = my-project
+organization = https://dev.azure.com/My-Organization/
+";
+        assert_eq!(
+            azure_organization_from_config(config),
+            Some("my-organization".to_string())
+        );
+        assert_eq!(azure_organization_from_config("[defaults]\n"), None);
+
+        let url = Url::parse("https://my-organization.visualstudio.com/").unwrap();
+        assert_eq!(
+            azure_organization(&url),
+            Some("my-organization".to_string())
+        );
+    }
+
+    /// `AZURE_DEVOPS_EXT_PAT` is only sent to the configured organization.
+    #[test]
+    fn azure_pat_scope() {
+        let registry = CloudRegistry::AzureArtifacts {
+            organization: "my-organization".to_string(),
+        };
+        let var = |name: &str| (name == "AZURE_DEVOPS_EXT_PAT").then(|| "pat".to_string());
+
+        let token = registry
+            .azure_pat(var, || Some("my-organization".to_string()))
+            .unwrap();
+        assert_eq!(token.value, "pat");
+        assert!(registry
+            .azure_pat(var, || Some("other-organization".to_string()))
+            .is_none());
+        assert!(registry.azure_pat(var, || None).is_none());
+    }
+
+    /// A device-code sign-in polls until the user completes it.
+    #[tokio::test]
+    async fn azure_device_code() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/devicecode"))
+            .and(body_string_contains(AZURE_CLI_CLIENT_ID))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "device_code": "device-code",
+                "user_code": "ABCDEFGHI",
+                "verification_uri": "https://microsoft.com/devicelogin",
+                "message": "To sign in, enter the code ABCDEFGHI.",
+                "expires_in": 900,
+                "interval": 0,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("device_code=device-code"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "authorization_pending",
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "token",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let authority = Url::parse(&format!("{}/", server.uri()))?;
+        let token = device_code_login(&Client::new(), &authority).await?;
+        assert_eq!(token.value, "token");
+        assert!(!token.is_stale(Instant::now()));
+
+        Ok(())
+    }
+
+    /// A declined device-code sign-in fails.
+    #[tokio::test]
+    async fn azure_device_code_declined() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/devicecode"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "device_code": "device-code",
+                "message": "To sign in, enter the code ABCDEFGHI.",
+                "expires_in": 900,
+                "interval": 0,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": "authorization_declined",
+                "error_description": "The user declined the sign-in.",
+            })))
+            .mount(&server)
+            .await;
+
+        let authority = Url::parse(&format!("{}/", server.uri()))?;
+        let err = device_code_login(&Client::new(), &authority)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "The user declined the sign-in.");
+
+        Ok(())
+    }
+
+    /// A failure to obtain a token is cached, rather than retried for every request.
+    #[tokio::test]
+    async fn cache_failure() {
+        let registry = CloudRegistry::ArtifactRegistry {
+            host: "us-central1-python.pkg.dev".to_string(),
+        };
+        let backend = CloudTokenBackend::Dummy(Mutex::new(std::collections::VecDeque::new()));
+        let cache = CloudTokenCache::default();
+        assert!(cache
+            .credentials(&registry, &backend, false)
+            .await
+            .is_none());
+
+        // Even once a token is available, the failure is reused until it expires.
+        let CloudTokenBackend::Dummy(tokens) = &backend else {
+            unreachable!()
+        };
+        tokens.lock().unwrap().push_back("token");
+        assert!(cache
+            .credentials(&registry, &backend, false)
+            .await
+            .is_none());
+        assert!(cache.credentials(&registry, &backend, true).await.is_none());
+        assert_eq!(tokens.lock().unwrap().len(), 1);
+    }
+
+    /// Concurrent requests share a single fetch.
+    #[tokio::test]
+    async fn share_fetch() {
+        let registry = CloudRegistry::ArtifactRegistry {
+            host: "us-central1-python.pkg.dev".to_string(),
+        };
+        let backend = CloudTokenBackend::Dummy(Mutex::new(["first", "second"].into()));
+        let cache = CloudTokenCache::default();
+        let (first, second) = tokio::join!(
+            cache.credentials(&registry, &backend, false),
+            cache.credentials(&registry, &backend, false),
+        );
+        assert_eq!(first.unwrap().password(), Some("first"));
+        assert_eq!(second.unwrap().password(), Some("first"));
+
+        // A refresh fetches a new token.
+        let refreshed = cache.credentials(&registry, &backend, true).await;
+        assert_eq!(refreshed.unwrap().password(), Some("second"));
+    }
+
+    /// An expired token embedded in the URL is replaced with a fresh one.
+    #[tokio::test]
+    async fn refresh_expired_token() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(basic_auth("oauth2accesstoken", "fresh"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let client = reqwest_middleware::ClientBuilder::new(Client::new())
+            .with(CloudAuthMiddleware::dummy(["fresh"]))
+            .build();
+
+        let mut url = Url::parse(&server.uri())?;
+        url.set_username("oauth2accesstoken").unwrap();
+        url.set_password(Some("expired")).unwrap();
+        assert_eq!(client.get(url.clone()).send().await?.status(), 200);
+
+        // Subsequent requests use the fresh token directly.
+        assert_eq!(client.get(url).send().await?.status(), 200);
+
+        Ok(())
+    }
+
+    /// Requests without credentials are authenticated with a token.
+    #[tokio::test]
+    async fn attach_token() -> Result<(), Box<dyn std::error::Error>> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(basic_auth("oauth2accesstoken", "token"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let client = reqwest_middleware::ClientBuilder::new(Client::new())
+            .with(CloudAuthMiddleware::dummy(["token"]))
+            .build();
+
+        assert_eq!(client.get(server.uri()).send().await?.status(), 200);
+        assert_eq!(
+            client
+                .get(format!("{}/foo", server.uri()))
+                .send()
+                .await?
+                .status(),
+            200,
+            "The token should be reused"
+        );
+
+        Ok(())
+    }
+}
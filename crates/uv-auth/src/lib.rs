@@ -1,4 +1,5 @@
 mod cache;
+mod cloud;
 mod credentials;
 mod keyring;
 mod middleware;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 use cache::CredentialsCache;
 use credentials::Credentials;
 
+pub use cloud::CloudAuthMiddleware;
 pub use keyring::KeyringProvider;
 pub use middleware::AuthMiddleware;
 use once_cell::sync::Lazy;
@@ -0,0 +1,132 @@
+//! Build wheels with a user-provided command instead of a PEP 517 build environment.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+use tokio::process::Command;
+use tracing::{debug, instrument};
+
+use uv_fs::Simplified;
+
+use crate::Error;
+
+/// A build that delegates to an external command (e.g., a hermetic Bazel or Nix wrapper).
+///
+/// The command is run through the platform shell with the source tree and the output directory
+/// appended as arguments, and is expected to print the path to the built wheel as the last line
+/// of its standard output.
+#[derive(Debug)]
+pub struct ExternalBuild {
+    command: String,
+    source_tree: PathBuf,
+    version_id: String,
+    environment_variables: FxHashMap<OsString, OsString>,
+}
+
+impl ExternalBuild {
+    /// Create an external build for the source tree at `source` (and optional `subdirectory`).
+    pub fn new(
+        command: String,
+        source: &Path,
+        subdirectory: Option<&Path>,
+        version_id: String,
+        environment_variables: FxHashMap<OsString, OsString>,
+    ) -> Self {
+        let source_tree = if let Some(subdir) = subdirectory {
+            source.join(subdir)
+        } else {
+            source.to_path_buf()
+        };
+        Self {
+            command,
+            source_tree,
+            version_id,
+            environment_variables,
+        }
+    }
+
+    /// Run the external command, returning the filename of the built wheel inside `wheel_dir`.
+    #[instrument(skip_all, fields(version_id = self.version_id))]
+    pub async fn build_wheel(&self, wheel_dir: &Path) -> Result<String, Error> {
+        debug!(
+            "Building {} with external command: {}",
+            self.version_id, self.command
+        );
+
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command
+                .arg("/C")
+                .arg(&self.command)
+                .arg(&self.source_tree)
+                .arg(wheel_dir);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(format!("{} \"$@\"", self.command))
+                .arg("sh")
+                .arg(&self.source_tree)
+                .arg(wheel_dir);
+            command
+        };
+        let output = command
+            .current_dir(&self.source_tree)
+            .envs(&self.environment_variables)
+            .output()
+            .await
+            .map_err(|err| Error::CommandFailed(PathBuf::from(&self.command), err))?;
+
+        if !output.status.success() {
+            return Err(Error::from_command_output(
+                format!(
+                    "External build command failed while building wheel for {}",
+                    self.version_id
+                ),
+                &output,
+                &self.version_id,
+            ));
+        }
+
+        // The last non-empty line of the output is the path to the wheel.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(line) = stdout.lines().map(str::trim).rfind(|line| !line.is_empty()) else {
+            return Err(Error::ExternalBuild(
+                self.command.clone(),
+                "no wheel path was printed".to_string(),
+            ));
+        };
+        let wheel = self.source_tree.join(line);
+        if !wheel
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("whl"))
+        {
+            return Err(Error::ExternalBuild(
+                self.command.clone(),
+                format!("`{}` is not a wheel", wheel.user_display()),
+            ));
+        }
+        let Some(filename) = wheel.file_name().and_then(|name| name.to_str()) else {
+            return Err(Error::ExternalBuild(
+                self.command.clone(),
+                format!("`{}` is not a valid wheel path", wheel.user_display()),
+            ));
+        };
+        if !wheel.is_file() {
+            return Err(Error::ExternalBuild(
+                self.command.clone(),
+                format!("`{}` does not exist", wheel.user_display()),
+            ));
+        }
+
+        // Move the wheel into the expected output directory, if the command placed it elsewhere.
+        let target = wheel_dir.join(filename);
+        if !target.is_file() {
+            fs_err::copy(&wheel, &target)?;
+        }
+
+        Ok(filename.to_string())
+    }
+}
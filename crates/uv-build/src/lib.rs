@@ -28,10 +28,18 @@ use tracing::{debug, info_span, instrument, Instrument};
 use distribution_types::{ParsedUrlError, Requirement, Resolution};
 use pep440_rs::Version;
 use pep508_rs::PackageName;
-use uv_configuration::{BuildKind, ConfigSettings, SetupPyStrategy};
+use uv_cache::CacheBucket;
+use uv_configuration::{BuildKind, ConfigSettings, PythonPreference, SetupPyStrategy};
 use uv_fs::{PythonExt, Simplified};
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_types::{BuildContext, BuildIsolation, SourceBuildTrait};
+use uv_warnings::warn_user;
+
+pub use crate::external::ExternalBuild;
+pub use crate::logs::BuildLog;
+
+mod external;
+mod logs;
 
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
 static MISSING_HEADER_RE_GCC: Lazy<Regex> = Lazy::new(|| {
@@ -118,6 +126,8 @@ pub enum Error {
     BuildScriptPath(#[source] env::JoinPathsError),
     #[error("Failed to parse requirements from build backend")]
     DirectUrl(#[source] Box<ParsedUrlError>),
+    #[error("External build command `{0}` did not produce a wheel: {1}")]
+    ExternalBuild(String, String),
 }
 
 #[derive(Debug)]
@@ -394,6 +404,8 @@ pub struct SourceBuild {
     environment_variables: FxHashMap<OsString, OsString>,
     /// Runner for Python scripts.
     runner: PythonRunner,
+    /// The directory in which to retain the output of failed builds.
+    build_logs: PathBuf,
 }
 
 impl SourceBuild {
@@ -416,7 +428,21 @@ impl SourceBuild {
         mut environment_variables: FxHashMap<OsString, OsString>,
         concurrent_builds: usize,
     ) -> Result<Self, Error> {
-        let temp_dir = tempdir_in(build_context.cache().root())?;
+        let cache = build_context.cache();
+        let temp_dir = tempdir_in(cache.build_dir().unwrap_or(cache.root()))?;
+
+        // If the user requested a dedicated build directory, stage the build backend's temporary
+        // files there too, rather than in the system's temporary directory.
+        if cache.build_dir().is_some() {
+            let tmp = temp_dir.path().join("tmp");
+            fs::create_dir(&tmp)?;
+            for key in ["TMPDIR", "TEMP", "TMP"] {
+                environment_variables
+                    .entry(OsString::from(key))
+                    .or_insert_with(|| tmp.clone().into_os_string());
+            }
+        }
+        let build_logs = build_context.cache().bucket(CacheBucket::BuildLogs);
 
         let source_tree = if let Some(subdir) = subdirectory {
             source.join(subdir)
@@ -438,6 +464,7 @@ impl SourceBuild {
                 interpreter.clone(),
                 uv_virtualenv::Prompt::None,
                 false,
+                PythonPreference::default(),
                 false,
             )?,
             BuildIsolation::Shared(venv) => venv.clone(),
@@ -510,7 +537,8 @@ impl SourceBuild {
                     &modified_path,
                     &temp_dir,
                 )
-                .await?;
+                .await
+                .map_err(|err| retain_build_logs(&build_logs, &version_id, &venv, err))?;
             }
         }
 
@@ -527,6 +555,7 @@ impl SourceBuild {
             environment_variables,
             modified_path,
             runner,
+            build_logs,
         })
     }
 
@@ -638,6 +667,11 @@ impl SourceBuild {
     /// Try calling `prepare_metadata_for_build_wheel` to get the metadata without executing the
     /// actual build.
     pub async fn get_metadata_without_build(&mut self) -> Result<Option<PathBuf>, Error> {
+        let result = self.prepare_metadata().await;
+        result.map_err(|err| retain_build_logs(&self.build_logs, &self.version_id, &self.venv, err))
+    }
+
+    async fn prepare_metadata(&mut self) -> Result<Option<PathBuf>, Error> {
         let Some(pep517_backend) = &self.pep517_backend else {
             return Ok(None);
         };
@@ -750,6 +784,12 @@ impl SourceBuild {
     /// <https://packaging.python.org/en/latest/specifications/source-distribution-format/>
     #[instrument(skip_all, fields(version_id = self.version_id))]
     pub async fn build_wheel(&self, wheel_dir: &Path) -> Result<String, Error> {
+        self.build(wheel_dir)
+            .await
+            .map_err(|err| retain_build_logs(&self.build_logs, &self.version_id, &self.venv, err))
+    }
+
+    async fn build(&self, wheel_dir: &Path) -> Result<String, Error> {
         // The build scripts run with the extracted root as cwd, so they need the absolute path.
         let wheel_dir = fs::canonicalize(wheel_dir)?;
 
@@ -897,6 +937,53 @@ impl SourceBuildTrait for SourceBuild {
     }
 }
 
+/// A source distribution build, either through a PEP 517 build environment or through a
+/// user-provided external command.
+pub enum AnySourceBuild {
+    Pep517(SourceBuild),
+    External(ExternalBuild),
+}
+
+impl SourceBuildTrait for AnySourceBuild {
+    async fn metadata(&mut self) -> anyhow::Result<Option<PathBuf>> {
+        match self {
+            Self::Pep517(build) => Ok(build.get_metadata_without_build().await?),
+            // External commands only produce wheels; the metadata is read from the built wheel.
+            Self::External(_) => Ok(None),
+        }
+    }
+
+    async fn wheel<'a>(&'a self, wheel_dir: &'a Path) -> anyhow::Result<String> {
+        match self {
+            Self::Pep517(build) => Ok(build.build_wheel(wheel_dir).await?),
+            Self::External(build) => Ok(build.build_wheel(wheel_dir).await?),
+        }
+    }
+}
+
+/// If the build backend failed, retain its output under the given directory and tell the user
+/// where to find it.
+fn retain_build_logs(
+    build_logs: &Path,
+    version_id: &str,
+    venv: &PythonEnvironment,
+    err: Error,
+) -> Error {
+    match logs::retain(build_logs, version_id, venv, &err) {
+        Ok(Some(path)) => {
+            warn_user!(
+                "Build logs for `{version_id}` were saved to: {}",
+                path.user_display()
+            );
+        }
+        Ok(None) => {}
+        Err(err) => {
+            debug!("Failed to retain build logs for `{version_id}`: {err}");
+        }
+    }
+    err
+}
+
 fn escape_path_for_python(path: &Path) -> String {
     path.to_string_lossy()
         .replace('\\', "\\\\")
@@ -0,0 +1,169 @@
+//! Retention of the output of failed builds, for later inspection.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use uv_interpreter::PythonEnvironment;
+
+use crate::Error;
+
+/// The metadata describing a retained build, stored as `build.json` in its directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildLog {
+    /// The package id of the distribution that failed to build, such as `foo-1.2.3`.
+    pub version_id: String,
+    /// The error message reported for the build.
+    pub message: String,
+    /// The exit status of the build backend.
+    pub exit_code: String,
+    /// The time at which the build failed, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The directory containing the retained logs.
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// Write the output of a failed build, along with the packages installed in its build
+/// environment, to a new directory under `root`.
+///
+/// Returns `None` if the error didn't originate from the build backend.
+pub(crate) fn retain(
+    root: &Path,
+    version_id: &str,
+    venv: &PythonEnvironment,
+    err: &Error,
+) -> io::Result<Option<PathBuf>> {
+    let (Error::BuildBackend {
+        message,
+        exit_code,
+        stdout,
+        stderr,
+    }
+    | Error::MissingHeader {
+        message,
+        exit_code,
+        stdout,
+        stderr,
+        ..
+    }) = err
+    else {
+        return Ok(None);
+    };
+
+    fs_err::create_dir_all(root)?;
+    let path = tempfile::Builder::new()
+        .prefix(&format!("{}-", sanitize(version_id)))
+        .tempdir_in(root)?
+        .into_path();
+
+    let log = BuildLog {
+        version_id: version_id.to_string(),
+        message: message.clone(),
+        exit_code: exit_code.to_string(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        path: path.clone(),
+    };
+    fs_err::write(path.join("stdout.log"), stdout)?;
+    fs_err::write(path.join("stderr.log"), stderr)?;
+    fs_err::write(path.join("packages.txt"), installed_packages(venv))?;
+    fs_err::write(
+        path.join("build.json"),
+        serde_json::to_string_pretty(&log).map_err(io::Error::other)?,
+    )?;
+
+    Ok(Some(path))
+}
+
+impl BuildLog {
+    /// List the builds retained under `root`, most recent first.
+    pub fn list(root: &Path) -> io::Result<Vec<Self>> {
+        let entries = match fs_err::read_dir(root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut logs = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let log = match fs_err::read(path.join("build.json")) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    debug!("Skipping build log directory without metadata: {err}");
+                    continue;
+                }
+            };
+            match serde_json::from_slice::<Self>(&log) {
+                Ok(log) => logs.push(Self { path, ..log }),
+                Err(err) => {
+                    debug!(
+                        "Skipping build log directory with invalid metadata `{}`: {err}",
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        logs.sort_by(|a, b| {
+            b.timestamp
+                .cmp(&a.timestamp)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        Ok(logs)
+    }
+}
+
+/// Render the packages installed in the build environment, one `name==version` per line.
+fn installed_packages(venv: &PythonEnvironment) -> String {
+    let mut packages = venv
+        .site_packages()
+        .filter_map(|site_packages| fs_err::read_dir(site_packages).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let stem = file_name.to_str()?.strip_suffix(".dist-info")?;
+            let (name, version) = stem.split_once('-')?;
+            Some(format!("{name}=={version}"))
+        })
+        .collect::<Vec<_>>();
+    packages.sort();
+
+    packages.into_iter().map(|package| package + "\n").collect()
+}
+
+/// Replace any characters that aren't safe for use in a directory name (e.g., in URLs).
+fn sanitize(version_id: &str) -> String {
+    version_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize;
+
+    #[test]
+    fn sanitize_version_id() {
+        assert_eq!(sanitize("anyio-4.3.0"), "anyio-4.3.0");
+        assert_eq!(
+            sanitize("file:///home/ferris/project"),
+            "file____home_ferris_project"
+        );
+    }
+}
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use crate::timestamp::Timestamp;
+use crate::timestamp::{FileStamp, Timestamp};
 
 #[derive(Deserialize, Serialize)]
 pub struct CachedByTimestamp<Data> {
     pub timestamp: Timestamp,
     pub data: Data,
 }
+
+/// Cached data, keyed by the [`FileStamp`] of the file from which it was derived.
+#[derive(Deserialize, Serialize)]
+pub struct CachedByFileStamp<Data> {
+    pub stamp: FileStamp,
+    pub data: Data,
+}
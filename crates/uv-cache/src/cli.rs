@@ -2,7 +2,6 @@ use std::io;
 use std::path::PathBuf;
 
 use clap::Parser;
-use directories::ProjectDirs;
 
 use crate::Cache;
 
@@ -25,33 +24,21 @@ pub struct CacheArgs {
     /// Linux, and `{FOLDERID_LocalAppData}\uv\cache` on Windows.
     #[arg(global = true, long, env = "UV_CACHE_DIR")]
     pub cache_dir: Option<PathBuf>,
-}
 
-impl Cache {
-    /// Prefer, in order:
-    /// 1. A temporary cache directory, if the user requested `--no-cache`.
-    /// 2. The specific cache directory specified by the user via `--cache-dir` or `UV_CACHE_DIR`.
-    /// 3. The system-appropriate cache directory.
-    /// 4. A `.uv_cache` directory in the current working directory.
+    /// Path to the directory in which to stage source distribution builds.
     ///
-    /// Returns an absolute cache dir.
-    pub fn from_settings(no_cache: bool, cache_dir: Option<PathBuf>) -> Result<Self, io::Error> {
-        if no_cache {
-            Cache::temp()
-        } else if let Some(cache_dir) = cache_dir {
-            Cache::from_path(cache_dir)
-        } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
-            Cache::from_path(project_dirs.cache_dir())
-        } else {
-            Cache::from_path(".uv_cache")
-        }
-    }
+    /// Build environments, and any temporary files created by the build backend, are placed in
+    /// this directory rather than in the cache directory (or, for temporary files, the system's
+    /// temporary directory). Defaults to the cache directory, which keeps builds on the same
+    /// filesystem as the cache.
+    #[arg(global = true, long, env = "UV_BUILD_DIR")]
+    pub build_dir: Option<PathBuf>,
 }
 
 impl TryFrom<CacheArgs> for Cache {
     type Error = io::Error;
 
     fn try_from(value: CacheArgs) -> Result<Self, Self::Error> {
-        Cache::from_settings(value.no_cache, value.cache_dir)
+        Ok(Cache::from_settings(value.no_cache, value.cache_dir)?.with_build_dir(value.build_dir))
     }
 }
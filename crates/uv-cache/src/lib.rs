@@ -1,10 +1,12 @@
 use std::cmp::max;
 use std::fmt::{Display, Formatter};
+use std::hash::Hasher;
 use std::io;
 use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use fs_err as fs;
 use rustc_hash::FxHashSet;
@@ -14,14 +16,14 @@ use tracing::debug;
 pub use archive::ArchiveId;
 use distribution_types::InstalledDist;
 use pypi_types::Metadata23;
-use uv_fs::{cachedir, directories};
+use uv_fs::{cachedir, directories, files};
 use uv_normalize::PackageName;
 
-pub use crate::by_timestamp::CachedByTimestamp;
+pub use crate::by_timestamp::{CachedByFileStamp, CachedByTimestamp};
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
 use crate::removal::{rm_rf, Removal};
-pub use crate::timestamp::Timestamp;
+pub use crate::timestamp::{FileStamp, Timestamp};
 pub use crate::wheel::WheelCache;
 use crate::wheel::WheelCacheKind;
 
@@ -33,6 +35,13 @@ mod removal;
 mod timestamp;
 mod wheel;
 
+/// The duration for which the logs of failed builds are retained, before `uv cache prune` removes
+/// them.
+const BUILD_LOG_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The duration for which cached resolutions are retained, before `uv cache prune` removes them.
+const RESOLUTION_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// A [`CacheEntry`] which may or may not exist yet.
 #[derive(Debug, Clone)]
 pub struct CacheEntry(PathBuf);
@@ -117,6 +126,10 @@ pub struct Cache {
     root: PathBuf,
     /// The refresh strategy to use when reading from the cache.
     refresh: Refresh,
+    /// The directory in which to stage source distribution builds, if not the cache directory.
+    build_dir: Option<PathBuf>,
+    /// The maximum time to wait for a lock on a cache entry, or `None` to wait indefinitely.
+    lock_timeout: Option<Duration>,
     /// A temporary cache directory, if the user requested `--no-cache`.
     ///
     /// Included to ensure that the temporary directory exists for the length of the operation, but
@@ -130,6 +143,8 @@ impl Cache {
         Ok(Self {
             root: root.into(),
             refresh: Refresh::None,
+            build_dir: None,
+            lock_timeout: None,
             _temp_dir_drop: None,
         })
     }
@@ -140,21 +155,73 @@ impl Cache {
         Ok(Self {
             root: temp_dir.path().to_path_buf(),
             refresh: Refresh::None,
+            build_dir: None,
+            lock_timeout: None,
             _temp_dir_drop: Some(Arc::new(temp_dir)),
         })
     }
 
+    /// Prefer, in order:
+    /// 1. A temporary cache directory, if the user requested `--no-cache`.
+    /// 2. The specific cache directory specified by the user via `--cache-dir` or `UV_CACHE_DIR`.
+    /// 3. The system-appropriate cache directory.
+    /// 4. A `.uv_cache` directory in the current working directory.
+    ///
+    /// Returns an absolute cache dir.
+    pub fn from_settings(no_cache: bool, cache_dir: Option<PathBuf>) -> Result<Self, io::Error> {
+        if no_cache {
+            Cache::temp()
+        } else if let Some(cache_dir) = cache_dir {
+            Cache::from_path(cache_dir)
+        } else if let Some(project_dirs) = ::directories::ProjectDirs::from("", "", "uv") {
+            Cache::from_path(project_dirs.cache_dir())
+        } else {
+            Cache::from_path(".uv_cache")
+        }
+    }
+
     /// Set the [`Refresh`] policy for the cache.
     #[must_use]
     pub fn with_refresh(self, refresh: Refresh) -> Self {
         Self { refresh, ..self }
     }
 
+    /// Set the directory in which to stage source distribution builds (i.e., build environments
+    /// and the build backend's temporary files).
+    ///
+    /// Extracted archives are always staged in the cache itself, such that they can be moved
+    /// (rather than copied) into place.
+    #[must_use]
+    pub fn with_build_dir(self, build_dir: Option<PathBuf>) -> Self {
+        Self { build_dir, ..self }
+    }
+
+    /// Set the maximum time to wait for a lock on a cache entry (or on an environment), rather
+    /// than waiting indefinitely.
+    #[must_use]
+    pub fn with_lock_timeout(self, lock_timeout: Option<Duration>) -> Self {
+        Self {
+            lock_timeout,
+            ..self
+        }
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
     }
 
+    /// Return the directory in which to stage source distribution builds, if the user requested
+    /// one other than the cache directory.
+    pub fn build_dir(&self) -> Option<&Path> {
+        self.build_dir.as_deref()
+    }
+
+    /// Return the maximum time to wait for a lock, if the user requested one.
+    pub fn lock_timeout(&self) -> Option<Duration> {
+        self.lock_timeout
+    }
+
     /// The folder for a specific cache bucket
     pub fn bucket(&self, cache_bucket: CacheBucket) -> PathBuf {
         self.root.join(cache_bucket.to_str())
@@ -289,8 +356,17 @@ impl Cache {
             .write(true)
             .open(root.join(CacheBucket::BuiltWheels.to_str()).join(".git"))?;
 
+        // Create the build directory, if it doesn't exist.
+        let build_dir = if let Some(build_dir) = &self.build_dir {
+            fs::create_dir_all(build_dir)?;
+            Some(fs::canonicalize(build_dir)?)
+        } else {
+            None
+        };
+
         Ok(Self {
             root: fs::canonicalize(root)?,
+            build_dir,
             ..self
         })
     }
@@ -349,6 +425,11 @@ impl Cache {
         let mut references = FxHashSet::default();
 
         for bucket in CacheBucket::iter() {
+            // Environments never reference archives, but may contain symlinks to interpreters
+            // that no longer exist.
+            if matches!(bucket, CacheBucket::Environments) {
+                continue;
+            }
             let bucket = self.bucket(bucket);
             if bucket.is_dir() {
                 for entry in walkdir::WalkDir::new(bucket) {
@@ -369,6 +450,52 @@ impl Cache {
             }
         }
 
+        // Third, remove any environments whose base interpreter no longer exists.
+        for path in directories(self.bucket(CacheBucket::Environments)) {
+            let Ok(contents) = fs::read_to_string(path.join("pyvenv.cfg")) else {
+                continue;
+            };
+            let home = contents
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key.trim() == "home")
+                .map(|(_, value)| PathBuf::from(value.trim()));
+            if home.is_some_and(|home| !home.exists()) {
+                debug!("Removing dangling cache environment: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+
+        // Fourth, remove any build logs that are older than the retention period.
+        let now = SystemTime::now();
+        for path in directories(self.bucket(CacheBucket::BuildLogs)) {
+            let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+                continue;
+            };
+            if now
+                .duration_since(modified)
+                .is_ok_and(|age| age > BUILD_LOG_RETENTION)
+            {
+                debug!("Removing expired build log: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+
+        // Fifth, remove any cached resolutions that are older than the retention period, as
+        // they're likely to be stale with respect to the index.
+        for path in files(self.bucket(CacheBucket::Resolutions)) {
+            let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+                continue;
+            };
+            if now
+                .duration_since(modified)
+                .is_ok_and(|age| age > RESOLUTION_RETENTION)
+            {
+                debug!("Removing expired resolution: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+
         Ok(summary)
     }
 }
@@ -605,6 +732,59 @@ pub enum CacheBucket {
     /// that cache entries can be atomically replaced and removed, as storing directories in the
     /// other buckets directly would make atomic operations impossible.
     Archive,
+    /// Compiled bytecode (`.pyc`) files, shared across environments.
+    ///
+    /// Files are partitioned by the interpreter's magic number (which changes whenever the
+    /// bytecode format does), and keyed by a digest of the source file's contents, along with the
+    /// optimization level and invalidation mode (and, for timestamp-based bytecode, the source
+    /// file's modification time and size). When compiling an environment to bytecode, cached files
+    /// are hardlinked into `__pycache__`, rather than recompiled.
+    ///
+    /// Cache structure: `bytecode-v0/<magic number>/<digest>.pyc`
+    Bytecode,
+    /// Virtual environments built for ephemeral requirements (e.g., by `uv tool run`), reused
+    /// across invocations.
+    ///
+    /// Environments are keyed by a digest of the interpreter, the resolved set of pinned
+    /// distributions, and the settings that affect their installation. Unlike other buckets, environments are stored as directories directly, since
+    /// virtual environments can't be relocated once created; an environment is only reused once
+    /// its `pyvenv.cfg` records the matching key, which is written after installation completes.
+    ///
+    /// Cache structure: `environments-v1/<digest>/`, alongside a `<digest>.lock` file.
+    Environments,
+    /// Resolutions, reused across invocations with identical inputs (e.g., via
+    /// `uv pip install --cache-resolution`).
+    ///
+    /// Resolutions are keyed by a digest of the resolver inputs (requirements, constraints,
+    /// overrides, indexes, markers, tags, `--exclude-newer`, and so on), and stored in the `uv.lock`
+    /// format.
+    ///
+    /// Resolutions older than a day are removed by `uv cache prune`.
+    ///
+    /// Cache structure: `resolutions-v0/<digest>.toml`.
+    Resolutions,
+    /// The output of failed source distribution builds, retained for later inspection (e.g., via
+    /// `uv cache builds list`).
+    ///
+    /// Each failed build is stored in its own directory, named after the distribution that was
+    /// being built, containing the build backend's standard output and standard error, the
+    /// packages installed in the build environment, and a `build.json` file describing the
+    /// failure.
+    ///
+    /// Logs older than a week are removed by `uv cache prune`.
+    ///
+    /// Cache structure: `build-logs-v0/<version-id>-<random>/{build.json, stdout.log, stderr.log, packages.txt}`.
+    BuildLogs,
+    /// Wheels built from local source trees for editable installs, reused across environments.
+    ///
+    /// Editable wheels are keyed by a digest of the path to the source tree, followed by a digest
+    /// of the interpreter (its base prefix, implementation, and version). Each entry contains the
+    /// built wheel, its metadata, and a pointer recording the filename of the wheel and a digest
+    /// of the contents of the source tree when it was built; the wheel is only reused if the
+    /// contents of the source tree are unchanged.
+    ///
+    /// Cache structure: `editables-v1/<digest(path)>/<digest(interpreter)>/{editable.msgpack, metadata.msgpack, <wheel>.whl}`.
+    Editables,
 }
 
 impl CacheBucket {
@@ -613,10 +793,15 @@ impl CacheBucket {
             Self::BuiltWheels => "built-wheels-v3",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v1",
+            Self::Interpreter => "interpreter-v2",
             Self::Simple => "simple-v7",
             Self::Wheels => "wheels-v1",
             Self::Archive => "archive-v0",
+            Self::Bytecode => "bytecode-v0",
+            Self::Environments => "environments-v1",
+            Self::Resolutions => "resolutions-v0",
+            Self::BuildLogs => "build-logs-v0",
+            Self::Editables => "editables-v1",
         }
     }
 
@@ -727,6 +912,41 @@ impl CacheBucket {
             Self::Archive => {
                 // Nothing to do.
             }
+            Self::Bytecode => {
+                // Nothing to do.
+            }
+            Self::Environments => {
+                // Nothing to do.
+            }
+            Self::Resolutions => {
+                // For resolutions, we expect a `uv.lock`-formatted file per set of inputs. To
+                // determine whether the resolution is relevant, we need to search for a
+                // distribution matching the package name.
+                let needle = format!("name = \"{name}\"");
+                let root = cache.bucket(self);
+                for path in files(root) {
+                    let Ok(contents) = fs_err::read_to_string(&path) else {
+                        continue;
+                    };
+                    if contents.lines().any(|line| line.trim() == needle) {
+                        summary += rm_rf(path)?;
+                    }
+                }
+            }
+            Self::BuildLogs => {
+                // Nothing to do.
+            }
+            Self::Editables => {
+                // For editables, we expect a directory for every source tree, followed by a
+                // directory per interpreter. To determine whether the source tree is relevant, we
+                // need to search for a wheel matching the package name.
+                let root = cache.bucket(self);
+                for path in directories(root) {
+                    if directories(&path).any(|interpreter| is_match(&interpreter, name)) {
+                        summary += rm_rf(path)?;
+                    }
+                }
+            }
         }
         Ok(summary)
     }
@@ -741,6 +961,11 @@ impl CacheBucket {
             CacheBucket::Interpreter,
             CacheBucket::Simple,
             CacheBucket::Archive,
+            CacheBucket::Bytecode,
+            CacheBucket::Environments,
+            CacheBucket::Resolutions,
+            CacheBucket::BuildLogs,
+            CacheBucket::Editables,
         ]
         .iter()
         .copied()
@@ -758,7 +983,8 @@ pub enum ArchiveTimestamp {
     /// The archive consists of a single file with the given modification time.
     Exact(Timestamp),
     /// The archive consists of a directory. The modification time is the latest modification time
-    /// of the `pyproject.toml` or `setup.py` file in the directory.
+    /// of the `pyproject.toml` or `setup.py` file in the directory or, if the contents of the
+    /// directory are considered, of any file in the directory.
     Approximate(Timestamp),
 }
 
@@ -825,6 +1051,39 @@ impl ArchiveTimestamp {
         Ok(Some(Self::Approximate(timestamp)))
     }
 
+    /// Return the modification timestamp for the contents of a source tree, i.e., a directory.
+    ///
+    /// Unlike [`ArchiveTimestamp::from_source_tree`], which only considers the build entrypoints,
+    /// this considers every file in the source tree, such that any change to the sources
+    /// invalidates the timestamp. Version control metadata, virtual environments, caches, and
+    /// build artifacts (which are written to the root of the source tree by some build backends)
+    /// are ignored.
+    ///
+    /// Walks the entire source tree, so should be run on a blocking thread from async code.
+    ///
+    /// If the source tree doesn't contain an entrypoint (i.e., no `pyproject.toml`, `setup.py`, or
+    /// `setup.cfg`), returns `None`.
+    pub fn from_source_tree_contents(path: impl AsRef<Path>) -> Result<Option<Self>, io::Error> {
+        let Some(entrypoint) = Self::from_source_tree(path.as_ref())? else {
+            return Ok(None);
+        };
+
+        // Take the most recent timestamp of any file in the source tree. Directories are skipped,
+        // since building a source tree may create new directories within it (e.g., `build`).
+        let mut timestamp = entrypoint.timestamp();
+        for entry in walkdir::WalkDir::new(path.as_ref())
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_ignored_source_entry(entry))
+        {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                timestamp = max(timestamp, Timestamp::from_metadata(&entry.metadata()?));
+            }
+        }
+
+        Ok(Some(Self::Approximate(timestamp)))
+    }
+
     /// Return the modification timestamp for an archive.
     pub fn timestamp(&self) -> Timestamp {
         match self {
@@ -841,7 +1100,29 @@ impl ArchiveTimestamp {
     ///
     /// The `source` is a source archive, i.e., a path to a built wheel or a Python package directory.
     pub fn up_to_date_with(source: &Path, target: ArchiveTarget) -> Result<bool, io::Error> {
-        let Some(modified_at) = Self::from_path(source)? else {
+        Self::is_up_to_date(Self::from_path(source)?, target)
+    }
+
+    /// Returns `true` if the `target` (an installed or cached distribution) is up-to-date with the
+    /// contents of the source archive (`source`).
+    ///
+    /// Like [`ArchiveTimestamp::up_to_date_with`], but if the `source` is a directory, any change
+    /// to the files within it is considered, rather than changes to the build entrypoints alone.
+    pub fn contents_up_to_date_with(
+        source: &Path,
+        target: ArchiveTarget,
+    ) -> Result<bool, io::Error> {
+        let modified_at = if source.is_dir() {
+            Self::from_source_tree_contents(source)?
+        } else {
+            Self::from_path(source)?
+        };
+        Self::is_up_to_date(modified_at, target)
+    }
+
+    /// Returns `true` if the `target` was created after the source was last modified.
+    fn is_up_to_date(modified_at: Option<Self>, target: ArchiveTarget) -> Result<bool, io::Error> {
+        let Some(modified_at) = modified_at else {
             // If there's no entrypoint, we can't determine the modification time, so we assume that the
             // target is not up-to-date.
             return Ok(false);
@@ -856,6 +1137,89 @@ impl ArchiveTimestamp {
     }
 }
 
+/// Compute a digest of the contents of a source tree, i.e., a directory.
+///
+/// The digest covers the relative path and contents of every file in the source tree, ignoring the
+/// same entries as [`ArchiveTimestamp::from_source_tree_contents`]. Unlike a modification time,
+/// the digest is unaffected by operations that touch files without changing them (e.g., switching
+/// branches and back), and detects changes that preserve the modification time.
+///
+/// Reads every file in the source tree, so should be run on a blocking thread from async code.
+///
+/// If the source tree doesn't contain an entrypoint (i.e., no `pyproject.toml`, `setup.py`, or
+/// `setup.cfg`), returns `None`.
+pub fn source_tree_digest(path: impl AsRef<Path>) -> Result<Option<String>, io::Error> {
+    let path = path.as_ref();
+    if ArchiveTimestamp::from_source_tree(path)?.is_none() {
+        return Ok(None);
+    }
+
+    let mut hasher = cache_key::StableHasher::new();
+    for entry in walkdir::WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_ignored_source_entry(entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        // Hash the relative path with `/` separators, followed by the contents of the file.
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .expect("walkdir starts with root")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        hasher.write_u64(relative.len() as u64);
+        hasher.write(relative.as_bytes());
+
+        let contents = fs::read(entry.path())?;
+        hasher.write_u64(contents.len() as u64);
+        hasher.write(&contents);
+    }
+
+    Ok(Some(format!("{:016x}", hasher.finish())))
+}
+
+/// Returns `true` if the entry should be ignored when determining the modification time of a
+/// source tree.
+///
+/// Build artifacts and virtual environments are only ignored at the root of the source tree, such
+/// that a package named (e.g.) `build` is still considered.
+fn is_ignored_source_entry(entry: &walkdir::DirEntry) -> bool {
+    let Some(name) = entry.file_name().to_str() else {
+        return false;
+    };
+    if entry.file_type().is_dir() {
+        matches!(
+            name,
+            ".git"
+                | ".hg"
+                | ".svn"
+                | ".tox"
+                | ".nox"
+                | ".venv"
+                | "__pycache__"
+                | ".mypy_cache"
+                | ".pytest_cache"
+                | ".ruff_cache"
+                | "node_modules"
+        ) || (entry.depth() == 1 && matches!(name, "build" | "dist" | "venv"))
+            || name.ends_with(".egg-info")
+            || entry.path().join("pyvenv.cfg").is_file()
+    } else {
+        // Compiled bytecode doesn't affect the build.
+        entry
+            .path()
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pyc"))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ArchiveTarget<'a> {
     /// The target is an installed package in a virtual environment.
@@ -9,7 +9,8 @@ use std::path::Path;
 /// the number of files and directories removed, along with a total byte count.
 pub(crate) fn rm_rf(path: impl AsRef<Path>) -> io::Result<Removal> {
     let mut removal = Removal::default();
-    removal.rm_rf(path.as_ref())?;
+    // Cache entries (e.g., unzipped wheels) may contain paths that exceed `MAX_PATH`.
+    removal.rm_rf(&uv_fs::verbatim_path(path.as_ref()))?;
     Ok(removal)
 }
 
@@ -44,3 +44,29 @@ impl Timestamp {
         Self(std::time::SystemTime::now())
     }
 }
+
+/// The identity of a file at a point in time, as determined by its [`Timestamp`] and size.
+///
+/// The size guards against changes that preserve the timestamp, such as an in-place upgrade that
+/// restores the original modification time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileStamp {
+    timestamp: Timestamp,
+    size: u64,
+}
+
+impl FileStamp {
+    /// Return the [`FileStamp`] for the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let metadata = path.as_ref().metadata()?;
+        Ok(Self::from_metadata(&metadata))
+    }
+
+    /// Return the [`FileStamp`] for the given metadata.
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            timestamp: Timestamp::from_metadata(metadata),
+            size: metadata.len(),
+        }
+    }
+}
@@ -1,29 +1,95 @@
 use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
 use reqwest::{Client, ClientBuilder};
-use reqwest_middleware::ClientWithMiddleware;
+use reqwest_middleware::{ClientWithMiddleware, Middleware};
 use reqwest_retry::policies::ExponentialBackoff;
 use reqwest_retry::RetryTransientMiddleware;
 use std::env;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
-use uv_auth::AuthMiddleware;
-use uv_configuration::KeyringProviderType;
+use uv_auth::{AuthMiddleware, CloudAuthMiddleware};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, KeyringProviderType, TrustedHost,
+};
 use uv_fs::Simplified;
 use uv_version::version;
 use uv_warnings::warn_user_once;
 
+use crate::limits::ResponseLimits;
 use crate::linehaul::LineHaul;
-use crate::middleware::OfflineMiddleware;
+use crate::middleware::{
+    trusted_host_redirect_policy, Http1FallbackMiddleware, OfflineMiddleware, RateLimitMiddleware,
+    TrustedHostMiddleware,
+};
+use crate::stats::NetworkRecorder;
 use crate::Connectivity;
 
+/// The timeouts to apply to network requests, in seconds.
+///
+/// Unset values fall back to the defaults: a 30s read timeout, with no separate connect timeout or
+/// overall deadline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkTimeouts {
+    /// The maximum time to wait for data on an open connection.
+    pub read: Option<u64>,
+    /// The maximum time to wait to establish a connection, including the TLS handshake.
+    pub connect: Option<u64>,
+    /// The maximum time for a request to complete, from connecting to reading the full body.
+    pub deadline: Option<u64>,
+}
+
+impl NetworkTimeouts {
+    /// Fill in any unset timeouts from the defaults.
+    fn resolve(self) -> Self {
+        Self {
+            read: Some(self.read.unwrap_or(30)),
+            ..self
+        }
+    }
+}
+
+/// Additional middleware to wrap a client with, as provided by library consumers (e.g., to sign
+/// requests, apply a custom authentication scheme, or record metrics).
+///
+/// The middleware runs after uv's retry and authentication middleware, in the order in which it
+/// was added, such that it sees each attempt of a request (with any credentials attached).
+#[derive(Clone, Default)]
+pub struct ExtraMiddleware(Vec<Arc<dyn Middleware>>);
+
+impl ExtraMiddleware {
+    /// Add a middleware to the end of the chain.
+    pub fn push(&mut self, middleware: impl Middleware) {
+        self.0.push(Arc::new(middleware));
+    }
+}
+
+impl Debug for ExtraMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtraMiddleware")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
 /// A builder for an [`BaseClient`].
 #[derive(Debug, Clone)]
 pub struct BaseClientBuilder<'a> {
     keyring: KeyringProviderType,
+    hermetic: bool,
     native_tls: bool,
+    timeouts: NetworkTimeouts,
+    host_overrides: Vec<HostOverride>,
+    trusted_hosts: Vec<TrustedHost>,
+    rate_limits: Vec<IndexRateLimit>,
+    http_version: Option<HttpVersion>,
+    extra_middleware: ExtraMiddleware,
+    network_recorder: NetworkRecorder,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
     retries: u32,
     connectivity: Connectivity,
     client: Option<Client>,
@@ -41,7 +107,17 @@ impl BaseClientBuilder<'_> {
     pub fn new() -> Self {
         Self {
             keyring: KeyringProviderType::default(),
+            hermetic: false,
             native_tls: false,
+            timeouts: NetworkTimeouts::default(),
+            host_overrides: Vec::new(),
+            trusted_hosts: Vec::new(),
+            rate_limits: Vec::new(),
+            http_version: None,
+            extra_middleware: ExtraMiddleware::default(),
+            network_recorder: NetworkRecorder::default(),
+            index_max_age: None,
+            response_limits: ResponseLimits::default(),
             connectivity: Connectivity::Online,
             retries: 3,
             client: None,
@@ -58,6 +134,15 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    /// Ignore credentials from the ambient environment, i.e., netrc files and the cloud provider
+    /// CLIs, such that requests are only authenticated with credentials embedded in the index
+    /// URLs or fetched from an explicitly configured keyring.
+    #[must_use]
+    pub fn hermetic(mut self, hermetic: bool) -> Self {
+        self.hermetic = hermetic;
+        self
+    }
+
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
         self.connectivity = connectivity;
@@ -76,6 +161,80 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    #[must_use]
+    pub fn timeouts(mut self, timeouts: NetworkTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    #[must_use]
+    pub fn host_overrides(mut self, host_overrides: Vec<HostOverride>) -> Self {
+        self.host_overrides = host_overrides;
+        self
+    }
+
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
+    #[must_use]
+    pub fn rate_limits(mut self, rate_limits: Vec<IndexRateLimit>) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Set the HTTP version to use for requests. If unset, only HTTP/1.1 is used.
+    #[must_use]
+    pub fn http_version(mut self, http_version: Option<HttpVersion>) -> Self {
+        self.http_version = http_version;
+        self
+    }
+
+    /// Wrap the client with the given middleware, e.g., to sign requests or record metrics.
+    ///
+    /// May be called multiple times; the middleware runs in the order in which it was added. It
+    /// isn't used in offline mode.
+    #[must_use]
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.extra_middleware.push(middleware);
+        self
+    }
+
+    #[must_use]
+    pub fn extra_middleware(mut self, extra_middleware: ExtraMiddleware) -> Self {
+        self.extra_middleware = extra_middleware;
+        self
+    }
+
+    /// Record HTTP cache usage and network traffic to the given [`NetworkRecorder`], e.g., to
+    /// share it with other clients.
+    #[must_use]
+    pub fn network_recorder(mut self, network_recorder: NetworkRecorder) -> Self {
+        self.network_recorder = network_recorder;
+        self
+    }
+
+    /// Set the maximum age (in seconds) of cached Simple API and `--find-links` responses.
+    ///
+    /// Cached responses older than this are revalidated against the index, even if the server
+    /// considers them fresh.
+    #[must_use]
+    pub fn index_max_age(mut self, index_max_age: Option<u64>) -> Self {
+        self.index_max_age = index_max_age;
+        self
+    }
+
+    /// Set the maximum size of the Simple API pages and `METADATA` files read from an index.
+    ///
+    /// Any unset limits fall back to the defaults.
+    #[must_use]
+    pub fn response_limits(mut self, response_limits: ResponseLimits) -> Self {
+        self.response_limits = response_limits;
+        self
+    }
+
     #[must_use]
     pub fn client(mut self, client: Client) -> Self {
         self.client = Some(client);
@@ -110,52 +269,27 @@ impl<'a> BaseClientBuilder<'a> {
             }
         }
 
-        // Timeout options, matching https://doc.rust-lang.org/nightly/cargo/reference/config.html#httptimeout
-        // `UV_REQUEST_TIMEOUT` is provided for backwards compatibility with v0.1.6
-        let default_timeout = 30;
-        let timeout = env::var("UV_HTTP_TIMEOUT")
-            .or_else(|_| env::var("UV_REQUEST_TIMEOUT"))
-            .or_else(|_| env::var("HTTP_TIMEOUT"))
-            .and_then(|value| {
-                value.parse::<u64>()
-                    .or_else(|_| {
-                        // On parse error, warn and use the default timeout
-                        warn_user_once!("Ignoring invalid value from environment for UV_HTTP_TIMEOUT. Expected integer number of seconds, got \"{value}\".");
-                        Ok(default_timeout)
-                    })
-            })
-            .unwrap_or(default_timeout);
-        debug!("Using registry request timeout of {timeout}s");
+        let timeouts = self.timeouts.resolve();
+        debug!(
+            "Using registry request timeout of {}s",
+            timeouts.read.unwrap_or_default()
+        );
+        if let Some(connect) = timeouts.connect {
+            debug!("Using connect timeout of {connect}s");
+        }
+        if let Some(deadline) = timeouts.deadline {
+            debug!("Using request deadline of {deadline}s");
+        }
+        let http_version = self.http_version.unwrap_or_default();
+        if http_version == HttpVersion::Auto {
+            debug!("Negotiating the HTTP version with each server");
+        } else {
+            debug!("Using {http_version} for all requests");
+        }
 
         // Initialize the base client.
         let client = self.client.clone().unwrap_or_else(|| {
-            // Check for the presence of an `SSL_CERT_FILE`.
-            let ssl_cert_file_exists = env::var_os("SSL_CERT_FILE").is_some_and(|path| {
-                let path_exists = Path::new(&path).exists();
-                if !path_exists {
-                    warn_user_once!(
-                        "Ignoring invalid `SSL_CERT_FILE`. File does not exist: {}.",
-                        path.simplified_display()
-                    );
-                }
-                path_exists
-            });
-
-            // Configure the builder.
-            let client_core = ClientBuilder::new()
-                .user_agent(user_agent_string)
-                .pool_max_idle_per_host(20)
-                .read_timeout(std::time::Duration::from_secs(timeout))
-                .tls_built_in_root_certs(false);
-
-            // Configure TLS.
-            let client_core = if self.native_tls || ssl_cert_file_exists {
-                client_core.tls_built_in_native_certs(true)
-            } else {
-                client_core.tls_built_in_webpki_certs(true)
-            };
-
-            client_core.build().expect("Failed to build HTTP client.")
+            self.create_client(&user_agent_string, timeouts, http_version, false)
         });
 
         // Wrap in any relevant middleware.
@@ -170,8 +304,69 @@ impl<'a> BaseClientBuilder<'a> {
                 let client = client.with(retry_strategy);
 
                 // Initialize the authentication middleware to set headers.
-                let client =
-                    client.with(AuthMiddleware::new().with_keyring(self.keyring.to_provider()));
+                let mut auth = AuthMiddleware::new().with_keyring(self.keyring.to_provider());
+                if self.hermetic {
+                    debug!("Ignoring netrc and cloud provider credentials in hermetic mode");
+                    auth = auth.with_netrc(None);
+                }
+                let mut client = client.with(auth);
+
+                // Authenticate requests to cloud-hosted indexes (i.e., AWS CodeArtifact, GCP
+                // Artifact Registry, and Azure Artifacts) with short-lived tokens, refreshing them
+                // as they expire.
+                if !self.hermetic {
+                    client = client.with(CloudAuthMiddleware::new());
+                }
+
+                // Apply any middleware provided by the caller.
+                for middleware in &self.extra_middleware.0 {
+                    client = client.with_arc(middleware.clone());
+                }
+
+                // Cap the rate of requests to any rate-limited hosts. This comes after the retry
+                // middleware, such that retries are rate-limited too.
+                if !self.rate_limits.is_empty() {
+                    for limit in &self.rate_limits {
+                        debug!("Using rate limit: {limit}");
+                    }
+                    client = client.with(RateLimitMiddleware::new(self.rate_limits.clone()));
+                }
+
+                // If the version is negotiated, retry requests that fail with an HTTP/2 protocol
+                // error over HTTP/1.1.
+                if http_version == HttpVersion::Auto && self.client.is_none() {
+                    let mut fallback = Http1FallbackMiddleware::new(self.create_client(
+                        &user_agent_string,
+                        timeouts,
+                        HttpVersion::Http1,
+                        false,
+                    ));
+                    if !self.trusted_hosts.is_empty() {
+                        fallback = fallback.with_trusted_hosts(
+                            self.trusted_hosts.clone(),
+                            self.create_client(
+                                &user_agent_string,
+                                timeouts,
+                                HttpVersion::Http1,
+                                true,
+                            ),
+                        );
+                    }
+                    client = client.with(fallback);
+                }
+
+                // Send requests to trusted hosts through a client that skips TLS verification.
+                // This must come last, as it short-circuits any subsequent middleware.
+                if !self.trusted_hosts.is_empty() {
+                    warn_user_once!(
+                        "TLS certificate verification is disabled for: {}. Connections to these hosts are vulnerable to interception.",
+                        self.trusted_hosts.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+                    );
+                    client = client.with(TrustedHostMiddleware::new(
+                        self.trusted_hosts.clone(),
+                        self.create_client(&user_agent_string, timeouts, http_version, true),
+                    ));
+                }
 
                 client.build()
             }
@@ -183,9 +378,77 @@ impl<'a> BaseClientBuilder<'a> {
         BaseClient {
             connectivity: self.connectivity,
             client,
-            timeout,
+            timeouts,
+            network_recorder: self.network_recorder.clone(),
+            index_max_age: self.index_max_age,
+            response_limits: self.response_limits.with_defaults(),
         }
     }
+
+    /// Create the underlying [`Client`]. If `insecure` is set, TLS certificates are not verified.
+    fn create_client(
+        &self,
+        user_agent: &str,
+        timeouts: NetworkTimeouts,
+        http_version: HttpVersion,
+        insecure: bool,
+    ) -> Client {
+        // Check for the presence of an `SSL_CERT_FILE`.
+        let ssl_cert_file_exists = env::var_os("SSL_CERT_FILE").is_some_and(|path| {
+            let path_exists = Path::new(&path).exists();
+            if !path_exists {
+                warn_user_once!(
+                    "Ignoring invalid `SSL_CERT_FILE`. File does not exist: {}.",
+                    path.simplified_display()
+                );
+            }
+            path_exists
+        });
+
+        // Configure the builder.
+        let mut client_core = ClientBuilder::new()
+            .user_agent(user_agent)
+            .pool_max_idle_per_host(20)
+            .read_timeout(Duration::from_secs(timeouts.read.unwrap_or_default()))
+            .tls_built_in_root_certs(false);
+        if let Some(connect) = timeouts.connect {
+            client_core = client_core.connect_timeout(Duration::from_secs(connect));
+        }
+        if let Some(deadline) = timeouts.deadline {
+            client_core = client_core.timeout(Duration::from_secs(deadline));
+        }
+
+        // Restrict the HTTP version, unless it's negotiated with the server.
+        match http_version {
+            HttpVersion::Http1 => client_core = client_core.http1_only(),
+            HttpVersion::Http2 => client_core = client_core.http2_prior_knowledge(),
+            HttpVersion::Auto => {}
+        }
+
+        // Pin any overridden hosts to the given addresses, bypassing DNS.
+        for host_override in &self.host_overrides {
+            debug!("Using DNS override: {host_override}");
+            client_core =
+                client_core.resolve_to_addrs(host_override.host(), &host_override.socket_addrs());
+        }
+
+        // Configure TLS.
+        let client_core = if self.native_tls || ssl_cert_file_exists {
+            client_core.tls_built_in_native_certs(true)
+        } else {
+            client_core.tls_built_in_webpki_certs(true)
+        };
+        let mut client_core = client_core.danger_accept_invalid_certs(insecure);
+
+        // Don't let a trusted host redirect requests to hosts that aren't trusted, which would
+        // otherwise be reached without verification.
+        if insecure {
+            client_core =
+                client_core.redirect(trusted_host_redirect_policy(self.trusted_hosts.clone()));
+        }
+
+        client_core.build().expect("Failed to build HTTP client.")
+    }
 }
 
 /// A base client for HTTP requests
@@ -195,8 +458,14 @@ pub struct BaseClient {
     client: ClientWithMiddleware,
     /// The connectivity mode to use.
     connectivity: Connectivity,
-    /// Configured client timeout, in seconds.
-    timeout: u64,
+    /// The configured timeouts, with any defaults filled in.
+    timeouts: NetworkTimeouts,
+    /// The recorder for HTTP cache usage and network traffic.
+    network_recorder: NetworkRecorder,
+    /// The maximum age of cached index responses, in seconds.
+    index_max_age: Option<u64>,
+    /// The configured response size limits, with any defaults filled in.
+    response_limits: ResponseLimits,
 }
 
 impl BaseClient {
@@ -207,13 +476,33 @@ impl BaseClient {
 
     /// The configured client timeout, in seconds.
     pub fn timeout(&self) -> u64 {
-        self.timeout
+        self.timeouts.read.unwrap_or_default()
+    }
+
+    /// The configured timeouts, in seconds.
+    pub fn timeouts(&self) -> NetworkTimeouts {
+        self.timeouts
     }
 
     /// The configured connectivity mode.
     pub fn connectivity(&self) -> Connectivity {
         self.connectivity
     }
+
+    /// The recorder for HTTP cache usage and network traffic.
+    pub fn network_recorder(&self) -> &NetworkRecorder {
+        &self.network_recorder
+    }
+
+    /// The maximum age of cached index responses, in seconds.
+    pub(crate) fn index_max_age(&self) -> Option<u64> {
+        self.index_max_age
+    }
+
+    /// The configured response size limits.
+    pub(crate) fn response_limits(&self) -> ResponseLimits {
+        self.response_limits
+    }
 }
 
 // To avoid excessively verbose call chains, as the [`BaseClient`] is often nested within other client types.
@@ -10,6 +10,7 @@ use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use uv_cache::{CacheEntry, Freshness};
 use uv_fs::write_atomic;
 
+use crate::stats::NetworkRecorder;
 use crate::BaseClient;
 use crate::{
     httpcache::{AfterResponse, BeforeRequest, CachePolicy, CachePolicyBuilder},
@@ -170,6 +171,11 @@ impl CachedClient {
         self.0.clone()
     }
 
+    /// The recorder for HTTP cache usage and network traffic.
+    pub fn network_recorder(&self) -> &NetworkRecorder {
+        self.0.network_recorder()
+    }
+
     /// Make a cached request with a custom response transformation
     /// while using serde to (de)serialize cached responses.
     ///
@@ -244,18 +250,22 @@ impl CachedClient {
             }
         };
         match cached_response {
-            CachedResponse::FreshCache(cached) => match Payload::from_aligned_bytes(cached.data) {
-                Ok(payload) => Ok(payload),
-                Err(err) => {
-                    warn!(
-                        "Broken fresh cache entry (for payload) at {}, removing: {err}",
-                        cache_entry.path().display()
-                    );
-                    self.resend_and_heal_cache(fresh_req, cache_entry, response_callback)
-                        .await
+            CachedResponse::FreshCache(cached) => {
+                self.0.network_recorder().record_cache_hit();
+                match Payload::from_aligned_bytes(cached.data) {
+                    Ok(payload) => Ok(payload),
+                    Err(err) => {
+                        warn!(
+                            "Broken fresh cache entry (for payload) at {}, removing: {err}",
+                            cache_entry.path().display()
+                        );
+                        self.resend_and_heal_cache(fresh_req, cache_entry, response_callback)
+                            .await
+                    }
                 }
-            },
+            }
             CachedResponse::NotModified { cached, new_policy } => {
+                self.0.network_recorder().record_cache_hit();
                 let refresh_cache =
                     info_span!("refresh_cache", file = %cache_entry.path().display());
                 async {
@@ -284,6 +294,7 @@ impl CachedClient {
                 response,
                 cache_policy,
             } => {
+                self.0.network_recorder().record_cache_miss();
                 // If we got a modified response, but it's a 304, then a validator failed (e.g., the
                 // ETag didn't match). We need to make a fresh request.
                 if response.status() == http::StatusCode::NOT_MODIFIED {
@@ -319,6 +330,7 @@ impl CachedClient {
         Callback: FnOnce(Response) -> CallbackReturn,
         CallbackReturn: Future<Output = Result<Payload, CallBackError>>,
     {
+        self.0.network_recorder().record_cache_miss();
         let (response, cache_policy) = self.fresh_request(req).await?;
 
         let payload = self
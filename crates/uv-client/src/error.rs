@@ -43,6 +43,24 @@ impl Error {
         matches!(&*self.kind, ErrorKind::Offline(_))
     }
 
+    /// Returns `true` if this error corresponds to a failure to reach the network (e.g., a DNS,
+    /// TLS, or timeout failure, or disabled network connectivity), as opposed to an error response
+    /// from the server, or a malformed request or response.
+    pub fn is_network_failure(&self) -> bool {
+        match &*self.kind {
+            ErrorKind::ReqwestError(err) => is_network_failure(&err.0),
+            // Middleware errors (e.g., from authentication) are only network failures if they
+            // were caused by one, as when retries are exhausted.
+            ErrorKind::ReqwestMiddlewareError(err) => err.chain().any(|cause| {
+                cause
+                    .downcast_ref::<reqwest::Error>()
+                    .is_some_and(is_network_failure)
+            }),
+            ErrorKind::Offline(_) => true,
+            _ => false,
+        }
+    }
+
     /// Returns `true` if this error corresponds to an I/O "not found" error.
     pub(crate) fn is_file_not_exists(&self) -> bool {
         let ErrorKind::Io(ref err) = &*self.kind else {
@@ -162,6 +180,10 @@ pub enum ErrorKind {
     #[error("Metadata file `{0}` was not found in {1}")]
     MetadataNotFound(WheelFilename, String),
 
+    /// The metadata file exceeds the maximum size.
+    #[error("Metadata file in `{0}` from {1} exceeds the maximum size of {2} bytes")]
+    MetadataTooLarge(WheelFilename, String, u64),
+
     /// The metadata file was not found in the registry.
     #[error("File `{0}` was not found in the registry at {1}.")]
     FileNotFound(String, #[source] BetterReqwestError),
@@ -225,6 +247,15 @@ pub enum ErrorKind {
     #[error("Unsupported `Content-Type` \"{1}\" for {0}. Expected JSON or HTML.")]
     UnsupportedMediaType(Url, String),
 
+    #[error("Response from {url} exceeds the maximum size of {limit} bytes")]
+    ResponseTooLarge { url: Url, limit: u64 },
+
+    #[error("Unsupported Simple API version `{1}` for {0}. Expected version 1.x.")]
+    UnsupportedApiVersion(Url, String),
+
+    #[error("Received an HTML page from {0}, but expected a `METADATA` file")]
+    UnexpectedHtmlMetadata(Url),
+
     #[error("Reading from cache archive failed: {0}")]
     ArchiveRead(String),
 
@@ -256,22 +287,64 @@ impl From<reqwest_middleware::Error> for ErrorKind {
     }
 }
 
-/// Handle the case with no internet by explicitly telling the user instead of showing an obscure
-/// DNS error.
+/// Returns `true` if the [`reqwest::Error`] corresponds to a failure to reach the server (e.g., a
+/// DNS, TLS, or timeout failure, or a dropped connection), as opposed to an error response (like a
+/// 404), an invalid URL, or a response body that couldn't be decoded.
+pub fn is_network_failure(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout() || err.is_request()
+}
+
+/// Replace obscure low-level network errors with friendly messages: no internet (a DNS error),
+/// TLS failures (e.g., a corporate proxy with its own certificate authority), and connect and read
+/// timeouts, each with a hint for the relevant setting.
 #[derive(Debug)]
 pub struct BetterReqwestError(reqwest::Error);
 
+/// A class of network failure with a dedicated error message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkFailure {
+    Dns,
+    Tls,
+    ConnectTimeout,
+    ReadTimeout,
+}
+
 impl BetterReqwestError {
-    fn is_likely_offline(&self) -> bool {
+    fn failure(&self) -> Option<NetworkFailure> {
+        if self.0.is_timeout() {
+            return Some(if self.0.is_connect() {
+                NetworkFailure::ConnectTimeout
+            } else {
+                NetworkFailure::ReadTimeout
+            });
+        }
         if !self.0.is_connect() {
-            return false;
+            return None;
         }
         // Self is "error sending request for url", the first source is "error trying to connect",
-        // the second source is "dns error". We have to check for the string because hyper errors
-        // are opaque.
-        std::error::Error::source(&self.0)
-            .and_then(|err| err.source())
-            .is_some_and(|err| err.to_string().starts_with("dns error: "))
+        // the second source is "dns error" or the TLS error. We have to check for the string
+        // because hyper errors are opaque.
+        let mut source = std::error::Error::source(&self.0);
+        while let Some(err) = source {
+            let message = err.to_string();
+            if message.starts_with("dns error: ") {
+                return Some(NetworkFailure::Dns);
+            }
+            let message = message.to_lowercase();
+            if message.contains("certificate")
+                || message.contains("tls")
+                || message.contains("handshake")
+            {
+                return Some(NetworkFailure::Tls);
+            }
+            source = err.source();
+        }
+        None
+    }
+
+    /// The host of the request, for display.
+    fn host(&self) -> Option<&str> {
+        self.0.url().and_then(Url::host_str)
     }
 }
 
@@ -291,17 +364,34 @@ impl Deref for BetterReqwestError {
 
 impl Display for BetterReqwestError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.is_likely_offline() {
-            f.write_str("Could not connect, are you offline?")
-        } else {
-            Display::fmt(&self.0, f)
+        let Some(failure) = self.failure() else {
+            return Display::fmt(&self.0, f);
+        };
+        let host = self.host().unwrap_or("the server");
+        match failure {
+            NetworkFailure::Dns => write!(
+                f,
+                "Could not connect, are you offline? Failed to resolve `{host}`; if you're behind a proxy, set `HTTPS_PROXY`"
+            ),
+            NetworkFailure::Tls => write!(
+                f,
+                "TLS handshake with `{host}` failed; if your network uses a custom certificate authority (e.g., a corporate proxy), try `--native-tls` or set `SSL_CERT_FILE`"
+            ),
+            NetworkFailure::ConnectTimeout => write!(
+                f,
+                "Timed out connecting to `{host}`; try increasing `--http-connect-timeout` (`UV_HTTP_CONNECT_TIMEOUT`)"
+            ),
+            NetworkFailure::ReadTimeout => write!(
+                f,
+                "Timed out waiting for a response from `{host}`; try increasing `--http-timeout` (`UV_HTTP_TIMEOUT`) or `--http-deadline` (`UV_HTTP_DEADLINE`)"
+            ),
         }
     }
 }
 
 impl std::error::Error for BetterReqwestError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        if self.is_likely_offline() {
+        if self.failure().is_some() {
             Some(&self.0)
         } else {
             self.0.source()
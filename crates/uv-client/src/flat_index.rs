@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
@@ -12,6 +12,8 @@ use uv_cache::{Cache, CacheBucket};
 
 use crate::cached_client::{CacheControl, CachedClientError};
 use crate::html::SimpleHtml;
+use crate::limits::read_limited_text;
+use crate::registry_client::ACCEPT_ENCODING;
 use crate::{Connectivity, Error, ErrorKind, RegistryClient};
 
 #[derive(Debug, thiserror::Error)]
@@ -80,12 +82,25 @@ impl FlatIndexEntries {
 pub struct FlatIndexClient<'a> {
     client: &'a RegistryClient,
     cache: &'a Cache,
+    recursive: bool,
 }
 
 impl<'a> FlatIndexClient<'a> {
     /// Create a new [`FlatIndexClient`].
     pub fn new(client: &'a RegistryClient, cache: &'a Cache) -> Self {
-        Self { client, cache }
+        Self {
+            client,
+            cache,
+            recursive: false,
+        }
+    }
+
+    /// Read local `--find-links` directories recursively, including the distributions in any
+    /// subdirectories (e.g., in a wheelhouse organized by package).
+    #[must_use]
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
     }
 
     /// Read the directories and flat remote indexes from `--find-links`.
@@ -97,8 +112,10 @@ impl<'a> FlatIndexClient<'a> {
         let mut fetches = futures::stream::iter(indexes)
             .map(|index| async move {
                 let entries = match index {
-                    FlatIndexLocation::Path(path) => Self::read_from_directory(path)
-                        .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?,
+                    FlatIndexLocation::Path(path) => {
+                        Self::read_from_directory(path, self.recursive)
+                            .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?
+                    }
                     FlatIndexLocation::Url(url) => self
                         .read_from_url(url)
                         .await
@@ -141,21 +158,31 @@ impl<'a> FlatIndexClient<'a> {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
-        let flat_index_request = self
+        let mut flat_index_request = self
             .client
             .uncached_client()
             .get(url.clone())
-            .header("Accept-Encoding", "gzip")
-            .header("Accept", "text/html")
-            .build()
-            .map_err(ErrorKind::from)?;
+            .header("Accept-Encoding", ACCEPT_ENCODING)
+            .header("Accept", "text/html");
+        if let Some(max_age) = self.client.index_max_age() {
+            // Per RFC 9111, a request `max-age` forces revalidation of any cached response that
+            // is older than the given number of seconds.
+            flat_index_request =
+                flat_index_request.header("cache-control", format!("max-age={max_age}"));
+        }
+        let flat_index_request = flat_index_request.build().map_err(ErrorKind::from)?;
         let parse_simple_response = |response: Response| {
             async {
                 // Use the response URL, rather than the request URL, as the base for relative URLs.
                 // This ensures that we handle redirects and other URL transformations correctly.
                 let url = response.url().clone();
 
-                let text = response.text().await.map_err(ErrorKind::from)?;
+                let text = read_limited_text(
+                    response,
+                    self.client.response_limits().simple,
+                    self.client.network_recorder(),
+                )
+                .await?;
                 let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url)
                     .map_err(|err| Error::from_html_err(err, url.clone()))?;
 
@@ -209,16 +236,46 @@ impl<'a> FlatIndexClient<'a> {
         }
     }
 
-    /// Read a flat remote index from a `--find-links` directory.
-    fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, FindLinksDirectoryError> {
+    /// Read a flat remote index from a `--find-links` directory, and, if `recursive` is set, from
+    /// its subdirectories.
+    fn read_from_directory(
+        path: &PathBuf,
+        recursive: bool,
+    ) -> Result<FlatIndexEntries, FindLinksDirectoryError> {
         // Absolute paths are required for the URL conversion.
         let path = fs_err::canonicalize(path)?;
         let index_url = IndexUrl::Path(VerbatimUrl::from_path(&path)?);
 
         let mut dists = Vec::new();
-        for entry in fs_err::read_dir(path)? {
+        let mut directories = vec![path];
+        while let Some(directory) = directories.pop() {
+            Self::read_directory_entries(
+                &directory,
+                &index_url,
+                recursive.then_some(&mut directories),
+                &mut dists,
+            )?;
+        }
+        Ok(FlatIndexEntries::from_entries(dists))
+    }
+
+    /// Read the distributions in a single directory, pushing any subdirectories onto
+    /// `subdirectories`, if provided.
+    fn read_directory_entries(
+        directory: &Path,
+        index_url: &IndexUrl,
+        mut subdirectories: Option<&mut Vec<PathBuf>>,
+        dists: &mut Vec<(DistFilename, File, IndexUrl)>,
+    ) -> Result<(), FindLinksDirectoryError> {
+        for entry in fs_err::read_dir(directory)? {
             let entry = entry?;
             let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                if let Some(subdirectories) = subdirectories.as_mut() {
+                    subdirectories.push(entry.path());
+                }
+                continue;
+            }
             if !metadata.is_file() {
                 continue;
             }
@@ -251,6 +308,6 @@ impl<'a> FlatIndexClient<'a> {
             };
             dists.push((filename, file, index_url.clone()));
         }
-        Ok(FlatIndexEntries::from_entries(dists))
+        Ok(())
     }
 }
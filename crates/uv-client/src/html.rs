@@ -230,6 +230,29 @@ impl SimpleHtml {
     }
 }
 
+/// A parsed structure from the root of a PyPI "HTML" index, listing the projects on the index.
+#[derive(Debug, Clone)]
+pub(crate) struct SimpleProjectsHtml {
+    /// The names of the projects, which may not be normalized.
+    pub(crate) projects: Vec<String>,
+}
+
+impl SimpleProjectsHtml {
+    /// Parse the project names from the `<a>` tags on the root page of an index.
+    pub(crate) fn parse(text: &str) -> Result<Self, Error> {
+        let dom = tl::parse(text, tl::ParserOptions::default())?;
+        let projects = dom
+            .nodes()
+            .iter()
+            .filter_map(|node| node.as_tag())
+            .filter(|link| link.name().as_bytes() == b"a")
+            .map(|link| link.inner_text(dom.parser()).trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        Ok(Self { projects })
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -1261,4 +1284,70 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_projects() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Simple index</h1>
+    <a href="/simple/beautifulsoup4/">beautifulsoup4</a>
+    <a href="/simple/flask-sqlalchemy/">Flask-SQLAlchemy</a>
+    <a href="/simple/requests/">
+      requests
+    </a>
+  </body>
+</html>
+        "#;
+        let result = SimpleProjectsHtml::parse(text).unwrap();
+        insta::assert_debug_snapshot!(result.projects, @r###"
+        [
+            "beautifulsoup4",
+            "Flask-SQLAlchemy",
+            "requests",
+        ]
+        "###);
+    }
+
+    /// Parse randomly mutated and truncated pages, to ensure that malformed HTML from an index
+    /// results in an error (or a partial result), and never in a panic.
+    #[test]
+    fn parse_mutated() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let seed = r#"
+<!DOCTYPE html>
+<html>
+  <head><base href="https://download.pytorch.org/whl/"></head>
+  <body>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-requires-python="&gt;=3.7" data-dist-info-metadata="sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-yanked="">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+    <a href="../simple/flask-sqlalchemy/Flask_SQLAlchemy-3.1.1.tar.gz?query=1#md5=abc">Flask-SQLAlchemy</a>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let special = b"<>\"'#=&;/?%\\ \n\0\xff";
+
+        // Use a fixed seed, such that any failure is reproducible.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            let mut bytes = seed.as_bytes().to_vec();
+            for _ in 0..rng.gen_range(1..8) {
+                let index = rng.gen_range(0..bytes.len());
+                match rng.gen_range(0..4) {
+                    0 => bytes[index] = special[rng.gen_range(0..special.len())],
+                    1 => bytes.insert(index, special[rng.gen_range(0..special.len())]),
+                    2 => {
+                        bytes.remove(index);
+                    }
+                    _ => bytes.truncate(index.max(1)),
+                }
+            }
+            let text = String::from_utf8_lossy(&bytes);
+            let _ = SimpleHtml::parse(&text, &base);
+            let _ = SimpleProjectsHtml::parse(&text);
+        }
+    }
 }
@@ -0,0 +1,241 @@
+use std::time::{Duration, Instant};
+
+use reqwest::header::{CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::IndexUrl;
+use pypi_types::{base_url_join_relative, File, SimpleJson};
+use uv_normalize::PackageName;
+
+use crate::html::SimpleHtml;
+use crate::RegistryClient;
+
+/// The media type of a JSON Simple API response (PEP 691).
+const JSON_MEDIA_TYPE: &str = "application/vnd.pypi.simple.v1+json";
+
+/// The media types of an HTML Simple API response (PEP 503).
+const HTML_MEDIA_TYPES: &[&str] = &["application/vnd.pypi.simple.v1+html", "text/html"];
+
+/// A report on the features of a package index that affect uv's performance against it, produced
+/// by probing the index's project page for a single package.
+#[derive(Debug)]
+pub struct IndexCheck {
+    /// The URL of the project page that was probed (e.g., `https://pypi.org/simple/pip/`).
+    pub url: Url,
+    /// The result of requesting the project page in the JSON format.
+    pub json: SimpleApiProbe,
+    /// The result of requesting the project page in the HTML format.
+    pub html: SimpleApiProbe,
+    /// The result of probing a wheel listed on the project page, if any.
+    pub wheel: Option<WheelProbe>,
+}
+
+impl IndexCheck {
+    /// Returns `true` if the index rejected the requests for lack of (valid) credentials.
+    pub fn requires_authentication(&self) -> bool {
+        [&self.json, &self.html].iter().any(|probe| {
+            matches!(
+                probe.status,
+                Ok(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+            )
+        })
+    }
+}
+
+/// The result of requesting a project page in a given format.
+#[derive(Debug)]
+pub struct SimpleApiProbe {
+    /// The status of the response, or the error if the request failed.
+    pub status: Result<StatusCode, String>,
+    /// The media type of the response, if any.
+    pub content_type: Option<String>,
+    /// The number of files listed on the page, if the page was served in the requested format
+    /// and could be parsed.
+    pub files: Option<usize>,
+    /// The time taken to receive the full response.
+    pub latency: Duration,
+}
+
+impl SimpleApiProbe {
+    /// Returns `true` if the index serves the project page in the requested format.
+    pub fn is_available(&self) -> bool {
+        self.files.is_some()
+    }
+}
+
+/// The result of probing a wheel listed on a project page.
+#[derive(Debug)]
+pub struct WheelProbe {
+    /// The filename of the wheel.
+    pub filename: String,
+    /// Whether the index advertises the wheel's metadata as a standalone file (PEP 658).
+    pub core_metadata: bool,
+    /// The status of the request for the standalone metadata file, if advertised, or the error if
+    /// the request failed.
+    pub metadata_status: Option<Result<StatusCode, String>>,
+    /// Whether the server responded to a range request for the wheel with partial content, or the
+    /// error if the request failed.
+    pub range_requests: Result<bool, String>,
+}
+
+impl RegistryClient {
+    /// Probe an index for Simple API availability (in both the JSON and HTML formats), metadata
+    /// and range request support, authentication requirements, and latency, using the project
+    /// page for the given package.
+    ///
+    /// Requests bypass the cache, such that the report reflects the current state of the index.
+    pub async fn check_index(&self, index: &IndexUrl, package_name: &PackageName) -> IndexCheck {
+        let mut url: Url = index.clone().into();
+        url.path_segments_mut()
+            .unwrap()
+            .pop_if_empty()
+            .push(package_name.as_ref())
+            .push("");
+
+        let (json, json_files) = self.probe_simple(&url, &[JSON_MEDIA_TYPE]).await;
+        let (html, html_files) = self.probe_simple(&url, HTML_MEDIA_TYPES).await;
+
+        // Probe the most recent wheel, which is the one most likely to be requested.
+        let wheel = match json_files.or(html_files) {
+            Some((base, files)) => {
+                let file = files
+                    .into_iter()
+                    .filter(|file| file.filename.ends_with(".whl"))
+                    .max_by(|a, b| a.upload_time.cmp(&b.upload_time));
+                match file {
+                    Some(file) => Some(self.probe_wheel(&base, file).await),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+
+        IndexCheck {
+            url,
+            json,
+            html,
+            wheel,
+        }
+    }
+
+    /// Request a project page in one of the given media types, returning the probe along with
+    /// the base URL and files of the page, if it was served in a requested format.
+    async fn probe_simple(
+        &self,
+        url: &Url,
+        media_types: &[&str],
+    ) -> (SimpleApiProbe, Option<(Url, Vec<File>)>) {
+        let start = Instant::now();
+        let response = match self
+            .uncached_client()
+            .get(url.clone())
+            .header("Accept", media_types.join(", "))
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => {
+                let probe = SimpleApiProbe {
+                    status: Err(err.to_string()),
+                    content_type: None,
+                    files: None,
+                    latency: start.elapsed(),
+                };
+                return (probe, None);
+            }
+        };
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.split(';').next().unwrap_or(value).trim().to_string());
+        let response_url = response.url().clone();
+        let text = response.text().await;
+        let latency = start.elapsed();
+
+        let files = match (status.is_success(), content_type.as_deref(), text) {
+            (true, Some(media_type), Ok(text)) if media_types.contains(&media_type) => {
+                if media_type == JSON_MEDIA_TYPE {
+                    serde_json::from_str::<SimpleJson>(&text)
+                        .map(|data| (response_url, data.files))
+                        .map_err(|err| debug!("Failed to parse JSON response from {url}: {err}"))
+                        .ok()
+                } else {
+                    SimpleHtml::parse(&text, &response_url)
+                        .map(|SimpleHtml { base, files }| (base.as_url().clone(), files))
+                        .map_err(|err| debug!("Failed to parse HTML response from {url}: {err}"))
+                        .ok()
+                }
+            }
+            _ => None,
+        };
+
+        let probe = SimpleApiProbe {
+            status: Ok(status),
+            content_type,
+            files: files.as_ref().map(|(_, files)| files.len()),
+            latency,
+        };
+        (probe, files)
+    }
+
+    /// Probe a wheel for standalone metadata and range request support.
+    async fn probe_wheel(&self, base: &Url, file: File) -> WheelProbe {
+        let core_metadata = file
+            .core_metadata
+            .as_ref()
+            .or(file.dist_info_metadata.as_ref())
+            .or(file.data_dist_info_metadata.as_ref())
+            .is_some_and(pypi_types::CoreMetadata::is_available);
+
+        let mut url = match base_url_join_relative(base.as_str(), &file.url) {
+            Ok(url) => url,
+            Err(err) => {
+                return WheelProbe {
+                    filename: file.filename,
+                    core_metadata,
+                    metadata_status: None,
+                    range_requests: Err(err.to_string()),
+                }
+            }
+        };
+        url.set_fragment(None);
+
+        // Request the standalone metadata file, if advertised.
+        let metadata_status = if core_metadata {
+            let metadata_url = Url::parse(&format!("{url}.metadata"))
+                .expect("Appending a suffix to a URL should produce a valid URL");
+            Some(
+                self.uncached_client()
+                    .get(metadata_url)
+                    .send()
+                    .await
+                    .map(|response| response.status())
+                    .map_err(|err| err.to_string()),
+            )
+        } else {
+            None
+        };
+
+        // Request the first byte of the wheel.
+        let range_requests = self
+            .uncached_client()
+            .get(url)
+            .header(RANGE, "bytes=0-0")
+            .send()
+            .await
+            .map(|response| response.status() == StatusCode::PARTIAL_CONTENT)
+            .map_err(|err| err.to_string());
+
+        WheelProbe {
+            filename: file.filename,
+            core_metadata,
+            metadata_status,
+            range_requests,
+        }
+    }
+}
@@ -1,13 +1,16 @@
-pub use base_client::{BaseClient, BaseClientBuilder};
+pub use base_client::{BaseClient, BaseClientBuilder, ExtraMiddleware, NetworkTimeouts};
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
-pub use error::{BetterReqwestError, Error, ErrorKind};
+pub use error::{is_network_failure, BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatIndexClient, FlatIndexEntries, FlatIndexError};
+pub use index_check::{IndexCheck, SimpleApiProbe, WheelProbe};
+pub use limits::ResponseLimits;
 pub use linehaul::LineHaul;
 pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
+pub use stats::{NetworkRecorder, NetworkStats};
 
 mod base_client;
 mod cached_client;
@@ -15,8 +18,11 @@ mod error;
 mod flat_index;
 mod html;
 mod httpcache;
+mod index_check;
+mod limits;
 mod linehaul;
 mod middleware;
 mod registry_client;
 mod remote_metadata;
 mod rkyvutil;
+mod stats;
@@ -0,0 +1,90 @@
+use reqwest::Response;
+use url::Url;
+
+use crate::stats::NetworkRecorder;
+use crate::{Error, ErrorKind};
+
+/// The default maximum size of a Simple API or `--find-links` page (256 MiB).
+///
+/// The largest pages on PyPI (e.g., for nightly-heavy projects) are in the tens of megabytes.
+const DEFAULT_MAX_SIMPLE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The default maximum size of a wheel's `METADATA` file (16 MiB).
+const DEFAULT_MAX_METADATA_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Upper bounds on the size of the responses read from an index, in bytes.
+///
+/// Responses are read into memory in full before they're parsed, so a misbehaving or malicious
+/// index could otherwise exhaust the available memory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResponseLimits {
+    /// The maximum size of a Simple API or `--find-links` page.
+    pub simple: Option<u64>,
+    /// The maximum size of a wheel's `METADATA` file.
+    pub metadata: Option<u64>,
+}
+
+impl ResponseLimits {
+    /// Fill in any unset limits from the defaults.
+    pub(crate) fn with_defaults(self) -> Self {
+        Self {
+            simple: Some(self.simple.unwrap_or(DEFAULT_MAX_SIMPLE_SIZE)),
+            metadata: Some(self.metadata.unwrap_or(DEFAULT_MAX_METADATA_SIZE)),
+        }
+    }
+}
+
+/// Read the body of a response into memory, failing if it exceeds the given limit.
+///
+/// The limit is enforced against the decoded body, so a small compressed response can't
+/// expand beyond it.
+pub(crate) async fn read_limited(
+    mut response: Response,
+    limit: Option<u64>,
+    network_recorder: &NetworkRecorder,
+) -> Result<Vec<u8>, Error> {
+    let Some(limit) = limit else {
+        let body = response.bytes().await.map_err(ErrorKind::from)?;
+        network_recorder.record_downloaded(body.len());
+        return Ok(body.to_vec());
+    };
+
+    let url = response.url().clone();
+
+    // If the server advertises the size up-front, fail before reading anything.
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit)
+    {
+        return Err(too_large(url, limit));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(ErrorKind::from)? {
+        if body.len() as u64 + chunk.len() as u64 > limit {
+            return Err(too_large(url, limit));
+        }
+        network_recorder.record_downloaded(chunk.len());
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Read the body of a response as text, failing if it exceeds the given limit.
+///
+/// Invalid UTF-8 is replaced, rather than rejected, matching [`Response::text`].
+pub(crate) async fn read_limited_text(
+    response: Response,
+    limit: Option<u64>,
+    network_recorder: &NetworkRecorder,
+) -> Result<String, Error> {
+    let body = read_limited(response, limit, network_recorder).await?;
+    Ok(match String::from_utf8(body) {
+        Ok(text) => text,
+        Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+    })
+}
+
+fn too_large(url: Url, limit: u64) -> Error {
+    ErrorKind::ResponseTooLarge { url, limit }.into()
+}
@@ -1,9 +1,15 @@
 use http::Extensions;
 use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-use reqwest::{Request, Response};
+use reqwest::{redirect, Client, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
-use url::Url;
+use rustc_hash::FxHashSet;
+use tracing::debug;
+use url::{Host, Url};
+
+use uv_configuration::{IndexRateLimit, TrustedHost};
 
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,3 +51,365 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that sends requests for trusted hosts through a separate client, which doesn't
+/// verify TLS certificates. Requests for any other host are passed through unchanged.
+pub(crate) struct TrustedHostMiddleware {
+    trusted_hosts: Vec<TrustedHost>,
+    insecure_client: Client,
+}
+
+impl TrustedHostMiddleware {
+    pub(crate) fn new(trusted_hosts: Vec<TrustedHost>, insecure_client: Client) -> Self {
+        Self {
+            trusted_hosts,
+            insecure_client,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TrustedHostMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if is_trusted(&self.trusted_hosts, req.url()) {
+            debug!("Skipping TLS verification for trusted host: {}", req.url());
+            return self
+                .insecure_client
+                .execute(req)
+                .await
+                .map_err(reqwest_middleware::Error::Reqwest);
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A redirect policy for clients that skip TLS verification, which only follows redirects to
+/// trusted hosts, such that a trusted host can't send a request on to an arbitrary host without
+/// verification.
+pub(crate) fn trusted_host_redirect_policy(trusted_hosts: Vec<TrustedHost>) -> redirect::Policy {
+    redirect::Policy::custom(move |attempt| {
+        // Match the limit of `reqwest`'s default policy.
+        if attempt.previous().len() >= 10 {
+            attempt.error("too many redirects")
+        } else if is_trusted(&trusted_hosts, attempt.url()) {
+            attempt.follow()
+        } else {
+            let message = format!(
+                "Refusing to follow a redirect to `{}` without TLS certificate verification, as it isn't a trusted host",
+                attempt.url()
+            );
+            attempt.error(message)
+        }
+    })
+}
+
+/// Returns `true` if the URL's host (and port) is trusted.
+fn is_trusted(trusted_hosts: &[TrustedHost], url: &Url) -> bool {
+    let Some((host, port)) = host_and_port(url) else {
+        return false;
+    };
+    trusted_hosts
+        .iter()
+        .any(|trusted| trusted.matches(&host, port))
+}
+
+/// A middleware that retries requests over HTTP/1.1 if they fail with an HTTP/2 protocol error,
+/// as when a proxy mangles HTTP/2 streams.
+///
+/// Once a request to a host has been downgraded, all subsequent requests to that host are sent
+/// over HTTP/1.1 directly.
+pub(crate) struct Http1FallbackMiddleware {
+    http1_client: Client,
+    trusted_hosts: Vec<TrustedHost>,
+    insecure_http1_client: Option<Client>,
+    downgraded: Mutex<FxHashSet<(String, Option<u16>)>>,
+}
+
+impl Http1FallbackMiddleware {
+    pub(crate) fn new(http1_client: Client) -> Self {
+        Self {
+            http1_client,
+            trusted_hosts: Vec::new(),
+            insecure_http1_client: None,
+            downgraded: Mutex::new(FxHashSet::default()),
+        }
+    }
+
+    /// Send requests for trusted hosts through the given client, which doesn't verify TLS
+    /// certificates, when falling back to HTTP/1.1.
+    #[must_use]
+    pub(crate) fn with_trusted_hosts(
+        self,
+        trusted_hosts: Vec<TrustedHost>,
+        insecure_http1_client: Client,
+    ) -> Self {
+        Self {
+            trusted_hosts,
+            insecure_http1_client: Some(insecure_http1_client),
+            ..self
+        }
+    }
+
+    /// Send a request over HTTP/1.1.
+    async fn execute(&self, req: Request) -> reqwest_middleware::Result<Response> {
+        let client = match &self.insecure_http1_client {
+            Some(insecure_http1_client) if is_trusted(&self.trusted_hosts, req.url()) => {
+                insecure_http1_client
+            }
+            _ => &self.http1_client,
+        };
+        client
+            .execute(req)
+            .await
+            .map_err(reqwest_middleware::Error::Reqwest)
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for Http1FallbackMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(host) = host_and_port(req.url()) else {
+            return next.run(req, extensions).await;
+        };
+
+        if self.downgraded.lock().unwrap().contains(&host) {
+            return self.execute(req).await;
+        }
+
+        // Requests with streaming bodies can't be retried.
+        let Some(retry) = req.try_clone() else {
+            return next.run(req, extensions).await;
+        };
+
+        match next.run(req, extensions).await {
+            Err(reqwest_middleware::Error::Reqwest(err)) if is_http2_error(&err) => {
+                debug!(
+                    "Request to {} failed with an HTTP/2 protocol error; retrying over HTTP/1.1: {}",
+                    retry.url(),
+                    DisplayChain(&err)
+                );
+                self.downgraded.lock().unwrap().insert(host);
+                self.execute(retry).await
+            }
+            result => result,
+        }
+    }
+}
+
+/// Returns `true` if the error was caused by an HTTP/2 protocol error, e.g., a stream reset.
+fn is_http2_error(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if err.downcast_ref::<h2::Error>().is_some() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Display an error along with its chain of causes, on a single line.
+struct DisplayChain<'a>(&'a reqwest::Error);
+
+impl std::fmt::Display for DisplayChain<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut source = std::error::Error::source(self.0);
+        while let Some(err) = source {
+            write!(f, ": {err}")?;
+            source = err.source();
+        }
+        Ok(())
+    }
+}
+
+/// The longest pause honored from a `Retry-After` header, such that a misbehaving server can't
+/// stall the client indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// A middleware that caps the rate of requests sent to each rate-limited host, using a token
+/// bucket per limit.
+///
+/// Requests to a host are delayed (rather than rejected) once its burst is exhausted. If the host
+/// responds with `429 Too Many Requests` or `503 Service Unavailable` and a `Retry-After` header,
+/// all subsequent requests to the host are paused until then.
+pub(crate) struct RateLimitMiddleware {
+    limits: Vec<(IndexRateLimit, Mutex<TokenBucket>)>,
+}
+
+impl RateLimitMiddleware {
+    pub(crate) fn new(limits: Vec<IndexRateLimit>) -> Self {
+        Self {
+            limits: limits
+                .into_iter()
+                .map(|limit| {
+                    let bucket = TokenBucket::new(&limit);
+                    (limit, Mutex::new(bucket))
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the bucket for the first limit that applies to the URL, if any.
+    fn bucket(&self, url: &Url) -> Option<(&IndexRateLimit, &Mutex<TokenBucket>)> {
+        let (host, port) = host_and_port(url)?;
+        self.limits
+            .iter()
+            .find(|(limit, _)| limit.matches(&host, port))
+            .map(|(limit, bucket)| (limit, bucket))
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some((limit, bucket)) = self.bucket(req.url()) else {
+            return next.run(req, extensions).await;
+        };
+
+        let delay = bucket.lock().unwrap().acquire(limit, Instant::now());
+        if !delay.is_zero() {
+            debug!(
+                "Delaying request to {} by {:.2}s to respect rate limit: {limit}",
+                req.url(),
+                delay.as_secs_f64()
+            );
+            tokio::time::sleep(delay).await;
+        }
+
+        let response = next.run(req, extensions).await?;
+
+        if matches!(
+            response.status(),
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ) {
+            if let Some(retry_after) = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+            {
+                let retry_after = Duration::from_secs(retry_after).min(MAX_RETRY_AFTER);
+                debug!(
+                    "Pausing requests to {} for {}s, as requested by the server",
+                    response.url(),
+                    retry_after.as_secs()
+                );
+                bucket.lock().unwrap().pause(Instant::now() + retry_after);
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+/// A token bucket, which refills at the limit's rate, up to its burst.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of available tokens, which may be negative if requests are queued.
+    tokens: f64,
+    /// The last time the bucket was refilled.
+    updated: Instant,
+    /// The time until which no requests should be sent, as requested by the server.
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(limit: &IndexRateLimit) -> Self {
+        Self {
+            tokens: f64::from(limit.burst()),
+            updated: Instant::now(),
+            paused_until: None,
+        }
+    }
+
+    /// Take a token from the bucket, returning how long to wait before sending the request.
+    ///
+    /// Tokens are reserved eagerly, such that concurrent requests are queued in order, rather
+    /// than all waking at once.
+    fn acquire(&mut self, limit: &IndexRateLimit, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.rate()).min(f64::from(limit.burst()));
+        self.updated = now;
+        self.tokens -= 1.0;
+
+        let delay = if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / limit.rate())
+        } else {
+            Duration::ZERO
+        };
+        let pause = self
+            .paused_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+        delay.max(pause)
+    }
+
+    /// Pause all requests until the given time.
+    fn pause(&mut self, until: Instant) {
+        self.paused_until = Some(self.paused_until.map_or(until, |paused| paused.max(until)));
+    }
+}
+
+/// Returns the host and port (or the scheme's default port) of a URL.
+fn host_and_port(url: &Url) -> Option<(String, Option<u16>)> {
+    let host = match url.host()? {
+        Host::Domain(domain) => domain.to_string(),
+        Host::Ipv4(addr) => addr.to_string(),
+        Host::Ipv6(addr) => addr.to_string(),
+    };
+    Some((host, url.port_or_known_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::{Duration, Instant};
+
+    use uv_configuration::IndexRateLimit;
+
+    use super::TokenBucket;
+
+    #[test]
+    fn token_bucket() {
+        let limit = IndexRateLimit::from_str("artifactory.internal=10:2").unwrap();
+        let mut bucket = TokenBucket::new(&limit);
+        let start = Instant::now();
+
+        // The burst is available immediately.
+        assert_eq!(bucket.acquire(&limit, start), Duration::ZERO);
+        assert_eq!(bucket.acquire(&limit, start), Duration::ZERO);
+
+        // Subsequent requests are queued, at the given rate.
+        assert_eq!(bucket.acquire(&limit, start), Duration::from_millis(100));
+        assert_eq!(bucket.acquire(&limit, start), Duration::from_millis(200));
+
+        // Once the queue drains, the bucket refills up to the burst.
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bucket.acquire(&limit, later), Duration::ZERO);
+        assert_eq!(bucket.acquire(&limit, later), Duration::ZERO);
+        assert_eq!(bucket.acquire(&limit, later), Duration::from_millis(100));
+
+        // A pause requested by the server takes precedence.
+        bucket.pause(later + Duration::from_secs(5));
+        assert_eq!(
+            bucket.acquire(&limit, later + Duration::from_secs(1)),
+            Duration::from_secs(4)
+        );
+    }
+}
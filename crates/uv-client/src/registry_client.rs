@@ -7,6 +7,7 @@ use async_http_range_reader::AsyncHttpRangeReader;
 use futures::{FutureExt, TryStreamExt};
 use http::HeaderMap;
 use reqwest::{Client, Response, StatusCode};
+use reqwest_middleware::Middleware;
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncReadExt;
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
@@ -19,17 +20,19 @@ use install_wheel_rs::metadata::{find_archive_dist_info, is_metadata_entry};
 use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
-use pypi_types::{Metadata23, SimpleJson};
+use pypi_types::{Metadata23, SimpleJson, SimpleJsonMeta, SimpleProjectsJson};
 use uv_cache::{Cache, CacheBucket, WheelCache};
-use uv_configuration::IndexStrategy;
 use uv_configuration::KeyringProviderType;
+use uv_configuration::{HostOverride, HttpVersion, IndexRateLimit, IndexStrategy, TrustedHost};
 use uv_normalize::PackageName;
 
-use crate::base_client::{BaseClient, BaseClientBuilder};
+use crate::base_client::{BaseClient, BaseClientBuilder, NetworkTimeouts};
 use crate::cached_client::CacheControl;
-use crate::html::SimpleHtml;
-use crate::remote_metadata::wheel_metadata_from_remote_zip;
+use crate::html::{SimpleHtml, SimpleProjectsHtml};
+use crate::limits::{read_limited, read_limited_text, ResponseLimits};
+use crate::remote_metadata::{wheel_dist_info_from_remote_zip, wheel_metadata_from_remote_zip};
 use crate::rkyvutil::OwnedArchive;
+use crate::stats::NetworkRecorder;
 use crate::{CachedClient, CachedClientError, Error, ErrorKind};
 
 /// A builder for an [`RegistryClient`].
@@ -37,14 +40,8 @@ use crate::{CachedClient, CachedClientError, Error, ErrorKind};
 pub struct RegistryClientBuilder<'a> {
     index_urls: IndexUrls,
     index_strategy: IndexStrategy,
-    keyring: KeyringProviderType,
-    native_tls: bool,
-    retries: u32,
-    connectivity: Connectivity,
     cache: Cache,
-    client: Option<Client>,
-    markers: Option<&'a MarkerEnvironment>,
-    platform: Option<&'a Platform>,
+    base_client_builder: BaseClientBuilder<'a>,
 }
 
 impl RegistryClientBuilder<'_> {
@@ -52,19 +49,21 @@ impl RegistryClientBuilder<'_> {
         Self {
             index_urls: IndexUrls::default(),
             index_strategy: IndexStrategy::default(),
-            keyring: KeyringProviderType::default(),
-            native_tls: false,
             cache,
-            connectivity: Connectivity::Online,
-            retries: 3,
-            client: None,
-            markers: None,
-            platform: None,
+            base_client_builder: BaseClientBuilder::new(),
         }
     }
 }
 
 impl<'a> RegistryClientBuilder<'a> {
+    /// Inherit the network settings (e.g., connectivity, TLS verification, and timeouts) of the
+    /// given [`BaseClientBuilder`], replacing any that were set previously.
+    #[must_use]
+    pub fn base_client_builder(mut self, base_client_builder: BaseClientBuilder<'a>) -> Self {
+        self.base_client_builder = base_client_builder;
+        self
+    }
+
     #[must_use]
     pub fn index_urls(mut self, index_urls: IndexUrls) -> Self {
         self.index_urls = index_urls;
@@ -79,25 +78,95 @@ impl<'a> RegistryClientBuilder<'a> {
 
     #[must_use]
     pub fn keyring(mut self, keyring_type: KeyringProviderType) -> Self {
-        self.keyring = keyring_type;
+        self.base_client_builder = self.base_client_builder.keyring(keyring_type);
+        self
+    }
+
+    /// Ignore netrc files and cloud provider credentials (see [`BaseClientBuilder::hermetic`]).
+    #[must_use]
+    pub fn hermetic(mut self, hermetic: bool) -> Self {
+        self.base_client_builder = self.base_client_builder.hermetic(hermetic);
         self
     }
 
     #[must_use]
     pub fn connectivity(mut self, connectivity: Connectivity) -> Self {
-        self.connectivity = connectivity;
+        self.base_client_builder = self.base_client_builder.connectivity(connectivity);
+        self
+    }
+
+    /// Set the maximum age (in seconds) of cached Simple API and `--find-links` responses (see
+    /// [`BaseClientBuilder::index_max_age`]).
+    #[must_use]
+    pub fn index_max_age(mut self, index_max_age: Option<u64>) -> Self {
+        self.base_client_builder = self.base_client_builder.index_max_age(index_max_age);
+        self
+    }
+
+    /// Set the maximum size of the Simple API pages and `METADATA` files read from an index (see
+    /// [`BaseClientBuilder::response_limits`]).
+    #[must_use]
+    pub fn response_limits(mut self, response_limits: ResponseLimits) -> Self {
+        self.base_client_builder = self.base_client_builder.response_limits(response_limits);
         self
     }
 
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
-        self.retries = retries;
+        self.base_client_builder = self.base_client_builder.retries(retries);
         self
     }
 
     #[must_use]
     pub fn native_tls(mut self, native_tls: bool) -> Self {
-        self.native_tls = native_tls;
+        self.base_client_builder = self.base_client_builder.native_tls(native_tls);
+        self
+    }
+
+    #[must_use]
+    pub fn timeouts(mut self, timeouts: NetworkTimeouts) -> Self {
+        self.base_client_builder = self.base_client_builder.timeouts(timeouts);
+        self
+    }
+
+    #[must_use]
+    pub fn host_overrides(mut self, host_overrides: Vec<HostOverride>) -> Self {
+        self.base_client_builder = self.base_client_builder.host_overrides(host_overrides);
+        self
+    }
+
+    #[must_use]
+    pub fn trusted_hosts(mut self, trusted_hosts: Vec<TrustedHost>) -> Self {
+        self.base_client_builder = self.base_client_builder.trusted_hosts(trusted_hosts);
+        self
+    }
+
+    #[must_use]
+    pub fn rate_limits(mut self, rate_limits: Vec<IndexRateLimit>) -> Self {
+        self.base_client_builder = self.base_client_builder.rate_limits(rate_limits);
+        self
+    }
+
+    #[must_use]
+    pub fn http_version(mut self, http_version: Option<HttpVersion>) -> Self {
+        self.base_client_builder = self.base_client_builder.http_version(http_version);
+        self
+    }
+
+    #[must_use]
+    pub fn network_recorder(mut self, network_recorder: NetworkRecorder) -> Self {
+        self.base_client_builder = self.base_client_builder.network_recorder(network_recorder);
+        self
+    }
+
+    /// Wrap the client with the given middleware, e.g., to apply a custom authentication scheme
+    /// (like AWS SigV4), sign requests, or record metrics.
+    ///
+    /// May be called multiple times; the middleware runs in the order in which it was added, after
+    /// uv's retry and authentication middleware. It isn't used in offline mode.
+    #[must_use]
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.base_client_builder = self.base_client_builder.middleware(middleware);
         self
     }
 
@@ -109,48 +178,32 @@ impl<'a> RegistryClientBuilder<'a> {
 
     #[must_use]
     pub fn client(mut self, client: Client) -> Self {
-        self.client = Some(client);
+        self.base_client_builder = self.base_client_builder.client(client);
         self
     }
 
     #[must_use]
     pub fn markers(mut self, markers: &'a MarkerEnvironment) -> Self {
-        self.markers = Some(markers);
+        self.base_client_builder = self.base_client_builder.markers(markers);
         self
     }
 
     #[must_use]
     pub fn platform(mut self, platform: &'a Platform) -> Self {
-        self.platform = Some(platform);
+        self.base_client_builder = self.base_client_builder.platform(platform);
         self
     }
 
     pub fn build(self) -> RegistryClient {
         // Build a base client
-        let mut builder = BaseClientBuilder::new();
-
-        if let Some(client) = self.client {
-            builder = builder.client(client)
-        }
-
-        if let Some(markers) = self.markers {
-            builder = builder.markers(markers)
-        }
-
-        if let Some(platform) = self.platform {
-            builder = builder.platform(platform)
-        }
-
-        let client = builder
-            .retries(self.retries)
-            .connectivity(self.connectivity)
-            .native_tls(self.native_tls)
-            .keyring(self.keyring)
-            .build();
+        let client = self.base_client_builder.build();
 
         let timeout = client.timeout();
         let connectivity = client.connectivity();
 
+        let index_max_age = client.index_max_age();
+        let response_limits = client.response_limits();
+
         // Wrap in the cache middleware.
         let client = CachedClient::new(client);
 
@@ -159,6 +212,8 @@ impl<'a> RegistryClientBuilder<'a> {
             index_strategy: self.index_strategy,
             cache: self.cache,
             connectivity,
+            index_max_age,
+            response_limits,
             client,
             timeout,
         }
@@ -178,11 +233,25 @@ pub struct RegistryClient {
     cache: Cache,
     /// The connectivity mode to use.
     connectivity: Connectivity,
+    /// The maximum age of cached Simple API and `--find-links` responses, in seconds.
+    index_max_age: Option<u64>,
+    /// The maximum size of the responses read from an index.
+    response_limits: ResponseLimits,
     /// Configured client timeout, in seconds.
     timeout: u64,
 }
 
 impl RegistryClient {
+    /// Return the maximum age of cached index pages, in seconds.
+    pub(crate) fn index_max_age(&self) -> Option<u64> {
+        self.index_max_age
+    }
+
+    /// Return the maximum size of the responses read from an index.
+    pub(crate) fn response_limits(&self) -> ResponseLimits {
+        self.response_limits
+    }
+
     /// Return the [`CachedClient`] used by this client.
     pub fn cached_client(&self) -> &CachedClient {
         &self.client
@@ -193,6 +262,11 @@ impl RegistryClient {
         self.client.uncached()
     }
 
+    /// Return the recorder for HTTP cache usage and network traffic.
+    pub fn network_recorder(&self) -> &NetworkRecorder {
+        self.client.network_recorder()
+    }
+
     /// Return the [`Connectivity`] mode used by this client.
     pub fn connectivity(&self) -> Connectivity {
         self.connectivity
@@ -264,6 +338,32 @@ impl RegistryClient {
         Ok(results)
     }
 
+    /// Fetch a package from a single index via the simple API, returning `None` if the package
+    /// isn't available on that index.
+    ///
+    /// Unlike [`RegistryClient::simple`], an index that rejects the request (i.e., with a `401` or
+    /// `403`) is an error, rather than an index that lacks the package.
+    pub async fn simple_index(
+        &self,
+        package_name: &PackageName,
+        index: &IndexUrl,
+    ) -> Result<Option<OwnedArchive<SimpleMetadata>>, Error> {
+        match self.simple_single_index(package_name, index).await? {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(CachedClientError::Client(err)) => match err.into_kind() {
+                ErrorKind::Offline(_) => Ok(None),
+                ErrorKind::ReqwestError(err) => {
+                    if err.status() == Some(StatusCode::NOT_FOUND) {
+                        return Ok(None);
+                    }
+                    Err(ErrorKind::from(err).into())
+                }
+                other => Err(other.into()),
+            },
+            Err(CachedClientError::Callback(err)) => Err(err),
+        }
+    }
+
     async fn simple_single_index(
         &self,
         package_name: &PackageName,
@@ -299,44 +399,45 @@ impl RegistryClient {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
-        let simple_request = self
+        let mut simple_request = self
             .uncached_client()
             .get(url.clone())
-            .header("Accept-Encoding", "gzip")
-            .header("Accept", MediaType::accepts())
-            .build()
-            .map_err(ErrorKind::from)?;
+            .header("Accept-Encoding", ACCEPT_ENCODING)
+            .header("Accept", MediaType::accepts());
+        if let Some(max_age) = self.index_max_age {
+            // Per RFC 9111, a request `max-age` forces revalidation of any cached response that
+            // is older than the given number of seconds.
+            simple_request = simple_request.header("cache-control", format!("max-age={max_age}"));
+        }
+        let simple_request = simple_request.build().map_err(ErrorKind::from)?;
         let parse_simple_response = |response: Response| {
             async {
                 // Use the response URL, rather than the request URL, as the base for relative URLs.
                 // This ensures that we handle redirects and other URL transformations correctly.
                 let url = response.url().clone();
-
-                let content_type = response
-                    .headers()
-                    .get("content-type")
-                    .ok_or_else(|| Error::from(ErrorKind::MissingContentType(url.clone())))?;
-                let content_type = content_type.to_str().map_err(|err| {
-                    Error::from(ErrorKind::InvalidContentTypeHeader(url.clone(), err))
-                })?;
-                let media_type = content_type.split(';').next().unwrap_or(content_type);
-                let media_type = MediaType::from_str(media_type).ok_or_else(|| {
-                    Error::from(ErrorKind::UnsupportedMediaType(
-                        url.clone(),
-                        media_type.to_string(),
-                    ))
-                })?;
+                let media_type = MediaType::from_response(&response)?;
 
                 let unarchived = match media_type {
                     MediaType::Json => {
-                        let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+                        let bytes = read_limited(
+                            response,
+                            self.response_limits.simple,
+                            self.network_recorder(),
+                        )
+                        .await?;
                         let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                        check_api_version(data.meta.as_ref(), &url)?;
 
                         SimpleMetadata::from_files(data.files, package_name, &url)
                     }
                     MediaType::Html => {
-                        let text = response.text().await.map_err(ErrorKind::from)?;
+                        let text = read_limited_text(
+                            response,
+                            self.response_limits.simple,
+                            self.network_recorder(),
+                        )
+                        .await?;
                         let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url)
                             .map_err(|err| Error::from_html_err(err, url.clone()))?;
 
@@ -360,6 +461,103 @@ impl RegistryClient {
         Ok(result)
     }
 
+    /// Fetch the names of the projects available on an index, from the root of its simple API.
+    ///
+    /// The project list is only used to suggest alternatives for packages that could not be
+    /// found. Since the root listing of a large index can be sizable, the response is cached
+    /// alongside the index's project pages, and any project names that fail to parse are skipped.
+    #[instrument(skip_all, fields(index = % index))]
+    pub async fn project_names(&self, index: &IndexUrl) -> Result<Vec<PackageName>, Error> {
+        let url: Url = index.clone().into();
+
+        trace!("Fetching project list from {url}");
+
+        let cache_entry = self.cache.entry(
+            CacheBucket::Simple,
+            Path::new(&match index {
+                IndexUrl::Pypi(_) => "pypi".to_string(),
+                IndexUrl::Url(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
+                IndexUrl::Path(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
+            }),
+            "projects.msgpack",
+        );
+        let cache_control = match self.connectivity {
+            Connectivity::Online => CacheControl::from(
+                self.cache
+                    .freshness(&cache_entry, None)
+                    .map_err(ErrorKind::Io)?,
+            ),
+            Connectivity::Offline => CacheControl::AllowStale,
+        };
+
+        let mut projects_request = self
+            .uncached_client()
+            .get(url.clone())
+            .header("Accept-Encoding", ACCEPT_ENCODING)
+            .header("Accept", MediaType::accepts());
+        if let Some(max_age) = self.index_max_age {
+            // Per RFC 9111, a request `max-age` forces revalidation of any cached response that
+            // is older than the given number of seconds.
+            projects_request =
+                projects_request.header("cache-control", format!("max-age={max_age}"));
+        }
+        let projects_request = projects_request.build().map_err(ErrorKind::from)?;
+        let parse_projects_response = |response: Response| {
+            async {
+                let url = response.url().clone();
+                let media_type = MediaType::from_response(&response)?;
+
+                let projects = match media_type {
+                    MediaType::Json => {
+                        let bytes = read_limited(
+                            response,
+                            self.response_limits.simple,
+                            self.network_recorder(),
+                        )
+                        .await?;
+                        let data: SimpleProjectsJson = serde_json::from_slice(bytes.as_ref())
+                            .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                        check_api_version(data.meta.as_ref(), &url)?;
+                        data.projects
+                            .into_iter()
+                            .map(|project| project.name)
+                            .collect::<Vec<_>>()
+                    }
+                    MediaType::Html => {
+                        let text = read_limited_text(
+                            response,
+                            self.response_limits.simple,
+                            self.network_recorder(),
+                        )
+                        .await?;
+                        SimpleProjectsHtml::parse(&text)
+                            .map_err(|err| Error::from_html_err(err, url.clone()))?
+                            .projects
+                    }
+                };
+
+                Ok::<Vec<PackageName>, Error>(
+                    projects
+                        .into_iter()
+                        .filter_map(|name| PackageName::new(name).ok())
+                        .collect(),
+                )
+            }
+            .boxed_local()
+            .instrument(info_span!("parse_simple_projects", url = % url))
+        };
+
+        Ok(self
+            .cached_client()
+            .get_serde(
+                projects_request,
+                &cache_entry,
+                cache_control,
+                parse_projects_response,
+            )
+            .await?)
+    }
+
     /// Fetch the metadata for a remote wheel file.
     ///
     /// For a remote wheel, we try the following ways to fetch the metadata:
@@ -448,7 +646,26 @@ impl RegistryClient {
             };
 
             let response_callback = |response: Response| async {
-                let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+                // An HTML response is almost certainly a login or error page served in place of
+                // the metadata file (e.g., by a misconfigured proxy).
+                if response
+                    .headers()
+                    .get("content-type")
+                    .and_then(|content_type| content_type.to_str().ok())
+                    .is_some_and(|content_type| {
+                        MediaType::essence(content_type).eq_ignore_ascii_case("text/html")
+                    })
+                {
+                    let url = response.url().clone();
+                    return Err(Error::from(ErrorKind::UnexpectedHtmlMetadata(url)));
+                }
+
+                let bytes = read_limited(
+                    response,
+                    self.response_limits.metadata,
+                    self.network_recorder(),
+                )
+                .await?;
 
                 info_span!("parse_metadata21")
                     .in_scope(|| Metadata23::parse_metadata(bytes.as_ref()))
@@ -463,6 +680,7 @@ impl RegistryClient {
             let req = self
                 .uncached_client()
                 .get(url.clone())
+                .header("Accept-Encoding", ACCEPT_ENCODING)
                 .build()
                 .map_err(ErrorKind::from)?;
             Ok(self
@@ -478,6 +696,50 @@ impl RegistryClient {
         }
     }
 
+    /// Read the given files from the `.dist-info` directory of a remote wheel, using HTTP range
+    /// requests to avoid downloading the entire wheel.
+    ///
+    /// Returns the `.dist-info` prefix, along with the name and contents of each requested file.
+    /// Files that are missing from the wheel are omitted. The response is not cached.
+    pub async fn wheel_dist_info_files(
+        &self,
+        filename: &WheelFilename,
+        url: &Url,
+        names: &[&str],
+    ) -> Result<(String, Vec<(String, String)>), Error> {
+        let req = self
+            .uncached_client()
+            .head(url.clone())
+            .header(
+                "accept-encoding",
+                http::HeaderValue::from_static("identity"),
+            )
+            .build()
+            .map_err(ErrorKind::from)?;
+
+        // Copy authorization headers from the HEAD request to subsequent requests
+        let mut headers = HeaderMap::default();
+        if let Some(authorization) = req.headers().get("authorization") {
+            headers.append("authorization", authorization.clone());
+        }
+
+        let response = self
+            .uncached_client()
+            .execute(req)
+            .await
+            .map_err(ErrorKind::from)?;
+        let mut reader = AsyncHttpRangeReader::from_head_response(
+            self.uncached_client().client(),
+            response,
+            url.clone(),
+            headers,
+        )
+        .await
+        .map_err(ErrorKind::AsyncHttpRangeReader)?;
+        trace!("Getting `.dist-info` files for {filename} by range request");
+        wheel_dist_info_from_remote_zip(filename, &mut reader, names).await
+    }
+
     /// Get the wheel metadata if it isn't available in an index through PEP 658
     async fn wheel_metadata_no_pep658<'data>(
         &self,
@@ -585,10 +847,17 @@ impl RegistryClient {
             async {
                 let reader = response
                     .bytes_stream()
+                    .inspect_ok(|chunk| self.network_recorder().record_downloaded(chunk.len()))
                     .map_err(|err| self.handle_response_errors(err))
                     .into_async_read();
 
-                read_metadata_async_stream(filename, url.to_string(), reader).await
+                read_metadata_async_stream(
+                    filename,
+                    url.to_string(),
+                    reader,
+                    self.response_limits.metadata,
+                )
+                .await
             }
             .instrument(info_span!("read_metadata_stream", wheel = %filename))
         };
@@ -605,7 +874,7 @@ impl RegistryClient {
             std::io::Error::new(
                 std::io::ErrorKind::TimedOut,
                 format!(
-                    "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {}s).",  self.timeout()
+                    "Failed to download distribution due to network timeout. Try increasing `--http-timeout` or UV_HTTP_TIMEOUT (current value: {}s).",  self.timeout()
                 ),
             )
         } else {
@@ -653,10 +922,14 @@ async fn read_metadata_async_seek(
 }
 
 /// Like [`read_metadata_async_seek`], but doesn't use seek.
+///
+/// If `max_size` is set, the `METADATA` entry is rejected if it exceeds the given number of bytes
+/// once decompressed.
 async fn read_metadata_async_stream<R: futures::AsyncRead + Unpin>(
     filename: &WheelFilename,
     debug_source: String,
     reader: R,
+    max_size: Option<u64>,
 ) -> Result<Metadata23, Error> {
     let reader = futures::io::BufReader::with_capacity(128 * 1024, reader);
     let mut zip = async_zip::base::read::stream::ZipFileReader::new(reader);
@@ -675,9 +948,27 @@ async fn read_metadata_async_stream<R: futures::AsyncRead + Unpin>(
             .map_err(|err| ErrorKind::Zip(filename.clone(), err))?;
 
         if is_metadata_entry(path, filename) {
-            let mut reader = entry.reader_mut().compat();
+            // Read one byte past the limit, to distinguish a file that's exactly at the limit
+            // from one that exceeds it.
+            let mut reader = entry
+                .reader_mut()
+                .compat()
+                .take(max_size.map_or(u64::MAX, |max_size| max_size.saturating_add(1)));
             let mut contents = Vec::new();
-            reader.read_to_end(&mut contents).await.unwrap();
+            reader
+                .read_to_end(&mut contents)
+                .await
+                .map_err(ErrorKind::Io)?;
+            if let Some(max_size) = max_size {
+                if contents.len() as u64 > max_size {
+                    return Err(ErrorKind::MetadataTooLarge(
+                        filename.clone(),
+                        debug_source,
+                        max_size,
+                    )
+                    .into());
+                }
+            }
 
             let metadata = Metadata23::parse_metadata(&contents).map_err(|err| {
                 ErrorKind::MetadataParseError(filename.clone(), debug_source, Box::new(err))
@@ -826,6 +1117,12 @@ impl ArchivedSimpleMetadata {
     }
 }
 
+/// The `Accept-Encoding` header value for index pages and metadata files.
+///
+/// Simple API responses are highly compressible, so we advertise every content encoding that
+/// `reqwest` decodes transparently, rather than relying on the server's default.
+pub(crate) const ACCEPT_ENCODING: &str = "gzip, br, zstd";
+
 #[derive(Debug)]
 enum MediaType {
     Json,
@@ -834,14 +1131,49 @@ enum MediaType {
 
 impl MediaType {
     /// Parse a media type from a string, returning `None` if the media type is not supported.
+    ///
+    /// Per RFC 9110, media types are case-insensitive.
     fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "application/vnd.pypi.simple.v1+json" => Some(Self::Json),
-            "application/vnd.pypi.simple.v1+html" | "text/html" => Some(Self::Html),
-            _ => None,
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("application/vnd.pypi.simple.v1+json") {
+            Some(Self::Json)
+        } else if s.eq_ignore_ascii_case("application/vnd.pypi.simple.v1+html")
+            || s.eq_ignore_ascii_case("text/html")
+        {
+            Some(Self::Html)
+        } else {
+            None
         }
     }
 
+    /// Parse the media type from the `Content-Type` header of a Simple API response.
+    fn from_response(response: &Response) -> Result<Self, Error> {
+        let url = response.url();
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .ok_or_else(|| Error::from(ErrorKind::MissingContentType(url.clone())))?;
+        let content_type = content_type
+            .to_str()
+            .map_err(|err| Error::from(ErrorKind::InvalidContentTypeHeader(url.clone(), err)))?;
+        let media_type = Self::essence(content_type);
+        Self::from_str(media_type).ok_or_else(|| {
+            Error::from(ErrorKind::UnsupportedMediaType(
+                url.clone(),
+                media_type.to_string(),
+            ))
+        })
+    }
+
+    /// Strip any parameters (e.g., `charset`) from a `Content-Type` header value.
+    fn essence(content_type: &str) -> &str {
+        content_type
+            .split(';')
+            .next()
+            .unwrap_or(content_type)
+            .trim()
+    }
+
     /// Return the `Accept` header value for all supported media types.
     #[inline]
     const fn accepts() -> &'static str {
@@ -850,6 +1182,21 @@ impl MediaType {
     }
 }
 
+/// Reject a Simple API response with an unsupported major version.
+///
+/// Per PEP 629, clients must fail on a major version they don't recognize, since its semantics
+/// may have changed incompatibly. (A minor version bump is backwards-compatible.)
+fn check_api_version(meta: Option<&SimpleJsonMeta>, url: &Url) -> Result<(), Error> {
+    let Some(version) = meta.and_then(|meta| meta.api_version.as_deref()) else {
+        return Ok(());
+    };
+    if version.split('.').next().map(str::trim) == Some("1") {
+        Ok(())
+    } else {
+        Err(ErrorKind::UnsupportedApiVersion(url.clone(), version.to_string()).into())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub enum Connectivity {
     /// Allow access to the network.
@@ -963,4 +1310,56 @@ mod tests {
 
         Ok(())
     }
+
+    /// Parse randomly mutated and truncated Simple API responses, to ensure that malformed JSON
+    /// from an index results in an error (or skipped files), and never in a panic.
+    #[test]
+    fn parse_mutated_json() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let seed = r#"
+        {
+          "meta": {"api-version": "1.1"},
+          "name": "pyflyby",
+          "files": [
+            {
+              "core-metadata": {"sha256": "0c4d953f405a7be1300b440dbdbc6917011a07d8401345a97e72cd410d5fb291"},
+              "filename": "pyflyby-1.7.8-py3-none-any.whl",
+              "hashes": {
+                "sha256": "1ee37474f6da8f98653dbcc208793f50b7ace1d9066f49e2707750a5ba5d53c6"
+              },
+              "requires-python": ">=2.5, !=3.0.*, <4",
+              "size": 424460,
+              "upload-time": "2022-08-04T10:42:02.190074Z",
+              "url": "../../packages/pyflyby-1.7.8-py3-none-any.whl",
+              "yanked": "broken"
+            }
+          ]
+        }
+        "#;
+        let base = Url::parse("https://pypi.org/simple/pyflyby/").unwrap();
+        let package_name = PackageName::from_str("pyflyby").unwrap();
+        let special = b"{}[]\":,.-0\\ \0\xff";
+
+        // Use a fixed seed, such that any failure is reproducible.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            let mut bytes = seed.as_bytes().to_vec();
+            for _ in 0..rng.gen_range(1..4) {
+                let index = rng.gen_range(0..bytes.len());
+                match rng.gen_range(0..4) {
+                    0 => bytes[index] = special[rng.gen_range(0..special.len())],
+                    1 => bytes.insert(index, special[rng.gen_range(0..special.len())]),
+                    2 => {
+                        bytes.remove(index);
+                    }
+                    _ => bytes.truncate(index.max(1)),
+                }
+            }
+            if let Ok(data) = serde_json::from_slice::<SimpleJson>(&bytes) {
+                SimpleMetadata::from_files(data.files, &package_name, &base);
+            }
+        }
+    }
 }
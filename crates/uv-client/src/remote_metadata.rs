@@ -52,6 +52,27 @@ pub(crate) async fn wheel_metadata_from_remote_zip(
     filename: &WheelFilename,
     reader: &mut AsyncHttpRangeReader,
 ) -> Result<String, Error> {
+    let (_, files) = wheel_dist_info_from_remote_zip(filename, reader, &["METADATA"]).await?;
+    // The `METADATA` file is guaranteed to exist, as it's used to locate the `.dist-info` directory.
+    Ok(files
+        .into_iter()
+        .next()
+        .map(|(_, contents)| contents)
+        .unwrap_or_default())
+}
+
+/// Read the given files from the `.dist-info` directory of an async remote zip reader, fetching
+/// only the byte ranges that contain the central directory and the requested files.
+///
+/// See [`wheel_metadata_from_remote_zip`] for the license of the derived source.
+///
+/// Returns the `.dist-info` prefix, along with the name and contents of each requested file, in the
+/// order requested. Files that are missing from the archive are omitted.
+pub(crate) async fn wheel_dist_info_from_remote_zip(
+    filename: &WheelFilename,
+    reader: &mut AsyncHttpRangeReader,
+    names: &[&str],
+) -> Result<(String, Vec<(String, String)>), Error> {
     // Make sure we have the back part of the stream.
     // Best guess for the central directory size inside the zip
     const CENTRAL_DIRECTORY_SIZE: u64 = 16384;
@@ -66,7 +87,7 @@ pub(crate) async fn wheel_metadata_from_remote_zip(
         .await
         .map_err(|err| ErrorKind::Zip(filename.clone(), err))?;
 
-    let ((metadata_idx, metadata_entry), _dist_info_prefix) = find_archive_dist_info(
+    let (_, dist_info_prefix) = find_archive_dist_info(
         filename,
         reader
             .file()
@@ -76,34 +97,52 @@ pub(crate) async fn wheel_metadata_from_remote_zip(
             .filter_map(|(idx, e)| Some(((idx, e), e.filename().as_str().ok()?))),
     )
     .map_err(ErrorKind::DistInfo)?;
+    let dist_info_prefix = dist_info_prefix.to_string();
 
-    let offset = metadata_entry.header_offset();
-    let size = metadata_entry.compressed_size()
-        + 30 // Header size in bytes
-        + metadata_entry.filename().as_bytes().len() as u64;
+    let mut files = Vec::with_capacity(names.len());
+    for name in names {
+        let path = format!("{dist_info_prefix}.dist-info/{name}");
+        let Some((idx, offset, size)) = reader
+            .file()
+            .entries()
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| entry.filename().as_str().is_ok_and(|name| name == path))
+            .map(|(idx, entry)| {
+                let size = entry.compressed_size()
+                    + 30 // Header size in bytes
+                    + entry.filename().as_bytes().len() as u64;
+                (idx, entry.header_offset(), size)
+            })
+        else {
+            continue;
+        };
 
-    // The zip archive uses as BufReader which reads in chunks of 8192. To ensure we prefetch
-    // enough data we round the size up to the nearest multiple of the buffer size.
-    let buffer_size = 8192;
-    let size = ((size + buffer_size - 1) / buffer_size) * buffer_size;
+        // The zip archive uses as BufReader which reads in chunks of 8192. To ensure we prefetch
+        // enough data we round the size up to the nearest multiple of the buffer size.
+        let buffer_size = 8192;
+        let size = ((size + buffer_size - 1) / buffer_size) * buffer_size;
 
-    // Fetch the bytes from the zip archive that contain the requested file.
-    reader
-        .inner_mut()
-        .get_mut()
-        .get_mut()
-        .prefetch(offset..offset + size)
-        .await;
+        // Fetch the bytes from the zip archive that contain the requested file.
+        reader
+            .inner_mut()
+            .get_mut()
+            .get_mut()
+            .prefetch(offset..offset + size)
+            .await;
 
-    // Read the contents of the METADATA file
-    let mut contents = String::new();
-    reader
-        .reader_with_entry(metadata_idx)
-        .await
-        .map_err(|err| ErrorKind::Zip(filename.clone(), err))?
-        .read_to_string_checked(&mut contents)
-        .await
-        .map_err(|err| ErrorKind::Zip(filename.clone(), err))?;
+        // Read the contents of the file.
+        let mut contents = String::new();
+        reader
+            .reader_with_entry(idx)
+            .await
+            .map_err(|err| ErrorKind::Zip(filename.clone(), err))?
+            .read_to_string_checked(&mut contents)
+            .await
+            .map_err(|err| ErrorKind::Zip(filename.clone(), err))?;
+
+        files.push(((*name).to_string(), contents));
+    }
 
-    Ok(contents)
+    Ok((dist_info_prefix, files))
 }
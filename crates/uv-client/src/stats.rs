@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counters of HTTP cache usage and network traffic (e.g., for `--summary-file`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkStats {
+    /// The number of cacheable requests served from the HTTP cache, including those that were
+    /// revalidated with the server.
+    pub cache_hits: u64,
+    /// The number of cacheable requests that required a full response from the server.
+    pub cache_misses: u64,
+    /// The number of (decompressed) response body bytes read from the network.
+    pub bytes_downloaded: u64,
+}
+
+impl NetworkStats {
+    /// The fraction of cacheable requests that were served from the HTTP cache, or `None` if no
+    /// cacheable requests were made.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        if total == 0 {
+            None
+        } else {
+            Some(self.cache_hits as f64 / total as f64)
+        }
+    }
+}
+
+/// Accumulates [`NetworkStats`] across every client that shares it.
+///
+/// Cloning the recorder shares the underlying counters, such that the clients built from a
+/// single [`BaseClientBuilder`](crate::BaseClientBuilder) all report to the same place.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkRecorder(Arc<Counters>);
+
+#[derive(Debug, Default)]
+struct Counters {
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    bytes_downloaded: AtomicU64,
+}
+
+impl NetworkRecorder {
+    /// Return the counters accumulated so far.
+    pub fn stats(&self) -> NetworkStats {
+        NetworkStats {
+            cache_hits: self.0.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.0.cache_misses.load(Ordering::Relaxed),
+            bytes_downloaded: self.0.bytes_downloaded.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Record a cacheable request that was served from the HTTP cache.
+    pub(crate) fn record_cache_hit(&self) {
+        self.0.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a cacheable request that required a full response from the server.
+    pub(crate) fn record_cache_miss(&self) {
+        self.0.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the given number of response body bytes as read from the network.
+    pub fn record_downloaded(&self, bytes: usize) {
+        self.0
+            .bytes_downloaded
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,98 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use async_compression::tokio::bufread::ZstdEncoder;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{OwnedArchive, RegistryClientBuilder};
+use uv_normalize::PackageName;
+
+#[tokio::test]
+async fn simple_api_zstd() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn a server that serves a `zstd`-compressed Simple API response, but only if the client
+    // advertises support for it.
+    let server_task = tokio::spawn(async move {
+        let svc = service_fn(move |req: Request<hyper::body::Incoming>| async move {
+            let accept_encoding = req
+                .headers()
+                .get(ACCEPT_ENCODING)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            assert!(
+                accept_encoding
+                    .split(',')
+                    .any(|encoding| encoding.trim() == "zstd"),
+                "Missing `zstd` in `Accept-Encoding`: {accept_encoding}"
+            );
+
+            let body = r#"{
+                "meta": {"api-version": "1.0"},
+                "name": "tqdm",
+                "files": [
+                    {
+                        "filename": "tqdm-4.66.1-py3-none-any.whl",
+                        "hashes": {"sha256": "d302b3c5b53d47bce91fea46679d9c3c6508cf6332229aa1e7d8653723793386"},
+                        "url": "https://files.pythonhosted.org/packages/00/e5/f12a80907d0884e6dff9c16d0c0114d81b8cd07dc3ae54c5e962cc83037e/tqdm-4.66.1-py3-none-any.whl"
+                    }
+                ]
+            }"#;
+            let mut compressed = Vec::new();
+            ZstdEncoder::new(body.as_bytes())
+                .read_to_end(&mut compressed)
+                .await?;
+
+            Response::builder()
+                .header(CONTENT_TYPE, "application/vnd.pypi.simple.v1+json")
+                .header(CONTENT_ENCODING, "zstd")
+                .body(Full::new(Bytes::from(compressed)))
+                .map_err(std::io::Error::other)
+        });
+        // Start Server (not wrapped in loop {} since we want a single response server)
+        let (socket, _) = listener.accept().await.unwrap();
+        let socket = TokioIo::new(socket);
+        tokio::task::spawn(async move {
+            http1::Builder::new()
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        });
+    });
+
+    // Point the client at the local index.
+    let index = IndexUrl::from_str(&format!("http://{addr}/simple"))?;
+    let index_locations = IndexLocations::new(Some(index), Vec::new(), Vec::new(), false);
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache)
+        .index_urls(index_locations.index_urls())
+        .build();
+
+    // Fetch and decode the response.
+    let results = client.simple(&PackageName::from_str("tqdm")?).await?;
+    let (_, metadata) = results.first().expect("Missing index response");
+    let metadata = OwnedArchive::deserialize(metadata);
+    let versions: Vec<String> = metadata
+        .iter()
+        .map(|datum| datum.version.to_string())
+        .collect();
+    assert_eq!(versions, vec!["4.66.1".to_string()]);
+
+    // Wait for the server task to complete, to be a good citizen.
+    server_task.await?;
+
+    Ok(())
+}
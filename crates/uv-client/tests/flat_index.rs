@@ -0,0 +1,62 @@
+use anyhow::Result;
+
+use distribution_types::FlatIndexLocation;
+use uv_cache::Cache;
+use uv_client::{FlatIndexClient, RegistryClientBuilder};
+
+#[tokio::test]
+async fn find_links_recursive() -> Result<()> {
+    // Create a wheelhouse organized by package.
+    let wheelhouse = tempfile::tempdir()?;
+    fs_err::write(
+        wheelhouse.path().join("iniconfig-2.0.0-py3-none-any.whl"),
+        "",
+    )?;
+    fs_err::create_dir_all(wheelhouse.path().join("tqdm"))?;
+    fs_err::write(
+        wheelhouse
+            .path()
+            .join("tqdm")
+            .join("tqdm-4.66.2-py3-none-any.whl"),
+        "",
+    )?;
+    fs_err::write(wheelhouse.path().join("tqdm").join("README.md"), "")?;
+
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let locations = [FlatIndexLocation::Path(wheelhouse.path().to_path_buf())];
+
+    // By default, only the top-level directory is read.
+    let entries = FlatIndexClient::new(&client, &cache)
+        .recursive(false)
+        .fetch(locations.iter())
+        .await?;
+    let mut filenames = entries
+        .entries
+        .iter()
+        .map(|(_, file, _)| file.filename.as_str())
+        .collect::<Vec<_>>();
+    filenames.sort_unstable();
+    assert_eq!(filenames, ["iniconfig-2.0.0-py3-none-any.whl"]);
+
+    // With recursion, distributions in subdirectories are included, too.
+    let entries = FlatIndexClient::new(&client, &cache)
+        .recursive(true)
+        .fetch(locations.iter())
+        .await?;
+    let mut filenames = entries
+        .entries
+        .iter()
+        .map(|(_, file, _)| file.filename.as_str())
+        .collect::<Vec<_>>();
+    filenames.sort_unstable();
+    assert_eq!(
+        filenames,
+        [
+            "iniconfig-2.0.0-py3-none-any.whl",
+            "tqdm-4.66.2-py3-none-any.whl"
+        ]
+    );
+
+    Ok(())
+}
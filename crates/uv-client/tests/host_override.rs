@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::HOST;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_configuration::HostOverride;
+
+#[tokio::test]
+async fn host_override() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Echo the `Host` header, to verify that the request was sent for the overridden host.
+    let server_task = tokio::spawn(async move {
+        let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+            let host = req
+                .headers()
+                .get(HOST)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            future::ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from(host))))
+        });
+        let (socket, _) = listener.accept().await.unwrap();
+        let socket = TokioIo::new(socket);
+        tokio::task::spawn(async move {
+            http1::Builder::new()
+                .serve_connection(socket, svc)
+                .await
+                .expect("Server Started");
+        });
+    });
+
+    // Pin a host that doesn't exist to the server. The override applies on any port, and the
+    // connection is made on the port of the requested URL.
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache)
+        .host_overrides(vec![HostOverride::from_str("index.invalid:443:127.0.0.1")?])
+        .build();
+
+    let res = client
+        .cached_client()
+        .uncached()
+        .get(format!("http://index.invalid:{}", addr.port()))
+        .send()
+        .await?;
+    assert!(res.status().is_success());
+    assert_eq!(res.text().await?, format!("index.invalid:{}", addr.port()));
+
+    server_task.await?;
+    Ok(())
+}
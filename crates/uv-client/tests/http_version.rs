@@ -0,0 +1,164 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::{http1, http2};
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+use uv_configuration::{HttpVersion, TrustedHost};
+
+mod common;
+
+/// Start an HTTP/1.1 server which responds `ok` to every request.
+async fn server() -> Result<Url> {
+    common::server(|_| Bytes::from("ok")).await
+}
+
+/// Start an HTTPS server which offers both HTTP/2 and HTTP/1.1, like a proxy that mangles HTTP/2
+/// streams: every HTTP/2 request is reset (and counted), while HTTP/1.1 requests succeed.
+async fn broken_http2_server(http2_requests: Arc<AtomicUsize>) -> Result<Url> {
+    let acceptor = common::self_signed_acceptor(vec![b"h2".to_vec(), b"http/1.1".to_vec()])?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            let http2_requests = http2_requests.clone();
+            tokio::spawn(async move {
+                let Ok(stream) = acceptor.accept(socket).await else {
+                    return;
+                };
+                if stream.get_ref().1.alpn_protocol() == Some(b"h2".as_slice()) {
+                    // hyper resets the stream if the service fails.
+                    let svc = service_fn(move |_req: Request<hyper::body::Incoming>| {
+                        http2_requests.fetch_add(1, Ordering::SeqCst);
+                        future::err::<Response<Full<Bytes>>, _>(std::io::Error::other("reset"))
+                    });
+                    let _ = http2::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(stream), svc)
+                        .await;
+                } else {
+                    let svc = service_fn(|_req: Request<hyper::body::Incoming>| {
+                        future::ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from("ok"))))
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), svc)
+                        .await;
+                }
+            });
+        }
+    });
+    Ok(Url::parse(&format!("https://{addr}/simple/"))?)
+}
+
+#[tokio::test]
+async fn http_version_default() -> Result<()> {
+    let url = server().await?;
+
+    let client = BaseClientBuilder::new().retries(0).build();
+    let res = client.get(url).send().await?;
+    assert_eq!(res.version(), http::Version::HTTP_11);
+    assert_eq!(res.text().await?, "ok");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_version_default_tls() -> Result<()> {
+    let http2_requests = Arc::new(AtomicUsize::new(0));
+    let url = broken_http2_server(http2_requests.clone()).await?;
+
+    // Even though the server offers HTTP/2, it's only used if requested.
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .trusted_hosts(vec![TrustedHost::from_str("127.0.0.1")?])
+        .build();
+    let res = client.get(url).send().await?;
+    assert_eq!(res.version(), http::Version::HTTP_11);
+    assert_eq!(res.text().await?, "ok");
+    assert_eq!(http2_requests.load(Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_version_auto_fallback() -> Result<()> {
+    let http2_requests = Arc::new(AtomicUsize::new(0));
+    let url = broken_http2_server(http2_requests.clone()).await?;
+
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .trusted_hosts(vec![TrustedHost::from_str("127.0.0.1")?])
+        .http_version(Some(HttpVersion::Auto))
+        .build();
+
+    // The first request is reset over HTTP/2, and retried over HTTP/1.1.
+    let res = client.get(url.clone()).send().await?;
+    assert_eq!(res.version(), http::Version::HTTP_11);
+    assert_eq!(res.text().await?, "ok");
+    assert_eq!(http2_requests.load(Ordering::SeqCst), 1);
+
+    // Once downgraded, subsequent requests to the host skip HTTP/2 entirely.
+    let res = client.get(url).send().await?;
+    assert_eq!(res.version(), http::Version::HTTP_11);
+    assert_eq!(res.text().await?, "ok");
+    assert_eq!(http2_requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_version_http2_no_fallback() -> Result<()> {
+    let http2_requests = Arc::new(AtomicUsize::new(0));
+    let url = broken_http2_server(http2_requests.clone()).await?;
+
+    // If HTTP/2 was requested explicitly, reset requests aren't retried over HTTP/1.1.
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .trusted_hosts(vec![TrustedHost::from_str("127.0.0.1")?])
+        .http_version(Some(HttpVersion::Http2))
+        .build();
+    assert!(client.get(url).send().await.is_err());
+    assert_eq!(http2_requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_version_http1() -> Result<()> {
+    let url = server().await?;
+
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .http_version(Some(HttpVersion::Http1))
+        .build();
+    let res = client.get(url).send().await?;
+    assert_eq!(res.version(), http::Version::HTTP_11);
+    assert_eq!(res.text().await?, "ok");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_version_http2() -> Result<()> {
+    let url = server().await?;
+
+    // The server only speaks HTTP/1.1, so forcing HTTP/2 fails.
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .http_version(Some(HttpVersion::Http2))
+        .build();
+    assert!(client.get(url).send().await.is_err());
+
+    Ok(())
+}
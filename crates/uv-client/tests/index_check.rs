@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{ACCEPT, CONTENT_TYPE, RANGE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::IndexUrl;
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+#[tokio::test]
+async fn check_index() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn a server that serves a JSON project page (but no HTML page) listing a single wheel,
+    // along with its standalone metadata, and that supports range requests for the wheel.
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let socket = TokioIo::new(socket);
+            let svc = service_fn(move |req: Request<hyper::body::Incoming>| async move {
+                let accept = req
+                    .headers()
+                    .get(ACCEPT)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+                match req.uri().path() {
+                    "/simple/tqdm/" if accept.contains("json") => {
+                        let body = r#"{
+                            "meta": {"api-version": "1.0"},
+                            "name": "tqdm",
+                            "files": [{
+                                "filename": "tqdm-4.66.2-py3-none-any.whl",
+                                "url": "/files/tqdm-4.66.2-py3-none-any.whl",
+                                "hashes": {},
+                                "core-metadata": true
+                            }]
+                        }"#;
+                        Response::builder()
+                            .header(CONTENT_TYPE, "application/vnd.pypi.simple.v1+json")
+                            .body(Full::new(Bytes::from(body)))
+                    }
+                    "/files/tqdm-4.66.2-py3-none-any.whl.metadata" => {
+                        Response::builder().body(Full::new(Bytes::from("Name: tqdm\n")))
+                    }
+                    "/files/tqdm-4.66.2-py3-none-any.whl" if req.headers().contains_key(RANGE) => {
+                        Response::builder()
+                            .status(StatusCode::PARTIAL_CONTENT)
+                            .body(Full::new(Bytes::from("P")))
+                    }
+                    _ => Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Full::new(Bytes::new())),
+                }
+                .map_err(std::io::Error::other)
+            });
+            tokio::task::spawn(async move {
+                http1::Builder::new()
+                    .serve_connection(socket, svc)
+                    .await
+                    .expect("Server Started");
+            });
+        }
+    });
+
+    let index = IndexUrl::from_str(&format!("http://{addr}/simple"))?;
+    let cache = Cache::temp()?.init()?;
+    let package = PackageName::from_str("tqdm")?;
+
+    let client = RegistryClientBuilder::new(cache).build();
+    let report = client.check_index(&index, &package).await;
+
+    assert_eq!(report.url.as_str(), format!("http://{addr}/simple/tqdm/"));
+    assert!(report.json.is_available());
+    assert_eq!(report.json.files, Some(1));
+    assert!(!report.html.is_available());
+    assert!(!report.requires_authentication());
+
+    let wheel = report.wheel.expect("a wheel should be probed");
+    assert_eq!(wheel.filename, "tqdm-4.66.2-py3-none-any.whl");
+    assert!(wheel.core_metadata);
+    assert!(wheel
+        .metadata_status
+        .is_some_and(|status| status.is_ok_and(|status| status.is_success())));
+    assert_eq!(wheel.range_requests, Ok(true));
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+#[tokio::test]
+async fn simple_api_index_max_age() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn a server that serves a Simple API response that is cacheable for ten minutes, and
+    // counts the number of requests it receives.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let socket = TokioIo::new(socket);
+            let counter = counter.clone();
+            let svc = service_fn(move |_req: Request<hyper::body::Incoming>| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let body = r#"{
+                        "meta": {"api-version": "1.0"},
+                        "name": "tqdm",
+                        "files": []
+                    }"#;
+                    Response::builder()
+                        .header(CONTENT_TYPE, "application/vnd.pypi.simple.v1+json")
+                        .header(CACHE_CONTROL, "max-age=600, public")
+                        .body(Full::new(Bytes::from(body)))
+                        .map_err(std::io::Error::other)
+                }
+            });
+            tokio::task::spawn(async move {
+                http1::Builder::new()
+                    .serve_connection(socket, svc)
+                    .await
+                    .expect("Server Started");
+            });
+        }
+    });
+
+    let index = IndexUrl::from_str(&format!("http://{addr}/simple"))?;
+    let index_locations = IndexLocations::new(Some(index), Vec::new(), Vec::new(), false);
+    let cache = Cache::temp()?.init()?;
+    let package = PackageName::from_str("tqdm")?;
+
+    // Populate the cache.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .build();
+    client.simple(&package).await?;
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // The cached response is still fresh, so it should be reused.
+    client.simple(&package).await?;
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // Once the cached response is older than the configured maximum age, it should be revalidated.
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let client = RegistryClientBuilder::new(cache)
+        .index_urls(index_locations.index_urls())
+        .index_max_age(Some(0))
+        .build();
+    client.simple(&package).await?;
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http::Extensions;
+use hyper::body::Bytes;
+use reqwest_middleware::{Middleware, Next};
+use url::Url;
+
+use uv_cache::Cache;
+use uv_client::{BaseClientBuilder, RegistryClientBuilder};
+
+mod common;
+
+/// Start an HTTP server which responds with the value of the `x-signature` header, if any.
+async fn server() -> Result<Url> {
+    common::server(|req| {
+        req.headers()
+            .get("x-signature")
+            .map(|value| Bytes::copy_from_slice(value.as_bytes()))
+            .unwrap_or_default()
+    })
+    .await
+}
+
+/// A middleware that signs each request, and counts the requests it has seen.
+struct SigningMiddleware {
+    requests: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Middleware for SigningMiddleware {
+    async fn handle(
+        &self,
+        mut req: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        req.headers_mut()
+            .insert("x-signature", http::HeaderValue::from_static("signed"));
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn base_client_middleware() -> Result<()> {
+    let url = server().await?;
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .middleware(SigningMiddleware {
+            requests: requests.clone(),
+        })
+        .build();
+    for _ in 0..2 {
+        let res = client.get(url.clone()).send().await?;
+        assert_eq!(res.text().await?, "signed");
+    }
+    assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn registry_client_middleware() -> Result<()> {
+    let url = server().await?;
+
+    let cache = Cache::temp()?.init()?;
+    let requests = Arc::new(AtomicUsize::new(0));
+    let client = RegistryClientBuilder::new(cache)
+        .retries(0)
+        .middleware(SigningMiddleware {
+            requests: requests.clone(),
+        })
+        .build();
+    let res = client.uncached_client().get(url).send().await?;
+    assert_eq!(res.text().await?, "signed");
+    assert_eq!(requests.load(Ordering::Relaxed), 1);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use hyper::body::Bytes;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+use uv_configuration::IndexRateLimit;
+
+mod common;
+
+/// Start an HTTP server which responds `ok` to every request.
+async fn server() -> Result<Url> {
+    common::server(|_| Bytes::from("ok")).await
+}
+
+#[tokio::test]
+async fn rate_limit() -> Result<()> {
+    let url = server().await?;
+
+    // With a burst of two, the first two requests are sent immediately, and the remaining two
+    // are spaced out at five requests per second.
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .rate_limits(vec![IndexRateLimit::from_str("127.0.0.1=5:2")?])
+        .build();
+    let start = Instant::now();
+    for _ in 0..4 {
+        let res = client.get(url.clone()).send().await?;
+        assert_eq!(res.text().await?, "ok");
+    }
+    assert!(start.elapsed() >= Duration::from_millis(400));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limit_other_host() -> Result<()> {
+    let url = server().await?;
+
+    // Requests to other hosts aren't limited.
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .rate_limits(vec![IndexRateLimit::from_str("pypi.internal=0.1")?])
+        .build();
+    let start = Instant::now();
+    for _ in 0..4 {
+        let res = client.get(url.clone()).send().await?;
+        assert_eq!(res.text().await?, "ok");
+    }
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    Ok(())
+}
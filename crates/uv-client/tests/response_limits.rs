@@ -0,0 +1,104 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{ErrorKind, RegistryClientBuilder, ResponseLimits};
+use uv_normalize::PackageName;
+
+/// Spawn a server that responds to every request with the given content type and body, returning
+/// the URL of its Simple API.
+async fn serve(content_type: &'static str, body: String) -> Result<IndexUrl> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let socket = TokioIo::new(socket);
+            let body = body.clone();
+            let svc = service_fn(move |_req: Request<hyper::body::Incoming>| {
+                let body = body.clone();
+                async move {
+                    Response::builder()
+                        .header(CONTENT_TYPE, content_type)
+                        .body(Full::new(Bytes::from(body)))
+                        .map_err(std::io::Error::other)
+                }
+            });
+            tokio::task::spawn(async move {
+                http1::Builder::new()
+                    .serve_connection(socket, svc)
+                    .await
+                    .expect("Server Started");
+            });
+        }
+    });
+
+    Ok(IndexUrl::from_str(&format!("http://{addr}/simple"))?)
+}
+
+#[tokio::test]
+async fn simple_api_response_too_large() -> Result<()> {
+    // Serve a page with many (valid) links, well beyond the configured limit.
+    let links = (0..1000)
+        .map(|i| format!(r#"<a href="tqdm-4.{i}.0.tar.gz">tqdm-4.{i}.0.tar.gz</a>"#))
+        .collect::<String>();
+    let index = serve("text/html", format!("<html><body>{links}</body></html>")).await?;
+
+    let index_locations = IndexLocations::new(Some(index), Vec::new(), Vec::new(), false);
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_locations.index_urls())
+        .response_limits(ResponseLimits {
+            simple: Some(1024),
+            metadata: None,
+        })
+        .build();
+
+    let err = client
+        .simple(&PackageName::from_str("tqdm")?)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::ResponseTooLarge { limit: 1024, .. }),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn simple_api_unsupported_version() -> Result<()> {
+    let body = r#"{
+        "meta": {"api-version": "2.0"},
+        "name": "tqdm",
+        "files": []
+    }"#;
+    let index = serve("application/vnd.pypi.simple.v1+json", body.to_string()).await?;
+
+    let index_locations = IndexLocations::new(Some(index), Vec::new(), Vec::new(), false);
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_locations.index_urls())
+        .build();
+
+    let err = client
+        .simple(&PackageName::from_str("tqdm")?)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::UnsupportedApiVersion(_, version) if version == "2.0"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, BetterReqwestError, NetworkTimeouts};
+
+/// Start a server that accepts connections, but never responds.
+async fn unresponsive_server() -> Result<Url> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    Ok(Url::parse(&format!("http://{addr}/simple/anyio/"))?)
+}
+
+/// Send a request with the given timeouts, and return the (friendly) error message.
+async fn timeout_error(timeouts: NetworkTimeouts) -> Result<String> {
+    let url = unresponsive_server().await?;
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .timeouts(timeouts)
+        .build();
+    let err = client
+        .get(url)
+        .send()
+        .await
+        .expect_err("the server never responds");
+    let reqwest_middleware::Error::Reqwest(err) = err else {
+        anyhow::bail!("expected a `reqwest` error, got: {err}");
+    };
+    Ok(BetterReqwestError::from(err).to_string())
+}
+
+#[tokio::test]
+async fn read_timeout() -> Result<()> {
+    let message = timeout_error(NetworkTimeouts {
+        read: Some(1),
+        ..NetworkTimeouts::default()
+    })
+    .await?;
+    assert_eq!(
+        message,
+        "Timed out waiting for a response from `127.0.0.1`; try increasing `--http-timeout` (`UV_HTTP_TIMEOUT`) or `--http-deadline` (`UV_HTTP_DEADLINE`)"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn deadline() -> Result<()> {
+    let start = Instant::now();
+    let message = timeout_error(NetworkTimeouts {
+        read: Some(60),
+        deadline: Some(1),
+        ..NetworkTimeouts::default()
+    })
+    .await?;
+    assert!(message.starts_with("Timed out waiting for a response from `127.0.0.1`"));
+    assert!(start.elapsed() < Duration::from_secs(30));
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::LOCATION;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, BetterReqwestError};
+use uv_configuration::TrustedHost;
+
+mod common;
+
+/// Start an HTTPS server with a self-signed certificate, which responds `ok` to every request.
+///
+/// Requests to `/redirect/same-host` are redirected to `/simple/` on the same host, and requests
+/// to `/redirect/other-host` are redirected to `/simple/` on `localhost` (rather than
+/// `127.0.0.1`).
+async fn self_signed_server() -> Result<Url> {
+    let acceptor = common::self_signed_acceptor(Vec::new())?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(stream) = acceptor.accept(socket).await else {
+                    return;
+                };
+                let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+                    let location = match req.uri().path() {
+                        "/redirect/same-host" => Some(format!("https://{addr}/simple/")),
+                        "/redirect/other-host" => {
+                            Some(format!("https://localhost:{}/simple/", addr.port()))
+                        }
+                        _ => None,
+                    };
+                    let response = if let Some(location) = location {
+                        Response::builder()
+                            .status(StatusCode::FOUND)
+                            .header(LOCATION, location)
+                            .body(Full::new(Bytes::new()))
+                    } else {
+                        Response::builder().body(Full::new(Bytes::from("ok")))
+                    };
+                    future::ready(response)
+                });
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), svc)
+                    .await;
+            });
+        }
+    });
+    Ok(Url::parse(&format!("https://{addr}/simple/"))?)
+}
+
+#[tokio::test]
+async fn trusted_host() -> Result<()> {
+    let url = self_signed_server().await?;
+
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .trusted_hosts(vec![TrustedHost::from_str("127.0.0.1")?])
+        .build();
+    let res = client.get(url.clone()).send().await?;
+    assert_eq!(res.text().await?, "ok");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn untrusted_host() -> Result<()> {
+    let url = self_signed_server().await?;
+
+    // Neither an untrusted client, nor a client that trusts the host on a different port, should
+    // accept the certificate.
+    for trusted_hosts in [vec![], vec![TrustedHost::from_str("127.0.0.1:1")?]] {
+        let client = BaseClientBuilder::new()
+            .retries(0)
+            .trusted_hosts(trusted_hosts)
+            .build();
+        let err = client
+            .get(url.clone())
+            .send()
+            .await
+            .expect_err("the certificate is self-signed");
+        let reqwest_middleware::Error::Reqwest(err) = err else {
+            anyhow::bail!("expected a `reqwest` error, got: {err}");
+        };
+        let message = BetterReqwestError::from(err).to_string();
+        assert!(
+            message.starts_with("TLS handshake with `127.0.0.1` failed"),
+            "{message}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trusted_host_redirect() -> Result<()> {
+    let url = self_signed_server().await?;
+
+    let client = BaseClientBuilder::new()
+        .retries(0)
+        .trusted_hosts(vec![TrustedHost::from_str("127.0.0.1")?])
+        .build();
+
+    // A redirect to the same (trusted) host should be followed.
+    let res = client.get(url.join("/redirect/same-host")?).send().await?;
+    assert_eq!(res.text().await?, "ok");
+
+    // A redirect to a host that isn't trusted should be refused, even though the certificate is
+    // also valid for `localhost`.
+    let err = client
+        .get(url.join("/redirect/other-host")?)
+        .send()
+        .await
+        .expect_err("the redirect target isn't trusted");
+    let message = anyhow::Error::new(err)
+        .chain()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(
+        message.contains("Refusing to follow a redirect to `https://localhost:"),
+        "{message}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+
+/// An external command with which to build a package, specified as `PACKAGE=COMMAND`.
+#[derive(Debug, Clone)]
+pub struct BuildCommandEntry {
+    /// The package to build. For example, given `package=command`, this would be `package`.
+    package: PackageName,
+    /// The command to run. For example, given `package=command`, this would be `command`.
+    command: String,
+}
+
+impl FromStr for BuildCommandEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, command)) = s.split_once('=') else {
+            return Err(anyhow::anyhow!(
+                "Invalid build command: {s} (expected `PACKAGE=COMMAND`)"
+            ));
+        };
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Invalid build command: {s} (expected a non-empty `COMMAND`)"
+            ));
+        }
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            command: command.to_string(),
+        })
+    }
+}
+
+/// External commands with which to build specific packages, in lieu of the built-in PEP 517 build
+/// frontend (e.g., to build within a hermetic Bazel or Nix sandbox), structured as a map from
+/// package name to command.
+///
+/// Each command is run via the platform shell, with the path to the source tree and the path to an
+/// output directory appended as arguments. On success, the command must print the path to the
+/// built wheel as the last line of its standard output.
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BuildCommands(BTreeMap<PackageName, String>);
+
+impl FromIterator<BuildCommandEntry> for BuildCommands {
+    fn from_iter<T: IntoIterator<Item = BuildCommandEntry>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|entry| (entry.package, entry.command))
+                .collect(),
+        )
+    }
+}
+
+impl BuildCommands {
+    /// Returns the external build command for the given package, if any.
+    pub fn get(&self, package: &PackageName) -> Option<&str> {
+        self.0.get(package).map(String::as_str)
+    }
+
+    /// Merge two sets of external build commands, preferring the values in `self`.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        let mut commands = other.0;
+        commands.extend(self.0);
+        Self(commands)
+    }
+
+    /// Returns `true` if no external build commands are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_build_commands() {
+        let commands: BuildCommands = ["foo=bazel run //:foo --", " bar = ./build.sh "]
+            .into_iter()
+            .map(|entry| BuildCommandEntry::from_str(entry).unwrap())
+            .collect();
+        assert_eq!(
+            commands.get(&PackageName::from_str("foo").unwrap()),
+            Some("bazel run //:foo --")
+        );
+        assert_eq!(
+            commands.get(&PackageName::from_str("bar").unwrap()),
+            Some("./build.sh")
+        );
+        assert_eq!(commands.get(&PackageName::from_str("baz").unwrap()), None);
+
+        assert!(BuildCommandEntry::from_str("foo").is_err());
+        assert!(BuildCommandEntry::from_str("foo=").is_err());
+    }
+}
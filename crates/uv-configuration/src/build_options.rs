@@ -110,6 +110,24 @@ impl NoBinary {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Returns `true` if installing wheels is disallowed for the given package.
+    ///
+    /// As in pip, a package named explicitly takes precedence over `:all:`, and `--only-binary`
+    /// takes precedence over `--no-binary`. For example, `--no-binary :all: --only-binary foo`
+    /// allows wheels for `foo`.
+    pub fn no_binary_package(&self, package: &PackageName, no_build: &NoBuild) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => !no_build.contains(package),
+            Self::Packages(packages) => packages.contains(package) && !no_build.contains(package),
+        }
+    }
+
+    /// Returns `true` if the given package is named explicitly.
+    fn contains(&self, package: &PackageName) -> bool {
+        matches!(self, Self::Packages(packages) if packages.contains(package))
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -123,6 +141,9 @@ pub enum NoBuild {
 
     /// Do not allow building wheels from the given package's source distributions.
     Packages(Vec<PackageName>),
+
+    /// Only allow building wheels from the given packages' source distributions.
+    AllExcept(Vec<PackageName>),
 }
 
 impl NoBuild {
@@ -145,6 +166,18 @@ impl NoBuild {
         Self::from_args(vec![no_build], false)
     }
 
+    /// Restrict builds to the given packages, such that every other package must be installed
+    /// from a pre-built wheel.
+    ///
+    /// An empty allow-list leaves the strategy unchanged.
+    #[must_use]
+    pub fn with_allowed(self, allow_build: Vec<PackageName>) -> Self {
+        if allow_build.is_empty() {
+            return self;
+        }
+        self.combine(Self::AllExcept(allow_build))
+    }
+
     /// Combine a set of [`NoBuild`] values.
     #[must_use]
     pub fn combine(self, other: Self) -> Self {
@@ -156,36 +189,30 @@ impl NoBuild {
             // If one is `None`, the result is the other.
             (Self::Packages(a), Self::None) => Self::Packages(a),
             (Self::None, Self::Packages(b)) => Self::Packages(b),
+            (Self::AllExcept(a), Self::None) => Self::AllExcept(a),
+            (Self::None, Self::AllExcept(b)) => Self::AllExcept(b),
             // If both are `Packages`, the result is the union of the two.
             (Self::Packages(mut a), Self::Packages(b)) => {
                 a.extend(b);
                 Self::Packages(a)
             }
+            // If one is an allow-list, the result excludes the other's packages from it.
+            (Self::AllExcept(mut a), Self::Packages(b))
+            | (Self::Packages(b), Self::AllExcept(mut a)) => {
+                a.retain(|package| !b.contains(package));
+                Self::AllExcept(a)
+            }
+            // If both are allow-lists, the result is the intersection of the two.
+            (Self::AllExcept(mut a), Self::AllExcept(b)) => {
+                a.retain(|package| b.contains(package));
+                Self::AllExcept(a)
+            }
         }
     }
 
     /// Extend a [`NoBuild`] value with another.
     pub fn extend(&mut self, other: Self) {
-        match (&mut *self, other) {
-            // If either is `All`, the result is `All`.
-            (Self::All, _) | (_, Self::All) => *self = Self::All,
-            // If both are `None`, the result is `None`.
-            (Self::None, Self::None) => {
-                // Nothing to do.
-            }
-            // If one is `None`, the result is the other.
-            (Self::Packages(_), Self::None) => {
-                // Nothing to do.
-            }
-            (Self::None, Self::Packages(b)) => {
-                // Take ownership of `b`.
-                *self = Self::Packages(b);
-            }
-            // If both are `Packages`, the result is the union of the two.
-            (Self::Packages(a), Self::Packages(b)) => {
-                a.extend(b);
-            }
-        }
+        *self = std::mem::take(self).combine(other);
     }
 }
 
@@ -194,6 +221,113 @@ impl NoBuild {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+
+    /// Returns `true` if building source distributions is disallowed for the given package.
+    ///
+    /// As in pip, a package named explicitly takes precedence over `:all:`. For example,
+    /// `--only-binary :all: --no-binary foo` allows building `foo` from source.
+    ///
+    /// An allow-list is strict: naming a package in `--no-binary` doesn't permit building it.
+    pub fn no_build_package(&self, package: &PackageName, no_binary: &NoBinary) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => !no_binary.contains(package),
+            Self::Packages(packages) => packages.contains(package),
+            Self::AllExcept(allowed) => !allowed.contains(package),
+        }
+    }
+
+    /// Returns `true` if building source distributions is disallowed for packages whose name
+    /// isn't known (e.g., direct URLs to source archives).
+    pub fn no_build_unnamed(&self) -> bool {
+        matches!(self, Self::All | Self::AllExcept(_))
+    }
+
+    /// Returns `true` if the given package is named explicitly.
+    fn contains(&self, package: &PackageName) -> bool {
+        matches!(self, Self::Packages(packages) if packages.contains(package))
+    }
+}
+
+/// Whether to prefer source distributions over pre-built wheels, when both are available.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum PreferSource {
+    /// Prefer pre-built wheels for all packages.
+    #[default]
+    None,
+
+    /// Prefer source distributions for all packages.
+    All,
+
+    /// Prefer source distributions for the given packages.
+    Packages(Vec<PackageName>),
+}
+
+impl PreferSource {
+    /// Determine the source preference to use for the given arguments.
+    pub fn from_args(prefer_source: Vec<PackageNameSpecifier>) -> Self {
+        let combined = PackageNameSpecifiers::from_iter(prefer_source.into_iter());
+        match combined {
+            PackageNameSpecifiers::All => Self::All,
+            PackageNameSpecifiers::None => Self::None,
+            PackageNameSpecifiers::Packages(packages) => Self::Packages(packages),
+        }
+    }
+
+    /// Returns `true` if wheels are preferred for all packages.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Returns `true` if source distributions are preferred for the given package.
+    pub fn prefer_source_package(&self, package: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Packages(packages) => packages.contains(package),
+        }
+    }
+}
+
+/// Whether to prefer older versions with a compatible wheel over newer versions that would need
+/// to be built from a source distribution.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum PreferBinary {
+    /// Select the newest compatible version for all packages, regardless of its distributions.
+    #[default]
+    None,
+
+    /// Prefer versions with a compatible wheel for all packages.
+    All,
+
+    /// Prefer versions with a compatible wheel for the given packages.
+    Packages(Vec<PackageName>),
+}
+
+impl PreferBinary {
+    /// Determine the binary preference to use for the given arguments.
+    pub fn from_args(prefer_binary: Vec<PackageNameSpecifier>) -> Self {
+        let combined = PackageNameSpecifiers::from_iter(prefer_binary.into_iter());
+        match combined {
+            PackageNameSpecifiers::All => Self::All,
+            PackageNameSpecifiers::None => Self::None,
+            PackageNameSpecifiers::Packages(packages) => Self::Packages(packages),
+        }
+    }
+
+    /// Returns `true` if no packages prefer versions with a compatible wheel.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Returns `true` if versions with a compatible wheel are preferred for the given package.
+    pub fn prefer_binary_package(&self, package: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Packages(packages) => packages.contains(package),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, serde::Deserialize)]
@@ -300,4 +434,107 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn prefer_source_from_args() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+
+        let prefer_source = PreferSource::from_args(vec![PackageNameSpecifier::from_str("foo")?]);
+        assert!(prefer_source.prefer_source_package(&foo));
+        assert!(!prefer_source.prefer_source_package(&bar));
+
+        let prefer_source = PreferSource::from_args(vec![PackageNameSpecifier::from_str(":all:")?]);
+        assert!(prefer_source.prefer_source_package(&bar));
+
+        let prefer_source = PreferSource::from_args(vec![
+            PackageNameSpecifier::from_str("foo")?,
+            PackageNameSpecifier::from_str(":none:")?,
+        ]);
+        assert!(prefer_source.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn prefer_binary_from_args() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+
+        let prefer_binary = PreferBinary::from_args(vec![PackageNameSpecifier::from_str("foo")?]);
+        assert!(prefer_binary.prefer_binary_package(&foo));
+        assert!(!prefer_binary.prefer_binary_package(&bar));
+
+        let prefer_binary = PreferBinary::from_args(vec![PackageNameSpecifier::from_str(":all:")?]);
+        assert!(prefer_binary.prefer_binary_package(&bar));
+
+        let prefer_binary = PreferBinary::from_args(vec![
+            PackageNameSpecifier::from_str(":all:")?,
+            PackageNameSpecifier::from_str(":none:")?,
+        ]);
+        assert!(prefer_binary.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn package_precedence() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+
+        // `--no-binary :all: --only-binary foo`
+        let no_binary = NoBinary::All;
+        let no_build = NoBuild::Packages(vec![foo.clone()]);
+        assert!(!no_binary.no_binary_package(&foo, &no_build));
+        assert!(no_binary.no_binary_package(&bar, &no_build));
+        assert!(no_build.no_build_package(&foo, &no_binary));
+        assert!(!no_build.no_build_package(&bar, &no_binary));
+
+        // `--only-binary :all: --no-binary foo`
+        let no_binary = NoBinary::Packages(vec![foo.clone()]);
+        let no_build = NoBuild::All;
+        assert!(no_binary.no_binary_package(&foo, &no_build));
+        assert!(!no_binary.no_binary_package(&bar, &no_build));
+        assert!(!no_build.no_build_package(&foo, &no_binary));
+        assert!(no_build.no_build_package(&bar, &no_binary));
+
+        // `--no-binary foo --only-binary foo`
+        let no_binary = NoBinary::Packages(vec![foo.clone()]);
+        let no_build = NoBuild::Packages(vec![foo.clone()]);
+        assert!(!no_binary.no_binary_package(&foo, &no_build));
+        assert!(no_build.no_build_package(&foo, &no_binary));
+
+        Ok(())
+    }
+
+    #[test]
+    fn allow_build() -> Result<(), Error> {
+        let foo = PackageName::from_str("foo")?;
+        let bar = PackageName::from_str("bar")?;
+
+        // `--allow-build foo`
+        let no_build = NoBuild::None.with_allowed(vec![foo.clone()]);
+        assert_eq!(no_build, NoBuild::AllExcept(vec![foo.clone()]));
+        assert!(!no_build.no_build_package(&foo, &NoBinary::None));
+        assert!(no_build.no_build_package(&bar, &NoBinary::None));
+        assert!(no_build.no_build_unnamed());
+
+        // `--no-build-package foo`: unnamed requirements can still be built.
+        assert!(!NoBuild::Packages(vec![foo.clone()]).no_build_unnamed());
+
+        // `--allow-build foo --no-binary bar`: the allow-list is strict.
+        assert!(no_build.no_build_package(&bar, &NoBinary::Packages(vec![bar.clone()])));
+
+        // `--allow-build foo --allow-build bar --only-binary foo`
+        let no_build = NoBuild::Packages(vec![foo.clone()]).with_allowed(vec![foo, bar.clone()]);
+        assert_eq!(no_build, NoBuild::AllExcept(vec![bar]));
+
+        // `--allow-build foo --only-binary :all:`
+        assert_eq!(
+            NoBuild::All.with_allowed(vec![PackageName::from_str("foo")?]),
+            NoBuild::All
+        );
+
+        Ok(())
+    }
 }
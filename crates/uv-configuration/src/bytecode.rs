@@ -0,0 +1,40 @@
+/// The invalidation mode to use for compiled bytecode (`.pyc`) files.
+///
+/// See: <https://docs.python.org/3/library/py_compile.html#py_compile.PycInvalidationMode>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum PycInvalidationMode {
+    /// Invalidate the bytecode based on the source file's modification time and size.
+    Timestamp,
+    /// Invalidate the bytecode based on a hash of the source file, which is checked on import.
+    CheckedHash,
+    /// Embed a hash of the source file in the bytecode, but never check it on import.
+    UncheckedHash,
+}
+
+impl PycInvalidationMode {
+    /// Returns the name of the mode, as understood by `py_compile.PycInvalidationMode`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timestamp => "TIMESTAMP",
+            Self::CheckedHash => "CHECKED_HASH",
+            Self::UncheckedHash => "UNCHECKED_HASH",
+        }
+    }
+}
+
+/// The options to use when compiling Python source files to bytecode.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// The optimization levels to compile for (e.g., `1` for `-O`, `2` for `-OO`). If empty, the
+    /// interpreter's default optimization level is used.
+    pub optimization_levels: Vec<u8>,
+    /// Glob patterns, relative to `site-packages`, for files and directories to exclude from
+    /// compilation (e.g., `debugpy` or `*/tests/*`).
+    pub exclude: Vec<String>,
+    /// The invalidation mode to use for the compiled bytecode. If unset, the interpreter's
+    /// default (or the `SOURCE_DATE_EPOCH`-dependent default) is used.
+    pub invalidation_mode: Option<PycInvalidationMode>,
+}
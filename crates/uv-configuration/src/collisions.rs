@@ -0,0 +1,17 @@
+/// How to handle a file that's written by multiple distributions with differing contents (e.g., a
+/// module that's provided by two packages, or a namespace package with a non-empty
+/// `__init__.py`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FileCollisionPolicy {
+    /// Refuse to install any distributions whose files collide.
+    Error,
+    /// Warn about any colliding files, and install them regardless, such that the last
+    /// distribution to be installed wins.
+    #[default]
+    Warn,
+    /// Skip the check for colliding files.
+    Ignore,
+}
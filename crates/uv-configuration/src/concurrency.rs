@@ -15,6 +15,10 @@ pub struct Concurrency {
     ///
     /// Note this value must be non-zero.
     pub installs: usize,
+    /// The number of threads used to hash, unzip, and verify wheels.
+    ///
+    /// Note this value must be non-zero.
+    pub unzips: usize,
 }
 
 impl Default for Concurrency {
@@ -23,6 +27,7 @@ impl Default for Concurrency {
             downloads: Concurrency::DEFAULT_DOWNLOADS,
             builds: Concurrency::threads(),
             installs: Concurrency::threads(),
+            unzips: Concurrency::threads(),
         }
     }
 }
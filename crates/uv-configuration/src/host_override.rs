@@ -0,0 +1,162 @@
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+/// A DNS override for a host, in the style of curl's `--resolve`: `HOST:PORT:ADDR[,ADDR...]`.
+///
+/// Connections to `HOST` use the given addresses instead of resolving the host via DNS. IPv6
+/// addresses may be enclosed in brackets, as in `pypi.internal:443:[fd00::1]`.
+///
+/// Unlike curl, the override applies to the host regardless of the port being connected to; the
+/// port is accepted (and may be `*`) for compatibility.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOverride {
+    host: String,
+    port: Option<u16>,
+    addrs: Vec<IpAddr>,
+}
+
+impl HostOverride {
+    /// The host to override, e.g., `pypi.internal`.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The addresses to connect to.
+    ///
+    /// The port of each address is `0`, such that connections are made on the port of the
+    /// requested URL (or the scheme's default port), rather than the port of the override.
+    pub fn socket_addrs(&self) -> Vec<SocketAddr> {
+        self.addrs
+            .iter()
+            .map(|addr| SocketAddr::new(*addr, 0))
+            .collect()
+    }
+}
+
+impl FromStr for HostOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let (Some(host), Some(port), Some(addrs)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow::anyhow!(
+                "Invalid host override: {s} (expected `HOST:PORT:ADDR[,ADDR...]`)"
+            ));
+        };
+
+        let host = host.trim();
+        if host.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Invalid host override: {s} (expected a host name)"
+            ));
+        }
+
+        let port = match port.trim() {
+            "*" => None,
+            port => Some(port.parse::<u16>().map_err(|_| {
+                anyhow::anyhow!("Invalid host override: {s} (expected a port number or `*`)")
+            })?),
+        };
+
+        let addrs = addrs
+            .split(',')
+            .map(|addr| {
+                let addr = addr.trim();
+                let addr = addr
+                    .strip_prefix('[')
+                    .and_then(|addr| addr.strip_suffix(']'))
+                    .unwrap_or(addr);
+                addr.parse::<IpAddr>().map_err(|_| {
+                    anyhow::anyhow!("Invalid host override: {s} (`{addr}` is not an IP address)")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            port,
+            addrs,
+        })
+    }
+}
+
+impl Display for HostOverride {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.host)?;
+        match self.port {
+            Some(port) => write!(f, "{port}:")?,
+            None => write!(f, "*:")?,
+        }
+        for (i, addr) in self.addrs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            match addr {
+                IpAddr::V4(addr) => write!(f, "{addr}")?,
+                IpAddr::V6(addr) => write!(f, "[{addr}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for HostOverride {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        HostOverride::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for HostOverride {
+    fn schema_name() -> String {
+        "HostOverride".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A DNS override for a host, as `HOST:PORT:ADDR[,ADDR...]`, e.g., `pypi.internal:443:10.0.0.5`.".to_string()),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+
+    use super::HostOverride;
+
+    #[test]
+    fn parse() {
+        let host = HostOverride::from_str("PyPI.internal:443:10.0.0.5,[fd00::1]").unwrap();
+        assert_eq!(host.host(), "pypi.internal");
+        assert_eq!(
+            host.socket_addrs(),
+            vec![
+                "10.0.0.5:0".parse::<SocketAddr>().unwrap(),
+                "[fd00::1]:0".parse::<SocketAddr>().unwrap()
+            ]
+        );
+        assert_eq!(host.to_string(), "pypi.internal:443:10.0.0.5,[fd00::1]");
+
+        let host = HostOverride::from_str("pypi.internal:*:10.0.0.5").unwrap();
+        assert_eq!(host.to_string(), "pypi.internal:*:10.0.0.5");
+
+        assert!(HostOverride::from_str("pypi.internal:10.0.0.5").is_err());
+        assert!(HostOverride::from_str("pypi.internal:https:10.0.0.5").is_err());
+        assert!(HostOverride::from_str("pypi.internal:443:pypi.org").is_err());
+        assert!(HostOverride::from_str(":443:10.0.0.5").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The HTTP version to use for requests, as `1.1`, `2`, or `auto`.
+///
+/// Defaults to HTTP/1.1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// Only use HTTP/1.1.
+    #[default]
+    Http1,
+    /// Only use HTTP/2, without negotiating the version with the server.
+    Http2,
+    /// Negotiate the version with each server (preferring HTTP/2), and retry requests that fail
+    /// with an HTTP/2 protocol error over HTTP/1.1.
+    Auto,
+}
+
+impl FromStr for HttpVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let version = s
+            .strip_prefix("HTTP/")
+            .or_else(|| s.strip_prefix("http/"))
+            .unwrap_or(s);
+        match version {
+            "1.1" => Ok(Self::Http1),
+            "2" | "2.0" => Ok(Self::Http2),
+            "auto" => Ok(Self::Auto),
+            _ => Err(anyhow::anyhow!(
+                "Invalid HTTP version: {s} (expected `1.1`, `2`, or `auto`)"
+            )),
+        }
+    }
+}
+
+impl Display for HttpVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http1 => write!(f, "HTTP/1.1"),
+            Self::Http2 => write!(f, "HTTP/2"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for HttpVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        HttpVersion::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for HttpVersion {
+    fn schema_name() -> String {
+        "HttpVersion".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            enum_values: Some(vec!["1.1".into(), "2".into(), "auto".into()]),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some(
+                    "The HTTP version to use for requests, as `1.1`, `2`, or `auto`.".to_string(),
+                ),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::HttpVersion;
+
+    #[test]
+    fn parse() {
+        assert_eq!(HttpVersion::from_str("1.1").unwrap(), HttpVersion::Http1);
+        assert_eq!(
+            HttpVersion::from_str("HTTP/1.1").unwrap(),
+            HttpVersion::Http1
+        );
+        assert_eq!(HttpVersion::from_str("2").unwrap(), HttpVersion::Http2);
+        assert_eq!(HttpVersion::from_str("http/2").unwrap(), HttpVersion::Http2);
+        assert_eq!(HttpVersion::from_str("auto").unwrap(), HttpVersion::Auto);
+        assert!(HttpVersion::from_str("1.0").is_err());
+        assert!(HttpVersion::from_str("3").is_err());
+    }
+}
@@ -1,21 +1,41 @@
 pub use authentication::*;
+pub use build_commands::*;
 pub use build_options::*;
+pub use bytecode::*;
+pub use collisions::*;
 pub use concurrency::*;
 pub use config_settings::*;
 pub use constraints::*;
+pub use host_override::*;
+pub use http_version::*;
 pub use name_specifiers::*;
 pub use overrides::*;
 pub use package_options::*;
+pub use patches::*;
 pub use preview::*;
+pub use python_preference::*;
+pub use rate_limit::*;
+pub use seed_source::*;
 pub use target_triple::*;
+pub use trusted_host::*;
 
 mod authentication;
+mod build_commands;
 mod build_options;
+mod bytecode;
+mod collisions;
 mod concurrency;
 mod config_settings;
 mod constraints;
+mod host_override;
+mod http_version;
 mod name_specifiers;
 mod overrides;
 mod package_options;
+mod patches;
 mod preview;
+mod python_preference;
+mod rate_limit;
+mod seed_source;
 mod target_triple;
+mod trusted_host;
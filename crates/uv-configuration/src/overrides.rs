@@ -6,32 +6,62 @@ use rustc_hash::FxHashMap;
 use distribution_types::Requirement;
 use uv_normalize::PackageName;
 
+/// The override for a single package.
+#[derive(Debug, Clone)]
+enum Override {
+    /// Replace any requirement on the package with the given requirements.
+    Requirements(Vec<Requirement>),
+    /// Drop any requirement on the package, omitting it from the resolution entirely.
+    Exclude,
+}
+
 /// A set of overrides for a set of requirements.
 #[derive(Debug, Default, Clone)]
-pub struct Overrides(FxHashMap<PackageName, Vec<Requirement>>);
+pub struct Overrides(FxHashMap<PackageName, Override>);
 
 impl Overrides {
     /// Create a new set of overrides from a set of requirements.
     pub fn from_requirements(requirements: Vec<Requirement>) -> Self {
-        let mut overrides: FxHashMap<PackageName, Vec<Requirement>> =
+        let mut overrides: FxHashMap<PackageName, Override> =
             FxHashMap::with_capacity_and_hasher(requirements.len(), BuildHasherDefault::default());
         for requirement in requirements {
-            overrides
+            match overrides
                 .entry(requirement.name.clone())
-                .or_default()
-                .push(requirement);
+                .or_insert_with(|| Override::Requirements(Vec::new()))
+            {
+                Override::Requirements(requirements) => requirements.push(requirement),
+                Override::Exclude => {}
+            }
         }
         Self(overrides)
     }
 
+    /// Exclude the given packages, such that any requirement on them is dropped, in lieu of any
+    /// override requirements for the same packages.
+    #[must_use]
+    pub fn with_exclusions(mut self, packages: impl IntoIterator<Item = PackageName>) -> Self {
+        for package in packages {
+            self.0.insert(package, Override::Exclude);
+        }
+        self
+    }
+
     /// Return an iterator over all [`Requirement`]s in the override set.
     pub fn requirements(&self) -> impl Iterator<Item = &Requirement> {
-        self.0.values().flat_map(|requirements| requirements.iter())
+        self.0.values().flat_map(|value| match value {
+            Override::Requirements(requirements) => requirements.as_slice(),
+            Override::Exclude => &[],
+        })
     }
 
     /// Get the overrides for a package.
-    pub fn get(&self, name: &PackageName) -> Option<&Vec<Requirement>> {
-        self.0.get(name)
+    ///
+    /// Returns an empty slice if the package is excluded.
+    pub fn get(&self, name: &PackageName) -> Option<&[Requirement]> {
+        self.0.get(name).map(|value| match value {
+            Override::Requirements(requirements) => requirements.as_slice(),
+            Override::Exclude => &[],
+        })
     }
 
     /// Apply the overrides to a set of requirements.
@@ -82,4 +82,35 @@ impl Upgrade {
     pub fn is_all(&self) -> bool {
         matches!(self, Self::All)
     }
+
+    /// Apply the given [`UpgradeStrategy`] to the upgrade.
+    ///
+    /// Under [`UpgradeStrategy::OnlyIfNeeded`], an upgrade of all packages is limited to the given
+    /// direct requirements, such that transitive dependencies are only upgraded if required by the
+    /// upgraded direct requirements.
+    #[must_use]
+    pub fn with_strategy(
+        self,
+        strategy: UpgradeStrategy,
+        direct: impl IntoIterator<Item = PackageName>,
+    ) -> Self {
+        match (self, strategy) {
+            (Self::All, UpgradeStrategy::OnlyIfNeeded) => {
+                Self::Packages(direct.into_iter().collect())
+            }
+            (upgrade, _) => upgrade,
+        }
+    }
+}
+
+/// The strategy to use when upgrading the dependencies of upgraded packages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum UpgradeStrategy {
+    /// Upgrade all packages, including transitive dependencies, to the latest compatible version.
+    #[default]
+    Eager,
+    /// Upgrade direct requirements; upgrade transitive dependencies only if the installed version
+    /// no longer satisfies the upgraded requirements.
+    OnlyIfNeeded,
 }
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use rustc_hash::FxHashMap;
+
+use distribution_types::{ParsedUrl, Requirement, RequirementSource};
+use pep508_rs::VerbatimUrl;
+use uv_normalize::PackageName;
+
+/// A replacement source for a package that's otherwise fetched from an index, as in
+/// `[tool.uv.pip.patch]`.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum PackagePatch {
+    /// A remote Git repository, available over HTTPS or SSH.
+    ///
+    /// Example:
+    /// ```toml
+    /// flask = { git = "https://github.com/pallets/flask", branch = "main" }
+    /// ```
+    Git {
+        /// The repository URL (without the `git+` prefix).
+        git: String,
+        /// The path to the directory with the `pyproject.toml`, if it's not in the repository
+        /// root.
+        subdirectory: Option<String>,
+        // Only one of the three may be used.
+        rev: Option<String>,
+        tag: Option<String>,
+        branch: Option<String>,
+    },
+    /// The path to a local source tree, wheel, or source distribution, relative to the directory
+    /// containing the configuration file.
+    ///
+    /// Example:
+    /// ```toml
+    /// flask = { path = "../flask" }
+    /// ```
+    Path { path: PathBuf },
+}
+
+impl PackagePatch {
+    /// Convert the patch to the [`RequirementSource`] with which to replace a requirement's source.
+    fn to_source(&self) -> Result<RequirementSource> {
+        let url = match self {
+            Self::Git {
+                git,
+                subdirectory,
+                rev,
+                tag,
+                branch,
+            } => {
+                let reference = match (rev, tag, branch) {
+                    (None, None, None) => None,
+                    (Some(reference), None, None)
+                    | (None, Some(reference), None)
+                    | (None, None, Some(reference)) => Some(reference),
+                    _ => return Err(anyhow!("Can only specify one of `rev`, `tag`, or `branch`")),
+                };
+
+                // Create a PEP 508-compatible URL.
+                let mut url = if git.starts_with("git+") {
+                    git.clone()
+                } else {
+                    format!("git+{git}")
+                };
+                if let Some(reference) = reference {
+                    url = format!("{url}@{reference}");
+                }
+                if let Some(subdirectory) = subdirectory {
+                    url = format!("{url}#subdirectory={subdirectory}");
+                }
+                VerbatimUrl::parse_url(&url)?.with_given(url)
+            }
+            Self::Path { path } => {
+                let absolute = if path.is_absolute() {
+                    path.clone()
+                } else {
+                    std::env::current_dir()?.join(path)
+                };
+                VerbatimUrl::from_path(absolute)?
+            }
+        };
+        let parsed_url = ParsedUrl::try_from(url.to_url())?;
+        Ok(RequirementSource::from_parsed_url(parsed_url, url))
+    }
+}
+
+/// Replacement sources for specific packages, structured as a map from package name to source.
+///
+/// Unlike `tool.uv.sources`, which applies to a project's own dependencies, patches apply to every
+/// requirement on the package, whether it comes from a requirements file, the command line, or the
+/// dependencies of another package.
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackagePatches(BTreeMap<PackageName, PackagePatch>);
+
+impl PackagePatches {
+    /// Resolve any relative paths against the given directory (i.e., the directory containing
+    /// the configuration file).
+    #[must_use]
+    pub fn relative_to(self, root: &Path) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|(name, patch)| {
+                    let patch = match patch {
+                        PackagePatch::Path { path } => PackagePatch::Path {
+                            path: root.join(path),
+                        },
+                        patch @ PackagePatch::Git { .. } => patch,
+                    };
+                    (name, patch)
+                })
+                .collect(),
+        )
+    }
+
+    /// Merge two sets of patches, preferring the values in `self`.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        let mut patches = other.0;
+        patches.extend(self.0);
+        Self(patches)
+    }
+
+    /// Returns `true` if no patches are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A set of [`PackagePatches`], resolved to the source for each patched package.
+#[derive(Debug, Default, Clone)]
+pub struct Patches(FxHashMap<PackageName, RequirementSource>);
+
+impl Patches {
+    /// Resolve the [`PackagePatches`] to a set of [`RequirementSource`]s.
+    pub fn from_patches(patches: &PackagePatches) -> Result<Self> {
+        Ok(Self(
+            patches
+                .0
+                .iter()
+                .map(|(name, patch)| {
+                    let source = patch
+                        .to_source()
+                        .map_err(|err| anyhow!("Invalid patch for `{name}`: {err}"))?;
+                    Ok((name.clone(), source))
+                })
+                .collect::<Result<_>>()?,
+        ))
+    }
+
+    /// Returns `true` if no patches are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the package is patched.
+    pub fn contains(&self, name: &PackageName) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Apply the patches to a requirement, replacing its source if the package is patched.
+    ///
+    /// The requirement's extras and markers are retained.
+    pub fn apply(&self, requirement: Requirement) -> Requirement {
+        match self.0.get(&requirement.name) {
+            Some(source) => Requirement {
+                source: source.clone(),
+                ..requirement
+            },
+            None => requirement,
+        }
+    }
+
+    /// Return a requirement for each patched package, to be used as a constraint, such that the
+    /// patch also applies to any transitive requirements on the package.
+    pub fn requirements(&self) -> impl Iterator<Item = Requirement> + '_ {
+        self.0.iter().map(|(name, source)| Requirement {
+            name: name.clone(),
+            extras: vec![],
+            marker: None,
+            source: source.clone(),
+            origin: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn git(branch: Option<&str>, tag: Option<&str>) -> PackagePatches {
+        PackagePatches(BTreeMap::from([(
+            PackageName::from_str("flask").unwrap(),
+            PackagePatch::Git {
+                git: "https://github.com/pallets/flask".to_string(),
+                subdirectory: None,
+                rev: None,
+                tag: tag.map(ToString::to_string),
+                branch: branch.map(ToString::to_string),
+            },
+        )]))
+    }
+
+    #[test]
+    fn apply_patches() {
+        let patches = Patches::from_patches(&git(Some("main"), None)).unwrap();
+
+        let requirement =
+            Requirement::from_pep508(pep508_rs::Requirement::from_str("flask[async]>=3").unwrap())
+                .unwrap();
+        assert_eq!(
+            patches.apply(requirement).to_string(),
+            "flask[async] @ git+https://github.com/pallets/flask@main"
+        );
+
+        let requirement =
+            Requirement::from_pep508(pep508_rs::Requirement::from_str("jinja2>=3").unwrap())
+                .unwrap();
+        assert_eq!(patches.apply(requirement).to_string(), "jinja2>=3");
+    }
+
+    #[test]
+    fn conflicting_references() {
+        assert!(Patches::from_patches(&git(Some("main"), Some("3.0.0"))).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+/// How to provide the Python executable in a virtual environment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum PythonPreference {
+    /// Symlink the base interpreter into the virtual environment.
+    #[default]
+    Symlink,
+    /// Copy the base interpreter into the virtual environment, rather than symlinking it.
+    Copy,
+}
@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A cap on the rate of requests sent to a host, as `HOST[:PORT]=RATE[:BURST]`, where `RATE` is
+/// the number of requests per second, and `BURST` is the number of requests that may be sent at
+/// once before the rate applies.
+///
+/// Without a port, the limit applies to the host on any port. Without a burst, requests are
+/// spaced evenly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexRateLimit {
+    host: String,
+    port: Option<u16>,
+    rate: f64,
+    burst: u32,
+}
+
+impl IndexRateLimit {
+    /// Returns `true` if the limit applies to the given host and port (e.g., of a request URL).
+    pub fn matches(&self, host: &str, port: Option<u16>) -> bool {
+        if !self.host.eq_ignore_ascii_case(host) {
+            return false;
+        }
+        match self.port {
+            Some(limited) => port == Some(limited),
+            None => true,
+        }
+    }
+
+    /// The maximum sustained number of requests per second.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// The number of requests that may be sent at once, before the rate applies.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+impl FromStr for IndexRateLimit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || {
+            anyhow::anyhow!("Invalid index rate limit: {s} (expected `HOST[:PORT]=RATE[:BURST]`)")
+        };
+
+        let (host, limit) = s.split_once('=').ok_or_else(invalid)?;
+
+        // Split off the port, taking care not to split a bracketed IPv6 address.
+        let (host, port) = match host.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                let port = port.parse::<u16>().map_err(|_| invalid())?;
+                (host, Some(port))
+            }
+            _ => (host, None),
+        };
+        let host = host
+            .strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host);
+        if host.is_empty() || host.contains('/') || (host.contains(':') && !host.contains("::")) {
+            return Err(invalid());
+        }
+
+        let (rate, burst) = match limit.split_once(':') {
+            Some((rate, burst)) => (rate, Some(burst)),
+            None => (limit, None),
+        };
+        let rate = rate.parse::<f64>().map_err(|_| invalid())?;
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "Invalid index rate limit: {s} (expected a positive number of requests per second)"
+            ));
+        }
+        let burst = match burst {
+            Some(burst) => burst.parse::<u32>().ok().filter(|burst| *burst > 0).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid index rate limit: {s} (expected a positive number of requests for the burst)"
+                )
+            })?,
+            None => 1,
+        };
+
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            port,
+            rate,
+            burst,
+        })
+    }
+}
+
+impl Display for IndexRateLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?;
+        } else {
+            write!(f, "{}", self.host)?;
+        }
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+        write!(f, "={}", self.rate)?;
+        if self.burst > 1 {
+            write!(f, ":{}", self.burst)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for IndexRateLimit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        IndexRateLimit::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for IndexRateLimit {
+    fn schema_name() -> String {
+        "IndexRateLimit".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A cap on the requests per second sent to a host, with an optional burst, as `HOST[:PORT]=RATE[:BURST]`, e.g., `artifactory.internal=10:20`.".to_string()),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::IndexRateLimit;
+
+    #[test]
+    fn parse() {
+        let limit = IndexRateLimit::from_str("Artifactory.internal=10").unwrap();
+        assert_eq!(limit.to_string(), "artifactory.internal=10");
+        assert_eq!(limit.burst(), 1);
+        assert!(limit.matches("artifactory.internal", Some(443)));
+        assert!(!limit.matches("pypi.org", None));
+
+        let limit = IndexRateLimit::from_str("artifactory.internal:8443=0.5:20").unwrap();
+        assert_eq!(limit.to_string(), "artifactory.internal:8443=0.5:20");
+        assert_eq!(limit.burst(), 20);
+        assert!(limit.matches("artifactory.internal", Some(8443)));
+        assert!(!limit.matches("artifactory.internal", Some(443)));
+
+        let limit = IndexRateLimit::from_str("[fd00::1]:8443=5").unwrap();
+        assert_eq!(limit.to_string(), "[fd00::1]:8443=5");
+        assert!(limit.matches("fd00::1", Some(8443)));
+
+        assert!(IndexRateLimit::from_str("artifactory.internal").is_err());
+        assert!(IndexRateLimit::from_str("artifactory.internal=0").is_err());
+        assert!(IndexRateLimit::from_str("artifactory.internal=10:0").is_err());
+        assert!(IndexRateLimit::from_str("https://artifactory.internal=10").is_err());
+        assert!(IndexRateLimit::from_str("=10").is_err());
+    }
+}
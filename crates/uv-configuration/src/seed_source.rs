@@ -0,0 +1,16 @@
+/// Where to install the seed packages (`pip`, `setuptools`, and `wheel`) from, when creating a
+/// virtual environment with `--seed`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SeedSource {
+    /// Resolve the seed packages from the package index, falling back to local wheels if they
+    /// can't be resolved.
+    #[default]
+    Index,
+    /// Install the seed packages from local wheels only (i.e., the `pip` wheel embedded in uv,
+    /// those shipped alongside uv, those bundled with the interpreter, or those in the cache),
+    /// without accessing the network.
+    Embedded,
+}
@@ -53,34 +53,248 @@ pub enum TargetTriple {
     Aarch64UnknownLinuxGnu,
 
     /// An ARM64 Linux target.
-    #[cfg_attr(feature = "clap", value(name = "aarch64-unknown-linux-musl"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-unknown-linux-musl", alias = "musllinux_1_2_aarch64")
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-unknown-linux-musl"))]
+    #[serde(alias = "musllinux_1_2_aarch64")]
     Aarch64UnknownLinuxMusl,
 
     /// An `x86_64` Linux target.
-    #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-musl"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-unknown-linux-musl", alias = "musllinux_1_2_x86_64")
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-unknown-linux-musl"))]
+    #[serde(alias = "musllinux_1_2_x86_64")]
     X8664UnknownLinuxMusl,
 
     /// An `x86_64` target for the `manylinux_2_17` platform.
-    #[cfg_attr(feature = "clap", value(name = "x86_64-manylinux_2_17"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(
+            name = "x86_64-manylinux_2_17",
+            alias = "manylinux_2_17_x86_64",
+            alias = "manylinux2014_x86_64"
+        )
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_17"))]
+    #[serde(alias = "manylinux_2_17_x86_64")]
+    #[serde(alias = "manylinux2014_x86_64")]
     X8664Manylinux217,
 
     /// An `x86_64` target for the `manylinux_2_28` platform.
-    #[cfg_attr(feature = "clap", value(name = "x86_64-manylinux_2_28"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_28", alias = "manylinux_2_28_x86_64")
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_28"))]
+    #[serde(alias = "manylinux_2_28_x86_64")]
     X8664Manylinux228,
 
     /// An ARM64 target for the `manylinux_2_17` platform.
-    #[cfg_attr(feature = "clap", value(name = "aarch64-manylinux_2_17"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(
+            name = "aarch64-manylinux_2_17",
+            alias = "manylinux_2_17_aarch64",
+            alias = "manylinux2014_aarch64"
+        )
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_17"))]
+    #[serde(alias = "manylinux_2_17_aarch64")]
+    #[serde(alias = "manylinux2014_aarch64")]
     Aarch64Manylinux217,
 
     /// An ARM64 target for the `manylinux_2_28` platform.
-    #[cfg_attr(feature = "clap", value(name = "aarch64-manylinux_2_28"))]
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_28", alias = "manylinux_2_28_aarch64")
+    )]
     #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_28"))]
+    #[serde(alias = "manylinux_2_28_aarch64")]
     Aarch64Manylinux228,
+
+    /// An `x86_64` target for the `manylinux_2_31` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_31", alias = "manylinux_2_31_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_31"))]
+    #[serde(alias = "manylinux_2_31_x86_64")]
+    X8664Manylinux231,
+
+    /// An `x86_64` target for the `manylinux_2_32` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_32", alias = "manylinux_2_32_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_32"))]
+    #[serde(alias = "manylinux_2_32_x86_64")]
+    X8664Manylinux232,
+
+    /// An `x86_64` target for the `manylinux_2_33` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_33", alias = "manylinux_2_33_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_33"))]
+    #[serde(alias = "manylinux_2_33_x86_64")]
+    X8664Manylinux233,
+
+    /// An `x86_64` target for the `manylinux_2_34` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_34", alias = "manylinux_2_34_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_34"))]
+    #[serde(alias = "manylinux_2_34_x86_64")]
+    X8664Manylinux234,
+
+    /// An `x86_64` target for the `manylinux_2_35` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_35", alias = "manylinux_2_35_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_35"))]
+    #[serde(alias = "manylinux_2_35_x86_64")]
+    X8664Manylinux235,
+
+    /// An `x86_64` target for the `manylinux_2_36` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_36", alias = "manylinux_2_36_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_36"))]
+    #[serde(alias = "manylinux_2_36_x86_64")]
+    X8664Manylinux236,
+
+    /// An `x86_64` target for the `manylinux_2_37` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_37", alias = "manylinux_2_37_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_37"))]
+    #[serde(alias = "manylinux_2_37_x86_64")]
+    X8664Manylinux237,
+
+    /// An `x86_64` target for the `manylinux_2_38` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_38", alias = "manylinux_2_38_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_38"))]
+    #[serde(alias = "manylinux_2_38_x86_64")]
+    X8664Manylinux238,
+
+    /// An `x86_64` target for the `manylinux_2_39` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_39", alias = "manylinux_2_39_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_39"))]
+    #[serde(alias = "manylinux_2_39_x86_64")]
+    X8664Manylinux239,
+
+    /// An `x86_64` target for the `manylinux_2_40` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "x86_64-manylinux_2_40", alias = "manylinux_2_40_x86_64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-manylinux_2_40"))]
+    #[serde(alias = "manylinux_2_40_x86_64")]
+    X8664Manylinux240,
+
+    /// An ARM64 target for the `manylinux_2_31` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_31", alias = "manylinux_2_31_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_31"))]
+    #[serde(alias = "manylinux_2_31_aarch64")]
+    Aarch64Manylinux231,
+
+    /// An ARM64 target for the `manylinux_2_32` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_32", alias = "manylinux_2_32_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_32"))]
+    #[serde(alias = "manylinux_2_32_aarch64")]
+    Aarch64Manylinux232,
+
+    /// An ARM64 target for the `manylinux_2_33` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_33", alias = "manylinux_2_33_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_33"))]
+    #[serde(alias = "manylinux_2_33_aarch64")]
+    Aarch64Manylinux233,
+
+    /// An ARM64 target for the `manylinux_2_34` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_34", alias = "manylinux_2_34_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_34"))]
+    #[serde(alias = "manylinux_2_34_aarch64")]
+    Aarch64Manylinux234,
+
+    /// An ARM64 target for the `manylinux_2_35` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_35", alias = "manylinux_2_35_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_35"))]
+    #[serde(alias = "manylinux_2_35_aarch64")]
+    Aarch64Manylinux235,
+
+    /// An ARM64 target for the `manylinux_2_36` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_36", alias = "manylinux_2_36_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_36"))]
+    #[serde(alias = "manylinux_2_36_aarch64")]
+    Aarch64Manylinux236,
+
+    /// An ARM64 target for the `manylinux_2_37` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_37", alias = "manylinux_2_37_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_37"))]
+    #[serde(alias = "manylinux_2_37_aarch64")]
+    Aarch64Manylinux237,
+
+    /// An ARM64 target for the `manylinux_2_38` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_38", alias = "manylinux_2_38_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_38"))]
+    #[serde(alias = "manylinux_2_38_aarch64")]
+    Aarch64Manylinux238,
+
+    /// An ARM64 target for the `manylinux_2_39` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_39", alias = "manylinux_2_39_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_39"))]
+    #[serde(alias = "manylinux_2_39_aarch64")]
+    Aarch64Manylinux239,
+
+    /// An ARM64 target for the `manylinux_2_40` platform.
+    #[cfg_attr(
+        feature = "clap",
+        value(name = "aarch64-manylinux_2_40", alias = "manylinux_2_40_aarch64")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-manylinux_2_40"))]
+    #[serde(alias = "manylinux_2_40_aarch64")]
+    Aarch64Manylinux240,
 }
 
 impl TargetTriple {
@@ -150,6 +364,146 @@ impl TargetTriple {
                 },
                 Arch::Aarch64,
             ),
+            Self::X8664Manylinux231 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 31,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux232 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 32,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux233 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 33,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux234 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 34,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux235 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 35,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux236 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 36,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux237 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 37,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux238 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 38,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux239 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 39,
+                },
+                Arch::X86_64,
+            ),
+            Self::X8664Manylinux240 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 40,
+                },
+                Arch::X86_64,
+            ),
+            Self::Aarch64Manylinux231 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 31,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux232 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 32,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux233 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 33,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux234 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 34,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux235 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 35,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux236 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 36,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux237 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 37,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux238 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 38,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux239 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 39,
+                },
+                Arch::Aarch64,
+            ),
+            Self::Aarch64Manylinux240 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 40,
+                },
+                Arch::Aarch64,
+            ),
         }
     }
 
@@ -167,6 +521,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "x86_64",
             Self::Aarch64Manylinux217 => "aarch64",
             Self::Aarch64Manylinux228 => "aarch64",
+            Self::X8664Manylinux231 => "x86_64",
+            Self::X8664Manylinux232 => "x86_64",
+            Self::X8664Manylinux233 => "x86_64",
+            Self::X8664Manylinux234 => "x86_64",
+            Self::X8664Manylinux235 => "x86_64",
+            Self::X8664Manylinux236 => "x86_64",
+            Self::X8664Manylinux237 => "x86_64",
+            Self::X8664Manylinux238 => "x86_64",
+            Self::X8664Manylinux239 => "x86_64",
+            Self::X8664Manylinux240 => "x86_64",
+            Self::Aarch64Manylinux231 => "aarch64",
+            Self::Aarch64Manylinux232 => "aarch64",
+            Self::Aarch64Manylinux233 => "aarch64",
+            Self::Aarch64Manylinux234 => "aarch64",
+            Self::Aarch64Manylinux235 => "aarch64",
+            Self::Aarch64Manylinux236 => "aarch64",
+            Self::Aarch64Manylinux237 => "aarch64",
+            Self::Aarch64Manylinux238 => "aarch64",
+            Self::Aarch64Manylinux239 => "aarch64",
+            Self::Aarch64Manylinux240 => "aarch64",
         }
     }
 
@@ -184,6 +558,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "Linux",
             Self::Aarch64Manylinux217 => "Linux",
             Self::Aarch64Manylinux228 => "Linux",
+            Self::X8664Manylinux231 => "Linux",
+            Self::X8664Manylinux232 => "Linux",
+            Self::X8664Manylinux233 => "Linux",
+            Self::X8664Manylinux234 => "Linux",
+            Self::X8664Manylinux235 => "Linux",
+            Self::X8664Manylinux236 => "Linux",
+            Self::X8664Manylinux237 => "Linux",
+            Self::X8664Manylinux238 => "Linux",
+            Self::X8664Manylinux239 => "Linux",
+            Self::X8664Manylinux240 => "Linux",
+            Self::Aarch64Manylinux231 => "Linux",
+            Self::Aarch64Manylinux232 => "Linux",
+            Self::Aarch64Manylinux233 => "Linux",
+            Self::Aarch64Manylinux234 => "Linux",
+            Self::Aarch64Manylinux235 => "Linux",
+            Self::Aarch64Manylinux236 => "Linux",
+            Self::Aarch64Manylinux237 => "Linux",
+            Self::Aarch64Manylinux238 => "Linux",
+            Self::Aarch64Manylinux239 => "Linux",
+            Self::Aarch64Manylinux240 => "Linux",
         }
     }
 
@@ -201,6 +595,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "",
             Self::Aarch64Manylinux217 => "",
             Self::Aarch64Manylinux228 => "",
+            Self::X8664Manylinux231 => "",
+            Self::X8664Manylinux232 => "",
+            Self::X8664Manylinux233 => "",
+            Self::X8664Manylinux234 => "",
+            Self::X8664Manylinux235 => "",
+            Self::X8664Manylinux236 => "",
+            Self::X8664Manylinux237 => "",
+            Self::X8664Manylinux238 => "",
+            Self::X8664Manylinux239 => "",
+            Self::X8664Manylinux240 => "",
+            Self::Aarch64Manylinux231 => "",
+            Self::Aarch64Manylinux232 => "",
+            Self::Aarch64Manylinux233 => "",
+            Self::Aarch64Manylinux234 => "",
+            Self::Aarch64Manylinux235 => "",
+            Self::Aarch64Manylinux236 => "",
+            Self::Aarch64Manylinux237 => "",
+            Self::Aarch64Manylinux238 => "",
+            Self::Aarch64Manylinux239 => "",
+            Self::Aarch64Manylinux240 => "",
         }
     }
 
@@ -218,6 +632,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "",
             Self::Aarch64Manylinux217 => "",
             Self::Aarch64Manylinux228 => "",
+            Self::X8664Manylinux231 => "",
+            Self::X8664Manylinux232 => "",
+            Self::X8664Manylinux233 => "",
+            Self::X8664Manylinux234 => "",
+            Self::X8664Manylinux235 => "",
+            Self::X8664Manylinux236 => "",
+            Self::X8664Manylinux237 => "",
+            Self::X8664Manylinux238 => "",
+            Self::X8664Manylinux239 => "",
+            Self::X8664Manylinux240 => "",
+            Self::Aarch64Manylinux231 => "",
+            Self::Aarch64Manylinux232 => "",
+            Self::Aarch64Manylinux233 => "",
+            Self::Aarch64Manylinux234 => "",
+            Self::Aarch64Manylinux235 => "",
+            Self::Aarch64Manylinux236 => "",
+            Self::Aarch64Manylinux237 => "",
+            Self::Aarch64Manylinux238 => "",
+            Self::Aarch64Manylinux239 => "",
+            Self::Aarch64Manylinux240 => "",
         }
     }
 
@@ -235,6 +669,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "posix",
             Self::Aarch64Manylinux217 => "posix",
             Self::Aarch64Manylinux228 => "posix",
+            Self::X8664Manylinux231 => "posix",
+            Self::X8664Manylinux232 => "posix",
+            Self::X8664Manylinux233 => "posix",
+            Self::X8664Manylinux234 => "posix",
+            Self::X8664Manylinux235 => "posix",
+            Self::X8664Manylinux236 => "posix",
+            Self::X8664Manylinux237 => "posix",
+            Self::X8664Manylinux238 => "posix",
+            Self::X8664Manylinux239 => "posix",
+            Self::X8664Manylinux240 => "posix",
+            Self::Aarch64Manylinux231 => "posix",
+            Self::Aarch64Manylinux232 => "posix",
+            Self::Aarch64Manylinux233 => "posix",
+            Self::Aarch64Manylinux234 => "posix",
+            Self::Aarch64Manylinux235 => "posix",
+            Self::Aarch64Manylinux236 => "posix",
+            Self::Aarch64Manylinux237 => "posix",
+            Self::Aarch64Manylinux238 => "posix",
+            Self::Aarch64Manylinux239 => "posix",
+            Self::Aarch64Manylinux240 => "posix",
         }
     }
 
@@ -252,6 +706,26 @@ impl TargetTriple {
             Self::X8664Manylinux228 => "linux",
             Self::Aarch64Manylinux217 => "linux",
             Self::Aarch64Manylinux228 => "linux",
+            Self::X8664Manylinux231 => "linux",
+            Self::X8664Manylinux232 => "linux",
+            Self::X8664Manylinux233 => "linux",
+            Self::X8664Manylinux234 => "linux",
+            Self::X8664Manylinux235 => "linux",
+            Self::X8664Manylinux236 => "linux",
+            Self::X8664Manylinux237 => "linux",
+            Self::X8664Manylinux238 => "linux",
+            Self::X8664Manylinux239 => "linux",
+            Self::X8664Manylinux240 => "linux",
+            Self::Aarch64Manylinux231 => "linux",
+            Self::Aarch64Manylinux232 => "linux",
+            Self::Aarch64Manylinux233 => "linux",
+            Self::Aarch64Manylinux234 => "linux",
+            Self::Aarch64Manylinux235 => "linux",
+            Self::Aarch64Manylinux236 => "linux",
+            Self::Aarch64Manylinux237 => "linux",
+            Self::Aarch64Manylinux238 => "linux",
+            Self::Aarch64Manylinux239 => "linux",
+            Self::Aarch64Manylinux240 => "linux",
         }
     }
 
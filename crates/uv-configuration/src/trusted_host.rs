@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A host for which TLS certificate verification is disabled, as in pip's `--trusted-host`:
+/// `HOST[:PORT]`.
+///
+/// Without a port, the host is trusted on any port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedHost {
+    host: String,
+    port: Option<u16>,
+}
+
+impl TrustedHost {
+    /// Returns `true` if the given host and port (e.g., of a request URL) are trusted.
+    pub fn matches(&self, host: &str, port: Option<u16>) -> bool {
+        if !self.host.eq_ignore_ascii_case(host) {
+            return false;
+        }
+        match self.port {
+            Some(trusted) => port == Some(trusted),
+            None => true,
+        }
+    }
+}
+
+impl FromStr for TrustedHost {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        // Split off the port, taking care not to split a bracketed IPv6 address.
+        let (host, port) = match s.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                let port = port.parse::<u16>().map_err(|_| {
+                    anyhow::anyhow!("Invalid trusted host: {s} (expected a port number)")
+                })?;
+                (host, Some(port))
+            }
+            _ => (s, None),
+        };
+        let host = host
+            .strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host);
+
+        if host.is_empty() || host.contains('/') || (host.contains(':') && !host.contains("::")) {
+            return Err(anyhow::anyhow!(
+                "Invalid trusted host: {s} (expected `HOST[:PORT]`)"
+            ));
+        }
+
+        Ok(Self {
+            host: host.to_ascii_lowercase(),
+            port,
+        })
+    }
+}
+
+impl Display for TrustedHost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?;
+        } else {
+            write!(f, "{}", self.host)?;
+        }
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::TrustedHost;
+
+    #[test]
+    fn parse() {
+        let host = TrustedHost::from_str("PyPI.internal").unwrap();
+        assert_eq!(host.to_string(), "pypi.internal");
+        assert!(host.matches("pypi.internal", None));
+        assert!(host.matches("PYPI.internal", Some(8443)));
+        assert!(!host.matches("pypi.org", None));
+        assert!(!host.matches("sub.pypi.internal", None));
+
+        let host = TrustedHost::from_str("pypi.internal:8443").unwrap();
+        assert_eq!(host.to_string(), "pypi.internal:8443");
+        assert!(host.matches("pypi.internal", Some(8443)));
+        assert!(!host.matches("pypi.internal", None));
+        assert!(!host.matches("pypi.internal", Some(443)));
+
+        let host = TrustedHost::from_str("[fd00::1]:8443").unwrap();
+        assert_eq!(host.to_string(), "[fd00::1]:8443");
+        assert!(host.matches("fd00::1", Some(8443)));
+
+        assert!(TrustedHost::from_str("pypi.internal:https").is_err());
+        assert!(TrustedHost::from_str("https://pypi.internal").is_err());
+        assert!(TrustedHost::from_str("").is_err());
+    }
+}
@@ -55,7 +55,7 @@ pub(crate) async fn build(args: BuildArgs) -> Result<PathBuf> {
 
     let cache = Cache::try_from(args.cache_args)?.init()?;
 
-    let venv = PythonEnvironment::from_virtualenv(&cache)?;
+    let venv = PythonEnvironment::from_virtualenv(None, &cache)?;
     let client = RegistryClientBuilder::new(cache.clone()).build();
     let index_urls = IndexLocations::default();
     let flat_index = FlatIndex::default();
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use tracing::info;
 use uv_cache::{Cache, CacheArgs};
+use uv_configuration::CompileOptions;
 use uv_interpreter::PythonEnvironment;
 
 #[derive(Parser)]
@@ -20,16 +21,21 @@ pub(crate) async fn compile(args: CompileArgs) -> anyhow::Result<()> {
     let interpreter = if let Some(python) = args.python {
         python
     } else {
-        let venv = PythonEnvironment::from_virtualenv(&cache)?;
+        let venv = PythonEnvironment::from_virtualenv(None, &cache)?;
         venv.python_executable().to_path_buf()
     };
 
-    let files = uv_installer::compile_tree(
+    let summary = uv_installer::compile_tree(
         &fs_err::canonicalize(args.root)?,
         &interpreter,
-        cache.root(),
+        &cache,
+        &CompileOptions::default(),
     )
     .await?;
-    info!("Compiled {files} files");
+    info!(
+        "Compiled {} files ({} failed)",
+        summary.files,
+        summary.failures.len()
+    );
     Ok(())
 }
@@ -10,16 +10,22 @@ use std::str::FromStr;
 #[cfg(unix)]
 use std::{collections::HashMap, path::PathBuf};
 use tokio::time::Instant;
-use tracing::{info, info_span, Instrument};
+use tracing::{info, info_span, warn, Instrument};
 
 use uv_fs::Simplified;
 use uv_interpreter::managed::{
-    DownloadResult, Error, PythonDownload, PythonDownloadRequest, TOOLCHAIN_DIRECTORY,
+    DownloadResult, Error, PythonDownload, PythonDownloadRequest, Toolchain, TOOLCHAIN_DIRECTORY,
 };
+use uv_interpreter::{register_with_py_launcher, PyLauncherError};
 
 #[derive(Parser, Debug)]
 pub(crate) struct FetchPythonArgs {
     versions: Vec<String>,
+
+    /// Register each toolchain with the `py` launcher (Windows only), such that it can be
+    /// selected with, e.g., `py -V:uv/3.12`.
+    #[arg(long)]
+    register: bool,
 }
 
 pub(crate) async fn fetch_python(args: FetchPythonArgs) -> Result<()> {
@@ -97,6 +103,25 @@ pub(crate) async fn fetch_python(args: FetchPythonArgs) -> Result<()> {
         info!("All versions downloaded already.");
     };
 
+    // Register the toolchains with the `py` launcher.
+    if args.register {
+        for (_, path) in &results {
+            let registration = Toolchain::new(path.clone())?.py_launcher_registration();
+            match register_with_py_launcher(&registration) {
+                Ok(()) => info!(
+                    "Registered `{}` with the `py` launcher as `-V:uv/{}`",
+                    path.user_display(),
+                    registration.tag
+                ),
+                Err(PyLauncherError::Unsupported) => {
+                    warn!("`--register` is only supported on Windows; ignoring");
+                    break;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
     // Order matters here, as we overwrite previous links
     info!("Installing to `{}`...", bootstrap_dir.user_display());
 
@@ -13,19 +13,28 @@ use rustc_hash::FxHashMap;
 use tracing::{debug, instrument};
 
 use distribution_types::{CachedDist, IndexLocations, Name, Requirement, Resolution, SourceDist};
-use uv_build::{SourceBuild, SourceBuildContext};
+use install_wheel_rs::UninstallOptions;
+use uv_build::{AnySourceBuild, ExternalBuild, SourceBuild, SourceBuildContext};
 use uv_cache::Cache;
 use uv_client::RegistryClient;
 use uv_configuration::Concurrency;
-use uv_configuration::{BuildKind, ConfigSettings, NoBinary, NoBuild, Reinstall, SetupPyStrategy};
+use uv_configuration::{
+    BuildCommands, BuildKind, ConfigSettings, NoBinary, NoBuild, PreferBinary, PreferSource,
+    Reinstall, SetupPyStrategy,
+};
 use uv_distribution::DistributionDatabase;
-use uv_installer::{Downloader, Installer, Plan, Planner, SitePackages};
+use uv_extract::ExtractPool;
+use uv_installer::{Installer, Plan, Planner, Preparer, SitePackages};
 use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 use uv_resolver::{FlatIndex, InMemoryIndex, Manifest, Options, PythonRequirement, Resolver};
-use uv_types::{BuildContext, BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
+use uv_types::{
+    BuildContext, BuildCounter, BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight,
+};
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
+#[derive(Clone)]
 pub struct BuildDispatch<'a> {
     client: &'a RegistryClient,
     cache: &'a Cache,
@@ -39,11 +48,22 @@ pub struct BuildDispatch<'a> {
     link_mode: install_wheel_rs::linker::LinkMode,
     no_build: &'a NoBuild,
     no_binary: &'a NoBinary,
+    prefer_source: PreferSource,
+    prefer_binary: PreferBinary,
+    build_commands: BuildCommands,
     config_settings: &'a ConfigSettings,
     source_build_context: SourceBuildContext,
     options: Options,
     build_extra_env_vars: FxHashMap<OsString, OsString>,
     concurrency: Concurrency,
+    extract_pool: ExtractPool,
+    build_counter: BuildCounter,
+    /// The source distributions whose build requirements are being installed by this dispatch,
+    /// outermost first, used to detect cycles in the build dependencies.
+    build_stack: Vec<PackageName>,
+    /// The maximum depth of nested source builds, where a source distribution built for the
+    /// build requirements of another is one level deeper.
+    max_build_depth: Option<usize>,
 }
 
 impl<'a> BuildDispatch<'a> {
@@ -78,10 +98,18 @@ impl<'a> BuildDispatch<'a> {
             link_mode,
             no_build,
             no_binary,
+            prefer_source: PreferSource::default(),
+            prefer_binary: PreferBinary::default(),
+            build_commands: BuildCommands::default(),
             concurrency,
+            extract_pool: ExtractPool::new(concurrency.unzips)
+                .expect("failed to initialize extraction thread pool"),
+            build_counter: BuildCounter::default(),
             source_build_context: SourceBuildContext::default(),
             options: Options::default(),
             build_extra_env_vars: FxHashMap::default(),
+            build_stack: Vec::new(),
+            max_build_depth: None,
         }
     }
 
@@ -91,6 +119,49 @@ impl<'a> BuildDispatch<'a> {
         self
     }
 
+    /// Set the packages for which source distributions are preferred over wheels.
+    #[must_use]
+    pub fn with_prefer_source(mut self, prefer_source: PreferSource) -> Self {
+        self.prefer_source = prefer_source;
+        self
+    }
+
+    /// Set the packages for which versions with a compatible wheel are preferred over newer
+    /// versions that would need to be built from source.
+    #[must_use]
+    pub fn with_prefer_binary(mut self, prefer_binary: PreferBinary) -> Self {
+        self.prefer_binary = prefer_binary;
+        self
+    }
+
+    /// Set the maximum depth of nested source builds (i.e., source distributions built to satisfy
+    /// the build requirements of other source distributions).
+    #[must_use]
+    pub fn with_max_build_depth(mut self, max_build_depth: Option<usize>) -> Self {
+        self.max_build_depth = max_build_depth;
+        self
+    }
+
+    /// Record each source distribution built into a wheel to the given counter, e.g., to share
+    /// it with the caller.
+    #[must_use]
+    pub fn with_build_counter(mut self, build_counter: BuildCounter) -> Self {
+        self.build_counter = build_counter;
+        self
+    }
+
+    /// Set the external commands used to build specific packages.
+    #[must_use]
+    pub fn with_build_commands(mut self, build_commands: BuildCommands) -> Self {
+        self.build_commands = build_commands;
+        self
+    }
+
+    /// Return the external commands used to build specific packages.
+    pub fn build_commands(&self) -> &BuildCommands {
+        &self.build_commands
+    }
+
     /// Set the environment variables to be used when building a source distribution.
     #[must_use]
     pub fn with_build_extra_env_vars<I, K, V>(mut self, sdist_build_env_variables: I) -> Self
@@ -105,10 +176,24 @@ impl<'a> BuildDispatch<'a> {
             .collect();
         self
     }
+
+    /// Return a [`BuildDispatch`] for installing the build requirements of the given source
+    /// distribution, such that any builds it triggers in turn can be traced back to it.
+    fn for_build_requirements(&self, package: &PackageName) -> Self {
+        let mut dispatch = self.clone();
+        dispatch.build_stack.push(package.clone());
+        dispatch
+    }
+
+    /// Returns `true` if this dispatch is installing the build requirements of another source
+    /// distribution.
+    fn is_nested(&self) -> bool {
+        !self.build_stack.is_empty()
+    }
 }
 
 impl<'a> BuildContext for BuildDispatch<'a> {
-    type SourceDistBuilder = SourceBuild;
+    type SourceDistBuilder = AnySourceBuild;
 
     fn cache(&self) -> &Cache {
         self.cache
@@ -130,6 +215,14 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.no_binary
     }
 
+    fn prefer_source(&self) -> &PreferSource {
+        &self.prefer_source
+    }
+
+    fn prefer_binary(&self) -> &PreferBinary {
+        &self.prefer_binary
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         self.index_locations
     }
@@ -138,11 +231,36 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.setup_py
     }
 
+    fn build_command(&self, package: &PackageName) -> Option<&str> {
+        self.build_commands.get(package)
+    }
+
+    fn extract_pool(&self) -> &ExtractPool {
+        &self.extract_pool
+    }
+
+    fn build_counter(&self) -> &BuildCounter {
+        &self.build_counter
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
         let markers = self.interpreter.markers();
         let python_requirement =
             PythonRequirement::from_marker_environment(self.interpreter, markers);
         let tags = self.interpreter.tags()?;
+
+        // Nested builds resolve against their own index: in a cycle, an outer resolution is
+        // waiting on this build for the metadata of a package that this resolution needs in turn,
+        // so waiting on the shared index would deadlock rather than reach the cycle check in
+        // `setup_build`.
+        let nested_index;
+        let index = if self.is_nested() {
+            nested_index = InMemoryIndex::default();
+            &nested_index
+        } else {
+            self.index
+        };
+
         let resolver = Resolver::new(
             Manifest::simple(requirements.to_vec()),
             self.options,
@@ -150,7 +268,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             Some(markers),
             tags,
             self.flat_index,
-            self.index,
+            index,
             &HashStrategy::None,
             self,
             EmptyInstalledPackages,
@@ -198,17 +316,24 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             cached,
             remote,
             reinstalls,
+            hash_mismatches: _,
+            reinstall_reasons: _,
             extraneous: _,
-        } = Planner::with_requirements(&requirements).build(
-            site_packages,
-            &Reinstall::None,
-            &NoBinary::None,
-            &HashStrategy::None,
-            self.index_locations,
-            self.cache(),
-            venv,
-            tags,
-        )?;
+        } = Planner::with_requirements(&requirements)
+            .with_resolution(resolution)
+            .with_build_commands(&self.build_commands)
+            .with_prefer_source(&self.prefer_source)
+            .build(
+                site_packages,
+                &Reinstall::None,
+                &NoBinary::None,
+                &NoBuild::None,
+                &HashStrategy::None,
+                self.index_locations,
+                self.cache(),
+                venv,
+                tags,
+            )?;
 
         // Nothing to do.
         if remote.is_empty() && cached.is_empty() && reinstalls.is_empty() {
@@ -231,8 +356,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         let wheels = if remote.is_empty() {
             vec![]
         } else {
-            // TODO(konstin): Check that there is no endless recursion.
-            let downloader = Downloader::new(
+            let preparer = Preparer::new(
                 self.cache,
                 tags,
                 &HashStrategy::None,
@@ -245,8 +369,18 @@ impl<'a> BuildContext for BuildDispatch<'a> {
                 remote.iter().map(ToString::to_string).join(", ")
             );
 
-            downloader
-                .download(remote, self.in_flight)
+            // As in `resolve`, nested builds can't wait on downloads and builds that are in flight
+            // for an outer build.
+            let nested_in_flight;
+            let in_flight = if self.is_nested() {
+                nested_in_flight = InFlight::default();
+                &nested_in_flight
+            } else {
+                self.in_flight
+            };
+
+            preparer
+                .prepare(remote, in_flight)
                 .await
                 .context("Failed to download and build distributions")?
         };
@@ -254,7 +388,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         // Remove any unnecessary packages.
         if !reinstalls.is_empty() {
             for dist_info in &reinstalls {
-                let summary = uv_installer::uninstall(dist_info)
+                let summary = uv_installer::uninstall(dist_info, &UninstallOptions::default())
                     .await
                     .context("Failed to uninstall build dependencies")?;
                 debug!(
@@ -279,6 +413,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             Installer::new(venv)
                 .with_link_mode(self.link_mode)
                 .install(&wheels)
+                .await
                 .context("Failed to install build dependencies")?;
         }
 
@@ -293,32 +428,97 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         version_id: &'data str,
         dist: Option<&'data SourceDist>,
         build_kind: BuildKind,
-    ) -> Result<SourceBuild> {
+    ) -> Result<AnySourceBuild> {
         match self.no_build {
             NoBuild::All => debug_assert!(
-                matches!(build_kind, BuildKind::Editable),
-                "Only editable builds are exempt from 'no build' checks"
+                matches!(build_kind, BuildKind::Editable)
+                    || dist.is_some_and(|dist| !self
+                        .no_build
+                        .no_build_package(dist.name(), self.no_binary)),
+                "Only editable builds and packages named in `--no-binary` are exempt from 'no build' checks"
             ),
             NoBuild::None => {}
-            NoBuild::Packages(packages) => {
-                // We can only prevent builds by name for packages with names. For editable
-                // packages and unnamed requirements, we can't prevent the build.
+            NoBuild::Packages(_) | NoBuild::AllExcept(_) => {
+                // We can only prevent builds by name for packages with names. Editable packages
+                // are always allowed to build, while unnamed requirements can only build if the
+                // strategy isn't an allow-list (which they can't be matched against).
                 if let Some(dist) = dist {
-                    if packages.contains(dist.name()) {
+                    if self.no_build.no_build_package(dist.name(), self.no_binary) {
                         bail!(
                             "Building source distributions for {} is disabled",
                             dist.name()
                         );
                     }
+                } else if !matches!(build_kind, BuildKind::Editable)
+                    && self.no_build.no_build_unnamed()
+                {
+                    bail!("Building source distributions for {version_id} is disabled");
                 }
             }
         }
 
+        // Refuse to build a package whose build requirements (transitively) require building the
+        // package itself, which would otherwise recurse indefinitely.
+        if let Some(dist) = dist {
+            if let Some(position) = self
+                .build_stack
+                .iter()
+                .position(|package| package == dist.name())
+            {
+                bail!(
+                    "Detected a cycle in the build dependencies of `{}`: {}. Provide a pre-built wheel for one of these packages, or remove it from `--no-binary`",
+                    dist.name(),
+                    self.build_stack[position..]
+                        .iter()
+                        .chain(std::iter::once(dist.name()))
+                        .map(|package| format!("`{package}`"))
+                        .join(" -> ")
+                );
+            }
+            if let Some(max_build_depth) = self.max_build_depth {
+                if self.build_stack.len() > max_build_depth {
+                    bail!(
+                        "Building `{}` would exceed the maximum build depth of {max_build_depth}: {}. Provide a pre-built wheel for one of these packages, or increase `--max-build-depth`",
+                        dist.name(),
+                        self.build_stack
+                            .iter()
+                            .chain(std::iter::once(dist.name()))
+                            .map(|package| format!("`{package}`"))
+                            .join(" -> ")
+                    );
+                }
+            }
+            if !self.build_stack.is_empty() {
+                debug!(
+                    "Building {} for the build requirements of: {}",
+                    dist.name(),
+                    self.build_stack.iter().join(" -> ")
+                );
+            }
+        }
+
+        // Delegate to an external build command, if one is configured for the package.
+        if matches!(build_kind, BuildKind::Wheel) {
+            if let Some(command) = dist.and_then(|dist| self.build_commands.get(dist.name())) {
+                debug!("Using external build command for {version_id}: {command}");
+                return Ok(AnySourceBuild::External(ExternalBuild::new(
+                    command.to_string(),
+                    source,
+                    subdirectory,
+                    version_id.to_string(),
+                    self.build_extra_env_vars.clone(),
+                )));
+            }
+        }
+
+        // Install the build requirements through a dispatch that tracks this build, such that any
+        // cycles back to this package are detected.
+        let dispatch = dist.map(|dist| self.for_build_requirements(dist.name()));
         let builder = SourceBuild::setup(
             source,
             subdirectory,
             self.interpreter,
-            self,
+            dispatch.as_ref().unwrap_or(self),
             self.source_build_context.clone(),
             version_id.to_string(),
             self.setup_py,
@@ -330,6 +530,6 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         )
         .boxed_local()
         .await?;
-        Ok(builder)
+        Ok(AnySourceBuild::Pep517(builder))
     }
 }
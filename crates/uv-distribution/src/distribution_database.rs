@@ -1,12 +1,10 @@
 use std::future::Future;
-use std::io;
+use std::io::{self, Seek};
 use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
-use tempfile::TempDir;
-use tokio::io::AsyncSeekExt;
 use tokio::sync::Semaphore;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::{info_span, instrument, warn, Instrument};
@@ -23,7 +21,6 @@ use uv_cache::{ArchiveId, ArchiveTimestamp, CacheBucket, CacheEntry, Timestamp,
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
 };
-use uv_configuration::{NoBinary, NoBuild};
 use uv_extract::hash::Hasher;
 use uv_fs::write_atomic;
 use uv_types::BuildContext;
@@ -81,7 +78,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             io::Error::new(
                 io::ErrorKind::TimedOut,
                 format!(
-                    "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {}s).",  self.client.unmanaged.timeout()
+                    "Failed to download distribution due to network timeout. Try increasing `--http-timeout` or UV_HTTP_TIMEOUT (current value: {}s).",  self.client.unmanaged.timeout()
                 ),
             )
         } else {
@@ -129,13 +126,15 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
     }
 
     /// Build a directory into an editable wheel.
+    ///
+    /// Returns `true` alongside the wheel if it was reused from the cache, rather than built.
     pub async fn build_wheel_editable(
         &self,
         editable: &LocalEditable,
         editable_wheel_dir: &Path,
-    ) -> Result<(LocalWheel, Metadata23), Error> {
+    ) -> Result<(LocalWheel, Metadata23, bool), Error> {
         // Build the wheel.
-        let (dist, disk_filename, filename, metadata) = self
+        let (dist, disk_filename, filename, metadata, reused) = self
             .builder
             .build_editable(editable, editable_wheel_dir)
             .await?;
@@ -151,7 +150,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             hashes: vec![],
         };
 
-        Ok((wheel, metadata))
+        Ok((wheel, metadata, reused))
     }
 
     /// Fetch a wheel from the cache or download it from the index.
@@ -163,12 +162,11 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         dist: &BuiltDist,
         hashes: HashPolicy<'_>,
     ) -> Result<LocalWheel, Error> {
-        let no_binary = match self.build_context.no_binary() {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(dist.name()),
-        };
-        if no_binary {
+        if self
+            .build_context
+            .no_binary()
+            .no_binary_package(dist.name(), self.build_context.no_build())
+        {
             return Err(Error::NoBinary);
         }
 
@@ -398,13 +396,10 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         source: &BuildableSource<'_>,
         hashes: HashPolicy<'_>,
     ) -> Result<ArchiveMetadata, Error> {
-        let no_build = match self.build_context.no_build() {
-            NoBuild::All => true,
-            NoBuild::None => false,
-            NoBuild::Packages(packages) => {
-                source.name().is_some_and(|name| packages.contains(name))
-            }
-        };
+        let no_build = self.build_context.no_build();
+        let no_build = source.name().map_or(no_build.no_build_unnamed(), |name| {
+            no_build.no_build_package(name, self.build_context.no_binary())
+        });
 
         // Optimization: Skip source dist download when we must not build them anyway.
         if no_build {
@@ -434,14 +429,25 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Hash the wheel with the algorithms reported by the index, even if no hashes are
+        // required, to detect a cached wheel whose contents have since changed upstream.
+        let index_hashes = index_hashes(dist);
+
         let download = |response: reqwest::Response| {
             async {
                 let reader = response
                     .bytes_stream()
+                    .inspect_ok(|chunk| {
+                        self.client
+                            .unmanaged
+                            .network_recorder()
+                            .record_downloaded(chunk.len());
+                    })
                     .map_err(|err| self.handle_response_errors(err))
                     .into_async_read();
 
                 // Create a hasher for each hash algorithm.
+                let hashes = hashes.or_index(index_hashes);
                 let algorithms = hashes.algorithms();
                 let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
                 let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
@@ -456,6 +462,9 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     hasher.finish().await.map_err(Error::HashExhaustion)?;
                 }
 
+                // Verify the extracted files against the `RECORD`.
+                self.verify_record(temp_dir.path()).await?;
+
                 // Persist the temporary directory to the directory store.
                 let id = self
                     .build_context
@@ -497,10 +506,16 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
-        // If the archive is missing the required hashes, force a refresh.
-        let archive = if archive.has_digests(hashes) {
+        // If the archive is missing the required hashes, force a refresh. If the cached archive
+        // doesn't match the required hashes or those reported by the index (e.g., if the index
+        // re-uploaded a file with the same filename but different contents), the cache entry is
+        // stale, so refresh it, too.
+        let archive = if archive.satisfies(hashes) && !archive.conflicts_with(index_hashes) {
             archive
         } else {
+            if archive.has_digests(hashes) {
+                warn!("Cached wheel for {dist} doesn't match the expected hashes; refreshing");
+            }
             self.client
                 .managed(|client| async {
                     client
@@ -530,10 +545,20 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         // Create an entry for the HTTP cache.
         let http_entry = wheel_entry.with_file(format!("{}.http", filename.stem()));
 
+        // Hash the wheel with the algorithms reported by the index, even if no hashes are
+        // required, to detect a cached wheel whose contents have since changed upstream.
+        let index_hashes = index_hashes(dist);
+
         let download = |response: reqwest::Response| {
             async {
                 let reader = response
                     .bytes_stream()
+                    .inspect_ok(|chunk| {
+                        self.client
+                            .unmanaged
+                            .network_recorder()
+                            .record_downloaded(chunk.len());
+                    })
                     .map_err(|err| self.handle_response_errors(err))
                     .into_async_read();
 
@@ -545,40 +570,13 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     .await
                     .map_err(Error::CacheWrite)?;
 
-                // Unzip the wheel to a temporary directory.
+                // Hash, unzip, and verify the wheel in a temporary directory.
                 let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
                     .map_err(Error::CacheWrite)?;
-                let mut file = writer.into_inner();
-                file.seek(io::SeekFrom::Start(0))
-                    .await
-                    .map_err(Error::CacheWrite)?;
-
-                // If no hashes are required, parallelize the unzip operation.
-                let hashes = if hashes.is_none() {
-                    let file = file.into_std().await;
-                    tokio::task::spawn_blocking({
-                        let target = temp_dir.path().to_owned();
-                        move || -> Result<(), uv_extract::Error> {
-                            // Unzip the wheel into a temporary directory.
-                            uv_extract::unzip(file, &target)?;
-                            Ok(())
-                        }
-                    })
-                    .await??;
-
-                    vec![]
-                } else {
-                    // Create a hasher for each hash algorithm.
-                    let algorithms = hashes.algorithms();
-                    let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
-                    let mut hasher = uv_extract::hash::HashReader::new(file, &mut hashers);
-                    uv_extract::stream::unzip(&mut hasher, temp_dir.path()).await?;
-
-                    // If necessary, exhaust the reader to compute the hash.
-                    hasher.finish().await.map_err(Error::HashExhaustion)?;
-
-                    hashers.into_iter().map(HashDigest::from).collect()
-                };
+                let file = writer.into_inner().into_std().await;
+                let hashes = self
+                    .unzip_file(file, temp_dir.path(), hashes.or_index(index_hashes))
+                    .await?;
 
                 // Persist the temporary directory to the directory store.
                 let id = self
@@ -617,10 +615,16 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
-        // If the archive is missing the required hashes, force a refresh.
-        let archive = if archive.has_digests(hashes) {
+        // If the archive is missing the required hashes, force a refresh. If the cached archive
+        // doesn't match the required hashes or those reported by the index (e.g., if the index
+        // re-uploaded a file with the same filename but different contents), the cache entry is
+        // stale, so refresh it, too.
+        let archive = if archive.satisfies(hashes) && !archive.conflicts_with(index_hashes) {
             archive
         } else {
+            if archive.has_digests(hashes) {
+                warn!("Cached wheel for {dist} doesn't match the expected hashes; refreshing");
+            }
             self.client
                 .managed(|client| async move {
                     client
@@ -687,24 +691,14 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             })
         } else {
             // If necessary, compute the hashes of the wheel.
-            let file = fs_err::tokio::File::open(path)
-                .await
-                .map_err(Error::CacheRead)?;
+            let (file, _) = fs_err::File::open(path)
+                .map_err(Error::CacheRead)?
+                .into_parts();
             let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
                 .map_err(Error::CacheWrite)?;
 
-            // Create a hasher for each hash algorithm.
-            let algorithms = hashes.algorithms();
-            let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
-            let mut hasher = uv_extract::hash::HashReader::new(file, &mut hashers);
-
-            // Unzip the wheel to a temporary directory.
-            uv_extract::stream::unzip(&mut hasher, temp_dir.path()).await?;
-
-            // Exhaust the reader to compute the hash.
-            hasher.finish().await.map_err(Error::HashExhaustion)?;
-
-            let hashes = hashers.into_iter().map(HashDigest::from).collect();
+            // Hash, unzip, and verify the wheel in a temporary directory.
+            let hashes = self.unzip_file(file, temp_dir.path(), hashes).await?;
 
             // Persist the temporary directory to the directory store.
             let id = self
@@ -735,17 +729,15 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
 
     /// Unzip a wheel into the cache, returning the path to the unzipped directory.
     async fn unzip_wheel(&self, path: &Path, target: &Path) -> Result<ArchiveId, Error> {
-        let temp_dir = tokio::task::spawn_blocking({
-            let path = path.to_owned();
-            let root = self.build_context.cache().root().to_path_buf();
-            move || -> Result<TempDir, uv_extract::Error> {
-                // Unzip the wheel into a temporary directory.
-                let temp_dir = tempfile::tempdir_in(root)?;
-                uv_extract::unzip(fs_err::File::open(path)?, temp_dir.path())?;
-                Ok(temp_dir)
-            }
-        })
-        .await??;
+        let (file, _) = fs_err::File::open(path)
+            .map_err(Error::CacheRead)?
+            .into_parts();
+        let temp_dir =
+            tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
+
+        // Unzip and verify the wheel in a temporary directory.
+        self.unzip_file(file, temp_dir.path(), HashPolicy::None)
+            .await?;
 
         // Persist the temporary directory to the directory store.
         let id = self
@@ -758,6 +750,64 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         Ok(id)
     }
 
+    /// Hash (if required), unzip, and verify a wheel on the [`ExtractPool`], returning the computed
+    /// digests.
+    ///
+    /// The wheel is extracted into the given directory, and verified against its `RECORD`.
+    ///
+    /// [`ExtractPool`]: uv_extract::ExtractPool
+    #[allow(clippy::disallowed_types)]
+    async fn unzip_file(
+        &self,
+        mut file: std::fs::File,
+        target: &Path,
+        hashes: HashPolicy<'_>,
+    ) -> Result<Vec<HashDigest>, Error> {
+        let algorithms = hashes.algorithms();
+        let target = target.to_path_buf();
+        self.build_context
+            .extract_pool()
+            .spawn(move || {
+                // Create a hasher for each hash algorithm, and hash the wheel before unzipping it.
+                let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
+                if !hashers.is_empty() {
+                    uv_extract::hash::hash_reader(&mut file, &mut hashers)
+                        .map_err(Error::HashExhaustion)?;
+                    file.seek(io::SeekFrom::Start(0))
+                        .map_err(Error::CacheRead)?;
+                }
+
+                // Unzip the wheel, in parallel. Very large wheels are extracted with in-kernel
+                // copies, where supported.
+                if file.metadata().map_err(Error::CacheRead)?.len()
+                    >= uv_extract::LARGE_ARCHIVE_THRESHOLD
+                {
+                    uv_extract::unzip_large(&file, &target)?;
+                } else {
+                    uv_extract::unzip(file, &target)?;
+                }
+
+                // Verify the extracted files against the `RECORD`.
+                install_wheel_rs::linker::verify_record(&target).map_err(Error::Record)?;
+
+                Ok(hashers.into_iter().map(HashDigest::from).collect())
+            })
+            .await
+    }
+
+    /// Verify an extracted wheel against its `RECORD` on the [`ExtractPool`], prior to persisting
+    /// it to the cache.
+    ///
+    /// [`ExtractPool`]: uv_extract::ExtractPool
+    async fn verify_record(&self, path: &Path) -> Result<(), Error> {
+        let path = path.to_owned();
+        self.build_context
+            .extract_pool()
+            .spawn(move || install_wheel_rs::linker::verify_record(path))
+            .await
+            .map_err(Error::Record)
+    }
+
     /// Returns a GET [`reqwest::Request`] for the given URL.
     fn request(&self, url: Url) -> Result<reqwest::Request, reqwest::Error> {
         self.client
@@ -813,6 +863,14 @@ impl<'a> ManagedClient<'a> {
     }
 }
 
+/// Return the digests reported by the index for a built distribution, if any.
+fn index_hashes(dist: &BuiltDist) -> &[HashDigest] {
+    match dist {
+        BuiltDist::Registry(wheels) => &wheels.best_wheel().file.hashes,
+        BuiltDist::DirectUrl(_) | BuiltDist::Path(_) => &[],
+    }
+}
+
 /// A pointer to an archive in the cache, fetched from an HTTP archive.
 ///
 /// Encoded with `MsgPack`, and represented on disk by a `.http` file.
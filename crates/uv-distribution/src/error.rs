@@ -68,6 +68,8 @@ pub enum Error {
     DirWithoutEntrypoint(PathBuf),
     #[error("Failed to extract archive")]
     Extract(#[from] uv_extract::Error),
+    #[error("Extracted wheel does not match its `RECORD`")]
+    Record(#[source] install_wheel_rs::Error),
     #[error("Source distribution not found at: {0}")]
     NotFound(PathBuf),
     #[error("The source distribution is missing a `PKG-INFO` file")]
@@ -63,6 +63,7 @@ pub(crate) async fn fetch_git_archive(
     let _lock = LockedFile::acquire(
         lock_dir.join(cache_key::digest(&repository_url)),
         &repository_url,
+        cache.lock_timeout(),
     )
     .map_err(Error::CacheWrite)?;
 
@@ -102,8 +102,8 @@ impl<'a> BuiltWheelIndex<'a> {
         };
 
         // Determine the last-modified time of the source distribution.
-        let Some(modified) =
-            ArchiveTimestamp::from_source_tree(&source_dist.path).map_err(Error::CacheRead)?
+        let Some(modified) = ArchiveTimestamp::from_source_tree_contents(&source_dist.path)
+            .map_err(Error::CacheRead)?
         else {
             return Err(Error::DirWithoutEntrypoint(source_dist.path.clone()));
         };
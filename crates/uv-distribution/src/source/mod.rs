@@ -4,13 +4,14 @@ use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use fs_err::tokio as fs;
 use futures::{FutureExt, TryStreamExt};
 use reqwest::Response;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{debug, info_span, instrument, Instrument};
+use tracing::{debug, info_span, instrument, warn, Instrument};
 use url::Url;
 use zip::ZipArchive;
 
@@ -28,9 +29,10 @@ use uv_cache::{
     WheelCache,
 };
 use uv_client::{
-    CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
+    CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, NetworkRecorder,
+    RegistryClient,
 };
-use uv_configuration::{BuildKind, NoBuild};
+use uv_configuration::BuildKind;
 use uv_extract::hash::Hasher;
 use uv_fs::{write_atomic, LockedFile};
 use uv_types::{BuildContext, SourceBuildTrait};
@@ -60,6 +62,9 @@ pub(crate) const LOCAL_REVISION: &str = "revision.rev";
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
+/// The name of the file that contains the pointer to a cached editable wheel, encoded via `MsgPack`.
+pub(crate) const EDITABLE: &str = "editable.msgpack";
+
 impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
     /// Initialize a [`SourceDistributionBuilder`] from a [`BuildContext`].
     pub fn new(build_context: &'a T) -> Self {
@@ -78,6 +83,28 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         }
     }
 
+    /// Return the [`CacheBucket::BuiltWheels`] shard for the given source.
+    ///
+    /// If the package is built with an external command, the shard is further scoped to the
+    /// command, such that changing the command invalidates any previously built wheels.
+    fn built_wheels_shard(
+        &self,
+        source: &BuildableSource<'_>,
+        dir: impl AsRef<Path>,
+    ) -> CacheShard {
+        let cache_shard = self
+            .build_context
+            .cache()
+            .shard(CacheBucket::BuiltWheels, dir);
+        match source
+            .name()
+            .and_then(|name| self.build_context.build_command(name))
+        {
+            Some(command) => cache_shard.shard(cache_key::digest(&command)),
+            None => cache_shard,
+        }
+    }
+
     /// Download and build a [`SourceDist`].
     pub(super) async fn download_and_build(
         &self,
@@ -90,8 +117,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             BuildableSource::Dist(SourceDist::Registry(dist)) => {
                 // For registry source distributions, shard by package, then version, for
                 // convenience in debugging.
-                let cache_shard = self.build_context.cache().shard(
-                    CacheBucket::BuiltWheels,
+                let cache_shard = self.built_wheels_shard(
+                    source,
                     WheelCache::Index(&dist.index)
                         .wheel_dir(dist.name.as_ref())
                         .join(dist.version.to_string()),
@@ -142,10 +169,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     ParsedArchiveUrl::from(dist.url.to_url());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Url(&url).root());
+                let cache_shard = self.built_wheels_shard(source, WheelCache::Url(&url).root());
 
                 self.url(
                     source,
@@ -171,10 +195,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .await?
             }
             BuildableSource::Dist(SourceDist::Path(dist)) => {
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Path(&dist.url).root());
+                let cache_shard =
+                    self.built_wheels_shard(source, WheelCache::Path(&dist.url).root());
                 self.archive(
                     source,
                     &PathSourceUrl::from(dist),
@@ -194,10 +216,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     ParsedArchiveUrl::from(resource.url.clone());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Url(&url).root());
+                let cache_shard = self.built_wheels_shard(source, WheelCache::Url(&url).root());
 
                 self.url(
                     source,
@@ -223,10 +242,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .await?
             }
             BuildableSource::Url(SourceUrl::Path(resource)) => {
-                let cache_shard = self.build_context.cache().shard(
-                    CacheBucket::BuiltWheels,
-                    WheelCache::Path(resource.url).root(),
-                );
+                let cache_shard =
+                    self.built_wheels_shard(source, WheelCache::Path(resource.url).root());
                 self.archive(source, resource, &cache_shard, tags, hashes)
                     .boxed_local()
                     .await?
@@ -248,8 +265,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         let metadata = match &source {
             BuildableSource::Dist(SourceDist::Registry(dist)) => {
                 // For registry source distributions, shard by package, then version.
-                let cache_shard = self.build_context.cache().shard(
-                    CacheBucket::BuiltWheels,
+                let cache_shard = self.built_wheels_shard(
+                    source,
                     WheelCache::Index(&dist.index)
                         .wheel_dir(dist.name.as_ref())
                         .join(dist.version.to_string()),
@@ -298,10 +315,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     ParsedArchiveUrl::from(dist.url.to_url());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Url(&url).root());
+                let cache_shard = self.built_wheels_shard(source, WheelCache::Url(&url).root());
 
                 self.url_metadata(
                     source,
@@ -326,10 +340,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     .await?
             }
             BuildableSource::Dist(SourceDist::Path(dist)) => {
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Path(&dist.url).root());
+                let cache_shard =
+                    self.built_wheels_shard(source, WheelCache::Path(&dist.url).root());
                 self.archive_metadata(source, &PathSourceUrl::from(dist), &cache_shard, hashes)
                     .boxed_local()
                     .await?
@@ -343,10 +355,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                     ParsedArchiveUrl::from(resource.url.clone());
 
                 // For direct URLs, cache directly under the hash of the URL itself.
-                let cache_shard = self
-                    .build_context
-                    .cache()
-                    .shard(CacheBucket::BuiltWheels, WheelCache::Url(&url).root());
+                let cache_shard = self.built_wheels_shard(source, WheelCache::Url(&url).root());
 
                 self.url_metadata(
                     source,
@@ -372,10 +381,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             }
 
             BuildableSource::Url(SourceUrl::Path(resource)) => {
-                let cache_shard = self.build_context.cache().shard(
-                    CacheBucket::BuiltWheels,
-                    WheelCache::Path(resource.url).root(),
-                );
+                let cache_shard =
+                    self.built_wheels_shard(source, WheelCache::Path(resource.url).root());
                 self.archive_metadata(source, resource, &cache_shard, hashes)
                     .boxed_local()
                     .await?
@@ -398,7 +405,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         hashes: HashPolicy<'_>,
         client: &ManagedClient<'_>,
     ) -> Result<BuiltWheelMetadata, Error> {
-        let _lock = lock_shard(cache_shard).await?;
+        let _lock = lock_shard(cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -469,7 +476,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         hashes: HashPolicy<'_>,
         client: &ManagedClient<'_>,
     ) -> Result<ArchiveMetadata, Error> {
-        let _lock = lock_shard(cache_shard).await?;
+        let _lock = lock_shard(cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -560,6 +567,15 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         client: &ManagedClient<'_>,
     ) -> Result<Revision, Error> {
         let cache_entry = cache_shard.entry(HTTP_REVISION);
+
+        // Hash the source distribution with the algorithms reported by the index, even if no
+        // hashes are required, to detect a cached revision whose contents have since changed
+        // upstream.
+        let index_hashes = match source {
+            BuildableSource::Dist(SourceDist::Registry(dist)) => dist.file.hashes.as_slice(),
+            BuildableSource::Dist(_) | BuildableSource::Url(_) => &[],
+        };
+
         let cache_control = match client.unmanaged.connectivity() {
             Connectivity::Online => CacheControl::from(
                 self.build_context
@@ -580,7 +596,14 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 debug!("Downloading source distribution: {source}");
                 let entry = cache_shard.shard(revision.id()).entry(filename);
                 let hashes = self
-                    .download_archive(response, source, filename, entry.path(), hashes)
+                    .download_archive(
+                        response,
+                        source,
+                        filename,
+                        entry.path(),
+                        hashes.or_index(index_hashes),
+                        client.unmanaged.network_recorder(),
+                    )
                     .await?;
 
                 Ok(revision.with_hashes(hashes))
@@ -601,10 +624,16 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 CachedClientError::Client(err) => Error::Client(err),
             })?;
 
-        // If the archive is missing the required hashes, force a refresh.
-        if revision.has_digests(hashes) {
+        // If the archive is missing the required hashes, force a refresh. If the cached archive
+        // doesn't match the required hashes or those reported by the index (e.g., if the index
+        // re-uploaded a file with the same filename but different contents), the cache entry is
+        // stale, so refresh it, too.
+        if revision.satisfies(hashes) && !revision.conflicts_with(index_hashes) {
             Ok(revision)
         } else {
+            if revision.has_digests(hashes) {
+                warn!("Cached source distribution for {source} doesn't match the expected hashes; refreshing");
+            }
             client
                 .managed(|client| async move {
                     client
@@ -629,7 +658,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         tags: &Tags,
         hashes: HashPolicy<'_>,
     ) -> Result<BuiltWheelMetadata, Error> {
-        let _lock = lock_shard(cache_shard).await?;
+        let _lock = lock_shard(cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -697,7 +726,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         cache_shard: &CacheShard,
         hashes: HashPolicy<'_>,
     ) -> Result<ArchiveMetadata, Error> {
-        let _lock = lock_shard(cache_shard).await?;
+        let _lock = lock_shard(cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -839,12 +868,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             return Err(Error::HashesNotSupportedSourceTree(source.to_string()));
         }
 
-        let cache_shard = self.build_context.cache().shard(
-            CacheBucket::BuiltWheels,
-            WheelCache::Path(resource.url).root(),
-        );
+        let cache_shard = self.built_wheels_shard(source, WheelCache::Path(resource.url).root());
 
-        let _lock = lock_shard(&cache_shard).await?;
+        let _lock = lock_shard(&cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -905,12 +931,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             return Err(Error::HashesNotSupportedSourceTree(source.to_string()));
         }
 
-        let cache_shard = self.build_context.cache().shard(
-            CacheBucket::BuiltWheels,
-            WheelCache::Path(resource.url).root(),
-        );
+        let cache_shard = self.built_wheels_shard(source, WheelCache::Path(resource.url).root());
 
-        let _lock = lock_shard(&cache_shard).await?;
+        let _lock = lock_shard(&cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // Fetch the revision for the source distribution.
         let revision = self
@@ -977,8 +1000,12 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         cache_shard: &CacheShard,
     ) -> Result<Revision, Error> {
         // Determine the last-modified time of the source distribution.
-        let Some(modified) =
-            ArchiveTimestamp::from_source_tree(&resource.path).map_err(Error::CacheRead)?
+        let Some(modified) = tokio::task::spawn_blocking({
+            let path = resource.path.to_path_buf();
+            move || ArchiveTimestamp::from_source_tree_contents(path)
+        })
+        .await?
+        .map_err(Error::CacheRead)?
         else {
             return Err(Error::DirWithoutEntrypoint(resource.path.to_path_buf()));
         };
@@ -1044,12 +1071,12 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             fetch_git_archive(&url, self.build_context.cache(), self.reporter.as_ref()).await?;
 
         let git_sha = fetch.git().precise().expect("Exact commit after checkout");
-        let cache_shard = self.build_context.cache().shard(
-            CacheBucket::BuiltWheels,
+        let cache_shard = self.built_wheels_shard(
+            source,
             WheelCache::Git(resource.url, &git_sha.to_short_string()).root(),
         );
 
-        let _lock = lock_shard(&cache_shard).await?;
+        let _lock = lock_shard(&cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
@@ -1120,12 +1147,12 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             fetch_git_archive(&url, self.build_context.cache(), self.reporter.as_ref()).await?;
 
         let git_sha = fetch.git().precise().expect("Exact commit after checkout");
-        let cache_shard = self.build_context.cache().shard(
-            CacheBucket::BuiltWheels,
+        let cache_shard = self.built_wheels_shard(
+            source,
             WheelCache::Git(resource.url, &git_sha.to_short_string()).root(),
         );
 
-        let _lock = lock_shard(&cache_shard).await?;
+        let _lock = lock_shard(&cache_shard, self.build_context.cache().lock_timeout()).await?;
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -1190,12 +1217,14 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         filename: &str,
         target: &Path,
         hashes: HashPolicy<'_>,
+        network_recorder: &NetworkRecorder,
     ) -> Result<Vec<HashDigest>, Error> {
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().bucket(CacheBucket::BuiltWheels))
                 .map_err(Error::CacheWrite)?;
         let reader = response
             .bytes_stream()
+            .inspect_ok(|chunk| network_recorder.record_downloaded(chunk.len()))
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .into_async_read();
 
@@ -1297,13 +1326,10 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         debug!("Building: {source}");
 
         // Guard against build of source distributions when disabled.
-        let no_build = match self.build_context.no_build() {
-            NoBuild::All => true,
-            NoBuild::None => false,
-            NoBuild::Packages(packages) => {
-                source.name().is_some_and(|name| packages.contains(name))
-            }
-        };
+        let no_build = self.build_context.no_build();
+        let no_build = source.name().map_or(no_build.no_build_unnamed(), |name| {
+            no_build.no_build_package(name, self.build_context.no_binary())
+        });
         if no_build {
             return Err(Error::NoBuild);
         }
@@ -1326,6 +1352,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             .wheel(cache_shard)
             .await
             .map_err(|err| Error::Build(source.to_string(), err))?;
+        self.build_context.build_counter().record();
 
         // Read the metadata from the wheel.
         let filename = WheelFilename::from_str(&disk_filename)?;
@@ -1415,34 +1442,114 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         Ok(Some(metadata))
     }
 
-    /// Build a single directory into an editable wheel
+    /// Build a wheel for the given editable, reusing a previously-built wheel if the source tree is
+    /// unchanged.
+    ///
+    /// Built editables are cached by source tree and interpreter, such that creating another
+    /// environment for the same project (e.g., with the same base interpreter) doesn't require
+    /// rebuilding it. Any change to the contents of the source tree invalidates the cached wheel.
+    ///
+    /// Returns `true` alongside the wheel if it was reused from the cache, rather than built.
     pub async fn build_editable(
         &self,
         editable: &LocalEditable,
         editable_wheel_dir: &Path,
-    ) -> Result<(Dist, String, WheelFilename, Metadata23), Error> {
-        debug!("Building (editable) {editable}");
-
+    ) -> Result<(Dist, String, WheelFilename, Metadata23, bool), Error> {
         // Verify that the editable exists.
         if !editable.path.exists() {
             return Err(Error::NotFound(editable.path.clone()));
         }
 
-        // Build the wheel.
-        let disk_filename = self
-            .build_context
-            .setup_build(
-                &editable.path,
-                None,
-                &editable.to_string(),
-                None,
-                BuildKind::Editable,
+        let interpreter = self.build_context.interpreter();
+        let cache_shard = self.build_context.cache().shard(
+            CacheBucket::Editables,
+            Path::new(&cache_key::digest(&editable.path)).join(cache_key::digest(&(
+                interpreter.base_prefix(),
+                interpreter.implementation_name(),
+                interpreter.python_full_version().to_string(),
+            ))),
+        );
+        let _lock = lock_shard(&cache_shard, self.build_context.cache().lock_timeout()).await?;
+
+        // Determine the digest of the contents of the source tree.
+        let digest = tokio::task::spawn_blocking({
+            let path = editable.path.clone();
+            move || uv_cache::source_tree_digest(path)
+        })
+        .await?
+        .map_err(Error::CacheRead)?;
+
+        // If the editable was already built for this interpreter, and the contents of the source
+        // tree haven't changed since, reuse the wheel.
+        let entry = cache_shard.entry(EDITABLE);
+        let cached = if let Some(digest) = digest.as_deref() {
+            if self
+                .build_context
+                .cache()
+                .freshness(&entry, None)
+                .map_err(Error::CacheRead)?
+                .is_fresh()
+            {
+                EditablePointer::read_from(&entry)?
+                    .filter(|pointer| pointer.is_up_to_date(digest))
+                    .filter(|pointer| cache_shard.join(&pointer.filename).is_file())
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        let reused = cached.is_some();
+
+        let disk_filename = if let Some(pointer) = cached {
+            debug!("Using cached editable build for: {editable}");
+            fs::create_dir_all(editable_wheel_dir)
+                .await
+                .map_err(Error::CacheWrite)?;
+            fs::copy(
+                cache_shard.join(&pointer.filename),
+                editable_wheel_dir.join(&pointer.filename),
             )
             .await
-            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?
-            .wheel(editable_wheel_dir)
-            .await
-            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
+            .map_err(Error::CacheRead)?;
+            pointer.filename
+        } else {
+            debug!("Building (editable) {editable}");
+
+            // Build the wheel.
+            let disk_filename = self
+                .build_context
+                .setup_build(
+                    &editable.path,
+                    None,
+                    &editable.to_string(),
+                    None,
+                    BuildKind::Editable,
+                )
+                .await
+                .map_err(|err| Error::BuildEditable(editable.to_string(), err))?
+                .wheel(editable_wheel_dir)
+                .await
+                .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
+
+            // Store the wheel in the cache, writing the pointer last.
+            if let Some(digest) = digest {
+                fs::copy(
+                    editable_wheel_dir.join(&disk_filename),
+                    cache_shard.join(&disk_filename),
+                )
+                .await
+                .map_err(Error::CacheWrite)?;
+                EditablePointer {
+                    digest,
+                    filename: disk_filename.clone(),
+                }
+                .write_to(&entry)
+                .await?;
+            }
+
+            disk_filename
+        };
         let filename = WheelFilename::from_str(&disk_filename)?;
 
         // We finally have the name of the package and can construct the dist.
@@ -1454,8 +1561,16 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         }));
         let metadata = read_wheel_metadata(&filename, editable_wheel_dir.join(&disk_filename))?;
 
+        // Store the metadata alongside the wheel, such that the entry can be removed by name.
+        write_atomic(
+            cache_shard.entry(METADATA).path(),
+            rmp_serde::to_vec(&metadata)?,
+        )
+        .await
+        .map_err(Error::CacheWrite)?;
+
         debug!("Finished building (editable): {dist}");
-        Ok((dist, disk_filename, filename, metadata))
+        Ok((dist, disk_filename, filename, metadata, reused))
     }
 
     /// Returns a GET [`reqwest::Request`] for the given URL.
@@ -1567,6 +1682,41 @@ impl LocalRevisionPointer {
     }
 }
 
+/// A pointer to an editable wheel in the cache, built from a local source tree.
+///
+/// Encoded with `MsgPack`, and represented on disk by an `editable.msgpack` file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct EditablePointer {
+    digest: String,
+    filename: String,
+}
+
+impl EditablePointer {
+    /// Read an [`EditablePointer`] from the cache.
+    pub(crate) fn read_from(path: impl AsRef<Path>) -> Result<Option<Self>, Error> {
+        match fs_err::read(path) {
+            Ok(cached) => Ok(Some(rmp_serde::from_slice::<EditablePointer>(&cached)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Error::CacheRead(err)),
+        }
+    }
+
+    /// Write an [`EditablePointer`] to the cache.
+    async fn write_to(&self, entry: &CacheEntry) -> Result<(), Error> {
+        fs::create_dir_all(&entry.dir())
+            .await
+            .map_err(Error::CacheWrite)?;
+        write_atomic(entry.path(), rmp_serde::to_vec(&self)?)
+            .await
+            .map_err(Error::CacheWrite)
+    }
+
+    /// Returns `true` if the wheel was built from a source tree with the given digest.
+    pub(crate) fn is_up_to_date(&self, digest: &str) -> bool {
+        self.digest == digest
+    }
+}
+
 /// Read the [`Metadata23`] from a source distribution's `PKG-INFO` file, if it uses Metadata 2.2
 /// or later _and_ none of the required fields (`Requires-Python`, `Requires-Dist`, and
 /// `Provides-Extra`) are marked as dynamic.
@@ -1640,15 +1790,19 @@ fn read_wheel_metadata(
     Ok(Metadata23::parse_metadata(&dist_info)?)
 }
 
-/// Apply an advisory lock to a [`CacheShard`] to prevent concurrent builds.
-async fn lock_shard(cache_shard: &CacheShard) -> Result<LockedFile, Error> {
+/// Apply an advisory lock to a [`CacheShard`] to prevent concurrent builds, waiting for at most
+/// `timeout` (or indefinitely, if `None`).
+async fn lock_shard(
+    cache_shard: &CacheShard,
+    timeout: Option<Duration>,
+) -> Result<LockedFile, Error> {
     let root = cache_shard.as_ref();
 
     fs_err::create_dir_all(root).map_err(Error::CacheWrite)?;
 
     let lock: LockedFile = tokio::task::spawn_blocking({
         let root = root.to_path_buf();
-        move || LockedFile::acquire(root.join(".lock"), root.display())
+        move || LockedFile::acquire(root.join(".lock"), root.display(), timeout)
     })
     .await?
     .map_err(Error::CacheWrite)?;
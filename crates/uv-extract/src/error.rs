@@ -16,6 +16,20 @@ pub enum Error {
     NonSingularArchive(Vec<OsString>),
     #[error("The top-level of the archive must only contain a list directory, but it's empty")]
     EmptyArchive,
+    #[error("Bad CRC (got {computed:08x}, expected {expected:08x}) for file: {}", path.display())]
+    BadCrc32 {
+        path: PathBuf,
+        computed: u32,
+        expected: u32,
+    },
+    #[error("Bad uncompressed size (got {computed}, expected {expected}) for file: {}", path.display())]
+    BadUncompressedSize {
+        path: PathBuf,
+        computed: u64,
+        expected: u64,
+    },
+    #[error("Archive contains files that differ only in case, which would collide on a case-insensitive filesystem: `{}` and `{}`", .0.display(), .1.display())]
+    CaseCollision(PathBuf, PathBuf),
 }
 
 impl Error {
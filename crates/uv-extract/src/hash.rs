@@ -144,3 +144,19 @@ where
         }
     }
 }
+
+/// Read the given reader to the end, updating each of the hashers with its contents.
+///
+/// A synchronous counterpart to [`HashReader`], for use on a blocking thread.
+pub fn hash_reader(mut reader: impl std::io::Read, hashers: &mut [Hasher]) -> std::io::Result<()> {
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buf[..n]);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+use uv_fs::verbatim_path;
+use zip::{CompressionMethod, ZipArchive};
+
+use crate::sync::check_case_collisions;
+use crate::vendor::CloneableSeekableReader;
+use crate::Error;
+
+/// The archive size above which [`unzip_large`] is preferred over [`crate::unzip`].
+///
+/// Below this size, the savings don't outweigh the extra system calls per entry.
+pub const LARGE_ARCHIVE_THRESHOLD: u64 = 128 * 1024 * 1024;
+
+/// Unzip a large `.zip` archive file into the target directory.
+///
+/// On Linux, stored (uncompressed) entries, which make up the bulk of very large wheels (e.g.,
+/// CUDA libraries), are copied from the archive to their destination with `copy_file_range`,
+/// such that the kernel can copy (or reflink) the data without a round-trip through a userspace
+/// buffer. Their CRCs are then validated against the extracted files. All other entries, and
+/// stored entries on other platforms, are extracted as in [`crate::unzip`].
+///
+/// The archive is read through regular file I/O, rather than memory-mapped, so an archive that's
+/// truncated during extraction results in an error, rather than a crash.
+#[allow(clippy::disallowed_types)]
+pub fn unzip_large(file: &std::fs::File, target: &Path) -> Result<(), Error> {
+    // Use extended-length paths, since deeply nested packages can easily exceed `MAX_PATH`.
+    let target = verbatim_path(target);
+    let target = target.as_ref();
+
+    let archive = ZipArchive::new(CloneableSeekableReader::new(file.try_clone()?))?;
+    check_case_collisions(archive.file_names(), target)?;
+    let directories = Mutex::new(FxHashSet::default());
+
+    (0..archive.len())
+        .into_par_iter()
+        .map(|file_number| {
+            let mut archive = archive.clone();
+            let mut entry = archive.by_index(file_number)?;
+
+            // Determine the path of the file within the wheel.
+            let Some(enclosed_name) = entry.enclosed_name() else {
+                return Ok(());
+            };
+
+            // Create necessary parent directories.
+            let path = target.join(enclosed_name);
+            if entry.is_dir() {
+                let mut directories = directories.lock().unwrap();
+                if directories.insert(path.clone()) {
+                    fs_err::create_dir_all(path)?;
+                }
+                return Ok(());
+            }
+
+            if let Some(parent) = path.parent() {
+                let mut directories = directories.lock().unwrap();
+                if directories.insert(parent.to_path_buf()) {
+                    fs_err::create_dir_all(parent)?;
+                }
+            }
+
+            let mut outfile = fs_err::File::create(&path)?;
+            let copied = entry.compression() == CompressionMethod::Stored
+                && copy_range(file, entry.data_start(), entry.size(), &mut outfile)?;
+            if copied {
+                // Validate the CRC against the extracted file, since the contents bypassed the
+                // reader.
+                let mut hasher = crc32fast::Hasher::new();
+                let mut reader = fs_err::File::open(&path)?;
+                let mut buffer = vec![0; 64 * 1024];
+                loop {
+                    let n = reader.read(&mut buffer)?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..n]);
+                }
+                let computed = hasher.finalize();
+                let expected = entry.crc32();
+                if computed != expected {
+                    return Err(Error::BadCrc32 {
+                        path,
+                        computed,
+                        expected,
+                    });
+                }
+            } else {
+                // Copy the file contents. The CRC is validated by the reader once the end of the
+                // file is reached.
+                let computed = std::io::copy(&mut entry, &mut outfile)?;
+
+                // Validate the size of the extracted file, to catch truncated archives.
+                let expected = entry.size();
+                if computed != expected {
+                    return Err(Error::BadUncompressedSize {
+                        path,
+                        computed,
+                        expected,
+                    });
+                }
+            }
+
+            #[cfg(unix)]
+            {
+                use std::fs::Permissions;
+                use std::os::unix::fs::PermissionsExt;
+
+                if let Some(mode) = entry.unix_mode() {
+                    // https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/utils/unpacking.py#L88-L100
+                    let has_any_executable_bit = mode & 0o111;
+                    if has_any_executable_bit != 0 {
+                        let permissions = fs_err::metadata(&path)?.permissions();
+                        fs_err::set_permissions(
+                            &path,
+                            Permissions::from_mode(permissions.mode() | 0o111),
+                        )?;
+                    }
+                }
+            }
+
+            Ok(())
+        })
+        .collect::<Result<_, Error>>()
+}
+
+/// Copy `len` bytes at `offset` in the archive into the output file, in the kernel.
+///
+/// Returns `false` if the copy isn't supported (e.g., across filesystems on older kernels, or on
+/// platforms other than Linux), in which case nothing was written and the caller should fall back
+/// to reading the entry.
+#[allow(unsafe_code, clippy::disallowed_types)]
+fn copy_range(
+    archive: &std::fs::File,
+    offset: u64,
+    len: u64,
+    outfile: &mut fs_err::File,
+) -> Result<bool, Error> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::fd::AsRawFd;
+
+        let mut copied = 0;
+        while copied < len {
+            let mut off_in = offset
+                .checked_add(copied)
+                .and_then(|off_in| libc::loff_t::try_from(off_in).ok())
+                .ok_or(zip::result::ZipError::InvalidArchive(
+                    "Entry offset out of range",
+                ))?;
+            let remaining = usize::try_from(len - copied).unwrap_or(usize::MAX);
+            // SAFETY: Both file descriptors are valid for the duration of the call, and the
+            // output offset is taken from (and advances) the output file's position.
+            let result = unsafe {
+                libc::copy_file_range(
+                    archive.as_raw_fd(),
+                    &mut off_in,
+                    outfile.file().as_raw_fd(),
+                    std::ptr::null_mut(),
+                    remaining,
+                    0,
+                )
+            };
+            match u64::try_from(result) {
+                // The archive is shorter than its central directory claims (e.g., it was
+                // truncated while we were reading it).
+                Ok(0) => {
+                    return Err(zip::result::ZipError::InvalidArchive(
+                        "Entry extends past the end of the archive",
+                    )
+                    .into());
+                }
+                Ok(n) => copied += n,
+                Err(_) => {
+                    let err = std::io::Error::last_os_error();
+                    if copied == 0
+                        && matches!(
+                            err.raw_os_error(),
+                            Some(libc::ENOSYS | libc::EXDEV | libc::EINVAL | libc::EOPNOTSUPP)
+                        )
+                    {
+                        return Ok(false);
+                    }
+                    return Err(err.into());
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (archive, offset, len, outfile);
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use zip::write::FileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    use super::unzip_large;
+
+    #[test]
+    fn unzip_stored_and_deflated() {
+        let contents = "x".repeat(64 * 1024);
+
+        let mut archive = tempfile::tempfile().unwrap();
+        let mut writer = ZipWriter::new(&mut archive);
+        for (name, method) in [
+            ("foo/stored.txt", CompressionMethod::Stored),
+            ("foo/deflated.txt", CompressionMethod::Deflated),
+        ] {
+            writer
+                .start_file(name, FileOptions::default().compression_method(method))
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        drop(writer);
+        archive.rewind().unwrap();
+
+        let target = tempfile::tempdir().unwrap();
+        unzip_large(&archive, target.path()).unwrap();
+
+        for name in ["stored.txt", "deflated.txt"] {
+            let extracted = fs_err::read_to_string(target.path().join("foo").join(name)).unwrap();
+            assert_eq!(extracted, contents);
+        }
+    }
+}
@@ -1,8 +1,12 @@
 pub use error::Error;
+pub use large::{unzip_large, LARGE_ARCHIVE_THRESHOLD};
+pub use pool::ExtractPool;
 pub use sync::*;
 
 mod error;
 pub mod hash;
+mod large;
+mod pool;
 pub mod stream;
 mod sync;
 mod tar;
@@ -0,0 +1,84 @@
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use tokio::sync::{oneshot, Semaphore};
+
+/// A dedicated thread pool for CPU-heavy archive work, like hashing, unzipping, and verifying
+/// wheels, so that it doesn't contend with the async runtime.
+///
+/// At most one task per thread is admitted at a time; additional tasks wait for a permit, which
+/// applies backpressure to the downloads that feed the pool. Parallel iterators (as used by
+/// [`crate::unzip`]) within a task run on the same pool.
+#[derive(Debug, Clone)]
+pub struct ExtractPool {
+    pool: Arc<rayon::ThreadPool>,
+    permits: Arc<Semaphore>,
+}
+
+impl ExtractPool {
+    /// Create a pool with the given number of threads.
+    pub fn new(threads: usize) -> Result<Self, rayon::ThreadPoolBuildError> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("uv-extract-{index}"))
+            .build()?;
+        Ok(Self {
+            pool: Arc::new(pool),
+            permits: Arc::new(Semaphore::new(threads.max(1))),
+        })
+    }
+
+    /// The number of threads in the pool.
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Run a task on the pool, waiting for a free thread first.
+    ///
+    /// Panics in the task are propagated to the caller.
+    pub async fn spawn<F, T>(&self, task: F) -> T
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        let (sender, receiver) = oneshot::channel();
+        self.pool.spawn(move || {
+            let _ = sender.send(catch_unwind(AssertUnwindSafe(task)));
+        });
+        match receiver.await.expect("the task always sends its result") {
+            Ok(result) => result,
+            Err(panic) => resume_unwind(panic),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::ExtractPool;
+
+    #[tokio::test]
+    async fn spawn_runs_on_pool() {
+        let pool = ExtractPool::new(2).unwrap();
+        assert_eq!(pool.threads(), 2);
+
+        // Parallel iterators within a task run on the pool's own threads.
+        let names = pool
+            .spawn(|| {
+                (0..16)
+                    .into_par_iter()
+                    .map(|_| std::thread::current().name().map(ToString::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .await;
+        assert!(names.iter().all(|name| name
+            .as_deref()
+            .is_some_and(|name| name.starts_with("uv-extract-"))));
+    }
+}
@@ -2,9 +2,10 @@ use std::path::Path;
 use std::pin::Pin;
 
 use futures::StreamExt;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 use tracing::warn;
+use uv_fs::{is_case_insensitive, verbatim_path};
 
 use crate::Error;
 
@@ -17,12 +18,18 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
     reader: R,
     target: impl AsRef<Path>,
 ) -> Result<(), Error> {
+    // Use extended-length paths, since deeply nested packages can easily exceed `MAX_PATH`.
+    let target = verbatim_path(target.as_ref());
     let target = target.as_ref();
     let mut reader = futures::io::BufReader::with_capacity(128 * 1024, reader.compat());
     let mut zip = async_zip::base::read::stream::ZipFileReader::new(&mut reader);
 
     let mut directories = FxHashSet::default();
 
+    // The files extracted so far, keyed by their lowercase name. Since the central directory comes
+    // last, collisions are detected as the entries are read, rather than up front.
+    let mut files = FxHashMap::default();
+
     while let Some(mut entry) = zip.next_with_entry().await? {
         // Construct the (expected) path to the file on-disk.
         let path = entry.reader().entry().filename().as_str()?;
@@ -35,6 +42,15 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
                 fs_err::tokio::create_dir_all(path).await?;
             }
         } else {
+            // Files that differ only in case would clobber each other on a case-insensitive
+            // filesystem.
+            let key = path.to_string_lossy().to_lowercase();
+            if let Some(existing) = files.insert(key, path.clone()) {
+                if existing != path && is_case_insensitive(target)? {
+                    return Err(Error::CaseCollision(existing, path));
+                }
+            }
+
             if let Some(parent) = path.parent() {
                 if directories.insert(parent.to_path_buf()) {
                     fs_err::tokio::create_dir_all(parent).await?;
@@ -51,6 +67,27 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
                 };
             let mut reader = entry.reader_mut().compat();
             tokio::io::copy(&mut reader, &mut writer).await?;
+
+            // Validate the CRC and size of the extracted file, to catch truncated or corrupted
+            // archives.
+            let computed = entry.reader_mut().compute_hash();
+            let expected = entry.reader().entry().crc32();
+            if computed != expected {
+                return Err(Error::BadCrc32 {
+                    path,
+                    computed,
+                    expected,
+                });
+            }
+            let computed = entry.reader().bytes_read();
+            let expected = entry.reader().entry().uncompressed_size();
+            if computed != expected {
+                return Err(Error::BadUncompressedSize {
+                    path,
+                    computed,
+                    expected,
+                });
+            }
         }
 
         // Close current file to get access to the next one. See docs:
@@ -3,6 +3,7 @@ use std::sync::Mutex;
 
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
+use uv_fs::{find_case_collision, is_case_insensitive, verbatim_path};
 use zip::ZipArchive;
 
 use crate::vendor::{CloneableSeekableReader, HasLength};
@@ -13,8 +14,13 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
     reader: R,
     target: &Path,
 ) -> Result<(), Error> {
+    // Use extended-length paths, since deeply nested packages can easily exceed `MAX_PATH`.
+    let target = verbatim_path(target);
+    let target = target.as_ref();
+
     // Unzip in parallel.
     let archive = ZipArchive::new(CloneableSeekableReader::new(reader))?;
+    check_case_collisions(archive.file_names(), target)?;
     let directories = Mutex::new(FxHashSet::default());
     (0..archive.len())
         .into_par_iter()
@@ -44,9 +50,20 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
                 }
             }
 
-            // Copy the file contents.
+            // Copy the file contents. The CRC is validated by the reader once the end of the file
+            // is reached.
             let mut outfile = fs_err::File::create(&path)?;
-            std::io::copy(&mut file, &mut outfile)?;
+            let computed = std::io::copy(&mut file, &mut outfile)?;
+
+            // Validate the size of the extracted file, to catch truncated archives.
+            let expected = file.size();
+            if computed != expected {
+                return Err(Error::BadUncompressedSize {
+                    path,
+                    computed,
+                    expected,
+                });
+            }
 
             // See `uv_extract::stream::unzip`. For simplicity, this is identical with the code there except for being
             // sync.
@@ -73,6 +90,27 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
         .collect::<Result<_, Error>>()
 }
 
+/// Reject an archive whose file entries differ only in case, if the target directory is on a
+/// case-insensitive filesystem, where extracting both would silently clobber one with the other.
+pub(crate) fn check_case_collisions<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    target: &Path,
+) -> Result<(), Error> {
+    let files = names
+        .into_iter()
+        .filter(|name| !name.ends_with('/'))
+        .map(Path::new);
+    if let Some((first, second)) = find_case_collision(files) {
+        if is_case_insensitive(target)? {
+            return Err(Error::CaseCollision(
+                first.to_path_buf(),
+                second.to_path_buf(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Extract the top-level directory from an unpacked archive.
 ///
 /// The specification says:
@@ -1,10 +1,13 @@
 use std::fmt::Display;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use fs2::FileExt;
 use fs_err as fs;
 use tempfile::NamedTempFile;
 use tracing::{debug, error, trace, warn};
+use walkdir::WalkDir;
 
 use uv_warnings::warn_user;
 
@@ -282,40 +285,148 @@ pub fn files(path: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
         .map(|entry| entry.path())
 }
 
+/// Determine whether the filesystem containing the given directory is case-insensitive.
+///
+/// Creates a temporary file with a lowercase name in the directory, then checks whether it can be
+/// found under its uppercase name.
+pub fn is_case_insensitive(dir: impl AsRef<Path>) -> std::io::Result<bool> {
+    let file = tempfile::Builder::new()
+        .prefix(".uv-case-")
+        .tempfile_in(dir.as_ref())?;
+    let Some(name) = file.path().file_name() else {
+        return Ok(false);
+    };
+    let uppercase = dir.as_ref().join(name.to_string_lossy().to_uppercase());
+    Ok(uppercase.exists())
+}
+
+/// The error returned when a [`LockedFile`] couldn't be acquired within its timeout, as the source
+/// of an [`std::io::Error`] with kind [`std::io::ErrorKind::TimedOut`].
+#[derive(Debug)]
+pub struct LockTimeout {
+    timeout: Duration,
+    resource: String,
+    path: PathBuf,
+}
+
+impl LockTimeout {
+    /// Returns the [`LockTimeout`] underlying the given I/O error, if any.
+    pub fn from_io_error(err: &std::io::Error) -> Option<&Self> {
+        err.get_ref()?.downcast_ref::<Self>()
+    }
+}
+
+impl Display for LockTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Timed out after {}s waiting to acquire lock for {} (lockfile: {})",
+            self.timeout.as_secs(),
+            self.resource,
+            self.path.user_display()
+        )
+    }
+}
+
+impl std::error::Error for LockTimeout {}
+
 /// A file lock that is automatically released when dropped.
 #[derive(Debug)]
 pub struct LockedFile(fs_err::File);
 
 impl LockedFile {
-    pub fn acquire(path: impl AsRef<Path>, resource: impl Display) -> Result<Self, std::io::Error> {
-        let file = fs_err::File::create(path.as_ref())?;
+    /// Acquire an exclusive lock on the given file, waiting for at most `timeout` (or indefinitely,
+    /// if `None`) for any other process that holds it.
+    ///
+    /// The ID of the process holding the lock is written to the file, such that contending
+    /// processes can report it.
+    pub fn acquire(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+        timeout: Option<Duration>,
+    ) -> Result<Self, std::io::Error> {
+        // Avoid truncating the file, which would erase the process ID of the current holder.
+        let mut file = fs_err::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.as_ref())?;
         trace!("Checking lock for `{resource}`");
         match file.file().try_lock_exclusive() {
             Ok(()) => {
                 debug!("Acquired lock for `{resource}`");
-                Ok(Self(file))
             }
             Err(err) => {
                 // Log error code and enum kind to help debugging more exotic failures
                 debug!("Try lock error, waiting for exclusive lock: {:?}", err);
+                let holder = match read_lock_holder(&mut file) {
+                    Some(pid) => format!(" held by PID {pid}"),
+                    None => String::new(),
+                };
                 warn_user!(
-                    "Waiting to acquire lock for {} (lockfile: {})",
+                    "Waiting to acquire lock for {}{} (lockfile: {})",
                     resource,
+                    holder,
                     path.user_display(),
                 );
-                file.file().lock_exclusive().map_err(|err| {
-                    // Not an fs_err method, we need to build our own path context
-                    std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!("Could not lock {}: {}", path.as_ref().user_display(), err),
-                    )
-                })?;
-                Ok(Self(file))
+                if let Some(timeout) = timeout {
+                    let start = Instant::now();
+                    loop {
+                        if file.file().try_lock_exclusive().is_ok() {
+                            break;
+                        }
+                        if start.elapsed() >= timeout {
+                            return Err(std::io::Error::new(
+                                std::io::ErrorKind::TimedOut,
+                                LockTimeout {
+                                    timeout,
+                                    resource: format!("{resource}{holder}"),
+                                    path: path.as_ref().to_path_buf(),
+                                },
+                            ));
+                        }
+                        std::thread::sleep(Duration::from_millis(50));
+                    }
+                } else {
+                    file.file().lock_exclusive().map_err(|err| {
+                        // Not an fs_err method, we need to build our own path context
+                        std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            format!("Could not lock {}: {}", path.as_ref().user_display(), err),
+                        )
+                    })?;
+                }
+                debug!("Acquired lock for `{resource}`");
             }
         }
+
+        // Record the process ID of the holder. The lock is advisory, so failing to do so is
+        // non-fatal.
+        if let Err(err) = file
+            .file()
+            .set_len(0)
+            .and_then(|()| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| file.write_all(std::process::id().to_string().as_bytes()))
+        {
+            debug!(
+                "Failed to write process ID to lockfile {}: {err}",
+                path.user_display()
+            );
+        }
+
+        Ok(Self(file))
     }
 }
 
+/// Read the process ID of the current holder of a lock file, if available.
+fn read_lock_holder(file: &mut fs_err::File) -> Option<u32> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
 impl Drop for LockedFile {
     fn drop(&mut self) {
         if let Err(err) = self.0.file().unlock() {
@@ -338,3 +449,185 @@ pub fn metadata_if_exists(path: impl AsRef<Path>) -> std::io::Result<Option<std:
         Err(err) => Err(err),
     }
 }
+
+/// Return the number of bytes available to the current user on the filesystem containing the
+/// given path, or `None` if it can't be determined.
+///
+/// If the path doesn't exist yet (e.g., a `--target` directory), its nearest existing ancestor is
+/// used instead.
+pub fn available_space(path: impl AsRef<Path>) -> Option<u64> {
+    let path = path
+        .as_ref()
+        .ancestors()
+        .find(|ancestor| ancestor.exists())?;
+    fs2::available_space(path).ok()
+}
+
+/// Return the total size of the files in a directory, without following symlinks.
+///
+/// Entries that can't be read are skipped.
+pub fn directory_size(path: impl AsRef<Path>) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Returns `true` if both paths are known to reside on the same filesystem.
+///
+/// As with [`available_space`], paths that don't exist yet are resolved to their nearest
+/// existing ancestor. Always `false` on platforms without device identifiers.
+pub fn is_same_filesystem(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let device = |path: &Path| {
+            let path = path.ancestors().find(|ancestor| ancestor.exists())?;
+            fs::metadata(path).ok().map(|metadata| metadata.dev())
+        };
+        matches!((device(a.as_ref()), device(b.as_ref())), (Some(a), Some(b)) if a == b)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (a, b);
+        false
+    }
+}
+
+/// Returns the resolved location of `path` if it's a symlink to a system directory (like `/usr`)
+/// or, if a `root` is provided, to a location outside of `root`.
+///
+/// Writing through such a symlink (e.g., a `.venv` that links to another project's environment,
+/// or to the system Python) is rarely intended. Only a symlink at `path` itself is considered,
+/// such that a `root` that's reached through a symlinked parent (e.g., `/tmp` on macOS) is
+/// unaffected. Paths that don't exist are never reported.
+pub fn find_symlink_escape(path: impl AsRef<Path>, root: Option<&Path>) -> Option<PathBuf> {
+    let path = path.as_ref();
+    if !fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_symlink()) {
+        return None;
+    }
+    let target = fs::canonicalize(path).ok()?;
+    if is_system_directory(&target) {
+        return Some(target);
+    }
+    if let Some(root) = root {
+        let root = fs::canonicalize(root).ok()?;
+        if !target.starts_with(root) {
+            return Some(target);
+        }
+    }
+    None
+}
+
+/// Returns `true` if the path is within a directory that's managed by the operating system.
+fn is_system_directory(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        const SYSTEM: &[&str] = &[
+            "/bin", "/boot", "/etc", "/lib", "/lib64", "/sbin", "/usr", "/System", "/Library",
+        ];
+        path == Path::new("/")
+            || (SYSTEM.iter().any(|system| path.starts_with(system))
+                && !path.starts_with("/usr/local"))
+    }
+    #[cfg(windows)]
+    {
+        ["SystemRoot", "ProgramFiles", "ProgramFiles(x86)"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .filter_map(|system| fs::canonicalize(system).ok())
+            .any(|system| path.starts_with(system))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_timeout() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join(".lock");
+
+        let _lock = LockedFile::acquire(&path, "test", None)?;
+
+        // A second attempt should time out, and report the holder of the lock.
+        let err = LockedFile::acquire(&path, "test", Some(Duration::ZERO)).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(err
+            .to_string()
+            .contains(&format!("held by PID {}", std::process::id())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn available_space_missing_path() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let missing = temp_dir.path().join("target").join("site-packages");
+
+        // Paths that don't exist yet resolve to their nearest existing ancestor.
+        assert!(available_space(&missing).is_some());
+        #[cfg(unix)]
+        assert!(is_same_filesystem(temp_dir.path(), &missing));
+
+        Ok(())
+    }
+
+    #[test]
+    fn directory_size_nested() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        fs::create_dir_all(temp_dir.path().join("inner"))?;
+        fs::write(temp_dir.path().join("a.txt"), "abc")?;
+        fs::write(temp_dir.path().join("inner").join("b.txt"), "defgh")?;
+
+        assert_eq!(directory_size(temp_dir.path()), 8);
+        assert_eq!(directory_size(temp_dir.path().join("missing")), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlink_escape() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let project = temp_dir.path().join("project");
+        let other = temp_dir.path().join("other");
+        fs::create_dir_all(project.join("inner"))?;
+        fs::create_dir_all(&other)?;
+
+        // A symlink within the root is permitted.
+        std::os::unix::fs::symlink(project.join("inner"), project.join("internal"))?;
+        assert_eq!(
+            find_symlink_escape(project.join("internal"), Some(&project)),
+            None
+        );
+
+        // A symlink outside of the root is reported, but only if a root is provided.
+        std::os::unix::fs::symlink(&other, project.join("external"))?;
+        assert_eq!(
+            find_symlink_escape(project.join("external"), Some(&project)),
+            Some(fs::canonicalize(&other)?)
+        );
+        assert_eq!(find_symlink_escape(project.join("external"), None), None);
+
+        // A symlink to a system directory is always reported.
+        std::os::unix::fs::symlink("/usr", project.join("system"))?;
+        assert!(find_symlink_escape(project.join("system"), None).is_some());
+
+        // Regular directories are never reported.
+        assert_eq!(find_symlink_escape(project.join("inner"), None), None);
+
+        Ok(())
+    }
+}
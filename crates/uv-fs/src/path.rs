@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::{Component, Path, PathBuf};
 
 use once_cell::sync::Lazy;
@@ -147,6 +148,78 @@ pub fn normalize_path(path: &Path) -> Result<PathBuf, std::io::Error> {
     Ok(ret)
 }
 
+/// Convert an absolute path to an extended-length (`\\?\`) path on Windows.
+///
+/// Extended-length paths aren't subject to the `MAX_PATH` (260 character) limit, which is easily
+/// exceeded by deeply nested packages installed into a deeply nested environment. Since such paths
+/// are passed to the filesystem verbatim, any `.` and `..` components are resolved up front.
+///
+/// Relative paths, paths that are already in extended-length form, and all paths on non-Windows
+/// platforms are returned unchanged.
+pub fn verbatim_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(windows)]
+    {
+        use std::ffi::OsString;
+        use std::path::Prefix;
+
+        if !path.is_absolute() {
+            return Cow::Borrowed(path);
+        }
+
+        let Some(Component::Prefix(prefix)) = path.components().next() else {
+            return Cow::Borrowed(path);
+        };
+
+        let Ok(normalized) = normalize_path(path) else {
+            return Cow::Borrowed(path);
+        };
+
+        match prefix.kind() {
+            // Ex) `C:\Users\ferris` to `\\?\C:\Users\ferris`
+            Prefix::Disk(_) => {
+                let mut verbatim = OsString::from(r"\\?\");
+                verbatim.push(normalized.as_os_str());
+                Cow::Owned(PathBuf::from(verbatim))
+            }
+            // Ex) `\\server\share\ferris` to `\\?\UNC\server\share\ferris`
+            Prefix::UNC(..) => {
+                let Some(rest) = normalized
+                    .to_str()
+                    .and_then(|normalized| normalized.strip_prefix(r"\\"))
+                else {
+                    return Cow::Borrowed(path);
+                };
+                Cow::Owned(PathBuf::from(format!(r"\\?\UNC\{rest}")))
+            }
+            _ => Cow::Borrowed(path),
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        Cow::Borrowed(path)
+    }
+}
+
+/// Find a pair of paths that differ only in case.
+///
+/// Such paths refer to the same file on case-insensitive filesystems (the default on Windows and
+/// macOS), such that writing both would silently clobber one with the other.
+pub fn find_case_collision<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+) -> Option<(&'a Path, &'a Path)> {
+    let mut seen = HashMap::new();
+    for path in paths {
+        let key = path.to_string_lossy().to_lowercase();
+        if let Some(existing) = seen.insert(key, path) {
+            if existing != path {
+                return Some((existing, path));
+            }
+        }
+    }
+    None
+}
+
 /// Convert a path to an absolute path, relative to the current working directory.
 ///
 /// Unlike [`std::fs::canonicalize`], this function does not resolve symlinks and does not require
@@ -334,4 +407,41 @@ mod tests {
         let err = normalize_path(path).unwrap_err();
         assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
     }
+
+    #[test]
+    fn test_find_case_collision() {
+        let paths = [
+            Path::new("foo/__init__.py"),
+            Path::new("foo/bar.py"),
+            Path::new("foo/Bar.py"),
+        ];
+        assert_eq!(
+            find_case_collision(paths),
+            Some((Path::new("foo/bar.py"), Path::new("foo/Bar.py")))
+        );
+
+        let paths = [Path::new("foo/bar.py"), Path::new("foo/baz.py")];
+        assert_eq!(find_case_collision(paths), None);
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_verbatim_path() {
+        assert_eq!(
+            verbatim_path(Path::new(r"C:\Users\ferris\..\crab\.venv")),
+            Path::new(r"\\?\C:\Users\crab\.venv")
+        );
+        assert_eq!(
+            verbatim_path(Path::new(r"\\server\share\.venv")),
+            Path::new(r"\\?\UNC\server\share\.venv")
+        );
+        assert_eq!(
+            verbatim_path(Path::new(r"\\?\C:\Users\ferris")),
+            Path::new(r"\\?\C:\Users\ferris")
+        );
+        assert_eq!(
+            verbatim_path(Path::new(r"ferris\.venv")),
+            Path::new(r"ferris\.venv")
+        );
+    }
 }
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use uv_normalize::PackageName;
+
+use crate::transaction::dist_info_name;
+
+/// A file that's provided by more than one distribution, with differing contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCollision {
+    /// The path to the file, relative to `site-packages`.
+    pub path: String,
+    /// The distributions that provide the file, in sorted order.
+    pub packages: Vec<PackageName>,
+}
+
+impl Display for FileCollision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is provided by {}",
+            self.path,
+            self.packages
+                .iter()
+                .map(|package| format!("`{package}`"))
+                .collect::<Vec<_>>()
+                .join(" and ")
+        )
+    }
+}
+
+/// Identify any files that would be overwritten with different contents by installing the given
+/// distributions.
+///
+/// A collision arises when two of the `installs` write the same path with different hashes (per
+/// their `RECORD` files), or when one of the `installs` writes a path that's owned by a
+/// `retained` distribution (i.e., an installed distribution that won't be removed or replaced)
+/// with a different hash. Identical files, like the empty `__init__.py` of a namespace package
+/// that's split across distributions, are not considered collisions.
+///
+/// The `RECORD` files of the `retained` distributions are only read if one of the `installs`
+/// writes a path that already exists in `site_packages`.
+pub fn find_file_collisions<'a>(
+    installs: impl IntoIterator<Item = &'a CachedDist>,
+    retained: impl IntoIterator<Item = &'a InstalledDist>,
+    site_packages: &Path,
+) -> Result<Vec<FileCollision>, install_wheel_rs::Error> {
+    let mut owners: BTreeMap<String, FxHashMap<PackageName, String>> = BTreeMap::new();
+    for dist in installs {
+        let Some(dist_info) = dist_info_name(dist.path()) else {
+            continue;
+        };
+        for (path, hash) in install_wheel_rs::recorded_hashes(&dist.path().join(dist_info))? {
+            if let Some(path) = site_packages_path(&path) {
+                owners
+                    .entry(path.to_string())
+                    .or_default()
+                    .insert(dist.name().clone(), hash);
+            }
+        }
+    }
+
+    // Only files that are (re-)written by the installation can collide.
+    if owners.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // A retained distribution can only own a file that already exists; if the installation
+    // writes none, there's no need to read any installed `RECORD`.
+    if owners.keys().any(|path| site_packages.join(path).exists()) {
+        for dist in retained {
+            // Ignore any installed distributions without a readable `RECORD` (e.g., `.egg-info`
+            // distributions); their files will be overwritten, as before.
+            let hashes = match install_wheel_rs::recorded_hashes(dist.path()) {
+                Ok(hashes) => hashes,
+                Err(err) => {
+                    debug!("Failed to read `RECORD` for {dist}: {err}");
+                    continue;
+                }
+            };
+            for (path, hash) in hashes {
+                if let Some(packages) = owners.get_mut(path.as_str()) {
+                    packages.entry(dist.name().clone()).or_insert(hash);
+                }
+            }
+        }
+    }
+
+    Ok(owners
+        .into_iter()
+        .filter(|(_, packages)| {
+            let mut hashes = packages.values();
+            hashes
+                .next()
+                .is_some_and(|first| hashes.any(|hash| hash != first))
+        })
+        .map(|(path, packages)| {
+            let mut packages = packages.into_keys().collect::<Vec<_>>();
+            packages.sort();
+            FileCollision { path, packages }
+        })
+        .collect())
+}
+
+/// Returns the path within `site-packages` to which a wheel's `RECORD` path is installed, if it's
+/// not part of the distribution's own metadata (i.e., its `.dist-info` directory) or installed
+/// elsewhere (e.g., `scripts` or `headers` from its `.data` directory).
+///
+/// Files in the `purelib` and `platlib` subdirectories of the `.data` directory are installed
+/// into `site-packages` directly, so `foo-1.0.data/purelib/foo.py` maps to `foo.py`.
+fn site_packages_path(path: &str) -> Option<&str> {
+    if path.starts_with("..") || path.starts_with('/') {
+        return None;
+    }
+    let Some((first, rest)) = path.split_once('/') else {
+        return Some(path);
+    };
+    if first.ends_with(".data") {
+        let (scheme, rest) = rest.split_once('/')?;
+        return matches!(scheme, "purelib" | "platlib")
+            .then_some(rest)
+            .and_then(site_packages_path);
+    }
+    if first.ends_with(".dist-info") || first == "__pycache__" {
+        return None;
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::site_packages_path;
+
+    #[test]
+    fn site_packages_paths() {
+        assert_eq!(
+            site_packages_path("foo/__init__.py"),
+            Some("foo/__init__.py")
+        );
+        assert_eq!(site_packages_path("foo.py"), Some("foo.py"));
+        assert_eq!(
+            site_packages_path("foo-1.0.data/purelib/foo/__init__.py"),
+            Some("foo/__init__.py")
+        );
+        assert_eq!(
+            site_packages_path("foo-1.0.data/platlib/_foo.so"),
+            Some("_foo.so")
+        );
+        assert_eq!(site_packages_path("foo-1.0.data/scripts/foo"), None);
+        assert_eq!(
+            site_packages_path("foo-1.0.data/purelib/foo-1.0.dist-info/RECORD"),
+            None
+        );
+        assert_eq!(site_packages_path("foo-1.0.dist-info/METADATA"), None);
+        assert_eq!(site_packages_path("__pycache__/foo.cpython-312.pyc"), None);
+        assert_eq!(site_packages_path("../../bin/foo"), None);
+    }
+}
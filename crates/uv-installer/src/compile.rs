@@ -5,6 +5,7 @@ use std::process::Stdio;
 use std::time::Duration;
 
 use async_channel::{Receiver, SendError};
+use glob::Pattern;
 use tempfile::tempdir_in;
 use thiserror::Error;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
@@ -13,6 +14,8 @@ use tokio::task::JoinError;
 use tracing::{debug, instrument};
 use walkdir::WalkDir;
 
+use uv_cache::{Cache, CacheBucket};
+use uv_configuration::CompileOptions;
 use uv_fs::Simplified;
 use uv_warnings::warn_user;
 
@@ -32,6 +35,8 @@ pub enum CompileError {
     PythonSubcommand(#[source] io::Error),
     #[error("Failed to create temporary script file")]
     TempFile(#[source] io::Error),
+    #[error("Failed to initialize the bytecode cache")]
+    BytecodeCache(#[source] io::Error),
     #[error(r#"Bytecode compilation failed, expected "{0}", received: "{1}""#)]
     WrongPath(String, String),
     #[error("Failed to write to Python {device}")]
@@ -48,28 +53,61 @@ pub enum CompileError {
     },
     #[error("Bytecode timed out ({}s)", _0.as_secs_f32())]
     Timeout(Duration),
+    #[error("Invalid bytecode optimization level: {0} (expected 0, 1, or 2)")]
+    OptimizationLevel(u8),
+    #[error("Invalid bytecode compilation exclusion pattern: `{0}`")]
+    Pattern(String, #[source] glob::PatternError),
+}
+
+/// The outcome of compiling a directory to bytecode.
+#[derive(Debug, Default)]
+pub struct CompileSummary {
+    /// The number of source files that were passed to the compiler.
+    pub files: usize,
+    /// The source files that failed to compile (e.g., due to syntax errors).
+    pub failures: Vec<PathBuf>,
 }
 
 /// Bytecode compile all file in `dir` using a pool of work-stealing Python interpreters running a
 /// Python script that calls `compileall.compile_file`.
 ///
-/// All compilation errors are muted (like pip). There is a 60s timeout for each file to handle
-/// a broken `python`.
+/// Compilation errors in individual files don't abort the compilation (like pip); instead, the
+/// failing files are returned in the [`CompileSummary`]. There is a 60s timeout for each file to
+/// handle a broken `python`.
+///
+/// Files and directories matching any of the exclusion patterns in the [`CompileOptions`] (relative
+/// to `dir`) are skipped.
 ///
 /// We only compile all files, but we don't update the RECORD, relying on PEP 491:
 /// > Uninstallers should be smart enough to remove .pyc even if it is not mentioned in RECORD.
 ///
 /// We've confirmed that both `uv` and `pip` (as of 24.0.0) remove the `__pycache__` directory.
-#[instrument(skip(python_executable))]
+///
+/// Compiled files are stored in [`CacheBucket::Bytecode`] and hardlinked into place when the same
+/// source file is compiled again (e.g., in another environment), so that compiling an identical
+/// environment is nearly free.
+#[instrument(skip(python_executable, cache))]
 pub async fn compile_tree(
     dir: &Path,
     python_executable: &Path,
-    cache: &Path,
-) -> Result<usize, CompileError> {
+    cache: &Cache,
+    options: &CompileOptions,
+) -> Result<CompileSummary, CompileError> {
     debug_assert!(
         dir.is_absolute(),
         "compileall doesn't work with relative paths"
     );
+    if let Some(level) = options.optimization_levels.iter().find(|level| **level > 2) {
+        return Err(CompileError::OptimizationLevel(*level));
+    }
+    let exclude = options
+        .exclude
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern).map_err(|err| CompileError::Pattern(pattern.clone(), err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     let worker_count = std::thread::available_parallelism().unwrap_or_else(|err| {
         warn_user!("Couldn't determine number of cores, compiling with a single thread: {err}");
         NonZeroUsize::MIN
@@ -79,7 +117,13 @@ pub async fn compile_tree(
     let (sender, receiver) = async_channel::bounded::<PathBuf>(worker_count.get() * 10);
 
     // Running Python with an actual file will produce better error messages.
-    let tempdir = tempdir_in(cache).map_err(CompileError::TempFile)?;
+    let tempdir = tempdir_in(cache.root()).map_err(CompileError::TempFile)?;
+
+    // Share compiled files across environments.
+    let bytecode_cache = cache.bucket(CacheBucket::Bytecode);
+    fs_err::tokio::create_dir_all(&bytecode_cache)
+        .await
+        .map_err(CompileError::BytecodeCache)?;
     let pip_compileall_py = tempdir.path().join("pip_compileall.py");
 
     debug!("Starting {} bytecode compilation workers", worker_count);
@@ -89,6 +133,8 @@ pub async fn compile_tree(
             dir.to_path_buf(),
             python_executable.to_path_buf(),
             pip_compileall_py.clone(),
+            bytecode_cache.clone(),
+            options.clone(),
             receiver.clone(),
         )));
     }
@@ -98,10 +144,16 @@ pub async fn compile_tree(
     // Start the producer, sending all `.py` files to workers.
     let mut source_files = 0;
     let mut send_error = None;
-    let walker = WalkDir::new(dir)
-        .into_iter()
+    let walker = WalkDir::new(dir).into_iter().filter_entry(|entry| {
         // Otherwise we stumble over temporary files from `compileall`.
-        .filter_entry(|dir| dir.file_name() != "__pycache__");
+        if entry.file_name() == "__pycache__" {
+            return false;
+        }
+        // Skip any excluded files and directories.
+        entry.path().strip_prefix(dir).map_or(true, |relative| {
+            !exclude.iter().any(|pattern| pattern.matches_path(relative))
+        })
+    });
     for entry in walker {
         let entry = entry?;
         // https://github.com/pypa/pip/blob/3820b0e52c7fed2b2c43ba731b718f316e6816d1/src/pip/_internal/operations/install/wheel.py#L593-L604
@@ -123,15 +175,17 @@ pub async fn compile_tree(
     drop(sender);
 
     // Make sure all workers exit regularly, avoid hiding errors.
+    let mut failures = Vec::new();
     for result in futures::future::join_all(worker_handles).await {
         match result {
             // There spawning earlier errored due to a panic in a task.
             Err(join_err) => return Err(CompileError::Join(join_err)),
             // The worker reports an error.
             Ok(Err(compile_error)) => return Err(compile_error),
-            Ok(Ok(())) => {}
+            Ok(Ok(worker_failures)) => failures.extend(worker_failures),
         }
     }
+    failures.sort();
 
     if let Some(send_error) = send_error {
         // This is suspicious: Why did the channel stop working, but all workers exited
@@ -139,15 +193,20 @@ pub async fn compile_tree(
         return Err(CompileError::WorkerDisappeared(send_error));
     }
 
-    Ok(source_files)
+    Ok(CompileSummary {
+        files: source_files,
+        failures,
+    })
 }
 
 async fn worker(
     dir: PathBuf,
     interpreter: PathBuf,
     pip_compileall_py: PathBuf,
+    bytecode_cache: PathBuf,
+    options: CompileOptions,
     receiver: Receiver<PathBuf>,
-) -> Result<(), CompileError> {
+) -> Result<Vec<PathBuf>, CompileError> {
     fs_err::tokio::write(&pip_compileall_py, COMPILEALL_SCRIPT)
         .await
         .map_err(CompileError::TempFile)?;
@@ -159,8 +218,14 @@ async fn worker(
     let wait_until_ready = async {
         loop {
             // If the interpreter started successful, return it, else retry.
-            if let Some(child) =
-                launch_bytecode_compiler(&dir, &interpreter, &pip_compileall_py).await?
+            if let Some(child) = launch_bytecode_compiler(
+                &dir,
+                &interpreter,
+                &pip_compileall_py,
+                &bytecode_cache,
+                &options,
+            )
+            .await?
             {
                 break Ok::<_, CompileError>(child);
             }
@@ -198,13 +263,13 @@ async fn worker(
     } else {
         let stderr = String::from_utf8_lossy(&child_stderr_collected);
         match result {
-            Ok(()) => {
+            Ok(failures) => {
                 debug!(
                     "Bytecode compilation `python` at {} stderr:\n{}\n---",
                     interpreter.user_display(),
                     stderr
                 );
-                Ok(())
+                Ok(failures)
             }
             Err(err) => Err(CompileError::ErrorWithStderr {
                 stderr: stderr.trim().to_string(),
@@ -223,6 +288,8 @@ async fn launch_bytecode_compiler(
     dir: &Path,
     interpreter: &Path,
     pip_compileall_py: &Path,
+    bytecode_cache: &Path,
+    options: &CompileOptions,
 ) -> Result<
     Option<(
         Child,
@@ -232,8 +299,10 @@ async fn launch_bytecode_compiler(
     )>,
     CompileError,
 > {
-    // We input the paths through stdin and get the successful paths returned through stdout.
-    let mut bytecode_compiler = Command::new(interpreter)
+    // We input the paths through stdin and get the paths, with their compilation status, returned
+    // through stdout.
+    let mut command = Command::new(interpreter);
+    command
         .arg(pip_compileall_py)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -241,8 +310,22 @@ async fn launch_bytecode_compiler(
         .current_dir(dir)
         // Otherwise stdout is buffered and we'll wait forever for a response
         .env("PYTHONUNBUFFERED", "1")
-        .spawn()
-        .map_err(CompileError::PythonSubcommand)?;
+        .env("UV_BYTECODE_CACHE", bytecode_cache);
+    if !options.optimization_levels.is_empty() {
+        command.env(
+            "UV_COMPILE_OPTIMIZATION_LEVELS",
+            options
+                .optimization_levels
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    if let Some(invalidation_mode) = options.invalidation_mode {
+        command.env("PYC_INVALIDATION_MODE", invalidation_mode.as_str());
+    }
+    let mut bytecode_compiler = command.spawn().map_err(CompileError::PythonSubcommand)?;
 
     // https://stackoverflow.com/questions/49218599/write-to-child-process-stdin-in-rust/49597789#comment120223107_49597789
     // Unbuffered, we need to write immediately or the python process will get stuck waiting
@@ -291,16 +374,19 @@ async fn launch_bytecode_compiler(
 }
 
 /// We use stdin/stdout as a sort of bounded channel. We write one path to stdin, then wait until
-/// we get the same path back from stdout. This way we ensure one worker is only working on one
-/// piece of work at the same time.
+/// we get the same path back from stdout, prefixed by its compilation status. This way we ensure
+/// one worker is only working on one piece of work at the same time.
+///
+/// Returns the files that failed to compile.
 async fn worker_main_loop(
     receiver: Receiver<PathBuf>,
     mut child_stdin: ChildStdin,
     child_stdout: &mut BufReader<ChildStdout>,
-) -> Result<(), CompileError> {
+) -> Result<Vec<PathBuf>, CompileError> {
+    let mut failures = Vec::new();
     let mut out_line = String::new();
-    while let Ok(source_file) = receiver.recv().await {
-        let source_file = source_file.display().to_string();
+    while let Ok(source_path) = receiver.recv().await {
+        let source_file = source_path.display().to_string();
         if source_file.contains(['\r', '\n']) {
             warn_user!("Path contains newline, skipping: {source_file:?}");
             continue;
@@ -336,9 +422,14 @@ async fn worker_main_loop(
         // This is a sanity check, if we don't get the path back something has gone wrong, e.g.
         // we're not actually running a python interpreter.
         let actual = out_line.trim_end_matches(['\n', '\r']);
-        if actual != source_file {
-            return Err(CompileError::WrongPath(source_file, actual.to_string()));
+        match actual.split_once(' ') {
+            Some(("ok", path)) if path == source_file => {}
+            Some(("error", path)) if path == source_file => {
+                debug!("Failed to compile to bytecode: {source_file}");
+                failures.push(source_path);
+            }
+            _ => return Err(CompileError::WrongPath(source_file, actual.to_string())),
         }
     }
-    Ok(())
+    Ok(failures)
 }
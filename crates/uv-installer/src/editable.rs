@@ -22,6 +22,8 @@ pub struct BuiltEditable {
     pub editable: LocalEditable,
     pub wheel: CachedDist,
     pub metadata: Metadata23,
+    /// Whether the wheel was reused from the cache, rather than built.
+    pub cached: bool,
 }
 
 /// An editable distribution that has been resolved to a concrete distribution.
@@ -112,24 +114,86 @@ impl std::fmt::Display for ResolvedEditable {
     }
 }
 
-/// Returns `true` if the source tree at the given path contains dynamic metadata.
+/// Returns `true` if the source tree at the given path contains dynamic metadata that affects
+/// installation, such that previously-built metadata may be stale.
 pub fn is_dynamic(path: &Path) -> bool {
-    // If there's no `pyproject.toml`, we assume it's dynamic.
-    let Ok(contents) = fs_err::read_to_string(path.join("pyproject.toml")) else {
-        return true;
-    };
-    let Ok(pyproject_toml) = toml::from_str::<PyProjectToml>(&contents) else {
-        return true;
-    };
-    // If `[project]` is not present, we assume it's dynamic.
-    let Some(project) = pyproject_toml.project else {
-        // ...unless it appears to be a Poetry project.
-        return pyproject_toml
-            .tool
-            .map_or(true, |tool| tool.poetry.is_none());
-    };
-    // `[project.dynamic]` must be present and non-empty.
-    project.dynamic.is_some_and(|dynamic| !dynamic.is_empty())
+    DynamicMetadata::from_source_tree(path).affects_install()
+}
+
+/// The dynamic metadata declared by a source tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicMetadata {
+    /// All metadata is declared statically.
+    Static,
+    /// The given `[project]` fields are declared in `[project.dynamic]`.
+    Fields(Vec<String>),
+    /// The metadata can't be read statically (e.g., there's no `pyproject.toml`, or no
+    /// `[project]` table), so all of it is assumed to be dynamic.
+    Unknown,
+}
+
+impl DynamicMetadata {
+    /// The `[project]` fields that affect installation, and thus the reuse of an installed
+    /// editable, if declared as dynamic.
+    const INSTALL_FIELDS: &'static [&'static str] = &[
+        "name",
+        "version",
+        "requires-python",
+        "dependencies",
+        "optional-dependencies",
+        "scripts",
+        "gui-scripts",
+        "entry-points",
+    ];
+
+    /// Read the dynamic metadata declared by the source tree at the given path.
+    pub fn from_source_tree(path: &Path) -> Self {
+        // If there's no `pyproject.toml`, we assume it's dynamic.
+        let Ok(contents) = fs_err::read_to_string(path.join("pyproject.toml")) else {
+            return Self::Unknown;
+        };
+        let Ok(pyproject_toml) = toml::from_str::<PyProjectToml>(&contents) else {
+            return Self::Unknown;
+        };
+        // If `[project]` is not present, we assume it's dynamic.
+        let Some(project) = pyproject_toml.project else {
+            // ...unless it appears to be a Poetry project.
+            return if pyproject_toml
+                .tool
+                .is_some_and(|tool| tool.poetry.is_some())
+            {
+                Self::Static
+            } else {
+                Self::Unknown
+            };
+        };
+        match project.dynamic {
+            Some(dynamic) if !dynamic.is_empty() => Self::Fields(dynamic),
+            _ => Self::Static,
+        }
+    }
+
+    /// Returns `true` if any of the dynamic metadata affects installation (e.g., the version or
+    /// dependencies), as opposed to purely descriptive fields like `readme`.
+    pub fn affects_install(&self) -> bool {
+        match self {
+            Self::Static => false,
+            Self::Fields(fields) => fields
+                .iter()
+                .any(|field| Self::INSTALL_FIELDS.contains(&field.as_str())),
+            Self::Unknown => true,
+        }
+    }
+}
+
+impl std::fmt::Display for DynamicMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static => write!(f, "none"),
+            Self::Fields(fields) => write!(f, "{}", fields.join(", ")),
+            Self::Unknown => write!(f, "unknown (no static `[project]` metadata)"),
+        }
+    }
 }
 
 /// A pyproject.toml as specified in PEP 517.
@@ -0,0 +1,289 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::debug;
+
+use distribution_types::{CachedDist, InstalledDist, InstalledMetadata, InstalledVersion, Name};
+use uv_normalize::PackageName;
+
+use crate::ReinstallReason;
+
+/// A stage of the installation lifecycle at which hooks are invoked.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// Invoked once all distributions have been downloaded and built, but before any
+    /// distributions are removed from or added to the environment. If a hook fails, the
+    /// installation is aborted.
+    PreInstall,
+    /// Invoked after the distributions have been installed into the environment.
+    PostInstall,
+    /// Invoked after distributions have been removed from the environment.
+    PostUninstall,
+}
+
+impl Display for HookEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PreInstall => write!(f, "pre-install"),
+            Self::PostInstall => write!(f, "post-install"),
+            Self::PostUninstall => write!(f, "post-uninstall"),
+        }
+    }
+}
+
+/// A distribution that's added to or removed from the environment, as reported to a hook.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookDist {
+    pub name: String,
+    pub version: String,
+    /// The URL from which the distribution was installed, for direct URL dependencies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The reason that the distribution is removed, for distributions that are re-installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<ReinstallReason>,
+}
+
+impl From<&CachedDist> for HookDist {
+    fn from(dist: &CachedDist) -> Self {
+        Self {
+            name: dist.name().to_string(),
+            version: dist.filename().version.to_string(),
+            url: match dist {
+                CachedDist::Registry(_) => None,
+                CachedDist::Url(dist) => Some(dist.url.to_string()),
+            },
+            reason: None,
+        }
+    }
+}
+
+impl From<&InstalledDist> for HookDist {
+    fn from(dist: &InstalledDist) -> Self {
+        Self {
+            name: dist.name().to_string(),
+            version: dist.version().to_string(),
+            url: match dist.installed_version() {
+                InstalledVersion::Version(_) => None,
+                InstalledVersion::Url(url, _) => Some(url.to_string()),
+            },
+            reason: None,
+        }
+    }
+}
+
+/// The set of changes to the environment, as reported to a hook (serialized as JSON).
+#[derive(Debug, Clone, Serialize)]
+pub struct HookPlan {
+    /// The lifecycle stage at which the hook is invoked.
+    pub event: HookEvent,
+    /// The root of the Python environment that's being modified.
+    pub environment: PathBuf,
+    /// The distributions that are added to the environment.
+    pub install: Vec<HookDist>,
+    /// The distributions that are removed from the environment.
+    pub uninstall: Vec<HookDist>,
+}
+
+impl HookPlan {
+    /// Create a [`HookPlan`] for the given event, from the distributions that are added to and
+    /// removed from the environment.
+    pub fn new<'a>(
+        event: HookEvent,
+        environment: &Path,
+        install: impl IntoIterator<Item = &'a CachedDist>,
+        uninstall: impl IntoIterator<Item = &'a InstalledDist>,
+    ) -> Self {
+        Self {
+            event,
+            environment: environment.to_path_buf(),
+            install: install.into_iter().map(HookDist::from).collect(),
+            uninstall: uninstall.into_iter().map(HookDist::from).collect(),
+        }
+    }
+
+    /// Annotate the removed distributions with the reason that they're re-installed, if any.
+    #[must_use]
+    pub fn with_reasons(mut self, reasons: &FxHashMap<PackageName, ReinstallReason>) -> Self {
+        for dist in &mut self.uninstall {
+            dist.reason = reasons
+                .iter()
+                .find_map(|(name, reason)| (name.as_ref() == dist.name).then_some(*reason));
+        }
+        self
+    }
+}
+
+/// A hook that's invoked at a stage of the installation lifecycle.
+///
+/// Library users can implement this trait (or pass a closure) to receive callbacks; the CLI uses
+/// [`CommandHook`]. Hooks may block, as they're run on a blocking thread.
+pub trait Hook: Send + Sync {
+    /// Run the hook for the given plan.
+    fn run(&self, plan: &HookPlan) -> Result<(), HookError>;
+}
+
+impl<F> Hook for F
+where
+    F: Fn(&HookPlan) -> Result<(), String> + Send + Sync,
+{
+    fn run(&self, plan: &HookPlan) -> Result<(), HookError> {
+        self(plan).map_err(|message| HookError::Callback(plan.event, message))
+    }
+}
+
+/// A hook that runs a shell command, passing the [`HookPlan`] as JSON on `stdin`.
+#[derive(Debug, Clone)]
+pub struct CommandHook {
+    command: String,
+}
+
+impl CommandHook {
+    /// Create a [`CommandHook`] that runs the given command via the platform shell.
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+}
+
+impl Hook for CommandHook {
+    fn run(&self, plan: &HookPlan) -> Result<(), HookError> {
+        let json = serde_json::to_vec(plan)?;
+
+        // Forward the hook's output to `stderr`, such that it can't interleave with uv's own output
+        // (e.g., `--format json` or `--report -`).
+        debug!("Running {} hook: {}", plan.event, self.command);
+        let mut child = if cfg!(windows) {
+            Command::new("cmd")
+                .arg("/C")
+                .arg(&self.command)
+                .stdin(Stdio::piped())
+                .stdout(io::stderr())
+                .spawn()
+        } else {
+            Command::new("sh")
+                .arg("-c")
+                .arg(&self.command)
+                .stdin(Stdio::piped())
+                .stdout(io::stderr())
+                .spawn()
+        }
+        .map_err(|err| HookError::Spawn(self.command.clone(), err))?;
+
+        // Write the plan to `stdin`, then close it. The hook may exit without reading the plan,
+        // so ignore broken pipes.
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(&json) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(err) => return Err(HookError::Spawn(self.command.clone(), err)),
+            }
+        }
+
+        let status = child
+            .wait()
+            .map_err(|err| HookError::Spawn(self.command.clone(), err))?;
+        if !status.success() {
+            return Err(HookError::Failed(
+                plan.event,
+                self.command.clone(),
+                status.to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The hooks to invoke over the installation lifecycle.
+#[derive(Default, Clone)]
+pub struct InstallHooks {
+    pre_install: Vec<Arc<dyn Hook>>,
+    post_install: Vec<Arc<dyn Hook>>,
+    post_uninstall: Vec<Arc<dyn Hook>>,
+}
+
+impl InstallHooks {
+    /// Add a hook to invoke before the environment is modified.
+    #[must_use]
+    pub fn with_pre_install(mut self, hook: impl Hook + 'static) -> Self {
+        self.pre_install.push(Arc::new(hook));
+        self
+    }
+
+    /// Add a hook to invoke after distributions are installed.
+    #[must_use]
+    pub fn with_post_install(mut self, hook: impl Hook + 'static) -> Self {
+        self.post_install.push(Arc::new(hook));
+        self
+    }
+
+    /// Add a hook to invoke after distributions are uninstalled.
+    #[must_use]
+    pub fn with_post_uninstall(mut self, hook: impl Hook + 'static) -> Self {
+        self.post_uninstall.push(Arc::new(hook));
+        self
+    }
+
+    /// Returns `true` if no hooks are registered for the given event.
+    pub fn is_empty(&self, event: HookEvent) -> bool {
+        self.hooks(event).is_empty()
+    }
+
+    /// Returns `true` if no hooks are registered for any event.
+    pub fn is_disabled(&self) -> bool {
+        self.pre_install.is_empty()
+            && self.post_install.is_empty()
+            && self.post_uninstall.is_empty()
+    }
+
+    /// Run the hooks registered for the plan's event, in order, stopping at the first failure.
+    pub async fn run(&self, plan: &HookPlan) -> Result<(), HookError> {
+        for hook in self.hooks(plan.event) {
+            let hook = hook.clone();
+            let plan = plan.clone();
+            tokio::task::spawn_blocking(move || hook.run(&plan)).await??;
+        }
+        Ok(())
+    }
+
+    fn hooks(&self, event: HookEvent) -> &[Arc<dyn Hook>] {
+        match event {
+            HookEvent::PreInstall => &self.pre_install,
+            HookEvent::PostInstall => &self.post_install,
+            HookEvent::PostUninstall => &self.post_uninstall,
+        }
+    }
+}
+
+impl Debug for InstallHooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstallHooks")
+            .field("pre_install", &self.pre_install.len())
+            .field("post_install", &self.post_install.len())
+            .field("post_uninstall", &self.post_uninstall.len())
+            .finish()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("Failed to serialize the installation plan")]
+    Serialize(#[from] serde_json::Error),
+    #[error("Failed to run hook: `{0}`")]
+    Spawn(String, #[source] io::Error),
+    #[error("The {0} hook `{1}` failed ({2})")]
+    Failed(HookEvent, String, String),
+    #[error("The {0} hook failed: {1}")]
+    Callback(HookEvent, String),
+    #[error("The hook panicked")]
+    Join(#[from] tokio::task::JoinError),
+}
@@ -0,0 +1,198 @@
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use futures::{stream, StreamExt};
+use thiserror::Error;
+use tokio::process::Command;
+use tracing::{debug, instrument};
+
+use distribution_types::{CachedDist, Name};
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+/// Import a module by name, with the environment's `site-packages` directories (which may include
+/// a `--target` directory) at the front of the path.
+const IMPORT_SCRIPT: &str =
+    "import importlib, sys; sys.path[:0] = sys.argv[2:]; importlib.import_module(sys.argv[1])";
+/// This is longer than any import should ever take.
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum ImportCheckError {
+    #[error("Failed to list the top-level modules of: {0}")]
+    ReadDir(String, #[source] io::Error),
+    #[error("Failed to start Python interpreter to import `{0}`")]
+    PythonSubcommand(String, #[source] io::Error),
+}
+
+/// A top-level module that failed to import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportFailure {
+    /// The distribution that provides the module.
+    pub package: PackageName,
+    /// The name of the module.
+    pub module: String,
+    /// The last line of the interpreter's error output, e.g., the `ImportError` message.
+    pub message: String,
+}
+
+/// Import each top-level module provided by the given distributions, each in a separate Python
+/// subprocess, to catch broken wheels (e.g., extension modules that link against missing shared
+/// libraries) at install time rather than at first use.
+///
+/// Failures to import don't abort the check; instead, the failing modules are returned. As with
+/// bytecode compilation, there is a 60s timeout for each module to handle a broken `python`.
+#[instrument(skip_all)]
+pub async fn check_imports(
+    wheels: &[CachedDist],
+    venv: &PythonEnvironment,
+) -> Result<Vec<ImportFailure>, ImportCheckError> {
+    let python_executable = venv.python_executable();
+    let site_packages = venv
+        .site_packages()
+        .map(Path::to_path_buf)
+        .collect::<Vec<_>>();
+
+    let mut modules = Vec::new();
+    for wheel in wheels {
+        let names = top_level_modules(wheel.path())
+            .map_err(|err| ImportCheckError::ReadDir(wheel.to_string(), err))?;
+        modules.extend(
+            names
+                .into_iter()
+                .map(|module| (wheel.name().clone(), module)),
+        );
+    }
+
+    let worker_count = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+    debug!(
+        "Importing {} top-level modules with {} workers",
+        modules.len(),
+        worker_count
+    );
+
+    let site_packages = &site_packages;
+    let results = stream::iter(modules)
+        .map(|(package, module)| async move {
+            let message = import_module(python_executable, site_packages, &module).await?;
+            Ok::<_, ImportCheckError>(message.map(|message| ImportFailure {
+                package,
+                module,
+                message,
+            }))
+        })
+        .buffer_unordered(worker_count.get())
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut failures = Vec::new();
+    for result in results {
+        failures.extend(result?);
+    }
+    failures.sort_by(|a, b| {
+        a.package
+            .cmp(&b.package)
+            .then_with(|| a.module.cmp(&b.module))
+    });
+    Ok(failures)
+}
+
+/// Import a module in an isolated Python subprocess, returning an error message if the import
+/// fails.
+async fn import_module(
+    python_executable: &Path,
+    site_packages: &[PathBuf],
+    module: &str,
+) -> Result<Option<String>, ImportCheckError> {
+    // Use isolated mode, so that modules in the working directory don't shadow those that were
+    // installed.
+    let child = Command::new(python_executable)
+        .arg("-I")
+        .arg("-c")
+        .arg(IMPORT_SCRIPT)
+        .arg(module)
+        .args(site_packages)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| ImportCheckError::PythonSubcommand(module.to_string(), err))?;
+
+    let output = match tokio::time::timeout(IMPORT_TIMEOUT, child.wait_with_output()).await {
+        Ok(output) => {
+            output.map_err(|err| ImportCheckError::PythonSubcommand(module.to_string(), err))?
+        }
+        Err(_) => {
+            return Ok(Some(format!(
+                "Import timed out ({}s)",
+                IMPORT_TIMEOUT.as_secs_f32()
+            )))
+        }
+    };
+
+    if output.status.success() {
+        debug!("Imported `{module}`");
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .last()
+        .map_or_else(
+            || format!("Python exited with {}", output.status),
+            ToString::to_string,
+        );
+    debug!("Failed to import `{module}`: {message}");
+    Ok(Some(message))
+}
+
+/// Determine the importable top-level modules of an unzipped wheel.
+///
+/// Packages (directories) and modules (`.py` files and extension modules) at the root of the
+/// wheel are included; the `.dist-info` and `.data` directories, and any names that aren't valid
+/// Python identifiers (e.g., `.pth` files), are skipped.
+pub fn top_level_modules(wheel: &Path) -> Result<Vec<String>, io::Error> {
+    let mut modules = Vec::new();
+    for entry in fs_err::read_dir(wheel)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        let module = if entry.file_type()?.is_dir() {
+            file_name
+        } else if let Some(stem) = file_name.strip_suffix(".py") {
+            stem
+        } else if Path::new(file_name)
+            .extension()
+            .is_some_and(|ext| ext == "so" || ext == "pyd")
+        {
+            // Extension modules may carry an ABI tag, as in `_foo.cpython-312-x86_64-linux-gnu.so`.
+            file_name.split('.').next().unwrap_or(file_name)
+        } else {
+            continue;
+        };
+        if is_identifier(module) && module != "__pycache__" {
+            modules.push(module.to_string());
+        }
+    }
+    modules.sort_unstable();
+    modules.dedup();
+    Ok(modules)
+}
+
+/// Returns `true` if the name is a valid (ASCII) Python identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
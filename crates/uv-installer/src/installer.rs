@@ -1,15 +1,68 @@
-use anyhow::{Context, Error, Result};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use tokio::task::JoinError;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 
-use distribution_types::CachedDist;
+use distribution_types::{CachedDist, Hashed};
+use install_wheel_rs::Layout;
 use uv_interpreter::PythonEnvironment;
 
+use crate::ErrorKind;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to install: {} ({})", _0.filename(), _0)]
+    Install(Box<CachedDist>, #[source] install_wheel_rs::Error),
+    #[error("Failed to determine the direct URL of: {0}")]
+    DirectUrl(Box<CachedDist>, #[source] anyhow::Error),
+    /// Should not occur; only seen when the installation task panicked.
+    #[error("The task executor is broken, did some other task panic?")]
+    Join(#[from] JoinError),
+    #[error("The installation was cancelled")]
+    Cancelled,
+}
+
+impl Error {
+    /// Returns the [`ErrorKind`] of this error, e.g., to distinguish filesystem failures from
+    /// cancellation.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Install(_, err) => ErrorKind::from(err),
+            Self::DirectUrl(..) | Self::Join(..) => ErrorKind::Other,
+            Self::Cancelled => ErrorKind::Cancelled,
+        }
+    }
+
+    /// Returns the wheel that failed to install, if known.
+    pub fn wheel(&self) -> Option<&CachedDist> {
+        match self {
+            Self::Install(wheel, _) | Self::DirectUrl(wheel, _) => Some(wheel),
+            Self::Join(..) | Self::Cancelled => None,
+        }
+    }
+}
+
+/// The error formerly returned by [`Installer::install`] if the installation was cancelled.
+#[deprecated(note = "match on `InstallError::Cancelled` instead")]
+#[derive(Debug, thiserror::Error)]
+#[error("The installation was cancelled")]
+pub struct InstallCancelled;
+
+#[allow(deprecated)]
+impl From<InstallCancelled> for Error {
+    fn from(_: InstallCancelled) -> Self {
+        Self::Cancelled
+    }
+}
+
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
     link_mode: install_wheel_rs::linker::LinkMode,
+    script_options: install_wheel_rs::ScriptOptions,
+    filter: install_wheel_rs::InstallFilter,
     reporter: Option<Box<dyn Reporter>>,
     installer_name: Option<String>,
+    cancellation: CancellationToken,
 }
 
 impl<'a> Installer<'a> {
@@ -18,8 +71,11 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: install_wheel_rs::linker::LinkMode::default(),
+            script_options: install_wheel_rs::ScriptOptions::default(),
+            filter: install_wheel_rs::InstallFilter::default(),
             reporter: None,
             installer_name: Some("uv".to_string()),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -29,6 +85,23 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Set the [`ScriptOptions`][`install_wheel_rs::ScriptOptions`] to use when generating
+    /// entrypoint scripts.
+    #[must_use]
+    pub fn with_script_options(self, script_options: install_wheel_rs::ScriptOptions) -> Self {
+        Self {
+            script_options,
+            ..self
+        }
+    }
+
+    /// Set the [`InstallFilter`][`install_wheel_rs::InstallFilter`] for files to omit from each
+    /// wheel.
+    #[must_use]
+    pub fn with_filter(self, filter: install_wheel_rs::InstallFilter) -> Self {
+        Self { filter, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -47,37 +120,124 @@ impl<'a> Installer<'a> {
         }
     }
 
+    /// Set the [`CancellationToken`] with which to abort the installation.
+    ///
+    /// Wheels that are already being installed are allowed to finish, but no further wheels are
+    /// installed, and [`Installer::install`] returns [`Error::Cancelled`], such that the caller can
+    /// roll back any partial installation (e.g., via a [`crate::Transaction`]).
+    #[must_use]
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation,
+            ..self
+        }
+    }
+
     /// Install a set of wheels into a Python virtual environment.
+    ///
+    /// The wheels are installed in parallel on a blocking thread pool, such that the returned
+    /// future can be awaited from any runtime.
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
-    pub fn install(self, wheels: &[CachedDist]) -> Result<()> {
+    pub async fn install(self, wheels: &[CachedDist]) -> Result<(), Error> {
         let layout = self.venv.interpreter().layout();
-        tokio::task::block_in_place(|| {
-            wheels.par_iter().try_for_each(|wheel| {
-                install_wheel_rs::linker::install_wheel(
-                    &layout,
-                    wheel.path(),
-                    wheel.filename(),
-                    wheel
-                        .parsed_url()?
-                        .as_ref()
-                        .map(pypi_types::DirectUrl::try_from)
-                        .transpose()?
-                        .as_ref(),
-                    self.installer_name.as_deref(),
-                    self.link_mode,
-                )
-                .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
-
-                if let Some(reporter) = self.reporter.as_ref() {
-                    reporter.on_install_progress(wheel);
-                }
-
-                Ok::<(), Error>(())
-            })
+        let wheels = wheels.to_vec();
+        let Self {
+            venv: _,
+            link_mode,
+            script_options,
+            filter,
+            reporter,
+            installer_name,
+            cancellation,
+        } = self;
+        tokio::task::spawn_blocking(move || {
+            install(
+                &layout,
+                &wheels,
+                link_mode,
+                script_options,
+                &filter,
+                reporter.as_deref(),
+                installer_name.as_deref(),
+                &cancellation,
+            )
         })
+        .await?
+    }
+
+    /// Install a set of wheels into a Python virtual environment, blocking the current thread.
+    #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
+    pub fn install_blocking(self, wheels: &[CachedDist]) -> Result<(), Error> {
+        install(
+            &self.venv.interpreter().layout(),
+            wheels,
+            self.link_mode,
+            self.script_options,
+            &self.filter,
+            self.reporter.as_deref(),
+            self.installer_name.as_deref(),
+            &self.cancellation,
+        )
     }
 }
 
+/// Install a set of wheels into the given [`Layout`] in parallel.
+#[allow(clippy::too_many_arguments)]
+fn install(
+    layout: &Layout,
+    wheels: &[CachedDist],
+    link_mode: install_wheel_rs::linker::LinkMode,
+    script_options: install_wheel_rs::ScriptOptions,
+    filter: &install_wheel_rs::InstallFilter,
+    reporter: Option<&dyn Reporter>,
+    installer_name: Option<&str>,
+    cancellation: &CancellationToken,
+) -> Result<(), Error> {
+    wheels.par_iter().try_for_each(|wheel| {
+        if cancellation.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let direct_url = wheel
+            .parsed_url()
+            .and_then(|parsed_url| {
+                Ok(parsed_url
+                    .as_ref()
+                    .map(pypi_types::DirectUrl::try_from)
+                    .transpose()?)
+            })
+            .map_err(|err| Error::DirectUrl(Box::new(wheel.clone()), err))?
+            .map(|direct_url| direct_url.with_hashes(wheel.hashes()));
+
+        // Registry distributions have no `direct_url.json`, so their hashes are recorded separately.
+        let archive_info = match wheel {
+            CachedDist::Registry(dist) if !dist.hashes.is_empty() => {
+                Some(pypi_types::ArchiveInfo::from_hashes(&dist.hashes))
+            }
+            CachedDist::Registry(_) | CachedDist::Url(_) => None,
+        };
+
+        install_wheel_rs::linker::install_wheel(
+            layout,
+            wheel.path(),
+            wheel.filename(),
+            direct_url.as_ref(),
+            archive_info.as_ref(),
+            installer_name,
+            link_mode,
+            script_options,
+            filter,
+        )
+        .map_err(|err| Error::Install(Box::new(wheel.clone()), err))?;
+
+        if let Some(reporter) = reporter {
+            reporter.on_install_progress(wheel);
+        }
+
+        Ok(())
+    })
+}
+
 pub trait Reporter: Send + Sync {
     /// Callback to invoke when a dependency is installed.
     fn on_install_progress(&self, wheel: &CachedDist);
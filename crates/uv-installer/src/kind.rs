@@ -0,0 +1,170 @@
+/// The broad category of a [`crate::PrepareError`] or [`crate::InstallError`], for callers that
+/// need to react to failures (e.g., by retrying network errors) without matching on every
+/// underlying error type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A distribution or repository couldn't be fetched, e.g., due to a connection failure or an
+    /// HTTP error.
+    Network,
+    /// A distribution didn't match the expected hashes, or hashes were required but missing.
+    Hash,
+    /// A file couldn't be read from or written to the cache or the environment.
+    Filesystem,
+    /// A source distribution or editable couldn't be built, or the built wheel was invalid.
+    Build,
+    /// The operation was cancelled via its [`tokio_util::sync::CancellationToken`].
+    Cancelled,
+    /// Any other failure.
+    Other,
+}
+
+impl From<&uv_distribution::Error> for ErrorKind {
+    fn from(err: &uv_distribution::Error) -> Self {
+        match err {
+            uv_distribution::Error::Url(..)
+            | uv_distribution::Error::JoinRelativeUrl(..)
+            | uv_distribution::Error::Git(..)
+            | uv_distribution::Error::Reqwest(..)
+            | uv_distribution::Error::Client(..)
+            | uv_distribution::Error::ReqwestMiddlewareError(..) => Self::Network,
+            uv_distribution::Error::HashExhaustion(..)
+            | uv_distribution::Error::MismatchedHashes { .. }
+            | uv_distribution::Error::MissingHashes { .. }
+            | uv_distribution::Error::MissingActualHashes { .. }
+            | uv_distribution::Error::MissingExpectedHashes { .. }
+            | uv_distribution::Error::HashesNotSupportedSourceTree(..)
+            | uv_distribution::Error::HashesNotSupportedGit(..) => Self::Hash,
+            uv_distribution::Error::RelativePath(..)
+            | uv_distribution::Error::CacheRead(..)
+            | uv_distribution::Error::CacheWrite(..)
+            | uv_distribution::Error::CacheDecode(..)
+            | uv_distribution::Error::CacheEncode(..)
+            | uv_distribution::Error::Extract(..)
+            | uv_distribution::Error::NotFound(..) => Self::Filesystem,
+            uv_distribution::Error::Record(err) => Self::from(err),
+            uv_distribution::Error::Build(..)
+            | uv_distribution::Error::BuildEditable(..)
+            | uv_distribution::Error::WheelFilename(..)
+            | uv_distribution::Error::NameMismatch { .. }
+            | uv_distribution::Error::VersionMismatch { .. }
+            | uv_distribution::Error::Metadata(..)
+            | uv_distribution::Error::DistInfo(..)
+            | uv_distribution::Error::Zip(..)
+            | uv_distribution::Error::DirWithoutEntrypoint(..)
+            | uv_distribution::Error::MissingPkgInfo
+            | uv_distribution::Error::DynamicPkgInfo(..)
+            | uv_distribution::Error::MissingPyprojectToml
+            | uv_distribution::Error::DynamicPyprojectToml(..) => Self::Build,
+            uv_distribution::Error::NoBuild
+            | uv_distribution::Error::NoBinary
+            | uv_distribution::Error::DirectUrl(..)
+            | uv_distribution::Error::UnsupportedScheme(..)
+            | uv_distribution::Error::Join(..) => Self::Other,
+        }
+    }
+}
+
+impl From<&install_wheel_rs::Error> for ErrorKind {
+    fn from(err: &install_wheel_rs::Error) -> Self {
+        match err {
+            install_wheel_rs::Error::Io(..)
+            | install_wheel_rs::Error::Reflink { .. }
+            | install_wheel_rs::Error::WalkDir(..)
+            | install_wheel_rs::Error::MissingRecord(..)
+            | install_wheel_rs::Error::MissingTopLevel(..) => Self::Filesystem,
+            install_wheel_rs::Error::RecordMismatch(..) => Self::Hash,
+            install_wheel_rs::Error::InvalidWheel(..)
+            | install_wheel_rs::Error::InvalidWheelFileName(..)
+            | install_wheel_rs::Error::Zip(..)
+            | install_wheel_rs::Error::RecordFile(..)
+            | install_wheel_rs::Error::RecordCsv(..)
+            | install_wheel_rs::Error::DirectUrlJson(..)
+            | install_wheel_rs::Error::MissingDistInfo
+            | install_wheel_rs::Error::MultipleDistInfo(..)
+            | install_wheel_rs::Error::MissingDistInfoSegments(..)
+            | install_wheel_rs::Error::MissingDistInfoPackageName(..)
+            | install_wheel_rs::Error::MissingDistInfoVersion(..)
+            | install_wheel_rs::Error::InvalidDistInfoPrefix
+            | install_wheel_rs::Error::InvalidSize
+            | install_wheel_rs::Error::InvalidName(..)
+            | install_wheel_rs::Error::InvalidVersion(..)
+            | install_wheel_rs::Error::MismatchedName(..)
+            | install_wheel_rs::Error::MismatchedVersion(..)
+            | install_wheel_rs::Error::CaseCollision(..) => Self::Build,
+            install_wheel_rs::Error::IncompatibleWheel { .. }
+            | install_wheel_rs::Error::PythonSubcommand(..)
+            | install_wheel_rs::Error::BrokenVenv(..)
+            | install_wheel_rs::Error::UnsupportedWindowsArch(..)
+            | install_wheel_rs::Error::NotWindows
+            | install_wheel_rs::Error::PlatformInfo(..)
+            | install_wheel_rs::Error::Pep440
+            | install_wheel_rs::Error::InvalidEggLink(..)
+            | install_wheel_rs::Error::InvalidPattern(..) => Self::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{InstallError, PrepareError};
+
+    use super::ErrorKind;
+
+    #[test]
+    fn distribution_error_kind() {
+        let err = uv_distribution::Error::hash_mismatch("foo==1.0.0".to_string(), &[], &[]);
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Hash);
+
+        let err = uv_distribution::Error::CacheWrite(std::io::Error::other("disk full"));
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Filesystem);
+
+        let err = uv_distribution::Error::MissingPyprojectToml;
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Build);
+
+        let err = uv_distribution::Error::NoBinary;
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Other);
+    }
+
+    #[test]
+    fn record_error_kind() {
+        // A file that doesn't match its `RECORD` hash is a hash failure, even when it's
+        // reported by the distribution database.
+        let err = install_wheel_rs::Error::RecordMismatch("`foo.py` has hash ...".to_string());
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Hash);
+        let err = uv_distribution::Error::Record(err);
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Hash);
+
+        // A malformed `RECORD` is a broken wheel.
+        let err = install_wheel_rs::Error::RecordFile("missing entry".to_string());
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Build);
+
+        let err = install_wheel_rs::Error::MissingRecord(PathBuf::from("foo-1.0.dist-info"));
+        assert_eq!(ErrorKind::from(&err), ErrorKind::Filesystem);
+    }
+
+    #[test]
+    fn prepare_error_kind() {
+        let err = PrepareError::Editable(uv_distribution::Error::hash_mismatch(
+            "foo==1.0.0".to_string(),
+            &[],
+            &[],
+        ));
+        assert_eq!(err.kind(), ErrorKind::Hash);
+
+        let err = PrepareError::CacheWrite(std::io::Error::other("disk full"));
+        assert_eq!(err.kind(), ErrorKind::Filesystem);
+
+        let err = PrepareError::Thread("failed".to_string());
+        assert_eq!(err.kind(), ErrorKind::Other);
+
+        assert_eq!(PrepareError::Cancelled.kind(), ErrorKind::Cancelled);
+    }
+
+    #[test]
+    fn install_error_kind() {
+        assert_eq!(InstallError::Cancelled.kind(), ErrorKind::Cancelled);
+    }
+}
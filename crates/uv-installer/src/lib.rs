@@ -1,16 +1,42 @@
-pub use compile::{compile_tree, CompileError};
-pub use downloader::{Downloader, Reporter as DownloadReporter};
-pub use editable::{is_dynamic, BuiltEditable, InstalledEditable, ResolvedEditable};
-pub use installer::{Installer, Reporter as InstallReporter};
-pub use plan::{Plan, Planner};
+pub use collisions::{find_file_collisions, FileCollision};
+pub use compile::{compile_tree, CompileError, CompileSummary};
+pub use editable::{
+    is_dynamic, BuiltEditable, DynamicMetadata, InstalledEditable, ResolvedEditable,
+};
+pub use hooks::{CommandHook, Hook, HookDist, HookError, HookEvent, HookPlan, InstallHooks};
+pub use import_check::{check_imports, top_level_modules, ImportCheckError, ImportFailure};
+#[allow(deprecated)]
+pub use installer::InstallCancelled;
+pub use installer::{Error as InstallError, Installer, Reporter as InstallReporter};
+pub use kind::ErrorKind;
+pub use pipeline::{Event, Pipeline, PipelineBuilder};
+pub use plan::{Plan, Planner, ReinstallReason};
+#[allow(deprecated)]
+pub use preparer::{DownloadError, Downloader};
+pub use preparer::{Error as PrepareError, Preparer, Reporter as PrepareReporter};
+pub use scripts::{find_script_conflicts, ScriptConflict};
 pub use site_packages::{SatisfiesResult, SitePackages, SitePackagesDiagnostic};
+pub use size::installed_size;
+pub use space::{InsufficientSpace, SpaceEstimate};
+pub use symlinks::{check_target, SymlinkedTarget};
+pub use transaction::{Transaction, TransactionError};
 pub use uninstall::{uninstall, UninstallError};
 
+mod collisions;
 mod compile;
-mod downloader;
 mod editable;
+mod hooks;
+mod import_check;
 mod installer;
+mod kind;
+mod pipeline;
 mod plan;
+mod preparer;
 mod satisfies;
+mod scripts;
 mod site_packages;
+mod size;
+mod space;
+mod symlinks;
+mod transaction;
 mod uninstall;
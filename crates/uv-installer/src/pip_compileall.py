@@ -6,14 +6,87 @@ pip silently just swallows all pyc compilation errors, but `python -m compileall
 not have such a flag, so we adapt the pip code. This is relevant, e.g., for
 `debugpy-1.5.1-cp38-cp38-manylinux_2_5_x86_64.manylinux1_x86_64.manylinux_2_12_x86_64.manylinux2010_x86_64`,
 which contains some vendored Python 2 code which fails to compile.
+
+If `UV_BYTECODE_CACHE` is set, compiled files are stored in (and reused from) a cache directory
+shared across environments, keyed by the interpreter's magic number and a hash of the source file.
+Reusing bytecode compiled for a different path is safe, as the import system replaces the
+embedded source path (`co_filename`) on load.
 """
 
 import compileall
+import hashlib
+import importlib.util
 import os
 import py_compile
+import shutil
 import sys
 import warnings
 
+
+def link(src, dst):
+    """Atomically hardlink (or, failing that, copy) `src` to `dst`."""
+    os.makedirs(os.path.dirname(dst), exist_ok=True)
+    tmp = f"{dst}.{os.getpid()}.tmp"
+    try:
+        try:
+            os.link(src, tmp)
+        except OSError:
+            shutil.copyfile(src, tmp)
+        os.replace(tmp, dst)
+        return True
+    except OSError:
+        try:
+            os.unlink(tmp)
+        except OSError:
+            pass
+        return False
+
+
+def cache_key(path, invalidation_mode, level):
+    """Compute the key under which the bytecode for `path` is cached."""
+    with open(path, "rb") as source:
+        key = hashlib.sha256(source.read())
+    # Timestamp-based bytecode embeds the modification time and size of the source file, so
+    # they're part of the key.
+    if invalidation_mode in (None, py_compile.PycInvalidationMode.TIMESTAMP):
+        stat = os.stat(path)
+        key.update(f":{int(stat.st_mtime)}:{stat.st_size}".encode())
+    mode = invalidation_mode.name if invalidation_mode is not None else None
+    key.update(f":{mode}:{level}".encode())
+    return key.hexdigest()
+
+
+def compile_file(path, invalidation_mode, force, level, bytecode_cache):
+    """Compile `path` for the given optimization level, reusing cached bytecode if possible."""
+    if bytecode_cache is not None:
+        try:
+            target = importlib.util.cache_from_source(
+                path, optimization=None if level < 0 else (level or "")
+            )
+            cached = os.path.join(
+                bytecode_cache, cache_key(path, invalidation_mode, level) + ".pyc"
+            )
+        except (OSError, ValueError, NotImplementedError):
+            target = cached = None
+        if cached is not None and os.path.isfile(cached) and link(cached, target):
+            return True
+
+    # Unlike pip, we set quiet=2, so we don't have to capture stdout. Instead, we report whether
+    # compilation succeeded, and leave it to uv to surface the failures.
+    success = compileall.compile_file(
+        path,
+        invalidation_mode=invalidation_mode,
+        force=force,
+        quiet=2,
+        optimize=level,
+    )
+
+    if success and bytecode_cache is not None and cached is not None:
+        if os.path.isfile(target):
+            link(target, cached)
+    return success
+
+
 with warnings.catch_warnings():
     warnings.filterwarnings("ignore")
 
@@ -42,6 +115,24 @@ with warnings.catch_warnings():
         except AttributeError:
             invalidation_mode = None  # guard against implementation details
 
+    # The optimization levels to compile for, e.g., `1,2` for `-O` and `-OO`. By default, we
+    # compile for the interpreter's optimization level.
+    optimization_levels = os.environ.get("UV_COMPILE_OPTIMIZATION_LEVELS")
+    if optimization_levels:
+        optimization_levels = [int(level) for level in optimization_levels.split(",")]
+    else:
+        optimization_levels = [-1]
+
+    # The bytecode cache is partitioned by the interpreter's magic number, which changes whenever
+    # the bytecode format does.
+    bytecode_cache = os.environ.get("UV_BYTECODE_CACHE")
+    if bytecode_cache:
+        bytecode_cache = os.path.join(
+            bytecode_cache, importlib.util.MAGIC_NUMBER.hex()
+        )
+    else:
+        bytecode_cache = None
+
     # Unlike pip, we will usually set force=False. It's unclear why pip sets force=True, but it
     # doesn't matter much for them, as pip only compiles newly installed files.
     force = False
@@ -57,11 +148,11 @@ with warnings.catch_warnings():
         path = path.strip()
         if not path:
             continue
-        # Unlike pip, we set quiet=2, so we don't have to capture stdout.
-        # We'd like to show those errors, but given that pip thinks that's totally fine,
-        # we can't really change that.
-        success = compileall.compile_file(
-            path, invalidation_mode=invalidation_mode, force=force, quiet=2
+        success = all(
+            [
+                compile_file(path, invalidation_mode, force, level, bytecode_cache)
+                for level in optimization_levels
+            ]
         )
         # We're ready for the next file.
-        print(path)
+        print(("ok " if success else "error ") + path)
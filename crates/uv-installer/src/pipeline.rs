@@ -0,0 +1,339 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures::Stream;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+use distribution_types::{BuildableSource, CachedDist, Dist, LocalEditable};
+use platform_tags::Tags;
+use uv_cache::Cache;
+use uv_distribution::DistributionDatabase;
+use uv_interpreter::PythonEnvironment;
+use uv_types::{BuildContext, HashStrategy, InFlight};
+
+use crate::{InstallError, InstallReporter, Installer, PrepareError, PrepareReporter, Preparer};
+
+/// A progress event emitted by a [`Pipeline`].
+///
+/// Unlike the [`PrepareReporter`] and [`InstallReporter`] callbacks, events are plain values, so
+/// they can be forwarded across tasks or threads and matched on by the consumer.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A source distribution build started.
+    BuildStarted { id: usize, source: String },
+    /// A source distribution build completed.
+    BuildCompleted { id: usize, source: String },
+    /// An editable build started.
+    EditableBuildStarted { id: usize, editable: LocalEditable },
+    /// An editable build completed.
+    EditableBuildCompleted { id: usize, editable: LocalEditable },
+    /// A repository checkout started.
+    CheckoutStarted { id: usize, url: Url, rev: String },
+    /// A repository checkout completed.
+    CheckoutCompleted { id: usize, url: Url, rev: String },
+    /// A distribution was downloaded, built if necessary, and unzipped into the cache.
+    Prepared(CachedDist),
+    /// All distributions were prepared.
+    PrepareComplete,
+    /// A wheel was installed into the environment.
+    Installed(CachedDist),
+    /// All wheels were installed.
+    InstallComplete,
+}
+
+/// Forwards reporter callbacks as [`Event`]s.
+#[derive(Clone)]
+struct EventReporter {
+    sender: UnboundedSender<Event>,
+    next_id: Arc<AtomicUsize>,
+}
+
+impl EventReporter {
+    fn new(sender: UnboundedSender<Event>) -> Self {
+        Self {
+            sender,
+            next_id: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn send(&self, event: Event) {
+        // If the receiver was dropped, the consumer is no longer interested in events.
+        let _ = self.sender.send(event);
+    }
+
+    fn next_id(&self) -> usize {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl PrepareReporter for EventReporter {
+    fn on_progress(&self, dist: &CachedDist) {
+        self.send(Event::Prepared(dist.clone()));
+    }
+
+    fn on_complete(&self) {
+        self.send(Event::PrepareComplete);
+    }
+
+    fn on_build_start(&self, source: &BuildableSource) -> usize {
+        let id = self.next_id();
+        self.send(Event::BuildStarted {
+            id,
+            source: source.to_string(),
+        });
+        id
+    }
+
+    fn on_build_complete(&self, source: &BuildableSource, id: usize) {
+        self.send(Event::BuildCompleted {
+            id,
+            source: source.to_string(),
+        });
+    }
+
+    fn on_editable_build_start(&self, dist: &LocalEditable) -> usize {
+        let id = self.next_id();
+        self.send(Event::EditableBuildStarted {
+            id,
+            editable: dist.clone(),
+        });
+        id
+    }
+
+    fn on_editable_build_complete(&self, dist: &LocalEditable, id: usize) {
+        self.send(Event::EditableBuildCompleted {
+            id,
+            editable: dist.clone(),
+        });
+    }
+
+    fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
+        let id = self.next_id();
+        self.send(Event::CheckoutStarted {
+            id,
+            url: url.clone(),
+            rev: rev.to_string(),
+        });
+        id
+    }
+
+    fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
+        self.send(Event::CheckoutCompleted {
+            id: index,
+            url: url.clone(),
+            rev: rev.to_string(),
+        });
+    }
+}
+
+impl InstallReporter for EventReporter {
+    fn on_install_progress(&self, wheel: &CachedDist) {
+        self.send(Event::Installed(wheel.clone()));
+    }
+
+    fn on_install_complete(&self) {
+        self.send(Event::InstallComplete);
+    }
+}
+
+/// A builder for a [`Pipeline`].
+pub struct PipelineBuilder<'a> {
+    venv: &'a PythonEnvironment,
+    cache: &'a Cache,
+    tags: &'a Tags,
+    hashes: &'a HashStrategy,
+    link_mode: install_wheel_rs::linker::LinkMode,
+    script_options: install_wheel_rs::ScriptOptions,
+    filter: install_wheel_rs::InstallFilter,
+    installer_name: Option<String>,
+    cancellation: CancellationToken,
+    events: Option<UnboundedSender<Event>>,
+}
+
+impl<'a> PipelineBuilder<'a> {
+    /// Start building a [`Pipeline`] that installs into the given environment.
+    pub fn new(
+        venv: &'a PythonEnvironment,
+        cache: &'a Cache,
+        tags: &'a Tags,
+        hashes: &'a HashStrategy,
+    ) -> Self {
+        Self {
+            venv,
+            cache,
+            tags,
+            hashes,
+            link_mode: install_wheel_rs::linker::LinkMode::default(),
+            script_options: install_wheel_rs::ScriptOptions::default(),
+            filter: install_wheel_rs::InstallFilter::default(),
+            installer_name: Some("uv".to_string()),
+            cancellation: CancellationToken::new(),
+            events: None,
+        }
+    }
+
+    /// Set the [`LinkMode`][`install_wheel_rs::linker::LinkMode`] to use when installing.
+    #[must_use]
+    pub fn link_mode(self, link_mode: install_wheel_rs::linker::LinkMode) -> Self {
+        Self { link_mode, ..self }
+    }
+
+    /// Set the [`ScriptOptions`][`install_wheel_rs::ScriptOptions`] to use when generating
+    /// entrypoint scripts.
+    #[must_use]
+    pub fn script_options(self, script_options: install_wheel_rs::ScriptOptions) -> Self {
+        Self {
+            script_options,
+            ..self
+        }
+    }
+
+    /// Set the [`InstallFilter`][`install_wheel_rs::InstallFilter`] for files to omit from each
+    /// wheel.
+    #[must_use]
+    pub fn filter(self, filter: install_wheel_rs::InstallFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    /// Set the `installer_name` to something other than `"uv"`.
+    #[must_use]
+    pub fn installer_name(self, installer_name: Option<String>) -> Self {
+        Self {
+            installer_name,
+            ..self
+        }
+    }
+
+    /// Set the [`CancellationToken`] with which to abort both phases.
+    #[must_use]
+    pub fn cancellation(self, cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation,
+            ..self
+        }
+    }
+
+    /// Send an [`Event`] to the given channel as each phase makes progress.
+    #[must_use]
+    pub fn events(self, events: UnboundedSender<Event>) -> Self {
+        Self {
+            events: Some(events),
+            ..self
+        }
+    }
+
+    /// Build the [`Pipeline`], fetching distributions from the given database.
+    pub fn build<Context: BuildContext>(
+        self,
+        database: DistributionDatabase<'a, Context>,
+    ) -> Pipeline<'a, Context> {
+        let reporter = self.events.map(EventReporter::new);
+
+        let mut preparer = Preparer::new(self.cache, self.tags, self.hashes, database)
+            .with_cancellation(self.cancellation.clone());
+        if let Some(reporter) = reporter.clone() {
+            preparer = preparer.with_reporter(reporter);
+        }
+
+        Pipeline {
+            venv: self.venv,
+            preparer,
+            link_mode: self.link_mode,
+            script_options: self.script_options,
+            filter: self.filter,
+            installer_name: self.installer_name,
+            cancellation: self.cancellation,
+            reporter,
+        }
+    }
+}
+
+/// Prepare distributions and install them into an environment, as two separate phases.
+///
+/// Constructed via [`PipelineBuilder`].
+///
+/// Each phase is a future of its own, so the caller decides when (and whether) to install what
+/// was prepared, e.g., to prepare for several environments up front, or to stage the installation
+/// in a [`crate::Transaction`]. Progress is reported as typed [`Event`]s, and failures as
+/// [`PrepareError`] or [`InstallError`], which can be classified via their `kind()`.
+pub struct Pipeline<'a, Context: BuildContext> {
+    venv: &'a PythonEnvironment,
+    preparer: Preparer<'a, Context>,
+    link_mode: install_wheel_rs::linker::LinkMode,
+    script_options: install_wheel_rs::ScriptOptions,
+    filter: install_wheel_rs::InstallFilter,
+    installer_name: Option<String>,
+    cancellation: CancellationToken,
+    reporter: Option<EventReporter>,
+}
+
+impl<'a, Context: BuildContext> Pipeline<'a, Context> {
+    /// The preparation phase, yielding each distribution as soon as it's prepared.
+    pub fn prepare_stream<'stream>(
+        &'stream self,
+        distributions: Vec<Dist>,
+        in_flight: &'stream InFlight,
+    ) -> impl Stream<Item = Result<CachedDist, PrepareError>> + 'stream {
+        self.preparer.prepare_stream(distributions, in_flight)
+    }
+
+    /// The preparation phase: download, build, and unzip the distributions into the cache.
+    pub async fn prepare(
+        &self,
+        distributions: Vec<Dist>,
+        in_flight: &InFlight,
+    ) -> Result<Vec<CachedDist>, PrepareError> {
+        self.preparer.prepare(distributions, in_flight).await
+    }
+
+    /// The installation phase: install prepared (or already cached) wheels into the environment.
+    pub async fn install(&self, wheels: &[CachedDist]) -> Result<(), InstallError> {
+        let mut installer = Installer::new(self.venv)
+            .with_link_mode(self.link_mode)
+            .with_script_options(self.script_options)
+            .with_filter(self.filter.clone())
+            .with_installer_name(self.installer_name.clone())
+            .with_cancellation(self.cancellation.clone());
+        if let Some(reporter) = self.reporter.clone() {
+            installer = installer.with_reporter(reporter);
+        }
+        installer.install(wheels).await?;
+
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.on_install_complete();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::PrepareReporter;
+
+    use super::{Event, EventReporter};
+
+    #[test]
+    fn checkout_events() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let reporter = EventReporter::new(sender);
+
+        let url = Url::parse("https://github.com/astral-sh/uv").unwrap();
+        let id = reporter.on_checkout_start(&url, "main");
+        reporter.on_checkout_complete(&url, "main", id);
+        assert_ne!(reporter.on_checkout_start(&url, "main"), id);
+
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::CheckoutStarted { id: started, .. }) if started == id
+        ));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Event::CheckoutCompleted { id: completed, rev, .. }) if completed == id && rev == "main"
+        ));
+    }
+}
@@ -1,29 +1,33 @@
 use std::collections::hash_map::Entry;
+use std::fmt::{Display, Formatter};
 use std::hash::BuildHasherDefault;
 use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::{bail, Result};
 use rustc_hash::FxHashMap;
+use serde::Serialize;
 use tracing::{debug, warn};
 
 use distribution_filename::WheelFilename;
 use distribution_types::{
-    CachedDirectUrlDist, CachedDist, DirectUrlBuiltDist, DirectUrlSourceDist, DirectorySourceDist,
-    Error, GitSourceDist, Hashed, IndexLocations, InstalledDist, InstalledMetadata,
-    InstalledVersion, Name, PathBuiltDist, PathSourceDist, RemoteSource, Requirement,
-    RequirementSource, Verbatim,
+    BuiltDist, CachedDirectUrlDist, CachedDist, DirectUrlBuiltDist, DirectUrlSourceDist,
+    DirectorySourceDist, Dist, Error, GitSourceDist, Hashed, IndexLocations, InstalledDist,
+    InstalledMetadata, InstalledVersion, Name, PathBuiltDist, PathSourceDist, RemoteSource,
+    Requirement, RequirementSource, Resolution, Verbatim,
 };
 use platform_tags::Tags;
 use uv_cache::{ArchiveTimestamp, Cache, CacheBucket, WheelCache};
-use uv_configuration::{NoBinary, Reinstall};
+use uv_configuration::{BuildCommands, NoBinary, NoBuild, PreferSource, Reinstall};
 use uv_distribution::{
     BuiltWheelIndex, HttpArchivePointer, LocalArchivePointer, RegistryWheelIndex,
 };
 use uv_fs::Simplified;
 use uv_git::GitUrl;
 use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
 use uv_types::HashStrategy;
+use uv_warnings::warn_user;
 
 use crate::satisfies::RequirementSatisfaction;
 use crate::{ResolvedEditable, SitePackages};
@@ -33,6 +37,10 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    ignore_platform_compatibility: bool,
+    build_commands: Option<&'a BuildCommands>,
+    prefer_source: Option<&'a PreferSource>,
+    resolution: Option<&'a Resolution>,
 }
 
 impl<'a> Planner<'a> {
@@ -42,6 +50,10 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            ignore_platform_compatibility: false,
+            build_commands: None,
+            prefer_source: None,
+            resolution: None,
         }
     }
 
@@ -54,6 +66,46 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Allow wheel files (from a URL or path) whose tags are incompatible with the current
+    /// platform to be installed, warning rather than failing.
+    #[must_use]
+    pub fn with_ignore_platform_compatibility(self, ignore_platform_compatibility: bool) -> Self {
+        Self {
+            ignore_platform_compatibility,
+            ..self
+        }
+    }
+
+    /// Set the external build commands, such that packages built with an external command are
+    /// never linked from wheels built by the built-in build frontend.
+    #[must_use]
+    pub fn with_build_commands(self, build_commands: &'a BuildCommands) -> Self {
+        Self {
+            build_commands: Some(build_commands),
+            ..self
+        }
+    }
+
+    /// Set the source distribution preference, such that packages that prefer source
+    /// distributions are never linked from wheels downloaded from a registry.
+    #[must_use]
+    pub fn with_prefer_source(self, prefer_source: &'a PreferSource) -> Self {
+        Self {
+            prefer_source: Some(prefer_source),
+            ..self
+        }
+    }
+
+    /// Set the [`Resolution`] from which the requirements were derived, such that cached registry
+    /// wheels are checked against the digests reported by the index.
+    #[must_use]
+    pub fn with_resolution(self, resolution: &'a Resolution) -> Self {
+        Self {
+            resolution: Some(resolution),
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -63,14 +115,18 @@ impl<'a> Planner<'a> {
     /// read those entries from the cache after revalidating them.
     ///
     /// The install plan will also respect the required hashes, such that it will never return a
-    /// cached distribution that does not match the required hash. Like pip, though, it _will_
-    /// return an _installed_ distribution that does not match the required hash.
+    /// cached distribution that does not match the required hash. If an installed distribution
+    /// records the hash of the archive from which it was installed (as uv does in
+    /// `direct_url.json`, or in `uv_archive_info.json` for registry distributions), and that hash
+    /// doesn't match the required hash, the distribution will be reinstalled. Like pip, though, it _will_ return an _installed_ distribution for which no
+    /// such hash was recorded.
     #[allow(clippy::too_many_arguments)]
     pub fn build(
         self,
         mut site_packages: SitePackages,
         reinstall: &Reinstall,
         no_binary: &NoBinary,
+        no_build: &NoBuild,
         hasher: &HashStrategy,
         index_locations: &IndexLocations,
         cache: &Cache,
@@ -84,6 +140,8 @@ impl<'a> Planner<'a> {
         let mut cached = vec![];
         let mut remote = vec![];
         let mut reinstalls = vec![];
+        let mut hash_mismatches = vec![];
+        let mut reinstall_reasons = FxHashMap::default();
         let mut extraneous = vec![];
         let mut seen = FxHashMap::with_capacity_and_hasher(
             self.requirements.len(),
@@ -135,6 +193,8 @@ impl<'a> Planner<'a> {
                     let existing = site_packages.remove_packages(built.name());
                     reinstalls.extend(existing);
 
+                    reinstall_reasons.insert(built.name().clone(), ReinstallReason::Editable);
+
                     cached.push(built.wheel.clone());
                 }
             }
@@ -172,28 +232,51 @@ impl<'a> Planner<'a> {
             };
 
             // Check if installation of a binary version of the package should be allowed.
-            let no_binary = match no_binary {
-                NoBinary::None => false,
-                NoBinary::All => true,
-                NoBinary::Packages(packages) => packages.contains(&requirement.name),
-            };
+            let no_binary = no_binary.no_binary_package(&requirement.name, no_build);
 
             if reinstall {
                 let installed_dists = site_packages.remove_packages(&requirement.name);
+                if !installed_dists.is_empty() {
+                    reinstall_reasons.insert(requirement.name.clone(), ReinstallReason::Requested);
+                }
                 reinstalls.extend(installed_dists);
             } else {
                 let installed_dists = site_packages.remove_packages(&requirement.name);
                 match installed_dists.as_slice() {
                     [] => {}
                     [distribution] => {
-                        match RequirementSatisfaction::check(distribution, &requirement.source)? {
-                            RequirementSatisfaction::Mismatch => {}
+                        match RequirementSatisfaction::check(
+                            distribution,
+                            &requirement.source,
+                            hasher.get_requirement(requirement).digests(),
+                        )? {
+                            RequirementSatisfaction::Mismatch => {
+                                let reason = if matches!(
+                                    requirement.source,
+                                    RequirementSource::Registry { .. }
+                                ) {
+                                    ReinstallReason::VersionMismatch
+                                } else {
+                                    ReinstallReason::SourceMismatch
+                                };
+                                reinstall_reasons.insert(requirement.name.clone(), reason);
+                            }
                             RequirementSatisfaction::Satisfied => {
                                 debug!("Requirement already installed: {distribution}");
                                 continue;
                             }
                             RequirementSatisfaction::OutOfDate => {
                                 debug!("Requirement installed, but not fresh: {distribution}");
+                                reinstall_reasons
+                                    .insert(requirement.name.clone(), ReinstallReason::OutOfDate);
+                            }
+                            RequirementSatisfaction::HashMismatch => {
+                                debug!("Requirement installed, but hash mismatch: {distribution}");
+                                hash_mismatches.push(distribution.clone());
+                                reinstall_reasons.insert(
+                                    requirement.name.clone(),
+                                    ReinstallReason::HashMismatch,
+                                );
                             }
                         }
                         reinstalls.push(distribution.clone());
@@ -201,7 +284,11 @@ impl<'a> Planner<'a> {
                     // We reinstall installed distributions with multiple versions because
                     // we do not want to keep multiple incompatible versions but removing
                     // one version is likely to break another.
-                    _ => reinstalls.extend(installed_dists),
+                    _ => {
+                        reinstall_reasons
+                            .insert(requirement.name.clone(), ReinstallReason::MultipleVersions);
+                        reinstalls.extend(installed_dists);
+                    }
                 }
             }
 
@@ -211,12 +298,57 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            // Wheels built with an external command are cached separately from those in the
+            // indexes below, so defer to the distribution database.
+            if self
+                .build_commands
+                .is_some_and(|build_commands| build_commands.get(&requirement.name).is_some())
+            {
+                debug!(
+                    "Requirement is built with an external command: {}",
+                    requirement.name
+                );
+                remote.push(requirement.clone());
+                continue;
+            }
+
             // Identify any cached distributions that satisfy the requirement.
             match &requirement.source {
+                RequirementSource::Registry { .. }
+                    if self.prefer_source.is_some_and(|prefer_source| {
+                        prefer_source.prefer_source_package(&requirement.name)
+                    }) =>
+                {
+                    // The registry index doesn't distinguish between downloaded and built
+                    // wheels, so defer to the distribution database, which will reuse a
+                    // previously-built wheel for the source distribution selected by the
+                    // resolver.
+                    debug!(
+                        "Requirement prefers a source distribution: {}",
+                        requirement.name
+                    );
+                }
                 RequirementSource::Registry { specifier, .. } => {
+                    // Skip a cached wheel whose digests contradict those the index reports for
+                    // the same file (e.g., if the index re-uploaded a file with the same filename
+                    // but different contents), such that the distribution database refreshes it.
+                    let index_wheel = self.resolution.and_then(|resolution| {
+                        match resolution.get_remote(&requirement.name) {
+                            Some(Dist::Built(BuiltDist::Registry(wheels))) => {
+                                Some(wheels.best_wheel())
+                            }
+                            _ => None,
+                        }
+                    });
                     if let Some((_version, distribution)) = registry_index
                         .get(&requirement.name)
                         .find(|(version, _)| specifier.contains(version))
+                        .filter(|(_, distribution)| {
+                            !index_wheel.is_some_and(|wheel| {
+                                wheel.filename == distribution.filename
+                                    && distribution.conflicts_with(&wheel.file.hashes)
+                            })
+                        })
                     {
                         debug!("Requirement already cached: {distribution}");
                         cached.push(CachedDist::Registry(distribution.clone()));
@@ -251,9 +383,15 @@ impl<'a> Planner<'a> {
                         };
 
                         if !wheel.filename.is_compatible(tags) {
-                            bail!(
-                                "A URL dependency is incompatible with the current platform: {}",
-                                wheel.url
+                            if !self.ignore_platform_compatibility {
+                                bail!(
+                                    "A URL dependency is incompatible with the current platform: {}",
+                                    wheel.url
+                                );
+                            }
+                            warn_user!(
+                                "Installing `{}` despite it being incompatible with the current platform (`--ignore-platform-compatibility`); the package may fail to import or behave unexpectedly",
+                                wheel.filename
                             );
                         }
 
@@ -385,9 +523,15 @@ impl<'a> Planner<'a> {
                         };
 
                         if !wheel.filename.is_compatible(tags) {
-                            bail!(
-                                "A path dependency is incompatible with the current platform: {}",
-                                wheel.path.user_display()
+                            if !self.ignore_platform_compatibility {
+                                bail!(
+                                    "A path dependency is incompatible with the current platform: {}",
+                                    wheel.path.user_display()
+                                );
+                            }
+                            warn_user!(
+                                "Installing `{}` despite it being incompatible with the current platform (`--ignore-platform-compatibility`); the package may fail to import or behave unexpectedly",
+                                wheel.filename
                             );
                         }
 
@@ -473,6 +617,8 @@ impl<'a> Planner<'a> {
             cached,
             remote,
             reinstalls,
+            hash_mismatches,
+            reinstall_reasons,
             extraneous,
         })
     }
@@ -500,7 +646,52 @@ pub struct Plan {
     /// re-installed (including upgraded) to satisfy the requirements.
     pub reinstalls: Vec<InstalledDist>,
 
+    /// Any distributions that are already installed in the current environment, and match the
+    /// requirements, but were installed from an archive whose hash doesn't match the required
+    /// hashes. These distributions are also included in `reinstalls`.
+    pub hash_mismatches: Vec<InstalledDist>,
+
+    /// The reason that each package in `reinstalls` will be re-installed, by package name.
+    pub reinstall_reasons: FxHashMap<PackageName, ReinstallReason>,
+
     /// Any distributions that are already installed in the current environment, and are
     /// _not_ necessary to satisfy the requirements.
     pub extraneous: Vec<InstalledDist>,
 }
+
+/// The reason that an installed distribution is re-installed as part of a [`Plan`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReinstallReason {
+    /// The package was marked for re-installation (e.g., via `--reinstall`).
+    Requested,
+    /// The installed version doesn't satisfy the requirement.
+    VersionMismatch,
+    /// The installed distribution doesn't match the requirement's source (e.g., it was installed
+    /// from a different direct URL, or from a registry rather than a URL).
+    SourceMismatch,
+    /// The installed distribution matches the requirement, but the source has since changed
+    /// (e.g., a local archive was modified, or a Git reference now points to a different commit).
+    OutOfDate,
+    /// The installed distribution was installed from an archive whose hash doesn't match the
+    /// required hashes.
+    HashMismatch,
+    /// Multiple versions of the package are installed.
+    MultipleVersions,
+    /// The package is an editable that was rebuilt.
+    Editable,
+}
+
+impl Display for ReinstallReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Requested => write!(f, "re-installation was requested"),
+            Self::VersionMismatch => write!(f, "the installed version doesn't match"),
+            Self::SourceMismatch => write!(f, "the installed source doesn't match"),
+            Self::OutOfDate => write!(f, "the source has changed since installation"),
+            Self::HashMismatch => write!(f, "the installed archive doesn't match the hashes"),
+            Self::MultipleVersions => write!(f, "multiple versions are installed"),
+            Self::Editable => write!(f, "the editable was rebuilt"),
+        }
+    }
+}
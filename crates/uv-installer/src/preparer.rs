@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use futures::{stream::FuturesUnordered, FutureExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
 use tokio::task::JoinError;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use url::Url;
 
@@ -17,6 +18,7 @@ use uv_distribution::{DistributionDatabase, LocalWheel};
 use uv_types::{BuildContext, HashStrategy, InFlight};
 
 use crate::editable::BuiltEditable;
+use crate::ErrorKind;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -33,18 +35,51 @@ pub enum Error {
     CacheWrite(#[source] std::io::Error),
     #[error("Unzip failed in another thread: {0}")]
     Thread(String),
+    #[error("The download was cancelled")]
+    Cancelled,
 }
 
-/// Download, build, and unzip a set of distributions.
-pub struct Downloader<'a, Context: BuildContext> {
+impl Error {
+    /// Returns the [`ErrorKind`] of this error, e.g., to distinguish network failures from hash
+    /// mismatches.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Unzip(..) | Self::CacheWrite(..) => ErrorKind::Filesystem,
+            Self::Fetch(_, err) | Self::Editable(err) => ErrorKind::from(err),
+            Self::Join(..) | Self::Thread(..) => ErrorKind::Other,
+            Self::Cancelled => ErrorKind::Cancelled,
+        }
+    }
+
+    /// Returns the distribution that failed to be prepared, if known.
+    pub fn dist(&self) -> Option<&Dist> {
+        match self {
+            Self::Unzip(dist, _) | Self::Fetch(dist, _) => Some(dist),
+            _ => None,
+        }
+    }
+}
+
+/// The former name of [`Preparer`].
+#[deprecated(note = "use `Preparer` instead")]
+pub type Downloader<'a, Context> = Preparer<'a, Context>;
+
+/// The former name of [`Error`].
+#[deprecated(note = "use `PrepareError` instead")]
+pub type DownloadError = Error;
+
+/// Prepare a set of distributions for installation: download, build, and unzip them into the
+/// cache.
+pub struct Preparer<'a, Context: BuildContext> {
     tags: &'a Tags,
     cache: &'a Cache,
     hashes: &'a HashStrategy,
     database: DistributionDatabase<'a, Context>,
     reporter: Option<Arc<dyn Reporter>>,
+    cancellation: CancellationToken,
 }
 
-impl<'a, Context: BuildContext> Downloader<'a, Context> {
+impl<'a, Context: BuildContext> Preparer<'a, Context> {
     pub fn new(
         cache: &'a Cache,
         tags: &'a Tags,
@@ -57,10 +92,11 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
             hashes,
             database,
             reporter: None,
+            cancellation: CancellationToken::new(),
         }
     }
 
-    /// Set the [`Reporter`] to use for this downloader.
+    /// Set the [`Reporter`] to use for this preparer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
         let reporter: Arc<dyn Reporter> = Arc::new(reporter);
@@ -70,19 +106,44 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
             hashes: self.hashes,
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             reporter: Some(reporter.clone()),
+            cancellation: self.cancellation,
         }
     }
 
-    /// Fetch, build, and unzip the distributions in parallel.
-    pub fn download_stream<'stream>(
+    /// Set the [`CancellationToken`] with which to abort the preparation.
+    ///
+    /// Any in-flight downloads are dropped on cancellation, and yield [`Error::Cancelled`]. Since
+    /// each wheel is unzipped into a temporary directory and only then persisted to the cache, the
+    /// cache is left consistent.
+    #[must_use]
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        Self {
+            cancellation,
+            ..self
+        }
+    }
+
+    /// Fetch, build, and unzip the distributions in parallel, yielding each as it's prepared.
+    ///
+    /// The distributions are yielded in the order in which they complete. On cancellation, each
+    /// pending distribution yields [`Error::Cancelled`].
+    pub fn prepare_stream<'stream>(
         &'stream self,
-        distributions: Vec<Dist>,
+        mut distributions: Vec<Dist>,
         in_flight: &'stream InFlight,
     ) -> impl Stream<Item = Result<CachedDist, Error>> + 'stream {
+        // Sort the distributions by size, such that the largest are started first.
+        distributions
+            .sort_unstable_by_key(|distribution| Reverse(distribution.size().unwrap_or(u64::MAX)));
+
         distributions
             .into_iter()
             .map(|dist| async {
-                let wheel = self.get_wheel(dist, in_flight).boxed_local().await?;
+                let wheel = tokio::select! {
+                    biased;
+                    () = self.cancellation.cancelled() => return Err(Error::Cancelled),
+                    wheel = self.get_wheel(dist, in_flight).boxed_local() => wheel?,
+                };
                 if let Some(reporter) = self.reporter.as_ref() {
                     reporter.on_progress(&wheel);
                 }
@@ -91,20 +152,16 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
             .collect::<FuturesUnordered<_>>()
     }
 
-    /// Download, build, and unzip a set of downloaded wheels.
+    /// Download, build, and unzip a set of distributions.
     #[instrument(skip_all, fields(total = distributions.len()))]
-    pub async fn download(
+    pub async fn prepare(
         &self,
-        mut distributions: Vec<Dist>,
+        distributions: Vec<Dist>,
         in_flight: &InFlight,
     ) -> Result<Vec<CachedDist>, Error> {
-        // Sort the distributions by size.
-        distributions
-            .sort_unstable_by_key(|distribution| Reverse(distribution.size().unwrap_or(u64::MAX)));
-
         let wheels = self
-            .download_stream(distributions, in_flight)
-            .try_collect()
+            .prepare_stream(distributions, in_flight)
+            .try_collect::<Vec<_>>()
             .await?;
 
         if let Some(reporter) = self.reporter.as_ref() {
@@ -114,6 +171,26 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
         Ok(wheels)
     }
 
+    /// The former name of [`Preparer::prepare_stream`].
+    #[deprecated(note = "use `Preparer::prepare_stream` instead")]
+    pub fn download_stream<'stream>(
+        &'stream self,
+        distributions: Vec<Dist>,
+        in_flight: &'stream InFlight,
+    ) -> impl Stream<Item = Result<CachedDist, Error>> + 'stream {
+        self.prepare_stream(distributions, in_flight)
+    }
+
+    /// The former name of [`Preparer::prepare`].
+    #[deprecated(note = "use `Preparer::prepare` instead")]
+    pub async fn download(
+        &self,
+        distributions: Vec<Dist>,
+        in_flight: &InFlight,
+    ) -> Result<Vec<CachedDist>, Error> {
+        self.prepare(distributions, in_flight).await
+    }
+
     /// Build a set of editables
     #[instrument(skip_all)]
     pub async fn build_editables(
@@ -130,7 +207,7 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
                     .reporter
                     .as_ref()
                     .map(|reporter| reporter.on_editable_build_start(&editable));
-                let (local_wheel, metadata) = self
+                let (local_wheel, metadata, cached) = self
                     .database
                     .build_wheel_editable(&editable, editable_wheel_dir)
                     .await
@@ -141,11 +218,11 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
                         reporter.on_editable_build_complete(&editable, task_id);
                     }
                 }
-                Ok::<_, Error>((editable, cached_dist, metadata))
+                Ok::<_, Error>((editable, cached_dist, metadata, cached))
             })
             .collect::<FuturesUnordered<_>>();
 
-        while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
+        while let Some((editable, wheel, metadata, cached)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
                 reporter.on_progress(&wheel);
             }
@@ -153,6 +230,7 @@ impl<'a, Context: BuildContext> Downloader<'a, Context> {
                 editable,
                 wheel,
                 metadata,
+                cached,
             });
         }
 
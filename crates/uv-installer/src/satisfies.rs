@@ -5,7 +5,7 @@ use tracing::log::debug;
 use tracing::trace;
 
 use distribution_types::{InstalledDirectUrlDist, InstalledDist, RequirementSource};
-use pypi_types::{DirInfo, DirectUrl, VcsInfo, VcsKind};
+use pypi_types::{DirInfo, DirectUrl, HashDigest, VcsInfo, VcsKind};
 use uv_cache::{ArchiveTarget, ArchiveTimestamp};
 
 #[derive(Debug, Copy, Clone)]
@@ -13,13 +13,42 @@ pub(crate) enum RequirementSatisfaction {
     Mismatch,
     Satisfied,
     OutOfDate,
+    /// The installed distribution matches the requirement, but was installed from an archive whose
+    /// recorded hash doesn't match any of the required hashes.
+    HashMismatch,
 }
 
 impl RequirementSatisfaction {
     /// Returns true if a requirement is satisfied by an installed distribution.
     ///
+    /// If any `hashes` are provided, and the installed distribution records the hashes of the
+    /// archive from which it was installed (in `direct_url.json` or `uv_archive_info.json`), at
+    /// least one of the recorded hashes must match.
+    ///
     /// Returns an error if IO fails during a freshness check for a local path.
-    pub(crate) fn check(distribution: &InstalledDist, source: &RequirementSource) -> Result<Self> {
+    pub(crate) fn check(
+        distribution: &InstalledDist,
+        source: &RequirementSource,
+        hashes: &[HashDigest],
+    ) -> Result<Self> {
+        match Self::check_source(distribution, source)? {
+            Self::Satisfied if !Self::hashes_match(distribution, hashes) => Ok(Self::HashMismatch),
+            satisfaction => Ok(satisfaction),
+        }
+    }
+
+    /// Returns `false` if the installed distribution records the hashes of its archive, and none
+    /// of them are among the required hashes.
+    fn hashes_match(distribution: &InstalledDist, hashes: &[HashDigest]) -> bool {
+        if hashes.is_empty() {
+            return true;
+        }
+        let recorded = distribution.hashes();
+        recorded.is_empty() || recorded.iter().any(|digest| hashes.contains(digest))
+    }
+
+    /// Returns true if a requirement's source is satisfied by an installed distribution.
+    fn check_source(distribution: &InstalledDist, source: &RequirementSource) -> Result<Self> {
         trace!(
             "Comparing installed with source: {:?} {:?}",
             distribution,
@@ -152,15 +181,19 @@ impl RequirementSatisfaction {
                 else {
                     return Ok(Self::Mismatch);
                 };
-                let DirectUrl::LocalDirectory {
-                    url: installed_url,
-                    dir_info:
-                        DirInfo {
-                            editable: installed_editable,
-                        },
-                } = direct_url.as_ref()
-                else {
-                    return Ok(Self::Mismatch);
+                // Local archives are recorded with `archive_info`, and local directories with
+                // `dir_info`.
+                let (installed_url, installed_editable) = match direct_url.as_ref() {
+                    DirectUrl::LocalDirectory {
+                        url,
+                        dir_info: DirInfo { editable },
+                    } => (url, *editable),
+                    DirectUrl::ArchiveUrl {
+                        url,
+                        subdirectory: None,
+                        ..
+                    } => (url, None),
+                    _ => return Ok(Self::Mismatch),
                 };
 
                 if *requested_editable != installed_editable.unwrap_or_default() {
@@ -183,7 +216,17 @@ impl RequirementSatisfaction {
                     return Ok(Self::Mismatch);
                 }
 
-                if !ArchiveTimestamp::up_to_date_with(path, ArchiveTarget::Install(distribution))? {
+                // Editables reflect any changes to the source tree, so only changes to the build
+                // entrypoints require a rebuild; otherwise, consider any change to the sources.
+                let up_to_date = if *requested_editable {
+                    ArchiveTimestamp::up_to_date_with(path, ArchiveTarget::Install(distribution))?
+                } else {
+                    ArchiveTimestamp::contents_up_to_date_with(
+                        path,
+                        ArchiveTarget::Install(distribution),
+                    )?
+                };
+                if !up_to_date {
                     trace!("Out of date");
                     return Ok(Self::OutOfDate);
                 }
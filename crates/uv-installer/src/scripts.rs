@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use tracing::debug;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use uv_normalize::PackageName;
+
+use crate::transaction::dist_info_name;
+
+/// A console or GUI script that's provided by more than one distribution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptConflict {
+    /// The name of the script.
+    pub script: String,
+    /// The distributions that provide the script, in sorted order.
+    pub packages: Vec<PackageName>,
+}
+
+impl Display for ScriptConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is provided by {}",
+            self.script,
+            self.packages
+                .iter()
+                .map(|package| format!("`{package}`"))
+                .collect::<Vec<_>>()
+                .join(" and ")
+        )
+    }
+}
+
+/// Identify any scripts that would be overwritten by installing the given distributions.
+///
+/// A conflict arises when two of the `installs` declare the same script, or when one of the
+/// `installs` declares a script that's already owned by a `retained` distribution (i.e., an
+/// installed distribution that won't be removed or replaced) of a different name.
+pub fn find_script_conflicts<'a>(
+    installs: impl IntoIterator<Item = &'a CachedDist>,
+    retained: impl IntoIterator<Item = &'a InstalledDist>,
+) -> Result<Vec<ScriptConflict>, install_wheel_rs::Error> {
+    let mut installed_owners: BTreeMap<String, Vec<PackageName>> = BTreeMap::new();
+    for dist in installs {
+        let Some(dist_info) = dist_info_name(dist.path()) else {
+            continue;
+        };
+        for script in install_wheel_rs::script_names(&dist.path().join(dist_info))? {
+            let owners = installed_owners.entry(script).or_default();
+            if !owners.contains(dist.name()) {
+                owners.push(dist.name().clone());
+            }
+        }
+    }
+
+    // Only scripts that are (re-)written by the installation can conflict.
+    if installed_owners.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    for dist in retained {
+        // Ignore any installed distributions whose entry points can't be read (e.g., a broken
+        // `.egg-info`); they'll be overwritten, as before.
+        let scripts = match install_wheel_rs::script_names(dist.path()) {
+            Ok(scripts) => scripts,
+            Err(err) => {
+                debug!("Failed to read entry points for {dist}: {err}");
+                continue;
+            }
+        };
+        for script in scripts {
+            if let Some(owners) = installed_owners.get_mut(&script) {
+                if !owners.contains(dist.name()) {
+                    owners.push(dist.name().clone());
+                }
+            }
+        }
+    }
+
+    Ok(installed_owners
+        .into_iter()
+        .filter(|(_, packages)| packages.len() > 1)
+        .map(|(script, mut packages)| {
+            packages.sort();
+            ScriptConflict { script, packages }
+        })
+        .collect())
+}
@@ -1,10 +1,12 @@
 use std::iter::Flatten;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::{collections::BTreeSet, hash::BuildHasherDefault};
 
 use anyhow::{Context, Result};
 use fs_err as fs;
 use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
 use url::Url;
 
 use distribution_types::{
@@ -12,6 +14,7 @@ use distribution_types::{
     UnresolvedRequirementSpecification,
 };
 use pep440_rs::{Version, VersionSpecifiers};
+use pypi_types::HashDigest;
 use requirements_txt::EditableRequirement;
 use uv_cache::{ArchiveTarget, ArchiveTimestamp};
 use uv_interpreter::PythonEnvironment;
@@ -23,13 +26,14 @@ use crate::satisfies::RequirementSatisfaction;
 
 /// An index over the packages installed in an environment.
 ///
-/// Packages are indexed by both name and (for editable installs) URL.
+/// Packages are indexed by both name and (for editable installs) URL. Editable installs are also
+/// indexed by the directories their `.pth` files add to `sys.path`.
 #[derive(Debug, Clone)]
 pub struct SitePackages {
     venv: PythonEnvironment,
-    /// The vector of all installed distributions. The `by_name` and `by_url` indices index into
-    /// this vector. The vector may contain `None` values, which represent distributions that were
-    /// removed from the virtual environment.
+    /// The vector of all installed distributions. The `by_name`, `by_url`, and `by_root` indices
+    /// index into this vector. The vector may contain `None` values, which represent distributions
+    /// that were removed from the virtual environment.
     distributions: Vec<Option<InstalledDist>>,
     /// The installed distributions, keyed by name. Although the Python runtime does not support it,
     /// it is possible to have multiple distributions with the same name to be present in the
@@ -37,6 +41,10 @@ pub struct SitePackages {
     by_name: FxHashMap<PackageName, Vec<usize>>,
     /// The installed editable distributions, keyed by URL.
     by_url: FxHashMap<Url, Vec<usize>>,
+    /// The installed editable distributions, keyed by the URLs of the roots they add to
+    /// `sys.path` (e.g., the `src` directory in a `src` layout), which may differ from the URL
+    /// they were installed from.
+    by_root: FxHashMap<Url, Vec<usize>>,
 }
 
 impl SitePackages {
@@ -45,6 +53,7 @@ impl SitePackages {
         let mut distributions: Vec<Option<InstalledDist>> = Vec::new();
         let mut by_name = FxHashMap::default();
         let mut by_url = FxHashMap::default();
+        let mut by_root = FxHashMap::default();
 
         for site_packages in venv.site_packages() {
             // Read the site-packages directory.
@@ -73,6 +82,7 @@ impl SitePackages {
                         distributions,
                         by_name,
                         by_url,
+                        by_root,
                     });
                 }
                 Err(err) => return Err(err).context("Failed to read site-packages directory"),
@@ -94,12 +104,38 @@ impl SitePackages {
                     .or_insert_with(Vec::new)
                     .push(idx);
 
-                // Index the distribution by URL.
+                // Index the distribution by URL (for legacy editables, the URL of the project
+                // root).
+                let url = match &dist_info {
+                    InstalledDist::Url(dist) => Some(&dist.url),
+                    InstalledDist::LegacyEditable(dist) => Some(&dist.url),
+                    InstalledDist::Registry(_) | InstalledDist::EggInfo(_) => None,
+                };
+                if let Some(url) = url {
+                    by_url.entry(url.clone()).or_insert_with(Vec::new).push(idx);
+                }
+
+                // Index editables by each of their roots, since a single project can add several
+                // directories to `sys.path` (e.g., multiple source directories, or namespace
+                // portions spread across directories).
                 if let InstalledDist::Url(dist) = &dist_info {
-                    by_url
-                        .entry(dist.url.clone())
-                        .or_insert_with(Vec::new)
-                        .push(idx);
+                    if dist.editable {
+                        // A broken installation (e.g., with a missing `RECORD`) is reported
+                        // elsewhere, so it's enough to skip it here.
+                        let roots = match install_wheel_rs::editable_roots(&dist.path) {
+                            Ok(roots) => roots,
+                            Err(err) => {
+                                debug!("Failed to read editable roots for {dist}: {err}");
+                                Vec::new()
+                            }
+                        };
+                        for root in roots {
+                            let Ok(root) = Url::from_file_path(&root) else {
+                                continue;
+                            };
+                            by_root.entry(root).or_insert_with(Vec::new).push(idx);
+                        }
+                    }
                 }
 
                 // Add the distribution to the database.
@@ -112,6 +148,7 @@ impl SitePackages {
             distributions,
             by_name,
             by_url,
+            by_root,
         })
     }
 
@@ -154,6 +191,9 @@ impl SitePackages {
     }
 
     /// Returns the editable distribution installed from the given URL, if any.
+    ///
+    /// Legacy editables (i.e., `.egg-link` files) are omitted, as they're never reused: they're
+    /// replaced by a standards-compliant editable install instead.
     pub fn get_editables(&self, url: &Url) -> Vec<&InstalledDist> {
         let Some(indexes) = self.by_url.get(url) else {
             return Vec::new();
@@ -161,7 +201,19 @@ impl SitePackages {
         indexes
             .iter()
             .flat_map(|&index| &self.distributions[index])
-            .filter(|dist| dist.is_editable())
+            .filter(|dist| matches!(dist, InstalledDist::Url(dist) if dist.editable))
+            .collect()
+    }
+
+    /// Returns the editable distributions that add the directory at the given URL to `sys.path`,
+    /// if any (e.g., the `src` directory of a project with a `src` layout).
+    pub fn get_editable_roots(&self, url: &Url) -> Vec<&InstalledDist> {
+        let Some(indexes) = self.by_root.get(url) else {
+            return Vec::new();
+        };
+        indexes
+            .iter()
+            .flat_map(|&index| &self.distributions[index])
             .collect()
     }
 
@@ -285,11 +337,15 @@ impl SitePackages {
     }
 
     /// Returns if the installed packages satisfy the given requirements.
+    ///
+    /// If `recursive` is `false`, only the given requirements are checked, and not their
+    /// dependencies (as when installing with `--no-deps`).
     pub fn satisfies(
         &self,
         requirements: &[UnresolvedRequirementSpecification],
         editables: &[EditableRequirement],
         constraints: &[Requirement],
+        recursive: bool,
     ) -> Result<SatisfiesResult> {
         let mut stack = Vec::with_capacity(requirements.len());
         let mut seen =
@@ -329,6 +385,10 @@ impl SitePackages {
                         return Ok(SatisfiesResult::Unsatisfied(requirement.to_string()));
                     }
 
+                    if !recursive {
+                        continue;
+                    }
+
                     // Recurse into the dependencies.
                     let metadata = distribution
                         .metadata()
@@ -371,20 +431,30 @@ impl SitePackages {
                     return Ok(SatisfiesResult::Unsatisfied(entry.requirement.to_string()));
                 }
                 [distribution] => {
+                    let hashes = entry
+                        .hashes
+                        .iter()
+                        .filter_map(|hash| HashDigest::from_str(hash).ok())
+                        .collect::<Vec<_>>();
                     match RequirementSatisfaction::check(
                         distribution,
                         entry.requirement.source()?.as_ref(),
+                        &hashes,
                     )? {
-                        RequirementSatisfaction::Mismatch | RequirementSatisfaction::OutOfDate => {
+                        RequirementSatisfaction::Mismatch
+                        | RequirementSatisfaction::OutOfDate
+                        | RequirementSatisfaction::HashMismatch => {
                             return Ok(SatisfiesResult::Unsatisfied(entry.requirement.to_string()))
                         }
                         RequirementSatisfaction::Satisfied => {}
                     }
                     // Validate that the installed version satisfies the constraints.
                     for constraint in constraints {
-                        match RequirementSatisfaction::check(distribution, &constraint.source)? {
+                        match RequirementSatisfaction::check(distribution, &constraint.source, &[])?
+                        {
                             RequirementSatisfaction::Mismatch
-                            | RequirementSatisfaction::OutOfDate => {
+                            | RequirementSatisfaction::OutOfDate
+                            | RequirementSatisfaction::HashMismatch => {
                                 return Ok(SatisfiesResult::Unsatisfied(
                                     entry.requirement.to_string(),
                                 ))
@@ -393,6 +463,10 @@ impl SitePackages {
                         }
                     }
 
+                    if !recursive {
+                        continue;
+                    }
+
                     // Recurse into the dependencies.
                     let metadata = distribution
                         .metadata()
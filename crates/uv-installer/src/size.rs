@@ -0,0 +1,73 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashSet;
+
+use distribution_types::InstalledDist;
+
+/// Return the on-disk size of an installed distribution, in bytes.
+///
+/// The size is the total size of the files listed in the distribution's `RECORD`, as they exist
+/// on disk, along with any bytecode compiled for its modules (which is rarely listed in the
+/// `RECORD`).
+///
+/// Returns `None` for distributions that don't record their files (i.e., `.egg-info`
+/// distributions and legacy editables), or that are missing a `RECORD`.
+pub fn installed_size(dist: &InstalledDist) -> Result<Option<u64>, install_wheel_rs::Error> {
+    let files = match dist {
+        InstalledDist::Registry(_) | InstalledDist::Url(_) => {
+            match install_wheel_rs::installed_files(dist.path()) {
+                Ok(files) => files,
+                Err(install_wheel_rs::Error::MissingRecord(_)) => return Ok(None),
+                Err(err) => return Err(err),
+            }
+        }
+        InstalledDist::EggInfo(_) | InstalledDist::LegacyEditable(_) => return Ok(None),
+    };
+
+    let mut seen = FxHashSet::default();
+    let mut size = 0;
+    for file in &files {
+        size += file_size(file, &mut seen)?;
+    }
+
+    // Include the bytecode for any modules, e.g., `__pycache__/foo.cpython-312.pyc` for `foo.py`.
+    for module in files
+        .iter()
+        .filter(|file| file.extension().is_some_and(|ext| ext == "py"))
+    {
+        let (Some(parent), Some(stem)) = (module.parent(), module.file_stem()) else {
+            continue;
+        };
+        let read_dir = match fs_err::read_dir(parent.join("__pycache__")) {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let prefix = format!("{}.", stem.to_string_lossy());
+        for entry in read_dir {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if file_name.starts_with(&prefix) && file_name.ends_with(".pyc") {
+                size += file_size(&entry.path(), &mut seen)?;
+            }
+        }
+    }
+
+    Ok(Some(size))
+}
+
+/// Return the size of a file (or symlink), counting each path once, and treating missing files
+/// as empty.
+fn file_size(path: &Path, seen: &mut FxHashSet<PathBuf>) -> io::Result<u64> {
+    if !seen.insert(path.to_path_buf()) {
+        return Ok(0);
+    }
+    match fs_err::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => Ok(0),
+        Ok(metadata) => Ok(metadata.len()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(err),
+    }
+}
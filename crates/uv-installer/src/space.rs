@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use distribution_types::{BuiltDist, CachedDist, Dist, SourceDist};
+use install_wheel_rs::linker::LinkMode;
+use uv_fs::{available_space, directory_size, is_same_filesystem};
+
+/// The assumed ratio of a wheel's unpacked size to its archive size.
+///
+/// Wheels are typically compressed by a factor of two to three; underestimating is preferable to
+/// refusing an installation that would have fit.
+const UNPACKED_RATIO: u64 = 2;
+
+/// An estimate of the disk space required to execute an installation plan.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceEstimate {
+    /// The bytes to be written to the cache: downloaded and unzipped wheels, and any source
+    /// distributions that need to be built.
+    pub cache: u64,
+    /// The bytes to be written to the target environment (i.e., `site-packages`).
+    pub target: u64,
+}
+
+/// A filesystem without enough free space for an installation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsufficientSpace {
+    /// The path (or paths, if they share a filesystem) that need the space.
+    pub paths: Vec<PathBuf>,
+    /// The estimated number of bytes required.
+    pub required: u64,
+    /// The number of bytes available.
+    pub available: u64,
+}
+
+impl SpaceEstimate {
+    /// Estimate the space required to fetch the `remote` distributions into the cache, and to
+    /// install them into the environment.
+    ///
+    /// Distributions of unknown size (e.g., Git dependencies, or direct URLs) are not counted.
+    pub fn from_plan(remote: &[Dist]) -> Self {
+        let mut estimate = Self::default();
+        for dist in remote {
+            let Some(size) = archive_size(dist) else {
+                continue;
+            };
+            let unpacked = size.saturating_mul(UNPACKED_RATIO);
+            estimate.cache = estimate.cache.saturating_add(match dist {
+                // The cache holds the unpacked wheel.
+                Dist::Built(_) => unpacked,
+                // The cache holds the archive, its unpacked source tree, the built wheel, and the
+                // unpacked wheel.
+                Dist::Source(_) => size.saturating_add(unpacked.saturating_mul(3)),
+            });
+            estimate.target = estimate.target.saturating_add(unpacked);
+        }
+        estimate
+    }
+
+    /// Verify that there's enough free space for the estimate, along with the `cached`
+    /// distributions to be installed from the cache, on both the cache and target filesystems.
+    ///
+    /// Linked installs (i.e., via hardlinks or copy-on-write clones) don't consume additional
+    /// space when the cache and target share a filesystem, in which case the `cached`
+    /// distributions aren't measured at all. Filesystems whose free space can't be determined are
+    /// assumed to have enough.
+    pub fn check(
+        &self,
+        cache: &Path,
+        target: &Path,
+        link_mode: LinkMode,
+        cached: &[CachedDist],
+    ) -> Result<(), InsufficientSpace> {
+        if is_same_filesystem(cache, target) {
+            let required = match link_mode {
+                LinkMode::Copy => self
+                    .cache
+                    .saturating_add(self.target)
+                    .saturating_add(cached_size(cached)),
+                LinkMode::Clone | LinkMode::Hardlink => self.cache,
+            };
+            return Self::check_path(&[cache, target], required);
+        }
+        Self::check_path(&[cache], self.cache)?;
+        Self::check_path(&[target], self.target.saturating_add(cached_size(cached)))?;
+        Ok(())
+    }
+
+    fn check_path(paths: &[&Path], required: u64) -> Result<(), InsufficientSpace> {
+        if required == 0 {
+            return Ok(());
+        }
+        let Some(available) = available_space(paths[0]) else {
+            return Ok(());
+        };
+        if available < required {
+            return Err(InsufficientSpace {
+                paths: paths.iter().map(|path| path.to_path_buf()).collect(),
+                required,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Return the size of a distribution's archive, if known.
+fn archive_size(dist: &Dist) -> Option<u64> {
+    if let Some(size) = dist.file().and_then(|file| file.size) {
+        return Some(size);
+    }
+    let path = match dist {
+        Dist::Built(BuiltDist::Path(wheel)) => &wheel.path,
+        Dist::Source(SourceDist::Path(sdist)) => &sdist.path,
+        _ => return None,
+    };
+    fs_err::metadata(path).ok().map(|metadata| metadata.len())
+}
+
+/// Return the total size of the unzipped wheels of the given cached distributions.
+fn cached_size(cached: &[CachedDist]) -> u64 {
+    cached
+        .iter()
+        .map(|dist| directory_size(dist.path()))
+        .fold(0, u64::saturating_add)
+}
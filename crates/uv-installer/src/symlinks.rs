@@ -0,0 +1,42 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use uv_fs::{find_symlink_escape, Simplified};
+use uv_interpreter::PythonEnvironment;
+
+/// An environment directory that's a symlink to an unexpected location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymlinkedTarget {
+    /// The path to the directory, as seen by the user.
+    pub path: PathBuf,
+    /// The resolved location of the directory.
+    pub target: PathBuf,
+}
+
+impl Display for SymlinkedTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is a symlink to `{}`",
+            self.path.user_display(),
+            self.target.user_display()
+        )
+    }
+}
+
+/// Verify that modifying the environment won't write through a symlink to a system directory
+/// (e.g., a `.venv` that links to `/usr`) or, if a `root` is provided, to a location outside of
+/// `root`.
+///
+/// Both the environment root and its `site-packages` directories are checked.
+pub fn check_target(venv: &PythonEnvironment, root: Option<&Path>) -> Result<(), SymlinkedTarget> {
+    for path in std::iter::once(venv.root()).chain(venv.site_packages()) {
+        if let Some(target) = find_symlink_escape(path, root) {
+            return Err(SymlinkedTarget {
+                path: path.to_path_buf(),
+                target,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use thiserror::Error;
+use tracing::debug;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+
+/// A journal of the modifications applied to a Python environment during an installation, which
+/// enables the environment to be restored to its previous state if the installation fails.
+///
+/// Before a distribution is uninstalled, its files are staged in a backup directory within the
+/// environment. If the installation fails, any newly installed distributions are removed, and the
+/// staged files are moved back into place. If the installation succeeds, the transaction is
+/// committed, and the staged files are discarded.
+///
+/// Only distributions installed from wheels (i.e., with a `RECORD` file) can be restored; `.egg-info`
+/// distributions and legacy editables are removed without a backup.
+#[derive(Debug)]
+pub struct Transaction {
+    /// The directory in which files are staged prior to removal.
+    staging: TempDir,
+    /// The files that were staged, as pairs of (original path, staged path).
+    staged: Vec<(PathBuf, PathBuf)>,
+    /// The site-packages directories of the environment.
+    site_packages: Vec<PathBuf>,
+}
+
+impl Transaction {
+    /// Begin a transaction against the given environment.
+    pub fn new(venv: &PythonEnvironment) -> Result<Self, TransactionError> {
+        let staging = tempfile::Builder::new()
+            .prefix(".uv-transaction-")
+            .tempdir_in(venv.root())
+            .map_err(TransactionError::Staging)?;
+        Ok(Self {
+            staging,
+            staged: Vec::new(),
+            site_packages: venv.site_packages().map(Path::to_path_buf).collect(),
+        })
+    }
+
+    /// Stage the files of an installed distribution, prior to its removal.
+    pub fn stage(&mut self, dist: &InstalledDist) -> Result<(), TransactionError> {
+        let files = match dist {
+            InstalledDist::Registry(_) | InstalledDist::Url(_) => {
+                match install_wheel_rs::installed_files(dist.path()) {
+                    Ok(files) => files,
+                    // If the `RECORD` is missing, the distribution can't be uninstalled either.
+                    Err(install_wheel_rs::Error::MissingRecord(_)) => return Ok(()),
+                    Err(err) => return Err(TransactionError::Record(dist.to_string(), err)),
+                }
+            }
+            InstalledDist::EggInfo(_) | InstalledDist::LegacyEditable(_) => {
+                debug!("Unable to stage non-wheel distribution: {dist}");
+                return Ok(());
+            }
+        };
+
+        let start = self.staged.len();
+        for file in files {
+            if !file.is_file() {
+                continue;
+            }
+            let staged = self.staging.path().join(self.staged.len().to_string());
+            link_or_copy(&file, &staged)
+                .map_err(|err| TransactionError::Stage(file.clone(), err))?;
+            self.staged.push((file, staged));
+        }
+
+        debug!(
+            "Staged {} file(s) for: {}",
+            self.staged.len() - start,
+            dist.name()
+        );
+
+        Ok(())
+    }
+
+    /// Roll back the transaction, removing any of the given distributions that were (fully or
+    /// partially) installed, and restoring all staged files.
+    pub fn rollback(self, wheels: &[CachedDist]) -> Result<(), TransactionError> {
+        // Remove any installed distributions.
+        for wheel in wheels {
+            let Some(dist_info) = dist_info_name(wheel.path()) else {
+                continue;
+            };
+            for site_packages in &self.site_packages {
+                let dist_info = site_packages.join(&dist_info);
+                if !dist_info.is_dir() {
+                    continue;
+                }
+                debug!("Rolling back installation of: {}", wheel.name());
+                match install_wheel_rs::uninstall_wheel(
+                    &dist_info,
+                    &install_wheel_rs::UninstallOptions::default(),
+                ) {
+                    Ok(_) => {}
+                    // The installation failed before the `RECORD` was written.
+                    Err(install_wheel_rs::Error::MissingRecord(_)) => {
+                        fs_err::remove_dir_all(&dist_info).map_err(TransactionError::Restore)?;
+                    }
+                    Err(err) => return Err(TransactionError::Record(wheel.to_string(), err)),
+                }
+            }
+        }
+
+        // Restore the staged files.
+        for (original, staged) in self.staged.iter().rev() {
+            if let Some(parent) = original.parent() {
+                fs_err::create_dir_all(parent).map_err(TransactionError::Restore)?;
+            }
+            if fs_err::rename(staged, original).is_err() {
+                fs_err::copy(staged, original).map_err(TransactionError::Restore)?;
+            }
+        }
+
+        debug!(
+            "Restored {} file(s) from: {}",
+            self.staged.len(),
+            self.staging.path().user_display()
+        );
+
+        Ok(())
+    }
+
+    /// Commit the transaction, discarding any staged files.
+    pub fn commit(self) -> Result<(), TransactionError> {
+        self.staging.close().map_err(TransactionError::Staging)
+    }
+}
+
+/// Return the name of the `.dist-info` directory in an unzipped wheel.
+pub(crate) fn dist_info_name(wheel: &Path) -> Option<PathBuf> {
+    fs_err::read_dir(wheel).ok()?.find_map(|entry| {
+        let entry = entry.ok()?;
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "dist-info") && path.is_dir() {
+            Some(PathBuf::from(entry.file_name()))
+        } else {
+            None
+        }
+    })
+}
+
+/// Hard link a file to a new location, falling back to a copy if hard links aren't supported.
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    if fs_err::hard_link(from, to).is_err() {
+        fs_err::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum TransactionError {
+    #[error("Failed to create staging directory for transaction")]
+    Staging(#[source] io::Error),
+    #[error("Failed to read installed files for: {0}")]
+    Record(String, #[source] install_wheel_rs::Error),
+    #[error("Failed to stage file: `{}`", _0.user_display())]
+    Stage(PathBuf, #[source] io::Error),
+    #[error("Failed to restore environment")]
+    Restore(#[source] io::Error),
+}
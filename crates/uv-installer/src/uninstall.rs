@@ -1,20 +1,23 @@
 use anyhow::Result;
 
 use distribution_types::InstalledDist;
+use install_wheel_rs::UninstallOptions;
 
 /// Uninstall a package from the specified Python environment.
 pub async fn uninstall(
     dist: &InstalledDist,
+    options: &UninstallOptions,
 ) -> Result<install_wheel_rs::Uninstall, UninstallError> {
     let uninstall = tokio::task::spawn_blocking({
         let dist = dist.clone();
+        let options = options.clone();
         move || match dist {
             InstalledDist::Registry(_) | InstalledDist::Url(_) => {
-                install_wheel_rs::uninstall_wheel(dist.path())
+                install_wheel_rs::uninstall_wheel(dist.path(), &options)
             }
-            InstalledDist::EggInfo(_) => install_wheel_rs::uninstall_egg(dist.path()),
+            InstalledDist::EggInfo(_) => install_wheel_rs::uninstall_egg(dist.path(), &options),
             InstalledDist::LegacyEditable(dist) => {
-                install_wheel_rs::uninstall_legacy_editable(&dist.egg_link)
+                install_wheel_rs::uninstall_legacy_editable(&dist.egg_link, &options)
             }
         }
     })
@@ -9,6 +9,7 @@ use which::which;
 use crate::implementation::{ImplementationName, LenientImplementationName};
 use crate::interpreter::Error as InterpreterError;
 use crate::managed::toolchains_for_current_platform;
+use crate::platform::Arch;
 use crate::py_launcher::py_list_paths;
 use crate::virtualenv::{
     conda_prefix_from_env, virtualenv_from_env, virtualenv_from_working_dir,
@@ -43,6 +44,8 @@ pub enum InterpreterRequest {
     Implementation(ImplementationName),
     /// A Python implementation name and version e.g. `pypy3.8` or `pypy@3.8`
     ImplementationVersion(ImplementationName, VersionRequest),
+    /// A version or implementation request for a specific CPU architecture e.g. `3.12-arm64`
+    Architecture(Box<InterpreterRequest>, Arch),
 }
 
 /// The sources to consider when finding a Python interpreter.
@@ -101,6 +104,8 @@ pub enum InterpreterNotFound {
     NoMatchingImplementation(SourceSelector, ImplementationName),
     /// No Python installations with the requested implementation name and version were found.
     NoMatchingImplementationVersion(SourceSelector, ImplementationName, VersionRequest),
+    /// No Python installations for the requested architecture were found.
+    NoMatchingArchitecture(SourceSelector, Box<InterpreterRequest>, Arch),
     /// The requested file path does not exist.
     FileNotFound(PathBuf),
     /// The requested directory path does not exist.
@@ -192,6 +197,7 @@ fn python_executables<'a>(
     version: Option<&'a VersionRequest>,
     implementation: Option<&'a ImplementationName>,
     sources: &SourceSelector,
+    venv_dir: Option<&'a Path>,
 ) -> impl Iterator<Item = Result<(InterpreterSource, PathBuf), Error>> + 'a {
     // Note we are careful to ensure the iterator chain is lazy to avoid unnecessary work
 
@@ -223,7 +229,7 @@ fn python_executables<'a>(
     .chain(
         sources.contains(InterpreterSource::DiscoveredEnvironment).then(||
             std::iter::once(
-                virtualenv_from_working_dir()
+                virtualenv_from_working_dir(venv_dir)
                 .map(|path|
                     path
                     .map(virtualenv_python_executable)
@@ -349,6 +355,57 @@ fn python_executables_from_search_path<'a>(
         })
 }
 
+/// Iterate over the version-specific Python executables in the `PATH`, e.g., `python3.12` or
+/// `pypy3.10`.
+///
+/// Unlike [`python_executables_from_search_path`], which only considers the version-specific
+/// names for a requested version, this finds every such executable, as when listing the available
+/// interpreters. Within each directory, executables are returned from the newest to the oldest
+/// version.
+fn versioned_python_executables_from_search_path() -> impl Iterator<Item = PathBuf> {
+    // `UV_TEST_PYTHON_PATH` can be used to override `PATH` to limit Python executable availability in the test suite
+    let search_path =
+        env::var_os("UV_TEST_PYTHON_PATH").unwrap_or(env::var_os("PATH").unwrap_or_default());
+
+    let search_dirs: Vec<_> = env::split_paths(&search_path).collect();
+    search_dirs
+        .into_iter()
+        .filter(|dir| dir.is_dir())
+        .flat_map(|dir| {
+            trace!(
+                "Checking `PATH` directory for versioned interpreters: {}",
+                dir.display()
+            );
+            let mut executables = fs_err::read_dir(&dir)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name();
+                    let minor = versioned_executable_minor(name.to_str()?)?;
+                    Some((minor, entry.path()))
+                })
+                .filter(|(_, path)| path.is_file() && !is_windows_store_shim(path))
+                .collect::<Vec<_>>();
+            executables.sort_by(|(a, a_path), (b, b_path)| b.cmp(a).then(a_path.cmp(b_path)));
+            executables.into_iter().map(|(_, path)| path)
+        })
+        .inspect(|path| trace!("Found possible Python executable: {}", path.display()))
+}
+
+/// If the given file name is a version-specific Python executable (e.g., `python3.12`), return
+/// its minor version.
+fn versioned_executable_minor(name: &str) -> Option<u8> {
+    let name = name.strip_suffix(std::env::consts::EXE_SUFFIX)?;
+    let version = ["python3.", "pypy3.", "graalpy3."]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))?;
+    if version.is_empty() || !version.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    version.parse().ok()
+}
+
 /// Lazily iterate over all discoverable Python interpreters.
 ///
 ///See [`python_executables`] for more information on discovery.
@@ -357,9 +414,24 @@ fn python_interpreters<'a>(
     implementation: Option<&'a ImplementationName>,
     system: SystemPython,
     sources: &SourceSelector,
+    venv_dir: Option<&'a Path>,
+    cache: &'a Cache,
+) -> impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>> + 'a {
+    python_interpreters_from_executables(
+        python_executables(version, implementation, sources, venv_dir),
+        system,
+        cache,
+    )
+}
+
+/// Query each of the given Python executables, omitting those that don't conform to the
+/// [`SystemPython`] request.
+fn python_interpreters_from_executables<'a>(
+    executables: impl Iterator<Item = Result<(InterpreterSource, PathBuf), Error>> + 'a,
+    system: SystemPython,
     cache: &'a Cache,
 ) -> impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>> + 'a {
-    python_executables(version, implementation, sources)
+    executables
         .map(|result| match result {
             Ok((source, path)) => Interpreter::query(&path, cache)
                 .map(|interpreter| (source, interpreter))
@@ -450,10 +522,14 @@ fn should_stop_discovery(err: &Error) -> bool {
 ///
 /// If an error is encountered while locating or inspecting a candidate interpreter,
 /// the error will raised instead of attempting further candidates.
+///
+/// When searching the current and parent directories for a virtual environment, a directory named
+/// `venv_dir` is considered instead of `.venv`, if provided.
 pub fn find_interpreter(
     request: &InterpreterRequest,
     system: SystemPython,
     sources: &SourceSelector,
+    venv_dir: Option<&Path>,
     cache: &Cache,
 ) -> Result<InterpreterResult, Error> {
     let result = match request {
@@ -520,7 +596,7 @@ pub fn find_interpreter(
         InterpreterRequest::Implementation(implementation) => {
             debug!("Searching for a {request} interpreter in {sources}");
             let Some((source, interpreter)) =
-                python_interpreters(None, Some(implementation), system, sources, cache)
+                python_interpreters(None, Some(implementation), system, sources, venv_dir, cache)
                     .find(|result| {
                         match result {
                             // Return the first critical error or matching interpreter
@@ -543,27 +619,75 @@ pub fn find_interpreter(
         }
         InterpreterRequest::ImplementationVersion(implementation, version) => {
             debug!("Searching for {request} in {sources}");
+            let Some((source, interpreter)) = python_interpreters(
+                Some(version),
+                Some(implementation),
+                system,
+                sources,
+                venv_dir,
+                cache,
+            )
+            .find(|result| {
+                match result {
+                    // Return the first critical error or matching interpreter
+                    Err(err) => should_stop_discovery(err),
+                    Ok((_source, interpreter)) => {
+                        version.matches_interpreter(interpreter)
+                            && interpreter.implementation_name() == implementation.as_str()
+                    }
+                }
+            })
+            .transpose()?
+            else {
+                // TODO(zanieb): Peek if there are any interpreters with the requested implementation
+                //               to improve the error message e.g. using `NoMatchingImplementation` instead
+                return Ok(InterpreterResult::Err(
+                    InterpreterNotFound::NoMatchingImplementationVersion(
+                        sources.clone(),
+                        *implementation,
+                        *version,
+                    ),
+                ));
+            };
+            DiscoveredInterpreter {
+                source,
+                interpreter,
+            }
+        }
+        InterpreterRequest::Architecture(inner, arch) => {
+            debug!("Searching for {request} in {sources}");
+            let (version, implementation) = match inner.as_ref() {
+                InterpreterRequest::Version(version) => (Some(version), None),
+                InterpreterRequest::Implementation(implementation) => (None, Some(implementation)),
+                InterpreterRequest::ImplementationVersion(implementation, version) => {
+                    (Some(version), Some(implementation))
+                }
+                _ => (None, None),
+            };
             let Some((source, interpreter)) =
-                python_interpreters(Some(version), Some(implementation), system, sources, cache)
+                python_interpreters(version, implementation, system, sources, venv_dir, cache)
                     .find(|result| {
                         match result {
                             // Return the first critical error or matching interpreter
                             Err(err) => should_stop_discovery(err),
                             Ok((_source, interpreter)) => {
-                                version.matches_interpreter(interpreter)
-                                    && interpreter.implementation_name() == implementation.as_str()
+                                interpreter.arch() == *arch
+                                    && version.map_or(true, |version| {
+                                        version.matches_interpreter(interpreter)
+                                    })
+                                    && implementation.map_or(true, |implementation| {
+                                        interpreter.implementation_name() == implementation.as_str()
+                                    })
                             }
                         }
                     })
                     .transpose()?
             else {
-                // TODO(zanieb): Peek if there are any interpreters with the requested implementation
-                //               to improve the error message e.g. using `NoMatchingImplementation` instead
                 return Ok(InterpreterResult::Err(
-                    InterpreterNotFound::NoMatchingImplementationVersion(
+                    InterpreterNotFound::NoMatchingArchitecture(
                         sources.clone(),
-                        *implementation,
-                        *version,
+                        inner.clone(),
+                        *arch,
                     ),
                 ));
             };
@@ -575,7 +699,7 @@ pub fn find_interpreter(
         InterpreterRequest::Any => {
             debug!("Searching for Python interpreter in {sources}");
             let Some((source, interpreter)) =
-                python_interpreters(None, None, system, sources, cache)
+                python_interpreters(None, None, system, sources, venv_dir, cache)
                     .find(|result| {
                         match result {
                             // Return the first critical error or interpreter
@@ -597,7 +721,7 @@ pub fn find_interpreter(
         InterpreterRequest::Version(version) => {
             debug!("Searching for {request} in {sources}");
             let Some((source, interpreter)) =
-                python_interpreters(Some(version), None, system, sources, cache)
+                python_interpreters(Some(version), None, system, sources, venv_dir, cache)
                     .find(|result| {
                         match result {
                             // Return the first critical error or matching interpreter
@@ -624,6 +748,31 @@ pub fn find_interpreter(
     Ok(InterpreterResult::Ok(result))
 }
 
+/// Lazily iterate over all discoverable Python interpreters from the given sources, along with
+/// the source from which each was discovered.
+///
+/// Unlike [`find_interpreter`], errors for individual interpreters are returned rather than
+/// stopping discovery, and the same interpreter may be returned from multiple sources.
+///
+/// In addition to the default executable names (e.g., `python3`), the search path is searched for
+/// version-specific executables (e.g., `python3.12`), such that every installed version is found.
+pub fn find_interpreters<'a>(
+    system: SystemPython,
+    sources: &SourceSelector,
+    venv_dir: Option<&'a Path>,
+    cache: &'a Cache,
+) -> impl Iterator<Item = Result<(InterpreterSource, Interpreter), Error>> + 'a {
+    let executables = python_executables(None, None, sources, venv_dir).chain(
+        sources
+            .contains(InterpreterSource::SearchPath)
+            .then(versioned_python_executables_from_search_path)
+            .into_iter()
+            .flatten()
+            .map(|path| Ok((InterpreterSource::SearchPath, path))),
+    );
+    python_interpreters_from_executables(executables, system, cache)
+}
+
 /// Find the default Python interpreter on the system.
 ///
 /// Virtual environments are not included in discovery.
@@ -633,7 +782,7 @@ pub fn find_default_interpreter(cache: &Cache) -> Result<InterpreterResult, Erro
     let request = InterpreterRequest::default();
     let sources = SourceSelector::System;
 
-    let result = find_interpreter(&request, SystemPython::Required, &sources, cache)?;
+    let result = find_interpreter(&request, SystemPython::Required, &sources, None, cache)?;
     if let Ok(ref found) = result {
         warn_on_unsupported_python(found.interpreter());
     }
@@ -655,6 +804,7 @@ pub fn find_default_interpreter(cache: &Cache) -> Result<InterpreterResult, Erro
 pub fn find_best_interpreter(
     request: &InterpreterRequest,
     system: SystemPython,
+    venv_dir: Option<&Path>,
     cache: &Cache,
 ) -> Result<InterpreterResult, Error> {
     debug!("Starting interpreter discovery for {}", request);
@@ -664,7 +814,7 @@ pub fn find_best_interpreter(
 
     // First, check for an exact match (or the first available version if no Python versfion was provided)
     debug!("Looking for exact match for request {request}");
-    let result = find_interpreter(request, system, &sources, cache)?;
+    let result = find_interpreter(request, system, &sources, venv_dir, cache)?;
     if let Ok(ref found) = result {
         warn_on_unsupported_python(found.interpreter());
         return Ok(result);
@@ -672,33 +822,27 @@ pub fn find_best_interpreter(
 
     // If that fails, and a specific patch version was requested try again allowing a
     // different patch version
-    if let Some(request) = match request {
-        InterpreterRequest::Version(version) => {
-            if version.has_patch() {
-                Some(InterpreterRequest::Version((*version).without_patch()))
-            } else {
-                None
-            }
-        }
-        InterpreterRequest::ImplementationVersion(implementation, version) => Some(
-            InterpreterRequest::ImplementationVersion(*implementation, (*version).without_patch()),
-        ),
-        _ => None,
-    } {
+    if let Some(request) = request.without_patch() {
         debug!("Looking for relaxed patch version {request}");
-        let result = find_interpreter(&request, system, &sources, cache)?;
+        let result = find_interpreter(&request, system, &sources, venv_dir, cache)?;
         if let Ok(ref found) = result {
             warn_on_unsupported_python(found.interpreter());
             return Ok(result);
         }
     }
 
-    // If a Python version was requested but cannot be fulfilled, just take any version
+    // If a Python version was requested but cannot be fulfilled, just take any version (but
+    // never silently give up on a requested architecture)
     debug!("Looking for Python interpreter with any version");
-    let request = InterpreterRequest::Any;
+    let request = match request {
+        InterpreterRequest::Architecture(_, arch) => {
+            InterpreterRequest::Architecture(Box::new(InterpreterRequest::Any), *arch)
+        }
+        _ => InterpreterRequest::Any,
+    };
     Ok(find_interpreter(
         // TODO(zanieb): Add a dedicated `Default` variant to `InterpreterRequest`
-        &request, system, &sources, cache,
+        &request, system, &sources, venv_dir, cache,
     )?
     .map_err(|err| {
         // Use a more general error in this case since we looked for multiple versions
@@ -860,6 +1004,21 @@ impl InterpreterRequest {
     ///
     /// This cannot fail, which means weird inputs will be parsed as [`InterpreterRequest::File`] or [`InterpreterRequest::ExecutableName`].
     pub fn parse(value: &str) -> Self {
+        // e.g. `3.12-arm64` or `pypy@3.10-x86_64`
+        if let Some((first, second)) = value.rsplit_once('-') {
+            if let Ok(arch) = Arch::from_str(second) {
+                let request = Self::parse(first);
+                if matches!(
+                    request,
+                    Self::Any
+                        | Self::Version(_)
+                        | Self::Implementation(_)
+                        | Self::ImplementationVersion(..)
+                ) {
+                    return Self::Architecture(Box::new(request), arch);
+                }
+            }
+        }
         // e.g. `3.12.1`
         if let Ok(version) = VersionRequest::from_str(value) {
             return Self::Version(version);
@@ -932,6 +1091,26 @@ impl InterpreterRequest {
         // e.g. foo.exe
         Self::ExecutableName(value.to_string())
     }
+
+    /// Return the request with any requested patch version dropped, if it has one.
+    fn without_patch(&self) -> Option<Self> {
+        match self {
+            Self::Version(version) => {
+                if version.has_patch() {
+                    Some(Self::Version((*version).without_patch()))
+                } else {
+                    None
+                }
+            }
+            Self::ImplementationVersion(implementation, version) => Some(
+                Self::ImplementationVersion(*implementation, (*version).without_patch()),
+            ),
+            Self::Architecture(request, arch) => request
+                .without_patch()
+                .map(|request| Self::Architecture(Box::new(request), *arch)),
+            _ => None,
+        }
+    }
 }
 
 impl VersionRequest {
@@ -1202,6 +1381,7 @@ impl fmt::Display for InterpreterRequest {
             Self::ImplementationVersion(implementation, version) => {
                 write!(f, "{implementation} {version}")
             }
+            Self::Architecture(request, arch) => write!(f, "{request} ({arch})"),
         }
     }
 }
@@ -1245,6 +1425,12 @@ impl fmt::Display for InterpreterNotFound {
                     "No interpreter found for {implementation} {version} in {sources}"
                 )
             }
+            Self::NoMatchingArchitecture(sources, request, arch) => {
+                write!(
+                    f,
+                    "No interpreter found for {request} ({arch}) in {sources}"
+                )
+            }
             Self::FileNotFound(path) => write!(
                 f,
                 "Requested interpreter path `{}` does not exist",
@@ -1345,6 +1531,7 @@ mod tests {
     use crate::{
         discovery::{InterpreterRequest, VersionRequest},
         implementation::ImplementationName,
+        platform::Arch,
     };
 
     #[test]
@@ -1390,6 +1577,39 @@ mod tests {
             InterpreterRequest::parse("pypy310"),
             InterpreterRequest::ExecutableName("pypy310".to_string())
         );
+        assert_eq!(
+            InterpreterRequest::parse("3.12-arm64"),
+            InterpreterRequest::Architecture(
+                Box::new(InterpreterRequest::Version(
+                    VersionRequest::from_str("3.12").unwrap()
+                )),
+                Arch::Aarch64
+            )
+        );
+        assert_eq!(
+            InterpreterRequest::parse("pypy@3.10-x86_64"),
+            InterpreterRequest::Architecture(
+                Box::new(InterpreterRequest::ImplementationVersion(
+                    ImplementationName::PyPy,
+                    VersionRequest::from_str("3.10").unwrap()
+                )),
+                Arch::X86_64
+            )
+        );
+        assert_eq!(
+            InterpreterRequest::parse("cpython-amd64"),
+            InterpreterRequest::Architecture(
+                Box::new(InterpreterRequest::Implementation(
+                    ImplementationName::CPython
+                )),
+                Arch::X86_64
+            )
+        );
+        assert_eq!(
+            InterpreterRequest::parse("foo-arm64"),
+            InterpreterRequest::ExecutableName("foo-arm64".to_string()),
+            "An architecture suffix on an executable name is not treated as an architecture"
+        );
 
         let tempdir = TempDir::new().unwrap();
         assert_eq!(
@@ -1428,4 +1648,29 @@ mod tests {
         );
         assert!(VersionRequest::from_str("1.foo.1").is_err());
     }
+
+    #[test]
+    fn versioned_executable_minor() {
+        let suffix = std::env::consts::EXE_SUFFIX;
+        assert_eq!(
+            super::versioned_executable_minor(&format!("python3.12{suffix}")),
+            Some(12)
+        );
+        assert_eq!(
+            super::versioned_executable_minor(&format!("pypy3.10{suffix}")),
+            Some(10)
+        );
+        assert_eq!(
+            super::versioned_executable_minor(&format!("python3{suffix}")),
+            None
+        );
+        assert_eq!(
+            super::versioned_executable_minor(&format!("python3.{suffix}")),
+            None
+        );
+        assert_eq!(
+            super::versioned_executable_minor(&format!("python3.12-config{suffix}")),
+            None
+        );
+    }
 }
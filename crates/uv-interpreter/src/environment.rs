@@ -2,6 +2,7 @@ use itertools::Either;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use same_file::is_same_file;
 
@@ -26,10 +27,17 @@ struct PythonEnvironmentShared {
 
 impl PythonEnvironment {
     /// Create a [`PythonEnvironment`] from a user request.
-    pub fn find(python: Option<&str>, system: SystemPython, cache: &Cache) -> Result<Self, Error> {
+    ///
+    /// See [`find_interpreter`] for the meaning of `venv_dir`.
+    pub fn find(
+        python: Option<&str>,
+        system: SystemPython,
+        venv_dir: Option<&Path>,
+        cache: &Cache,
+    ) -> Result<Self, Error> {
         // Detect the current Python interpreter.
         if let Some(python) = python {
-            Self::from_requested_python(python, system, cache)
+            Self::from_requested_python(python, system, venv_dir, cache)
         } else if system.is_preferred() {
             Self::from_default_python(cache)
         } else {
@@ -44,7 +52,7 @@ impl PythonEnvironment {
             }
 
             // Then a virtual environment
-            match Self::from_virtualenv(cache) {
+            match Self::from_virtualenv(venv_dir, cache) {
                 Ok(venv) => Ok(venv),
                 Err(Error::NotFound(_)) if system.is_allowed() => Self::from_default_python(cache),
                 Err(err) => Err(err),
@@ -55,10 +63,16 @@ impl PythonEnvironment {
     /// Create a [`PythonEnvironment`] for an existing virtual environment.
     ///
     /// Allows Conda environments (via `CONDA_PREFIX`) though they are not technically virtual environments.
-    pub fn from_virtualenv(cache: &Cache) -> Result<Self, Error> {
+    pub fn from_virtualenv(venv_dir: Option<&Path>, cache: &Cache) -> Result<Self, Error> {
         let sources = SourceSelector::VirtualEnv;
         let request = InterpreterRequest::Any;
-        let found = find_interpreter(&request, SystemPython::Disallowed, &sources, cache)??;
+        let found = find_interpreter(
+            &request,
+            SystemPython::Disallowed,
+            &sources,
+            venv_dir,
+            cache,
+        )??;
 
         debug_assert!(
             found.interpreter().is_virtualenv()
@@ -78,7 +92,7 @@ impl PythonEnvironment {
     pub fn from_parent_interpreter(system: SystemPython, cache: &Cache) -> Result<Self, Error> {
         let sources = SourceSelector::from_sources([InterpreterSource::ParentInterpreter]);
         let request = InterpreterRequest::Any;
-        let found = find_interpreter(&request, system, &sources, cache)??;
+        let found = find_interpreter(&request, system, &sources, None, cache)??;
 
         Ok(Self(Arc::new(PythonEnvironmentShared {
             root: found.interpreter().prefix().to_path_buf(),
@@ -110,11 +124,13 @@ impl PythonEnvironment {
     pub fn from_requested_python(
         request: &str,
         system: SystemPython,
+        venv_dir: Option<&Path>,
         cache: &Cache,
     ) -> Result<Self, Error> {
         let sources = SourceSelector::from_settings(system);
         let request = InterpreterRequest::parse(request);
-        let interpreter = find_interpreter(&request, system, &sources, cache)??.into_interpreter();
+        let interpreter =
+            find_interpreter(&request, system, &sources, venv_dir, cache)??.into_interpreter();
         Ok(Self(Arc::new(PythonEnvironmentShared {
             root: interpreter.prefix().to_path_buf(),
             interpreter,
@@ -199,19 +215,29 @@ impl PythonEnvironment {
         self.0.interpreter.scripts()
     }
 
-    /// Grab a file lock for the environment to prevent concurrent writes across processes.
-    pub fn lock(&self) -> Result<LockedFile, std::io::Error> {
+    /// Grab a file lock for the environment to prevent concurrent writes across processes, waiting
+    /// for at most `timeout` (or indefinitely, if `None`).
+    pub fn lock(&self, timeout: Option<Duration>) -> Result<LockedFile, std::io::Error> {
         if let Some(target) = self.0.interpreter.target() {
             // If we're installing into a `--target`, use a target-specific lock file.
-            LockedFile::acquire(target.root().join(".lock"), target.root().user_display())
+            LockedFile::acquire(
+                target.root().join(".lock"),
+                target.root().user_display(),
+                timeout,
+            )
         } else if self.0.interpreter.is_virtualenv() {
             // If the environment a virtualenv, use a virtualenv-specific lock file.
-            LockedFile::acquire(self.0.root.join(".lock"), self.0.root.user_display())
+            LockedFile::acquire(
+                self.0.root.join(".lock"),
+                self.0.root.user_display(),
+                timeout,
+            )
         } else {
             // Otherwise, use a global lock file.
             LockedFile::acquire(
                 env::temp_dir().join(format!("uv-{}.lock", cache_key::digest(&self.0.root))),
                 self.0.root.user_display(),
+                timeout,
             )
         }
     }
@@ -16,7 +16,7 @@ use pep508_rs::{MarkerEnvironment, StringVersion};
 use platform_tags::Platform;
 use platform_tags::{Tags, TagsError};
 use pypi_types::Scheme;
-use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
+use uv_cache::{Cache, CacheBucket, CachedByFileStamp, FileStamp, Freshness};
 use uv_fs::{write_atomic_sync, PythonExt, Simplified};
 
 use crate::pointer_size::PointerSize;
@@ -134,6 +134,17 @@ impl Interpreter {
         &self.platform
     }
 
+    /// Returns the CPU architecture of the interpreter.
+    pub fn arch(&self) -> crate::platform::Arch {
+        crate::platform::Arch::from(self.platform.arch())
+    }
+
+    /// Returns `true` if the interpreter's architecture differs from that of uv itself, e.g., an
+    /// `x86_64` interpreter running under Rosetta 2 on an `aarch64` machine.
+    pub fn is_foreign_arch(&self) -> bool {
+        crate::platform::Arch::from_env().is_ok_and(|arch| arch != self.arch())
+    }
+
     /// Returns the [`MarkerEnvironment`] for this Python executable.
     #[inline]
     pub const fn markers(&self) -> &MarkerEnvironment {
@@ -589,8 +600,8 @@ impl InterpreterInfo {
     /// A wrapper around [`markers::query_interpreter_info`] to cache the computed markers.
     ///
     /// Running a Python script is (relatively) expensive, and the markers won't change
-    /// unless the Python executable changes, so we use the executable's last modified
-    /// time as a cache key.
+    /// unless the Python executable changes, so we key the cache on the executable's path, and
+    /// invalidate it when the executable's last modified time or size changes.
     pub(crate) fn query_cached(executable: &Path, cache: &Cache) -> Result<Self, Error> {
         let cache_entry = cache.entry(
             CacheBucket::Interpreter,
@@ -598,7 +609,7 @@ impl InterpreterInfo {
             format!("{}.msgpack", digest(&executable)),
         );
 
-        let modified = Timestamp::from_path(uv_fs::canonicalize_executable(executable)?)?;
+        let stamp = FileStamp::from_path(uv_fs::canonicalize_executable(executable)?)?;
 
         // Read from the cache.
         if cache
@@ -606,9 +617,9 @@ impl InterpreterInfo {
             .is_ok_and(Freshness::is_fresh)
         {
             if let Ok(data) = fs::read(cache_entry.path()) {
-                match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
+                match rmp_serde::from_slice::<CachedByFileStamp<Self>>(&data) {
                     Ok(cached) => {
-                        if cached.timestamp == modified {
+                        if cached.stamp == stamp {
                             trace!(
                                 "Cached interpreter info for Python {}, skipping probing: {}",
                                 cached.data.markers.python_full_version(),
@@ -651,8 +662,8 @@ impl InterpreterInfo {
             fs::create_dir_all(cache_entry.dir())?;
             write_atomic_sync(
                 cache_entry.path(),
-                rmp_serde::to_vec(&CachedByTimestamp {
-                    timestamp: modified,
+                rmp_serde::to_vec(&CachedByFileStamp {
+                    stamp,
                     data: info.clone(),
                 })?,
             )?;
@@ -766,5 +777,33 @@ mod tests {
             interpreter.markers.python_version().version,
             Version::from_str("3.13").unwrap()
         );
+
+        // Replace the executable in-place, preserving its modification time. The change in size
+        // should still invalidate the cache.
+        let modified = fs::metadata(&mocked_interpreter)
+            .unwrap()
+            .modified()
+            .unwrap();
+        fs::write(
+            &mocked_interpreter,
+            formatdoc! {r##"
+            #!/bin/bash
+            # Upgraded in-place.
+            echo '{}'
+            "##, json.replace("3.12", "3.14")},
+        )
+        .unwrap();
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&mocked_interpreter)
+            .unwrap()
+            .file()
+            .set_modified(modified)
+            .unwrap();
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert_eq!(
+            interpreter.markers.python_version().version,
+            Version::from_str("3.14").unwrap()
+        );
     }
 }
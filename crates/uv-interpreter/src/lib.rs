@@ -2,16 +2,22 @@
 use thiserror::Error;
 
 pub use crate::discovery::{
-    find_best_interpreter, find_default_interpreter, find_interpreter, Error as DiscoveryError,
-    InterpreterNotFound, InterpreterRequest, InterpreterSource, SourceSelector, SystemPython,
-    VersionRequest,
+    find_best_interpreter, find_default_interpreter, find_interpreter, find_interpreters,
+    Error as DiscoveryError, InterpreterNotFound, InterpreterRequest, InterpreterSource,
+    SourceSelector, SystemPython, VersionRequest,
 };
 pub use crate::environment::PythonEnvironment;
 pub use crate::interpreter::Interpreter;
 pub use crate::pointer_size::PointerSize;
+pub use crate::py_launcher::{
+    register_with_py_launcher, Error as PyLauncherError, PyLauncherRegistration,
+};
 pub use crate::python_version::PythonVersion;
 pub use crate::target::Target;
-pub use crate::virtualenv::{Error as VirtualEnvError, PyVenvConfiguration, VirtualEnvironment};
+pub use crate::version_files::{PythonVersionFile, PYTHON_VERSION_FILENAME};
+pub use crate::virtualenv::{
+    virtualenv_python_executable, Error as VirtualEnvError, PyVenvConfiguration, VirtualEnvironment,
+};
 
 mod discovery;
 mod environment;
@@ -23,6 +29,7 @@ mod pointer_size;
 mod py_launcher;
 mod python_version;
 mod target;
+mod version_files;
 mod virtualenv;
 
 #[cfg(not(test))]
@@ -589,6 +596,7 @@ mod tests {
                     &InterpreterRequest::Any,
                     SystemPython::Allowed,
                     &SourceSelector::All,
+                    None,
                     &cache,
                 )
                 .unwrap()
@@ -623,6 +631,7 @@ mod tests {
                     &InterpreterRequest::Any,
                     SystemPython::Allowed,
                     &SourceSelector::All,
+                    None,
                     &cache,
                 )
                 .unwrap()
@@ -664,6 +673,7 @@ mod tests {
                     &InterpreterRequest::Any,
                     SystemPython::Required,
                     &SourceSelector::All,
+                    None,
                     &cache,
                 )
                 .unwrap()
@@ -704,6 +714,7 @@ mod tests {
                     &InterpreterRequest::Any,
                     SystemPython::Disallowed,
                     &SourceSelector::All,
+                    None,
                     &cache,
                 )
                 .unwrap()
@@ -743,6 +754,7 @@ mod tests {
                     &InterpreterRequest::parse("3.11"),
                     SystemPython::Allowed,
                     &sources,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -795,6 +807,7 @@ mod tests {
                     &InterpreterRequest::parse("3.11.2"),
                     SystemPython::Allowed,
                     &sources,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -847,6 +860,7 @@ mod tests {
                     &InterpreterRequest::parse("3.9"),
                     SystemPython::Allowed,
                     &sources,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -889,6 +903,7 @@ mod tests {
                     &InterpreterRequest::parse("3.11.9"),
                     SystemPython::Allowed,
                     &sources,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -929,6 +944,7 @@ mod tests {
                 let result = find_best_interpreter(
                     &InterpreterRequest::parse("3.11.9"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -979,6 +995,7 @@ mod tests {
                 let result = find_best_interpreter(
                     &InterpreterRequest::parse("3.11.9"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1021,6 +1038,7 @@ mod tests {
                 let result = find_best_interpreter(
                     &InterpreterRequest::parse("3.11.9"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1074,6 +1092,7 @@ mod tests {
                     // TODO(zanieb): Consider moving this test to `PythonEnvironment::find` instead
                     &InterpreterRequest::parse("3.12"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1125,6 +1144,7 @@ mod tests {
                     // TODO(zanieb): Consider moving this test to `PythonEnvironment::find` instead
                     &InterpreterRequest::parse("3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1176,6 +1196,7 @@ mod tests {
                     // TODO(zanieb): Consider moving this test to `PythonEnvironment::find` instead
                     &InterpreterRequest::parse("3.10.2"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1228,6 +1249,7 @@ mod tests {
                     // Request the search path Python with a matching minor
                     &InterpreterRequest::parse("3.11.2"),
                     crate::SystemPython::Disallowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1270,6 +1292,7 @@ mod tests {
                     // Request the search path Python with a matching minor
                     &InterpreterRequest::parse("3.10.2"),
                     crate::SystemPython::Disallowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1318,7 +1341,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Allowed, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Allowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1351,7 +1374,7 @@ mod tests {
             || {
                 let environment =
                     // Note this environment is not treated as a system interpreter
-                    PythonEnvironment::find(None, SystemPython::Disallowed, &cache)
+                    PythonEnvironment::find(None, SystemPython::Disallowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1386,7 +1409,7 @@ mod tests {
             || {
                 let environment =
                     // Note this environment is not treated as a system interpreter
-                    PythonEnvironment::find(None, SystemPython::Disallowed, &cache)
+                    PythonEnvironment::find(None, SystemPython::Disallowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1418,7 +1441,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Allowed, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Allowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1431,6 +1454,115 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_environment_from_discovered_environment_in_parent_directory() -> Result<()> {
+        let tempdir = TempDir::new()?;
+        let cache = Cache::temp()?;
+
+        let _venv = mock_venv(&tempdir, "3.12.0")?;
+        let subdir = tempdir.child("src").child("package");
+        subdir.create_dir_all()?;
+
+        with_vars(
+            [
+                ("UV_TEST_PYTHON_PATH", None),
+                ("UV_BOOTSTRAP_DIR", None),
+                (
+                    "PATH",
+                    Some(simple_mock_interpreters(&tempdir, &["3.10.1", "3.11.2"])?),
+                ),
+                ("PWD", Some(subdir.path().into())),
+            ],
+            || {
+                let environment =
+                    PythonEnvironment::find(None, crate::SystemPython::Allowed, None, &cache)
+                        .expect("An environment is found");
+                assert_eq!(
+                    environment.interpreter().python_full_version().to_string(),
+                    "3.12.0",
+                    "We should find the environment in a parent directory"
+                );
+            },
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_environment_from_discovered_environment_with_custom_name() -> Result<()> {
+        let tempdir = TempDir::new()?;
+        let cache = Cache::temp()?;
+
+        let venv = mock_venv(&tempdir, "3.12.0")?;
+        fs_err::rename(venv, tempdir.child("env"))?;
+        let subdir = tempdir.child("src");
+        subdir.create_dir_all()?;
+
+        with_vars(
+            [
+                ("UV_TEST_PYTHON_PATH", None),
+                ("UV_BOOTSTRAP_DIR", None),
+                (
+                    "PATH",
+                    Some(simple_mock_interpreters(&tempdir, &["3.10.1", "3.11.2"])?),
+                ),
+                ("PWD", Some(subdir.path().into())),
+            ],
+            || {
+                let environment = PythonEnvironment::find(
+                    None,
+                    crate::SystemPython::Allowed,
+                    Some(Path::new("env")),
+                    &cache,
+                )
+                .expect("An environment is found");
+                assert_eq!(
+                    environment.interpreter().python_full_version().to_string(),
+                    "3.12.0",
+                    "We should find the environment with the custom name"
+                );
+            },
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_environment_skips_custom_name_without_pyvenv_cfg() -> Result<()> {
+        let tempdir = TempDir::new()?;
+        let cache = Cache::temp()?;
+
+        // A directory with the custom name that isn't a virtual environment.
+        let subdir = tempdir.child("src");
+        subdir.child("env").create_dir_all()?;
+
+        with_vars(
+            [
+                ("UV_TEST_PYTHON_PATH", None),
+                ("UV_BOOTSTRAP_DIR", None),
+                (
+                    "PATH",
+                    Some(simple_mock_interpreters(&tempdir, &["3.10.1", "3.11.2"])?),
+                ),
+                ("PWD", Some(subdir.path().into())),
+            ],
+            || {
+                let result = PythonEnvironment::find(
+                    None,
+                    crate::SystemPython::Disallowed,
+                    Some(Path::new("env")),
+                    &cache,
+                );
+                assert!(
+                    matches!(result, Err(crate::Error::NotFound(_))),
+                    "We should skip the directory and not find an environment; got {result:?}"
+                );
+            },
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn find_environment_from_parent_interpreter() -> Result<()> {
         let tempdir = TempDir::new()?;
@@ -1460,7 +1592,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Allowed, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Allowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1502,7 +1634,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Explicit, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Explicit, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1544,7 +1676,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Disallowed, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Disallowed, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1586,7 +1718,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Required, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Required, None, &cache)
                         .expect("An environment is found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1619,7 +1751,7 @@ mod tests {
             ],
             || {
                 let environment =
-                    PythonEnvironment::find(None, crate::SystemPython::Required, &cache)
+                    PythonEnvironment::find(None, crate::SystemPython::Required, None, &cache)
                         .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
@@ -1641,9 +1773,13 @@ mod tests {
                 ("VIRTUAL_ENV", Some(venv.clone().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("3.12"), crate::SystemPython::Required, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("3.12"),
+                    crate::SystemPython::Required,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.12.2",
@@ -1665,8 +1801,12 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let result =
-                    PythonEnvironment::find(Some("3.12.3"), crate::SystemPython::Required, &cache);
+                let result = PythonEnvironment::find(
+                    Some("3.12.3"),
+                    crate::SystemPython::Required,
+                    None,
+                    &cache,
+                );
                 assert!(
                     result.is_err(),
                     "We should not find an environment; got {result:?}"
@@ -1693,7 +1833,8 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let result = PythonEnvironment::find(None, crate::SystemPython::Disallowed, &cache);
+                let result =
+                    PythonEnvironment::find(None, crate::SystemPython::Disallowed, None, &cache);
                 assert!(
                     result.is_err(),
                     "We should not find an environment; got {result:?}"
@@ -1723,9 +1864,13 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("foobar"), crate::SystemPython::Allowed, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("foobar"),
+                    crate::SystemPython::Allowed,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.10.0",
@@ -1760,6 +1905,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("./foo/bar"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -1797,6 +1943,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some(python.to_str().expect("Test path is valid unicode")),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -1829,6 +1976,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some(venv.to_str().expect("Test path is valid unicode")),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -1866,6 +2014,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some(python.to_str().expect("Test path is valid unicode")),
                     crate::SystemPython::Explicit,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -1903,6 +2052,7 @@ mod tests {
                 let result = PythonEnvironment::find(
                     Some(python.to_str().expect("Test path is valid unicode")),
                     crate::SystemPython::Disallowed,
+                    None,
                     &cache,
                 );
                 assert!(
@@ -1938,6 +2088,7 @@ mod tests {
                     PythonEnvironment::find(
                         Some("./foo/bar"),
                         crate::SystemPython::Allowed,
+                        None,
                         &cache,
                     );
                 assert!(
@@ -1974,9 +2125,13 @@ mod tests {
                 ("PWD", Some(pwd.path().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("foobar"), crate::SystemPython::Required, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("foobar"),
+                    crate::SystemPython::Required,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.10.0",
@@ -2006,9 +2161,13 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("pypy"), crate::SystemPython::Allowed, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("pypy"),
+                    crate::SystemPython::Allowed,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.10.1",
@@ -2041,9 +2200,13 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("pypy"), crate::SystemPython::Allowed, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("pypy"),
+                    crate::SystemPython::Allowed,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.10.1",
@@ -2077,9 +2240,13 @@ mod tests {
                 ("PWD", Some(tempdir.path().into())),
             ],
             || {
-                let environment =
-                    PythonEnvironment::find(Some("pypy3.10"), crate::SystemPython::Allowed, &cache)
-                        .expect("Environment should be found");
+                let environment = PythonEnvironment::find(
+                    Some("pypy3.10"),
+                    crate::SystemPython::Allowed,
+                    None,
+                    &cache,
+                )
+                .expect("Environment should be found");
                 assert_eq!(
                     environment.interpreter().python_full_version().to_string(),
                     "3.10.1",
@@ -2117,6 +2284,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("pypy@3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -2159,6 +2327,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("pypy@3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -2190,6 +2359,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("pypy@3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -2231,6 +2401,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("pypy@3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -2265,6 +2436,7 @@ mod tests {
                 let environment = PythonEnvironment::find(
                     Some("pypy@3.10"),
                     crate::SystemPython::Allowed,
+                    None,
                     &cache,
                 )
                 .expect("Environment should be found");
@@ -10,6 +10,7 @@ use uv_fs::Simplified;
 
 use crate::managed::downloads::Error;
 use crate::platform::{Arch, Libc, Os};
+use crate::py_launcher::PyLauncherRegistration;
 use crate::python_version::PythonVersion;
 
 /// The directory where Python toolchains we install are stored.
@@ -85,6 +86,21 @@ impl Toolchain {
     pub fn python_version(&self) -> &PythonVersion {
         &self.python_version
     }
+
+    /// The registration for this toolchain with the `py` launcher, tagged by its `major.minor`
+    /// version, such that it can be selected with, e.g., `py -V:uv/3.12`.
+    pub fn py_launcher_registration(&self) -> PyLauncherRegistration {
+        let major = self.python_version.major();
+        let minor = self.python_version.minor();
+        PyLauncherRegistration::new(
+            format!("{major}.{minor}"),
+            format!("Python {} (uv)", self.python_version),
+            (major, minor),
+            self.python_version.to_string(),
+            &self.path.join("install"),
+            &self.executable(),
+        )
+    }
 }
 
 /// Return the directories in the toolchain directory.
@@ -135,7 +135,7 @@ impl FromStr for Arch {
             "powerpc64le" | "ppc64le" => Ok(Self::Powerpc64Le),
             "powerpc64" | "ppc64" => Ok(Self::Powerpc64),
             "x86" | "i686" | "i386" => Ok(Self::X86),
-            "x86_64" | "amd64" => Ok(Self::X86_64),
+            "x86_64" | "amd64" | "x64" => Ok(Self::X86_64),
             "s390x" => Ok(Self::S390X),
             _ => Err(Error::ArchNotSupported(s.to_string())),
         }
@@ -148,6 +148,21 @@ impl Arch {
     }
 }
 
+impl From<platform_tags::Arch> for Arch {
+    fn from(arch: platform_tags::Arch) -> Self {
+        match arch {
+            platform_tags::Arch::Aarch64 => Self::Aarch64,
+            platform_tags::Arch::Armv6L => Self::Armv6L,
+            platform_tags::Arch::Armv7L => Self::Armv7L,
+            platform_tags::Arch::Powerpc64Le => Self::Powerpc64Le,
+            platform_tags::Arch::Powerpc64 => Self::Powerpc64,
+            platform_tags::Arch::X86 => Self::X86,
+            platform_tags::Arch::X86_64 => Self::X86_64,
+            platform_tags::Arch::S390X => Self::S390X,
+        }
+    }
+}
+
 impl Libc {
     pub(crate) fn from_env() -> Result<Self, Error> {
         // TODO(zanieb): Perform this lookup
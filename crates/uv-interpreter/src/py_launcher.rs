@@ -1,5 +1,5 @@
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus};
 
 use once_cell::sync::Lazy;
@@ -28,15 +28,25 @@ pub enum Error {
     Io(#[source] io::Error),
     #[error("The `py` launcher could not be found.")]
     NotFound,
+    #[error("Failed to run `reg` to register `{0}` with the `py` launcher.")]
+    Register(String, #[source] io::Error),
+    #[error(
+        "Registering Python installations with the `py` launcher is only supported on Windows."
+    )]
+    Unsupported,
 }
 
 /// ```text
 /// -V:3.12          C:\Users\Ferris\AppData\Local\Programs\Python\Python312\python.exe
 /// -V:3.8           C:\Users\Ferris\AppData\Local\Programs\Python\Python38\python.exe
+/// -V:uv/3.11       C:\Users\Ferris\.python\cpython-3.11.9-windows-x86_64-none\install\python.exe
 /// ```
+///
+/// Installations registered by companies other than `PythonCore` (per PEP 514) are prefixed by
+/// the company name; those with a version-like tag are included.
 static PY_LIST_PATHS: Lazy<Regex> = Lazy::new(|| {
     // Without the `R` flag, paths have trailing \r
-    Regex::new(r"(?mR)^ -(?:V:)?(\d).(\d+)-?(?:arm)?\d*\s*\*?\s*(.*)$").unwrap()
+    Regex::new(r"(?mR)^ -(?:V:)?(?:[^/\s]+/)?(\d)\.(\d+)-?(?:arm)?\d*\s*\*?\s*(.*)$").unwrap()
 });
 
 /// Use the `py` launcher to find installed Python versions.
@@ -75,8 +85,13 @@ pub(crate) fn py_list_paths() -> Result<Vec<PyListPath>, Error> {
         stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
     })?;
 
-    Ok(PY_LIST_PATHS
-        .captures_iter(&stdout)
+    Ok(parse_py_list_paths(&stdout))
+}
+
+/// Parse the output of `py --list-paths`.
+fn parse_py_list_paths(stdout: &str) -> Vec<PyListPath> {
+    PY_LIST_PATHS
+        .captures_iter(stdout)
         .filter_map(|captures| {
             let (_, [major, minor, path]) = captures.extract();
             if let (Some(major), Some(minor)) = (major.parse::<u8>().ok(), minor.parse::<u8>().ok())
@@ -90,5 +105,180 @@ pub(crate) fn py_list_paths() -> Result<Vec<PyListPath>, Error> {
                 None
             }
         })
-        .collect())
+        .collect()
+}
+
+/// A Python installation to register with the `py` launcher, per PEP 514.
+///
+/// Installations are registered under `HKEY_CURRENT_USER\Software\Python\uv\<tag>`, such that
+/// they can be selected with `py -V:uv/<tag>`.
+///
+/// See: <https://peps.python.org/pep-0514/>
+#[derive(Debug, Clone)]
+pub struct PyLauncherRegistration {
+    /// The tag under which to register the installation (e.g., `3.12`).
+    pub tag: String,
+    /// A human-readable name for the installation.
+    pub display_name: String,
+    /// The `major.minor` version of the installation.
+    pub sys_version: String,
+    /// The full version of the installation.
+    pub version: String,
+    /// The root directory of the installation.
+    pub install_path: PathBuf,
+    /// The path to the `python.exe` executable.
+    pub executable_path: PathBuf,
+}
+
+impl PyLauncherRegistration {
+    /// Create a [`PyLauncherRegistration`] for the installation rooted at `install_path`.
+    pub fn new(
+        tag: impl Into<String>,
+        display_name: impl Into<String>,
+        (major, minor): (u8, u8),
+        version: impl Into<String>,
+        install_path: &Path,
+        executable_path: &Path,
+    ) -> Self {
+        Self {
+            tag: tag.into(),
+            display_name: display_name.into(),
+            sys_version: format!("{major}.{minor}"),
+            version: version.into(),
+            install_path: install_path.to_path_buf(),
+            executable_path: executable_path.to_path_buf(),
+        }
+    }
+}
+
+/// The company under which uv registers Python installations.
+const COMPANY: &str = "uv";
+
+/// Register a Python installation with the `py` launcher, by writing its PEP 514 registry keys.
+///
+/// Any existing registration with the same tag is replaced.
+pub fn register_with_py_launcher(registration: &PyLauncherRegistration) -> Result<(), Error> {
+    if !cfg!(windows) {
+        return Err(Error::Unsupported);
+    }
+
+    let company = format!(r"HKCU\Software\Python\{COMPANY}");
+    let tag = format!(r"{company}\{}", registration.tag);
+    let install_path = format!(r"{tag}\InstallPath");
+    let windowed_executable = registration.executable_path.with_file_name("pythonw.exe");
+
+    let mut values: Vec<(&str, Option<&str>, String)> = vec![
+        (&company, Some("DisplayName"), "uv".to_string()),
+        (
+            &company,
+            Some("SupportUrl"),
+            "https://github.com/astral-sh/uv".to_string(),
+        ),
+        (&tag, Some("DisplayName"), registration.display_name.clone()),
+        (&tag, Some("SysVersion"), registration.sys_version.clone()),
+        (&tag, Some("Version"), registration.version.clone()),
+        (
+            &install_path,
+            None,
+            registration.install_path.display().to_string(),
+        ),
+        (
+            &install_path,
+            Some("ExecutablePath"),
+            registration.executable_path.display().to_string(),
+        ),
+    ];
+    if windowed_executable.is_file() {
+        values.push((
+            &install_path,
+            Some("WindowedExecutablePath"),
+            windowed_executable.display().to_string(),
+        ));
+    }
+
+    for (key, name, data) in values {
+        reg_add(&registration.tag, key, name, &data)?;
+    }
+    Ok(())
+}
+
+/// Write a string value to the registry with `reg add`.
+///
+/// If `name` is `None`, the key's default value is written.
+fn reg_add(tag: &str, key: &str, name: Option<&str>, data: &str) -> Result<(), Error> {
+    let mut command = Command::new("reg");
+    command.arg("add").arg(key);
+    if let Some(name) = name {
+        command.arg("/v").arg(name);
+    } else {
+        command.arg("/ve");
+    }
+    command
+        .arg("/t")
+        .arg("REG_SZ")
+        .arg("/d")
+        .arg(data)
+        .arg("/f");
+
+    let output = command
+        .output()
+        .map_err(|err| Error::Register(tag.to_string(), err))?;
+    if !output.status.success() {
+        return Err(Error::StatusCode {
+            message: format!(
+                "Running `reg add {key}` failed with status {}",
+                output.status
+            ),
+            exit_code: output.status,
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::parse_py_list_paths;
+
+    #[test]
+    fn list_paths() {
+        let stdout = [
+            r" -V:3.12 *        C:\Users\Ferris\AppData\Local\Programs\Python\Python312\python.exe",
+            r" -V:3.8           C:\Users\Ferris\AppData\Local\Programs\Python\Python38\python.exe",
+            r" -V:uv/3.11       C:\Users\Ferris\.python\cpython-3.11.9\install\python.exe",
+            r" -V:uv/project    C:\Users\Ferris\project\.venv\Scripts\python.exe",
+        ]
+        .join("\r\n");
+        let paths = parse_py_list_paths(&stdout)
+            .into_iter()
+            .map(|path| (path.major, path.minor, path.executable_path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    3,
+                    12,
+                    PathBuf::from(
+                        r"C:\Users\Ferris\AppData\Local\Programs\Python\Python312\python.exe"
+                    )
+                ),
+                (
+                    3,
+                    8,
+                    PathBuf::from(
+                        r"C:\Users\Ferris\AppData\Local\Programs\Python\Python38\python.exe"
+                    )
+                ),
+                (
+                    3,
+                    11,
+                    PathBuf::from(r"C:\Users\Ferris\.python\cpython-3.11.9\install\python.exe")
+                ),
+            ]
+        );
+    }
 }
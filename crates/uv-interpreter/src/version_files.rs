@@ -0,0 +1,141 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::InterpreterRequest;
+
+/// The name of the file used to pin the Python interpreter for a project.
+pub const PYTHON_VERSION_FILENAME: &str = ".python-version";
+
+/// A Python interpreter pinned via a `.python-version` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonVersionFile {
+    path: PathBuf,
+    request: String,
+}
+
+impl PythonVersionFile {
+    /// Find the nearest `.python-version` file in the given directory or any of its ancestors.
+    ///
+    /// As with `pyenv`, the first non-empty line that isn't a comment is used as the request.
+    /// Lines that uv can't interpret as a Python version or implementation (e.g., `system`, or the
+    /// name of a `pyenv-virtualenv` environment) are skipped, as are files that only contain such
+    /// lines.
+    pub fn discover(directory: &Path) -> Result<Option<Self>, io::Error> {
+        for ancestor in directory.ancestors() {
+            let path = ancestor.join(PYTHON_VERSION_FILENAME);
+            let contents = match fs_err::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            let Some(request) = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .find(|line| {
+                    let supported = !matches!(
+                        InterpreterRequest::parse(line),
+                        InterpreterRequest::ExecutableName(_)
+                    );
+                    if !supported {
+                        debug!(
+                            "Ignoring unsupported Python version `{line}` in: {}",
+                            path.display()
+                        );
+                    }
+                    supported
+                })
+            else {
+                debug!(
+                    "Ignoring Python version file with no supported versions: {}",
+                    path.display()
+                );
+                continue;
+            };
+            debug!("Found pinned Python `{request}` in: {}", path.display());
+            return Ok(Some(Self {
+                path,
+                request: request.to_string(),
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Pin the given Python request by writing a `.python-version` file to the given directory.
+    pub fn write(directory: &Path, request: &str) -> Result<Self, io::Error> {
+        let path = directory.join(PYTHON_VERSION_FILENAME);
+        fs_err::write(&path, format!("{request}\n"))?;
+        Ok(Self {
+            path,
+            request: request.to_string(),
+        })
+    }
+
+    /// Return the path to the `.python-version` file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the pinned request, as written in the file (e.g., `3.12` or `pypy@3.10`).
+    pub fn request(&self) -> &str {
+        &self.request
+    }
+
+    /// Return the pinned request, parsed as an [`InterpreterRequest`].
+    pub fn interpreter_request(&self) -> InterpreterRequest {
+        InterpreterRequest::parse(&self.request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn discover_python_version_file() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let child = root.child("project").child("src");
+        child.create_dir_all().unwrap();
+
+        assert_eq!(PythonVersionFile::discover(child.path()).unwrap(), None);
+
+        root.child(PYTHON_VERSION_FILENAME)
+            .write_str("# A comment\n\n  3.12  \n3.11\n")
+            .unwrap();
+        let file = PythonVersionFile::discover(child.path()).unwrap().unwrap();
+        assert_eq!(file.request(), "3.12");
+        assert_eq!(file.path(), root.child(PYTHON_VERSION_FILENAME).path());
+
+        // The nearest file takes precedence.
+        PythonVersionFile::write(root.child("project").path(), "pypy@3.10").unwrap();
+        let file = PythonVersionFile::discover(child.path()).unwrap().unwrap();
+        assert_eq!(file.request(), "pypy@3.10");
+    }
+
+    #[test]
+    fn discover_pyenv_python_version_file() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let child = root.child("project");
+        child.create_dir_all().unwrap();
+
+        // Names of `pyenv-virtualenv` environments and `system` are skipped in favor of the next
+        // line.
+        root.child(PYTHON_VERSION_FILENAME)
+            .write_str("my-virtualenv\nsystem\n3.11.4\n")
+            .unwrap();
+        let file = PythonVersionFile::discover(child.path()).unwrap().unwrap();
+        assert_eq!(file.request(), "3.11.4");
+
+        // A file with no supported lines is skipped in favor of an ancestor.
+        child
+            .child(PYTHON_VERSION_FILENAME)
+            .write_str("my-virtualenv\n")
+            .unwrap();
+        let file = PythonVersionFile::discover(child.path()).unwrap().unwrap();
+        assert_eq!(file.path(), root.child(PYTHON_VERSION_FILENAME).path());
+    }
+}
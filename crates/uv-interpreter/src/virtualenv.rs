@@ -6,6 +6,7 @@ use std::{
 use fs_err as fs;
 use pypi_types::Scheme;
 use thiserror::Error;
+use tracing::debug;
 
 /// The layout of a virtual environment.
 #[derive(Debug)]
@@ -64,10 +65,17 @@ pub(crate) fn conda_prefix_from_env() -> Option<PathBuf> {
 
 /// Locate a virtual environment by searching the file system.
 ///
-/// Searches for a `.venv` directory in the current or any parent directory. If the current
-/// directory is itself a virtual environment (or a subdirectory of a virtual environment), the
-/// containing virtual environment is returned.
-pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
+/// Searches for a `.venv` directory (or, if provided, a directory with the given name) in the
+/// current or any parent directory, such that running from a subdirectory of a project finds the
+/// project's environment. If the current directory is itself a virtual environment (or a
+/// subdirectory of a virtual environment), the containing virtual environment is returned.
+///
+/// A `.venv` directory without a `pyvenv.cfg` is an error. A directory with a custom name may
+/// not be a virtual environment at all (e.g., an `env` directory of environment files), so it's
+/// skipped instead.
+pub(crate) fn virtualenv_from_working_dir(
+    venv_dir: Option<&Path>,
+) -> Result<Option<PathBuf>, Error> {
     let current_dir = crate::current_dir()?;
 
     for dir in current_dir.ancestors() {
@@ -76,13 +84,26 @@ pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
             return Ok(Some(dir.to_path_buf()));
         }
 
-        // Otherwise, search for a `.venv` directory.
-        let dot_venv = dir.join(".venv");
-        if dot_venv.is_dir() {
-            if !dot_venv.join("pyvenv.cfg").is_file() {
-                return Err(Error::MissingPyVenvCfg(dot_venv));
+        // Otherwise, search for the environment directory.
+        if let Some(venv_dir) = venv_dir {
+            let venv = dir.join(venv_dir);
+            if venv.join("pyvenv.cfg").is_file() {
+                return Ok(Some(venv));
+            }
+            if venv.is_dir() {
+                debug!(
+                    "Ignoring `{}`: not a virtual environment (missing `pyvenv.cfg`)",
+                    venv.display()
+                );
+            }
+        } else {
+            let dot_venv = dir.join(".venv");
+            if dot_venv.is_dir() {
+                if !dot_venv.join("pyvenv.cfg").is_file() {
+                    return Err(Error::MissingPyVenvCfg(dot_venv));
+                }
+                return Ok(Some(dot_venv));
             }
-            return Ok(Some(dot_venv));
         }
     }
 
@@ -90,7 +111,7 @@ pub(crate) fn virtualenv_from_working_dir() -> Result<Option<PathBuf>, Error> {
 }
 
 /// Returns the path to the `python` executable inside a virtual environment.
-pub(crate) fn virtualenv_python_executable(venv: impl AsRef<Path>) -> PathBuf {
+pub fn virtualenv_python_executable(venv: impl AsRef<Path>) -> PathBuf {
     let venv = venv.as_ref();
     if cfg!(windows) {
         // Search for `python.exe` in the `Scripts` directory.
@@ -0,0 +1,169 @@
+//! Validation of requirements provided directly on the command line (e.g., `uv pip install
+//! "flask[dotenv]>=3 ; python_version >= '3.8'"`).
+//!
+//! Ad-hoc specifiers are validated against PEP 508 in full, including extras and markers. Since
+//! most malformed specifiers are the result of shell quoting, parse failures are paired with a
+//! hint on how to quote the requirement, where one applies.
+
+use std::path::Path;
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use pep508_rs::{MarkerWarningKind, Pep508ErrorSource};
+use requirements_txt::{RequirementsTxtRequirement, RequirementsTxtRequirementError};
+use uv_warnings::warn_user;
+
+/// A requirement provided on the command line that could not be parsed.
+#[derive(Debug, Error)]
+#[error("Failed to parse: `{spec}`")]
+pub struct CommandLineRequirementError {
+    spec: String,
+    #[source]
+    err: RequirementsTxtRequirementError,
+    hint: Option<String>,
+}
+
+impl CommandLineRequirementError {
+    /// A suggestion for fixing the requirement, typically by quoting it differently.
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+}
+
+/// Parse a requirement provided on the command line.
+///
+/// Any warnings raised while parsing the markers (e.g., a comparison that will always evaluate
+/// to `false`) are surfaced to the user, rather than logged.
+pub(crate) fn parse_command_line_requirement(
+    spec: &str,
+    working_dir: impl AsRef<Path>,
+) -> Result<RequirementsTxtRequirement, CommandLineRequirementError> {
+    let mut warnings = Vec::new();
+    let requirement = RequirementsTxtRequirement::parse_reporter(
+        spec,
+        working_dir,
+        &mut |kind: MarkerWarningKind, warning: String| warnings.push((kind, warning)),
+    )
+    .map_err(|err| CommandLineRequirementError {
+        spec: spec.to_string(),
+        hint: hint(spec, &err),
+        err,
+    })?;
+
+    for (_, warning) in warnings {
+        warn_user!(
+            "Ignoring marker in `{spec}`: {}",
+            warning.split_whitespace().join(" ")
+        );
+    }
+
+    Ok(requirement)
+}
+
+/// Suggest a fix for a requirement that failed to parse, based on common shell quoting mistakes.
+fn hint(spec: &str, err: &RequirementsTxtRequirementError) -> Option<String> {
+    let RequirementsTxtRequirementError::Pep508(err) = err else {
+        return None;
+    };
+    if !matches!(err.message, Pep508ErrorSource::String(_)) {
+        return None;
+    }
+
+    let trimmed = spec.trim();
+
+    // e.g., `'flask>=2'` in `cmd.exe`, which passes single quotes through verbatim.
+    for quote in ['\'', '"'] {
+        if trimmed.len() >= 2 && trimmed.starts_with(quote) && trimmed.ends_with(quote) {
+            return Some(format!(
+                "The requirement is wrapped in literal `{quote}` characters. If your shell doesn't strip them (e.g., `cmd.exe` with single quotes), use double quotes instead: `\"{}\"`",
+                &trimmed[1..trimmed.len() - 1]
+            ));
+        }
+    }
+
+    // e.g., `flask >= 2`, which the shell splits into `flask`, `>=`, and `2`.
+    if trimmed.starts_with(['<', '>', '=', '!', '~']) {
+        return Some(
+            "Version specifiers must follow a package name. If the shell split the requirement at a space, wrap the entire requirement in quotes (e.g., `\"flask >= 2\"`)".to_string(),
+        );
+    }
+
+    // e.g., `flask[dotenv`, where the shell consumed the closing bracket, or the extras were
+    // split at a space.
+    if let Some(start) = trimmed.find('[') {
+        if !trimmed[start..].contains(']') {
+            return Some(
+                "The extras are missing a closing `]`. Wrap the entire requirement in quotes to prevent the shell from splitting or expanding it (e.g., `\"flask[dotenv]\"`)".to_string(),
+            );
+        }
+    }
+
+    // e.g., `flask ; python_version < 3.12`, where the marker value lost its quotes.
+    if let Some(position) = spec.find(';') {
+        let markers = &spec[position + 1..];
+        if err.start > position && !markers.contains(['\'', '"']) {
+            return Some(format!(
+                "Marker values must be quoted. Wrap the requirement in double quotes, and the marker values in single quotes (e.g., `\"{} ; python_version < '3.12'\"`)",
+                spec[..position].trim()
+            ));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_hint(spec: &str) -> Option<String> {
+        parse_command_line_requirement(spec, std::env::current_dir().unwrap())
+            .unwrap_err()
+            .hint()
+            .map(ToString::to_string)
+    }
+
+    #[test]
+    fn hint_literal_quotes() {
+        let hint = parse_hint("'flask>=2'").unwrap();
+        assert!(hint.contains("`\"flask>=2\"`"), "{hint}");
+    }
+
+    #[test]
+    fn hint_split_specifier() {
+        let hint = parse_hint(">=2").unwrap();
+        assert!(hint.contains("Version specifiers must follow a package name"));
+    }
+
+    #[test]
+    fn hint_unterminated_extras() {
+        let hint = parse_hint("flask[dotenv").unwrap();
+        assert!(hint.contains("closing `]`"), "{hint}");
+    }
+
+    #[test]
+    fn hint_unquoted_marker() {
+        let hint = parse_hint("flask ; python_version < 3.12").unwrap();
+        assert!(
+            hint.contains("`\"flask ; python_version < '3.12'\"`"),
+            "{hint}"
+        );
+    }
+
+    #[test]
+    fn non_ascii_marker() {
+        let err = parse_command_line_requirement(
+            "flask ; python_version >= '3.8' üé",
+            std::env::current_dir().unwrap(),
+        )
+        .unwrap_err();
+        let source = std::error::Error::source(&err).unwrap().to_string();
+        assert!(source.contains("Unexpected character 'ü'"), "{source}");
+    }
+
+    #[test]
+    fn no_hint() {
+        assert_eq!(parse_hint("flask==1.0.x"), None);
+    }
+}
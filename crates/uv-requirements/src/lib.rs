@@ -1,3 +1,4 @@
+pub use crate::command_line::CommandLineRequirementError;
 pub use crate::lookahead::*;
 pub use crate::source_tree::*;
 pub use crate::sources::*;
@@ -5,6 +6,7 @@ pub use crate::specification::*;
 pub use crate::unnamed::*;
 pub use crate::workspace::*;
 
+mod command_line;
 mod confirm;
 mod lookahead;
 pub mod pyproject;
@@ -113,11 +113,10 @@ impl<'a, Context: BuildContext> LookaheadResolver<'a, Context> {
             .apply(self.overrides.apply(self.requirements))
             .filter(|requirement| requirement.evaluate_markers(markers, &[]))
             .chain(self.editables.iter().flat_map(|editable| {
+                let extras = editable.metadata.expand_extras(&editable.built.extras);
                 self.constraints
                     .apply(self.overrides.apply(&editable.requirements.dependencies))
-                    .filter(|requirement| {
-                        requirement.evaluate_markers(markers, &editable.built.extras)
-                    })
+                    .filter(move |requirement| requirement.evaluate_markers(markers, &extras))
             }))
             .cloned()
             .collect();
@@ -189,8 +188,9 @@ impl<'a, Context: BuildContext> LookaheadResolver<'a, Context> {
             } => Dist::from_file_url(requirement.name, &path, false, url)?,
         };
 
-        // Fetch the metadata for the distribution.
-        let requires_dist = {
+        // Fetch the metadata for the distribution, and determine the extras to enable, including
+        // any that are enabled transitively by the requested extras (e.g., `black[all]`).
+        let (requires_dist, extras) = {
             let id = dist.version_id();
             if let Some(archive) =
                 self.index
@@ -206,13 +206,17 @@ impl<'a, Context: BuildContext> LookaheadResolver<'a, Context> {
                     })
             {
                 // If the metadata is already in the index, return it.
-                archive
+                let requires_dist = archive
                     .metadata
                     .requires_dist
                     .iter()
                     .cloned()
                     .map(Requirement::from_pep508)
-                    .collect::<Result<_, _>>()?
+                    .collect::<Result<_, _>>()?;
+                (
+                    requires_dist,
+                    archive.metadata.expand_extras(&requirement.extras),
+                )
             } else {
                 // Run the PEP 517 build process to extract metadata from the source distribution.
                 let archive = self
@@ -227,16 +231,18 @@ impl<'a, Context: BuildContext> LookaheadResolver<'a, Context> {
                     })?;
 
                 let requires_dist = archive.metadata.requires_dist.clone();
+                let extras = archive.metadata.expand_extras(&requirement.extras);
 
                 // Insert the metadata into the index.
                 self.index
                     .distributions()
                     .done(id, Arc::new(MetadataResponse::Found(archive)));
 
-                requires_dist
+                let requires_dist = requires_dist
                     .into_iter()
                     .map(Requirement::from_pep508)
-                    .collect::<Result<_, _>>()?
+                    .collect::<Result<_, _>>()?;
+                (requires_dist, extras)
             }
         };
 
@@ -249,7 +255,7 @@ impl<'a, Context: BuildContext> LookaheadResolver<'a, Context> {
 
         // Return the requirements from the metadata.
         Ok(Some(RequestedRequirements::new(
-            requirement.extras,
+            extras,
             requires_dist,
             direct,
         )))
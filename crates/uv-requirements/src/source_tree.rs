@@ -13,11 +13,23 @@ use distribution_types::{
 use pep508_rs::RequirementOrigin;
 use uv_distribution::{DistributionDatabase, Reporter};
 use uv_fs::Simplified;
+use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{InMemoryIndex, MetadataResponse};
 use uv_types::{BuildContext, HashStrategy};
 
 use crate::ExtrasSpecification;
 
+/// The requirements of a source tree, along with the project's name and the extras it provides.
+#[derive(Debug, Clone)]
+pub struct SourceTreeResolution {
+    /// The requirements of the source tree, with the requested extras applied.
+    pub requirements: Vec<Requirement>,
+    /// The name of the project in the source tree.
+    pub project: PackageName,
+    /// The extras provided by the project.
+    pub extras: Vec<ExtraName>,
+}
+
 /// A resolver for requirements specified via source trees.
 ///
 /// Used, e.g., to determine the input requirements when a user specifies a `pyproject.toml`
@@ -63,23 +75,17 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
     }
 
     /// Resolve the requirements from the provided source trees.
-    pub async fn resolve(self) -> Result<Vec<Requirement>> {
-        let requirements: Vec<_> = self
-            .source_trees
+    pub async fn resolve(self) -> Result<Vec<SourceTreeResolution>> {
+        self.source_trees
             .iter()
             .map(|source_tree| async { self.resolve_source_tree(source_tree).await })
             .collect::<FuturesOrdered<_>>()
             .try_collect()
-            .await?;
-        Ok(requirements
-            .into_iter()
-            .flatten()
-            .map(Requirement::from_pep508)
-            .collect::<Result<_, _>>()?)
+            .await
     }
 
-    /// Infer the package name for a given "unnamed" requirement.
-    async fn resolve_source_tree(&self, path: &Path) -> Result<Vec<pep508_rs::Requirement>> {
+    /// Resolve the requirements for a given source tree, building its metadata if necessary.
+    async fn resolve_source_tree(&self, path: &Path) -> Result<SourceTreeResolution> {
         // Convert to a buildable source.
         let source_tree = fs_err::canonicalize(path).with_context(|| {
             format!(
@@ -106,7 +112,7 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
         // manual match.
         let hashes = match self.hasher {
             HashStrategy::None => HashPolicy::None,
-            HashStrategy::Generate => HashPolicy::Generate,
+            HashStrategy::Generate | HashStrategy::Collect => HashPolicy::Generate,
             HashStrategy::Validate { .. } => {
                 return Err(anyhow::anyhow!(
                     "Hash-checking is not supported for local directories: {}",
@@ -150,37 +156,38 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
         // Extract the origin.
         let origin = RequirementOrigin::Project(path.to_path_buf(), metadata.name.clone());
 
+        // Determine the extras to enable, including any that are enabled by the project's own
+        // extras (e.g., an `all` extra that requires `project[docs,test]`).
+        let extras: &[ExtraName] = match self.extras {
+            ExtrasSpecification::None => &[],
+            ExtrasSpecification::All => &metadata.provides_extras,
+            ExtrasSpecification::Some(extras) => extras,
+        };
+        let extras = metadata.expand_extras(extras);
+
         // Determine the appropriate requirements to return based on the extras. This involves
         // evaluating the `extras` expression in any markers, but preserving the remaining marker
-        // conditions.
-        match self.extras {
-            ExtrasSpecification::None => Ok(metadata
-                .requires_dist
-                .into_iter()
-                .map(|requirement| requirement.with_origin(origin.clone()))
-                .collect()),
-            ExtrasSpecification::All => Ok(metadata
-                .requires_dist
-                .into_iter()
-                .map(|requirement| pep508_rs::Requirement {
-                    origin: Some(origin.clone()),
-                    marker: requirement
-                        .marker
-                        .and_then(|marker| marker.simplify_extras(&metadata.provides_extras)),
-                    ..requirement
-                })
-                .collect()),
-            ExtrasSpecification::Some(extras) => Ok(metadata
-                .requires_dist
-                .into_iter()
-                .map(|requirement| pep508_rs::Requirement {
+        // conditions. Requirements on the project itself are replaced by the requirements of the
+        // extras they enable.
+        let requirements = metadata
+            .requires_dist
+            .into_iter()
+            .filter(|requirement| requirement.name != metadata.name)
+            .map(|requirement| {
+                Requirement::from_pep508(pep508_rs::Requirement {
                     origin: Some(origin.clone()),
                     marker: requirement
                         .marker
-                        .and_then(|marker| marker.simplify_extras(extras)),
+                        .and_then(|marker| marker.simplify_extras(&extras)),
                     ..requirement
                 })
-                .collect()),
-        }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SourceTreeResolution {
+            requirements,
+            project: metadata.name,
+            extras: metadata.provides_extras,
+        })
     }
 }
@@ -12,14 +12,13 @@ use distribution_types::{
     UnresolvedRequirementSpecification,
 };
 use pep508_rs::{UnnamedRequirement, VerbatimUrl};
-use requirements_txt::{
-    EditableRequirement, FindLink, RequirementEntry, RequirementsTxt, RequirementsTxtRequirement,
-};
+use requirements_txt::{EditableRequirement, FindLink, RequirementEntry, RequirementsTxt};
 use uv_client::BaseClientBuilder;
-use uv_configuration::{NoBinary, NoBuild, PreviewMode};
+use uv_configuration::{NoBinary, NoBuild, Patches, PreviewMode};
 use uv_fs::Simplified;
 use uv_normalize::{ExtraName, PackageName};
 
+use crate::command_line::parse_command_line_requirement;
 use crate::pyproject::{Pep621Metadata, PyProjectToml};
 use crate::{ExtrasSpecification, RequirementsSource};
 
@@ -31,6 +30,8 @@ pub struct RequirementsSpecification {
     pub requirements: Vec<UnresolvedRequirementSpecification>,
     /// The constraints for the project.
     pub constraints: Vec<Requirement>,
+    /// The hashes attached to constraints, enforced in `--require-hashes` mode.
+    pub constraint_hashes: Vec<(Requirement, Vec<String>)>,
     /// The overrides for the project.
     pub overrides: Vec<UnresolvedRequirementSpecification>,
     /// Package to install as editable installs
@@ -64,8 +65,7 @@ impl RequirementsSpecification {
     ) -> Result<Self> {
         Ok(match source {
             RequirementsSource::Package(name) => {
-                let requirement = RequirementsTxtRequirement::parse(name, std::env::current_dir()?)
-                    .with_context(|| format!("Failed to parse: `{name}`"))?;
+                let requirement = parse_command_line_requirement(name, std::env::current_dir()?)?;
                 Self {
                     requirements: vec![UnresolvedRequirementSpecification::try_from(
                         RequirementEntry {
@@ -273,12 +273,17 @@ impl RequirementsSpecification {
         }
 
         // Read all constraints, treating both requirements _and_ constraints as constraints.
-        // Overrides are ignored, as are the hashes, as they are not relevant for constraints.
+        // Overrides are ignored. Any hashes are retained separately, to restrict the distributions
+        // that can be selected in `--require-hashes` mode.
         for source in constraints {
             let source = Self::from_source(source, extras, client_builder, preview).await?;
             for entry in source.requirements {
                 match entry.requirement {
                     UnresolvedRequirement::Named(requirement) => {
+                        if !entry.hashes.is_empty() {
+                            spec.constraint_hashes
+                                .push((requirement.clone(), entry.hashes));
+                        }
                         spec.constraints.push(requirement);
                     }
                     UnresolvedRequirement::Unnamed(requirement) => {
@@ -334,6 +339,37 @@ impl RequirementsSpecification {
         Ok(spec)
     }
 
+    /// Redirect any requirements on a patched package to the patched source.
+    ///
+    /// Named requirements and overrides are rewritten in place, retaining their extras and
+    /// markers. Each patch is also added as a constraint, such that it applies to transitive
+    /// requirements on the package, too.
+    #[must_use]
+    pub fn with_patches(mut self, patches: &Patches) -> Self {
+        if patches.is_empty() {
+            return self;
+        }
+        let patch = |specification: UnresolvedRequirementSpecification| match specification
+            .requirement
+        {
+            UnresolvedRequirement::Named(requirement) if patches.contains(&requirement.name) => {
+                UnresolvedRequirementSpecification {
+                    requirement: UnresolvedRequirement::Named(patches.apply(requirement)),
+                    // Any hashes refer to the original source, rather than the patched source.
+                    hashes: vec![],
+                }
+            }
+            requirement => UnresolvedRequirementSpecification {
+                requirement,
+                hashes: specification.hashes,
+            },
+        };
+        self.requirements = self.requirements.into_iter().map(patch).collect();
+        self.overrides = self.overrides.into_iter().map(patch).collect();
+        self.constraints.extend(patches.requirements());
+        self
+    }
+
     /// Read the requirements from a set of sources.
     pub async fn from_simple_sources(
         requirements: &[RequirementsSource],
@@ -7,7 +7,7 @@ use glob::{glob, GlobError, PatternError};
 use tracing::{debug, trace};
 
 use uv_fs::Simplified;
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 use uv_warnings::warn_user;
 
 use crate::pyproject::{PyProjectToml, Source, ToolUvWorkspace};
@@ -213,6 +213,18 @@ impl ProjectWorkspace {
         ]
     }
 
+    /// Return the extras provided by the project, as declared in its
+    /// `project.optional-dependencies`.
+    pub fn extras(&self) -> Vec<ExtraName> {
+        self.workspace
+            .packages
+            .get(&self.project_name)
+            .and_then(|member| member.pyproject_toml.project.as_ref())
+            .and_then(|project| project.optional_dependencies.as_ref())
+            .map(|optional_dependencies| optional_dependencies.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
     fn from_project_root(path: &Path) -> Result<Self, WorkspaceError> {
         let pyproject_path = path.join("pyproject.toml");
 
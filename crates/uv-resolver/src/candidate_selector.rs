@@ -218,6 +218,7 @@ impl CandidateSelector {
         );
         let highest = self.use_highest_version(package_name);
         let allow_prerelease = self.allow_prereleases(package_name);
+        let prefer_binary = version_maps.iter().any(VersionMap::prefer_binary);
 
         if self.index_strategy == IndexStrategy::UnsafeBestMatch {
             if highest {
@@ -229,6 +230,7 @@ impl CandidateSelector {
                     package_name,
                     range,
                     allow_prerelease,
+                    prefer_binary,
                 )
             } else {
                 Self::select_candidate(
@@ -239,6 +241,7 @@ impl CandidateSelector {
                     package_name,
                     range,
                     allow_prerelease,
+                    prefer_binary,
                 )
             }
         } else {
@@ -249,6 +252,7 @@ impl CandidateSelector {
                         package_name,
                         range,
                         allow_prerelease,
+                        prefer_binary,
                     )
                 })
             } else {
@@ -258,6 +262,7 @@ impl CandidateSelector {
                         package_name,
                         range,
                         allow_prerelease,
+                        prefer_binary,
                     )
                 })
             }
@@ -278,11 +283,15 @@ impl CandidateSelector {
 
     /// Select the first-matching [`Candidate`] from a set of candidate versions and files,
     /// preferring wheels over source distributions.
+    ///
+    /// If `prefer_binary` is set, versions that lack a compatible wheel are skipped in favor of
+    /// the first version that has one, falling back to the first-matching version otherwise.
     fn select_candidate<'a>(
         versions: impl Iterator<Item = (&'a Version, VersionMapDistHandle<'a>)>,
         package_name: &'a PackageName,
         range: &Range<Version>,
         allow_prerelease: AllowPreRelease,
+        prefer_binary: bool,
     ) -> Option<Candidate<'a>> {
         #[derive(Debug)]
         enum PreReleaseCandidate<'a> {
@@ -291,6 +300,7 @@ impl CandidateSelector {
         }
 
         let mut prerelease = None;
+        let mut source_only = None;
         let mut steps = 0usize;
         for (version, maybe_dist) in versions {
             steps += 1;
@@ -372,6 +382,20 @@ impl CandidateSelector {
                 continue;
             }
 
+            // If the candidate would need to be built from source, look for an older version with
+            // a compatible wheel, but remember the first such candidate as a fallback.
+            if prefer_binary && candidate.is_source_only() {
+                tracing::trace!(
+                    "skipping source-only candidate for package {:?}: {:?}",
+                    package_name,
+                    candidate.version(),
+                );
+                if source_only.is_none() {
+                    source_only = Some(candidate);
+                }
+                continue;
+            }
+
             return Some(candidate);
         }
         tracing::trace!(
@@ -381,6 +405,9 @@ impl CandidateSelector {
             range,
             steps,
         );
+        if let Some(candidate) = source_only {
+            return Some(candidate);
+        }
         match prerelease {
             None => None,
             Some(PreReleaseCandidate::NotNecessary) => None,
@@ -460,6 +487,20 @@ impl<'a> Candidate<'a> {
     pub(crate) fn dist(&self) -> &CandidateDist<'a> {
         &self.dist
     }
+
+    /// Returns `true` if the candidate lacks a compatible wheel, and so would need to be built
+    /// from its source distribution.
+    fn is_source_only(&self) -> bool {
+        match self.compatible() {
+            Some(
+                CompatibleDist::SourceDist { prioritized, .. }
+                | CompatibleDist::IncompatibleWheel { prioritized, .. },
+            ) => !prioritized
+                .best_wheel()
+                .is_some_and(|(_, compatibility)| compatibility.is_compatible()),
+            _ => false,
+        }
+    }
 }
 
 impl Name for Candidate<'_> {
@@ -2,11 +2,13 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Formatter;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Duration;
 
 use indexmap::IndexMap;
 use pubgrub::range::Range;
-use pubgrub::report::{DefaultStringReporter, DerivationTree, External, Reporter};
+use pubgrub::report::{DefaultStringReporter, DerivationTree, External, ReportFormatter, Reporter};
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
 
 use dashmap::DashMap;
 use distribution_types::{BuiltDist, IndexLocations, InstalledDist, ParsedUrlError, SourceDist};
@@ -16,7 +18,9 @@ use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
 use crate::dependency_provider::UvDependencyProvider;
-use crate::pubgrub::{PubGrubPackage, PubGrubPackageInner, PubGrubPython, PubGrubReportFormatter};
+use crate::pubgrub::{
+    PubGrubHint, PubGrubPackage, PubGrubPackageInner, PubGrubPython, PubGrubReportFormatter,
+};
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{
     FxOnceMap, IncompletePackage, UnavailablePackage, UnavailableReason, VersionsResponse,
@@ -82,6 +86,12 @@ pub enum ResolveError {
     #[error(transparent)]
     NoSolution(#[from] NoSolutionError),
 
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceededError),
+
+    #[error("The resolution was cancelled")]
+    Cancelled,
+
     #[error("{package} {version} depends on itself")]
     SelfDependency {
         /// Package whose dependencies we want.
@@ -113,6 +123,67 @@ impl<T> From<tokio::sync::mpsc::error::SendError<T>> for ResolveError {
     }
 }
 
+/// The limit that caused a resolution to be aborted.
+#[derive(Debug, Clone, Copy)]
+pub enum ResolutionLimit {
+    /// The resolver backtracked more than the given number of times.
+    Backtracks(usize),
+    /// The resolver ran for longer than the given duration.
+    Timeout(Duration),
+}
+
+/// A resolution that was aborted for exceeding one of its [`crate::ResolutionLimits`].
+#[derive(Debug)]
+pub struct LimitExceededError {
+    pub(crate) limit: ResolutionLimit,
+    /// The packages on which the resolver backtracked, and the number of times it did so, in
+    /// descending order.
+    pub(crate) backtracks: Vec<(PackageName, usize)>,
+}
+
+impl LimitExceededError {
+    /// The limit that was exceeded.
+    pub fn limit(&self) -> ResolutionLimit {
+        self.limit
+    }
+
+    /// The packages that caused the most backtracking, and the number of times the resolver
+    /// backtracked on each, in descending order.
+    pub fn backtracks(&self) -> &[(PackageName, usize)] {
+        &self.backtracks
+    }
+}
+
+impl std::error::Error for LimitExceededError {}
+
+impl std::fmt::Display for LimitExceededError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.limit {
+            ResolutionLimit::Backtracks(limit) => write!(
+                f,
+                "Resolution exceeded the limit of {limit} backtrack{}",
+                if limit == 1 { "" } else { "s" }
+            )?,
+            ResolutionLimit::Timeout(timeout) => write!(
+                f,
+                "Resolution timed out after {:.1}s",
+                timeout.as_secs_f64()
+            )?,
+        }
+        if !self.backtracks.is_empty() {
+            write!(f, "; the packages that caused the most backtracking were:")?;
+            for (package, count) in self.backtracks.iter().take(10) {
+                write!(
+                    f,
+                    "\n  - {package} ({count} backtrack{})",
+                    if *count == 1 { "" } else { "s" }
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Given a [`DerivationTree`], collapse any [`External::FromDependencyOf`] incompatibilities
 /// wrap an [`PubGrubPackageInner::Extra`] package.
 fn collapse_extra_proxies(
@@ -196,6 +267,76 @@ pub struct NoSolutionError {
 
 impl std::error::Error for NoSolutionError {}
 
+/// A change to the inputs of a failed resolution that may allow it to succeed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Amendment {
+    /// Remove the version specifier from a direct requirement.
+    RelaxRequirement(PackageName),
+    /// Allow pre-release versions for all packages.
+    AllowPreReleases,
+    /// Exclude a transitive dependency from the resolution entirely.
+    ExcludePackage(PackageName),
+}
+
+impl std::fmt::Display for Amendment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RelaxRequirement(name) => write!(f, "relax the requirement on `{name}`"),
+            Self::AllowPreReleases => write!(f, "allow pre-release versions"),
+            Self::ExcludePackage(name) => write!(f, "exclude `{name}` from the resolution"),
+        }
+    }
+}
+
+/// A machine-readable representation of a [`NoSolutionError`].
+#[derive(Debug, Serialize)]
+pub struct NoSolutionReport {
+    /// The human-readable explanation of the resolution failure.
+    pub report: String,
+    /// The derivation tree from which the explanation was derived.
+    pub derivation_tree: DerivationNode,
+    /// Any hints that may help the user resolve the failure.
+    pub hints: Vec<String>,
+    /// Suggested changes to the requirements or environment that may resolve the failure.
+    pub suggestions: Vec<String>,
+}
+
+/// A node in the derivation tree of a [`NoSolutionError`].
+///
+/// Leaves correspond to external facts (e.g., "foo depends on bar>=2"), while inner nodes are
+/// incompatibilities derived from their two causes.
+#[derive(Debug, Serialize)]
+pub struct DerivationNode {
+    /// A human-readable description of the incompatibility.
+    pub message: String,
+    /// The incompatibilities from which this one was derived, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<DerivationNode>,
+}
+
+impl DerivationNode {
+    fn new(
+        tree: &DerivationTree<PubGrubPackage, Range<Version>, UnavailableReason>,
+        formatter: &PubGrubReportFormatter,
+    ) -> Self {
+        match tree {
+            DerivationTree::External(external) => Self {
+                message: anstream::adapter::strip_str(&formatter.format_external(external))
+                    .to_string(),
+                causes: Vec::new(),
+            },
+            DerivationTree::Derived(derived) => Self {
+                message: anstream::adapter::strip_str(&formatter.format_terms(&derived.terms))
+                    .to_string(),
+                causes: vec![
+                    Self::new(&derived.cause1, formatter),
+                    Self::new(&derived.cause2, formatter),
+                ],
+            },
+        }
+    }
+}
+
 impl std::fmt::Display for NoSolutionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         // Write the derivation report.
@@ -223,6 +364,131 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
+    /// Return a structured representation of the error, for machine-readable output.
+    pub fn report(&self) -> NoSolutionReport {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        let report =
+            DefaultStringReporter::report_with_formatter(&self.derivation_tree, &formatter);
+        let hints = formatter
+            .hints(
+                &self.derivation_tree,
+                &self.selector,
+                &self.index_locations,
+                &self.unavailable_packages,
+                &self.incomplete_packages,
+            )
+            .into_iter()
+            .map(|hint| anstream::adapter::strip_str(&hint.to_string()).to_string())
+            .collect();
+        let suggestions = formatter
+            .suggestions(&self.derivation_tree)
+            .into_iter()
+            .map(|suggestion| anstream::adapter::strip_str(&suggestion.to_string()).to_string())
+            .collect();
+        NoSolutionReport {
+            report: anstream::adapter::strip_str(&report).to_string(),
+            derivation_tree: DerivationNode::new(&self.derivation_tree, &formatter),
+            hints,
+            suggestions,
+        }
+    }
+
+    /// Return suggested changes to the user's requirements or environment that may resolve the
+    /// failure, such as the nearest available versions of a package, a direct requirement to
+    /// relax, or a Python version that would satisfy the conflicting packages.
+    pub fn suggestions(&self) -> Vec<String> {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        formatter
+            .suggestions(&self.derivation_tree)
+            .into_iter()
+            .map(|suggestion| suggestion.to_string())
+            .collect()
+    }
+
+    /// Return the changes to the resolution inputs that could be applied to work around the
+    /// failure, e.g., as offered by `uv pip compile --interactive`.
+    pub fn amendments(&self) -> Vec<Amendment> {
+        /// Collect the direct and transitive dependencies involved in the failure.
+        fn collect(
+            tree: &DerivationTree<PubGrubPackage, Range<Version>, UnavailableReason>,
+            direct: &mut BTreeSet<PackageName>,
+            transitive: &mut BTreeSet<PackageName>,
+        ) {
+            match tree {
+                DerivationTree::External(External::FromDependencyOf(
+                    package,
+                    _,
+                    dependency,
+                    dependency_set,
+                )) => {
+                    let PubGrubPackageInner::Package { name, .. } = &**dependency else {
+                        return;
+                    };
+                    match &**package {
+                        PubGrubPackageInner::Root(_) => {
+                            if dependency_set != &Range::full() {
+                                direct.insert(name.clone());
+                            }
+                        }
+                        PubGrubPackageInner::Package { .. } | PubGrubPackageInner::Extra { .. } => {
+                            transitive.insert(name.clone());
+                        }
+                        PubGrubPackageInner::Python(_) => {}
+                    }
+                }
+                DerivationTree::External(_) => {}
+                DerivationTree::Derived(derived) => {
+                    collect(&derived.cause1, direct, transitive);
+                    collect(&derived.cause2, direct, transitive);
+                }
+            }
+        }
+
+        let mut direct = BTreeSet::new();
+        let mut transitive = BTreeSet::new();
+        collect(&self.derivation_tree, &mut direct, &mut transitive);
+
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        let prerelease = formatter
+            .hints(
+                &self.derivation_tree,
+                &self.selector,
+                &self.index_locations,
+                &self.unavailable_packages,
+                &self.incomplete_packages,
+            )
+            .iter()
+            .any(|hint| {
+                matches!(
+                    hint,
+                    PubGrubHint::PreReleaseAvailable { .. }
+                        | PubGrubHint::PreReleaseRequested { .. }
+                )
+            });
+
+        let mut amendments = Vec::new();
+        amendments.extend(direct.iter().cloned().map(Amendment::RelaxRequirement));
+        if prerelease {
+            amendments.push(Amendment::AllowPreReleases);
+        }
+        amendments.extend(
+            transitive
+                .into_iter()
+                .filter(|name| !direct.contains(name))
+                .map(Amendment::ExcludePackage),
+        );
+        amendments
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -1,7 +1,7 @@
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::instrument;
 
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
@@ -27,6 +27,9 @@ pub struct FlatIndex {
     /// Whether any `--find-links` entries could not be resolved due to a lack of network
     /// connectivity.
     offline: bool,
+    /// The packages provided by local build directories (e.g., `dist/`), which are resolved from
+    /// the flat index alone, without consulting the registry.
+    local: FxHashSet<PackageName>,
 }
 
 impl FlatIndex {
@@ -58,7 +61,41 @@ impl FlatIndex {
         // Collect offline entries.
         let offline = entries.offline;
 
-        Self { index, offline }
+        Self {
+            index,
+            offline,
+            local: FxHashSet::default(),
+        }
+    }
+
+    /// Add the files from local build directories (e.g., `dist/`) to the [`FlatIndex`].
+    ///
+    /// Any package with a file in a local build directory is resolved from the flat index alone,
+    /// such that the locally-built distributions take precedence over those in the registry.
+    #[must_use]
+    pub fn with_local_entries(
+        mut self,
+        entries: FlatIndexEntries,
+        tags: &Tags,
+        hasher: &HashStrategy,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
+    ) -> Self {
+        for (filename, file, url) in entries.entries {
+            self.local.insert(filename.name().clone());
+            let distributions = self.index.entry(filename.name().clone()).or_default();
+            Self::add_file(
+                distributions,
+                file,
+                filename,
+                tags,
+                hasher,
+                no_build,
+                no_binary,
+                url,
+            );
+        }
+        self
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -78,8 +115,14 @@ impl FlatIndex {
             DistFilename::WheelFilename(filename) => {
                 let version = filename.version.clone();
 
-                let compatibility =
-                    Self::wheel_compatibility(&filename, &file.hashes, tags, hasher, no_binary);
+                let compatibility = Self::wheel_compatibility(
+                    &filename,
+                    &file.hashes,
+                    tags,
+                    hasher,
+                    no_binary,
+                    no_build,
+                );
                 let dist = RegistryBuiltWheel {
                     filename,
                     file: Box::new(file),
@@ -95,8 +138,13 @@ impl FlatIndex {
                 }
             }
             DistFilename::SourceDistFilename(filename) => {
-                let compatibility =
-                    Self::source_dist_compatibility(&filename, &file.hashes, hasher, no_build);
+                let compatibility = Self::source_dist_compatibility(
+                    &filename,
+                    &file.hashes,
+                    hasher,
+                    no_build,
+                    no_binary,
+                );
                 let dist = RegistrySourceDist {
                     name: filename.name.clone(),
                     version: filename.version.clone(),
@@ -121,18 +169,23 @@ impl FlatIndex {
         hashes: &[HashDigest],
         hasher: &HashStrategy,
         no_build: &NoBuild,
+        no_binary: &NoBinary,
     ) -> SourceDistCompatibility {
         // Check if source distributions are allowed for this package.
-        let no_build = match no_build {
-            NoBuild::None => false,
-            NoBuild::All => true,
-            NoBuild::Packages(packages) => packages.contains(&filename.name),
-        };
-
-        if no_build {
+        if no_build.no_build_package(&filename.name, no_binary) {
             return SourceDistCompatibility::Incompatible(IncompatibleSource::NoBuild);
         }
 
+        // Check if the hashes satisfy the hash constraint, if any.
+        if let Some(constraint) = hasher
+            .get_constraint(&filename.name)
+            .filter(|constraint| !constraint.allows(hashes))
+        {
+            return SourceDistCompatibility::Incompatible(IncompatibleSource::MismatchedHash(
+                constraint.requirement.clone(),
+            ));
+        }
+
         // Check if hashes line up
         let hash = if let HashPolicy::Validate(required) = hasher.get_package(&filename.name) {
             if hashes.is_empty() {
@@ -155,15 +208,10 @@ impl FlatIndex {
         tags: &Tags,
         hasher: &HashStrategy,
         no_binary: &NoBinary,
+        no_build: &NoBuild,
     ) -> WheelCompatibility {
         // Check if binaries are allowed for this package.
-        let no_binary = match no_binary {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(&filename.name),
-        };
-
-        if no_binary {
+        if no_binary.no_binary_package(&filename.name, no_build) {
             return WheelCompatibility::Incompatible(IncompatibleWheel::NoBinary);
         }
 
@@ -175,6 +223,16 @@ impl FlatIndex {
             TagCompatibility::Compatible(priority) => priority,
         };
 
+        // Check if the hashes satisfy the hash constraint, if any.
+        if let Some(constraint) = hasher
+            .get_constraint(&filename.name)
+            .filter(|constraint| !constraint.allows(hashes))
+        {
+            return WheelCompatibility::Incompatible(IncompatibleWheel::MismatchedHash(
+                constraint.requirement.clone(),
+            ));
+        }
+
         // Check if hashes line up
         let hash = if let HashPolicy::Validate(required) = hasher.get_package(&filename.name) {
             if hashes.is_empty() {
@@ -191,6 +249,12 @@ impl FlatIndex {
         WheelCompatibility::Compatible(hash, priority)
     }
 
+    /// Returns `true` if the package is provided by a local build directory, and should be
+    /// resolved from the flat index alone.
+    pub fn is_local(&self, package_name: &PackageName) -> bool {
+        self.local.contains(package_name)
+    }
+
     /// Get the [`FlatDistributions`] for the given package name.
     pub fn get(&self, package_name: &PackageName) -> Option<&FlatDistributions> {
         self.index.get(package_name)
@@ -200,6 +264,11 @@ impl FlatIndex {
     pub fn offline(&self) -> bool {
         self.offline
     }
+
+    /// Returns the names of all packages in the flat index.
+    pub fn package_names(&self) -> impl Iterator<Item = &PackageName> {
+        self.index.keys()
+    }
 }
 
 /// A set of [`PrioritizedDist`] from a `--find-links` entry for a single package, indexed
@@ -1,20 +1,30 @@
 pub use dependency_mode::DependencyMode;
 pub use editables::BuiltEditableMetadata;
-pub use error::ResolveError;
+pub use error::{
+    Amendment, DerivationNode, LimitExceededError, NoSolutionError, NoSolutionReport,
+    ResolutionLimit, ResolveError,
+};
 pub use exclude_newer::ExcludeNewer;
 pub use exclusions::Exclusions;
 pub use flat_index::FlatIndex;
-pub use lock::{Lock, LockError};
+pub use lock::{InstallLayer, LayerKind, LayerRequirement, Lock, LockError};
 pub use manifest::Manifest;
-pub use options::{Options, OptionsBuilder};
+pub use marker::{simplify_markers, DisplayMarkers};
+pub use options::{Options, OptionsBuilder, ResolutionLimits};
+pub use package_policy::{DeniedPackage, PackagePolicy};
 pub use preferences::{Preference, PreferenceError};
+pub use prefetch::{PrefetchSetting, PrefetchStrategy};
 pub use prerelease_mode::PreReleaseMode;
+pub use pubgrub::is_satisfiable;
 pub use python_requirement::PythonRequirement;
-pub use resolution::{AnnotationStyle, DisplayResolutionGraph, ResolutionGraph};
+pub use release_age::{MinReleaseAge, PackageReleaseAge, ReleaseAge};
+pub use resolution::{
+    AnnotationStyle, DisplayDependencyGraph, DisplayResolutionGraph, GraphFormat, ResolutionGraph,
+};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, MetadataResponse, PackageVersionsResult,
-    Reporter as ResolverReporter, Resolver, ResolverProvider, VersionsResponse,
+    Reporter as ResolverReporter, ResolveReporter, Resolver, ResolverProvider, VersionsResponse,
     WheelMetadataResult,
 };
 pub use version_map::VersionMap;
@@ -34,14 +44,18 @@ mod lock;
 mod manifest;
 mod marker;
 mod options;
+mod package_policy;
 mod pins;
 mod preferences;
+mod prefetch;
 mod prerelease_mode;
 mod pubgrub;
 mod python_requirement;
 mod redirect;
+mod release_age;
 mod resolution;
 mod resolution_mode;
 mod resolver;
+mod similar;
 mod version_map;
 mod yanks;
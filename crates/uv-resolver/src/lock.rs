@@ -2,11 +2,12 @@
 // as we build out universal locking.
 #![allow(dead_code, unreachable_code, unused_variables)]
 
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use url::Url;
 
 use distribution_filename::WheelFilename;
@@ -17,12 +18,13 @@ use distribution_types::{
     ResolvedDist, ToUrlError,
 };
 use pep440_rs::Version;
-use pep508_rs::{MarkerEnvironment, VerbatimUrl};
+use pep508_rs::{MarkerEnvironment, MarkerTree, VerbatimUrl};
 use platform_tags::{TagCompatibility, TagPriority, Tags};
 use pypi_types::HashDigest;
 use uv_git::{GitReference, GitSha};
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 
+use crate::marker::resolve_extras;
 use crate::resolution::AnnotatedDist;
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
@@ -53,11 +55,14 @@ impl Lock {
         Lock::try_from(wire)
     }
 
+    /// Convert the distributions reachable from the root distribution to a [`Resolution`],
+    /// including the optional dependencies of the root's enabled extras.
     pub fn to_resolution(
         &self,
         marker_env: &MarkerEnvironment,
         tags: &Tags,
         root_name: &PackageName,
+        extras: &impl Fn(&ExtraName) -> bool,
     ) -> Resolution {
         let root = self
             .find_by_name(root_name)
@@ -68,21 +73,144 @@ impl Lock {
             // correct.
             .expect("found too many distributions matching root")
             .expect("could not find root");
-        let mut queue: VecDeque<&Distribution> = VecDeque::new();
-        queue.push_back(root);
 
-        let mut map = BTreeMap::default();
-        while let Some(dist) = queue.pop_front() {
-            for dep in &dist.dependencies {
+        let map = self
+            .reachable(root, extras)
+            .into_iter()
+            .map(|dist| {
+                (
+                    dist.id.name.clone(),
+                    ResolvedDist::Installable(dist.to_dist(marker_env, tags)),
+                )
+            })
+            .collect();
+        let diagnostics = vec![];
+        Resolution::new(map, diagnostics)
+    }
+
+    /// Returns the distributions reachable from the root distribution (including the root
+    /// itself), in breadth-first order.
+    ///
+    /// The optional dependencies of the root are followed for each enabled extra, as are those of
+    /// any extras that a dependency requests of another distribution (e.g., `black[d]`).
+    fn reachable<'lock>(
+        &'lock self,
+        root: &'lock Distribution,
+        extras: &impl Fn(&ExtraName) -> bool,
+    ) -> Vec<&'lock Distribution> {
+        let mut seen: FxHashSet<(&DistributionId, Option<&ExtraName>)> = FxHashSet::default();
+        let mut queue: VecDeque<(&Distribution, Option<&ExtraName>)> = VecDeque::new();
+        seen.insert((&root.id, None));
+        queue.push_back((root, None));
+        for extra in root.optional_dependencies.keys() {
+            if extras(extra) {
+                seen.insert((&root.id, Some(extra)));
+                queue.push_back((root, Some(extra)));
+            }
+        }
+
+        let mut reachable = Vec::new();
+        while let Some((dist, extra)) = queue.pop_front() {
+            let dependencies = match extra {
+                None => {
+                    reachable.push(dist);
+                    dist.dependencies.as_slice()
+                }
+                Some(extra) => dist
+                    .optional_dependencies
+                    .get(extra)
+                    .map_or(&[][..], Vec::as_slice),
+            };
+            for dep in dependencies {
                 let dep_dist = self.find_by_id(&dep.id);
-                queue.push_back(dep_dist);
+                if seen.insert((&dep.id, None)) {
+                    queue.push_back((dep_dist, None));
+                }
+                for extra in &dep.extra {
+                    if seen.insert((&dep.id, Some(extra))) {
+                        queue.push_back((dep_dist, Some(extra)));
+                    }
+                }
             }
-            let name = dist.id.name.clone();
-            let resolved_dist = ResolvedDist::Installable(dist.to_dist(marker_env, tags));
-            map.insert(name, resolved_dist);
         }
-        let diagnostics = vec![];
-        Resolution::new(map, diagnostics)
+        reachable
+    }
+
+    /// Convert every distribution in the lock to a [`Resolution`], rather than only those
+    /// reachable from a root distribution.
+    pub fn to_flat_resolution(&self, marker_env: &MarkerEnvironment, tags: &Tags) -> Resolution {
+        let map = self
+            .distributions
+            .iter()
+            .map(|dist| {
+                (
+                    dist.id.name.clone(),
+                    ResolvedDist::Installable(dist.to_dist(marker_env, tags)),
+                )
+            })
+            .collect();
+        Resolution::new(map, vec![])
+    }
+
+    /// Returns the name and version of each distribution in the lock that comes from a registry,
+    /// along with the URL of the registry against which it was locked.
+    pub fn registry_distributions(&self) -> impl Iterator<Item = (&PackageName, &Version, &Url)> {
+        self.distributions
+            .iter()
+            .filter(|dist| dist.id.source.kind == SourceKind::Registry)
+            .map(|dist| (&dist.id.name, &dist.id.version, &dist.id.source.url))
+    }
+
+    /// Partition the dependencies of the root distribution into an ordered set of install layers,
+    /// from the least to the most likely to change.
+    ///
+    /// Registry distributions are ordered by release age (per the given upload times, in
+    /// milliseconds since the epoch), such that those released before `stable_before` come first,
+    /// and then by whether they're transitive or direct dependencies of the root. Distributions
+    /// from any other source (e.g., Git or a local path) are placed in a final layer.
+    pub fn to_layers(
+        &self,
+        root_name: &PackageName,
+        upload_times: &FxHashMap<(PackageName, Version), i64>,
+        stable_before: i64,
+    ) -> Result<Vec<InstallLayer>, String> {
+        let root = self
+            .find_by_name(root_name)?
+            .ok_or_else(|| format!("could not find `{root_name}` in the lockfile"))?;
+
+        // Collect every distribution that's reachable from the root, without its extras.
+        let mut layers: BTreeMap<LayerKind, Vec<LayerRequirement>> = BTreeMap::new();
+        for dist in self.reachable(root, &|_| false) {
+            if dist.id == root.id {
+                continue;
+            }
+            let direct = root.dependencies.iter().any(|dep| dep.id == dist.id);
+            let kind = if dist.id.source.kind == SourceKind::Registry {
+                let stable = upload_times
+                    .get(&(dist.id.name.clone(), dist.id.version.clone()))
+                    .is_some_and(|&upload_time| upload_time < stable_before);
+                match (stable, direct) {
+                    (true, false) => LayerKind::StableTransitive,
+                    (true, true) => LayerKind::StableDirect,
+                    (false, false) => LayerKind::RecentTransitive,
+                    (false, true) => LayerKind::RecentDirect,
+                }
+            } else {
+                LayerKind::Unversioned
+            };
+            layers
+                .entry(kind)
+                .or_default()
+                .push(LayerRequirement::from_distribution(dist));
+        }
+
+        Ok(layers
+            .into_iter()
+            .map(|(kind, mut requirements)| {
+                requirements.sort_by(|a, b| a.requirement.cmp(&b.requirement));
+                InstallLayer { kind, requirements }
+            })
+            .collect())
     }
 
     /// Returns the distribution with the given name. If there are multiple
@@ -111,6 +239,116 @@ impl Lock {
     }
 }
 
+/// A set of locked requirements to install together, as a single layer of a container image.
+#[derive(Clone, Debug)]
+pub struct InstallLayer {
+    /// The kind of requirements in the layer.
+    pub kind: LayerKind,
+    /// The requirements in the layer, sorted by name.
+    pub requirements: Vec<LayerRequirement>,
+}
+
+impl InstallLayer {
+    /// Returns `true` if every requirement in the layer is pinned to at least one hash, such that
+    /// the layer can be installed in hash-checking mode.
+    ///
+    /// Requirements without hashes include those on Git repositories and local directories, as
+    /// well as registry distributions that were locked without hashes.
+    pub fn requires_hashes(&self) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| !requirement.hashes.is_empty())
+    }
+}
+
+/// The kinds of [`InstallLayer`], ordered from the least to the most likely to change.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LayerKind {
+    /// Transitive dependencies on releases that predate the stability cutoff.
+    StableTransitive,
+    /// Direct dependencies on releases that predate the stability cutoff.
+    StableDirect,
+    /// Transitive dependencies on recent releases, or releases of unknown age.
+    RecentTransitive,
+    /// Direct dependencies on recent releases, or releases of unknown age.
+    RecentDirect,
+    /// Dependencies that don't come from a registry, like Git repositories or local paths.
+    Unversioned,
+}
+
+impl std::fmt::Display for LayerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::StableTransitive => write!(f, "stable transitive dependencies"),
+            Self::StableDirect => write!(f, "stable direct dependencies"),
+            Self::RecentTransitive => write!(f, "recent transitive dependencies"),
+            Self::RecentDirect => write!(f, "recent direct dependencies"),
+            Self::Unversioned => write!(f, "Git, URL, and local dependencies"),
+        }
+    }
+}
+
+/// A single locked requirement in an [`InstallLayer`], along with the hashes of its artifacts.
+#[derive(Clone, Debug)]
+pub struct LayerRequirement {
+    /// The `requirements.txt`-compatible requirement (e.g., `anyio==4.3.0`).
+    pub requirement: String,
+    /// The hashes of every artifact for the requirement, in `{algorithm}:{digest}` format.
+    pub hashes: Vec<String>,
+}
+
+impl LayerRequirement {
+    fn from_distribution(dist: &Distribution) -> Self {
+        let DistributionId {
+            name,
+            version,
+            source,
+        } = &dist.id;
+        let requirement = match &source.kind {
+            SourceKind::Registry => format!("{name}=={version}"),
+            SourceKind::Git(git) => {
+                let mut requirement = format!("{name} @ git+{}@{}", source.url, git.precise);
+                if let Some(subdirectory) = &git.subdirectory {
+                    write!(requirement, "#subdirectory={subdirectory}").unwrap();
+                }
+                requirement
+            }
+            SourceKind::Direct(direct) => {
+                let mut requirement = format!("{name} @ {}", source.url);
+                if let Some(subdirectory) = &direct.subdirectory {
+                    write!(requirement, "#subdirectory={subdirectory}").unwrap();
+                }
+                requirement
+            }
+            SourceKind::Path | SourceKind::Directory => format!("{name} @ {}", source.url),
+            SourceKind::Editable => format!("-e {}", source.url),
+        };
+        let hashes = dist
+            .wheels
+            .iter()
+            .filter_map(|wheel| wheel.hash.as_ref())
+            .chain(dist.sdist.iter().filter_map(|sdist| sdist.hash.as_ref()))
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        Self {
+            requirement,
+            hashes,
+        }
+    }
+}
+
+impl std::fmt::Display for LayerRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.requirement)?;
+        for hash in &self.hashes {
+            write!(f, " \\\n    --hash={hash}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 struct LockWire {
     version: u32,
@@ -134,14 +372,18 @@ impl TryFrom<LockWire> for Lock {
         // Put all dependencies for each distribution in a canonical order and
         // check for duplicates.
         for dist in &mut wire.distributions {
-            dist.dependencies.sort();
-            for windows in dist.dependencies.windows(2) {
-                let (dep1, dep2) = (&windows[0], &windows[1]);
-                if dep1.id == dep2.id {
-                    return Err(LockError::duplicate_dependency(
-                        dist.id.clone(),
-                        dep1.id.clone(),
-                    ));
+            for dependencies in std::iter::once(&mut dist.dependencies)
+                .chain(dist.optional_dependencies.values_mut())
+            {
+                dependencies.sort();
+                for windows in dependencies.windows(2) {
+                    let (dep1, dep2) = (&windows[0], &windows[1]);
+                    if dep1.id == dep2.id {
+                        return Err(LockError::duplicate_dependency(
+                            dist.id.clone(),
+                            dep1.id.clone(),
+                        ));
+                    }
                 }
             }
         }
@@ -160,7 +402,11 @@ impl TryFrom<LockWire> for Lock {
         // it implies we somehow have a dependency with no corresponding locked
         // distribution.
         for dist in &wire.distributions {
-            for dep in &dist.dependencies {
+            for dep in dist
+                .dependencies
+                .iter()
+                .chain(dist.optional_dependencies.values().flatten())
+            {
                 if !by_id.contains_key(&dep.id) {
                     return Err(LockError::unrecognized_dependency(
                         dist.id.clone(),
@@ -206,6 +452,13 @@ pub(crate) struct Distribution {
     pub(crate) wheels: Vec<Wheel>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub(crate) dependencies: Vec<Dependency>,
+    /// The dependencies that are only required by an extra, keyed by the extra.
+    #[serde(
+        default,
+        rename = "optional-dependencies",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub(crate) optional_dependencies: BTreeMap<ExtraName, Vec<Dependency>>,
 }
 
 impl Distribution {
@@ -223,12 +476,62 @@ impl Distribution {
             sdist,
             wheels,
             dependencies: vec![],
+            optional_dependencies: BTreeMap::default(),
         })
     }
 
-    pub(crate) fn add_dependency(&mut self, annotated_dist: &AnnotatedDist) {
-        self.dependencies
-            .push(Dependency::from_annotated_dist(annotated_dist));
+    /// Add a dependency on `dependency`, as declared in the metadata of `annotated_dist`.
+    ///
+    /// Dependencies that are only declared under an extra (e.g., `extra == 'dev'`) are recorded
+    /// as optional dependencies of that extra, along with any extras requested of the dependency
+    /// itself (e.g., `black[d]`).
+    pub(crate) fn add_dependency(
+        &mut self,
+        annotated_dist: &AnnotatedDist,
+        dependency: &AnnotatedDist,
+    ) {
+        let requirements = annotated_dist
+            .metadata
+            .requires_dist
+            .iter()
+            .filter(|requirement| &requirement.name == dependency.name())
+            .collect::<Vec<_>>();
+
+        // If the dependency isn't declared in the metadata (e.g., it was introduced by an
+        // override), treat it as unconditional.
+        if requirements.is_empty() {
+            Dependency::insert(&mut self.dependencies, Dependency::new(dependency, vec![]));
+            return;
+        }
+
+        for requirement in requirements {
+            let extras = requirement
+                .extras
+                .iter()
+                .filter(|extra| dependency.extras.contains(extra))
+                .cloned()
+                .collect::<Vec<_>>();
+            let applies = |enabled: &dyn Fn(&ExtraName) -> bool| {
+                requirement.marker.as_ref().map_or(true, |marker| {
+                    !matches!(
+                        resolve_extras(marker.clone(), &enabled),
+                        MarkerTree::Or(trees) if trees.is_empty()
+                    )
+                })
+            };
+            if applies(&|_| false) {
+                Dependency::insert(&mut self.dependencies, Dependency::new(dependency, extras));
+            } else {
+                for extra in &annotated_dist.extras {
+                    if applies(&|enabled| enabled == extra) {
+                        Dependency::insert(
+                            self.optional_dependencies.entry(extra.clone()).or_default(),
+                            Dependency::new(dependency, extras.clone()),
+                        );
+                    }
+                }
+            }
+        }
     }
 
     /// Convert the [`Distribution`] to a [`Dist`] that can be used in installation.
@@ -1133,12 +1436,27 @@ impl TryFrom<WheelWire> for Wheel {
 pub(crate) struct Dependency {
     #[serde(flatten)]
     id: DistributionId,
+    /// The extras requested of the dependency (e.g., `d` in `black[d]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extra: Vec<ExtraName>,
 }
 
 impl Dependency {
-    fn from_annotated_dist(annotated_dist: &AnnotatedDist) -> Dependency {
+    fn new(annotated_dist: &AnnotatedDist, extra: Vec<ExtraName>) -> Dependency {
         let id = DistributionId::from_annotated_dist(annotated_dist);
-        Dependency { id }
+        Dependency { id, extra }
+    }
+
+    /// Insert a [`Dependency`] into a list, merging its extras with those of any existing
+    /// dependency on the same distribution.
+    fn insert(dependencies: &mut Vec<Dependency>, dependency: Dependency) {
+        if let Some(existing) = dependencies.iter_mut().find(|dep| dep.id == dependency.id) {
+            existing.extra.extend(dependency.extra);
+            existing.extra.sort();
+            existing.extra.dedup();
+        } else {
+            dependencies.push(dependency);
+        }
     }
 }
 
@@ -1521,4 +1839,109 @@ hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
         let result: Result<Lock, _> = toml::from_str(data);
         insta::assert_debug_snapshot!(result);
     }
+
+    #[test]
+    fn layers() {
+        let data = r#"
+version = 1
+
+[[distribution]]
+name = "project"
+version = "0.1.0"
+source = "editable+file:///foo/project"
+
+[distribution.sdist]
+url = "file:///foo/project"
+
+[[distribution.dependencies]]
+name = "anyio"
+version = "4.3.0"
+source = "registry+https://pypi.org/simple"
+
+[[distribution.dependencies]]
+name = "flask"
+version = "3.0.0"
+source = "git+https://github.com/pallets/flask?rev=main#a13b6c4b2a72c7b4a4f3a3ad3e6a5e5b4c7e2f1a"
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+source = "registry+https://pypi.org/simple"
+
+[[distribution.wheel]]
+url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl"
+hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+
+[[distribution.dependencies]]
+name = "idna"
+version = "3.6"
+source = "registry+https://pypi.org/simple"
+
+[[distribution]]
+name = "flask"
+version = "3.0.0"
+source = "git+https://github.com/pallets/flask?rev=main#a13b6c4b2a72c7b4a4f3a3ad3e6a5e5b4c7e2f1a"
+
+[distribution.sdist]
+url = "https://github.com/pallets/flask?rev=main#a13b6c4b2a72c7b4a4f3a3ad3e6a5e5b4c7e2f1a"
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+source = "registry+https://pypi.org/simple"
+
+[[distribution.wheel]]
+url = "https://files.pythonhosted.org/packages/c2/e7/a82b05cf63a603df6e68d59ae6a68bf5064484a0718ea5033660af4b54a9/idna-3.6-py3-none-any.whl"
+hash = "sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"
+"#;
+        let lock: Lock = toml::from_str(data).unwrap();
+
+        // `idna` was released before the cutoff, while `anyio` was released after.
+        let upload_times = FxHashMap::from_iter([
+            (
+                (
+                    PackageName::from_str("anyio").unwrap(),
+                    Version::from_str("4.3.0").unwrap(),
+                ),
+                2,
+            ),
+            (
+                (
+                    PackageName::from_str("idna").unwrap(),
+                    Version::from_str("3.6").unwrap(),
+                ),
+                0,
+            ),
+        ]);
+        let layers = lock
+            .to_layers(&PackageName::from_str("project").unwrap(), &upload_times, 1)
+            .unwrap();
+
+        let layers = layers
+            .iter()
+            .map(|layer| {
+                (
+                    layer.kind,
+                    layer.requires_hashes(),
+                    layer
+                        .requirements
+                        .iter()
+                        .map(|requirement| requirement.requirement.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            layers,
+            vec![
+                (LayerKind::StableTransitive, true, vec!["idna==3.6"]),
+                (LayerKind::RecentDirect, true, vec!["anyio==4.3.0"]),
+                (
+                    LayerKind::Unversioned,
+                    false,
+                    vec!["flask @ git+https://github.com/pallets/flask@a13b6c4b2a72c7b4a4f3a3ad3e6a5e5b4c7e2f1a"]
+                ),
+            ]
+        );
+    }
 }
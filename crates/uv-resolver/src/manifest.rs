@@ -7,7 +7,7 @@ use uv_normalize::PackageName;
 use uv_types::RequestedRequirements;
 
 use crate::editables::BuiltEditableMetadata;
-use crate::{preferences::Preference, DependencyMode, Exclusions};
+use crate::{preferences::Preference, DependencyMode, Exclusions, PackagePolicy, PrefetchStrategy};
 
 /// A manifest of requirements, constraints, and preferences.
 #[derive(Clone, Debug)]
@@ -49,6 +49,12 @@ pub struct Manifest {
     /// determinations around "allowed" versions (for example, "allowed" URLs or "allowed"
     /// pre-release versions).
     pub(crate) lookaheads: Vec<RequestedRequirements>,
+
+    /// The policy that restricts which packages may enter the resolution (e.g., `--deny-package`).
+    pub(crate) policy: PackagePolicy,
+
+    /// The tuning for version prefetching during resolution (e.g., `--prefetch`).
+    pub(crate) prefetch: PrefetchStrategy,
 }
 
 impl Manifest {
@@ -72,6 +78,8 @@ impl Manifest {
             editables,
             exclusions,
             lookaheads,
+            policy: PackagePolicy::default(),
+            prefetch: PrefetchStrategy::default(),
         }
     }
 
@@ -85,9 +93,23 @@ impl Manifest {
             editables: Vec::new(),
             exclusions: Exclusions::default(),
             lookaheads: Vec::new(),
+            policy: PackagePolicy::default(),
+            prefetch: PrefetchStrategy::default(),
         }
     }
 
+    /// Set the [`PackagePolicy`] that restricts which packages may enter the resolution.
+    #[must_use]
+    pub fn with_policy(self, policy: PackagePolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Set the [`PrefetchStrategy`] that tunes version prefetching during resolution.
+    #[must_use]
+    pub fn with_prefetch(self, prefetch: PrefetchStrategy) -> Self {
+        Self { prefetch, ..self }
+    }
+
     /// Return an iterator over all requirements, constraints, and overrides, in priority order,
     /// such that requirements come first, followed by constraints, followed by overrides.
     ///
@@ -114,11 +136,10 @@ impl Manifest {
                         })
                 })
                 .chain(self.editables.iter().flat_map(move |editable| {
+                    let extras = editable.metadata.expand_extras(&editable.built.extras);
                     self.overrides
                         .apply(&editable.requirements.dependencies)
-                        .filter(move |requirement| {
-                            requirement.evaluate_markers(markers, &editable.built.extras)
-                        })
+                        .filter(move |requirement| requirement.evaluate_markers(markers, &extras))
                 }))
                 .chain(
                     self.overrides
@@ -176,10 +197,11 @@ impl Manifest {
                             })
                     })
                     .chain(self.editables.iter().flat_map(move |editable| {
+                        let extras = editable.metadata.expand_extras(&editable.built.extras);
                         self.overrides
                             .apply(&editable.requirements.dependencies)
                             .filter(move |requirement| {
-                                requirement.evaluate_markers(markers, &editable.built.extras)
+                                requirement.evaluate_markers(markers, &extras)
                             })
                     }))
                     .chain(
@@ -1,5 +1,6 @@
 #![allow(clippy::enum_glob_use)]
 
+use std::fmt::{Display, Formatter};
 use std::ops::Bound::{self, *};
 use std::ops::RangeBounds;
 
@@ -8,12 +9,340 @@ use pep508_rs::{
     ExtraName, ExtraOperator, MarkerExpression, MarkerOperator, MarkerTree, MarkerValueString,
     MarkerValueVersion,
 };
+use pubgrub::range::Range;
 
 use crate::pubgrub::PubGrubSpecifier;
 
+/// Displays a simplified [`MarkerTree`], e.g., in the header of a compiled requirements file.
+///
+/// A tree that always applies and a tree that never applies would both display as an empty
+/// string, so they're displayed as [`DisplayMarkers::ALL_ENVIRONMENTS`] and
+/// [`DisplayMarkers::NO_ENVIRONMENTS`] instead.
+#[derive(Debug)]
+pub struct DisplayMarkers<'a>(pub &'a MarkerTree);
+
+impl DisplayMarkers<'_> {
+    /// The text displayed for a tree that always applies.
+    pub const ALL_ENVIRONMENTS: &'static str = "(all environments)";
+    /// The text displayed for a tree that never applies.
+    pub const NO_ENVIRONMENTS: &'static str = "(no environments)";
+
+    /// Parse the output of [`DisplayMarkers`] back into a [`MarkerTree`].
+    pub fn parse(text: &str) -> Option<MarkerTree> {
+        match text {
+            Self::ALL_ENVIRONMENTS => Some(MarkerTree::And(vec![])),
+            Self::NO_ENVIRONMENTS => Some(MarkerTree::Or(vec![])),
+            text => text.parse().ok(),
+        }
+    }
+}
+
+impl Display for DisplayMarkers<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            MarkerTree::And(trees) if trees.is_empty() => f.write_str(Self::ALL_ENVIRONMENTS),
+            MarkerTree::Or(trees) if trees.is_empty() => f.write_str(Self::NO_ENVIRONMENTS),
+            tree => write!(f, "{tree}"),
+        }
+    }
+}
+
+/// Simplify a marker tree, without changing the set of environments in which it applies.
+///
+/// Nested conjunctions and disjunctions are flattened, duplicate clauses are removed, and version
+/// expressions over the same key are merged into a single range where possible (e.g.,
+/// `python_version >= '3.8' and python_version >= '3.9'` becomes `python_version >= '3.9'`).
+/// Clauses that are always true in a conjunction (or always false in a disjunction) are dropped.
+///
+/// A tree that always applies is simplified to an empty conjunction, and a tree that never
+/// applies is simplified to an empty disjunction.
+pub fn simplify_markers(tree: MarkerTree) -> MarkerTree {
+    match tree {
+        MarkerTree::Expression(expr) => match keyed_range(&expr) {
+            Ok(Some((_, range))) if range == Range::full() => MarkerTree::And(vec![]),
+            Ok(Some((_, range))) if range.is_empty() => MarkerTree::Or(vec![]),
+            _ => MarkerTree::Expression(expr),
+        },
+        MarkerTree::And(trees) => {
+            let mut clauses = Vec::new();
+            for tree in trees {
+                match simplify_markers(tree) {
+                    MarkerTree::Or(trees) if trees.is_empty() => return MarkerTree::Or(vec![]),
+                    MarkerTree::And(trees) => clauses.extend(trees),
+                    tree => clauses.push(tree),
+                }
+            }
+            let Some(clauses) = merge_version_clauses(clauses, Junction::And) else {
+                return MarkerTree::Or(vec![]);
+            };
+
+            let clauses = absorb(clauses, Junction::And);
+
+            // If any two clauses can't both apply, the conjunction never applies.
+            for (i, first) in clauses.iter().enumerate() {
+                if clauses[i + 1..]
+                    .iter()
+                    .any(|second| is_disjoint(first, second))
+                {
+                    return MarkerTree::Or(vec![]);
+                }
+            }
+
+            if clauses.len() == 1 {
+                clauses.into_iter().next().unwrap()
+            } else {
+                MarkerTree::And(clauses)
+            }
+        }
+        MarkerTree::Or(trees) => {
+            let mut clauses = Vec::new();
+            for tree in trees {
+                match simplify_markers(tree) {
+                    MarkerTree::And(trees) if trees.is_empty() => return MarkerTree::And(vec![]),
+                    MarkerTree::Or(trees) => clauses.extend(trees),
+                    tree => clauses.push(tree),
+                }
+            }
+            let Some(clauses) = merge_version_clauses(clauses, Junction::Or) else {
+                return MarkerTree::And(vec![]);
+            };
+            let clauses = absorb(clauses, Junction::Or);
+
+            if clauses.len() == 1 {
+                clauses.into_iter().next().unwrap()
+            } else {
+                MarkerTree::Or(clauses)
+            }
+        }
+    }
+}
+
+/// Remove any clause that is redundant by the absorption law, i.e., any disjunct that implies
+/// the remaining disjuncts (as in `a or (a and b)`), or any conjunct that is implied by the
+/// remaining conjuncts (as in `a and (a or b)`).
+fn absorb(mut clauses: Vec<MarkerTree>, junction: Junction) -> Vec<MarkerTree> {
+    let mut index = 0;
+    while index < clauses.len() {
+        if is_absorbed(&clauses, index, junction) {
+            clauses.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    clauses
+}
+
+/// Returns `true` if the clause at the given index is redundant with the remaining clauses.
+fn is_absorbed(clauses: &[MarkerTree], index: usize, junction: Junction) -> bool {
+    // The terms of a clause, e.g., the conjuncts of a disjunct.
+    fn terms(tree: &MarkerTree, junction: Junction) -> Vec<&MarkerTree> {
+        match (junction, tree) {
+            (Junction::Or, MarkerTree::And(trees)) | (Junction::And, MarkerTree::Or(trees)) => {
+                trees.iter().collect()
+            }
+            _ => vec![tree],
+        }
+    }
+    let clause = terms(&clauses[index], junction);
+    let others = clauses
+        .iter()
+        .enumerate()
+        .filter(|(other, _)| *other != index)
+        .map(|(_, tree)| tree)
+        .collect::<Vec<_>>();
+
+    // E.g., `a and b` is redundant with `a`.
+    others
+        .iter()
+        .any(|other| terms(other, junction).iter().all(|term| clause.contains(term)))
+        // E.g., `(a or b) and c` is redundant with `a` and `b`.
+        || clause.iter().any(|term| match (junction, term) {
+            (Junction::Or, MarkerTree::Or(trees)) | (Junction::And, MarkerTree::And(trees)) => {
+                !trees.is_empty() && trees.iter().all(|tree| others.contains(&tree))
+            }
+            _ => false,
+        })
+}
+
+/// Replace the `extra` expressions in a marker tree with whether the extra is enabled, then
+/// simplify the tree.
+///
+/// For example, with the `socks` extra enabled, `extra == 'socks' and os_name == 'nt'` becomes
+/// `os_name == 'nt'`.
+pub(crate) fn resolve_extras(
+    tree: MarkerTree,
+    enabled: &impl Fn(&ExtraName) -> bool,
+) -> MarkerTree {
+    substitute_extras(tree, &|extra| {
+        enabled(extra).then(|| MarkerTree::And(vec![]))
+    })
+}
+
+/// Replace the `extra` expressions in a marker tree with the markers under which each extra is
+/// enabled (or `None`, if the extra isn't enabled at all), then simplify the tree.
+///
+/// For example, with the `gpu` extra enabled under `sys_platform == 'linux'`,
+/// `extra == 'gpu' and python_version >= '3.9'` becomes
+/// `sys_platform == 'linux' and python_version >= '3.9'`.
+///
+/// Since marker trees can't express negation, `extra != '...'` expressions are resolved based on
+/// whether the extra is enabled under any markers.
+pub(crate) fn substitute_extras(
+    tree: MarkerTree,
+    enabled: &impl Fn(&ExtraName) -> Option<MarkerTree>,
+) -> MarkerTree {
+    fn substitute(
+        tree: MarkerTree,
+        enabled: &impl Fn(&ExtraName) -> Option<MarkerTree>,
+    ) -> MarkerTree {
+        match tree {
+            MarkerTree::Expression(MarkerExpression::Extra { operator, name }) => {
+                match (operator, enabled(&name)) {
+                    (ExtraOperator::Equal, Some(marker)) => marker,
+                    (ExtraOperator::Equal, None) => MarkerTree::Or(vec![]),
+                    (ExtraOperator::NotEqual, Some(_)) => MarkerTree::Or(vec![]),
+                    (ExtraOperator::NotEqual, None) => MarkerTree::And(vec![]),
+                }
+            }
+            MarkerTree::Expression(expr) => MarkerTree::Expression(expr),
+            MarkerTree::And(trees) => MarkerTree::And(
+                trees
+                    .into_iter()
+                    .map(|tree| substitute(tree, enabled))
+                    .collect(),
+            ),
+            MarkerTree::Or(trees) => MarkerTree::Or(
+                trees
+                    .into_iter()
+                    .map(|tree| substitute(tree, enabled))
+                    .collect(),
+            ),
+        }
+    }
+    simplify_markers(substitute(tree, enabled))
+}
+
+/// The operator joining a list of marker clauses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Junction {
+    And,
+    Or,
+}
+
+impl Junction {
+    /// Combine the ranges of two joined clauses.
+    fn combine(self, first: &Range<Version>, second: &Range<Version>) -> Range<Version> {
+        match self {
+            Self::And => first.intersection(second),
+            Self::Or => first.union(second),
+        }
+    }
+}
+
+/// Deduplicate the given clauses, and merge the version expressions over each key into at most
+/// two expressions (a lower and an upper bound), where the merged range can be represented as
+/// such.
+///
+/// Returns `None` if the clauses are short-circuited, i.e., if the conjunction of the clauses
+/// never applies, or the disjunction of the clauses always applies.
+fn merge_version_clauses(clauses: Vec<MarkerTree>, junction: Junction) -> Option<Vec<MarkerTree>> {
+    let mut deduplicated = Vec::with_capacity(clauses.len());
+    for clause in clauses {
+        if !deduplicated.contains(&clause) {
+            deduplicated.push(clause);
+        }
+    }
+    let clauses = deduplicated;
+
+    // Group the version expressions by key, tracking the range of each expression.
+    let mut groups: Vec<(&MarkerValueVersion, Vec<_>)> = Vec::new();
+    for (index, clause) in clauses.iter().enumerate() {
+        let MarkerTree::Expression(expr) = clause else {
+            continue;
+        };
+        let Ok(Some((key, range))) = keyed_range(expr) else {
+            continue;
+        };
+        if let Some((_, members)) = groups.iter_mut().find(|(existing, _)| *existing == key) {
+            members.push((index, range));
+        } else {
+            groups.push((key, vec![(index, range)]));
+        }
+    }
+
+    // For each key, determine the expressions to retain in place of the group.
+    let mut replacements = vec![None; clauses.len()];
+    for (_, members) in groups.iter().filter(|(_, members)| members.len() > 1) {
+        let merged = members
+            .iter()
+            .skip(1)
+            .fold(members[0].1.clone(), |merged, (_, range)| {
+                junction.combine(&merged, range)
+            });
+        match junction {
+            Junction::And if merged.is_empty() => return None,
+            Junction::Or if merged == Range::full() => return None,
+            _ => {}
+        }
+
+        // Only merge ranges that consist of a single segment.
+        let mut segments = merged.iter();
+        let (Some((lower, upper)), None) = (segments.next(), segments.next()) else {
+            continue;
+        };
+
+        // Reuse the expressions that provide each bound, to preserve their formatting, preferring
+        // a single expression that provides both.
+        let provides_lower = |(_, range): &&(usize, Range<Version>)| {
+            range.iter().next().is_some_and(|(start, _)| start == lower)
+        };
+        let provides_upper = |(_, range): &&(usize, Range<Version>)| {
+            range.iter().last().is_some_and(|(_, end)| end == upper)
+        };
+        let retained = if let Some(member) = members
+            .iter()
+            .find(|member| provides_lower(member) && provides_upper(member))
+        {
+            vec![member]
+        } else {
+            let lower = members.iter().find(provides_lower);
+            let upper = members.iter().find(provides_upper);
+            let (Some(lower), Some(upper)) = (lower, upper) else {
+                continue;
+            };
+            vec![lower, upper]
+        };
+
+        // Ensure that the retained expressions are equivalent to the full group.
+        let combined = retained
+            .iter()
+            .skip(1)
+            .fold(retained[0].1.clone(), |combined, (_, range)| {
+                junction.combine(&combined, range)
+            });
+        if combined != merged {
+            continue;
+        }
+
+        let first = members[0].0;
+        for (index, _) in members {
+            replacements[*index] = Some(Vec::new());
+        }
+        replacements[first] = Some(retained.iter().map(|(index, _)| *index).collect());
+    }
+
+    let mut merged = Vec::with_capacity(clauses.len());
+    for (index, replacement) in replacements.iter().enumerate() {
+        match replacement {
+            None => merged.push(clauses[index].clone()),
+            Some(retained) => merged.extend(retained.iter().map(|index| clauses[*index].clone())),
+        }
+    }
+    Some(merged)
+}
+
 /// Returns `true` if there is no environment in which both marker trees can both apply, i.e.
 /// the expression `first and second` is always false.
-#[allow(dead_code)]
 pub(crate) fn is_disjoint(first: &MarkerTree, second: &MarkerTree) -> bool {
     let (expr1, expr2) = match (first, second) {
         (MarkerTree::Expression(expr1), MarkerTree::Expression(expr2)) => (expr1, expr2),
@@ -166,7 +495,7 @@ fn version_is_disjoint(this: &MarkerExpression, other: &MarkerExpression) -> boo
 /// Returns the key and version range for a version expression.
 fn keyed_range(
     expr: &MarkerExpression,
-) -> Result<Option<(&MarkerValueVersion, pubgrub::range::Range<Version>)>, ()> {
+) -> Result<Option<(&MarkerValueVersion, Range<Version>)>, ()> {
     let (key, specifier) = match expr {
         MarkerExpression::Version { key, specifier } => (key, specifier.clone()),
         MarkerExpression::VersionInverted {
@@ -219,6 +548,8 @@ fn reverse_marker_operator(operator: MarkerOperator) -> MarkerOperator {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use pep508_rs::TracingReporter;
 
     use super::*;
@@ -327,6 +658,159 @@ mod tests {
         ));
     }
 
+    fn simplify(tree: &str) -> String {
+        let tree = MarkerTree::parse_reporter(tree, &mut TracingReporter).unwrap();
+        DisplayMarkers(&simplify_markers(tree)).to_string()
+    }
+
+    #[test]
+    fn simplify_version_ranges() {
+        assert_eq!(
+            simplify("python_version >= '3.8' and python_version >= '3.9'"),
+            "python_version >= '3.9'"
+        );
+        assert_eq!(
+            simplify(
+                "python_version >= '3.8' and os_name == 'nt' and python_version < '3.12' and python_version >= '3.9'"
+            ),
+            "python_version >= '3.9' and python_version < '3.12' and os_name == 'nt'"
+        );
+        assert_eq!(
+            simplify("python_version < '3.8' or python_version < '3.10'"),
+            "python_version < '3.10'"
+        );
+        assert_eq!(
+            simplify("'3.8' <= python_version and python_version > '3.7'"),
+            "'3.8' <= python_version"
+        );
+
+        // Ranges that can't be expressed as a single segment are left as-is.
+        assert_eq!(
+            simplify("python_version < '3.8' or python_version >= '3.10'"),
+            "python_version < '3.8' or python_version >= '3.10'"
+        );
+        assert_eq!(
+            simplify("python_version != '3.9' and python_version >= '3.8'"),
+            "python_version != '3.9' and python_version >= '3.8'"
+        );
+    }
+
+    #[test]
+    fn simplify_tautologies() {
+        assert_eq!(
+            simplify("python_version < '3.8' or python_version >= '3.7'"),
+            "(all environments)"
+        );
+        assert_eq!(
+            simplify("os_name == 'nt' and (python_version < '3.8' or python_version >= '3.7')"),
+            "os_name == 'nt'"
+        );
+        assert_eq!(
+            simplify("os_name == 'nt' or (python_version < '3.8' and python_version >= '3.8')"),
+            "os_name == 'nt'"
+        );
+        assert_eq!(
+            simplify("sys_platform == 'linux' and sys_platform == 'win32'"),
+            "(no environments)"
+        );
+        assert!(matches!(
+            simplify_markers(
+                MarkerTree::from_str("sys_platform == 'linux' and sys_platform == 'win32'")
+                    .unwrap()
+            ),
+            MarkerTree::Or(clauses) if clauses.is_empty()
+        ));
+    }
+
+    #[test]
+    fn display_markers_round_trip() {
+        for text in [
+            "(all environments)",
+            "(no environments)",
+            "os_name == 'nt' and python_version >= '3.9'",
+        ] {
+            let tree = DisplayMarkers::parse(text).unwrap();
+            assert_eq!(DisplayMarkers(&tree).to_string(), text);
+        }
+        assert!(DisplayMarkers::parse("(some environments)").is_none());
+    }
+
+    #[test]
+    fn simplify_flatten() {
+        assert_eq!(
+            simplify("os_name == 'nt' and (sys_platform == 'win32' and os_name == 'nt')"),
+            "os_name == 'nt' and sys_platform == 'win32'"
+        );
+        assert_eq!(
+            simplify("(os_name == 'nt' or sys_platform == 'win32') or os_name == 'posix'"),
+            "os_name == 'nt' or sys_platform == 'win32' or os_name == 'posix'"
+        );
+        assert_eq!(
+            simplify("(os_name == 'nt' or sys_platform == 'win32') and os_name == 'posix'"),
+            "(os_name == 'nt' or sys_platform == 'win32') and os_name == 'posix'"
+        );
+    }
+
+    #[test]
+    fn simplify_absorption() {
+        assert_eq!(
+            simplify("os_name == 'nt' or (os_name == 'nt' and sys_platform == 'win32')"),
+            "os_name == 'nt'"
+        );
+        assert_eq!(
+            simplify("os_name == 'nt' and (os_name == 'nt' or sys_platform == 'win32')"),
+            "os_name == 'nt'"
+        );
+    }
+
+    #[test]
+    fn simplify_absorption_nested() {
+        assert_eq!(
+            simplify("os_name == 'nt' or sys_platform == 'win32' or ((os_name == 'nt' or sys_platform == 'win32') and python_version >= '3.8')"),
+            "os_name == 'nt' or sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn resolve_extra_markers() {
+        let socks = ExtraName::from_str("socks").unwrap();
+        let resolve = |marker: &str| {
+            resolve_extras(MarkerTree::from_str(marker).unwrap(), &|extra| {
+                *extra == socks
+            })
+            .to_string()
+        };
+        assert_eq!(
+            resolve("extra == 'socks' and os_name == 'nt'"),
+            "os_name == 'nt'"
+        );
+        assert_eq!(
+            resolve("extra != 'socks' or os_name == 'nt'"),
+            "os_name == 'nt'"
+        );
+        assert_eq!(resolve("extra == 'http2'"), "");
+    }
+
+    #[test]
+    fn substitute_extra_markers() {
+        let gpu = ExtraName::from_str("gpu").unwrap();
+        let substitute = |marker: &str| {
+            substitute_extras(MarkerTree::from_str(marker).unwrap(), &|extra| {
+                (*extra == gpu).then(|| MarkerTree::from_str("sys_platform == 'linux'").unwrap())
+            })
+            .to_string()
+        };
+        assert_eq!(
+            substitute("extra == 'gpu' and python_version >= '3.9'"),
+            "sys_platform == 'linux' and python_version >= '3.9'"
+        );
+        assert_eq!(
+            substitute("extra == 'gpu' or extra == 'cpu'"),
+            "sys_platform == 'linux'"
+        );
+        assert_eq!(substitute("extra != 'cpu'"), "");
+    }
+
     fn test_version_bounds(version: &str) {
         assert!(!is_disjoint(
             format!("{version} > '2.7.0'"),
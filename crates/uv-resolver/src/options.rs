@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use uv_configuration::IndexStrategy;
 
 use crate::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode};
@@ -10,6 +12,17 @@ pub struct Options {
     pub dependency_mode: DependencyMode,
     pub exclude_newer: Option<ExcludeNewer>,
     pub index_strategy: IndexStrategy,
+    pub limits: ResolutionLimits,
+}
+
+/// Limits on the work the resolver may perform before giving up, such that pathological
+/// requirement sets fail with an explanation rather than appearing to hang.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ResolutionLimits {
+    /// The maximum number of times the resolver may backtrack.
+    pub max_backtracks: Option<usize>,
+    /// The maximum wall-clock time the resolver may spend solving.
+    pub timeout: Option<Duration>,
 }
 
 /// Builder for [`Options`].
@@ -20,6 +33,7 @@ pub struct OptionsBuilder {
     dependency_mode: DependencyMode,
     exclude_newer: Option<ExcludeNewer>,
     index_strategy: IndexStrategy,
+    limits: ResolutionLimits,
 }
 
 impl OptionsBuilder {
@@ -63,6 +77,13 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the [`ResolutionLimits`].
+    #[must_use]
+    pub fn limits(mut self, limits: ResolutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
@@ -71,6 +92,7 @@ impl OptionsBuilder {
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
             index_strategy: self.index_strategy,
+            limits: self.limits,
         }
     }
 }
@@ -0,0 +1,204 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use itertools::Itertools;
+use pubgrub::range::Range;
+use rustc_hash::FxHashSet;
+
+use pep440_rs::{Version, VersionSpecifiers};
+use pep508_rs::{Requirement, VerbatimUrl, VersionOrUrl};
+use uv_normalize::PackageName;
+
+use crate::pubgrub::PubGrubSpecifier;
+use crate::{MinReleaseAge, ResolveError};
+
+/// A package (or range of package versions) that may never enter a resolution, e.g., `pycrypto` or
+/// `urllib3<2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeniedPackage {
+    name: PackageName,
+    specifier: VersionSpecifiers,
+}
+
+impl DeniedPackage {
+    /// Returns the name of the denied package.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Returns the denied versions of the package, as a PubGrub range.
+    fn range(&self) -> Result<Range<Version>, ResolveError> {
+        self.specifier
+            .iter()
+            .map(PubGrubSpecifier::try_from)
+            .fold_ok(Range::full(), |range, specifier| {
+                range.intersection(&specifier.into())
+            })
+    }
+}
+
+impl FromStr for DeniedPackage {
+    type Err = String;
+
+    /// Parse a [`DeniedPackage`] from a package name, with an optional version specifier (e.g.,
+    /// `pycrypto` or `urllib3<2`).
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let requirement =
+            Requirement::<VerbatimUrl>::from_str(input).map_err(|err| err.to_string())?;
+        if !requirement.extras.is_empty() || requirement.marker.is_some() {
+            return Err(format!(
+                "`{input}` must be a package name with an optional version specifier, without extras or markers"
+            ));
+        }
+        let specifier = match requirement.version_or_url {
+            None => VersionSpecifiers::empty(),
+            Some(VersionOrUrl::VersionSpecifier(specifier)) => specifier,
+            Some(VersionOrUrl::Url(_)) => {
+                return Err(format!(
+                    "`{input}` must be a package name with an optional version specifier, not a URL"
+                ));
+            }
+        };
+        Ok(Self {
+            name: requirement.name,
+            specifier,
+        })
+    }
+}
+
+impl Display for DeniedPackage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name, self.specifier)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DeniedPackage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        DeniedPackage::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for DeniedPackage {
+    fn schema_name() -> String {
+        "DeniedPackage".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("The name of a package, with an optional version specifier (e.g., `pycrypto` or `urllib3<2`).".to_string()),
+              ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// A policy that restricts which packages may enter a resolution.
+#[derive(Debug, Default, Clone)]
+pub struct PackagePolicy {
+    /// The packages (or package versions) that may never be selected.
+    deny: Vec<DeniedPackage>,
+    /// If set, the only packages that may be selected.
+    allow: Option<FxHashSet<PackageName>>,
+    /// The minimum time since upload for distributions to be selected.
+    min_release_age: MinReleaseAge,
+}
+
+impl PackagePolicy {
+    /// Create a [`PackagePolicy`] from a deny-list and an allow-list. An empty allow-list permits
+    /// all packages.
+    pub fn new(deny: Vec<DeniedPackage>, allow: Vec<PackageName>) -> Self {
+        Self {
+            deny,
+            allow: if allow.is_empty() {
+                None
+            } else {
+                Some(allow.into_iter().collect())
+            },
+            min_release_age: MinReleaseAge::default(),
+        }
+    }
+
+    /// Set the [`MinReleaseAge`] for distributions to be selected.
+    #[must_use]
+    pub fn with_min_release_age(mut self, min_release_age: MinReleaseAge) -> Self {
+        self.min_release_age = min_release_age;
+        self
+    }
+
+    /// Returns `true` if the policy doesn't restrict any packages.
+    pub fn is_empty(&self) -> bool {
+        self.deny.is_empty() && self.allow.is_none() && self.min_release_age.is_empty()
+    }
+
+    /// Returns the [`MinReleaseAge`] for distributions to be selected.
+    pub fn min_release_age(&self) -> &MinReleaseAge {
+        &self.min_release_age
+    }
+
+    /// Returns the range of versions of the given package that are forbidden by the policy, along
+    /// with the reason, or `None` if the package is unrestricted.
+    pub(crate) fn restriction(
+        &self,
+        name: &PackageName,
+    ) -> Result<Option<(Range<Version>, PolicyViolation)>, ResolveError> {
+        if self
+            .allow
+            .as_ref()
+            .is_some_and(|allow| !allow.contains(name))
+        {
+            return Ok(Some((Range::full(), PolicyViolation::NotAllowed)));
+        }
+
+        let mut denied = Range::empty();
+        for package in self.deny.iter().filter(|package| package.name == *name) {
+            denied = denied.union(&package.range()?);
+        }
+        if denied == Range::empty() {
+            Ok(None)
+        } else if denied == Range::full() {
+            Ok(Some((denied, PolicyViolation::Denied)))
+        } else {
+            Ok(Some((denied, PolicyViolation::DeniedVersions)))
+        }
+    }
+}
+
+/// The reason a package was rejected by a [`PackagePolicy`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum PolicyViolation {
+    /// All versions of the package are denied.
+    Denied,
+    /// Some versions of the package are denied.
+    DeniedVersions,
+    /// The package is not in the allow-list.
+    NotAllowed,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn denied_package() {
+        let package = DeniedPackage::from_str("urllib3<2").unwrap();
+        assert_eq!(package.to_string(), "urllib3<2");
+
+        let package = DeniedPackage::from_str("pycrypto").unwrap();
+        assert_eq!(package.to_string(), "pycrypto");
+
+        assert!(DeniedPackage::from_str("requests[security]").is_err());
+        assert!(DeniedPackage::from_str("requests ; python_version < '3.8'").is_err());
+        assert!(DeniedPackage::from_str("requests @ https://example.com/requests.tar.gz").is_err());
+    }
+}
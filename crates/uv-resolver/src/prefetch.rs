@@ -0,0 +1,171 @@
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use rustc_hash::FxHashMap;
+
+use uv_normalize::PackageName;
+
+/// The number of versions ahead to prefetch metadata for, either by default (e.g., `100`) or for a
+/// specific package (e.g., `botocore=200`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefetchSetting {
+    package: Option<PackageName>,
+    versions: usize,
+}
+
+impl FromStr for PrefetchSetting {
+    type Err = String;
+
+    /// Parse a [`PrefetchSetting`] from a number of versions, optionally preceded by a package
+    /// name (e.g., `100` or `botocore=200`).
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (package, versions) = match input.split_once('=') {
+            Some((package, versions)) => {
+                let package = package.trim();
+                if package.is_empty() {
+                    return Err(format!("`{input}` is missing a package name"));
+                }
+                let package = PackageName::from_str(package)
+                    .map_err(|err| format!("`{input}` has an invalid package name: {err}"))?;
+                (Some(package), versions)
+            }
+            None => (None, input),
+        };
+        let versions = versions.trim().parse::<usize>().map_err(|_| {
+            format!("`{input}` must be a number of versions, optionally preceded by a package name (e.g., `100` or `botocore=200`)")
+        })?;
+        Ok(Self { package, versions })
+    }
+}
+
+impl Display for PrefetchSetting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.package {
+            Some(package) => write!(f, "{package}={}", self.versions),
+            None => write!(f, "{}", self.versions),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PrefetchSetting {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        PrefetchSetting::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for PrefetchSetting {
+    fn schema_name() -> String {
+        "PrefetchSetting".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A number of versions, optionally preceded by a package name (e.g., `100` or `botocore=200`).".to_string()),
+              ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// Tuning for the resolver's version prefetching, which fetches the metadata for the versions of
+/// a package that the resolver is likely to try next, once it has tried (and rejected) several.
+#[derive(Debug, Clone)]
+pub struct PrefetchStrategy {
+    /// The number of versions of a package to try before prefetching starts.
+    threshold: usize,
+    /// The maximum number of versions to prefetch in a single batch.
+    versions: usize,
+    /// Per-package overrides for the maximum number of versions to prefetch.
+    packages: FxHashMap<PackageName, usize>,
+}
+
+impl Default for PrefetchStrategy {
+    fn default() -> Self {
+        Self {
+            threshold: 5,
+            versions: 50,
+            packages: FxHashMap::default(),
+        }
+    }
+}
+
+impl PrefetchStrategy {
+    /// Create a [`PrefetchStrategy`] from a list of [`PrefetchSetting`]s, with earlier settings
+    /// taking precedence over later ones, and an optional threshold.
+    pub fn new(settings: Vec<PrefetchSetting>, threshold: Option<NonZeroUsize>) -> Self {
+        let mut strategy = Self::default();
+        if let Some(threshold) = threshold {
+            strategy.threshold = threshold.get();
+        }
+        for setting in settings.into_iter().rev() {
+            match setting.package {
+                Some(package) => {
+                    strategy.packages.insert(package, setting.versions);
+                }
+                None => strategy.versions = setting.versions,
+            }
+        }
+        strategy
+    }
+
+    /// The number of versions of a package to try before prefetching starts.
+    pub(crate) fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The maximum number of versions of the given package to prefetch in a single batch.
+    pub(crate) fn versions(&self, package: &PackageName) -> usize {
+        self.packages.get(package).copied().unwrap_or(self.versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn prefetch_setting() {
+        let setting = PrefetchSetting::from_str("100").unwrap();
+        assert_eq!(setting.to_string(), "100");
+
+        let setting = PrefetchSetting::from_str("botocore=200").unwrap();
+        assert_eq!(setting.to_string(), "botocore=200");
+
+        assert!(PrefetchSetting::from_str("botocore").is_err());
+        assert!(PrefetchSetting::from_str("=200").is_err());
+        assert!(PrefetchSetting::from_str("botocore=-1").is_err());
+    }
+
+    #[test]
+    fn prefetch_strategy() {
+        let strategy = PrefetchStrategy::new(
+            vec![
+                PrefetchSetting::from_str("botocore=200").unwrap(),
+                PrefetchSetting::from_str("100").unwrap(),
+                PrefetchSetting::from_str("botocore=10").unwrap(),
+            ],
+            NonZeroUsize::new(2),
+        );
+        assert_eq!(strategy.threshold(), 2);
+        assert_eq!(
+            strategy.versions(&PackageName::from_str("botocore").unwrap()),
+            200
+        );
+        assert_eq!(
+            strategy.versions(&PackageName::from_str("boto3").unwrap()),
+            100
+        );
+    }
+}
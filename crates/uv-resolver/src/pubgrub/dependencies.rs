@@ -31,7 +31,11 @@ impl PubGrubDependencies {
         env: Option<&MarkerEnvironment>,
     ) -> Result<Self, ResolveError> {
         let mut dependencies = Vec::default();
-        let mut seen = FxHashSet::default();
+
+        // Track the extras whose requirements were already added, starting with the source extra,
+        // so that cyclic extras (e.g., `all` enabling `dev`, which enables `all`) are only
+        // visited once.
+        let mut seen = source_extra.into_iter().cloned().collect::<FxHashSet<_>>();
 
         add_requirements(
             requirements,
@@ -2,7 +2,8 @@ pub(crate) use crate::pubgrub::dependencies::{PubGrubDependencies, PubGrubRequir
 pub(crate) use crate::pubgrub::distribution::PubGrubDistribution;
 pub(crate) use crate::pubgrub::package::{PubGrubPackage, PubGrubPackageInner, PubGrubPython};
 pub(crate) use crate::pubgrub::priority::{PubGrubPriorities, PubGrubPriority};
-pub(crate) use crate::pubgrub::report::PubGrubReportFormatter;
+pub(crate) use crate::pubgrub::report::{PubGrubHint, PubGrubReportFormatter};
+pub use crate::pubgrub::specifier::is_satisfiable;
 pub(crate) use crate::pubgrub::specifier::PubGrubSpecifier;
 
 mod dependencies;
@@ -6,6 +6,7 @@ use std::ops::Bound;
 use derivative::Derivative;
 use distribution_types::IndexLocations;
 use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use pep440_rs::Version;
 use pubgrub::range::Range;
@@ -19,7 +20,7 @@ use crate::candidate_selector::CandidateSelector;
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{IncompletePackage, UnavailablePackage, UnavailableReason};
 
-use super::{PubGrubPackage, PubGrubPackageInner};
+use super::{PubGrubPackage, PubGrubPackageInner, PubGrubPython};
 
 #[derive(Debug)]
 pub(crate) struct PubGrubReportFormatter<'a> {
@@ -490,7 +491,17 @@ impl PubGrubReportFormatter<'_> {
                                         reason: reason.clone(),
                                     });
                                 }
-                                Some(UnavailablePackage::NotFound) => {}
+                                Some(UnavailablePackage::NotFound { similar }) => {
+                                    if !similar.is_empty() {
+                                        hints.insert(PubGrubHint::SimilarPackages {
+                                            package: package.clone(),
+                                            similar: similar.clone(),
+                                        });
+                                    }
+                                }
+                                Some(
+                                    UnavailablePackage::Denied | UnavailablePackage::NotAllowed,
+                                ) => {}
                                 None => {}
                             }
 
@@ -559,6 +570,227 @@ impl PubGrubReportFormatter<'_> {
     }
 }
 
+impl PubGrubReportFormatter<'_> {
+    /// Generate the [`PubGrubSuggestion`]s for a derivation tree.
+    ///
+    /// Unlike [`PubGrubHint`]s, which explain why a resolution failed, suggestions propose
+    /// concrete changes to the user's requirements or environment that may resolve the failure.
+    pub(crate) fn suggestions(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>, UnavailableReason>,
+    ) -> IndexSet<PubGrubSuggestion> {
+        let mut suggestions = IndexSet::default();
+        match derivation_tree {
+            DerivationTree::External(External::NoVersions(package, set)) => {
+                // Suggest the nearest available versions to a range that matched no versions.
+                if let PubGrubPackageInner::Package { .. } = &**package {
+                    if let Some(versions) = self.available_versions.get(package) {
+                        if !versions.iter().any(|version| set.contains(version)) {
+                            let lower = versions
+                                .iter()
+                                .rev()
+                                .find(|version| below(set, version))
+                                .cloned();
+                            let upper = versions
+                                .iter()
+                                .find(|version| !below(set, version))
+                                .cloned();
+                            if lower.is_some() || upper.is_some() {
+                                suggestions.insert(PubGrubSuggestion::NearestVersions {
+                                    package: package.clone(),
+                                    range: self.simplify_set(set, package).into_owned(),
+                                    lower,
+                                    upper,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            DerivationTree::External(External::FromDependencyOf(
+                package,
+                _,
+                dependency,
+                dependency_set,
+            )) => match (&**package, &**dependency) {
+                // Suggest relaxing a direct requirement that excludes available versions.
+                (PubGrubPackageInner::Root(_), PubGrubPackageInner::Package { .. }) => {
+                    if let Some(version) =
+                        self.available_versions
+                            .get(dependency)
+                            .and_then(|versions| {
+                                versions
+                                    .iter()
+                                    .rev()
+                                    .find(|version| !dependency_set.contains(version))
+                            })
+                    {
+                        suggestions.insert(PubGrubSuggestion::RelaxRequirement {
+                            package: dependency.clone(),
+                            range: self.simplify_set(dependency_set, dependency).into_owned(),
+                            version: version.clone(),
+                        });
+                    }
+                }
+                // Suggest a different Python version, if a package requires one.
+                (PubGrubPackageInner::Package { .. }, PubGrubPackageInner::Python(python)) => {
+                    if let Some(python_requirement) = self.python_requirement {
+                        let current = match python {
+                            PubGrubPython::Installed => python_requirement.installed(),
+                            PubGrubPython::Target => python_requirement.target(),
+                        };
+                        if !dependency_set.contains(current) {
+                            if let Some(version) = lowest(dependency_set) {
+                                suggestions.insert(PubGrubSuggestion::PythonVersion {
+                                    package: package.clone(),
+                                    python: python.clone(),
+                                    range: dependency_set.clone(),
+                                    version,
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            },
+            DerivationTree::External(_) => {}
+            DerivationTree::Derived(derived) => {
+                suggestions.extend(self.suggestions(&derived.cause1));
+                suggestions.extend(self.suggestions(&derived.cause2));
+            }
+        }
+        suggestions
+    }
+}
+
+/// Returns `true` if the version falls below the lower bound of the given range.
+fn below(range: &Range<Version>, version: &Version) -> bool {
+    match range.iter().next() {
+        Some((Bound::Included(start), _)) => version < start,
+        Some((Bound::Excluded(start), _)) => version <= start,
+        Some((Bound::Unbounded, _)) | None => false,
+    }
+}
+
+/// Returns the lowest version named by the lower bound of the given range that the range
+/// includes, if any.
+///
+/// An excluded lower bound (e.g., `>3.10`) is not itself in the range, so the last release
+/// segment is incremented instead (e.g., `3.11`).
+fn lowest(range: &Range<Version>) -> Option<Version> {
+    let version = match range.iter().next()? {
+        (Bound::Included(start), _) => start.clone(),
+        (Bound::Excluded(start), _) => {
+            let mut release = start.release().to_vec();
+            *release.last_mut()? += 1;
+            Version::new(release)
+        }
+        (Bound::Unbounded, _) => return None,
+    };
+    range.contains(&version).then_some(version)
+}
+
+/// A suggested change to the user's requirements or environment that may resolve a conflict.
+#[derive(Derivative, Debug, Clone)]
+#[derivative(Hash, PartialEq, Eq)]
+pub(crate) enum PubGrubSuggestion {
+    /// No versions of a package satisfied a range, but versions outside the range are available.
+    NearestVersions {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        range: Range<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        lower: Option<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        upper: Option<Version>,
+    },
+    /// A direct requirement excluded versions of a package that are available.
+    RelaxRequirement {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        range: Range<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        version: Version,
+    },
+    /// A package requires a Python version other than the one being resolved for.
+    PythonVersion {
+        package: PubGrubPackage,
+        python: PubGrubPython,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        range: Range<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        version: Version,
+    },
+}
+
+impl std::fmt::Display for PubGrubSuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NearestVersions {
+                package,
+                range,
+                lower,
+                upper,
+            } => {
+                let nearest = match (lower, upper) {
+                    (Some(lower), Some(upper)) => {
+                        format!("{} and {}", lower.bold(), upper.bold())
+                    }
+                    (Some(version), None) | (None, Some(version)) => {
+                        format!("{}", version.bold())
+                    }
+                    (None, None) => unreachable!("at least one version is available"),
+                };
+                write!(
+                    f,
+                    "{}{} No versions of {} satisfy {}; the nearest available versions are {}",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    PackageRange::compatibility(package, range).bold(),
+                    nearest
+                )
+            }
+            Self::RelaxRequirement {
+                package,
+                range,
+                version,
+            } => {
+                write!(
+                    f,
+                    "{}{} {} is constrained to {} by a direct requirement, but {}=={} is available (try: relaxing the requirement on {})",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    PackageRange::compatibility(package, range).bold(),
+                    package.bold(),
+                    version.bold(),
+                    package.bold(),
+                )
+            }
+            Self::PythonVersion {
+                package,
+                python,
+                range,
+                version,
+            } => {
+                let flag = match python {
+                    PubGrubPython::Installed => "--python",
+                    PubGrubPython::Target => "--python-version",
+                };
+                write!(
+                    f,
+                    "{}{} {} requires Python {}, which may be satisfied by a different Python version (try: `{flag} {version}`)",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    range.bold(),
+                )
+            }
+        }
+    }
+}
+
 #[derive(Derivative, Debug, Clone)]
 #[derivative(Hash, PartialEq, Eq)]
 pub(crate) enum PubGrubHint {
@@ -582,6 +814,12 @@ pub(crate) enum PubGrubHint {
     NoIndex,
     /// A package was not found in the registry, but network access was disabled.
     Offline,
+    /// A package was not found in the registry, but packages with similar names are available.
+    SimilarPackages {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        similar: Vec<PackageName>,
+    },
     /// Metadata for a package could not be parsed.
     InvalidPackageMetadata {
         package: PubGrubPackage,
@@ -660,6 +898,25 @@ impl std::fmt::Display for PubGrubHint {
                     ":".bold(),
                 )
             }
+            Self::SimilarPackages { package, similar } => {
+                let similar = match similar.as_slice() {
+                    [] => unreachable!("at least one similar package is available"),
+                    [name] => format!("{}", name.bold()),
+                    [rest @ .., last] => format!(
+                        "{} or {}",
+                        rest.iter().map(|name| name.bold().to_string()).join(", "),
+                        last.bold()
+                    ),
+                };
+                write!(
+                    f,
+                    "{}{} No package named {} was found; did you mean {}?",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    similar,
+                )
+            }
             Self::InvalidPackageMetadata { package, reason } => {
                 write!(
                     f,
@@ -981,3 +1238,42 @@ impl<T: std::fmt::Display> std::fmt::Display for Padded<'_, T> {
         write!(f, "{result}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep440_rs::Version;
+    use pubgrub::range::Range;
+
+    use super::lowest;
+
+    #[test]
+    fn lowest_included() {
+        let range = Range::higher_than(Version::from_str("3.10").unwrap());
+        assert_eq!(lowest(&range), Some(Version::from_str("3.10").unwrap()));
+    }
+
+    #[test]
+    fn lowest_excluded() {
+        let range = Range::strictly_higher_than(Version::from_str("3.10").unwrap());
+        assert_eq!(lowest(&range), Some(Version::from_str("3.11").unwrap()));
+
+        let range = Range::strictly_higher_than(Version::from_str("3.10.2").unwrap());
+        assert_eq!(lowest(&range), Some(Version::from_str("3.10.3").unwrap()));
+    }
+
+    #[test]
+    fn lowest_excluded_outside_range() {
+        let range = Range::strictly_higher_than(Version::from_str("3.10").unwrap()).intersection(
+            &Range::strictly_lower_than(Version::from_str("3.10.5").unwrap()),
+        );
+        assert_eq!(lowest(&range), None);
+    }
+
+    #[test]
+    fn lowest_unbounded() {
+        let range = Range::strictly_lower_than(Version::from_str("3.10").unwrap());
+        assert_eq!(lowest(&range), None);
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use pubgrub::range::Range;
 
-use pep440_rs::{Operator, PreRelease, Version, VersionSpecifier};
+use pep440_rs::{Operator, PreRelease, Version, VersionSpecifier, VersionSpecifiers};
 
 use crate::ResolveError;
 
@@ -114,3 +114,17 @@ impl TryFrom<&VersionSpecifier> for PubGrubSpecifier {
         Ok(Self(ranges))
     }
 }
+
+/// Returns `true` if at least one version could satisfy all of the given specifiers.
+///
+/// For example, `>=2,<1` can never be satisfied. Specifiers that can't be converted to a range
+/// (e.g., an invalid `~=`) are assumed to be satisfiable, and left to the resolver to report.
+pub fn is_satisfiable(specifiers: &VersionSpecifiers) -> bool {
+    specifiers
+        .iter()
+        .map(PubGrubSpecifier::try_from)
+        .try_fold(Range::full(), |range, specifier| {
+            Ok::<_, ResolveError>(range.intersection(&Range::from(specifier?)))
+        })
+        .map_or(true, |range| !range.is_empty())
+}
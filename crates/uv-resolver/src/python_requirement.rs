@@ -9,6 +9,9 @@ pub struct PythonRequirement {
     /// dependencies. This is typically the same as the installed version, but may be different
     /// when specifying an alternate Python version for the resolution.
     target: StringVersion,
+    /// The complete marker environment for which we are resolving dependencies, if known. When
+    /// set, the target version of Python is that of the environment.
+    target_environment: Option<MarkerEnvironment>,
 }
 
 impl PythonRequirement {
@@ -16,6 +19,7 @@ impl PythonRequirement {
         Self {
             installed: interpreter.python_full_version().clone(),
             target: target.clone(),
+            target_environment: None,
         }
     }
 
@@ -23,6 +27,19 @@ impl PythonRequirement {
         Self::new(interpreter, env.python_full_version())
     }
 
+    /// Create a [`PythonRequirement`] that targets a fully specified (and possibly foreign)
+    /// environment, such as another platform, rather than only a different version of Python.
+    ///
+    /// The resolver uses the target environment to evaluate markers, unless provided with a marker
+    /// environment explicitly.
+    pub fn from_target_environment(interpreter: &Interpreter, env: MarkerEnvironment) -> Self {
+        Self {
+            installed: interpreter.python_full_version().clone(),
+            target: env.python_full_version().clone(),
+            target_environment: Some(env),
+        }
+    }
+
     /// Return the installed version of Python.
     pub fn installed(&self) -> &StringVersion {
         &self.installed
@@ -32,4 +49,9 @@ impl PythonRequirement {
     pub fn target(&self) -> &StringVersion {
         &self.target
     }
+
+    /// Return the complete target marker environment, if known.
+    pub fn target_environment(&self) -> Option<&MarkerEnvironment> {
+        self.target_environment.as_ref()
+    }
 }
@@ -0,0 +1,298 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+/// The minimum time since a distribution was uploaded, before it may enter a resolution (e.g.,
+/// `7d`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ReleaseAge {
+    value: u64,
+    unit: ReleaseAgeUnit,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum ReleaseAgeUnit {
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+}
+
+impl ReleaseAgeUnit {
+    fn seconds(self) -> u64 {
+        match self {
+            Self::Minutes => 60,
+            Self::Hours => 60 * 60,
+            Self::Days => 24 * 60 * 60,
+            Self::Weeks => 7 * 24 * 60 * 60,
+        }
+    }
+
+    fn suffix(self) -> char {
+        match self {
+            Self::Minutes => 'm',
+            Self::Hours => 'h',
+            Self::Days => 'd',
+            Self::Weeks => 'w',
+        }
+    }
+}
+
+impl ReleaseAge {
+    /// Returns the age as a [`Duration`].
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.value.saturating_mul(self.unit.seconds()))
+    }
+
+    /// Returns the upload time, in milliseconds, after which distributions are too recent, given
+    /// the current time in milliseconds.
+    fn cutoff_millis(&self, now: i64) -> i64 {
+        let age = i64::try_from(self.duration().as_millis()).unwrap_or(i64::MAX);
+        now.saturating_sub(age)
+    }
+}
+
+impl FromStr for ReleaseAge {
+    type Err = String;
+
+    /// Parse a [`ReleaseAge`] from a number followed by a unit of minutes, hours, days, or weeks
+    /// (e.g., `30m`, `12h`, `7d`, or `2w`).
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let err = || {
+            format!(
+                "`{input}` is not a valid release age; expected a number followed by `m`, `h`, `d`, or `w` (e.g., `7d`)"
+            )
+        };
+        let mut chars = input.chars();
+        let unit = match chars.next_back().ok_or_else(err)? {
+            'm' => ReleaseAgeUnit::Minutes,
+            'h' => ReleaseAgeUnit::Hours,
+            'd' => ReleaseAgeUnit::Days,
+            'w' => ReleaseAgeUnit::Weeks,
+            _ => return Err(err()),
+        };
+        let value = chars.as_str().parse::<u64>().map_err(|_| err())?;
+        Ok(Self { value, unit })
+    }
+}
+
+impl Display for ReleaseAge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.suffix())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ReleaseAge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ReleaseAge::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ReleaseAge {
+    fn schema_name() -> String {
+        "ReleaseAge".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some(r"^\d+[mhdw]$".to_string()),
+                ..schemars::schema::StringValidation::default()
+            })),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("The minimum time since a distribution was uploaded, as a number followed by `m`, `h`, `d`, or `w` (e.g., `7d`).".to_string()),
+              ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// A minimum release age for a specific package, e.g., `requests=3d`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageReleaseAge {
+    name: PackageName,
+    age: ReleaseAge,
+}
+
+impl FromStr for PackageReleaseAge {
+    type Err = String;
+
+    /// Parse a [`PackageReleaseAge`] from a package name and a release age, separated by `=`
+    /// (e.g., `requests=3d`).
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let Some((name, age)) = input.split_once('=') else {
+            return Err(format!(
+                "`{input}` must be a package name and a release age, separated by `=` (e.g., `requests=3d`)"
+            ));
+        };
+        let name = PackageName::from_str(name.trim()).map_err(|err| err.to_string())?;
+        let age = ReleaseAge::from_str(age.trim())?;
+        Ok(Self { name, age })
+    }
+}
+
+impl Display for PackageReleaseAge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.age)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PackageReleaseAge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        PackageReleaseAge::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for PackageReleaseAge {
+    fn schema_name() -> String {
+        "PackageReleaseAge".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("The name of a package and a minimum release age, separated by `=` (e.g., `requests=3d`).".to_string()),
+              ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// A policy that excludes distributions uploaded more recently than a minimum age, unless their
+/// version is pinned exactly by a direct requirement or constraint.
+#[derive(Debug, Default, Clone)]
+pub struct MinReleaseAge {
+    /// The minimum release age for all packages.
+    default: Option<ReleaseAge>,
+    /// The minimum release age for specific packages, overriding the default.
+    packages: FxHashMap<PackageName, ReleaseAge>,
+    /// The versions that are pinned exactly (e.g., `flask==3.0.0`) by a direct requirement or
+    /// constraint, and so exempt.
+    pinned: FxHashMap<PackageName, FxHashSet<Version>>,
+}
+
+impl MinReleaseAge {
+    /// Create a [`MinReleaseAge`] from a default age and any per-package ages.
+    pub fn new(default: Option<ReleaseAge>, packages: Vec<PackageReleaseAge>) -> Self {
+        Self {
+            default,
+            packages: packages
+                .into_iter()
+                .map(|package| (package.name, package.age))
+                .collect(),
+            pinned: FxHashMap::default(),
+        }
+    }
+
+    /// Exempt the given versions, pinned exactly by a direct requirement or constraint, from the
+    /// policy.
+    #[must_use]
+    pub(crate) fn with_pinned(
+        mut self,
+        pinned: FxHashMap<PackageName, FxHashSet<Version>>,
+    ) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Returns `true` if the policy doesn't exclude any distributions.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.packages.is_empty()
+    }
+
+    /// Returns the minimum release age for the given package, if any.
+    pub fn get(&self, package_name: &PackageName) -> Option<ReleaseAge> {
+        self.packages
+            .get(package_name)
+            .or(self.default.as_ref())
+            .copied()
+    }
+
+    /// Returns the upload time cutoff, in milliseconds, for the given package at the current time,
+    /// along with the versions that are exempt from it.
+    pub(crate) fn cutoff(
+        &self,
+        package_name: &PackageName,
+    ) -> Option<(ReleaseAge, i64, FxHashSet<Version>)> {
+        let age = self.get(package_name)?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let pinned = self.pinned.get(package_name).cloned().unwrap_or_default();
+        Some((age, age.cutoff_millis(now), pinned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn release_age() {
+        let age = ReleaseAge::from_str("7d").unwrap();
+        assert_eq!(age.duration(), Duration::from_secs(7 * 24 * 60 * 60));
+        assert_eq!(age.to_string(), "7d");
+        assert_eq!(
+            ReleaseAge::from_str("2w").unwrap().duration(),
+            Duration::from_secs(14 * 24 * 60 * 60)
+        );
+        assert_eq!(
+            ReleaseAge::from_str("30m").unwrap().duration(),
+            Duration::from_secs(30 * 60)
+        );
+
+        assert!(ReleaseAge::from_str("").is_err());
+        assert!(ReleaseAge::from_str("7").is_err());
+        assert!(ReleaseAge::from_str("d").is_err());
+        assert!(ReleaseAge::from_str("-1d").is_err());
+        assert!(ReleaseAge::from_str("7y").is_err());
+    }
+
+    #[test]
+    fn package_release_age() {
+        let package = PackageReleaseAge::from_str("Requests=3d").unwrap();
+        assert_eq!(package.to_string(), "requests=3d");
+
+        assert!(PackageReleaseAge::from_str("requests").is_err());
+        assert!(PackageReleaseAge::from_str("requests=soon").is_err());
+    }
+
+    #[test]
+    fn min_release_age() {
+        let policy = MinReleaseAge::new(
+            Some(ReleaseAge::from_str("7d").unwrap()),
+            vec![PackageReleaseAge::from_str("requests=1h").unwrap()],
+        );
+        assert_eq!(
+            policy.get(&PackageName::from_str("flask").unwrap()),
+            Some(ReleaseAge::from_str("7d").unwrap())
+        );
+        assert_eq!(
+            policy.get(&PackageName::from_str("requests").unwrap()),
+            Some(ReleaseAge::from_str("1h").unwrap())
+        );
+        assert!(MinReleaseAge::default().is_empty());
+    }
+}
@@ -2,10 +2,13 @@ use std::borrow::Cow;
 use std::collections::BTreeSet;
 
 use owo_colors::OwoColorize;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
+use rustc_hash::FxHashMap;
 
-use distribution_types::{IndexUrl, LocalEditable, Name, SourceAnnotations, Verbatim};
+use distribution_types::{IndexUrl, LocalEditable, Name, Requirement, SourceAnnotations, Verbatim};
+use pep508_rs::MarkerTree;
 use pypi_types::HashDigest;
 use uv_normalize::PackageName;
 
@@ -34,6 +37,14 @@ pub struct DisplayResolutionGraph<'a> {
     annotation_style: AnnotationStyle,
     /// External sources for each package: requirements, constraints, and overrides.
     sources: SourceAnnotations,
+    /// The packages to consider unsafe in a requirements file, which are written to a separate
+    /// section at the end of the output.
+    unsafe_packages: &'a [PackageName],
+    /// Whether to pin the unsafe packages, rather than listing them by name.
+    allow_unsafe: bool,
+    /// The markers under which each package is required, to include in the output (e.g.,
+    /// `colorama==0.4.6 ; sys_platform == 'win32'`).
+    markers: FxHashMap<PackageName, MarkerTree>,
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
@@ -73,8 +84,32 @@ impl<'a> DisplayResolutionGraph<'a> {
             include_index_annotation,
             annotation_style,
             sources,
+            unsafe_packages: &[],
+            allow_unsafe: false,
+            markers: FxHashMap::default(),
         }
     }
+
+    /// Write the given packages to a separate section at the end of the output, as in
+    /// pip-compile. Unless `allow_unsafe` is set, the packages are listed by name, but not pinned.
+    #[must_use]
+    pub fn with_unsafe_packages(
+        mut self,
+        unsafe_packages: &'a [PackageName],
+        allow_unsafe: bool,
+    ) -> Self {
+        self.unsafe_packages = unsafe_packages;
+        self.allow_unsafe = allow_unsafe;
+        self
+    }
+
+    /// Include the markers under which each package is required in the output, as introduced by
+    /// the given top-level requirements.
+    #[must_use]
+    pub fn with_markers(mut self, requirements: &[Requirement]) -> Self {
+        self.markers = self.resolution.package_markers(requirements);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -146,125 +181,179 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
         // Sort the nodes by name, but with editable packages first.
         nodes.sort_unstable_by_key(|(index, node)| (node.key(), *index));
 
+        // Separate any unsafe packages, which are written at the end of the output.
+        let (unsafe_nodes, nodes): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|(_, node)| {
+            matches!(node, Node::Distribution(dist) if self.unsafe_packages.contains(dist.name()))
+        });
+
         // Print out the dependency graph.
         for (index, node) in nodes {
-            // Display the node itself.
-            let mut line = match node {
-                Node::Editable(editable) => format!("-e {}", editable.verbatim()),
-                Node::Distribution(dist) => {
-                    dist.to_requirements_txt(self.include_extras).to_string()
-                }
-            };
+            self.write_node(f, index, &node)?;
+        }
 
-            // Display the distribution hashes, if any.
-            let mut has_hashes = false;
-            if self.show_hashes {
-                for hash in node.hashes() {
-                    has_hashes = true;
-                    line.push_str(" \\\n");
-                    line.push_str("    --hash=");
-                    line.push_str(&hash.to_string());
+        // Print out the unsafe packages, pinning them only if allowed.
+        if !unsafe_nodes.is_empty() {
+            writeln!(f)?;
+            if self.show_hashes && !self.allow_unsafe {
+                writeln!(
+                    f,
+                    "{}",
+                    "# WARNING: The following packages were not pinned, but pip requires them to be\n\
+                    # pinned when the requirements file includes hashes and the requirement is not\n\
+                    # satisfied by a package already installed. Consider using the --allow-unsafe flag."
+                        .green()
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "{}",
+                    "# The following packages are considered to be unsafe in a requirements file:"
+                        .green()
+                )?;
+            }
+            for (index, node) in unsafe_nodes {
+                if self.allow_unsafe {
+                    self.write_node(f, index, &node)?;
+                } else if let Node::Distribution(dist) = node {
+                    writeln!(f, "{}", format!("# {}", dist.name()).green())?;
                 }
             }
+        }
 
-            // Determine the annotation comment and separator (between comment and requirement).
-            let mut annotation = None;
+        Ok(())
+    }
+}
 
-            // If enabled, include annotations to indicate the dependencies that requested each
-            // package (e.g., `# via mypy`).
-            if self.include_annotations {
-                // Display all dependencies.
-                let mut edges = self
-                    .resolution
-                    .petgraph
-                    .edges_directed(index, Direction::Incoming)
-                    .map(|edge| &self.resolution.petgraph[edge.source()])
-                    .collect::<Vec<_>>();
-                edges.sort_unstable_by_key(|package| package.name());
+impl DisplayResolutionGraph<'_> {
+    /// Write a single package, along with its hashes and annotations.
+    fn write_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        index: NodeIndex,
+        node: &Node<'_>,
+    ) -> std::fmt::Result {
+        // Display the node itself.
+        let mut line = match node {
+            Node::Editable(editable) => format!("-e {}", editable.verbatim()),
+            Node::Distribution(dist) => dist.to_requirements_txt(self.include_extras).to_string(),
+        };
 
-                // Include all external sources (e.g., requirements files).
-                let default = BTreeSet::default();
-                let source = match node {
-                    Node::Editable(editable) => {
-                        self.sources.get_editable(&editable.url).unwrap_or(&default)
-                    }
-                    Node::Distribution(dist) => self.sources.get(dist.name()).unwrap_or(&default),
-                };
+        // Display the markers under which the package is required, if any.
+        if let Node::Distribution(dist) = node {
+            if let Some(marker) = self.markers.get(dist.name()) {
+                line.push_str(" ; ");
+                line.push_str(&marker.to_string());
+            }
+        }
 
-                match self.annotation_style {
-                    AnnotationStyle::Line => match edges.as_slice() {
-                        [] if source.is_empty() => {}
-                        [] if source.len() == 1 => {
-                            let separator = if has_hashes { "\n    " } else { "  " };
-                            let comment = format!("# via {}", source.iter().next().unwrap())
-                                .green()
-                                .to_string();
-                            annotation = Some((separator, comment));
-                        }
-                        edges => {
-                            let separator = if has_hashes { "\n    " } else { "  " };
-                            let deps = edges
-                                .iter()
-                                .map(|dependency| format!("{}", dependency.name()))
-                                .chain(source.iter().map(std::string::ToString::to_string))
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            let comment = format!("# via {deps}").green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                    },
-                    AnnotationStyle::Split => match edges.as_slice() {
-                        [] if source.is_empty() => {}
-                        [] if source.len() == 1 => {
-                            let separator = "\n";
-                            let comment = format!("    # via {}", source.iter().next().unwrap())
-                                .green()
-                                .to_string();
-                            annotation = Some((separator, comment));
-                        }
-                        [edge] if source.is_empty() => {
-                            let separator = "\n";
-                            let comment = format!("    # via {}", edge.name()).green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                        edges => {
-                            let separator = "\n";
-                            let deps = source
-                                .iter()
-                                .map(std::string::ToString::to_string)
-                                .chain(
-                                    edges
-                                        .iter()
-                                        .map(|dependency| format!("{}", dependency.name())),
-                                )
-                                .map(|name| format!("    #   {name}"))
-                                .collect::<Vec<_>>()
-                                .join("\n");
-                            let comment = format!("    # via\n{deps}").green().to_string();
-                            annotation = Some((separator, comment));
-                        }
-                    },
-                }
+        // Display the distribution hashes, if any.
+        let mut has_hashes = false;
+        if self.show_hashes {
+            for hash in node.hashes() {
+                has_hashes = true;
+                line.push_str(" \\\n");
+                line.push_str("    --hash=");
+                line.push_str(&hash.to_string());
             }
+        }
+
+        // Determine the annotation comment and separator (between comment and requirement).
+        let mut annotation = None;
 
-            if let Some((separator, comment)) = annotation {
-                // Assemble the line with the annotations and remove trailing whitespaces.
-                for line in format!("{line:24}{separator}{comment}").lines() {
-                    let line = line.trim_end();
-                    writeln!(f, "{line}")?;
+        // If enabled, include annotations to indicate the dependencies that requested each
+        // package (e.g., `# via mypy`).
+        if self.include_annotations {
+            // Display all dependencies.
+            let mut edges = self
+                .resolution
+                .petgraph
+                .edges_directed(index, Direction::Incoming)
+                .map(|edge| &self.resolution.petgraph[edge.source()])
+                .collect::<Vec<_>>();
+            edges.sort_unstable_by_key(|package| package.name());
+
+            // Include all external sources (e.g., requirements files).
+            let default = BTreeSet::default();
+            let source = match node {
+                Node::Editable(editable) => {
+                    self.sources.get_editable(&editable.url).unwrap_or(&default)
                 }
-            } else {
-                // Write the line as is.
+                Node::Distribution(dist) => self.sources.get(dist.name()).unwrap_or(&default),
+            };
+
+            match self.annotation_style {
+                AnnotationStyle::Line => match edges.as_slice() {
+                    [] if source.is_empty() => {}
+                    [] if source.len() == 1 => {
+                        let separator = if has_hashes { "\n    " } else { "  " };
+                        let comment = format!("# via {}", source.iter().next().unwrap())
+                            .green()
+                            .to_string();
+                        annotation = Some((separator, comment));
+                    }
+                    edges => {
+                        let separator = if has_hashes { "\n    " } else { "  " };
+                        let deps = edges
+                            .iter()
+                            .map(|dependency| format!("{}", dependency.name()))
+                            .chain(source.iter().map(std::string::ToString::to_string))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let comment = format!("# via {deps}").green().to_string();
+                        annotation = Some((separator, comment));
+                    }
+                },
+                AnnotationStyle::Split => match edges.as_slice() {
+                    [] if source.is_empty() => {}
+                    [] if source.len() == 1 => {
+                        let separator = "\n";
+                        let comment = format!("    # via {}", source.iter().next().unwrap())
+                            .green()
+                            .to_string();
+                        annotation = Some((separator, comment));
+                    }
+                    [edge] if source.is_empty() => {
+                        let separator = "\n";
+                        let comment = format!("    # via {}", edge.name()).green().to_string();
+                        annotation = Some((separator, comment));
+                    }
+                    edges => {
+                        let separator = "\n";
+                        let deps = source
+                            .iter()
+                            .map(std::string::ToString::to_string)
+                            .chain(
+                                edges
+                                    .iter()
+                                    .map(|dependency| format!("{}", dependency.name())),
+                            )
+                            .map(|name| format!("    #   {name}"))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        let comment = format!("    # via\n{deps}").green().to_string();
+                        annotation = Some((separator, comment));
+                    }
+                },
+            }
+        }
+
+        if let Some((separator, comment)) = annotation {
+            // Assemble the line with the annotations and remove trailing whitespaces.
+            for line in format!("{line:24}{separator}{comment}").lines() {
+                let line = line.trim_end();
                 writeln!(f, "{line}")?;
             }
+        } else {
+            // Write the line as is.
+            writeln!(f, "{line}")?;
+        }
 
-            // If enabled, include indexes to indicate which index was used for each package (e.g.,
-            // `# from https://pypi.org/simple`).
-            if self.include_index_annotation {
-                if let Some(index) = node.index() {
-                    let url = index.redacted();
-                    writeln!(f, "{}", format!("    # from {url}").green())?;
-                }
+        // If enabled, include indexes to indicate which index was used for each package (e.g.,
+        // `# from https://pypi.org/simple`).
+        if self.include_index_annotation {
+            if let Some(index) = node.index() {
+                let url = index.redacted();
+                writeln!(f, "{}", format!("    # from {url}").green())?;
             }
         }
 
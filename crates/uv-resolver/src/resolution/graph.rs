@@ -11,12 +11,15 @@ use distribution_types::{
     ResolvedDist, VersionId, VersionOrUrlRef,
 };
 use pep440_rs::{Version, VersionSpecifier};
-use pep508_rs::MarkerEnvironment;
-use pypi_types::Yanked;
+use pep508_rs::{ExtraName, MarkerEnvironment, MarkerTree};
+use petgraph::visit::EdgeRef;
+use pypi_types::{HashDigest, Yanked};
 use uv_normalize::PackageName;
+use uv_types::HashStrategy;
 
 use crate::dependency_provider::UvDependencyProvider;
 use crate::editables::Editables;
+use crate::marker::{resolve_extras, simplify_markers, substitute_extras};
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
 use crate::pubgrub::{PubGrubDistribution, PubGrubPackageInner};
@@ -51,6 +54,7 @@ impl ResolutionGraph {
         state: &State<UvDependencyProvider>,
         preferences: &Preferences,
         editables: Editables,
+        hasher: &HashStrategy,
     ) -> anyhow::Result<Self, ResolveError> {
         // Collect and validate the extras.
         let mut extras = FxHashMap::default();
@@ -215,7 +219,7 @@ impl ResolutionGraph {
                     };
 
                     // Extract the metadata.
-                    let metadata = {
+                    let (metadata, archive_hashes) = {
                         let dist = PubGrubDistribution::from_registry(name, version);
 
                         let response = distributions.get(&dist.version_id()).unwrap_or_else(|| {
@@ -232,7 +236,17 @@ impl ResolutionGraph {
                             )
                         };
 
-                        archive.metadata.clone()
+                        (archive.metadata.clone(), archive.hashes.clone())
+                    };
+
+                    // If the index didn't provide any hashes (e.g., for a `--find-links` entry),
+                    // fall back to those of the artifact that was fetched during resolution.
+                    let hashes = if hashes.is_empty() && hasher.is_collect() {
+                        let mut digests = archive_hashes;
+                        digests.sort_unstable();
+                        digests
+                    } else {
+                        hashes
                     };
 
                     // Extract the extras.
@@ -411,11 +425,162 @@ impl ResolutionGraph {
             .map(|node| node.weight.dist)
     }
 
+    /// Iterate over the [`ResolvedDist`] entities in this resolution, along with their hashes.
+    pub fn distributions(&self) -> impl Iterator<Item = (&ResolvedDist, &[HashDigest])> {
+        self.petgraph
+            .node_weights()
+            .map(|node| (&node.dist, node.hashes.as_slice()))
+    }
+
     /// Return the [`ResolutionDiagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[ResolutionDiagnostic] {
         &self.diagnostics
     }
 
+    /// Return the markers under which each package in the resolution is required, as introduced
+    /// by the given top-level requirements and the dependencies of each package (e.g., a
+    /// `colorama ; sys_platform == 'win32'` dependency of `click`).
+    ///
+    /// Extras are tracked with the markers under which they're requested, such that the
+    /// dependencies of a conditionally-enabled extra (e.g., `torch[gpu] ; sys_platform == 'linux'`)
+    /// inherit its markers, even if the package itself is required unconditionally.
+    ///
+    /// Packages that are required unconditionally are omitted.
+    pub fn package_markers(
+        &self,
+        requirements: &[Requirement],
+    ) -> FxHashMap<PackageName, MarkerTree> {
+        // Determine the markers under which each package (and each of its extras) is required
+        // directly.
+        let direct = self
+            .petgraph
+            .node_indices()
+            .map(|index| {
+                let name = self.petgraph[index].name();
+                let mut markers = Vec::new();
+                let mut extras: FxHashMap<&ExtraName, Vec<MarkerTree>> = FxHashMap::default();
+                for requirement in requirements
+                    .iter()
+                    .filter(|requirement| &requirement.name == name)
+                {
+                    let marker = requirement
+                        .marker
+                        .clone()
+                        .map_or(MarkerTree::And(vec![]), |marker| {
+                            resolve_extras(marker, &|_| true)
+                        });
+                    for extra in &requirement.extras {
+                        extras.entry(extra).or_default().push(marker.clone());
+                    }
+                    markers.push(marker);
+                }
+
+                // Packages without any incoming edges (like editables) are roots of the graph.
+                if markers.is_empty()
+                    && self
+                        .petgraph
+                        .edges_directed(index, petgraph::Direction::Incoming)
+                        .next()
+                        .is_none()
+                {
+                    markers.push(MarkerTree::And(vec![]));
+                    for extra in &self.petgraph[index].extras {
+                        extras
+                            .entry(extra)
+                            .or_default()
+                            .push(MarkerTree::And(vec![]));
+                    }
+                }
+                (markers, extras)
+            })
+            .collect::<Vec<_>>();
+
+        // Propagate the markers along the edges of the graph, until they reach a fixed point. Each
+        // round extends the markers by one edge, so the number of rounds is bounded by the number
+        // of nodes.
+        let mut markers = direct
+            .iter()
+            .map(|(markers, extras)| {
+                (
+                    simplify_markers(MarkerTree::Or(markers.clone())),
+                    extras
+                        .iter()
+                        .map(|(extra, markers)| {
+                            (*extra, simplify_markers(MarkerTree::Or(markers.clone())))
+                        })
+                        .collect::<FxHashMap<_, _>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..self.petgraph.node_count() {
+            let mut changed = false;
+            for index in self.petgraph.node_indices() {
+                let dist = &self.petgraph[index];
+                let (direct_markers, direct_extras) = &direct[index.index()];
+                let mut disjuncts = direct_markers.clone();
+                let mut extras = direct_extras.clone();
+                for edge in self
+                    .petgraph
+                    .edges_directed(index, petgraph::Direction::Incoming)
+                {
+                    let parent = &self.petgraph[edge.source()];
+                    let (parent_markers, parent_extras) = &markers[edge.source().index()];
+                    for requirement in parent
+                        .metadata
+                        .requires_dist
+                        .iter()
+                        .filter(|requirement| &requirement.name == dist.name())
+                    {
+                        // Replace any `extra` markers with the markers under which the parent's
+                        // extras are enabled.
+                        let marker =
+                            requirement
+                                .marker
+                                .clone()
+                                .map_or(MarkerTree::And(vec![]), |marker| {
+                                    substitute_extras(marker, &|extra| {
+                                        parent_extras.get(extra).cloned()
+                                    })
+                                });
+                        let marker = MarkerTree::And(vec![parent_markers.clone(), marker]);
+                        for extra in &requirement.extras {
+                            if let Some(extra) = dist.extras.iter().find(|known| *known == extra) {
+                                extras.entry(extra).or_default().push(marker.clone());
+                            }
+                        }
+                        disjuncts.push(marker);
+                    }
+                }
+                expand_extra_markers(dist, &mut extras);
+                let marker = simplify_markers(MarkerTree::Or(disjuncts));
+                let extras = extras
+                    .into_iter()
+                    .map(|(extra, markers)| (extra, simplify_markers(MarkerTree::Or(markers))))
+                    .collect::<FxHashMap<_, _>>();
+                if marker != markers[index.index()].0 || extras != markers[index.index()].1 {
+                    markers[index.index()] = (marker, extras);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        // Omit any package that's required unconditionally. If the markers can't be determined
+        // (e.g., due to overrides), the package is treated as unconditional, too.
+        self.petgraph
+            .node_indices()
+            .filter_map(|index| {
+                let (marker, _) = markers[index.index()].clone();
+                match &marker {
+                    MarkerTree::And(trees) | MarkerTree::Or(trees) if trees.is_empty() => None,
+                    _ => Some((self.petgraph[index].name().clone(), marker)),
+                }
+            })
+            .collect()
+    }
+
     /// Return the marker tree specific to this resolution.
     ///
     /// This accepts a manifest, in-memory-index and marker environment. All
@@ -559,6 +724,8 @@ impl ResolutionGraph {
             };
             conjuncts.push(MarkerTree::Expression(expr));
         }
+        // Sort the terms, such that the expression is stable across invocations.
+        conjuncts.sort_by_cached_key(ToString::to_string);
         Ok(MarkerTree::And(conjuncts))
     }
 
@@ -569,7 +736,7 @@ impl ResolutionGraph {
             let mut locked_dist = lock::Distribution::from_annotated_dist(dist)?;
             for edge in self.petgraph.neighbors(node_index) {
                 let dependency_dist = &self.petgraph[edge];
-                locked_dist.add_dependency(dependency_dist);
+                locked_dist.add_dependency(dist, dependency_dist);
             }
             locked_dists.push(locked_dist);
         }
@@ -595,3 +762,49 @@ impl From<ResolutionGraph> for distribution_types::Resolution {
         )
     }
 }
+
+/// Extend the markers under which each extra of a distribution is enabled with those of the extras
+/// that enable it through a requirement on the distribution itself (e.g., `black[d] ; extra ==
+/// 'all'` enables `d` whenever `all` is enabled).
+fn expand_extra_markers<'a>(
+    dist: &'a AnnotatedDist,
+    extras: &mut FxHashMap<&'a ExtraName, Vec<MarkerTree>>,
+) {
+    let requirements = dist
+        .metadata
+        .requires_dist
+        .iter()
+        .filter(|requirement| &requirement.name == dist.name())
+        .collect::<Vec<_>>();
+
+    // Each round follows one more requirement on the distribution itself, so the number of rounds
+    // is bounded by the number of such requirements.
+    for _ in 0..requirements.len() {
+        let enabled = extras
+            .iter()
+            .map(|(extra, markers)| (*extra, simplify_markers(MarkerTree::Or(markers.clone()))))
+            .collect::<FxHashMap<_, _>>();
+        let mut changed = false;
+        for requirement in &requirements {
+            let marker = requirement
+                .marker
+                .clone()
+                .map_or(MarkerTree::And(vec![]), |marker| {
+                    substitute_extras(marker, &|extra| enabled.get(extra).cloned())
+                });
+            if matches!(&marker, MarkerTree::Or(trees) if trees.is_empty()) {
+                continue;
+            }
+            for extra in &requirement.extras {
+                let markers = extras.entry(extra).or_default();
+                if !markers.contains(&marker) {
+                    markers.push(marker.clone());
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
@@ -11,9 +11,11 @@ use uv_normalize::{ExtraName, PackageName};
 
 pub use crate::resolution::display::{AnnotationStyle, DisplayResolutionGraph};
 pub use crate::resolution::graph::ResolutionGraph;
+pub use crate::resolution::visualize::{DisplayDependencyGraph, GraphFormat};
 
 mod display;
 mod graph;
+mod visualize;
 
 /// A pinned package with its resolved distribution and metadata. The [`ResolvedDist`] refers to a
 /// specific distribution (e.g., a specific wheel), while the [`Metadata23`] refers to the metadata
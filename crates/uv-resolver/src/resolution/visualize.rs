@@ -0,0 +1,300 @@
+use std::fmt::{Display, Write};
+
+use itertools::Itertools;
+use petgraph::visit::EdgeRef;
+
+use distribution_types::{Name, Requirement};
+use pep508_rs::MarkerTree;
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::marker::simplify_markers;
+use crate::ResolutionGraph;
+
+/// The format in which to render a dependency graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum GraphFormat {
+    /// Render the graph in the Graphviz DOT language.
+    Dot,
+    /// Render the graph as a Mermaid flowchart.
+    Mermaid,
+}
+
+/// The DOT identifier of the synthetic root node.
+///
+/// Since package nodes are identified by their normalized name, which can only contain ASCII
+/// letters, digits, `-`, `_`, and `.`, the colons ensure that no package (e.g., one named `root`)
+/// can collide with it.
+const DOT_ROOT_ID: &str = ":root:";
+
+/// A [`std::fmt::Display`] implementation that renders the resolution as a dependency graph, with
+/// each edge labeled by the extras and markers under which the dependency is required.
+#[derive(Debug)]
+pub struct DisplayDependencyGraph<'a> {
+    /// The underlying graph.
+    resolution: &'a ResolutionGraph,
+    /// The top-level requirements, which are rendered as dependencies of a synthetic root node.
+    requirements: &'a [Requirement],
+    /// The format in which to render the graph.
+    format: GraphFormat,
+}
+
+/// A node in the rendered graph.
+#[derive(Debug)]
+struct Node<'a> {
+    id: String,
+    label: String,
+    name: Option<&'a PackageName>,
+}
+
+/// A labeled edge in the rendered graph.
+#[derive(Debug)]
+struct Edge {
+    source: usize,
+    target: usize,
+    label: Option<String>,
+}
+
+impl<'a> DisplayDependencyGraph<'a> {
+    /// Create a new [`DisplayDependencyGraph`] for the given graph.
+    pub fn new(
+        resolution: &'a ResolutionGraph,
+        requirements: &'a [Requirement],
+        format: GraphFormat,
+    ) -> Self {
+        Self {
+            resolution,
+            requirements,
+            format,
+        }
+    }
+
+    /// Collect the nodes and edges of the graph, sorted by package name.
+    fn collect(&self) -> (Vec<Node<'a>>, Vec<Edge>) {
+        let petgraph = &self.resolution.petgraph;
+
+        // Add a synthetic root node, followed by a node for each package.
+        let mut nodes = vec![Node {
+            id: match self.format {
+                GraphFormat::Dot => DOT_ROOT_ID.to_string(),
+                GraphFormat::Mermaid => "root".to_string(),
+            },
+            label: "requirements".to_string(),
+            name: None,
+        }];
+        let indices = petgraph
+            .node_indices()
+            .sorted_by_key(|index| petgraph[*index].name())
+            .collect::<Vec<_>>();
+        for index in &indices {
+            let dist = &petgraph[*index];
+            nodes.push(Node {
+                id: match self.format {
+                    GraphFormat::Dot => dist.name().to_string(),
+                    GraphFormat::Mermaid => format!("n{}", nodes.len()),
+                },
+                label: dist.to_requirements_txt(true).to_string(),
+                name: Some(dist.name()),
+            });
+        }
+        let position = |name: &PackageName| {
+            nodes
+                .iter()
+                .position(|node| node.name == Some(name))
+                .expect("Every package should have a node")
+        };
+
+        // Add an edge from the root to each top-level requirement that was included in the
+        // resolution.
+        let mut edges = Vec::new();
+        for (name, requirements) in &self
+            .requirements
+            .iter()
+            .filter(|requirement| {
+                indices
+                    .iter()
+                    .any(|index| petgraph[*index].name() == &requirement.name)
+            })
+            .sorted_by_key(|requirement| &requirement.name)
+            .chunk_by(|requirement| &requirement.name)
+        {
+            edges.push(Edge {
+                source: 0,
+                target: position(name),
+                label: edge_label(
+                    requirements.map(|requirement| (&requirement.extras, &requirement.marker)),
+                ),
+            });
+        }
+
+        // Add an edge for each dependency, labeled by the requirements that introduced it.
+        for index in &indices {
+            let dist = &petgraph[*index];
+            for target in petgraph
+                .edges(*index)
+                .map(|edge| &petgraph[edge.target()])
+                .sorted_by_key(|target| target.name())
+            {
+                edges.push(Edge {
+                    source: position(dist.name()),
+                    target: position(target.name()),
+                    label: edge_label(
+                        dist.metadata
+                            .requires_dist
+                            .iter()
+                            .filter(|requirement| &requirement.name == target.name())
+                            .map(|requirement| (&requirement.extras, &requirement.marker)),
+                    ),
+                });
+            }
+        }
+
+        (nodes, edges)
+    }
+}
+
+/// Render the label for an edge introduced by the given requirements, as the requested extras
+/// and markers of each requirement (e.g., `[socks] ; python_version < '3.8'`).
+///
+/// Returns `None` if none of the requirements request extras or declare markers.
+fn edge_label<'a>(
+    requirements: impl Iterator<Item = (&'a Vec<ExtraName>, &'a Option<MarkerTree>)>,
+) -> Option<String> {
+    let labels = requirements
+        .filter_map(|(extras, marker)| {
+            let marker = marker
+                .clone()
+                .map(simplify_markers)
+                .map(|marker| marker.to_string())
+                .filter(|marker| !marker.is_empty());
+            match (extras.is_empty(), marker) {
+                (true, None) => None,
+                (false, None) => Some(format!("[{}]", extras.iter().join(", "))),
+                (true, Some(marker)) => Some(format!("; {marker}")),
+                (false, Some(marker)) => Some(format!("[{}] ; {marker}", extras.iter().join(", "))),
+            }
+        })
+        .unique()
+        .collect::<Vec<_>>();
+    if labels.is_empty() {
+        None
+    } else {
+        Some(labels.join(" | "))
+    }
+}
+
+/// Escape a string for use in a double-quoted DOT identifier.
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape a string for use in a double-quoted Mermaid label.
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+impl Display for DisplayDependencyGraph<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (nodes, edges) = self.collect();
+        let mut output = String::new();
+        match self.format {
+            GraphFormat::Dot => {
+                writeln!(output, "digraph dependencies {{")?;
+                writeln!(output, "    node [shape=box];")?;
+                for (index, node) in nodes.iter().enumerate() {
+                    let shape = if index == 0 { ", shape=ellipse" } else { "" };
+                    writeln!(
+                        output,
+                        "    \"{}\" [label=\"{}\"{shape}];",
+                        escape_dot(&node.id),
+                        escape_dot(&node.label)
+                    )?;
+                }
+                for edge in &edges {
+                    write!(
+                        output,
+                        "    \"{}\" -> \"{}\"",
+                        escape_dot(&nodes[edge.source].id),
+                        escape_dot(&nodes[edge.target].id)
+                    )?;
+                    if let Some(label) = &edge.label {
+                        write!(output, " [label=\"{}\"]", escape_dot(label))?;
+                    }
+                    writeln!(output, ";")?;
+                }
+                writeln!(output, "}}")?;
+            }
+            GraphFormat::Mermaid => {
+                writeln!(output, "flowchart TD")?;
+                for (index, node) in nodes.iter().enumerate() {
+                    if index == 0 {
+                        writeln!(output, "    {}([\"{}\"])", node.id, node.label)?;
+                    } else {
+                        writeln!(
+                            output,
+                            "    {}[\"{}\"]",
+                            node.id,
+                            escape_mermaid(&node.label)
+                        )?;
+                    }
+                }
+                for edge in &edges {
+                    let source = &nodes[edge.source].id;
+                    let target = &nodes[edge.target].id;
+                    if let Some(label) = &edge.label {
+                        writeln!(
+                            output,
+                            "    {source} -->|\"{}\"| {target}",
+                            escape_mermaid(label)
+                        )?;
+                    } else {
+                        writeln!(output, "    {source} --> {target}")?;
+                    }
+                }
+            }
+        }
+        f.write_str(&output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn edge_labels() {
+        let socks = vec![ExtraName::from_str("socks").unwrap()];
+        let none = vec![];
+        let windows = Some(MarkerTree::from_str("sys_platform == 'win32'").unwrap());
+
+        assert_eq!(edge_label([(&none, &None)].into_iter()), None);
+        assert_eq!(
+            edge_label([(&socks, &None)].into_iter()).as_deref(),
+            Some("[socks]")
+        );
+        assert_eq!(
+            edge_label([(&none, &windows)].into_iter()).as_deref(),
+            Some("; sys_platform == 'win32'")
+        );
+        assert_eq!(
+            edge_label([(&socks, &windows), (&none, &None), (&socks, &windows)].into_iter())
+                .as_deref(),
+            Some("[socks] ; sys_platform == 'win32'")
+        );
+    }
+
+    #[test]
+    fn root_id_is_not_a_package_name() {
+        assert!(PackageName::from_str(DOT_ROOT_ID).is_err());
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_dot(r#"a "b" \c"#), r#"a \"b\" \\c"#);
+        assert_eq!(escape_mermaid(r#"a "b""#), "a #quot;b#quot;");
+    }
+}
@@ -12,7 +12,7 @@ use pep440_rs::Version;
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::pubgrub::{PubGrubPackage, PubGrubPackageInner};
 use crate::resolver::Request;
-use crate::{InMemoryIndex, ResolveError, VersionsResponse};
+use crate::{InMemoryIndex, PrefetchStrategy, ResolveError, VersionsResponse};
 
 enum BatchPrefetchStrategy {
     /// Go through the next versions assuming the existing selection and its constraints
@@ -35,14 +35,24 @@ enum BatchPrefetchStrategy {
 /// This is an optimization specifically targeted at cold cache urllib3/boto3/botocore, where we
 /// have to fetch the metadata for a lot of versions.
 ///
-/// Note that these all heuristics that could totally prefetch lots of irrelevant versions.
+/// Note that these all heuristics that could totally prefetch lots of irrelevant versions. The
+/// [`PrefetchStrategy`] controls when prefetching starts and how far ahead it looks.
 #[derive(Default)]
 pub(crate) struct BatchPrefetcher {
+    strategy: PrefetchStrategy,
     tried_versions: FxHashMap<PubGrubPackage, usize>,
     last_prefetch: FxHashMap<PubGrubPackage, usize>,
 }
 
 impl BatchPrefetcher {
+    /// Create a [`BatchPrefetcher`] with the given [`PrefetchStrategy`].
+    pub(crate) fn new(strategy: PrefetchStrategy) -> Self {
+        Self {
+            strategy,
+            ..Self::default()
+        }
+    }
+
     /// Prefetch a large number of versions if we already unsuccessfully tried many versions.
     pub(crate) fn prefetch_batches(
         &mut self,
@@ -67,7 +77,10 @@ impl BatchPrefetcher {
         if !do_prefetch {
             return Ok(());
         }
-        let total_prefetch = min(num_tried, 50);
+        let total_prefetch = min(num_tried, self.strategy.versions(name));
+        if total_prefetch == 0 {
+            return Ok(());
+        }
 
         // This is immediate, we already fetched the version map.
         let versions_response = index
@@ -149,8 +162,7 @@ impl BatchPrefetcher {
             prefetch_count += 1;
 
             if index.distributions().register(candidate.version_id()) {
-                let request = Request::from(dist);
-                request_sink.blocking_send(request)?;
+                request_sink.blocking_send(Request::from(dist))?;
             }
         }
 
@@ -172,16 +184,18 @@ impl BatchPrefetcher {
         *self.tried_versions.entry(package).or_default() += 1;
     }
 
-    /// After 5, 10, 20, 40 tried versions, prefetch that many versions to start early but not
-    /// too aggressive. Later we schedule the prefetch of 50 versions every 20 versions, this gives
-    /// us a good buffer until we see prefetch again and is high enough to saturate the task pool.
+    /// After 5, 10, 20 tried versions (by default; scaled by the threshold), prefetch that many
+    /// versions to start early but not too aggressive. Later we schedule the prefetch of 50
+    /// versions every 20 versions, this gives us a good buffer until we see prefetch again and is
+    /// high enough to saturate the task pool.
     fn should_prefetch(&self, next: &PubGrubPackage) -> (usize, bool) {
+        let threshold = self.strategy.threshold();
         let num_tried = self.tried_versions.get(next).copied().unwrap_or_default();
         let previous_prefetch = self.last_prefetch.get(next).copied().unwrap_or_default();
-        let do_prefetch = (num_tried >= 5 && previous_prefetch < 5)
-            || (num_tried >= 10 && previous_prefetch < 10)
-            || (num_tried >= 20 && previous_prefetch < 20)
-            || (num_tried >= 20 && num_tried - previous_prefetch >= 20);
+        let do_prefetch = (num_tried >= threshold && previous_prefetch < threshold)
+            || (num_tried >= 2 * threshold && previous_prefetch < 2 * threshold)
+            || (num_tried >= 4 * threshold && previous_prefetch < 4 * threshold)
+            || (num_tried >= 4 * threshold && num_tried - previous_prefetch >= 4 * threshold);
         (num_tried, do_prefetch)
     }
 
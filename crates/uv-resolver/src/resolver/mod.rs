@@ -5,6 +5,7 @@ use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 use anyhow::Result;
 use dashmap::DashMap;
@@ -13,10 +14,12 @@ use itertools::Itertools;
 use pubgrub::error::PubGrubError;
 use pubgrub::range::Range;
 use pubgrub::solver::{Incompatibility, State};
+use pubgrub::term::Term;
 use rustc_hash::{FxHashMap, FxHashSet};
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::oneshot;
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, enabled, instrument, trace, warn, Level};
 
 use distribution_types::{
@@ -38,8 +41,9 @@ use uv_types::{BuildContext, HashStrategy, InstalledPackagesProvider};
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::dependency_provider::UvDependencyProvider;
 use crate::editables::Editables;
-use crate::error::ResolveError;
+use crate::error::{LimitExceededError, ResolutionLimit, ResolveError};
 use crate::manifest::Manifest;
+use crate::package_policy::PolicyViolation;
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
 use crate::pubgrub::{
@@ -56,9 +60,12 @@ pub use crate::resolver::provider::{
     VersionsResponse, WheelMetadataResult,
 };
 use crate::resolver::reporter::Facade;
-pub use crate::resolver::reporter::{BuildId, Reporter};
-use crate::yanks::AllowedYanks;
-use crate::{DependencyMode, Exclusions, FlatIndex, Options};
+pub use crate::resolver::reporter::{BuildId, Reporter, ResolveReporter};
+use crate::yanks::{pinned_versions, AllowedYanks};
+use crate::{
+    DependencyMode, Exclusions, FlatIndex, Options, PackagePolicy, PrefetchStrategy,
+    ResolutionLimits,
+};
 
 mod batch_prefetch;
 mod index;
@@ -102,6 +109,8 @@ pub(crate) enum UnavailableVersion {
     InvalidStructure,
     /// The wheel metadata was not found in the cache and the network is not available.
     Offline,
+    /// The version is forbidden by the deny-list.
+    Denied,
     /// Forward any kind of resolver error.
     ResolverError(String),
 }
@@ -116,6 +125,7 @@ impl Display for UnavailableVersion {
             UnavailableVersion::Offline => f.write_str(
                 "network connectivity is disabled, but the metadata wasn't found in the cache",
             ),
+            UnavailableVersion::Denied => f.write_str("is on the deny-list"),
             UnavailableVersion::ResolverError(err) => f.write_str(err),
         }
     }
@@ -128,12 +138,17 @@ pub(crate) enum UnavailablePackage {
     NoIndex,
     /// Network requests were disabled (i.e., `--offline`), and the package was not found in the cache.
     Offline,
-    /// The package was not found in the registry.
-    NotFound,
+    /// The package was not found in the registry, along with any similarly-named packages that
+    /// are available.
+    NotFound { similar: Vec<PackageName> },
     /// The package metadata was found, but could not be parsed.
     InvalidMetadata(String),
     /// The package has an invalid structure.
     InvalidStructure(String),
+    /// The package is forbidden by the deny-list.
+    Denied,
+    /// The package is not on the allow-list.
+    NotAllowed,
 }
 
 impl UnavailablePackage {
@@ -141,9 +156,11 @@ impl UnavailablePackage {
         match self {
             UnavailablePackage::NoIndex => "was not found in the provided package locations",
             UnavailablePackage::Offline => "was not found in the cache",
-            UnavailablePackage::NotFound => "was not found in the package registry",
+            UnavailablePackage::NotFound { .. } => "was not found in the package registry",
             UnavailablePackage::InvalidMetadata(_) => "has invalid metadata",
             UnavailablePackage::InvalidStructure(_) => "has an invalid package format",
+            UnavailablePackage::Denied => "is on the deny-list",
+            UnavailablePackage::NotAllowed => "is not on the allow-list",
         }
     }
 }
@@ -188,10 +205,13 @@ struct ResolverState<InstalledPackages: InstalledPackagesProvider> {
     overrides: Overrides,
     preferences: Preferences,
     exclusions: Exclusions,
+    policy: PackagePolicy,
+    prefetch: PrefetchStrategy,
     editables: Editables,
     urls: Urls,
     locals: Locals,
     dependency_mode: DependencyMode,
+    limits: ResolutionLimits,
     hasher: HashStrategy,
     /// When not set, the resolver is in "universal" mode.
     markers: Option<MarkerEnvironment>,
@@ -204,6 +224,10 @@ struct ResolverState<InstalledPackages: InstalledPackagesProvider> {
     /// Incompatibilities for packages that are unavailable at specific versions.
     incomplete_packages: DashMap<PackageName, DashMap<Version, IncompletePackage>>,
     reporter: Option<Arc<dyn Reporter>>,
+    /// A token to abort the resolution cooperatively (e.g., on Ctrl-C).
+    cancellation: CancellationToken,
+    /// The number of times the resolver backtracked on each package.
+    backtracks: DashMap<PackageName, usize>,
 }
 
 impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
@@ -227,6 +251,10 @@ impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
     /// When a marker environment is provided, the resolver is in
     /// "non-universal" mode, which corresponds to standard `pip` behavior that
     /// works only for a specific marker environment.
+    ///
+    /// If no marker environment is provided, but the [`PythonRequirement`]
+    /// carries a complete target environment, the resolver is in
+    /// "non-universal" mode for that target environment.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         manifest: Manifest,
@@ -241,6 +269,13 @@ impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
         installed_packages: InstalledPackages,
         database: DistributionDatabase<'a, Context>,
     ) -> Result<Self, ResolveError> {
+        let markers = markers.or(python_requirement.target_environment());
+        let pinned = pinned_versions(manifest.requirements(markers, DependencyMode::Direct));
+        let min_release_age = manifest
+            .policy
+            .min_release_age()
+            .clone()
+            .with_pinned(pinned);
         let provider = DefaultResolverProvider::new(
             database,
             flat_index,
@@ -249,8 +284,11 @@ impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
             AllowedYanks::from_manifest(&manifest, markers, options.dependency_mode),
             hasher,
             options.exclude_newer,
+            min_release_age,
             build_context.no_binary(),
             build_context.no_build(),
+            build_context.prefer_source(),
+            build_context.prefer_binary(),
         );
 
         Self::new_custom_io(
@@ -281,12 +319,14 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
         provider: Provider,
         installed_packages: InstalledPackages,
     ) -> Result<Self, ResolveError> {
+        let markers = markers.or(python_requirement.target_environment());
         let state = ResolverState {
             index: index.clone(),
             unavailable_packages: DashMap::default(),
             incomplete_packages: DashMap::default(),
             selector: CandidateSelector::for_resolution(options, &manifest, markers),
             dependency_mode: options.dependency_mode,
+            limits: options.limits,
             urls: Urls::from_manifest(&manifest, markers, options.dependency_mode)?,
             locals: Locals::from_manifest(&manifest, markers, options.dependency_mode),
             project: manifest.project,
@@ -295,11 +335,15 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             overrides: manifest.overrides,
             preferences: Preferences::from_iter(manifest.preferences, markers),
             exclusions: manifest.exclusions,
+            policy: manifest.policy,
+            prefetch: manifest.prefetch,
             editables: Editables::from_requirements(manifest.editables),
             hasher: hasher.clone(),
             markers: markers.cloned(),
             python_requirement: python_requirement.clone(),
             reporter: None,
+            cancellation: CancellationToken::new(),
+            backtracks: DashMap::default(),
             installed_packages,
         };
         Ok(Self { state, provider })
@@ -319,6 +363,18 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
         }
     }
 
+    /// Set the [`CancellationToken`] with which to abort the resolution.
+    #[must_use]
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        Self {
+            state: ResolverState {
+                cancellation,
+                ..self.state
+            },
+            provider: self.provider,
+        }
+    }
+
     /// Resolve a set of requirements into a set of pinned versions.
     pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
         let state = Arc::new(self.state);
@@ -343,7 +399,8 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             .name("uv-resolver".into())
             .spawn(move || {
                 let result = solver.solve(request_sink);
-                tx.send(result).unwrap();
+                // The receiver is dropped if the resolution was cancelled or timed out.
+                let _ = tx.send(result);
             })
             .unwrap();
 
@@ -353,8 +410,30 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
                 .and_then(|result| result)
         };
 
-        // Wait for both to complete.
-        match tokio::try_join!(requests_fut, resolve_fut) {
+        // Wait for both to complete, unless the resolution is cancelled first. The solver thread
+        // will observe the cancellation and exit on its own.
+        //
+        // The solver only checks the timeout between steps, so a single step (e.g., building a
+        // source distribution to extract its metadata) could otherwise run past it.
+        let cancellation = state.cancellation.clone();
+        let resolution = async {
+            let resolution = async { tokio::try_join!(requests_fut, resolve_fut) };
+            match state.limits.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, resolution)
+                    .await
+                    .map_err(|_| ResolutionLimit::Timeout(timeout)),
+                None => Ok(resolution.await),
+            }
+        };
+        let result = tokio::select! {
+            biased;
+            () = cancellation.cancelled() => return Err(ResolveError::Cancelled),
+            result = resolution => match result {
+                Ok(result) => result,
+                Err(limit) => return Err(state.limit_exceeded(limit).into()),
+            },
+        };
+        match result {
             Ok(((), resolution)) => {
                 state.on_complete();
                 Ok(resolution)
@@ -402,14 +481,17 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         request_sink: Sender<Request>,
     ) -> Result<ResolutionGraph, ResolveError> {
         let root = PubGrubPackage::from(PubGrubPackageInner::Root(self.project.clone()));
-        let mut prefetcher = BatchPrefetcher::default();
+        let mut prefetcher = BatchPrefetcher::new(self.prefetch.clone());
         let mut state = SolveState {
             pubgrub: State::init(root.clone(), MIN_VERSION.clone()),
             next: root,
             pins: FilePins::default(),
             priorities: PubGrubPriorities::default(),
             added_dependencies: FxHashMap::default(),
+            restricted: FxHashSet::default(),
+            decisions: FxHashMap::default(),
         };
+        let start = Instant::now();
 
         debug!(
             "Solving with target Python version {}",
@@ -425,6 +507,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             if self.dependency_mode.is_transitive() {
                 Self::pre_visit(
                     state.pubgrub.partial_solution.prioritized_packages(),
+                    &state.priorities,
                     &request_sink,
                 )?;
             }
@@ -447,10 +530,60 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     &state.pubgrub,
                     &self.preferences,
                     self.editables.clone(),
+                    &self.hasher,
                 );
             };
             state.next = highest_priority_pkg;
 
+            // If the package was decided previously, the resolver must have backtracked past that
+            // decision.
+            if let Some(version) = state.decisions.remove(&state.next) {
+                self.on_backtrack(&state.next, &version);
+                if let PubGrubPackageInner::Package { name, .. } = &*state.next {
+                    *self.backtracks.entry(name.clone()).or_default() += 1;
+                }
+            }
+
+            // Abort if the resolution was cancelled.
+            if self.cancellation.is_cancelled() {
+                return Err(ResolveError::Cancelled);
+            }
+
+            // Abort if the resolution has exceeded its limits.
+            if let Some(limit) = self.exceeded_limit(start) {
+                return Err(self.limit_exceeded(limit).into());
+            }
+
+            // Forbid any versions that are excluded by the package policy (e.g., `--deny-package`),
+            // such that PubGrub can explain which dependency chain required them.
+            if let PubGrubPackageInner::Package { ref name, .. } = &*state.next {
+                if !state.restricted.contains(&state.next) {
+                    state.restricted.insert(state.next.clone());
+                    if let Some((range, violation)) = self.policy.restriction(name)? {
+                        debug!("Enforcing package policy for {name}: {violation:?}");
+                        let reason = match violation {
+                            PolicyViolation::Denied => {
+                                UnavailableReason::Package(UnavailablePackage::Denied)
+                            }
+                            PolicyViolation::DeniedVersions => {
+                                UnavailableReason::Version(UnavailableVersion::Denied)
+                            }
+                            PolicyViolation::NotAllowed => {
+                                UnavailableReason::Package(UnavailablePackage::NotAllowed)
+                            }
+                        };
+                        state
+                            .pubgrub
+                            .add_incompatibility(Incompatibility::custom_term(
+                                state.next.clone(),
+                                Term::Positive(range),
+                                reason,
+                            ));
+                        continue;
+                    }
+                }
+            }
+
             prefetcher.version_tried(state.next.clone());
 
             let term_intersection = state
@@ -504,7 +637,10 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                 Some(version) => version,
             };
             let version = match version {
-                ResolverVersion::Available(version) => version,
+                ResolverVersion::Available(version) => {
+                    self.on_version_tried(&state.next, &version);
+                    version
+                }
                 ResolverVersion::Unavailable(version, reason) => {
                     // Incompatible requires-python versions are special in that we track
                     // them as incompatible dependencies instead of marking the package version
@@ -563,6 +699,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             )?;
 
             self.on_progress(&state.next, &version);
+            state.decisions.insert(state.next.clone(), version.clone());
 
             if state
                 .added_dependencies
@@ -680,22 +817,33 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
 
     /// Visit the set of [`PubGrubPackage`] candidates prior to selection. This allows us to fetch
     /// metadata for all of the packages in parallel.
+    ///
+    /// The requests are sent in priority order, such that the metadata for the packages the
+    /// resolver will visit first is requested first.
     fn pre_visit<'data>(
         packages: impl Iterator<Item = (&'data PubGrubPackage, &'data Range<Version>)>,
+        priorities: &PubGrubPriorities,
         request_sink: &Sender<Request>,
     ) -> Result<(), ResolveError> {
         // Iterate over the potential packages, and fetch file metadata for any of them. These
         // represent our current best guesses for the versions that we _might_ select.
-        for (package, range) in packages {
-            let PubGrubPackageInner::Package {
-                name,
-                extra: None,
-                marker: None,
-                url: None,
-            } = &**package
-            else {
-                continue;
-            };
+        let mut packages = packages
+            .filter_map(|(package, range)| {
+                let PubGrubPackageInner::Package {
+                    name,
+                    extra: None,
+                    marker: None,
+                    url: None,
+                } = &**package
+                else {
+                    return None;
+                };
+                Some((priorities.get(package), name, range))
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+
+        for (_, name, range) in packages {
             request_sink.blocking_send(Request::Prefetch(name.clone(), range.clone()))?;
         }
         Ok(())
@@ -864,9 +1012,13 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                             .insert(name.clone(), UnavailablePackage::Offline);
                         &[]
                     }
-                    VersionsResponse::NotFound => {
-                        self.unavailable_packages
-                            .insert(name.clone(), UnavailablePackage::NotFound);
+                    VersionsResponse::NotFound { ref similar } => {
+                        self.unavailable_packages.insert(
+                            name.clone(),
+                            UnavailablePackage::NotFound {
+                                similar: similar.clone(),
+                            },
+                        );
                         &[]
                     }
                 };
@@ -1258,6 +1410,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     metadata,
                 }) => {
                     trace!("Received built distribution metadata for: {dist}");
+                    self.on_metadata_fetched(&dist);
                     match &metadata {
                         MetadataResponse::InvalidMetadata(err) => {
                             warn!("Unable to extract metadata for {dist}: {err}");
@@ -1276,6 +1429,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     metadata,
                 }) => {
                     trace!("Received source distribution metadata for: {dist}");
+                    self.on_metadata_fetched(&dist);
                     match &metadata {
                         MetadataResponse::InvalidMetadata(err) => {
                             warn!("Unable to extract metadata for {dist}: {err}");
@@ -1370,9 +1524,13 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
 
                         return Ok(None);
                     }
-                    VersionsResponse::NotFound => {
-                        self.unavailable_packages
-                            .insert(package_name.clone(), UnavailablePackage::NotFound);
+                    VersionsResponse::NotFound { ref similar } => {
+                        self.unavailable_packages.insert(
+                            package_name.clone(),
+                            UnavailablePackage::NotFound {
+                                similar: similar.clone(),
+                            },
+                        );
 
                         return Ok(None);
                     }
@@ -1468,6 +1626,63 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             reporter.on_complete();
         }
     }
+
+    /// Returns the [`ResolutionLimit`] that the resolution has exceeded, if any.
+    fn exceeded_limit(&self, start: Instant) -> Option<ResolutionLimit> {
+        if let Some(max_backtracks) = self.limits.max_backtracks {
+            if self
+                .backtracks
+                .iter()
+                .map(|entry| *entry.value())
+                .sum::<usize>()
+                > max_backtracks
+            {
+                return Some(ResolutionLimit::Backtracks(max_backtracks));
+            }
+        }
+        if let Some(timeout) = self.limits.timeout {
+            if start.elapsed() > timeout {
+                return Some(ResolutionLimit::Timeout(timeout));
+            }
+        }
+        None
+    }
+
+    /// Construct a [`LimitExceededError`] for the given limit, reporting the packages that caused
+    /// the most backtracking so far.
+    fn limit_exceeded(&self, limit: ResolutionLimit) -> LimitExceededError {
+        let mut backtracks = self
+            .backtracks
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect::<Vec<_>>();
+        backtracks.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+        LimitExceededError { limit, backtracks }
+    }
+
+    fn on_metadata_fetched(&self, dist: &impl DistributionMetadata) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.on_metadata_fetched(dist.name(), &dist.version_or_url());
+        }
+    }
+
+    fn on_version_tried(&self, package: &PubGrubPackage, version: &Version) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            if let PubGrubPackageInner::Package { name, .. } = &**package {
+                reporter.on_version_tried(name, version);
+            }
+        }
+    }
+
+    fn on_backtrack(&self, package: &PubGrubPackage, version: &Version) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            if let PubGrubPackageInner::Package { name, .. } = &**package {
+                reporter.on_backtrack(name, version);
+            }
+        }
+    }
 }
 
 /// State that is used during unit propagation in the resolver.
@@ -1502,6 +1717,11 @@ struct SolveState {
     /// This keeps track of the set of versions for each package that we've
     /// already visited during resolution. This avoids doing redundant work.
     added_dependencies: FxHashMap<PubGrubPackage, FxHashSet<Version>>,
+    /// The set of packages for which the [`PackagePolicy`] has already been enforced, by adding an
+    /// incompatibility for any forbidden versions.
+    restricted: FxHashSet<PubGrubPackage>,
+    /// The version most recently decided for each package, used to detect backtracking.
+    decisions: FxHashMap<PubGrubPackage, Version>,
 }
 
 /// Fetch the metadata for an item
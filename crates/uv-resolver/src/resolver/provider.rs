@@ -1,19 +1,22 @@
 use std::future::Future;
 
 use anyhow::Result;
+use tokio::sync::OnceCell;
+use tracing::debug;
 
-use distribution_types::{Dist, IndexLocations};
+use distribution_types::{Dist, IndexLocations, IndexUrl};
 use platform_tags::Tags;
-use uv_configuration::{NoBinary, NoBuild};
+use uv_configuration::{NoBinary, NoBuild, PreferBinary, PreferSource};
 use uv_distribution::{ArchiveMetadata, DistributionDatabase};
 use uv_normalize::PackageName;
 use uv_types::{BuildContext, HashStrategy};
 
 use crate::flat_index::FlatIndex;
 use crate::python_requirement::PythonRequirement;
+use crate::similar;
 use crate::version_map::VersionMap;
 use crate::yanks::AllowedYanks;
-use crate::ExcludeNewer;
+use crate::{ExcludeNewer, MinReleaseAge};
 
 pub type PackageVersionsResult = Result<VersionsResponse, uv_client::Error>;
 pub type WheelMetadataResult = Result<MetadataResponse, uv_distribution::Error>;
@@ -23,8 +26,9 @@ pub type WheelMetadataResult = Result<MetadataResponse, uv_distribution::Error>;
 pub enum VersionsResponse {
     /// The package was found in the registry with the included versions
     Found(Vec<VersionMap>),
-    /// The package was not found in the registry
-    NotFound,
+    /// The package was not found in the registry, along with any similarly-named packages that
+    /// are available.
+    NotFound { similar: Vec<PackageName> },
     /// The package was not found in the local registry
     NoIndex,
     /// The package was not found in the cache and the network is not available.
@@ -81,8 +85,14 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext> {
     allowed_yanks: AllowedYanks,
     hasher: HashStrategy,
     exclude_newer: Option<ExcludeNewer>,
+    min_release_age: MinReleaseAge,
     no_binary: NoBinary,
     no_build: NoBuild,
+    prefer_source: PreferSource,
+    prefer_binary: PreferBinary,
+    /// The names of the packages available on the indexes, fetched lazily to suggest
+    /// alternatives for packages that could not be found.
+    package_names: OnceCell<Vec<PackageName>>,
 }
 
 impl<'a, Context: BuildContext> DefaultResolverProvider<'a, Context> {
@@ -96,8 +106,11 @@ impl<'a, Context: BuildContext> DefaultResolverProvider<'a, Context> {
         allowed_yanks: AllowedYanks,
         hasher: &'a HashStrategy,
         exclude_newer: Option<ExcludeNewer>,
+        min_release_age: MinReleaseAge,
         no_binary: &'a NoBinary,
         no_build: &'a NoBuild,
+        prefer_source: &'a PreferSource,
+        prefer_binary: &'a PreferBinary,
     ) -> Self {
         Self {
             fetcher,
@@ -107,10 +120,48 @@ impl<'a, Context: BuildContext> DefaultResolverProvider<'a, Context> {
             allowed_yanks,
             hasher: hasher.clone(),
             exclude_newer,
+            min_release_age,
             no_binary: no_binary.clone(),
             no_build: no_build.clone(),
+            prefer_source: prefer_source.clone(),
+            prefer_binary: prefer_binary.clone(),
+            package_names: OnceCell::new(),
         }
     }
+
+    /// Return the names of available packages that are similar to the given (missing) package.
+    ///
+    /// Candidates are drawn from the `--find-links` entries, the project lists of any indexes
+    /// other than PyPI, and, if PyPI is among the indexes, a bundled list of popular packages.
+    async fn similar_package_names(&self, package_name: &PackageName) -> Vec<PackageName> {
+        let candidates = self
+            .package_names
+            .get_or_init(|| async {
+                let mut candidates = self.flat_index.package_names().cloned().collect::<Vec<_>>();
+                for index in self.fetcher.index_locations().indexes() {
+                    match index {
+                        IndexUrl::Pypi(_) => candidates.extend(similar::popular_packages()),
+                        IndexUrl::Url(_) => {
+                            match self
+                                .fetcher
+                                .client()
+                                .managed(|client| client.project_names(index))
+                                .await
+                            {
+                                Ok(names) => candidates.extend(names),
+                                Err(err) => {
+                                    debug!("Failed to fetch the project list for {index}: {err}");
+                                }
+                            }
+                        }
+                        IndexUrl::Path(_) => {}
+                    }
+                }
+                candidates
+            })
+            .await;
+        similar::similar_names(package_name, candidates)
+    }
 }
 
 impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a, Context> {
@@ -119,6 +170,18 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
         &'io self,
         package_name: &'io PackageName,
     ) -> PackageVersionsResult {
+        // Whether to skip over versions that lack a compatible wheel, if an older one has one.
+        let prefer_binary = self.prefer_binary.prefer_binary_package(package_name);
+
+        // Resolve packages provided by a local build directory without consulting the registry.
+        if self.flat_index.is_local(package_name) {
+            if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
+                return Ok(VersionsResponse::Found(vec![
+                    VersionMap::from(flat_index).with_prefer_binary(prefer_binary)
+                ]));
+            }
+        }
+
         let result = self
             .fetcher
             .client()
@@ -139,24 +202,32 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
                             &self.allowed_yanks,
                             &self.hasher,
                             self.exclude_newer.as_ref(),
+                            &self.min_release_age,
                             self.flat_index.get(package_name).cloned(),
                             &self.no_binary,
                             &self.no_build,
+                            &self.prefer_source,
                         )
+                        .with_prefer_binary(prefer_binary)
                     })
                     .collect(),
             )),
             Err(err) => match err.into_kind() {
                 uv_client::ErrorKind::PackageNotFound(_) => {
                     if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
-                        Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
+                        Ok(VersionsResponse::Found(vec![
+                            VersionMap::from(flat_index).with_prefer_binary(prefer_binary)
+                        ]))
                     } else {
-                        Ok(VersionsResponse::NotFound)
+                        let similar = self.similar_package_names(package_name).await;
+                        Ok(VersionsResponse::NotFound { similar })
                     }
                 }
                 uv_client::ErrorKind::NoIndex(_) => {
                     if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
-                        Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
+                        Ok(VersionsResponse::Found(vec![
+                            VersionMap::from(flat_index).with_prefer_binary(prefer_binary)
+                        ]))
                     } else if self.flat_index.offline() {
                         Ok(VersionsResponse::Offline)
                     } else {
@@ -165,7 +236,9 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
                 }
                 uv_client::ErrorKind::Offline(_) => {
                     if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
-                        Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
+                        Ok(VersionsResponse::Found(vec![
+                            VersionMap::from(flat_index).with_prefer_binary(prefer_binary)
+                        ]))
                     } else {
                         Ok(VersionsResponse::Offline)
                     }
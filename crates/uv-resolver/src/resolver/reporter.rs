@@ -3,11 +3,12 @@ use std::sync::Arc;
 use url::Url;
 
 use distribution_types::{BuildableSource, VersionOrUrlRef};
+use pep440_rs::Version;
 use uv_normalize::PackageName;
 
 pub type BuildId = usize;
 
-pub trait Reporter: Send + Sync {
+pub trait Reporter: ResolveReporter + Send + Sync {
     /// Callback to invoke when a dependency is resolved.
     fn on_progress(&self, name: &PackageName, version: &VersionOrUrlRef);
 
@@ -27,6 +28,24 @@ pub trait Reporter: Send + Sync {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
 }
 
+/// Callbacks for observing the phases of a resolution, beyond its overall progress.
+///
+/// All callbacks default to no-ops.
+pub trait ResolveReporter: Send + Sync {
+    /// Callback to invoke when the metadata for a package version has been fetched.
+    fn on_metadata_fetched(&self, _name: &PackageName, _version_or_url: &VersionOrUrlRef) {}
+
+    /// Callback to invoke when the resolver tries a version of a package.
+    fn on_version_tried(&self, _name: &PackageName, _version: &Version) {}
+
+    /// Callback to invoke when the resolver backtracks, discarding an earlier decision for a
+    /// package.
+    fn on_backtrack(&self, _name: &PackageName, _version: &Version) {}
+
+    /// Callback to invoke when a source distribution build is kicked off.
+    fn on_build_started(&self, _source: &BuildableSource) {}
+}
+
 /// A facade for converting from [`Reporter`] to [`uv_distribution::Reporter`].
 pub(crate) struct Facade {
     pub(crate) reporter: Arc<dyn Reporter>,
@@ -34,6 +53,7 @@ pub(crate) struct Facade {
 
 impl uv_distribution::Reporter for Facade {
     fn on_build_start(&self, source: &BuildableSource) -> usize {
+        self.reporter.on_build_started(source);
         self.reporter.on_build_start(source)
     }
 
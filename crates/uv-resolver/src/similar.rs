@@ -0,0 +1,103 @@
+//! Suggestions for package names that could not be found on any index (e.g., `beautifulsop4`
+//! for `beautifulsoup4`), based on their edit distance to known package names.
+
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+
+/// A list of popular packages on PyPI, used as suggestions when PyPI is among the configured
+/// indexes, since its full project list is too large to fetch.
+static POPULAR_PACKAGES: &str = include_str!("popular_packages.txt");
+
+/// The maximum number of suggestions to return.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Return the bundled list of popular packages on PyPI.
+pub(crate) fn popular_packages() -> impl Iterator<Item = PackageName> {
+    POPULAR_PACKAGES
+        .lines()
+        .filter_map(|line| PackageName::from_str(line.trim()).ok())
+}
+
+/// Return the candidates that are most similar to the given package name, most similar first.
+///
+/// A candidate is considered similar if its edit distance to the name is at most a third of the
+/// name's length (and at most three edits), such that short names don't match arbitrary
+/// candidates.
+pub(crate) fn similar_names<'a>(
+    name: &PackageName,
+    candidates: impl IntoIterator<Item = &'a PackageName>,
+) -> Vec<PackageName> {
+    let name = name.as_ref();
+    let threshold = (name.len() / 3).clamp(1, 3);
+
+    let mut matches: Vec<(usize, &PackageName)> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.as_ref() != name)
+        .filter(|candidate| candidate.as_ref().len().abs_diff(name.len()) <= threshold)
+        .filter_map(|candidate| {
+            let distance = edit_distance(name, candidate.as_ref());
+            (distance <= threshold).then_some((distance, candidate))
+        })
+        .collect();
+    matches.sort_unstable();
+    matches.dedup();
+
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+/// Compute the Levenshtein distance between two (ASCII) package names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use super::{edit_distance, popular_packages, similar_names};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("flask", "flask"), 0);
+        assert_eq!(edit_distance("flsak", "flask"), 2);
+        assert_eq!(edit_distance("beautifulsop4", "beautifulsoup4"), 1);
+        assert_eq!(edit_distance("", "six"), 3);
+    }
+
+    #[test]
+    fn popular() {
+        let popular = popular_packages().collect::<Vec<_>>();
+        let similar = |name: &str| {
+            similar_names(&PackageName::from_str(name).unwrap(), &popular)
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(similar("beautifulsop4"), vec!["beautifulsoup4"]);
+        assert_eq!(similar("reqeusts"), vec!["requests"]);
+        assert_eq!(similar("scikitlearn"), vec!["scikit-learn"]);
+        assert!(similar("requests").is_empty());
+        assert!(similar("xyz").is_empty());
+    }
+}
@@ -7,25 +7,32 @@ use tracing::instrument;
 
 use distribution_filename::{DistFilename, WheelFilename};
 use distribution_types::{
-    HashComparison, IncompatibleSource, IncompatibleWheel, IndexUrl, PrioritizedDist,
-    RegistryBuiltWheel, RegistrySourceDist, SourceDistCompatibility, WheelCompatibility,
+    CompatibleDist, HashComparison, IncompatibleSource, IncompatibleWheel, IndexUrl,
+    PrioritizedDist, RegistryBuiltWheel, RegistrySourceDist, SourceDistCompatibility,
+    WheelCompatibility,
 };
 use pep440_rs::{Version, VersionSpecifiers};
 use platform_tags::{TagCompatibility, Tags};
 use pypi_types::{HashDigest, Yanked};
 use uv_client::{OwnedArchive, SimpleMetadata, VersionFiles};
-use uv_configuration::{NoBinary, NoBuild};
+use uv_configuration::{NoBinary, NoBuild, PreferSource};
 use uv_normalize::PackageName;
-use uv_types::HashStrategy;
+use uv_types::{HashConstraint, HashStrategy};
 use uv_warnings::warn_user_once;
 
 use crate::flat_index::FlatDistributions;
-use crate::{python_requirement::PythonRequirement, yanks::AllowedYanks, ExcludeNewer};
+use crate::{
+    python_requirement::PythonRequirement, yanks::AllowedYanks, ExcludeNewer, MinReleaseAge,
+    ReleaseAge,
+};
 
 /// A map from versions to distributions.
 #[derive(Debug)]
 pub struct VersionMap {
     inner: VersionMapInner,
+    /// When true, versions with a compatible wheel are preferred over newer versions that would
+    /// need to be built from source.
+    prefer_binary: bool,
 }
 
 impl VersionMap {
@@ -40,7 +47,7 @@ impl VersionMap {
     /// PEP 592: <https://peps.python.org/pep-0592/#warehouse-pypi-implementation-notes>
     #[instrument(skip_all, fields(package_name))]
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn from_metadata(
+    pub fn from_metadata(
         simple_metadata: OwnedArchive<SimpleMetadata>,
         package_name: &PackageName,
         index: &IndexUrl,
@@ -49,9 +56,11 @@ impl VersionMap {
         allowed_yanks: &AllowedYanks,
         hasher: &HashStrategy,
         exclude_newer: Option<&ExcludeNewer>,
+        min_release_age: &MinReleaseAge,
         flat_index: Option<FlatDistributions>,
         no_binary: &NoBinary,
         no_build: &NoBuild,
+        prefer_source: &PreferSource,
     ) -> Self {
         let mut map = BTreeMap::new();
         // Create stubs for each entry in simple metadata. The full conversion
@@ -72,7 +81,11 @@ impl VersionMap {
         }
         // If a set of flat distributions have been given, we need to add those
         // to our map of entries as well.
-        for (version, prioritized_dist) in flat_index.into_iter().flatten() {
+        let prefer_source = prefer_source.prefer_source_package(package_name);
+        for (version, mut prioritized_dist) in flat_index.into_iter().flatten() {
+            if prefer_source {
+                prioritized_dist.prefer_source();
+            }
             match map.entry(version) {
                 Entry::Vacant(e) => {
                     e.insert(LazyPrioritizedDist::OnlyFlat(prioritized_dist));
@@ -95,39 +108,51 @@ impl VersionMap {
                 },
             }
         }
-        // Check if binaries are allowed for this package.
-        let no_binary = match no_binary {
-            NoBinary::None => false,
-            NoBinary::All => true,
-            NoBinary::Packages(packages) => packages.contains(package_name),
-        };
-        // Check if source distributions are allowed for this package.
-        let no_build = match no_build {
-            NoBuild::None => false,
-            NoBuild::All => true,
-            NoBuild::Packages(packages) => packages.contains(package_name),
-        };
+        // Check if binaries and source distributions are allowed for this package.
+        let (no_binary, no_build) = (
+            no_binary.no_binary_package(package_name, no_build),
+            no_build.no_build_package(package_name, no_binary),
+        );
         let allowed_yanks = allowed_yanks
             .allowed_versions(package_name)
             .cloned()
             .unwrap_or_default();
         let required_hashes = hasher.get_package(package_name).digests().to_vec();
+        let hash_constraint = hasher.get_constraint(package_name).cloned();
         Self {
             inner: VersionMapInner::Lazy(VersionMapLazy {
                 map,
                 simple_metadata,
                 no_binary,
                 no_build,
+                prefer_source,
                 index: index.clone(),
                 tags: tags.clone(),
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
+                min_release_age: min_release_age.cutoff(package_name),
                 allowed_yanks,
                 required_hashes,
+                hash_constraint,
             }),
+            prefer_binary: false,
         }
     }
 
+    /// Prefer versions with a compatible wheel over newer versions that would need to be built
+    /// from source.
+    #[must_use]
+    pub(crate) fn with_prefer_binary(mut self, prefer_binary: bool) -> Self {
+        self.prefer_binary = prefer_binary;
+        self
+    }
+
+    /// Returns `true` if versions with a compatible wheel are preferred over newer versions that
+    /// would need to be built from source.
+    pub(crate) fn prefer_binary(&self) -> bool {
+        self.prefer_binary
+    }
+
     /// Return the [`DistFile`] for the given version, if any.
     pub(crate) fn get(&self, version: &Version) -> Option<&PrioritizedDist> {
         self.get_with_version(version).map(|(_version, dist)| dist)
@@ -179,6 +204,16 @@ impl VersionMap {
         }
     }
 
+    /// Return the latest version with a compatible distribution, along with that distribution.
+    ///
+    /// Pre-release versions are only considered if `prereleases` is `true`.
+    pub fn latest(&self, prereleases: bool) -> Option<(&Version, CompatibleDist)> {
+        self.iter()
+            .rev()
+            .filter(|(version, _)| prereleases || !version.any_prerelease())
+            .find_map(|(version, handle)| Some((version, handle.prioritized_dist()?.get()?)))
+    }
+
     /// Return the [`Hashes`] for the given version, if any.
     pub(crate) fn hashes(&self, version: &Version) -> Option<Vec<HashDigest>> {
         match self.inner {
@@ -204,6 +239,7 @@ impl Default for VersionMap {
     fn default() -> Self {
         Self {
             inner: VersionMapInner::Eager(BTreeMap::default()),
+            prefer_binary: false,
         }
     }
 }
@@ -212,6 +248,7 @@ impl From<FlatDistributions> for VersionMap {
     fn from(flat_index: FlatDistributions) -> Self {
         Self {
             inner: VersionMapInner::Eager(flat_index.into()),
+            prefer_binary: false,
         }
     }
 }
@@ -220,6 +257,7 @@ impl From<BTreeMap<Version, PrioritizedDist>> for VersionMap {
     fn from(value: BTreeMap<Version, PrioritizedDist>) -> Self {
         Self {
             inner: VersionMapInner::Eager(value),
+            prefer_binary: false,
         }
     }
 }
@@ -293,6 +331,8 @@ struct VersionMapLazy {
     no_binary: bool,
     /// When true, source dists aren't allowed.
     no_build: bool,
+    /// When true, source dists are preferred over wheels.
+    prefer_source: bool,
     /// The URL of the index where this package came from.
     index: IndexUrl,
     /// The set of compatibility tags that determines whether a wheel is usable
@@ -304,10 +344,15 @@ struct VersionMapLazy {
     python_requirement: PythonRequirement,
     /// Whether files newer than this timestamp should be excluded or not.
     exclude_newer: Option<ExcludeNewer>,
+    /// The minimum release age, along with the upload time cutoff it implies and the versions
+    /// that are exempt from it.
+    min_release_age: Option<(ReleaseAge, i64, FxHashSet<Version>)>,
     /// Which yanked versions are allowed
     allowed_yanks: FxHashSet<Version>,
     /// The hashes of allowed distributions.
     required_hashes: Vec<HashDigest>,
+    /// The constraint restricting the hashes of allowed distributions, if any.
+    hash_constraint: Option<HashConstraint>,
 }
 
 impl VersionMapLazy {
@@ -380,6 +425,29 @@ impl VersionMapLazy {
                     (false, None)
                 };
 
+                // Exclude files uploaded more recently than the minimum release age, unless their
+                // version is pinned exactly.
+                let (too_recent, upload_time) = match self.min_release_age.as_ref() {
+                    Some((age, cutoff, pinned))
+                        if !excluded && !pinned.contains(filename.version()) =>
+                    {
+                        match file.upload_time_utc_ms.as_ref() {
+                            Some(&upload_time) if upload_time >= *cutoff => {
+                                (true, Some(upload_time))
+                            }
+                            None => {
+                                warn_user_once!(
+                                    "{} is missing an upload date, but user provided a minimum release age: {age}",
+                                    file.filename,
+                                );
+                                (true, None)
+                            }
+                            _ => (false, upload_time),
+                        }
+                    }
+                    _ => (false, upload_time),
+                };
+
                 // Prioritize amongst all available files.
                 let version = filename.version().clone();
                 let requires_python = file.requires_python.clone();
@@ -394,6 +462,7 @@ impl VersionMapLazy {
                             &hashes,
                             yanked,
                             excluded,
+                            too_recent,
                             upload_time,
                         );
                         let dist = RegistryBuiltWheel {
@@ -410,6 +479,7 @@ impl VersionMapLazy {
                             &hashes,
                             yanked,
                             excluded,
+                            too_recent,
                             upload_time,
                         );
                         let dist = RegistrySourceDist {
@@ -423,6 +493,9 @@ impl VersionMapLazy {
                     }
                 }
             }
+            if self.prefer_source {
+                priority_dist.prefer_source();
+            }
             if priority_dist.is_empty() {
                 None
             } else {
@@ -440,6 +513,7 @@ impl VersionMapLazy {
         hashes: &[HashDigest],
         yanked: Option<Yanked>,
         excluded: bool,
+        too_recent: bool,
         upload_time: Option<i64>,
     ) -> SourceDistCompatibility {
         // Check if builds are disabled
@@ -454,6 +528,13 @@ impl VersionMapLazy {
             ));
         }
 
+        // Check if uploaded more recently than the minimum release age
+        if too_recent {
+            return SourceDistCompatibility::Incompatible(IncompatibleSource::MinReleaseAge(
+                upload_time,
+            ));
+        }
+
         // Check if yanked
         if let Some(yanked) = yanked {
             if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
@@ -474,6 +555,17 @@ impl VersionMapLazy {
             }
         }
 
+        // Check if the hashes satisfy the hash constraint, if any.
+        if let Some(constraint) = self
+            .hash_constraint
+            .as_ref()
+            .filter(|constraint| !constraint.allows(hashes))
+        {
+            return SourceDistCompatibility::Incompatible(IncompatibleSource::MismatchedHash(
+                constraint.requirement.clone(),
+            ));
+        }
+
         // Check if hashes line up. If hashes aren't required, they're considered matching.
         let hash = if self.required_hashes.is_empty() {
             HashComparison::Matched
@@ -502,6 +594,7 @@ impl VersionMapLazy {
         hashes: &[HashDigest],
         yanked: Option<Yanked>,
         excluded: bool,
+        too_recent: bool,
         upload_time: Option<i64>,
     ) -> WheelCompatibility {
         // Check if binaries are disabled
@@ -514,6 +607,11 @@ impl VersionMapLazy {
             return WheelCompatibility::Incompatible(IncompatibleWheel::ExcludeNewer(upload_time));
         }
 
+        // Check if uploaded more recently than the minimum release age
+        if too_recent {
+            return WheelCompatibility::Incompatible(IncompatibleWheel::MinReleaseAge(upload_time));
+        }
+
         // Check if yanked
         if let Some(yanked) = yanked {
             if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
@@ -538,6 +636,17 @@ impl VersionMapLazy {
             TagCompatibility::Compatible(priority) => priority,
         };
 
+        // Check if the hashes satisfy the hash constraint, if any.
+        if let Some(constraint) = self
+            .hash_constraint
+            .as_ref()
+            .filter(|constraint| !constraint.allows(hashes))
+        {
+            return WheelCompatibility::Incompatible(IncompatibleWheel::MismatchedHash(
+                constraint.requirement.clone(),
+            ));
+        }
+
         // Check if hashes line up. If hashes aren't required, they're considered matching.
         let hash = if self.required_hashes.is_empty() {
             HashComparison::Matched
@@ -1,4 +1,4 @@
-use distribution_types::RequirementSource;
+use distribution_types::{Requirement, RequirementSource};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use pep440_rs::Version;
@@ -18,29 +18,11 @@ impl AllowedYanks {
         markers: Option<&MarkerEnvironment>,
         dependencies: DependencyMode,
     ) -> Self {
-        let mut allowed_yanks = FxHashMap::<PackageName, FxHashSet<Version>>::default();
-
-        for requirement in manifest
-            .requirements(markers, dependencies)
-            .chain(manifest.preferences.iter().map(Preference::requirement))
-        {
-            let RequirementSource::Registry { specifier, .. } = &requirement.source else {
-                continue;
-            };
-            let [specifier] = specifier.as_ref() else {
-                continue;
-            };
-            if matches!(
-                specifier.operator(),
-                pep440_rs::Operator::Equal | pep440_rs::Operator::ExactEqual
-            ) {
-                allowed_yanks
-                    .entry(requirement.name.clone())
-                    .or_default()
-                    .insert(specifier.version().clone());
-            }
-        }
-        Self(allowed_yanks)
+        Self(pinned_versions(
+            manifest
+                .requirements(markers, dependencies)
+                .chain(manifest.preferences.iter().map(Preference::requirement)),
+        ))
     }
 
     /// Returns versions for the given package which are allowed even if marked as yanked by the
@@ -49,3 +31,29 @@ impl AllowedYanks {
         self.0.get(package_name)
     }
 }
+
+/// Returns the versions that are pinned exactly (e.g., `flask==3.0.0`) by the given requirements.
+pub(crate) fn pinned_versions<'a>(
+    requirements: impl Iterator<Item = &'a Requirement>,
+) -> FxHashMap<PackageName, FxHashSet<Version>> {
+    let mut pinned = FxHashMap::<PackageName, FxHashSet<Version>>::default();
+
+    for requirement in requirements {
+        let RequirementSource::Registry { specifier, .. } = &requirement.source else {
+            continue;
+        };
+        let [specifier] = specifier.as_ref() else {
+            continue;
+        };
+        if matches!(
+            specifier.operator(),
+            pep440_rs::Operator::Equal | pep440_rs::Operator::ExactEqual
+        ) {
+            pinned
+                .entry(requirement.name.clone())
+                .or_default()
+                .insert(specifier.version().clone());
+        }
+    }
+    pinned
+}
@@ -11,14 +11,16 @@ use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 
 use distribution_types::{CachedDist, IndexLocations, Requirement, Resolution, SourceDist};
-use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder};
+use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder, PackageName};
 use platform_tags::{Arch, Os, Platform, Tags};
 use uv_cache::Cache;
 use uv_client::RegistryClientBuilder;
 use uv_configuration::{
-    BuildKind, Concurrency, Constraints, NoBinary, NoBuild, Overrides, SetupPyStrategy,
+    BuildKind, Concurrency, Constraints, NoBinary, NoBuild, Overrides, PreferBinary, PreferSource,
+    SetupPyStrategy,
 };
 use uv_distribution::DistributionDatabase;
+use uv_extract::ExtractPool;
 use uv_interpreter::{find_default_interpreter, Interpreter, PythonEnvironment};
 use uv_resolver::{
     DisplayResolutionGraph, ExcludeNewer, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options,
@@ -26,7 +28,8 @@ use uv_resolver::{
     Resolver,
 };
 use uv_types::{
-    BuildContext, BuildIsolation, EmptyInstalledPackages, HashStrategy, SourceBuildTrait,
+    BuildContext, BuildCounter, BuildIsolation, EmptyInstalledPackages, HashStrategy,
+    SourceBuildTrait,
 };
 
 // Exclude any packages uploaded after this date.
@@ -42,6 +45,8 @@ struct DummyContext {
     cache: Cache,
     interpreter: Interpreter,
     index_locations: IndexLocations,
+    extract_pool: ExtractPool,
+    build_counter: BuildCounter,
 }
 
 impl DummyContext {
@@ -50,6 +55,8 @@ impl DummyContext {
             cache,
             interpreter,
             index_locations: IndexLocations::default(),
+            extract_pool: ExtractPool::new(1).unwrap(),
+            build_counter: BuildCounter::default(),
         }
     }
 }
@@ -77,10 +84,30 @@ impl BuildContext for DummyContext {
         &NoBinary::None
     }
 
+    fn prefer_source(&self) -> &PreferSource {
+        &PreferSource::None
+    }
+
+    fn prefer_binary(&self) -> &PreferBinary {
+        &PreferBinary::None
+    }
+
     fn setup_py_strategy(&self) -> SetupPyStrategy {
         SetupPyStrategy::default()
     }
 
+    fn build_command(&self, _: &PackageName) -> Option<&str> {
+        None
+    }
+
+    fn extract_pool(&self) -> &ExtractPool {
+        &self.extract_pool
+    }
+
+    fn build_counter(&self) -> &BuildCounter {
+        &self.build_counter
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         &self.index_locations
     }
@@ -126,6 +153,27 @@ async fn resolve(
     options: Options,
     markers: &'static MarkerEnvironment,
     tags: &Tags,
+) -> Result<ResolutionGraph> {
+    resolve_impl(manifest, options, markers, tags, false).await
+}
+
+/// Resolve for the target environment carried by the [`PythonRequirement`], rather than for an
+/// explicit marker environment.
+async fn resolve_target_environment(
+    manifest: Manifest,
+    options: Options,
+    markers: &'static MarkerEnvironment,
+    tags: &Tags,
+) -> Result<ResolutionGraph> {
+    resolve_impl(manifest, options, markers, tags, true).await
+}
+
+async fn resolve_impl(
+    manifest: Manifest,
+    options: Options,
+    markers: &'static MarkerEnvironment,
+    tags: &Tags,
+    target_environment: bool,
 ) -> Result<ResolutionGraph> {
     let cache = Cache::temp().unwrap().init().unwrap();
     let client = RegistryClientBuilder::new(cache).build();
@@ -136,7 +184,11 @@ async fn resolve(
         .expect("Python should be installed")
         .into_interpreter();
     let interpreter = Interpreter::artificial(real_interpreter.platform().clone(), markers.clone());
-    let python_requirement = PythonRequirement::from_marker_environment(&interpreter, markers);
+    let python_requirement = if target_environment {
+        PythonRequirement::from_target_environment(&interpreter, markers.clone())
+    } else {
+        PythonRequirement::from_marker_environment(&interpreter, markers)
+    };
     let cache = Cache::temp().unwrap().init().unwrap();
     let build_context = DummyContext::new(cache, interpreter.clone());
     let hashes = HashStrategy::None;
@@ -146,7 +198,7 @@ async fn resolve(
         manifest,
         options,
         &python_requirement,
-        Some(markers),
+        (!target_environment).then_some(markers),
         tags,
         &flat_index,
         &index,
@@ -225,6 +277,39 @@ async fn black_colorama() -> Result<()> {
     Ok(())
 }
 
+/// Resolve Black for a complete target environment, provided via the [`PythonRequirement`] rather
+/// than as a marker environment. The resolution should match that for the marker environment.
+#[tokio::test]
+async fn black_colorama_target_environment() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_pep508(
+        pep508_rs::Requirement::from_str("black[colorama]<=23.9.1").unwrap(),
+    )
+    .unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve_target_environment(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    black==23.9.1
+    click==8.1.7
+        # via black
+    colorama==0.4.6
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    "###);
+
+    Ok(())
+}
+
 /// Resolve Black with an invalid extra. The resolver should ignore the extra.
 #[tokio::test]
 async fn black_tensorboard() -> Result<()> {
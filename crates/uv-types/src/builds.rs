@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 use uv_interpreter::PythonEnvironment;
 
 /// Whether to enforce build isolation when building source distributions.
@@ -14,3 +17,22 @@ impl<'a> BuildIsolation<'a> {
         matches!(self, Self::Isolated)
     }
 }
+
+/// The number of source distributions built into wheels.
+///
+/// Cloning the counter shares the underlying count, such that the builds triggered by nested
+/// build contexts (e.g., for build requirements) are counted alongside their parent's.
+#[derive(Debug, Clone, Default)]
+pub struct BuildCounter(Arc<AtomicUsize>);
+
+impl BuildCounter {
+    /// Record that a source distribution was built into a wheel.
+    pub fn record(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of source distributions built so far.
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
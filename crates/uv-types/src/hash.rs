@@ -18,18 +18,53 @@ pub enum HashStrategy {
     None,
     /// Hashes should be generated (specifically, a SHA-256 hash), but not validated.
     Generate,
+    /// Hashes should be generated (specifically, a SHA-256 hash) for every artifact that's
+    /// fetched during resolution, and reported for each distribution in the resolution (even if
+    /// the index doesn't provide any hashes), but not validated.
+    Collect,
     /// Hashes should be validated against a pre-defined list of hashes. If necessary, hashes should
     /// be generated so as to ensure that the archive is valid.
-    Validate(FxHashMap<PackageId, Vec<HashDigest>>),
+    Validate {
+        /// The allowed hashes for each package, as provided by the requirements.
+        hashes: FxHashMap<PackageId, Vec<HashDigest>>,
+        /// The hashes provided by constraints, which further restrict the distributions that can
+        /// be selected during resolution.
+        constraints: FxHashMap<PackageName, HashConstraint>,
+    },
+}
+
+/// A constraint that restricts a registry-based package to distributions with matching hashes.
+#[derive(Debug, Clone)]
+pub struct HashConstraint {
+    /// The constraint from which the hashes were read (e.g., `anyio==4.0.0`).
+    pub requirement: String,
+    /// The allowed hashes.
+    pub digests: Vec<HashDigest>,
+}
+
+impl HashConstraint {
+    /// Returns `true` if a distribution with the given hashes may satisfy the constraint.
+    ///
+    /// Distributions without any known hashes can't be ruled out ahead of time, and are instead
+    /// validated against the requirement's hashes when they're downloaded.
+    pub fn allows(&self, hashes: &[HashDigest]) -> bool {
+        hashes.is_empty() || hashes.iter().any(|hash| self.digests.contains(hash))
+    }
 }
 
 impl HashStrategy {
+    /// Returns `true` if the hashes of every artifact fetched during resolution should be
+    /// collected.
+    pub fn is_collect(&self) -> bool {
+        matches!(self, Self::Collect)
+    }
+
     /// Return the [`HashPolicy`] for the given distribution.
     pub fn get<T: DistributionMetadata>(&self, distribution: &T) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
-            Self::Validate(hashes) => HashPolicy::Validate(
+            Self::Generate | Self::Collect => HashPolicy::Generate,
+            Self::Validate { hashes, .. } => HashPolicy::Validate(
                 hashes
                     .get(&distribution.package_id())
                     .map(Vec::as_slice)
@@ -42,8 +77,8 @@ impl HashStrategy {
     pub fn get_package(&self, name: &PackageName) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
-            Self::Validate(hashes) => HashPolicy::Validate(
+            Self::Generate | Self::Collect => HashPolicy::Generate,
+            Self::Validate { hashes, .. } => HashPolicy::Validate(
                 hashes
                     .get(&PackageId::from_registry(name.clone()))
                     .map(Vec::as_slice)
@@ -56,8 +91,8 @@ impl HashStrategy {
     pub fn get_url(&self, url: &Url) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
-            Self::Validate(hashes) => HashPolicy::Validate(
+            Self::Generate | Self::Collect => HashPolicy::Generate,
+            Self::Validate { hashes, .. } => HashPolicy::Validate(
                 hashes
                     .get(&PackageId::from_url(url))
                     .map(Vec::as_slice)
@@ -66,12 +101,24 @@ impl HashStrategy {
         }
     }
 
+    /// Return the [`HashPolicy`] for the given requirement.
+    pub fn get_requirement(&self, requirement: &Requirement) -> HashPolicy {
+        match &requirement.source {
+            RequirementSource::Registry { .. } => self.get_package(&requirement.name),
+            RequirementSource::Url { url, .. }
+            | RequirementSource::Git { url, .. }
+            | RequirementSource::Path { url, .. } => self.get_url(url),
+        }
+    }
+
     /// Returns `true` if the given registry-based package is allowed.
     pub fn allows_package(&self, name: &PackageName) -> bool {
         match self {
             Self::None => true,
-            Self::Generate => true,
-            Self::Validate(hashes) => hashes.contains_key(&PackageId::from_registry(name.clone())),
+            Self::Generate | Self::Collect => true,
+            Self::Validate { hashes, .. } => {
+                hashes.contains_key(&PackageId::from_registry(name.clone()))
+            }
         }
     }
 
@@ -79,8 +126,17 @@ impl HashStrategy {
     pub fn allows_url(&self, url: &Url) -> bool {
         match self {
             Self::None => true,
-            Self::Generate => true,
-            Self::Validate(hashes) => hashes.contains_key(&PackageId::from_url(url)),
+            Self::Generate | Self::Collect => true,
+            Self::Validate { hashes, .. } => hashes.contains_key(&PackageId::from_url(url)),
+        }
+    }
+
+    /// Return the [`HashConstraint`] for the given registry-based package, if any.
+    pub fn get_constraint(&self, name: &PackageName) -> Option<&HashConstraint> {
+        match self {
+            Self::None => None,
+            Self::Generate | Self::Collect => None,
+            Self::Validate { constraints, .. } => constraints.get(name),
         }
     }
 
@@ -128,7 +184,62 @@ impl HashStrategy {
             hashes.insert(id, digests);
         }
 
-        Ok(Self::Validate(hashes))
+        Ok(Self::Validate {
+            hashes,
+            constraints: FxHashMap::default(),
+        })
+    }
+
+    /// Incorporate the hashes from a set of constraints.
+    ///
+    /// Unlike requirements, constraints aren't required to include hashes; but any hashes they do
+    /// include are enforced during resolution, such that only distributions matching both the
+    /// requirement and the constraint can be selected.
+    pub fn with_constraints<'a>(
+        self,
+        constraints: impl Iterator<Item = (&'a Requirement, &'a [String])>,
+        markers: Option<&MarkerEnvironment>,
+    ) -> Result<Self, HashStrategyError> {
+        let Self::Validate {
+            hashes,
+            constraints: mut constraints_by_name,
+        } = self
+        else {
+            return Ok(self);
+        };
+
+        for (constraint, digests) in constraints {
+            if digests.is_empty() || !constraint.evaluate_markers(markers, &[]) {
+                continue;
+            }
+
+            // Only pinned, registry-based constraints can carry hashes.
+            if !matches!(constraint.source, RequirementSource::Registry { .. })
+                || uv_requirement_to_package_id(constraint).is_err()
+            {
+                return Err(HashStrategyError::UnpinnedConstraint(
+                    constraint.to_string(),
+                ));
+            }
+
+            let digests = digests
+                .iter()
+                .map(|digest| HashDigest::from_str(digest))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            constraints_by_name.insert(
+                constraint.name.clone(),
+                HashConstraint {
+                    requirement: constraint.to_string(),
+                    digests,
+                },
+            );
+        }
+
+        Ok(Self::Validate {
+            hashes,
+            constraints: constraints_by_name,
+        })
     }
 }
 
@@ -165,4 +276,6 @@ pub enum HashStrategyError {
     UnpinnedRequirement(String),
     #[error("In `--require-hashes` mode, all requirement must have a hash, but none were provided for: {0}")]
     MissingHashes(String),
+    #[error("In `--require-hashes` mode, constraints with hashes must have their versions pinned with `==`, but found: {0}")]
+    UnpinnedConstraint(String),
 }
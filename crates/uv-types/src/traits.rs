@@ -9,10 +9,11 @@ use distribution_types::{
 };
 use pep508_rs::PackageName;
 use uv_cache::Cache;
-use uv_configuration::{BuildKind, NoBinary, NoBuild, SetupPyStrategy};
+use uv_configuration::{BuildKind, NoBinary, NoBuild, PreferBinary, PreferSource, SetupPyStrategy};
+use uv_extract::ExtractPool;
 use uv_interpreter::{Interpreter, PythonEnvironment};
 
-use crate::BuildIsolation;
+use crate::{BuildCounter, BuildIsolation};
 
 ///  Avoids cyclic crate dependencies between resolver, installer and builder.
 ///
@@ -72,12 +73,28 @@ pub trait BuildContext {
     /// Whether using pre-built wheels is disabled.
     fn no_binary(&self) -> &NoBinary;
 
+    /// Whether source distributions are preferred over pre-built wheels.
+    fn prefer_source(&self) -> &PreferSource;
+
+    /// Whether versions with a compatible wheel are preferred over newer source-only versions.
+    fn prefer_binary(&self) -> &PreferBinary;
+
     /// The index locations being searched.
     fn index_locations(&self) -> &IndexLocations;
 
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// The external command with which to build the given package, if any, in lieu of the
+    /// built-in PEP 517 build frontend.
+    fn build_command(&self, package: &PackageName) -> Option<&str>;
+
+    /// The thread pool on which to hash, unzip, and verify downloaded wheels.
+    fn extract_pool(&self) -> &ExtractPool;
+
+    /// The counter to which each source distribution built into a wheel is recorded.
+    fn build_counter(&self) -> &BuildCounter;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
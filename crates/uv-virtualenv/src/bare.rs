@@ -4,15 +4,16 @@ use std::env;
 use std::env::consts::EXE_SUFFIX;
 use std::io;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 use fs_err::File;
 use itertools::Itertools;
-use tracing::info;
+use tracing::{debug, info};
 
 use pypi_types::Scheme;
-use uv_fs::{cachedir, Simplified};
+use uv_configuration::PythonPreference;
+use uv_fs::{absolutize_path, cachedir, verbatim_path, Simplified};
 use uv_interpreter::{Interpreter, VirtualEnvironment};
 use uv_version::version;
 
@@ -43,44 +44,65 @@ fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
     Ok(())
 }
 
+/// Remove the contents of the virtualenv at the given location, preserving the directory itself.
+///
+/// Refuses to clear a directory that isn't a virtualenv (i.e., that's non-empty and lacks a
+/// `pyvenv.cfg`), or that is itself a symlink. Symlinks within the environment are removed rather
+/// than followed, such that nothing outside of the environment is modified.
+pub fn clear_venv(location: &Path) -> Result<(), Error> {
+    let metadata = match fs::symlink_metadata(location) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(Error::IO(err)),
+    };
+    if metadata.is_symlink() {
+        return Err(Error::ClearSymlink(
+            location.to_path_buf(),
+            fs::read_link(location)?,
+        ));
+    }
+    if !metadata.is_dir() {
+        return Err(Error::IO(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("File exists at `{}`", location.user_display()),
+        )));
+    }
+    if !location.join("pyvenv.cfg").is_file() && location.read_dir()?.next().is_some() {
+        return Err(Error::ClearNotVirtualenv(location.to_path_buf()));
+    }
+
+    info!("Clearing existing virtualenv");
+    // The existing environment may contain paths that exceed `MAX_PATH`.
+    let location = verbatim_path(&absolutize_path(location)?);
+    for entry in fs::read_dir(&location)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Note that `remove_dir_all` removes, but never traverses, any nested symlinks.
+        if entry.file_type()?.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else if let Err(err) = fs::remove_file(&path) {
+            // On Windows, symlinks to directories must be removed as directories.
+            if cfg!(windows) && entry.file_type()?.is_symlink() {
+                fs::remove_dir(&path)?;
+            } else {
+                return Err(Error::IO(err));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Write all the files that belong to a venv without any packages installed.
 pub fn create_bare_venv(
     location: &Path,
     interpreter: &Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    python_preference: PythonPreference,
     allow_existing: bool,
 ) -> Result<VirtualEnvironment, Error> {
-    // Determine the base Python executable; that is, the Python executable that should be
-    // considered the "base" for the virtual environment. This is typically the Python executable
-    // from the [`Interpreter`]; however, if the interpreter is a virtual environment itself, then
-    // the base Python executable is the Python executable of the interpreter's base interpreter.
-    let base_python = if cfg!(unix) {
-        // On Unix, follow symlinks to resolve the base interpreter, since the Python executable in
-        // a virtual environment is a symlink to the base interpreter.
-        uv_fs::canonicalize_executable(interpreter.sys_executable())?
-    } else if cfg!(windows) {
-        // On Windows, follow `virtualenv`. If we're in a virtual environment, use
-        // `sys._base_executable` if it exists; if not, use `sys.base_prefix`. For example, with
-        // Python installed from the Windows Store, `sys.base_prefix` is slightly "incorrect".
-        //
-        // If we're _not_ in a virtual environment, use the interpreter's executable, since it's
-        // already a "system Python". We canonicalize the path to ensure that it's real and
-        // consistent, though we don't expect any symlinks on Windows.
-        if interpreter.is_virtualenv() {
-            if let Some(base_executable) = interpreter.base_executable() {
-                base_executable.to_path_buf()
-            } else {
-                // Assume `python.exe`, though the exact executable name is never used (below) on
-                // Windows, only its parent directory.
-                interpreter.base_prefix().join("python.exe")
-            }
-        } else {
-            uv_fs::canonicalize_executable(interpreter.sys_executable())?
-        }
-    } else {
-        unimplemented!("Only Windows and Unix are supported")
-    };
+    let base_python = base_python(interpreter)?;
 
     // Validate the existing location.
     match location.metadata() {
@@ -95,7 +117,8 @@ pub fn create_bare_venv(
                     info!("Allowing existing directory");
                 } else if location.join("pyvenv.cfg").is_file() {
                     info!("Removing existing directory");
-                    fs::remove_dir_all(location)?;
+                    // The existing environment may contain paths that exceed `MAX_PATH`.
+                    fs::remove_dir_all(verbatim_path(&absolutize_path(location)?))?;
                     fs::create_dir_all(location)?;
                 } else if location
                     .read_dir()
@@ -121,13 +144,6 @@ pub fn create_bare_venv(
 
     let location = location.canonicalize()?;
 
-    let bin_name = if cfg!(unix) {
-        "bin"
-    } else if cfg!(windows) {
-        "Scripts"
-    } else {
-        unimplemented!("Only Windows and Unix are supported")
-    };
     let scripts = location.join(&interpreter.virtualenv().scripts);
     let prompt = match prompt {
         Prompt::CurrentDirectoryName => env::current_dir()?
@@ -144,48 +160,292 @@ pub fn create_bare_venv(
     fs::write(location.join(".gitignore"), "*")?;
 
     // Per PEP 405, the Python `home` is the parent directory of the interpreter.
-    let python_home = base_python.parent().ok_or_else(|| {
-        io::Error::new(
-            io::ErrorKind::NotFound,
-            "The Python interpreter needs to have a parent directory",
-        )
-    })?;
+    let python_home = python_home(&base_python)?;
 
     // Different names for the python interpreter
     fs::create_dir_all(&scripts)?;
     let executable = scripts.join(format!("python{EXE_SUFFIX}"));
+    link_interpreter(
+        interpreter,
+        &base_python,
+        &scripts,
+        python_home,
+        &executable,
+        python_preference,
+    )?;
+
+    // Add all the activate scripts for different shells
+    write_activation_scripts(&location, interpreter, prompt.as_deref())?;
 
+    let mut pyvenv_cfg_data = interpreter_cfg(interpreter, python_home);
+    pyvenv_cfg_data.push((
+        "include-system-site-packages".to_string(),
+        if system_site_packages {
+            "true".to_string()
+        } else {
+            "false".to_string()
+        },
+    ));
+
+    if let Some(prompt) = prompt {
+        pyvenv_cfg_data.push(("prompt".to_string(), prompt));
+    }
+
+    let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
+    write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
+    drop(pyvenv_cfg);
+
+    // Construct the path to the `site-packages` directory.
+    let site_packages = location.join(&interpreter.virtualenv().purelib);
+    fs::create_dir_all(&site_packages)?;
+
+    link_lib64(&location, interpreter)?;
+
+    // Populate `site-packages` with a `_virtualenv.py` file.
+    fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
+    fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
+
+    Ok(virtualenv(location, interpreter, executable))
+}
+
+/// The changes made when repairing a virtualenv.
+#[derive(Debug)]
+pub struct Repair {
+    /// The Python version of the virtualenv before it was repaired, as recorded in its
+    /// `pyvenv.cfg`.
+    pub previous_version: Option<String>,
+}
+
+/// Repair a virtualenv whose base interpreter moved or was upgraded, by re-linking (or copying)
+/// the interpreter, rewriting `pyvenv.cfg`, and regenerating the activation scripts.
+///
+/// Installed packages are preserved. Since packages (and, in particular, compiled extensions)
+/// aren't compatible across Python minor versions, the interpreter must match the minor version
+/// the virtualenv was created with.
+pub fn repair_bare_venv(
+    location: &Path,
+    interpreter: &Interpreter,
+    python_preference: PythonPreference,
+) -> Result<(VirtualEnvironment, Repair), Error> {
+    if !location.join("pyvenv.cfg").is_file() {
+        return Err(Error::RepairNotVirtualenv(location.to_path_buf()));
+    }
+    let location = location.canonicalize()?;
+    let pyvenv_cfg = read_pyvenv_cfg(&location)?;
+    let get = |key: &str| {
+        pyvenv_cfg
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.clone())
+    };
+    let previous_version = get("version_info");
+    let prompt = get("prompt");
+
+    // Refuse to change the Python minor version, rather than leave the installed packages in
+    // place for an interpreter they weren't built for.
+    if let Some(previous) = previous_version.as_deref() {
+        let previous_minor = previous.split('.').take(2).collect::<Vec<_>>().join(".");
+        let current_minor = format!(
+            "{}.{}",
+            interpreter.python_major(),
+            interpreter.python_minor()
+        );
+        if previous_minor != current_minor {
+            return Err(Error::RepairVersionMismatch(
+                location.clone(),
+                previous.to_string(),
+                interpreter.python_version().to_string(),
+            ));
+        }
+    }
+
+    let base_python = base_python(interpreter)?;
+    let python_home = python_home(&base_python)?;
+    let scripts = location.join(&interpreter.virtualenv().scripts);
+    let executable = scripts.join(format!("python{EXE_SUFFIX}"));
+
+    // Remove the existing interpreter links (e.g., `python3.11`), which may refer to a previous
+    // version of the interpreter.
+    #[cfg(unix)]
+    for entry in fs::read_dir(&scripts)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(suffix) = name.to_str().and_then(|name| name.strip_prefix("python")) else {
+            continue;
+        };
+        if suffix
+            .split('.')
+            .all(|part| part.bytes().all(|byte| byte.is_ascii_digit()))
+            && suffix.split('.').count() <= 2
+        {
+            debug!(
+                "Removing stale interpreter: {}",
+                entry.path().user_display()
+            );
+            fs::remove_file(entry.path())?;
+        }
+    }
+    link_interpreter(
+        interpreter,
+        &base_python,
+        &scripts,
+        python_home,
+        &executable,
+        python_preference,
+    )?;
+
+    // If the minor version changed, move `site-packages` to the path expected by the new
+    // interpreter (e.g., from `lib/python3.11` to `lib/python3.12`).
+    let mut moved_site_packages = false;
+    let previous_minor = previous_version.as_deref().and_then(|version| {
+        let mut parts = version.split('.');
+        Some((parts.next()?.to_string(), parts.next()?.to_string()))
+    });
+    if let Some((major, minor)) = previous_minor {
+        let previous = location.join("lib").join(format!("python{major}.{minor}"));
+        let current = location.join("lib").join(format!(
+            "python{}.{}",
+            interpreter.python_major(),
+            interpreter.python_minor()
+        ));
+        if previous != current && previous.is_dir() && !current.exists() {
+            info!(
+                "Moving `{}` to `{}`",
+                previous.user_display(),
+                current.user_display()
+            );
+            fs::rename(&previous, &current)?;
+            moved_site_packages = true;
+        }
+    }
+    let site_packages = location.join(&interpreter.virtualenv().purelib);
+    fs::create_dir_all(&site_packages)?;
+
+    // Update the interpreter details in `pyvenv.cfg`, preserving any other settings.
+    let updates = interpreter_cfg(interpreter, python_home);
+    update_pyvenv_cfg(
+        &location,
+        &updates
+            .iter()
+            .map(|(key, value)| (key.as_str(), Some(value.clone())))
+            .collect::<Vec<_>>(),
+    )?;
+
+    write_activation_scripts(&location, interpreter, prompt.as_deref())?;
+    link_lib64(&location, interpreter)?;
+
+    // Restore the `_virtualenv.py` patch, in case it was lost with `site-packages`.
+    fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
+    fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
+
+    Ok((
+        virtualenv(location, interpreter, executable),
+        Repair { previous_version },
+    ))
+}
+
+/// Determine the base Python executable; that is, the Python executable that should be
+/// considered the "base" for the virtual environment. This is typically the Python executable
+/// from the [`Interpreter`]; however, if the interpreter is a virtual environment itself, then
+/// the base Python executable is the Python executable of the interpreter's base interpreter.
+fn base_python(interpreter: &Interpreter) -> io::Result<PathBuf> {
+    if cfg!(unix) {
+        // On Unix, follow symlinks to resolve the base interpreter, since the Python executable in
+        // a virtual environment is a symlink to the base interpreter.
+        uv_fs::canonicalize_executable(interpreter.sys_executable())
+    } else if cfg!(windows) {
+        // On Windows, follow `virtualenv`. If we're in a virtual environment, use
+        // `sys._base_executable` if it exists; if not, use `sys.base_prefix`. For example, with
+        // Python installed from the Windows Store, `sys.base_prefix` is slightly "incorrect".
+        //
+        // If we're _not_ in a virtual environment, use the interpreter's executable, since it's
+        // already a "system Python". We canonicalize the path to ensure that it's real and
+        // consistent, though we don't expect any symlinks on Windows.
+        if interpreter.is_virtualenv() {
+            if let Some(base_executable) = interpreter.base_executable() {
+                Ok(base_executable.to_path_buf())
+            } else {
+                // Assume `python.exe`, though the exact executable name is never used (below) on
+                // Windows, only its parent directory.
+                Ok(interpreter.base_prefix().join("python.exe"))
+            }
+        } else {
+            uv_fs::canonicalize_executable(interpreter.sys_executable())
+        }
+    } else {
+        unimplemented!("Only Windows and Unix are supported")
+    }
+}
+
+/// Per PEP 405, the Python `home` is the parent directory of the interpreter.
+fn python_home(base_python: &Path) -> io::Result<&Path> {
+    base_python.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "The Python interpreter needs to have a parent directory",
+        )
+    })
+}
+
+/// Link (or copy) the base interpreter into the virtualenv's scripts directory.
+fn link_interpreter(
+    interpreter: &Interpreter,
+    base_python: &Path,
+    scripts: &Path,
+    python_home: &Path,
+    executable: &Path,
+    python_preference: PythonPreference,
+) -> Result<(), Error> {
     #[cfg(unix)]
     {
-        uv_fs::replace_symlink(&base_python, &executable)?;
-        uv_fs::replace_symlink(
-            "python",
+        let aliases = [
             scripts.join(format!("python{}", interpreter.python_major())),
-        )?;
-        uv_fs::replace_symlink(
-            "python",
             scripts.join(format!(
                 "python{}.{}",
                 interpreter.python_major(),
                 interpreter.python_minor(),
             )),
-        )?;
+        ];
+        match python_preference {
+            PythonPreference::Symlink => {
+                uv_fs::replace_symlink(base_python, executable)?;
+                for alias in aliases {
+                    uv_fs::replace_symlink("python", alias)?;
+                }
+            }
+            PythonPreference::Copy => {
+                // Copy the interpreter, rather than symlinking it. Any existing symlinks are
+                // removed first, to avoid writing through them.
+                for path in std::iter::once(executable).chain(aliases.iter().map(PathBuf::as_path))
+                {
+                    match fs::remove_file(path) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                        Err(err) => return Err(err.into()),
+                    }
+                    fs::copy(base_python, path)?;
+                }
+            }
+        }
     }
 
     // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
     if cfg!(windows) {
+        if python_preference == PythonPreference::Symlink {
+            debug!("Copying the interpreter launchers, since symlinks aren't supported on Windows");
+        }
         copy_launcher_windows(
             WindowsExecutable::Python,
             interpreter,
-            &base_python,
-            &scripts,
+            base_python,
+            scripts,
             python_home,
         )?;
         copy_launcher_windows(
             WindowsExecutable::Pythonw,
             interpreter,
-            &base_python,
-            &scripts,
+            base_python,
+            scripts,
             python_home,
         )?;
     }
@@ -195,40 +455,12 @@ pub fn create_bare_venv(
         compile_error!("Only Windows and Unix are supported")
     }
 
-    // Add all the activate scripts for different shells
-    for (name, template) in ACTIVATE_TEMPLATES {
-        let path_sep = if cfg!(windows) { ";" } else { ":" };
-
-        let relative_site_packages = [
-            interpreter.virtualenv().purelib.as_path(),
-            interpreter.virtualenv().platlib.as_path(),
-        ]
-        .iter()
-        .dedup()
-        .map(|path| {
-            pathdiff::diff_paths(path, &interpreter.virtualenv().scripts)
-                .expect("Failed to calculate relative path to site-packages")
-        })
-        .map(|path| path.simplified().to_str().unwrap().replace('\\', "\\\\"))
-        .join(path_sep);
-
-        let activator = template
-            .replace(
-                "{{ VIRTUAL_ENV_DIR }}",
-                // SAFETY: `unwrap` is guaranteed to succeed because `location` is an `Utf8PathBuf`.
-                location.simplified().to_str().unwrap(),
-            )
-            .replace("{{ BIN_NAME }}", bin_name)
-            .replace(
-                "{{ VIRTUAL_PROMPT }}",
-                prompt.as_deref().unwrap_or_default(),
-            )
-            .replace("{{ PATH_SEP }}", path_sep)
-            .replace("{{ RELATIVE_SITE_PACKAGES }}", &relative_site_packages);
-        fs::write(scripts.join(name), activator)?;
-    }
+    Ok(())
+}
 
-    let mut pyvenv_cfg_data: Vec<(String, String)> = vec![
+/// The `pyvenv.cfg` entries that describe the base interpreter.
+fn interpreter_cfg(interpreter: &Interpreter, python_home: &Path) -> Vec<(String, String)> {
+    vec![
         (
             "home".to_string(),
             python_home.simplified_display().to_string(),
@@ -245,30 +477,16 @@ pub fn create_bare_venv(
             "version_info".to_string(),
             interpreter.markers().python_full_version().string.clone(),
         ),
-        (
-            "include-system-site-packages".to_string(),
-            if system_site_packages {
-                "true".to_string()
-            } else {
-                "false".to_string()
-            },
-        ),
-    ];
-
-    if let Some(prompt) = prompt {
-        pyvenv_cfg_data.push(("prompt".to_string(), prompt));
-    }
-
-    let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
-    write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
-    drop(pyvenv_cfg);
-
-    // Construct the path to the `site-packages` directory.
-    let site_packages = location.join(&interpreter.virtualenv().purelib);
-    fs::create_dir_all(&site_packages)?;
+    ]
+}
 
-    // If necessary, create a symlink from `lib64` to `lib`.
-    // See: https://github.com/python/cpython/blob/b228655c227b2ca298a8ffac44d14ce3d22f6faa/Lib/venv/__init__.py#L135C11-L135C16
+/// If necessary, create a symlink from `lib64` to `lib`.
+///
+/// Like `venv --copies`, the symlink is created regardless of the [`PythonPreference`].
+///
+/// See: <https://github.com/python/cpython/blob/b228655c227b2ca298a8ffac44d14ce3d22f6faa/Lib/venv/__init__.py#L135C11-L135C16>
+#[allow(unused_variables)]
+fn link_lib64(location: &Path, interpreter: &Interpreter) -> io::Result<()> {
     #[cfg(unix)]
     if interpreter.pointer_size().is_64()
         && interpreter.markers().os_name() == "posix"
@@ -280,16 +498,20 @@ pub fn create_bare_venv(
             Ok(()) => {}
             Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
             Err(err) => {
-                return Err(err.into());
+                return Err(err);
             }
         }
     }
+    Ok(())
+}
 
-    // Populate `site-packages` with a `_virtualenv.py` file.
-    fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
-    fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
-
-    Ok(VirtualEnvironment {
+/// Construct the [`VirtualEnvironment`] for the virtualenv at the given location.
+fn virtualenv(
+    location: PathBuf,
+    interpreter: &Interpreter,
+    executable: PathBuf,
+) -> VirtualEnvironment {
+    VirtualEnvironment {
         scheme: Scheme {
             purelib: location.join(&interpreter.virtualenv().purelib),
             platlib: location.join(&interpreter.virtualenv().platlib),
@@ -299,7 +521,140 @@ pub fn create_bare_venv(
         },
         root: location,
         executable,
-    })
+    }
+}
+
+/// Write the activation scripts for each supported shell to the virtual environment's scripts
+/// directory.
+fn write_activation_scripts(
+    location: &Path,
+    interpreter: &Interpreter,
+    prompt: Option<&str>,
+) -> io::Result<()> {
+    let bin_name = if cfg!(unix) {
+        "bin"
+    } else if cfg!(windows) {
+        "Scripts"
+    } else {
+        unimplemented!("Only Windows and Unix are supported")
+    };
+    let scripts = location.join(&interpreter.virtualenv().scripts);
+
+    for (name, template) in ACTIVATE_TEMPLATES {
+        let path_sep = if cfg!(windows) { ";" } else { ":" };
+
+        let relative_site_packages = [
+            interpreter.virtualenv().purelib.as_path(),
+            interpreter.virtualenv().platlib.as_path(),
+        ]
+        .iter()
+        .dedup()
+        .map(|path| {
+            pathdiff::diff_paths(path, &interpreter.virtualenv().scripts)
+                .expect("Failed to calculate relative path to site-packages")
+        })
+        .map(|path| path.simplified().to_str().unwrap().replace('\\', "\\\\"))
+        .join(path_sep);
+
+        let activator = template
+            .replace(
+                "{{ VIRTUAL_ENV_DIR }}",
+                // SAFETY: `unwrap` is guaranteed to succeed because `location` is an `Utf8PathBuf`.
+                location.simplified().to_str().unwrap(),
+            )
+            .replace("{{ BIN_NAME }}", bin_name)
+            .replace("{{ VIRTUAL_PROMPT }}", prompt.unwrap_or_default())
+            .replace("{{ PATH_SEP }}", path_sep)
+            .replace("{{ RELATIVE_SITE_PACKAGES }}", &relative_site_packages);
+        fs::write(scripts.join(name), activator)?;
+    }
+
+    Ok(())
+}
+
+/// Update the configuration of an existing virtual environment, rewriting the given keys in its
+/// `pyvenv.cfg` and, if the prompt changed, regenerating its activation scripts.
+///
+/// Any other keys in the `pyvenv.cfg` are preserved as-is.
+pub fn configure_bare_venv(
+    location: &Path,
+    interpreter: &Interpreter,
+    system_site_packages: Option<bool>,
+    prompt: Option<Prompt>,
+) -> Result<(), Error> {
+    let prompt = match prompt {
+        Some(Prompt::CurrentDirectoryName) => Some(
+            env::current_dir()?
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+        ),
+        Some(Prompt::Static(value)) => Some(Some(value)),
+        Some(Prompt::None) => Some(None),
+        None => None,
+    };
+
+    let location = location.canonicalize()?;
+
+    let mut updates = Vec::new();
+    if let Some(system_site_packages) = system_site_packages {
+        updates.push((
+            "include-system-site-packages",
+            Some(system_site_packages.to_string()),
+        ));
+    }
+    if let Some(prompt) = &prompt {
+        updates.push(("prompt", prompt.clone()));
+    }
+    update_pyvenv_cfg(&location, &updates)?;
+
+    if let Some(prompt) = prompt {
+        info!(
+            "Regenerating activation scripts in: {}",
+            location.user_display()
+        );
+        write_activation_scripts(&location, interpreter, prompt.as_deref())?;
+    }
+
+    Ok(())
+}
+
+/// Read the `key = value` pairs from the `pyvenv.cfg` of the virtualenv at the given location,
+/// preserving their order.
+pub fn read_pyvenv_cfg(location: &Path) -> Result<Vec<(String, String)>, Error> {
+    let contents = fs::read_to_string(location.join("pyvenv.cfg"))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect())
+}
+
+/// Update the given keys in the `pyvenv.cfg` of the virtualenv at the given location.
+///
+/// Existing keys are updated in-place (or removed, if the value is `None`); missing keys are
+/// appended.
+pub fn update_pyvenv_cfg(location: &Path, updates: &[(&str, Option<String>)]) -> Result<(), Error> {
+    let mut pyvenv_cfg_data = read_pyvenv_cfg(location)?;
+
+    for (key, value) in updates {
+        let position = pyvenv_cfg_data
+            .iter()
+            .position(|(existing, _)| existing == key);
+        match (position, value) {
+            (Some(position), Some(value)) => pyvenv_cfg_data[position].1.clone_from(value),
+            (Some(position), None) => {
+                pyvenv_cfg_data.remove(position);
+            }
+            (None, Some(value)) => pyvenv_cfg_data.push(((*key).to_string(), value.clone())),
+            (None, None) => {}
+        }
+    }
+
+    let mut pyvenv_cfg = BufWriter::new(File::create(location.join("pyvenv.cfg"))?);
+    write_cfg(&mut pyvenv_cfg, &pyvenv_cfg_data)?;
+    pyvenv_cfg.flush()?;
+
+    Ok(())
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1,14 +1,21 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
 use platform_tags::PlatformError;
+use uv_configuration::PythonPreference;
+use uv_fs::Simplified;
 use uv_interpreter::{Interpreter, PythonEnvironment};
 
-pub use crate::bare::create_bare_venv;
+pub use crate::bare::{
+    clear_venv, configure_bare_venv, create_bare_venv, read_pyvenv_cfg, repair_bare_venv,
+    update_pyvenv_cfg, Repair,
+};
+pub use crate::store::EnvironmentStore;
 
 mod bare;
+mod store;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -22,6 +29,18 @@ pub enum Error {
     Platform(#[from] PlatformError),
     #[error("Could not find a suitable Python executable for the virtual environment based on the interpreter: {0}")]
     NotFound(String),
+    #[error("Invalid environment name `{0}`; names may only contain letters, digits, `-`, `_`, and `.`, and may not start with `.`")]
+    InvalidEnvironmentName(String),
+    #[error("Refusing to clear `{}`, since it's not a virtualenv", _0.user_display())]
+    ClearNotVirtualenv(PathBuf),
+    #[error("Refusing to repair `{}`, since it's not a virtualenv", _0.user_display())]
+    RepairNotVirtualenv(PathBuf),
+    #[error("Refusing to repair `{}` with Python {}, since it was created with Python {}; recreate the virtualenv instead, as installed packages aren't compatible across Python minor versions", _0.user_display(), _2, _1)]
+    RepairVersionMismatch(PathBuf, String, String),
+    #[error("Refusing to clear `{}`, since it's a symlink to `{}`", _0.user_display(), _1.user_display())]
+    ClearSymlink(PathBuf, PathBuf),
+    #[error("Refusing to write to `{}`, since it's a symlink to `{}` (pass `--allow-external-symlinks` to proceed anyway)", _0.user_display(), _1.user_display())]
+    SymlinkEscape(PathBuf, PathBuf),
 }
 
 /// The value to use for the shell prompt when inside a virtual environment.
@@ -53,6 +72,7 @@ pub fn create_venv(
     interpreter: Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    python_preference: PythonPreference,
     allow_existing: bool,
 ) -> Result<PythonEnvironment, Error> {
     // Create the virtualenv at the given location.
@@ -61,6 +81,7 @@ pub fn create_venv(
         &interpreter,
         prompt,
         system_site_packages,
+        python_preference,
         allow_existing,
     )?;
 
@@ -68,3 +89,40 @@ pub fn create_venv(
     let interpreter = interpreter.with_virtualenv(virtualenv);
     Ok(PythonEnvironment::from_interpreter(interpreter))
 }
+
+/// Repair a virtualenv whose base interpreter moved or was upgraded, preserving its installed
+/// packages.
+pub fn repair_venv(
+    location: &Path,
+    interpreter: Interpreter,
+    python_preference: PythonPreference,
+) -> Result<(PythonEnvironment, Repair), Error> {
+    let (virtualenv, repair) = repair_bare_venv(location, &interpreter, python_preference)?;
+    let interpreter = interpreter.with_virtualenv(virtualenv);
+    Ok((PythonEnvironment::from_interpreter(interpreter), repair))
+}
+
+/// Verify that the virtualenv location isn't a symlink to a system directory or, if a `root` is
+/// provided (e.g., the project root), to a location outside of `root`.
+pub fn check_location(location: &Path, root: Option<&Path>) -> Result<(), Error> {
+    match uv_fs::find_symlink_escape(location, root) {
+        Some(target) => Err(Error::SymlinkEscape(location.to_path_buf(), target)),
+        None => Ok(()),
+    }
+}
+
+/// Update the configuration of an existing virtualenv.
+///
+/// Options that are `None` are left unchanged.
+pub fn configure_venv(
+    venv: &PythonEnvironment,
+    system_site_packages: Option<bool>,
+    prompt: Option<Prompt>,
+) -> Result<(), Error> {
+    configure_bare_venv(
+        venv.root(),
+        venv.interpreter(),
+        system_site_packages,
+        prompt,
+    )
+}
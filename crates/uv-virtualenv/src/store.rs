@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+
+use cache_key::digest;
+use uv_fs::absolutize_path;
+
+use crate::Error;
+
+/// A central store of virtual environments, outside of any project directory (akin to
+/// `virtualenvwrapper`'s `WORKON_HOME`).
+///
+/// Environments in the store are addressed either by a user-specified name, or by a name derived
+/// from the path of the project they belong to.
+#[derive(Debug, Clone)]
+pub struct EnvironmentStore {
+    root: PathBuf,
+}
+
+impl EnvironmentStore {
+    /// Prefer, in order:
+    /// 1. The directory specified by the user (e.g., via `UV_ENVIRONMENTS_DIR`).
+    /// 2. An `environments` directory in the system-appropriate data directory.
+    /// 3. A `.uv_environments` directory in the current working directory.
+    ///
+    /// Returns an absolute store directory.
+    pub fn from_settings(root: Option<PathBuf>) -> Result<Self, Error> {
+        let root = if let Some(root) = root {
+            root
+        } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
+            project_dirs.data_dir().join("environments")
+        } else {
+            PathBuf::from(".uv_environments")
+        };
+        Ok(Self {
+            root: absolutize_path(&root)?.to_path_buf(),
+        })
+    }
+
+    /// The root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The location of the environment with the given user-specified name.
+    pub fn named(&self, name: &str) -> Result<PathBuf, Error> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(Error::InvalidEnvironmentName(name.to_string()));
+        }
+        Ok(self.root.join(name))
+    }
+
+    /// The location of the environment for the project at the given path.
+    ///
+    /// The name combines the project's directory name (for readability) with a digest of its
+    /// absolute path (for uniqueness), e.g., `foo-6b5e2b3dd8b5a1c4`.
+    pub fn for_project(&self, project_root: &Path) -> Result<PathBuf, Error> {
+        let project_root = project_root
+            .canonicalize()
+            .or_else(|_| absolutize_path(project_root).map(|path| path.to_path_buf()))?;
+        let prefix = project_root
+            .file_name()
+            .map(|name| {
+                name.to_string_lossy()
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                    .collect::<String>()
+            })
+            .unwrap_or_else(|| "project".to_string());
+        Ok(self
+            .root
+            .join(format!("{prefix}-{}", digest(&project_root))))
+    }
+}
@@ -3,9 +3,13 @@ use std::path::PathBuf;
 
 use distribution_types::IndexUrl;
 use install_wheel_rs::linker::LinkMode;
-use uv_configuration::{ConfigSettings, IndexStrategy, KeyringProviderType, TargetTriple};
+use install_wheel_rs::ScriptKind;
+use uv_configuration::{
+    BuildCommands, ConfigSettings, FileCollisionPolicy, HttpVersion, IndexStrategy,
+    KeyringProviderType, PackagePatches, PycInvalidationMode, TargetTriple,
+};
 use uv_interpreter::PythonVersion;
-use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ReleaseAge, ResolutionMode};
 
 use crate::{Options, PipOptions, Workspace};
 
@@ -42,10 +46,19 @@ impl Combine for Options {
     fn combine(self, other: Options) -> Options {
         Options {
             native_tls: self.native_tls.combine(other.native_tls),
+            http_timeout: self.http_timeout.combine(other.http_timeout),
+            http_connect_timeout: self
+                .http_connect_timeout
+                .combine(other.http_connect_timeout),
+            http_deadline: self.http_deadline.combine(other.http_deadline),
+            resolve: self.resolve.combine(other.resolve),
+            index_rate_limit: self.index_rate_limit.combine(other.index_rate_limit),
+            http_version: self.http_version.combine(other.http_version),
             offline: self.offline.combine(other.offline),
             no_cache: self.no_cache.combine(other.no_cache),
             preview: self.preview.combine(other.preview),
             cache_dir: self.cache_dir.combine(other.cache_dir),
+            build_dir: self.build_dir.combine(other.build_dir),
             pip: self.pip.combine(other.pip),
         }
     }
@@ -73,11 +86,25 @@ impl Combine for PipOptions {
             extra_index_url: self.extra_index_url.combine(other.extra_index_url),
             no_index: self.no_index.combine(other.no_index),
             find_links: self.find_links.combine(other.find_links),
+            local_dist: self.local_dist.combine(other.local_dist),
             index_strategy: self.index_strategy.combine(other.index_strategy),
+            check_dependency_confusion: self
+                .check_dependency_confusion
+                .combine(other.check_dependency_confusion),
+            dependency_confusion_allow: self
+                .dependency_confusion_allow
+                .combine(other.dependency_confusion_allow),
+            check_metadata_consistency: self
+                .check_metadata_consistency
+                .combine(other.check_metadata_consistency),
             keyring_provider: self.keyring_provider.combine(other.keyring_provider),
             no_build: self.no_build.combine(other.no_build),
             no_binary: self.no_binary.combine(other.no_binary),
             only_binary: self.only_binary.combine(other.only_binary),
+            prefer_source: self.prefer_source.combine(other.prefer_source),
+            prefer_binary: self.prefer_binary.combine(other.prefer_binary),
+            allow_build: self.allow_build.combine(other.allow_build),
+            max_build_depth: self.max_build_depth.combine(other.max_build_depth),
             no_build_isolation: self.no_build_isolation.combine(other.no_build_isolation),
             strict: self.strict.combine(other.strict),
             extra: self.extra.combine(other.extra),
@@ -87,18 +114,35 @@ impl Combine for PipOptions {
             prerelease: self.prerelease.combine(other.prerelease),
             output_file: self.output_file.combine(other.output_file),
             no_strip_extras: self.no_strip_extras.combine(other.no_strip_extras),
+            no_strip_markers: self.no_strip_markers.combine(other.no_strip_markers),
             no_annotate: self.no_annotate.combine(other.no_annotate),
             no_header: self.no_header.combine(other.no_header),
             custom_compile_command: self
                 .custom_compile_command
                 .combine(other.custom_compile_command),
+            custom_header: self.custom_header.combine(other.custom_header),
+            sign_with: self.sign_with.combine(other.sign_with),
             generate_hashes: self.generate_hashes.combine(other.generate_hashes),
             legacy_setup_py: self.legacy_setup_py.combine(other.legacy_setup_py),
             config_settings: self.config_settings.combine(other.config_settings),
+            build_commands: self.build_commands.combine(other.build_commands),
+            patch: self.patch.combine(other.patch),
             python_version: self.python_version.combine(other.python_version),
             python_platform: self.python_platform.combine(other.python_platform),
             exclude_newer: self.exclude_newer.combine(other.exclude_newer),
+            deny_package: self.deny_package.combine(other.deny_package),
+            allow_package: self.allow_package.combine(other.allow_package),
+            min_release_age: self.min_release_age.combine(other.min_release_age),
+            min_release_age_package: self
+                .min_release_age_package
+                .combine(other.min_release_age_package),
+            resolution_timeout: self.resolution_timeout.combine(other.resolution_timeout),
+            max_backtracks: self.max_backtracks.combine(other.max_backtracks),
+            prefetch: self.prefetch.combine(other.prefetch),
+            prefetch_threshold: self.prefetch_threshold.combine(other.prefetch_threshold),
             no_emit_package: self.no_emit_package.combine(other.no_emit_package),
+            unsafe_package: self.unsafe_package.combine(other.unsafe_package),
+            allow_unsafe: self.allow_unsafe.combine(other.allow_unsafe),
             emit_index_url: self.emit_index_url.combine(other.emit_index_url),
             emit_find_links: self.emit_find_links.combine(other.emit_find_links),
             emit_marker_expression: self
@@ -109,13 +153,29 @@ impl Combine for PipOptions {
                 .combine(other.emit_index_annotation),
             annotation_style: self.annotation_style.combine(other.annotation_style),
             link_mode: self.link_mode.combine(other.link_mode),
+            scripts: self.scripts.combine(other.scripts),
+            rewrite_shebangs: self.rewrite_shebangs.combine(other.rewrite_shebangs),
+            gui_trampolines: self.gui_trampolines.combine(other.gui_trampolines),
+            file_collisions: self.file_collisions.combine(other.file_collisions),
             compile_bytecode: self.compile_bytecode.combine(other.compile_bytecode),
+            cache_resolution: self.cache_resolution.combine(other.cache_resolution),
+            compile_optimize: self.compile_optimize.combine(other.compile_optimize),
+            compile_exclude: self.compile_exclude.combine(other.compile_exclude),
+            compile_invalidation_mode: self
+                .compile_invalidation_mode
+                .combine(other.compile_invalidation_mode),
+            no_install_tests: self.no_install_tests.combine(other.no_install_tests),
+            exclude_path_glob: self.exclude_path_glob.combine(other.exclude_path_glob),
             require_hashes: self.require_hashes.combine(other.require_hashes),
+            pre_install_hook: self.pre_install_hook.combine(other.pre_install_hook),
+            post_install_hook: self.post_install_hook.combine(other.post_install_hook),
+            post_uninstall_hook: self.post_uninstall_hook.combine(other.post_uninstall_hook),
             concurrent_downloads: self
                 .concurrent_downloads
                 .combine(other.concurrent_downloads),
             concurrent_builds: self.concurrent_builds.combine(other.concurrent_builds),
             concurrent_installs: self.concurrent_installs.combine(other.concurrent_installs),
+            concurrent_unzips: self.concurrent_unzips.combine(other.concurrent_unzips),
         }
     }
 }
@@ -132,6 +192,8 @@ macro_rules! impl_combine_or {
 
 impl_combine_or!(AnnotationStyle);
 impl_combine_or!(ExcludeNewer);
+impl_combine_or!(FileCollisionPolicy);
+impl_combine_or!(HttpVersion);
 impl_combine_or!(IndexStrategy);
 impl_combine_or!(IndexUrl);
 impl_combine_or!(KeyringProviderType);
@@ -139,11 +201,16 @@ impl_combine_or!(LinkMode);
 impl_combine_or!(NonZeroUsize);
 impl_combine_or!(PathBuf);
 impl_combine_or!(PreReleaseMode);
+impl_combine_or!(PycInvalidationMode);
 impl_combine_or!(PythonVersion);
+impl_combine_or!(ReleaseAge);
 impl_combine_or!(ResolutionMode);
+impl_combine_or!(ScriptKind);
 impl_combine_or!(String);
 impl_combine_or!(TargetTriple);
 impl_combine_or!(bool);
+impl_combine_or!(u64);
+impl_combine_or!(usize);
 
 impl<T> Combine for Option<Vec<T>> {
     /// Combine two vectors by extending the vector in `self` with the vector in `other`, if they're
@@ -159,6 +226,28 @@ impl<T> Combine for Option<Vec<T>> {
     }
 }
 
+impl Combine for Option<BuildCommands> {
+    /// Combine two maps by merging the map in `self` with the map in `other`, if they're both
+    /// `Some`.
+    fn combine(self, other: Option<BuildCommands>) -> Option<BuildCommands> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl Combine for Option<PackagePatches> {
+    /// Combine two maps by merging the map in `self` with the map in `other`, if they're both
+    /// `Some`.
+    fn combine(self, other: Option<PackagePatches>) -> Option<PackagePatches> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
 impl Combine for Option<ConfigSettings> {
     /// Combine two maps by merging the map in `self` with the map in `other`, if they're both
     /// `Some`.
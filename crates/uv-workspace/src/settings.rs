@@ -4,12 +4,18 @@ use serde::Deserialize;
 
 use distribution_types::{FlatIndexLocation, IndexUrl};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ScriptKind;
 use uv_configuration::{
-    ConfigSettings, IndexStrategy, KeyringProviderType, PackageNameSpecifier, TargetTriple,
+    BuildCommands, ConfigSettings, FileCollisionPolicy, HostOverride, HttpVersion, IndexRateLimit,
+    IndexStrategy, KeyringProviderType, PackageNameSpecifier, PackagePatches, PycInvalidationMode,
+    TargetTriple,
 };
 use uv_interpreter::PythonVersion;
 use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_resolver::{
+    AnnotationStyle, DeniedPackage, ExcludeNewer, PackageReleaseAge, PreReleaseMode,
+    PrefetchSetting, ReleaseAge, ResolutionMode,
+};
 
 /// A `pyproject.toml` with an (optional) `[tool.uv]` section.
 #[allow(dead_code)]
@@ -32,10 +38,17 @@ pub(crate) struct Tools {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Options {
     pub native_tls: Option<bool>,
+    pub http_timeout: Option<u64>,
+    pub http_connect_timeout: Option<u64>,
+    pub http_deadline: Option<u64>,
+    pub resolve: Option<Vec<HostOverride>>,
+    pub index_rate_limit: Option<Vec<IndexRateLimit>>,
+    pub http_version: Option<HttpVersion>,
     pub offline: Option<bool>,
     pub no_cache: Option<bool>,
     pub preview: Option<bool>,
     pub cache_dir: Option<PathBuf>,
+    pub build_dir: Option<PathBuf>,
     pub pip: Option<PipOptions>,
 }
 
@@ -53,11 +66,19 @@ pub struct PipOptions {
     pub extra_index_url: Option<Vec<IndexUrl>>,
     pub no_index: Option<bool>,
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    pub local_dist: Option<Vec<PathBuf>>,
     pub index_strategy: Option<IndexStrategy>,
+    pub check_dependency_confusion: Option<bool>,
+    pub dependency_confusion_allow: Option<Vec<PackageName>>,
+    pub check_metadata_consistency: Option<bool>,
     pub keyring_provider: Option<KeyringProviderType>,
     pub no_build: Option<bool>,
     pub no_binary: Option<Vec<PackageNameSpecifier>>,
     pub only_binary: Option<Vec<PackageNameSpecifier>>,
+    pub prefer_source: Option<Vec<PackageNameSpecifier>>,
+    pub prefer_binary: Option<Vec<PackageNameSpecifier>>,
+    pub allow_build: Option<Vec<PackageName>>,
+    pub max_build_depth: Option<usize>,
     pub no_build_isolation: Option<bool>,
     pub strict: Option<bool>,
     pub extra: Option<Vec<ExtraName>>,
@@ -67,25 +88,54 @@ pub struct PipOptions {
     pub prerelease: Option<PreReleaseMode>,
     pub output_file: Option<PathBuf>,
     pub no_strip_extras: Option<bool>,
+    pub no_strip_markers: Option<bool>,
     pub no_annotate: Option<bool>,
     pub no_header: Option<bool>,
     pub custom_compile_command: Option<String>,
+    pub custom_header: Option<String>,
+    pub sign_with: Option<PathBuf>,
     pub generate_hashes: Option<bool>,
     pub legacy_setup_py: Option<bool>,
     pub config_settings: Option<ConfigSettings>,
+    pub build_commands: Option<BuildCommands>,
+    pub patch: Option<PackagePatches>,
     pub python_version: Option<PythonVersion>,
     pub python_platform: Option<TargetTriple>,
     pub exclude_newer: Option<ExcludeNewer>,
+    pub deny_package: Option<Vec<DeniedPackage>>,
+    pub allow_package: Option<Vec<PackageName>>,
+    pub min_release_age: Option<ReleaseAge>,
+    pub min_release_age_package: Option<Vec<PackageReleaseAge>>,
+    pub resolution_timeout: Option<u64>,
+    pub max_backtracks: Option<usize>,
+    pub prefetch: Option<Vec<PrefetchSetting>>,
+    pub prefetch_threshold: Option<NonZeroUsize>,
     pub no_emit_package: Option<Vec<PackageName>>,
+    pub unsafe_package: Option<Vec<PackageName>>,
+    pub allow_unsafe: Option<bool>,
     pub emit_index_url: Option<bool>,
     pub emit_find_links: Option<bool>,
     pub emit_marker_expression: Option<bool>,
     pub emit_index_annotation: Option<bool>,
     pub annotation_style: Option<AnnotationStyle>,
     pub link_mode: Option<LinkMode>,
+    pub scripts: Option<ScriptKind>,
+    pub rewrite_shebangs: Option<bool>,
+    pub gui_trampolines: Option<bool>,
+    pub file_collisions: Option<FileCollisionPolicy>,
     pub compile_bytecode: Option<bool>,
+    pub compile_optimize: Option<Vec<u8>>,
+    pub compile_exclude: Option<Vec<String>>,
+    pub compile_invalidation_mode: Option<PycInvalidationMode>,
+    pub no_install_tests: Option<bool>,
+    pub exclude_path_glob: Option<Vec<String>>,
     pub require_hashes: Option<bool>,
+    pub cache_resolution: Option<bool>,
+    pub pre_install_hook: Option<String>,
+    pub post_install_hook: Option<String>,
+    pub post_uninstall_hook: Option<String>,
     pub concurrent_downloads: Option<NonZeroUsize>,
     pub concurrent_builds: Option<NonZeroUsize>,
     pub concurrent_installs: Option<NonZeroUsize>,
+    pub concurrent_unzips: Option<NonZeroUsize>,
 }
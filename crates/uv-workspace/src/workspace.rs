@@ -25,7 +25,10 @@ impl Workspace {
 
         debug!("Loading user configuration from: `{}`", file.display());
         match read_file(&file) {
-            Ok(options) => Ok(Some(Self { options, root })),
+            Ok(options) => Ok(Some(Self {
+                options: relative_to(options, &root),
+                root,
+            })),
             Err(WorkspaceError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
             Err(err) => Err(err),
         }
@@ -40,7 +43,7 @@ impl Workspace {
             match find_in_directory(ancestor) {
                 Ok(Some(options)) => {
                     return Ok(Some(Self {
-                        options,
+                        options: relative_to(options, ancestor),
                         root: ancestor.to_path_buf(),
                     }))
                 }
@@ -62,13 +65,23 @@ impl Workspace {
 
     /// Load a [`Workspace`] from a `uv.toml` file.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, WorkspaceError> {
+        let root = path.as_ref().parent().unwrap().to_path_buf();
         Ok(Self {
-            options: read_file(path.as_ref())?,
-            root: path.as_ref().parent().unwrap().to_path_buf(),
+            options: relative_to(read_file(path.as_ref())?, &root),
+            root,
         })
     }
 }
 
+/// Resolve any relative paths in the [`Options`] against the directory containing the
+/// configuration file, such that they remain valid when combined with options from another file.
+fn relative_to(mut options: Options, root: &Path) -> Options {
+    if let Some(pip) = options.pip.as_mut() {
+        pip.patch = pip.patch.take().map(|patch| patch.relative_to(root));
+    }
+    options
+}
+
 /// Returns the path to the user configuration directory.
 ///
 /// This is similar to the `config_dir()` returned by the `dirs` crate, but it uses the
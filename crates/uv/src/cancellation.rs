@@ -0,0 +1,94 @@
+//! Cooperative cancellation of in-flight operations (e.g., on Ctrl-C).
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use uv_warnings::warn_user;
+
+static CANCELLATION: OnceLock<CancellationToken> = OnceLock::new();
+
+/// The number of active [`Cancellable`] guards.
+static CANCELLABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of active [`CriticalSection`] guards.
+static CRITICAL: AtomicUsize = AtomicUsize::new(0);
+
+/// The process-wide [`CancellationToken`], to pass to the resolver, preparer, and installer.
+pub(crate) fn token() -> CancellationToken {
+    CANCELLATION.get_or_init(CancellationToken::new).clone()
+}
+
+/// Returns `true` if the process-wide [`CancellationToken`] has been cancelled.
+pub(crate) fn is_cancelled() -> bool {
+    CANCELLATION
+        .get()
+        .is_some_and(CancellationToken::is_cancelled)
+}
+
+/// A guard for an operation that observes the process-wide [`CancellationToken`], like a
+/// resolution or an installation. While any guard is held, Ctrl-C cancels the token rather than
+/// exiting the process.
+#[must_use]
+pub(crate) struct Cancellable(());
+
+impl Cancellable {
+    /// Enter a cancellable operation, which lasts until the guard is dropped.
+    pub(crate) fn enter() -> Self {
+        CANCELLABLE.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for Cancellable {
+    fn drop(&mut self) {
+        CANCELLABLE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A guard for a section of work that must run to completion (or be rolled back) once started,
+/// like the modification of an environment. While any guard is held, repeated Ctrl-C presses
+/// don't exit the process.
+#[must_use]
+pub(crate) struct CriticalSection(());
+
+impl CriticalSection {
+    /// Enter a critical section, which lasts until the guard is dropped.
+    pub(crate) fn enter() -> Self {
+        CRITICAL.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for CriticalSection {
+    fn drop(&mut self) {
+        CRITICAL.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Cancel any in-flight [`Cancellable`] operations on the first Ctrl-C, such that no new work is
+/// started and partial installations are rolled back; exit immediately on the next, unless the
+/// environment is mid-modification.
+///
+/// Outside of a [`Cancellable`] operation, Ctrl-C exits immediately, as if no handler were
+/// installed.
+pub(crate) fn cancel_on_ctrl_c() {
+    let token = token();
+    tokio::spawn(async move {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if !token.is_cancelled() && CANCELLABLE.load(Ordering::SeqCst) > 0 {
+                warn_user!("Cancelling; press Ctrl-C again to exit immediately");
+                token.cancel();
+                continue;
+            }
+            if CRITICAL.load(Ordering::SeqCst) > 0 {
+                warn_user!("Waiting for the environment to be restored to a consistent state");
+                continue;
+            }
+            std::process::exit(130);
+        }
+        debug!("Failed to register a Ctrl-C handler");
+    });
+}
@@ -1,4 +1,5 @@
 use std::ffi::OsString;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -7,15 +8,24 @@ use anyhow::Result;
 use clap::{Args, Parser, Subcommand};
 
 use distribution_types::{FlatIndexLocation, IndexUrl};
+use pypi_types::HashAlgorithm;
 use uv_cache::CacheArgs;
 use uv_configuration::{
-    ConfigSettingEntry, IndexStrategy, KeyringProviderType, PackageNameSpecifier, TargetTriple,
+    BuildCommandEntry, ConfigSettingEntry, FileCollisionPolicy, HostOverride, HttpVersion,
+    IndexRateLimit, IndexStrategy, KeyringProviderType, PackageNameSpecifier, PycInvalidationMode,
+    PythonPreference, SeedSource, TargetTriple, TrustedHost, UpgradeStrategy,
 };
 use uv_interpreter::PythonVersion;
 use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_resolver::{
+    AnnotationStyle, DeniedPackage, ExcludeNewer, GraphFormat, PackageReleaseAge, PreReleaseMode,
+    PrefetchSetting, ReleaseAge, ResolutionMode,
+};
 
-use crate::commands::{extra_name_with_clap_error, ListFormat, VersionFormat};
+use crate::commands::{
+    extra_name_with_clap_error, CompileGroup, CompileMatrix, ExportFormat, InterpreterListFormat,
+    ListFormat, VersionFormat,
+};
 use crate::compat;
 
 #[derive(Parser)]
@@ -51,6 +61,39 @@ pub(crate) struct GlobalArgs {
     #[arg(global = true, action = clap::ArgAction::Count, long, short, conflicts_with = "quiet")]
     pub(crate) verbose: u8,
 
+    /// Print a summary of the time spent in each phase of the command (resolve, download, build,
+    /// and install) once it completes.
+    #[arg(global = true, long)]
+    pub(crate) timings: bool,
+
+    /// Write a trace of the command to the given file, in the Chrome trace event format.
+    ///
+    /// The trace can be viewed in Perfetto (<https://ui.perfetto.dev>) or `chrome://tracing`, and
+    /// includes timing data for every resolver, download, build, and install span. The path may
+    /// be prefixed with `chrome://` (e.g., `--trace-output chrome://trace.json`).
+    #[arg(global = true, long, value_name = "FILE")]
+    pub(crate) trace_output: Option<String>,
+
+    /// Write a summary of the command to the given file, as JSON, once it completes.
+    ///
+    /// The summary includes the wall time spent in each phase (resolve, download, build, and
+    /// install), HTTP cache hits and misses, the number of bytes downloaded, and the number of
+    /// packages resolved, downloaded, built, installed, and uninstalled, such that dependency
+    /// installation performance can be tracked over time (e.g., in CI). The summary is only
+    /// written locally.
+    #[arg(global = true, long, value_name = "FILE", env = "UV_SUMMARY_FILE")]
+    pub(crate) summary_file: Option<PathBuf>,
+
+    /// The format in which to report errors.
+    ///
+    /// With `json`, errors are written to stderr as a single JSON object, including the
+    /// diagnostic code (e.g., `uv::venv::creation`) and, for resolution failures, the derivation
+    /// tree that explains the conflict. Resolver progress (metadata fetched, versions tried,
+    /// backtracks, and source distribution builds) is likewise reported as one JSON event per
+    /// line, in lieu of the progress bars.
+    #[arg(global = true, long, value_enum, default_value = "text")]
+    pub(crate) output_format: OutputFormat,
+
     /// Disable colors; provided for compatibility with `pip`.
     #[arg(global = true, long, hide = true, conflicts_with = "color")]
     pub(crate) no_color: bool,
@@ -81,6 +124,94 @@ pub(crate) struct GlobalArgs {
     #[arg(global = true, long, overrides_with("native_tls"), hide = true)]
     pub(crate) no_native_tls: bool,
 
+    /// The maximum time to wait for data from an open connection, in seconds.
+    ///
+    /// Can also be set with the `UV_HTTP_TIMEOUT` (or `HTTP_TIMEOUT`) environment variable.
+    /// Defaults to 30s.
+    #[arg(global = true, long, value_name = "SECONDS")]
+    pub(crate) http_timeout: Option<u64>,
+
+    /// The maximum time to wait to establish a connection, including the TLS handshake, in
+    /// seconds.
+    ///
+    /// Can also be set with the `UV_HTTP_CONNECT_TIMEOUT` environment variable. By default,
+    /// connection attempts are only bounded by the operating system.
+    #[arg(global = true, long, value_name = "SECONDS")]
+    pub(crate) http_connect_timeout: Option<u64>,
+
+    /// The maximum time for any single request to complete, including downloading the response
+    /// body, in seconds.
+    ///
+    /// Can also be set with the `UV_HTTP_DEADLINE` environment variable. By default, requests
+    /// have no overall deadline, so large downloads over slow connections aren't cut short.
+    #[arg(global = true, long, value_name = "SECONDS")]
+    pub(crate) http_deadline: Option<u64>,
+
+    /// Connect to a host at the given addresses, rather than resolving it via DNS, in the form
+    /// `HOST:PORT:ADDR[,ADDR...]` (as in curl's `--resolve`).
+    ///
+    /// Useful for reaching an internal index from environments with split-horizon DNS, or without
+    /// DNS at all. TLS certificates are still verified against the host name. The override applies
+    /// to the host on any port; the port may be given as `*`.
+    ///
+    /// May be provided multiple times.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_RESOLVE",
+        value_delimiter = ' ',
+        value_name = "HOST:PORT:ADDR"
+    )]
+    pub(crate) resolve: Option<Vec<HostOverride>>,
+
+    /// Disable TLS certificate verification for the given host, in the form `HOST[:PORT]`.
+    ///
+    /// Intended for internal indexes that use self-signed certificates. Verification is only
+    /// skipped for connections to the given hosts (and, if provided, ports); all other hosts are
+    /// verified as usual.
+    ///
+    /// WARNING: Connections to trusted hosts are vulnerable to interception. Prefer adding the
+    /// index's certificate authority to the system store (with `--native-tls`) or to
+    /// `SSL_CERT_FILE`.
+    ///
+    /// May be provided multiple times.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_TRUSTED_HOST",
+        value_delimiter = ' ',
+        value_name = "HOST[:PORT]"
+    )]
+    pub(crate) trusted_host: Option<Vec<TrustedHost>>,
+
+    /// Cap the rate of requests sent to a host, in the form `HOST[:PORT]=RATE[:BURST]`, where
+    /// `RATE` is the number of requests per second.
+    ///
+    /// Once the burst (by default, a single request) is exhausted, requests to the host are
+    /// delayed to respect the rate, rather than failing. Useful for indexes that ban clients that
+    /// exceed a request quota, e.g., `--index-rate-limit artifactory.internal=10:20`.
+    ///
+    /// If the host responds with a `Retry-After` header, requests to it are paused accordingly
+    /// (for up to a minute).
+    ///
+    /// May be provided multiple times.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_INDEX_RATE_LIMIT",
+        value_delimiter = ' ',
+        value_name = "HOST[:PORT]=RATE[:BURST]"
+    )]
+    pub(crate) index_rate_limit: Option<Vec<IndexRateLimit>>,
+
+    /// The HTTP version to use for requests, as `1.1`, `2`, or `auto`.
+    ///
+    /// Defaults to `1.1`. With `auto`, the version is negotiated with each server (preferring
+    /// HTTP/2), and requests that fail with an HTTP/2 protocol error (e.g., due to a proxy that
+    /// mangles HTTP/2 streams) are retried over HTTP/1.1, as reported in the verbose output.
+    #[arg(global = true, long, env = "UV_HTTP_VERSION", value_name = "VERSION")]
+    pub(crate) http_version: Option<HttpVersion>,
+
     /// Disable network access, relying only on locally cached data and locally available files.
     #[arg(global = true, long, overrides_with("no_offline"))]
     pub(crate) offline: bool,
@@ -99,11 +230,48 @@ pub(crate) struct GlobalArgs {
     /// parent directories.
     #[arg(global = true, long, hide = true)]
     pub(crate) isolated: bool,
+
+    /// The name of the virtual environment directory to search for in the current and parent
+    /// directories when no virtual environment is active, in place of `.venv`.
+    #[arg(global = true, long, hide = true, env = "UV_VENV_DIR")]
+    pub(crate) venv_dir: Option<PathBuf>,
+
+    /// Run the install hooks (i.e., `pre-install-hook`, `post-install-hook`, and
+    /// `post-uninstall-hook`) and external build commands (i.e., `build-commands`) defined in the
+    /// configuration file.
+    ///
+    /// Hooks and build commands run arbitrary shell commands, so they're ignored unless explicitly
+    /// allowed, such that a project's configuration can't run commands on the user's behalf.
+    /// Build commands passed via `--build-command` are always allowed.
+    #[arg(global = true, long, env = "UV_ALLOW_HOOKS", value_parser = clap::builder::BoolishValueParser::new())]
+    pub(crate) allow_hooks: bool,
+
+    /// Run hermetically, using only the arguments provided on the command-line.
+    ///
+    /// Ignores any `UV_*` and `PIP_*` environment variables, `pyproject.toml` and `uv.toml`
+    /// files in the current directory, its parents, or the user configuration directory, and
+    /// credentials from netrc files and cloud provider CLIs. Keyring authentication is only used
+    /// if requested with `--keyring-provider`.
+    ///
+    /// Intended for reproducible builds, in which the host environment must not influence
+    /// resolution.
+    #[arg(global = true, long)]
+    pub(crate) hermetic: bool,
+
+    /// Allow modifying an environment that's a symlink to a system directory or, for a project
+    /// environment, to a location outside of the project.
+    ///
+    /// By default, uv refuses to write through such symlinks (e.g., a `.venv` that links to
+    /// another project's environment, or to the system Python), since doing so is rarely
+    /// intended.
+    #[arg(global = true, long, env = "UV_ALLOW_EXTERNAL_SYMLINKS", value_parser = clap::builder::BoolishValueParser::new())]
+    pub(crate) allow_external_symlinks: bool,
 }
 
-#[derive(Debug, Clone, clap::ValueEnum)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub(crate) enum ColorChoice {
     /// Enables colored output only when the output is going to a terminal or TTY with support.
+    #[default]
     Auto,
 
     /// Enables colored output regardless of the detected environment.
@@ -113,6 +281,16 @@ pub(crate) enum ColorChoice {
     Never,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Report errors as human-readable text.
+    #[default]
+    Text,
+
+    /// Report errors as JSON objects.
+    Json,
+}
+
 impl From<ColorChoice> for anstream::ColorChoice {
     fn from(value: ColorChoice) -> Self {
         match value {
@@ -133,8 +311,20 @@ pub(crate) enum Commands {
     /// Create a virtual environment.
     #[command(alias = "virtualenv", alias = "v")]
     Venv(VenvArgs),
+    /// Inspect and manage existing virtual environments.
+    Environment(EnvironmentNamespace),
     /// Manage the cache.
     Cache(CacheNamespace),
+    /// Inspect, retag, and audit wheels.
+    Wheel(WheelNamespace),
+    /// Inspect package indexes.
+    Index(IndexNamespace),
+    /// Inspect Python interpreters.
+    #[command(alias = "python")]
+    Interpreter(InterpreterNamespace),
+    /// Diagnose common problems with interpreter discovery, the cache, index access, and the
+    /// keyring, suggesting a fix for each.
+    Doctor(DoctorArgs),
     /// Manage the `uv` executable.
     #[command(name = "self")]
     #[cfg(feature = "self-update")]
@@ -151,6 +341,9 @@ pub(crate) enum Commands {
     /// Resolve the project requirements into a lockfile.
     #[clap(hide = true)]
     Lock(LockArgs),
+    /// Export the project lockfile to an alternate format.
+    #[clap(hide = true)]
+    Export(ExportArgs),
     /// Display uv's version
     Version {
         #[arg(long, value_enum, default_value = "text")]
@@ -189,6 +382,271 @@ pub(crate) enum CacheCommand {
     Prune,
     /// Show the cache directory.
     Dir,
+    /// Inspect the logs retained for failed source distribution builds.
+    ///
+    /// Logs are retained for a week, after which they're removed by `uv cache prune`.
+    Builds(CacheBuildsNamespace),
+    /// Resolve and download a set of requirements into the cache, without installing them.
+    Warm(CacheWarmArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct CacheBuildsNamespace {
+    #[command(subcommand)]
+    pub(crate) command: CacheBuildsCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CacheBuildsCommand {
+    /// List the failed builds whose logs are retained in the cache, most recent first.
+    List,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct CacheWarmArgs {
+    /// Download all packages listed in the given requirements files.
+    #[arg(long, short, required = true)]
+    pub(crate) requirement: Vec<PathBuf>,
+
+    /// Constrain versions using the given requirements files.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's downloaded. However, including a package in a constraints file will
+    /// _not_ trigger the download of that package.
+    #[arg(long, short, env = "UV_CONSTRAINT", value_delimiter = ' ', value_parser = parse_file_path)]
+    pub(crate) constraint: Vec<Maybe<PathBuf>>,
+
+    /// Build wheels for any source distributions, such that they can later be installed without
+    /// invoking a build backend.
+    ///
+    /// By default, source distributions are downloaded (and, if necessary to determine their
+    /// metadata, unpacked), but not built.
+    #[arg(long)]
+    pub(crate) build: bool,
+
+    /// The Python interpreter for which the requirements should be resolved and downloaded.
+    ///
+    /// By default, uv uses the first Python interpreter found in the system `PATH`.
+    #[arg(long, short, env = "UV_PYTHON", verbatim_doc_comment)]
+    pub(crate) python: Option<String>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    #[arg(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    #[arg(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Option<Vec<Maybe<IndexUrl>>>,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    #[arg(long, short)]
+    pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
+    /// discovered via `--find-links`.
+    #[arg(long)]
+    pub(crate) no_index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available
+    /// (`first-match`).
+    #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    pub(crate) index_strategy: Option<IndexStrategy>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+}
+
+#[derive(Args)]
+pub(crate) struct WheelNamespace {
+    #[command(subcommand)]
+    pub(crate) command: WheelCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum WheelCommand {
+    /// Show the tags, metadata, `RECORD` summary, and entry points of a wheel, without installing it.
+    Inspect(WheelInspectArgs),
+    /// Rewrite the Python, ABI, or platform tags of a wheel, updating its `WHEEL` and `RECORD`
+    /// files to match.
+    Retag(WheelRetagArgs),
+    /// Check the shared libraries in a Linux wheel against a `manylinux` policy, and retag the
+    /// wheel for that policy if it's compatible.
+    ///
+    /// Unlike `auditwheel repair`, external libraries are not grafted into the wheel: a wheel
+    /// that links against libraries outside of the policy is rejected.
+    Audit(WheelAuditArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct WheelInspectArgs {
+    /// The path or URL of the wheel to inspect.
+    ///
+    /// For URLs, only the necessary members of the archive are fetched, using HTTP range
+    /// requests where supported by the server.
+    pub(crate) wheel: String,
+}
+
+#[derive(Args)]
+pub(crate) struct WheelRetagArgs {
+    /// The path to the wheel to retag.
+    pub(crate) wheel: PathBuf,
+
+    /// The Python tag(s) for the retagged wheel, separated by dots (e.g., `py2.py3`).
+    #[arg(long)]
+    pub(crate) python_tag: Option<String>,
+
+    /// The ABI tag(s) for the retagged wheel, separated by dots (e.g., `abi3`).
+    #[arg(long)]
+    pub(crate) abi_tag: Option<String>,
+
+    /// The platform tag(s) for the retagged wheel, separated by dots (e.g.,
+    /// `manylinux_2_17_x86_64.manylinux2014_x86_64`).
+    #[arg(long)]
+    pub(crate) platform_tag: Option<String>,
+
+    /// The directory to which the retagged wheel should be written.
+    ///
+    /// Defaults to the directory containing the original wheel.
+    #[arg(long, short)]
+    pub(crate) out_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub(crate) struct WheelAuditArgs {
+    /// The path to the wheel to audit.
+    pub(crate) wheel: PathBuf,
+
+    /// The `manylinux` platform tag(s) for the audited wheel, separated by dots (e.g.,
+    /// `manylinux_2_17_x86_64.manylinux2014_x86_64`).
+    ///
+    /// The wheel's shared libraries must only link against libraries that are bundled in the
+    /// wheel or permitted by the `manylinux` policy, and must not require newer `GLIBC`,
+    /// `GLIBCXX`, `CXXABI` or `GCC` symbol versions than the policy allows.
+    #[arg(long)]
+    pub(crate) platform_tag: String,
+
+    /// The directory to which the retagged wheel should be written.
+    ///
+    /// Defaults to the directory containing the original wheel.
+    #[arg(long, short)]
+    pub(crate) out_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub(crate) struct IndexNamespace {
+    #[command(subcommand)]
+    pub(crate) command: IndexCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum IndexCommand {
+    /// Check an index for Simple API availability, metadata and range request support,
+    /// authentication requirements, and latency.
+    Check(IndexCheckArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct IndexCheckArgs {
+    /// The URL of the index to check (e.g., `https://pypi.org/simple`).
+    pub(crate) url: IndexUrl,
+
+    /// The package whose project page should be used to probe the index.
+    #[arg(long, default_value = "pip")]
+    pub(crate) package: PackageName,
+
+    /// Attempt to use `keyring` for authentication for the index URL.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+}
+
+#[derive(Args)]
+pub(crate) struct DoctorArgs {
+    /// The Python interpreter whose discovery should be checked.
+    ///
+    /// By default, uv checks for an activated or local virtual environment, or the first Python
+    /// interpreter found in the system `PATH`.
+    #[arg(long, short, env = "UV_PYTHON", verbatim_doc_comment)]
+    pub(crate) python: Option<String>,
+
+    /// The URL of the Python package index to check (by default: <https://pypi.org/simple>).
+    #[arg(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to check, in addition to `--index-url`.
+    #[arg(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Option<Vec<Maybe<IndexUrl>>>,
+
+    /// Skip checking the registry index (e.g., PyPI).
+    #[arg(long)]
+    pub(crate) no_index: bool,
+
+    /// Attempt to use `keyring` for authentication for index URLs, and check that it's available.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+}
+
+#[derive(Args)]
+pub(crate) struct InterpreterNamespace {
+    #[command(subcommand)]
+    pub(crate) command: InterpreterCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum InterpreterCommand {
+    /// List the Python interpreters that can be discovered, with their version, implementation,
+    /// architecture, path, and the source from which each was discovered.
+    List(InterpreterListArgs),
+    /// Pin the Python interpreter for the current directory, or show the current pin.
+    ///
+    /// The pin is written to a `.python-version` file at the root of the current workspace (or to
+    /// the current directory, outside of a workspace), which is respected by `uv venv`, `uv run`,
+    /// and `uv pip compile` in the directory and its subdirectories when `--python` is omitted.
+    Pin(InterpreterPinArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct InterpreterListArgs {
+    /// Mark the interpreter that uv would select for the given request, as with `--python`.
+    ///
+    /// Supports the same formats as `--python`, e.g., `3.11`, `pypy@3.10`, or a path to an
+    /// executable. If omitted, the interpreter uv would select by default is marked.
+    pub(crate) request: Option<String>,
+
+    /// Select the output format between: `text` (default) or `json`.
+    #[arg(long, value_enum, default_value_t = InterpreterListFormat::default())]
+    pub(crate) format: InterpreterListFormat,
+
+    /// Probe every interpreter anew, rather than reading its metadata from the cache.
+    ///
+    /// Cached metadata is otherwise reused until the interpreter's executable changes (as
+    /// determined by its modification time and size).
+    #[arg(long)]
+    pub(crate) refresh: bool,
+}
+
+#[derive(Args)]
+pub(crate) struct InterpreterPinArgs {
+    /// The Python interpreter to pin, e.g., `3.12`, `pypy@3.10`, or `3.12-arm64`.
+    ///
+    /// Supports the same formats as `--python`. If omitted, the current pin is shown.
+    pub(crate) request: Option<String>,
+
+    /// Pin the exact version and implementation of the interpreter that satisfies the request,
+    /// rather than the request itself.
+    ///
+    /// For example, `uv python pin --resolved 3.12` might pin `cpython@3.12.3`.
+    #[arg(long)]
+    pub(crate) resolved: bool,
 }
 
 #[derive(Args)]
@@ -220,8 +678,19 @@ pub(crate) enum PipCommand {
     List(PipListArgs),
     /// Show information about one or more installed packages.
     Show(PipShowArgs),
+    /// Explain why a package is installed, by showing the dependency paths that require it.
+    Why(PipWhyArgs),
     /// Verify installed packages have compatible dependencies.
     Check(PipCheckArgs),
+    /// Validate requirements files before resolving them, to detect invalid markers, unsatisfiable
+    /// version specifiers, conflicting duplicate requirements, and unknown extras.
+    Lint(PipLintArgs),
+    /// Compute the hashes of local files, or of the artifacts for a pinned requirement, for use
+    /// in a hashed requirements file.
+    Hash(PipHashArgs),
+    /// Canonicalize requirements files: normalize package names, sort requirements, merge
+    /// duplicates, and align hashes, without changing their meaning.
+    Format(PipFormatArgs),
 }
 
 /// A re-implementation of `Option`, used to avoid Clap's automatic `Option` flattening in
@@ -271,7 +740,7 @@ pub(crate) struct PipCompileArgs {
     /// Include all packages listed in the given `requirements.in` files.
     ///
     /// When the path is `-`, then requirements are read from stdin.
-    #[arg(required(true))]
+    #[arg(required_unless_present("group"))]
     pub(crate) src_file: Vec<PathBuf>,
 
     /// Constrain versions using the given requirements files.
@@ -337,6 +806,30 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, short)]
     pub(crate) output_file: Option<PathBuf>,
 
+    /// Resolve an additional requirement group, writing the result to its own output file; may
+    /// be provided more than once.
+    ///
+    /// Groups are resolved concurrently, sharing the package metadata fetched by one another.
+    /// Each group is specified as comma-separated `KEY=VALUE` pairs, as in
+    /// `output-file=requirements-linux.txt,python-platform=linux,python-version=3.11`. The
+    /// `python-version`, `python-platform`, `extra`, and `requirement` keys are optional, and
+    /// default to the values provided on the command line. An empty `extra` resolves the group
+    /// without any extras.
+    #[arg(long, conflicts_with_all = ["output_file", "interactive", "unstable_uv_lock_file"])]
+    pub(crate) group: Vec<CompileGroup>,
+
+    /// Resolve a matrix of requirement groups, writing each cell to its own output file.
+    ///
+    /// The matrix is specified as comma-separated `KEY=VALUES` pairs, with the values for each
+    /// axis separated by `|`, as in `python-version=3.11|3.12,python-platform=linux|windows,extra=|dev`.
+    /// An empty `extra` resolves the cell without any extras.
+    ///
+    /// Each cell is written alongside the `--output-file` (or `requirements.txt`), with a suffix
+    /// for each axis, as in `requirements-py3.11-linux-dev.txt`. Cells are resolved concurrently,
+    /// as with `--group`.
+    #[arg(long, conflicts_with_all = ["interactive", "unstable_uv_lock_file"])]
+    pub(crate) matrix: Option<CompileMatrix>,
+
     /// Include extras in the output file.
     ///
     /// By default, `uv` strips extras, as any packages pulled in by the extras are already included
@@ -345,9 +838,22 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, overrides_with("strip_extras"))]
     pub(crate) no_strip_extras: bool,
 
-    #[arg(long, overrides_with("no_strip_extras"), hide = true)]
+    /// Strip extras from the pinned packages in the output file (e.g., `requests==2.32.3` rather
+    /// than `requests[socks]==2.32.3`). This is the default.
+    #[arg(long, overrides_with("no_strip_extras"))]
     pub(crate) strip_extras: bool,
 
+    /// Include the markers under which each package is required in the output file (e.g.,
+    /// `colorama==0.4.6 ; sys_platform == 'win32'`).
+    ///
+    /// By default, `uv` strips markers, such that every package is pinned unconditionally.
+    /// Markers are derived from the top-level requirements and the dependencies of each package.
+    #[arg(long, overrides_with("strip_markers"))]
+    pub(crate) no_strip_markers: bool,
+
+    #[arg(long, overrides_with("no_strip_markers"), hide = true)]
+    pub(crate) strip_markers: bool,
+
     /// Exclude comment annotations indicating the source of each package.
     #[arg(long, overrides_with("annotate"))]
     pub(crate) no_annotate: bool,
@@ -372,6 +878,24 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, env = "UV_CUSTOM_COMPILE_COMMAND")]
     pub(crate) custom_compile_command: Option<String>,
 
+    /// Replace the comment header at the top of the generated output file with the given text.
+    ///
+    /// Each line of the text is written as a comment, prefixed with `#` if necessary.
+    #[arg(
+        long,
+        env = "UV_CUSTOM_HEADER",
+        conflicts_with = "custom_compile_command"
+    )]
+    pub(crate) custom_header: Option<String>,
+
+    /// Sign the output file (and the `uv.lock` file, if generated) with the given SSH private key.
+    ///
+    /// The signature is created with `ssh-keygen -Y sign` and embedded at the end of the file as a
+    /// comment, such that it can be verified with `--trusted-signers` before installing from the
+    /// file.
+    #[arg(long, env = "UV_SIGN_WITH")]
+    pub(crate) sign_with: Option<PathBuf>,
+
     /// Run offline, i.e., without accessing the network.
 
     /// Refresh all cached data.
@@ -445,6 +969,27 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: Option<IndexStrategy>,
 
+    /// Warn when a package resolved from one index is also available from another configured
+    /// index at a higher version, a common signal of a "dependency confusion" attack.
+    ///
+    /// Packages listed in `dependency-confusion-allow` are exempt.
+    #[arg(long, overrides_with("no_check_dependency_confusion"))]
+    pub(crate) check_dependency_confusion: bool,
+
+    #[arg(long, overrides_with("check_dependency_confusion"), hide = true)]
+    pub(crate) no_check_dependency_confusion: bool,
+
+    /// Warn when the source distribution and wheels of a resolved package disagree on their
+    /// dependency metadata (`Requires-Dist` and `Requires-Python`).
+    ///
+    /// Such packages resolve differently depending on which artifact is read. Checking a package
+    /// may require building its source distribution.
+    #[arg(long, overrides_with("no_check_metadata_consistency"))]
+    pub(crate) check_metadata_consistency: bool,
+
+    #[arg(long, overrides_with("check_metadata_consistency"), hide = true)]
+    pub(crate) no_check_metadata_consistency: bool,
+
     /// Attempt to use `keyring` for authentication for index URLs.
     ///
     /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
@@ -463,6 +1008,7 @@ pub(crate) struct PipCompileArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(long, verbatim_doc_comment, group = "discovery")]
@@ -556,10 +1102,73 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Option<Vec<PackageNameSpecifier>>,
 
+    /// Prefer building from source distributions over using pre-built wheels.
+    ///
+    /// When both a source distribution and a compatible wheel are available for a given version,
+    /// the given packages will be built from the source distribution (e.g., to compile against a
+    /// local toolchain). Wheels will still be used when no source distribution is available.
+    ///
+    /// Multiple packages may be provided. Prefer source distributions for all packages with
+    /// `:all:`. Clear previously specified packages with `:none:`.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) prefer_source: Option<Vec<PackageNameSpecifier>>,
+
+    /// Prefer older versions with a compatible wheel over newer versions that would need to be
+    /// built from a source distribution.
+    ///
+    /// By default, the newest compatible version is selected, even if it's only available as a
+    /// source distribution. With this option, the given packages will instead resolve to the newest
+    /// version that has a compatible wheel, if any; a source distribution is only used if no such
+    /// version exists (e.g., to avoid breaking builds on machines without a compiler when a new
+    /// release is published without wheels).
+    ///
+    /// Like pip's `--prefer-binary`, the flag may be provided without a value to prefer wheels for
+    /// all packages (i.e., `:all:`). Packages must be attached with an equals sign, as a
+    /// comma-separated list (e.g., `--prefer-binary=numpy,scipy`). Clear previously specified
+    /// packages with `:none:`.
+    #[arg(
+        long,
+        num_args = 0..,
+        require_equals = true,
+        value_delimiter = ',',
+        default_missing_value = ":all:"
+    )]
+    pub(crate) prefer_binary: Option<Vec<PackageNameSpecifier>>,
+
+    /// Only allow building source distributions for the given packages; all other packages must
+    /// be installed from pre-built wheels.
+    ///
+    /// Applies to build dependencies as well, such that a source build can't trigger builds of
+    /// packages outside of the allow-list. Editable requirements are not affected, but unnamed
+    /// requirements (e.g., a URL to a source archive) can't be matched against the allow-list, and
+    /// so must be pre-built wheels.
+    ///
+    /// Multiple packages may be provided.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) allow_build: Option<Vec<PackageName>>,
+
+    /// The maximum depth of nested source builds.
+    ///
+    /// Building a source distribution may require building its build dependencies from source in
+    /// turn. A depth of `0` allows building the requested packages, but requires the build
+    /// dependencies of every source distribution to be available as wheels. By default, the depth
+    /// is unlimited.
+    #[arg(long)]
+    pub(crate) max_build_depth: Option<usize>,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[arg(long, short = 'C', alias = "config-settings")]
     pub(crate) config_setting: Option<Vec<ConfigSettingEntry>>,
 
+    /// Build the given package with an external command instead of the built-in PEP 517 build
+    /// frontend, specified as `PACKAGE=COMMAND` pairs.
+    ///
+    /// The command is run via the platform shell, with the path to the source tree and the path
+    /// to an output directory appended as arguments. On success, the command must print the path
+    /// to the built wheel as the last line of its standard output.
+    #[arg(long)]
+    pub(crate) build_command: Option<Vec<BuildCommandEntry>>,
+
     /// The minimum Python version that should be supported by the compiled requirements (e.g.,
     /// `3.7` or `3.7.9`).
     ///
@@ -573,6 +1182,10 @@ pub(crate) struct PipCompileArgs {
     /// Represented as a "target triple", a string that describes the target platform in terms of
     /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
     /// `aaarch64-apple-darwin`.
+    ///
+    /// To target a specific glibc or musl version (e.g., that of a deployment image), pass a
+    /// `manylinux` or `musllinux` target, like `x86_64-manylinux_2_28`. The equivalent pip-style
+    /// platform tags (e.g., `manylinux_2_28_x86_64`) are accepted as aliases.
     #[arg(long)]
     pub(crate) python_platform: Option<TargetTriple>,
 
@@ -583,31 +1196,115 @@ pub(crate) struct PipCompileArgs {
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
-    /// Specify a package to omit from the output resolution. Its dependencies will still be
-    /// included in the resolution. Equivalent to pip-compile's `--unsafe-package` option.
-    #[arg(long, alias = "unsafe-package")]
-    pub(crate) no_emit_package: Option<Vec<PackageName>>,
+    /// Forbid a package, or a range of its versions, from entering the resolution (e.g., `pycrypto`
+    /// or `urllib3<2`).
+    ///
+    /// If a forbidden package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) deny_package: Option<Vec<DeniedPackage>>,
 
-    /// Include `--index-url` and `--extra-index-url` entries in the generated output file.
-    #[arg(long, overrides_with("no_emit_index_url"))]
-    pub(crate) emit_index_url: bool,
+    /// Restrict the resolution to the given packages.
+    ///
+    /// If any other package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) allow_package: Option<Vec<PackageName>>,
 
-    #[arg(long, overrides_with("emit_index_url"), hide = true)]
-    pub(crate) no_emit_index_url: bool,
+    /// Refuse distributions uploaded more recently than the given age (e.g., `7d`), unless their
+    /// version is pinned exactly by a direct requirement or constraint (e.g., `flask==3.0.0`).
+    ///
+    /// Accepts a number followed by `m`, `h`, `d`, or `w`. Since hijacked releases are typically
+    /// detected and yanked within days, waiting before adopting new releases mitigates the risk of
+    /// installing one. Distributions without an upload time from the index are refused.
+    #[arg(long, value_name = "AGE")]
+    pub(crate) min_release_age: Option<ReleaseAge>,
 
-    /// Include `--find-links` entries in the generated output file.
-    #[arg(long, overrides_with("no_emit_find_links"))]
-    pub(crate) emit_find_links: bool,
+    /// Refuse distributions of a specific package uploaded more recently than the given age,
+    /// overriding `--min-release-age` (e.g., `requests=1d`).
+    #[arg(long, value_name = "PACKAGE=AGE")]
+    pub(crate) min_release_age_package: Option<Vec<PackageReleaseAge>>,
 
-    #[arg(long, overrides_with("emit_find_links"), hide = true)]
-    pub(crate) no_emit_find_links: bool,
+    /// Abort the resolution if it takes longer than the given number of seconds.
+    ///
+    /// On timeout, uv reports the packages that caused the most backtracking, which are often the
+    /// best candidates for tighter constraints.
+    #[arg(long, value_name = "SECONDS")]
+    pub(crate) resolution_timeout: Option<u64>,
 
-    /// Whether to emit a marker string indicating when it is known that the
-    /// resulting set of pinned dependencies is valid.
+    /// Abort the resolution if the resolver backtracks more than the given number of times.
     ///
-    /// The pinned dependencies may be valid even when the marker expression is
-    /// false, but when the expression is true, the requirements are known to
-    /// be correct.
+    /// When exceeded, uv reports the packages that caused the most backtracking, which are often
+    /// the best candidates for tighter constraints.
+    #[arg(long)]
+    pub(crate) max_backtracks: Option<usize>,
+
+    /// The maximum number of versions of a package to prefetch metadata for at once, once the
+    /// resolver has tried several of its versions.
+    ///
+    /// Accepts a number of versions (e.g., `100`), which applies to all packages, or a package
+    /// name and a number of versions (e.g., `botocore=200`). May be provided multiple times.
+    /// Larger values help against indexes with high per-request latency; `0` disables
+    /// prefetching.
+    ///
+    /// Defaults to `50`.
+    #[arg(long, value_name = "[PACKAGE=]VERSIONS")]
+    pub(crate) prefetch: Option<Vec<PrefetchSetting>>,
+
+    /// The number of versions of a package the resolver tries before it starts prefetching
+    /// metadata for the versions it is likely to try next.
+    ///
+    /// Must be at least `1`. Defaults to `5`.
+    #[arg(long)]
+    pub(crate) prefetch_threshold: Option<NonZeroUsize>,
+
+    /// Specify a package to omit from the output resolution. Its dependencies will still be
+    /// included in the resolution.
+    ///
+    /// The package is still considered during resolution, such that the pinned versions remain
+    /// compatible with it. Useful when the deployment platform provides its own version of a
+    /// package (e.g., `boto3` on AWS Lambda).
+    #[arg(long, visible_alias = "omit")]
+    pub(crate) no_emit_package: Option<Vec<PackageName>>,
+
+    /// Specify a package to consider unsafe in a requirements file; may be provided more than
+    /// once. Equivalent to pip-compile's `--unsafe-package` option.
+    ///
+    /// Unsafe packages are listed in a separate section at the end of the output, and are left
+    /// unpinned unless `--allow-unsafe` is provided. If `--allow-unsafe` or `--no-allow-unsafe`
+    /// is provided without `--unsafe-package`, `setuptools`, `distribute`, and `pip` are
+    /// considered unsafe, as in pip-compile.
+    #[arg(long)]
+    pub(crate) unsafe_package: Option<Vec<PackageName>>,
+
+    /// Pin packages that are considered unsafe in a requirements file (e.g., `setuptools` and
+    /// `pip`), rather than listing them by name, in a comment at the end of the output.
+    #[arg(long, overrides_with("no_allow_unsafe"))]
+    pub(crate) allow_unsafe: bool,
+
+    #[arg(long, overrides_with("allow_unsafe"), hide = true)]
+    pub(crate) no_allow_unsafe: bool,
+
+    /// Include `--index-url` and `--extra-index-url` entries in the generated output file.
+    #[arg(long, overrides_with("no_emit_index_url"))]
+    pub(crate) emit_index_url: bool,
+
+    #[arg(long, overrides_with("emit_index_url"), hide = true)]
+    pub(crate) no_emit_index_url: bool,
+
+    /// Include `--find-links` entries in the generated output file.
+    #[arg(long, overrides_with("no_emit_find_links"))]
+    pub(crate) emit_find_links: bool,
+
+    #[arg(long, overrides_with("emit_find_links"), hide = true)]
+    pub(crate) no_emit_find_links: bool,
+
+    /// Whether to emit a marker string indicating when it is known that the
+    /// resulting set of pinned dependencies is valid.
+    ///
+    /// The pinned dependencies may be valid even when the marker expression is
+    /// false, but when the expression is true, the requirements are known to
+    /// be correct.
     #[arg(long, overrides_with("no_emit_marker_expression"), hide = true)]
     pub(crate) emit_marker_expression: bool,
 
@@ -616,10 +1313,19 @@ pub(crate) struct PipCompileArgs {
 
     /// Include comment annotations indicating the index used to resolve each package (e.g.,
     /// `# from https://pypi.org/simple`).
-    #[arg(long, overrides_with("no_emit_index_annotation"))]
+    #[arg(
+        long,
+        alias = "annotate-index",
+        overrides_with("no_emit_index_annotation")
+    )]
     pub(crate) emit_index_annotation: bool,
 
-    #[arg(long, overrides_with("emit_index_annotation"), hide = true)]
+    #[arg(
+        long,
+        alias = "no-annotate-index",
+        overrides_with("emit_index_annotation"),
+        hide = true
+    )]
     pub(crate) no_emit_index_annotation: bool,
 
     #[arg(long, overrides_with("no_unstable_uv_lock_file"), hide = true)]
@@ -628,6 +1334,27 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, overrides_with("unstable_uv_lock_file"), hide = true)]
     pub(crate) no_unstable_uv_lock_file: bool,
 
+    /// Prompt for an amendment to the requirements when resolution fails, rather than exiting.
+    ///
+    /// On an unsatisfiable conflict, `uv` offers to relax a direct requirement, allow
+    /// pre-releases, or exclude a transitive dependency, and then resumes resolution with the
+    /// chosen amendment. Any amendments are recorded in the header of the output file.
+    ///
+    /// Requires an interactive terminal.
+    #[arg(long, overrides_with("no_interactive"))]
+    pub(crate) interactive: bool,
+
+    #[arg(long, overrides_with("interactive"), hide = true)]
+    pub(crate) no_interactive: bool,
+
+    /// Write the resolved dependency graph in the given format, rather than a `requirements.txt`
+    /// file.
+    ///
+    /// Each edge is labeled with the extras and markers under which the dependency is required,
+    /// such that platform-conditional subtrees can be identified.
+    #[arg(long, value_enum, conflicts_with = "unstable_uv_lock_file")]
+    pub(crate) graph: Option<GraphFormat>,
+
     #[command(flatten)]
     pub(crate) compat_args: compat::PipCompileCompatArgs,
 }
@@ -683,6 +1410,68 @@ pub(crate) struct PipSyncArgs {
     #[arg(long, value_enum, env = "UV_LINK_MODE")]
     pub(crate) link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
+    /// The method to use when generating entrypoint scripts (e.g., `console_scripts`).
+    ///
+    /// By default, wrapper scripts are written to the scripts directory (`wrapper`). With
+    /// `symlink`, the wrapper scripts are written to the package's `.dist-info` directory and
+    /// symlinked into the scripts directory. On Windows, launcher executables are always used.
+    #[arg(long, value_enum)]
+    pub(crate) scripts: Option<install_wheel_rs::ScriptKind>,
+
+    /// Don't rewrite the shebangs of installed scripts to point to the environment's interpreter.
+    ///
+    /// Scripts retain the `#!python` placeholder, e.g., for relocatable installs whose shebangs
+    /// are rewritten once the environment is moved into place.
+    #[arg(long, overrides_with("rewrite_shebangs"))]
+    pub(crate) no_rewrite_shebangs: bool,
+
+    #[arg(long, overrides_with("no_rewrite_shebangs"), hide = true)]
+    pub(crate) rewrite_shebangs: bool,
+
+    /// Use console launchers, rather than GUI launchers, for `gui_scripts` on Windows.
+    ///
+    /// GUI launchers don't open a console window, but also don't display any output.
+    #[arg(long, overrides_with("gui_trampolines"))]
+    pub(crate) no_gui_trampolines: bool,
+
+    #[arg(long, overrides_with("no_gui_trampolines"), hide = true)]
+    pub(crate) gui_trampolines: bool,
+
+    /// Allow a console or GUI script to be overwritten by a package other than the one that
+    /// provides it, warning rather than failing.
+    ///
+    /// By default, uv refuses to install packages that would overwrite each other's scripts, or
+    /// the scripts of an installed package.
+    #[arg(long)]
+    pub(crate) force: bool,
+
+    /// Allow wheel files (from a path or URL) to be installed even if their platform tags are
+    /// incompatible with the target interpreter, warning rather than failing.
+    ///
+    /// Intended for advanced use cases, like populating an environment for a different platform
+    /// than the current one (e.g., when building a container image). The installed packages may
+    /// fail to import on the current platform.
+    #[arg(long)]
+    pub(crate) ignore_platform_compatibility: bool,
+
+    /// Allow installing requirements that were compiled for a different environment, warning
+    /// rather than failing.
+    ///
+    /// By default, the header of a file produced by `uv pip compile` is validated against the
+    /// target environment, such that requirements compiled for a newer Python version (with
+    /// `--python-version`) or a different platform (with `--python-platform`) are rejected.
+    #[arg(long)]
+    pub(crate) ignore_compiled_environment: bool,
+
+    /// How to handle files that are provided by multiple packages with differing contents.
+    ///
+    /// Such collisions (e.g., two packages that provide the same module) are a common cause of
+    /// broken imports, as whichever package is installed last overwrites the other's files.
+    ///
+    /// Defaults to `warn`.
+    #[arg(long, value_enum, env = "UV_FILE_COLLISIONS")]
+    pub(crate) file_collisions: Option<FileCollisionPolicy>,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -715,6 +1504,15 @@ pub(crate) struct PipSyncArgs {
     #[arg(long, short)]
     pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
 
+    /// Directories of locally-built distributions (e.g., `dist/`) to resolve from before
+    /// consulting any index.
+    ///
+    /// Any package with a wheel or source distribution in one of these directories is installed
+    /// from that directory, rather than from the registry, even if the registry offers a
+    /// compatible distribution for the same version.
+    #[arg(long)]
+    pub(crate) local_dist: Option<Vec<PathBuf>>,
+
     /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies and those
     /// discovered via `--find-links`.
     #[arg(long)]
@@ -729,6 +1527,16 @@ pub(crate) struct PipSyncArgs {
     #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: Option<IndexStrategy>,
 
+    /// Warn when a package resolved from one index is also available from another configured
+    /// index at a higher version, a common signal of a "dependency confusion" attack.
+    ///
+    /// Packages listed in `dependency-confusion-allow` are exempt.
+    #[arg(long, overrides_with("no_check_dependency_confusion"))]
+    pub(crate) check_dependency_confusion: bool,
+
+    #[arg(long, overrides_with("check_dependency_confusion"), hide = true)]
+    pub(crate) no_check_dependency_confusion: bool,
+
     /// Require a matching hash for each requirement.
     ///
     /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
@@ -747,6 +1555,18 @@ pub(crate) struct PipSyncArgs {
     #[arg(long, overrides_with("require_hashes"), hide = true)]
     pub(crate) no_require_hashes: bool,
 
+    /// Verify the signatures of the requirements and constraints files against the public keys in
+    /// the given `allowed_signers` file before installing from them.
+    ///
+    /// Each file, and any file it includes via `-r` or `-c`, must have been signed (e.g., with
+    /// `uv pip compile --sign-with`) by one of the listed principals, and must not have been
+    /// modified since. Remote includes are rejected. Uses `ssh-keygen -Y verify`.
+    ///
+    /// This setting can't be set in a `pyproject.toml` or `uv.toml`, such that a project can't
+    /// choose which signers it trusts.
+    #[arg(long, env = "UV_TRUSTED_SIGNERS")]
+    pub(crate) trusted_signers: Option<PathBuf>,
+
     /// Attempt to use `keyring` for authentication for index URLs.
     ///
     /// Function's similar to `pip`'s `--keyring-provider subprocess` argument,
@@ -766,6 +1586,7 @@ pub(crate) struct PipSyncArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -820,6 +1641,25 @@ pub(crate) struct PipSyncArgs {
     #[arg(long)]
     pub(crate) target: Option<PathBuf>,
 
+    /// Sync each of the given virtual environments, rather than the discovered environment.
+    ///
+    /// The requirements are resolved once, and the same resolution is applied to every
+    /// environment, sharing downloads and builds (e.g., for per-service environments that share a
+    /// single set of requirements). The environments must use the same Python version and
+    /// platform. If syncing any environment fails, all of them are left unchanged. May be
+    /// provided multiple times.
+    #[arg(
+        long = "environment",
+        value_name = "PATH",
+        conflicts_with_all = ["python", "system", "target", "all_environments"]
+    )]
+    pub(crate) environments: Vec<PathBuf>,
+
+    /// Sync every virtual environment in the current project or workspace, as listed by
+    /// `uv environment list`.
+    #[arg(long, conflicts_with_all = ["python", "system", "target"])]
+    pub(crate) all_environments: bool,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[arg(long, overrides_with("no_legacy_setup_py"))]
@@ -888,6 +1728,60 @@ pub(crate) struct PipSyncArgs {
     #[arg(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Option<Vec<PackageNameSpecifier>>,
 
+    /// Prefer building from source distributions over using pre-built wheels.
+    ///
+    /// When both a source distribution and a compatible wheel are available for a given version,
+    /// the given packages will be built from the source distribution (e.g., to compile against a
+    /// local toolchain). Wheels will still be used when no source distribution is available.
+    ///
+    /// Multiple packages may be provided. Prefer source distributions for all packages with
+    /// `:all:`. Clear previously specified packages with `:none:`.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) prefer_source: Option<Vec<PackageNameSpecifier>>,
+
+    /// Prefer older versions with a compatible wheel over newer versions that would need to be
+    /// built from a source distribution.
+    ///
+    /// By default, the newest compatible version is selected, even if it's only available as a
+    /// source distribution. With this option, the given packages will instead resolve to the newest
+    /// version that has a compatible wheel, if any; a source distribution is only used if no such
+    /// version exists (e.g., to avoid breaking builds on machines without a compiler when a new
+    /// release is published without wheels).
+    ///
+    /// Like pip's `--prefer-binary`, the flag may be provided without a value to prefer wheels for
+    /// all packages (i.e., `:all:`). Packages must be attached with an equals sign, as a
+    /// comma-separated list (e.g., `--prefer-binary=numpy,scipy`). Clear previously specified
+    /// packages with `:none:`.
+    #[arg(
+        long,
+        num_args = 0..,
+        require_equals = true,
+        value_delimiter = ',',
+        default_missing_value = ":all:"
+    )]
+    pub(crate) prefer_binary: Option<Vec<PackageNameSpecifier>>,
+
+    /// Only allow building source distributions for the given packages; all other packages must
+    /// be installed from pre-built wheels.
+    ///
+    /// Applies to build dependencies as well, such that a source build can't trigger builds of
+    /// packages outside of the allow-list. Editable requirements are not affected, but unnamed
+    /// requirements (e.g., a URL to a source archive) can't be matched against the allow-list, and
+    /// so must be pre-built wheels.
+    ///
+    /// Multiple packages may be provided.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) allow_build: Option<Vec<PackageName>>,
+
+    /// The maximum depth of nested source builds.
+    ///
+    /// Building a source distribution may require building its build dependencies from source in
+    /// turn. A depth of `0` allows building the requested packages, but requires the build
+    /// dependencies of every source distribution to be available as wheels. By default, the depth
+    /// is unlimited.
+    #[arg(long)]
+    pub(crate) max_build_depth: Option<usize>,
+
     /// Compile Python files to bytecode.
     ///
     /// By default, does not compile Python (`.py`) files to bytecode (`__pycache__/*.pyc`), instead
@@ -908,10 +1802,67 @@ pub(crate) struct PipSyncArgs {
     )]
     pub(crate) no_compile_bytecode: bool,
 
+    /// The optimization level to use when compiling Python files to bytecode.
+    ///
+    /// Accepts `0` (no optimization), `1` (equivalent to `python -O`), or `2` (equivalent to
+    /// `python -OO`). May be provided multiple times to compile for multiple optimization levels.
+    /// Defaults to the interpreter's optimization level.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=2))]
+    pub(crate) compile_optimize: Option<Vec<u8>>,
+
+    /// Exclude files and directories matching the given glob pattern from bytecode compilation.
+    ///
+    /// Patterns are matched against paths relative to `site-packages` (e.g., `debugpy` to skip an
+    /// entire package, or `*/tests/*` to skip test modules). May be provided multiple times.
+    #[arg(long)]
+    pub(crate) compile_exclude: Option<Vec<String>>,
+
+    /// The invalidation mode to use for compiled bytecode files.
+    ///
+    /// By default, bytecode is invalidated based on the source file's modification time, unless
+    /// `SOURCE_DATE_EPOCH` is set. Hash-based modes are useful for reproducible builds.
+    #[arg(long, value_enum)]
+    pub(crate) compile_invalidation_mode: Option<PycInvalidationMode>,
+
+    /// After installation, import each top-level module of the installed packages in a separate
+    /// Python subprocess, and fail if any of them can't be imported.
+    ///
+    /// Catches broken wheels (e.g., extension modules linked against missing shared libraries)
+    /// at install time, rather than at first use.
+    #[arg(long)]
+    pub(crate) check_import: bool,
+
+    /// Omit test suites (i.e., any `tests` directory within a package) when installing wheels.
+    ///
+    /// Omitted files are removed from each package's `RECORD`, such that the package can still be
+    /// uninstalled and verified. Intended for slim deployments, like container images.
+    #[arg(long, overrides_with("install_tests"))]
+    pub(crate) no_install_tests: bool,
+
+    #[arg(long, overrides_with("no_install_tests"), hide = true)]
+    pub(crate) install_tests: bool,
+
+    /// Omit files and directories matching the given glob pattern when installing wheels.
+    ///
+    /// Patterns are matched against paths relative to `site-packages` (e.g., `*.pyi` to omit type
+    /// stubs, or `*/docs` to omit bundled documentation). May be provided multiple times. Omitted
+    /// files are removed from each package's `RECORD`.
+    #[arg(long)]
+    pub(crate) exclude_path_glob: Option<Vec<String>>,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[arg(long, short = 'C', alias = "config-settings")]
     pub(crate) config_setting: Option<Vec<ConfigSettingEntry>>,
 
+    /// Build the given package with an external command instead of the built-in PEP 517 build
+    /// frontend, specified as `PACKAGE=COMMAND` pairs.
+    ///
+    /// The command is run via the platform shell, with the path to the source tree and the path
+    /// to an output directory appended as arguments. On success, the command must print the path
+    /// to the built wheel as the last line of its standard output.
+    #[arg(long)]
+    pub(crate) build_command: Option<Vec<BuildCommandEntry>>,
+
     /// The minimum Python version that should be supported by the requirements (e.g.,
     /// `3.7` or `3.7.9`).
     ///
@@ -926,6 +1877,10 @@ pub(crate) struct PipSyncArgs {
     /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
     /// `aaarch64-apple-darwin`.
     ///
+    /// To target a specific glibc or musl version (e.g., that of a deployment image), pass a
+    /// `manylinux` or `musllinux` target, like `x86_64-manylinux_2_28`. The equivalent pip-style
+    /// platform tags (e.g., `manylinux_2_28_x86_64`) are accepted as aliases.
+    ///
     /// WARNING: When specified, uv will select wheels that are compatible with the _target_
     /// platform; as a result, the installed distributions may not be compatible with the _current_
     /// platform. Conversely, any distributions that are built from source may be incompatible with
@@ -949,11 +1904,82 @@ pub(crate) struct PipSyncArgs {
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
+    /// Forbid a package, or a range of its versions, from entering the resolution (e.g., `pycrypto`
+    /// or `urllib3<2`).
+    ///
+    /// If a forbidden package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) deny_package: Option<Vec<DeniedPackage>>,
+
+    /// Restrict the resolution to the given packages.
+    ///
+    /// If any other package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) allow_package: Option<Vec<PackageName>>,
+
+    /// Refuse distributions uploaded more recently than the given age (e.g., `7d`), unless their
+    /// version is pinned exactly by a direct requirement or constraint (e.g., `flask==3.0.0`).
+    ///
+    /// Accepts a number followed by `m`, `h`, `d`, or `w`. Since hijacked releases are typically
+    /// detected and yanked within days, waiting before adopting new releases mitigates the risk of
+    /// installing one. Distributions without an upload time from the index are refused.
+    #[arg(long, value_name = "AGE")]
+    pub(crate) min_release_age: Option<ReleaseAge>,
+
+    /// Refuse distributions of a specific package uploaded more recently than the given age,
+    /// overriding `--min-release-age` (e.g., `requests=1d`).
+    #[arg(long, value_name = "PACKAGE=AGE")]
+    pub(crate) min_release_age_package: Option<Vec<PackageReleaseAge>>,
+
+    /// Abort the resolution if it takes longer than the given number of seconds.
+    ///
+    /// On timeout, uv reports the packages that caused the most backtracking, which are often the
+    /// best candidates for tighter constraints.
+    #[arg(long, value_name = "SECONDS")]
+    pub(crate) resolution_timeout: Option<u64>,
+
+    /// Abort the resolution if the resolver backtracks more than the given number of times.
+    ///
+    /// When exceeded, uv reports the packages that caused the most backtracking, which are often
+    /// the best candidates for tighter constraints.
+    #[arg(long)]
+    pub(crate) max_backtracks: Option<usize>,
+
+    /// The maximum number of versions of a package to prefetch metadata for at once, once the
+    /// resolver has tried several of its versions.
+    ///
+    /// Accepts a number of versions (e.g., `100`), which applies to all packages, or a package
+    /// name and a number of versions (e.g., `botocore=200`). May be provided multiple times.
+    /// Larger values help against indexes with high per-request latency; `0` disables
+    /// prefetching.
+    ///
+    /// Defaults to `50`.
+    #[arg(long, value_name = "[PACKAGE=]VERSIONS")]
+    pub(crate) prefetch: Option<Vec<PrefetchSetting>>,
+
+    /// The number of versions of a package the resolver tries before it starts prefetching
+    /// metadata for the versions it is likely to try next.
+    ///
+    /// Must be at least `1`. Defaults to `5`.
+    #[arg(long)]
+    pub(crate) prefetch_threshold: Option<NonZeroUsize>,
+
     /// Perform a dry run, i.e., don't actually install anything but resolve the dependencies and
     /// print the resulting plan.
     #[arg(long)]
     pub(crate) dry_run: bool,
 
+    /// Write a JSON report of the distributions to be installed to the given path, or to stdout
+    /// if `-`.
+    ///
+    /// The report is compatible with that of `pip install --report`, and additionally lists any
+    /// requirements that were skipped since their markers don't match the target environment,
+    /// along with the marker expressions that decided it. May be combined with `--dry-run`.
+    #[arg(long, value_name = "PATH")]
+    pub(crate) report: Option<PathBuf>,
+
     #[command(flatten)]
     pub(crate) compat_args: compat::PipSyncCompatArgs,
 }
@@ -1018,6 +2044,15 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, short = 'P')]
     pub(crate) upgrade_package: Vec<PackageName>,
 
+    /// The strategy to use when upgrading with `--upgrade`.
+    ///
+    /// By default, uv upgrades all packages, including transitive dependencies, to their latest
+    /// compatible versions (`eager`). With `only-if-needed`, only the direct requirements are
+    /// upgraded; transitive dependencies retain their installed versions unless they're
+    /// incompatible with the upgraded requirements.
+    #[arg(long, value_enum, requires = "upgrade")]
+    pub(crate) upgrade_strategy: Option<UpgradeStrategy>,
+
     /// Reinstall all packages, regardless of whether they're already installed.
     #[arg(long, alias = "force-reinstall", overrides_with("no_reinstall"))]
     pub(crate) reinstall: bool,
@@ -1060,6 +2095,59 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, value_enum, env = "UV_LINK_MODE")]
     pub(crate) link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
+    /// The method to use when generating entrypoint scripts (e.g., `console_scripts`).
+    ///
+    /// By default, wrapper scripts are written to the scripts directory (`wrapper`). With
+    /// `symlink`, the wrapper scripts are written to the package's `.dist-info` directory and
+    /// symlinked into the scripts directory. On Windows, launcher executables are always used.
+    #[arg(long, value_enum)]
+    pub(crate) scripts: Option<install_wheel_rs::ScriptKind>,
+
+    /// Don't rewrite the shebangs of installed scripts to point to the environment's interpreter.
+    ///
+    /// Scripts retain the `#!python` placeholder, e.g., for relocatable installs whose shebangs
+    /// are rewritten once the environment is moved into place.
+    #[arg(long, overrides_with("rewrite_shebangs"))]
+    pub(crate) no_rewrite_shebangs: bool,
+
+    #[arg(long, overrides_with("no_rewrite_shebangs"), hide = true)]
+    pub(crate) rewrite_shebangs: bool,
+
+    /// Use console launchers, rather than GUI launchers, for `gui_scripts` on Windows.
+    ///
+    /// GUI launchers don't open a console window, but also don't display any output.
+    #[arg(long, overrides_with("gui_trampolines"))]
+    pub(crate) no_gui_trampolines: bool,
+
+    #[arg(long, overrides_with("no_gui_trampolines"), hide = true)]
+    pub(crate) gui_trampolines: bool,
+
+    /// Allow a console or GUI script to be overwritten by a package other than the one that
+    /// provides it, warning rather than failing.
+    ///
+    /// By default, uv refuses to install packages that would overwrite each other's scripts, or
+    /// the scripts of an installed package.
+    #[arg(long)]
+    pub(crate) force: bool,
+
+    /// Allow wheel files (from a path or URL) to be installed even if their platform tags are
+    /// incompatible with the target interpreter, warning rather than failing.
+    ///
+    /// Intended for advanced use cases, like populating an environment for a different platform
+    /// than the current one (e.g., when building a container image). The installed packages may
+    /// fail to import on the current platform.
+    #[arg(long)]
+    pub(crate) ignore_platform_compatibility: bool,
+
+    /// How to handle files that are provided by multiple packages with differing contents.
+    ///
+    /// Such collisions (e.g., two packages that provide the same module) are a common cause of
+    /// broken imports, as whichever package is installed last overwrites the other's files.
+    ///
+    /// Defaults to `warn`.
+    #[arg(long, value_enum, env = "UV_FILE_COLLISIONS")]
+    pub(crate) file_collisions: Option<FileCollisionPolicy>,
+
     /// The strategy to use when selecting between the different compatible versions for a given
     /// package requirement.
     ///
@@ -1124,6 +2212,16 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: Option<IndexStrategy>,
 
+    /// Warn when a package resolved from one index is also available from another configured
+    /// index at a higher version, a common signal of a "dependency confusion" attack.
+    ///
+    /// Packages listed in `dependency-confusion-allow` are exempt.
+    #[arg(long, overrides_with("no_check_dependency_confusion"))]
+    pub(crate) check_dependency_confusion: bool,
+
+    #[arg(long, overrides_with("check_dependency_confusion"), hide = true)]
+    pub(crate) no_check_dependency_confusion: bool,
+
     /// Require a matching hash for each requirement.
     ///
     /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
@@ -1142,6 +2240,18 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, overrides_with("require_hashes"), hide = true)]
     pub(crate) no_require_hashes: bool,
 
+    /// Verify the signatures of the requirements and constraints files against the public keys in
+    /// the given `allowed_signers` file before installing from them.
+    ///
+    /// Each file, and any file it includes via `-r` or `-c`, must have been signed (e.g., with
+    /// `uv pip compile --sign-with`) by one of the listed principals, and must not have been
+    /// modified since. Remote includes are rejected. Uses `ssh-keygen -Y verify`.
+    ///
+    /// This setting can't be set in a `pyproject.toml` or `uv.toml`, such that a project can't
+    /// choose which signers it trusts.
+    #[arg(long, env = "UV_TRUSTED_SIGNERS")]
+    pub(crate) trusted_signers: Option<PathBuf>,
+
     /// Attempt to use `keyring` for authentication for index URLs.
     ///
     /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
@@ -1161,6 +2271,7 @@ pub(crate) struct PipInstallArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1215,6 +2326,26 @@ pub(crate) struct PipInstallArgs {
     #[arg(long)]
     pub(crate) target: Option<PathBuf>,
 
+    /// Install packages into each of the given virtual environments, rather than into the
+    /// discovered environment.
+    ///
+    /// The requirements are resolved once, and the same resolution is applied to every
+    /// environment, sharing downloads and builds (e.g., for per-service environments that share a
+    /// single set of requirements). The environments must use the same Python version and
+    /// platform. If installing into any environment fails, all of them are left unchanged. May be
+    /// provided multiple times.
+    #[arg(
+        long = "environment",
+        value_name = "PATH",
+        conflicts_with_all = ["python", "system", "target", "all_environments"]
+    )]
+    pub(crate) environments: Vec<PathBuf>,
+
+    /// Install packages into every virtual environment in the current project or workspace, as
+    /// listed by `uv environment list`.
+    #[arg(long, conflicts_with_all = ["python", "system", "target"])]
+    pub(crate) all_environments: bool,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[arg(long, overrides_with("no_legacy_setup_py"))]
@@ -1282,6 +2413,75 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Option<Vec<PackageNameSpecifier>>,
 
+    /// Prefer building from source distributions over using pre-built wheels.
+    ///
+    /// When both a source distribution and a compatible wheel are available for a given version,
+    /// the given packages will be built from the source distribution (e.g., to compile against a
+    /// local toolchain). Wheels will still be used when no source distribution is available.
+    ///
+    /// Multiple packages may be provided. Prefer source distributions for all packages with
+    /// `:all:`. Clear previously specified packages with `:none:`.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) prefer_source: Option<Vec<PackageNameSpecifier>>,
+
+    /// Prefer older versions with a compatible wheel over newer versions that would need to be
+    /// built from a source distribution.
+    ///
+    /// By default, the newest compatible version is selected, even if it's only available as a
+    /// source distribution. With this option, the given packages will instead resolve to the newest
+    /// version that has a compatible wheel, if any; a source distribution is only used if no such
+    /// version exists (e.g., to avoid breaking builds on machines without a compiler when a new
+    /// release is published without wheels).
+    ///
+    /// Like pip's `--prefer-binary`, the flag may be provided without a value to prefer wheels for
+    /// all packages (i.e., `:all:`). Packages must be attached with an equals sign, as a
+    /// comma-separated list (e.g., `--prefer-binary=numpy,scipy`). Clear previously specified
+    /// packages with `:none:`.
+    #[arg(
+        long,
+        num_args = 0..,
+        require_equals = true,
+        value_delimiter = ',',
+        default_missing_value = ":all:"
+    )]
+    pub(crate) prefer_binary: Option<Vec<PackageNameSpecifier>>,
+
+    /// Only allow building source distributions for the given packages; all other packages must
+    /// be installed from pre-built wheels.
+    ///
+    /// Applies to build dependencies as well, such that a source build can't trigger builds of
+    /// packages outside of the allow-list. Editable requirements are not affected, but unnamed
+    /// requirements (e.g., a URL to a source archive) can't be matched against the allow-list, and
+    /// so must be pre-built wheels.
+    ///
+    /// Multiple packages may be provided.
+    #[arg(long, conflicts_with = "no_build")]
+    pub(crate) allow_build: Option<Vec<PackageName>>,
+
+    /// The maximum depth of nested source builds.
+    ///
+    /// Building a source distribution may require building its build dependencies from source in
+    /// turn. A depth of `0` allows building the requested packages, but requires the build
+    /// dependencies of every source distribution to be available as wheels. By default, the depth
+    /// is unlimited.
+    #[arg(long)]
+    pub(crate) max_build_depth: Option<usize>,
+
+    /// Reuse a previous resolution from the cache when the inputs to the resolver are unchanged.
+    ///
+    /// Resolutions are cached by the requirements, constraints, overrides, index locations, target
+    /// markers and tags, `--exclude-newer` cutoff, and installed packages. Repeated invocations
+    /// with identical inputs skip resolution entirely. Requirements that refer to local paths,
+    /// direct URLs, or Git repositories disable the cache.
+    ///
+    /// Since the package index is not consulted on a cache hit, newly published versions will not
+    /// be considered; combine with `--exclude-newer` for reproducible results.
+    #[arg(long, overrides_with("no_cache_resolution"))]
+    pub(crate) cache_resolution: bool,
+
+    #[arg(long, overrides_with("cache_resolution"), hide = true)]
+    pub(crate) no_cache_resolution: bool,
+
     /// Compile Python files to bytecode.
     ///
     /// By default, does not compile Python (`.py`) files to bytecode (`__pycache__/*.pyc`), instead
@@ -1302,10 +2502,67 @@ pub(crate) struct PipInstallArgs {
     )]
     pub(crate) no_compile_bytecode: bool,
 
+    /// The optimization level to use when compiling Python files to bytecode.
+    ///
+    /// Accepts `0` (no optimization), `1` (equivalent to `python -O`), or `2` (equivalent to
+    /// `python -OO`). May be provided multiple times to compile for multiple optimization levels.
+    /// Defaults to the interpreter's optimization level.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=2))]
+    pub(crate) compile_optimize: Option<Vec<u8>>,
+
+    /// Exclude files and directories matching the given glob pattern from bytecode compilation.
+    ///
+    /// Patterns are matched against paths relative to `site-packages` (e.g., `debugpy` to skip an
+    /// entire package, or `*/tests/*` to skip test modules). May be provided multiple times.
+    #[arg(long)]
+    pub(crate) compile_exclude: Option<Vec<String>>,
+
+    /// The invalidation mode to use for compiled bytecode files.
+    ///
+    /// By default, bytecode is invalidated based on the source file's modification time, unless
+    /// `SOURCE_DATE_EPOCH` is set. Hash-based modes are useful for reproducible builds.
+    #[arg(long, value_enum)]
+    pub(crate) compile_invalidation_mode: Option<PycInvalidationMode>,
+
+    /// After installation, import each top-level module of the installed packages in a separate
+    /// Python subprocess, and fail if any of them can't be imported.
+    ///
+    /// Catches broken wheels (e.g., extension modules linked against missing shared libraries)
+    /// at install time, rather than at first use.
+    #[arg(long)]
+    pub(crate) check_import: bool,
+
+    /// Omit test suites (i.e., any `tests` directory within a package) when installing wheels.
+    ///
+    /// Omitted files are removed from each package's `RECORD`, such that the package can still be
+    /// uninstalled and verified. Intended for slim deployments, like container images.
+    #[arg(long, overrides_with("install_tests"))]
+    pub(crate) no_install_tests: bool,
+
+    #[arg(long, overrides_with("no_install_tests"), hide = true)]
+    pub(crate) install_tests: bool,
+
+    /// Omit files and directories matching the given glob pattern when installing wheels.
+    ///
+    /// Patterns are matched against paths relative to `site-packages` (e.g., `*.pyi` to omit type
+    /// stubs, or `*/docs` to omit bundled documentation). May be provided multiple times. Omitted
+    /// files are removed from each package's `RECORD`.
+    #[arg(long)]
+    pub(crate) exclude_path_glob: Option<Vec<String>>,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[arg(long, short = 'C', alias = "config-settings")]
     pub(crate) config_setting: Option<Vec<ConfigSettingEntry>>,
 
+    /// Build the given package with an external command instead of the built-in PEP 517 build
+    /// frontend, specified as `PACKAGE=COMMAND` pairs.
+    ///
+    /// The command is run via the platform shell, with the path to the source tree and the path
+    /// to an output directory appended as arguments. On success, the command must print the path
+    /// to the built wheel as the last line of its standard output.
+    #[arg(long)]
+    pub(crate) build_command: Option<Vec<BuildCommandEntry>>,
+
     /// The minimum Python version that should be supported by the requirements (e.g.,
     /// `3.7` or `3.7.9`).
     ///
@@ -1320,6 +2577,10 @@ pub(crate) struct PipInstallArgs {
     /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
     /// `aaarch64-apple-darwin`.
     ///
+    /// To target a specific glibc or musl version (e.g., that of a deployment image), pass a
+    /// `manylinux` or `musllinux` target, like `x86_64-manylinux_2_28`. The equivalent pip-style
+    /// platform tags (e.g., `manylinux_2_28_x86_64`) are accepted as aliases.
+    ///
     /// WARNING: When specified, uv will select wheels that are compatible with the _target_
     /// platform; as a result, the installed distributions may not be compatible with the _current_
     /// platform. Conversely, any distributions that are built from source may be incompatible with
@@ -1343,11 +2604,98 @@ pub(crate) struct PipInstallArgs {
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
+    /// Forbid a package, or a range of its versions, from entering the resolution (e.g., `pycrypto`
+    /// or `urllib3<2`).
+    ///
+    /// If a forbidden package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) deny_package: Option<Vec<DeniedPackage>>,
+
+    /// Restrict the resolution to the given packages.
+    ///
+    /// If any other package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) allow_package: Option<Vec<PackageName>>,
+
+    /// Refuse distributions uploaded more recently than the given age (e.g., `7d`), unless their
+    /// version is pinned exactly by a direct requirement or constraint (e.g., `flask==3.0.0`).
+    ///
+    /// Accepts a number followed by `m`, `h`, `d`, or `w`. Since hijacked releases are typically
+    /// detected and yanked within days, waiting before adopting new releases mitigates the risk of
+    /// installing one. Distributions without an upload time from the index are refused.
+    #[arg(long, value_name = "AGE")]
+    pub(crate) min_release_age: Option<ReleaseAge>,
+
+    /// Refuse distributions of a specific package uploaded more recently than the given age,
+    /// overriding `--min-release-age` (e.g., `requests=1d`).
+    #[arg(long, value_name = "PACKAGE=AGE")]
+    pub(crate) min_release_age_package: Option<Vec<PackageReleaseAge>>,
+
+    /// Abort the resolution if it takes longer than the given number of seconds.
+    ///
+    /// On timeout, uv reports the packages that caused the most backtracking, which are often the
+    /// best candidates for tighter constraints.
+    #[arg(long, value_name = "SECONDS")]
+    pub(crate) resolution_timeout: Option<u64>,
+
+    /// Abort the resolution if the resolver backtracks more than the given number of times.
+    ///
+    /// When exceeded, uv reports the packages that caused the most backtracking, which are often
+    /// the best candidates for tighter constraints.
+    #[arg(long)]
+    pub(crate) max_backtracks: Option<usize>,
+
+    /// The maximum number of versions of a package to prefetch metadata for at once, once the
+    /// resolver has tried several of its versions.
+    ///
+    /// Accepts a number of versions (e.g., `100`), which applies to all packages, or a package
+    /// name and a number of versions (e.g., `botocore=200`). May be provided multiple times.
+    /// Larger values help against indexes with high per-request latency; `0` disables
+    /// prefetching.
+    ///
+    /// Defaults to `50`.
+    #[arg(long, value_name = "[PACKAGE=]VERSIONS")]
+    pub(crate) prefetch: Option<Vec<PrefetchSetting>>,
+
+    /// The number of versions of a package the resolver tries before it starts prefetching
+    /// metadata for the versions it is likely to try next.
+    ///
+    /// Must be at least `1`. Defaults to `5`.
+    #[arg(long)]
+    pub(crate) prefetch_threshold: Option<NonZeroUsize>,
+
+    /// Remove any installed packages that aren't required, such that the environment exactly
+    /// matches the requirements (as in `uv pip sync`).
+    #[arg(long)]
+    pub(crate) exact: bool,
+
+    /// Check whether the environment satisfies the requirements, without modifying it.
+    ///
+    /// Exits with a non-zero status if any package would be installed, reinstalled, or (with
+    /// `--exact`) uninstalled, e.g., due to a missing package, a mismatched version or hash, or an
+    /// extraneous package.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub(crate) check_only: bool,
+
     /// Perform a dry run, i.e., don't actually install anything but resolve the dependencies and
     /// print the resulting plan.
     #[arg(long)]
     pub(crate) dry_run: bool,
 
+    /// Write a JSON report of the distributions to be installed to the given path, or to stdout
+    /// if `-`.
+    ///
+    /// The report is compatible with that of `pip install --report`, including the origin
+    /// (PEP 610), hashes, and core metadata of each distribution, and whether it was requested
+    /// directly. Any requirements (including dependencies, along with the package that requires
+    /// them) that were skipped since their markers don't match the target environment are listed
+    /// under `skipped`, along with the marker expressions that decided it. May be combined with
+    /// `--dry-run`.
+    #[arg(long, value_name = "PATH")]
+    pub(crate) report: Option<PathBuf>,
+
     #[arg(long, hide = true, group = "sources")]
     pub(crate) unstable_uv_lock_file: Option<String>,
 
@@ -1367,6 +2715,10 @@ pub(crate) struct PipUninstallArgs {
     #[arg(long, short, group = "sources")]
     pub(crate) requirement: Vec<PathBuf>,
 
+    /// Uninstall all packages in the environment, except for `pip`, `setuptools`, and `wheel`.
+    #[arg(long, group = "sources", conflicts_with_all = ["package", "requirement"])]
+    pub(crate) all: bool,
+
     /// The Python interpreter from which packages should be uninstalled.
     ///
     /// By default, `uv` uninstalls from the virtual environment in the current working directory or
@@ -1377,6 +2729,7 @@ pub(crate) struct PipUninstallArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1439,6 +2792,11 @@ pub(crate) struct PipUninstallArgs {
     /// or system Python interpreter.
     #[arg(long)]
     pub(crate) target: Option<PathBuf>,
+
+    /// Perform a dry run, i.e., don't actually uninstall anything but print the files, directories,
+    /// and scripts that would be removed.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
 }
 
 #[derive(Args)]
@@ -1448,6 +2806,14 @@ pub(crate) struct PipFreezeArgs {
     #[arg(long)]
     pub(crate) exclude_editable: bool,
 
+    /// Include the hashes of the installed distributions in the output, where known.
+    ///
+    /// Hashes are only recorded for distributions installed by uv with hash checking enabled (e.g.,
+    /// via `--require-hashes`). Distributions without a recorded hash are listed without one, with
+    /// a warning.
+    #[arg(long)]
+    pub(crate) hashes: bool,
+
     /// Validate the virtual environment, to detect packages with missing dependencies or other
     /// issues.
     #[arg(long, overrides_with("no_strict"))]
@@ -1465,6 +2831,7 @@ pub(crate) struct PipFreezeArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1517,10 +2884,90 @@ pub(crate) struct PipListArgs {
     #[arg(long, value_enum, default_value_t = ListFormat::default())]
     pub(crate) format: ListFormat,
 
-    /// Validate the virtual environment, to detect packages with missing dependencies or other
-    /// issues.
-    #[arg(long, overrides_with("no_strict"))]
-    pub(crate) strict: bool,
+    /// List only packages for which a newer version is available from the configured indexes,
+    /// along with the latest version and the type of distribution (`wheel` or `sdist`) that would
+    /// be installed.
+    #[arg(long, overrides_with("no_outdated"))]
+    pub(crate) outdated: bool,
+
+    #[arg(long, overrides_with("outdated"), hide = true)]
+    pub(crate) no_outdated: bool,
+
+    /// Include the on-disk size of each package, along with the total size of the listed
+    /// packages.
+    ///
+    /// Sizes are computed from the files recorded as installed by each package (in its `RECORD`),
+    /// including any compiled bytecode. Sizes are unavailable for packages that don't record their
+    /// files, like legacy editable installs.
+    #[arg(long)]
+    pub(crate) sizes: bool,
+
+    /// The strategy to use when considering pre-release versions with `--outdated`.
+    ///
+    /// By default, pre-releases are only considered for packages whose installed version is
+    /// itself a pre-release.
+    #[arg(long, value_enum, env = "UV_PRERELEASE")]
+    pub(crate) prerelease: Option<PreReleaseMode>,
+
+    #[arg(long, hide = true)]
+    pub(crate) pre: bool,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[arg(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[arg(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Option<Vec<Maybe<IndexUrl>>>,
+
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    ///
+    /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
+    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    ///
+    /// If a URL, the page must contain a flat list of links to package files.
+    #[arg(long, short)]
+    pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on those discovered via
+    /// `--find-links`.
+    #[arg(long)]
+    pub(crate) no_index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available
+    /// (`first-match`).
+    #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    pub(crate) index_strategy: Option<IndexStrategy>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
+    /// implemented `uv` will try to use `keyring` via CLI when this flag is used.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
+    /// format (e.g., `2006-12-02`).
+    #[arg(long)]
+    pub(crate) exclude_newer: Option<ExcludeNewer>,
+
+    /// Validate the virtual environment, to detect packages with missing dependencies or other
+    /// issues.
+    #[arg(long, overrides_with("no_strict"))]
+    pub(crate) strict: bool,
 
     #[arg(long, overrides_with("strict"), hide = true)]
     pub(crate) no_strict: bool,
@@ -1534,6 +2981,7 @@ pub(crate) struct PipListArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1565,9 +3013,6 @@ pub(crate) struct PipListArgs {
 
     #[arg(long, overrides_with("system"))]
     pub(crate) no_system: bool,
-
-    #[command(flatten)]
-    pub(crate) compat_args: compat::PipListCompatArgs,
 }
 
 #[derive(Args)]
@@ -1582,6 +3027,7 @@ pub(crate) struct PipCheckArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1615,6 +3061,103 @@ pub(crate) struct PipCheckArgs {
     pub(crate) no_system: bool,
 }
 
+#[derive(Args)]
+pub(crate) struct PipHashArgs {
+    /// The files or pinned requirements (e.g., `flask==3.0.0`) to hash.
+    ///
+    /// For a pinned requirement, every artifact (wheel or source distribution) published for the
+    /// pinned version is hashed.
+    #[arg(required = true)]
+    pub(crate) artifact: Vec<String>,
+
+    /// The hash algorithm to use.
+    ///
+    /// One of `sha256`, `sha384`, `sha512`, or `md5`.
+    #[arg(long, short, default_value = "sha256")]
+    pub(crate) algorithm: HashAlgorithm,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[arg(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[arg(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Option<Vec<Maybe<IndexUrl>>>,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available
+    /// (`first-match`).
+    #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    pub(crate) index_strategy: Option<IndexStrategy>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
+    /// implemented `uv` will try to use `keyring` via CLI when this flag is used.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+}
+
+#[derive(Args)]
+pub(crate) struct PipFormatArgs {
+    /// The `requirements.txt` files to format, in place.
+    #[arg(required = true)]
+    pub(crate) src_file: Vec<PathBuf>,
+
+    /// Check whether the files are formatted, without modifying them.
+    ///
+    /// Exits with a non-zero status if any file would be reformatted.
+    #[arg(long)]
+    pub(crate) check: bool,
+}
+
+#[derive(Args)]
+pub(crate) struct PipLintArgs {
+    /// The files to lint, as `requirements.txt`, `requirements.in`, `pyproject.toml`, `setup.py`,
+    /// or `setup.cfg` files.
+    ///
+    /// For `pyproject.toml`, `setup.py`, and `setup.cfg` files, all optional dependencies are
+    /// linted too.
+    #[arg(required = true)]
+    pub(crate) src_file: Vec<PathBuf>,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index is used to check that any requested extras exist on the referenced package.
+    #[arg(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    #[arg(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Option<Vec<Maybe<IndexUrl>>>,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    ///
+    /// By default, `uv` will stop at the first index on which a given package is available
+    /// (`first-match`).
+    #[arg(long, value_enum, env = "UV_INDEX_STRATEGY")]
+    pub(crate) index_strategy: Option<IndexStrategy>,
+
+    /// Attempt to use `keyring` for authentication for index URLs.
+    ///
+    /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
+    /// implemented `uv` will try to use `keyring` via CLI when this flag is used.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipShowArgs {
@@ -1638,6 +3181,7 @@ pub(crate) struct PipShowArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1671,6 +3215,54 @@ pub(crate) struct PipShowArgs {
     pub(crate) no_system: bool,
 }
 
+#[derive(Args)]
+pub(crate) struct PipWhyArgs {
+    /// The package to explain.
+    pub(crate) package: PackageName,
+
+    /// The Python interpreter whose installed packages should be inspected.
+    ///
+    /// By default, `uv` inspects the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        group = "discovery"
+    )]
+    pub(crate) python: Option<String>,
+
+    /// Inspect the packages installed for the system Python.
+    ///
+    /// By default, `uv` inspects the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found. The `--system` option
+    /// instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[arg(
+        long,
+        env = "UV_SYSTEM_PYTHON",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        group = "discovery",
+        overrides_with("no_system")
+    )]
+    pub(crate) system: bool,
+
+    #[arg(long, overrides_with("system"))]
+    pub(crate) no_system: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct VenvArgs {
@@ -1679,6 +3271,7 @@ pub(crate) struct VenvArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     ///
@@ -1714,9 +3307,25 @@ pub(crate) struct VenvArgs {
     pub(crate) no_system: bool,
 
     /// Install seed packages (`pip`, `setuptools`, and `wheel`) into the virtual environment.
+    ///
+    /// In offline mode, or if the seed packages can't be resolved, the packages are installed from
+    /// the cache or from the wheels bundled with the interpreter for `ensurepip`, where available.
     #[arg(long)]
     pub(crate) seed: bool,
 
+    /// Where to install the seed packages from, when `--seed` is provided.
+    ///
+    /// With `embedded`, the seed packages are installed from local wheels only, without accessing
+    /// the network: those shipped alongside uv (in a `seed-wheels` directory next to the `uv`
+    /// executable, in `../share/uv/seed-wheels` relative to it, or in the directory given by
+    /// `UV_SEED_WHEELS_DIR`), those bundled with the interpreter for `ensurepip`, or those in the
+    /// cache. A `pip` wheel is embedded in uv itself, so `pip` can always be seeded; `setuptools`
+    /// and `wheel` are skipped if no local wheel is available for them.
+    ///
+    /// Defaults to `index`.
+    #[arg(long, value_enum, requires = "seed")]
+    pub(crate) seed_source: Option<SeedSource>,
+
     /// Preserve any existing files or directories at the target path.
     ///
     /// By default, `uv venv` will remove an existing virtual environment at the given path, and
@@ -1729,10 +3338,51 @@ pub(crate) struct VenvArgs {
     #[clap(long)]
     pub(crate) allow_existing: bool,
 
+    /// Remove the contents of any existing virtual environment at the target path before
+    /// re-creating it.
+    ///
+    /// Unlike `--allow-existing`, which can leave stale packages behind, `--clear` ensures that the
+    /// environment starts empty, while preserving the directory itself (e.g., a mount point). uv
+    /// refuses to clear a directory that isn't a virtual environment, or that is a symlink, and
+    /// never follows symlinks within the environment.
+    #[clap(long, conflicts_with = "allow_existing")]
+    pub(crate) clear: bool,
+
+    /// Register the virtual environment with the `py` launcher (Windows only).
+    ///
+    /// The environment is registered per PEP 514 under the `uv` company, such that it can be
+    /// selected with `py -V:uv/<tag>`. Any existing registration with the same tag is replaced.
+    #[arg(long)]
+    pub(crate) register: bool,
+
+    /// The tag under which to register the virtual environment with the `py` launcher.
+    ///
+    /// Defaults to the name of the virtual environment's directory or, if the directory is
+    /// hidden (e.g., `.venv`), the name of its parent directory.
+    #[arg(long, requires = "register")]
+    pub(crate) register_tag: Option<String>,
+
     /// The path to the virtual environment to create.
     #[arg(default_value = ".venv")]
     pub(crate) name: PathBuf,
 
+    /// Create the virtual environment in the central environment store under the given name,
+    /// rather than at a path.
+    ///
+    /// The store is located at `UV_ENVIRONMENTS_DIR`, if set, or in the user's data directory
+    /// otherwise (e.g., `~/.local/share/uv/environments` on Linux). Named environments can be used
+    /// with `uv run --environment`.
+    #[arg(long = "name", value_name = "NAME", conflicts_with_all = ["name", "central"])]
+    pub(crate) env_name: Option<String>,
+
+    /// Create the current project's virtual environment in the central environment store, rather
+    /// than at `.venv` in the project directory.
+    ///
+    /// The environment is addressed by a hash of the project's path, and is used by `uv run` when
+    /// the project has no `.venv`.
+    #[arg(long, conflicts_with = "name")]
+    pub(crate) central: bool,
+
     /// Provide an alternative prompt prefix for the virtual environment.
     ///
     /// The default behavior depends on whether the virtual environment path is provided:
@@ -1755,6 +3405,20 @@ pub(crate) struct VenvArgs {
     #[arg(long)]
     pub(crate) system_site_packages: bool,
 
+    /// Whether to symlink or copy the base interpreter into the virtual environment.
+    ///
+    /// With `copy`, the interpreter is copied rather than symlinked, for use with backup tools that
+    /// don't follow symlinks, or when caching environments across machines. As with `venv --copies`,
+    /// a `lib64` symlink to `lib` is still created on 64-bit Linux. Copied interpreters may not
+    /// work for all Python installations (e.g., those that rely on relative paths to a shared
+    /// `libpython`).
+    ///
+    /// On Windows, the interpreter is always copied.
+    ///
+    /// Defaults to `symlink`.
+    #[arg(long, value_enum)]
+    pub(crate) python_preference: Option<PythonPreference>,
+
     /// The method to use when installing packages from the global cache.
     ///
     /// This option is only used for installing seed packages.
@@ -1764,6 +3428,16 @@ pub(crate) struct VenvArgs {
     #[arg(long, value_enum, env = "UV_LINK_MODE")]
     pub(crate) link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
+    /// Don't install pre-built wheels for the given seed packages.
+    ///
+    /// Seed packages are otherwise only installed from pre-built wheels. Naming a package here
+    /// allows it to be built from a source distribution instead, as with `--only-binary :all:
+    /// --no-binary <package>` in `pip install`.
+    ///
+    /// Multiple packages may be provided. Clear previously specified packages with `:none:`.
+    #[arg(long)]
+    pub(crate) no_binary: Option<Vec<PackageNameSpecifier>>,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -1821,6 +3495,193 @@ pub(crate) struct VenvArgs {
     pub(crate) compat_args: compat::VenvCompatArgs,
 }
 
+#[derive(Args)]
+pub(crate) struct EnvironmentNamespace {
+    #[command(subcommand)]
+    pub(crate) command: EnvironmentCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum EnvironmentCommand {
+    /// Update the configuration of an existing virtual environment.
+    Configure(EnvironmentConfigureArgs),
+    /// List the virtual environments in the current project or workspace.
+    List(EnvironmentListArgs),
+    /// Repair a virtual environment whose base interpreter moved or was upgraded.
+    ///
+    /// Re-links the environment's Python executables, updates `pyvenv.cfg`, regenerates the
+    /// activation scripts, and rewrites console scripts that point at a stale interpreter, all
+    /// while preserving the installed packages.
+    Repair(EnvironmentRepairArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct EnvironmentListArgs {
+    /// The directory to search for virtual environments.
+    ///
+    /// Defaults to the root of the current workspace, or the current directory if not in a
+    /// workspace.
+    pub(crate) path: Option<PathBuf>,
+}
+
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("configuration").required(true).multiple(true))]
+pub(crate) struct EnvironmentConfigureArgs {
+    /// The path to the virtual environment to configure.
+    #[arg(default_value = ".venv")]
+    pub(crate) name: PathBuf,
+
+    /// Give the virtual environment access to the system site packages directory (`on`), or
+    /// revoke it (`off`).
+    #[arg(
+        long,
+        value_name = "on|off",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        group = "configuration"
+    )]
+    pub(crate) system_site_packages: Option<bool>,
+
+    /// Change the prompt prefix for the virtual environment, regenerating its activation scripts.
+    ///
+    /// Possible values:
+    /// - `.`: Use the current directory name.
+    /// - Any string: Use the given string.
+    #[arg(long, verbatim_doc_comment, group = "configuration")]
+    pub(crate) prompt: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct EnvironmentRepairArgs {
+    /// The path to the virtual environment to repair.
+    #[arg(default_value = ".venv")]
+    pub(crate) name: PathBuf,
+
+    /// The Python interpreter to re-link the virtual environment against.
+    ///
+    /// Defaults to an interpreter matching the Python minor version recorded in the virtual
+    /// environment's `pyvenv.cfg`. The interpreter must have the same minor version, since
+    /// installed packages aren't compatible across Python minor versions.
+    #[arg(long, short)]
+    pub(crate) python: Option<String>,
+
+    /// Whether to symlink or copy the Python interpreter into the virtual environment.
+    ///
+    /// Defaults to preserving the virtual environment's existing layout.
+    #[arg(long, value_enum)]
+    pub(crate) python_preference: Option<PythonPreference>,
+}
+
+/// Options shared by the project commands.
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct ProjectSharedArgs {
+    /// Only allow building source distributions for the given packages; all other packages must
+    /// be installed from pre-built wheels.
+    ///
+    /// Applies to build dependencies as well, such that a source build can't trigger builds of
+    /// packages outside of the allow-list. Editable requirements are not affected, but unnamed
+    /// requirements (e.g., a URL to a source archive) can't be matched against the allow-list, and
+    /// so must be pre-built wheels.
+    ///
+    /// Multiple packages may be provided.
+    #[arg(long)]
+    pub(crate) allow_build: Option<Vec<PackageName>>,
+
+    /// The maximum depth of nested source builds.
+    ///
+    /// Building a source distribution may require building its build dependencies from source in
+    /// turn. A depth of `0` allows building the project's requirements, but requires the build
+    /// dependencies of every source distribution to be available as wheels. By default, the depth
+    /// is unlimited.
+    #[arg(long)]
+    pub(crate) max_build_depth: Option<usize>,
+
+    /// Forbid a package, or a range of its versions, from entering the resolution (e.g., `pycrypto`
+    /// or `urllib3<2`).
+    ///
+    /// If a forbidden package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) deny_package: Option<Vec<DeniedPackage>>,
+
+    /// Restrict the resolution to the given packages.
+    ///
+    /// If any other package is required, resolution fails with an explanation of the dependency
+    /// chain that required it.
+    #[arg(long)]
+    pub(crate) allow_package: Option<Vec<PackageName>>,
+
+    /// The method to use when generating entrypoint scripts (e.g., `console_scripts`).
+    ///
+    /// By default, wrapper scripts are written to the scripts directory (`wrapper`). With
+    /// `symlink`, the wrapper scripts are written to the package's `.dist-info` directory and
+    /// symlinked into the scripts directory. On Windows, launcher executables are always used.
+    #[arg(long, value_enum)]
+    pub(crate) scripts: Option<install_wheel_rs::ScriptKind>,
+
+    /// Don't rewrite the shebangs of installed scripts to point to the environment's interpreter.
+    ///
+    /// Scripts retain the `#!python` placeholder, e.g., for relocatable installs whose shebangs
+    /// are rewritten once the environment is moved into place.
+    #[arg(long, overrides_with("rewrite_shebangs"))]
+    pub(crate) no_rewrite_shebangs: bool,
+
+    #[arg(long, overrides_with("no_rewrite_shebangs"), hide = true)]
+    pub(crate) rewrite_shebangs: bool,
+
+    /// Use console launchers, rather than GUI launchers, for `gui_scripts` on Windows.
+    ///
+    /// GUI launchers don't open a console window, but also don't display any output.
+    #[arg(long, overrides_with("gui_trampolines"))]
+    pub(crate) no_gui_trampolines: bool,
+
+    #[arg(long, overrides_with("no_gui_trampolines"), hide = true)]
+    pub(crate) gui_trampolines: bool,
+
+    /// Allow a console or GUI script to be overwritten by a package other than the one that
+    /// provides it, warning rather than failing.
+    ///
+    /// By default, uv refuses to install packages that would overwrite each other's scripts.
+    #[arg(long)]
+    pub(crate) force: bool,
+
+    /// Compile Python files to bytecode after installation.
+    ///
+    /// By default, Python lazily compiles each module to bytecode the first time it's imported.
+    #[arg(long, alias = "compile", overrides_with("no_compile_bytecode"))]
+    pub(crate) compile_bytecode: bool,
+
+    #[arg(
+        long,
+        alias = "no_compile",
+        overrides_with("compile_bytecode"),
+        hide = true
+    )]
+    pub(crate) no_compile_bytecode: bool,
+
+    /// The optimization level to use when compiling Python files to bytecode.
+    ///
+    /// Accepts `0` (no optimization), `1` (equivalent to `python -O`), or `2` (equivalent to
+    /// `python -OO`). May be provided multiple times to compile for multiple optimization levels.
+    /// Defaults to the interpreter's optimization level.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=2))]
+    pub(crate) compile_optimize: Option<Vec<u8>>,
+
+    /// Exclude files and directories matching the given glob pattern from bytecode compilation.
+    ///
+    /// Patterns are matched against paths relative to `site-packages` (e.g., `debugpy` to skip an
+    /// entire package, or `*/tests/*` to skip test modules). May be provided multiple times.
+    #[arg(long)]
+    pub(crate) compile_exclude: Option<Vec<String>>,
+
+    /// The invalidation mode to use for compiled bytecode files.
+    ///
+    /// By default, bytecode is invalidated based on the source file's modification time, unless
+    /// `SOURCE_DATE_EPOCH` is set. Hash-based modes are useful for reproducible builds.
+    #[arg(long, value_enum)]
+    pub(crate) compile_invalidation_mode: Option<PycInvalidationMode>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct RunArgs {
@@ -1835,6 +3696,13 @@ pub(crate) struct RunArgs {
     #[arg(long)]
     pub(crate) with: Vec<String>,
 
+    /// Use the named environment from the central environment store as the project environment,
+    /// rather than the project's `.venv`.
+    ///
+    /// The environment is created if it doesn't exist. See `uv venv --name`.
+    #[arg(long, value_name = "NAME")]
+    pub(crate) environment: Option<String>,
+
     /// The Python interpreter to use to build the run environment.
     ///
     /// By default, `uv` uses the virtual environment in the current working directory or any parent
@@ -1844,6 +3712,7 @@ pub(crate) struct RunArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1854,11 +3723,33 @@ pub(crate) struct RunArgs {
         group = "discovery"
     )]
     pub(crate) python: Option<String>,
+
+    #[command(flatten)]
+    pub(crate) shared: ProjectSharedArgs,
 }
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct SyncArgs {
+    /// Include optional dependencies in the given extra group name; may be provided more than once.
+    ///
+    /// The optional dependencies of each extra are read from the lockfile, which records them
+    /// for all of the project's extras.
+    #[arg(long, conflicts_with = "all_extras", value_parser = extra_name_with_clap_error)]
+    pub(crate) extra: Option<Vec<ExtraName>>,
+
+    /// Include all optional dependencies.
+    #[arg(long, conflicts_with = "extra")]
+    pub(crate) all_extras: bool,
+
+    /// Verify the signature of the lockfile against the public keys in the given
+    /// `allowed_signers` file before installing from it.
+    ///
+    /// The lockfile must have been signed (e.g., with `uv lock --sign-with`) by one of the listed
+    /// principals, and must not have been modified since. Uses `ssh-keygen -Y verify`.
+    #[arg(long, env = "UV_TRUSTED_SIGNERS", conflicts_with_all = ["extra", "all_extras"])]
+    pub(crate) trusted_signers: Option<PathBuf>,
+
     /// The Python interpreter to use to build the run environment.
     ///
     /// By default, `uv` uses the virtual environment in the current working directory or any parent
@@ -1868,6 +3759,7 @@ pub(crate) struct SyncArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1878,11 +3770,21 @@ pub(crate) struct SyncArgs {
         group = "discovery"
     )]
     pub(crate) python: Option<String>,
+
+    #[command(flatten)]
+    pub(crate) shared: ProjectSharedArgs,
 }
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct LockArgs {
+    /// Sign the lockfile with the given SSH private key.
+    ///
+    /// The signature is created with `ssh-keygen -Y sign` and embedded at the end of the lockfile
+    /// as a comment, such that it can be verified with `uv sync --trusted-signers`.
+    #[arg(long, env = "UV_SIGN_WITH")]
+    pub(crate) sign_with: Option<PathBuf>,
+
     /// The Python interpreter to use to build the run environment.
     ///
     /// By default, `uv` uses the virtual environment in the current working directory or any parent
@@ -1892,6 +3794,7 @@ pub(crate) struct LockArgs {
     /// Supported formats:
     /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
     ///   `python3.10` on Linux and macOS.
+    /// - `3.10-arm64` looks for an installed Python 3.10 built for the given architecture.
     /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
     /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
     #[arg(
@@ -1902,6 +3805,31 @@ pub(crate) struct LockArgs {
         group = "discovery"
     )]
     pub(crate) python: Option<String>,
+
+    #[command(flatten)]
+    pub(crate) shared: ProjectSharedArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct ExportArgs {
+    /// The format to which the lockfile should be exported.
+    #[arg(long, value_enum)]
+    pub(crate) format: ExportFormat,
+
+    /// The number of days after which a release is considered stable, when ordering the layers
+    /// of a `docker` export.
+    ///
+    /// Packages pinned to releases older than the given age are installed in the earlier layers,
+    /// such that they're less likely to be invalidated by subsequent upgrades.
+    #[arg(long, default_value_t = 180)]
+    pub(crate) stable_age: u32,
+
+    /// Attempt to use `keyring` for authentication for the indexes in the lockfile, when
+    /// determining the release date of each package.
+    ///
+    /// Defaults to `disabled`.
+    #[arg(long, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: Option<KeyringProviderType>,
 }
 
 #[derive(Args)]
@@ -1959,4 +3887,7 @@ pub(crate) struct ToolRunArgs {
         group = "discovery"
     )]
     pub(crate) python: Option<String>,
+
+    #[command(flatten)]
+    pub(crate) shared: ProjectSharedArgs,
 }
@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use owo_colors::OwoColorize;
+
+use uv_build::BuildLog;
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the failed builds whose logs are retained in the cache.
+pub(crate) fn cache_builds_list(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    let root = cache.bucket(CacheBucket::BuildLogs);
+    let logs = BuildLog::list(&root)
+        .with_context(|| format!("Failed to read build logs at: {}", root.user_display()))?;
+
+    if logs.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "No build logs found in: {}",
+            root.user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for (index, log) in logs.iter().enumerate() {
+        if index > 0 {
+            writeln!(printer.stdout())?;
+        }
+        writeln!(printer.stdout(), "{}", log.path.user_display().bold())?;
+        writeln!(printer.stdout(), "  Package: {}", log.version_id)?;
+        if let Some(failed) = i64::try_from(log.timestamp)
+            .ok()
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        {
+            writeln!(printer.stdout(), "  Failed: {}", failed.to_rfc3339())?;
+        }
+        writeln!(
+            printer.stdout(),
+            "  Error: {} ({})",
+            log.message,
+            log.exit_code
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -7,6 +7,7 @@ use uv_cache::Cache;
 use uv_fs::Simplified;
 
 use crate::commands::{human_readable_bytes, ExitStatus};
+use crate::messages::Message;
 use crate::printer::Printer;
 
 /// Prune all unreachable objects from the cache.
@@ -14,16 +15,22 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
     if !cache.root().exists() {
         writeln!(
             printer.stderr(),
-            "No cache found at: {}",
-            cache.root().user_display().cyan()
+            "{}",
+            printer.messages().format(
+                Message::NoCache,
+                &[("path", &cache.root().user_display().cyan())]
+            )
         )?;
         return Ok(ExitStatus::Success);
     }
 
     writeln!(
         printer.stderr(),
-        "Pruning cache at: {}",
-        cache.root().user_display().cyan()
+        "{}",
+        printer.messages().format(
+            Message::PruningCache,
+            &[("path", &cache.root().user_display().cyan())]
+        )
     )?;
 
     let summary = cache
@@ -33,7 +40,11 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
     // Write a summary of the number of files and directories removed.
     match (summary.num_files, summary.num_dirs) {
         (0, 0) => {
-            write!(printer.stderr(), "No unused entries found")?;
+            write!(
+                printer.stderr(),
+                "{}",
+                printer.messages().format(Message::NoUnusedEntries, &[])
+            )?;
         }
         (0, 1) => {
             write!(printer.stderr(), "Removed 1 directory")?;
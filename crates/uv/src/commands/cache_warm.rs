@@ -0,0 +1,257 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{Dist, IndexLocations, Resolution, ResolvedDist};
+use install_wheel_rs::linker::LinkMode;
+use uv_cache::Cache;
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndexClient, NetworkTimeouts, RegistryClientBuilder,
+    ResponseLimits,
+};
+use uv_configuration::{
+    Concurrency, ConfigSettings, HostOverride, HttpVersion, IndexRateLimit, IndexStrategy,
+    KeyringProviderType, NoBinary, NoBuild, PreviewMode, Reinstall, SetupPyStrategy, TrustedHost,
+    Upgrade, UpgradeStrategy,
+};
+use uv_dispatch::BuildDispatch;
+use uv_distribution::DistributionDatabase;
+use uv_fs::Simplified;
+use uv_installer::Preparer;
+use uv_interpreter::{
+    find_best_interpreter, find_interpreter, InterpreterRequest, SourceSelector, SystemPython,
+};
+use uv_requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use uv_resolver::{FlatIndex, InMemoryIndex, Options, PackagePolicy, PrefetchStrategy};
+use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
+use uv_warnings::warn_user;
+
+use crate::cancellation::{self, Cancellable};
+use crate::commands::pip::operations;
+use crate::commands::reporters::PrepareReporter;
+use crate::commands::{elapsed, ExitStatus};
+use crate::editables::ResolvedEditables;
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// Resolve a set of requirements and download them into the cache, without installing them.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn cache_warm(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    build: bool,
+    python: Option<&str>,
+    venv_dir: Option<&Path>,
+    index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    concurrency: Concurrency,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    find_links_recursive: bool,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    preview: PreviewMode,
+    cache: &Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
+        .connectivity(connectivity)
+        .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
+        .keyring(keyring_provider);
+
+    // Read all requirements from the provided sources.
+    let spec = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        &[],
+        &ExtrasSpecification::None,
+        &client_builder,
+        preview,
+    )
+    .await?;
+
+    // Editables are built from the local source tree on every install, so there's nothing to cache.
+    if !spec.editables.is_empty() {
+        warn_user!("Editable requirements are not cached, and will be ignored");
+    }
+
+    // Find an interpreter for which to resolve the requirements.
+    let system = SystemPython::Allowed;
+    let interpreter = if let Some(python) = python {
+        let request = InterpreterRequest::parse(python);
+        let sources = SourceSelector::from_settings(system);
+        find_interpreter(&request, system, &sources, venv_dir, cache)??
+    } else {
+        find_best_interpreter(&InterpreterRequest::default(), system, venv_dir, cache)??
+    }
+    .into_interpreter();
+
+    debug!(
+        "Using Python {} interpreter at {} to warm the cache",
+        interpreter.python_version(),
+        interpreter.sys_executable().user_display().cyan()
+    );
+
+    // Determine the tags and markers to use for resolution.
+    let tags = interpreter.tags()?;
+    let markers = interpreter.markers();
+
+    // Initialize the registry client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .network_recorder(summary.network().clone())
+        .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .index_max_age(index_max_age)
+        .response_limits(response_limits)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
+        .connectivity(connectivity)
+        .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
+        .keyring(keyring_provider)
+        .markers(markers)
+        .platform(interpreter.platform())
+        .build();
+
+    let hasher = HashStrategy::None;
+    let no_binary = NoBinary::default();
+    let no_build = NoBuild::default();
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, cache).recursive(find_links_recursive);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags, &hasher, &no_build, &no_binary)
+    };
+
+    let config_settings = ConfigSettings::default();
+    let in_flight = InFlight::default();
+    let index = InMemoryIndex::default();
+
+    // Create a build dispatch.
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        BuildIsolation::Isolated,
+        LinkMode::default(),
+        &no_build,
+        &no_binary,
+        concurrency,
+    )
+    .with_build_counter(summary.builds().clone());
+
+    // Resolve the requirements.
+    let resolution = operations::resolve(
+        spec.requirements,
+        spec.constraints,
+        spec.overrides,
+        spec.source_trees,
+        spec.project,
+        &ExtrasSpecification::None,
+        &ResolvedEditables::default(),
+        EmptyInstalledPackages,
+        &hasher,
+        &Reinstall::None,
+        &Upgrade::None,
+        UpgradeStrategy::default(),
+        &PackagePolicy::default(),
+        &PrefetchStrategy::default(),
+        &interpreter,
+        tags,
+        markers,
+        &client,
+        &flat_index,
+        &index,
+        &build_dispatch,
+        concurrency,
+        Options::default(),
+        summary,
+        printer,
+    )
+    .await?;
+    let resolution = Resolution::from(resolution);
+
+    // Download any wheels, and, if requested, build any source distributions. (Source
+    // distributions are otherwise fetched as needed during resolution, to determine their
+    // metadata.)
+    let (wheels, sdists): (Vec<Dist>, Vec<Dist>) = resolution
+        .distributions()
+        .filter_map(|dist| match dist {
+            ResolvedDist::Installable(dist) => Some(dist.clone()),
+            ResolvedDist::Installed(_) => None,
+        })
+        .partition(|dist| matches!(dist, Dist::Built(_)));
+    let remote = if build {
+        wheels.into_iter().chain(sdists).collect::<Vec<_>>()
+    } else {
+        if !sdists.is_empty() {
+            let s = if sdists.len() == 1 { "" } else { "s" };
+            debug!(
+                "Skipping build for {} source distribution{s} (pass `--build` to build them)",
+                sdists.len()
+            );
+        }
+        wheels
+    };
+
+    if !remote.is_empty() {
+        let _cancellable = Cancellable::enter();
+        let start = std::time::Instant::now();
+
+        let preparer = Preparer::new(
+            cache,
+            tags,
+            &hasher,
+            DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
+        )
+        .with_reporter(PrepareReporter::from(printer).with_length(remote.len() as u64))
+        .with_cancellation(cancellation::token());
+
+        let wheels = preparer
+            .prepare(remote, &in_flight)
+            .await
+            .context("Failed to download distributions")?;
+
+        let s = if wheels.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Cached {} in {}",
+                format!("{} package{}", wheels.len(), s).bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,510 @@
+use std::fmt::Write;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use url::Url;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, NetworkTimeouts, RegistryClient, RegistryClientBuilder, ResponseLimits,
+};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, KeyringProviderType, TrustedHost,
+};
+use uv_fs::Simplified;
+use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_normalize::PackageName;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// The environment variables from which `reqwest` reads proxy settings for `https` URLs, in order
+/// of precedence.
+const HTTPS_PROXY_VARIABLES: &[&str] = &["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"];
+
+/// The environment variables from which `reqwest` reads proxy settings for `http` URLs, in order
+/// of precedence.
+const HTTP_PROXY_VARIABLES: &[&str] = &["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"];
+
+/// The environment variables from which `reqwest` reads the hosts that bypass the proxy.
+const NO_PROXY_VARIABLES: &[&str] = &["NO_PROXY", "no_proxy"];
+
+/// The registry key under which Windows records whether long paths are enabled.
+const FILESYSTEM_KEY: &str = r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem";
+
+/// The outcome of a single diagnostic check.
+#[derive(Debug)]
+enum Outcome {
+    /// The check passed.
+    Pass(String),
+    /// The check failed, along with a suggested fix.
+    Fail { message: String, fix: String },
+    /// The check found a problem that uv works around (e.g., at the cost of performance), along
+    /// with a suggested fix.
+    Warn { message: String, fix: String },
+    /// The check doesn't apply to this system or configuration.
+    Skip(String),
+}
+
+/// A named diagnostic check and its [`Outcome`].
+#[derive(Debug)]
+struct Diagnostic {
+    name: String,
+    outcome: Outcome,
+}
+
+impl Diagnostic {
+    fn new(name: impl Into<String>, outcome: Outcome) -> Self {
+        Self {
+            name: name.into(),
+            outcome,
+        }
+    }
+
+    /// Write the diagnostic, and the suggested fix if it failed.
+    fn write(&self, printer: Printer) -> Result<()> {
+        match &self.outcome {
+            Outcome::Pass(message) => {
+                writeln!(
+                    printer.stdout(),
+                    "{} {}: {message}",
+                    "ok".green().bold(),
+                    self.name.bold()
+                )?;
+            }
+            Outcome::Fail { message, fix } => {
+                writeln!(
+                    printer.stdout(),
+                    "{} {}: {message}",
+                    "error".red().bold(),
+                    self.name.bold()
+                )?;
+                writeln!(
+                    printer.stdout(),
+                    "  {}{} {fix}",
+                    "hint".bold().cyan(),
+                    ":".bold()
+                )?;
+            }
+            Outcome::Warn { message, fix } => {
+                writeln!(
+                    printer.stdout(),
+                    "{} {}: {message}",
+                    "warning".yellow().bold(),
+                    self.name.bold()
+                )?;
+                writeln!(
+                    printer.stdout(),
+                    "  {}{} {fix}",
+                    "hint".bold().cyan(),
+                    ":".bold()
+                )?;
+            }
+            Outcome::Skip(message) => {
+                writeln!(
+                    printer.stdout(),
+                    "{} {}: {message}",
+                    "skipped".dimmed(),
+                    self.name.bold()
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self.outcome, Outcome::Fail { .. })
+    }
+}
+
+/// Diagnose common problems with uv's environment: interpreter discovery, the cache directory and
+/// its support for hardlinks, the reachability of the configured indexes, the availability of the
+/// keyring, and (on Windows) support for long paths.
+///
+/// Each check is reported along with a suggested fix on failure; the command fails if any check
+/// fails. Problems that uv works around, like a lack of hardlink support, are reported as warnings.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn doctor(
+    python: Option<&str>,
+    venv_dir: Option<&Path>,
+    index_locations: &IndexLocations,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    cache: Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let mut diagnostics = Vec::new();
+
+    // Check the cache first, since interpreter discovery relies on it; if it isn't usable, fall
+    // back to a temporary cache for the remaining checks.
+    let cache = match check_cache(cache) {
+        Ok(cache) => {
+            diagnostics.push(Diagnostic::new(
+                "Cache directory",
+                Outcome::Pass(format!(
+                    "writable (`{}`)",
+                    cache.root().simplified_display()
+                )),
+            ));
+            cache
+        }
+        Err((root, err)) => {
+            diagnostics.push(Diagnostic::new(
+                "Cache directory",
+                Outcome::Fail {
+                    message: format!("`{}` is not writable ({err})", root.simplified_display()),
+                    fix: "Set `UV_CACHE_DIR` (or pass `--cache-dir`) to a writable directory"
+                        .to_string(),
+                },
+            ));
+            Cache::temp()?
+        }
+    };
+
+    let environment = match PythonEnvironment::find(python, SystemPython::Allowed, venv_dir, &cache)
+    {
+        Ok(environment) => {
+            let interpreter = environment.interpreter();
+            diagnostics.push(Diagnostic::new(
+                "Python interpreter",
+                Outcome::Pass(format!(
+                    "{} {} (`{}`)",
+                    interpreter.implementation_name(),
+                    interpreter.python_full_version(),
+                    interpreter.sys_executable().simplified_display()
+                )),
+            ));
+            Some(environment)
+        }
+        Err(err) => {
+            let fix = if python.is_some() {
+                "Check that the interpreter passed to `--python` (or `UV_PYTHON`) exists, or create a virtual environment with `uv venv`"
+            } else {
+                "Install Python and add it to the `PATH`, activate a virtual environment, or create one with `uv venv`"
+            };
+            diagnostics.push(Diagnostic::new(
+                "Python interpreter",
+                Outcome::Fail {
+                    message: err.to_string(),
+                    fix: fix.to_string(),
+                },
+            ));
+            None
+        }
+    };
+
+    diagnostics.push(Diagnostic::new(
+        "Hardlinks",
+        check_hardlinks(&cache, environment.as_ref()),
+    ));
+
+    // Check the reachability of each configured index.
+    let indexes = index_locations.indexes().collect::<Vec<_>>();
+    if indexes.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            "Indexes",
+            Outcome::Skip("no indexes are configured (`--no-index`)".to_string()),
+        ));
+    } else if connectivity == Connectivity::Offline {
+        for index in indexes {
+            diagnostics.push(Diagnostic::new(
+                format!("Index {index}"),
+                Outcome::Skip("network access is disabled (`--offline`)".to_string()),
+            ));
+        }
+    } else {
+        let client = RegistryClientBuilder::new(cache.clone())
+            .network_recorder(summary.network().clone())
+            .native_tls(native_tls)
+            .hermetic(hermetic)
+            .timeouts(timeouts)
+            .index_max_age(index_max_age)
+            .response_limits(response_limits)
+            .host_overrides(host_overrides.to_vec())
+            .trusted_hosts(trusted_hosts.to_vec())
+            .rate_limits(rate_limits.to_vec())
+            .http_version(http_version)
+            .connectivity(connectivity)
+            .keyring(keyring_provider)
+            .build();
+        for index in indexes {
+            diagnostics.push(Diagnostic::new(
+                format!("Index {index}"),
+                check_index(&client, index, native_tls).await,
+            ));
+        }
+    }
+
+    diagnostics.push(Diagnostic::new("Keyring", check_keyring(keyring_provider)));
+    diagnostics.push(Diagnostic::new("Long paths", check_long_paths()));
+
+    for diagnostic in &diagnostics {
+        diagnostic.write(printer)?;
+    }
+
+    if diagnostics.iter().any(Diagnostic::is_failure) {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// Initialize the cache, and verify that files can be written to it.
+fn check_cache(cache: Cache) -> Result<Cache, (PathBuf, std::io::Error)> {
+    let root = cache.root().to_path_buf();
+    let cache = cache.init().map_err(|err| (root.clone(), err))?;
+    tempfile::NamedTempFile::new_in(cache.root())
+        .and_then(|mut file| file.write_all(b"uv"))
+        .map_err(|err| (root, err))?;
+    Ok(cache)
+}
+
+/// Verify that files in the cache can be hardlinked into the virtual environment, as uv does when
+/// installing wheels.
+///
+/// If hardlinking fails, uv falls back to copying files, so a failure is reported as a warning.
+fn check_hardlinks(cache: &Cache, environment: Option<&PythonEnvironment>) -> Outcome {
+    let Some(target) = environment
+        .filter(|environment| environment.interpreter().is_virtualenv())
+        .map(PythonEnvironment::root)
+    else {
+        return Outcome::Skip("no virtual environment was found to link into".to_string());
+    };
+
+    match try_hardlink(cache.root(), target) {
+        Ok(()) => Outcome::Pass(format!(
+            "supported between the cache and `{}`",
+            target.simplified_display()
+        )),
+        Err(err) => Outcome::Warn {
+            message: format!(
+                "failed to hardlink from the cache into `{}` ({err}); uv will fall back to copying files, which is slower",
+                target.simplified_display()
+            ),
+            fix: "Move the cache onto the same filesystem as your environments with `UV_CACHE_DIR`, or pass `--link-mode copy` (or set `UV_LINK_MODE=copy`) to copy files without attempting to link them".to_string(),
+        },
+    }
+}
+
+/// Attempt to hardlink a temporary file in the `source` directory into the `target` directory.
+fn try_hardlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    let source = tempfile::NamedTempFile::new_in(source)?;
+    let target = tempfile::tempdir_in(target)?;
+    fs_err::hard_link(source.path(), target.path().join("link"))?;
+    Ok(())
+}
+
+/// Verify that an index serves the Simple API, reporting its latency.
+async fn check_index(client: &RegistryClient, index: &IndexUrl, native_tls: bool) -> Outcome {
+    let package = PackageName::new("pip".to_string()).expect("`pip` is a valid package name");
+    let report = client.check_index(index, &package).await;
+
+    let proxy = find_proxy(index.url());
+
+    if report.json.is_available() || report.html.is_available() {
+        let latency = if report.json.is_available() {
+            report.json.latency
+        } else {
+            report.html.latency
+        };
+        return Outcome::Pass(match proxy {
+            Some(variable) => format!(
+                "reachable via the proxy in `{variable}` ({})",
+                elapsed(latency)
+            ),
+            None => format!("reachable ({})", elapsed(latency)),
+        });
+    }
+
+    if report.requires_authentication() {
+        return Outcome::Fail {
+            message: "credentials were missing or rejected".to_string(),
+            fix: "Include credentials in the index URL, add them to a `.netrc` file, or pass `--keyring-provider subprocess` to read them from the keyring".to_string(),
+        };
+    }
+
+    match (&report.json.status, &report.html.status) {
+        (Err(err), _) | (_, Err(err)) => {
+            let fix = if !native_tls && err.contains("certificate") {
+                "If the index uses a certificate issued by a private authority (e.g., a corporate proxy), pass `--native-tls` to use the system's certificate store".to_string()
+            } else if let Some(variable) = proxy {
+                format!("Check the proxy configured in `{variable}`, or add the index host to `NO_PROXY` to bypass it")
+            } else {
+                "Check your network connection; if you're behind a proxy, set `HTTPS_PROXY` to its URL".to_string()
+            };
+            Outcome::Fail {
+                message: format!("unreachable ({err})"),
+                fix,
+            }
+        }
+        (Ok(status), _) => Outcome::Fail {
+            message: format!("does not serve the Simple API ({status})"),
+            fix: format!(
+                "Check that `{index}` is the URL of a Simple API index (e.g., it typically ends in `/simple`)"
+            ),
+        },
+    }
+}
+
+/// Return the environment variable that configures the proxy for the given URL, if any, mirroring
+/// how `reqwest` selects a proxy: by scheme, unless the host is excluded via `NO_PROXY`.
+fn find_proxy(url: &Url) -> Option<&'static str> {
+    let variables = match url.scheme() {
+        "https" => HTTPS_PROXY_VARIABLES,
+        "http" => HTTP_PROXY_VARIABLES,
+        _ => return None,
+    };
+    let variable = variables
+        .iter()
+        .find(|variable| std::env::var_os(variable).is_some_and(|value| !value.is_empty()))?;
+
+    let host = url.host_str()?;
+    let bypassed = NO_PROXY_VARIABLES
+        .iter()
+        .find_map(|variable| std::env::var(variable).ok())
+        .is_some_and(|no_proxy| is_no_proxy(host, &no_proxy));
+    if bypassed {
+        None
+    } else {
+        Some(variable)
+    }
+}
+
+/// Returns `true` if the host matches an entry in a `NO_PROXY` list, e.g., `localhost,.corp.com`.
+///
+/// An entry matches the host itself and any of its subdomains; `*` matches every host.
+fn is_no_proxy(host: &str, no_proxy: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    no_proxy
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            if entry == "*" {
+                return true;
+            }
+            let entry = entry.trim_start_matches('.');
+            host.eq_ignore_ascii_case(entry)
+                || host
+                    .len()
+                    .checked_sub(entry.len() + 1)
+                    .is_some_and(|start| {
+                        host.as_bytes()[start] == b'.'
+                            && host[start + 1..].eq_ignore_ascii_case(entry)
+                    })
+        })
+}
+
+/// Verify that the `keyring` command is available, if the keyring provider is enabled.
+fn check_keyring(keyring_provider: KeyringProviderType) -> Outcome {
+    match keyring_provider {
+        KeyringProviderType::Disabled => {
+            Outcome::Skip("the keyring provider is disabled (`--keyring-provider`)".to_string())
+        }
+        KeyringProviderType::Subprocess => {
+            match Command::new("keyring").arg("--list-backends").output() {
+                Ok(output) if output.status.success() => {
+                    Outcome::Pass("the `keyring` command is available".to_string())
+                }
+                Ok(output) => Outcome::Fail {
+                    message: format!(
+                        "`keyring --list-backends` failed with {}",
+                        output.status
+                    ),
+                    fix: "Run `keyring --list-backends` to diagnose the keyring installation"
+                        .to_string(),
+                },
+                Err(err) => Outcome::Fail {
+                    message: format!("failed to run the `keyring` command ({err})"),
+                    fix: "Install the `keyring` package, such that the `keyring` command is on the `PATH` (e.g., with `pipx install keyring`)".to_string(),
+                },
+            }
+        }
+    }
+}
+
+/// Verify that long paths are enabled on Windows, since package files can exceed 260 characters.
+fn check_long_paths() -> Outcome {
+    if !cfg!(windows) {
+        return Outcome::Skip("only applicable on Windows".to_string());
+    }
+
+    let enabled = Command::new("reg")
+        .arg("query")
+        .arg(FILESYSTEM_KEY)
+        .arg("/v")
+        .arg("LongPathsEnabled")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_long_paths_enabled(&String::from_utf8_lossy(&output.stdout)));
+
+    match enabled {
+        Some(true) => Outcome::Pass("enabled".to_string()),
+        Some(false) | None => Outcome::Fail {
+            message: "long paths are not enabled, so installing packages with deeply nested files may fail".to_string(),
+            fix: format!("From an administrator prompt, run `reg add {FILESYSTEM_KEY} /v LongPathsEnabled /t REG_DWORD /d 1 /f`"),
+        },
+    }
+}
+
+/// Parse the value of `LongPathsEnabled` from the output of `reg query`, e.g.:
+///
+/// ```text
+/// HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\FileSystem
+///     LongPathsEnabled    REG_DWORD    0x1
+/// ```
+fn parse_long_paths_enabled(stdout: &str) -> Option<bool> {
+    stdout.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next()? != "LongPathsEnabled" {
+            return None;
+        }
+        let value = fields.nth(1)?;
+        let value = u32::from_str_radix(value.strip_prefix("0x")?, 16).ok()?;
+        Some(value != 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_no_proxy, parse_long_paths_enabled};
+
+    #[test]
+    fn no_proxy() {
+        assert!(is_no_proxy("localhost", "localhost"));
+        assert!(is_no_proxy("pypi.corp.com", "localhost, corp.com"));
+        assert!(is_no_proxy("pypi.corp.com", ".corp.com"));
+        assert!(is_no_proxy("PyPI.Corp.com", "corp.com"));
+        assert!(is_no_proxy("pypi.org", "*"));
+        assert!(!is_no_proxy("pypi.org", "corp.com"));
+        assert!(!is_no_proxy("notcorp.com", "corp.com"));
+        assert!(!is_no_proxy("pypi.org", ""));
+    }
+
+    #[test]
+    fn long_paths_enabled() {
+        let stdout = "\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\r\n    LongPathsEnabled    REG_DWORD    0x1\r\n\r\n";
+        assert_eq!(parse_long_paths_enabled(stdout), Some(true));
+
+        let stdout = "\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\r\n    LongPathsEnabled    REG_DWORD    0x0\r\n\r\n";
+        assert_eq!(parse_long_paths_enabled(stdout), Some(false));
+
+        assert_eq!(parse_long_paths_enabled(""), None);
+    }
+}
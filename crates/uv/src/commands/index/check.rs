@@ -0,0 +1,130 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use distribution_types::IndexUrl;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, NetworkTimeouts, RegistryClientBuilder, ResponseLimits, SimpleApiProbe,
+};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, KeyringProviderType, TrustedHost,
+};
+use uv_normalize::PackageName;
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// Check an index for Simple API availability, metadata and range request support, authentication
+/// requirements, and latency.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn check(
+    index: &IndexUrl,
+    package: &PackageName,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    cache: &Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let client = RegistryClientBuilder::new(cache.clone())
+        .network_recorder(summary.network().clone())
+        .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .index_max_age(index_max_age)
+        .response_limits(response_limits)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
+        .connectivity(connectivity)
+        .keyring(keyring_provider)
+        .build();
+    let report = client.check_index(index, package).await;
+
+    writeln!(printer.stdout(), "{}", "Index:".bold())?;
+    writeln!(printer.stdout(), "  URL: {index}")?;
+    writeln!(printer.stdout(), "  Project page: {}", report.url)?;
+    writeln!(
+        printer.stdout(),
+        "  Authentication: {}",
+        if report.requires_authentication() {
+            "required (credentials were missing or rejected)"
+                .yellow()
+                .to_string()
+        } else {
+            "not required".to_string()
+        }
+    )?;
+
+    writeln!(printer.stdout())?;
+    writeln!(printer.stdout(), "{}", "Simple API:".bold())?;
+    write_probe(printer, "JSON (PEP 691)", &report.json)?;
+    write_probe(printer, "HTML (PEP 503)", &report.html)?;
+
+    writeln!(printer.stdout())?;
+    writeln!(printer.stdout(), "{}", "Wheels:".bold())?;
+    if let Some(wheel) = &report.wheel {
+        writeln!(printer.stdout(), "  Probed: {}", wheel.filename)?;
+        let metadata = match &wheel.metadata_status {
+            None => "not supported".yellow().to_string(),
+            Some(Ok(status)) if status.is_success() => "supported".green().to_string(),
+            Some(Ok(status)) => format!("advertised, but unavailable ({status})")
+                .yellow()
+                .to_string(),
+            Some(Err(err)) => format!("advertised, but unavailable ({err})")
+                .yellow()
+                .to_string(),
+        };
+        writeln!(printer.stdout(), "  Metadata (PEP 658): {metadata}")?;
+        let range_requests = match &wheel.range_requests {
+            Ok(true) => "supported".green().to_string(),
+            Ok(false) => "not supported".yellow().to_string(),
+            Err(err) => format!("unknown ({err})").yellow().to_string(),
+        };
+        writeln!(printer.stdout(), "  Range requests: {range_requests}")?;
+    } else {
+        writeln!(
+            printer.stdout(),
+            "  No wheels found for `{package}`; use `--package` to probe a different package"
+        )?;
+    }
+
+    if report.json.is_available() || report.html.is_available() {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Write the result of requesting a project page in a given format.
+fn write_probe(printer: Printer, format: &str, probe: &SimpleApiProbe) -> Result<()> {
+    let latency = elapsed(probe.latency);
+    let result = match &probe.status {
+        Ok(status) if probe.is_available() => format!(
+            "{} ({status}, {} files, {latency})",
+            "available".green(),
+            probe.files.unwrap_or_default()
+        ),
+        Ok(status) => format!(
+            "{} ({status}, {}, {latency})",
+            "unavailable".yellow(),
+            probe.content_type.as_deref().unwrap_or("no content type")
+        ),
+        Err(err) => format!("{} ({err}, {latency})", "unavailable".red()),
+    };
+    writeln!(printer.stdout(), "  {format}: {result}")?;
+    Ok(())
+}
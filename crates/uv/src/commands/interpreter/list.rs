@@ -0,0 +1,128 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::{find_interpreters, PythonEnvironment, SourceSelector, SystemPython};
+
+use crate::commands::{ExitStatus, InterpreterListFormat};
+use crate::printer::Printer;
+
+/// A discovered Python interpreter, as reported by `uv interpreter list`.
+#[derive(Debug, Serialize)]
+struct InterpreterEntry {
+    implementation: String,
+    version: String,
+    architecture: String,
+    path: PathBuf,
+    source: String,
+    virtualenv: bool,
+    /// Whether this is the interpreter uv would select for the request.
+    selected: bool,
+}
+
+/// List the Python interpreters that can be discovered, along with the source of each.
+///
+/// Interpreter metadata is read from the cache when the executable is unchanged; with
+/// `--refresh`, every interpreter is probed anew and the cache is repopulated.
+///
+/// The interpreter that uv would select for the given request (e.g., `3.11`), or by default if
+/// none is given, is marked.
+pub(crate) fn list(
+    request: Option<&str>,
+    format: InterpreterListFormat,
+    venv_dir: Option<&Path>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let selected = match PythonEnvironment::find(request, SystemPython::Allowed, venv_dir, cache) {
+        // The selected interpreter may be found under a different name (e.g., `python3.11` rather
+        // than `python3`), so compare canonical paths.
+        Ok(environment) => {
+            uv_fs::canonicalize_executable(environment.interpreter().sys_executable()).ok()
+        }
+        Err(err) => {
+            debug!("No interpreter would be selected: {err}");
+            None
+        }
+    };
+
+    let mut seen = FxHashSet::default();
+    let mut entries = Vec::new();
+    for result in find_interpreters(SystemPython::Allowed, &SourceSelector::All, venv_dir, cache) {
+        let (source, interpreter) = match result {
+            Ok(found) => found,
+            Err(err) => {
+                debug!("Skipping interpreter: {err}");
+                continue;
+            }
+        };
+
+        // The same interpreter may be discovered from multiple sources; report the first.
+        if !seen.insert(interpreter.sys_executable().to_path_buf()) {
+            continue;
+        }
+
+        // Mark only the first entry for the selected interpreter, as discovery may find several
+        // names for the same executable.
+        let is_selected = selected.is_some()
+            && !entries
+                .iter()
+                .any(|entry: &InterpreterEntry| entry.selected)
+            && uv_fs::canonicalize_executable(interpreter.sys_executable()).ok() == selected;
+
+        entries.push(InterpreterEntry {
+            implementation: interpreter.implementation_name().to_string(),
+            version: interpreter.python_full_version().to_string(),
+            architecture: interpreter.platform().arch().to_string(),
+            path: interpreter.sys_executable().to_path_buf(),
+            source: source.to_string(),
+            virtualenv: interpreter.is_virtualenv(),
+            selected: is_selected,
+        });
+    }
+
+    if matches!(format, InterpreterListFormat::Json) {
+        writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&entries)?
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if entries.is_empty() {
+        writeln!(printer.stderr(), "No Python interpreters found")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for entry in &entries {
+        writeln!(
+            printer.stdout(),
+            "{} {}-{} {} {} {}",
+            if entry.selected { "*" } else { " " },
+            entry.implementation,
+            entry.version,
+            entry.architecture,
+            entry.path.simplified_display().cyan(),
+            format!("({})", entry.source).dimmed()
+        )?;
+    }
+
+    if let Some(request) = request {
+        if !entries.iter().any(|entry| entry.selected) {
+            writeln!(
+                printer.stderr(),
+                "No discovered interpreter satisfies `{request}`"
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,78 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::{PythonEnvironment, PythonVersionFile, SystemPython, PYTHON_VERSION_FILENAME};
+use uv_requirements::ProjectWorkspace;
+use uv_warnings::warn_user;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Pin the Python interpreter for the current directory, or show the current pin.
+///
+/// The pin is written to a `.python-version` file at the root of the current workspace, where the
+/// project commands look for it, or to the current directory outside of a workspace. If `resolved`
+/// is set, the exact version and implementation of the interpreter that satisfies the request is
+/// pinned instead of the request itself.
+pub(crate) fn pin(
+    request: Option<&str>,
+    resolved: bool,
+    venv_dir: Option<&Path>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let current_dir = std::env::current_dir()?;
+
+    let Some(request) = request else {
+        let Some(version_file) = PythonVersionFile::discover(&current_dir)? else {
+            bail!("No pinned Python interpreter found (i.e., no `{PYTHON_VERSION_FILENAME}` file with a supported version in the current directory or its parents)");
+        };
+        writeln!(printer.stdout(), "{}", version_file.request())?;
+        return Ok(ExitStatus::Success);
+    };
+
+    let pin = match PythonEnvironment::find(Some(request), SystemPython::Allowed, venv_dir, cache) {
+        Ok(environment) if resolved => {
+            let interpreter = environment.interpreter();
+            let mut pin = format!(
+                "{}@{}",
+                interpreter.implementation_name(),
+                interpreter.python_full_version()
+            );
+            // Retain the architecture if it differs from uv's own, e.g., under Rosetta 2.
+            if interpreter.is_foreign_arch() {
+                pin.push_str(&format!("-{}", interpreter.arch()));
+            }
+            pin
+        }
+        Ok(_) => request.to_string(),
+        Err(err) if resolved => return Err(err.into()),
+        Err(err) => {
+            warn_user!("No interpreter found for `{request}`, but pinning it anyway: {err}");
+            request.to_string()
+        }
+    };
+
+    let directory = match ProjectWorkspace::discover(&current_dir) {
+        Ok(project) => project.workspace().root().clone(),
+        Err(err) => {
+            debug!("No workspace found ({err}); pinning in the current directory");
+            current_dir
+        }
+    };
+    let version_file = PythonVersionFile::write(&directory, &pin)?;
+    writeln!(
+        printer.stderr(),
+        "Pinned `{}` to `{}`",
+        version_file.request().cyan(),
+        version_file.path().user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
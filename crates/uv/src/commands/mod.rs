@@ -1,21 +1,36 @@
+use std::path::Path;
 use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 
+pub(crate) use cache_builds_list::cache_builds_list;
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
 pub(crate) use cache_prune::cache_prune;
-use distribution_types::InstalledMetadata;
+pub(crate) use cache_warm::cache_warm;
+use distribution_types::{CachedDist, InstalledMetadata};
+pub(crate) use doctor::doctor;
+pub(crate) use index::check::check as index_check;
+pub(crate) use interpreter::list::list as interpreter_list;
+pub(crate) use interpreter::pin::pin as interpreter_pin;
 pub(crate) use pip::check::pip_check;
-pub(crate) use pip::compile::{extra_name_with_clap_error, pip_compile};
+pub(crate) use pip::compile::{
+    extra_name_with_clap_error, pip_compile, CompileGroup, CompileMatrix, SharedIndexes,
+};
+pub(crate) use pip::format::pip_format;
 pub(crate) use pip::freeze::pip_freeze;
+pub(crate) use pip::hash::pip_hash;
 pub(crate) use pip::install::pip_install;
+pub(crate) use pip::lint::pip_lint;
 pub(crate) use pip::list::pip_list;
 pub(crate) use pip::show::pip_show;
 pub(crate) use pip::sync::pip_sync;
 pub(crate) use pip::uninstall::pip_uninstall;
+pub(crate) use pip::why::pip_why;
+pub(crate) use project::export::export;
 pub(crate) use project::lock::lock;
 pub(crate) use project::run::run;
 pub(crate) use project::sync::sync;
@@ -23,18 +38,31 @@ pub(crate) use project::sync::sync;
 pub(crate) use self_update::self_update;
 pub(crate) use tool::run::run as run_tool;
 use uv_cache::Cache;
+use uv_configuration::CompileOptions;
 use uv_fs::Simplified;
-use uv_installer::compile_tree;
+use uv_installer::{check_imports, compile_tree};
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user;
 pub(crate) use venv::venv;
+pub(crate) use venv_configure::venv_configure;
+pub(crate) use venv_list::{target_environments, venv_list};
+pub(crate) use venv_repair::venv_repair;
 pub(crate) use version::version;
+pub(crate) use wheel::inspect::inspect as wheel_inspect;
+pub(crate) use wheel::retag::{audit as wheel_audit, retag as wheel_retag};
 
+use crate::messages::Message;
 use crate::printer::Printer;
 
+mod cache_builds_list;
 mod cache_clean;
 mod cache_dir;
 mod cache_prune;
+mod cache_warm;
+mod doctor;
+mod index;
+mod interpreter;
 mod pip;
 mod project;
 pub(crate) mod reporters;
@@ -43,7 +71,11 @@ mod tool;
 #[cfg(feature = "self-update")]
 mod self_update;
 mod venv;
+mod venv_configure;
+mod venv_list;
+mod venv_repair;
 mod version;
+mod wheel;
 
 #[derive(Copy, Clone)]
 pub(crate) enum ExitStatus {
@@ -55,6 +87,26 @@ pub(crate) enum ExitStatus {
 
     /// The command failed with an unexpected error.
     Error,
+
+    /// The requirements could not be resolved, as they conflict with one another.
+    ResolutionConflict,
+
+    /// A network request failed (e.g., due to a DNS, TLS, or timeout failure), or the network
+    /// was required but disabled (e.g., with `--offline`).
+    NetworkFailure,
+
+    /// A distribution didn't match its expected hashes, or was missing hashes when hash-checking
+    /// is enabled.
+    HashMismatch,
+
+    /// A source distribution failed to build.
+    BuildFailure,
+
+    /// A lock on an environment or cache entry could not be acquired within `UV_LOCK_TIMEOUT`.
+    EnvironmentLocked,
+
+    /// The command was interrupted (e.g., by Ctrl-C).
+    Interrupted,
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -63,12 +115,18 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => Self::from(0),
             ExitStatus::Failure => Self::from(1),
             ExitStatus::Error => Self::from(2),
+            ExitStatus::ResolutionConflict => Self::from(3),
+            ExitStatus::NetworkFailure => Self::from(4),
+            ExitStatus::HashMismatch => Self::from(5),
+            ExitStatus::BuildFailure => Self::from(6),
+            ExitStatus::EnvironmentLocked => Self::from(7),
+            ExitStatus::Interrupted => Self::from(130),
         }
     }
 }
 
 /// Format a duration as a human-readable string, Cargo-style.
-pub(super) fn elapsed(duration: Duration) -> String {
+pub(crate) fn elapsed(duration: Duration) -> String {
     let secs = duration.as_secs();
     let ms = duration.subsec_millis();
 
@@ -110,6 +168,23 @@ pub(crate) enum VersionFormat {
     Json,
 }
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    /// Export the lockfile as a sequence of Dockerfile `RUN` instructions, each installing a
+    /// hash-pinned layer of packages, ordered from the least to the most likely to change.
+    Docker,
+}
+
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum InterpreterListFormat {
+    /// Display the list of interpreters in a human-readable format, with one interpreter per
+    /// line.
+    #[default]
+    Text,
+    /// Display the list of interpreters in a machine-readable JSON format.
+    Json,
+}
+
 #[derive(Debug, Default, Clone, clap::ValueEnum)]
 pub(crate) enum ListFormat {
     /// Display the list of packages in a human-readable table.
@@ -125,16 +200,21 @@ pub(crate) enum ListFormat {
 /// Compile all Python source files in site-packages to bytecode, to speed up the
 /// initial run of any subsequent executions.
 ///
+/// Files that fail to compile (e.g., vendored Python 2 code) are reported, but don't abort the
+/// compilation.
+///
 /// See the `--compile` option on `pip sync` and `pip install`.
 pub(super) async fn compile_bytecode(
     venv: &PythonEnvironment,
+    options: &CompileOptions,
     cache: &Cache,
     printer: Printer,
 ) -> anyhow::Result<()> {
     let start = std::time::Instant::now();
     let mut files = 0;
+    let mut failures = Vec::new();
     for site_packages in venv.site_packages() {
-        files += compile_tree(site_packages, venv.python_executable(), cache.root())
+        let summary = compile_tree(site_packages, venv.python_executable(), cache, options)
             .await
             .with_context(|| {
                 format!(
@@ -142,6 +222,12 @@ pub(super) async fn compile_bytecode(
                     site_packages.user_display()
                 )
             })?;
+        files += summary.files;
+        failures.extend(summary.failures.into_iter().map(|path| {
+            path.strip_prefix(site_packages)
+                .map(Path::to_path_buf)
+                .unwrap_or(path)
+        }));
     }
     let s = if files == 1 { "" } else { "s" };
     writeln!(
@@ -154,6 +240,64 @@ pub(super) async fn compile_bytecode(
         )
         .dimmed()
     )?;
+    if !failures.is_empty() {
+        let s = if failures.len() == 1 { "" } else { "s" };
+        warn_user!(
+            "Failed to compile {} file{s} to bytecode:\n{}",
+            failures.len(),
+            failures
+                .iter()
+                .map(|path| format!("  {}", path.user_display()))
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// Import the top-level modules of the installed distributions, each in a Python subprocess, and
+/// fail if any of them can't be imported.
+pub(super) async fn check_installed_imports(
+    venv: &PythonEnvironment,
+    wheels: &[CachedDist],
+    printer: Printer,
+) -> anyhow::Result<()> {
+    let start = std::time::Instant::now();
+    let failures = check_imports(wheels, venv)
+        .await
+        .context("Failed to check the imports of the installed packages")?;
+    writeln!(
+        printer.stderr(),
+        "{}",
+        printer
+            .messages()
+            .format(
+                Message::CheckedImports,
+                &[
+                    (
+                        "packages",
+                        &printer.messages().packages(wheels.len()).bold()
+                    ),
+                    ("elapsed", &elapsed(start.elapsed())),
+                ]
+            )
+            .dimmed()
+    )?;
+    if !failures.is_empty() {
+        let s = if failures.len() == 1 { "" } else { "s" };
+        anyhow::bail!(
+            "Failed to import {} module{s} after installation:\n{}",
+            failures.len(),
+            failures
+                .iter()
+                .map(|failure| format!(
+                    "  {} ({}): {}",
+                    failure.module.bold(),
+                    failure.package,
+                    failure.message
+                ))
+                .join("\n")
+        );
+    }
     Ok(())
 }
 
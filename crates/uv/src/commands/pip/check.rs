@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 use std::time::Instant;
 
 use anyhow::Result;
@@ -12,12 +13,14 @@ use uv_installer::{SitePackages, SitePackagesDiagnostic};
 use uv_interpreter::{PythonEnvironment, SystemPython};
 
 use crate::commands::{elapsed, ExitStatus};
+use crate::messages::Message;
 use crate::printer::Printer;
 
 /// Check for incompatibilities in installed packages.
 pub(crate) fn pip_check(
     python: Option<&str>,
     system: bool,
+    venv_dir: Option<&Path>,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -29,7 +32,7 @@ pub(crate) fn pip_check(
     } else {
         SystemPython::Allowed
     };
-    let venv = PythonEnvironment::find(python, system, cache)?;
+    let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -41,16 +44,22 @@ pub(crate) fn pip_check(
     let site_packages = SitePackages::from_executable(&venv)?;
     let packages: Vec<&InstalledDist> = site_packages.iter().collect();
 
-    let s = if packages.len() == 1 { "" } else { "s" };
     writeln!(
         printer.stderr(),
         "{}",
-        format!(
-            "Checked {} in {}",
-            format!("{} package{}", packages.len(), s).bold(),
-            elapsed(start.elapsed())
-        )
-        .dimmed()
+        printer
+            .messages()
+            .format(
+                Message::Checked,
+                &[
+                    (
+                        "packages",
+                        &printer.messages().packages(packages.len()).bold()
+                    ),
+                    ("elapsed", &elapsed(start.elapsed())),
+                ]
+            )
+            .dimmed()
     )?;
 
     let diagnostics: Vec<SitePackagesDiagnostic> =
@@ -60,7 +69,10 @@ pub(crate) fn pip_check(
         writeln!(
             printer.stderr(),
             "{}",
-            "All installed packages are compatible".to_string().dimmed()
+            printer
+                .messages()
+                .format(Message::AllCompatible, &[])
+                .dimmed()
         )?;
 
         Ok(ExitStatus::Success)
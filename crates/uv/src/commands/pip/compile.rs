@@ -1,17 +1,19 @@
 use std::borrow::Cow;
 use std::env;
 use std::fmt::Write;
-use std::io::stdout;
-use std::ops::Deref;
-use std::path::Path;
+use std::io::{stdout, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Mutex;
 
-use anstream::{eprint, AutoStream, StripStream};
+use anstream::{AutoStream, StripStream};
 use anyhow::{anyhow, Context, Result};
+use console::{style, Term};
 use fs_err as fs;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
 use tempfile::tempdir_in;
 use tracing::debug;
 
@@ -19,44 +21,55 @@ use distribution_types::{
     IndexLocations, LocalEditable, LocalEditables, ParsedUrlError, SourceAnnotation,
     SourceAnnotations, Verbatim,
 };
-use distribution_types::{Requirement, Requirements};
+use distribution_types::{Requirement, RequirementSource, Requirements};
 use install_wheel_rs::linker::LinkMode;
+use pep440_rs::VersionSpecifiers;
 use platform_tags::Tags;
 use requirements_txt::EditableRequirement;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndexClient, NetworkTimeouts, RegistryClientBuilder,
+    ResponseLimits,
+};
 use uv_configuration::{
-    Concurrency, ConfigSettings, Constraints, IndexStrategy, NoBinary, NoBuild, Overrides,
-    PreviewMode, SetupPyStrategy, Upgrade,
+    BuildCommands, Concurrency, ConfigSettings, Constraints, HostOverride, HttpVersion,
+    IndexRateLimit, IndexStrategy, NoBinary, NoBuild, Overrides, PreferBinary, PreferSource,
+    PreviewMode, SetupPyStrategy, TrustedHost, Upgrade,
 };
-use uv_configuration::{KeyringProviderType, TargetTriple};
+use uv_configuration::{KeyringProviderType, PackagePatches, Patches, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
-use uv_installer::Downloader;
+use uv_installer::Preparer;
 use uv_interpreter::{
     find_best_interpreter, find_interpreter, InterpreterRequest, PythonEnvironment, SystemPython,
     VersionRequest,
 };
-use uv_interpreter::{PythonVersion, SourceSelector};
+use uv_interpreter::{PythonVersion, PythonVersionFile, SourceSelector};
 use uv_normalize::{ExtraName, PackageName};
 use uv_requirements::{
     upgrade::read_lockfile, ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver,
     RequirementsSource, RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
-    AnnotationStyle, BuiltEditableMetadata, DependencyMode, DisplayResolutionGraph, ExcludeNewer,
-    Exclusions, FlatIndex, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode,
-    PythonRequirement, ResolutionMode, Resolver,
+    simplify_markers, Amendment, AnnotationStyle, BuiltEditableMetadata, DependencyMode,
+    DisplayDependencyGraph, DisplayMarkers, DisplayResolutionGraph, ExcludeNewer, Exclusions,
+    FlatIndex, GraphFormat, InMemoryIndex, Manifest, OptionsBuilder, PackagePolicy, PreReleaseMode,
+    PrefetchStrategy, PythonRequirement, ResolutionLimits, ResolutionMode, Resolver,
 };
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
 use uv_warnings::warn_user;
 
+use crate::cancellation::{self, Cancellable};
 use crate::commands::pip::operations;
-use crate::commands::reporters::{DownloadReporter, ResolverReporter};
+use crate::commands::reporters::{PrepareReporter, ResolverReporter};
 use crate::commands::{elapsed, ExitStatus};
+use crate::errors::report_no_solution;
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::signature;
+use crate::summary::{Packages, Summary};
 
 /// Resolve a set of requirements into a set of pinned versions.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -72,37 +85,69 @@ pub(crate) async fn pip_compile(
     upgrade: Upgrade,
     generate_hashes: bool,
     no_emit_packages: Vec<PackageName>,
+    unsafe_packages: Vec<PackageName>,
+    allow_unsafe: bool,
     include_extras: bool,
+    include_markers: bool,
     include_annotations: bool,
     include_header: bool,
     custom_compile_command: Option<String>,
+    custom_header: Option<String>,
+    sign_with: Option<&Path>,
     include_index_url: bool,
     include_find_links: bool,
     include_marker_expression: bool,
     include_index_annotation: bool,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
+    check_dependency_confusion: bool,
+    dependency_confusion_allow: &[PackageName],
+    check_metadata_consistency: bool,
     keyring_provider: KeyringProviderType,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
     connectivity: Connectivity,
     no_build_isolation: bool,
     no_build: NoBuild,
+    prefer_source: PreferSource,
+    prefer_binary: PreferBinary,
+    max_build_depth: Option<usize>,
+    build_commands: BuildCommands,
+    patches: &PackagePatches,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
     exclude_newer: Option<ExcludeNewer>,
+    package_policy: PackagePolicy,
+    resolution_limits: ResolutionLimits,
+    prefetch: PrefetchStrategy,
     annotation_style: AnnotationStyle,
     link_mode: LinkMode,
     python: Option<String>,
     system: bool,
+    venv_dir: Option<&Path>,
     concurrency: Concurrency,
+    indexes: &SharedIndexes,
     uv_lock: bool,
+    interactive: bool,
+    graph: Option<GraphFormat>,
     native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    find_links_recursive: bool,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
     quiet: bool,
     preview: PreviewMode,
     cache: Cache,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    let messages = printer.messages();
+
     let start = std::time::Instant::now();
 
     // If the user requests `extras` but does not provide a valid source (e.g., a `pyproject.toml`),
@@ -113,20 +158,35 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // Signing requires an output file (or a lockfile) to embed the signature in.
+    if sign_with.is_some() && output_file.is_none() && !uv_lock {
+        return Err(anyhow!(
+            "Signing the output requires an output file (`--output-file`)"
+        ));
+    }
+
     let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .keyring(keyring_provider);
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
-        project,
+        mut project,
         requirements,
         constraints,
+        constraint_hashes: _,
         overrides,
         editables,
         source_trees,
-        extras: used_extras,
+        extras: mut used_extras,
         index_url,
         extra_index_urls,
         no_index,
@@ -141,26 +201,13 @@ pub(crate) async fn pip_compile(
         &client_builder,
         preview,
     )
-    .await?;
+    .await?
+    .with_patches(&Patches::from_patches(patches)?);
 
     // If all the metadata could be statically resolved, validate that every extra was used. If we
-    // need to resolve metadata via PEP 517, we don't know which extras are used until much later.
+    // need to resolve metadata via PEP 517, the extras are validated once the metadata is built.
     if source_trees.is_empty() {
-        if let ExtrasSpecification::Some(extras) = &extras {
-            let mut unused_extras = extras
-                .iter()
-                .filter(|extra| !used_extras.contains(extra))
-                .collect::<Vec<_>>();
-            if !unused_extras.is_empty() {
-                unused_extras.sort_unstable();
-                unused_extras.dedup();
-                let s = if unused_extras.len() == 1 { "" } else { "s" };
-                return Err(anyhow!(
-                    "Requested extra{s} not found: {}",
-                    unused_extras.iter().join(", ")
-                ));
-            }
-        }
+        operations::validate_extras(&extras, &used_extras)?;
     }
 
     // Find an interpreter to use for building distributions
@@ -172,21 +219,25 @@ pub(crate) async fn pip_compile(
     let interpreter = if let Some(python) = python.as_ref() {
         let request = InterpreterRequest::parse(python);
         let sources = SourceSelector::from_settings(system);
-        find_interpreter(&request, system, &sources, &cache)??
+        find_interpreter(&request, system, &sources, venv_dir, &cache)??
     } else {
         let request = if let Some(version) = python_version.as_ref() {
             // TODO(zanieb): We should consolidate `VersionRequest` and `PythonVersion`
             InterpreterRequest::Version(VersionRequest::from(version))
+        } else if let Some(version_file) = PythonVersionFile::discover(&std::env::current_dir()?)? {
+            // Respect the pinned interpreter (i.e., `.python-version`).
+            version_file.interpreter_request()
         } else {
             InterpreterRequest::default()
         };
-        find_best_interpreter(&request, system, &cache)??
+        find_best_interpreter(&request, system, venv_dir, &cache)??
     }
     .into_interpreter();
 
     debug!(
-        "Using Python {} interpreter at {} for builds",
+        "Using Python {} ({}) interpreter at {} for builds",
         interpreter.python_version(),
+        interpreter.arch(),
         interpreter.sys_executable().user_display().cyan()
     );
 
@@ -210,16 +261,20 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    // Create a shared in-memory index.
-    let source_index = InMemoryIndex::default();
-
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
-        InMemoryIndexRef::Owned(InMemoryIndex::default())
+    // Create a shared in-memory index, reusing any index from a concurrent resolution that
+    // builds against the same interpreter.
+    let source_index = indexes.get(format!("{}", interpreter.sys_executable().user_display()));
+
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
+        indexes.get(format!(
+            "{} ({python_version:?}, {python_platform:?})",
+            interpreter.sys_executable().user_display(),
+        ))
     } else {
-        InMemoryIndexRef::Borrowed(&source_index)
+        source_index.clone()
     };
 
     // Determine the tags, markers, and interpreter to use for resolution.
@@ -268,9 +323,10 @@ pub(crate) async fn pip_compile(
     // pyproject.toml).
     let python_requirement = PythonRequirement::from_marker_environment(&interpreter, &markers);
 
-    // Generate, but don't enforce hashes for the requirements.
+    // Generate, but don't enforce hashes for the requirements. Collect the hashes of any artifacts
+    // fetched during resolution, to report digests for distributions that lack them on the index.
     let hasher = if generate_hashes {
-        HashStrategy::Generate
+        HashStrategy::Collect
     } else {
         HashStrategy::None
     };
@@ -286,7 +342,16 @@ pub(crate) async fn pip_compile(
 
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
+        .network_recorder(summary.network().clone())
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .index_max_age(index_max_age)
+        .response_limits(response_limits)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -300,7 +365,7 @@ pub(crate) async fn pip_compile(
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
+        let client = FlatIndexClient::new(&client, &cache).recursive(find_links_recursive);
         let entries = client.fetch(index_locations.flat_index()).await?;
         FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &NoBinary::None)
     };
@@ -336,7 +401,12 @@ pub(crate) async fn pip_compile(
         &NoBinary::None,
         concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_counter(summary.builds().clone())
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_prefer_source(prefer_source)
+    .with_prefer_binary(prefer_binary)
+    .with_max_build_depth(max_build_depth)
+    .with_build_commands(build_commands);
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -351,20 +421,28 @@ pub(crate) async fn pip_compile(
         .resolve()
         .await?;
 
-        // Resolve any source trees into requirements.
+        // Resolve any source trees into requirements, building their metadata if necessary.
         if !source_trees.is_empty() {
-            requirements.extend(
-                SourceTreeResolver::new(
-                    source_trees,
-                    &extras,
-                    &hasher,
-                    &top_level_index,
-                    DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
-                )
-                .with_reporter(ResolverReporter::from(printer))
-                .resolve()
-                .await?,
-            );
+            let resolutions = SourceTreeResolver::new(
+                source_trees,
+                &extras,
+                &hasher,
+                &top_level_index,
+                DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
+            )
+            .with_reporter(ResolverReporter::from(printer))
+            .resolve()
+            .await?;
+
+            // Now that the metadata is known, validate that every extra was used.
+            for resolution in resolutions {
+                if project.is_none() {
+                    project = Some(resolution.project);
+                }
+                used_extras.extend(resolution.extras);
+                requirements.extend(resolution.requirements);
+            }
+            operations::validate_extras(&extras, &used_extras)?;
         }
 
         requirements
@@ -429,10 +507,6 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    // Collect constraints and overrides.
-    let constraints = Constraints::from_requirements(constraints);
-    let overrides = Overrides::from_requirements(overrides);
-
     // Build the editables and add their requirements
     let editables = if editables.is_empty() {
         Vec::new()
@@ -450,20 +524,25 @@ pub(crate) async fn pip_compile(
             LocalEditable { url, path, extras }
         }));
 
-        let downloader = Downloader::new(
+        let preparer = Preparer::new(
             &cache,
             &tags,
             &hasher,
             DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
         )
-        .with_reporter(DownloadReporter::from(printer).with_length(editables.len() as u64));
+        .with_reporter(PrepareReporter::from(printer).with_length(editables.len() as u64));
 
         // Build all editables.
         let editable_wheel_dir = tempdir_in(cache.root())?;
-        let editables: Vec<BuiltEditableMetadata> = downloader
+        let editables = preparer
             .build_editables(editables, editable_wheel_dir.path())
             .await
-            .context("Failed to build editables")?
+            .context("Failed to build editables")?;
+
+        // Editables that were reused from the cache weren't built.
+        let built = editables.iter().filter(|editable| !editable.cached).count();
+
+        let editables: Vec<BuiltEditableMetadata> = editables
             .into_iter()
             .map(|built_editable| {
                 let requirements = Requirements {
@@ -498,136 +577,230 @@ pub(crate) async fn pip_compile(
             }
         }
 
-        let s = if editables.len() == 1 { "" } else { "s" };
-        writeln!(
-            printer.stderr(),
-            "{}",
-            format!(
-                "Built {} in {}",
-                format!("{} editable{}", editables.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
-        )?;
+        if built > 0 {
+            let s = if built == 1 { "" } else { "s" };
+            writeln!(
+                printer.stderr(),
+                "{}",
+                format!(
+                    "Built {} in {}",
+                    format!("{built} editable{s}").bold(),
+                    elapsed(start.elapsed())
+                )
+                .dimmed()
+            )?;
+        }
         editables
     };
 
-    // Determine any lookahead requirements.
-    let lookaheads = match dependency_mode {
-        DependencyMode::Transitive => {
-            LookaheadResolver::new(
-                &requirements,
-                &constraints,
-                &overrides,
-                &editables,
-                &hasher,
-                &top_level_index,
-                DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
-            )
-            .with_reporter(ResolverReporter::from(printer))
-            .resolve(marker_filter)
-            .await?
-        }
-        DependencyMode::Direct => Vec::new(),
-    };
+    // Only prompt for amendments if we can interact with the user.
+    let interactive =
+        if interactive && !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+            warn_user!("`--interactive` requires an interactive terminal; ignoring.");
+            false
+        } else {
+            interactive
+        };
 
-    // Create a manifest of the requirements.
-    let manifest = Manifest::new(
-        requirements,
-        constraints,
-        overrides,
-        preferences,
-        project,
-        editables,
-        // Do not consider any installed packages during resolution.
-        Exclusions::All,
-        lookaheads,
-    );
+    let mut requirements = requirements;
+    let mut constraints = constraints;
+    let mut excluded = Vec::new();
+    let mut prerelease_mode = prerelease_mode;
+    let mut amendments = Vec::new();
+
+    let (resolution, manifest) = loop {
+        // Collect constraints and overrides.
+        let collected_constraints = Constraints::from_requirements(constraints.clone());
+        let collected_overrides =
+            Overrides::from_requirements(overrides.clone()).with_exclusions(excluded.clone());
+
+        // Determine any lookahead requirements.
+        let lookaheads = match dependency_mode {
+            DependencyMode::Transitive => {
+                LookaheadResolver::new(
+                    &requirements,
+                    &collected_constraints,
+                    &collected_overrides,
+                    &editables,
+                    &hasher,
+                    &top_level_index,
+                    DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
+                )
+                .with_reporter(ResolverReporter::from(printer))
+                .resolve(marker_filter)
+                .await?
+            }
+            DependencyMode::Direct => Vec::new(),
+        };
 
-    let options = OptionsBuilder::new()
-        .resolution_mode(resolution_mode)
-        .prerelease_mode(prerelease_mode)
-        .dependency_mode(dependency_mode)
-        .exclude_newer(exclude_newer)
-        .index_strategy(index_strategy)
-        .build();
+        // Create a manifest of the requirements.
+        let manifest = Manifest::new(
+            requirements.clone(),
+            collected_constraints,
+            collected_overrides,
+            preferences.clone(),
+            project.clone(),
+            editables.clone(),
+            // Do not consider any installed packages during resolution.
+            Exclusions::All,
+            lookaheads,
+        )
+        .with_policy(package_policy.clone())
+        .with_prefetch(prefetch.clone());
+
+        let options = OptionsBuilder::new()
+            .resolution_mode(resolution_mode)
+            .prerelease_mode(prerelease_mode)
+            .dependency_mode(dependency_mode)
+            .exclude_newer(exclude_newer)
+            .index_strategy(index_strategy)
+            .limits(resolution_limits)
+            .build();
+
+        // Resolve the dependencies.
+        let resolver = Resolver::new(
+            manifest.clone(),
+            options,
+            &python_requirement,
+            marker_filter,
+            &tags,
+            &flat_index,
+            &top_level_index,
+            &hasher,
+            &build_dispatch,
+            EmptyInstalledPackages,
+            DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
+        )?
+        .with_reporter(ResolverReporter::from(printer))
+        .with_cancellation(cancellation::token());
 
-    // Resolve the dependencies.
-    let resolver = Resolver::new(
-        manifest.clone(),
-        options,
-        &python_requirement,
-        marker_filter,
-        &tags,
-        &flat_index,
-        &top_level_index,
-        &hasher,
-        &build_dispatch,
-        EmptyInstalledPackages,
-        DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
-    )?
-    .with_reporter(ResolverReporter::from(printer));
-
-    let resolution = match resolver.resolve().await {
-        Err(uv_resolver::ResolveError::NoSolution(err)) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+        let resolution = {
+            let _cancellable = Cancellable::enter();
+            resolver.resolve().await
+        };
+        match resolution {
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                report_no_solution(&err, preview, printer.output_format());
+
+                // If running interactively, offer to amend the inputs and try again.
+                let amendment = if interactive {
+                    prompt_amendment(&err.amendments(), &amendments)?
+                } else {
+                    None
+                };
+                let Some(amendment) = amendment else {
+                    return Ok(ExitStatus::ResolutionConflict);
+                };
+                amend(
+                    &amendment,
+                    &mut requirements,
+                    &mut constraints,
+                    &mut excluded,
+                    &mut prerelease_mode,
+                );
+                amendments.push(amendment);
+            }
+            result => break (result?, manifest),
         }
-        result => result,
-    }?;
+    };
 
-    let s = if resolution.len() == 1 { "" } else { "s" };
+    summary.record(Packages::Resolved, resolution.len());
     writeln!(
         printer.stderr(),
         "{}",
-        format!(
-            "Resolved {} in {}",
-            format!("{} package{}", resolution.len(), s).bold(),
-            elapsed(start.elapsed())
-        )
-        .dimmed()
+        messages
+            .format(
+                Message::Resolved,
+                &[
+                    ("packages", &messages.packages(resolution.len()).bold()),
+                    ("elapsed", &elapsed(start.elapsed())),
+                ]
+            )
+            .dimmed()
     )?;
 
     // Write the resolved dependencies to the output channel.
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
-    if include_header {
-        writeln!(
+    // If requested, write the dependency graph in lieu of a `requirements.txt` file.
+    if let Some(graph) = graph {
+        write!(
             writer,
             "{}",
-            "# This file was autogenerated by uv via the following command:".green()
+            DisplayDependencyGraph::new(&resolution, &requirements, graph)
         )?;
-        writeln!(
-            writer,
-            "{}",
-            format!(
-                "#    {}",
-                cmd(
-                    include_index_url,
-                    include_find_links,
-                    custom_compile_command
+        operations::diagnose_resolution(resolution.diagnostics(), printer)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if include_header {
+        if let Some(custom_header) = custom_header.as_deref() {
+            for line in custom_header.lines() {
+                let line = line.trim_end();
+                if line.starts_with('#') {
+                    writeln!(writer, "{}", line.green())?;
+                } else if line.is_empty() {
+                    writeln!(writer, "{}", "#".green())?;
+                } else {
+                    writeln!(writer, "{}", format!("# {line}").green())?;
+                }
+            }
+        } else {
+            writeln!(
+                writer,
+                "{}",
+                "# This file was autogenerated by uv via the following command:".green()
+            )?;
+            writeln!(
+                writer,
+                "{}",
+                format!(
+                    "#    {}",
+                    cmd(
+                        include_index_url,
+                        include_find_links,
+                        custom_compile_command
+                    )
                 )
-            )
-            .green()
-        )?;
+                .green()
+            )?;
+        }
+
+        // Record any amendments that were made interactively.
+        if !amendments.is_empty() {
+            writeln!(
+                writer,
+                "{}",
+                "# The following amendments were applied to resolve a conflict:".green()
+            )?;
+            for amendment in &amendments {
+                writeln!(writer, "{}", format!("#    {amendment}").green())?;
+            }
+        }
     }
 
     if include_marker_expression {
-        let relevant_markers = resolution.marker_tree(&manifest, &top_level_index, &markers)?;
+        let relevant_markers =
+            simplify_markers(resolution.marker_tree(&manifest, &top_level_index, &markers)?);
         writeln!(
             writer,
             "{}",
             "# Pinned dependencies known to be valid for:".green()
         )?;
-        writeln!(writer, "{}", format!("#    {relevant_markers}").green())?;
+        writeln!(
+            writer,
+            "{}",
+            format!("#    {}", DisplayMarkers(&relevant_markers)).green()
+        )?;
     }
 
     if uv_lock {
         let lock = resolution.lock()?;
         let encoded = toml::to_string_pretty(&lock)?;
         fs::tokio::write("uv.lock", encoded.as_bytes()).await?;
+        if let Some(key) = sign_with {
+            signature::sign(Path::new("uv.lock"), key)?;
+        }
     }
 
     // Write the index locations to the output channel.
@@ -658,22 +831,23 @@ pub(crate) async fn pip_compile(
         writeln!(writer)?;
     }
 
-    write!(
-        writer,
-        "{}",
-        DisplayResolutionGraph::new(
-            &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_extras,
-            include_annotations,
-            include_index_annotation,
-            annotation_style,
-            sources,
-        )
-    )?;
+    let mut display = DisplayResolutionGraph::new(
+        &resolution,
+        &no_emit_packages,
+        generate_hashes,
+        include_extras,
+        include_annotations,
+        include_index_annotation,
+        annotation_style,
+        sources,
+    )
+    .with_unsafe_packages(&unsafe_packages, allow_unsafe);
+    if include_markers {
+        display = display.with_markers(&requirements);
+    }
+    write!(writer, "{display}")?;
 
-    // If any "unsafe" packages were excluded, notify the user.
+    // If any packages were excluded via `--no-emit-package`, notify the user.
     let excluded = no_emit_packages
         .into_iter()
         .filter(|name| resolution.contains(name))
@@ -690,12 +864,120 @@ pub(crate) async fn pip_compile(
         }
     }
 
+    // Sign the output file, now that it's complete.
+    drop(writer);
+    if let (Some(key), Some(output_file)) = (sign_with, output_file) {
+        signature::sign(output_file, key)?;
+    }
+
     // Notify the user of any resolution diagnostics.
     operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
+    // If requested, warn about packages that are shadowed by a newer version on another index.
+    if check_dependency_confusion {
+        operations::diagnose_dependency_confusion(
+            resolution.distributions().map(|(dist, _)| dist),
+            dependency_confusion_allow,
+            &index_locations,
+            &client,
+        )
+        .await?;
+    }
+
+    // If requested, warn about packages whose source distribution and wheels disagree.
+    if check_metadata_consistency {
+        operations::diagnose_metadata_consistency(
+            resolution.distributions().map(|(dist, _)| dist),
+            &DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads),
+            &hasher,
+            &no_build,
+            &NoBinary::None,
+            concurrency.builds,
+            printer,
+        )
+        .await?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Prompt the user to choose one of the given [`Amendment`]s, skipping any that were already
+/// applied.
+///
+/// Returns `None` if there are no amendments to offer, or if the user chose to abort.
+fn prompt_amendment(amendments: &[Amendment], applied: &[Amendment]) -> Result<Option<Amendment>> {
+    let amendments = amendments
+        .iter()
+        .filter(|amendment| !applied.contains(amendment))
+        .collect::<Vec<_>>();
+    if amendments.is_empty() {
+        return Ok(None);
+    }
+
+    let term = Term::stderr();
+    term.write_line("")?;
+    term.write_line(&format!(
+        "{} {}",
+        style("?").for_stderr().yellow(),
+        style("Choose an amendment to retry the resolution with:")
+            .for_stderr()
+            .bold()
+    ))?;
+    for (index, amendment) in amendments.iter().enumerate() {
+        term.write_line(&format!(
+            "  {} {amendment}",
+            style(format!("{})", index + 1)).for_stderr().cyan()
+        ))?;
+    }
+    term.write_line(&format!("  {} abort", style("0)").for_stderr().cyan()))?;
+
+    loop {
+        term.write_str(&format!("{} ", style("›").for_stderr().black().bright()))?;
+        term.flush()?;
+        let input = term.read_line()?;
+        match input.trim().parse::<usize>() {
+            Ok(0) => return Ok(None),
+            Ok(index) if index <= amendments.len() => {
+                return Ok(Some(amendments[index - 1].clone()));
+            }
+            _ => {
+                term.write_line(&format!(
+                    "Enter a number between 0 and {}",
+                    amendments.len()
+                ))?;
+            }
+        }
+    }
+}
+
+/// Apply an [`Amendment`] to the inputs of a resolution.
+fn amend(
+    amendment: &Amendment,
+    requirements: &mut [Requirement],
+    constraints: &mut [Requirement],
+    excluded: &mut Vec<PackageName>,
+    prerelease_mode: &mut PreReleaseMode,
+) {
+    match amendment {
+        Amendment::RelaxRequirement(name) => {
+            // Drop the version specifiers from any requirements or constraints on the package.
+            for requirement in requirements.iter_mut().chain(constraints.iter_mut()) {
+                if requirement.name == *name {
+                    if let RequirementSource::Registry { specifier, .. } = &mut requirement.source {
+                        *specifier = VersionSpecifiers::empty();
+                    }
+                }
+            }
+        }
+        Amendment::AllowPreReleases => {
+            *prerelease_mode = PreReleaseMode::Allow;
+        }
+        Amendment::ExcludePackage(name) => {
+            excluded.push(name.clone());
+        }
+    }
+}
+
 /// Format the `uv` command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
 fn cmd(
@@ -764,6 +1046,22 @@ fn cmd(
                 return Some(None);
             }
 
+            // Always skip the `--interactive` flag, since any amendments are recorded separately.
+            if arg == "--interactive" {
+                *skip_next = None;
+                return Some(None);
+            }
+
+            // Always skip the `--timings` and `--trace-output` flags.
+            if arg == "--timings" || arg.starts_with("--trace-output=") {
+                *skip_next = None;
+                return Some(None);
+            }
+            if arg == "--trace-output" {
+                *skip_next = Some(true);
+                return Some(None);
+            }
+
             // Return the argument.
             Some(Some(arg))
         })
@@ -821,19 +1119,315 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
     })
 }
 
-/// An owned or unowned [`InMemoryIndex`].
-enum InMemoryIndexRef<'a> {
-    Owned(InMemoryIndex),
-    Borrowed(&'a InMemoryIndex),
+/// A set of [`InMemoryIndex`]es that can be shared across concurrent resolutions within a
+/// single invocation.
+///
+/// The package metadata in an index depends on the target tags and Python version, so
+/// resolutions only share an index if they target the same environment.
+#[derive(Default)]
+pub(crate) struct SharedIndexes(Mutex<FxHashMap<String, InMemoryIndex>>);
+
+impl SharedIndexes {
+    /// Return the index for the given target environment, creating it if necessary.
+    fn get(&self, target: String) -> InMemoryIndex {
+        self.0.lock().unwrap().entry(target).or_default().clone()
+    }
+}
+
+/// A requirement group to resolve alongside the others in a single `pip compile` invocation,
+/// e.g., one entry in a per-platform or per-Python-version matrix.
+///
+/// Specified as comma-separated `KEY=VALUE` pairs, as in
+/// `output-file=requirements-linux.txt,python-platform=linux,python-version=3.11`. Any of
+/// `python-version`, `python-platform`, `extra`, or `requirement` that are omitted are inherited
+/// from the command line. An empty `extra` (i.e., `extra=`) disables the extras provided on the
+/// command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompileGroup {
+    /// The file to which the resolution should be written.
+    pub(crate) output_file: PathBuf,
+    /// The Python version to resolve against.
+    pub(crate) python_version: Option<PythonVersion>,
+    /// The platform to resolve against.
+    pub(crate) python_platform: Option<TargetTriple>,
+    /// The extras to include, in lieu of those provided on the command line, or `None` to inherit
+    /// them. An empty list includes no extras.
+    pub(crate) extras: Option<Vec<ExtraName>>,
+    /// The `requirements.in` files to resolve, in lieu of those provided on the command line.
+    pub(crate) requirements: Vec<PathBuf>,
+}
+
+impl FromStr for CompileGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut output_file = None;
+        let mut python_version = None;
+        let mut python_platform = None;
+        let mut extras: Option<Vec<ExtraName>> = None;
+        let mut requirements = Vec::new();
+
+        for pair in s.split(',') {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!(
+                    "Invalid group entry `{pair}`; expected `KEY=VALUE` (e.g., `output-file=requirements.txt`)"
+                ));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "output-file" | "o" => output_file = Some(PathBuf::from(value)),
+                "python-version" => python_version = Some(PythonVersion::from_str(value)?),
+                "python-platform" => {
+                    python_platform = Some(
+                        <TargetTriple as clap::ValueEnum>::from_str(value, true)
+                            .map_err(|_| format!("Invalid Python platform `{value}`"))?,
+                    );
+                }
+                "extra" => {
+                    let extras = extras.get_or_insert_with(Vec::new);
+                    if !value.is_empty() {
+                        extras.push(
+                            ExtraName::from_str(value)
+                                .map_err(|err| format!("Invalid extra `{value}`: {err}"))?,
+                        );
+                    }
+                }
+                "requirement" | "r" => requirements.push(PathBuf::from(value)),
+                key => {
+                    return Err(format!(
+                        "Unknown group key `{key}`; expected one of `output-file`, `python-version`, `python-platform`, `extra`, or `requirement`"
+                    ));
+                }
+            }
+        }
+
+        let Some(output_file) = output_file else {
+            return Err(format!("Group `{s}` is missing an `output-file`"));
+        };
+
+        Ok(Self {
+            output_file,
+            python_version,
+            python_platform,
+            extras,
+            requirements,
+        })
+    }
+}
+
+/// A matrix of requirement groups to resolve in a single `pip compile` invocation, expanded to
+/// one [`CompileGroup`] per combination of Python version, platform, and extra.
+///
+/// Specified as comma-separated `KEY=VALUES` pairs, with the values for each axis separated by
+/// `|`, as in `python-version=3.11|3.12,python-platform=linux|windows,extra=|dev`. An empty
+/// `extra` represents the cell without any extras.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompileMatrix {
+    /// The Python versions to resolve against.
+    python_versions: Vec<PythonVersion>,
+    /// The platforms to resolve against, along with their names as given.
+    python_platforms: Vec<(String, TargetTriple)>,
+    /// The extras to include, if any, in each cell.
+    extras: Vec<Option<ExtraName>>,
+}
+
+impl CompileMatrix {
+    /// Expand the matrix into a [`CompileGroup`] for each cell.
+    ///
+    /// Each cell is written alongside the given output file (or `requirements.txt`), with a
+    /// suffix for each axis of the matrix, as in `requirements-py3.11-linux-dev.txt`.
+    pub(crate) fn expand(&self, output_file: Option<&Path>) -> Vec<CompileGroup> {
+        let output_file = output_file.unwrap_or(Path::new("requirements.txt"));
+        let stem = output_file
+            .file_stem()
+            .map_or(Cow::Borrowed("requirements"), |stem| stem.to_string_lossy());
+        let extension = output_file
+            .extension()
+            .map_or(Cow::Borrowed("txt"), |extension| {
+                extension.to_string_lossy()
+            });
+
+        // Treat any omitted axis as a single cell that inherits from the command line.
+        let python_versions = if self.python_versions.is_empty() {
+            vec![None]
+        } else {
+            self.python_versions.iter().map(Some).collect()
+        };
+        let python_platforms = if self.python_platforms.is_empty() {
+            vec![None]
+        } else {
+            self.python_platforms.iter().map(Some).collect()
+        };
+        let extras = if self.extras.is_empty() {
+            vec![None]
+        } else {
+            self.extras.iter().map(Some).collect()
+        };
+
+        let mut groups = Vec::new();
+        for python_version in &python_versions {
+            for python_platform in &python_platforms {
+                for extra in &extras {
+                    let mut name = stem.to_string();
+                    if let Some(python_version) = python_version {
+                        write!(name, "-py{python_version}").unwrap();
+                    }
+                    if let Some((platform, _)) = python_platform {
+                        write!(name, "-{platform}").unwrap();
+                    }
+                    if let Some(Some(extra)) = extra {
+                        write!(name, "-{extra}").unwrap();
+                    }
+                    groups.push(CompileGroup {
+                        output_file: output_file.with_file_name(format!("{name}.{extension}")),
+                        python_version: python_version.cloned(),
+                        python_platform: python_platform.map(|(_, platform)| *platform),
+                        extras: extra.map(|extra| extra.iter().cloned().collect()),
+                        requirements: Vec::new(),
+                    });
+                }
+            }
+        }
+        groups
+    }
 }
 
-impl Deref for InMemoryIndexRef<'_> {
-    type Target = InMemoryIndex;
+impl FromStr for CompileMatrix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut python_versions = Vec::new();
+        let mut python_platforms = Vec::new();
+        let mut extras = Vec::new();
 
-    fn deref(&self) -> &Self::Target {
-        match self {
-            Self::Owned(index) => index,
-            Self::Borrowed(index) => index,
+        for pair in s.split(',') {
+            let Some((key, values)) = pair.split_once('=') else {
+                return Err(format!(
+                    "Invalid matrix entry `{pair}`; expected `KEY=VALUES` (e.g., `python-version=3.11|3.12`)"
+                ));
+            };
+            let values = values.split('|').map(str::trim);
+            match key.trim() {
+                "python-version" => {
+                    for value in values {
+                        python_versions.push(PythonVersion::from_str(value)?);
+                    }
+                }
+                "python-platform" => {
+                    for value in values {
+                        let platform = <TargetTriple as clap::ValueEnum>::from_str(value, true)
+                            .map_err(|_| format!("Invalid Python platform `{value}`"))?;
+                        python_platforms.push((value.to_string(), platform));
+                    }
+                }
+                "extra" => {
+                    for value in values {
+                        extras.push(if value.is_empty() {
+                            None
+                        } else {
+                            Some(
+                                ExtraName::from_str(value)
+                                    .map_err(|err| format!("Invalid extra `{value}`: {err}"))?,
+                            )
+                        });
+                    }
+                }
+                key => {
+                    return Err(format!(
+                        "Unknown matrix key `{key}`; expected one of `python-version`, `python-platform`, or `extra`"
+                    ));
+                }
+            }
         }
+
+        Ok(Self {
+            python_versions,
+            python_platforms,
+            extras,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_compile_group() {
+        let group = CompileGroup::from_str(
+            "output-file=requirements-linux.txt,python-version=3.11,python-platform=linux,extra=dev,requirement=a.in,requirement=b.in",
+        )
+        .unwrap();
+        assert_eq!(group.output_file, PathBuf::from("requirements-linux.txt"));
+        assert_eq!(
+            group.python_version,
+            Some(PythonVersion::from_str("3.11").unwrap())
+        );
+        assert_eq!(group.python_platform, Some(TargetTriple::Linux));
+        assert_eq!(
+            group.extras,
+            Some(vec![ExtraName::from_str("dev").unwrap()])
+        );
+        assert_eq!(
+            group.requirements,
+            vec![PathBuf::from("a.in"), PathBuf::from("b.in")]
+        );
+
+        let group = CompileGroup::from_str("o=requirements.txt").unwrap();
+        assert_eq!(group.output_file, PathBuf::from("requirements.txt"));
+        assert_eq!(group.python_version, None);
+        assert_eq!(group.extras, None);
+        assert!(group.requirements.is_empty());
+
+        // An empty extra disables the extras provided on the command line.
+        let group = CompileGroup::from_str("o=requirements.txt,extra=").unwrap();
+        assert_eq!(group.extras, Some(Vec::new()));
+
+        assert!(CompileGroup::from_str("python-version=3.11").is_err());
+        assert!(CompileGroup::from_str("output-file=requirements.txt,color=blue").is_err());
+        assert!(CompileGroup::from_str("requirements.txt").is_err());
+    }
+
+    #[test]
+    fn expand_compile_matrix() {
+        let matrix =
+            CompileMatrix::from_str("python-version=3.11|3.12,python-platform=linux,extra=|dev")
+                .unwrap();
+        let groups = matrix.expand(Some(Path::new("locks/requirements.txt")));
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| group.output_file.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("locks/requirements-py3.11-linux.txt"),
+                PathBuf::from("locks/requirements-py3.11-linux-dev.txt"),
+                PathBuf::from("locks/requirements-py3.12-linux.txt"),
+                PathBuf::from("locks/requirements-py3.12-linux-dev.txt"),
+            ]
+        );
+        assert_eq!(groups[1].python_platform, Some(TargetTriple::Linux));
+        assert_eq!(
+            groups[1].extras,
+            Some(vec![ExtraName::from_str("dev").unwrap()])
+        );
+        // The empty cell includes no extras, rather than inheriting from the command line.
+        assert_eq!(groups[0].extras, Some(Vec::new()));
+
+        // Omitted axes inherit from the command line.
+        let groups = CompileMatrix::from_str("extra=test").unwrap().expand(None);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].output_file,
+            PathBuf::from("requirements-test.txt")
+        );
+        assert_eq!(groups[0].python_version, None);
+        let groups = CompileMatrix::from_str("python-version=3.12")
+            .unwrap()
+            .expand(None);
+        assert_eq!(groups[0].extras, None);
+
+        assert!(CompileMatrix::from_str("python-version=3.11|latest").is_err());
+        assert!(CompileMatrix::from_str("color=blue").is_err());
     }
 }
@@ -0,0 +1,79 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use requirements_txt::format_requirements_txt;
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Canonicalize requirements files in place, or check whether they're already canonical.
+pub(crate) fn pip_format(
+    src_files: &[PathBuf],
+    check: bool,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let mut unformatted = 0usize;
+
+    for path in src_files {
+        let content = fs_err::read_to_string(path)?;
+        let formatted = format_requirements_txt(&content, std::env::current_dir()?)
+            .with_context(|| format!("Failed to format: `{}`", path.user_display()))?;
+
+        if formatted == content {
+            continue;
+        }
+        unformatted += 1;
+
+        if check {
+            writeln!(
+                printer.stderr(),
+                "Would reformat: {}",
+                path.user_display().cyan()
+            )?;
+        } else {
+            fs_err::write(path, formatted)?;
+            writeln!(
+                printer.stderr(),
+                "Reformatted: {}",
+                path.user_display().cyan()
+            )?;
+        }
+    }
+
+    let unchanged = src_files.len() - unformatted;
+    let s = |count: usize| if count == 1 { "" } else { "s" };
+    if check {
+        if unformatted > 0 {
+            writeln!(
+                printer.stderr(),
+                "{} file{} would be reformatted, {} file{} already formatted",
+                unformatted.bold(),
+                s(unformatted),
+                unchanged.bold(),
+                s(unchanged)
+            )?;
+            return Ok(ExitStatus::Failure);
+        }
+        writeln!(
+            printer.stderr(),
+            "{} file{} already formatted",
+            unchanged.bold(),
+            s(unchanged)
+        )?;
+    } else {
+        writeln!(
+            printer.stderr(),
+            "{} file{} reformatted, {} file{} left unchanged",
+            unformatted.bold(),
+            s(unformatted),
+            unchanged.bold(),
+            s(unchanged)
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
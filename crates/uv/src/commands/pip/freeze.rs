@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::Result;
 use itertools::Itertools;
@@ -10,16 +11,21 @@ use uv_cache::Cache;
 use uv_fs::Simplified;
 use uv_installer::SitePackages;
 use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_warnings::warn_user;
 
 use crate::commands::ExitStatus;
+use crate::messages::Message;
 use crate::printer::Printer;
 
 /// Enumerate the installed packages in the current environment.
+#[allow(clippy::fn_params_excessive_bools)]
 pub(crate) fn pip_freeze(
     exclude_editable: bool,
+    hashes: bool,
     strict: bool,
     python: Option<&str>,
     system: bool,
+    venv_dir: Option<&Path>,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -29,7 +35,7 @@ pub(crate) fn pip_freeze(
     } else {
         SystemPython::Allowed
     };
-    let venv = PythonEnvironment::find(python, system, cache)?;
+    let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -39,38 +45,68 @@ pub(crate) fn pip_freeze(
 
     // Build the installed index.
     let site_packages = SitePackages::from_executable(&venv)?;
+    let mut missing_hashes = 0usize;
     for dist in site_packages
         .iter()
         .filter(|dist| !(exclude_editable && dist.is_editable()))
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
     {
-        match dist {
+        let line = match dist {
             InstalledDist::Registry(dist) => {
-                writeln!(printer.stdout(), "{}=={}", dist.name().bold(), dist.version)?;
+                format!("{}=={}", dist.name().bold(), dist.version)
             }
             InstalledDist::Url(dist) => {
                 if dist.editable {
-                    writeln!(printer.stdout(), "-e {}", dist.url)?;
+                    format!("-e {}", dist.url)
                 } else {
-                    writeln!(printer.stdout(), "{} @ {}", dist.name().bold(), dist.url)?;
+                    format!("{} @ {}", dist.name().bold(), dist.url)
                 }
             }
             InstalledDist::EggInfo(dist) => {
-                writeln!(printer.stdout(), "{}=={}", dist.name().bold(), dist.version)?;
+                format!("{}=={}", dist.name().bold(), dist.version)
             }
             InstalledDist::LegacyEditable(dist) => {
-                writeln!(printer.stdout(), "-e {}", dist.target.display())?;
+                format!("-e {}", dist.target.display())
+            }
+        };
+
+        let digests = if hashes { dist.hashes() } else { vec![] };
+
+        if digests.is_empty() {
+            if hashes && !dist.is_editable() {
+                missing_hashes += 1;
+            }
+            writeln!(printer.stdout(), "{line}")?;
+        } else {
+            writeln!(printer.stdout(), "{line} \\")?;
+            for (index, digest) in digests.iter().enumerate() {
+                if index + 1 == digests.len() {
+                    writeln!(printer.stdout(), "    --hash={digest}")?;
+                } else {
+                    writeln!(printer.stdout(), "    --hash={digest} \\")?;
+                }
             }
         }
     }
 
+    if missing_hashes > 0 {
+        let s = if missing_hashes == 1 { "" } else { "s" };
+        warn_user!(
+            "Hashes are unavailable for {missing_hashes} package{s}; hashes are only recorded for packages installed by uv with hash checking enabled"
+        );
+    }
+
     // Validate that the environment is consistent.
     if strict {
         for diagnostic in site_packages.diagnostics()? {
             writeln!(
                 printer.stderr(),
                 "{}{} {}",
-                "warning".yellow().bold(),
+                printer
+                    .messages()
+                    .format(Message::Warning, &[])
+                    .yellow()
+                    .bold(),
                 ":".bold(),
                 diagnostic.message().bold()
             )?;
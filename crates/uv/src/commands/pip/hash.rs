@@ -0,0 +1,219 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{File, IndexLocations};
+use pep440_rs::{Operator, Version};
+use pep508_rs::{Requirement, VersionOrUrl};
+use pypi_types::{HashAlgorithm, HashDigest};
+use uv_auth::store_credentials_from_url;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, NetworkTimeouts, OwnedArchive, RegistryClient, RegistryClientBuilder,
+    ResponseLimits,
+};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, IndexStrategy, KeyringProviderType, TrustedHost,
+};
+use uv_extract::hash::Hasher;
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// Compute the hashes of local files, or of the artifacts for pinned requirements.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_hash(
+    artifacts: &[String],
+    algorithm: HashAlgorithm,
+    index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    cache: &Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Initialize the registry client, lazily, as it's only needed for requirements.
+    let mut client = None;
+
+    for artifact in artifacts {
+        let path = Path::new(artifact);
+        if path.is_file() {
+            let digest = hash_file(path, algorithm)
+                .with_context(|| format!("Failed to hash: `{}`", path.user_display()))?;
+            writeln!(printer.stdout(), "{}:", path.user_display())?;
+            writeln!(printer.stdout(), "--hash={digest}")?;
+            continue;
+        }
+
+        // Otherwise, the argument must be a pinned requirement.
+        let Ok(requirement) = Requirement::from_str(artifact) else {
+            bail!(
+                "`{artifact}` is neither an existing file nor a valid requirement (e.g., `flask==3.0.0`)"
+            );
+        };
+        let Some(version) = pinned_version(&requirement) else {
+            bail!(
+                "Requirements must be pinned to an exact version (e.g., `{}==1.0.0`), but received: `{artifact}`",
+                requirement.name
+            );
+        };
+
+        let client = client.get_or_insert_with(|| {
+            for url in index_locations.urls() {
+                store_credentials_from_url(url);
+            }
+            RegistryClientBuilder::new(cache.clone())
+                .network_recorder(summary.network().clone())
+                .native_tls(native_tls)
+                .hermetic(hermetic)
+                .timeouts(timeouts)
+                .index_max_age(index_max_age)
+                .response_limits(response_limits)
+                .host_overrides(host_overrides.to_vec())
+                .trusted_hosts(trusted_hosts.to_vec())
+                .rate_limits(rate_limits.to_vec())
+                .http_version(http_version)
+                .connectivity(connectivity)
+                .index_urls(index_locations.index_urls())
+                .index_strategy(index_strategy)
+                .keyring(keyring_provider)
+                .build()
+        });
+
+        // Collect the files for the pinned version across all matching indexes.
+        let mut files = Vec::new();
+        for (index, metadata) in client.simple(&requirement.name).await? {
+            let metadata = OwnedArchive::deserialize(&metadata);
+            for datum in metadata.iter().filter(|datum| datum.version == version) {
+                debug!(
+                    "Found {} artifact(s) for {}=={} on: {index}",
+                    datum.files.wheels.len() + datum.files.source_dists.len(),
+                    requirement.name,
+                    version
+                );
+                files.extend(datum.files.wheels.iter().map(|wheel| wheel.file.clone()));
+                files.extend(
+                    datum
+                        .files
+                        .source_dists
+                        .iter()
+                        .map(|sdist| sdist.file.clone()),
+                );
+            }
+        }
+        if files.is_empty() {
+            bail!(
+                "No artifacts found for `{}=={version}` in the configured indexes",
+                requirement.name
+            );
+        }
+
+        let mut digests = BTreeSet::new();
+        for file in &files {
+            digests.insert(hash_remote_file(client, file, algorithm).await?);
+        }
+
+        write!(printer.stdout(), "{}=={version}", requirement.name)?;
+        for digest in &digests {
+            write!(printer.stdout(), " \\\n    --hash={digest}")?;
+        }
+        writeln!(printer.stdout())?;
+
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Hashed {} artifact(s) for {}=={version}",
+                files.len(),
+                requirement.name
+            )
+            .dimmed()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Return the version to which a requirement is pinned, if it's pinned via `==` or `===`.
+fn pinned_version(requirement: &Requirement) -> Option<Version> {
+    let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &requirement.version_or_url else {
+        return None;
+    };
+    let [specifier] = specifiers.as_ref() else {
+        return None;
+    };
+    if matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual) {
+        Some(specifier.version().clone())
+    } else {
+        None
+    }
+}
+
+/// Compute the digest of a local file.
+fn hash_file(path: &Path, algorithm: HashAlgorithm) -> Result<HashDigest> {
+    let mut hasher = Hasher::from(algorithm);
+    let mut file = fs_err::File::open(path)?;
+    let mut buffer = vec![0; 8192];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(HashDigest::from(hasher))
+}
+
+/// Compute the digest of a remote file, using the hash reported by the index if available, and
+/// otherwise downloading the file.
+async fn hash_remote_file(
+    client: &RegistryClient,
+    file: &File,
+    algorithm: HashAlgorithm,
+) -> Result<HashDigest> {
+    if let Some(digest) = file
+        .hashes
+        .iter()
+        .find(|digest| digest.algorithm() == algorithm)
+    {
+        return Ok(digest.clone());
+    }
+
+    let url = file.url.to_url()?;
+    debug!(
+        "Downloading {} to compute its {algorithm} hash",
+        file.filename
+    );
+    let mut response = client
+        .uncached_client()
+        .get(url.clone())
+        .send()
+        .await
+        .with_context(|| format!("Failed to download: {url}"))?
+        .error_for_status()
+        .with_context(|| format!("Failed to download: {url}"))?;
+
+    let mut hasher = Hasher::from(algorithm);
+    while let Some(chunk) = response.chunk().await? {
+        hasher.update(&chunk);
+    }
+    Ok(HashDigest::from(hasher))
+}
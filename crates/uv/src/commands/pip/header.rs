@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tracing::debug;
+
+use pep508_rs::{MarkerEnvironment, MarkerTree};
+use uv_configuration::TargetTriple;
+use uv_fs::Simplified;
+use uv_interpreter::PythonVersion;
+use uv_requirements::RequirementsSource;
+use uv_resolver::DisplayMarkers;
+
+/// The settings recorded in the header of a `requirements.txt` file produced by
+/// `uv pip compile`.
+#[derive(Debug, Default)]
+pub(crate) struct CompiledHeader {
+    /// The path to the compiled file.
+    path: PathBuf,
+    /// The `--python-version` the file was compiled for, if any.
+    python_version: Option<PythonVersion>,
+    /// The `--python-platform` the file was compiled for, if any.
+    python_platform: Option<TargetTriple>,
+    /// Whether the file was compiled with `--generate-hashes`.
+    generate_hashes: bool,
+    /// The marker expression for which the pinned dependencies are known to be valid, if emitted.
+    marker_expression: Option<MarkerTree>,
+}
+
+impl CompiledHeader {
+    /// Read the header from a `requirements.txt` source, if it was produced by `uv pip compile`.
+    pub(crate) fn from_source(source: &RequirementsSource) -> Option<Self> {
+        let RequirementsSource::RequirementsTxt(path) = source else {
+            return None;
+        };
+        if path == Path::new("-") {
+            return None;
+        }
+        let contents = fs_err::read_to_string(path).ok()?;
+        let header = Self::parse(path.clone(), &contents)?;
+        debug!(
+            "Found compilation header in: `{}`",
+            header.path.user_display()
+        );
+        Some(header)
+    }
+
+    /// Parse the header from the leading comments of a compiled file.
+    fn parse(path: PathBuf, contents: &str) -> Option<Self> {
+        let mut header = Self {
+            path,
+            ..Self::default()
+        };
+        let mut found = false;
+
+        let mut lines = contents
+            .lines()
+            .map_while(|line| line.trim_start().strip_prefix('#'))
+            .map(str::trim);
+        while let Some(line) = lines.next() {
+            match line {
+                "This file was autogenerated by uv via the following command:" => {
+                    let Some(command) = lines.next() else {
+                        break;
+                    };
+                    found = true;
+                    header.parse_command(command);
+                }
+                "Pinned dependencies known to be valid for:" => {
+                    let Some(marker) = lines.next() else {
+                        break;
+                    };
+                    found = true;
+                    header.marker_expression = DisplayMarkers::parse(marker);
+                }
+                _ => {}
+            }
+        }
+
+        found.then_some(header)
+    }
+
+    /// Extract the relevant settings from the recorded `uv pip compile` command.
+    fn parse_command(&mut self, command: &str) {
+        let mut args = command.split_whitespace();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (arg, None),
+            };
+            match flag {
+                "--python-version" => {
+                    self.python_version = value
+                        .or_else(|| args.next())
+                        .and_then(|value| PythonVersion::from_str(value).ok());
+                }
+                "--python-platform" => {
+                    self.python_platform = value.or_else(|| args.next()).and_then(|value| {
+                        <TargetTriple as clap::ValueEnum>::from_str(value, true).ok()
+                    });
+                }
+                "--generate-hashes" => self.generate_hashes = true,
+                "--no-generate-hashes" => self.generate_hashes = false,
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether the file was compiled with `--generate-hashes`.
+    pub(crate) fn generate_hashes(&self) -> bool {
+        self.generate_hashes
+    }
+
+    /// Return a description of the mismatch between the environment the file was compiled for and
+    /// the target environment, if any.
+    pub(crate) fn mismatch(&self, markers: &MarkerEnvironment) -> Option<String> {
+        let path = self.path.user_display();
+
+        // Requirements compiled with `--python-version` are valid for that version or newer.
+        if let Some(python_version) = &self.python_version {
+            let target = &markers.python_version().version;
+            let release = target.release();
+            let minimum = [
+                u64::from(python_version.major()),
+                u64::from(python_version.minor()),
+            ];
+            if release.iter().take(2).lt(minimum.iter()) {
+                return Some(format!(
+                    "`{path}` was compiled for Python {}.{} or newer (`--python-version {python_version}`), but the target environment uses Python {target}",
+                    python_version.major(),
+                    python_version.minor(),
+                ));
+            }
+        }
+
+        if let Some(python_platform) = self.python_platform {
+            if python_platform.sys_platform() != markers.sys_platform() {
+                return Some(format!(
+                    "`{path}` was compiled for `{}` (`--python-platform`), but the target environment uses `{}`",
+                    python_platform.sys_platform(),
+                    markers.sys_platform(),
+                ));
+            }
+        }
+
+        if let Some(marker_expression) = &self.marker_expression {
+            if !marker_expression.evaluate(markers, &[]) {
+                return Some(format!(
+                    "`{path}` is only known to be valid for environments matching `{}`, which excludes the target environment",
+                    DisplayMarkers(marker_expression)
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pep508_rs::MarkerTree;
+    use uv_configuration::TargetTriple;
+
+    use super::CompiledHeader;
+
+    #[test]
+    fn parse() {
+        let contents = indoc::indoc! {r"
+            # This file was autogenerated by uv via the following command:
+            #    uv pip compile requirements.in --python-version 3.11 --python-platform=linux --generate-hashes
+            # Pinned dependencies known to be valid for:
+            #    python_version == '3.11' and sys_platform == 'linux'
+            anyio==4.0.0 \
+                --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+        "};
+        let header = CompiledHeader::parse(PathBuf::from("requirements.txt"), contents).unwrap();
+        assert_eq!(header.python_version.unwrap().to_string(), "3.11");
+        assert_eq!(header.python_platform, Some(TargetTriple::Linux));
+        assert!(header.generate_hashes());
+        assert_eq!(
+            header.marker_expression.unwrap().to_string(),
+            "python_version == '3.11' and sys_platform == 'linux'"
+        );
+    }
+
+    #[test]
+    fn parse_never_valid() {
+        let contents = indoc::indoc! {r"
+            # This file was autogenerated by uv via the following command:
+            #    uv pip compile requirements.in
+            # Pinned dependencies known to be valid for:
+            #    (no environments)
+        "};
+        let header = CompiledHeader::parse(PathBuf::from("requirements.txt"), contents).unwrap();
+        assert!(matches!(
+            header.marker_expression,
+            Some(MarkerTree::Or(clauses)) if clauses.is_empty()
+        ));
+    }
+
+    #[test]
+    fn parse_no_header() {
+        let contents = indoc::indoc! {r"
+            anyio==4.0.0
+            # This file was autogenerated by uv via the following command:
+            #    uv pip compile requirements.in --python-version 3.11
+        "};
+        assert!(CompiledHeader::parse(PathBuf::from("requirements.txt"), contents).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
 
-use anstream::eprint;
 use fs_err as fs;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
@@ -9,32 +9,42 @@ use tracing::{debug, enabled, Level};
 
 use distribution_types::{IndexLocations, Resolution};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::{InstallFilter, ScriptOptions};
 use platform_tags::Tags;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndexClient, NetworkTimeouts, RegistryClientBuilder,
+    ResponseLimits,
+};
 use uv_configuration::{
-    Concurrency, ConfigSettings, IndexStrategy, NoBinary, NoBuild, PreviewMode, Reinstall,
-    SetupPyStrategy, Upgrade,
+    BuildCommands, CompileOptions, Concurrency, ConfigSettings, FileCollisionPolicy, HostOverride,
+    HttpVersion, IndexRateLimit, IndexStrategy, NoBinary, NoBuild, PreferBinary, PreferSource,
+    PreviewMode, Reinstall, SetupPyStrategy, TrustedHost, Upgrade, UpgradeStrategy,
 };
-use uv_configuration::{KeyringProviderType, TargetTriple};
+use uv_configuration::{KeyringProviderType, PackagePatches, Patches, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{SatisfiesResult, SitePackages};
-use uv_interpreter::{PythonEnvironment, PythonVersion, SystemPython, Target};
+use uv_installer::{InstallHooks, SatisfiesResult, SitePackages};
+use uv_interpreter::{PythonEnvironment, PythonVersion, Target};
 use uv_normalize::PackageName;
 use uv_requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 use uv_resolver::{
-    DependencyMode, ExcludeNewer, FlatIndex, InMemoryIndex, Lock, OptionsBuilder, PreReleaseMode,
-    ResolutionMode,
+    DependencyMode, ExcludeNewer, FlatIndex, InMemoryIndex, Lock, OptionsBuilder, PackagePolicy,
+    PreReleaseMode, PrefetchStrategy, ResolutionLimits, ResolutionMode,
 };
 use uv_types::{BuildIsolation, HashStrategy, InFlight};
 
 use crate::commands::pip::operations;
 use crate::commands::pip::operations::Modifications;
+use crate::commands::pip::report::{InstallReport, RequestedRequirements, SkippedRequirement};
+use crate::commands::pip::resolution_cache::{ResolutionCache, ResolutionInputs};
 use crate::commands::{elapsed, ExitStatus};
 use crate::editables::ResolvedEditables;
+use crate::errors::report_no_solution;
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::summary::Summary;
 
 /// Install packages into the current environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -47,40 +57,85 @@ pub(crate) async fn pip_install(
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
+    upgrade_strategy: UpgradeStrategy,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
+    check_dependency_confusion: bool,
+    dependency_confusion_allow: &[PackageName],
     keyring_provider: KeyringProviderType,
     reinstall: Reinstall,
     link_mode: LinkMode,
+    script_options: ScriptOptions,
+    install_filter: &InstallFilter,
+    file_collisions: FileCollisionPolicy,
     compile: bool,
+    compile_options: CompileOptions,
+    check_imports: bool,
+    cache_resolution: bool,
     require_hashes: bool,
+    trusted_signers: Option<&Path>,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
     no_build_isolation: bool,
     no_build: NoBuild,
     no_binary: NoBinary,
+    prefer_source: PreferSource,
+    prefer_binary: PreferBinary,
+    max_build_depth: Option<usize>,
+    build_commands: BuildCommands,
+    patches: &PackagePatches,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    ignore_platform_compatibility: bool,
     strict: bool,
     exclude_newer: Option<ExcludeNewer>,
+    package_policy: PackagePolicy,
+    resolution_limits: ResolutionLimits,
+    prefetch: PrefetchStrategy,
     python: Option<String>,
+    environments: &[PathBuf],
     system: bool,
+    venv_dir: Option<&Path>,
     break_system_packages: bool,
     target: Option<Target>,
     concurrency: Concurrency,
+    install_hooks: InstallHooks,
     uv_lock: Option<String>,
     native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    find_links_recursive: bool,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
     preview: PreviewMode,
     cache: Cache,
+    exact: bool,
+    check_only: bool,
     dry_run: bool,
+    report: Option<&Path>,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
+    let messages = printer.messages();
+
     let start = std::time::Instant::now();
 
     let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .keyring(keyring_provider);
 
     // Read all requirements from the provided sources.
@@ -88,6 +143,7 @@ pub(crate) async fn pip_install(
         project,
         requirements,
         constraints,
+        constraint_hashes,
         overrides,
         editables,
         source_trees,
@@ -102,112 +158,123 @@ pub(crate) async fn pip_install(
         requirements,
         constraints,
         overrides,
+        &Patches::from_patches(patches)?,
         extras,
+        trusted_signers,
         &client_builder,
         preview,
     )
     .await?;
 
-    // Detect the current Python interpreter.
-    let system = if system {
-        SystemPython::Required
-    } else {
-        SystemPython::Explicit
-    };
-    let venv = PythonEnvironment::find(python.as_deref(), system, &cache)?;
-
-    debug!(
-        "Using Python {} environment at {}",
-        venv.interpreter().python_version(),
-        venv.python_executable().user_display().cyan()
-    );
-
-    // Apply any `--target` directory.
-    let venv = if let Some(target) = target {
-        debug!(
-            "Using `--target` directory at {}",
-            target.root().user_display()
-        );
-        target.init()?;
-        venv.with_target(target)
-    } else {
-        venv
-    };
-
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
-        if break_system_packages {
-            debug!("Ignoring externally managed environment due to `--break-system-packages`");
-        } else {
-            return if let Some(error) = externally_managed.into_error() {
-                Err(anyhow::anyhow!(
-                    "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
-                    venv.root().user_display().cyan(),
-                    textwrap::indent(&error, "  ").green(),
-                ))
-            } else {
-                Err(anyhow::anyhow!(
-                    "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
-                    venv.root().user_display().cyan()
-                ))
-            };
-        }
-    }
-
-    let _lock = venv.lock()?;
-
-    // Determine the set of installed packages.
-    let site_packages = SitePackages::from_executable(&venv)?;
-
-    // Check if the current environment satisfies the requirements.
+    // Find the environments to install into.
+    let venvs = operations::find_environments(
+        environments,
+        python.as_deref(),
+        system,
+        venv_dir,
+        target,
+        break_system_packages,
+        &cache,
+    )?;
+    let _locks = venvs
+        .iter()
+        .map(PythonEnvironment::lock)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Resolve against the first environment, and apply the same resolution to each of them.
+    let venv = &venvs[0];
+
+    // Determine the set of installed packages in each environment.
+    let site_packages = venvs
+        .iter()
+        .map(SitePackages::from_executable)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // Check if the current environments satisfy the requirements.
     // Ideally, the resolver would be fast enough to let us remove this check. But right now, for large environments,
     // it's an order of magnitude faster to validate the environment than to resolve the requirements.
+    // (With `--exact`, we also need to identify any extraneous packages, which requires a resolution.)
     if reinstall.is_none()
         && upgrade.is_none()
         && source_trees.is_empty()
         && overrides.is_empty()
         && uv_lock.is_none()
+        && !exact
     {
-        match site_packages.satisfies(&requirements, &editables, &constraints)? {
-            // If the requirements are already satisfied, we're done.
-            SatisfiesResult::Fresh {
-                recursive_requirements,
-            } => {
-                if enabled!(Level::DEBUG) {
-                    for requirement in recursive_requirements
-                        .iter()
-                        .map(|entry| entry.requirement.to_string())
-                        .sorted()
-                    {
-                        debug!("Requirement satisfied: {requirement}");
+        let mut satisfied = true;
+        for site_packages in &site_packages {
+            match site_packages.satisfies(
+                &requirements,
+                &editables,
+                &constraints,
+                dependency_mode.is_transitive(),
+            )? {
+                SatisfiesResult::Fresh {
+                    recursive_requirements,
+                } => {
+                    if enabled!(Level::DEBUG) {
+                        for requirement in recursive_requirements
+                            .iter()
+                            .map(|entry| entry.requirement.to_string())
+                            .sorted()
+                        {
+                            debug!("Requirement satisfied: {requirement}");
+                        }
                     }
                 }
-                if !editables.is_empty() {
-                    debug!(
-                        "All editables satisfied: {}",
-                        editables.iter().map(ToString::to_string).join(" | ")
-                    );
+                SatisfiesResult::Unsatisfied(requirement) => {
+                    debug!("At least one requirement is not satisfied: {requirement}");
+                    satisfied = false;
+                    break;
                 }
-                let num_requirements = requirements.len() + editables.len();
-                let s = if num_requirements == 1 { "" } else { "s" };
+            }
+        }
+
+        // If the requirements are already satisfied, we're done.
+        if satisfied {
+            if !editables.is_empty() {
+                debug!(
+                    "All editables satisfied: {}",
+                    editables.iter().map(ToString::to_string).join(" | ")
+                );
+            }
+            let num_requirements = requirements.len() + editables.len();
+            writeln!(
+                printer.stderr(),
+                "{}",
+                messages
+                    .format(
+                        Message::Audited,
+                        &[
+                            ("packages", &messages.packages(num_requirements).bold()),
+                            ("elapsed", &elapsed(start.elapsed())),
+                        ]
+                    )
+                    .dimmed()
+            )?;
+            if dry_run {
                 writeln!(
                     printer.stderr(),
                     "{}",
-                    format!(
-                        "Audited {} in {}",
-                        format!("{num_requirements} package{s}").bold(),
-                        elapsed(start.elapsed())
-                    )
-                    .dimmed()
+                    messages.format(Message::WouldMakeNoChanges, &[])
                 )?;
-                if dry_run {
-                    writeln!(printer.stderr(), "Would make no changes")?;
-                }
-                return Ok(ExitStatus::Success);
             }
-            SatisfiesResult::Unsatisfied(requirement) => {
-                debug!("At least one requirement is not satisfied: {requirement}");
+
+            // Write the installation report, which is empty, since there's nothing to install.
+            if let Some(report) = report {
+                let markers = venv.interpreter().markers();
+                let mut skipped = SkippedRequirement::from_requirements(&requirements, markers);
+                if dependency_mode.is_transitive() {
+                    skipped.extend(SkippedRequirement::from_site_packages(
+                        &requirements,
+                        &site_packages[0],
+                        markers,
+                    ));
+                }
+                InstallReport::empty(&skipped, markers).write(report)?;
             }
+
+            return Ok(ExitStatus::Success);
         }
     }
 
@@ -258,6 +325,12 @@ pub(crate) async fn pip_install(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
         )?
+        .with_constraints(
+            constraint_hashes
+                .iter()
+                .map(|(constraint, hashes)| (constraint, hashes.as_slice())),
+            Some(&markers),
+        )?
     } else {
         HashStrategy::None
     };
@@ -273,7 +346,16 @@ pub(crate) async fn pip_install(
 
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
+        .network_recorder(summary.network().clone())
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .index_max_age(index_max_age)
+        .response_limits(response_limits)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -284,14 +366,14 @@ pub(crate) async fn pip_install(
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
+        let client = FlatIndexClient::new(&client, &cache).recursive(find_links_recursive);
         let entries = client.fetch(index_locations.flat_index()).await?;
         FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &no_binary)
     };
 
     // Determine whether to enable build isolation.
     let build_isolation = if no_build_isolation {
-        BuildIsolation::Shared(&venv)
+        BuildIsolation::Shared(venv)
     } else {
         BuildIsolation::Isolated
     };
@@ -323,16 +405,27 @@ pub(crate) async fn pip_install(
         &no_binary,
         concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_counter(summary.builds().clone())
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_prefer_source(prefer_source.clone())
+    .with_prefer_binary(prefer_binary.clone())
+    .with_max_build_depth(max_build_depth)
+    .with_build_commands(build_commands.clone());
 
     // Build all editable distributions. The editables are shared between resolution and
-    // installation, and should live for the duration of the command.
+    // installation, and should live for the duration of the command. With multiple environments,
+    // an editable that's up-to-date in the first environment may be missing from the others, so
+    // build them all.
     let editables = ResolvedEditables::resolve(
         editables
             .into_iter()
             .map(ResolvedEditables::from_requirement),
-        &site_packages,
-        &reinstall,
+        &site_packages[0],
+        if venvs.len() > 1 {
+            &Reinstall::All
+        } else {
+            &reinstall
+        },
         &hasher,
         venv.interpreter(),
         &tags,
@@ -344,12 +437,19 @@ pub(crate) async fn pip_install(
     )
     .await?;
 
+    // Track the requirements that were requested directly, for the installation report and to
+    // determine the dependencies that were skipped.
+    let requested = RequestedRequirements::from_requirements(&requirements);
+
+    // Determine the requirements that don't apply to the target environment.
+    let mut skipped = SkippedRequirement::from_requirements(&requirements, &markers);
+
     // Resolve the requirements.
     let resolution = if let Some(ref root) = uv_lock {
         let root = PackageName::new(root.to_string())?;
         let encoded = fs::tokio::read_to_string("uv.lock").await?;
         let lock: Lock = toml::from_str(&encoded)?;
-        lock.to_resolution(&markers, &tags, &root)
+        lock.to_resolution(&markers, &tags, &root, &|_| false)
     } else {
         let options = OptionsBuilder::new()
             .resolution_mode(resolution_mode)
@@ -357,44 +457,119 @@ pub(crate) async fn pip_install(
             .dependency_mode(dependency_mode)
             .exclude_newer(exclude_newer)
             .index_strategy(index_strategy)
+            .limits(resolution_limits)
             .build();
 
-        match operations::resolve(
-            requirements,
-            constraints,
-            overrides,
-            source_trees,
-            project,
-            extras,
-            &editables,
-            site_packages.clone(),
-            &hasher,
-            &reinstall,
-            &upgrade,
-            &interpreter,
-            &tags,
-            &markers,
-            &client,
-            &flat_index,
-            &index,
-            &resolve_dispatch,
-            concurrency,
-            options,
-            printer,
-        )
-        .await
+        // Reuse a cached resolution, if enabled and the inputs are unchanged.
+        let resolution_cache = if cache_resolution
+            && upgrade.is_none()
+            && reinstall.is_none()
+            && editables.is_empty()
+            && source_trees.is_empty()
         {
-            Ok(resolution) => Resolution::from(resolution),
-            Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
-                let report = miette::Report::msg(format!("{err}"))
-                    .context("No solution found when resolving dependencies:");
-                eprint!("{report:?}");
-                return Ok(ExitStatus::Failure);
+            ResolutionCache::from_inputs(
+                &ResolutionInputs {
+                    requirements: &requirements,
+                    constraints: &constraints,
+                    overrides: &overrides,
+                    index_locations: &index_locations,
+                    markers: &markers,
+                    tags: &tags,
+                    options,
+                    no_binary: &no_binary,
+                    no_build: &no_build,
+                    prefer_source: &prefer_source,
+                    prefer_binary: &prefer_binary,
+                    package_policy: &package_policy,
+                    site_packages: &site_packages[0],
+                },
+                &cache,
+            )
+        } else {
+            None
+        };
+        let cached = if let Some(resolution_cache) = resolution_cache.as_ref() {
+            resolution_cache.read(&markers, &tags).await
+        } else {
+            None
+        };
+
+        if let Some(resolution) = cached {
+            resolution
+        } else {
+            match operations::resolve(
+                requirements,
+                constraints,
+                overrides,
+                source_trees,
+                project,
+                extras,
+                &editables,
+                site_packages[0].clone(),
+                &hasher,
+                &reinstall,
+                &upgrade,
+                upgrade_strategy,
+                &package_policy,
+                &prefetch,
+                &interpreter,
+                &tags,
+                &markers,
+                &client,
+                &flat_index,
+                &index,
+                &resolve_dispatch,
+                concurrency,
+                options,
+                summary,
+                printer,
+            )
+            .await
+            {
+                Ok(resolution) => {
+                    if let Some(resolution_cache) = resolution_cache {
+                        if let Err(err) = resolution_cache.write(&resolution).await {
+                            debug!("Failed to cache resolution: {err}");
+                        }
+                    }
+                    Resolution::from(resolution)
+                }
+                Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
+                    report_no_solution(&err, preview, printer.output_format());
+                    return Ok(ExitStatus::ResolutionConflict);
+                }
+                Err(err) => return Err(err.into()),
             }
-            Err(err) => return Err(err.into()),
         }
     };
 
+    // Determine the dependencies that don't apply to the target environment.
+    if dependency_mode.is_transitive() {
+        skipped.extend(SkippedRequirement::from_resolution(
+            &resolution,
+            &index,
+            &requested,
+            &markers,
+        ));
+    }
+
+    // Write the installation report, which describes the distributions to be installed.
+    if let Some(report) = report {
+        InstallReport::new(&resolution, &index, &requested, &skipped, &markers)?.write(report)?;
+    }
+
+    // If requested, warn about packages that are shadowed by a newer version on another index,
+    // before anything is installed.
+    if check_dependency_confusion {
+        operations::diagnose_dependency_confusion(
+            resolution.distributions(),
+            dependency_confusion_allow,
+            &index_locations,
+            &client,
+        )
+        .await?;
+    }
+
     // Re-initialize the in-flight map.
     let in_flight = InFlight::default();
 
@@ -419,39 +594,96 @@ pub(crate) async fn pip_install(
             &no_binary,
             concurrency,
         )
+        .with_build_counter(summary.builds().clone())
         .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_prefer_source(prefer_source)
+        .with_prefer_binary(prefer_binary)
+        .with_max_build_depth(max_build_depth)
+        .with_build_commands(build_commands)
     };
 
-    // Sync the environment.
-    operations::install(
-        &resolution,
-        &editables,
-        site_packages,
-        Modifications::Sufficient,
-        &reinstall,
-        &no_binary,
-        link_mode,
-        compile,
-        &index_locations,
-        &hasher,
-        &tags,
-        &client,
-        &in_flight,
-        concurrency,
-        &install_dispatch,
-        &cache,
-        &venv,
-        dry_run,
-        printer,
-    )
-    .await?;
+    // With `--exact`, remove any extraneous packages.
+    let modifications = if exact {
+        Modifications::Exact
+    } else {
+        Modifications::Sufficient
+    };
+
+    // Install into each environment (or, with `--check-only`, determine whether they're already in
+    // sync). With multiple environments, the modifications are only committed once every
+    // environment has been updated, such that a failure in any of them leaves all of them
+    // untouched.
+    let mut modified = false;
+    let mut pending = Vec::with_capacity(venvs.len());
+    for (venv, site_packages) in venvs.iter().zip(site_packages) {
+        if !environments.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "Installing into environment at: {}",
+                venv.root().user_display().cyan()
+            )?;
+        }
+
+        let result = operations::install(
+            &resolution,
+            &editables,
+            site_packages,
+            modifications,
+            &reinstall,
+            &no_binary,
+            link_mode,
+            script_options,
+            install_filter,
+            file_collisions,
+            compile,
+            &compile_options,
+            check_imports,
+            &index_locations,
+            &hasher,
+            &tags,
+            ignore_platform_compatibility,
+            &client,
+            &in_flight,
+            concurrency,
+            &install_dispatch,
+            &cache,
+            venv,
+            &install_hooks,
+            allow_external_symlinks,
+            (venvs.len() > 1).then_some(&mut pending),
+            dry_run || check_only,
+            summary,
+            printer,
+        )
+        .await;
+
+        match result {
+            Ok(result) => modified |= result,
+            Err(err) => {
+                for pending in pending.into_iter().rev() {
+                    pending.rollback(printer)?;
+                }
+                return Err(err.into());
+            }
+        }
+    }
+    for pending in pending {
+        pending.commit()?;
+    }
+
+    // With `--check-only`, any required modification indicates that the environment is out of date.
+    if check_only && modified {
+        return Ok(ExitStatus::Failure);
+    }
 
     // Notify the user of any resolution diagnostics.
     operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
     // Notify the user of any environment diagnostics.
-    if strict && !dry_run {
-        operations::diagnose_environment(&resolution, &venv, printer)?;
+    if strict && !dry_run && !check_only {
+        for venv in &venvs {
+            operations::diagnose_environment(&resolution, venv, printer)?;
+        }
     }
 
     Ok(ExitStatus::Success)
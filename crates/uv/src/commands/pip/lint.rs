@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::time::Instant;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{
+    BuiltDist, IndexLocations, RegistryBuiltDist, RegistryBuiltWheel, Requirement,
+    RequirementSource, UnresolvedRequirement,
+};
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::{ExtraName, MarkerExpression, MarkerTree};
+use uv_auth::store_credentials_from_url;
+use uv_cache::Cache;
+use uv_client::{
+    BaseClientBuilder, Connectivity, NetworkTimeouts, OwnedArchive, RegistryClient,
+    RegistryClientBuilder, ResponseLimits,
+};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, IndexStrategy, KeyringProviderType, PreviewMode,
+    TrustedHost,
+};
+use uv_normalize::PackageName;
+use uv_requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use uv_resolver::{is_satisfiable, simplify_markers};
+
+use crate::commands::{elapsed, ExitStatus};
+use crate::messages::Message;
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// Validate requirements files, without resolving them.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_lint(
+    sources: &[RequirementsSource],
+    index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    preview: PreviewMode,
+    cache: &Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = Instant::now();
+
+    let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
+        .connectivity(connectivity)
+        .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
+        .keyring(keyring_provider);
+
+    let mut problems = Vec::new();
+
+    // Read each source independently, such that a source that fails to parse (e.g., due to an
+    // unknown marker) doesn't prevent linting the others.
+    let mut requirements: Vec<(&RequirementsSource, Requirement)> = Vec::new();
+    for source in sources {
+        let spec = match RequirementsSpecification::from_source(
+            source,
+            &ExtrasSpecification::All,
+            &client_builder,
+            preview,
+        )
+        .await
+        {
+            Ok(spec) => spec,
+            Err(err) => {
+                problems.push(format!("{source}: {}", err.chain().join(": ")));
+                continue;
+            }
+        };
+
+        for entry in spec.requirements.into_iter().chain(spec.overrides) {
+            match entry.requirement {
+                UnresolvedRequirement::Named(requirement) => {
+                    requirements.push((source, requirement));
+                }
+                UnresolvedRequirement::Unnamed(requirement) => {
+                    if let Some(marker) = &requirement.marker {
+                        lint_marker(source, &requirement, marker, &mut problems);
+                    }
+                }
+            }
+        }
+        requirements.extend(
+            spec.constraints
+                .into_iter()
+                .map(|constraint| (source, constraint)),
+        );
+    }
+
+    // Lint each requirement in isolation.
+    for (source, requirement) in &requirements {
+        if let Some(marker) = &requirement.marker {
+            lint_marker(source, requirement, marker, &mut problems);
+        }
+        if let RequirementSource::Registry { specifier, .. } = &requirement.source {
+            if !is_satisfiable(specifier) {
+                problems.push(format!(
+                    "{source}: `{requirement}` has a version specifier that can never be satisfied"
+                ));
+            }
+        }
+    }
+
+    // Lint duplicate requirements for the same package against each other.
+    let mut by_name: BTreeMap<&PackageName, Vec<(&RequirementsSource, &Requirement)>> =
+        BTreeMap::new();
+    for (source, requirement) in &requirements {
+        by_name
+            .entry(&requirement.name)
+            .or_default()
+            .push((source, requirement));
+    }
+    for duplicates in by_name.values() {
+        for (i, (source_a, a)) in duplicates.iter().enumerate() {
+            for (source_b, b) in &duplicates[i + 1..] {
+                if conflicts(a, b) {
+                    problems.push(format!(
+                        "Conflicting requirements for `{}`: `{a}` ({source_a}) and `{b}` ({source_b})",
+                        a.name
+                    ));
+                }
+            }
+        }
+    }
+
+    // Check that any requested extras exist on the referenced package.
+    if connectivity == Connectivity::Offline {
+        debug!("Skipping extras validation in offline mode");
+    } else {
+        let mut client = None;
+        let mut seen = Vec::new();
+        for (source, requirement) in &requirements {
+            let RequirementSource::Registry { specifier, .. } = &requirement.source else {
+                continue;
+            };
+            if requirement.extras.is_empty() || seen.contains(&(&requirement.name, specifier)) {
+                continue;
+            }
+            seen.push((&requirement.name, specifier));
+
+            let client = client.get_or_insert_with(|| {
+                for url in index_locations.urls() {
+                    store_credentials_from_url(url);
+                }
+                RegistryClientBuilder::new(cache.clone())
+                    .network_recorder(summary.network().clone())
+                    .native_tls(native_tls)
+                    .hermetic(hermetic)
+                    .timeouts(timeouts)
+                    .index_max_age(index_max_age)
+                    .response_limits(response_limits)
+                    .host_overrides(host_overrides.to_vec())
+                    .trusted_hosts(trusted_hosts.to_vec())
+                    .rate_limits(rate_limits.to_vec())
+                    .http_version(http_version)
+                    .connectivity(connectivity)
+                    .index_urls(index_locations.index_urls())
+                    .index_strategy(index_strategy)
+                    .keyring(keyring_provider)
+                    .build()
+            });
+
+            let provided = match provided_extras(client, &requirement.name, specifier).await {
+                Ok(Some(provided)) => provided,
+                Ok(None) => {
+                    debug!(
+                        "Skipping extras validation for `{}` (no matching wheel)",
+                        requirement.name
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    debug!(
+                        "Skipping extras validation for `{}`: {err}",
+                        requirement.name
+                    );
+                    continue;
+                }
+            };
+            for extra in &requirement.extras {
+                if !provided.contains(extra) {
+                    problems.push(format!(
+                        "{source}: `{requirement}` requests the extra `{extra}`, but `{}` does not provide it",
+                        requirement.name
+                    ));
+                }
+            }
+        }
+    }
+
+    let s = if requirements.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Linted {} in {}",
+            format!("{} requirement{}", requirements.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if problems.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            printer.messages().format(Message::NoProblems, &[]).dimmed()
+        )?;
+
+        Ok(ExitStatus::Success)
+    } else {
+        let s = if problems.len() == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Found {}",
+                format!("{} problem{}", problems.len(), s).bold()
+            )
+            .dimmed()
+        )?;
+
+        for problem in &problems {
+            writeln!(printer.stderr(), "{}", problem.bold())?;
+        }
+
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Lint the marker of a requirement, to detect markers that can never apply.
+fn lint_marker(
+    source: &RequirementsSource,
+    requirement: &impl Display,
+    marker: &MarkerTree,
+    problems: &mut Vec<String>,
+) {
+    if is_invalid(marker) {
+        problems.push(format!(
+            "{source}: `{requirement}` has an invalid marker, which always evaluates to false: `{marker}`"
+        ));
+    } else if matches!(simplify_markers(marker.clone()), MarkerTree::Or(trees) if trees.is_empty())
+    {
+        problems.push(format!(
+            "{source}: `{requirement}` has a marker that can never be satisfied: `{marker}`"
+        ));
+    }
+}
+
+/// Returns `true` if the marker contains an invalid expression (e.g., `python_version > os_name`).
+fn is_invalid(marker: &MarkerTree) -> bool {
+    match marker {
+        MarkerTree::Expression(MarkerExpression::Arbitrary { .. }) => true,
+        MarkerTree::Expression(_) => false,
+        MarkerTree::And(trees) | MarkerTree::Or(trees) => trees.iter().any(is_invalid),
+    }
+}
+
+/// Returns `true` if two requirements for the same package, which apply in the same environments,
+/// can't both be satisfied.
+fn conflicts(a: &Requirement, b: &Requirement) -> bool {
+    // Requirements with different markers may apply in disjoint environments.
+    if a.marker != b.marker {
+        return false;
+    }
+    match (&a.source, &b.source) {
+        (
+            RequirementSource::Registry { specifier: a, .. },
+            RequirementSource::Registry { specifier: b, .. },
+        ) => !is_satisfiable(
+            &a.iter()
+                .chain(b.iter())
+                .cloned()
+                .collect::<VersionSpecifiers>(),
+        ),
+        // A version specifier may be satisfied by the version at a URL.
+        (RequirementSource::Registry { .. }, _) | (_, RequirementSource::Registry { .. }) => false,
+        (a, b) => a != b,
+    }
+}
+
+/// Returns the extras provided by the latest version of the package that satisfies the specifier,
+/// or `None` if there's no matching wheel from which to read the metadata.
+async fn provided_extras(
+    client: &RegistryClient,
+    name: &PackageName,
+    specifier: &VersionSpecifiers,
+) -> Result<Option<Vec<ExtraName>>> {
+    // Only consider pre-releases if the specifier opts in to them.
+    let allow_prerelease = specifier
+        .iter()
+        .any(|specifier| specifier.version().any_prerelease());
+
+    let mut best = None;
+    for (index, metadata) in client.simple(name).await? {
+        let metadata = OwnedArchive::deserialize(&metadata);
+        for datum in metadata.iter() {
+            if !specifier.contains(&datum.version)
+                || (datum.version.any_prerelease() && !allow_prerelease)
+            {
+                continue;
+            }
+            let Some(wheel) = datum.files.wheels.first() else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .map_or(true, |(version, _)| datum.version > *version)
+            {
+                best = Some((
+                    datum.version.clone(),
+                    RegistryBuiltWheel {
+                        filename: wheel.name.clone(),
+                        file: Box::new(wheel.file.clone()),
+                        index: index.clone(),
+                    },
+                ));
+            }
+        }
+    }
+    let Some((_, wheel)) = best else {
+        return Ok(None);
+    };
+
+    let metadata = client
+        .wheel_metadata(&BuiltDist::Registry(RegistryBuiltDist {
+            wheels: vec![wheel],
+            best_wheel_index: 0,
+            sdist: None,
+        }))
+        .await?;
+
+    // Older metadata may omit `Provides-Extra`, so include any extras referenced by the
+    // dependencies too.
+    let mut extras = metadata.provides_extras;
+    for requirement in &metadata.requires_dist {
+        if let Some(marker) = &requirement.marker {
+            collect_extras(marker, &mut extras);
+        }
+    }
+    Ok(Some(extras))
+}
+
+/// Collect the extras referenced in a marker tree.
+fn collect_extras(marker: &MarkerTree, extras: &mut Vec<ExtraName>) {
+    match marker {
+        MarkerTree::Expression(MarkerExpression::Extra { name, .. }) => {
+            extras.push(name.clone());
+        }
+        MarkerTree::Expression(_) => {}
+        MarkerTree::And(trees) | MarkerTree::Or(trees) => {
+            for tree in trees {
+                collect_extras(tree, extras);
+            }
+        }
+    }
+}
@@ -1,35 +1,75 @@
 use std::cmp::max;
 use std::fmt::Write;
+use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use futures::{StreamExt, TryStreamExt};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
 use serde::Serialize;
 use tracing::debug;
 use unicode_width::UnicodeWidthStr;
 
-use distribution_types::{Diagnostic, InstalledDist, Name};
+use distribution_types::{CompatibleDist, Diagnostic, IndexLocations, InstalledDist, Name};
+use pep440_rs::Version;
+use platform_tags::Tags;
+use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndexClient, NetworkTimeouts, RegistryClient, RegistryClientBuilder,
+    ResponseLimits,
+};
+use uv_configuration::{
+    Concurrency, HostOverride, HttpVersion, IndexRateLimit, IndexStrategy, KeyringProviderType,
+    NoBinary, NoBuild, PreferSource, TrustedHost,
+};
 use uv_fs::Simplified;
-use uv_installer::SitePackages;
+use uv_installer::{installed_size, SitePackages};
 use uv_interpreter::{PythonEnvironment, SystemPython};
 use uv_normalize::PackageName;
+use uv_resolver::{
+    AllowedYanks, ExcludeNewer, FlatIndex, MinReleaseAge, PreReleaseMode, PythonRequirement,
+    VersionMap,
+};
+use uv_types::HashStrategy;
 
-use crate::commands::ExitStatus;
-use crate::commands::ListFormat;
+use crate::commands::{human_readable_bytes, ExitStatus, ListFormat};
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::summary::Summary;
 
 /// Enumerate the installed packages in the current environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
-pub(crate) fn pip_list(
+pub(crate) async fn pip_list(
     editable: bool,
     exclude_editable: bool,
     exclude: &[PackageName],
     format: &ListFormat,
+    outdated: bool,
+    sizes: bool,
+    prerelease: PreReleaseMode,
+    index_locations: IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProviderType,
+    exclude_newer: Option<ExcludeNewer>,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    find_links_recursive: bool,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
     strict: bool,
     python: Option<&str>,
     system: bool,
+    venv_dir: Option<&Path>,
     cache: &Cache,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     // Detect the current Python interpreter.
@@ -38,7 +78,16 @@ pub(crate) fn pip_list(
     } else {
         SystemPython::Allowed
     };
-    let venv = PythonEnvironment::find(python, system, cache)?;
+    if outdated && matches!(format, ListFormat::Freeze) {
+        return Err(anyhow!(
+            "`--format freeze` cannot be used with `--outdated`"
+        ));
+    }
+    if sizes && matches!(format, ListFormat::Freeze) {
+        return Err(anyhow!("`--format freeze` cannot be used with `--sizes`"));
+    }
+
+    let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -59,9 +108,119 @@ pub(crate) fn pip_list(
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
         .collect_vec();
 
+    // If requested, look up the latest available version of each package, and retain only those
+    // packages for which a newer version is available.
+    let (results, latest) = if outdated {
+        // Initialize the registry client.
+        for url in index_locations.urls() {
+            store_credentials_from_url(url);
+        }
+        let interpreter = venv.interpreter();
+        let markers = interpreter.markers();
+        let tags = interpreter.tags()?;
+        let python_requirement = PythonRequirement::from_marker_environment(interpreter, markers);
+        let client = RegistryClientBuilder::new(cache.clone())
+            .network_recorder(summary.network().clone())
+            .native_tls(native_tls)
+            .hermetic(hermetic)
+            .timeouts(timeouts)
+            .index_max_age(index_max_age)
+            .response_limits(response_limits)
+            .host_overrides(host_overrides.to_vec())
+            .trusted_hosts(trusted_hosts.to_vec())
+            .rate_limits(rate_limits.to_vec())
+            .http_version(http_version)
+            .connectivity(connectivity)
+            .index_urls(index_locations.index_urls())
+            .index_strategy(index_strategy)
+            .keyring(keyring_provider)
+            .markers(markers)
+            .platform(interpreter.platform())
+            .build();
+
+        // Resolve the flat indexes from `--find-links`.
+        let flat_index = {
+            let client = FlatIndexClient::new(&client, cache).recursive(find_links_recursive);
+            let entries = client.fetch(index_locations.flat_index()).await?;
+            FlatIndex::from_entries(
+                entries,
+                tags,
+                &HashStrategy::None,
+                &NoBuild::None,
+                &NoBinary::None,
+            )
+        };
+
+        let latest: FxHashMap<&PackageName, Latest> =
+            futures::stream::iter(results.iter().filter(|dist| !dist.is_editable()))
+                .map(|dist| {
+                    // Like `pip`, consider pre-releases if requested, or if the installed version is itself
+                    // a pre-release.
+                    let prereleases = matches!(prerelease, PreReleaseMode::Allow)
+                        || dist.version().any_prerelease();
+                    let client = &client;
+                    let flat_index = &flat_index;
+                    let python_requirement = &python_requirement;
+                    async move {
+                        let latest = find_latest(
+                            dist.name(),
+                            client,
+                            flat_index,
+                            tags,
+                            python_requirement,
+                            exclude_newer.as_ref(),
+                            prereleases,
+                        )
+                        .await?;
+                        Ok::<_, anyhow::Error>(latest.map(|latest| (dist.name(), latest)))
+                    }
+                })
+                .buffered(Concurrency::DEFAULT_DOWNLOADS)
+                .try_filter_map(|latest| async move { Ok(latest) })
+                .try_collect()
+                .await?;
+
+        let results = results
+            .into_iter()
+            .filter(|dist| {
+                latest
+                    .get(dist.name())
+                    .is_some_and(|latest| latest.version > *dist.version())
+            })
+            .collect_vec();
+        (results, latest)
+    } else {
+        (results, FxHashMap::default())
+    };
+
+    // If requested, compute the on-disk size of each package.
+    let sizes: FxHashMap<&Path, u64> = if sizes {
+        results
+            .iter()
+            .map(|dist| {
+                let size = installed_size(dist)
+                    .with_context(|| format!("Failed to read installed files for: {dist}"))?;
+                Ok::<_, anyhow::Error>(size.map(|size| (dist.path(), size)))
+            })
+            .filter_map(Result::transpose)
+            .collect::<Result<_>>()?
+    } else {
+        FxHashMap::default()
+    };
+
     match format {
         ListFormat::Json => {
-            let rows = results.iter().copied().map(Entry::from).collect_vec();
+            let rows = results
+                .iter()
+                .copied()
+                .map(|dist| {
+                    Entry::new(
+                        dist,
+                        latest.get(dist.name()),
+                        sizes.get(dist.path()).copied(),
+                    )
+                })
+                .collect_vec();
             let output = serde_json::to_string(&rows)?;
             writeln!(printer.stdout(), "{output}")?;
         }
@@ -86,6 +245,43 @@ pub(crate) fn pip_list(
                 },
             ];
 
+            // The latest version and file type are only displayed with `--outdated`.
+            if outdated {
+                columns.push(Column {
+                    header: String::from("Latest"),
+                    rows: results
+                        .iter()
+                        .map(|dist| latest[dist.name()].version.to_string())
+                        .collect_vec(),
+                });
+                columns.push(Column {
+                    header: String::from("Type"),
+                    rows: results
+                        .iter()
+                        .map(|dist| latest[dist.name()].filetype.to_string())
+                        .collect_vec(),
+                });
+            }
+
+            // The size is only displayed with `--sizes`.
+            if !sizes.is_empty() {
+                columns.push(Column {
+                    header: String::from("Size"),
+                    rows: results
+                        .iter()
+                        .map(|dist| {
+                            sizes
+                                .get(dist.path())
+                                .map(|size| {
+                                    let (bytes, unit) = human_readable_bytes(*size);
+                                    format!("{bytes:.1}{unit}")
+                                })
+                                .unwrap_or_default()
+                        })
+                        .collect_vec(),
+                });
+            }
+
             // Editable column is only displayed if at least one editable package is found.
             if results.iter().copied().any(InstalledDist::is_editable) {
                 columns.push(Column {
@@ -106,6 +302,13 @@ pub(crate) fn pip_list(
             for elems in MultiZip(columns.iter().map(Column::fmt).collect_vec()) {
                 writeln!(printer.stdout(), "{}", elems.join(" ").trim_end())?;
             }
+
+            // Summarize the total size of the listed packages.
+            if !sizes.is_empty() {
+                let (bytes, unit) = human_readable_bytes(sizes.values().sum());
+                writeln!(printer.stdout())?;
+                writeln!(printer.stdout(), "Total size: {bytes:.1}{unit}")?;
+            }
         }
         ListFormat::Freeze if results.is_empty() => {}
         ListFormat::Freeze => {
@@ -126,7 +329,11 @@ pub(crate) fn pip_list(
             writeln!(
                 printer.stderr(),
                 "{}{} {}",
-                "warning".yellow().bold(),
+                printer
+                    .messages()
+                    .format(Message::Warning, &[])
+                    .yellow()
+                    .bold(),
                 ":".bold(),
                 diagnostic.message().bold()
             )?;
@@ -136,23 +343,122 @@ pub(crate) fn pip_list(
     Ok(ExitStatus::Success)
 }
 
+/// Find the latest version of a package that's available from the configured indexes, along
+/// with the type of distribution that would be installed.
+async fn find_latest(
+    name: &PackageName,
+    client: &RegistryClient,
+    flat_index: &FlatIndex,
+    tags: &Tags,
+    python_requirement: &PythonRequirement,
+    exclude_newer: Option<&ExcludeNewer>,
+    prereleases: bool,
+) -> Result<Option<Latest>> {
+    let version_maps = match client.simple(name).await {
+        Ok(results) => results
+            .into_iter()
+            .map(|(index, metadata)| {
+                VersionMap::from_metadata(
+                    metadata,
+                    name,
+                    &index,
+                    tags,
+                    python_requirement,
+                    &AllowedYanks::default(),
+                    &HashStrategy::None,
+                    exclude_newer,
+                    &MinReleaseAge::default(),
+                    flat_index.get(name).cloned(),
+                    &NoBinary::None,
+                    &NoBuild::None,
+                    &PreferSource::None,
+                )
+            })
+            .collect_vec(),
+        Err(err) => match err.into_kind() {
+            uv_client::ErrorKind::PackageNotFound(_)
+            | uv_client::ErrorKind::NoIndex(_)
+            | uv_client::ErrorKind::Offline(_) => flat_index
+                .get(name)
+                .cloned()
+                .map(VersionMap::from)
+                .into_iter()
+                .collect_vec(),
+            kind => return Err(uv_client::Error::from(kind).into()),
+        },
+    };
+
+    Ok(version_maps
+        .iter()
+        .filter_map(|version_map| {
+            let (version, dist) = version_map.latest(prereleases)?;
+            let filetype = match dist {
+                CompatibleDist::CompatibleWheel { .. } | CompatibleDist::InstalledDist(_) => {
+                    FileType::Wheel
+                }
+                CompatibleDist::SourceDist { .. } | CompatibleDist::IncompatibleWheel { .. } => {
+                    FileType::SourceDist
+                }
+            };
+            Some(Latest {
+                version: version.clone(),
+                filetype,
+            })
+        })
+        .max_by(|a, b| a.version.cmp(&b.version)))
+}
+
+/// The latest available version of an installed package.
+#[derive(Debug)]
+struct Latest {
+    version: Version,
+    filetype: FileType,
+}
+
+/// The type of distribution that would be installed for the latest version of a package.
+#[derive(Debug, Copy, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileType {
+    Wheel,
+    #[serde(rename = "sdist")]
+    SourceDist,
+}
+
+impl std::fmt::Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wheel => f.write_str("wheel"),
+            Self::SourceDist => f.write_str("sdist"),
+        }
+    }
+}
+
 /// An entry in a JSON list of installed packages.
 #[derive(Debug, Serialize)]
 struct Entry {
     name: String,
     version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    latest_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_filetype: Option<FileType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     editable_project_location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
 }
 
-impl From<&InstalledDist> for Entry {
-    fn from(dist: &InstalledDist) -> Self {
+impl Entry {
+    fn new(dist: &InstalledDist, latest: Option<&Latest>, size: Option<u64>) -> Self {
         Self {
             name: dist.name().to_string(),
             version: dist.version().to_string(),
+            latest_version: latest.map(|latest| latest.version.to_string()),
+            latest_filetype: latest.map(|latest| latest.filetype),
             editable_project_location: dist
                 .as_editable()
                 .map(|url| url.to_file_path().unwrap().simplified_display().to_string()),
+            size,
         }
     }
 }
@@ -1,9 +1,16 @@
 pub(crate) mod check;
 pub(crate) mod compile;
+pub(crate) mod format;
 pub(crate) mod freeze;
+pub(crate) mod hash;
+pub(crate) mod header;
 pub(crate) mod install;
+pub(crate) mod lint;
 pub(crate) mod list;
 pub(crate) mod operations;
+pub(crate) mod report;
+pub(crate) mod resolution_cache;
 pub(crate) mod show;
 pub(crate) mod sync;
 pub(crate) mod uninstall;
+pub(crate) mod why;
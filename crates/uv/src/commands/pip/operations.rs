@@ -1,59 +1,78 @@
 //! Common operations shared across the `pip` API and subcommands.
 
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context};
+use futures::StreamExt;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::debug;
 
 use distribution_types::{
-    CachedDist, Diagnostic, InstalledDist, Requirement, ResolutionDiagnostic,
-    UnresolvedRequirementSpecification,
+    BuildableSource, BuiltDist, Dist, DistributionMetadata, IndexLocations, InstalledMetadata,
+    InstalledVersion, LocalDist, Name, ParsedUrl, RegistryBuiltDist, RequirementSource, Resolution,
+    ResolvedDist, SourceDist, VersionOrUrlRef,
 };
 use distribution_types::{
-    DistributionMetadata, IndexLocations, InstalledMetadata, InstalledVersion, LocalDist, Name,
-    ParsedUrl, RequirementSource, Resolution,
+    CachedDist, Diagnostic, InstalledDist, Requirement, ResolutionDiagnostic,
+    UnresolvedRequirementSpecification,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::{InstallFilter, ScriptOptions, UninstallOptions};
 use pep440_rs::{VersionSpecifier, VersionSpecifiers};
 use pep508_rs::{MarkerEnvironment, VerbatimUrl};
 use platform_tags::Tags;
+use pypi_types::{MetadataDiff, Yanked};
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, RegistryClient};
+use uv_client::{BaseClientBuilder, OwnedArchive, RegistryClient, VersionFiles};
 use uv_configuration::{
-    Concurrency, Constraints, NoBinary, Overrides, PreviewMode, Reinstall, Upgrade,
+    CompileOptions, Concurrency, Constraints, FileCollisionPolicy, NoBinary, NoBuild, Overrides,
+    Patches, PreviewMode, Reinstall, Upgrade, UpgradeStrategy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
-use uv_installer::{Downloader, Plan, Planner, ResolvedEditable, SitePackages};
-use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_normalize::PackageName;
+use uv_installer::{
+    find_file_collisions, find_script_conflicts, HookEvent, HookPlan, InstallHooks, Plan, Planner,
+    Preparer, ReinstallReason, ResolvedEditable, SitePackages, SpaceEstimate, Transaction,
+};
+use uv_interpreter::{Interpreter, PythonEnvironment, SystemPython, Target};
+use uv_normalize::{ExtraName, PackageName};
 use uv_requirements::{
     ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver, RequirementsSource,
     RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
-    DependencyMode, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options, Preference,
-    PythonRequirement, ResolutionGraph, Resolver,
+    DependencyMode, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options, PackagePolicy,
+    Preference, PrefetchStrategy, PythonRequirement, ResolutionGraph, Resolver,
 };
-use uv_types::{HashStrategy, InFlight, InstalledPackagesProvider};
+use uv_types::{BuildContext, HashStrategy, InFlight, InstalledPackagesProvider};
 use uv_warnings::warn_user;
 
-use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
+use crate::cancellation::{self, Cancellable, CriticalSection};
+use crate::commands::reporters::{InstallReporter, PrepareReporter, ResolverReporter};
 use crate::commands::DryRunEvent;
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind};
+use crate::commands::{
+    check_installed_imports, compile_bytecode, elapsed, human_readable_bytes, ChangeEvent,
+    ChangeEventKind,
+};
 use crate::editables::ResolvedEditables;
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::signature;
+use crate::summary::{Packages, Summary};
+use crate::symlinks;
 
 /// Consolidate the requirements for an installation.
 pub(crate) async fn read_requirements(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    patches: &Patches,
     extras: &ExtrasSpecification,
+    trusted_signers: Option<&Path>,
     client_builder: &BaseClientBuilder<'_>,
     preview: PreviewMode,
 ) -> Result<RequirementsSpecification, Error> {
@@ -66,6 +85,15 @@ pub(crate) async fn read_requirements(
         .into());
     }
 
+    // Verify the signatures of any requirements files before trusting their contents.
+    if let Some(trusted_signers) = trusted_signers {
+        for source in requirements.iter().chain(constraints).chain(overrides) {
+            if let RequirementsSource::RequirementsTxt(path) = source {
+                signature::verify_requirements_txt(path, trusted_signers)?;
+            }
+        }
+    }
+
     // Read all requirements from the provided sources.
     let spec = RequirementsSpecification::from_sources(
         requirements,
@@ -75,30 +103,129 @@ pub(crate) async fn read_requirements(
         client_builder,
         preview,
     )
-    .await?;
+    .await?
+    .with_patches(patches);
 
     // If all the metadata could be statically resolved, validate that every extra was used. If we
     // need to resolve metadata via PEP 517, we don't know which extras are used until much later.
     if spec.source_trees.is_empty() {
-        if let ExtrasSpecification::Some(extras) = extras {
-            let mut unused_extras = extras
-                .iter()
-                .filter(|extra| !spec.extras.contains(extra))
-                .collect::<Vec<_>>();
-            if !unused_extras.is_empty() {
-                unused_extras.sort_unstable();
-                unused_extras.dedup();
-                let s = if unused_extras.len() == 1 { "" } else { "s" };
+        validate_extras(extras, &spec.extras)?;
+    }
+
+    Ok(spec)
+}
+
+/// Validate that every requested extra is provided by (at least one of) the requirement sources.
+pub(crate) fn validate_extras(
+    extras: &ExtrasSpecification,
+    provided: &FxHashSet<ExtraName>,
+) -> anyhow::Result<()> {
+    if let ExtrasSpecification::Some(extras) = extras {
+        let mut unused_extras = extras
+            .iter()
+            .filter(|extra| !provided.contains(extra))
+            .collect::<Vec<_>>();
+        if !unused_extras.is_empty() {
+            unused_extras.sort_unstable();
+            unused_extras.dedup();
+            let s = if unused_extras.len() == 1 { "" } else { "s" };
+            return Err(anyhow!(
+                "Requested extra{s} not found: {}",
+                unused_extras.iter().join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Find the environments to install into: each of the given environments or, if none are given,
+/// the environment for the requested (or discovered) Python interpreter.
+///
+/// A single resolution is applied to every environment, so the environments must share the same
+/// markers (i.e., the same Python version and platform).
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) fn find_environments(
+    environments: &[PathBuf],
+    python: Option<&str>,
+    system: bool,
+    venv_dir: Option<&Path>,
+    target: Option<Target>,
+    break_system_packages: bool,
+    cache: &Cache,
+) -> anyhow::Result<Vec<PythonEnvironment>> {
+    let venvs = if environments.is_empty() {
+        // Detect the current Python interpreter.
+        let system = if system {
+            SystemPython::Required
+        } else {
+            SystemPython::Explicit
+        };
+        let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
+
+        // Apply any `--target` directory.
+        let venv = if let Some(target) = target {
+            debug!(
+                "Using `--target` directory at {}",
+                target.root().user_display()
+            );
+            target.init()?;
+            venv.with_target(target)
+        } else {
+            venv
+        };
+
+        vec![venv]
+    } else {
+        environments
+            .iter()
+            .map(|root| PythonEnvironment::from_root(root, cache))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    for venv in &venvs {
+        debug!(
+            "Using Python {} environment at {}",
+            venv.interpreter().python_version(),
+            venv.python_executable().user_display().cyan()
+        );
+
+        // If the environment is externally managed, abort.
+        if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+            if break_system_packages {
+                debug!("Ignoring externally managed environment due to `--break-system-packages`");
+            } else {
+                return if let Some(error) = externally_managed.into_error() {
+                    Err(anyhow!(
+                        "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
+                        venv.root().user_display().cyan(),
+                        textwrap::indent(&error, "  ").green(),
+                    ))
+                } else {
+                    Err(anyhow!(
+                        "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
+                        venv.root().user_display().cyan()
+                    ))
+                };
+            }
+        }
+    }
+
+    // Validate that the same resolution applies to every environment.
+    if let Some((first, rest)) = venvs.split_first() {
+        for venv in rest {
+            if venv.interpreter().markers() != first.interpreter().markers() {
                 return Err(anyhow!(
-                    "Requested extra{s} not found: {}",
-                    unused_extras.iter().join(", ")
-                )
-                .into());
+                    "The environments at {} (Python {}) and {} (Python {}) use different interpreters, so the same requirements can't be installed into both",
+                    first.root().user_display().cyan(),
+                    first.interpreter().python_version(),
+                    venv.root().user_display().cyan(),
+                    venv.interpreter().python_version(),
+                ));
             }
         }
     }
 
-    Ok(spec)
+    Ok(venvs)
 }
 
 /// Resolve a set of requirements, similar to running `pip compile`.
@@ -115,6 +242,9 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     hasher: &HashStrategy,
     reinstall: &Reinstall,
     upgrade: &Upgrade,
+    upgrade_strategy: UpgradeStrategy,
+    policy: &PackagePolicy,
+    prefetch: &PrefetchStrategy,
     interpreter: &Interpreter,
     tags: &Tags,
     markers: &MarkerEnvironment,
@@ -124,8 +254,12 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     build_dispatch: &BuildDispatch<'_>,
     concurrency: Concurrency,
     options: Options,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ResolutionGraph, Error> {
+    let messages = printer.messages();
+
+    let _cancellable = Cancellable::enter();
     let start = std::time::Instant::now();
 
     // Resolve the requirements from the provided sources.
@@ -153,7 +287,9 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
                 )
                 .with_reporter(ResolverReporter::from(printer))
                 .resolve()
-                .await?,
+                .await?
+                .into_iter()
+                .flat_map(|source_tree| source_tree.requirements),
             );
         }
 
@@ -198,8 +334,21 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
         DependencyMode::Direct => Vec::new(),
     };
 
+    // Limit the upgrade to the direct requirements, if requested.
+    let upgrade = upgrade.clone().with_strategy(
+        upgrade_strategy,
+        requirements
+            .iter()
+            .map(|requirement| requirement.name.clone())
+            .chain(
+                editables
+                    .iter()
+                    .map(|editable| editable.metadata.name.clone()),
+            ),
+    );
+
     // TODO(zanieb): Consider consuming these instead of cloning
-    let exclusions = Exclusions::new(reinstall.clone(), upgrade.clone());
+    let exclusions = Exclusions::new(reinstall.clone(), upgrade);
 
     // Prefer current site packages; filter out packages that are marked for reinstall or upgrade
     let preferences = installed_packages
@@ -244,7 +393,9 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
         editables,
         exclusions,
         lookaheads,
-    );
+    )
+    .with_policy(policy.clone())
+    .with_prefetch(prefetch.clone());
 
     // Resolve the dependencies.
     let resolution = {
@@ -269,21 +420,25 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
             installed_packages,
             DistributionDatabase::new(client, build_dispatch, concurrency.downloads),
         )?
-        .with_reporter(reporter);
+        .with_reporter(reporter)
+        .with_cancellation(cancellation::token());
 
         resolver.resolve().await?
     };
 
-    let s = if resolution.len() == 1 { "" } else { "s" };
+    summary.record(Packages::Resolved, resolution.len());
     writeln!(
         printer.stderr(),
         "{}",
-        format!(
-            "Resolved {} in {}",
-            format!("{} package{}", resolution.len(), s).bold(),
-            elapsed(start.elapsed())
-        )
-        .dimmed()
+        messages
+            .format(
+                Message::Resolved,
+                &[
+                    ("packages", &messages.packages(resolution.len()).bold()),
+                    ("elapsed", &elapsed(start.elapsed())),
+                ]
+            )
+            .dimmed()
     )?;
 
     Ok(resolution)
@@ -305,7 +460,12 @@ pub(crate) enum Modifications {
 }
 
 /// Install a set of requirements into the current environment.
-#[allow(clippy::too_many_arguments)]
+///
+/// Returns `true` if the environment was modified (or, in a dry run, would have been).
+///
+/// If `pending` is provided, the modifications are left uncommitted and pushed onto it, such that
+/// the caller can commit them, or roll them back, alongside those of other environments.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn install(
     resolution: &Resolution,
     editables: &[ResolvedEditable],
@@ -314,19 +474,32 @@ pub(crate) async fn install(
     reinstall: &Reinstall,
     no_binary: &NoBinary,
     link_mode: LinkMode,
+    script_options: ScriptOptions,
+    install_filter: &InstallFilter,
+    file_collisions: FileCollisionPolicy,
     compile: bool,
+    compile_options: &CompileOptions,
+    check_imports: bool,
     index_urls: &IndexLocations,
     hasher: &HashStrategy,
     tags: &Tags,
+    ignore_platform_compatibility: bool,
     client: &RegistryClient,
     in_flight: &InFlight,
     concurrency: Concurrency,
     build_dispatch: &BuildDispatch<'_>,
     cache: &Cache,
     venv: &PythonEnvironment,
+    install_hooks: &InstallHooks,
+    allow_external_symlinks: bool,
+    pending: Option<&mut Vec<PendingInstall>>,
     dry_run: bool,
+    summary: &Summary,
     printer: Printer,
-) -> Result<(), Error> {
+) -> Result<bool, Error> {
+    let messages = printer.messages();
+
+    let _cancellable = Cancellable::enter();
     let start = std::time::Instant::now();
 
     // Extract the requirements from the resolution, filtering out any editables that were already
@@ -348,11 +521,16 @@ pub(crate) async fn install(
     // Partition into those that should be linked from the cache (`local`), those that need to be
     // downloaded (`remote`), and those that should be removed (`extraneous`).
     let plan = Planner::with_requirements(&requirements)
+        .with_resolution(resolution)
         .with_editable_requirements(editables)
+        .with_ignore_platform_compatibility(ignore_platform_compatibility)
+        .with_build_commands(build_dispatch.build_commands())
+        .with_prefer_source(build_dispatch.prefer_source())
         .build(
             site_packages,
             reinstall,
             no_binary,
+            build_dispatch.no_build(),
             hasher,
             index_urls,
             cache,
@@ -361,6 +539,13 @@ pub(crate) async fn install(
         )
         .context("Failed to determine installation plan")?;
 
+    // Explain any re-installations, which can otherwise appear arbitrary.
+    for dist_info in &plan.reinstalls {
+        if let Some(reason) = plan.reinstall_reasons.get(dist_info.name()) {
+            debug!("Reinstalling {dist_info}, since {reason}");
+        }
+    }
+
     if dry_run {
         return report_dry_run(resolution, plan, modifications, start, printer);
     }
@@ -369,6 +554,8 @@ pub(crate) async fn install(
         cached,
         remote,
         reinstalls,
+        hash_mismatches,
+        reinstall_reasons,
         extraneous,
     } = plan;
 
@@ -378,20 +565,29 @@ pub(crate) async fn install(
         Modifications::Exact => extraneous,
     };
 
+    // Note any installed distributions that will be replaced due to a hash mismatch.
+    for dist_info in &hash_mismatches {
+        warn_user!(
+            "The installed archive for {dist_info} does not match the required hashes; reinstalling"
+        );
+    }
+
     // Nothing to do.
     if remote.is_empty() && cached.is_empty() && reinstalls.is_empty() && extraneous.is_empty() {
-        let s = if resolution.len() == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Audited {} in {}",
-                format!("{} package{}", resolution.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::Audited,
+                    &[
+                        ("packages", &messages.packages(resolution.len()).bold()),
+                        ("elapsed", &elapsed(start.elapsed())),
+                    ]
+                )
+                .dimmed()
         )?;
-        return Ok(());
+        return Ok(false);
     }
 
     // Map any registry-based requirements back to those returned by the resolver.
@@ -405,46 +601,272 @@ pub(crate) async fn install(
         })
         .collect::<Vec<_>>();
 
+    // Refuse to write through a symlink to a system directory.
+    symlinks::check_environment(venv, allow_external_symlinks)?;
+
+    // Verify that there's enough disk space for the installation, to fail before modifying
+    // anything rather than midway through extraction.
+    if let Some(site_packages) = venv.site_packages().next() {
+        SpaceEstimate::from_plan(&remote)
+            .check(cache.root(), site_packages, link_mode, &cached)
+            .map_err(|err| {
+                let (required, required_unit) = human_readable_bytes(err.required);
+                let (available, available_unit) = human_readable_bytes(err.available);
+                anyhow!(
+                    "Not enough disk space: the installation requires an estimated {} in {}, but only {} is available",
+                    format!("{required:.1}{required_unit}").bold(),
+                    err.paths
+                        .iter()
+                        .map(|path| format!("`{}`", path.user_display().cyan()))
+                        .join(" and "),
+                    format!("{available:.1}{available_unit}").bold(),
+                )
+            })?;
+    }
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         vec![]
     } else {
         let start = std::time::Instant::now();
 
-        let downloader = Downloader::new(
+        let preparer = Preparer::new(
             cache,
             tags,
             hasher,
             DistributionDatabase::new(client, build_dispatch, concurrency.downloads),
         )
-        .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
+        .with_reporter(PrepareReporter::from(printer).with_length(remote.len() as u64))
+        .with_cancellation(cancellation::token());
 
-        let wheels = downloader
-            .download(remote.clone(), in_flight)
+        let wheels = preparer
+            .prepare(remote.clone(), in_flight)
             .await
             .context("Failed to download distributions")?;
 
-        let s = if wheels.len() == 1 { "" } else { "s" };
+        summary.record(Packages::Downloaded, wheels.len());
+        writeln!(
+            printer.stderr(),
+            "{}",
+            messages
+                .format(
+                    Message::Downloaded,
+                    &[
+                        ("packages", &messages.packages(wheels.len()).bold()),
+                        ("elapsed", &elapsed(start.elapsed())),
+                    ]
+                )
+                .dimmed()
+        )?;
+
+        wheels
+    };
+
+    // Detect any scripts or files that would be overwritten by a different package, to fail
+    // before modifying anything rather than silently letting the last wheel win.
+    if !wheels.is_empty() || !cached.is_empty() {
+        // Any installed packages that aren't replaced or removed will retain their files.
+        let site_packages = SitePackages::from_executable(venv)?;
+        let retained = site_packages
+            .iter()
+            .filter(|dist_info| {
+                !wheels
+                    .iter()
+                    .chain(cached.iter())
+                    .any(|dist| dist.name() == dist_info.name())
+                    && !extraneous
+                        .iter()
+                        .chain(reinstalls.iter())
+                        .any(|removed| removed.path() == dist_info.path())
+            })
+            .collect::<Vec<_>>();
+
+        let conflicts =
+            find_script_conflicts(wheels.iter().chain(cached.iter()), retained.iter().copied())
+                .context("Failed to read entry points")?;
+        if !conflicts.is_empty() {
+            if script_options.allow_conflicts {
+                for conflict in &conflicts {
+                    warn_user!("Script {conflict}; it will be overwritten");
+                }
+            } else {
+                return Err(anyhow!(
+                    "Multiple packages provide the same scripts, which would overwrite each other:\n{}\n\nUse `--force` to install anyway.",
+                    conflicts
+                        .iter()
+                        .map(|conflict| format!("  - {conflict}"))
+                        .join("\n")
+                )
+                .into());
+            }
+        }
+
+        if file_collisions != FileCollisionPolicy::Ignore {
+            let collisions = find_file_collisions(
+                wheels.iter().chain(cached.iter()),
+                retained.iter().copied(),
+                venv.site_packages().next().unwrap_or(venv.root()),
+            )
+            .context("Failed to read installed files")?;
+            if !collisions.is_empty() {
+                if file_collisions == FileCollisionPolicy::Error {
+                    return Err(anyhow!(
+                        "Multiple packages provide the same files with different contents:\n{}\n\nUse `--file-collisions warn` to install anyway.",
+                        collisions
+                            .iter()
+                            .map(|collision| format!("  - {collision}"))
+                            .join("\n")
+                    )
+                    .into());
+                }
+                for collision in &collisions {
+                    warn_user!(
+                        "File {collision}, with different contents; the last package to be installed will overwrite it"
+                    );
+                }
+            }
+        }
+    }
+
+    // Run any pre-install hooks, before the environment is modified.
+    if !install_hooks.is_empty(HookEvent::PreInstall) {
+        install_hooks
+            .run(
+                &HookPlan::new(
+                    HookEvent::PreInstall,
+                    venv.root(),
+                    wheels.iter().chain(cached.iter()),
+                    extraneous.iter().chain(reinstalls.iter()),
+                )
+                .with_reasons(&reinstall_reasons),
+            )
+            .await?;
+    }
+
+    // Journal the modifications to the environment, such that a failed or interrupted
+    // installation can be rolled back.
+    let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
+    let mut transaction = Transaction::new(venv)?;
+    let critical = CriticalSection::enter();
+
+    // Apply the modifications, routing any failure through the rollback.
+    if let Err(err) = modify_environment(
+        &mut transaction,
+        &extraneous,
+        &reinstalls,
+        &reinstall_reasons,
+        &wheels,
+        link_mode,
+        script_options,
+        install_filter,
+        venv,
+        install_hooks,
+        summary,
+        printer,
+    )
+    .await
+    {
+        transaction.rollback(&wheels)?;
+        writeln!(
+            printer.stderr(),
+            "{}",
+            "Rolled back the environment to its previous state".dimmed()
+        )?;
+        return Err(err);
+    }
+
+    if let Some(pending) = pending {
+        pending.push(PendingInstall {
+            venv: venv.clone(),
+            transaction,
+            wheels: wheels.clone(),
+        });
+    } else {
+        transaction.commit()?;
+    }
+    drop(critical);
+
+    if compile {
+        compile_bytecode(venv, compile_options, cache, printer).await?;
+    }
+
+    // Import the installed packages, but notify the user of the modifications regardless.
+    let imports = if check_imports && !wheels.is_empty() {
+        check_installed_imports(venv, &wheels, printer).await
+    } else {
+        Ok(())
+    };
+
+    // Notify the user of any environment modifications.
+    report_modifications(wheels, reinstalls, extraneous, printer)?;
+
+    imports?;
+
+    Ok(true)
+}
+
+/// The modifications applied to an environment by [`install`], which have yet to be committed.
+#[derive(Debug)]
+pub(crate) struct PendingInstall {
+    venv: PythonEnvironment,
+    transaction: Transaction,
+    wheels: Vec<CachedDist>,
+}
+
+impl PendingInstall {
+    /// Commit the modifications, discarding the backup of any removed distributions.
+    pub(crate) fn commit(self) -> Result<(), Error> {
+        self.transaction.commit()?;
+        Ok(())
+    }
+
+    /// Roll back the modifications, restoring the environment to its previous state.
+    pub(crate) fn rollback(self, printer: Printer) -> Result<(), Error> {
+        self.transaction.rollback(&self.wheels)?;
         writeln!(
             printer.stderr(),
             "{}",
             format!(
-                "Downloaded {} in {}",
-                format!("{} package{}", wheels.len(), s).bold(),
-                elapsed(start.elapsed())
+                "Rolled back the environment at {} to its previous state",
+                self.venv.root().user_display()
             )
             .dimmed()
         )?;
+        Ok(())
+    }
+}
 
-        wheels
-    };
+/// Apply an installation plan to the environment, journaling any removals in the given
+/// [`Transaction`].
+///
+/// On failure, the caller is responsible for rolling back the transaction.
+#[allow(clippy::too_many_arguments)]
+async fn modify_environment(
+    transaction: &mut Transaction,
+    extraneous: &[InstalledDist],
+    reinstalls: &[InstalledDist],
+    reinstall_reasons: &FxHashMap<PackageName, ReinstallReason>,
+    wheels: &[CachedDist],
+    link_mode: LinkMode,
+    script_options: ScriptOptions,
+    install_filter: &InstallFilter,
+    venv: &PythonEnvironment,
+    install_hooks: &InstallHooks,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<(), Error> {
+    let messages = printer.messages();
 
     // Remove any upgraded or extraneous installations.
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
 
         for dist_info in extraneous.iter().chain(reinstalls.iter()) {
-            match uv_installer::uninstall(dist_info).await {
+            if cancellation::is_cancelled() {
+                return Err(uv_installer::InstallError::Cancelled.into());
+            }
+            transaction.stage(dist_info)?;
+            match uv_installer::uninstall(dist_info, &UninstallOptions::default()).await {
                 Ok(summary) => {
                     debug!(
                         "Uninstalled {} ({} file{}, {} director{})",
@@ -467,67 +889,104 @@ pub(crate) async fn install(
             }
         }
 
-        let s = if extraneous.len() + reinstalls.len() == 1 {
-            ""
-        } else {
-            "s"
-        };
+        summary.record(Packages::Uninstalled, extraneous.len() + reinstalls.len());
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Uninstalled {} in {}",
-                format!("{} package{}", extraneous.len() + reinstalls.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::Uninstalled,
+                    &[
+                        (
+                            "packages",
+                            &messages
+                                .packages(extraneous.len() + reinstalls.len())
+                                .bold()
+                        ),
+                        ("elapsed", &elapsed(start.elapsed())),
+                    ]
+                )
+                .dimmed()
         )?;
+
+        if !install_hooks.is_empty(HookEvent::PostUninstall) {
+            install_hooks
+                .run(
+                    &HookPlan::new(
+                        HookEvent::PostUninstall,
+                        venv.root(),
+                        std::iter::empty(),
+                        extraneous.iter().chain(reinstalls.iter()),
+                    )
+                    .with_reasons(reinstall_reasons),
+                )
+                .await?;
+        }
     }
 
     // Install the resolved distributions.
-    let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
         uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_script_options(script_options)
+            .with_filter(install_filter.clone())
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
-            .install(&wheels)?;
+            .with_cancellation(cancellation::token())
+            .install(wheels)
+            .await?;
 
-        let s = if wheels.len() == 1 { "" } else { "s" };
+        summary.record(Packages::Installed, wheels.len());
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Installed {} in {}",
-                format!("{} package{}", wheels.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::Installed,
+                    &[
+                        ("packages", &messages.packages(wheels.len()).bold()),
+                        ("elapsed", &elapsed(start.elapsed())),
+                    ]
+                )
+                .dimmed()
         )?;
-    }
 
-    if compile {
-        compile_bytecode(venv, cache, printer).await?;
+        if !install_hooks.is_empty(HookEvent::PostInstall) {
+            install_hooks
+                .run(
+                    &HookPlan::new(
+                        HookEvent::PostInstall,
+                        venv.root(),
+                        wheels.iter(),
+                        extraneous.iter().chain(reinstalls.iter()),
+                    )
+                    .with_reasons(reinstall_reasons),
+                )
+                .await?;
+        }
     }
 
-    // Notify the user of any environment modifications.
-    report_modifications(wheels, reinstalls, extraneous, printer)?;
-
     Ok(())
 }
 
 /// Report on the results of a dry-run installation.
+///
+/// Returns `true` if the installation would modify the environment.
 fn report_dry_run(
     resolution: &Resolution,
     plan: Plan,
     modifications: Modifications,
     start: std::time::Instant,
     printer: Printer,
-) -> Result<(), Error> {
+) -> Result<bool, Error> {
+    let messages = printer.messages();
+
     let Plan {
         cached,
         remote,
         reinstalls,
+        hash_mismatches,
+        reinstall_reasons: _,
         extraneous,
     } = plan;
 
@@ -537,21 +996,34 @@ fn report_dry_run(
         Modifications::Exact => extraneous,
     };
 
+    // Note any installed distributions that will be replaced due to a hash mismatch.
+    for dist_info in &hash_mismatches {
+        warn_user!(
+            "The installed archive for {dist_info} does not match the required hashes; reinstalling"
+        );
+    }
+
     // Nothing to do.
     if remote.is_empty() && cached.is_empty() && reinstalls.is_empty() && extraneous.is_empty() {
-        let s = if resolution.len() == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Audited {} in {}",
-                format!("{} package{}", resolution.len(), s).bold(),
-                elapsed(start.elapsed())
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::Audited,
+                    &[
+                        ("packages", &messages.packages(resolution.len()).bold()),
+                        ("elapsed", &elapsed(start.elapsed())),
+                    ]
+                )
+                .dimmed()
         )?;
-        writeln!(printer.stderr(), "Would make no changes")?;
-        return Ok(());
+        writeln!(
+            printer.stderr(),
+            "{}",
+            messages.format(Message::WouldMakeNoChanges, &[])
+        )?;
+        return Ok(false);
     }
 
     // Map any registry-based requirements back to those returned by the resolver.
@@ -569,34 +1041,35 @@ fn report_dry_run(
     let wheels = if remote.is_empty() {
         vec![]
     } else {
-        let s = if remote.len() == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Would download {}",
-                format!("{} package{}", remote.len(), s).bold(),
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::WouldDownload,
+                    &[("packages", &messages.packages(remote.len()).bold())]
+                )
+                .dimmed()
         )?;
         remote.clone()
     };
 
     // Remove any upgraded or extraneous installations.
     if !extraneous.is_empty() || !reinstalls.is_empty() {
-        let s = if extraneous.len() + reinstalls.len() == 1 {
-            ""
-        } else {
-            "s"
-        };
         writeln!(
             printer.stderr(),
             "{}",
-            format!(
-                "Would uninstall {}",
-                format!("{} package{}", extraneous.len() + reinstalls.len(), s).bold(),
-            )
-            .dimmed()
+            messages
+                .format(
+                    Message::WouldUninstall,
+                    &[(
+                        "packages",
+                        &messages
+                            .packages(extraneous.len() + reinstalls.len())
+                            .bold()
+                    )]
+                )
+                .dimmed()
         )?;
     }
 
@@ -604,17 +1077,22 @@ fn report_dry_run(
     let installs = wheels.len() + cached.len();
 
     if installs > 0 {
-        let s = if installs == 1 { "" } else { "s" };
         writeln!(
             printer.stderr(),
             "{}",
-            format!("Would install {}", format!("{installs} package{s}").bold()).dimmed()
+            messages
+                .format(
+                    Message::WouldInstall,
+                    &[("packages", &messages.packages(installs).bold())]
+                )
+                .dimmed()
         )?;
     }
 
     // TDOO(charlie): DRY this up with `report_modifications`. The types don't quite line up.
     for event in reinstalls
         .into_iter()
+        .chain(extraneous)
         .map(|distribution| DryRunEvent {
             name: distribution.name().clone(),
             version: distribution.installed_version().to_string(),
@@ -654,7 +1132,7 @@ fn report_dry_run(
         }
     }
 
-    Ok(())
+    Ok(true)
 }
 
 /// Report on any modifications to the Python environment.
@@ -724,6 +1202,215 @@ pub(crate) fn diagnose_resolution(
     Ok(())
 }
 
+/// Warn about any distributions that were resolved from one index, but are also available from
+/// another configured index at a higher version, a common signal of a "dependency confusion"
+/// attack. Packages in the `allow` list are exempt.
+///
+/// Only stable, non-yanked versions on the other indexes are considered. An index that rejects the
+/// query (e.g., due to missing credentials) is an error, since the check can't be completed.
+pub(crate) async fn diagnose_dependency_confusion<'a>(
+    distributions: impl Iterator<Item = &'a ResolvedDist>,
+    allow: &[PackageName],
+    index_locations: &IndexLocations,
+    client: &RegistryClient,
+) -> Result<(), Error> {
+    let indexes = index_locations.indexes().collect::<Vec<_>>();
+    if indexes.len() < 2 {
+        return Ok(());
+    }
+
+    let mut candidates = distributions
+        .filter_map(|dist| {
+            let index = dist.index()?;
+            let VersionOrUrlRef::Version(version) = dist.version_or_url() else {
+                return None;
+            };
+            if allow.contains(dist.name()) {
+                return None;
+            }
+            Some((dist.name(), version, index))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_unstable_by_key(|(name, ..)| *name);
+
+    // Query every other index for each package, regardless of the index strategy.
+    let shadows = futures::future::try_join_all(candidates.iter().map(|(name, version, index)| {
+        let indexes = &indexes;
+        async move {
+            let mut shadows = Vec::new();
+            for other in indexes.iter().filter(|other| **other != *index) {
+                let Some(metadata) = client.simple_index(name, other).await? else {
+                    continue;
+                };
+                let metadata = OwnedArchive::deserialize(&metadata);
+                let latest = metadata
+                    .iter()
+                    .filter(|datum| !datum.version.any_prerelease())
+                    .filter(|datum| !is_yanked(&datum.files))
+                    .map(|datum| &datum.version)
+                    .max();
+                if let Some(latest) = latest {
+                    if latest > *version {
+                        shadows.push((*other, latest.clone()));
+                    }
+                }
+            }
+            Ok::<_, Error>(shadows)
+        }
+    }))
+    .await?;
+
+    for ((name, version, index), shadows) in candidates.iter().zip(shadows) {
+        for (other, latest) in shadows {
+            warn_user!(
+                "`{name}` was resolved to {version} from {}, but a newer version ({latest}) is available from {}. If this is unexpected, the package may be the target of a dependency confusion attack.",
+                index.redacted(),
+                other.redacted(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if every file for a version has been yanked, such that the version can't be
+/// selected.
+fn is_yanked(files: &VersionFiles) -> bool {
+    files
+        .wheels
+        .iter()
+        .map(|wheel| &wheel.file)
+        .chain(files.source_dists.iter().map(|sdist| &sdist.file))
+        .all(|file| file.yanked.as_ref().is_some_and(Yanked::is_yanked))
+}
+
+/// Warn about any registry distributions whose source distribution and wheels disagree on their
+/// dependency metadata (`Requires-Dist` and `Requires-Python`), since such packages resolve
+/// differently depending on which artifact is read.
+///
+/// The check is best-effort: distributions whose metadata can't be retrieved are skipped, as are
+/// those whose source distributions are disallowed from being built (per `no_build`). A source
+/// distribution is only built if its wheel metadata was retrieved, with at most `concurrency`
+/// checks in flight at once.
+pub(crate) async fn diagnose_metadata_consistency<'a, Context: BuildContext>(
+    distributions: impl Iterator<Item = &'a ResolvedDist>,
+    database: &DistributionDatabase<'_, Context>,
+    hasher: &HashStrategy,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    concurrency: usize,
+    printer: Printer,
+) -> Result<(), Error> {
+    // Identify the distributions with both a source distribution and at least one wheel.
+    let mut candidates = distributions
+        .filter_map(|dist| match dist {
+            ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) => {
+                let sdist = wheels.sdist.clone()?;
+                Some((
+                    BuiltDist::Registry(wheels.clone()),
+                    SourceDist::Registry(sdist),
+                ))
+            }
+            ResolvedDist::Installable(Dist::Source(SourceDist::Registry(sdist))) => {
+                if sdist.wheels.is_empty() {
+                    return None;
+                }
+                Some((
+                    BuiltDist::Registry(RegistryBuiltDist {
+                        wheels: sdist.wheels.clone(),
+                        best_wheel_index: 0,
+                        sdist: Some(sdist.clone()),
+                    }),
+                    SourceDist::Registry(sdist.clone()),
+                ))
+            }
+            _ => None,
+        })
+        .filter(|(_, sdist)| {
+            if no_build.no_build_package(sdist.name(), no_binary) {
+                debug!("Skipping metadata consistency check for {sdist}: building is disabled");
+                false
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_unstable_by(|(a, _), (b, _)| a.name().cmp(b.name()));
+
+    let diffs = futures::stream::iter(candidates.iter().map(|(wheel, sdist)| async move {
+        let wheel_metadata = match database.get_wheel_metadata(wheel, hasher.get(wheel)).await {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                debug!("Skipping metadata consistency check for {sdist}: {err}");
+                return None;
+            }
+        };
+        let sdist_metadata = match database
+            .build_wheel_metadata(&BuildableSource::Dist(sdist), hasher.get(sdist))
+            .await
+        {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                debug!("Skipping metadata consistency check for {sdist}: {err}");
+                return None;
+            }
+        };
+        Some(MetadataDiff::new(
+            &wheel_metadata.metadata,
+            &sdist_metadata.metadata,
+        ))
+    }))
+    .buffered(concurrency)
+    .collect::<Vec<_>>()
+    .await;
+
+    for ((wheel, _), diff) in candidates.iter().zip(diffs) {
+        let Some(diff) = diff.filter(|diff| !diff.is_empty()) else {
+            continue;
+        };
+        writeln!(
+            printer.stderr(),
+            "{}{} {}",
+            printer
+                .messages()
+                .format(Message::Warning, &[])
+                .yellow()
+                .bold(),
+            ":".bold(),
+            format!("The source distribution and wheels for `{wheel}` have inconsistent metadata:")
+                .bold()
+        )?;
+        if let Some((wheel_python, sdist_python)) = &diff.requires_python {
+            let display = |requires_python: &Option<VersionSpecifiers>| {
+                requires_python.as_ref().map_or_else(
+                    || "(none)".to_string(),
+                    |specifiers| format!("`{specifiers}`"),
+                )
+            };
+            writeln!(
+                printer.stderr(),
+                "  Requires-Python: {} (wheel), {} (source distribution)",
+                display(wheel_python),
+                display(sdist_python)
+            )?;
+        }
+        for requirement in &diff.requires_dist_removed {
+            writeln!(
+                printer.stderr(),
+                "  Requires-Dist only in the wheel: `{requirement}`"
+            )?;
+        }
+        for requirement in &diff.requires_dist_added {
+            writeln!(
+                printer.stderr(),
+                "  Requires-Dist only in the source distribution: `{requirement}`"
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Report any diagnostics on installed distributions in the Python environment.
 pub(crate) fn diagnose_environment(
     resolution: &Resolution,
@@ -754,9 +1441,18 @@ pub(crate) enum Error {
     #[error(transparent)]
     Resolve(#[from] uv_resolver::ResolveError),
 
+    #[error(transparent)]
+    Install(#[from] uv_installer::InstallError),
+
     #[error(transparent)]
     Uninstall(#[from] uv_installer::UninstallError),
 
+    #[error(transparent)]
+    Hook(#[from] uv_installer::HookError),
+
+    #[error(transparent)]
+    Transaction(#[from] uv_installer::TransactionError),
+
     #[error(transparent)]
     Hash(#[from] uv_types::HashStrategyError),
 
@@ -769,6 +1465,9 @@ pub(crate) enum Error {
     #[error(transparent)]
     Lookahead(#[from] uv_requirements::LookaheadError),
 
+    #[error(transparent)]
+    Client(#[from] uv_client::Error),
+
     #[error(transparent)]
     ParsedUrl(Box<distribution_types::ParsedUrlError>),
 
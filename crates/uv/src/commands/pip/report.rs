@@ -0,0 +1,421 @@
+use std::collections::VecDeque;
+use std::path::Path;
+
+use anyhow::Result;
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{
+    DistributionMetadata, Name, ParsedUrl, Resolution, ResolvedDist, UnresolvedRequirement,
+    UnresolvedRequirementSpecification, VersionOrUrlRef,
+};
+use pep440_rs::Version;
+use pep508_rs::{MarkerEnvironment, MarkerExpression, MarkerTree};
+use pypi_types::{ArchiveInfo, DirectUrl, Metadata23, Yanked};
+use uv_installer::SitePackages;
+use uv_normalize::{ExtraName, PackageName};
+use uv_resolver::{InMemoryIndex, MetadataResponse};
+
+/// The requirements that were requested directly (i.e., on the command line or in a requirements
+/// file), as opposed to those that were installed as dependencies.
+#[derive(Debug, Default)]
+pub(crate) struct RequestedRequirements {
+    /// The extras requested for each named requirement.
+    names: FxHashMap<PackageName, Vec<ExtraName>>,
+    /// The extras requested for each unnamed (direct URL) requirement.
+    urls: FxHashMap<Url, Vec<ExtraName>>,
+}
+
+impl RequestedRequirements {
+    /// Collect the directly requested requirements.
+    pub(crate) fn from_requirements(requirements: &[UnresolvedRequirementSpecification]) -> Self {
+        let mut requested = Self::default();
+        for entry in requirements {
+            let extras = match &entry.requirement {
+                UnresolvedRequirement::Named(requirement) => {
+                    requested.names.entry(requirement.name.clone()).or_default()
+                }
+                UnresolvedRequirement::Unnamed(requirement) => {
+                    requested.urls.entry(requirement.url.to_url()).or_default()
+                }
+            };
+            extras.extend(entry.requirement.extras().iter().cloned());
+        }
+        requested
+    }
+
+    /// Return the extras requested for the given distribution, if it was requested directly.
+    fn get(&self, dist: &ResolvedDist) -> Option<Vec<ExtraName>> {
+        let extras = self.names.get(dist.name()).or_else(|| {
+            let VersionOrUrlRef::Url(url) = dist.version_or_url() else {
+                return None;
+            };
+            self.urls.get(&url.to_url())
+        })?;
+        Some(extras.iter().sorted().dedup().cloned().collect())
+    }
+}
+
+/// A requirement that was skipped, since its markers don't match the target environment.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct SkippedRequirement {
+    /// The name of the requirement or, for an unnamed requirement, its URL.
+    requirement: String,
+    marker: String,
+    /// The marker expressions that evaluated to `false`, and so decided to skip the requirement.
+    unsatisfied: Vec<String>,
+    /// The package that declared the requirement, if it's a dependency rather than a directly
+    /// requested requirement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required_by: Option<PackageName>,
+}
+
+impl SkippedRequirement {
+    /// Collect the directly requested requirements whose markers don't match the target
+    /// environment, logging each (along with the expressions that decided it).
+    pub(crate) fn from_requirements(
+        requirements: &[UnresolvedRequirementSpecification],
+        markers: &MarkerEnvironment,
+    ) -> Vec<Self> {
+        let mut skipped = Vec::new();
+        for entry in requirements {
+            let Some(marker) = entry.requirement.marker() else {
+                continue;
+            };
+            let unsatisfied = marker.unsatisfied_expressions(markers, &[]);
+            if unsatisfied.is_empty() {
+                continue;
+            }
+
+            let requirement = match &entry.requirement {
+                UnresolvedRequirement::Named(requirement) => requirement.name.to_string(),
+                UnresolvedRequirement::Unnamed(requirement) => requirement.url.to_string(),
+            };
+            skipped.push(Self::new(requirement, marker, &unsatisfied, None));
+        }
+        skipped
+    }
+
+    /// Collect the dependencies of the directly requested distributions in the [`Resolution`],
+    /// transitively, whose markers don't match the target environment.
+    ///
+    /// Dependencies are read from the metadata in the [`InMemoryIndex`], and so are omitted for
+    /// distributions whose metadata wasn't fetched during resolution.
+    pub(crate) fn from_resolution(
+        resolution: &Resolution,
+        index: &InMemoryIndex,
+        requested: &RequestedRequirements,
+        markers: &MarkerEnvironment,
+    ) -> Vec<Self> {
+        let dists = resolution
+            .distributions()
+            .map(|dist| (dist.name(), dist))
+            .collect::<FxHashMap<_, _>>();
+        let roots = resolution
+            .distributions()
+            .filter_map(|dist| Some((dist.name().clone(), requested.get(dist)?)));
+        Self::from_dependencies(roots, markers, |name| {
+            let dist = dists.get(name)?;
+            let response = index.distributions().get(&dist.version_id())?;
+            match &*response {
+                MetadataResponse::Found(archive) => Some(archive.metadata.clone()),
+                _ => None,
+            }
+        })
+    }
+
+    /// Collect the dependencies of the directly requested packages in an environment that already
+    /// satisfies the requirements, transitively, whose markers don't match the target
+    /// environment.
+    pub(crate) fn from_site_packages(
+        requirements: &[UnresolvedRequirementSpecification],
+        site_packages: &SitePackages,
+        markers: &MarkerEnvironment,
+    ) -> Vec<Self> {
+        let roots = requirements.iter().filter_map(|entry| {
+            let UnresolvedRequirement::Named(requirement) = &entry.requirement else {
+                return None;
+            };
+            if !requirement.evaluate_markers(Some(markers), &[]) {
+                return None;
+            }
+            Some((requirement.name.clone(), requirement.extras.clone()))
+        });
+        Self::from_dependencies(roots, markers, |name| {
+            site_packages.get_packages(name).first()?.metadata().ok()
+        })
+    }
+
+    /// Walk the dependencies of the given packages (with the given extras enabled), collecting
+    /// those whose markers don't match the target environment.
+    ///
+    /// Only the dependencies of packages that apply are followed, via their `metadata`.
+    /// Dependencies that are conditional on an extra that wasn't enabled are omitted, as they
+    /// don't apply in any environment.
+    fn from_dependencies(
+        roots: impl IntoIterator<Item = (PackageName, Vec<ExtraName>)>,
+        markers: &MarkerEnvironment,
+        metadata: impl Fn(&PackageName) -> Option<Metadata23>,
+    ) -> Vec<Self> {
+        let mut skipped = Vec::new();
+        let mut seen = FxHashSet::default();
+        let mut queue = VecDeque::from_iter(roots);
+        while let Some((name, mut extras)) = queue.pop_front() {
+            extras.sort_unstable();
+            extras.dedup();
+            if !seen.insert((name.clone(), extras.clone())) {
+                continue;
+            }
+            let Some(Metadata23 { requires_dist, .. }) = metadata(&name) else {
+                continue;
+            };
+
+            for requirement in &requires_dist {
+                let Some(marker) = requirement.marker.as_ref() else {
+                    queue.push_back((requirement.name.clone(), requirement.extras.clone()));
+                    continue;
+                };
+                let unsatisfied = marker.unsatisfied_expressions(markers, &extras);
+                if unsatisfied.is_empty() {
+                    queue.push_back((requirement.name.clone(), requirement.extras.clone()));
+                    continue;
+                }
+                if unsatisfied
+                    .iter()
+                    .any(|expression| matches!(expression, MarkerExpression::Extra { .. }))
+                {
+                    continue;
+                }
+
+                let entry = Self::new(
+                    requirement.name.to_string(),
+                    marker,
+                    &unsatisfied,
+                    Some(&name),
+                );
+                if !skipped.contains(&entry) {
+                    skipped.push(entry);
+                }
+            }
+        }
+        skipped
+    }
+
+    /// Create a [`SkippedRequirement`], logging it (along with the expressions that decided it).
+    fn new(
+        requirement: String,
+        marker: &MarkerTree,
+        unsatisfied: &[&MarkerExpression],
+        required_by: Option<&PackageName>,
+    ) -> Self {
+        let unsatisfied = unsatisfied
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if let Some(required_by) = required_by {
+            debug!(
+                "Skipping {requirement} (required by {required_by}), since its markers don't match the target environment (unsatisfied: `{}`)",
+                unsatisfied.join("`, `")
+            );
+        } else {
+            debug!(
+                "Skipping {requirement}, since its markers don't match the target environment (unsatisfied: `{}`)",
+                unsatisfied.join("`, `")
+            );
+        }
+        Self {
+            requirement,
+            marker: marker.to_string(),
+            unsatisfied,
+            required_by: required_by.cloned(),
+        }
+    }
+}
+
+/// A report of the distributions to be installed, compatible with `pip install --report`.
+///
+/// See: <https://pip.pypa.io/en/stable/reference/installation-report/>
+#[derive(Debug, Serialize)]
+pub(crate) struct InstallReport<'a> {
+    version: &'static str,
+    /// Tools that consume pip's reports expect a `pip_version`; we report the version of uv.
+    pip_version: String,
+    install: Vec<InstallReportItem>,
+    /// An extension to pip's format: the requirements that were skipped, since their markers don't
+    /// match the target environment.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    skipped: &'a [SkippedRequirement],
+    environment: &'a MarkerEnvironment,
+}
+
+/// A distribution to be installed, as reported by [`InstallReport`].
+#[derive(Debug, Serialize)]
+struct InstallReportItem {
+    /// The core metadata of the distribution, in its JSON form.
+    metadata: ReportMetadata,
+    /// Whether the distribution was requested via a direct URL, rather than from an index.
+    is_direct: bool,
+    is_yanked: bool,
+    /// Whether the distribution was requested directly, rather than as a dependency.
+    requested: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requested_extras: Option<Vec<ExtraName>>,
+    /// The provenance of the distribution, as a PEP 610 direct URL data structure.
+    download_info: DirectUrl,
+}
+
+/// The JSON form of the core metadata, per PEP 566.
+///
+/// Only the fields relevant to installation are reported.
+#[derive(Debug, Serialize)]
+struct ReportMetadata {
+    metadata_version: &'static str,
+    name: PackageName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    requires_dist: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_python: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    provides_extra: Vec<ExtraName>,
+}
+
+impl<'a> InstallReport<'a> {
+    /// Create a report for the distributions in the [`Resolution`] that will be installed (i.e.,
+    /// excluding those that are already installed).
+    ///
+    /// Metadata is read from the [`InMemoryIndex`]; distributions whose metadata was not fetched
+    /// during resolution (e.g., when reusing a cached resolution) report their name and version
+    /// alone.
+    pub(crate) fn new(
+        resolution: &Resolution,
+        index: &InMemoryIndex,
+        requested: &RequestedRequirements,
+        skipped: &'a [SkippedRequirement],
+        markers: &'a MarkerEnvironment,
+    ) -> Result<Self> {
+        let mut install = Vec::new();
+        for dist in resolution.distributions() {
+            let ResolvedDist::Installable(installable) = dist else {
+                continue;
+            };
+
+            let response = index.distributions().get(&dist.version_id());
+            let metadata = response.as_deref().and_then(|response| match response {
+                MetadataResponse::Found(archive) => Some(archive),
+                _ => None,
+            });
+
+            let file = installable.file();
+            let download_info = match dist.version_or_url() {
+                VersionOrUrlRef::Version(_) => {
+                    let file = file.expect("Registry distributions should have a file");
+                    DirectUrl::ArchiveUrl {
+                        url: file.url.to_url()?.to_string(),
+                        archive_info: ArchiveInfo::from_hashes(&file.hashes),
+                        subdirectory: None,
+                    }
+                }
+                VersionOrUrlRef::Url(url) => {
+                    let parsed_url = ParsedUrl::try_from(url.to_url())?;
+                    let direct_url = DirectUrl::try_from(&parsed_url)?;
+                    match direct_url {
+                        DirectUrl::LocalDirectory { url, mut dir_info } => {
+                            if installable.is_editable() {
+                                dir_info.editable = Some(true);
+                            }
+                            DirectUrl::LocalDirectory { url, dir_info }
+                        }
+                        direct_url => direct_url.with_hashes(
+                            metadata
+                                .map(|archive| archive.hashes.as_slice())
+                                .unwrap_or_default(),
+                        ),
+                    }
+                }
+            };
+
+            let requested_extras = requested.get(dist);
+            install.push(InstallReportItem {
+                metadata: match metadata {
+                    Some(archive) => ReportMetadata::from(&archive.metadata),
+                    None => ReportMetadata::from_dist(dist.name(), installable.version()),
+                },
+                is_direct: matches!(dist.version_or_url(), VersionOrUrlRef::Url(_)),
+                is_yanked: file
+                    .and_then(|file| file.yanked.as_ref())
+                    .is_some_and(Yanked::is_yanked),
+                requested: requested_extras.is_some(),
+                requested_extras: requested_extras.filter(|extras| !extras.is_empty()),
+                download_info,
+            });
+        }
+
+        Ok(Self {
+            install,
+            ..Self::empty(skipped, markers)
+        })
+    }
+
+    /// Create a report for an environment that already satisfies the requirements, such that
+    /// nothing will be installed.
+    pub(crate) fn empty(skipped: &'a [SkippedRequirement], markers: &'a MarkerEnvironment) -> Self {
+        Self {
+            version: "1",
+            pip_version: env!("CARGO_PKG_VERSION").to_string(),
+            install: Vec::new(),
+            skipped,
+            environment: markers,
+        }
+    }
+
+    /// Write the report to the given path, or to stdout if the path is `-`.
+    ///
+    /// The report is written to stdout regardless of the verbosity (e.g., `--quiet`), as it's the
+    /// requested output rather than a progress message.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let report = serde_json::to_string_pretty(self)?;
+        if path == Path::new("-") {
+            anstream::println!("{report}");
+        } else {
+            fs_err::write(path, format!("{report}\n"))?;
+        }
+        Ok(())
+    }
+}
+
+impl ReportMetadata {
+    /// Create metadata from the name and version of a distribution alone, for use when its core
+    /// metadata is unavailable.
+    fn from_dist(name: &PackageName, version: Option<&Version>) -> Self {
+        Self {
+            metadata_version: "2.3",
+            name: name.clone(),
+            version: version.cloned(),
+            requires_dist: Vec::new(),
+            requires_python: None,
+            provides_extra: Vec::new(),
+        }
+    }
+}
+
+impl From<&Metadata23> for ReportMetadata {
+    fn from(metadata: &Metadata23) -> Self {
+        Self {
+            metadata_version: "2.3",
+            name: metadata.name.clone(),
+            version: Some(metadata.version.clone()),
+            requires_dist: metadata
+                .requires_dist
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            requires_python: metadata.requires_python.as_ref().map(ToString::to_string),
+            provides_extra: metadata.provides_extras.clone(),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use tracing::debug;
+
+use cache_key::digest;
+use distribution_types::{
+    IndexLocations, InstalledMetadata, Name, Requirement, RequirementSource, Resolution,
+    ResolvedDist, UnresolvedRequirement, UnresolvedRequirementSpecification,
+};
+use pep508_rs::MarkerEnvironment;
+use platform_tags::Tags;
+use uv_cache::{Cache, CacheBucket, CacheEntry};
+use uv_configuration::{NoBinary, NoBuild, PreferBinary, PreferSource};
+use uv_installer::SitePackages;
+use uv_resolver::{Lock, Options, PackagePolicy, ResolutionGraph};
+
+/// A resolution stored in the cache, keyed by the inputs to the resolver, such that repeated
+/// invocations with unchanged inputs can skip resolution entirely.
+#[derive(Debug)]
+pub(crate) struct ResolutionCache {
+    entry: CacheEntry,
+}
+
+/// The inputs to a resolution, from which a [`ResolutionCache`] key is derived.
+#[derive(Debug)]
+pub(crate) struct ResolutionInputs<'a> {
+    pub(crate) requirements: &'a [UnresolvedRequirementSpecification],
+    pub(crate) constraints: &'a [Requirement],
+    pub(crate) overrides: &'a [UnresolvedRequirementSpecification],
+    pub(crate) index_locations: &'a IndexLocations,
+    pub(crate) markers: &'a MarkerEnvironment,
+    pub(crate) tags: &'a Tags,
+    pub(crate) options: Options,
+    pub(crate) no_binary: &'a NoBinary,
+    pub(crate) no_build: &'a NoBuild,
+    pub(crate) prefer_source: &'a PreferSource,
+    pub(crate) prefer_binary: &'a PreferBinary,
+    pub(crate) package_policy: &'a PackagePolicy,
+    pub(crate) site_packages: &'a SitePackages,
+}
+
+impl ResolutionCache {
+    /// Determine the cache entry for the given resolver inputs.
+    ///
+    /// Returns `None` if the inputs can't be cached, i.e., if any requirement refers to a source
+    /// whose contents may change without its specifier changing (like a local directory or a Git
+    /// branch).
+    pub(crate) fn from_inputs(inputs: &ResolutionInputs, cache: &Cache) -> Option<Self> {
+        let cacheable = |requirement: &UnresolvedRequirementSpecification| {
+            matches!(
+                &requirement.requirement,
+                UnresolvedRequirement::Named(Requirement {
+                    source: RequirementSource::Registry { .. },
+                    ..
+                })
+            )
+        };
+        if !inputs.requirements.iter().all(cacheable)
+            || !inputs.overrides.iter().all(cacheable)
+            || !inputs
+                .constraints
+                .iter()
+                .all(|constraint| matches!(constraint.source, RequirementSource::Registry { .. }))
+        {
+            return None;
+        }
+
+        let mut key = String::new();
+        for requirement in inputs.requirements {
+            let _ = writeln!(
+                key,
+                "requirement: {} {}",
+                requirement.requirement,
+                requirement.hashes.iter().sorted().join(" ")
+            );
+        }
+        for constraint in inputs.constraints {
+            let _ = writeln!(key, "constraint: {constraint}");
+        }
+        for requirement in inputs.overrides {
+            let _ = writeln!(key, "override: {}", requirement.requirement);
+        }
+        for dist in inputs
+            .site_packages
+            .iter()
+            .map(|dist| format!("{}=={}", dist.name(), dist.installed_version()))
+            .sorted()
+        {
+            let _ = writeln!(key, "installed: {dist}");
+        }
+        let _ = writeln!(key, "index-locations: {:?}", inputs.index_locations);
+        let _ = writeln!(key, "markers: {:?}", inputs.markers);
+        let _ = writeln!(key, "tags: {}", inputs.tags);
+        let _ = writeln!(key, "options: {:?}", inputs.options);
+        let _ = writeln!(key, "no-binary: {:?}", inputs.no_binary);
+        let _ = writeln!(key, "no-build: {:?}", inputs.no_build);
+        let _ = writeln!(key, "prefer-source: {:?}", inputs.prefer_source);
+        let _ = writeln!(key, "prefer-binary: {:?}", inputs.prefer_binary);
+        let _ = writeln!(key, "package-policy: {:?}", inputs.package_policy);
+
+        Some(Self {
+            entry: cache.entry(
+                CacheBucket::Resolutions,
+                "",
+                format!("{}.toml", digest(&key)),
+            ),
+        })
+    }
+
+    /// Read the cached resolution, if any.
+    pub(crate) async fn read(
+        &self,
+        markers: &MarkerEnvironment,
+        tags: &Tags,
+    ) -> Option<Resolution> {
+        let encoded = match fs_err::tokio::read_to_string(self.entry.path()).await {
+            Ok(encoded) => encoded,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                debug!("Failed to read cached resolution: {err}");
+                return None;
+            }
+        };
+        match toml::from_str::<Lock>(&encoded) {
+            Ok(lock) => {
+                debug!(
+                    "Using cached resolution from: {}",
+                    self.entry.path().display()
+                );
+                Some(lock.to_flat_resolution(markers, tags))
+            }
+            Err(err) => {
+                debug!("Ignoring invalid cached resolution: {err}");
+                None
+            }
+        }
+    }
+
+    /// Write a resolution to the cache.
+    ///
+    /// Resolutions that include already-installed distributions aren't cached, since they can't
+    /// be represented in the `uv.lock` format.
+    pub(crate) async fn write(&self, resolution: &ResolutionGraph) -> anyhow::Result<()> {
+        if resolution
+            .distributions()
+            .any(|(dist, _)| matches!(dist, ResolvedDist::Installed(_)))
+        {
+            debug!("Skipping resolution cache for resolution with installed distributions");
+            return Ok(());
+        }
+        let lock = resolution.lock()?;
+        let encoded = toml::to_string_pretty(&lock)?;
+        fs_err::tokio::create_dir_all(self.entry.dir()).await?;
+        uv_fs::write_atomic(self.entry.path(), encoded).await?;
+        Ok(())
+    }
+}
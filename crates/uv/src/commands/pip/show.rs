@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::Result;
 use itertools::{Either, Itertools};
@@ -6,14 +7,15 @@ use owo_colors::OwoColorize;
 use rustc_hash::FxHashMap;
 use tracing::debug;
 
-use distribution_types::{Diagnostic, Name};
+use distribution_types::{Diagnostic, InstalledDist, Name};
 use uv_cache::Cache;
 use uv_fs::Simplified;
-use uv_installer::SitePackages;
+use uv_installer::{DynamicMetadata, SitePackages};
 use uv_interpreter::{PythonEnvironment, SystemPython};
 use uv_normalize::PackageName;
 
 use crate::commands::ExitStatus;
+use crate::messages::Message;
 use crate::printer::Printer;
 
 /// Show information about one or more installed packages.
@@ -22,6 +24,7 @@ pub(crate) fn pip_show(
     strict: bool,
     python: Option<&str>,
     system: bool,
+    venv_dir: Option<&Path>,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -31,7 +34,11 @@ pub(crate) fn pip_show(
             writeln!(
                 printer.stderr(),
                 "{}{} Please provide a package name or names.",
-                "warning".yellow().bold(),
+                printer
+                    .messages()
+                    .format(Message::Warning, &[])
+                    .yellow()
+                    .bold(),
                 ":".bold(),
             )?;
         }
@@ -44,7 +51,7 @@ pub(crate) fn pip_show(
     } else {
         SystemPython::Allowed
     };
-    let venv = PythonEnvironment::find(python, system, cache)?;
+    let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -76,9 +83,16 @@ pub(crate) fn pip_show(
         writeln!(
             printer.stderr(),
             "{}{} Package(s) not found for: {}",
-            "warning".yellow().bold(),
+            printer
+                .messages()
+                .format(Message::Warning, &[])
+                .yellow()
+                .bold(),
             ":".bold(),
-            missing.iter().join(", ").bold()
+            printer
+                .policy()
+                .join_truncated(&missing, "warning: Package(s) not found for: ".len())
+                .bold()
         )?;
     }
 
@@ -159,6 +173,27 @@ pub(crate) fn pip_show(
                 "Editable project location: {}",
                 path.simplified_display()
             )?;
+
+            // Flag any dynamic metadata, which is re-prepared whenever the editable is installed.
+            let dynamic = DynamicMetadata::from_source_tree(&path);
+            if dynamic != DynamicMetadata::Static {
+                writeln!(printer.stdout(), "Dynamic: {dynamic}")?;
+            }
+        }
+
+        // If installed from a direct URL, print its provenance, as recorded in `direct_url.json`.
+        if let InstalledDist::Url(dist) = distribution {
+            if !dist.editable {
+                writeln!(printer.stdout(), "Direct URL: {}", dist.url)?;
+                let hashes = distribution.hashes();
+                if !hashes.is_empty() {
+                    writeln!(
+                        printer.stdout(),
+                        "Direct URL hashes: {}",
+                        hashes.iter().join(", ")
+                    )?;
+                }
+            }
         }
 
         // If available, print the requirements.
@@ -197,7 +232,11 @@ pub(crate) fn pip_show(
             writeln!(
                 printer.stderr(),
                 "{}{} {}",
-                "warning".yellow().bold(),
+                printer
+                    .messages()
+                    .format(Message::Warning, &[])
+                    .yellow()
+                    .bold(),
                 ":".bold(),
                 diagnostic.message().bold()
             )?;
@@ -1,38 +1,50 @@
 use std::borrow::Cow;
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
 
-use anstream::eprint;
 use anyhow::Result;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, Resolution};
+use distribution_types::{FlatIndexLocation, IndexLocations, Resolution};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::{InstallFilter, ScriptOptions};
 use platform_tags::Tags;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    BaseClientBuilder, Connectivity, FlatIndexClient, NetworkTimeouts, RegistryClientBuilder,
+    ResponseLimits,
+};
 use uv_configuration::{
-    Concurrency, ConfigSettings, IndexStrategy, NoBinary, NoBuild, PreviewMode, Reinstall,
-    SetupPyStrategy, Upgrade,
+    BuildCommands, CompileOptions, Concurrency, ConfigSettings, FileCollisionPolicy, HostOverride,
+    HttpVersion, IndexRateLimit, IndexStrategy, NoBinary, NoBuild, PreferBinary, PreferSource,
+    PreviewMode, Reinstall, SetupPyStrategy, TrustedHost, Upgrade, UpgradeStrategy,
 };
-use uv_configuration::{KeyringProviderType, TargetTriple};
+use uv_configuration::{KeyringProviderType, PackagePatches, Patches, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::SitePackages;
-use uv_interpreter::{PythonEnvironment, PythonVersion, SystemPython, Target};
+use uv_installer::{InstallHooks, SitePackages};
+use uv_interpreter::{PythonEnvironment, PythonVersion, Target};
+use uv_normalize::PackageName;
 use uv_requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 use uv_resolver::{
-    DependencyMode, ExcludeNewer, FlatIndex, InMemoryIndex, OptionsBuilder, PreReleaseMode,
-    ResolutionMode,
+    DependencyMode, ExcludeNewer, FlatIndex, InMemoryIndex, OptionsBuilder, PackagePolicy,
+    PreReleaseMode, PrefetchStrategy, ResolutionLimits, ResolutionMode,
 };
 use uv_types::{BuildIsolation, HashStrategy, InFlight};
+use uv_warnings::warn_user;
 
+use crate::commands::pip::header::CompiledHeader;
 use crate::commands::pip::operations;
 use crate::commands::pip::operations::Modifications;
+use crate::commands::pip::report::{InstallReport, RequestedRequirements, SkippedRequirement};
 use crate::commands::ExitStatus;
 use crate::editables::ResolvedEditables;
+use crate::errors::report_no_solution;
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::summary::Summary;
 
 /// Install a set of locked requirements into the current Python environment.
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -41,10 +53,20 @@ pub(crate) async fn pip_sync(
     constraints: &[RequirementsSource],
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    script_options: ScriptOptions,
+    install_filter: &InstallFilter,
+    file_collisions: FileCollisionPolicy,
     compile: bool,
+    compile_options: CompileOptions,
+    check_imports: bool,
     require_hashes: bool,
+    no_require_hashes: bool,
+    trusted_signers: Option<&Path>,
     index_locations: IndexLocations,
+    local_dist: &[PathBuf],
     index_strategy: IndexStrategy,
+    check_dependency_confusion: bool,
+    dependency_confusion_allow: &[PackageName],
     keyring_provider: KeyringProviderType,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
@@ -52,24 +74,56 @@ pub(crate) async fn pip_sync(
     no_build_isolation: bool,
     no_build: NoBuild,
     no_binary: NoBinary,
+    prefer_source: PreferSource,
+    prefer_binary: PreferBinary,
+    max_build_depth: Option<usize>,
+    build_commands: BuildCommands,
+    patches: &PackagePatches,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    ignore_platform_compatibility: bool,
+    ignore_compiled_environment: bool,
     strict: bool,
     exclude_newer: Option<ExcludeNewer>,
+    package_policy: PackagePolicy,
+    resolution_limits: ResolutionLimits,
+    prefetch: PrefetchStrategy,
     python: Option<String>,
+    environments: &[PathBuf],
     system: bool,
+    venv_dir: Option<&Path>,
     break_system_packages: bool,
     target: Option<Target>,
     concurrency: Concurrency,
+    install_hooks: InstallHooks,
     native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    find_links_recursive: bool,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
     preview: PreviewMode,
     cache: Cache,
     dry_run: bool,
+    report: Option<&Path>,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .keyring(keyring_provider);
 
     // Initialize a few defaults.
@@ -80,11 +134,18 @@ pub(crate) async fn pip_sync(
     let prerelease_mode = PreReleaseMode::default();
     let dependency_mode = DependencyMode::Direct;
 
+    // Read the headers of any files produced by `uv pip compile`.
+    let headers = requirements
+        .iter()
+        .filter_map(CompiledHeader::from_source)
+        .collect::<Vec<_>>();
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
         requirements,
         constraints,
+        constraint_hashes,
         overrides,
         editables,
         source_trees,
@@ -99,7 +160,9 @@ pub(crate) async fn pip_sync(
         requirements,
         constraints,
         overrides,
+        &Patches::from_patches(patches)?,
         &ExtrasSpecification::default(),
+        trusted_signers,
         &client_builder,
         preview,
     )
@@ -108,57 +171,31 @@ pub(crate) async fn pip_sync(
     // Validate that the requirements are non-empty.
     let num_requirements = requirements.len() + source_trees.len() + editables.len();
     if num_requirements == 0 {
-        writeln!(printer.stderr(), "No requirements found")?;
+        writeln!(
+            printer.stderr(),
+            "{}",
+            printer.messages().format(Message::NoRequirements, &[])
+        )?;
         return Ok(ExitStatus::Success);
     }
 
-    // Detect the current Python interpreter.
-    let system = if system {
-        SystemPython::Required
-    } else {
-        SystemPython::Explicit
-    };
-    let venv = PythonEnvironment::find(python.as_deref(), system, &cache)?;
-
-    debug!(
-        "Using Python {} environment at {}",
-        venv.interpreter().python_version(),
-        venv.python_executable().user_display().cyan()
-    );
-
-    // Apply any `--target` directory.
-    let venv = if let Some(target) = target {
-        debug!(
-            "Using `--target` directory at {}",
-            target.root().user_display()
-        );
-        target.init()?;
-        venv.with_target(target)
-    } else {
-        venv
-    };
-
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
-        if break_system_packages {
-            debug!("Ignoring externally managed environment due to `--break-system-packages`");
-        } else {
-            return if let Some(error) = externally_managed.into_error() {
-                Err(anyhow::anyhow!(
-                    "The interpreter at {} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`.",
-                    venv.root().user_display().cyan(),
-                    textwrap::indent(&error, "  ").green(),
-                ))
-            } else {
-                Err(anyhow::anyhow!(
-                    "The interpreter at {} is externally managed. Instead, create a virtual environment with `uv venv`.",
-                    venv.root().user_display().cyan()
-                ))
-            };
-        }
-    }
+    // Find the environments to sync.
+    let venvs = operations::find_environments(
+        environments,
+        python.as_deref(),
+        system,
+        venv_dir,
+        target,
+        break_system_packages,
+        &cache,
+    )?;
+    let _locks = venvs
+        .iter()
+        .map(PythonEnvironment::lock)
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let _lock = venv.lock()?;
+    // Resolve against the first environment, and apply the same resolution to each of them.
+    let venv = &venvs[0];
 
     let interpreter = venv.interpreter();
 
@@ -198,6 +235,33 @@ pub(crate) async fn pip_sync(
         (None, None) => Cow::Borrowed(interpreter.markers()),
     };
 
+    // Validate that the requirements were compiled for the target environment.
+    let mut require_hashes = require_hashes;
+    for header in &headers {
+        if let Some(mismatch) = header.mismatch(&markers) {
+            if ignore_compiled_environment {
+                warn_user!("{mismatch}; installing anyway (`--ignore-compiled-environment`)");
+            } else {
+                return Err(anyhow::anyhow!(
+                    "{mismatch}. Recompile the requirements for the target environment, or pass `--ignore-compiled-environment` to install them anyway."
+                ));
+            }
+        }
+
+        // Enforce the hashes in any file compiled with `--generate-hashes`, unless hash-checking
+        // was explicitly disabled.
+        if header.generate_hashes() && !require_hashes {
+            if no_require_hashes {
+                debug!("Ignoring hashes from `--generate-hashes`, as hash-checking is disabled");
+            } else {
+                debug!(
+                    "Enabling hash-checking mode for requirements compiled with `--generate-hashes`"
+                );
+                require_hashes = true;
+            }
+        }
+    }
+
     // Collect the set of required hashes.
     let hasher = if require_hashes {
         HashStrategy::from_requirements(
@@ -206,6 +270,12 @@ pub(crate) async fn pip_sync(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
         )?
+        .with_constraints(
+            constraint_hashes
+                .iter()
+                .map(|(constraint, hashes)| (constraint, hashes.as_slice())),
+            Some(&markers),
+        )?
     } else {
         HashStrategy::None
     };
@@ -221,7 +291,16 @@ pub(crate) async fn pip_sync(
 
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
+        .network_recorder(summary.network().clone())
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .index_max_age(index_max_age)
+        .response_limits(response_limits)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -231,15 +310,26 @@ pub(crate) async fn pip_sync(
         .build();
 
     // Resolve the flat indexes from `--find-links`.
-    let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
-        let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &no_binary)
-    };
+    let flat_index =
+        {
+            let client = FlatIndexClient::new(&client, &cache).recursive(find_links_recursive);
+            let entries = client.fetch(index_locations.flat_index()).await?;
+            let local_entries = client
+                .fetch(
+                    local_dist
+                        .iter()
+                        .map(|path| FlatIndexLocation::Path(path.clone()))
+                        .collect::<Vec<_>>()
+                        .iter(),
+                )
+                .await?;
+            FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &no_binary)
+                .with_local_entries(local_entries, &tags, &hasher, &no_build, &no_binary)
+        };
 
     // Determine whether to enable build isolation.
     let build_isolation = if no_build_isolation {
-        BuildIsolation::Shared(&venv)
+        BuildIsolation::Shared(venv)
     } else {
         BuildIsolation::Isolated
     };
@@ -271,19 +361,33 @@ pub(crate) async fn pip_sync(
         &no_binary,
         concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
-
-    // Determine the set of installed packages.
-    let site_packages = SitePackages::from_executable(&venv)?;
+    .with_build_counter(summary.builds().clone())
+    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+    .with_prefer_source(prefer_source.clone())
+    .with_prefer_binary(prefer_binary.clone())
+    .with_max_build_depth(max_build_depth)
+    .with_build_commands(build_commands.clone());
+
+    // Determine the set of installed packages in each environment.
+    let site_packages = venvs
+        .iter()
+        .map(SitePackages::from_executable)
+        .collect::<Result<Vec<_>>>()?;
 
     // Build all editable distributions. The editables are shared between resolution and
-    // installation, and should live for the duration of the command.
+    // installation, and should live for the duration of the command. With multiple environments,
+    // an editable that's up-to-date in the first environment may be missing from the others, so
+    // build them all.
     let editables = ResolvedEditables::resolve(
         editables
             .into_iter()
             .map(ResolvedEditables::from_requirement),
-        &site_packages,
-        reinstall,
+        &site_packages[0],
+        if venvs.len() > 1 {
+            &Reinstall::All
+        } else {
+            reinstall
+        },
         &hasher,
         venv.interpreter(),
         &tags,
@@ -295,12 +399,23 @@ pub(crate) async fn pip_sync(
     )
     .await?;
 
+    // Track the requirements that were requested directly, for the installation report.
+    let requested = if report.is_some() {
+        RequestedRequirements::from_requirements(&requirements)
+    } else {
+        RequestedRequirements::default()
+    };
+
+    // Determine the requirements that don't apply to the target environment.
+    let skipped = SkippedRequirement::from_requirements(&requirements, &markers);
+
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
         .index_strategy(index_strategy)
+        .limits(resolution_limits)
         .build();
 
     let resolution = match operations::resolve(
@@ -311,10 +426,13 @@ pub(crate) async fn pip_sync(
         project,
         &extras,
         &editables,
-        site_packages.clone(),
+        site_packages[0].clone(),
         &hasher,
         reinstall,
         &upgrade,
+        UpgradeStrategy::default(),
+        &package_policy,
+        &prefetch,
         interpreter,
         &tags,
         &markers,
@@ -324,20 +442,36 @@ pub(crate) async fn pip_sync(
         &resolve_dispatch,
         concurrency,
         options,
+        summary,
         printer,
     )
     .await
     {
         Ok(resolution) => Resolution::from(resolution),
         Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            report_no_solution(&err, preview, printer.output_format());
+            return Ok(ExitStatus::ResolutionConflict);
         }
         Err(err) => return Err(err.into()),
     };
 
+    // Write the installation report, which describes the distributions to be installed.
+    if let Some(report) = report {
+        InstallReport::new(&resolution, &index, &requested, &skipped, &markers)?.write(report)?;
+    }
+
+    // If requested, warn about packages that are shadowed by a newer version on another index,
+    // before anything is installed.
+    if check_dependency_confusion {
+        operations::diagnose_dependency_confusion(
+            resolution.distributions(),
+            dependency_confusion_allow,
+            &index_locations,
+            &client,
+        )
+        .await?;
+    }
+
     // Re-initialize the in-flight map.
     let in_flight = InFlight::default();
 
@@ -362,39 +496,82 @@ pub(crate) async fn pip_sync(
             &no_binary,
             concurrency,
         )
+        .with_build_counter(summary.builds().clone())
         .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_prefer_source(prefer_source)
+        .with_prefer_binary(prefer_binary)
+        .with_max_build_depth(max_build_depth)
+        .with_build_commands(build_commands)
     };
 
-    // Sync the environment.
-    operations::install(
-        &resolution,
-        &editables,
-        site_packages,
-        Modifications::Exact,
-        reinstall,
-        &no_binary,
-        link_mode,
-        compile,
-        &index_locations,
-        &hasher,
-        &tags,
-        &client,
-        &in_flight,
-        concurrency,
-        &install_dispatch,
-        &cache,
-        &venv,
-        dry_run,
-        printer,
-    )
-    .await?;
+    // Sync each environment. With multiple environments, the modifications are only committed
+    // once every environment has been synced, such that a failure in any of them leaves all of
+    // them untouched.
+    let mut pending = Vec::with_capacity(venvs.len());
+    for (venv, site_packages) in venvs.iter().zip(site_packages) {
+        if !environments.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                printer.messages().format(
+                    Message::SyncingEnvironment,
+                    &[("path", &venv.root().user_display().cyan())]
+                )
+            )?;
+        }
+
+        let result = operations::install(
+            &resolution,
+            &editables,
+            site_packages,
+            Modifications::Exact,
+            reinstall,
+            &no_binary,
+            link_mode,
+            script_options,
+            install_filter,
+            file_collisions,
+            compile,
+            &compile_options,
+            check_imports,
+            &index_locations,
+            &hasher,
+            &tags,
+            ignore_platform_compatibility,
+            &client,
+            &in_flight,
+            concurrency,
+            &install_dispatch,
+            &cache,
+            venv,
+            &install_hooks,
+            allow_external_symlinks,
+            (venvs.len() > 1).then_some(&mut pending),
+            dry_run,
+            summary,
+            printer,
+        )
+        .await;
+
+        if let Err(err) = result {
+            for pending in pending.into_iter().rev() {
+                pending.rollback(printer)?;
+            }
+            return Err(err.into());
+        }
+    }
+    for pending in pending {
+        pending.commit()?;
+    }
 
     // Notify the user of any resolution diagnostics.
     operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
     // Notify the user of any environment diagnostics.
     if strict && !dry_run {
-        operations::diagnose_environment(&resolution, &venv, printer)?;
+        for venv in &venvs {
+            operations::diagnose_environment(&resolution, venv, printer)?;
+        }
     }
 
     Ok(ExitStatus::Success)
@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::Result;
 use itertools::{Either, Itertools};
@@ -6,36 +7,67 @@ use owo_colors::OwoColorize;
 use tracing::debug;
 
 use distribution_types::{InstalledMetadata, Name, Requirement, UnresolvedRequirement};
+use install_wheel_rs::UninstallOptions;
 use pep508_rs::UnnamedRequirement;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity};
-use uv_configuration::{KeyringProviderType, PreviewMode};
+use uv_client::{BaseClientBuilder, Connectivity, NetworkTimeouts};
+use uv_configuration::{
+    HostOverride, HttpVersion, IndexRateLimit, KeyringProviderType, PreviewMode, TrustedHost,
+};
 use uv_fs::Simplified;
+use uv_installer::{HookEvent, HookPlan, InstallHooks};
 use uv_interpreter::{PythonEnvironment, SystemPython, Target};
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
 
 use crate::commands::{elapsed, ExitStatus};
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::summary::{Packages, Summary};
+use crate::symlinks;
+
+/// The packages that are retained by `--all`, like the seed packages of a virtual environment.
+const PROTECTED_PACKAGES: &[&str] = &["pip", "setuptools", "wheel"];
 
 /// Uninstall packages from the current environment.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub(crate) async fn pip_uninstall(
     sources: &[RequirementsSource],
     python: Option<String>,
     system: bool,
+    venv_dir: Option<&Path>,
     break_system_packages: bool,
     target: Option<Target>,
+    all: bool,
+    dry_run: bool,
     cache: Cache,
     connectivity: Connectivity,
     native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
     preview: PreviewMode,
     keyring_provider: KeyringProviderType,
+    install_hooks: InstallHooks,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
+    let messages = printer.messages();
+
     let start = std::time::Instant::now();
     let client_builder = BaseClientBuilder::new()
+        .network_recorder(summary.network().clone())
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .hermetic(hermetic)
+        .timeouts(timeouts)
+        .host_overrides(host_overrides.to_vec())
+        .trusted_hosts(trusted_hosts.to_vec())
+        .rate_limits(rate_limits.to_vec())
+        .http_version(http_version)
         .keyring(keyring_provider);
 
     // Read all requirements from the provided sources.
@@ -48,7 +80,7 @@ pub(crate) async fn pip_uninstall(
     } else {
         SystemPython::Explicit
     };
-    let venv = PythonEnvironment::find(python.as_deref(), system, &cache)?;
+    let venv = PythonEnvironment::find(python.as_deref(), system, venv_dir, &cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -88,7 +120,10 @@ pub(crate) async fn pip_uninstall(
         }
     }
 
-    let _lock = venv.lock()?;
+    // Refuse to write through a symlink to a system directory.
+    symlinks::check_environment(&venv, allow_external_symlinks)?;
+
+    let _lock = venv.lock(cache.lock_timeout())?;
 
     // Index the current `site-packages` directory.
     let site_packages = uv_installer::SitePackages::from_executable(&venv)?;
@@ -126,7 +161,15 @@ pub(crate) async fn pip_uninstall(
     };
 
     // Map to the local distributions.
-    let distributions = {
+    let distributions = if all {
+        // Uninstall everything, except for the seed packages.
+        let mut distributions = site_packages
+            .iter()
+            .filter(|dist| !PROTECTED_PACKAGES.contains(&dist.name().as_ref()))
+            .collect::<Vec<_>>();
+        distributions.sort_unstable_by_key(|dist| dist.path());
+        distributions
+    } else {
         let mut distributions = Vec::with_capacity(names.len() + urls.len());
 
         // Identify all packages that are installed.
@@ -147,7 +190,12 @@ pub(crate) async fn pip_uninstall(
 
         // Identify all unnamed distributions that are installed.
         for url in &urls {
-            let installed = site_packages.get_urls(url);
+            // If nothing was installed from the URL, it may point to one of the roots of an
+            // editable install (e.g., the `src` directory, rather than the project root).
+            let mut installed = site_packages.get_urls(url);
+            if installed.is_empty() {
+                installed = site_packages.get_editable_roots(url);
+            }
             if installed.is_empty() {
                 writeln!(
                     printer.stderr(),
@@ -177,9 +225,42 @@ pub(crate) async fn pip_uninstall(
         return Ok(ExitStatus::Success);
     }
 
+    let options = UninstallOptions {
+        dry_run,
+        scripts: Some(venv.scripts().to_path_buf()),
+    };
+
+    // In a dry run, report the paths that would be removed for each package.
+    if dry_run {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            messages
+                .format(
+                    Message::WouldUninstall,
+                    &[("packages", &messages.packages(distributions.len()).bold())]
+                )
+                .dimmed()
+        )?;
+        for distribution in distributions {
+            let summary = uv_installer::uninstall(distribution, &options).await?;
+            writeln!(
+                printer.stderr(),
+                " {} {}{}",
+                "-".red(),
+                distribution.name().as_ref().bold(),
+                distribution.installed_version().to_string().dimmed()
+            )?;
+            for path in summary.paths {
+                writeln!(printer.stderr(), "     {}", path.user_display())?;
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
     // Uninstall each package.
     for distribution in &distributions {
-        let summary = uv_installer::uninstall(distribution).await?;
+        let summary = uv_installer::uninstall(distribution, &options).await?;
         debug!(
             "Uninstalled {} ({} file{}, {} director{})",
             distribution.name(),
@@ -190,20 +271,30 @@ pub(crate) async fn pip_uninstall(
         );
     }
 
+    if !install_hooks.is_empty(HookEvent::PostUninstall) {
+        install_hooks
+            .run(&HookPlan::new(
+                HookEvent::PostUninstall,
+                venv.root(),
+                std::iter::empty(),
+                distributions.iter().copied(),
+            ))
+            .await?;
+    }
+
+    summary.record(Packages::Uninstalled, distributions.len());
     writeln!(
         printer.stderr(),
         "{}",
-        format!(
-            "Uninstalled {} in {}",
-            format!(
-                "{} package{}",
-                distributions.len(),
-                if distributions.len() == 1 { "" } else { "s" }
+        messages
+            .format(
+                Message::Uninstalled,
+                &[
+                    ("packages", &messages.packages(distributions.len()).bold()),
+                    ("elapsed", &elapsed(start.elapsed())),
+                ]
             )
-            .bold(),
-            elapsed(start.elapsed())
-        )
-        .dimmed()
+            .dimmed()
     )?;
 
     for distribution in distributions {
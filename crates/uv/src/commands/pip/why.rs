@@ -0,0 +1,187 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::commands::ExitStatus;
+use crate::messages::Message;
+use crate::printer::Printer;
+
+/// Explain why a package is installed, by printing every dependency path from a top-level package
+/// (i.e., a package that isn't required by any other installed package) to it.
+pub(crate) fn pip_why(
+    package: &PackageName,
+    python: Option<&str>,
+    system: bool,
+    venv_dir: Option<&Path>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let system = if system {
+        SystemPython::Required
+    } else {
+        SystemPython::Allowed
+    };
+    let venv = PythonEnvironment::find(python, system, venv_dir, cache)?;
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Determine the markers to use for evaluating requirements.
+    let markers = venv.interpreter().markers();
+
+    let Some(target) = site_packages.get_packages(package).into_iter().next() else {
+        writeln!(
+            printer.stderr(),
+            "{}{} Package not found: {}",
+            printer
+                .messages()
+                .format(Message::Warning, &[])
+                .yellow()
+                .bold(),
+            ":".bold(),
+            package.bold()
+        )?;
+        return Ok(ExitStatus::Failure);
+    };
+
+    // Parse the requirements of every installed package once.
+    let mut requires = FxHashMap::default();
+    for dist in site_packages.iter() {
+        match dist.metadata() {
+            Ok(metadata) => {
+                requires.insert(dist.name(), metadata.requires_dist);
+            }
+            Err(err) => {
+                debug!("Failed to read metadata for {}: {err}", dist.name());
+            }
+        }
+    }
+
+    // Determine the extras that are activated for each package, by propagating the extras
+    // requested by any active requirement until a fixed point is reached.
+    let mut extras: FxHashMap<&PackageName, FxHashSet<ExtraName>> = FxHashMap::default();
+    loop {
+        let mut changed = false;
+        for (name, requirements) in &requires {
+            let activated = extras
+                .get(name)
+                .map(|extras| extras.iter().cloned().collect::<Vec<_>>())
+                .unwrap_or_default();
+            for requirement in requirements {
+                if requirement.extras.is_empty()
+                    || !requirement.evaluate_markers(markers, &activated)
+                {
+                    continue;
+                }
+                let Some(dependency) = site_packages
+                    .get_packages(&requirement.name)
+                    .into_iter()
+                    .next()
+                else {
+                    continue;
+                };
+                let entry = extras.entry(dependency.name()).or_default();
+                for extra in &requirement.extras {
+                    changed |= entry.insert(extra.clone());
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Build the reverse dependency graph, mapping each package to the installed packages that
+    // require it.
+    let mut required_by: FxHashMap<&PackageName, FxHashSet<&PackageName>> = FxHashMap::default();
+    for (name, requirements) in &requires {
+        let activated = extras
+            .get(name)
+            .map(|extras| extras.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for requirement in requirements {
+            if !requirement.evaluate_markers(markers, &activated) {
+                continue;
+            }
+            if let Some(dependency) = site_packages
+                .get_packages(&requirement.name)
+                .into_iter()
+                .next()
+            {
+                required_by
+                    .entry(dependency.name())
+                    .or_default()
+                    .insert(name);
+            }
+        }
+    }
+
+    // Enumerate every path from a top-level package to the target.
+    let mut paths = Vec::new();
+    let mut stack = vec![target.name()];
+    collect_paths(&required_by, &mut stack, &mut paths);
+
+    let versions: FxHashMap<&PackageName, &InstalledDist> = site_packages
+        .iter()
+        .map(|dist| (dist.name(), dist))
+        .collect();
+    for path in paths
+        .into_iter()
+        .map(|path| {
+            path.into_iter()
+                .rev()
+                .map(|name| format!("{name}=={}", versions[name].version()))
+                .join(" -> ")
+        })
+        .sorted()
+    {
+        writeln!(printer.stdout(), "{path}")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Collect every path through the reverse dependency graph, from the last package on the `stack`
+/// to a package that isn't required by any other package. Cycles are skipped.
+fn collect_paths<'a>(
+    required_by: &FxHashMap<&'a PackageName, FxHashSet<&'a PackageName>>,
+    stack: &mut Vec<&'a PackageName>,
+    paths: &mut Vec<Vec<&'a PackageName>>,
+) {
+    let current = stack.last().copied().expect("stack is non-empty");
+    let parents = required_by
+        .get(current)
+        .into_iter()
+        .flatten()
+        .filter(|parent| !stack.contains(parent))
+        .copied()
+        .collect::<Vec<_>>();
+    if parents.is_empty() {
+        paths.push(stack.clone());
+        return;
+    }
+    for parent in parents {
+        stack.push(parent);
+        collect_paths(required_by, stack, paths);
+        stack.pop();
+    }
+}
@@ -0,0 +1,399 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use itertools::Itertools;
+use tracing::debug;
+
+use cache_key::digest;
+use distribution_types::{BuiltDist, Dist, IndexLocations, Resolution, ResolvedDist, SourceDist};
+use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::InstallFilter;
+use uv_cache::{ArchiveTimestamp, Cache, CacheBucket};
+use uv_client::{BaseClientBuilder, RegistryClientBuilder};
+use uv_configuration::{
+    Concurrency, ConfigSettings, FileCollisionPolicy, NoBinary, Patches, PreviewMode,
+    PythonPreference, Reinstall, SetupPyStrategy, Upgrade, UpgradeStrategy,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::{directories, LockedFile, Simplified};
+use uv_installer::{InstallHooks, SitePackages};
+use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use uv_resolver::{FlatIndex, InMemoryIndex, Options, PrefetchStrategy};
+use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
+
+use crate::commands::pip;
+use crate::editables::ResolvedEditables;
+use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::summary::Summary;
+
+/// The `pyvenv.cfg` key under which the cache key of a [`CachedEnvironment`] is recorded.
+const CACHE_KEY: &str = "uv-cache-key";
+
+/// The `pyvenv.cfg` key under which the digest of the requested requirements of a
+/// [`CachedEnvironment`] is recorded.
+const REQUESTED_KEY: &str = "uv-cache-requested";
+
+/// A virtual environment for an ephemeral set of requirements (e.g., for `uv tool run`), stored
+/// in the cache and reused across invocations.
+///
+/// Environments are keyed by the interpreter, the resolved set of pinned distributions (including
+/// the modification time of any local sources), and the build and installation settings, so a
+/// requirement set that resolves to the same pins on the same interpreter is only ever installed
+/// once. When a new environment is created for the same requested requirements (e.g., because a
+/// newer version was published, or a local source was modified), the environments it supersedes
+/// are removed.
+#[derive(Debug)]
+pub(crate) struct CachedEnvironment(PythonEnvironment);
+
+impl CachedEnvironment {
+    /// Get or create a [`CachedEnvironment`] satisfying the given requirements.
+    pub(crate) async fn get_or_create(
+        requirements: &[RequirementsSource],
+        interpreter: Interpreter,
+        settings: &ProjectSharedSettings,
+        client_builder: &BaseClientBuilder<'_>,
+        install_hooks: &InstallHooks,
+        preview: PreviewMode,
+        cache: &Cache,
+        allow_external_symlinks: bool,
+        summary: &Summary,
+        printer: Printer,
+    ) -> Result<Self> {
+        // Identify the requested requirements, to detect superseded environments.
+        let requested = digest(&(
+            interpreter.sys_executable(),
+            requirements
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+        ));
+
+        // Read all requirements from the provided sources.
+        let spec = RequirementsSpecification::from_sources(
+            requirements,
+            &[],
+            &[],
+            &ExtrasSpecification::None,
+            client_builder,
+            preview,
+        )
+        .await?
+        .with_patches(&Patches::from_patches(&settings.patches)?);
+
+        // Determine the tags and markers to use for resolution.
+        let tags = interpreter.tags()?;
+        let markers = interpreter.markers();
+
+        // Initialize the registry client.
+        let client = RegistryClientBuilder::new(cache.clone())
+            .base_client_builder(client_builder.clone())
+            .markers(markers)
+            .platform(interpreter.platform())
+            .build();
+
+        // TODO(charlie): Respect project configuration.
+        let build_isolation = BuildIsolation::default();
+        let concurrency = Concurrency::default();
+        let config_settings = ConfigSettings::default();
+        let extras = ExtrasSpecification::default();
+        let flat_index = FlatIndex::default();
+        let hasher = HashStrategy::default();
+        let in_flight = InFlight::default();
+        let index = InMemoryIndex::default();
+        let index_locations = IndexLocations::default();
+        let link_mode = LinkMode::default();
+        let no_binary = NoBinary::default();
+        let options = Options::default();
+        let reinstall = Reinstall::default();
+        let setup_py = SetupPyStrategy::default();
+        let upgrade = Upgrade::default();
+
+        // Create a build dispatch.
+        let build_dispatch = BuildDispatch::new(
+            &client,
+            cache,
+            &interpreter,
+            &index_locations,
+            &flat_index,
+            &index,
+            &in_flight,
+            setup_py,
+            &config_settings,
+            build_isolation,
+            link_mode,
+            &settings.no_build,
+            &no_binary,
+            concurrency,
+        )
+        .with_build_counter(summary.builds().clone())
+        .with_max_build_depth(settings.max_build_depth);
+
+        // Build all editable distributions. The editables are shared between resolution and
+        // installation, and should live for the duration of the command.
+        let editables = ResolvedEditables::resolve(
+            spec.editables
+                .iter()
+                .cloned()
+                .map(ResolvedEditables::from_requirement),
+            &EmptyInstalledPackages,
+            &reinstall,
+            &hasher,
+            &interpreter,
+            tags,
+            cache,
+            &client,
+            &build_dispatch,
+            concurrency,
+            printer,
+        )
+        .await?;
+
+        // Resolve the requirements, as if into an empty environment.
+        let resolution = Resolution::from(
+            pip::operations::resolve(
+                spec.requirements,
+                spec.constraints,
+                spec.overrides,
+                spec.source_trees,
+                spec.project,
+                &extras,
+                &editables,
+                EmptyInstalledPackages,
+                &hasher,
+                &reinstall,
+                &upgrade,
+                UpgradeStrategy::default(),
+                &settings.package_policy,
+                &PrefetchStrategy::default(),
+                &interpreter,
+                tags,
+                markers,
+                &client,
+                &flat_index,
+                &index,
+                &build_dispatch,
+                concurrency,
+                options,
+                summary,
+                printer,
+            )
+            .await?,
+        );
+
+        // Key the environment by everything that determines its contents.
+        let key = Self::cache_key(&interpreter, &resolution, settings)?;
+
+        let bucket = cache.bucket(CacheBucket::Environments);
+        let root = bucket.join(&key);
+        fs_err::create_dir_all(&bucket)?;
+
+        // Hold the lock while checking for (or creating) the environment, such that concurrent
+        // invocations don't install into the same environment.
+        let _lock = LockedFile::acquire(
+            bucket.join(format!("{key}.lock")),
+            root.user_display(),
+            cache.lock_timeout(),
+        )?;
+
+        if let Some(venv) = Self::read(&root, &key, cache) {
+            debug!(
+                "Using cached environment at: {}",
+                venv.root().user_display()
+            );
+            return Ok(Self(venv));
+        }
+
+        // Remove any partially-created environment (e.g., from an interrupted installation).
+        if root.exists() {
+            debug!(
+                "Removing incomplete cached environment at: {}",
+                root.user_display()
+            );
+            fs_err::remove_dir_all(&root)?;
+        }
+
+        debug!("Creating cached environment at: {}", root.user_display());
+        let venv = uv_virtualenv::create_venv(
+            &root,
+            interpreter.clone(),
+            uv_virtualenv::Prompt::None,
+            false,
+            PythonPreference::default(),
+            false,
+        )?;
+
+        // Install the requirements.
+        let site_packages = SitePackages::from_executable(&venv)?;
+        pip::operations::install(
+            &resolution,
+            &editables,
+            site_packages,
+            pip::operations::Modifications::Exact,
+            &reinstall,
+            &no_binary,
+            link_mode,
+            settings.script_options,
+            &InstallFilter::default(),
+            FileCollisionPolicy::default(),
+            settings.compile_bytecode,
+            &settings.compile_options,
+            false,
+            &index_locations,
+            &hasher,
+            tags,
+            false,
+            &client,
+            &in_flight,
+            concurrency,
+            &build_dispatch,
+            cache,
+            &venv,
+            install_hooks,
+            allow_external_symlinks,
+            None,
+            false,
+            summary,
+            printer,
+        )
+        .await?;
+
+        // Notify the user of any resolution diagnostics.
+        pip::operations::diagnose_resolution(resolution.diagnostics(), printer)?;
+
+        // Mark the environment as complete.
+        uv_virtualenv::update_pyvenv_cfg(
+            venv.root(),
+            &[
+                (CACHE_KEY, Some(key.clone())),
+                (REQUESTED_KEY, Some(requested.clone())),
+            ],
+        )?;
+
+        // Remove any environments that were created for the same requested requirements, which
+        // this environment supersedes.
+        Self::prune(cache, &key, &requested);
+
+        Ok(Self(venv))
+    }
+
+    /// Compute the cache key for an environment, from the inputs that determine its contents.
+    fn cache_key(
+        interpreter: &Interpreter,
+        resolution: &Resolution,
+        settings: &ProjectSharedSettings,
+    ) -> Result<String> {
+        let mut key = String::new();
+        let _ = writeln!(
+            key,
+            "interpreter: {} {}",
+            interpreter.sys_executable().display(),
+            interpreter.python_full_version()
+        );
+        for dist in resolution.distributions().map(ToString::to_string).sorted() {
+            let _ = writeln!(key, "dist: {dist}");
+        }
+        for dist in resolution.distributions() {
+            let ResolvedDist::Installable(dist) = dist else {
+                continue;
+            };
+
+            // Registry distributions are identified by the selected file.
+            if let Some(file) = dist.file() {
+                let _ = writeln!(
+                    key,
+                    "file: {} {}",
+                    file.filename,
+                    file.hashes
+                        .iter()
+                        .map(ToString::to_string)
+                        .sorted()
+                        .join(" ")
+                );
+            }
+
+            // Local distributions may change without their URL changing.
+            let modified = match dist {
+                Dist::Built(BuiltDist::Path(wheel)) => ArchiveTimestamp::from_path(&wheel.path)?,
+                Dist::Source(SourceDist::Path(sdist)) => ArchiveTimestamp::from_path(&sdist.path)?,
+                Dist::Source(SourceDist::Directory(sdist)) if !sdist.editable => {
+                    ArchiveTimestamp::from_source_tree_contents(&sdist.path)?
+                }
+                _ => None,
+            };
+            if let Some(modified) = modified {
+                let _ = writeln!(key, "modified: {dist} {:?}", modified.timestamp());
+            }
+        }
+        let _ = writeln!(key, "no-build: {:?}", settings.no_build);
+        let _ = writeln!(key, "max-build-depth: {:?}", settings.max_build_depth);
+        let _ = writeln!(key, "scripts: {:?}", settings.script_options);
+        let _ = writeln!(
+            key,
+            "compile-bytecode: {:?} {:?}",
+            settings.compile_bytecode, settings.compile_options
+        );
+        Ok(digest(&key))
+    }
+
+    /// Remove any complete environments in the cache that were created for the `requested`
+    /// requirements, other than the environment with the given `key`.
+    fn prune(cache: &Cache, key: &str, requested: &str) {
+        let bucket = cache.bucket(CacheBucket::Environments);
+        for root in directories(&bucket) {
+            let Ok(cfg) = uv_virtualenv::read_pyvenv_cfg(&root) else {
+                continue;
+            };
+            let value = |name: &str| {
+                cfg.iter()
+                    .find(|(existing, _)| existing == name)
+                    .map(|(_, value)| value.as_str())
+            };
+            if value(REQUESTED_KEY) != Some(requested) {
+                continue;
+            }
+            let Some(existing) = value(CACHE_KEY) else {
+                continue;
+            };
+            if existing == key {
+                continue;
+            }
+
+            // Hold the environment's lock while removing it, such that we don't remove an
+            // environment that's concurrently being validated.
+            let Ok(_lock) = LockedFile::acquire(
+                bucket.join(format!("{existing}.lock")),
+                root.user_display(),
+                cache.lock_timeout(),
+            ) else {
+                continue;
+            };
+            debug!(
+                "Removing superseded cached environment at: {}",
+                root.user_display()
+            );
+            if let Err(err) = fs_err::remove_dir_all(&root) {
+                debug!("Failed to remove superseded cached environment: {err}");
+            }
+        }
+    }
+
+    /// Read a complete cached environment, if one exists at the given location.
+    fn read(root: &Path, key: &str, cache: &Cache) -> Option<PythonEnvironment> {
+        let cfg = uv_virtualenv::read_pyvenv_cfg(root).ok()?;
+        if !cfg
+            .iter()
+            .any(|(existing, value)| existing == CACHE_KEY && value == key)
+        {
+            return None;
+        }
+        PythonEnvironment::from_root(root, cache).ok()
+    }
+
+    /// Convert the [`CachedEnvironment`] into a [`PythonEnvironment`].
+    pub(crate) fn into_inner(self) -> PythonEnvironment {
+        self.0
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Write;
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use futures::{StreamExt, TryStreamExt};
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use distribution_types::IndexUrl;
+use pep508_rs::VerbatimUrl;
+use uv_cache::Cache;
+use uv_client::{BaseClientBuilder, OwnedArchive, RegistryClientBuilder};
+use uv_configuration::{Concurrency, KeyringProviderType, PreviewMode};
+use uv_fs::Simplified;
+use uv_requirements::ProjectWorkspace;
+use uv_resolver::Lock;
+use uv_warnings::warn_user;
+
+use crate::commands::{ExitStatus, ExportFormat};
+use crate::printer::Printer;
+
+/// Export the project lockfile to an alternate format.
+pub(crate) async fn export(
+    format: ExportFormat,
+    stable_age: u32,
+    keyring_provider: KeyringProviderType,
+    client_builder: &BaseClientBuilder<'_>,
+    preview: PreviewMode,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user!("`uv export` is experimental and may change without warning.");
+    }
+
+    // Find the project requirements.
+    let project = ProjectWorkspace::discover(std::env::current_dir()?)?;
+
+    // Read the lockfile.
+    let lockfile = project.workspace().root().join("uv.lock");
+    if !lockfile.is_file() {
+        return Err(anyhow!(
+            "No lockfile found at: `{}` (run `uv lock` to create one)",
+            lockfile.user_display()
+        ));
+    }
+    let encoded = fs_err::tokio::read_to_string(&lockfile).await?;
+    let lock: Lock = toml::from_str(&encoded)?;
+
+    match format {
+        ExportFormat::Docker => {
+            // Determine the release date of each registry distribution, as the earliest upload
+            // time of any of its files on the index against which it was locked.
+            let client = RegistryClientBuilder::new(cache.clone())
+                .base_client_builder(client_builder.clone())
+                .keyring(keyring_provider)
+                .build();
+            let concurrency = Concurrency::default();
+            let upload_times = futures::stream::iter(lock.registry_distributions())
+                .map(|(name, version, index)| {
+                    let client = &client;
+                    async move {
+                        let index = IndexUrl::from(VerbatimUrl::from_url(index.clone()));
+                        let mut upload_time = None;
+                        if let Some(metadata) = client.simple_index(name, &index).await? {
+                            let metadata = OwnedArchive::deserialize(&metadata);
+                            for datum in metadata.iter().filter(|datum| datum.version == *version) {
+                                upload_time = datum
+                                    .files
+                                    .wheels
+                                    .iter()
+                                    .map(|wheel| wheel.file.upload_time_utc_ms)
+                                    .chain(
+                                        datum
+                                            .files
+                                            .source_dists
+                                            .iter()
+                                            .map(|sdist| sdist.file.upload_time_utc_ms),
+                                    )
+                                    .flatten()
+                                    .chain(upload_time)
+                                    .min();
+                            }
+                        }
+                        if upload_time.is_none() {
+                            debug!("No upload time found for: {name}=={version}");
+                        }
+                        Ok::<_, anyhow::Error>(
+                            upload_time
+                                .map(|upload_time| ((name.clone(), version.clone()), upload_time)),
+                        )
+                    }
+                })
+                .buffer_unordered(concurrency.downloads)
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .flatten()
+                .collect::<FxHashMap<_, _>>();
+
+            let stable_before =
+                (Utc::now() - Duration::days(i64::from(stable_age))).timestamp_millis();
+            let layers = lock
+                .to_layers(project.project_name(), &upload_times, stable_before)
+                .map_err(|err| anyhow!("Failed to export `{}`: {err}", lockfile.user_display()))?;
+
+            // The `RUN` instructions pass each layer's requirements via a heredoc, which requires
+            // the Dockerfile 1.4 syntax (or later).
+            writeln!(printer.stdout(), "# syntax=docker/dockerfile:1")?;
+
+            // Emit a `RUN` instruction for each layer, such that changes to the more volatile
+            // layers don't invalidate the cache for the more stable layers that precede them.
+            for (index, layer) in layers.iter().enumerate() {
+                writeln!(printer.stdout())?;
+                let s = if layer.requirements.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                };
+                writeln!(
+                    printer.stdout(),
+                    "# Layer {}: {} ({} package{s})",
+                    index + 1,
+                    layer.kind,
+                    layer.requirements.len(),
+                )?;
+                let require_hashes = if layer.requires_hashes() {
+                    " --require-hashes"
+                } else {
+                    ""
+                };
+                writeln!(
+                    printer.stdout(),
+                    "RUN uv pip install --system --no-deps{require_hashes} -r - <<EOF"
+                )?;
+                for requirement in &layer.requirements {
+                    writeln!(printer.stdout(), "{requirement}")?;
+                }
+                writeln!(printer.stdout(), "EOF")?;
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -1,29 +1,40 @@
-use anstream::eprint;
+use std::path::Path;
+
 use anyhow::Result;
 
-use distribution_types::IndexLocations;
+use distribution_types::{IndexLocations, UnresolvedRequirement};
 use install_wheel_rs::linker::LinkMode;
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ConfigSettings, NoBinary, NoBuild, PreviewMode, Reinstall, SetupPyStrategy,
-    Upgrade,
+    Concurrency, ConfigSettings, NoBinary, Patches, PreviewMode, Reinstall, SetupPyStrategy,
+    Upgrade, UpgradeStrategy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_requirements::{ExtrasSpecification, ProjectWorkspace, RequirementsSpecification};
-use uv_resolver::{FlatIndex, InMemoryIndex, Options};
+use uv_resolver::{FlatIndex, InMemoryIndex, Options, PrefetchStrategy};
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
 use uv_warnings::warn_user;
 
 use crate::commands::{pip, project, ExitStatus};
 use crate::editables::ResolvedEditables;
+use crate::errors::report_no_solution;
 use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::signature;
+use crate::summary::Summary;
 
 /// Resolve the project requirements into a lockfile.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn lock(
+    sign_with: Option<&Path>,
+    settings: &ProjectSharedSettings,
+    client_builder: &BaseClientBuilder<'_>,
     preview: PreviewMode,
+    environments_dir: Option<&Path>,
     cache: &Cache,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     if preview.is_disabled() {
@@ -34,23 +45,42 @@ pub(crate) async fn lock(
     let project = ProjectWorkspace::discover(std::env::current_dir()?)?;
 
     // Discover or create the virtual environment.
-    let venv = project::init_environment(&project, cache, printer)?;
-
-    // TODO(zanieb): Support client configuration
-    let client_builder = BaseClientBuilder::default();
+    let venv = project::init_environment(
+        &project,
+        None,
+        environments_dir,
+        cache,
+        allow_external_symlinks,
+        printer,
+    )?;
 
     // Read all requirements from the provided sources.
-    // TODO(zanieb): Consider allowing constraints and extras
-    // TODO(zanieb): Allow specifying extras somehow
-    let spec = RequirementsSpecification::from_sources(
+    // TODO(zanieb): Consider allowing constraints
+    let mut spec = RequirementsSpecification::from_sources(
         &project.requirements(),
         &[],
         &[],
         &ExtrasSpecification::None,
-        &client_builder,
+        client_builder,
         preview,
     )
-    .await?;
+    .await?
+    .with_patches(&Patches::from_patches(&settings.patches)?);
+
+    // Lock the project with all of its extras enabled, such that its optional dependencies are
+    // recorded in the lockfile (e.g., for `uv sync --extra`).
+    let project_extras = project.extras();
+    for requirement in &mut spec.requirements {
+        if let UnresolvedRequirement::Unnamed(requirement) = &mut requirement.requirement {
+            if requirement
+                .url
+                .to_file_path()
+                .is_ok_and(|path| path == project.project_root())
+            {
+                requirement.extras.clone_from(&project_extras);
+            }
+        }
+    }
 
     // Determine the tags, markers, and interpreter to use for resolution.
     let interpreter = venv.interpreter().clone();
@@ -58,8 +88,8 @@ pub(crate) async fn lock(
     let markers = venv.interpreter().markers();
 
     // Initialize the registry client.
-    // TODO(zanieb): Support client options e.g. offline, tls, etc.
     let client = RegistryClientBuilder::new(cache.clone())
+        .base_client_builder(client_builder.clone())
         .markers(markers)
         .platform(venv.interpreter().platform())
         .build();
@@ -76,7 +106,6 @@ pub(crate) async fn lock(
     let index_locations = IndexLocations::default();
     let link_mode = LinkMode::default();
     let no_binary = NoBinary::default();
-    let no_build = NoBuild::default();
     let options = Options::default();
     let reinstall = Reinstall::default();
     let setup_py = SetupPyStrategy::default();
@@ -95,10 +124,12 @@ pub(crate) async fn lock(
         &config_settings,
         build_isolation,
         link_mode,
-        &no_build,
+        &settings.no_build,
         &no_binary,
         concurrency,
-    );
+    )
+    .with_build_counter(summary.builds().clone())
+    .with_max_build_depth(settings.max_build_depth);
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command.
@@ -133,6 +164,9 @@ pub(crate) async fn lock(
         &hasher,
         &reinstall,
         &upgrade,
+        UpgradeStrategy::default(),
+        &settings.package_policy,
+        &PrefetchStrategy::default(),
         &interpreter,
         tags,
         markers,
@@ -142,16 +176,15 @@ pub(crate) async fn lock(
         &build_dispatch,
         concurrency,
         options,
+        summary,
         printer,
     )
     .await;
 
     let resolution = match resolution {
         Err(pip::operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            report_no_solution(&err, preview, printer.output_format());
+            return Ok(ExitStatus::ResolutionConflict);
         }
         result => result,
     }?;
@@ -159,11 +192,13 @@ pub(crate) async fn lock(
     // Write the lockfile to disk.
     let lock = resolution.lock()?;
     let encoded = toml::to_string_pretty(&lock)?;
-    fs_err::tokio::write(
-        project.workspace().root().join("uv.lock"),
-        encoded.as_bytes(),
-    )
-    .await?;
+    let lockfile = project.workspace().root().join("uv.lock");
+    fs_err::tokio::write(&lockfile, encoded.as_bytes()).await?;
+
+    // If requested, sign the lockfile.
+    if let Some(key) = sign_with {
+        signature::sign(&lockfile, key)?;
+    }
 
     Ok(ExitStatus::Success)
 }
@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::Result;
 use itertools::Itertools;
@@ -8,25 +9,37 @@ use tracing::debug;
 use crate::commands::pip;
 use distribution_types::{IndexLocations, Resolution};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::InstallFilter;
 use uv_cache::Cache;
-use uv_client::{BaseClientBuilder, Connectivity, RegistryClientBuilder};
+use uv_client::{BaseClientBuilder, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ConfigSettings, NoBinary, NoBuild, PreviewMode, Reinstall, SetupPyStrategy,
-    Upgrade,
+    Concurrency, ConfigSettings, FileCollisionPolicy, NoBinary, Patches, PreviewMode,
+    PythonPreference, Reinstall, SetupPyStrategy, Upgrade, UpgradeStrategy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{SatisfiesResult, SitePackages};
-use uv_interpreter::{find_default_interpreter, PythonEnvironment};
+use uv_installer::{InstallHooks, SatisfiesResult, SitePackages};
+use uv_interpreter::{
+    find_default_interpreter, find_interpreter, PythonEnvironment, PythonVersionFile,
+    SourceSelector, SystemPython,
+};
 use uv_requirements::{
     ExtrasSpecification, ProjectWorkspace, RequirementsSource, RequirementsSpecification,
 };
-use uv_resolver::{FlatIndex, InMemoryIndex, Options};
+use uv_resolver::{FlatIndex, InMemoryIndex, Options, PrefetchStrategy};
 use uv_types::{BuildIsolation, HashStrategy, InFlight};
+use uv_virtualenv::EnvironmentStore;
 
+use crate::commands::pip::operations::Modifications;
 use crate::editables::ResolvedEditables;
+use crate::messages::Message;
 use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::summary::Summary;
+use crate::symlinks;
 
+pub(crate) mod environment;
+pub(crate) mod export;
 pub(crate) mod lock;
 pub(crate) mod run;
 pub(crate) mod sync;
@@ -41,15 +54,47 @@ pub(crate) enum Error {
 
     #[error(transparent)]
     Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
 }
 
 /// Initialize a virtual environment for the current project.
+///
+/// If an `environment` name is provided, the named environment from the central environment store
+/// is used. Otherwise, the project's `.venv` is used, falling back to the project's environment in
+/// the central store (see `uv venv --central`) if the project has no `.venv`.
 pub(crate) fn init_environment(
     project: &ProjectWorkspace,
+    environment: Option<&str>,
+    environments_dir: Option<&Path>,
     cache: &Cache,
+    allow_external_symlinks: bool,
     printer: Printer,
 ) -> Result<PythonEnvironment, Error> {
-    let venv = project.workspace().root().join(".venv");
+    let store = EnvironmentStore::from_settings(environments_dir.map(Path::to_path_buf))?;
+    let venv = if let Some(name) = environment {
+        store.named(name)?
+    } else {
+        let venv = project.workspace().root().join(".venv");
+        let central = store.for_project(project.workspace().root())?;
+        if !venv.exists() && central.exists() {
+            debug!(
+                "Using project environment from the central store: {}",
+                central.user_display()
+            );
+            central
+        } else {
+            venv
+        }
+    };
+
+    // Refuse to use an environment that's a symlink to a location outside the project.
+    symlinks::check_location(
+        &venv,
+        Some(project.workspace().root()),
+        allow_external_symlinks,
+    )?;
 
     // Discover or create the virtual environment.
     // TODO(charlie): If the environment isn't compatible with `--python`, recreate it.
@@ -57,22 +102,50 @@ pub(crate) fn init_environment(
         Ok(venv) => Ok(venv),
         Err(uv_interpreter::Error::NotFound(_)) => {
             // TODO(charlie): Respect `--python`; if unset, respect `Requires-Python`.
-            let interpreter = find_default_interpreter(cache)
-                .map_err(uv_interpreter::Error::from)?
-                .map_err(uv_interpreter::Error::from)?
-                .into_interpreter();
+            let interpreter = if let Some(version_file) =
+                PythonVersionFile::discover(project.workspace().root())?
+            {
+                // Respect the project's pinned interpreter (i.e., `.python-version`).
+                let system = SystemPython::Required;
+                find_interpreter(
+                    &version_file.interpreter_request(),
+                    system,
+                    &SourceSelector::from_settings(system),
+                    None,
+                    cache,
+                )
+            } else {
+                find_default_interpreter(cache)
+            }
+            .map_err(uv_interpreter::Error::from)?
+            .map_err(uv_interpreter::Error::from)?
+            .into_interpreter();
 
+            // Report the architecture if it differs from uv's own, e.g., under Rosetta 2.
+            let version = if interpreter.is_foreign_arch() {
+                format!("{} ({})", interpreter.python_version(), interpreter.arch())
+            } else {
+                interpreter.python_version().to_string()
+            };
             writeln!(
                 printer.stderr(),
-                "Using Python {} interpreter at: {}",
-                interpreter.python_version(),
-                interpreter.sys_executable().user_display().cyan()
+                "{}",
+                printer.messages().format(
+                    Message::UsingInterpreter,
+                    &[
+                        ("version", &version),
+                        ("path", &interpreter.sys_executable().user_display().cyan()),
+                    ]
+                )
             )?;
 
             writeln!(
                 printer.stderr(),
-                "Creating virtualenv at: {}",
-                venv.user_display().cyan()
+                "{}",
+                printer.messages().format(
+                    Message::CreatingVirtualenv,
+                    &[("path", &venv.user_display().cyan())]
+                )
             )?;
 
             Ok(uv_virtualenv::create_venv(
@@ -80,6 +153,7 @@ pub(crate) fn init_environment(
                 interpreter,
                 uv_virtualenv::Prompt::None,
                 false,
+                PythonPreference::default(),
                 false,
             )?)
         }
@@ -88,34 +162,46 @@ pub(crate) fn init_environment(
 }
 
 /// Update a [`PythonEnvironment`] to satisfy a set of [`RequirementsSource`]s.
+///
+/// With [`Modifications::Exact`], any packages that aren't required are removed from the
+/// environment.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn update_environment(
     venv: PythonEnvironment,
     requirements: &[RequirementsSource],
+    extras: &ExtrasSpecification,
+    modifications: Modifications,
+    settings: &ProjectSharedSettings,
+    client_builder: &BaseClientBuilder<'_>,
+    install_hooks: &InstallHooks,
     preview: PreviewMode,
-    connectivity: Connectivity,
     cache: &Cache,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<PythonEnvironment> {
-    // TODO(zanieb): Support client configuration
-    let client_builder = BaseClientBuilder::default().connectivity(connectivity);
-
     // Read all requirements from the provided sources.
-    // TODO(zanieb): Consider allowing constraints and extras
-    // TODO(zanieb): Allow specifying extras somehow
+    // TODO(zanieb): Consider allowing constraints
     let spec = RequirementsSpecification::from_sources(
         requirements,
         &[],
         &[],
-        &ExtrasSpecification::None,
-        &client_builder,
+        extras,
+        client_builder,
         preview,
     )
-    .await?;
+    .await?
+    .with_patches(&Patches::from_patches(&settings.patches)?);
 
     // Check if the current environment satisfies the requirements
     let site_packages = SitePackages::from_executable(&venv)?;
-    if spec.source_trees.is_empty() {
-        match site_packages.satisfies(&spec.requirements, &spec.editables, &spec.constraints)? {
+    if spec.source_trees.is_empty() && matches!(modifications, Modifications::Sufficient) {
+        match site_packages.satisfies(
+            &spec.requirements,
+            &spec.editables,
+            &spec.constraints,
+            true,
+        )? {
             // If the requirements are already satisfied, we're done.
             SatisfiesResult::Fresh {
                 recursive_requirements,
@@ -148,9 +234,8 @@ pub(crate) async fn update_environment(
     let markers = venv.interpreter().markers();
 
     // Initialize the registry client.
-    // TODO(zanieb): Support client options e.g. offline, tls, etc.
     let client = RegistryClientBuilder::new(cache.clone())
-        .connectivity(connectivity)
+        .base_client_builder(client_builder.clone())
         .markers(markers)
         .platform(venv.interpreter().platform())
         .build();
@@ -165,13 +250,10 @@ pub(crate) async fn update_environment(
     let index_locations = IndexLocations::default();
     let link_mode = LinkMode::default();
     let no_binary = NoBinary::default();
-    let no_build = NoBuild::default();
     let setup_py = SetupPyStrategy::default();
     let concurrency = Concurrency::default();
     let reinstall = Reinstall::default();
-    let compile = false;
     let dry_run = false;
-    let extras = ExtrasSpecification::default();
     let upgrade = Upgrade::default();
     let options = Options::default();
 
@@ -188,10 +270,12 @@ pub(crate) async fn update_environment(
         &config_settings,
         build_isolation,
         link_mode,
-        &no_build,
+        &settings.no_build,
         &no_binary,
         concurrency,
-    );
+    )
+    .with_build_counter(summary.builds().clone())
+    .with_max_build_depth(settings.max_build_depth);
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command.
@@ -220,12 +304,15 @@ pub(crate) async fn update_environment(
         spec.overrides,
         spec.source_trees,
         spec.project,
-        &extras,
+        extras,
         &editables,
         site_packages.clone(),
         &hasher,
         &reinstall,
         &upgrade,
+        UpgradeStrategy::default(),
+        &settings.package_policy,
+        &PrefetchStrategy::default(),
         &interpreter,
         tags,
         markers,
@@ -235,6 +322,7 @@ pub(crate) async fn update_environment(
         &resolve_dispatch,
         concurrency,
         options,
+        summary,
         printer,
     )
     .await
@@ -263,10 +351,12 @@ pub(crate) async fn update_environment(
             &config_settings,
             build_isolation,
             link_mode,
-            &no_build,
+            &settings.no_build,
             &no_binary,
             concurrency,
         )
+        .with_build_counter(summary.builds().clone())
+        .with_max_build_depth(settings.max_build_depth)
     };
 
     // Sync the environment.
@@ -274,21 +364,31 @@ pub(crate) async fn update_environment(
         &resolution,
         &editables,
         site_packages,
-        pip::operations::Modifications::Sufficient,
+        modifications,
         &reinstall,
         &no_binary,
         link_mode,
-        compile,
+        settings.script_options,
+        &InstallFilter::default(),
+        FileCollisionPolicy::default(),
+        settings.compile_bytecode,
+        &settings.compile_options,
+        false,
         &index_locations,
         &hasher,
         tags,
+        false,
         &client,
         &in_flight,
         concurrency,
         &install_dispatch,
         cache,
         &venv,
+        install_hooks,
+        allow_external_symlinks,
+        None,
         dry_run,
+        summary,
         printer,
     )
     .await?;
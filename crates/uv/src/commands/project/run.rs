@@ -1,21 +1,25 @@
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use itertools::Itertools;
-use tempfile::tempdir_in;
 use tokio::process::Command;
 use tracing::debug;
 
 use uv_cache::Cache;
-use uv_client::Connectivity;
+use uv_client::BaseClientBuilder;
 use uv_configuration::PreviewMode;
-use uv_interpreter::{PythonEnvironment, SystemPython};
-use uv_requirements::{ProjectWorkspace, RequirementsSource};
+use uv_installer::InstallHooks;
+use uv_interpreter::{PythonEnvironment, PythonVersionFile, SystemPython};
+use uv_requirements::{ExtrasSpecification, ProjectWorkspace, RequirementsSource};
 use uv_warnings::warn_user;
 
+use crate::commands::pip::operations::Modifications;
+use crate::commands::project::environment::CachedEnvironment;
 use crate::commands::{project, ExitStatus};
 use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::summary::Summary;
 
 /// Run a command.
 #[allow(clippy::too_many_arguments)]
@@ -23,11 +27,18 @@ pub(crate) async fn run(
     target: Option<String>,
     mut args: Vec<OsString>,
     requirements: Vec<RequirementsSource>,
+    environment: Option<String>,
     python: Option<String>,
+    venv_dir: Option<&Path>,
+    settings: &ProjectSharedSettings,
     isolated: bool,
+    client_builder: &BaseClientBuilder<'_>,
+    install_hooks: &InstallHooks,
     preview: PreviewMode,
-    connectivity: Connectivity,
+    environments_dir: Option<&Path>,
     cache: &Cache,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     if preview.is_disabled() {
@@ -58,16 +69,29 @@ pub(crate) async fn run(
 
         let project = ProjectWorkspace::discover(std::env::current_dir()?)?;
 
-        let venv = project::init_environment(&project, cache, printer)?;
+        let venv = project::init_environment(
+            &project,
+            environment.as_deref(),
+            environments_dir,
+            cache,
+            allow_external_symlinks,
+            printer,
+        )?;
 
         // Install the project requirements.
         Some(
             project::update_environment(
                 venv,
                 &project.requirements(),
+                &ExtrasSpecification::None,
+                Modifications::Sufficient,
+                settings,
+                client_builder,
+                install_hooks,
                 preview,
-                connectivity,
                 cache,
+                allow_external_symlinks,
+                summary,
                 printer,
             )
             .await?,
@@ -75,7 +99,6 @@ pub(crate) async fn run(
     };
 
     // If necessary, create an environment for the ephemeral requirements.
-    let tmpdir;
     let ephemeral_env = if requirements.is_empty() {
         None
     } else {
@@ -85,34 +108,44 @@ pub(crate) async fn run(
         let interpreter = if let Some(project_env) = &project_env {
             project_env.interpreter().clone()
         } else if let Some(python) = python.as_ref() {
-            PythonEnvironment::from_requested_python(python, SystemPython::Allowed, cache)?
-                .into_interpreter()
+            PythonEnvironment::from_requested_python(
+                python,
+                SystemPython::Allowed,
+                venv_dir,
+                cache,
+            )?
+            .into_interpreter()
+        } else if let Some(version_file) = PythonVersionFile::discover(&std::env::current_dir()?)? {
+            // Respect the pinned interpreter (i.e., `.python-version`).
+            PythonEnvironment::from_requested_python(
+                version_file.request(),
+                SystemPython::Allowed,
+                venv_dir,
+                cache,
+            )?
+            .into_interpreter()
         } else {
             PythonEnvironment::from_default_python(cache)?.into_interpreter()
         };
 
-        // TODO(charlie): If the environment satisfies the requirements, skip creation.
-        // TODO(charlie): Pass the already-installed versions as preferences, or even as the
-        // "installed" packages, so that we can skip re-installing them in the ephemeral
-        // environment.
-
-        // Create a virtual environment
-        // TODO(zanieb): Move this path derivation elsewhere
-        let uv_state_path = std::env::current_dir()?.join(".uv");
-        fs_err::create_dir_all(&uv_state_path)?;
-        tmpdir = tempdir_in(uv_state_path)?;
-        let venv = uv_virtualenv::create_venv(
-            tmpdir.path(),
-            interpreter,
-            uv_virtualenv::Prompt::None,
-            false,
-            false,
-        )?;
+        // TODO(charlie): If the project environment satisfies the requirements, skip creation.
 
-        // Install the ephemeral requirements.
+        // Install the ephemeral requirements into a cached environment.
         Some(
-            project::update_environment(venv, &requirements, preview, connectivity, cache, printer)
-                .await?,
+            CachedEnvironment::get_or_create(
+                &requirements,
+                interpreter,
+                settings,
+                client_builder,
+                install_hooks,
+                preview,
+                cache,
+                allow_external_symlinks,
+                summary,
+                printer,
+            )
+            .await?
+            .into_inner(),
         )
     };
 
@@ -1,15 +1,21 @@
+use std::path::Path;
+
 use anyhow::Result;
+use tracing::debug;
 
 use distribution_types::IndexLocations;
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::InstallFilter;
 use uv_cache::Cache;
-use uv_client::RegistryClientBuilder;
+use uv_client::{BaseClientBuilder, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ConfigSettings, NoBinary, NoBuild, PreviewMode, Reinstall, SetupPyStrategy,
+    Concurrency, ConfigSettings, FileCollisionPolicy, NoBinary, PreviewMode, Reinstall,
+    SetupPyStrategy,
 };
 use uv_dispatch::BuildDispatch;
-use uv_installer::SitePackages;
-use uv_requirements::ProjectWorkspace;
+use uv_fs::Simplified;
+use uv_installer::{InstallHooks, SitePackages};
+use uv_requirements::{ExtrasSpecification, ProjectWorkspace};
 use uv_resolver::{FlatIndex, InMemoryIndex, Lock};
 use uv_types::{BuildIsolation, HashStrategy, InFlight};
 use uv_warnings::warn_user;
@@ -18,12 +24,23 @@ use crate::commands::pip::operations::Modifications;
 use crate::commands::{pip, project, ExitStatus};
 use crate::editables::ResolvedEditables;
 use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::signature;
+use crate::summary::Summary;
 
-/// Sync the project environment.
+/// Sync the project environment, removing any packages that aren't required by the project.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn sync(
+    extras: &ExtrasSpecification,
+    trusted_signers: Option<&Path>,
+    settings: &ProjectSharedSettings,
+    client_builder: &BaseClientBuilder<'_>,
+    install_hooks: &InstallHooks,
     preview: PreviewMode,
+    environments_dir: Option<&Path>,
     cache: &Cache,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     if preview.is_disabled() {
@@ -34,28 +51,74 @@ pub(crate) async fn sync(
     let project = ProjectWorkspace::discover(std::env::current_dir()?)?;
 
     // Discover or create the virtual environment.
-    let venv = project::init_environment(&project, cache, printer)?;
+    let venv = project::init_environment(
+        &project,
+        None,
+        environments_dir,
+        cache,
+        allow_external_symlinks,
+        printer,
+    )?;
+
+    // If there's no lockfile, resolve the project's requirements directly.
+    let lockfile = project.workspace().root().join("uv.lock");
+    if trusted_signers.is_some() && !lockfile.is_file() {
+        return Err(anyhow::anyhow!(
+            "Signature verification requires a lockfile, but none was found at: `{}`",
+            lockfile.user_display()
+        ));
+    }
+    if !lockfile.is_file() {
+        debug!(
+            "Resolving project requirements from: {}",
+            project.workspace().root().user_display()
+        );
+        project::update_environment(
+            venv,
+            &project.requirements(),
+            extras,
+            Modifications::Exact,
+            settings,
+            client_builder,
+            install_hooks,
+            preview,
+            cache,
+            allow_external_symlinks,
+            summary,
+            printer,
+        )
+        .await?;
+        return Ok(ExitStatus::Success);
+    }
+
+    pip::operations::validate_extras(extras, &project.extras().into_iter().collect())?;
+
     let markers = venv.interpreter().markers();
     let tags = venv.interpreter().tags()?;
 
-    // Read the lockfile.
+    // Verify the signature of the lockfile before trusting its pins.
+    if let Some(trusted_signers) = trusted_signers {
+        signature::verify(&lockfile, trusted_signers)?;
+    }
+
+    // Read the lockfile, including the optional dependencies of the requested extras.
     let resolution = {
-        let encoded =
-            fs_err::tokio::read_to_string(project.workspace().root().join("uv.lock")).await?;
+        let encoded = fs_err::tokio::read_to_string(&lockfile).await?;
         let lock: Lock = toml::from_str(&encoded)?;
-        lock.to_resolution(markers, tags, project.project_name())
+        lock.to_resolution(markers, tags, project.project_name(), &|extra| {
+            extras.contains(extra)
+        })
     };
 
     // Initialize the registry client.
-    // TODO(zanieb): Support client options e.g. offline, tls, etc.
     let client = RegistryClientBuilder::new(cache.clone())
+        .base_client_builder(client_builder.clone())
         .markers(markers)
         .platform(venv.interpreter().platform())
         .build();
 
     // TODO(charlie): Respect project configuration.
     let build_isolation = BuildIsolation::default();
-    let compile = false;
     let concurrency = Concurrency::default();
     let config_settings = ConfigSettings::default();
     let dry_run = false;
@@ -66,7 +129,6 @@ pub(crate) async fn sync(
     let index_locations = IndexLocations::default();
     let link_mode = LinkMode::default();
     let no_binary = NoBinary::default();
-    let no_build = NoBuild::default();
     let reinstall = Reinstall::default();
     let setup_py = SetupPyStrategy::default();
 
@@ -83,10 +145,12 @@ pub(crate) async fn sync(
         &config_settings,
         build_isolation,
         link_mode,
-        &no_build,
+        &settings.no_build,
         &no_binary,
         concurrency,
-    );
+    )
+    .with_build_counter(summary.builds().clone())
+    .with_max_build_depth(settings.max_build_depth);
 
     let site_packages = SitePackages::from_executable(&venv)?;
 
@@ -113,21 +177,31 @@ pub(crate) async fn sync(
         &resolution,
         &editables,
         site_packages,
-        Modifications::Sufficient,
+        Modifications::Exact,
         &reinstall,
         &no_binary,
         link_mode,
-        compile,
+        settings.script_options,
+        &InstallFilter::default(),
+        FileCollisionPolicy::default(),
+        settings.compile_bytecode,
+        &settings.compile_options,
+        false,
         &index_locations,
         &hasher,
         tags,
+        false,
         &client,
         &in_flight,
         concurrency,
         &build_dispatch,
         cache,
         &venv,
+        install_hooks,
+        allow_external_symlinks,
+        None,
         dry_run,
+        summary,
         printer,
     )
     .await?;
@@ -1,27 +1,31 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
+use serde::Serialize;
 use url::Url;
 
 use distribution_types::{
     BuildableSource, CachedDist, DistributionMetadata, LocalEditable, Name, SourceDist,
     VersionOrUrlRef,
 };
+use pep440_rs::Version;
 use uv_normalize::PackageName;
 
+use crate::cli::OutputFormat;
 use crate::printer::Printer;
 
 #[derive(Debug)]
-pub(crate) struct DownloadReporter {
+pub(crate) struct PrepareReporter {
     printer: Printer,
     multi_progress: MultiProgress,
     progress: ProgressBar,
     bars: Arc<Mutex<Vec<ProgressBar>>>,
 }
 
-impl From<Printer> for DownloadReporter {
+impl From<Printer> for PrepareReporter {
     fn from(printer: Printer) -> Self {
         let multi_progress = MultiProgress::with_draw_target(printer.target());
 
@@ -40,7 +44,7 @@ impl From<Printer> for DownloadReporter {
     }
 }
 
-impl DownloadReporter {
+impl PrepareReporter {
     #[must_use]
     pub(crate) fn with_length(self, length: u64) -> Self {
         self.progress.set_length(length);
@@ -48,7 +52,7 @@ impl DownloadReporter {
     }
 }
 
-impl DownloadReporter {
+impl PrepareReporter {
     fn on_any_build_start(&self, color_string: &str) -> usize {
         let progress = self.multi_progress.insert_before(
             &self.progress,
@@ -70,7 +74,7 @@ impl DownloadReporter {
     }
 }
 
-impl uv_installer::DownloadReporter for DownloadReporter {
+impl uv_installer::PrepareReporter for PrepareReporter {
     fn on_progress(&self, dist: &CachedDist) {
         self.progress.set_message(format!("{dist}"));
         self.progress.inc(1);
@@ -169,10 +173,49 @@ pub(crate) struct ResolverReporter {
     multi_progress: MultiProgress,
     progress: ProgressBar,
     bars: Arc<Mutex<Vec<ProgressBar>>>,
+    backtracks: AtomicUsize,
+}
+
+/// A resolver event, written to `stderr` as a single line of JSON (`--output-format json`).
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+enum ResolverEvent<'a> {
+    MetadataFetched {
+        package: &'a PackageName,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+    },
+    VersionTried {
+        package: &'a PackageName,
+        version: String,
+    },
+    Backtrack {
+        package: &'a PackageName,
+        version: String,
+    },
+    BuildStarted {
+        source: String,
+    },
+}
+
+impl ResolverEvent<'_> {
+    fn emit(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            anstream::eprintln!("{json}");
+        }
+    }
 }
 
 impl From<Printer> for ResolverReporter {
     fn from(printer: Printer) -> Self {
+        // In JSON mode, progress is reported as events rather than progress bars.
+        let printer = if printer.output_format() == OutputFormat::Json {
+            printer.quiet()
+        } else {
+            printer
+        };
         let multi_progress = MultiProgress::with_draw_target(printer.target());
 
         let progress = multi_progress.add(ProgressBar::with_draw_target(None, printer.target()));
@@ -189,11 +232,17 @@ impl From<Printer> for ResolverReporter {
             multi_progress,
             progress,
             bars: Arc::new(Mutex::new(Vec::new())),
+            backtracks: AtomicUsize::new(0),
         }
     }
 }
 
 impl ResolverReporter {
+    /// Returns `true` if progress is reported as JSON events (`--output-format json`).
+    fn is_json(&self) -> bool {
+        self.printer.output_format() == OutputFormat::Json
+    }
+
     #[must_use]
     pub(crate) fn with_length(self, length: u64) -> Self {
         self.progress.set_length(length);
@@ -275,6 +324,58 @@ impl ResolverReporter {
     }
 }
 
+impl uv_resolver::ResolveReporter for ResolverReporter {
+    fn on_metadata_fetched(&self, name: &PackageName, version_or_url: &VersionOrUrlRef) {
+        if self.is_json() {
+            let (version, url) = match version_or_url {
+                VersionOrUrlRef::Version(version) => (Some(version.to_string()), None),
+                VersionOrUrlRef::Url(url) => (None, Some(url.to_string())),
+            };
+            ResolverEvent::MetadataFetched {
+                package: name,
+                version,
+                url,
+            }
+            .emit();
+        }
+    }
+
+    fn on_version_tried(&self, name: &PackageName, version: &Version) {
+        if self.is_json() {
+            ResolverEvent::VersionTried {
+                package: name,
+                version: version.to_string(),
+            }
+            .emit();
+        }
+    }
+
+    fn on_backtrack(&self, name: &PackageName, version: &Version) {
+        let backtracks = self.backtracks.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.is_json() {
+            ResolverEvent::Backtrack {
+                package: name,
+                version: version.to_string(),
+            }
+            .emit();
+        } else {
+            self.progress.set_message(format!(
+                "Backtracking {name}=={version} ({backtracks} backtrack{})",
+                if backtracks == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    fn on_build_started(&self, source: &BuildableSource) {
+        if self.is_json() {
+            ResolverEvent::BuildStarted {
+                source: source.to_string(),
+            }
+            .emit();
+        }
+    }
+}
+
 impl uv_resolver::ResolverReporter for ResolverReporter {
     fn on_progress(&self, name: &PackageName, version_or_url: &VersionOrUrlRef) {
         self.on_progress(name, version_or_url);
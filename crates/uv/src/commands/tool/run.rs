@@ -1,22 +1,24 @@
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use itertools::Itertools;
-use tempfile::tempdir_in;
 use tokio::process::Command;
 use tracing::debug;
 
 use uv_cache::Cache;
-use uv_client::Connectivity;
+use uv_client::BaseClientBuilder;
 use uv_configuration::PreviewMode;
+use uv_installer::InstallHooks;
 use uv_interpreter::{PythonEnvironment, SystemPython};
 use uv_requirements::RequirementsSource;
 use uv_warnings::warn_user;
 
-use crate::commands::project::update_environment;
+use crate::commands::project::environment::CachedEnvironment;
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
+use crate::settings::ProjectSharedSettings;
+use crate::summary::Summary;
 
 /// Run a command.
 #[allow(clippy::too_many_arguments)]
@@ -24,12 +26,17 @@ pub(crate) async fn run(
     target: String,
     args: Vec<OsString>,
     python: Option<String>,
+    venv_dir: Option<&Path>,
     from: Option<String>,
     with: Vec<String>,
+    settings: &ProjectSharedSettings,
     _isolated: bool,
+    client_builder: &BaseClientBuilder<'_>,
+    install_hooks: &InstallHooks,
     preview: PreviewMode,
-    connectivity: Connectivity,
     cache: &Cache,
+    allow_external_symlinks: bool,
+    summary: &Summary,
     printer: Printer,
 ) -> Result<ExitStatus> {
     if preview.is_disabled() {
@@ -51,28 +58,29 @@ pub(crate) async fn run(
 
     // Discover an interpreter.
     let interpreter = if let Some(python) = python.as_ref() {
-        PythonEnvironment::from_requested_python(python, SystemPython::Allowed, cache)?
+        PythonEnvironment::from_requested_python(python, SystemPython::Allowed, venv_dir, cache)?
             .into_interpreter()
     } else {
         PythonEnvironment::from_default_python(cache)?.into_interpreter()
     };
 
-    // Create a virtual environment
-    // TODO(zanieb): Move this path derivation elsewhere
-    let uv_state_path = std::env::current_dir()?.join(".uv");
-    fs_err::create_dir_all(&uv_state_path)?;
-    let tmpdir = tempdir_in(uv_state_path)?;
-    let venv = uv_virtualenv::create_venv(
-        tmpdir.path(),
-        interpreter,
-        uv_virtualenv::Prompt::None,
-        false,
-        false,
-    )?;
-
-    // Install the ephemeral requirements.
-    let ephemeral_env =
-        Some(update_environment(venv, &requirements, preview, connectivity, cache, printer).await?);
+    // Install the ephemeral requirements into a cached environment.
+    let ephemeral_env = Some(
+        CachedEnvironment::get_or_create(
+            &requirements,
+            interpreter,
+            settings,
+            client_builder,
+            install_hooks,
+            preview,
+            cache,
+            allow_external_symlinks,
+            summary,
+            printer,
+        )
+        .await?
+        .into_inner(),
+    );
 
     // TODO(zanieb): Determine the command via the package entry points
     let command = target;
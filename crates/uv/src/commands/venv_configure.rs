@@ -0,0 +1,57 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Update the configuration of an existing virtual environment.
+pub(crate) fn venv_configure(
+    path: &Path,
+    system_site_packages: Option<bool>,
+    prompt: Option<String>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let venv = PythonEnvironment::from_root(path, cache)?;
+
+    uv_virtualenv::configure_venv(
+        &venv,
+        system_site_packages,
+        prompt
+            .clone()
+            .map(|prompt| uv_virtualenv::Prompt::from_args(Some(prompt))),
+    )?;
+
+    writeln!(
+        printer.stderr(),
+        "Configured virtualenv at: {}",
+        path.user_display().cyan()
+    )?;
+    if let Some(system_site_packages) = system_site_packages {
+        writeln!(
+            printer.stderr(),
+            "  System site packages: {}",
+            if system_site_packages { "on" } else { "off" }
+        )?;
+    }
+    if let Some(prompt) = prompt {
+        let prompt = if prompt == "." {
+            std::env::current_dir()?
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        } else {
+            prompt
+        };
+        writeln!(printer.stderr(), "  Prompt: {prompt}")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
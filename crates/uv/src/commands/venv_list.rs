@@ -0,0 +1,212 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Result};
+use chrono::DateTime;
+use owo_colors::OwoColorize;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use uv_fs::{directory_size, Simplified};
+use uv_interpreter::virtualenv_python_executable;
+use uv_requirements::ProjectWorkspace;
+
+use crate::commands::{human_readable_bytes, ExitStatus};
+use crate::printer::Printer;
+
+/// Directories that are never searched for virtual environments.
+const SKIPPED_DIRECTORIES: &[&str] = &[".git", "node_modules", "__pycache__"];
+
+/// List the virtual environments under the current project or workspace.
+pub(crate) fn venv_list(path: Option<&Path>, printer: Printer) -> Result<ExitStatus> {
+    let root = if let Some(path) = path {
+        path.to_path_buf()
+    } else {
+        workspace_root()?
+    };
+
+    if !root.is_dir() {
+        bail!("Directory not found: `{}`", root.user_display());
+    }
+
+    let environments = find_environments(&root);
+    if environments.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "No virtual environments found in: {}",
+            root.user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for (index, path) in environments.iter().enumerate() {
+        if index > 0 {
+            writeln!(printer.stdout())?;
+        }
+        describe_environment(path, printer)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The virtual environments targeted by `--environment` or, with `--all-environments`, every
+/// virtual environment under the current project or workspace.
+pub(crate) fn target_environments(
+    environments: Vec<PathBuf>,
+    all_environments: bool,
+) -> Result<Vec<PathBuf>> {
+    if all_environments {
+        workspace_environments()
+    } else {
+        Ok(environments)
+    }
+}
+
+/// Find the virtual environments under the current project or workspace, as listed by
+/// `uv environment list`.
+fn workspace_environments() -> Result<Vec<PathBuf>> {
+    let root = workspace_root()?;
+    let environments = find_environments(&root);
+    if environments.is_empty() {
+        bail!(
+            "No virtual environments found in: `{}`",
+            root.user_display()
+        );
+    }
+    Ok(environments)
+}
+
+/// The root of the current workspace, or the current directory if not in a workspace.
+fn workspace_root() -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    match ProjectWorkspace::discover(&cwd) {
+        Ok(project) => Ok(project.workspace().root().clone()),
+        Err(err) => {
+            debug!("No workspace found ({err}); searching the current directory");
+            Ok(cwd)
+        }
+    }
+}
+
+/// Find all virtual environments (i.e., directories containing a `pyvenv.cfg`) under the given
+/// directory, without descending into the environments themselves.
+fn find_environments(root: &Path) -> Vec<PathBuf> {
+    let mut environments = Vec::new();
+    let mut walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRECTORIES.contains(&name)))
+        });
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                debug!("Failed to read directory entry: {err}");
+                continue;
+            }
+        };
+        if entry.file_type().is_dir() && entry.path().join("pyvenv.cfg").is_file() {
+            environments.push(entry.into_path());
+            walker.skip_current_dir();
+        }
+    }
+    environments
+}
+
+/// Print the interpreter, size, creation metadata, and staleness of an environment.
+fn describe_environment(path: &Path, printer: Printer) -> Result<()> {
+    let cfg = uv_virtualenv::read_pyvenv_cfg(path)?;
+    let get = |key: &str| {
+        cfg.iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.as_str())
+    };
+
+    writeln!(printer.stdout(), "{}", path.user_display().bold())?;
+
+    let version = get("version_info")
+        .or_else(|| get("version"))
+        .unwrap_or("unknown");
+    let implementation = get("implementation").unwrap_or("Python");
+    match get("home") {
+        Some(home) => writeln!(
+            printer.stdout(),
+            "  Python: {implementation} {version} ({home})"
+        )?,
+        None => writeln!(printer.stdout(), "  Python: {implementation} {version}")?,
+    }
+
+    let (bytes, unit) = human_readable_bytes(directory_size(path));
+    writeln!(printer.stdout(), "  Size: {bytes:.1}{unit}")?;
+
+    if let Some(created) = get("uv-created") {
+        match get("uv-command") {
+            Some(command) => writeln!(printer.stdout(), "  Created: {created} (`{command}`)")?,
+            None => writeln!(printer.stdout(), "  Created: {created}")?,
+        }
+    }
+    if let Some(seed) = get("uv-seed") {
+        writeln!(printer.stdout(), "  Seed: {seed}")?;
+    }
+    if let Some(project) = get("uv-project") {
+        writeln!(printer.stdout(), "  Project: {project}")?;
+    }
+
+    let status = if let Some(reason) = staleness(path, get("uv-created"), get("uv-project")) {
+        format!("stale ({reason})").yellow().to_string()
+    } else {
+        "up-to-date".green().to_string()
+    };
+    writeln!(printer.stdout(), "  Status: {status}")?;
+
+    Ok(())
+}
+
+/// Determine whether an environment is stale, returning the reason if so.
+///
+/// An environment is stale if its base interpreter no longer exists, or if the project's
+/// dependency files (`pyproject.toml`, `uv.lock`, or `requirements*.txt`) were modified after the
+/// environment was created.
+fn staleness(path: &Path, created: Option<&str>, project: Option<&str>) -> Option<String> {
+    if !virtualenv_python_executable(path).exists() {
+        return Some("base interpreter not found".to_string());
+    }
+
+    let created = SystemTime::from(DateTime::parse_from_rfc3339(created?).ok()?);
+
+    // Default to the directory containing the environment for environments created outside of a
+    // project.
+    let project = project.map_or_else(|| path.parent(), |project| Some(Path::new(project)))?;
+
+    let mut files = fs_err::read_dir(project)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_name().to_str().is_some_and(|name| {
+                name == "pyproject.toml"
+                    || name == "uv.lock"
+                    || (name.starts_with("requirements")
+                        && Path::new(name)
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("txt")))
+            })
+        })
+        .collect::<Vec<_>>();
+    files.sort_by_key(fs_err::DirEntry::file_name);
+
+    files.into_iter().find_map(|entry| {
+        let modified = entry.metadata().ok()?.modified().ok()?;
+        (modified > created).then(|| {
+            format!(
+                "`{}` modified since creation",
+                entry.file_name().to_string_lossy()
+            )
+        })
+    })
+}
@@ -0,0 +1,124 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_configuration::PythonPreference;
+use uv_fs::Simplified;
+use uv_interpreter::{find_interpreter, InterpreterRequest, SourceSelector, SystemPython};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Repair a virtual environment whose base interpreter moved or was upgraded.
+pub(crate) fn venv_repair(
+    path: &Path,
+    python_request: Option<&str>,
+    python_preference: Option<PythonPreference>,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if !path.join("pyvenv.cfg").is_file() {
+        return Err(uv_virtualenv::Error::RepairNotVirtualenv(path.to_path_buf()).into());
+    }
+
+    // The environment's own interpreter may be broken, so read its settings from `pyvenv.cfg`
+    // rather than querying it.
+    let pyvenv_cfg = uv_virtualenv::read_pyvenv_cfg(path)
+        .with_context(|| format!("Failed to read `pyvenv.cfg` in: `{}`", path.user_display()))?;
+    let previous_version = pyvenv_cfg
+        .iter()
+        .find(|(key, _)| key == "version_info")
+        .map(|(_, value)| value.clone());
+
+    // Unless requested otherwise, use the same Python minor version as before.
+    let python_request = match python_request {
+        Some(python) => python.to_string(),
+        None => {
+            let Some(version) = previous_version.as_deref().and_then(|version| {
+                let mut parts = version.split('.');
+                Some(format!("{}.{}", parts.next()?, parts.next()?))
+            }) else {
+                anyhow::bail!(
+                    "Unable to determine the Python version of the virtual environment at `{}`; provide one with `--python`",
+                    path.user_display()
+                );
+            };
+            version
+        }
+    };
+
+    // Locate the base interpreter, ignoring any virtual environments.
+    let system = SystemPython::Required;
+    let request = InterpreterRequest::parse(&python_request);
+    let sources = SourceSelector::from_settings(system);
+    let interpreter =
+        find_interpreter(&request, system, &sources, None, cache)??.into_interpreter();
+    writeln!(
+        printer.stderr(),
+        "Using Python {} interpreter at: {}",
+        interpreter.python_version(),
+        interpreter.sys_executable().user_display().cyan()
+    )?;
+
+    // Unless requested otherwise, preserve whether the interpreter was symlinked or copied.
+    let python_preference = python_preference.unwrap_or_else(|| {
+        let executable = path.join("bin").join("python");
+        if cfg!(unix)
+            && fs_err::symlink_metadata(executable).is_ok_and(|metadata| !metadata.is_symlink())
+        {
+            PythonPreference::Copy
+        } else {
+            PythonPreference::Symlink
+        }
+    });
+
+    let (venv, repair) = uv_virtualenv::repair_venv(path, interpreter, python_preference)?;
+
+    // Rewrite any console scripts that refer to an interpreter outside the environment that no
+    // longer exists, or that belongs to another virtual environment (e.g., if the environment was
+    // moved or copied).
+    let executable = venv.python_executable();
+    let rewritten = install_wheel_rs::rewrite_shebangs(
+        venv.scripts(),
+        executable,
+        venv.interpreter().markers().os_name(),
+        |previous| {
+            previous
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("python"))
+                && (!previous.exists()
+                    || previous
+                        .parent()
+                        .and_then(Path::parent)
+                        .is_some_and(|root| root.join("pyvenv.cfg").is_file()))
+        },
+    )?;
+
+    writeln!(
+        printer.stderr(),
+        "Repaired virtualenv at: {}",
+        path.user_display().cyan()
+    )?;
+    if let Some(previous_version) = repair.previous_version {
+        let version = venv.interpreter().python_full_version();
+        if previous_version != version.to_string() {
+            writeln!(
+                printer.stderr(),
+                "  Python: {previous_version} -> {version}"
+            )?;
+        }
+    }
+    if !rewritten.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "  Rewrote {} script{}",
+            rewritten.len(),
+            if rewritten.len() == 1 { "" } else { "s" }
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
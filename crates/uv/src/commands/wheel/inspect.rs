@@ -0,0 +1,160 @@
+use std::fmt::Write;
+use std::io::Cursor;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+use url::Url;
+
+use distribution_filename::WheelFilename;
+use install_wheel_rs::inspect::{WheelInspection, INSPECTED_FILES};
+use uv_cache::Cache;
+use uv_client::{Connectivity, NetworkTimeouts, RegistryClientBuilder, ResponseLimits};
+use uv_configuration::{HostOverride, HttpVersion, IndexRateLimit, TrustedHost};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::summary::Summary;
+
+/// Show the tags, metadata, `RECORD` summary, and entry points of a wheel.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn inspect(
+    wheel: &str,
+    connectivity: Connectivity,
+    native_tls: bool,
+    hermetic: bool,
+    timeouts: NetworkTimeouts,
+    index_max_age: Option<u64>,
+    response_limits: ResponseLimits,
+    host_overrides: &[HostOverride],
+    trusted_hosts: &[TrustedHost],
+    rate_limits: &[IndexRateLimit],
+    http_version: Option<HttpVersion>,
+    cache: &Cache,
+    summary: &Summary,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let (filename, inspection) = match Url::parse(wheel) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {
+            let name = url
+                .path_segments()
+                .and_then(Iterator::last)
+                .unwrap_or_default();
+            let filename = WheelFilename::from_str(name)
+                .with_context(|| format!("Expected a wheel URL, but received: {url}"))?;
+
+            let client = RegistryClientBuilder::new(cache.clone())
+                .network_recorder(summary.network().clone())
+                .native_tls(native_tls)
+                .hermetic(hermetic)
+                .timeouts(timeouts)
+                .index_max_age(index_max_age)
+                .response_limits(response_limits)
+                .host_overrides(host_overrides.to_vec())
+                .trusted_hosts(trusted_hosts.to_vec())
+                .rate_limits(rate_limits.to_vec())
+                .http_version(http_version)
+                .connectivity(connectivity)
+                .build();
+
+            let inspection = match client
+                .wheel_dist_info_files(&filename, &url, INSPECTED_FILES)
+                .await
+            {
+                Ok((prefix, files)) => WheelInspection::from_dist_info_files(prefix, &files)?,
+                Err(err) if err.is_http_range_requests_unsupported() => {
+                    // If the server doesn't support range requests, download the entire wheel.
+                    debug!("Range requests not supported for {filename}; downloading wheel");
+                    let bytes = client
+                        .uncached_client()
+                        .get(url.clone())
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .bytes()
+                        .await?;
+                    WheelInspection::from_reader(&filename, Cursor::new(bytes))?
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Failed to inspect: {url}"));
+                }
+            };
+            (filename, inspection)
+        }
+        _ => {
+            let path = Path::new(wheel);
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let filename = WheelFilename::from_str(name)
+                .with_context(|| format!("Expected a wheel file, but received: {wheel}"))?;
+            let file = fs_err::File::open(path)?;
+            let inspection = WheelInspection::from_reader(&filename, file)?;
+            (filename, inspection)
+        }
+    };
+
+    writeln!(printer.stdout(), "{}", "Wheel:".bold())?;
+    writeln!(printer.stdout(), "  Filename: {filename}")?;
+    writeln!(
+        printer.stdout(),
+        "  Python tags: {}",
+        filename.python_tag.join(".")
+    )?;
+    writeln!(
+        printer.stdout(),
+        "  ABI tags: {}",
+        filename.abi_tag.join(".")
+    )?;
+    writeln!(
+        printer.stdout(),
+        "  Platform tags: {}",
+        filename.platform_tag.join(".")
+    )?;
+    for (key, value) in &inspection.wheel {
+        write_field(printer, key, value)?;
+    }
+
+    writeln!(printer.stdout())?;
+    writeln!(printer.stdout(), "{}", "Metadata:".bold())?;
+    for (key, value) in &inspection.metadata {
+        // Indent continuation lines (e.g., in `License`) to align with the field.
+        write_field(printer, key, &value.replace('\n', "\n    "))?;
+    }
+
+    writeln!(printer.stdout())?;
+    writeln!(printer.stdout(), "{}", "Record:".bold())?;
+    writeln!(printer.stdout(), "  Files: {}", inspection.record.files)?;
+    writeln!(printer.stdout(), "  Size: {} bytes", inspection.record.size)?;
+    writeln!(
+        printer.stdout(),
+        "  Unhashed: {}",
+        inspection.record.unhashed
+    )?;
+
+    if !inspection.entry_points.is_empty() {
+        writeln!(printer.stdout())?;
+        writeln!(printer.stdout(), "{}", "Entry points:".bold())?;
+        for (section, entries) in &inspection.entry_points {
+            writeln!(printer.stdout(), "  [{section}]")?;
+            for (name, value) in entries {
+                writeln!(printer.stdout(), "  {name} = {value}")?;
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Write a `Key: value` field, omitting the separator's trailing space for empty values.
+fn write_field(printer: Printer, key: &str, value: &str) -> Result<()> {
+    if value.is_empty() {
+        writeln!(printer.stdout(), "  {key}:")?;
+    } else {
+        writeln!(printer.stdout(), "  {key}: {value}")?;
+    }
+    Ok(())
+}
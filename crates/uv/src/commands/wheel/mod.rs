@@ -0,0 +1,2 @@
+pub(crate) mod inspect;
+pub(crate) mod retag;
@@ -0,0 +1,135 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use distribution_filename::WheelFilename;
+use install_wheel_rs::audit::{parse_manylinux_tag, WheelAudit};
+use install_wheel_rs::retag::retag_wheel;
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Rewrite the Python, ABI, or platform tags of a wheel.
+pub(crate) fn retag(
+    wheel: &Path,
+    python_tag: Option<&str>,
+    abi_tag: Option<&str>,
+    platform_tag: Option<&str>,
+    out_dir: Option<&Path>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let filename = parse_filename(wheel)?;
+
+    let split = |tag: &str| tag.split('.').map(ToString::to_string).collect::<Vec<_>>();
+    let retagged = WheelFilename {
+        python_tag: python_tag.map_or_else(|| filename.python_tag.clone(), split),
+        abi_tag: abi_tag.map_or_else(|| filename.abi_tag.clone(), split),
+        platform_tag: platform_tag.map_or_else(|| filename.platform_tag.clone(), split),
+        ..filename.clone()
+    };
+
+    write_retagged(wheel, &filename, &retagged, out_dir, printer)
+}
+
+/// Audit the shared libraries in a wheel against one or more `manylinux` platform tags, retagging
+/// the wheel for those tags if it's compatible.
+pub(crate) fn audit(
+    wheel: &Path,
+    platform_tag: &str,
+    out_dir: Option<&Path>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let filename = parse_filename(wheel)?;
+
+    let targets = platform_tag
+        .split('.')
+        .map(|tag| {
+            parse_manylinux_tag(tag).map(|target| (tag, target)).with_context(|| {
+                format!("Expected a `manylinux` platform tag (e.g., `manylinux_2_17_x86_64`), but received: `{tag}`")
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let audit = WheelAudit::from_reader(&filename, fs_err::File::open(wheel)?)
+        .with_context(|| format!("Failed to audit: {}", wheel.user_display()))?;
+
+    let mut violations = Vec::new();
+    for (tag, (glibc, arch)) in &targets {
+        for violation in audit.manylinux_violations(*glibc, *arch) {
+            violations.push(format!("`{tag}`: {violation}"));
+        }
+    }
+    if !violations.is_empty() {
+        let mut message = format!("`{filename}` is incompatible with the requested platform tags:");
+        for violation in violations {
+            write!(message, "\n  - {violation}")?;
+        }
+        bail!(message);
+    }
+
+    writeln!(
+        printer.stderr(),
+        "Audited {} shared {}",
+        audit.libraries.len(),
+        if audit.libraries.len() == 1 {
+            "library"
+        } else {
+            "libraries"
+        }
+    )?;
+
+    let retagged = WheelFilename {
+        platform_tag: targets.iter().map(|(tag, _)| (*tag).to_string()).collect(),
+        ..filename.clone()
+    };
+    write_retagged(wheel, &filename, &retagged, out_dir, printer)
+}
+
+/// Parse the [`WheelFilename`] from the path to a wheel.
+fn parse_filename(wheel: &Path) -> Result<WheelFilename> {
+    let name = wheel
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    WheelFilename::from_str(name).with_context(|| {
+        format!(
+            "Expected a wheel file, but received: {}",
+            wheel.user_display()
+        )
+    })
+}
+
+/// Write the retagged wheel to the output directory (by default, alongside the original wheel).
+fn write_retagged(
+    wheel: &Path,
+    filename: &WheelFilename,
+    retagged: &WheelFilename,
+    out_dir: Option<&Path>,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let out_dir = match out_dir.or_else(|| wheel.parent()) {
+        Some(out_dir) if !out_dir.as_os_str().is_empty() => out_dir,
+        _ => Path::new("."),
+    };
+    fs_err::create_dir_all(out_dir)?;
+    let target = out_dir.join(retagged.to_string());
+
+    // Write to a temporary file, then persist it, in case the target is the original wheel.
+    let mut temp = tempfile::NamedTempFile::new_in(out_dir)?;
+    retag_wheel(
+        filename,
+        retagged,
+        fs_err::File::open(wheel)?,
+        temp.as_file_mut(),
+    )
+    .with_context(|| format!("Failed to retag: {}", wheel.user_display()))?;
+    temp.persist(&target)?;
+
+    writeln!(printer.stderr(), "Wrote {}", target.user_display().cyan())?;
+
+    Ok(ExitStatus::Success)
+}
@@ -4,6 +4,7 @@ use std::ops::Deref;
 use anyhow::{anyhow, Context, Result};
 use indexmap::IndexMap;
 use owo_colors::OwoColorize;
+use tracing::debug;
 
 use distribution_types::{
     InstalledDist, LocalEditable, LocalEditables, Name, ParsedUrlError, Requirement, Requirements,
@@ -15,13 +16,14 @@ use uv_client::RegistryClient;
 use uv_configuration::{Concurrency, Reinstall};
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
-use uv_installer::{is_dynamic, Downloader, InstalledEditable, ResolvedEditable};
+use uv_fs::Simplified;
+use uv_installer::{DynamicMetadata, InstalledEditable, Preparer, ResolvedEditable};
 use uv_interpreter::Interpreter;
 use uv_resolver::BuiltEditableMetadata;
 use uv_types::{HashStrategy, InstalledPackagesProvider};
 
 use crate::commands::elapsed;
-use crate::commands::reporters::DownloadReporter;
+use crate::commands::reporters::PrepareReporter;
 use crate::printer::Printer;
 
 #[derive(Debug, Default)]
@@ -99,19 +101,19 @@ impl ResolvedEditables {
         } else {
             let start = std::time::Instant::now();
 
-            let downloader = Downloader::new(
+            let preparer = Preparer::new(
                 cache,
                 tags,
                 hasher,
                 DistributionDatabase::new(client, build_dispatch, concurrency.downloads),
             )
-            .with_reporter(DownloadReporter::from(printer).with_length(builds.len() as u64));
+            .with_reporter(PrepareReporter::from(printer).with_length(builds.len() as u64));
 
             let editables = LocalEditables::from_editables(builds.into_iter());
 
             let temp_dir = tempfile::tempdir_in(cache.root())?;
 
-            let editables: Vec<_> = downloader
+            let editables: Vec<_> = preparer
                 .build_editables(editables, temp_dir.path())
                 .await
                 .context("Failed to build editables")?
@@ -132,17 +134,21 @@ impl ResolvedEditables {
                 }
             }
 
-            let s = if editables.len() == 1 { "" } else { "s" };
-            writeln!(
-                printer.stderr(),
-                "{}",
-                format!(
-                    "Built {} in {}",
-                    format!("{} editable{}", editables.len(), s).bold(),
-                    elapsed(start.elapsed())
-                )
-                .dimmed()
-            )?;
+            // Editables that were reused from the cache weren't built.
+            let built = editables.iter().filter(|editable| !editable.cached).count();
+            if built > 0 {
+                let s = if built == 1 { "" } else { "s" };
+                writeln!(
+                    printer.stderr(),
+                    "{}",
+                    format!(
+                        "Built {} in {}",
+                        format!("{built} editable{s}").bold(),
+                        elapsed(start.elapsed())
+                    )
+                    .dimmed()
+                )?;
+            }
 
             (editables, Some(temp_dir))
         };
@@ -199,8 +205,13 @@ fn up_to_date(editable: &LocalEditable, dist: &InstalledDist) -> Result<Option<I
         return Ok(None);
     };
 
-    // If the editable is dynamic, don't reuse it.
-    if is_dynamic(&editable.path) {
+    // If the editable is dynamic, its metadata may be stale, so re-prepare it.
+    let dynamic = DynamicMetadata::from_source_tree(&editable.path);
+    if dynamic.affects_install() {
+        debug!(
+            "Editable at {} has dynamic metadata ({dynamic}); rebuilding",
+            editable.path.user_display()
+        );
         return Ok(None);
     };
 
@@ -0,0 +1,189 @@
+use anstream::eprint;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use uv_configuration::PreviewMode;
+use uv_fs::LockTimeout;
+use uv_requirements::CommandLineRequirementError;
+use uv_resolver::{NoSolutionError, NoSolutionReport, ResolveError};
+
+use crate::cli::OutputFormat;
+use crate::commands::ExitStatus;
+use crate::messages::{Catalog, Message};
+
+/// A machine-readable error, written to `stderr` as a single line of JSON.
+#[derive(Debug, Serialize)]
+struct JsonError {
+    /// The top-level error message.
+    message: String,
+    /// The `miette` diagnostic code, if any (e.g., `uv::venv::creation`).
+    code: Option<String>,
+    /// The chain of underlying causes, from outermost to innermost.
+    causes: Vec<String>,
+    /// The resolver's explanation, if the error is a resolution failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution: Option<NoSolutionReport>,
+    /// A suggestion for fixing the error, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl JsonError {
+    fn emit(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => anstream::eprintln!("{json}"),
+            Err(err) => anstream::eprintln!("{}: {err}", "error".red().bold()),
+        }
+    }
+}
+
+/// Strip any ANSI escape codes from a message, for inclusion in JSON output.
+fn plain(message: impl std::fmt::Display) -> String {
+    anstream::adapter::strip_str(&message.to_string()).to_string()
+}
+
+/// Find a suggestion for fixing the error anywhere in its chain, if any.
+fn hint(err: &anyhow::Error) -> Option<&str> {
+    err.chain().find_map(|cause| {
+        if let Some(err) = cause.downcast_ref::<CommandLineRequirementError>() {
+            return err.hint();
+        }
+        cause
+            .downcast_ref::<std::io::Error>()
+            .and_then(LockTimeout::from_io_error)
+            .map(|_| "Increase `UV_LOCK_TIMEOUT` to wait longer for the lock")
+    })
+}
+
+/// Report a top-level error, as returned from a command.
+pub(crate) fn report_error(err: &anyhow::Error, output_format: OutputFormat, messages: &Catalog) {
+    if output_format == OutputFormat::Json {
+        JsonError {
+            message: plain(err),
+            code: None,
+            causes: err.chain().skip(1).map(plain).collect(),
+            resolution: err
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<NoSolutionError>())
+                .map(NoSolutionError::report),
+            hint: hint(err).map(plain),
+        }
+        .emit();
+    } else {
+        let mut causes = err.chain();
+        anstream::eprintln!(
+            "{}: {}",
+            messages.format(Message::Error, &[]).red().bold(),
+            causes.next().unwrap()
+        );
+        for err in causes {
+            anstream::eprintln!(
+                "  {}: {}",
+                messages.format(Message::CausedBy, &[]).red().bold(),
+                err
+            );
+        }
+        if let Some(hint) = hint(err) {
+            anstream::eprintln!("\n{}{} {hint}", "hint".bold().cyan(), ":".bold());
+        }
+    }
+}
+
+/// Report a `miette` diagnostic, including its diagnostic code.
+pub(crate) fn report_diagnostic(report: &miette::Report, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        JsonError {
+            message: plain(report),
+            code: report.code().map(plain),
+            causes: report.chain().skip(1).map(plain).collect(),
+            resolution: None,
+            hint: None,
+        }
+        .emit();
+    } else {
+        eprint!("{report:?}");
+    }
+}
+
+/// Report a resolution failure, including the derivation tree that explains it.
+///
+/// In preview mode, the report is followed by suggested fixes (e.g., the nearest available
+/// versions, or a direct requirement to relax).
+pub(crate) fn report_no_solution(
+    err: &NoSolutionError,
+    preview: PreviewMode,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json {
+        JsonError {
+            message: "No solution found when resolving dependencies".to_string(),
+            code: Some("uv::resolve::no_solution".to_string()),
+            causes: Vec::new(),
+            resolution: Some(err.report()),
+            hint: None,
+        }
+        .emit();
+    } else {
+        let mut message = format!("{err}");
+        if preview.is_enabled() {
+            for suggestion in err.suggestions() {
+                message.push_str(&format!("\n\n{suggestion}"));
+            }
+        }
+        let report =
+            miette::Report::msg(message).context("No solution found when resolving dependencies:");
+        eprint!("{report:?}");
+    }
+}
+
+/// Classify a top-level error into an [`ExitStatus`], such that scripts can branch on the kind of
+/// failure without parsing `stderr`.
+///
+/// The innermost recognized cause wins, such that (e.g.) a build that failed because its build
+/// requirements couldn't be downloaded is reported as a network failure.
+pub(crate) fn exit_status(err: &anyhow::Error) -> ExitStatus {
+    err.chain()
+        .filter_map(classify)
+        .last()
+        .unwrap_or(ExitStatus::Error)
+}
+
+/// Return the [`ExitStatus`] for a single cause in an error chain, if it's a recognized kind.
+fn classify(cause: &(dyn std::error::Error + 'static)) -> Option<ExitStatus> {
+    if let Some(err) = cause.downcast_ref::<ResolveError>() {
+        return matches!(err, ResolveError::NoSolution(_))
+            .then_some(ExitStatus::ResolutionConflict);
+    }
+    if cause.is::<NoSolutionError>() {
+        return Some(ExitStatus::ResolutionConflict);
+    }
+    if let Some(err) = cause.downcast_ref::<uv_client::Error>() {
+        return err
+            .is_network_failure()
+            .then_some(ExitStatus::NetworkFailure);
+    }
+    if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+        return uv_client::is_network_failure(err).then_some(ExitStatus::NetworkFailure);
+    }
+    if let Some(err) = cause.downcast_ref::<uv_distribution::Error>() {
+        return match err {
+            uv_distribution::Error::MismatchedHashes { .. }
+            | uv_distribution::Error::MissingHashes { .. }
+            | uv_distribution::Error::MissingActualHashes { .. }
+            | uv_distribution::Error::MissingExpectedHashes { .. } => {
+                Some(ExitStatus::HashMismatch)
+            }
+            uv_distribution::Error::Build(..) | uv_distribution::Error::BuildEditable(..) => {
+                Some(ExitStatus::BuildFailure)
+            }
+            _ => None,
+        };
+    }
+    if cause.is::<uv_build::Error>() {
+        return Some(ExitStatus::BuildFailure);
+    }
+    if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+        return LockTimeout::from_io_error(err).map(|_| ExitStatus::EnvironmentLocked);
+    }
+    None
+}
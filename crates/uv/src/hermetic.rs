@@ -0,0 +1,102 @@
+//! Hermetic (`--hermetic`) mode, in which uv ignores all ambient configuration from the
+//! environment, such that only the arguments provided on the command-line take effect.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+
+/// The prefixes of environment variables that configure uv, or any `pip` invoked on uv's behalf.
+const CONFIGURATION_PREFIXES: &[&str] = &["UV_", "PIP_"];
+
+/// The prefixes of environment variables that uv uses to communicate with its own child processes
+/// and test harness, or that only affect the presentation of its output, rather than to accept
+/// configuration.
+const PRESERVED_PREFIXES: &[&str] = &["UV_INTERNAL__", "UV_TEST_", "UV_NO_WRAP", "UV_STACK_SIZE"];
+
+/// Returns `true` if `--hermetic` appears among the given command-line arguments, before any `--`
+/// separator.
+///
+/// This is only a hint, used to clear the environment before the command-line is parsed; the
+/// parsed arguments are authoritative (e.g., `--hermetic` may be an argument to a command that's
+/// passed through by `uv run`).
+pub(crate) fn requested(args: impl IntoIterator<Item = OsString>) -> bool {
+    args.into_iter()
+        .skip(1)
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == "--hermetic")
+}
+
+/// Remove all uv and pip configuration from the process environment, returning the removed
+/// variables.
+///
+/// Since the environment is inherited, this also applies to any child processes (e.g., build
+/// backends).
+///
+/// Must be called while the process is single-threaded, i.e., before the runtime is started.
+pub(crate) fn clear_environment() -> Vec<(OsString, OsString)> {
+    let removed = env::vars_os()
+        .filter(|(key, _)| is_configuration(key))
+        .collect::<Vec<_>>();
+    for (key, _) in &removed {
+        env::remove_var(key);
+    }
+    removed
+}
+
+/// Restore variables removed by [`clear_environment`].
+///
+/// Must be called while the process is single-threaded, i.e., before the runtime is started.
+pub(crate) fn restore_environment(removed: Vec<(OsString, OsString)>) {
+    for (key, value) in removed {
+        env::set_var(key, value);
+    }
+}
+
+/// Returns `true` if the given environment variable configures uv or pip.
+fn is_configuration(key: &OsStr) -> bool {
+    let Some(key) = key.to_str() else {
+        return false;
+    };
+    CONFIGURATION_PREFIXES
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+        && !PRESERVED_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{OsStr, OsString};
+
+    use super::{is_configuration, requested};
+
+    #[test]
+    fn configuration() {
+        assert!(is_configuration(OsStr::new("UV_INDEX_URL")));
+        assert!(is_configuration(OsStr::new("PIP_INDEX_URL")));
+        assert!(!is_configuration(OsStr::new(
+            "UV_INTERNAL__PARENT_INTERPRETER"
+        )));
+        assert!(!is_configuration(OsStr::new("UV_TEST_PYTHON_PATH")));
+        assert!(!is_configuration(OsStr::new("UV_NO_WRAP")));
+        assert!(!is_configuration(OsStr::new("UV_STACK_SIZE")));
+        assert!(!is_configuration(OsStr::new("VIRTUAL_ENV")));
+        assert!(!is_configuration(OsStr::new("PIPX_HOME")));
+    }
+
+    #[test]
+    fn hermetic_requested() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert!(requested(args(&["uv", "pip", "compile", "--hermetic"])));
+        assert!(requested(args(&["uv", "--hermetic", "pip", "sync"])));
+        assert!(!requested(args(&["uv", "pip", "compile"])));
+        assert!(!requested(args(&[
+            "uv",
+            "run",
+            "--",
+            "script",
+            "--hermetic"
+        ])));
+        assert!(!requested(args(&["--hermetic"])));
+    }
+}
@@ -1,10 +1,17 @@
+use std::cell::Cell;
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anstream::ColorChoice;
 use anyhow::Context;
 use chrono::Utc;
 use owo_colors::OwoColorize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
 use tracing::{Event, Subscriber};
 #[cfg(feature = "tracing-durations-export")]
 use tracing_durations_export::{
@@ -20,6 +27,11 @@ use tracing_subscriber::{EnvFilter, Layer, Registry};
 use tracing_tree::time::Uptime;
 use tracing_tree::HierarchicalLayer;
 
+use uv_warnings::warn_user;
+
+use crate::commands::elapsed;
+use crate::summary::{Packages, Summary};
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Level {
     /// Suppress all tracing output by default (overridable by `RUST_LOG`).
@@ -115,6 +127,7 @@ where
 pub(crate) fn setup_logging(
     level: Level,
     durations: impl Layer<Registry> + Send + Sync,
+    timings: Option<TimingsLayer>,
 ) -> anyhow::Result<()> {
     let default_directive = match level {
         Level::Default => {
@@ -132,6 +145,10 @@ pub(crate) fn setup_logging(
         tracing_subscriber::filter::Targets::new()
             .with_target("", tracing::level_filters::LevelFilter::INFO),
     );
+    let timings_layer = timings.with_filter(
+        tracing_subscriber::filter::Targets::new()
+            .with_target("", tracing::level_filters::LevelFilter::INFO),
+    );
 
     let filter = EnvFilter::builder()
         .with_default_directive(default_directive)
@@ -154,6 +171,7 @@ pub(crate) fn setup_logging(
             };
             tracing_subscriber::registry()
                 .with(durations_layer)
+                .with(timings_layer)
                 .with(
                     tracing_subscriber::fmt::layer()
                         .event_format(format)
@@ -167,6 +185,7 @@ pub(crate) fn setup_logging(
             // Regardless of the tracing level, include the uptime and target for each message.
             tracing_subscriber::registry()
                 .with(durations_layer)
+                .with(timings_layer)
                 .with(
                     HierarchicalLayer::default()
                         .with_targets(true)
@@ -214,3 +233,324 @@ pub(crate) fn setup_duration() -> anyhow::Result<(
         Ok((None, None))
     }
 }
+
+/// The phases reported by `--timings`, along with the names of the spans that belong to each.
+const PHASES: &[(&str, &[&str])] = &[
+    ("Resolve", &["solve"]),
+    ("Download", &["download", "get_wheel"]),
+    (
+        "Build",
+        &["build_distribution", "build_metadata", "build_editables"],
+    ),
+    ("Install", &["install", "install_wheel", "compile_tree"]),
+];
+
+/// A closed span, as recorded by the [`TimingsLayer`].
+#[derive(Debug)]
+struct SpanTiming {
+    name: &'static str,
+    target: &'static str,
+    start: Instant,
+    end: Instant,
+    thread: u64,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The state attached to a span while it's open.
+#[derive(Debug)]
+struct OpenSpan {
+    start: Instant,
+    thread: u64,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Records span fields as JSON values, for inclusion in the trace output.
+#[derive(Debug, Default)]
+struct JsonVisitor(serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+/// Return a small, stable identifier for the current thread.
+fn thread_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static ID: Cell<u64> = const { Cell::new(0) };
+    }
+    ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+/// A [`Layer`] that records the start and end of every span, to support `--timings`,
+/// `--trace-output`, and `--summary-file`.
+pub(crate) struct TimingsLayer {
+    spans: Arc<Mutex<Vec<SpanTiming>>>,
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &Attributes<'_>,
+        id: &Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = JsonVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(OpenSpan {
+            start: Instant::now(),
+            thread: thread_id(),
+            fields: visitor.0,
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(open) = span.extensions_mut().remove::<OpenSpan>() else {
+            return;
+        };
+        let timing = SpanTiming {
+            name: span.metadata().name(),
+            target: span.metadata().target(),
+            start: open.start,
+            end: Instant::now(),
+            thread: open.thread,
+            fields: open.fields,
+        };
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(timing);
+        }
+    }
+}
+
+/// Writes the `--trace-output` and `--summary-file` files and prints the `--timings` summary when
+/// dropped.
+pub(crate) struct TimingsGuard {
+    epoch: Instant,
+    spans: Arc<Mutex<Vec<SpanTiming>>>,
+    trace_output: Option<PathBuf>,
+    summary: bool,
+    summary_file: Option<PathBuf>,
+    counts: Summary,
+}
+
+impl TimingsGuard {
+    /// Write the recorded spans to the given file, in the Chrome trace event format.
+    ///
+    /// The resulting file can be loaded in Perfetto (<https://ui.perfetto.dev>) or
+    /// `chrome://tracing`.
+    fn write_trace(&self, spans: &[SpanTiming], path: &PathBuf) -> anyhow::Result<()> {
+        let pid = std::process::id();
+        let events = spans
+            .iter()
+            .map(|span| {
+                serde_json::json!({
+                    "name": span.name,
+                    "cat": span.target,
+                    "ph": "X",
+                    "ts": span.start.duration_since(self.epoch).as_secs_f64() * 1_000_000.0,
+                    "dur": span.end.duration_since(span.start).as_secs_f64() * 1_000_000.0,
+                    "pid": pid,
+                    "tid": span.thread,
+                    "args": span.fields,
+                })
+            })
+            .collect::<Vec<_>>();
+        let trace = serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        });
+
+        if let Some(parent) = path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(path, serde_json::to_vec(&trace)?)?;
+        Ok(())
+    }
+
+    /// Write a machine-readable summary of the command to the given file, as JSON.
+    ///
+    /// The summary includes the wall time spent in each phase, the HTTP cache hit rate, the
+    /// number of bytes downloaded, and the number of packages resolved, downloaded, built,
+    /// installed, and uninstalled.
+    fn write_summary_file(&self, spans: &[SpanTiming], path: &PathBuf) -> anyhow::Result<()> {
+        let phases = PHASES
+            .iter()
+            .map(|(phase, names)| {
+                (
+                    phase.to_ascii_lowercase(),
+                    phase_time(spans, names).as_secs_f64().into(),
+                )
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+
+        let mut packages = Packages::ALL
+            .iter()
+            .map(|packages| {
+                (
+                    packages.key().to_string(),
+                    self.counts.count(*packages).into(),
+                )
+            })
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        packages.insert("built".to_string(), self.counts.builds().count().into());
+
+        let network = self.counts.network().stats();
+        let summary = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "total": self.epoch.elapsed().as_secs_f64(),
+            "phases": phases,
+            "cache": {
+                "hits": network.cache_hits,
+                "misses": network.cache_misses,
+                "hit_rate": network.cache_hit_rate(),
+            },
+            "bytes_downloaded": network.bytes_downloaded,
+            "packages": packages,
+        });
+
+        if let Some(parent) = path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(path, serde_json::to_vec_pretty(&summary)?)?;
+        Ok(())
+    }
+
+    /// Print the wall time spent in each phase to `stderr`.
+    fn write_summary(&self, spans: &[SpanTiming]) {
+        anstream::eprintln!("{}", "Timings:".bold());
+        for (phase, names) in PHASES {
+            anstream::eprintln!(
+                "  {:<10}{}",
+                phase,
+                elapsed(phase_time(spans, names)).dimmed()
+            );
+        }
+        anstream::eprintln!(
+            "  {:<10}{}",
+            "Total",
+            elapsed(self.epoch.elapsed()).dimmed()
+        );
+    }
+}
+
+impl Drop for TimingsGuard {
+    fn drop(&mut self) {
+        let spans = match self.spans.lock() {
+            Ok(mut spans) => std::mem::take(&mut *spans),
+            Err(_) => return,
+        };
+
+        if let Some(path) = self.trace_output.as_ref() {
+            if let Err(err) = self.write_trace(&spans, path) {
+                warn_user!("Failed to write trace to {}: {err}", path.display());
+            }
+        }
+
+        if let Some(path) = self.summary_file.as_ref() {
+            if let Err(err) = self.write_summary_file(&spans, path) {
+                warn_user!("Failed to write summary to {}: {err}", path.display());
+            }
+        }
+
+        if self.summary {
+            self.write_summary(&spans);
+        }
+    }
+}
+
+/// Compute the wall time spent in the spans with the given names.
+fn phase_time(spans: &[SpanTiming], names: &[&str]) -> Duration {
+    wall_time(
+        spans
+            .iter()
+            .filter(|span| names.contains(&span.name))
+            .map(|span| (span.start, span.end))
+            .collect(),
+    )
+}
+
+/// Compute the wall time covered by a set of (possibly overlapping) intervals.
+fn wall_time(mut intervals: Vec<(Instant, Instant)>) -> Duration {
+    intervals.sort_unstable();
+    let mut total = Duration::ZERO;
+    let mut current: Option<(Instant, Instant)> = None;
+    for (start, end) in intervals {
+        match current {
+            Some((current_start, current_end)) if start <= current_end => {
+                current = Some((current_start, current_end.max(end)));
+            }
+            _ => {
+                if let Some((current_start, current_end)) = current {
+                    total += current_end.duration_since(current_start);
+                }
+                current = Some((start, end));
+            }
+        }
+    }
+    if let Some((current_start, current_end)) = current {
+        total += current_end.duration_since(current_start);
+    }
+    total
+}
+
+/// Set up span recording for `--timings`, `--trace-output`, and `--summary-file`.
+///
+/// The `--summary-file` report includes the package counts and network statistics accumulated in
+/// `counts` by the time the guard is dropped.
+///
+/// Returns `None` if none are enabled, to avoid the overhead of recording every span.
+pub(crate) fn setup_timings(
+    trace_output: Option<PathBuf>,
+    summary: bool,
+    summary_file: Option<PathBuf>,
+    counts: Summary,
+) -> (Option<TimingsLayer>, Option<TimingsGuard>) {
+    if trace_output.is_none() && !summary && summary_file.is_none() {
+        return (None, None);
+    }
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let layer = TimingsLayer {
+        spans: spans.clone(),
+    };
+    let guard = TimingsGuard {
+        epoch: Instant::now(),
+        spans,
+        trace_output,
+        summary,
+        summary_file,
+        counts,
+    };
+    (Some(layer), Some(guard))
+}
@@ -3,27 +3,34 @@ use std::io::stdout;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
-use anstream::eprintln;
 use anyhow::Result;
 use clap::error::{ContextKind, ContextValue};
 use clap::{CommandFactory, Parser};
-use owo_colors::OwoColorize;
 use tracing::instrument;
 
-use cli::{ToolCommand, ToolNamespace};
-use uv_cache::Cache;
-use uv_requirements::RequirementsSource;
+use cli::{
+    EnvironmentCommand, EnvironmentNamespace, IndexCommand, IndexNamespace, InterpreterCommand,
+    InterpreterNamespace, ToolCommand, ToolNamespace, WheelCommand, WheelNamespace,
+};
+use install_wheel_rs::{InstallFilter, ScriptOptions};
+use uv_cache::{Cache, Refresh};
+use uv_requirements::{ExtrasSpecification, ProjectWorkspace, RequirementsSource};
 use uv_workspace::Combine;
 
-use crate::cli::{CacheCommand, CacheNamespace, Cli, Commands, PipCommand, PipNamespace};
+use crate::cli::{
+    CacheBuildsCommand, CacheBuildsNamespace, CacheCommand, CacheNamespace, Cli, Commands,
+    PipCommand, PipNamespace,
+};
 #[cfg(feature = "self-update")]
 use crate::cli::{SelfCommand, SelfNamespace};
 use crate::commands::ExitStatus;
 use crate::compat::CompatArgs;
 use crate::settings::{
     CacheSettings, GlobalSettings, PipCheckSettings, PipCompileSettings, PipFreezeSettings,
-    PipInstallSettings, PipListSettings, PipShowSettings, PipSyncSettings, PipUninstallSettings,
+    PipHashSettings, PipInstallSettings, PipLintSettings, PipListSettings, PipShowSettings,
+    PipSyncSettings, PipUninstallSettings, PipWhySettings,
 };
+use crate::summary::Summary;
 
 #[cfg(target_os = "windows")]
 #[global_allocator]
@@ -41,19 +48,27 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 #[global_allocator]
 static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
+mod cancellation;
 mod cli;
 mod commands;
 mod compat;
 mod editables;
+mod errors;
+mod hermetic;
 mod logging;
+mod messages;
 mod printer;
 mod settings;
 mod shell;
+mod signature;
+mod summary;
+mod symlinks;
 mod version;
 
-#[instrument]
-async fn run() -> Result<ExitStatus> {
-    let cli = match Cli::try_parse() {
+/// Parse the command-line, suggesting the equivalent `uv pip` command for any pip-like
+/// subcommand.
+fn parse_cli() -> Cli {
+    match Cli::try_parse() {
         Ok(cli) => cli,
         Err(mut err) => {
             if let Some(ContextValue::String(subcommand)) = err.get(ContextKind::InvalidSubcommand)
@@ -106,15 +121,21 @@ async fn run() -> Result<ExitStatus> {
             }
             err.exit()
         }
-    };
+    }
+}
 
+#[instrument(skip_all)]
+async fn run(cli: Cli, messages: &'static messages::Catalog) -> Result<ExitStatus> {
     // Load the workspace settings, prioritizing (in order):
     // 1. The configuration file specified on the command-line.
     // 2. The configuration file in the current directory.
     // 3. The user configuration file.
+    //
+    // In isolated and hermetic modes, only a configuration file specified on the command-line is
+    // used.
     let workspace = if let Some(config_file) = cli.config_file.as_ref() {
         Some(uv_workspace::Workspace::from_file(config_file)?)
-    } else if cli.global_args.isolated {
+    } else if cli.global_args.isolated || cli.global_args.hermetic {
         None
     } else {
         let project = uv_workspace::Workspace::find(env::current_dir()?)?;
@@ -125,11 +146,22 @@ async fn run() -> Result<ExitStatus> {
     // Resolve the global settings.
     let globals = GlobalSettings::resolve(cli.global_args, workspace.as_ref());
 
+    // Configure the styling of user-facing output, including logs and errors.
+    let policy = printer::OutputPolicy::from_env(globals.color);
+    policy.install();
+
     // Configure the `tracing` crate, which controls internal logging.
     #[cfg(feature = "tracing-durations-export")]
     let (duration_layer, _duration_guard) = logging::setup_duration()?;
     #[cfg(not(feature = "tracing-durations-export"))]
     let duration_layer = None::<tracing_subscriber::layer::Identity>;
+    let summary = Summary::default();
+    let (timings_layer, _timings_guard) = logging::setup_timings(
+        globals.trace_output.clone(),
+        globals.timings,
+        globals.summary_file.clone(),
+        summary.clone(),
+    );
     logging::setup_logging(
         match globals.verbose {
             0 => logging::Level::Default,
@@ -137,38 +169,49 @@ async fn run() -> Result<ExitStatus> {
             2.. => logging::Level::ExtraVerbose,
         },
         duration_layer,
+        timings_layer,
     )?;
 
     // Configure the `Printer`, which controls user-facing output in the CLI.
-    let printer = if globals.quiet {
-        printer::Printer::Quiet
-    } else if globals.verbose > 0 {
-        printer::Printer::Verbose
-    } else {
-        printer::Printer::Default
-    };
+    let printer = printer::Printer::new(
+        if globals.quiet {
+            printer::Verbosity::Quiet
+        } else if globals.verbose > 0 {
+            printer::Verbosity::Verbose
+        } else {
+            printer::Verbosity::Default
+        },
+        policy,
+        messages,
+        globals.output_format,
+    );
 
     // Configure the `warn!` macros, which control user-facing warnings in the CLI.
     if !globals.quiet {
         uv_warnings::enable();
     }
 
-    anstream::ColorChoice::write_global(globals.color.into());
-
-    miette::set_hook(Box::new(|_| {
-        Box::new(
-            miette::MietteHandlerOpts::new()
-                .break_words(false)
-                .word_separator(textwrap::WordSeparator::AsciiSpace)
-                .word_splitter(textwrap::WordSplitter::NoHyphenation)
-                .wrap_lines(env::var("UV_NO_WRAP").map(|_| false).unwrap_or(true))
-                .build(),
-        )
+    // Cancel any in-flight resolutions, downloads, and installations on Ctrl-C.
+    cancellation::cancel_on_ctrl_c();
+
+    miette::set_hook(Box::new(move |_| {
+        let mut options = miette::MietteHandlerOpts::new()
+            .break_words(false)
+            .word_separator(textwrap::WordSeparator::AsciiSpace)
+            .word_splitter(textwrap::WordSplitter::NoHyphenation)
+            .wrap_lines(env::var("UV_NO_WRAP").map(|_| false).unwrap_or(true))
+            .color(policy.color());
+        if let Some(width) = policy.width() {
+            options = options.width(width);
+        }
+        Box::new(options.build())
     }))?;
 
     // Resolve the cache settings.
     let cache = CacheSettings::resolve(cli.cache_args, workspace.as_ref());
-    let cache = Cache::from_settings(cache.no_cache, cache.cache_dir)?;
+    let cache = Cache::from_settings(cache.no_cache, cache.cache_dir)?
+        .with_build_dir(cache.build_dir)
+        .with_lock_timeout(globals.lock_timeout);
 
     match cli.command {
         Commands::Pip(PipNamespace {
@@ -202,50 +245,138 @@ async fn run() -> Result<ExitStatus> {
                 .map(RequirementsSource::from_overrides_txt)
                 .collect::<Vec<_>>();
 
-            commands::pip_compile(
-                &requirements,
-                &constraints,
-                &overrides,
-                args.shared.extras,
-                args.shared.output_file.as_deref(),
-                args.shared.resolution,
-                args.shared.prerelease,
-                args.shared.dependency_mode,
-                args.upgrade,
-                args.shared.generate_hashes,
-                args.shared.no_emit_package,
-                args.shared.no_strip_extras,
-                !args.shared.no_annotate,
-                !args.shared.no_header,
-                args.shared.custom_compile_command,
-                args.shared.emit_index_url,
-                args.shared.emit_find_links,
-                args.shared.emit_marker_expression,
-                args.shared.emit_index_annotation,
-                args.shared.index_locations,
-                args.shared.index_strategy,
-                args.shared.keyring_provider,
-                args.shared.setup_py,
-                args.shared.config_setting,
-                globals.connectivity,
-                args.shared.no_build_isolation,
-                args.shared.no_build,
-                args.shared.python_version,
-                args.shared.python_platform,
-                args.shared.exclude_newer,
-                args.shared.annotation_style,
-                args.shared.link_mode,
-                args.shared.python,
-                args.shared.system,
-                args.shared.concurrency,
-                args.uv_lock,
-                globals.native_tls,
-                globals.quiet,
-                globals.preview,
-                cache,
-                printer,
-            )
-            .await
+            // Resolve each requirement group concurrently, sharing in-memory indexes across
+            // resolutions that target the same environment. Without any groups, resolve the
+            // command-line requirements on their own.
+            let groups = if args.group.is_empty() {
+                vec![None]
+            } else {
+                args.group.into_iter().map(Some).collect()
+            };
+            let indexes = commands::SharedIndexes::default();
+            let (uv_lock, interactive, graph) = (args.uv_lock, args.interactive, args.graph);
+            let shared = &args.shared;
+            let upgrade = &args.upgrade;
+            let statuses = futures::future::try_join_all(groups.into_iter().map(|group| {
+                let requirements = match group.as_ref() {
+                    Some(group) if !group.requirements.is_empty() => group
+                        .requirements
+                        .iter()
+                        .cloned()
+                        .map(RequirementsSource::from_requirements_file)
+                        .collect::<Vec<_>>(),
+                    _ => requirements.clone(),
+                };
+                let output_file = group.as_ref().map_or_else(
+                    || shared.output_file.clone(),
+                    |group| Some(group.output_file.clone()),
+                );
+                let python_version = group
+                    .as_ref()
+                    .and_then(|group| group.python_version.clone())
+                    .or_else(|| shared.python_version.clone());
+                let python_platform = group
+                    .as_ref()
+                    .and_then(|group| group.python_platform)
+                    .or(shared.python_platform);
+                let extras = match group.as_ref().and_then(|group| group.extras.as_ref()) {
+                    Some(extras) => ExtrasSpecification::from_args(false, extras.clone()),
+                    None => shared.extras.clone(),
+                };
+                // When resolving multiple groups, only write each resolution to its output file.
+                let quiet = globals.quiet || group.is_some();
+                let constraints = &constraints;
+                let overrides = &overrides;
+                let cache = cache.clone();
+                let indexes = &indexes;
+                let host_overrides = &globals.host_overrides;
+                let trusted_hosts = &globals.trusted_hosts;
+                let rate_limits = &globals.rate_limits;
+                let venv_dir = globals.venv_dir.as_deref();
+                async move {
+                    commands::pip_compile(
+                        &requirements,
+                        constraints,
+                        overrides,
+                        extras,
+                        output_file.as_deref(),
+                        shared.resolution,
+                        shared.prerelease,
+                        shared.dependency_mode,
+                        upgrade.clone(),
+                        shared.generate_hashes,
+                        shared.no_emit_package.clone(),
+                        shared.unsafe_package.clone(),
+                        shared.allow_unsafe,
+                        shared.no_strip_extras,
+                        shared.no_strip_markers,
+                        !shared.no_annotate,
+                        !shared.no_header,
+                        shared.custom_compile_command.clone(),
+                        shared.custom_header.clone(),
+                        shared.sign_with.as_deref(),
+                        shared.emit_index_url,
+                        shared.emit_find_links,
+                        shared.emit_marker_expression,
+                        shared.emit_index_annotation,
+                        shared.index_locations.clone(),
+                        shared.index_strategy,
+                        shared.check_dependency_confusion,
+                        &shared.dependency_confusion_allow,
+                        shared.check_metadata_consistency,
+                        shared.keyring_provider,
+                        shared.setup_py,
+                        shared.config_setting.clone(),
+                        globals.connectivity,
+                        shared.no_build_isolation,
+                        shared.no_build.clone(),
+                        shared.prefer_source.clone(),
+                        shared.prefer_binary.clone(),
+                        shared.max_build_depth,
+                        globals.build_commands(&shared.build_commands),
+                        &shared.patches,
+                        python_version,
+                        python_platform,
+                        shared.exclude_newer,
+                        shared.package_policy.clone(),
+                        shared.resolution_limits,
+                        shared.prefetch.clone(),
+                        shared.annotation_style,
+                        shared.link_mode,
+                        shared.python.clone(),
+                        shared.system,
+                        venv_dir,
+                        shared.concurrency,
+                        indexes,
+                        uv_lock,
+                        interactive,
+                        graph,
+                        globals.native_tls,
+                        globals.hermetic,
+                        globals.timeouts,
+                        globals.index_max_age,
+                        globals.response_limits,
+                        globals.find_links_recursive,
+                        host_overrides,
+                        trusted_hosts,
+                        rate_limits,
+                        globals.http_version,
+                        quiet,
+                        globals.preview,
+                        cache,
+                        &summary,
+                        printer,
+                    )
+                    .await
+                }
+            }))
+            .await?;
+
+            // Report the first unsuccessful group, if any.
+            Ok(statuses
+                .into_iter()
+                .find(|status| !matches!(status, ExitStatus::Success))
+                .unwrap_or(ExitStatus::Success))
         }
         Commands::Pip(PipNamespace {
             command: PipCommand::Sync(args),
@@ -273,15 +404,31 @@ async fn run() -> Result<ExitStatus> {
                 .map(RequirementsSource::from_constraints_txt)
                 .collect::<Vec<_>>();
 
+            let environments =
+                commands::target_environments(args.environments, args.all_environments)?;
+
             commands::pip_sync(
                 &requirements,
                 &constraints,
                 &args.reinstall,
                 args.shared.link_mode,
+                ScriptOptions {
+                    allow_conflicts: args.force,
+                    ..args.shared.script_options
+                },
+                &InstallFilter::new(args.shared.no_install_tests, &args.shared.exclude_path_glob)?,
+                args.shared.file_collisions,
                 args.shared.compile_bytecode,
+                args.shared.compile_options,
+                args.check_import,
                 args.shared.require_hashes,
+                args.no_require_hashes,
+                args.trusted_signers.as_deref(),
                 args.shared.index_locations,
+                &args.shared.local_dist,
                 args.shared.index_strategy,
+                args.shared.check_dependency_confusion,
+                &args.shared.dependency_confusion_allow,
                 args.shared.keyring_provider,
                 args.shared.setup_py,
                 globals.connectivity,
@@ -289,19 +436,44 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.no_build_isolation,
                 args.shared.no_build,
                 args.shared.no_binary,
+                args.shared.prefer_source,
+                args.shared.prefer_binary,
+                args.shared.max_build_depth,
+                globals.build_commands(&args.shared.build_commands),
+                &args.shared.patches,
                 args.shared.python_version,
                 args.shared.python_platform,
+                args.ignore_platform_compatibility,
+                args.ignore_compiled_environment,
                 args.shared.strict,
                 args.shared.exclude_newer,
+                args.shared.package_policy,
+                args.shared.resolution_limits,
+                args.shared.prefetch,
                 args.shared.python,
+                &environments,
                 args.shared.system,
+                globals.venv_dir.as_deref(),
                 args.shared.break_system_packages,
                 args.shared.target,
                 args.shared.concurrency,
+                globals.install_hooks(),
                 globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                globals.find_links_recursive,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 globals.preview,
                 cache,
                 args.dry_run,
+                args.report.as_deref(),
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
@@ -342,6 +514,9 @@ async fn run() -> Result<ExitStatus> {
                 .map(RequirementsSource::from_overrides_txt)
                 .collect::<Vec<_>>();
 
+            let environments =
+                commands::target_environments(args.environments, args.all_environments)?;
+
             commands::pip_install(
                 &requirements,
                 &constraints,
@@ -351,33 +526,72 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.prerelease,
                 args.shared.dependency_mode,
                 args.upgrade,
+                args.upgrade_strategy,
                 args.shared.index_locations,
                 args.shared.index_strategy,
+                args.shared.check_dependency_confusion,
+                &args.shared.dependency_confusion_allow,
                 args.shared.keyring_provider,
                 args.reinstall,
                 args.shared.link_mode,
+                ScriptOptions {
+                    allow_conflicts: args.force,
+                    ..args.shared.script_options
+                },
+                &InstallFilter::new(args.shared.no_install_tests, &args.shared.exclude_path_glob)?,
+                args.shared.file_collisions,
                 args.shared.compile_bytecode,
+                args.shared.compile_options,
+                args.check_import,
+                args.shared.cache_resolution,
                 args.shared.require_hashes,
+                args.trusted_signers.as_deref(),
                 args.shared.setup_py,
                 globals.connectivity,
                 &args.shared.config_setting,
                 args.shared.no_build_isolation,
                 args.shared.no_build,
                 args.shared.no_binary,
+                args.shared.prefer_source,
+                args.shared.prefer_binary,
+                args.shared.max_build_depth,
+                globals.build_commands(&args.shared.build_commands),
+                &args.shared.patches,
                 args.shared.python_version,
                 args.shared.python_platform,
+                args.ignore_platform_compatibility,
                 args.shared.strict,
                 args.shared.exclude_newer,
+                args.shared.package_policy,
+                args.shared.resolution_limits,
+                args.shared.prefetch,
                 args.shared.python,
+                &environments,
                 args.shared.system,
+                globals.venv_dir.as_deref(),
                 args.shared.break_system_packages,
                 args.shared.target,
                 args.shared.concurrency,
+                globals.install_hooks(),
                 args.uv_lock,
                 globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                globals.find_links_recursive,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 globals.preview,
                 cache,
+                args.exact,
+                args.check_only,
                 args.dry_run,
+                args.report.as_deref(),
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
@@ -405,13 +619,25 @@ async fn run() -> Result<ExitStatus> {
                 &sources,
                 args.shared.python,
                 args.shared.system,
+                globals.venv_dir.as_deref(),
                 args.shared.break_system_packages,
                 args.shared.target,
+                args.all,
+                args.dry_run,
                 cache,
                 globals.connectivity,
                 globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 globals.preview,
                 args.shared.keyring_provider,
+                globals.install_hooks(),
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
@@ -427,9 +653,11 @@ async fn run() -> Result<ExitStatus> {
 
             commands::pip_freeze(
                 args.exclude_editable,
+                args.hashes,
                 args.shared.strict,
                 args.shared.python.as_deref(),
                 args.shared.system,
+                globals.venv_dir.as_deref(),
                 &cache,
                 printer,
             )
@@ -437,8 +665,6 @@ async fn run() -> Result<ExitStatus> {
         Commands::Pip(PipNamespace {
             command: PipCommand::List(args),
         }) => {
-            args.compat_args.validate()?;
-
             // Resolve the settings from the command-line arguments and workspace configuration.
             let args = PipListSettings::resolve(args, workspace);
 
@@ -450,12 +676,64 @@ async fn run() -> Result<ExitStatus> {
                 args.exclude_editable,
                 &args.exclude,
                 &args.format,
+                args.outdated,
+                args.sizes,
+                args.shared.prerelease,
+                args.shared.index_locations,
+                args.shared.index_strategy,
+                args.shared.keyring_provider,
+                args.shared.exclude_newer,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                globals.find_links_recursive,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 args.shared.strict,
                 args.shared.python.as_deref(),
                 args.shared.system,
+                globals.venv_dir.as_deref(),
+                &cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Hash(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = PipHashSettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::pip_hash(
+                &args.artifact,
+                args.algorithm,
+                args.shared.index_locations,
+                args.shared.index_strategy,
+                args.shared.keyring_provider,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 &cache,
+                &summary,
                 printer,
             )
+            .await
         }
         Commands::Pip(PipNamespace {
             command: PipCommand::Show(args),
@@ -471,6 +749,25 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.strict,
                 args.shared.python.as_deref(),
                 args.shared.system,
+                globals.venv_dir.as_deref(),
+                &cache,
+                printer,
+            )
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Why(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = PipWhySettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::pip_why(
+                &args.package,
+                args.shared.python.as_deref(),
+                args.shared.system,
+                globals.venv_dir.as_deref(),
                 &cache,
                 printer,
             )
@@ -487,10 +784,50 @@ async fn run() -> Result<ExitStatus> {
             commands::pip_check(
                 args.shared.python.as_deref(),
                 args.shared.system,
+                globals.venv_dir.as_deref(),
                 &cache,
                 printer,
             )
         }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Lint(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = PipLintSettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            let sources = args
+                .src_file
+                .into_iter()
+                .map(RequirementsSource::from_requirements_file)
+                .collect::<Vec<_>>();
+            commands::pip_lint(
+                &sources,
+                args.shared.index_locations,
+                args.shared.index_strategy,
+                args.shared.keyring_provider,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
+                globals.preview,
+                &cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Format(args),
+        }) => commands::pip_format(&args.src_file, args.check, printer),
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Clean(args),
         })
@@ -504,6 +841,215 @@ async fn run() -> Result<ExitStatus> {
             commands::cache_dir(&cache);
             Ok(ExitStatus::Success)
         }
+        Commands::Cache(CacheNamespace {
+            command:
+                CacheCommand::Builds(CacheBuildsNamespace {
+                    command: CacheBuildsCommand::List,
+                }),
+        }) => commands::cache_builds_list(&cache, printer),
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Warm(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::CacheWarmSettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            let requirements = args
+                .requirement
+                .into_iter()
+                .map(RequirementsSource::from_requirements_file)
+                .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_constraints_txt)
+                .collect::<Vec<_>>();
+
+            commands::cache_warm(
+                &requirements,
+                &constraints,
+                args.build,
+                args.shared.python.as_deref(),
+                globals.venv_dir.as_deref(),
+                args.shared.index_locations,
+                args.shared.index_strategy,
+                args.shared.keyring_provider,
+                args.shared.concurrency,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                globals.find_links_recursive,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
+                globals.preview,
+                &cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Wheel(WheelNamespace {
+            command: WheelCommand::Inspect(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::wheel_inspect(
+                &args.wheel,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
+                &cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Wheel(WheelNamespace {
+            command: WheelCommand::Retag(args),
+        }) => commands::wheel_retag(
+            &args.wheel,
+            args.python_tag.as_deref(),
+            args.abi_tag.as_deref(),
+            args.platform_tag.as_deref(),
+            args.out_dir.as_deref(),
+            printer,
+        ),
+        Commands::Wheel(WheelNamespace {
+            command: WheelCommand::Audit(args),
+        }) => commands::wheel_audit(
+            &args.wheel,
+            &args.platform_tag,
+            args.out_dir.as_deref(),
+            printer,
+        ),
+        Commands::Index(IndexNamespace {
+            command: IndexCommand::Check(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::index_check(
+                &args.url,
+                &args.package,
+                args.keyring_provider.unwrap_or_default(),
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
+                &cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Interpreter(InterpreterNamespace {
+            command: InterpreterCommand::List(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache
+                .init()?
+                .with_refresh(Refresh::from_args(Some(args.refresh), Vec::new()));
+
+            commands::interpreter_list(
+                args.request.as_deref(),
+                args.format,
+                globals.venv_dir.as_deref(),
+                &cache,
+                printer,
+            )
+        }
+        Commands::Interpreter(InterpreterNamespace {
+            command: InterpreterCommand::Pin(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::interpreter_pin(
+                args.request.as_deref(),
+                args.resolved,
+                globals.venv_dir.as_deref(),
+                &cache,
+                printer,
+            )
+        }
+        Commands::Doctor(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::DoctorSettings::resolve(args, workspace);
+
+            // The cache is initialized as part of the diagnostics, to report any failure.
+            commands::doctor(
+                args.shared.python.as_deref(),
+                globals.venv_dir.as_deref(),
+                &args.shared.index_locations,
+                args.shared.keyring_provider,
+                globals.connectivity,
+                globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
+                cache,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Environment(EnvironmentNamespace {
+            command: EnvironmentCommand::Configure(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::venv_configure(
+                &args.name,
+                args.system_site_packages,
+                args.prompt,
+                &cache,
+                printer,
+            )
+        }
+        Commands::Environment(EnvironmentNamespace {
+            command: EnvironmentCommand::List(args),
+        }) => commands::venv_list(args.path.as_deref(), printer),
+        Commands::Environment(EnvironmentNamespace {
+            command: EnvironmentCommand::Repair(args),
+        }) => {
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::venv_repair(
+                &args.name,
+                args.python.as_deref(),
+                args.python_preference,
+                &cache,
+                printer,
+            )
+        }
         Commands::Venv(args) => {
             args.compat_args.validate()?;
 
@@ -513,9 +1059,27 @@ async fn run() -> Result<ExitStatus> {
             // Initialize the cache.
             let cache = cache.init()?;
 
-            // Since we use ".venv" as the default name, we use "." as the default prompt.
+            // Determine the location of the virtual environment, which may be in the central
+            // environment store.
+            let path = if let Some(name) = args.env_name.as_deref() {
+                uv_virtualenv::EnvironmentStore::from_settings(globals.environments_dir.clone())?
+                    .named(name)?
+            } else if args.central {
+                let cwd = std::env::current_dir()?;
+                let root = ProjectWorkspace::discover(&cwd)
+                    .map_or(cwd, |project| project.workspace().root().clone());
+                uv_virtualenv::EnvironmentStore::from_settings(globals.environments_dir.clone())?
+                    .for_project(&root)?
+            } else {
+                args.name.clone()
+            };
+
+            // Since we use ".venv" as the default name, we use "." as the default prompt. Named
+            // environments use their name as the prompt.
             let prompt = args.prompt.or_else(|| {
-                if args.name == PathBuf::from(".venv") {
+                if let Some(name) = args.env_name {
+                    Some(name)
+                } else if args.name == PathBuf::from(".venv") {
                     Some(".".to_string())
                 } else {
                     None
@@ -523,20 +1087,37 @@ async fn run() -> Result<ExitStatus> {
             });
 
             commands::venv(
-                &args.name,
+                &path,
                 args.shared.python.as_deref(),
                 args.shared.link_mode,
+                &args.shared.no_binary,
                 &args.shared.index_locations,
                 args.shared.index_strategy,
                 args.shared.keyring_provider,
                 uv_virtualenv::Prompt::from_args(prompt),
                 args.system_site_packages,
+                args.python_preference,
                 globals.connectivity,
                 args.seed,
+                args.seed_source,
                 args.allow_existing,
+                args.clear,
+                args.register,
+                args.register_tag.as_deref(),
                 args.shared.exclude_newer,
                 globals.native_tls,
+                globals.hermetic,
+                globals.timeouts,
+                globals.index_max_age,
+                globals.response_limits,
+                globals.find_links_recursive,
+                &globals.host_overrides,
+                &globals.trusted_hosts,
+                &globals.rate_limits,
+                globals.http_version,
                 &cache,
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
@@ -572,32 +1153,81 @@ async fn run() -> Result<ExitStatus> {
                 args.target,
                 args.args,
                 requirements,
+                args.environment,
                 args.python,
+                globals.venv_dir.as_deref(),
+                &args.shared,
                 globals.isolated,
+                &globals.client_builder(summary.network()),
+                &globals.install_hooks(),
                 globals.preview,
-                globals.connectivity,
+                globals.environments_dir.as_deref(),
                 &cache,
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
         }
         Commands::Sync(args) => {
             // Resolve the settings from the command-line arguments and workspace configuration.
-            let _args = settings::SyncSettings::resolve(args, workspace);
+            let args = settings::SyncSettings::resolve(args, workspace);
 
             // Initialize the cache.
             let cache = cache.init()?;
 
-            commands::sync(globals.preview, &cache, printer).await
+            commands::sync(
+                &args.extras,
+                args.trusted_signers.as_deref(),
+                &args.shared,
+                &globals.client_builder(summary.network()),
+                &globals.install_hooks(),
+                globals.preview,
+                globals.environments_dir.as_deref(),
+                &cache,
+                globals.allow_external_symlinks,
+                &summary,
+                printer,
+            )
+            .await
         }
         Commands::Lock(args) => {
             // Resolve the settings from the command-line arguments and workspace configuration.
-            let _args = settings::LockSettings::resolve(args, workspace);
+            let args = settings::LockSettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::lock(
+                args.sign_with.as_deref(),
+                &args.shared,
+                &globals.client_builder(summary.network()),
+                globals.preview,
+                globals.environments_dir.as_deref(),
+                &cache,
+                globals.allow_external_symlinks,
+                &summary,
+                printer,
+            )
+            .await
+        }
+        Commands::Export(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ExportSettings::resolve(args, workspace);
 
             // Initialize the cache.
             let cache = cache.init()?;
 
-            commands::lock(globals.preview, &cache, printer).await
+            commands::export(
+                args.format,
+                args.stable_age,
+                args.keyring_provider,
+                &globals.client_builder(summary.network()),
+                globals.preview,
+                &cache,
+                printer,
+            )
+            .await
         }
         #[cfg(feature = "self-update")]
         Commands::Self_(SelfNamespace {
@@ -614,6 +1244,9 @@ async fn run() -> Result<ExitStatus> {
         Commands::Tool(ToolNamespace {
             command: ToolCommand::Run(args),
         }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let shared = settings::ProjectSharedSettings::resolve(args.shared, workspace.as_ref());
+
             // Initialize the cache.
             let cache = cache.init()?;
 
@@ -621,12 +1254,17 @@ async fn run() -> Result<ExitStatus> {
                 args.target,
                 args.args,
                 args.python,
+                globals.venv_dir.as_deref(),
                 args.from,
                 args.with,
+                &shared,
                 globals.isolated,
+                &globals.client_builder(summary.network()),
+                &globals.install_hooks(),
                 globals.preview,
-                globals.connectivity,
                 &cache,
+                globals.allow_external_symlinks,
+                &summary,
                 printer,
             )
             .await
@@ -635,6 +1273,27 @@ async fn run() -> Result<ExitStatus> {
 }
 
 fn main() -> ExitCode {
+    // In hermetic mode, discard any configuration from the environment before parsing the
+    // command-line, such that only explicit arguments take effect. The environment can only be
+    // modified soundly while the process is single-threaded, i.e., before the runtime starts.
+    let removed = if hermetic::requested(env::args_os()) {
+        hermetic::clear_environment()
+    } else {
+        Vec::new()
+    };
+    let cli = parse_cli();
+    if !cli.global_args.hermetic {
+        hermetic::restore_environment(removed);
+    }
+
+    // Load the message catalog for the user's locale, if any. It's used for the remainder of the
+    // process, including to report any error returned by the command.
+    let messages: &'static messages::Catalog = Box::leak(Box::new(messages::Catalog::from_env()));
+
+    // Capture the format in which to report any error returned by the command, as the arguments
+    // are consumed by the command itself.
+    let output_format = cli.global_args.output_format;
+
     let result = if let Ok(stack_size) = env::var("UV_STACK_SIZE") {
         // Artificially limit the stack size to test for stack overflows. Windows has a default stack size of 1MB,
         // which is lower than the linux and mac default.
@@ -646,7 +1305,7 @@ fn main() -> ExitCode {
                 .thread_stack_size(stack_size)
                 .build()
                 .expect("Failed building the Runtime")
-                .block_on(run())
+                .block_on(run(cli, messages))
         };
         std::thread::Builder::new()
             .stack_size(stack_size)
@@ -659,18 +1318,18 @@ fn main() -> ExitCode {
             .enable_all()
             .build()
             .expect("Failed building the Runtime")
-            .block_on(run())
+            .block_on(run(cli, messages))
     };
 
     match result {
         Ok(code) => code.into(),
         Err(err) => {
-            let mut causes = err.chain();
-            eprintln!("{}: {}", "error".red().bold(), causes.next().unwrap());
-            for err in causes {
-                eprintln!("  {}: {}", "Caused by".red().bold(), err);
+            errors::report_error(&err, output_format, messages);
+            if cancellation::is_cancelled() {
+                ExitStatus::Interrupted.into()
+            } else {
+                errors::exit_status(&err).into()
             }
-            ExitStatus::Error.into()
         }
     }
 }
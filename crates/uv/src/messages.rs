@@ -0,0 +1,261 @@
+//! The catalog of user-facing messages, which may be localized at runtime.
+//!
+//! Each [`Message`] has a stable key and an English template. Downstream distributions can ship
+//! translations as TOML files (e.g., `pt_BR.toml`, mapping keys to templates) in a catalog
+//! directory, provided via `UV_MESSAGE_CATALOG_DIR` at runtime or at build time. The locale is
+//! read from `UV_LOCALE`, falling back to the standard `LC_ALL`, `LC_MESSAGES`, and `LANG`
+//! variables. Any message that's missing from the catalog falls back to English.
+//!
+//! The [`Catalog`] is loaded once at startup, and passed to each command via its
+//! [`Printer`](crate::printer::Printer).
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use uv_fs::Simplified;
+
+/// A user-facing message, identified by a stable key in the message catalog.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Message {
+    /// A count of packages, in the singular.
+    PackagesOne,
+    /// A count of packages, in the plural.
+    PackagesOther,
+    Resolved,
+    Audited,
+    Downloaded,
+    Installed,
+    Uninstalled,
+    WouldMakeNoChanges,
+    WouldDownload,
+    WouldUninstall,
+    WouldInstall,
+    Error,
+    CausedBy,
+    Warning,
+    UsingInterpreter,
+    CreatingVirtualenv,
+    ActivateWith,
+    SyncingEnvironment,
+    NoRequirements,
+    CheckedImports,
+    Checked,
+    AllCompatible,
+    NoProblems,
+    PruningCache,
+    NoCache,
+    NoUnusedEntries,
+}
+
+impl Message {
+    /// The stable key used to look up the message in a catalog.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::PackagesOne => "packages-one",
+            Self::PackagesOther => "packages-other",
+            Self::Resolved => "resolved",
+            Self::Audited => "audited",
+            Self::Downloaded => "downloaded",
+            Self::Installed => "installed",
+            Self::Uninstalled => "uninstalled",
+            Self::WouldMakeNoChanges => "would-make-no-changes",
+            Self::WouldDownload => "would-download",
+            Self::WouldUninstall => "would-uninstall",
+            Self::WouldInstall => "would-install",
+            Self::Error => "error",
+            Self::CausedBy => "caused-by",
+            Self::Warning => "warning",
+            Self::UsingInterpreter => "using-interpreter",
+            Self::CreatingVirtualenv => "creating-virtualenv",
+            Self::ActivateWith => "activate-with",
+            Self::SyncingEnvironment => "syncing-environment",
+            Self::NoRequirements => "no-requirements",
+            Self::CheckedImports => "checked-imports",
+            Self::Checked => "checked",
+            Self::AllCompatible => "all-compatible",
+            Self::NoProblems => "no-problems",
+            Self::PruningCache => "pruning-cache",
+            Self::NoCache => "no-cache",
+            Self::NoUnusedEntries => "no-unused-entries",
+        }
+    }
+
+    /// The English template for the message.
+    fn english(self) -> &'static str {
+        match self {
+            Self::PackagesOne => "{count} package",
+            Self::PackagesOther => "{count} packages",
+            Self::Resolved => "Resolved {packages} in {elapsed}",
+            Self::Audited => "Audited {packages} in {elapsed}",
+            Self::Downloaded => "Downloaded {packages} in {elapsed}",
+            Self::Installed => "Installed {packages} in {elapsed}",
+            Self::Uninstalled => "Uninstalled {packages} in {elapsed}",
+            Self::WouldMakeNoChanges => "Would make no changes",
+            Self::WouldDownload => "Would download {packages}",
+            Self::WouldUninstall => "Would uninstall {packages}",
+            Self::WouldInstall => "Would install {packages}",
+            Self::Error => "error",
+            Self::CausedBy => "Caused by",
+            Self::Warning => "warning",
+            Self::UsingInterpreter => "Using Python {version} interpreter at: {path}",
+            Self::CreatingVirtualenv => "Creating virtualenv at: {path}",
+            Self::ActivateWith => "Activate with: {command}",
+            Self::SyncingEnvironment => "Syncing environment at: {path}",
+            Self::NoRequirements => "No requirements found",
+            Self::CheckedImports => "Checked imports of {packages} in {elapsed}",
+            Self::Checked => "Checked {packages} in {elapsed}",
+            Self::AllCompatible => "All installed packages are compatible",
+            Self::NoProblems => "No problems found",
+            Self::PruningCache => "Pruning cache at: {path}",
+            Self::NoCache => "No cache found at: {path}",
+            Self::NoUnusedEntries => "No unused entries found",
+        }
+    }
+}
+
+/// Read the user's preferred locale from the environment (e.g., `pt_BR`), if any.
+fn locale() -> Option<String> {
+    ["UV_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        .and_then(|value| {
+            // Strip the encoding and modifier (e.g., `pt_BR.UTF-8@euro`).
+            let locale = value
+                .split(['.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            if locale.is_empty() || locale == "C" || locale == "POSIX" {
+                None
+            } else {
+                Some(locale)
+            }
+        })
+}
+
+/// The directory containing the message catalogs, if any.
+fn catalog_dir() -> Option<PathBuf> {
+    std::env::var_os("UV_MESSAGE_CATALOG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| option_env!("UV_MESSAGE_CATALOG_DIR").map(PathBuf::from))
+}
+
+/// A set of translated message templates, keyed by [`Message::key`].
+///
+/// An empty catalog renders every message in English.
+#[derive(Debug, Default)]
+pub(crate) struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Load the message catalog for the locale in the environment, if any.
+    pub(crate) fn from_env() -> Self {
+        let Some(locale) = locale() else {
+            return Self::default();
+        };
+        let Some(directory) = catalog_dir() else {
+            return Self::default();
+        };
+        Self::find(&directory, &locale).unwrap_or_default()
+    }
+
+    /// Render the message in the catalog's locale, substituting each `{name}` placeholder.
+    pub(crate) fn format(&self, message: Message, args: &[(&str, &dyn Display)]) -> String {
+        substitute(self.get(message).unwrap_or_else(|| message.english()), args)
+    }
+
+    /// Render a count of packages (e.g., `1 package` or `2 packages`) in the catalog's locale.
+    pub(crate) fn packages(&self, count: usize) -> String {
+        let message = if count == 1 {
+            Message::PackagesOne
+        } else {
+            Message::PackagesOther
+        };
+        self.format(message, &[("count", &count)])
+    }
+
+    /// Find the catalog for the given locale (e.g., `pt_BR.toml`), falling back to that for its
+    /// language (e.g., `pt.toml`).
+    fn find(directory: &Path, locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '-']).next().unwrap_or(locale);
+        [locale, language].into_iter().find_map(|name| {
+            let path = directory.join(format!("{name}.toml"));
+            let contents = fs_err::read_to_string(&path).ok()?;
+            match Self::parse(&contents) {
+                Ok(catalog) => {
+                    debug!("Using message catalog at: {}", path.user_display());
+                    Some(catalog)
+                }
+                Err(err) => {
+                    debug!(
+                        "Ignoring invalid message catalog at {}: {err}",
+                        path.user_display()
+                    );
+                    None
+                }
+            }
+        })
+    }
+
+    /// Parse a catalog from a TOML table of message keys to templates.
+    fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        Ok(Self {
+            messages: toml::from_str(contents)?,
+        })
+    }
+
+    fn get(&self, message: Message) -> Option<&str> {
+        self.messages.get(message.key()).map(String::as_str)
+    }
+}
+
+/// Substitute each `{name}` placeholder in the template with the corresponding argument.
+fn substitute(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut output = template.to_string();
+    for (name, value) in args {
+        output = output.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{substitute, Catalog, Message};
+
+    #[test]
+    fn english() {
+        assert_eq!(
+            substitute(
+                Message::Resolved.english(),
+                &[("packages", &"3 packages"), ("elapsed", &"1ms")]
+            ),
+            "Resolved 3 packages in 1ms"
+        );
+    }
+
+    #[test]
+    fn catalog() {
+        let catalog = Catalog::parse(
+            r#"
+            resolved = "{packages} resolvidos em {elapsed}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            catalog.get(Message::Resolved),
+            Some("{packages} resolvidos em {elapsed}")
+        );
+        assert_eq!(catalog.get(Message::Installed), None);
+        assert_eq!(
+            catalog.format(
+                Message::Installed,
+                &[("packages", &"1 package"), ("elapsed", &"1ms")]
+            ),
+            "Installed 1 package in 1ms"
+        );
+    }
+}
@@ -1,43 +1,193 @@
+use std::fmt::Display;
+
 use anstream::{eprint, print};
 use indicatif::ProgressDrawTarget;
 
+use crate::cli::{ColorChoice, OutputFormat};
+use crate::messages::Catalog;
+
+/// The styling policy for user-facing output, resolved once from `--color`, `NO_COLOR`, and the
+/// terminal, and carried by the [`Printer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct OutputPolicy {
+    /// The user's color choice, as provided via `--color`.
+    choice: ColorChoice,
+    /// Whether to emit colors and other styles on `stderr`.
+    color: bool,
+    /// The width of the terminal, if `stderr` is a terminal.
+    width: Option<usize>,
+}
+
+impl OutputPolicy {
+    /// Resolve the output policy from the user's color choice and the environment.
+    pub(crate) fn from_env(choice: ColorChoice) -> Self {
+        let color = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            // Respect `NO_COLOR`, `CLICOLOR_FORCE`, and the like, in addition to the terminal.
+            ColorChoice::Auto => !matches!(
+                anstream::AutoStream::choice(&std::io::stderr()),
+                anstream::ColorChoice::Never
+            ),
+        };
+        let width = console::Term::stderr()
+            .size_checked()
+            .map(|(_, columns)| usize::from(columns));
+        Self {
+            choice,
+            color,
+            width,
+        }
+    }
+
+    /// Apply the policy to the underlying styling crates (`anstream`, for `owo_colors` output,
+    /// and `console`).
+    pub(crate) fn install(self) {
+        // In `auto` mode, `anstream` detects color support for `stdout` and `stderr` separately.
+        anstream::ColorChoice::write_global(self.choice.into());
+        console::set_colors_enabled_stderr(self.color);
+        if self.choice != ColorChoice::Auto {
+            console::set_colors_enabled(self.color);
+        }
+    }
+
+    /// Returns `true` if output should be styled.
+    pub(crate) fn color(self) -> bool {
+        self.color
+    }
+
+    /// The width of the terminal, if known.
+    pub(crate) fn width(self) -> Option<usize> {
+        self.width
+    }
+
+    /// Join the items with `, `, truncating the list (e.g., `a, b, and 3 more`) such that it fits
+    /// within the terminal alongside `reserved` columns of surrounding text.
+    pub(crate) fn join_truncated<T: Display>(
+        self,
+        items: impl IntoIterator<Item = T>,
+        reserved: usize,
+    ) -> String {
+        let items = items
+            .into_iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>();
+        let Some(width) = self.width else {
+            return items.join(", ");
+        };
+        let available = width.saturating_sub(reserved);
+
+        let mut joined = String::new();
+        for (index, item) in items.iter().enumerate() {
+            let remaining = items.len() - index - 1;
+            let separator = if joined.is_empty() { "" } else { ", " };
+            let suffix = if remaining == 0 {
+                String::new()
+            } else {
+                format!(", and {remaining} more")
+            };
+            // Always include the first item, even if it alone exceeds the width.
+            if !joined.is_empty()
+                && joined.len() + separator.len() + item.len() + suffix.len() > available
+            {
+                joined.push_str(&format!(", and {} more", items.len() - index));
+                return joined;
+            }
+            joined.push_str(separator);
+            joined.push_str(item);
+        }
+        joined
+    }
+}
+
+/// The verbosity of user-facing output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum Printer {
-    /// A printer that prints to standard streams (e.g., stdout).
+pub(crate) enum Verbosity {
+    /// Print to standard streams (e.g., stdout).
     Default,
-    /// A printer that suppresses all output.
+    /// Suppress all output.
     Quiet,
-    /// A printer that prints all output, including debug messages.
+    /// Print all output, including debug messages.
     Verbose,
 }
 
+/// The destination and styling of user-facing output, passed to each command.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Printer {
+    verbosity: Verbosity,
+    policy: OutputPolicy,
+    messages: &'static Catalog,
+    output_format: OutputFormat,
+}
+
 impl Printer {
+    /// Create a [`Printer`] with the given verbosity, styling policy, message catalog, and format
+    /// for errors and progress events.
+    pub(crate) fn new(
+        verbosity: Verbosity,
+        policy: OutputPolicy,
+        messages: &'static Catalog,
+        output_format: OutputFormat,
+    ) -> Self {
+        Self {
+            verbosity,
+            policy,
+            messages,
+            output_format,
+        }
+    }
+
+    /// Return a [`Printer`] that suppresses all output, with the same styling, messages, and
+    /// output format.
+    #[must_use]
+    pub(crate) fn quiet(self) -> Self {
+        Self {
+            verbosity: Verbosity::Quiet,
+            ..self
+        }
+    }
+
+    /// Return the [`OutputPolicy`] that governs styling for this printer.
+    pub(crate) fn policy(self) -> OutputPolicy {
+        self.policy
+    }
+
+    /// Return the message [`Catalog`] in which to render user-facing messages.
+    pub(crate) fn messages(self) -> &'static Catalog {
+        self.messages
+    }
+
+    /// Return the format in which to report errors and progress events (`--output-format`).
+    pub(crate) fn output_format(self) -> OutputFormat {
+        self.output_format
+    }
+
     /// Return the [`ProgressDrawTarget`] for this printer.
     pub(crate) fn target(self) -> ProgressDrawTarget {
-        match self {
-            Self::Default => ProgressDrawTarget::stderr(),
-            Self::Quiet => ProgressDrawTarget::hidden(),
+        match self.verbosity {
+            Verbosity::Default => ProgressDrawTarget::stderr(),
+            Verbosity::Quiet => ProgressDrawTarget::hidden(),
             // Confusingly, hide the progress bar when in verbose mode.
             // Otherwise, it gets interleaved with debug messages.
-            Self::Verbose => ProgressDrawTarget::hidden(),
+            Verbosity::Verbose => ProgressDrawTarget::hidden(),
         }
     }
 
     /// Return the [`Stdout`] for this printer.
     pub(crate) fn stdout(self) -> Stdout {
-        match self {
-            Self::Default => Stdout::Enabled,
-            Self::Quiet => Stdout::Disabled,
-            Self::Verbose => Stdout::Enabled,
+        match self.verbosity {
+            Verbosity::Default => Stdout::Enabled,
+            Verbosity::Quiet => Stdout::Disabled,
+            Verbosity::Verbose => Stdout::Enabled,
         }
     }
 
     /// Return the [`Stderr`] for this printer.
     pub(crate) fn stderr(self) -> Stderr {
-        match self {
-            Self::Default => Stderr::Enabled,
-            Self::Quiet => Stderr::Disabled,
-            Self::Verbose => Stderr::Enabled,
+        match self.verbosity {
+            Verbosity::Default => Stderr::Enabled,
+            Verbosity::Quiet => Stderr::Disabled,
+            Verbosity::Verbose => Stderr::Enabled,
         }
     }
 }
@@ -85,3 +235,26 @@ impl std::fmt::Write for Stderr {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::OutputPolicy;
+
+    #[test]
+    fn join_truncated() {
+        let items = ["anyio", "idna", "sniffio", "typing-extensions"];
+
+        let policy = OutputPolicy::default();
+        assert_eq!(
+            policy.join_truncated(items, 0),
+            "anyio, idna, sniffio, typing-extensions"
+        );
+
+        let policy = OutputPolicy {
+            width: Some(30),
+            ..OutputPolicy::default()
+        };
+        assert_eq!(policy.join_truncated(items, 0), "anyio, idna, and 2 more");
+        assert_eq!(policy.join_truncated(items, 30), "anyio, and 3 more");
+    }
+}
@@ -4,27 +4,40 @@ use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::process;
 use std::str::FromStr;
+use std::time::Duration;
 
 use distribution_types::IndexLocations;
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ScriptOptions;
+use pypi_types::HashAlgorithm;
 use uv_cache::{CacheArgs, Refresh};
-use uv_client::Connectivity;
+use uv_client::{
+    BaseClientBuilder, Connectivity, NetworkRecorder, NetworkTimeouts, ResponseLimits,
+};
 use uv_configuration::{
-    Concurrency, ConfigSettings, IndexStrategy, KeyringProviderType, NoBinary, NoBuild,
-    PreviewMode, Reinstall, SetupPyStrategy, TargetTriple, Upgrade,
+    BuildCommands, CompileOptions, Concurrency, ConfigSettings, FileCollisionPolicy, HostOverride,
+    HttpVersion, IndexRateLimit, IndexStrategy, KeyringProviderType, NoBinary, NoBuild,
+    PackagePatches, PreferBinary, PreferSource, PreviewMode, PythonPreference, Reinstall,
+    SeedSource, SetupPyStrategy, TargetTriple, TrustedHost, Upgrade, UpgradeStrategy,
 };
+use uv_installer::{CommandHook, InstallHooks};
 use uv_interpreter::{PythonVersion, Target};
 use uv_normalize::PackageName;
 use uv_requirements::ExtrasSpecification;
-use uv_resolver::{AnnotationStyle, DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_resolver::{
+    AnnotationStyle, DependencyMode, ExcludeNewer, GraphFormat, MinReleaseAge, PackagePolicy,
+    PreReleaseMode, PrefetchStrategy, ResolutionLimits, ResolutionMode,
+};
+use uv_warnings::warn_user_once;
 use uv_workspace::{Combine, PipOptions, Workspace};
 
 use crate::cli::{
-    ColorChoice, GlobalArgs, LockArgs, Maybe, PipCheckArgs, PipCompileArgs, PipFreezeArgs,
-    PipInstallArgs, PipListArgs, PipShowArgs, PipSyncArgs, PipUninstallArgs, RunArgs, SyncArgs,
-    VenvArgs,
+    CacheWarmArgs, ColorChoice, DoctorArgs, ExportArgs, GlobalArgs, LockArgs, Maybe, OutputFormat,
+    PipCheckArgs, PipCompileArgs, PipFreezeArgs, PipHashArgs, PipInstallArgs, PipLintArgs,
+    PipListArgs, PipShowArgs, PipSyncArgs, PipUninstallArgs, PipWhyArgs, ProjectSharedArgs,
+    RunArgs, SyncArgs, VenvArgs,
 };
-use crate::commands::ListFormat;
+use crate::commands::{CompileGroup, ExportFormat, ListFormat};
 
 /// The resolved global settings to use for any invocation of the CLI.
 #[allow(clippy::struct_excessive_bools)]
@@ -32,11 +45,31 @@ use crate::commands::ListFormat;
 pub(crate) struct GlobalSettings {
     pub(crate) quiet: bool,
     pub(crate) verbose: u8,
+    pub(crate) timings: bool,
+    pub(crate) trace_output: Option<PathBuf>,
+    pub(crate) summary_file: Option<PathBuf>,
+    pub(crate) output_format: OutputFormat,
     pub(crate) color: ColorChoice,
     pub(crate) native_tls: bool,
+    pub(crate) timeouts: NetworkTimeouts,
+    pub(crate) index_max_age: Option<u64>,
+    pub(crate) response_limits: ResponseLimits,
+    pub(crate) find_links_recursive: bool,
+    pub(crate) lock_timeout: Option<Duration>,
+    pub(crate) host_overrides: Vec<HostOverride>,
+    pub(crate) trusted_hosts: Vec<TrustedHost>,
+    pub(crate) rate_limits: Vec<IndexRateLimit>,
+    pub(crate) http_version: Option<HttpVersion>,
     pub(crate) connectivity: Connectivity,
     pub(crate) isolated: bool,
+    pub(crate) hermetic: bool,
+    pub(crate) venv_dir: Option<PathBuf>,
+    pub(crate) environments_dir: Option<PathBuf>,
+    pub(crate) install_hooks: InstallHooks,
+    pub(crate) build_commands: BuildCommands,
+    pub(crate) allow_hooks: bool,
     pub(crate) preview: PreviewMode,
+    pub(crate) allow_external_symlinks: bool,
 }
 
 impl GlobalSettings {
@@ -45,6 +78,16 @@ impl GlobalSettings {
         Self {
             quiet: args.quiet,
             verbose: args.verbose,
+            timings: args.timings,
+            trace_output: args.trace_output.map(|trace_output| {
+                PathBuf::from(
+                    trace_output
+                        .strip_prefix("chrome://")
+                        .unwrap_or(&trace_output),
+                )
+            }),
+            summary_file: args.summary_file,
+            output_format: args.output_format,
             color: if args.no_color {
                 ColorChoice::Never
             } else {
@@ -53,6 +96,48 @@ impl GlobalSettings {
             native_tls: flag(args.native_tls, args.no_native_tls)
                 .combine(workspace.and_then(|workspace| workspace.options.native_tls))
                 .unwrap_or(false),
+            // As with other settings, the environment takes precedence over the configuration.
+            timeouts: NetworkTimeouts {
+                read: args
+                    .http_timeout
+                    .combine(
+                        env(env::HTTP_TIMEOUT)
+                            .or_else(|| env(env::REQUEST_TIMEOUT))
+                            .or_else(|| env(env::CARGO_HTTP_TIMEOUT)),
+                    )
+                    .combine(workspace.and_then(|workspace| workspace.options.http_timeout)),
+                connect: args
+                    .http_connect_timeout
+                    .combine(env(env::HTTP_CONNECT_TIMEOUT))
+                    .combine(
+                        workspace.and_then(|workspace| workspace.options.http_connect_timeout),
+                    ),
+                deadline: args
+                    .http_deadline
+                    .combine(env(env::HTTP_DEADLINE))
+                    .combine(workspace.and_then(|workspace| workspace.options.http_deadline)),
+            },
+            index_max_age: env(env::INDEX_MAX_AGE),
+            response_limits: ResponseLimits {
+                simple: env(env::MAX_SIMPLE_RESPONSE_SIZE),
+                metadata: env(env::MAX_METADATA_SIZE),
+            },
+            find_links_recursive: env(env::FIND_LINKS_RECURSIVE).is_some_and(|EnvFlag(flag)| flag),
+            lock_timeout: env(env::LOCK_TIMEOUT).map(Duration::from_secs),
+            host_overrides: args
+                .resolve
+                .combine(workspace.and_then(|workspace| workspace.options.resolve.clone()))
+                .unwrap_or_default(),
+            // Trusted hosts can't be set in the workspace configuration, such that a project can't
+            // disable certificate verification on behalf of the user.
+            trusted_hosts: args.trusted_host.unwrap_or_default(),
+            rate_limits: args
+                .index_rate_limit
+                .combine(workspace.and_then(|workspace| workspace.options.index_rate_limit.clone()))
+                .unwrap_or_default(),
+            http_version: args
+                .http_version
+                .combine(workspace.and_then(|workspace| workspace.options.http_version)),
             connectivity: if flag(args.offline, args.no_offline)
                 .combine(workspace.and_then(|workspace| workspace.options.offline))
                 .unwrap_or(false)
@@ -62,11 +147,86 @@ impl GlobalSettings {
                 Connectivity::Online
             },
             isolated: args.isolated,
+            hermetic: args.hermetic,
+            venv_dir: args
+                .venv_dir
+                .filter(|venv_dir| !venv_dir.as_os_str().is_empty()),
+            environments_dir: env::<PathBuf>(env::ENVIRONMENTS_DIR)
+                .filter(|environments_dir| !environments_dir.as_os_str().is_empty()),
+            install_hooks: workspace
+                .and_then(|workspace| workspace.options.pip.as_ref())
+                .map(|pip| {
+                    let mut hooks = InstallHooks::default();
+                    if let Some(command) = &pip.pre_install_hook {
+                        hooks = hooks.with_pre_install(CommandHook::new(command));
+                    }
+                    if let Some(command) = &pip.post_install_hook {
+                        hooks = hooks.with_post_install(CommandHook::new(command));
+                    }
+                    if let Some(command) = &pip.post_uninstall_hook {
+                        hooks = hooks.with_post_uninstall(CommandHook::new(command));
+                    }
+                    hooks
+                })
+                .unwrap_or_default(),
+            build_commands: workspace
+                .and_then(|workspace| workspace.options.pip.as_ref())
+                .and_then(|pip| pip.build_commands.clone())
+                .unwrap_or_default(),
+            allow_hooks: args.allow_hooks,
             preview: PreviewMode::from(
                 flag(args.preview, args.no_preview)
                     .combine(workspace.and_then(|workspace| workspace.options.preview))
                     .unwrap_or(false),
             ),
+            allow_external_symlinks: args.allow_external_symlinks,
+        }
+    }
+
+    /// The [`BaseClientBuilder`] for commands that don't accept their own network settings (e.g.,
+    /// the project commands), configured from the global network settings and reporting to the
+    /// given [`NetworkRecorder`].
+    pub(crate) fn client_builder(
+        &self,
+        network_recorder: &NetworkRecorder,
+    ) -> BaseClientBuilder<'static> {
+        BaseClientBuilder::new()
+            .network_recorder(network_recorder.clone())
+            .connectivity(self.connectivity)
+            .native_tls(self.native_tls)
+            .timeouts(self.timeouts)
+            .index_max_age(self.index_max_age)
+            .response_limits(self.response_limits)
+            .host_overrides(self.host_overrides.clone())
+            .trusted_hosts(self.trusted_hosts.clone())
+            .rate_limits(self.rate_limits.clone())
+            .http_version(self.http_version)
+            .hermetic(self.hermetic)
+    }
+
+    /// The [`InstallHooks`] defined in the configuration file, if they were allowed with
+    /// `--allow-hooks`.
+    pub(crate) fn install_hooks(&self) -> InstallHooks {
+        if self.allow_hooks || self.install_hooks.is_disabled() {
+            self.install_hooks.clone()
+        } else {
+            warn_user_once!(
+                "Ignoring the install hooks defined in the configuration file. Pass `--allow-hooks` (or set `UV_ALLOW_HOOKS=1`) to run them."
+            );
+            InstallHooks::default()
+        }
+    }
+
+    /// The external build commands defined in the configuration file, if they were allowed with
+    /// `--allow-hooks`, merged with (and overridden by) those provided on the command-line.
+    pub(crate) fn build_commands(&self, build_commands: &BuildCommands) -> BuildCommands {
+        if self.allow_hooks || self.build_commands.is_empty() {
+            build_commands.clone().merge(self.build_commands.clone())
+        } else {
+            warn_user_once!(
+                "Ignoring the build commands defined in the configuration file. Pass `--allow-hooks` (or set `UV_ALLOW_HOOKS=1`) to run them."
+            );
+            build_commands.clone()
         }
     }
 }
@@ -77,6 +237,7 @@ impl GlobalSettings {
 pub(crate) struct CacheSettings {
     pub(crate) no_cache: bool,
     pub(crate) cache_dir: Option<PathBuf>,
+    pub(crate) build_dir: Option<PathBuf>,
 }
 
 impl CacheSettings {
@@ -90,6 +251,9 @@ impl CacheSettings {
             cache_dir: args
                 .cache_dir
                 .or_else(|| workspace.and_then(|workspace| workspace.options.cache_dir.clone())),
+            build_dir: args
+                .build_dir
+                .or_else(|| workspace.and_then(|workspace| workspace.options.build_dir.clone())),
         }
     }
 }
@@ -102,18 +266,24 @@ pub(crate) struct RunSettings {
     pub(crate) target: Option<String>,
     pub(crate) args: Vec<OsString>,
     pub(crate) with: Vec<String>,
+    pub(crate) environment: Option<String>,
     pub(crate) python: Option<String>,
+
+    // Shared settings.
+    pub(crate) shared: ProjectSharedSettings,
 }
 
 impl RunSettings {
     /// Resolve the [`RunSettings`] from the CLI and workspace configuration.
     #[allow(clippy::needless_pass_by_value)]
-    pub(crate) fn resolve(args: RunArgs, _workspace: Option<Workspace>) -> Self {
+    pub(crate) fn resolve(args: RunArgs, workspace: Option<Workspace>) -> Self {
         let RunArgs {
             target,
             args,
             with,
+            environment,
             python,
+            shared,
         } = args;
 
         Self {
@@ -121,7 +291,11 @@ impl RunSettings {
             target,
             args,
             with,
+            environment,
             python,
+
+            // Shared settings.
+            shared: ProjectSharedSettings::resolve(shared, workspace.as_ref()),
         }
     }
 }
@@ -131,18 +305,34 @@ impl RunSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct SyncSettings {
     // CLI-only settings.
+    pub(crate) extras: ExtrasSpecification,
+    pub(crate) trusted_signers: Option<PathBuf>,
     pub(crate) python: Option<String>,
+
+    // Shared settings.
+    pub(crate) shared: ProjectSharedSettings,
 }
 
 impl SyncSettings {
     /// Resolve the [`SyncSettings`] from the CLI and workspace configuration.
     #[allow(clippy::needless_pass_by_value)]
-    pub(crate) fn resolve(args: SyncArgs, _workspace: Option<Workspace>) -> Self {
-        let SyncArgs { python } = args;
+    pub(crate) fn resolve(args: SyncArgs, workspace: Option<Workspace>) -> Self {
+        let SyncArgs {
+            extra,
+            all_extras,
+            trusted_signers,
+            python,
+            shared,
+        } = args;
 
         Self {
             // CLI-only settings.
+            extras: ExtrasSpecification::from_args(all_extras, extra.unwrap_or_default()),
+            trusted_signers,
             python,
+
+            // Shared settings.
+            shared: ProjectSharedSettings::resolve(shared, workspace.as_ref()),
         }
     }
 }
@@ -152,18 +342,147 @@ impl SyncSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct LockSettings {
     // CLI-only settings.
+    pub(crate) sign_with: Option<PathBuf>,
     pub(crate) python: Option<String>,
+
+    // Shared settings.
+    pub(crate) shared: ProjectSharedSettings,
 }
 
 impl LockSettings {
     /// Resolve the [`LockSettings`] from the CLI and workspace configuration.
     #[allow(clippy::needless_pass_by_value)]
-    pub(crate) fn resolve(args: LockArgs, _workspace: Option<Workspace>) -> Self {
-        let LockArgs { python } = args;
+    pub(crate) fn resolve(args: LockArgs, workspace: Option<Workspace>) -> Self {
+        let LockArgs {
+            sign_with,
+            python,
+            shared,
+        } = args;
 
         Self {
             // CLI-only settings.
+            sign_with,
             python,
+
+            // Shared settings.
+            shared: ProjectSharedSettings::resolve(shared, workspace.as_ref()),
+        }
+    }
+}
+
+/// The resolved settings to use for the project commands (e.g., `lock`, `sync`, and `run`).
+///
+/// Represents the shared settings that are used across all project commands.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProjectSharedSettings {
+    pub(crate) no_build: NoBuild,
+    pub(crate) max_build_depth: Option<usize>,
+    pub(crate) patches: PackagePatches,
+    pub(crate) package_policy: PackagePolicy,
+    pub(crate) script_options: ScriptOptions,
+    pub(crate) compile_bytecode: bool,
+    pub(crate) compile_options: CompileOptions,
+}
+
+impl ProjectSharedSettings {
+    /// Resolve the [`ProjectSharedSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: ProjectSharedArgs, workspace: Option<&Workspace>) -> Self {
+        let ProjectSharedArgs {
+            allow_build,
+            max_build_depth,
+            deny_package,
+            allow_package,
+            scripts,
+            no_rewrite_shebangs,
+            rewrite_shebangs,
+            no_gui_trampolines,
+            gui_trampolines,
+            force,
+            compile_bytecode,
+            no_compile_bytecode,
+            compile_optimize,
+            compile_exclude,
+            compile_invalidation_mode,
+        } = args;
+        let pip = workspace.and_then(|workspace| workspace.options.pip.as_ref());
+
+        Self {
+            no_build: NoBuild::None.with_allowed(
+                allow_build
+                    .combine(pip.and_then(|pip| pip.allow_build.clone()))
+                    .unwrap_or_default(),
+            ),
+            max_build_depth: max_build_depth.combine(pip.and_then(|pip| pip.max_build_depth)),
+            patches: pip.and_then(|pip| pip.patch.clone()).unwrap_or_default(),
+            package_policy: PackagePolicy::new(
+                deny_package
+                    .combine(pip.and_then(|pip| pip.deny_package.clone()))
+                    .unwrap_or_default(),
+                allow_package
+                    .combine(pip.and_then(|pip| pip.allow_package.clone()))
+                    .unwrap_or_default(),
+            ),
+            script_options: ScriptOptions {
+                kind: scripts
+                    .combine(pip.and_then(|pip| pip.scripts))
+                    .unwrap_or_default(),
+                rewrite_shebangs: flag(rewrite_shebangs, no_rewrite_shebangs)
+                    .combine(pip.and_then(|pip| pip.rewrite_shebangs))
+                    .unwrap_or(true),
+                gui_trampolines: flag(gui_trampolines, no_gui_trampolines)
+                    .combine(pip.and_then(|pip| pip.gui_trampolines))
+                    .unwrap_or(true),
+                allow_conflicts: force,
+            },
+            compile_bytecode: flag(compile_bytecode, no_compile_bytecode)
+                .combine(pip.and_then(|pip| pip.compile_bytecode))
+                .unwrap_or_default(),
+            compile_options: CompileOptions {
+                optimization_levels: compile_optimize
+                    .combine(pip.and_then(|pip| pip.compile_optimize.clone()))
+                    .unwrap_or_default(),
+                exclude: compile_exclude
+                    .combine(pip.and_then(|pip| pip.compile_exclude.clone()))
+                    .unwrap_or_default(),
+                invalidation_mode: compile_invalidation_mode
+                    .combine(pip.and_then(|pip| pip.compile_invalidation_mode)),
+            },
+        }
+    }
+}
+
+/// The resolved settings to use for an `export` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportSettings {
+    // CLI-only settings.
+    pub(crate) format: ExportFormat,
+    pub(crate) stable_age: u32,
+
+    // Shared settings.
+    pub(crate) keyring_provider: KeyringProviderType,
+}
+
+impl ExportSettings {
+    /// Resolve the [`ExportSettings`] from the CLI and workspace configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ExportArgs, workspace: Option<Workspace>) -> Self {
+        let ExportArgs {
+            format,
+            stable_age,
+            keyring_provider,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            format,
+            stable_age,
+
+            // Shared settings.
+            keyring_provider: keyring_provider
+                .combine(workspace.and_then(|workspace| {
+                    workspace.options.pip.and_then(|pip| pip.keyring_provider)
+                }))
+                .unwrap_or_default(),
         }
     }
 }
@@ -178,7 +497,10 @@ pub(crate) struct PipCompileSettings {
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) refresh: Refresh,
     pub(crate) upgrade: Upgrade,
+    pub(crate) group: Vec<CompileGroup>,
     pub(crate) uv_lock: bool,
+    pub(crate) interactive: bool,
+    pub(crate) graph: Option<GraphFormat>,
 
     // Shared settings.
     pub(crate) shared: PipSharedSettings,
@@ -200,14 +522,20 @@ impl PipCompileSettings {
             prerelease,
             pre,
             output_file,
+            group,
+            matrix,
             no_strip_extras,
             strip_extras,
+            no_strip_markers,
+            strip_markers,
             no_annotate,
             annotate,
             no_header,
             header,
             annotation_style,
             custom_compile_command,
+            custom_header,
+            sign_with,
 
             refresh,
             no_refresh,
@@ -217,6 +545,10 @@ impl PipCompileSettings {
             extra_index_url,
             no_index,
             index_strategy,
+            check_dependency_confusion,
+            no_check_dependency_confusion,
+            check_metadata_consistency,
+            no_check_metadata_consistency,
             keyring_provider,
             find_links,
             python,
@@ -234,11 +566,27 @@ impl PipCompileSettings {
             no_build,
             build,
             only_binary,
+            prefer_source,
+            prefer_binary,
+            allow_build,
+            max_build_depth,
             config_setting,
+            build_command,
             python_version,
             python_platform,
             exclude_newer,
+            deny_package,
+            allow_package,
+            min_release_age,
+            min_release_age_package,
+            resolution_timeout,
+            max_backtracks,
+            prefetch,
+            prefetch_threshold,
             no_emit_package,
+            unsafe_package,
+            allow_unsafe,
+            no_allow_unsafe,
             emit_index_url,
             no_emit_index_url,
             emit_find_links,
@@ -249,6 +597,9 @@ impl PipCompileSettings {
             no_emit_index_annotation,
             unstable_uv_lock_file,
             no_unstable_uv_lock_file,
+            interactive,
+            no_interactive,
+            graph,
             compat_args: _,
         } = args;
 
@@ -262,7 +613,17 @@ impl PipCompileSettings {
             r#override,
             refresh: Refresh::from_args(flag(refresh, no_refresh), refresh_package),
             upgrade: Upgrade::from_args(flag(upgrade, no_upgrade), upgrade_package),
+            group: group
+                .into_iter()
+                .chain(
+                    matrix
+                        .iter()
+                        .flat_map(|matrix| matrix.expand(output_file.as_deref())),
+                )
+                .collect(),
             uv_lock: flag(unstable_uv_lock_file, no_unstable_uv_lock_file).unwrap_or(false),
+            interactive: flag(interactive, no_interactive).unwrap_or(false),
+            graph,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -280,9 +641,21 @@ impl PipCompileSettings {
                     no_index: Some(no_index),
                     find_links,
                     index_strategy,
+                    check_dependency_confusion: flag(
+                        check_dependency_confusion,
+                        no_check_dependency_confusion,
+                    ),
+                    check_metadata_consistency: flag(
+                        check_metadata_consistency,
+                        no_check_metadata_consistency,
+                    ),
                     keyring_provider,
                     no_build: flag(no_build, build),
                     only_binary,
+                    prefer_source,
+                    prefer_binary,
+                    allow_build,
+                    max_build_depth,
                     no_build_isolation: flag(no_build_isolation, build_isolation),
                     extra,
                     all_extras: flag(all_extras, no_all_extras),
@@ -295,18 +668,34 @@ impl PipCompileSettings {
                     },
                     output_file,
                     no_strip_extras: flag(no_strip_extras, strip_extras),
+                    no_strip_markers: flag(no_strip_markers, strip_markers),
                     no_annotate: flag(no_annotate, annotate),
                     no_header: flag(no_header, header),
                     custom_compile_command,
+                    custom_header,
+                    sign_with,
                     generate_hashes: flag(generate_hashes, no_generate_hashes),
                     legacy_setup_py: flag(legacy_setup_py, no_legacy_setup_py),
                     config_settings: config_setting.map(|config_settings| {
                         config_settings.into_iter().collect::<ConfigSettings>()
                     }),
+                    build_commands: build_command.map(|build_commands| {
+                        build_commands.into_iter().collect::<BuildCommands>()
+                    }),
                     python_version,
                     python_platform,
                     exclude_newer,
+                    deny_package,
+                    allow_package,
+                    min_release_age,
+                    min_release_age_package,
+                    resolution_timeout,
+                    max_backtracks,
+                    prefetch,
+                    prefetch_threshold,
                     no_emit_package,
+                    unsafe_package,
+                    allow_unsafe: flag(allow_unsafe, no_allow_unsafe),
                     emit_index_url: flag(emit_index_url, no_emit_index_url),
                     emit_find_links: flag(emit_find_links, no_emit_find_links),
                     emit_marker_expression: flag(emit_marker_expression, no_emit_marker_expression),
@@ -316,6 +705,7 @@ impl PipCompileSettings {
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
                     concurrent_installs: env(env::CONCURRENT_INSTALLS),
+                    concurrent_unzips: env(env::CONCURRENT_UNZIPS),
                     ..PipOptions::default()
                 },
                 workspace,
@@ -333,7 +723,16 @@ pub(crate) struct PipSyncSettings {
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) reinstall: Reinstall,
     pub(crate) refresh: Refresh,
+    pub(crate) check_import: bool,
+    pub(crate) force: bool,
+    pub(crate) ignore_platform_compatibility: bool,
+    pub(crate) ignore_compiled_environment: bool,
+    pub(crate) no_require_hashes: bool,
+    pub(crate) environments: Vec<PathBuf>,
+    pub(crate) all_environments: bool,
     pub(crate) dry_run: bool,
+    pub(crate) report: Option<PathBuf>,
+    pub(crate) trusted_signers: Option<PathBuf>,
 
     // Shared settings.
     pub(crate) shared: PipSharedSettings,
@@ -352,13 +751,26 @@ impl PipSyncSettings {
             no_refresh,
             refresh_package,
             link_mode,
+            scripts,
+            no_rewrite_shebangs,
+            rewrite_shebangs,
+            no_gui_trampolines,
+            gui_trampolines,
+            force,
+            ignore_platform_compatibility,
+            ignore_compiled_environment,
+            file_collisions,
             index_url,
             extra_index_url,
             find_links,
+            local_dist,
             no_index,
             index_strategy,
+            check_dependency_confusion,
+            no_check_dependency_confusion,
             require_hashes,
             no_require_hashes,
+            trusted_signers,
             keyring_provider,
             python,
             system,
@@ -366,6 +778,8 @@ impl PipSyncSettings {
             break_system_packages,
             no_break_system_packages,
             target,
+            environments,
+            all_environments,
             legacy_setup_py,
             no_legacy_setup_py,
             no_build_isolation,
@@ -374,18 +788,51 @@ impl PipSyncSettings {
             build,
             no_binary,
             only_binary,
+            prefer_source,
+            prefer_binary,
+            allow_build,
+            max_build_depth,
             compile_bytecode,
             no_compile_bytecode,
+            compile_optimize,
+            compile_exclude,
+            compile_invalidation_mode,
+            check_import,
+            no_install_tests,
+            install_tests,
+            exclude_path_glob,
             config_setting,
+            build_command,
             python_version,
             python_platform,
             strict,
             no_strict,
             exclude_newer,
+            deny_package,
+            allow_package,
+            min_release_age,
+            min_release_age_package,
+            resolution_timeout,
+            max_backtracks,
+            prefetch,
+            prefetch_threshold,
             dry_run,
+            report,
             compat_args: _,
         } = args;
 
+        // Hash-checking is enabled for requirements compiled with `--generate-hashes`, unless it
+        // was explicitly disabled.
+        let require_hashes = flag(require_hashes, no_require_hashes);
+        let no_require_hashes = require_hashes
+            .combine(
+                workspace
+                    .as_ref()
+                    .and_then(|workspace| workspace.options.pip.as_ref())
+                    .and_then(|pip| pip.require_hashes),
+            )
+            .is_some_and(|require_hashes| !require_hashes);
+
         Self {
             // CLI-only settings.
             src_file,
@@ -395,7 +842,16 @@ impl PipSyncSettings {
                 .collect(),
             reinstall: Reinstall::from_args(flag(reinstall, no_reinstall), reinstall_package),
             refresh: Refresh::from_args(flag(refresh, no_refresh), refresh_package),
+            check_import,
+            force,
+            ignore_platform_compatibility,
+            ignore_compiled_environment,
+            no_require_hashes,
+            environments,
+            all_environments,
             dry_run,
+            report,
+            trusted_signers,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -414,26 +870,56 @@ impl PipSyncSettings {
                     }),
                     no_index: Some(no_index),
                     find_links,
+                    local_dist,
                     index_strategy,
+                    check_dependency_confusion: flag(
+                        check_dependency_confusion,
+                        no_check_dependency_confusion,
+                    ),
                     keyring_provider,
                     no_build: flag(no_build, build),
                     no_binary,
                     only_binary,
+                    prefer_source,
+                    prefer_binary,
+                    allow_build,
+                    max_build_depth,
                     no_build_isolation: flag(no_build_isolation, build_isolation),
                     strict: flag(strict, no_strict),
                     legacy_setup_py: flag(legacy_setup_py, no_legacy_setup_py),
                     config_settings: config_setting.map(|config_settings| {
                         config_settings.into_iter().collect::<ConfigSettings>()
                     }),
+                    build_commands: build_command.map(|build_commands| {
+                        build_commands.into_iter().collect::<BuildCommands>()
+                    }),
                     python_version,
                     python_platform,
                     exclude_newer,
+                    deny_package,
+                    allow_package,
+                    min_release_age,
+                    min_release_age_package,
+                    resolution_timeout,
+                    max_backtracks,
+                    prefetch,
+                    prefetch_threshold,
                     link_mode,
+                    scripts,
+                    rewrite_shebangs: flag(rewrite_shebangs, no_rewrite_shebangs),
+                    gui_trampolines: flag(gui_trampolines, no_gui_trampolines),
+                    file_collisions,
                     compile_bytecode: flag(compile_bytecode, no_compile_bytecode),
-                    require_hashes: flag(require_hashes, no_require_hashes),
+                    compile_optimize,
+                    compile_exclude,
+                    compile_invalidation_mode,
+                    no_install_tests: flag(no_install_tests, install_tests),
+                    exclude_path_glob,
+                    require_hashes,
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
                     concurrent_installs: env(env::CONCURRENT_INSTALLS),
+                    concurrent_unzips: env(env::CONCURRENT_UNZIPS),
                     ..PipOptions::default()
                 },
                 workspace,
@@ -453,10 +939,20 @@ pub(crate) struct PipInstallSettings {
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) upgrade: Upgrade,
+    pub(crate) upgrade_strategy: UpgradeStrategy,
     pub(crate) reinstall: Reinstall,
     pub(crate) refresh: Refresh,
+    pub(crate) check_import: bool,
+    pub(crate) force: bool,
+    pub(crate) ignore_platform_compatibility: bool,
+    pub(crate) exact: bool,
+    pub(crate) check_only: bool,
     pub(crate) dry_run: bool,
+    pub(crate) report: Option<PathBuf>,
     pub(crate) uv_lock: Option<String>,
+    pub(crate) environments: Vec<PathBuf>,
+    pub(crate) all_environments: bool,
+    pub(crate) trusted_signers: Option<PathBuf>,
 
     // Shared settings.
     pub(crate) shared: PipSharedSettings,
@@ -477,6 +973,7 @@ impl PipInstallSettings {
             upgrade,
             no_upgrade,
             upgrade_package,
+            upgrade_strategy,
             reinstall,
             no_reinstall,
             reinstall_package,
@@ -486,6 +983,14 @@ impl PipInstallSettings {
             no_deps,
             deps,
             link_mode,
+            scripts,
+            no_rewrite_shebangs,
+            rewrite_shebangs,
+            no_gui_trampolines,
+            gui_trampolines,
+            force,
+            ignore_platform_compatibility,
+            file_collisions,
             resolution,
             prerelease,
             pre,
@@ -494,8 +999,11 @@ impl PipInstallSettings {
             find_links,
             no_index,
             index_strategy,
+            check_dependency_confusion,
+            no_check_dependency_confusion,
             require_hashes,
             no_require_hashes,
+            trusted_signers,
             keyring_provider,
             python,
             system,
@@ -503,6 +1011,8 @@ impl PipInstallSettings {
             break_system_packages,
             no_break_system_packages,
             target,
+            environments,
+            all_environments,
             legacy_setup_py,
             no_legacy_setup_py,
             no_build_isolation,
@@ -511,15 +1021,40 @@ impl PipInstallSettings {
             build,
             no_binary,
             only_binary,
+            prefer_source,
+            prefer_binary,
+            allow_build,
+            max_build_depth,
+            cache_resolution,
+            no_cache_resolution,
             compile_bytecode,
             no_compile_bytecode,
+            compile_optimize,
+            compile_exclude,
+            compile_invalidation_mode,
+            check_import,
+            no_install_tests,
+            install_tests,
+            exclude_path_glob,
             config_setting,
+            build_command,
             python_version,
             python_platform,
             strict,
             no_strict,
             exclude_newer,
+            deny_package,
+            allow_package,
+            min_release_age,
+            min_release_age_package,
+            resolution_timeout,
+            max_backtracks,
+            prefetch,
+            prefetch_threshold,
+            exact,
+            check_only,
             dry_run,
+            report,
             unstable_uv_lock_file,
             compat_args: _,
         } = args;
@@ -535,10 +1070,20 @@ impl PipInstallSettings {
                 .collect(),
             r#override,
             upgrade: Upgrade::from_args(flag(upgrade, no_upgrade), upgrade_package),
+            upgrade_strategy: upgrade_strategy.unwrap_or_default(),
             reinstall: Reinstall::from_args(flag(reinstall, no_reinstall), reinstall_package),
             refresh: Refresh::from_args(flag(refresh, no_refresh), refresh_package),
+            check_import,
+            force,
+            ignore_platform_compatibility,
+            exact,
+            check_only,
             dry_run,
+            report,
             uv_lock: unstable_uv_lock_file,
+            environments,
+            all_environments,
+            trusted_signers,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -558,10 +1103,18 @@ impl PipInstallSettings {
                     no_index: Some(no_index),
                     find_links,
                     index_strategy,
+                    check_dependency_confusion: flag(
+                        check_dependency_confusion,
+                        no_check_dependency_confusion,
+                    ),
                     keyring_provider,
                     no_build: flag(no_build, build),
                     no_binary,
                     only_binary,
+                    prefer_source,
+                    prefer_binary,
+                    allow_build,
+                    max_build_depth,
                     no_build_isolation: flag(no_build_isolation, build_isolation),
                     strict: flag(strict, no_strict),
                     extra,
@@ -577,15 +1130,37 @@ impl PipInstallSettings {
                     config_settings: config_setting.map(|config_settings| {
                         config_settings.into_iter().collect::<ConfigSettings>()
                     }),
+                    build_commands: build_command.map(|build_commands| {
+                        build_commands.into_iter().collect::<BuildCommands>()
+                    }),
                     python_version,
                     python_platform,
                     exclude_newer,
+                    deny_package,
+                    allow_package,
+                    min_release_age,
+                    min_release_age_package,
+                    resolution_timeout,
+                    max_backtracks,
+                    prefetch,
+                    prefetch_threshold,
                     link_mode,
+                    scripts,
+                    rewrite_shebangs: flag(rewrite_shebangs, no_rewrite_shebangs),
+                    gui_trampolines: flag(gui_trampolines, no_gui_trampolines),
+                    file_collisions,
                     compile_bytecode: flag(compile_bytecode, no_compile_bytecode),
+                    cache_resolution: flag(cache_resolution, no_cache_resolution),
+                    compile_optimize,
+                    compile_exclude,
+                    compile_invalidation_mode,
+                    no_install_tests: flag(no_install_tests, install_tests),
+                    exclude_path_glob,
                     require_hashes: flag(require_hashes, no_require_hashes),
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
                     concurrent_installs: env(env::CONCURRENT_INSTALLS),
+                    concurrent_unzips: env(env::CONCURRENT_UNZIPS),
                     ..PipOptions::default()
                 },
                 workspace,
@@ -601,6 +1176,8 @@ pub(crate) struct PipUninstallSettings {
     // CLI-only settings.
     pub(crate) package: Vec<String>,
     pub(crate) requirement: Vec<PathBuf>,
+    pub(crate) all: bool,
+    pub(crate) dry_run: bool,
     // Shared settings.
     pub(crate) shared: PipSharedSettings,
 }
@@ -618,12 +1195,16 @@ impl PipUninstallSettings {
             break_system_packages,
             no_break_system_packages,
             target,
+            all,
+            dry_run,
         } = args;
 
         Self {
             // CLI-only settings.
             package,
             requirement,
+            all,
+            dry_run,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -648,6 +1229,7 @@ impl PipUninstallSettings {
 pub(crate) struct PipFreezeSettings {
     // CLI-only settings.
     pub(crate) exclude_editable: bool,
+    pub(crate) hashes: bool,
     // Shared settings.
     pub(crate) shared: PipSharedSettings,
 }
@@ -657,6 +1239,7 @@ impl PipFreezeSettings {
     pub(crate) fn resolve(args: PipFreezeArgs, workspace: Option<Workspace>) -> Self {
         let PipFreezeArgs {
             exclude_editable,
+            hashes,
             strict,
             no_strict,
             python,
@@ -667,6 +1250,7 @@ impl PipFreezeSettings {
         Self {
             // CLI-only settings.
             exclude_editable,
+            hashes,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -691,6 +1275,8 @@ pub(crate) struct PipListSettings {
     pub(crate) exclude_editable: bool,
     pub(crate) exclude: Vec<PackageName>,
     pub(crate) format: ListFormat,
+    pub(crate) outdated: bool,
+    pub(crate) sizes: bool,
 
     // CLI-only settings.
     pub(crate) shared: PipSharedSettings,
@@ -704,12 +1290,23 @@ impl PipListSettings {
             exclude_editable,
             exclude,
             format,
+            outdated,
+            no_outdated,
+            sizes,
+            prerelease,
+            pre,
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            keyring_provider,
+            exclude_newer,
             strict,
             no_strict,
             python,
             system,
             no_system,
-            compat_args: _,
         } = args;
 
         Self {
@@ -718,12 +1315,31 @@ impl PipListSettings {
             exclude_editable,
             exclude,
             format,
+            outdated: flag(outdated, no_outdated).unwrap_or(false),
+            sizes,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
                 PipOptions {
                     python,
                     system: flag(system, no_system),
+                    index_url: index_url.and_then(Maybe::into_option),
+                    extra_index_url: extra_index_url.map(|extra_index_urls| {
+                        extra_index_urls
+                            .into_iter()
+                            .filter_map(Maybe::into_option)
+                            .collect()
+                    }),
+                    no_index: Some(no_index),
+                    find_links,
+                    index_strategy,
+                    keyring_provider,
+                    prerelease: if pre {
+                        Some(PreReleaseMode::Allow)
+                    } else {
+                        prerelease
+                    },
+                    exclude_newer,
                     strict: flag(strict, no_strict),
                     ..PipOptions::default()
                 },
@@ -774,6 +1390,43 @@ impl PipShowSettings {
     }
 }
 
+/// The resolved settings to use for a `pip why` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct PipWhySettings {
+    // CLI-only settings.
+    pub(crate) package: PackageName,
+
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl PipWhySettings {
+    /// Resolve the [`PipWhySettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: PipWhyArgs, workspace: Option<Workspace>) -> Self {
+        let PipWhyArgs {
+            package,
+            python,
+            system,
+            no_system,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            package,
+
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    python,
+                    system: flag(system, no_system),
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
 /// The resolved settings to use for a `pip check` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
@@ -807,16 +1460,216 @@ impl PipCheckSettings {
     }
 }
 
+/// The resolved settings to use for a `pip hash` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct PipHashSettings {
+    // CLI-only settings.
+    pub(crate) artifact: Vec<String>,
+    pub(crate) algorithm: HashAlgorithm,
+
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl PipHashSettings {
+    /// Resolve the [`PipHashSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: PipHashArgs, workspace: Option<Workspace>) -> Self {
+        let PipHashArgs {
+            artifact,
+            algorithm,
+            index_url,
+            extra_index_url,
+            index_strategy,
+            keyring_provider,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            artifact,
+            algorithm,
+
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    index_url: index_url.and_then(Maybe::into_option),
+                    extra_index_url: extra_index_url.map(|extra_index_urls| {
+                        extra_index_urls
+                            .into_iter()
+                            .filter_map(Maybe::into_option)
+                            .collect()
+                    }),
+                    index_strategy,
+                    keyring_provider,
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
+/// The resolved settings to use for a `pip lint` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct PipLintSettings {
+    // CLI-only settings.
+    pub(crate) src_file: Vec<PathBuf>,
+
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl PipLintSettings {
+    /// Resolve the [`PipLintSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: PipLintArgs, workspace: Option<Workspace>) -> Self {
+        let PipLintArgs {
+            src_file,
+            index_url,
+            extra_index_url,
+            index_strategy,
+            keyring_provider,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            src_file,
+
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    index_url: index_url.and_then(Maybe::into_option),
+                    extra_index_url: extra_index_url.map(|extra_index_urls| {
+                        extra_index_urls
+                            .into_iter()
+                            .filter_map(Maybe::into_option)
+                            .collect()
+                    }),
+                    index_strategy,
+                    keyring_provider,
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
+/// The resolved settings to use for a `cache warm` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct CacheWarmSettings {
+    // CLI-only settings.
+    pub(crate) requirement: Vec<PathBuf>,
+    pub(crate) constraint: Vec<PathBuf>,
+    pub(crate) build: bool,
+
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl CacheWarmSettings {
+    /// Resolve the [`CacheWarmSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: CacheWarmArgs, workspace: Option<Workspace>) -> Self {
+        let CacheWarmArgs {
+            requirement,
+            constraint,
+            build,
+            python,
+            index_url,
+            extra_index_url,
+            find_links,
+            no_index,
+            index_strategy,
+            keyring_provider,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            requirement,
+            constraint: constraint
+                .into_iter()
+                .filter_map(Maybe::into_option)
+                .collect(),
+            build,
+
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    python,
+                    index_url: index_url.and_then(Maybe::into_option),
+                    extra_index_url: extra_index_url.map(|extra_index_urls| {
+                        extra_index_urls
+                            .into_iter()
+                            .filter_map(Maybe::into_option)
+                            .collect()
+                    }),
+                    no_index: Some(no_index),
+                    find_links,
+                    index_strategy,
+                    keyring_provider,
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
+/// The resolved settings to use for a `doctor` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct DoctorSettings {
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl DoctorSettings {
+    /// Resolve the [`DoctorSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: DoctorArgs, workspace: Option<Workspace>) -> Self {
+        let DoctorArgs {
+            python,
+            index_url,
+            extra_index_url,
+            no_index,
+            keyring_provider,
+        } = args;
+
+        Self {
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    python,
+                    index_url: index_url.and_then(Maybe::into_option),
+                    extra_index_url: extra_index_url.map(|extra_index_urls| {
+                        extra_index_urls
+                            .into_iter()
+                            .filter_map(Maybe::into_option)
+                            .collect()
+                    }),
+                    no_index: Some(no_index),
+                    keyring_provider,
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
 /// The resolved settings to use for a `pip check` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct VenvSettings {
     // CLI-only settings.
     pub(crate) seed: bool,
+    pub(crate) seed_source: SeedSource,
     pub(crate) allow_existing: bool,
+    pub(crate) clear: bool,
     pub(crate) name: PathBuf,
+    pub(crate) env_name: Option<String>,
+    pub(crate) central: bool,
     pub(crate) prompt: Option<String>,
     pub(crate) system_site_packages: bool,
+    pub(crate) python_preference: PythonPreference,
+    pub(crate) register: bool,
+    pub(crate) register_tag: Option<String>,
 
     // CLI-only settings.
     pub(crate) shared: PipSharedSettings,
@@ -830,11 +1683,19 @@ impl VenvSettings {
             system,
             no_system,
             seed,
+            seed_source,
             allow_existing,
+            clear,
+            register,
+            register_tag,
             name,
+            env_name,
+            central,
             prompt,
             system_site_packages,
+            python_preference,
             link_mode,
+            no_binary,
             index_url,
             extra_index_url,
             no_index,
@@ -848,10 +1709,17 @@ impl VenvSettings {
         Self {
             // CLI-only settings.
             seed,
+            seed_source: seed_source.unwrap_or_default(),
             allow_existing,
+            clear,
             name,
+            env_name,
+            central,
             prompt,
             system_site_packages,
+            python_preference: python_preference.unwrap_or_default(),
+            register,
+            register_tag,
 
             // Shared settings.
             shared: PipSharedSettings::combine(
@@ -871,6 +1739,7 @@ impl VenvSettings {
                     keyring_provider,
                     exclude_newer,
                     link_mode,
+                    no_binary,
                     ..PipOptions::default()
                 },
                 workspace,
@@ -886,15 +1755,22 @@ impl VenvSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct PipSharedSettings {
     pub(crate) index_locations: IndexLocations,
+    pub(crate) local_dist: Vec<PathBuf>,
     pub(crate) python: Option<String>,
     pub(crate) system: bool,
     pub(crate) extras: ExtrasSpecification,
     pub(crate) break_system_packages: bool,
     pub(crate) target: Option<Target>,
     pub(crate) index_strategy: IndexStrategy,
+    pub(crate) check_dependency_confusion: bool,
+    pub(crate) dependency_confusion_allow: Vec<PackageName>,
+    pub(crate) check_metadata_consistency: bool,
     pub(crate) keyring_provider: KeyringProviderType,
     pub(crate) no_binary: NoBinary,
     pub(crate) no_build: NoBuild,
+    pub(crate) prefer_source: PreferSource,
+    pub(crate) prefer_binary: PreferBinary,
+    pub(crate) max_build_depth: Option<usize>,
     pub(crate) no_build_isolation: bool,
     pub(crate) strict: bool,
     pub(crate) dependency_mode: DependencyMode,
@@ -902,23 +1778,39 @@ pub(crate) struct PipSharedSettings {
     pub(crate) prerelease: PreReleaseMode,
     pub(crate) output_file: Option<PathBuf>,
     pub(crate) no_strip_extras: bool,
+    pub(crate) no_strip_markers: bool,
     pub(crate) no_annotate: bool,
     pub(crate) no_header: bool,
     pub(crate) custom_compile_command: Option<String>,
+    pub(crate) custom_header: Option<String>,
+    pub(crate) sign_with: Option<PathBuf>,
     pub(crate) generate_hashes: bool,
     pub(crate) setup_py: SetupPyStrategy,
     pub(crate) config_setting: ConfigSettings,
+    pub(crate) build_commands: BuildCommands,
+    pub(crate) patches: PackagePatches,
     pub(crate) python_version: Option<PythonVersion>,
     pub(crate) python_platform: Option<TargetTriple>,
     pub(crate) exclude_newer: Option<ExcludeNewer>,
+    pub(crate) package_policy: PackagePolicy,
+    pub(crate) resolution_limits: ResolutionLimits,
+    pub(crate) prefetch: PrefetchStrategy,
     pub(crate) no_emit_package: Vec<PackageName>,
+    pub(crate) unsafe_package: Vec<PackageName>,
+    pub(crate) allow_unsafe: bool,
     pub(crate) emit_index_url: bool,
     pub(crate) emit_find_links: bool,
     pub(crate) emit_marker_expression: bool,
     pub(crate) emit_index_annotation: bool,
     pub(crate) annotation_style: AnnotationStyle,
     pub(crate) link_mode: LinkMode,
+    pub(crate) script_options: ScriptOptions,
+    pub(crate) file_collisions: FileCollisionPolicy,
     pub(crate) compile_bytecode: bool,
+    pub(crate) compile_options: CompileOptions,
+    pub(crate) no_install_tests: bool,
+    pub(crate) exclude_path_glob: Vec<String>,
+    pub(crate) cache_resolution: bool,
     pub(crate) require_hashes: bool,
     pub(crate) concurrency: Concurrency,
 }
@@ -935,11 +1827,19 @@ impl PipSharedSettings {
             extra_index_url,
             no_index,
             find_links,
+            local_dist,
             index_strategy,
+            check_dependency_confusion,
+            dependency_confusion_allow,
+            check_metadata_consistency,
             keyring_provider,
             no_build,
             no_binary,
             only_binary,
+            prefer_source,
+            prefer_binary,
+            allow_build,
+            max_build_depth,
             no_build_isolation,
             strict,
             extra,
@@ -949,27 +1849,56 @@ impl PipSharedSettings {
             prerelease,
             output_file,
             no_strip_extras,
+            no_strip_markers,
             no_annotate,
             no_header,
             custom_compile_command,
+            custom_header,
+            sign_with,
             generate_hashes,
             legacy_setup_py,
             config_settings,
+            build_commands: _,
+            patch,
             python_version,
             python_platform,
             exclude_newer,
+            deny_package,
+            allow_package,
+            min_release_age,
+            min_release_age_package,
+            resolution_timeout,
+            max_backtracks,
+            prefetch,
+            prefetch_threshold,
             no_emit_package,
+            unsafe_package,
+            allow_unsafe,
             emit_index_url,
             emit_find_links,
             emit_marker_expression,
             emit_index_annotation,
             annotation_style,
             link_mode,
+            scripts,
+            rewrite_shebangs,
+            gui_trampolines,
+            file_collisions,
             compile_bytecode,
+            compile_optimize,
+            compile_exclude,
+            compile_invalidation_mode,
+            no_install_tests,
+            exclude_path_glob,
+            cache_resolution,
             require_hashes,
+            pre_install_hook: _,
+            post_install_hook: _,
+            post_uninstall_hook: _,
             concurrent_builds,
             concurrent_downloads,
             concurrent_installs,
+            concurrent_unzips,
         } = workspace
             .and_then(|workspace| workspace.options.pip)
             .unwrap_or_default();
@@ -983,6 +1912,7 @@ impl PipSharedSettings {
                 args.find_links.combine(find_links).unwrap_or_default(),
                 args.no_index.combine(no_index).unwrap_or_default(),
             ),
+            local_dist: args.local_dist.combine(local_dist).unwrap_or_default(),
             extras: ExtrasSpecification::from_args(
                 args.all_extras.combine(all_extras).unwrap_or_default(),
                 args.extra.combine(extra).unwrap_or_default(),
@@ -999,9 +1929,15 @@ impl PipSharedSettings {
                 .no_strip_extras
                 .combine(no_strip_extras)
                 .unwrap_or_default(),
+            no_strip_markers: args
+                .no_strip_markers
+                .combine(no_strip_markers)
+                .unwrap_or_default(),
             no_annotate: args.no_annotate.combine(no_annotate).unwrap_or_default(),
             no_header: args.no_header.combine(no_header).unwrap_or_default(),
             custom_compile_command: args.custom_compile_command.combine(custom_compile_command),
+            custom_header: args.custom_header.combine(custom_header),
+            sign_with: args.sign_with.combine(sign_with),
             annotation_style: args
                 .annotation_style
                 .combine(annotation_style)
@@ -1010,6 +1946,18 @@ impl PipSharedSettings {
                 .index_strategy
                 .combine(index_strategy)
                 .unwrap_or_default(),
+            check_dependency_confusion: args
+                .check_dependency_confusion
+                .combine(check_dependency_confusion)
+                .unwrap_or_default(),
+            dependency_confusion_allow: args
+                .dependency_confusion_allow
+                .combine(dependency_confusion_allow)
+                .unwrap_or_default(),
+            check_metadata_consistency: args
+                .check_metadata_consistency
+                .combine(check_metadata_consistency)
+                .unwrap_or_default(),
             keyring_provider: args
                 .keyring_provider
                 .combine(keyring_provider)
@@ -1034,18 +1982,60 @@ impl PipSharedSettings {
             no_build: NoBuild::from_args(
                 args.only_binary.combine(only_binary).unwrap_or_default(),
                 args.no_build.combine(no_build).unwrap_or_default(),
-            ),
+            )
+            .with_allowed(args.allow_build.combine(allow_build).unwrap_or_default()),
+            max_build_depth: args.max_build_depth.combine(max_build_depth),
             config_setting: args
                 .config_settings
                 .combine(config_settings)
                 .unwrap_or_default(),
+            build_commands: args.build_commands.unwrap_or_default(),
+            patches: args.patch.combine(patch).unwrap_or_default(),
             python_version: args.python_version.combine(python_version),
             python_platform: args.python_platform.combine(python_platform),
             exclude_newer: args.exclude_newer.combine(exclude_newer),
+            package_policy: PackagePolicy::new(
+                args.deny_package.combine(deny_package).unwrap_or_default(),
+                args.allow_package
+                    .combine(allow_package)
+                    .unwrap_or_default(),
+            )
+            .with_min_release_age(MinReleaseAge::new(
+                args.min_release_age.combine(min_release_age),
+                args.min_release_age_package
+                    .combine(min_release_age_package)
+                    .unwrap_or_default(),
+            )),
+            resolution_limits: ResolutionLimits {
+                max_backtracks: args.max_backtracks.combine(max_backtracks),
+                timeout: args
+                    .resolution_timeout
+                    .combine(resolution_timeout)
+                    .map(Duration::from_secs),
+            },
+            prefetch: PrefetchStrategy::new(
+                args.prefetch.combine(prefetch).unwrap_or_default(),
+                args.prefetch_threshold.combine(prefetch_threshold),
+            ),
             no_emit_package: args
                 .no_emit_package
                 .combine(no_emit_package)
                 .unwrap_or_default(),
+            unsafe_package: args
+                .unsafe_package
+                .combine(unsafe_package)
+                .or_else(|| {
+                    // As in pip-compile, consider `setuptools`, `distribute`, and `pip` unsafe by
+                    // default, but only if `--allow-unsafe` or `--no-allow-unsafe` is provided.
+                    args.allow_unsafe.combine(allow_unsafe).map(|_| {
+                        ["setuptools", "distribute", "pip"]
+                            .into_iter()
+                            .map(|name| PackageName::from_str(name).unwrap())
+                            .collect()
+                    })
+                })
+                .unwrap_or_default(),
+            allow_unsafe: args.allow_unsafe.combine(allow_unsafe).unwrap_or_default(),
             emit_index_url: args
                 .emit_index_url
                 .combine(emit_index_url)
@@ -1063,6 +2053,22 @@ impl PipSharedSettings {
                 .combine(emit_index_annotation)
                 .unwrap_or_default(),
             link_mode: args.link_mode.combine(link_mode).unwrap_or_default(),
+            script_options: ScriptOptions {
+                kind: args.scripts.combine(scripts).unwrap_or_default(),
+                rewrite_shebangs: args
+                    .rewrite_shebangs
+                    .combine(rewrite_shebangs)
+                    .unwrap_or(true),
+                gui_trampolines: args
+                    .gui_trampolines
+                    .combine(gui_trampolines)
+                    .unwrap_or(true),
+                allow_conflicts: false,
+            },
+            file_collisions: args
+                .file_collisions
+                .combine(file_collisions)
+                .unwrap_or_default(),
             require_hashes: args
                 .require_hashes
                 .combine(require_hashes)
@@ -1075,10 +2081,45 @@ impl PipSharedSettings {
                 .unwrap_or_default(),
             target: args.target.combine(target).map(Target::from),
             no_binary: NoBinary::from_args(args.no_binary.combine(no_binary).unwrap_or_default()),
+            prefer_source: PreferSource::from_args(
+                args.prefer_source
+                    .combine(prefer_source)
+                    .unwrap_or_default(),
+            ),
+            prefer_binary: PreferBinary::from_args(
+                args.prefer_binary
+                    .combine(prefer_binary)
+                    .unwrap_or_default(),
+            ),
             compile_bytecode: args
                 .compile_bytecode
                 .combine(compile_bytecode)
                 .unwrap_or_default(),
+            cache_resolution: args
+                .cache_resolution
+                .combine(cache_resolution)
+                .unwrap_or_default(),
+            compile_options: CompileOptions {
+                optimization_levels: args
+                    .compile_optimize
+                    .combine(compile_optimize)
+                    .unwrap_or_default(),
+                exclude: args
+                    .compile_exclude
+                    .combine(compile_exclude)
+                    .unwrap_or_default(),
+                invalidation_mode: args
+                    .compile_invalidation_mode
+                    .combine(compile_invalidation_mode),
+            },
+            no_install_tests: args
+                .no_install_tests
+                .combine(no_install_tests)
+                .unwrap_or_default(),
+            exclude_path_glob: args
+                .exclude_path_glob
+                .combine(exclude_path_glob)
+                .unwrap_or_default(),
             strict: args.strict.combine(strict).unwrap_or_default(),
             concurrency: Concurrency {
                 downloads: args
@@ -1096,6 +2137,11 @@ impl PipSharedSettings {
                     .combine(concurrent_installs)
                     .map(NonZeroUsize::get)
                     .unwrap_or_else(Concurrency::threads),
+                unzips: args
+                    .concurrent_unzips
+                    .combine(concurrent_unzips)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
             },
         }
     }
@@ -1111,6 +2157,43 @@ mod env {
 
     pub(super) const CONCURRENT_INSTALLS: (&str, &str) =
         ("UV_CONCURRENT_INSTALLS", "a non-zero integer");
+
+    pub(super) const CONCURRENT_UNZIPS: (&str, &str) =
+        ("UV_CONCURRENT_UNZIPS", "a non-zero integer");
+
+    pub(super) const HTTP_TIMEOUT: (&str, &str) =
+        ("UV_HTTP_TIMEOUT", "an integer number of seconds");
+
+    /// Provided for backwards compatibility with v0.1.6.
+    pub(super) const REQUEST_TIMEOUT: (&str, &str) =
+        ("UV_REQUEST_TIMEOUT", "an integer number of seconds");
+
+    /// Matching Cargo's `http.timeout`; see
+    /// <https://doc.rust-lang.org/nightly/cargo/reference/config.html#httptimeout>.
+    pub(super) const CARGO_HTTP_TIMEOUT: (&str, &str) =
+        ("HTTP_TIMEOUT", "an integer number of seconds");
+
+    pub(super) const HTTP_CONNECT_TIMEOUT: (&str, &str) =
+        ("UV_HTTP_CONNECT_TIMEOUT", "an integer number of seconds");
+
+    pub(super) const HTTP_DEADLINE: (&str, &str) =
+        ("UV_HTTP_DEADLINE", "an integer number of seconds");
+
+    pub(super) const INDEX_MAX_AGE: (&str, &str) =
+        ("UV_INDEX_MAX_AGE", "an integer number of seconds");
+
+    pub(super) const MAX_SIMPLE_RESPONSE_SIZE: (&str, &str) =
+        ("UV_MAX_SIMPLE_RESPONSE_SIZE", "an integer number of bytes");
+
+    pub(super) const MAX_METADATA_SIZE: (&str, &str) =
+        ("UV_MAX_METADATA_SIZE", "an integer number of bytes");
+
+    pub(super) const FIND_LINKS_RECURSIVE: (&str, &str) = ("UV_FIND_LINKS_RECURSIVE", "a boolean");
+
+    pub(super) const ENVIRONMENTS_DIR: (&str, &str) = ("UV_ENVIRONMENTS_DIR", "a path");
+
+    pub(super) const LOCK_TIMEOUT: (&str, &str) =
+        ("UV_LOCK_TIMEOUT", "an integer number of seconds");
 }
 
 /// Attempt to load and parse an environment variable with the given name.
@@ -1133,6 +2216,22 @@ where
     )
 }
 
+/// A boolean environment variable, set with `1`, `true`, or `yes` (and unset with `0`, `false`,
+/// `no`, or an empty value).
+struct EnvFlag(bool);
+
+impl FromStr for EnvFlag {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" => Ok(Self(true)),
+            "0" | "false" | "no" | "" => Ok(Self(false)),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Prints a parse error and exits the process.
 #[allow(clippy::exit, clippy::print_stderr)]
 fn parse_failure(name: &str, expected: &str) -> ! {
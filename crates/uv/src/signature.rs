@@ -0,0 +1,460 @@
+//! Detached signatures for lockfiles and `requirements.txt` files.
+//!
+//! Signatures are created with `ssh-keygen -Y sign` over the canonicalized file contents (i.e.,
+//! with any existing signature removed, line endings normalized, and trailing blank lines
+//! stripped), and embedded at the end of the file as a comment, such that signed files remain
+//! readable by tools that don't verify them.
+
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context, Result};
+use rustc_hash::FxHashSet;
+use tracing::debug;
+
+use requirements_txt::RequirementsTxt;
+use uv_fs::Simplified;
+
+/// The namespace in which signatures are created, such that they can't be confused with
+/// signatures created for other purposes (e.g., Git commits).
+const NAMESPACE: &str = "uv-lock";
+
+const BEGIN: &str = "-----BEGIN SSH SIGNATURE-----";
+const END: &str = "-----END SSH SIGNATURE-----";
+
+/// Sign the file at the given path with the given private key, replacing any existing signature.
+pub(crate) fn sign(path: &Path, key: &Path) -> Result<()> {
+    let contents = fs_err::read_to_string(path)?;
+    let (content, _) = split(&contents).with_context(|| {
+        format!(
+            "Failed to read the existing signature of `{}`",
+            path.user_display()
+        )
+    })?;
+
+    let signature = ssh_keygen(
+        [
+            OsStr::new("-Y"),
+            OsStr::new("sign"),
+            OsStr::new("-n"),
+            OsStr::new(NAMESPACE),
+            OsStr::new("-f"),
+            key.as_os_str(),
+        ],
+        content.as_bytes(),
+    )
+    .with_context(|| format!("Failed to sign `{}`", path.user_display()))?;
+
+    let mut signed = content;
+    signed.push('\n');
+    for line in signature.lines() {
+        signed.push_str("# ");
+        signed.push_str(line.trim_end());
+        signed.push('\n');
+    }
+    fs_err::write(path, signed)?;
+
+    debug!("Signed `{}`", path.user_display());
+    Ok(())
+}
+
+/// Verify the embedded signature of the file at the given path against the public keys in the
+/// given `allowed_signers` file (as accepted by `ssh-keygen -Y verify`).
+///
+/// Returns the principal that signed the file.
+pub(crate) fn verify(path: &Path, allowed_signers: &Path) -> Result<String> {
+    let (principal, _) = verify_content(path, allowed_signers)?;
+    Ok(principal)
+}
+
+/// Verify the embedded signatures of the `requirements.txt` file at the given path and of any
+/// requirements or constraints files it includes (transitively), against the public keys in the
+/// given `allowed_signers` file.
+///
+/// Remote includes can't be verified, and are rejected.
+pub(crate) fn verify_requirements_txt(path: &Path, allowed_signers: &Path) -> Result<()> {
+    let working_dir = std::env::current_dir()?;
+
+    let mut seen = FxHashSet::default();
+    let mut queue = vec![path.to_path_buf()];
+    while let Some(path) = queue.pop() {
+        if !seen.insert(uv_fs::normalize_path(&working_dir.join(&path))?) {
+            continue;
+        }
+
+        let (_, content) = verify_content(&path, allowed_signers)?;
+        let includes = RequirementsTxt::includes(&content, &working_dir, &path)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+        for include in includes {
+            let url = include.to_string_lossy();
+            if url.starts_with("http://") || url.starts_with("https://") {
+                bail!(
+                    "`{}` includes the remote file `{url}`, which can't be verified; only local files can be included when signatures are required",
+                    path.user_display()
+                );
+            }
+            queue.push(include);
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify the embedded signature of the file at the given path, returning the principal that
+/// signed it and the signed (canonicalized) content.
+fn verify_content(path: &Path, allowed_signers: &Path) -> Result<(String, String)> {
+    let contents = fs_err::read_to_string(path)?;
+    let (content, signature) = split(&contents)
+        .with_context(|| format!("Failed to read the signature of `{}`", path.user_display()))?;
+    let Some(signature) = signature else {
+        bail!(
+            "`{}` is not signed, but signature verification was requested",
+            path.user_display()
+        );
+    };
+
+    let mut file = tempfile::NamedTempFile::new()?;
+    file.write_all(signature.as_bytes())?;
+
+    // Identify the signer, which must be listed in the `allowed_signers` file.
+    let principals = ssh_keygen(
+        [
+            OsStr::new("-Y"),
+            OsStr::new("find-principals"),
+            OsStr::new("-s"),
+            file.path().as_os_str(),
+            OsStr::new("-f"),
+            allowed_signers.as_os_str(),
+        ],
+        &[],
+    )
+    .map_err(|_| {
+        anyhow!(
+            "`{}` was not signed by a trusted signer (as listed in `{}`)",
+            path.user_display(),
+            allowed_signers.user_display()
+        )
+    })?;
+    let principal = principals
+        .lines()
+        .next()
+        .map(str::trim)
+        .filter(|principal| !principal.is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "`{}` was not signed by a trusted signer (as listed in `{}`)",
+                path.user_display(),
+                allowed_signers.user_display()
+            )
+        })?
+        .to_string();
+
+    // Verify the signature over the canonicalized contents.
+    ssh_keygen(
+        [
+            OsStr::new("-Y"),
+            OsStr::new("verify"),
+            OsStr::new("-n"),
+            OsStr::new(NAMESPACE),
+            OsStr::new("-f"),
+            allowed_signers.as_os_str(),
+            OsStr::new("-I"),
+            OsStr::new(&principal),
+            OsStr::new("-s"),
+            file.path().as_os_str(),
+        ],
+        content.as_bytes(),
+    )
+    .with_context(|| {
+        format!(
+            "The signature of `{}` doesn't match its contents; the file may have been modified after it was signed",
+            path.user_display()
+        )
+    })?;
+
+    debug!("Verified `{}` (signed by {principal})", path.user_display());
+    Ok((principal, content))
+}
+
+/// Split the contents of a file into its canonicalized content and its embedded signature, if
+/// any.
+///
+/// The signature must be the last thing in the file, and every line within it must be a comment,
+/// such that nothing outside the signed content can affect how the file is interpreted.
+fn split(contents: &str) -> Result<(String, Option<String>)> {
+    let mut lines = contents.lines().enumerate();
+
+    let mut content = Vec::new();
+    let mut signature = None;
+    for (_, line) in lines.by_ref() {
+        if line.trim_end() == format!("# {BEGIN}") {
+            signature = Some(vec![BEGIN]);
+            break;
+        }
+        content.push(line);
+    }
+
+    if let Some(signature) = signature.as_mut() {
+        let mut terminated = false;
+        for (index, line) in lines.by_ref() {
+            let Some(armor) = line.trim_end().strip_prefix("# ") else {
+                bail!(
+                    "Expected the signature to continue on line {}, but found: `{line}`",
+                    index + 1
+                );
+            };
+            signature.push(armor);
+            if armor == END {
+                terminated = true;
+                break;
+            }
+        }
+        if !terminated {
+            bail!("The signature is missing its `{END}` line");
+        }
+
+        // Nothing but blank lines may follow the signature.
+        if let Some((index, line)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+            bail!(
+                "Expected the signature to be at the end of the file, but found content on line {}: `{line}`",
+                index + 1
+            );
+        }
+    }
+
+    // Strip any trailing blank lines, such that the separator preceding the signature isn't
+    // considered part of the content.
+    while content.last().is_some_and(|line| line.trim().is_empty()) {
+        content.pop();
+    }
+
+    let mut canonical = content.join("\n");
+    canonical.push('\n');
+
+    let signature = signature.map(|lines| {
+        let mut signature = lines.join("\n");
+        signature.push('\n');
+        signature
+    });
+
+    Ok((canonical, signature))
+}
+
+/// Run `ssh-keygen` with the given arguments, passing the given data on standard input.
+///
+/// Returns the standard output of the command.
+fn ssh_keygen<'a>(args: impl IntoIterator<Item = &'a OsStr>, stdin: &[u8]) -> Result<String> {
+    let mut child = Command::new("ssh-keygen")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run `ssh-keygen`; is OpenSSH installed?")?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin)?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "`ssh-keygen` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use anyhow::Result;
+
+    use super::{sign, split, verify, verify_requirements_txt};
+
+    #[test]
+    fn split_signature() -> Result<()> {
+        let (content, signature) = split(indoc::indoc! {"
+            anyio==4.0.0\r
+            idna==3.6
+
+            # -----BEGIN SSH SIGNATURE-----
+            # U1NIU0lH
+            # -----END SSH SIGNATURE-----
+
+        "})?;
+        assert_eq!(content, "anyio==4.0.0\nidna==3.6\n");
+        assert_eq!(
+            signature.as_deref(),
+            Some("-----BEGIN SSH SIGNATURE-----\nU1NIU0lH\n-----END SSH SIGNATURE-----\n")
+        );
+
+        let (content, signature) = split("anyio==4.0.0\n\n\n")?;
+        assert_eq!(content, "anyio==4.0.0\n");
+        assert!(signature.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_rejects_unsigned_lines() {
+        // A requirement within the signature block isn't covered by the signature.
+        let err = split(indoc::indoc! {"
+            anyio==4.0.0
+
+            # -----BEGIN SSH SIGNATURE-----
+            # U1NIU0lH
+            idna==3.6
+            # -----END SSH SIGNATURE-----
+        "})
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected the signature to continue on line 5, but found: `idna==3.6`"
+        );
+
+        // Nor is a requirement following the signature block.
+        let err = split(indoc::indoc! {"
+            anyio==4.0.0
+
+            # -----BEGIN SSH SIGNATURE-----
+            # U1NIU0lH
+            # -----END SSH SIGNATURE-----
+            idna==3.6
+        "})
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected the signature to be at the end of the file, but found content on line 6: `idna==3.6`"
+        );
+
+        let err = split(indoc::indoc! {"
+            anyio==4.0.0
+
+            # -----BEGIN SSH SIGNATURE-----
+            # U1NIU0lH
+        "})
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The signature is missing its `-----END SSH SIGNATURE-----` line"
+        );
+    }
+
+    /// Generate a key pair, and an `allowed_signers` file that trusts it.
+    fn keypair(dir: &std::path::Path, name: &str) -> Result<()> {
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", name, "-f"])
+            .arg(dir.join(name))
+            .status()?;
+        assert!(status.success());
+
+        let public = fs_err::read_to_string(dir.join(format!("{name}.pub")))?;
+        fs_err::write(
+            dir.join(format!("{name}.allowed_signers")),
+            format!("{name} {public}"),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "ssh-keygen"),
+        ignore = "requires `ssh-keygen` (enable the `ssh-keygen` feature)"
+    )]
+    fn sign_and_verify() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        keypair(temp_dir.path(), "alice")?;
+        keypair(temp_dir.path(), "mallory")?;
+
+        let requirements_txt = temp_dir.path().join("requirements.txt");
+        fs_err::write(&requirements_txt, "anyio==4.0.0\nidna==3.6\n")?;
+
+        // Signing and verifying should succeed; re-signing should replace the signature.
+        sign(&requirements_txt, &temp_dir.path().join("alice"))?;
+        sign(&requirements_txt, &temp_dir.path().join("alice"))?;
+        let contents = fs_err::read_to_string(&requirements_txt)?;
+        assert!(
+            contents.starts_with("anyio==4.0.0\nidna==3.6\n\n# -----BEGIN SSH SIGNATURE-----\n")
+        );
+        assert_eq!(contents.matches("BEGIN SSH SIGNATURE").count(), 1);
+        let principal = verify(
+            &requirements_txt,
+            &temp_dir.path().join("alice.allowed_signers"),
+        )?;
+        assert_eq!(principal, "alice");
+
+        // A signer that isn't trusted should be rejected.
+        let err = verify(
+            &requirements_txt,
+            &temp_dir.path().join("mallory.allowed_signers"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not signed by a trusted signer"));
+
+        // A modified file should be rejected.
+        fs_err::write(
+            &requirements_txt,
+            contents.replace("anyio==4.0.0", "anyio==3.0.0"),
+        )?;
+        let err = verify(
+            &requirements_txt,
+            &temp_dir.path().join("alice.allowed_signers"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("doesn't match its contents"));
+
+        // An unsigned file should be rejected.
+        fs_err::write(&requirements_txt, "anyio==4.0.0\n")?;
+        let err = verify(
+            &requirements_txt,
+            &temp_dir.path().join("alice.allowed_signers"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("is not signed"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "ssh-keygen"),
+        ignore = "requires `ssh-keygen` (enable the `ssh-keygen` feature)"
+    )]
+    fn verify_includes() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        keypair(temp_dir.path(), "alice")?;
+        let key = temp_dir.path().join("alice");
+        let allowed_signers = temp_dir.path().join("alice.allowed_signers");
+
+        let requirements_txt = temp_dir.path().join("requirements.txt");
+        let constraints_txt = temp_dir.path().join("constraints.txt");
+        fs_err::write(&requirements_txt, "-c constraints.txt\nanyio\n")?;
+        fs_err::write(&constraints_txt, "anyio==4.0.0\n")?;
+        sign(&requirements_txt, &key)?;
+
+        // An unsigned constraints file should be rejected, even if the including file is signed.
+        let err = verify_requirements_txt(&requirements_txt, &allowed_signers).unwrap_err();
+        assert!(err.to_string().contains("constraints.txt` is not signed"));
+
+        sign(&constraints_txt, &key)?;
+        verify_requirements_txt(&requirements_txt, &allowed_signers)?;
+
+        // Remote includes can't be verified.
+        fs_err::write(
+            &requirements_txt,
+            "-r https://example.com/requirements.txt\n",
+        )?;
+        sign(&requirements_txt, &key)?;
+        let err = verify_requirements_txt(&requirements_txt, &allowed_signers).unwrap_err();
+        assert!(err.to_string().contains("which can't be verified"));
+
+        Ok(())
+    }
+}
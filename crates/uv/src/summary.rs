@@ -0,0 +1,70 @@
+//! Package counts and network statistics for the `--summary-file` report.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use uv_client::NetworkRecorder;
+use uv_types::BuildCounter;
+
+/// The packages counted in the `--summary-file` report, by the operation applied to them.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Packages {
+    Resolved,
+    Downloaded,
+    Installed,
+    Uninstalled,
+}
+
+impl Packages {
+    /// All operations, in the order in which they're reported.
+    pub(crate) const ALL: [Self; 4] = [
+        Self::Resolved,
+        Self::Downloaded,
+        Self::Installed,
+        Self::Uninstalled,
+    ];
+
+    /// The key under which the count is reported.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::Resolved => "resolved",
+            Self::Downloaded => "downloaded",
+            Self::Installed => "installed",
+            Self::Uninstalled => "uninstalled",
+        }
+    }
+}
+
+/// The counters behind the `--summary-file` report, created in `main` and passed to each command
+/// that resolves, downloads, builds, installs, or uninstalls packages.
+///
+/// Cloning the summary shares the underlying counters.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Summary {
+    packages: Arc<[AtomicUsize; 4]>,
+    builds: BuildCounter,
+    network: NetworkRecorder,
+}
+
+impl Summary {
+    /// Record that the operation was applied to the given number of packages.
+    pub(crate) fn record(&self, packages: Packages, count: usize) {
+        self.packages[packages as usize].fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// The number of packages to which the operation was applied so far.
+    pub(crate) fn count(&self, packages: Packages) -> usize {
+        self.packages[packages as usize].load(Ordering::Relaxed)
+    }
+
+    /// The counter for source distributions built into wheels, to pass to each
+    /// [`BuildDispatch`](uv_dispatch::BuildDispatch).
+    pub(crate) fn builds(&self) -> &BuildCounter {
+        &self.builds
+    }
+
+    /// The recorder for HTTP cache usage and network traffic, to pass to each client.
+    pub(crate) fn network(&self) -> &NetworkRecorder {
+        &self.network
+    }
+}
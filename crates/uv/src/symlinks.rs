@@ -0,0 +1,43 @@
+//! Refusal to modify environments that are reached through unexpected symlinks (e.g., a `.venv`
+//! that links to another project's environment, or to the system Python).
+
+use std::path::Path;
+
+use owo_colors::OwoColorize;
+
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+
+/// Verify that the virtualenv at `location` isn't a symlink to a system directory or, if a `root`
+/// is provided, to a location outside of `root`.
+///
+/// Always succeeds if `allow_external` is set (`--allow-external-symlinks`).
+pub(crate) fn check_location(
+    location: &Path,
+    root: Option<&Path>,
+    allow_external: bool,
+) -> Result<(), uv_virtualenv::Error> {
+    if allow_external {
+        return Ok(());
+    }
+    uv_virtualenv::check_location(location, root)
+}
+
+/// Verify that modifying the environment won't write through a symlink to a system directory.
+///
+/// Always succeeds if `allow_external` is set (`--allow-external-symlinks`).
+pub(crate) fn check_environment(
+    venv: &PythonEnvironment,
+    allow_external: bool,
+) -> anyhow::Result<()> {
+    if allow_external {
+        return Ok(());
+    }
+    uv_installer::check_target(venv, None).map_err(|err| {
+        anyhow::anyhow!(
+            "Refusing to modify the environment at {}, since {err}. To proceed anyway, pass `{}`.",
+            venv.root().user_display().cyan(),
+            "--allow-external-symlinks".green()
+        )
+    })
+}
@@ -156,3 +156,101 @@ fn prune_stale_symlink() -> Result<()> {
 
     Ok(())
 }
+
+/// `cache prune` should remove the logs of failed builds once they're older than a week.
+#[test]
+fn prune_build_logs() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Add a recent and an expired build log to the cache.
+    let recent = context
+        .cache_dir
+        .child("build-logs-v0")
+        .child("recent-1.0.0-abc123");
+    recent.child("build.json").write_str("{}")?;
+    let expired = context
+        .cache_dir
+        .child("build-logs-v0")
+        .child("expired-1.0.0-def456");
+    expired.child("build.json").write_str("{}")?;
+    let month_ago = filetime::FileTime::from_unix_time(
+        filetime::FileTime::now().unix_seconds() - 30 * 24 * 60 * 60,
+        0,
+    );
+    filetime::set_file_mtime(expired.path(), month_ago)?;
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--verbose"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    DEBUG Removing expired build log: [CACHE_DIR]/build-logs-v0/expired-1.0.0-def456
+    Removed 1 file ([SIZE])
+    "###);
+
+    expired.assert(predicates::path::missing());
+    recent
+        .child("build.json")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
+
+/// `cache prune` should remove cached resolutions once they're older than a day.
+#[test]
+fn prune_resolutions() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Add a recent and an expired resolution to the cache.
+    let recent = context
+        .cache_dir
+        .child("resolutions-v0")
+        .child("abc123.toml");
+    recent.write_str("version = 1")?;
+    let expired = context
+        .cache_dir
+        .child("resolutions-v0")
+        .child("def456.toml");
+    expired.write_str("version = 1")?;
+    let week_ago = filetime::FileTime::from_unix_time(
+        filetime::FileTime::now().unix_seconds() - 7 * 24 * 60 * 60,
+        0,
+    );
+    filetime::set_file_mtime(expired.path(), week_ago)?;
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--verbose"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    DEBUG Removing expired resolution: [CACHE_DIR]/resolutions-v0/def456.toml
+    Removed 1 file ([SIZE])
+    "###);
+
+    expired.assert(predicates::path::missing());
+    recent.assert(predicates::path::exists());
+
+    Ok(())
+}
@@ -38,6 +38,8 @@ pub const INSTA_FILTERS: &[(&str, &str)] = &[
     // Rewrite Windows output to Unix output
     (r"\\([\w\d])", "/$1"),
     (r"uv.exe", "uv"),
+    // Retained build logs are stored in uniquely-named directories
+    (r"build-logs-v0/[^\s]+", "build-logs-v0/[BUILD]"),
     // The exact message is host language dependent
     (
         r"Caused by: .* \(os error 2\)",
@@ -407,6 +409,7 @@ pub fn python_path_with_versions(
                     // because the venv subcommand requires a system interpreter.
                     uv_interpreter::SystemPython::Required,
                     &sources,
+                    None,
                     &cache,
                 )
                 .unwrap()
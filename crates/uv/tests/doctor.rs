@@ -0,0 +1,93 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `doctor` command with options shared across scenarios.
+fn doctor_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("doctor")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (8 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Standard filters for `doctor`, plus a filter for the platform-specific interpreter path.
+fn doctor_filters(context: &TestContext) -> Vec<(&str, &str)> {
+    [(r"\[VENV\]/(bin|Scripts)/python(\.exe)?", "[VENV]/[PYTHON]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect()
+}
+
+/// With network access disabled, `doctor` should report the interpreter, cache, and hardlink
+/// checks, and skip the index checks.
+#[test]
+#[cfg(not(windows))]
+fn doctor_offline() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(doctor_filters(&context), doctor_command(&context).arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok Cache directory: writable (`[CACHE_DIR]/`)
+    ok Python interpreter: cpython 3.12.[X] (`[VENV]/[PYTHON]`)
+    ok Hardlinks: supported between the cache and `[VENV]/`
+    skipped Index https://pypi.org/simple: network access is disabled (`--offline`)
+    skipped Keyring: the keyring provider is disabled (`--keyring-provider`)
+    skipped Long paths: only applicable on Windows
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// `doctor` should fail, suggesting a fix, if the keyring is enabled but unavailable.
+#[test]
+#[cfg(not(windows))]
+fn doctor_keyring_missing() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(doctor_filters(&context), doctor_command(&context)
+        .arg("--offline")
+        .arg("--keyring-provider")
+        .arg("subprocess")
+        .env("PATH", ""), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    ok Cache directory: writable (`[CACHE_DIR]/`)
+    ok Python interpreter: cpython 3.12.[X] (`[VENV]/[PYTHON]`)
+    ok Hardlinks: supported between the cache and `[VENV]/`
+    skipped Index https://pypi.org/simple: network access is disabled (`--offline`)
+    error Keyring: failed to run the `keyring` command (No such file or directory (os error 2))
+      hint: Install the `keyring` package, such that the `keyring` command is on the `PATH` (e.g., with `pipx install keyring`)
+    skipped Long paths: only applicable on Windows
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
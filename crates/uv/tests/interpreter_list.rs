@@ -0,0 +1,177 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+use crate::common::{get_bin, venv_to_interpreter, TestContext};
+
+mod common;
+
+/// Create an `interpreter list` command with options shared across scenarios.
+fn list_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("interpreter")
+        .arg("list")
+        .arg("--format")
+        .arg("json")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Run the command, parsing the listed interpreters from its JSON output.
+fn list_interpreters(command: &mut Command) -> Result<Vec<serde_json::Value>> {
+    let output = command.assert().success().get_output().stdout.clone();
+    Ok(serde_json::from_slice(&output)?)
+}
+
+/// The interpreter in the active virtual environment is the one uv would select by default.
+#[test]
+fn list_selected_virtualenv() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let interpreters =
+        list_interpreters(list_command(&context).env("VIRTUAL_ENV", context.venv.as_os_str()))?;
+
+    let selected = interpreters
+        .iter()
+        .filter(|interpreter| interpreter["selected"] == true)
+        .collect::<Vec<_>>();
+    assert_eq!(selected.len(), 1, "{interpreters:#?}");
+    assert_eq!(selected[0]["source"], "active virtual environment");
+    assert_eq!(selected[0]["virtualenv"], true);
+    assert!(selected[0]["version"]
+        .as_str()
+        .is_some_and(|version| version.starts_with("3.12.")));
+
+    Ok(())
+}
+
+/// Version-specific executables in the `PATH` (e.g., `python3.12`) are listed, even without a
+/// `python3` or `python` alongside them.
+#[test]
+#[cfg(unix)]
+fn list_versioned_executable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let bin = context.temp_dir.join("bin");
+    fs_err::create_dir_all(&bin)?;
+    let executable = bin.join("python3.12");
+    fs_err::os::unix::fs::symlink(
+        fs_err::canonicalize(venv_to_interpreter(&context.venv))?,
+        &executable,
+    )?;
+
+    let interpreters = list_interpreters(
+        list_command(&context)
+            .env_remove("VIRTUAL_ENV")
+            .env("UV_TEST_PYTHON_PATH", &bin),
+    )?;
+
+    assert!(
+        interpreters.iter().any(|interpreter| {
+            interpreter["path"] == executable.to_str().unwrap()
+                && interpreter["source"] == "search path"
+        }),
+        "{interpreters:#?}"
+    );
+
+    Ok(())
+}
+
+/// Without a request that any interpreter satisfies, none is selected.
+#[test]
+fn list_unsatisfied_request() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let interpreters = list_interpreters(
+        list_command(&context)
+            .arg("3.6")
+            .env("VIRTUAL_ENV", context.venv.as_os_str()),
+    )?;
+
+    assert!(
+        interpreters
+            .iter()
+            .all(|interpreter| interpreter["selected"] == false),
+        "{interpreters:#?}"
+    );
+
+    Ok(())
+}
+
+/// A virtual environment with a custom name (`UV_VENV_DIR`) is discovered in a parent directory,
+/// skipping any directory with the same name that isn't a virtual environment.
+#[test]
+fn list_custom_venv_dir() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    fs_err::rename(&context.venv, context.temp_dir.join("env"))?;
+    let subdir = context.temp_dir.join("src");
+    fs_err::create_dir_all(subdir.join("env"))?;
+
+    let interpreters = list_interpreters(
+        list_command(&context)
+            .env_remove("VIRTUAL_ENV")
+            .env("UV_VENV_DIR", "env")
+            .current_dir(&subdir),
+    )?;
+
+    let selected = interpreters
+        .iter()
+        .filter(|interpreter| interpreter["selected"] == true)
+        .collect::<Vec<_>>();
+    assert_eq!(selected.len(), 1, "{interpreters:#?}");
+    assert_eq!(selected[0]["source"], "virtual environment");
+    assert_eq!(selected[0]["virtualenv"], true);
+
+    Ok(())
+}
+
+/// Cached interpreter metadata is reused, unless `--refresh` is passed.
+#[test]
+fn list_refresh() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Start from an empty cache, such that every entry is written by `interpreter list`.
+    fs_err::remove_dir_all(context.cache_dir.join("interpreter-v2"))?;
+    list_interpreters(list_command(&context).env("VIRTUAL_ENV", context.venv.as_os_str()))?;
+
+    // Backdate the cache entries, so that we can tell whether they're rewritten.
+    let entries = fs_err::read_dir(context.cache_dir.join("interpreter-v2"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(!entries.is_empty());
+    let month_ago = filetime::FileTime::from_unix_time(
+        filetime::FileTime::now().unix_seconds() - 30 * 24 * 60 * 60,
+        0,
+    );
+    for entry in &entries {
+        filetime::set_file_mtime(entry, month_ago)?;
+    }
+
+    // Without `--refresh`, the cache entries are read, but not rewritten.
+    list_interpreters(list_command(&context).env("VIRTUAL_ENV", context.venv.as_os_str()))?;
+    for entry in &entries {
+        let modified = filetime::FileTime::from_last_modification_time(&fs_err::metadata(entry)?);
+        assert_eq!(modified, month_ago, "{}", entry.display());
+    }
+
+    // With `--refresh`, every interpreter is probed anew and its cache entry rewritten.
+    list_interpreters(
+        list_command(&context)
+            .arg("--refresh")
+            .env("VIRTUAL_ENV", context.venv.as_os_str()),
+    )?;
+    for entry in &entries {
+        let modified = filetime::FileTime::from_last_modification_time(&fs_err::metadata(entry)?);
+        assert!(modified > month_ago, "{}", entry.display());
+    }
+
+    Ok(())
+}
@@ -124,6 +124,59 @@ fn missing_requirements_in() {
     requirements_in.assert(predicates::path::missing());
 }
 
+/// Report a missing requirements file as JSON with `--output-format json`.
+#[test]
+fn missing_requirements_in_json() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-format")
+            .arg("json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    {"message":"failed to read from file `requirements.in`","code":null,"causes":["No such file or directory (os error 2)"]}
+    "###
+    );
+}
+
+/// Report resolver progress as one JSON event per line with `--output-format json`.
+#[test]
+fn compile_requirements_in_json_events() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-format")
+        .arg("json")
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+
+    let stderr = String::from_utf8(output.stderr)?;
+    let events = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .collect::<Vec<_>>();
+    for (package, version) in [("anyio", "3.7.0"), ("idna", "3.6"), ("sniffio", "1.3.1")] {
+        for kind in ["metadata-fetched", "version-tried"] {
+            assert!(
+                events.iter().any(|event| event["event"] == kind
+                    && event["package"] == package
+                    && event["version"] == version),
+                "Missing `{kind}` event for {package}=={version}:\n{stderr}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn missing_venv() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -635,6 +688,128 @@ setup(
     Ok(())
 }
 
+/// Request an extra that isn't provided by a `pyproject.toml` with dynamic metadata, which is only
+/// known once the metadata is built.
+#[test]
+fn compile_pyproject_toml_setuptools_extra_missing() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools"]
+build-backend = "setuptools.build_meta"
+"#,
+    )?;
+
+    let setup_cfg = context.temp_dir.child("setup.cfg");
+    setup_cfg.write_str(
+        r#"[options]
+packages = find:
+install_requires=
+    anyio
+
+[options.extras_require]
+dev =
+    iniconfig
+"#,
+    )?;
+
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str(
+        r#"# setup.py
+from setuptools import setup
+
+
+setup(
+    name="dummypkg",
+    description="A dummy package",
+)
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--extra")
+            .arg("docs"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requested extra not found: docs
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile a `pyproject.toml` file with dynamic metadata, in which an extra enables other extras
+/// of the same project.
+#[test]
+fn compile_pyproject_toml_setuptools_recursive_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools"]
+build-backend = "setuptools.build_meta"
+"#,
+    )?;
+
+    let setup_cfg = context.temp_dir.child("setup.cfg");
+    setup_cfg.write_str(
+        r#"[options]
+packages = find:
+install_requires=
+    anyio
+
+[options.extras_require]
+dev =
+    iniconfig
+all =
+    dummypkg[dev]
+"#,
+    )?;
+
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str(
+        r#"# setup.py
+from setuptools import setup
+
+
+setup(
+    name="dummypkg",
+    description="A dummy package",
+)
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--extra")
+            .arg("all"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z pyproject.toml --extra all
+    anyio==4.3.0
+        # via dummypkg (pyproject.toml)
+    idna==3.6
+        # via anyio
+    iniconfig==2.0.0
+        # via dummypkg (pyproject.toml)
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Compile a `setup.cfg` file.
 #[test]
 fn compile_setup_cfg() -> Result<()> {
@@ -996,7 +1171,7 @@ fn compile_python_37() -> Result<()> {
             .arg("--python-version")
             .arg("3.7"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1091,6 +1266,31 @@ fn compile_python_dev_version() -> Result<()> {
     Ok(())
 }
 
+/// Reject a prefetch threshold of zero.
+#[test]
+fn compile_prefetch_threshold_zero() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--prefetch-threshold")
+            .arg("0"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '0' for '--prefetch-threshold <PREFETCH_THRESHOLD>': number would be zero for non-zero type
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Omit the constraint annotation (e.g., `# from -c constraints.txt`) when the constraint is not
 /// applicable due to a marker expression.
 #[test]
@@ -1624,7 +1824,7 @@ fn conflicting_direct_url_dependency() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1750,7 +1950,7 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1763,143 +1963,290 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     Ok(())
 }
 
-/// Request `anyio` via two different URLs which resolve to the same canonical version.
+/// Request Flask, but deny its transitive dependency on Werkzeug.
 #[test]
-fn compatible_repeated_url_dependency() -> Result<()> {
+fn deny_transitive_package() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
-        anyio @ git+https://github.com/agronholm/anyio@4.3.0
-    "})?;
+    requirements_in.write_str("flask==3.0.0")?;
 
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
-    success: true
-    exit_code: 0
+            .arg("requirements.in")
+            .arg("--deny-package")
+            .arg("werkzeug"), @r###"
+    success: false
+    exit_code: 3
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
-    anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
-        # via -r requirements.in
-    idna==3.6
-        # via anyio
-    sniffio==1.3.1
-        # via anyio
 
     ----- stderr -----
-    Resolved 3 packages in [TIME]
+      × No solution found when resolving dependencies:
+      ╰─▶ Because werkzeug is on the deny-list and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask==3.0.0 cannot be used.
+          And because you require flask==3.0.0, we can conclude that the requirements are unsatisfiable.
     "###
     );
 
     Ok(())
 }
 
-/// Request `anyio` via two different URLs which resolve to the same repository, but different
-/// commits.
+/// Request Flask, but restrict the resolution to an allow-list that omits Werkzeug.
 #[test]
-fn conflicting_repeated_url_dependency() -> Result<()> {
+fn allow_package_omits_transitive() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
-        anyio @ git+https://github.com/agronholm/anyio.git@4.0.0
-    "})?;
+    requirements_in.write_str("flask==3.0.0")?;
 
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
+            .arg("requirements.in")
+            .arg("--allow-package")
+            .arg("flask"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
-    error: Requirements contain conflicting URLs for package `anyio`:
-    - git+https://github.com/agronholm/anyio.git@4.3.0
-    - git+https://github.com/agronholm/anyio.git@4.0.0
+      × No solution found when resolving dependencies:
+      ╰─▶ Because werkzeug is not on the allow-list and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask==3.0.0 cannot be used.
+          And because you require flask==3.0.0, we can conclude that the requirements are unsatisfiable.
     "###
     );
 
     Ok(())
 }
 
-/// Request `anyio` via three different URLs: `4.3.0`, a short SHA, and a precise SHA. All three
-/// are compatible, since they resolve to the same canonical version.
+/// Request a package with a minimum release age that excludes all of its distributions, unless
+/// the version is pinned exactly or the package's own age allows it.
 #[test]
-fn compatible_narrowed_url_dependency() -> Result<()> {
+fn min_release_age() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
-        anyio @ git+https://github.com/agronholm/anyio@437a7e31
-        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
-    "})?;
 
+    // Every version of `iniconfig` is more recent than the minimum release age.
+    requirements_in.write_str("iniconfig")?;
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--min-release-age")
+        .arg("5000w")
+        .output()?;
+    assert!(!output.status.success());
+
+    // Unless the package's own minimum release age allows it.
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
+            .arg("requirements.in")
+            .arg("--min-release-age")
+            .arg("5000w")
+            .arg("--min-release-age-package")
+            .arg("iniconfig=1d"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
-    anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --min-release-age 5000w --min-release-age-package iniconfig=1d
+    iniconfig==2.0.0
         # via -r requirements.in
-    idna==3.6
-        # via anyio
-    sniffio==1.3.1
-        # via anyio
 
     ----- stderr -----
-    Resolved 3 packages in [TIME]
+    Resolved 1 package in [TIME]
     "###
     );
 
-    Ok(())
-}
-
-/// Request `anyio` via three different URLs: a precise SHA, a short SHA, and `4.3.0`. All three
-/// are compatible, since they resolve to the same canonical version.
-#[test]
-fn compatible_broader_url_dependency() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
-        anyio @ git+https://github.com/agronholm/anyio@437a7e31
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
-    "})?;
-
+    // Or the version is pinned exactly.
+    requirements_in.write_str("iniconfig==2.0.0")?;
     uv_snapshot!(context.compile()
-            .arg("requirements.in"), @r###"
+            .arg("requirements.in")
+            .arg("--min-release-age")
+            .arg("5000w"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
-    anyio @ git+https://github.com/agronholm/anyio.git@437a7e310925a962cab4a58fcd2455fbcd578d51
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --min-release-age 5000w
+    iniconfig==2.0.0
         # via -r requirements.in
-    idna==3.6
-        # via anyio
-    sniffio==1.3.1
-        # via anyio
 
     ----- stderr -----
-    Resolved 3 packages in [TIME]
+    Resolved 1 package in [TIME]
     "###
     );
 
-    Ok(())
-}
-
-/// Request `anyio` via two different URLs: `4.3.0`, and a precise SHA, followed by `4.3.0` again.
-/// All three are compatible, since they resolve to the same canonical version.
-#[test]
-fn compatible_repeated_narrowed_url_dependency() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
-        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
-        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+    // Or pinned exactly by a constraint.
+    requirements_in.write_str("iniconfig")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("iniconfig==2.0.0")?;
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt")
+            .arg("--min-release-age")
+            .arg("5000w"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --constraint constraints.txt --min-release-age 5000w
+    iniconfig==2.0.0
+        # via
+        #   -c constraints.txt
+        #   -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // A pin in an existing output file isn't exempt.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0")?;
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--min-release-age")
+        .arg("5000w")
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+/// Request `anyio` via two different URLs which resolve to the same canonical version.
+#[test]
+fn compatible_repeated_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+        anyio @ git+https://github.com/agronholm/anyio@4.3.0
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request `anyio` via two different URLs which resolve to the same repository, but different
+/// commits.
+#[test]
+fn conflicting_repeated_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+        anyio @ git+https://github.com/agronholm/anyio.git@4.0.0
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requirements contain conflicting URLs for package `anyio`:
+    - git+https://github.com/agronholm/anyio.git@4.3.0
+    - git+https://github.com/agronholm/anyio.git@4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request `anyio` via three different URLs: `4.3.0`, a short SHA, and a precise SHA. All three
+/// are compatible, since they resolve to the same canonical version.
+#[test]
+fn compatible_narrowed_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+        anyio @ git+https://github.com/agronholm/anyio@437a7e31
+        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request `anyio` via three different URLs: a precise SHA, a short SHA, and `4.3.0`. All three
+/// are compatible, since they resolve to the same canonical version.
+#[test]
+fn compatible_broader_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+        anyio @ git+https://github.com/agronholm/anyio@437a7e31
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio @ git+https://github.com/agronholm/anyio.git@437a7e310925a962cab4a58fcd2455fbcd578d51
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request `anyio` via two different URLs: `4.3.0`, and a precise SHA, followed by `4.3.0` again.
+/// All three are compatible, since they resolve to the same canonical version.
+#[test]
+fn compatible_repeated_narrowed_url_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
+        anyio @ git+https://github.com/agronholm/anyio@437a7e310925a962cab4a58fcd2455fbcd578d51
+        anyio @ git+https://github.com/agronholm/anyio.git@4.3.0
     "})?;
 
     uv_snapshot!(context.compile()
@@ -2112,7 +2459,7 @@ fn requirement_constraint_override_url() -> Result<()> {
         .arg("--override")
         .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2331,7 +2678,7 @@ dependencies = ["anyio==3.7.0", "anyio==4.0.0"]
     uv_snapshot!(context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2362,7 +2709,7 @@ dependencies = ["anyio==300.1.4"]
     uv_snapshot!(context.compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2822,7 +3169,7 @@ fn compile_yanked_version_indirect() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3283,7 +3630,7 @@ fn error_missing_unnamed_env_var() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Couldn't parse requirement in `requirements.in` at position 0
+    error: Couldn't parse requirement in `requirements.in` at line 1, column 1
       Caused by: Expected package name starting with an alphanumeric character, found '$'
     ${URL}
     ^
@@ -4031,6 +4378,35 @@ fn generate_hashes_editable() -> Result<()> {
     Ok(())
 }
 
+/// Include the hashes of source distributions that were built during resolution, when the
+/// `--find-links` directory doesn't provide any.
+#[test]
+fn generate_hashes_find_links_source_distribution() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm==999.0.0")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--generate-hashes")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --generate-hashes
+    tqdm==999.0.0 \
+        --hash=sha256:89fa05cffa7f457658373b85de302d24d0c205ceda2819a8739e324b75e9430b
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Compile using `--find-links` with a local directory.
 #[test]
 fn find_links_directory() -> Result<()> {
@@ -4067,81 +4443,86 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+/// Suggest the nearest available versions, and the direct requirement to relax, when a requested
+/// version does not exist.
 #[test]
-fn find_links_url() -> Result<()> {
+fn compile_unsolvable_suggestions() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("tqdm")?;
+    requirements_in.write_str("validation==2.5.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
+            .arg("--preview")
             .arg("--no-index")
             .arg("--find-links")
-            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
-    success: true
-    exit_code: 0
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: false
+    exit_code: 3
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
-    tqdm==4.64.1
-        # via -r requirements.in
 
     ----- stderr -----
-    Resolved 1 package in [TIME]
+      × No solution found when resolving dependencies:
+      ╰─▶ Because there is no version of validation==2.5.0 and you require validation==2.5.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: No versions of validation satisfy validation==2.5.0; the nearest available versions are 2.0.0 and 3.0.0
+
+          hint: validation is constrained to validation==2.5.0 by a direct requirement, but validation==3.0.0 is available (try: relaxing the requirement on validation)
     "###
     );
 
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL passed via an environment variable.
+/// `--interactive` requires a terminal; otherwise, resolution failures are reported as usual.
 #[test]
-fn find_links_env_var() -> Result<()> {
+fn compile_interactive_without_terminal() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
+    requirements_in.write_str("validation==2.5.0")?;
 
-    uv_snapshot!(context.compile()
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
+            .arg("--interactive")
             .arg("--no-index")
-            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
-    success: true
-    exit_code: 0
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: false
+    exit_code: 3
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
-    tqdm==4.64.1
-        # via -r requirements.in
 
     ----- stderr -----
-    Resolved 1 package in [TIME]
+    warning: `--interactive` requires an interactive terminal; ignoring.
+      × No solution found when resolving dependencies:
+      ╰─▶ Because there is no version of validation==2.5.0 and you require validation==2.5.0, we can conclude that the requirements are unsatisfiable.
     "###
     );
 
     Ok(())
 }
 
-/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
-/// with the URL itself provided in a `requirements.txt` file.
+/// Resolve to an older version with a compatible wheel, rather than a newer version that is only
+/// available as a source distribution, with `--prefer-binary`.
 #[test]
-fn find_links_requirements_txt() -> Result<()> {
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+fn prefer_binary() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+    requirements_in.write_str("tqdm<1000")?;
 
-    uv_snapshot!(context.compile()
+    // By default, the newest version is selected, even though it's only available as a source
+    // distribution.
+    uv_snapshot!(context.filters(), context.compile()
             .arg("requirements.in")
             .arg("--no-index")
-            .arg("--emit-find-links"), @r###"
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --emit-find-links
-    --find-links https://download.pytorch.org/whl/torch_stable.html
-
-    tqdm==4.64.1
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==999.0.0
         # via -r requirements.in
 
     ----- stderr -----
@@ -4149,22 +4530,174 @@ fn find_links_requirements_txt() -> Result<()> {
     "###
     );
 
-    Ok(())
-}
-
-/// `extras==0.0.2` fails to build (i.e., it always throws). Since `extras==0.0.1` is pinned, we
-/// should never even attempt to build `extras==0.0.2`, despite an unpinned `extras[dev]`
-/// requirement.
-#[test]
-fn avoid_irrelevant_extras() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str(indoc! {r"
-        extras==0.0.1
-        extras[dev]
-    "})?;
-
-    uv_snapshot!(context.compile()
+    // With `--prefer-binary`, the newest version with a compatible wheel is selected instead.
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--prefer-binary=tqdm")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --prefer-binary=tqdm --no-index
+    tqdm==4.66.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Without a value, `--prefer-binary` applies to all packages, and doesn't consume the
+    // positional arguments that follow it.
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links"))
+            .arg("--prefer-binary")
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z --no-index --prefer-binary requirements.in
+    tqdm==4.66.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Suggest similarly-named packages when a requested package isn't found on any index.
+#[test]
+fn not_found_similar_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("simple-launchr")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because simple-launchr was not found in the package registry and you require simple-launchr, we can conclude that the requirements are unsatisfiable.
+
+          hint: No package named simple-launchr was found; did you mean simple-launcher?
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
+#[test]
+fn find_links_url() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==4.64.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL passed via an environment variable.
+#[test]
+fn find_links_env_var() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm\n--find-links ${URL}")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .env("URL", "https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==4.64.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index,
+/// with the URL itself provided in a `requirements.txt` file.
+#[test]
+fn find_links_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-f https://download.pytorch.org/whl/torch_stable.html\ntqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-find-links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --emit-find-links
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// `extras==0.0.2` fails to build (i.e., it always throws). Since `extras==0.0.1` is pinned, we
+/// should never even attempt to build `extras==0.0.2`, despite an unpinned `extras[dev]`
+/// requirement.
+#[test]
+fn avoid_irrelevant_extras() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        extras==0.0.1
+        extras[dev]
+    "})?;
+
+    uv_snapshot!(context.compile()
             .arg("requirements.in")
             .arg("--find-links")
             .arg(context.workspace_root.join("scripts").join("links")), @r###"
@@ -4562,6 +5095,38 @@ fn no_header() -> Result<()> {
     Ok(())
 }
 
+/// Ignore `UV_*` environment variables in hermetic mode.
+#[test]
+fn hermetic_ignores_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--hermetic")
+            .env("UV_CUSTOM_COMPILE_COMMAND", "./custom-uv-compile.sh")
+            .env("UV_INDEX_URL", "https://test.pypi.org/simple"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --hermetic
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Include custom compile command in the header.
 #[test]
 fn custom_compile_command() -> Result<()> {
@@ -4626,7 +5191,89 @@ fn custom_compile_command() -> Result<()> {
     Ok(())
 }
 
-/// Emit warnings when users pass redundant options from `pip-compile`.
+/// Replace the header with custom text.
+#[test]
+fn custom_header() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--custom-header")
+            .arg("Generated by `make lock`.\n\n# Do not edit."), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # Generated by `make lock`.
+    #
+    # Do not edit.
+    black==23.10.1
+        # via -r requirements.in
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==24.0
+        # via black
+    pathspec==0.12.1
+        # via black
+    platformdirs==4.2.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package, marking a dependency as unsafe and pinning it with `--allow-unsafe`.
+#[test]
+fn unsafe_package_allow_unsafe() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--unsafe-package")
+            .arg("jinja2")
+            .arg("--allow-unsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --unsafe-package jinja2 --allow-unsafe
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    # The following packages are considered to be unsafe in a requirements file:
+    jinja2==3.1.3
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Pass `--allow-unsafe` without any unsafe packages in the resolution.
 #[test]
 fn allow_unsafe() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -4647,7 +5294,6 @@ fn allow_unsafe() -> Result<()> {
         # via -r requirements.in
 
     ----- stderr -----
-    warning: pip-compile's `--allow-unsafe` has no effect (uv can safely pin `pip` and other packages).
     Resolved 2 packages in [TIME]
     "###
     );
@@ -4677,6 +5323,81 @@ fn resolver_legacy() -> Result<()> {
     Ok(())
 }
 
+/// Warn when a package resolved from the primary index is available at a newer version on an
+/// extra index, unless it's included in `dependency-confusion-allow`.
+#[test]
+fn check_dependency_confusion() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("jinja2==3.1.3")?;
+
+    // The latest versions on PyPI will change over time.
+    let filters: Vec<_> = [(r"newer version \([^)]+\)", "newer version ([VERSION])")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--index-url")
+            .arg("https://test.pypi.org/simple/")
+            .arg("--extra-index-url")
+            .arg("https://pypi.org/simple")
+            .arg("--check-dependency-confusion"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --index-url https://test.pypi.org/simple/ --extra-index-url https://pypi.org/simple --check-dependency-confusion
+    jinja2==3.1.3
+        # via -r requirements.in
+    markupsafe==2.1.5
+        # via jinja2
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    warning: `jinja2` was resolved to 3.1.3 from https://test.pypi.org/simple/, but a newer version ([VERSION]) is available from https://pypi.org/simple. If this is unexpected, the package may be the target of a dependency confusion attack.
+    warning: `markupsafe` was resolved to 2.1.5 from https://test.pypi.org/simple/, but a newer version ([VERSION]) is available from https://pypi.org/simple. If this is unexpected, the package may be the target of a dependency confusion attack.
+    "###
+    );
+
+    // Allow `markupsafe` to be resolved from either index.
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+
+        [tool.uv.pip]
+        dependency-confusion-allow = ["markupsafe"]
+    "#})?;
+
+    uv_snapshot!(filters, context.compile()
+            .arg("requirements.in")
+            .arg("--index-url")
+            .arg("https://test.pypi.org/simple/")
+            .arg("--extra-index-url")
+            .arg("https://pypi.org/simple")
+            .arg("--check-dependency-confusion"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --index-url https://test.pypi.org/simple/ --extra-index-url https://pypi.org/simple --check-dependency-confusion
+    jinja2==3.1.3
+        # via -r requirements.in
+    markupsafe==2.1.5
+        # via jinja2
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    warning: `jinja2` was resolved to 3.1.3 from https://test.pypi.org/simple/, but a newer version ([VERSION]) is available from https://pypi.org/simple. If this is unexpected, the package may be the target of a dependency confusion attack.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Emit the `--index-url` and `--extra-index-url` locations.
 /// Also, preserve the `--index-url` and `--extra-index-url` flags in the command in the header.
 #[test]
@@ -4771,7 +5492,7 @@ fn no_index_requirements_txt() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4880,7 +5601,7 @@ fn offline_registry() -> Result<()> {
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5011,7 +5732,7 @@ fn offline_find_links() -> Result<()> {
             .arg("https://download.pytorch.org/whl/torch_stable.html")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5030,7 +5751,7 @@ fn offline_find_links() -> Result<()> {
             .arg("--no-index")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5056,7 +5777,7 @@ fn offline_direct_url() -> Result<()> {
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 4
     ----- stdout -----
 
     ----- stderr -----
@@ -5116,7 +5837,7 @@ fn invalid_metadata_requires_python() -> Result<()> {
             .arg("--find-links")
             .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5148,7 +5869,7 @@ fn invalid_metadata_multiple_dist_info() -> Result<()> {
             .arg("--find-links")
             .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5376,7 +6097,7 @@ fn compile_constraints_incompatible_url() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5399,7 +6120,7 @@ fn index_url_in_requirements() -> Result<()> {
     uv_snapshot!(context.compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5581,37 +6302,139 @@ dependencies = [
         # via -r requirements.in
 
     ----- stderr -----
-    Built 2 editables in [TIME]
-    Resolved 2 packages in [TIME]
+    Built 2 editables in [TIME]
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve an editable package with an invalid extra.
+#[test]
+fn editable_invalid_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-e ../../scripts/packages/black_editable[empty]")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg(requirements_in.path())
+            .current_dir(current_dir()?), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z [TEMP_DIR]/requirements.in
+    -e ../../scripts/packages/black_editable
+        # via -r [TEMP_DIR]/requirements.in
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    warning: The package `black @ file://[WORKSPACE]/scripts/packages/black_editable` does not have an extra named `empty`.
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a package with `--no-strip-markers`, to include the markers under which each package
+/// is required in the output.
+#[test]
+fn no_strip_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0 ; python_version >= '3.8'")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-strip-markers"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-strip-markers
+    anyio==3.7.0 ; python_version >= '3.8'
+        # via -r requirements.in
+    idna==3.6 ; python_version >= '3.8'
+        # via anyio
+    sniffio==1.3.1 ; python_version >= '3.8'
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package with a platform-specific extra and `--no-strip-markers`. The dependencies
+/// introduced by the extra should inherit its markers, even though the package itself is
+/// required unconditionally.
+#[test]
+#[cfg(target_os = "linux")]
+fn no_strip_markers_conditional_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        flask
+        flask[dotenv] ; sys_platform == 'linux'
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-strip-markers"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-strip-markers
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.3
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    python-dotenv==1.0.1 ; sys_platform == 'linux'
+        # via flask
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 8 packages in [TIME]
     "###
     );
 
     Ok(())
 }
 
-/// Resolve an editable package with an invalid extra.
+/// Abort a resolution that exceeds `--resolution-timeout`.
 #[test]
-fn editable_invalid_extra() -> Result<()> {
+fn resolution_timeout() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("-e ../../scripts/packages/black_editable[empty]")?;
+    requirements_in.write_str("anyio==3.7.0")?;
 
-    uv_snapshot!(context.filters(), context.compile()
-            .arg(requirements_in.path())
-            .current_dir(current_dir()?), @r###"
-    success: true
-    exit_code: 0
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--resolution-timeout")
+            .arg("0"), @r###"
+    success: false
+    exit_code: 2
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z [TEMP_DIR]/requirements.in
-    -e ../../scripts/packages/black_editable
-        # via -r [TEMP_DIR]/requirements.in
 
     ----- stderr -----
-    Built 1 editable in [TIME]
-    Resolved 1 package in [TIME]
-    warning: The package `black @ file://[WORKSPACE]/scripts/packages/black_editable` does not have an extra named `empty`.
-    "###);
+    error: Resolution timed out after 0.0s
+    "###
+    );
 
     Ok(())
 }
@@ -5813,7 +6636,7 @@ fn compile_constraints_incompatible_version() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5841,7 +6664,7 @@ fn conflicting_url_markers() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5995,7 +6818,7 @@ fn override_with_incompatible_constraint() -> Result<()> {
             .arg("--override")
             .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -6040,6 +6863,48 @@ fn unsafe_package() -> Result<()> {
     werkzeug==3.0.1
         # via flask
 
+    # The following packages are considered to be unsafe in a requirements file:
+    # jinja2
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package, omitting a dependency from the output with `--omit`.
+#[test]
+fn omit_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--omit")
+            .arg("jinja2"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --omit jinja2
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
     # The following packages were excluded from the output:
     # jinja2
 
@@ -6745,7 +7610,7 @@ requires-python = "<=3.8"
     uv_snapshot!(context.filters(), context.compile()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -7370,7 +8235,7 @@ fn emit_marker_expression_exciting_linux() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --emit-marker-expression
     # Pinned dependencies known to be valid for:
-    #    platform_system == 'Linux' and python_version == '3.12' and platform_python_implementation == 'CPython'
+    #    platform_python_implementation == 'CPython' and platform_system == 'Linux' and python_version == '3.12'
     anyio==4.3.0
         # via -r requirements.in
     idna==3.6
@@ -7407,7 +8272,7 @@ fn emit_marker_expression_direct() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --emit-marker-expression
     # Pinned dependencies known to be valid for:
-    #    python_version == '3.12' and platform_python_implementation == 'CPython' and sys_platform == 'linux' and platform_system == 'Linux'
+    #    platform_python_implementation == 'CPython' and platform_system == 'Linux' and python_version == '3.12' and sys_platform == 'linux'
     anyio==4.3.0
         # via -r requirements.in
     idna==3.6
@@ -7487,7 +8352,7 @@ fn emit_marker_expression_pypy() -> Result<()> {
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --emit-marker-expression
     # Pinned dependencies known to be valid for:
-    #    python_version == '3.12' and implementation_name == 'cpython'
+    #    implementation_name == 'cpython' and python_version == '3.12'
     pendulum==3.0.0
         # via -r requirements.in
     python-dateutil==2.9.0.post0
@@ -7607,6 +8472,40 @@ fn local_version_of_remote_package() -> Result<()> {
     Ok(())
 }
 
+/// Redirect a package to a local source tree via `[tool.uv.pip.patch]`.
+#[test]
+fn compile_patch_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(&indoc::formatdoc! {r#"
+        [tool.uv.pip.patch]
+        anyio = {{ path = "{workspace_root}/scripts/packages/anyio_local" }}
+        "#,
+        workspace_root = context.workspace_root.simplified_display(),
+    })?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio>=4")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio @ file://[WORKSPACE]/scripts/packages/anyio_local
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn pendulum_no_tzdata_on_windows() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -7879,7 +8778,7 @@ requires-python = ">3.8"
         .arg("--override")
         .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -7925,6 +8824,76 @@ requires-python = ">3.8"
     Ok(())
 }
 
+/// Resolve a transitive URL dependency that's enabled through a recursive extra (i.e., an `all`
+/// extra that enables the project's own `dev` extra).
+#[test]
+fn transitive_url_recursive_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a library.
+    let lib = context.temp_dir.child("lib");
+    lib.create_dir_all()?;
+    let pyproject_toml = lib.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "lib"
+version = "0.0.0"
+dependencies = [
+    "iniconfig"
+]
+requires-python = ">3.8"
+"#,
+    )?;
+
+    // Create an application that depends on the library, but only via its `dev` extra, which is
+    // in turn enabled by its `all` extra.
+    let app = context.temp_dir.child("app");
+    app.create_dir_all()?;
+    let pyproject_toml = app.child("pyproject.toml");
+    pyproject_toml.write_str(&format!(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = []
+requires-python = ">3.8"
+
+[project.optional-dependencies]
+dev = [
+  "lib @ {}"
+]
+all = [
+  "example[dev]"
+]
+"#,
+        Url::from_directory_path(lib.path()).unwrap().as_str(),
+    ))?;
+
+    // Write to a requirements file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("./app[all]")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    ./app
+        # via -r requirements.in
+    iniconfig==2.0.0
+        # via lib
+    lib @ file://[TEMP_DIR]/lib
+        # via example
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Allow pre-releases for dependencies of source path requirements.
 #[test]
 fn pre_release_path_requirement() -> Result<()> {
@@ -8053,7 +9022,7 @@ fn compile_index_url_first_match() -> Result<()> {
         .arg("requirements.in")
         .arg("--no-deps"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -8428,7 +9397,7 @@ fn no_version_for_direct_dependency() -> Result<()> {
         // Must error before we make any network requests
         .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -8511,6 +9480,47 @@ fn python_platform() -> Result<()> {
     Ok(())
 }
 
+/// Compile against a `manylinux` policy, using the pip-style platform tag as an alias for the
+/// target triple.
+#[test]
+fn python_platform_manylinux_tag() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black")?;
+
+    uv_snapshot!(context.filters(),
+        windows_filters=false,
+        context.compile()
+        .arg("requirements.in")
+        .arg("--python-platform")
+        .arg("manylinux_2_28_x86_64"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-platform manylinux_2_28_x86_64
+    black==24.3.0
+        # via -r requirements.in
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==24.0
+        # via black
+    pathspec==0.12.1
+        # via black
+    platformdirs==4.2.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Verify that command-line arguments take precedence over on-disk configuration.
 #[test]
 fn resolve_configuration() -> Result<()> {
@@ -8736,7 +9746,7 @@ fn resolve_configuration() -> Result<()> {
         .arg("--extra-index-url")
         .arg("https://test.pypi.org/simple"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -9238,3 +10248,87 @@ fn tool_uv_sources() -> Result<()> {
 
     Ok(())
 }
+
+/// Write a Chrome trace of the resolution with `--trace-output`.
+#[test]
+fn compile_trace_output() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--trace-output")
+        .arg("chrome://trace.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    let trace: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.join("trace.json"),
+    )?)?;
+    let events = trace["traceEvents"]
+        .as_array()
+        .expect("`traceEvents` should be an array");
+    assert!(events.iter().any(|event| event["name"] == "solve"));
+
+    Ok(())
+}
+
+/// Write a summary of the resolution with `--summary-file`.
+#[test]
+fn compile_summary_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--summary-file")
+        .arg("summary.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    let summary: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.join("summary.json"),
+    )?)?;
+    assert_eq!(summary["packages"]["resolved"], 3);
+    assert_eq!(summary["packages"]["installed"], 0);
+    assert!(summary["phases"]["resolve"].is_f64());
+    assert!(summary["total"].is_f64());
+    assert!(summary["cache"]["misses"]
+        .as_u64()
+        .is_some_and(|misses| misses > 0));
+    assert!(summary["bytes_downloaded"]
+        .as_u64()
+        .is_some_and(|bytes| bytes > 0));
+
+    Ok(())
+}
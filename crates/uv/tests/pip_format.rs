@@ -0,0 +1,130 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+use insta::assert_snapshot;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip format` command with options shared across scenarios.
+fn format_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("format")
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Format a requirements file in place, then verify that it's left unchanged on a second pass.
+#[test]
+fn format_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # Web
+        Werkzeug==3.0.1 --hash=sha256:bbb
+        --index-url https://pypi.org/simple
+        Flask_SQLAlchemy==3.1.1  # pinned
+        werkzeug==3.0.1 \
+          --hash=sha256:aaa
+    "})?;
+
+    uv_snapshot!(context.filters(), format_command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Reformatted: requirements.txt
+    1 file reformatted, 0 files left unchanged
+    "###
+    );
+
+    let formatted = fs_err::read_to_string(&requirements_txt)?;
+    assert_snapshot!(formatted, @r###"
+    # Web
+    --index-url https://pypi.org/simple
+
+    flask-sqlalchemy==3.1.1  # pinned
+    werkzeug==3.0.1 \
+        --hash=sha256:aaa \
+        --hash=sha256:bbb
+    "###);
+
+    uv_snapshot!(context.filters(), format_command(&context)
+        .arg("requirements.txt")
+        .arg("--check"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    1 file already formatted
+    "###
+    );
+
+    Ok(())
+}
+
+/// With `--check`, report unformatted files without modifying them.
+#[test]
+fn format_check() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("idna\nanyio\n")?;
+
+    uv_snapshot!(context.filters(), format_command(&context)
+        .arg("requirements.txt")
+        .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Would reformat: requirements.txt
+    1 file would be reformatted, 0 files already formatted
+    "###
+    );
+
+    assert_eq!(fs_err::read_to_string(&requirements_txt)?, "idna\nanyio\n");
+
+    Ok(())
+}
+
+/// Refuse to format a file with an invalid requirement.
+#[test]
+fn format_invalid_requirement() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio\nflask==1.0.x\n")?;
+
+    uv_snapshot!(context.filters(), format_command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to format: `requirements.txt`
+      Caused by: Couldn't parse requirement at line 2
+      Caused by: after parsing '1.0', found '.x', which is not part of a valid version
+    flask==1.0.x
+         ^^^^^^^
+    "###
+    );
+
+    Ok(())
+}
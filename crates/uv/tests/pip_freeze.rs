@@ -160,6 +160,39 @@ fn freeze_url() -> Result<()> {
     Ok(())
 }
 
+/// List packages with the hashes recorded at installation time.
+#[test]
+fn freeze_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0 --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f\niniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374")?;
+
+    // Run `pip sync`.
+    sync_command(&context)
+        .arg(requirements_txt.path())
+        .arg("--require-hashes")
+        .assert()
+        .success();
+
+    // Run `pip freeze`.
+    uv_snapshot!(command(&context)
+        .arg("--hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    anyio==4.0.0 \
+        --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl \
+        --hash=sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn freeze_with_editable() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -0,0 +1,79 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip hash` command with options shared across scenarios.
+fn hash_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("hash")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Hash a local file, with the default and a non-default algorithm.
+#[test]
+fn hash_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let file = context.temp_dir.child("file.txt");
+    file.write_str("hello\n")?;
+
+    uv_snapshot!(context.filters(), hash_command(&context)
+        .arg("file.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    file.txt:
+    --hash=sha256:5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.filters(), hash_command(&context)
+        .arg("file.txt")
+        .arg("--algorithm")
+        .arg("sha512"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    file.txt:
+    --hash=sha512:e7c22b994c59d9cf2b48e549b1e24666636045930d3da7c1acb299d1c3b7f931f94aae41edda2c2b207a36e10f8bcb8d45223e54878f5b316e7ce3b6bc019629
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Requirements must be pinned to an exact version.
+#[test]
+fn hash_unpinned_requirement() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), hash_command(&context)
+        .arg("flask>=3"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requirements must be pinned to an exact version (e.g., `flask==1.0.0`), but received: `flask>=3`
+    "###
+    );
+}
@@ -1,6 +1,8 @@
 #![cfg(all(feature = "python", feature = "pypi"))]
 
+use std::io::{BufRead, Write};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
@@ -8,11 +10,12 @@ use assert_fs::prelude::*;
 use base64::{prelude::BASE64_STANDARD as base64, Engine};
 use indoc::indoc;
 use itertools::Itertools;
+use sha2::Digest;
 
 use common::{uv_snapshot, TestContext};
 use uv_fs::Simplified;
 
-use crate::common::{get_bin, venv_bin_path, BUILD_VENDOR_LINKS_URL};
+use crate::common::{create_venv, get_bin, venv_bin_path, BUILD_VENDOR_LINKS_URL};
 
 mod common;
 
@@ -130,6 +133,52 @@ fn missing_pyproject_toml() {
     );
 }
 
+/// Suggest quoting a requirement that was split by the shell.
+#[test]
+fn invalid_requirement_split_specifier() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("flask")
+        .arg(">=")
+        .arg("2"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse: `>=`
+      Caused by: Expected package name starting with an alphanumeric character, found '>'
+    >=
+    ^
+
+    hint: Version specifiers must follow a package name. If the shell split the requirement at a space, wrap the entire requirement in quotes (e.g., `"flask >= 2"`)
+    "###
+    );
+}
+
+/// Suggest quoting the values in a requirement's markers.
+#[test]
+fn invalid_requirement_unquoted_marker() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("flask ; python_version < 3.12"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to parse: `flask ; python_version < 3.12`
+      Caused by: Expected a valid marker name, found '3.12'
+    flask ; python_version < 3.12
+                             ^^^^
+
+    hint: Marker values must be quoted. Wrap the requirement in double quotes, and the marker values in single quotes (e.g., `"flask ; python_version < '3.12'"`)
+    "###
+    );
+}
+
 #[test]
 fn invalid_pyproject_toml_syntax() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -251,10 +300,11 @@ dependencies = ["flask==1.0.x"]
         .arg("-r")
         .arg("requirements.txt"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
+    warning: Build logs for `project @ file://[TEMP_DIR]/path_dep` were saved to: [CACHE_DIR]/build-logs-v0/[BUILD]
     error: Failed to download and build: `project @ file://[TEMP_DIR]/path_dep`
       Caused by: Failed to build: `project @ file://[TEMP_DIR]/path_dep`
       Caused by: Build backend failed to determine extra requires with `build_wheel()` with exit code: 1
@@ -341,7 +391,7 @@ fn no_solution() {
         .arg("WerkZeug<1.0.0")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -463,7 +513,7 @@ werkzeug==3.0.1
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -895,6 +945,126 @@ fn install_editable() {
     );
 }
 
+/// Reuse a built editable when installing the same project into another virtual environment.
+#[test]
+fn install_editable_cached() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install the editable package.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("-e")
+        .arg(context.workspace_root.join("scripts/packages/poetry_editable")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 4 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + anyio==4.3.0
+     + idna==3.6
+     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+     + sniffio==1.3.1
+    "###
+    );
+
+    // The built editable should be stored in the cache.
+    context
+        .cache_dir
+        .child("editables-v1")
+        .assert(predicates::path::is_dir());
+
+    // Install it into a new virtual environment, reusing the cached wheel.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("-e")
+        .arg(context.workspace_root.join("scripts/packages/poetry_editable"))
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + anyio==4.3.0
+     + idna==3.6
+     + poetry-editable==0.1.0 (from file://[WORKSPACE]/scripts/packages/poetry_editable)
+     + sniffio==1.3.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Rebuild a cached editable when the contents of the source tree change, even if the
+/// modification time of the changed file is preserved.
+#[test]
+fn install_editable_cached_content_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package.
+    let editable_dir = context.temp_dir.child("editable");
+    editable_dir.create_dir_all()?;
+    editable_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+requires-python = ">=3.8"
+"#,
+    )?;
+    let init_py = editable_dir.child("example").child("__init__.py");
+    init_py.write_str("VALUE = 1\n")?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--editable")
+        .arg(editable_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    // Modify a source file, restoring its modification time.
+    let modified = filetime::FileTime::from_last_modification_time(&fs_err::metadata(&init_py)?);
+    init_py.write_str("VALUE = 2\n")?;
+    filetime::set_file_mtime(init_py.path(), modified)?;
+
+    // Installing into a new virtual environment should rebuild the editable.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--editable")
+        .arg(editable_dir.path())
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn install_editable_and_registry() {
     let context = TestContext::new("3.12");
@@ -1043,7 +1213,7 @@ fn install_editable_incompatible_constraint_version() -> Result<()> {
         .arg("--constraint")
         .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1134,7 +1304,7 @@ fn install_no_index() {
         .arg("Flask")
         .arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1158,7 +1328,7 @@ fn install_no_index_version() {
         .arg("Flask==3.0.0")
         .arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1680,6 +1850,87 @@ fn reinstall_no_binary() {
     context.assert_command("import anyio").success();
 }
 
+/// Build from the source distribution with `--prefer-source`, even though a compatible wheel is
+/// available.
+#[test]
+fn install_prefer_source() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--prefer-source")
+        .arg("iniconfig")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The wheel should've been built from the source distribution, rather than downloaded.
+    assert!(context
+        .cache_dir
+        .child("built-wheels-v3")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    context.assert_command("import iniconfig").success();
+}
+
+/// Build from the source distribution with `--prefer-source`, even if a wheel for the same
+/// version was already downloaded into the cache.
+#[test]
+fn install_prefer_source_warm_cache() {
+    let context = TestContext::new("3.12");
+
+    // Download the wheel into the cache, then remove it from the environment.
+    context
+        .install()
+        .arg("iniconfig==2.0.0")
+        .arg("--strict")
+        .assert()
+        .success();
+    uninstall_command(&context)
+        .arg("iniconfig")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--prefer-source")
+        .arg("iniconfig")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The wheel should've been built from the source distribution, rather than linked from the
+    // downloaded wheel.
+    assert!(context
+        .cache_dir
+        .child("built-wheels-v3")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    context.assert_command("import iniconfig").success();
+}
+
 /// Respect `--only-binary` flags in `requirements.txt`
 #[test]
 fn only_binary_requirements_txt() {
@@ -1699,7 +1950,7 @@ fn only_binary_requirements_txt() {
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1795,6 +2046,83 @@ fn only_binary_editable_setup_py() {
     );
 }
 
+/// `--allow-build` disallows building any package outside of the allow-list.
+#[test]
+fn allow_build() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("django_allauth==0.51.0")
+        .arg("--allow-build")
+        .arg("anyio")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because django-allauth==0.51.0 has no usable wheels and building from source is disabled and you require django-allauth==0.51.0, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+}
+
+/// `--allow-build` does not apply to editable requirements.
+#[test]
+fn allow_build_editable() {
+    let context = TestContext::new("3.12");
+
+    // Install the editable package.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("--allow-build")
+        .arg("iniconfig")
+        .arg("-e")
+        .arg(context.workspace_root.join("scripts/packages/anyio_local")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.3.0+foo (from file://[WORKSPACE]/scripts/packages/anyio_local)
+    "###
+    );
+}
+
+/// Unnamed requirements can't be matched against the `--allow-build` list, so building them is
+/// disabled.
+#[test]
+fn allow_build_unnamed() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a source tree without static metadata, such that its name is only known after a
+    // build.
+    let package = context.temp_dir.child("package");
+    package.create_dir_all()?;
+    package.child("setup.py").write_str(indoc! {r#"
+        from setuptools import setup
+
+        setup(name="package", version="0.1.0")
+        "#
+    })?;
+    let url = url::Url::from_file_path(package.path()).unwrap();
+
+    context
+        .install()
+        .arg(url.as_str())
+        .arg("--allow-build")
+        .arg("package")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Building source distributions is disabled",
+        ));
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment, and ensuring that the executable permissions
 /// are retained.
 ///
@@ -1931,6 +2259,19 @@ fn no_deps() {
     );
 
     context.assert_command("import flask").failure();
+
+    // Re-installing with `--no-deps` shouldn't require the missing dependencies.
+    uv_snapshot!(context.install()
+        .arg("Flask")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
 }
 
 /// Install an editable package from the command line into a virtual environment, ignoring its
@@ -2057,31 +2398,439 @@ fn install_upgrade() {
     );
 }
 
-/// Install a package from a `requirements.txt` file, with a `constraints.txt` file.
+/// Upgrade a package with `--upgrade-strategy only-if-needed`, which should retain the installed
+/// versions of its dependencies, unlike the default `eager` strategy.
 #[test]
-fn install_constraints_txt() -> Result<()> {
+fn install_upgrade_strategy() {
     let context = TestContext::new("3.12");
-    let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str("anyio==3.7.0")?;
 
-    let constraints_txt = context.temp_dir.child("constraints.txt");
-    constraints_txt.write_str("idna<3.4")?;
+    // Install an old version of anyio, along with an old version of one of its dependencies.
+    context
+        .install()
+        .arg("anyio==3.6.2")
+        .arg("idna==3.4")
+        .assert()
+        .success();
 
+    // Upgrade anyio, but only upgrade its dependencies if needed.
     uv_snapshot!(context.install()
-            .arg("-r")
-            .arg("requirements.txt")
-            .arg("--constraint")
-            .arg("constraints.txt"), @r###"
+        .arg("anyio")
+        .arg("--upgrade")
+        .arg("--upgrade-strategy")
+        .arg("only-if-needed"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
-    Downloaded 3 packages in [TIME]
-    Installed 3 packages in [TIME]
-     + anyio==3.7.0
-     + idna==3.3
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - anyio==3.6.2
+     + anyio==4.3.0
+    "###
+    );
+
+    // Upgrade anyio eagerly, which should upgrade its dependencies too.
+    uv_snapshot!(context.install()
+        .arg("anyio")
+        .arg("--upgrade")
+        .arg("--upgrade-strategy")
+        .arg("eager"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - idna==3.4
+     + idna==3.6
+    "###
+    );
+}
+
+/// Run the configured hooks over the installation lifecycle.
+#[test]
+#[cfg(unix)]
+fn install_hooks() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // A failing pre-install hook should abort the installation.
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        pre-install-hook = "exit 1"
+    "#})?;
+
+    // Without `--allow-hooks`, the configured hooks should be ignored.
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Ignoring the install hooks defined in the configuration file. Pass `--allow-hooks` (or set `UV_ALLOW_HOOKS=1`) to run them.
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + iniconfig==2.0.0
+    "###
+    );
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--allow-hooks"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error: The pre-install hook `exit 1` failed (exit status: 1)
+    "###
+    );
+
+    context.assert_command("import iniconfig").failure();
+
+    // A post-install hook should receive the installation plan on `stdin`.
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        post-install-hook = "cat > plan.json"
+    "#})?;
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--allow-hooks"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&fs_err::read_to_string(context.temp_dir.join("plan.json"))?)?;
+    assert_eq!(plan["event"], "post-install");
+    assert_eq!(plan["install"][0]["name"], "iniconfig");
+    assert_eq!(plan["install"][0]["version"], "2.0.0");
+    assert_eq!(plan["uninstall"], serde_json::json!([]));
+
+    Ok(())
+}
+
+/// A failing post-install hook should roll back the environment to its previous state.
+#[test]
+#[cfg(unix)]
+fn install_hooks_rollback() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.install().arg("iniconfig==1.1.1").assert().success();
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        post-install-hook = "exit 1"
+    "#})?;
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--allow-hooks"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Rolled back the environment to its previous state
+    error: The post-install hook `exit 1` failed (exit status: 1)
+    "###
+    );
+
+    context
+        .assert_command(
+            "import importlib.metadata; assert importlib.metadata.version('iniconfig') == '1.1.1'",
+        )
+        .success();
+
+    Ok(())
+}
+
+/// A hook's output should be forwarded to `stderr`, rather than interleaved with uv's `stdout`.
+#[test]
+#[cfg(unix)]
+fn install_hooks_output() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        pre-install-hook = "echo from-hook"
+    "#})?;
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--allow-hooks"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    from-hook
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// The hook plan should explain why any distributions are re-installed.
+#[test]
+#[cfg(unix)]
+fn install_hooks_reinstall_reason() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.install().arg("iniconfig==2.0.0").assert().success();
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        post-uninstall-hook = "cat > plan.json"
+    "#})?;
+
+    // Changing the version should report a version mismatch.
+    context
+        .install()
+        .arg("iniconfig==1.1.1")
+        .arg("--allow-hooks")
+        .assert()
+        .success();
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&fs_err::read_to_string(context.temp_dir.join("plan.json"))?)?;
+    assert_eq!(plan["uninstall"][0]["name"], "iniconfig");
+    assert_eq!(plan["uninstall"][0]["version"], "2.0.0");
+    assert_eq!(plan["uninstall"][0]["reason"], "version-mismatch");
+
+    // Requesting a re-installation should report as much.
+    context
+        .install()
+        .arg("iniconfig==1.1.1")
+        .arg("--reinstall")
+        .arg("--allow-hooks")
+        .assert()
+        .success();
+
+    let plan: serde_json::Value =
+        serde_json::from_str(&fs_err::read_to_string(context.temp_dir.join("plan.json"))?)?;
+    assert_eq!(plan["uninstall"][0]["name"], "iniconfig");
+    assert_eq!(plan["uninstall"][0]["reason"], "requested");
+
+    Ok(())
+}
+
+/// Build a package with an external command, rebuilding whenever the command changes.
+#[test]
+#[cfg(unix)]
+fn build_command() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("simple_launcher");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "simple-launcher"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+    "#})?;
+
+    // Each command copies a pre-built wheel into the output directory, and records that it ran.
+    let wheel = context
+        .workspace_root
+        .join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl");
+    for name in ["a", "b"] {
+        context
+            .temp_dir
+            .child(format!("build-{name}.sh"))
+            .write_str(&indoc::formatdoc! {r#"
+                cp {wheel} "$2"
+                echo {name} >> {log}
+                echo "$2/simple_launcher-0.1.0-py3-none-any.whl"
+            "#,
+                wheel = wheel.display(),
+                log = context.temp_dir.join("builds.log").display(),
+            })?;
+    }
+    let command = |name: &str| {
+        format!(
+            "simple-launcher=sh {}",
+            context.temp_dir.join(format!("build-{name}.sh")).display()
+        )
+    };
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg(project.path())
+        .arg("--build-command")
+        .arg(command("a")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + simple-launcher==0.1.0 (from file://[TEMP_DIR]/simple_launcher)
+    "###
+    );
+
+    // Re-installing with the same command should reuse the built wheel.
+    uninstall_command(&context)
+        .arg("simple-launcher")
+        .assert()
+        .success();
+    context
+        .install()
+        .arg(project.path())
+        .arg("--build-command")
+        .arg(command("a"))
+        .assert()
+        .success();
+    assert_eq!(
+        fs_err::read_to_string(context.temp_dir.join("builds.log"))?,
+        "a\n"
+    );
+
+    // Re-installing with a different command should rebuild the wheel.
+    uninstall_command(&context)
+        .arg("simple-launcher")
+        .assert()
+        .success();
+    context
+        .install()
+        .arg(project.path())
+        .arg("--build-command")
+        .arg(command("b"))
+        .assert()
+        .success();
+    assert_eq!(
+        fs_err::read_to_string(context.temp_dir.join("builds.log"))?,
+        "a\nb\n"
+    );
+
+    Ok(())
+}
+
+/// Build commands defined in the configuration file should be ignored without `--allow-hooks`.
+#[test]
+fn build_command_requires_allow_hooks() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.uv.pip]
+        build-commands = { iniconfig = "exit 1" }
+    "#})?;
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Ignoring the build commands defined in the configuration file. Pass `--allow-hooks` (or set `UV_ALLOW_HOOKS=1`) to run them.
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install entrypoints as symlinks into the `.dist-info` directory, without rewriting shebangs.
+#[test]
+#[cfg(unix)]
+fn install_scripts_symlink() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("flask==3.0.2")
+        .arg("--no-deps")
+        .arg("--scripts")
+        .arg("symlink")
+        .arg("--no-rewrite-shebangs"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + flask==3.0.2
+    "###
+    );
+
+    // The entrypoint should be a symlink to the script in the `.dist-info` directory.
+    let script = venv_bin_path(&context.venv).join("flask");
+    let target = fs_err::read_link(&script)?;
+    assert!(target.ends_with("flask-3.0.2.dist-info/scripts/flask"));
+
+    // The script should retain the `#!python` placeholder.
+    let contents = fs_err::read_to_string(&script)?;
+    assert!(contents.starts_with("#!python\n"));
+
+    // Uninstalling should remove the symlink.
+    uninstall_command(&context).arg("flask").assert().success();
+    assert!(fs_err::symlink_metadata(&script).is_err());
+
+    Ok(())
+}
+
+/// Install a package from a `requirements.txt` file, with a `constraints.txt` file.
+#[test]
+fn install_constraints_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==3.7.0")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("idna<3.4")?;
+
+    uv_snapshot!(context.install()
+            .arg("-r")
+            .arg("requirements.txt")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.3
      + sniffio==1.3.1
     "###
     );
@@ -2377,20 +3126,311 @@ fn launcher_with_symlink() -> Result<()> {
         context.temp_dir.join("simple_launcher"),
     )?;
 
-    // Only support windows or linux
-    #[cfg(not(any(windows, unix)))]
-    return Ok(());
+    // Only support windows or linux
+    #[cfg(not(any(windows, unix)))]
+    return Ok(());
+
+    uv_snapshot!(Command::new(
+        context.temp_dir.join("simple_launcher")
+    ), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hi from the simple launcher!
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
+/// Refuse to install a package whose script is owned by a different installed package, unless
+/// `--force` is provided.
+#[test]
+fn script_conflict_installed() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let wheel = context
+        .workspace_root
+        .join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl");
+
+    // Create an installed package that provides a `simple_launcher` script.
+    let dist_info = context
+        .site_packages()
+        .join("other_launcher-1.0.0.dist-info");
+    fs_err::create_dir_all(&dist_info)?;
+    fs_err::write(
+        dist_info.join("METADATA"),
+        "Metadata-Version: 2.1\nName: other-launcher\nVersion: 1.0.0\n",
+    )?;
+    fs_err::write(
+        dist_info.join("entry_points.txt"),
+        "[console_scripts]\nsimple_launcher = other_launcher:main\n",
+    )?;
+    fs_err::write(dist_info.join("RECORD"), "")?;
+
+    let filters = [
+        (r"(\d+m )?(\d+\.)?\d+(ms|s)", "[TIME]"),
+        (
+            r"simple-launcher==0\.1\.0 \(from .+\.whl\)",
+            "simple_launcher.whl",
+        ),
+    ];
+
+    uv_snapshot!(filters, context.install().arg(format!("simple_launcher@{}", wheel.display())), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error: Multiple packages provide the same scripts, which would overwrite each other:
+      - `simple_launcher` is provided by `other-launcher` and `simple-launcher`
+
+    Use `--force` to install anyway.
+    "###
+    );
+
+    // Nothing was installed.
+    assert!(!venv_bin_path(&context.venv)
+        .join(format!("simple_launcher{}", std::env::consts::EXE_SUFFIX))
+        .exists());
+
+    context
+        .install()
+        .arg(format!("simple_launcher@{}", wheel.display()))
+        .arg("--force")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "warning: Script `simple_launcher` is provided by `other-launcher` and `simple-launcher`; it will be overwritten",
+        ));
+
+    Ok(())
+}
+
+/// Reinstalling a package doesn't conflict with the scripts it already provides.
+#[test]
+fn script_conflict_reinstall() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let wheel = context
+        .workspace_root
+        .join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl");
+
+    context
+        .install()
+        .arg(format!("simple_launcher@{}", wheel.display()))
+        .assert()
+        .success();
+
+    let output = context
+        .install()
+        .arg(format!("simple_launcher@{}", wheel.display()))
+        .arg("--reinstall")
+        .assert()
+        .success();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr);
+    assert!(!stderr.contains("provided by"), "{stderr}");
+
+    Ok(())
+}
+
+/// Install into several environments at once. If installing into any environment fails, the
+/// environments that were already modified are rolled back.
+#[test]
+fn install_multiple_environments_rollback() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("../.."))?;
+
+    let parent = context.temp_dir.child("service-a");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    // In the second environment, create an installed package that provides a `simple_launcher`
+    // script.
+    let dist_info = context
+        .site_packages()
+        .join("other_launcher-1.0.0.dist-info");
+    fs_err::create_dir_all(&dist_info)?;
+    fs_err::write(
+        dist_info.join("METADATA"),
+        "Metadata-Version: 2.1\nName: other-launcher\nVersion: 1.0.0\n",
+    )?;
+    fs_err::write(
+        dist_info.join("entry_points.txt"),
+        "[console_scripts]\nsimple_launcher = other_launcher:main\n",
+    )?;
+    fs_err::write(dist_info.join("RECORD"), "")?;
+
+    let filters = [
+        (r"(\d+m )?(\d+\.)?\d+(ms|s)", "[TIME]"),
+        (
+            r"simple-launcher==0\.1\.0 \(from .+\.whl\)",
+            "simple_launcher.whl",
+        ),
+    ];
+
+    uv_snapshot!(
+        filters,
+        context.install()
+        .arg(format!("simple_launcher@{}", project_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl").display()))
+        .arg("--environment")
+        .arg("service-a/.venv")
+        .arg("--environment")
+        .arg(".venv"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installing into environment at: service-a/.venv
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + simple_launcher.whl
+    Installing into environment at: .venv
+    Rolled back the environment at service-a/.venv to its previous state
+    error: Multiple packages provide the same scripts, which would overwrite each other:
+      - `simple_launcher` is provided by `other-launcher` and `simple-launcher`
+
+    Use `--force` to install anyway.
+    "###
+    );
+
+    // The package was removed from the first environment again.
+    assert!(!venv_bin_path(&venv)
+        .join(format!("simple_launcher{}", std::env::consts::EXE_SUFFIX))
+        .exists());
+    Command::new(venv_bin_path(&venv).join(format!("python{}", std::env::consts::EXE_SUFFIX)))
+        .arg("-c")
+        .arg("import simple_launcher")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+/// Report files that are provided by multiple packages with different contents, per the
+/// `--file-collisions` policy.
+#[test]
+fn file_collision() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("../.."))?;
+
+    // Create an installed package that provides a different `simple_launcher/__init__.py`.
+    let dist_info = context
+        .site_packages()
+        .join("other_launcher-1.0.0.dist-info");
+    fs_err::create_dir_all(&dist_info)?;
+    fs_err::write(
+        dist_info.join("METADATA"),
+        "Metadata-Version: 2.1\nName: other-launcher\nVersion: 1.0.0\n",
+    )?;
+    fs_err::write(
+        dist_info.join("RECORD"),
+        "simple_launcher/__init__.py,sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU,0\nother_launcher-1.0.0.dist-info/RECORD,,\n",
+    )?;
+
+    let filters = [
+        (r"(\d+m )?(\d+\.)?\d+(ms|s)", "[TIME]"),
+        (
+            r"simple-launcher==0\.1\.0 \(from .+\.whl\)",
+            "simple_launcher.whl",
+        ),
+    ];
+
+    uv_snapshot!(
+        filters,
+        context.install()
+        .arg(format!("simple_launcher@{}", project_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl").display()))
+        .arg("--file-collisions")
+        .arg("error"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error: Multiple packages provide the same files with different contents:
+      - `simple_launcher/__init__.py` is provided by `other-launcher` and `simple-launcher`
+
+    Use `--file-collisions warn` to install anyway.
+    "###
+    );
+
+    uv_snapshot!(
+        filters,
+        context.install()
+        .arg(format!("simple_launcher@{}", project_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl").display())), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: File `simple_launcher/__init__.py` is provided by `other-launcher` and `simple-launcher`, with different contents; the last package to be installed will overwrite it
+    Installed 1 package in [TIME]
+     + simple_launcher.whl
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a wheel whose platform tags are incompatible with the current platform, using
+/// `--ignore-platform-compatibility`.
+#[test]
+fn ignore_platform_compatibility() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("../.."))?;
+
+    // Copy a pure-Python wheel under a filename that targets another platform.
+    let wheel = context
+        .temp_dir
+        .child("simple_launcher-0.1.0-py3-none-macosx_10_9_ppc.whl");
+    fs_err::copy(
+        project_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl"),
+        &wheel,
+    )?;
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(
+            r"simple-launcher==0\.1\.0 \(from .+\.whl\)",
+            "simple_launcher.whl",
+        )])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.install()
+        .arg(format!("simple_launcher@{}", wheel.path().display())), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to determine installation plan
+      Caused by: A path dependency is incompatible with the current platform: [TEMP_DIR]/simple_launcher-0.1.0-py3-none-macosx_10_9_ppc.whl
+    "###
+    );
 
-    uv_snapshot!(Command::new(
-        context.temp_dir.join("simple_launcher")
-    ), @r###"
+    uv_snapshot!(filters, context.install()
+        .arg(format!("simple_launcher@{}", wheel.path().display()))
+        .arg("--ignore-platform-compatibility"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
-    Hi from the simple launcher!
 
     ----- stderr -----
-    "###);
+    Resolved 1 package in [TIME]
+    warning: Installing `simple_launcher-0.1.0-py3-none-macosx_10_9_ppc.whl` despite it being incompatible with the current platform (`--ignore-platform-compatibility`); the package may fail to import or behave unexpectedly
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + simple_launcher.whl
+    "###
+    );
 
     Ok(())
 }
@@ -2808,6 +3848,137 @@ requires-python = ">=3.8"
     Ok(())
 }
 
+/// Rebuild a local directory dependency when any of its sources change, not only its build
+/// entrypoints.
+#[test]
+fn invalidate_path_on_source_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package.
+    let project_dir = context.temp_dir.child("project");
+    project_dir.create_dir_all()?;
+    project_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = ">=3.8"
+"#,
+    )?;
+    let init_py = project_dir.child("example").child("__init__.py");
+    init_py.write_str("VALUE = 1\n")?;
+
+    context
+        .install()
+        .arg("example @ .")
+        .current_dir(project_dir.path())
+        .assert()
+        .success();
+
+    // Re-installing should be a no-op.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("example @ .")
+        .current_dir(project_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // Modify a source file, without modifying the `pyproject.toml`.
+    init_py.write_str("VALUE = 2\n")?;
+
+    // Re-installing should rebuild the package.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("example @ .")
+        .current_dir(project_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from file://[TEMP_DIR]/project)
+     + example==0.0.0 (from file://[TEMP_DIR]/project)
+    "###
+    );
+
+    context
+        .assert_command("import example; assert example.VALUE == 2")
+        .success();
+
+    Ok(())
+}
+
+/// Rebuild a local directory dependency when a source file changes within a package directory
+/// whose name matches a build artifact (e.g., `build`), which is only ignored at the root.
+#[test]
+fn invalidate_path_on_nested_build_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package with a `build` subpackage.
+    let project_dir = context.temp_dir.child("project");
+    project_dir.create_dir_all()?;
+    project_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = ">=3.8"
+"#,
+    )?;
+    project_dir.child("example").child("__init__.py").touch()?;
+    let build_py = project_dir
+        .child("example")
+        .child("build")
+        .child("__init__.py");
+    build_py.write_str("VALUE = 1\n")?;
+
+    context
+        .install()
+        .arg("example @ .")
+        .current_dir(project_dir.path())
+        .assert()
+        .success();
+
+    // Modify a source file in the `build` subpackage.
+    build_py.write_str("VALUE = 2\n")?;
+
+    // Re-installing should rebuild the package.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("example @ .")
+        .current_dir(project_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from file://[TEMP_DIR]/project)
+     + example==0.0.0 (from file://[TEMP_DIR]/project)
+    "###
+    );
+
+    context
+        .assert_command("import example.build; assert example.build.VALUE == 2")
+        .success();
+
+    Ok(())
+}
+
 /// Ignore a URL dependency with a non-matching marker.
 #[test]
 fn editable_url_with_marker() -> Result<()> {
@@ -2902,10 +4073,11 @@ fn no_build_isolation() -> Result<()> {
         .arg("requirements.in")
         .arg("--no-build-isolation"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
+    warning: Build logs for `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz` were saved to: [CACHE_DIR]/build-logs-v0/[BUILD]
     error: Failed to download and build: `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz`
       Caused by: Failed to build: `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz`
       Caused by: Build backend failed to determine metadata through `prepare_metadata_for_build_wheel` with exit status: 1
@@ -2973,10 +4145,11 @@ fn respect_no_build_isolation_env_var() -> Result<()> {
         .arg("requirements.in")
         .env("UV_NO_BUILD_ISOLATION", "yes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
+    warning: Build logs for `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz` were saved to: [CACHE_DIR]/build-logs-v0/[BUILD]
     error: Failed to download and build: `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz`
       Caused by: Failed to build: `anyio @ https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz`
       Caused by: Build backend failed to determine metadata through `prepare_metadata_for_build_wheel` with exit status: 1
@@ -3028,6 +4201,124 @@ fn respect_no_build_isolation_env_var() -> Result<()> {
     Ok(())
 }
 
+/// Stage builds in the directory provided via `--build-dir`, including the build backend's
+/// temporary files.
+#[test]
+fn build_dir() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a project with an in-tree build backend that records its temporary directory.
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [build-system]
+        requires = []
+        build-backend = "backend"
+        backend-path = ["."]
+        "#
+    })?;
+    project.child("backend.py").write_str(indoc! {r#"
+        import tempfile
+
+        def prepare_metadata_for_build_wheel(metadata_directory, config_settings=None):
+            with open("tempdir.txt", "w") as fp:
+                fp.write(tempfile.gettempdir())
+            raise RuntimeError("Failed to prepare metadata")
+        "#
+    })?;
+
+    let build_dir = context.temp_dir.child("build");
+    context
+        .install()
+        .arg("./project")
+        .arg("--no-build-isolation")
+        .env("UV_BUILD_DIR", build_dir.path())
+        .assert()
+        .failure();
+
+    let tempdir = fs_err::read_to_string(project.child("tempdir.txt").path())?;
+    let build_dir = fs_err::canonicalize(build_dir.path())?;
+    assert!(
+        std::path::Path::new(&tempdir).starts_with(&build_dir),
+        "Expected `{tempdir}` to be in `{}`",
+        build_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Retain the output of a failed build in the cache, and list it with `uv cache builds list`.
+#[test]
+fn retain_build_logs() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a project with an in-tree build backend that fails to prepare metadata. (The project
+    // omits static metadata, so that the build is required to determine the package name.)
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [build-system]
+        requires = []
+        build-backend = "backend"
+        backend-path = ["."]
+        "#
+    })?;
+    project.child("backend.py").write_str(indoc! {r#"
+        def prepare_metadata_for_build_wheel(metadata_directory, config_settings=None):
+            print("Preparing metadata")
+            raise RuntimeError("Failed to prepare metadata")
+        "#
+    })?;
+
+    context
+        .install()
+        .arg("./project")
+        .arg("--no-build-isolation")
+        .assert()
+        .failure();
+
+    // The build output should be retained in the cache.
+    let logs = fs_err::read_dir(context.cache_dir.child("build-logs-v0").path())?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let [log] = logs.as_slice() else {
+        panic!("Expected a single build log directory, found: {logs:?}");
+    };
+    assert_eq!(
+        fs_err::read_to_string(log.join("stdout.log"))?,
+        "Preparing metadata"
+    );
+    assert!(fs_err::read_to_string(log.join("stderr.log"))?
+        .contains("RuntimeError: Failed to prepare metadata"));
+    assert!(log.join("packages.txt").is_file());
+
+    let filters = [
+        (r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\+00:00", "[DATE]"),
+        (r"exit code: 1", "exit status: 1"),
+    ]
+    .into_iter()
+    .chain(context.filters())
+    .collect::<Vec<_>>();
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("cache")
+        .arg("builds")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [CACHE_DIR]/build-logs-v0/[BUILD]
+      Package: file://[TEMP_DIR]/project
+      Failed: [DATE]
+      Error: Build backend failed to determine metadata through `prepare_metadata_for_build_wheel` (exit status: 1)
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
 /// This tests that `uv` can read UTF-16LE encoded requirements.txt files.
 ///
 /// Ref: <https://github.com/astral-sh/uv/issues/2276>
@@ -3132,6 +4423,150 @@ fn dry_run_install() -> std::result::Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Write a `pip install --report`-compatible report of the distributions to be installed.
+#[test]
+fn dry_run_install_report() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--dry-run")
+        .arg("--report")
+        .arg("report.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + iniconfig==2.0.0
+    "###
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("report.json"),
+    )?)?;
+    assert_eq!(report["version"], "1");
+    assert_eq!(report["environment"]["python_version"], "3.12");
+
+    let install = report["install"].as_array().unwrap();
+    assert_eq!(install.len(), 1);
+    assert_eq!(install[0]["metadata"]["name"], "iniconfig");
+    assert_eq!(install[0]["metadata"]["version"], "2.0.0");
+    assert_eq!(install[0]["requested"], true);
+    assert_eq!(install[0]["is_direct"], false);
+    assert!(install[0]["download_info"]["url"]
+        .as_str()
+        .unwrap()
+        .ends_with("/iniconfig-2.0.0-py3-none-any.whl"));
+    assert!(install[0]["download_info"]["archive_info"]["hashes"]["sha256"].is_string());
+
+    Ok(())
+}
+
+/// Write the report to stdout with `--report -`, even with `--quiet`, and write an empty report if
+/// the requirements are already satisfied.
+#[test]
+fn install_report_stdout() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = context
+        .install()
+        .arg("iniconfig==2.0.0")
+        .arg("--dry-run")
+        .arg("--quiet")
+        .arg("--report")
+        .arg("-")
+        .output()?;
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let install = report["install"].as_array().unwrap();
+    assert_eq!(install.len(), 1);
+    assert_eq!(install[0]["metadata"]["name"], "iniconfig");
+
+    // Install the package, such that the environment satisfies the requirements.
+    context.install().arg("iniconfig==2.0.0").assert().success();
+
+    let output = context
+        .install()
+        .arg("iniconfig==2.0.0")
+        .arg("--quiet")
+        .arg("--report")
+        .arg("-")
+        .output()?;
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["version"], "1");
+    assert_eq!(report["environment"]["python_version"], "3.12");
+    assert_eq!(report["install"], serde_json::json!([]));
+
+    Ok(())
+}
+
+/// Report the dependencies that are skipped since their markers don't match the environment, along
+/// with the package that requires them, whether resolving or reusing the installed packages.
+#[test]
+fn install_report_skipped_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let skipped = |output: &std::process::Output| -> Result<Vec<(String, String)>> {
+        assert!(output.status.success());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        let mut skipped = report["skipped"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                (
+                    entry["requirement"].as_str().unwrap().to_string(),
+                    entry["required_by"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        skipped.sort();
+        Ok(skipped)
+    };
+
+    // `pytest` depends on `colorama` on Windows, and on `exceptiongroup` and `tomli` before Python
+    // 3.11. Dependencies of its `testing` extra, which wasn't requested, aren't reported.
+    let output = context
+        .install()
+        .arg("pytest==8.1.1")
+        .arg("--python-platform")
+        .arg("linux")
+        .arg("--dry-run")
+        .arg("--quiet")
+        .arg("--report")
+        .arg("-")
+        .output()?;
+    assert_eq!(
+        skipped(&output)?,
+        vec![
+            ("colorama".to_string(), "pytest".to_string()),
+            ("exceptiongroup".to_string(), "pytest".to_string()),
+            ("tomli".to_string(), "pytest".to_string()),
+        ]
+    );
+
+    // Install the package, such that the environment satisfies the requirements.
+    context.install().arg("pytest==8.1.1").assert().success();
+
+    let output = context
+        .install()
+        .arg("pytest==8.1.1")
+        .arg("--quiet")
+        .arg("--report")
+        .arg("-")
+        .output()?;
+    let skipped = skipped(&output)?;
+    assert!(skipped.contains(&("exceptiongroup".to_string(), "pytest".to_string())));
+    assert!(skipped.contains(&("tomli".to_string(), "pytest".to_string())));
+
+    Ok(())
+}
+
 #[test]
 fn dry_run_install_url_dependency() -> std::result::Result<(), Box<dyn std::error::Error>> {
     let context = TestContext::new("3.12");
@@ -3303,63 +4738,138 @@ fn dry_run_install_transitive_dependency_already_installed(
     Would install 4 packages
      + anyio==4.3.0
      + httpx==0.25.1
-     + idna==3.6
-     + sniffio==1.3.1
+     + idna==3.6
+     + sniffio==1.3.1
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dry_run_install_then_upgrade() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let context = TestContext::new("3.12");
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("httpx==0.25.0")?;
+
+    // Install the package
+    uv_snapshot!(context.install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Downloaded 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + anyio==4.3.0
+     + certifi==2024.2.2
+     + h11==0.14.0
+     + httpcore==0.18.0
+     + httpx==0.25.0
+     + idna==3.6
+     + sniffio==1.3.1
+    "###
+    );
+
+    // Bump the version and install with dry run enabled
+    requirements_txt.write_str("httpx==0.25.1")?;
+    uv_snapshot!(context.install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    Would download 1 package
+    Would uninstall 1 package
+    Would install 1 package
+     - httpx==0.25.0
+     + httpx==0.25.1
     "###
     );
 
     Ok(())
 }
 
+/// `--check-only` should report whether the environment satisfies the requirements, exiting with a
+/// non-zero status (and without modifying the environment) if it doesn't.
 #[test]
-fn dry_run_install_then_upgrade() -> std::result::Result<(), Box<dyn std::error::Error>> {
+fn check_only() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_txt = context.temp_dir.child("requirements.txt");
-    requirements_txt.write_str("httpx==0.25.0")?;
+    requirements_txt.write_str("anyio==4.0.0")?;
 
-    // Install the package
+    // Install the requirements, along with an extraneous package.
+    context
+        .install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("iniconfig==2.0.0")
+        .assert()
+        .success();
+
+    // The environment satisfies the requirements.
     uv_snapshot!(context.install()
         .arg("-r")
         .arg("requirements.txt")
-        .arg("--strict"), @r###"
+        .arg("--check-only"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
-    Resolved 7 packages in [TIME]
-    Downloaded 7 packages in [TIME]
-    Installed 7 packages in [TIME]
-     + anyio==4.3.0
-     + certifi==2024.2.2
-     + h11==0.14.0
-     + httpcore==0.18.0
-     + httpx==0.25.0
-     + idna==3.6
-     + sniffio==1.3.1
+    Audited 1 package in [TIME]
     "###
     );
 
-    // Bump the version and install with dry run enabled
-    requirements_txt.write_str("httpx==0.25.1")?;
+    // With `--exact`, the extraneous package is reported.
     uv_snapshot!(context.install()
         .arg("-r")
         .arg("requirements.txt")
-        .arg("--dry-run"), @r###"
-    success: true
-    exit_code: 0
+        .arg("--exact")
+        .arg("--check-only"), @r###"
+    success: false
+    exit_code: 1
     ----- stdout -----
 
     ----- stderr -----
-    Resolved 7 packages in [TIME]
+    Resolved 3 packages in [TIME]
+    Would uninstall 1 package
+     - iniconfig==2.0.0
+    "###
+    );
+
+    // A mismatched version is reported.
+    requirements_txt.write_str("anyio==4.0.0\niniconfig==1.1.1")?;
+    uv_snapshot!(context.install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--exact")
+        .arg("--check-only"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
     Would download 1 package
     Would uninstall 1 package
     Would install 1 package
-     - httpx==0.25.0
-     + httpx==0.25.1
+     - iniconfig==2.0.0
+     + iniconfig==1.1.1
     "###
     );
 
+    // Nothing should have been modified.
+    context.assert_installed("iniconfig", "2.0.0");
+
     Ok(())
 }
 
@@ -3386,7 +4896,7 @@ requires-python = "<=3.8"
     uv_snapshot!(context.filters(), context.install()
         .arg(format!("example @ {}", editable_dir.path().display())), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3832,7 +5342,7 @@ fn reinstall_no_index() {
         .arg("--reinstall")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3950,7 +5460,7 @@ fn already_installed_dependent_editable() {
         .arg("--find-links")
         .arg(BUILD_VENDOR_LINKS_URL), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4047,7 +5557,7 @@ fn already_installed_local_path_dependent() {
         .arg("--find-links")
         .arg(BUILD_VENDOR_LINKS_URL), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4088,7 +5598,7 @@ fn already_installed_local_path_dependent() {
         .arg("--find-links")
         .arg(BUILD_VENDOR_LINKS_URL), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4160,7 +5670,7 @@ fn already_installed_local_version_of_remote_package() {
         .arg("anyio==4.2.0")
         .arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4177,7 +5687,7 @@ fn already_installed_local_version_of_remote_package() {
         .arg("anyio==4.3.0+foo")
         .arg("--reinstall"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4418,7 +5928,7 @@ fn already_installed_remote_url() {
         .arg("--no-index")
         .arg("--reinstall"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4464,7 +5974,7 @@ fn already_installed_remote_url() {
     uv_snapshot!(
         context.install().arg("uv-public-pypackage==0.2.0").arg("--no-index"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4752,6 +6262,62 @@ fn require_hashes_constraint() -> Result<()> {
     Ok(())
 }
 
+/// If a constraint includes hashes, distributions that don't match them are rejected during
+/// resolution, rather than at download time.
+#[test]
+fn require_hashes_constraint_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Include the correct hash in the requirements file.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0 --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    // Include the wrong hash in the constraint file.
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("anyio==4.0.0 --hash=sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    uv_snapshot!(context.install()
+        .arg("-r")
+        .arg(requirements_txt.path())
+        .arg("--no-deps")
+        .arg("--require-hashes")
+        .arg("-c")
+        .arg(constraints_txt.path()), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because anyio==4.0.0 has no distributions with hashes matching the constraint `anyio==4.0.0` and you require anyio==4.0.0, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    // Include the correct hash in the constraint file.
+    constraints_txt.write_str("anyio==4.0.0 --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    uv_snapshot!(context.install()
+        .arg("-r")
+        .arg(requirements_txt.path())
+        .arg("--no-deps")
+        .arg("--require-hashes")
+        .arg("-c")
+        .arg(constraints_txt.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// We allow `--require-hashes` for unnamed URL dependencies.
 #[test]
 fn require_hashes_unnamed() -> Result<()> {
@@ -5022,3 +6588,296 @@ fn tool_uv_sources_is_in_preview() -> Result<()> {
 
     Ok(())
 }
+
+/// Warn about packages that are shadowed by a newer version on another index before installing
+/// anything.
+#[test]
+fn check_dependency_confusion() {
+    let context = TestContext::new("3.12");
+
+    // The latest versions on PyPI will change over time.
+    let filters: Vec<_> = [(r"newer version \([^)]+\)", "newer version ([VERSION])")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, context.install()
+            .arg("jinja2==3.1.3")
+            .arg("--index-url")
+            .arg("https://test.pypi.org/simple/")
+            .arg("--extra-index-url")
+            .arg("https://pypi.org/simple")
+            .arg("--check-dependency-confusion"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    warning: `jinja2` was resolved to 3.1.3 from https://test.pypi.org/simple/, but a newer version ([VERSION]) is available from https://pypi.org/simple. If this is unexpected, the package may be the target of a dependency confusion attack.
+    warning: `markupsafe` was resolved to 2.1.5 from https://test.pypi.org/simple/, but a newer version ([VERSION]) is available from https://pypi.org/simple. If this is unexpected, the package may be the target of a dependency confusion attack.
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + jinja2==3.1.3
+     + markupsafe==2.1.5
+    "###
+    );
+}
+
+/// A cycle in the build dependencies of source trees is reported with the chain of packages,
+/// rather than recursing (or waiting on the cycle) indefinitely.
+#[test]
+fn build_dependency_cycle() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let a = context.temp_dir.child("a");
+    let b = context.temp_dir.child("b");
+    for (dir, name, other, other_name) in [(&a, "a", &b, "b"), (&b, "b", &a, "a")] {
+        let url = url::Url::from_file_path(other.path()).unwrap();
+        dir.child("pyproject.toml").write_str(&format!(
+            indoc! {r#"
+            [project]
+            name = "{}"
+            version = "0.1.0"
+
+            [build-system]
+            requires = ["{} @ {}"]
+            build-backend = "setuptools.build_meta"
+            "#},
+            name, other_name, url
+        ))?;
+    }
+
+    context
+        .install()
+        .arg("./a")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Detected a cycle in the build dependencies of `a`: `a` -> `b` -> `a`",
+        ));
+
+    Ok(())
+}
+
+/// Reuse a cached resolution with `--cache-resolution`, skipping the resolver when the inputs are
+/// unchanged.
+#[test]
+fn install_cache_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--cache-resolution")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The resolution should be cached.
+    let resolutions = fs_err::read_dir(context.cache_dir.child("resolutions-v0"))?.count();
+    assert_eq!(resolutions, 1);
+
+    // Installing into an identical, empty environment should reuse the cached resolution.
+    let parent = context.temp_dir.child("parent");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--cache-resolution")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // Without `--cache-resolution`, the resolver should run as usual.
+    let parent = context.temp_dir.child("other");
+    parent.create_dir_all()?;
+    let venv = create_venv(&parent, &context.cache_dir, "3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--strict")
+        .env("VIRTUAL_ENV", venv.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Don't cache resolutions that include local paths, since their contents may change.
+#[test]
+fn install_cache_resolution_local_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(
+            r"simple-launcher==0\.1\.0 \(from .+\.whl\)",
+            "simple_launcher.whl",
+        )])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.install()
+        .arg(format!("simple_launcher@{}", context.workspace_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl").display()))
+        .arg("--cache-resolution")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + simple_launcher.whl
+    "###
+    );
+
+    context
+        .cache_dir
+        .child("resolutions-v0")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Build a wheel for `example-pkg==1.0.0`, whose module exposes the given value.
+fn example_pkg_wheel(value: &str) -> Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (path, contents) in [
+        ("example_pkg/__init__.py", format!("VALUE = {value:?}\n")),
+        (
+            "example_pkg-1.0.0.dist-info/METADATA",
+            "Metadata-Version: 2.1\nName: example-pkg\nVersion: 1.0.0\n".to_string(),
+        ),
+        (
+            "example_pkg-1.0.0.dist-info/WHEEL",
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n".to_string(),
+        ),
+        (
+            "example_pkg-1.0.0.dist-info/RECORD",
+            "example_pkg/__init__.py,,\nexample_pkg-1.0.0.dist-info/METADATA,,\nexample_pkg-1.0.0.dist-info/WHEEL,,\nexample_pkg-1.0.0.dist-info/RECORD,,\n".to_string(),
+        ),
+    ] {
+        writer.start_file(path, zip::write::FileOptions::default())?;
+        writer.write_all(contents.as_bytes())?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// Serve a `--find-links` page listing a single wheel, along with the wheel itself, returning the
+/// page's URL.
+///
+/// Like PyPI, the wheel is served as immutable, such that only the digest on the page reveals
+/// that its contents have changed.
+fn serve_find_links(wheel: Arc<Mutex<Vec<u8>>>) -> Result<String> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+
+            // Read the request line, and skip the headers.
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            if reader.read_line(&mut request).is_err() {
+                continue;
+            }
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|len| len > 2) {
+                header.clear();
+            }
+
+            let wheel = wheel.lock().unwrap().clone();
+            let (content_type, cache_control, body) = if request.starts_with("GET /files/") {
+                (
+                    "application/octet-stream",
+                    "max-age=365000000, immutable",
+                    wheel,
+                )
+            } else {
+                let digest = format!("{:x}", sha2::Sha256::digest(wheel));
+                let page = format!(
+                    "<a href=\"/files/example_pkg-1.0.0-py3-none-any.whl#sha256={digest}\">example_pkg-1.0.0-py3-none-any.whl</a>"
+                );
+                ("text/html", "no-cache", page.into_bytes())
+            };
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nCache-Control: {cache_control}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream
+                .write_all(head.as_bytes())
+                .and_then(|()| stream.write_all(&body));
+        }
+    });
+    Ok(format!("http://{addr}/"))
+}
+
+/// If the index re-uploads a wheel with the same filename but different contents, the cached
+/// wheel should be refreshed, even without `--require-hashes`.
+#[test]
+fn install_refreshes_changed_registry_wheel() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let wheel = Arc::new(Mutex::new(example_pkg_wheel("before")?));
+    let find_links = serve_find_links(wheel.clone())?;
+
+    context
+        .install_without_exclude_newer()
+        .arg("example-pkg")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(&find_links)
+        .assert()
+        .success();
+    context
+        .assert_command("import example_pkg; assert example_pkg.VALUE == 'before'")
+        .success();
+
+    // Re-upload the wheel under the same filename.
+    *wheel.lock().unwrap() = example_pkg_wheel("after")?;
+
+    context
+        .install_without_exclude_newer()
+        .arg("example-pkg")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(&find_links)
+        .arg("--reinstall")
+        .assert()
+        .success();
+    context
+        .assert_command("import example_pkg; assert example_pkg.VALUE == 'after'")
+        .success();
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip lint` command with options shared across scenarios.
+fn lint_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("lint")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Lint valid requirements.
+#[test]
+fn lint_valid() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in
+        .write_str("anyio>=3 ; python_version >= '3.8'\nanyio<5\nflask[dotenv]==3.0.0")?;
+
+    uv_snapshot!(context.filters(), lint_command(&context)
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Linted 3 requirements in [TIME]
+    No problems found
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lint requirements with unsatisfiable specifiers and markers, conflicting duplicates, and
+/// unknown extras.
+#[test]
+fn lint_problems() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r#"
+        foo>=2,<1
+        bar==1.0
+        bar==2.0
+        bar==1.0 ; sys_platform == "win32"
+        baz ; python_version > "3.8" and python_version < "3.7"
+        qux ; python_version > os_name
+        flask[nonexistent]==3.0.0
+    "#})?;
+
+    let invalid_txt = context.temp_dir.child("invalid.txt");
+    invalid_txt.write_str("foo ; platform == 'linux'")?;
+
+    uv_snapshot!(context.filters(), lint_command(&context)
+        .arg("requirements.in")
+        .arg("invalid.txt"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Linted 7 requirements in [TIME]
+    Found 6 problems
+    invalid.txt: Couldn't parse requirement in `invalid.txt` at line 1, column 1: Expected a valid marker name, found 'platform'
+    foo ; platform == 'linux'
+          ^^^^^^^^
+    requirements.in: `foo>=2, <1` has a version specifier that can never be satisfied
+    requirements.in: `baz ; python_version > '3.8' and python_version < '3.7'` has a marker that can never be satisfied: `python_version > '3.8' and python_version < '3.7'`
+    requirements.in: `qux ; python_version > os_name` has an invalid marker, which always evaluates to false: `python_version > os_name`
+    Conflicting requirements for `bar`: `bar==1.0` (requirements.in) and `bar==2.0` (requirements.in)
+    requirements.in: `flask[nonexistent]==3.0.0` requests the extra `nonexistent`, but `flask` does not provide it
+    "###
+    );
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::process::Command;
 
 use anyhow::Result;
+use assert_cmd::prelude::*;
 use assert_fs::fixture::ChildPath;
 use assert_fs::fixture::FileWriteStr;
 use assert_fs::fixture::PathChild;
@@ -152,6 +153,190 @@ fn list_single_no_editable() -> Result<()> {
     Ok(())
 }
 
+/// List packages for which a newer version is available.
+#[test]
+fn list_outdated() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\nidna==3.6")?;
+
+    install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--outdated")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Package    Version Latest Type
+    ---------- ------- ------ -----
+    markupsafe 2.1.3   2.1.5  wheel
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--outdated")
+        .arg("--format")
+        .arg("json")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"markupsafe","version":"2.1.3","latest_version":"2.1.5","latest_filetype":"wheel"}]
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// `--outdated` can't be combined with `--format freeze`.
+#[test]
+fn list_outdated_freeze() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--outdated")
+        .arg("--format")
+        .arg("freeze")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--format freeze` cannot be used with `--outdated`
+    "###
+    );
+}
+
+/// List packages along with their on-disk sizes.
+#[test]
+fn list_sizes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\nidna==3.6")?;
+
+    install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain(vec![
+            (r"\-\-\-\-\-\-+.*", "[UNDERLINE]"),
+            (r"\d+\.\d(B|KiB|MiB)", "[SIZE]"),
+            ("  +", " "),
+        ])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--sizes")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Package Version Size
+    [UNDERLINE]
+    idna 3.6 [SIZE]
+    markupsafe 2.1.3 [SIZE]
+
+    Total size: [SIZE]
+
+    ----- stderr -----
+    "###
+    );
+
+    // In JSON, the size is reported in bytes.
+    let output = Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--sizes")
+        .arg("--format")
+        .arg("json")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .output()?;
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        assert!(entry["size"].as_u64().is_some_and(|size| size > 0));
+    }
+
+    Ok(())
+}
+
+/// `--sizes` can't be combined with `--format freeze`.
+#[test]
+fn list_sizes_freeze() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("list")
+        .arg("--sizes")
+        .arg("--format")
+        .arg("freeze")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--format freeze` cannot be used with `--sizes`
+    "###
+    );
+}
+
 #[test]
 fn list_editable() {
     let context = TestContext::new("3.12");
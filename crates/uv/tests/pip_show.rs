@@ -485,6 +485,50 @@ fn show_editable() -> Result<()> {
     Ok(())
 }
 
+/// Flag the dynamic metadata of an editable package.
+#[test]
+fn show_editable_dynamic() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install the editable package.
+    install_command(&context)
+        .arg("-e")
+        .arg("../../scripts/packages/hatchling_dynamic")
+        .current_dir(current_dir()?)
+        .env(
+            "CARGO_TARGET_DIR",
+            "../../../target/target_install_editable",
+        )
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("show")
+        .arg("hatchling-dynamic")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: hatchling-dynamic
+    Version: 1.0.0
+    Location: [SITE_PACKAGES]/
+    Editable project location: [WORKSPACE]/scripts/packages/hatchling_dynamic
+    Dynamic: dependencies
+    Requires: anyio
+    Required-by:
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn show_required_by_multiple() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -171,6 +171,109 @@ fn install() -> Result<()> {
     Ok(())
 }
 
+/// Sync several virtual environments in one invocation, resolving the requirements and downloading
+/// each package once.
+#[test]
+fn sync_multiple_environments() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let mut venvs = Vec::new();
+    for service in ["service-a", "service-b"] {
+        let parent = context.temp_dir.child(service);
+        parent.create_dir_all()?;
+        venvs.push(create_venv(&parent, &context.cache_dir, "3.12"));
+    }
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--environment")
+        .arg("service-a/.venv")
+        .arg("--environment")
+        .arg("service-b/.venv"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Syncing environment at: service-a/.venv
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    Syncing environment at: service-b/.venv
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    for venv in &venvs {
+        check_command(venv, "import markupsafe", &context.temp_dir);
+    }
+
+    // The discovered environment is left untouched.
+    context.assert_command("import markupsafe").failure();
+
+    Ok(())
+}
+
+/// Report the requirements that are skipped since their markers don't match the environment,
+/// along with the marker expressions that decided it.
+#[test]
+fn sync_report_skipped_requirements() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        iniconfig==2.0.0
+        colorama==0.4.6 ; python_version >= '3.8' and sys_platform == 'win32'
+        "
+    })?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--python-platform")
+        .arg("linux")
+        .arg("--dry-run")
+        .arg("--report")
+        .arg("report.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + iniconfig==2.0.0
+    "###
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.child("report.json"),
+    )?)?;
+
+    let install = report["install"].as_array().unwrap();
+    assert_eq!(install.len(), 1);
+    assert_eq!(install[0]["metadata"]["name"], "iniconfig");
+
+    let skipped = report["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0]["requirement"], "colorama");
+    assert_eq!(
+        skipped[0]["marker"],
+        "python_version >= '3.8' and sys_platform == 'win32'"
+    );
+    assert_eq!(
+        skipped[0]["unsatisfied"],
+        serde_json::json!(["sys_platform == 'win32'"])
+    );
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment using copy semantics.
 #[test]
 fn install_copy() -> Result<()> {
@@ -833,7 +936,7 @@ fn install_no_index() -> Result<()> {
         .arg("--no-index")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -885,7 +988,7 @@ fn install_no_index_cached() -> Result<()> {
         .arg("--no-index")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1090,11 +1193,58 @@ fn mismatched_version() -> Result<()> {
     ----- stderr -----
     Resolved 1 package in [TIME]
     Downloaded 1 package in [TIME]
+    Rolled back the environment to its previous state
+    error: Failed to install: tomli-3.7.2-py3-none-any.whl (tomli==3.7.2 (from file://[TEMP_DIR]/tomli-3.7.2-py3-none-any.whl))
+      Caused by: Wheel version does not match filename: 2.0.1 != 3.7.2
+    "###
+    );
+
+    Ok(())
+}
+
+/// If an installation fails, any uninstalled packages should be restored.
+#[test]
+fn install_rollback() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install a version of `tomli`.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.0")?;
+
+    command(&context).arg("requirements.txt").assert().success();
+
+    // Download a wheel whose actual version doesn't match its filename.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
+    let archive = context.temp_dir.child("tomli-3.7.2-py3-none-any.whl");
+    let mut archive_file = fs_err::File::create(archive.path())?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut archive_file)?;
+
+    requirements_txt.write_str(&format!(
+        "tomli @ {}",
+        Url::from_file_path(archive.path()).unwrap()
+    ))?;
+
+    // The installation should fail, and the existing version should be restored.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Rolled back the environment to its previous state
     error: Failed to install: tomli-3.7.2-py3-none-any.whl (tomli==3.7.2 (from file://[TEMP_DIR]/tomli-3.7.2-py3-none-any.whl))
       Caused by: Wheel version does not match filename: 2.0.1 != 3.7.2
     "###
     );
 
+    context
+        .assert_command("import tomli; assert tomli.__version__ == '2.0.0'")
+        .success();
+
     Ok(())
 }
 
@@ -1119,7 +1269,7 @@ fn mismatched_name() -> Result<()> {
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1869,7 +2019,7 @@ fn duplicate_package_overlap() -> Result<()> {
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2593,6 +2743,114 @@ fn find_links() -> Result<()> {
     Ok(())
 }
 
+/// Sync using `--local-dist`, which should install the locally-built wheel, and resolve any other
+/// packages from the registry.
+#[test]
+fn local_dist() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Populate a `dist/` directory with a locally-built wheel.
+    let dist = context.temp_dir.child("dist");
+    dist.create_dir_all()?;
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts/links/tqdm-1000.0.0-py3-none-any.whl"),
+        dist.child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        markupsafe==2.1.3
+        tqdm==1000.0.0
+    "})?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--local-dist")
+        .arg("dist"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + markupsafe==2.1.3
+     + tqdm==1000.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// `--local-dist` should take precedence over other sources, even if they provide a newer version.
+#[test]
+fn local_dist_precedence() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Provide `tqdm==1000.0.0` via `--find-links`, and `tqdm==999.0.0` via `--local-dist`.
+    let links = context.temp_dir.child("links");
+    links.create_dir_all()?;
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts/links/tqdm-1000.0.0-py3-none-any.whl"),
+        links.child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+    let dist = context.temp_dir.child("dist");
+    dist.create_dir_all()?;
+    fs_err::copy(
+        context
+            .workspace_root
+            .join("scripts/links/tqdm-999.0.0.tar.gz"),
+        dist.child("tqdm-999.0.0.tar.gz"),
+    )?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tqdm")?;
+
+    // Without `--local-dist`, the newest version wins.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run")
+        .arg("--find-links")
+        .arg("links"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would download 1 package
+    Would install 1 package
+     + tqdm==1000.0.0
+    "###
+    );
+
+    // With `--local-dist`, the locally-built version wins.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--find-links")
+        .arg("links")
+        .arg("--local-dist")
+        .arg("dist"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tqdm==999.0.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Sync using `--find-links` with `--no-index`, which should accept the local wheel.
 #[test]
 fn find_links_no_index_match() -> Result<()> {
@@ -2670,7 +2928,7 @@ fn find_links_offline_no_match() -> Result<()> {
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2795,7 +3053,7 @@ fn offline() -> Result<()> {
         .arg("requirements.in")
         .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2886,7 +3144,7 @@ fn incompatible_constraint() -> Result<()> {
         .arg("--constraint")
         .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2983,7 +3241,7 @@ fn repeat_requirement_incompatible() -> Result<()> {
     uv_snapshot!(command(&context)
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3244,6 +3502,156 @@ fn compile_invalid_pyc_invalidation_mode() -> Result<()> {
     Ok(())
 }
 
+/// Install with bytecode compilation, reusing the compiled files from the cache on reinstall.
+#[test]
+#[cfg(unix)]
+fn compile_cached() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--compile")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    // The compiled files should be stored in the cache, and hardlinked into the environment.
+    let pyc = context
+        .site_packages()
+        .join("markupsafe")
+        .join("__pycache__")
+        .join("__init__.cpython-312.pyc");
+    let inode = fs_err::metadata(&pyc)?.ino();
+    assert_eq!(fs_err::metadata(&pyc)?.nlink(), 2);
+
+    // Reinstalling should reuse the cached files, rather than recompiling.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--compile")
+        .arg("--reinstall")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 3 files in [TIME]
+     - markupsafe==2.1.3
+     + markupsafe==2.1.3
+    "###
+    );
+
+    assert_eq!(fs_err::metadata(&pyc)?.ino(), inode);
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Install with bytecode compilation, using an optimization level, an exclusion pattern, and a
+/// hash-based invalidation mode. Files that fail to compile should be reported, without aborting
+/// the compilation.
+#[test]
+fn compile_options() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // Add a file that can't be compiled.
+    fs_err::write(context.site_packages().join("broken.py"), "def broken(:\n")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--compile")
+        .arg("--compile-optimize")
+        .arg("2")
+        .arg("--compile-exclude")
+        .arg("markupsafe/_native.py")
+        .arg("--compile-invalidation-mode")
+        .arg("checked-hash")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+    Bytecode compiled 3 files in [TIME]
+    warning: Failed to compile 1 file to bytecode:
+      broken.py
+     + markupsafe==2.1.3
+    "###
+    );
+
+    let pycache = context
+        .site_packages()
+        .join("markupsafe")
+        .join("__pycache__");
+    assert!(!pycache.join("__init__.cpython-312.pyc").exists());
+    assert!(!pycache.join("_native.cpython-312.opt-2.pyc").exists());
+
+    // The bytecode should use the checked-hash invalidation mode (i.e., the flags in the header
+    // should have both the "hash-based" and "check source" bits set).
+    let pyc = fs_err::read(pycache.join("__init__.cpython-312.opt-2.pyc"))?;
+    assert_eq!(pyc[4..8], [0b11, 0, 0, 0]);
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Omit files matching an exclusion pattern when installing, removing them from the `RECORD`.
+#[test]
+fn exclude_path_glob() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--exclude-path-glob")
+        .arg("*.pyi"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    let markupsafe = context.site_packages().join("markupsafe");
+    assert!(markupsafe.join("__init__.py").exists());
+    assert!(!markupsafe.join("_speedups.pyi").exists());
+
+    let record = fs_err::read_to_string(
+        context
+            .site_packages()
+            .join("MarkupSafe-2.1.3.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(!record.contains("_speedups.pyi"));
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
 /// Raise an error when an editable's `Requires-Python` constraint is not met.
 #[test]
 fn requires_python_editable() -> Result<()> {
@@ -3339,7 +3747,7 @@ requires-python = "<=3.5"
     uv_snapshot!(context.filters(), command(&context)
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3416,6 +3824,149 @@ fn require_hashes_missing_hash() -> Result<()> {
     Ok(())
 }
 
+/// Sync a file that was compiled with `--generate-hashes`, which enables hash-checking mode.
+#[test]
+fn compiled_generate_hashes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --generate-hashes
+        anyio==4.0.0
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, all requirement must have a hash, but none were provided for: anyio==4.0.0
+    "###
+    );
+
+    // An explicit `--no-require-hashes` takes precedence.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--no-require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Sync a file that was compiled for an older Python version, which remains valid for newer
+/// versions.
+#[test]
+fn compiled_python_version_older() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.11
+        anyio==4.0.0
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Sync a file that was compiled for a newer Python version than the target environment.
+#[test]
+fn compiled_python_version_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.13
+        anyio==4.0.0
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `requirements.txt` was compiled for Python 3.13 or newer (`--python-version 3.13`), but the target environment uses Python 3.12. Recompile the requirements for the target environment, or pass `--ignore-compiled-environment` to install them anyway.
+    "###
+    );
+
+    // Install anyway with `--ignore-compiled-environment`.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--ignore-compiled-environment"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `requirements.txt` was compiled for Python 3.13 or newer (`--python-version 3.13`), but the target environment uses Python 3.12; installing anyway (`--ignore-compiled-environment`)
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Sync a file whose emitted marker expression excludes the target environment.
+#[test]
+fn compiled_marker_expression_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc::indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --emit-marker-expression
+        # Pinned dependencies known to be valid for:
+        #    python_version == '3.11'
+        anyio==4.0.0
+    "})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `requirements.txt` is only known to be valid for environments matching `python_version == '3.11'`, which excludes the target environment. Recompile the requirements for the target environment, or pass `--ignore-compiled-environment` to install them anyway.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Omit the version with `--require-hashes`.
 #[test]
 fn require_hashes_missing_version() -> Result<()> {
@@ -3513,7 +4064,7 @@ fn require_hashes_wheel_no_binary() -> Result<()> {
         .arg(":all:")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3606,7 +4157,7 @@ fn require_hashes_source_only_binary() -> Result<()> {
         .arg(":all:")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3639,7 +4190,7 @@ fn require_hashes_wrong_digest() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3672,7 +4223,7 @@ fn require_hashes_wrong_algorithm() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3744,7 +4295,7 @@ fn require_hashes_source_url() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3775,7 +4326,7 @@ fn require_hashes_source_url_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3845,7 +4396,7 @@ fn require_hashes_wheel_url() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3900,7 +4451,7 @@ fn require_hashes_wheel_url_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3920,6 +4471,113 @@ fn require_hashes_wheel_url_mismatch() -> Result<()> {
     Ok(())
 }
 
+/// Reinstall a direct URL dependency if the hash recorded at install time doesn't match the
+/// required hash.
+#[test]
+fn require_hashes_installed_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt
+        .write_str("anyio @ https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes")
+        .assert()
+        .success();
+
+    // The hash of the archive should be recorded in `direct_url.json`.
+    let direct_url = context
+        .site_packages()
+        .join("anyio-4.0.0.dist-info")
+        .join("direct_url.json");
+    let contents = fs::read_to_string(&direct_url)?;
+    assert!(contents.contains("cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f"));
+
+    // Simulate an installation from a different archive.
+    fs::write(
+        &direct_url,
+        contents.replace(
+            "cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+            "afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+        ),
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: The installed archive for anyio==4.0.0 (from https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl) does not match the required hashes; reinstalling
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - anyio==4.0.0 (from https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl)
+     + anyio==4.0.0 (from https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reinstall a registry dependency if the hash recorded at install time doesn't match the
+/// required hash.
+#[test]
+fn require_hashes_installed_registry_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "anyio==4.0.0 --hash=sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+    )?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes")
+        .assert()
+        .success();
+
+    // The hash of the archive should be recorded in `uv_archive_info.json`.
+    let archive_info = context
+        .site_packages()
+        .join("anyio-4.0.0.dist-info")
+        .join("uv_archive_info.json");
+    let contents = fs::read_to_string(&archive_info)?;
+    assert!(contents.contains("cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f"));
+
+    // Simulate an installation from a different archive.
+    fs::write(
+        &archive_info,
+        contents.replace(
+            "cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+            "afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f",
+        ),
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: The installed archive for anyio==4.0.0 does not match the required hashes; reinstalling
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - anyio==4.0.0
+     + anyio==4.0.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Reject Git dependencies when `--require-hashes` is provided.
 #[test]
 fn require_hashes_git() -> Result<()> {
@@ -4008,7 +4666,7 @@ fn require_hashes_re_download() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4100,7 +4758,7 @@ fn require_hashes_wheel_path_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4170,7 +4828,7 @@ fn require_hashes_source_path_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4382,7 +5040,7 @@ fn require_hashes_repeated_hash() -> Result<()> {
         .arg("--require-hashes")
         .arg("--reinstall"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4514,7 +5172,7 @@ fn require_hashes_find_links_no_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/no-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4544,7 +5202,7 @@ fn require_hashes_find_links_no_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/no-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4636,7 +5294,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4665,7 +5323,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4746,7 +5404,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4809,7 +5467,7 @@ fn require_hashes_registry_valid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://astral-test.github.io/astral-test-hash/valid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4837,7 +5495,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4866,7 +5524,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4947,7 +5605,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -5172,3 +5830,55 @@ fn target_no_build_isolation() -> Result<()> {
 
     Ok(())
 }
+
+/// Check that the installed packages can be imported, e.g., to catch extension modules that link
+/// against missing shared libraries.
+#[test]
+fn check_import() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a package that fails to import.
+    let project = context.temp_dir.child("broken");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "broken"
+        version = "1.0.0"
+        description = "A package that fails to import"
+
+        [build-system]
+        requires = ["flit_core>=3.2"]
+        build-backend = "flit_core.buildapi"
+        "#
+    })?;
+    project
+        .child("broken")
+        .child("__init__.py")
+        .write_str("raise ImportError('libbroken.so: cannot open shared object file')\n")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1\n./broken")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--check-import"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+    Checked imports of 2 packages in [TIME]
+     + broken==1.0.0 (from file://[TEMP_DIR]/broken)
+     + tomli==2.0.1
+    error: Failed to import 1 module after installation:
+      broken (broken): ImportError: libbroken.so: cannot open shared object file
+    "###
+    );
+
+    // The packages remain installed.
+    context.assert_command("import tomli").success();
+
+    Ok(())
+}
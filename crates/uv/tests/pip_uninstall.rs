@@ -141,7 +141,7 @@ fn invalid_requirements_txt_requirement() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Couldn't parse requirement in `requirements.txt` at position 0
+    error: Couldn't parse requirement in `requirements.txt` at line 1, column 1
       Caused by: after parsing '1.0', found '.x', which is not part of a valid version
     flask==1.0.x
          ^^^^^^^
@@ -538,3 +538,225 @@ Version: 0.22.0
 
     Ok(())
 }
+
+/// Uninstall a legacy editable package with a `src` layout and namespace packages by path.
+#[test]
+fn uninstall_legacy_editable_namespace() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = ChildPath::new(context.site_packages());
+
+    // The `.egg-info` lives in `src`, rather than in the project root.
+    let project = context.temp_dir.child("zstandard_project");
+    let target = project.child("src");
+    target.child("ns").child("zstd").create_dir_all()?;
+    target
+        .child("ns")
+        .child("zstd")
+        .child("__init__.py")
+        .write_str("")?;
+
+    target.child("zstandard.egg-info").create_dir_all()?;
+    target
+        .child("zstandard.egg-info")
+        .child("PKG-INFO")
+        .write_str(
+            "Metadata-Version: 2.1
+Name: zstandard
+Version: 0.22.0
+",
+        )?;
+
+    site_packages
+        .child("zstandard.egg-link")
+        .write_str(&format!("{}\n../", target.path().to_str().unwrap()))?;
+    site_packages
+        .child("zstandard-nspkg.pth")
+        .write_str("import sys, types, os\n")?;
+
+    site_packages.child("easy-install.pth").write_str(&format!(
+        "something\n{}\nanother thing\n",
+        normcase(target.path().to_str().unwrap())
+    ))?;
+
+    // Uninstall by the path to the project root.
+    uv_snapshot!(uninstall_command(&context)
+        .arg(project.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - zstandard==0.22.0
+    "###);
+
+    // The entry in `easy-install.pth` should be removed.
+    assert_eq!(
+        fs_err::read_to_string(site_packages.child("easy-install.pth"))?,
+        "something\nanother thing\n",
+        "easy-install.pth should not contain the path to the uninstalled package"
+    );
+    // The `.egg-link` and `-nspkg.pth` files should be removed.
+    assert!(!site_packages.child("zstandard.egg-link").exists());
+    assert!(!site_packages.child("zstandard-nspkg.pth").exists());
+    // The `.egg-info` directory should still exist.
+    assert!(target.child("zstandard.egg-info").exists());
+
+    Ok(())
+}
+
+/// Uninstall a PEP 660 editable whose `.pth` file adds several roots to `sys.path`, including
+/// namespace portions, by the path to one of its roots.
+#[test]
+fn uninstall_editable_multiple_roots() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = ChildPath::new(context.site_packages());
+
+    // The project maps packages from both `src` and `lib`, each with a portion of the `ns`
+    // namespace package.
+    let project = context.temp_dir.child("multi_root");
+    let src = project.child("src");
+    let lib = project.child("lib");
+    src.child("ns").child("a").child("__init__.py").touch()?;
+    lib.child("ns").child("b").child("__init__.py").touch()?;
+
+    let dist_info = site_packages.child("multi_root-0.1.0.dist-info");
+    dist_info.child("METADATA").write_str(
+        "Metadata-Version: 2.1
+Name: multi-root
+Version: 0.1.0
+",
+    )?;
+    dist_info.child("direct_url.json").write_str(&format!(
+        r#"{{"url":"{}","dir_info":{{"editable":true}}}}"#,
+        url::Url::from_file_path(project.path()).unwrap()
+    ))?;
+    dist_info.child("RECORD").write_str(
+        "_multi_root.pth,,
+multi_root-0.1.0.dist-info/METADATA,,
+multi_root-0.1.0.dist-info/direct_url.json,,
+multi_root-0.1.0.dist-info/RECORD,,
+",
+    )?;
+    site_packages.child("_multi_root.pth").write_str(&format!(
+        "{}\n{}\n",
+        src.path().to_str().unwrap(),
+        lib.path().to_str().unwrap()
+    ))?;
+
+    Command::new(venv_to_interpreter(&context.venv))
+        .arg("-c")
+        .arg("import ns.a, ns.b")
+        .assert()
+        .success();
+
+    // Uninstall by the path to the second root.
+    uv_snapshot!(context.filters(), uninstall_command(&context)
+        .arg(lib.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - multi-root==0.1.0 (from file://[TEMP_DIR]/multi_root)
+    "###);
+
+    // The `.pth` file and the `.dist-info` directory should be removed, such that neither
+    // namespace portion is importable.
+    site_packages
+        .child("_multi_root.pth")
+        .assert(predicates::path::missing());
+    dist_info.assert(predicates::path::missing());
+    Command::new(venv_to_interpreter(&context.venv))
+        .arg("-c")
+        .arg("import ns.a")
+        .assert()
+        .failure();
+    Command::new(venv_to_interpreter(&context.venv))
+        .arg("-c")
+        .arg("import ns.b")
+        .assert()
+        .failure();
+
+    // The sources should be left untouched.
+    src.child("ns")
+        .child("a")
+        .child("__init__.py")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
+
+/// Uninstall the packages listed in a requirements file, skipping any that aren't installed.
+#[test]
+fn uninstall_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    let uninstall_txt = context.temp_dir.child("uninstall.txt");
+    uninstall_txt.write_str("markupsafe\niniconfig\ntomli")?;
+
+    uv_snapshot!(uninstall_command(&context)
+        .arg("-r")
+        .arg("uninstall.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Skipping iniconfig as it is not installed.
+    Uninstalled 2 packages in [TIME]
+     - markupsafe==2.1.3
+     - tomli==2.0.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Uninstall every package in the environment, except for the seed packages.
+#[test]
+fn uninstall_all() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\npip==21.3.1\ntomli==2.0.1")?;
+
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    uv_snapshot!(uninstall_command(&context)
+        .arg("--all"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 2 packages in [TIME]
+     - markupsafe==2.1.3
+     - tomli==2.0.1
+    "###
+    );
+
+    // `pip` is retained.
+    Command::new(venv_to_interpreter(&context.venv))
+        .arg("-c")
+        .arg("import pip")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    Ok(())
+}
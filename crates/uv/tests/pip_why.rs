@@ -0,0 +1,86 @@
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip why` command with options shared across scenarios.
+fn why_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("why")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Explain a transitive dependency that's required via multiple paths.
+#[test]
+fn why_transitive() {
+    let context = TestContext::new("3.12");
+
+    context
+        .install()
+        .arg("flask==3.0.2")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    uv_snapshot!(why_command(&context).arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask==3.0.2 -> jinja2==3.1.3 -> markupsafe==2.1.5
+    flask==3.0.2 -> werkzeug==3.0.1 -> markupsafe==2.1.5
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Explain a package that isn't required by any other installed package.
+#[test]
+fn why_top_level() {
+    let context = TestContext::new("3.12");
+
+    context
+        .install()
+        .arg("flask==3.0.2")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    uv_snapshot!(why_command(&context).arg("flask"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flask==3.0.2
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Explain a package that isn't installed.
+#[test]
+fn why_missing() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(why_command(&context).arg("flask"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Package not found: flask
+    "###
+    );
+}
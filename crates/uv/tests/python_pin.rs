@@ -0,0 +1,128 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `python pin` command with options shared across scenarios.
+fn pin_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("python")
+        .arg("pin")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Pinning from a subdirectory of a project writes the pin to the project root, where it's then
+/// discovered from the subdirectory.
+#[test]
+fn pin_project_root() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        "#})?;
+    let subdir = context.temp_dir.child("src").child("project");
+    subdir.create_dir_all()?;
+
+    uv_snapshot!(context.filters(), pin_command(&context).arg("3.12").current_dir(&subdir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pinned `3.12` to `[TEMP_DIR]/.python-version`
+    "###
+    );
+
+    context.temp_dir.child(".python-version").assert("3.12\n");
+    subdir
+        .child(".python-version")
+        .assert(predicates::path::missing());
+
+    uv_snapshot!(context.filters(), pin_command(&context).current_dir(&subdir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3.12
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Outside of a project, the pin is written to the current directory.
+#[test]
+fn pin_current_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), pin_command(&context).arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pinned `3.12` to `.python-version`
+    "###
+    );
+
+    context.temp_dir.child(".python-version").assert("3.12\n");
+
+    Ok(())
+}
+
+/// `pyenv`-specific entries, like the name of a `pyenv-virtualenv` environment, are skipped.
+#[test]
+fn pin_show_pyenv_entries() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child(".python-version")
+        .write_str("my-virtualenv\nsystem\n")?;
+
+    uv_snapshot!(context.filters(), pin_command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No pinned Python interpreter found (i.e., no `.python-version` file with a supported version in the current directory or its parents)
+    "###
+    );
+
+    context
+        .temp_dir
+        .child(".python-version")
+        .write_str("my-virtualenv\n3.12\n")?;
+
+    uv_snapshot!(context.filters(), pin_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3.12
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::{get_bin, TestContext};
+
+mod common;
+
+/// Sync the project's dependencies and the requested extras, removing anything else.
+#[test]
+fn sync_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install a package that isn't required by the project.
+    context.install().arg("iniconfig==2.0.0").assert().success();
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["sniffio==1.3.1"]
+
+[project.optional-dependencies]
+types = ["typing-extensions==4.10.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--extra")
+        .arg("types")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    context
+        .assert_command("import sniffio, typing_extensions")
+        .success();
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Sync the requested extras from the lockfile, rather than resolving them anew.
+#[test]
+fn sync_extra_locked() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["sniffio==1.3.1"]
+
+[project.optional-dependencies]
+types = ["typing-extensions==4.10.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("lock")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    assert!(
+        lock.contains("[[distribution.optional-dependencies.types]]"),
+        "{lock}"
+    );
+
+    // Change the optional dependencies without re-locking; the locked version should be installed.
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["sniffio==1.3.1"]
+
+[project.optional-dependencies]
+types = ["typing-extensions==4.9.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--extra")
+        .arg("types")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    context
+        .assert_command(
+            "import sniffio, importlib.metadata; \
+             assert importlib.metadata.version('typing_extensions') == '4.10.0'",
+        )
+        .success();
+
+    // Without the extra, its optional dependencies are removed.
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    context.assert_command("import sniffio").success();
+    context.assert_command("import typing_extensions").failure();
+
+    Ok(())
+}
+
+/// `--allow-build` restricts source builds for the project's dependencies.
+#[test]
+fn sync_allow_build() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["django_allauth==0.51.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--allow-build")
+        .arg("anyio")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "django-allauth==0.51.0 has no usable wheels and building from source is disabled",
+        ));
+
+    Ok(())
+}
+
+/// `--deny-package` applies to the project's transitive dependencies.
+#[test]
+fn sync_deny_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["flask==3.0.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--deny-package")
+        .arg("werkzeug")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("werkzeug is on the deny-list"));
+
+    Ok(())
+}
+
+/// Packages redirected via `[tool.uv.pip.patch]` are installed from the patched source.
+#[test]
+fn sync_patch_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(&indoc::formatdoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio>=4"]
+
+        [tool.uv.pip.patch]
+        anyio = {{ path = "{workspace_root}/scripts/packages/anyio_local" }}
+        "#,
+        workspace_root = context.workspace_root.display(),
+    })?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    // The local `anyio` is an empty package.
+    context
+        .assert_command("import anyio; assert not hasattr(anyio, 'run')")
+        .success();
+
+    Ok(())
+}
+
+/// `--compile-bytecode` compiles the project's dependencies, skipping excluded packages.
+#[test]
+fn sync_compile_bytecode() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "project"
+version = "0.1.0"
+dependencies = ["anyio==4.3.0"]
+"#,
+    )?;
+
+    std::process::Command::new(get_bin())
+        .arg("sync")
+        .arg("--compile-bytecode")
+        .arg("--compile-exclude")
+        .arg("sniffio")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    let site_packages = context.site_packages();
+    assert!(site_packages.join("anyio").join("__pycache__").is_dir());
+    assert!(!site_packages.join("sniffio").join("__pycache__").exists());
+
+    Ok(())
+}
+
+/// Dependencies that provide the same script are rejected, unless `--force` is provided.
+#[test]
+fn sync_script_conflict() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package that provides a `simple_launcher` script.
+    let other_launcher = context.temp_dir.child("other_launcher");
+    other_launcher.child("pyproject.toml").write_str(
+        r#"[project]
+name = "other-launcher"
+version = "1.0.0"
+
+[project.scripts]
+simple_launcher = "other_launcher:main"
+
+[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+"#,
+    )?;
+    other_launcher
+        .child("other_launcher")
+        .child("__init__.py")
+        .write_str("def main():\n    pass\n")?;
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(&indoc::formatdoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = [
+            "simple_launcher @ {simple_launcher}",
+            "other-launcher @ {other_launcher}",
+        ]
+        "#,
+        simple_launcher = url::Url::from_file_path(
+            context
+                .workspace_root
+                .join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl"),
+        )
+        .unwrap(),
+        other_launcher = url::Url::from_file_path(other_launcher.path()).unwrap(),
+    })?;
+
+    let sync = || {
+        let mut command = std::process::Command::new(get_bin());
+        command
+            .arg("sync")
+            .arg("--cache-dir")
+            .arg(context.cache_dir.path())
+            .env("VIRTUAL_ENV", context.venv.as_os_str())
+            .env("UV_NO_WRAP", "1")
+            .current_dir(&context.temp_dir);
+        command
+    };
+
+    sync().assert().failure().stderr(predicates::str::contains(
+        "`simple_launcher` is provided by `other-launcher` and `simple-launcher`",
+    ));
+
+    sync()
+        .arg("--force")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "warning: Script `simple_launcher` is provided by `other-launcher` and `simple-launcher`; it will be overwritten",
+        ));
+
+    Ok(())
+}
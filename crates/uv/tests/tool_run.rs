@@ -0,0 +1,126 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `tool run` command with options shared across scenarios.
+fn tool_run_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("tool")
+        .arg("run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Count the cached environments.
+fn cached_environments(context: &TestContext) -> Result<usize> {
+    let mut count = 0;
+    for entry in fs_err::read_dir(context.cache_dir.join("environments-v1"))? {
+        if entry?.file_type()?.is_dir() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Reuse the cached environment for the same requirements, and create a new environment for
+/// different requirements.
+#[test]
+fn tool_run_reuse_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    tool_run_command(&context)
+        .arg("--from")
+        .arg("iniconfig==2.0.0")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig")
+        .assert()
+        .success();
+    assert_eq!(cached_environments(&context)?, 1);
+
+    // Running again should reuse the environment.
+    tool_run_command(&context)
+        .arg("--from")
+        .arg("iniconfig==2.0.0")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig")
+        .assert()
+        .success();
+    assert_eq!(cached_environments(&context)?, 1);
+
+    // Different requirements should use a separate environment, retaining the first.
+    tool_run_command(&context)
+        .arg("--from")
+        .arg("iniconfig==1.1.1")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig")
+        .assert()
+        .success();
+    assert_eq!(cached_environments(&context)?, 2);
+
+    Ok(())
+}
+
+/// Modifying a local source invalidates the cached environment, and the superseded environment is
+/// removed.
+#[test]
+fn tool_run_local_source_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package.
+    let project_dir = context.temp_dir.child("example");
+    project_dir.create_dir_all()?;
+    project_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+requires-python = ">=3.8"
+"#,
+    )?;
+    let init_py = project_dir.child("example").child("__init__.py");
+    init_py.write_str("VALUE = 1\n")?;
+
+    let url = url::Url::from_file_path(project_dir.path()).unwrap();
+    let from = format!("example @ {url}");
+
+    tool_run_command(&context)
+        .arg("--from")
+        .arg(&from)
+        .arg("python")
+        .arg("-c")
+        .arg("import example; print(example.VALUE)")
+        .assert()
+        .success()
+        .stdout("1\n");
+
+    // Modify a source file, without modifying the `pyproject.toml`.
+    init_py.write_str("VALUE = 2\n")?;
+
+    tool_run_command(&context)
+        .arg("--from")
+        .arg(&from)
+        .arg("python")
+        .arg("-c")
+        .arg("import example; print(example.VALUE)")
+        .assert()
+        .success()
+        .stdout("2\n");
+    assert_eq!(cached_environments(&context)?, 1);
+
+    Ok(())
+}
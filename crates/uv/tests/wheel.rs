@@ -0,0 +1,85 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `wheel inspect` command with options shared across scenarios.
+fn inspect_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("wheel")
+        .arg("inspect")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Inspect a local wheel, including its entry points.
+#[test]
+fn inspect_local() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), inspect_command(&context)
+        .arg(context.workspace_root.join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Wheel:
+      Filename: simple_launcher-0.1.0-py3-none-any.whl
+      Python tags: py3
+      ABI tags: none
+      Platform tags: any
+      Wheel-Version: 1.0
+      Generator: poetry-core 1.8.1
+      Root-Is-Purelib: true
+      Tag: py3-none-any
+
+    Metadata:
+      Metadata-Version: 2.1
+      Name: simple-launcher
+      Version: 0.1.0
+      Summary:
+      Author: konstin
+      Author-email: konstin@mailbox.org
+      Requires-Python: >=3.12,<4.0
+      Classifier: Programming Language :: Python :: 3
+      Classifier: Programming Language :: Python :: 3.12
+
+    Record:
+      Files: 5
+      Size: 449 bytes
+      Unhashed: 1
+
+    Entry points:
+      [console_scripts]
+      simple_launcher = simple_launcher:say_hi
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Inspecting a file that isn't a wheel should fail.
+#[test]
+fn inspect_invalid_filename() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), inspect_command(&context)
+        .arg("foo.tar.gz"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Expected a wheel file, but received: foo.tar.gz
+      Caused by: The wheel filename "foo.tar.gz" is invalid: Must end with .whl
+    "###
+    );
+}